@@ -0,0 +1,15 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // gRPC control API: generate the server and client from the proto.
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto");
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        // SAFETY: the build script is single-threaded.
+        unsafe { std::env::set_var("PROTOC", protoc) };
+        tonic_build::configure()
+            .compile_protos(&["proto/workgraph/v1/control.proto"], &["proto"])
+            .expect("failed to compile proto/workgraph/v1/control.proto");
+    }
+}
@@ -0,0 +1,34 @@
+//! Fuzz `parser::load_graph` with arbitrary graph.jsonl contents.
+//!
+//! Any input must load or fail with a `ParseError`; a panic is a bug. A
+//! graph that loads must also survive the queries every command runs on it
+//! and save back into something that loads again.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::path::PathBuf;
+
+use workgraph::parser::{load_graph, save_graph};
+use workgraph::query::ready_tasks;
+
+fn scratch(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("wg-fuzz-{}-{}.jsonl", std::process::id(), name))
+}
+
+fuzz_target!(|data: &[u8]| {
+    let input = scratch("input");
+    std::fs::write(&input, data).unwrap();
+    let Ok(graph) = load_graph(&input) else {
+        return;
+    };
+
+    let _ = ready_tasks(&graph);
+    let _ = graph.compute_cycle_analysis();
+
+    let output = scratch("output");
+    let _ = std::fs::remove_file(&output);
+    save_graph(&graph, &output).expect("a loaded graph must save");
+    let reloaded = load_graph(&output).expect("a saved graph must load");
+    assert_eq!(graph.len(), reloaded.len());
+});
@@ -0,0 +1,20 @@
+//! Fuzz trace function YAML loading (`function::parse_function`, the body of
+//! `load_function`) and validation.
+//!
+//! Functions arrive from peers, so any input must parse or fail with a
+//! `TraceFunctionError`; a panic is a bug.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use workgraph::function::{parse_function, validate_function};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(func) = parse_function(text) {
+        let _ = validate_function(&func);
+    }
+});
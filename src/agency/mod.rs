@@ -7,9 +7,11 @@ mod hash;
 mod lineage;
 mod output;
 mod prompt;
+pub mod reputation;
 pub mod run_mode;
 pub(crate) mod starters;
 mod store;
+mod tool_policy;
 mod types;
 
 /// Agency federation compatibility surface implemented by this wg build.
@@ -27,4 +29,5 @@ pub use prompt::*;
 pub use run_mode::*;
 pub use starters::*;
 pub use store::*;
+pub use tool_policy::*;
 pub use types::*;
@@ -391,6 +391,11 @@ pub struct EvaluatorInput<'a> {
     pub constraint_fidelity_score: Option<f64>,
     /// Number of unanchored constraints detected by constraint-fidelity lint.
     pub constraint_fidelity_unanchored: Option<usize>,
+    /// Rendered summary of the task's latest recorded test run, with failing
+    /// tests marked real or known-flaky.
+    pub test_results: Option<&'a str>,
+    /// Pre-rendered criteria of the rubrics that apply to the task, if any.
+    pub rubric: Option<&'a str>,
 }
 
 /// Render the evaluator prompt that an LLM evaluator will receive.
@@ -619,6 +624,17 @@ pub fn render_evaluator_prompt(input: &EvaluatorInput) -> String {
         }
     }
 
+    // -- Recorded test results --
+    if let Some(results) = input.test_results {
+        out.push_str("## Test Results\n\n");
+        out.push_str(results);
+        out.push('\n');
+        out.push_str(
+            "NOTE: Failures marked \"known flaky\" have changed outcome without a code change \
+             before. Do not penalize the agent for them; weigh the other failures as real.\n\n",
+        );
+    }
+
     // -- Constraint-fidelity lint results --
     if let Some(cf_score) = input.constraint_fidelity_score {
         out.push_str("## Constraint-Fidelity Lint Results\n\n");
@@ -675,6 +691,17 @@ pub fn render_evaluator_prompt(input: &EvaluatorInput) -> String {
          need to be scored by the evaluator. Do not include it in your output dimensions.\n\n",
     );
 
+    // -- Task rubrics --
+    if let Some(rubric) = input.rubric {
+        out.push_str("## Task Rubric\n\n");
+        out.push_str(
+            "This task has project rubrics. Score their criteria in addition to the \
+             dimensions above; they do not change the overall score formula.\n\n",
+        );
+        out.push_str(rubric);
+        out.push('\n');
+    }
+
     // -- Rubric spectrum --
     out.push_str(
         "### Rubric Spectrum\n\n\
@@ -1342,6 +1369,8 @@ mod tests {
             child_tasks: &[],
             constraint_fidelity_score: None,
             constraint_fidelity_unanchored: None,
+            test_results: None,
+            rubric: None,
         };
 
         let output = render_evaluator_prompt(&input);
@@ -1440,6 +1469,8 @@ mod tests {
             child_tasks: &[],
             constraint_fidelity_score: None,
             constraint_fidelity_unanchored: None,
+            test_results: None,
+            rubric: None,
         };
 
         let output = render_evaluator_prompt(&input);
@@ -1486,6 +1517,8 @@ mod tests {
             child_tasks: &[],
             constraint_fidelity_score: None,
             constraint_fidelity_unanchored: None,
+            test_results: None,
+            rubric: None,
         };
 
         let output = render_evaluator_prompt(&input);
@@ -1545,6 +1578,8 @@ mod tests {
             child_tasks: &[],
             constraint_fidelity_score: None,
             constraint_fidelity_unanchored: None,
+            test_results: None,
+            rubric: None,
         };
 
         let output = render_evaluator_prompt(&input);
@@ -1595,6 +1630,8 @@ mod tests {
             child_tasks: &[],
             constraint_fidelity_score: None,
             constraint_fidelity_unanchored: None,
+            test_results: None,
+            rubric: None,
         };
 
         let output = render_evaluator_prompt(&input);
@@ -1633,6 +1670,8 @@ mod tests {
             child_tasks: &[],
             constraint_fidelity_score: None,
             constraint_fidelity_unanchored: None,
+            test_results: None,
+            rubric: None,
         };
 
         let output = render_evaluator_prompt(&input);
@@ -1681,6 +1720,8 @@ mod tests {
             child_tasks: &child_tasks,
             constraint_fidelity_score: None,
             constraint_fidelity_unanchored: None,
+            test_results: None,
+            rubric: None,
         };
 
         let output = render_evaluator_prompt(&input);
@@ -1736,6 +1777,8 @@ mod tests {
             child_tasks: &[],
             constraint_fidelity_score: None,
             constraint_fidelity_unanchored: None,
+            test_results: None,
+            rubric: None,
         };
 
         let output = render_evaluator_prompt(&input);
@@ -1745,6 +1788,76 @@ mod tests {
         assert!(!output.contains("Verification Status"));
     }
 
+    #[test]
+    fn test_render_evaluator_prompt_test_results() {
+        let input = EvaluatorInput {
+            task_title: "With tests",
+            task_description: None,
+            task_skills: &[],
+            verify: None,
+            agent: None,
+            role: None,
+            tradeoff: None,
+            artifacts: &[],
+            log_entries: &[],
+            started_at: None,
+            completed_at: None,
+            artifact_diff: None,
+            evaluator_identity: None,
+            downstream_tasks: &[],
+            flip_score: None,
+            verify_status: None,
+            verify_findings: None,
+            resolved_outcome_name: None,
+            child_tasks: &[],
+            constraint_fidelity_score: None,
+            constraint_fidelity_unanchored: None,
+            test_results: Some(
+                "9 passed, 1 failed (source: verify)\n- FAILED `net::retry` (known flaky)\n",
+            ),
+            rubric: None,
+        };
+
+        let output = render_evaluator_prompt(&input);
+
+        assert!(output.contains("## Test Results\n\n9 passed, 1 failed"));
+        assert!(output.contains("Do not penalize the agent for them"));
+    }
+
+    #[test]
+    fn test_render_evaluator_prompt_rubric() {
+        let input = EvaluatorInput {
+            task_title: "Rubric task",
+            task_description: None,
+            task_skills: &[],
+            verify: None,
+            agent: None,
+            role: None,
+            tradeoff: None,
+            artifacts: &[],
+            log_entries: &[],
+            started_at: None,
+            completed_at: None,
+            artifact_diff: None,
+            evaluator_identity: None,
+            downstream_tasks: &[],
+            flip_score: None,
+            verify_status: None,
+            verify_findings: None,
+            resolved_outcome_name: None,
+            child_tasks: &[],
+            constraint_fidelity_score: None,
+            constraint_fidelity_unanchored: None,
+            test_results: None,
+            rubric: Some("### Rubric: api-change\n- `rubric:compat` (weight 1): No breaks\n"),
+        };
+
+        let output = render_evaluator_prompt(&input);
+
+        assert!(output.contains("## Task Rubric"));
+        assert!(output.contains("`rubric:compat`"));
+    }
+
     // -- Rich component resolution tests ------------------------------------
 
     use super::super::starters::{build_component, build_outcome};
@@ -0,0 +1,295 @@
+//! Per-agent reputation: how an agent's finished tasks turned out.
+//!
+//! Outcomes are kept in `agency/reputation.json`, one entry per agent and
+//! task, so they outlive the graph (archived tasks keep counting) and
+//! refreshing is idempotent: [`refresh`] upserts the final outcome of every
+//! finished task that names an agent. A task that failed and later
+//! succeeded on retry counts once, as a success.
+//!
+//! An agent's reputation blends three signals into a 0–1 score:
+//! the average evaluation score, the success rate (smoothed so a single
+//! outcome doesn't swing it to 0 or 1), and timeliness (how actual duration
+//! compares to the estimate). Signals an agent has no data for are left
+//! out; an agent with no data at all has no reputation.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::store::AgencyError;
+use super::types::Agent;
+use crate::graph::{Status, Task, WorkGraph};
+
+const WEIGHT_SCORE: f64 = 0.5;
+const WEIGHT_SUCCESS: f64 = 0.35;
+const WEIGHT_TIMELINESS: f64 = 0.15;
+
+/// Reputation assumed for agents with no history when ranking.
+pub const NEUTRAL: f64 = 0.5;
+
+/// The final outcome of one task an agent worked on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskOutcome {
+    pub succeeded: bool,
+    /// Actual hours divided by estimated hours, when both are known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ratio: Option<f64>,
+    pub finished_at: String,
+}
+
+/// Agent id → task id → outcome.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReputationStore {
+    #[serde(flatten)]
+    pub agents: BTreeMap<String, BTreeMap<String, TaskOutcome>>,
+}
+
+pub fn store_path(agency_dir: &Path) -> PathBuf {
+    agency_dir.join("reputation.json")
+}
+
+impl ReputationStore {
+    /// Load the store; a missing file is an empty store.
+    pub fn load(agency_dir: &Path) -> Result<Self, AgencyError> {
+        let path = store_path(agency_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, agency_dir: &Path) -> Result<(), AgencyError> {
+        std::fs::create_dir_all(agency_dir)?;
+        let path = store_path(agency_dir);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+/// The outcome of a finished task, if it counts toward its agent's record.
+/// Abandoned tasks don't: abandoning is usually a planning decision.
+pub fn task_outcome(task: &Task) -> Option<TaskOutcome> {
+    let succeeded = match task.status {
+        Status::Done => true,
+        Status::Failed => false,
+        _ => return None,
+    };
+    let parse = |ts: &Option<String>| ts.as_deref()?.parse::<DateTime<Utc>>().ok();
+    let finished = parse(&task.completed_at);
+    let duration_ratio = match (parse(&task.started_at), finished, &task.estimate) {
+        (Some(start), Some(end), Some(est)) => est
+            .hours
+            .filter(|h| *h > 0.0)
+            .map(|h| (end - start).num_seconds().max(0) as f64 / 3600.0 / h),
+        _ => None,
+    };
+    Some(TaskOutcome {
+        succeeded,
+        duration_ratio,
+        finished_at: task.completed_at.clone().unwrap_or_default(),
+    })
+}
+
+/// Record the outcome of every finished task in `graph` that names an agent.
+/// Saves only when something changed.
+pub fn refresh(agency_dir: &Path, graph: &WorkGraph) -> Result<ReputationStore, AgencyError> {
+    let mut store = ReputationStore::load(agency_dir)?;
+    let mut changed = false;
+    for task in graph.tasks() {
+        let Some(ref agent) = task.agent else {
+            continue;
+        };
+        let Some(outcome) = task_outcome(task) else {
+            continue;
+        };
+        let entry = store.agents.entry(agent.clone()).or_default();
+        if entry.get(&task.id) != Some(&outcome) {
+            entry.insert(task.id.clone(), outcome);
+            changed = true;
+        }
+    }
+    if changed {
+        store.save(agency_dir)?;
+    }
+    Ok(store)
+}
+
+/// Aggregated track record for one agent.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AgentStats {
+    pub agent_id: String,
+    pub succeeded: u32,
+    pub failed: u32,
+    /// Raw share of finished tasks that succeeded
+    pub success_rate: Option<f64>,
+    /// Average evaluation score
+    pub avg_score: Option<f64>,
+    pub evaluations: u32,
+    /// Mean of actual/estimated duration over tasks with an estimate
+    pub avg_duration_ratio: Option<f64>,
+    /// Blended 0–1 score used by `reputation` dispatch
+    pub reputation: Option<f64>,
+}
+
+impl AgentStats {
+    pub fn finished(&self) -> u32 {
+        self.succeeded + self.failed
+    }
+}
+
+/// Stats for `agent` from the store and its evaluation record.
+pub fn agent_stats(store: &ReputationStore, agent: &Agent) -> AgentStats {
+    let outcomes = store.agents.get(&agent.id);
+    let mut stats = AgentStats {
+        agent_id: agent.id.clone(),
+        avg_score: agent.performance.avg_score,
+        evaluations: agent.performance.task_count,
+        ..Default::default()
+    };
+    let mut ratios = Vec::new();
+    for outcome in outcomes.into_iter().flat_map(|o| o.values()) {
+        if outcome.succeeded {
+            stats.succeeded += 1;
+        } else {
+            stats.failed += 1;
+        }
+        ratios.extend(outcome.duration_ratio);
+    }
+    if stats.finished() > 0 {
+        stats.success_rate = Some(stats.succeeded as f64 / stats.finished() as f64);
+    }
+    if !ratios.is_empty() {
+        stats.avg_duration_ratio = Some(ratios.iter().sum::<f64>() / ratios.len() as f64);
+    }
+    stats.reputation = reputation(&stats);
+    stats
+}
+
+/// Blend the available signals into a 0–1 score.
+pub fn reputation(stats: &AgentStats) -> Option<f64> {
+    let mut signals = Vec::new();
+    if let Some(score) = stats.avg_score {
+        signals.push((WEIGHT_SCORE, score.clamp(0.0, 1.0)));
+    }
+    if stats.finished() > 0 {
+        // Laplace smoothing: one success and one failure of prior evidence.
+        let smoothed = (stats.succeeded as f64 + 1.0) / (stats.finished() as f64 + 2.0);
+        signals.push((WEIGHT_SUCCESS, smoothed));
+    }
+    if let Some(ratio) = stats.avg_duration_ratio {
+        let timeliness = if ratio <= 1.0 { 1.0 } else { 1.0 / ratio };
+        signals.push((WEIGHT_TIMELINESS, timeliness));
+    }
+    let weight: f64 = signals.iter().map(|(w, _)| w).sum();
+    (weight > 0.0).then(|| signals.iter().map(|(w, v)| w * v).sum::<f64>() / weight)
+}
+
+/// Reputation by agent id, for the agents that have one.
+pub fn reputation_scores(store: &ReputationStore, agents: &[Agent]) -> HashMap<String, f64> {
+    agents
+        .iter()
+        .filter_map(|a| Some((a.id.clone(), agent_stats(store, a).reputation?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agency::{Lineage, PerformanceRecord, TrustLevel};
+    use crate::graph::{Estimate, Node};
+    use crate::test_helpers::make_task;
+
+    fn finished(id: &str, agent: &str, status: Status, hours: Option<f64>) -> Node {
+        let mut t = make_task(id, id);
+        t.agent = Some(agent.to_string());
+        t.status = status;
+        t.started_at = Some("2026-03-01T10:00:00Z".to_string());
+        t.completed_at = Some("2026-03-01T12:00:00Z".to_string());
+        t.estimate = hours.map(|h| Estimate {
+            hours: Some(h),
+            cost: None,
+        });
+        Node::Task(t)
+    }
+
+    fn agent(id: &str, avg_score: Option<f64>) -> Agent {
+        Agent {
+            id: id.to_string(),
+            role_id: String::new(),
+            tradeoff_id: String::new(),
+            name: id.to_string(),
+            performance: PerformanceRecord {
+                task_count: avg_score.map_or(0, |_| 1),
+                avg_score,
+                evaluations: vec![],
+            },
+            lineage: Lineage::default(),
+            capabilities: vec![],
+            rate: None,
+            capacity: None,
+            trust_level: TrustLevel::Provisional,
+            contact: None,
+            executor: "claude".to_string(),
+            preferred_model: None,
+            preferred_provider: None,
+            deployment_history: vec![],
+            attractor_weight: 0.5,
+            staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
+        }
+    }
+
+    #[test]
+    fn test_refresh_is_idempotent_and_keeps_history() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(finished("a", "x", Status::Done, Some(4.0)));
+        graph.add_node(finished("b", "x", Status::Failed, None));
+        graph.add_node(finished("c", "x", Status::Abandoned, None));
+        graph.add_node(Node::Task(make_task("d", "open")));
+
+        let store = refresh(tmp.path(), &graph).unwrap();
+        assert_eq!(store.agents["x"].len(), 2);
+        assert_eq!(store.agents["x"]["a"].duration_ratio, Some(0.5));
+
+        // Refreshing against a graph without the tasks (archived) keeps them.
+        let store = refresh(tmp.path(), &WorkGraph::new()).unwrap();
+        assert_eq!(store.agents["x"].len(), 2);
+
+        // A failed task that later succeeds counts once, as a success.
+        let mut graph = WorkGraph::new();
+        graph.add_node(finished("b", "x", Status::Done, None));
+        let store = refresh(tmp.path(), &graph).unwrap();
+        let stats = agent_stats(&store, &agent("x", None));
+        assert_eq!((stats.succeeded, stats.failed), (2, 0));
+    }
+
+    #[test]
+    fn test_stats_and_reputation() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(finished("a", "x", Status::Done, Some(1.0)));
+        graph.add_node(finished("b", "x", Status::Done, Some(1.0)));
+        graph.add_node(finished("c", "x", Status::Failed, None));
+        let store = refresh(tmp.path(), &graph).unwrap();
+
+        let stats = agent_stats(&store, &agent("x", Some(0.8)));
+        assert_eq!(stats.finished(), 3);
+        assert!((stats.success_rate.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats.avg_duration_ratio, Some(2.0));
+        // 0.5*0.8 + 0.35*(3/5) + 0.15*(1/2)
+        assert!((stats.reputation.unwrap() - 0.685).abs() < 1e-9);
+
+        // No history at all: no reputation.
+        assert_eq!(agent_stats(&store, &agent("new", None)).reputation, None);
+        // Evaluations alone are enough.
+        assert_eq!(
+            agent_stats(&store, &agent("new", Some(0.9))).reputation,
+            Some(0.9)
+        );
+    }
+}
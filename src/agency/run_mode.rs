@@ -853,6 +853,7 @@ mod tests {
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         };
         save_role(&role, &roles_dir).unwrap();
 
@@ -880,6 +881,8 @@ mod tests {
             deployment_history: vec![],
             attractor_weight: 0.5,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         save_agent(&agent, &agents_dir).unwrap();
 
@@ -26,6 +26,7 @@ pub fn build_role(
         lineage: Lineage::default(),
         default_context_scope: None,
         default_exec_mode: None,
+        tool_policy: Default::default(),
     }
 }
 
@@ -975,6 +976,7 @@ pub(crate) fn mutate_role(
         lineage: Lineage::mutation(&parent.id, parent.lineage.generation, run_id),
         default_context_scope: parent.default_context_scope.clone(),
         default_exec_mode: parent.default_exec_mode.clone(),
+        tool_policy: parent.tool_policy.clone(),
     }
 }
 
@@ -1152,6 +1154,8 @@ mod tests {
             attractor_weight: 1.0,
             deployment_history: vec![],
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         }
     }
 
@@ -1802,6 +1806,8 @@ performance:
             attractor_weight: 1.0,
             deployment_history: vec![],
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         let path = save_agent(&agent, tmp.path()).unwrap();
         let loaded = load_agent(&path).unwrap();
@@ -1840,6 +1846,8 @@ performance:
             attractor_weight: 1.0,
             deployment_history: vec![],
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         let a2 = Agent {
             id: content_hash_agent(&r2.id, &m.id),
@@ -1859,6 +1867,8 @@ performance:
             attractor_weight: 1.0,
             deployment_history: vec![],
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
 
         save_agent(&a1, dir).unwrap();
@@ -2227,6 +2237,8 @@ performance:
             attractor_weight: 1.0,
             deployment_history: vec![],
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         let a2 = Agent {
             id: content_hash_agent(&r2.id, &m.id),
@@ -2246,6 +2258,8 @@ performance:
             attractor_weight: 1.0,
             deployment_history: vec![],
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
 
         save_agent(&a1, dir).unwrap();
@@ -2396,6 +2410,8 @@ performance:
             attractor_weight: 1.0,
             deployment_history: vec![],
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         // default_executor() returns "claude" which is not human
         assert!(!agent.is_human());
@@ -0,0 +1,442 @@
+//! Role-scoped tool permissions.
+//!
+//! A role's [`ToolPolicy`] is turned into executor-specific enforcement at
+//! spawn time: `--allowedTools` / `--disallowedTools` for Claude, PATH shims
+//! that intercept denied shell commands for every executor, and a prompt
+//! section telling the agent what it may not do. Shims record each denied
+//! invocation in [`TOOL_VIOLATIONS_FILE`] and fail the task.
+//!
+//! The shims are best-effort: they only see commands resolved through
+//! `PATH`, so `/usr/bin/git push` or a script calling the program by full
+//! path goes straight to the real binary. The violations file, which the
+//! agent wrapper checks on exit, is the enforcement point for what they do
+//! catch; the prompt section and Claude's `--disallowedTools` cover the rest.
+
+use super::ToolPolicy;
+
+/// File in the agent output directory where shims record denied invocations.
+pub const TOOL_VIOLATIONS_FILE: &str = "tool_violations.log";
+
+/// Global options that take their value as the next word, per program, so
+/// the subcommand after them is still found (`git -C dir push`).
+const VALUE_OPTIONS: &[(&str, &[&str])] = &[
+    (
+        "git",
+        &["-C", "-c", "--git-dir", "--work-tree", "--namespace"],
+    ),
+    ("cargo", &["-C", "-Z", "--config", "--color"]),
+    ("npm", &["--prefix", "-w", "--workspace"]),
+    (
+        "docker",
+        &[
+            "-H",
+            "--host",
+            "-c",
+            "--context",
+            "--config",
+            "-l",
+            "--log-level",
+        ],
+    ),
+    (
+        "kubectl",
+        &[
+            "-n",
+            "--namespace",
+            "--context",
+            "--kubeconfig",
+            "-s",
+            "--server",
+        ],
+    ),
+];
+
+fn value_options(program: &str) -> &'static [&'static str] {
+    VALUE_OPTIONS
+        .iter()
+        .find(|(p, _)| *p == program)
+        .map_or(&[], |(_, options)| options)
+}
+
+/// The words of a command after its leading global options (and their
+/// values), i.e. the subcommand and its arguments.
+fn subcommand_words<'a>(program: &str, args: &[&'a str]) -> Vec<&'a str> {
+    let takes_value = value_options(program);
+    let mut i = 0;
+    while let Some(arg) = args.get(i) {
+        if !arg.starts_with('-') {
+            break;
+        }
+        i += if takes_value.contains(arg) { 2 } else { 1 };
+    }
+    args.get(i..).unwrap_or_default().to_vec()
+}
+
+/// A single tool-policy entry, classified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolRule {
+    /// An executor tool name or pattern, e.g. `Edit` or `WebFetch`.
+    Tool(String),
+    /// A shell command prefix split into words, e.g. `["git", "push"]`.
+    Command(Vec<String>),
+}
+
+impl ToolRule {
+    /// Classify a policy entry. `Bash(git push:*)` and `git push` both parse
+    /// to a command rule; a single capitalised word is a tool name.
+    pub fn parse(entry: &str) -> Option<ToolRule> {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            return None;
+        }
+        if let Some(inner) = entry
+            .strip_prefix("Bash(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            let inner = inner.trim_end_matches('*').trim_end_matches(':');
+            let words: Vec<String> = inner.split_whitespace().map(str::to_string).collect();
+            return if words.is_empty() {
+                Some(ToolRule::Tool("Bash".to_string()))
+            } else {
+                Some(ToolRule::Command(words))
+            };
+        }
+        let is_tool_name = entry.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+            && !entry.contains(char::is_whitespace);
+        if is_tool_name {
+            Some(ToolRule::Tool(entry.to_string()))
+        } else {
+            Some(ToolRule::Command(
+                entry.split_whitespace().map(str::to_string).collect(),
+            ))
+        }
+    }
+
+    /// The Claude CLI tool pattern for this rule.
+    pub fn claude_pattern(&self) -> String {
+        match self {
+            ToolRule::Tool(name) => name.clone(),
+            ToolRule::Command(words) => format!("Bash({}:*)", words.join(" ")),
+        }
+    }
+}
+
+impl ToolPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Allowed entries as Claude tool patterns.
+    pub fn claude_allowed_tools(&self) -> Vec<String> {
+        self.allow
+            .iter()
+            .filter_map(|e| ToolRule::parse(e))
+            .map(|r| r.claude_pattern())
+            .collect()
+    }
+
+    /// Denied entries as Claude tool patterns.
+    pub fn claude_denied_tools(&self) -> Vec<String> {
+        self.deny
+            .iter()
+            .filter_map(|e| ToolRule::parse(e))
+            .map(|r| r.claude_pattern())
+            .collect()
+    }
+
+    /// Denied shell command prefixes, each split into words.
+    pub fn denied_commands(&self) -> Vec<Vec<String>> {
+        self.deny
+            .iter()
+            .filter_map(|e| match ToolRule::parse(e) {
+                Some(ToolRule::Command(words)) => Some(words),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Check a shell command line against the denied commands.
+    ///
+    /// The line is split into simple commands on `;`, `&`, `|`, parentheses,
+    /// backticks and newlines. Leading `VAR=value` assignments are skipped and
+    /// the program is compared by basename, so `/usr/bin/git push` matches a
+    /// `git push` rule; global options before the subcommand are skipped as
+    /// the shims do. Returns the first denied prefix that matches.
+    pub fn check_command(&self, command_line: &str) -> Option<String> {
+        let denied = self.denied_commands();
+        if denied.is_empty() {
+            return None;
+        }
+        for segment in command_line.split([';', '&', '|', '(', ')', '`', '\n']) {
+            let mut words = segment
+                .split_whitespace()
+                .map(|w| w.trim_matches(|c| c == '"' || c == '\''))
+                .skip_while(|w| is_env_assignment(w));
+            let Some(program) = words.next() else {
+                continue;
+            };
+            let program = program.rsplit('/').next().unwrap_or(program);
+            let args: Vec<&str> = words.collect();
+            let rest = subcommand_words(program, &args);
+            for rule in &denied {
+                if rule[0] == program
+                    && rule.len() - 1 <= rest.len()
+                    && rule[1..].iter().zip(&rest).all(|(a, b)| a == b)
+                {
+                    return Some(rule.join(" "));
+                }
+            }
+        }
+        None
+    }
+
+    /// Prompt section describing the policy to the agent. Empty when the
+    /// policy is empty.
+    pub fn prompt_section(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let mut out = String::from("\n## Tool Permissions\n\n");
+        out.push_str("Your role restricts which tools and commands you may use.\n");
+        if !self.allow.is_empty() {
+            out.push_str("\nYou may only use:\n");
+            for entry in &self.allow {
+                out.push_str(&format!("- `{}`\n", entry));
+            }
+        }
+        if !self.deny.is_empty() {
+            out.push_str("\nYou must NOT use:\n");
+            for entry in &self.deny {
+                out.push_str(&format!("- `{}`\n", entry));
+            }
+        }
+        out.push_str(
+            "\nDenied invocations are blocked, logged, and fail the task. If the work \
+             needs one of them, stop and explain why with `wg fail --reason` instead.\n",
+        );
+        out
+    }
+}
+
+fn is_env_assignment(word: &str) -> bool {
+    match word.split_once('=') {
+        Some((name, _)) => {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+/// Render a bash shim for `program` that blocks the denied prefixes.
+///
+/// The shim skips leading `-` options, and the values of those listed in
+/// [`VALUE_OPTIONS`], to find the subcommand (so `git --no-pager push` and
+/// `git -C . push` are still caught by a `git push` rule), records a
+/// violation, fails the task via `wg fail --class tool-policy-violation`, and
+/// exits 126.
+/// Anything else is passed through to the real program with the shim
+/// directory removed from `PATH`.
+pub fn render_command_shim(
+    program: &str,
+    denied: &[Vec<String>],
+    shim_dir: &str,
+    violations_file: &str,
+) -> String {
+    let patterns: Vec<String> = denied
+        .iter()
+        .filter(|rule| rule.first().map(String::as_str) == Some(program))
+        .map(|rule| shell_quote(&rule.join(" ")))
+        .collect();
+    let value_case = match value_options(program) {
+        [] => String::new(),
+        options => {
+            let options: Vec<String> = options.iter().map(|o| shell_quote(o)).collect();
+            format!(
+                "            {}) SKIP_VALUE=1; continue ;;\n",
+                options.join("|")
+            )
+        }
+    };
+    format!(
+        r#"#!/bin/bash
+# Generated by wg spawn: role tool policy shim for `{program}`.
+SHIM_DIR={shim_dir}
+VIOLATIONS_FILE={violations_file}
+WORDS=()
+SKIP_VALUE=0
+for ARG in "$@"; do
+    if [ ${{#WORDS[@]}} -eq 0 ]; then
+        if [ $SKIP_VALUE -eq 1 ]; then
+            SKIP_VALUE=0
+            continue
+        fi
+        case "$ARG" in
+{value_case}            -*) continue ;;
+        esac
+    fi
+    WORDS+=("$ARG")
+done
+INVOCATION="{program} ${{WORDS[*]}}"
+for DENIED in {patterns}; do
+    case "$INVOCATION " in
+        "$DENIED "*)
+            echo "$(date -u +%Y-%m-%dT%H:%M:%SZ) denied '$DENIED': {program} $*" >> "$VIOLATIONS_FILE"
+            echo "wg: '$DENIED' is not permitted by this task's role tool policy" >&2
+            if [ -n "$WG_TASK_ID" ]; then
+                wg fail "$WG_TASK_ID" --class tool-policy-violation --reason "Role tool policy violation: ran '$DENIED'" >/dev/null 2>&1 || true
+            fi
+            exit 126
+            ;;
+    esac
+done
+PATH=$(printf '%s' "$PATH" | tr ':' '\n' | grep -vxF "$SHIM_DIR" | paste -sd: -)
+exec {program} "$@"
+"#,
+        program = program,
+        shim_dir = shell_quote(shim_dir),
+        violations_file = shell_quote(violations_file),
+        patterns = patterns.join(" "),
+        value_case = value_case,
+    )
+}
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allow: &[&str], deny: &[&str]) -> ToolPolicy {
+        ToolPolicy {
+            allow: allow.iter().map(|s| s.to_string()).collect(),
+            deny: deny.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!(
+            ToolRule::parse("Edit"),
+            Some(ToolRule::Tool("Edit".to_string()))
+        );
+        assert_eq!(
+            ToolRule::parse("git push"),
+            Some(ToolRule::Command(vec!["git".into(), "push".into()]))
+        );
+        assert_eq!(
+            ToolRule::parse("Bash(git push:*)"),
+            Some(ToolRule::Command(vec!["git".into(), "push".into()]))
+        );
+        assert_eq!(
+            ToolRule::parse("curl").unwrap().claude_pattern(),
+            "Bash(curl:*)"
+        );
+        assert_eq!(ToolRule::parse("  "), None);
+    }
+
+    #[test]
+    fn test_claude_patterns() {
+        let p = policy(&["Read", "cargo test"], &["git push", "WebFetch"]);
+        assert_eq!(p.claude_allowed_tools(), vec!["Read", "Bash(cargo test:*)"]);
+        assert_eq!(
+            p.claude_denied_tools(),
+            vec!["Bash(git push:*)", "WebFetch"]
+        );
+        assert_eq!(
+            p.denied_commands(),
+            vec![vec!["git".to_string(), "push".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_check_command() {
+        let p = policy(&[], &["git push", "rm -rf"]);
+        assert_eq!(p.check_command("cargo test"), None);
+        assert_eq!(p.check_command("git pull --rebase"), None);
+        assert_eq!(
+            p.check_command("cargo test && git push origin main")
+                .as_deref(),
+            Some("git push")
+        );
+        assert_eq!(
+            p.check_command("GIT_SSH=x /usr/bin/git push").as_deref(),
+            Some("git push")
+        );
+        assert_eq!(p.check_command("echo 'rm -rf'"), None);
+        assert_eq!(
+            p.check_command("(cd x; rm -rf build)").as_deref(),
+            Some("rm -rf")
+        );
+        assert_eq!(
+            p.check_command("git -C . push").as_deref(),
+            Some("git push")
+        );
+        assert_eq!(
+            p.check_command("git -c k=v --no-pager push").as_deref(),
+            Some("git push")
+        );
+        assert_eq!(p.check_command("git -C push status"), None);
+    }
+
+    #[test]
+    fn test_prompt_section() {
+        assert!(ToolPolicy::default().prompt_section().is_empty());
+        let section = policy(&[], &["git push"]).prompt_section();
+        assert!(section.contains("## Tool Permissions"));
+        assert!(section.contains("`git push`"));
+    }
+
+    #[test]
+    fn test_render_shim_only_includes_own_program() {
+        let p = policy(&[], &["git push", "npm publish"]);
+        let shim = render_command_shim("git", &p.denied_commands(), "/tmp/shims", "/tmp/v.log");
+        assert!(shim.contains("for DENIED in 'git push'; do"));
+        assert!(!shim.contains("npm publish"));
+        assert!(shim.contains("exec git \"$@\""));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shim_skips_option_values() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let violations = tmp.path().join("v.log");
+        let p = policy(&[], &["git push"]);
+        let shim = render_command_shim(
+            "git",
+            &p.denied_commands(),
+            &tmp.path().to_string_lossy(),
+            &violations.to_string_lossy(),
+        );
+        let shim_path = tmp.path().join("git");
+        std::fs::write(&shim_path, shim).unwrap();
+        for args in [
+            &["push"][..],
+            &["-C", ".", "push", "origin"],
+            &["-c", "k=v", "push"],
+            &["--git-dir", ".git", "--no-pager", "push"],
+        ] {
+            let status = std::process::Command::new("bash")
+                .arg(&shim_path)
+                .args(args)
+                .env_remove("WG_TASK_ID")
+                .status()
+                .unwrap();
+            assert_eq!(status.code(), Some(126), "git {:?}", args);
+        }
+        let log = std::fs::read_to_string(&violations).unwrap();
+        assert_eq!(log.lines().count(), 4);
+        assert!(log.contains("git -C . push origin"));
+    }
+
+    #[test]
+    fn test_role_tool_policy_serde_default() {
+        let yaml =
+            "id: r\nname: r\ndescription: d\nperformance:\n  task_count: 0\n  avg_score: null\n";
+        let role: crate::agency::Role = serde_yaml::from_str(yaml).unwrap();
+        assert!(role.tool_policy.is_empty());
+        let out = serde_yaml::to_string(&role).unwrap();
+        assert!(!out.contains("tool_policy"));
+    }
+}
@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -491,12 +492,55 @@ pub struct Role {
     /// Values: "full" (default), "light" (read-only tools), "bare" (wg CLI only), "shell" (no LLM).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_exec_mode: Option<String>,
+    /// Tools and shell commands agents with this role may or may not use.
+    #[serde(default, skip_serializing_if = "ToolPolicy::is_empty")]
+    pub tool_policy: ToolPolicy,
+}
+
+/// Role-scoped tool permissions, enforced by the executor at spawn time.
+///
+/// Entries are either executor tool names (`Edit`, `WebFetch`), Claude-style
+/// tool patterns (`Bash(git push:*)`), or plain shell command prefixes
+/// (`git push`). Plain commands are denied both in the Claude `Bash` tool and,
+/// via PATH shims, for any process the agent runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolPolicy {
+    /// Tools the agent may use. Empty means the executor's default set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Tools or commands the agent must not use.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
 }
 
 fn default_executor() -> String {
     "claude".to_string()
 }
 
+/// What an agent declares it can take on, registered with
+/// `wg agent register`. Assignment checks task requirements against it;
+/// agents without one are matched on skills alone.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityDocument {
+    /// Skills the agent covers. On a registered agent these are kept in
+    /// `Agent::capabilities`, so this is empty there.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    /// Models the agent can run. Empty means any model.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub models: Vec<String>,
+    /// Largest context window (tokens) the agent can work in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_context: Option<u64>,
+    /// Most the agent may spend on one task (USD).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_ceiling: Option<f64>,
+    /// Graph resources the agent can use. Tasks that `require` any other
+    /// resource are out of reach.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<String>,
+}
+
 /// A first-class agent entity — a role paired with a trade-off configuration.
 ///
 /// Stored in `cache/agents/{hash}.yaml`.
@@ -539,6 +583,13 @@ pub struct Agent {
     pub attractor_weight: f64,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub staleness_flags: Vec<StalenessFlag>,
+    /// Declared limits checked at assignment; see [`CapabilityDocument`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capability_doc: Option<CapabilityDocument>,
+    /// Most the agent may spend per UTC day, in USD. The service stops
+    /// dispatching to an agent over its budget; see `dispatch::budget`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_budget: Option<f64>,
 }
 
 fn default_attractor_weight() -> f64 {
@@ -727,7 +778,7 @@ fn default_eval_source() -> String {
 
 /// How propagation should be applied to dependents when a task retries.
 /// Used in IterationConfig.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(rename_all = "snake_case")]
 #[derive(Default)]
 pub enum PropagationPolicy {
@@ -741,7 +792,7 @@ pub enum PropagationPolicy {
 }
 
 /// Retry strategy recommended by the evaluator.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum RetryStrategy {
     /// Retry with the same model/executor
@@ -754,7 +805,7 @@ pub enum RetryStrategy {
 
 /// Configuration for task iteration/retry behavior.
 /// Attached to tasks via --max-retries, --propagation, --retry-strategy flags.
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct IterationConfig {
     /// Maximum number of retries allowed (evaluator-triggered)
@@ -946,6 +997,8 @@ mod tests {
             deployment_history: vec![],
             attractor_weight: 0.5,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         }
     }
 
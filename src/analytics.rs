@@ -0,0 +1,352 @@
+//! Stable traversal API for custom graph analytics.
+//!
+//! Programs that link against the `workgraph` library can load a graph with
+//! [`load_graph`](crate::load_graph) and walk it with the functions here
+//! instead of reading `graph.jsonl` and re-deriving its edges:
+//!
+//! - [`edges`] lists every edge once, typed by [`EdgeKind`] and pointing
+//!   upstream → downstream whichever side declared it (`after` or `before`).
+//! - [`topological`] orders tasks so each one follows its dependencies; loop
+//!   back-edges are ignored so cyclic graphs still order.
+//! - [`walk`] drives a [`GraphVisitor`] over resources, tasks, and edges.
+//! - [`subgraph`] extracts the tasks matching a predicate as a new graph.
+//!
+//! # Stability
+//!
+//! The rest of the library changes with the CLI; this module doesn't. Items
+//! here are only added, never removed or changed in meaning, outside a
+//! semver-major release. [`EdgeKind`] and [`Edge`] are `#[non_exhaustive]`
+//! so new edge kinds and attributes can be added without breaking callers.
+//! Every order is deterministic, with ties broken by id, so analytics output
+//! diffs cleanly between runs.
+//!
+//! # Example
+//! ```
+//! use workgraph::analytics::{self, EdgeKind};
+//! use workgraph::graph::{Node, Task, WorkGraph};
+//!
+//! let mut graph = WorkGraph::new();
+//! for (id, after) in [("build", vec![]), ("test", vec!["build"])] {
+//!     graph.add_node(Node::Task(Task {
+//!         id: id.to_string(),
+//!         title: id.to_string(),
+//!         after: after.into_iter().map(String::from).collect(),
+//!         ..Task::default()
+//!     }));
+//! }
+//!
+//! let order: Vec<&str> = analytics::topological(&graph)
+//!     .iter()
+//!     .map(|t| t.id.as_str())
+//!     .collect();
+//! assert_eq!(order, ["build", "test"]);
+//!
+//! let edges = analytics::edges(&graph);
+//! assert_eq!(edges.len(), 1);
+//! assert_eq!(edges[0].kind, EdgeKind::Dependency);
+//! assert_eq!((edges[0].from, edges[0].to), ("build", "test"));
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::graph::{DependencyEdge, Node, Resource, Task, WorkGraph};
+
+/// What an [`Edge`] means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum EdgeKind {
+    /// `to` waits for `from` to finish.
+    Dependency,
+    /// A dependency that closes a loop: `to` is the loop header and runs
+    /// again after `from`.
+    LoopBack,
+    /// Task `from` needs resource `to`.
+    Requires,
+}
+
+/// One edge of the graph, between existing nodes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct Edge<'a> {
+    pub kind: EdgeKind,
+    pub from: &'a str,
+    pub to: &'a str,
+    /// Lag and weight, for dependencies that declare them
+    pub attrs: Option<&'a DependencyEdge>,
+}
+
+/// Every edge in `graph`, sorted by `from`, then `to`. A dependency
+/// declared on both sides appears once; references to missing nodes are
+/// skipped.
+pub fn edges(graph: &WorkGraph) -> Vec<Edge<'_>> {
+    let back_edges = graph.compute_cycle_analysis().back_edges;
+    let mut deps: BTreeSet<(&str, &str)> = BTreeSet::new();
+    let mut out = Vec::new();
+
+    for task in graph.tasks() {
+        for upstream in &task.after {
+            if graph.get_task(upstream).is_some() {
+                deps.insert((upstream.as_str(), task.id.as_str()));
+            }
+        }
+        for downstream in &task.before {
+            if graph.get_task(downstream).is_some() {
+                deps.insert((task.id.as_str(), downstream.as_str()));
+            }
+        }
+        for resource in &task.requires {
+            if let Some(r) = graph.get_resource(resource) {
+                out.push(Edge {
+                    kind: EdgeKind::Requires,
+                    from: task.id.as_str(),
+                    to: r.id.as_str(),
+                    attrs: None,
+                });
+            }
+        }
+    }
+
+    for (from, to) in deps {
+        let is_back = back_edges.contains(&(from.to_string(), to.to_string()));
+        out.push(Edge {
+            kind: if is_back {
+                EdgeKind::LoopBack
+            } else {
+                EdgeKind::Dependency
+            },
+            from,
+            to,
+            attrs: graph.get_task(to).and_then(|t| t.edges.get(from)),
+        });
+    }
+    out.sort_by(|a, b| (a.from, a.to, a.kind).cmp(&(b.from, b.to, b.kind)));
+    out.dedup_by(|a, b| (a.from, a.to, a.kind) == (b.from, b.to, b.kind));
+    out
+}
+
+/// Tasks `id` directly depends on, including through loop back-edges,
+/// sorted by id.
+pub fn upstream<'a>(graph: &'a WorkGraph, id: &str) -> Vec<&'a Task> {
+    edges(graph)
+        .into_iter()
+        .filter(|e| e.kind != EdgeKind::Requires && e.to == id)
+        .filter_map(|e| graph.get_task(e.from))
+        .collect()
+}
+
+/// Tasks that directly depend on `id`, including through loop back-edges,
+/// sorted by id.
+pub fn downstream<'a>(graph: &'a WorkGraph, id: &str) -> Vec<&'a Task> {
+    edges(graph)
+        .into_iter()
+        .filter(|e| e.kind != EdgeKind::Requires && e.from == id)
+        .filter_map(|e| graph.get_task(e.to))
+        .collect()
+}
+
+/// All tasks, each after the tasks it depends on. Among tasks that are ready
+/// at the same point the lowest id comes first. Loop back-edges are ignored;
+/// tasks still caught in a cycle without one come last, by id.
+pub fn topological(graph: &WorkGraph) -> Vec<&Task> {
+    let mut indegree: BTreeMap<&str, usize> = graph.tasks().map(|t| (t.id.as_str(), 0)).collect();
+    let mut successors: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for edge in edges(graph) {
+        if edge.kind == EdgeKind::Dependency {
+            *indegree.entry(edge.to).or_default() += 1;
+            successors.entry(edge.from).or_default().push(edge.to);
+        }
+    }
+
+    let mut ready: BTreeSet<&str> = indegree
+        .iter()
+        .filter(|(_, n)| **n == 0)
+        .map(|(id, _)| *id)
+        .collect();
+    let mut order = Vec::with_capacity(indegree.len());
+    let mut placed = HashSet::new();
+    while let Some(id) = ready.pop_first() {
+        order.push(id);
+        placed.insert(id);
+        for next in successors.get(id).into_iter().flatten() {
+            let n = indegree
+                .get_mut(next)
+                .expect("edge targets an existing task");
+            *n -= 1;
+            if *n == 0 {
+                ready.insert(*next);
+            }
+        }
+    }
+    order.extend(indegree.keys().copied().filter(|id| !placed.contains(id)));
+
+    order
+        .into_iter()
+        .filter_map(|id| graph.get_task(id))
+        .collect()
+}
+
+/// Callbacks for [`walk`]. Every method defaults to doing nothing, so a
+/// visitor implements only what it needs.
+pub trait GraphVisitor {
+    fn visit_resource(&mut self, _resource: &Resource) {}
+    fn visit_task(&mut self, _task: &Task) {}
+    fn visit_edge(&mut self, _edge: &Edge<'_>) {}
+}
+
+/// Visit every resource (by id), then every task in [`topological`] order,
+/// then every edge in [`edges`] order.
+pub fn walk(graph: &WorkGraph, visitor: &mut impl GraphVisitor) {
+    let mut resources: Vec<&Resource> = graph.resources().collect();
+    resources.sort_by(|a, b| a.id.cmp(&b.id));
+    for resource in resources {
+        visitor.visit_resource(resource);
+    }
+    for task in topological(graph) {
+        visitor.visit_task(task);
+    }
+    for edge in edges(graph) {
+        visitor.visit_edge(&edge);
+    }
+}
+
+/// A new graph holding the tasks that match `keep` and the resources they
+/// require. Dependencies on tasks left out are dropped, so the result is
+/// self-contained.
+pub fn subgraph(graph: &WorkGraph, keep: impl Fn(&Task) -> bool) -> WorkGraph {
+    let kept: HashSet<&str> = graph
+        .tasks()
+        .filter(|t| keep(t))
+        .map(|t| t.id.as_str())
+        .collect();
+    let mut out = WorkGraph::new();
+    let mut resources = BTreeSet::new();
+    for task in graph.tasks().filter(|t| kept.contains(t.id.as_str())) {
+        let mut task = task.clone();
+        task.after.retain(|id| kept.contains(id.as_str()));
+        task.before.retain(|id| kept.contains(id.as_str()));
+        task.edges.retain(|id, _| kept.contains(id.as_str()));
+        resources.extend(task.requires.iter().cloned());
+        out.add_node(Node::Task(task));
+    }
+    for id in resources {
+        if let Some(resource) = graph.get_resource(&id) {
+            out.add_node(Node::Resource(resource.clone()));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::make_task;
+
+    fn task(id: &str, after: &[&str]) -> Task {
+        let mut t = make_task(id, id);
+        t.after = after.iter().map(|s| s.to_string()).collect();
+        t
+    }
+
+    fn ids(tasks: &[&Task]) -> Vec<String> {
+        tasks.iter().map(|t| t.id.clone()).collect()
+    }
+
+    /// `c` and `b` both follow `a` (`b` through `a.before`), `d` joins them,
+    /// and `d` needs resource `gpu`.
+    fn diamond() -> WorkGraph {
+        let mut graph = WorkGraph::new();
+        let mut a = task("a", &[]);
+        a.before = vec!["b".to_string()];
+        graph.add_node(Node::Task(a));
+        let mut b = task("b", &["a"]);
+        b.edges.insert(
+            "a".to_string(),
+            DependencyEdge {
+                lag: Some("1h".to_string()),
+                weight: None,
+            },
+        );
+        graph.add_node(Node::Task(b));
+        graph.add_node(Node::Task(task("c", &["a"])));
+        let mut d = task("d", &["b", "c", "missing"]);
+        d.requires = vec!["gpu".to_string()];
+        graph.add_node(Node::Task(d));
+        graph.add_node(Node::Resource(Resource {
+            id: "gpu".to_string(),
+            name: None,
+            resource_type: None,
+            available: None,
+            unit: None,
+        }));
+        graph
+    }
+
+    #[test]
+    fn test_edges_are_typed_deduped_and_sorted() {
+        let graph = diamond();
+        let edges = edges(&graph);
+        let pairs: Vec<(EdgeKind, &str, &str)> =
+            edges.iter().map(|e| (e.kind, e.from, e.to)).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (EdgeKind::Dependency, "a", "b"),
+                (EdgeKind::Dependency, "a", "c"),
+                (EdgeKind::Dependency, "b", "d"),
+                (EdgeKind::Dependency, "c", "d"),
+                (EdgeKind::Requires, "d", "gpu"),
+            ]
+        );
+        assert_eq!(edges[0].attrs.unwrap().lag.as_deref(), Some("1h"));
+        assert_eq!(ids(&upstream(&graph, "d")), ["b", "c"]);
+        assert_eq!(ids(&downstream(&graph, "a")), ["b", "c"]);
+    }
+
+    #[test]
+    fn test_topological_order_survives_loops() {
+        let graph = diamond();
+        assert_eq!(ids(&topological(&graph)), ["a", "b", "c", "d"]);
+
+        // x → y → z → y: the back-edge into the loop header is ignored.
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task("x", &[])));
+        graph.add_node(Node::Task(task("y", &["x", "z"])));
+        graph.add_node(Node::Task(task("z", &["y"])));
+        assert_eq!(ids(&topological(&graph)), ["x", "y", "z"]);
+        assert!(
+            edges(&graph)
+                .iter()
+                .any(|e| e.kind == EdgeKind::LoopBack && (e.from, e.to) == ("z", "y"))
+        );
+    }
+
+    #[test]
+    fn test_walk_and_subgraph() {
+        #[derive(Default)]
+        struct Recorder(Vec<String>);
+        impl GraphVisitor for Recorder {
+            fn visit_resource(&mut self, resource: &Resource) {
+                self.0.push(format!("resource {}", resource.id));
+            }
+            fn visit_task(&mut self, task: &Task) {
+                self.0.push(format!("task {}", task.id));
+            }
+        }
+
+        let graph = diamond();
+        let mut recorder = Recorder::default();
+        walk(&graph, &mut recorder);
+        assert_eq!(
+            recorder.0,
+            ["resource gpu", "task a", "task b", "task c", "task d"]
+        );
+
+        let sub = subgraph(&graph, |t| t.id != "a");
+        assert_eq!(sub.tasks().count(), 3);
+        assert!(sub.get_resource("gpu").is_some());
+        let b = sub.get_task("b").unwrap();
+        assert!(b.after.is_empty() && b.edges.is_empty());
+        assert_eq!(sub.get_task("d").unwrap().after, ["b", "c"]);
+        assert_eq!(ids(&topological(&sub)), ["b", "c", "d"]);
+    }
+}
@@ -0,0 +1,497 @@
+//! Anomaly detection on task metrics.
+//!
+//! Three patterns are flagged, with thresholds from `[anomaly]` in the
+//! config:
+//!
+//! - **overrun**: an in-progress task has been running `overrun_factor`
+//!   times its estimated hours or more.
+//! - **fast_low_score**: an agent keeps finishing tasks suspiciously fast
+//!   (under `fast_fraction` of the estimate, or under `fast_unestimated`
+//!   without one) and getting evaluation scores below `low_score`.
+//! - **failure_spike**: a tag collects `failure_min` or more failures within
+//!   `failure_window`, at least `failure_spike_factor` times its usual rate
+//!   over the preceding `failure_baseline`. Every failure counts, including
+//!   ones since retried.
+//!
+//! The service runs [`run_sweep`] after each coordinator tick. Each anomaly
+//! is alerted once, on the urgent route of `notify.toml` (Matrix default
+//! room as fallback), and again only if it clears and comes back. Alerted
+//! anomalies are kept in `.wg/service/anomalies.json`; `wg anomalies` lists
+//! the current ones.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::agency::Evaluation;
+use crate::config::AnomalyConfig;
+use crate::graph::{Status, Task, WorkGraph, is_system_task, parse_delay};
+use crate::notify::config::NotifyConfig;
+
+const STATE_FILE: &str = "anomalies.json";
+
+/// Log prefixes written when a task fails (`wg fail`, evaluation reject).
+const FAILURE_LOG_PREFIXES: [&str; 2] = ["Task marked as failed", "Evaluation rejected task"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    Overrun,
+    FastLowScore,
+    FailureSpike,
+}
+
+impl std::fmt::Display for AnomalyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overrun => write!(f, "overrun"),
+            Self::FastLowScore => write!(f, "fast_low_score"),
+            Self::FailureSpike => write!(f, "failure_spike"),
+        }
+    }
+}
+
+/// One detected anomaly.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Anomaly {
+    pub kind: AnomalyKind,
+    /// Task ID, agent ID or tag, depending on `kind`.
+    pub subject: String,
+    pub detail: String,
+    /// Tasks involved.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tasks: Vec<String>,
+}
+
+impl Anomaly {
+    /// Identity used to alert once per anomaly.
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.kind, self.subject)
+    }
+
+    /// Text sent to the notification channel.
+    pub fn message(&self) -> String {
+        match self.kind {
+            AnomalyKind::Overrun => format!("Anomaly: task '{}' {}", self.subject, self.detail),
+            AnomalyKind::FastLowScore => {
+                format!("Anomaly: agent '{}' {}", self.subject, self.detail)
+            }
+            AnomalyKind::FailureSpike => format!("Anomaly: tag '{}' {}", self.subject, self.detail),
+        }
+    }
+}
+
+fn parse_time(s: Option<&str>) -> Option<DateTime<Utc>> {
+    s?.parse::<DateTime<Utc>>().ok()
+}
+
+fn parse_window(field: &str, value: &str) -> Result<Duration> {
+    let secs = parse_delay(value).with_context(|| {
+        format!(
+            "Invalid anomaly.{} '{}': expected e.g. 30m, 24h, 7d",
+            field, value
+        )
+    })?;
+    Ok(Duration::seconds(secs as i64))
+}
+
+fn estimate_hours(task: &Task) -> Option<f64> {
+    task.estimate
+        .as_ref()
+        .and_then(|e| e.hours)
+        .filter(|h| *h > 0.0)
+}
+
+fn hours(d: Duration) -> f64 {
+    d.num_seconds() as f64 / 3600.0
+}
+
+/// In-progress tasks running `overrun_factor` times their estimate.
+fn overruns(graph: &WorkGraph, now: DateTime<Utc>, config: &AnomalyConfig) -> Vec<Anomaly> {
+    graph
+        .tasks()
+        .filter(|t| t.status == Status::InProgress && !is_system_task(&t.id))
+        .filter_map(|t| {
+            let estimate = estimate_hours(t)?;
+            let running = hours(now - parse_time(t.started_at.as_deref())?);
+            (running >= estimate * config.overrun_factor).then(|| Anomaly {
+                kind: AnomalyKind::Overrun,
+                subject: t.id.clone(),
+                detail: format!(
+                    "has been running {} against an estimate of {} ({:.1}x)",
+                    crate::format_duration((running * 3600.0) as i64, false),
+                    crate::format_duration((estimate * 3600.0) as i64, false),
+                    running / estimate
+                ),
+                tasks: vec![t.id.clone()],
+            })
+        })
+        .collect()
+}
+
+/// Agents with several fast, low-scored completions in the window.
+fn fast_low_scores(
+    graph: &WorkGraph,
+    evaluations: &[Evaluation],
+    now: DateTime<Utc>,
+    config: &AnomalyConfig,
+) -> Result<Vec<Anomaly>> {
+    let since = now - parse_window("window", &config.window)?;
+    let unestimated = parse_window("fast_unestimated", &config.fast_unestimated)?;
+
+    let mut by_agent: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for eval in evaluations {
+        if eval.score >= config.low_score
+            || parse_time(Some(&eval.timestamp)).is_none_or(|at| at < since)
+        {
+            continue;
+        }
+        let Some(task) = graph.get_task(&eval.task_id) else {
+            continue;
+        };
+        let agent = if eval.agent_id.is_empty() {
+            match task.agent {
+                Some(ref agent) => agent.clone(),
+                None => continue,
+            }
+        } else {
+            eval.agent_id.clone()
+        };
+        let (Some(started), Some(completed)) = (
+            parse_time(task.started_at.as_deref()),
+            parse_time(task.completed_at.as_deref()),
+        ) else {
+            continue;
+        };
+        let took = completed - started;
+        let fast = match estimate_hours(task) {
+            Some(estimate) => hours(took) < estimate * config.fast_fraction,
+            None => took < unestimated,
+        };
+        if fast {
+            by_agent.entry(agent).or_default().insert(task.id.clone());
+        }
+    }
+
+    Ok(by_agent
+        .into_iter()
+        .filter(|(_, tasks)| tasks.len() >= config.fast_min_tasks.max(1))
+        .map(|(agent, tasks)| Anomaly {
+            kind: AnomalyKind::FastLowScore,
+            detail: format!(
+                "finished {} task(s) unusually fast with evaluation scores below {}",
+                tasks.len(),
+                config.low_score
+            ),
+            subject: agent,
+            tasks: tasks.into_iter().collect(),
+        })
+        .collect())
+}
+
+/// When each failure of `task` happened, from its log.
+fn failure_times(task: &Task) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+    task.log
+        .iter()
+        .filter(|e| {
+            FAILURE_LOG_PREFIXES
+                .iter()
+                .any(|p| e.message.starts_with(p))
+        })
+        .filter_map(|e| parse_time(Some(&e.timestamp)))
+}
+
+/// Tags whose recent failures spike above their usual rate.
+fn failure_spikes(
+    graph: &WorkGraph,
+    now: DateTime<Utc>,
+    config: &AnomalyConfig,
+) -> Result<Vec<Anomaly>> {
+    let window = parse_window("failure_window", &config.failure_window)?;
+    let baseline = parse_window("failure_baseline", &config.failure_baseline)?;
+    let recent_since = now - window;
+    let baseline_since = recent_since - baseline;
+
+    // tag -> (recent failures, baseline failures, recently failed tasks)
+    let mut by_tag: BTreeMap<&str, (usize, usize, BTreeSet<&str>)> = BTreeMap::new();
+    for task in graph.tasks().filter(|t| !is_system_task(&t.id)) {
+        for at in failure_times(task) {
+            for tag in &task.tags {
+                let entry = by_tag.entry(tag.as_str()).or_default();
+                if at >= recent_since {
+                    entry.0 += 1;
+                    entry.2.insert(task.id.as_str());
+                } else if at >= baseline_since {
+                    entry.1 += 1;
+                }
+            }
+        }
+    }
+
+    let scale = window.num_seconds() as f64 / baseline.num_seconds().max(1) as f64;
+    Ok(by_tag
+        .into_iter()
+        .filter_map(|(tag, (recent, usual, tasks))| {
+            let expected = usual as f64 * scale;
+            (recent >= config.failure_min.max(1)
+                && recent as f64 >= expected * config.failure_spike_factor)
+                .then(|| Anomaly {
+                    kind: AnomalyKind::FailureSpike,
+                    subject: tag.to_string(),
+                    detail: format!(
+                        "had {} failure(s) in the last {}, against {:.1} expected from the previous {}",
+                        recent, config.failure_window, expected, config.failure_baseline
+                    ),
+                    tasks: tasks.into_iter().map(String::from).collect(),
+                })
+        })
+        .collect())
+}
+
+/// All current anomalies, ordered by kind and subject.
+pub fn detect(
+    graph: &WorkGraph,
+    evaluations: &[Evaluation],
+    now: DateTime<Utc>,
+    config: &AnomalyConfig,
+) -> Result<Vec<Anomaly>> {
+    let mut anomalies = overruns(graph, now, config);
+    anomalies.extend(fast_low_scores(graph, evaluations, now, config)?);
+    anomalies.extend(failure_spikes(graph, now, config)?);
+    anomalies.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.subject.cmp(&b.subject)));
+    Ok(anomalies)
+}
+
+/// Anomalies already alerted, by [`Anomaly::key`], with when they were
+/// first seen.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnomalyState {
+    #[serde(default)]
+    pub alerted: BTreeMap<String, String>,
+}
+
+impl AnomalyState {
+    fn path(workgraph_dir: &Path) -> PathBuf {
+        workgraph_dir.join("service").join(STATE_FILE)
+    }
+
+    /// Load the state; empty if none was recorded yet.
+    pub fn load(workgraph_dir: &Path) -> Result<Self> {
+        let path = Self::path(workgraph_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, workgraph_dir: &Path) -> Result<()> {
+        let path = Self::path(workgraph_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Forget anomalies that cleared and return the ones not alerted yet,
+    /// marking them alerted.
+    pub fn observe(&mut self, anomalies: &[Anomaly], now: DateTime<Utc>) -> Vec<Anomaly> {
+        let current: HashSet<String> = anomalies.iter().map(Anomaly::key).collect();
+        self.alerted.retain(|key, _| current.contains(key));
+        anomalies
+            .iter()
+            .filter(|a| self.alerted.insert(a.key(), now.to_rfc3339()).is_none())
+            .cloned()
+            .collect()
+    }
+}
+
+/// Detect anomalies in the workgraph at `dir` with its configured
+/// thresholds.
+pub fn detect_in(dir: &Path, graph_path: &Path) -> Result<Vec<Anomaly>> {
+    let graph = crate::parser::load_graph(graph_path)?;
+    let evaluations =
+        crate::agency::load_all_evaluations_or_warn(&dir.join("agency").join("evaluations"));
+    let config = crate::config::Config::load_or_default(dir).anomaly;
+    detect(&graph, &evaluations, Utc::now(), &config)
+}
+
+/// Detect anomalies, alert on new ones and persist the state. Returns the
+/// anomalies alerted, each with its delivery outcome. With `dry_run`, only
+/// reports the anomalies that would be alerted.
+pub fn run_sweep(
+    dir: &Path,
+    graph_path: &Path,
+    config: Option<&NotifyConfig>,
+    dry_run: bool,
+) -> Result<Vec<(Anomaly, String)>> {
+    let anomalies = detect_in(dir, graph_path)?;
+    let mut state = AnomalyState::load(dir)?;
+    let new = state.observe(&anomalies, Utc::now());
+    if dry_run {
+        return Ok(new
+            .into_iter()
+            .map(|a| (a, "not sent (dry run)".to_string()))
+            .collect());
+    }
+    state.save(dir)?;
+    Ok(new
+        .into_iter()
+        .map(|a| {
+            let outcome = crate::deadline::deliver(dir, config, &a.key(), &a.message());
+            (a, outcome)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Estimate, LogEntry, Node};
+    use crate::test_helpers::make_task_with_status;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn estimated(id: &str, status: Status, hours: f64) -> Task {
+        let mut task = make_task_with_status(id, id, status);
+        task.estimate = Some(Estimate {
+            hours: Some(hours),
+            cost: None,
+        });
+        task
+    }
+
+    fn evaluation(task_id: &str, agent_id: &str, score: f64, timestamp: &str) -> Evaluation {
+        Evaluation {
+            id: format!("eval-{}", task_id),
+            task_id: task_id.to_string(),
+            agent_id: agent_id.to_string(),
+            role_id: String::new(),
+            tradeoff_id: String::new(),
+            score,
+            dimensions: Default::default(),
+            notes: String::new(),
+            evaluator: "test".to_string(),
+            timestamp: timestamp.to_string(),
+            model: None,
+            source: "llm".to_string(),
+            loop_iteration: 0,
+        }
+    }
+
+    fn fail_at(task: &mut Task, timestamp: &str) {
+        task.log.push(LogEntry {
+            timestamp: timestamp.to_string(),
+            actor: None,
+            user: None,
+            message: "Task marked as failed: boom".to_string(),
+        });
+    }
+
+    #[test]
+    fn test_detects_overrun() {
+        let now = at("2025-06-10T12:00:00Z");
+        let mut graph = WorkGraph::new();
+        let mut slow = estimated("slow", Status::InProgress, 1.0);
+        slow.started_at = Some("2025-06-10T06:00:00Z".to_string());
+        graph.add_node(Node::Task(slow));
+        let mut fine = estimated("fine", Status::InProgress, 2.0);
+        fine.started_at = Some("2025-06-10T06:00:00Z".to_string());
+        graph.add_node(Node::Task(fine));
+
+        let found = detect(&graph, &[], now, &AnomalyConfig::default()).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, AnomalyKind::Overrun);
+        assert_eq!(found[0].subject, "slow");
+    }
+
+    #[test]
+    fn test_detects_fast_low_scored_agent() {
+        let now = at("2025-06-10T12:00:00Z");
+        let mut graph = WorkGraph::new();
+        for id in ["a", "b", "c"] {
+            let mut task = estimated(id, Status::Done, 4.0);
+            task.started_at = Some("2025-06-09T10:00:00Z".to_string());
+            task.completed_at = Some("2025-06-09T10:10:00Z".to_string());
+            graph.add_node(Node::Task(task));
+        }
+        let evals = vec![
+            evaluation("a", "agent-x", 0.2, "2025-06-09T11:00:00Z"),
+            evaluation("b", "agent-x", 0.3, "2025-06-09T11:00:00Z"),
+            // Scored well, and another agent: neither counts against agent-x
+            evaluation("c", "agent-x", 0.9, "2025-06-09T11:00:00Z"),
+            evaluation("c", "agent-y", 0.1, "2025-06-09T11:00:00Z"),
+        ];
+
+        let found = detect(&graph, &evals, now, &AnomalyConfig::default()).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, AnomalyKind::FastLowScore);
+        assert_eq!(found[0].subject, "agent-x");
+        assert_eq!(found[0].tasks, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_detects_failure_spike_against_baseline() {
+        let now = at("2025-06-10T12:00:00Z");
+        let mut graph = WorkGraph::new();
+        for (i, when) in [
+            "2025-06-10T01:00:00Z",
+            "2025-06-10T05:00:00Z",
+            "2025-06-10T09:00:00Z",
+        ]
+        .iter()
+        .enumerate()
+        {
+            let mut task =
+                make_task_with_status(&format!("deploy-{}", i), "Deploy", Status::Failed);
+            task.tags = vec!["deploy".to_string()];
+            fail_at(&mut task, when);
+            graph.add_node(Node::Task(task));
+        }
+        // "ci" fails steadily, three times a day: no spike
+        let mut flaky = make_task_with_status("ci", "CI", Status::Failed);
+        flaky.tags = vec!["ci".to_string()];
+        for day in 3..10 {
+            for hour in ["01", "09", "17"] {
+                fail_at(&mut flaky, &format!("2025-06-0{}T{}:00:00Z", day, hour));
+            }
+        }
+        for hour in ["01", "05", "09"] {
+            fail_at(&mut flaky, &format!("2025-06-10T{}:00:00Z", hour));
+        }
+        graph.add_node(Node::Task(flaky));
+
+        let found = detect(&graph, &[], now, &AnomalyConfig::default()).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, AnomalyKind::FailureSpike);
+        assert_eq!(found[0].subject, "deploy");
+        assert_eq!(found[0].tasks.len(), 3);
+    }
+
+    #[test]
+    fn test_state_alerts_once_until_cleared() {
+        let now = at("2025-06-10T12:00:00Z");
+        let anomaly = Anomaly {
+            kind: AnomalyKind::Overrun,
+            subject: "t".to_string(),
+            detail: String::new(),
+            tasks: vec![],
+        };
+        let mut state = AnomalyState::default();
+        assert_eq!(state.observe(std::slice::from_ref(&anomaly), now).len(), 1);
+        assert!(
+            state
+                .observe(std::slice::from_ref(&anomaly), now)
+                .is_empty()
+        );
+        assert!(state.observe(&[], now).is_empty());
+        assert_eq!(state.observe(&[anomaly], now).len(), 1);
+    }
+}
@@ -0,0 +1,278 @@
+//! Cold storage for archived tasks.
+//!
+//! `wg archive` moves done/abandoned tasks out of `graph.jsonl` into monthly
+//! partitions under `.wg/archive/YYYY-MM.jsonl` (by completion date), so the
+//! graph every command loads stays small. Archives written before
+//! partitioning live in `.wg/archive.jsonl`; they are still read, and tasks
+//! restored from them are removed from that file.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::graph::{Node, Task, WorkGraph};
+
+/// Return the partitioned archive directory: `.wg/archive/`
+pub fn archive_dir(workgraph_dir: &Path) -> PathBuf {
+    workgraph_dir.join("archive")
+}
+
+/// Return the pre-partitioning single-file archive: `.wg/archive.jsonl`
+pub fn legacy_path(workgraph_dir: &Path) -> PathBuf {
+    workgraph_dir.join("archive.jsonl")
+}
+
+/// Partition key (`YYYY-MM`) for a task: its completion month, falling back
+/// to its creation month, then the current month.
+pub fn partition_key(task: &Task) -> String {
+    task.completed_at
+        .as_deref()
+        .or(task.created_at.as_deref())
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now)
+        .format("%Y-%m")
+        .to_string()
+}
+
+/// Return the path of the partition file for `key`.
+pub fn partition_path(workgraph_dir: &Path, key: &str) -> PathBuf {
+    archive_dir(workgraph_dir).join(format!("{}.jsonl", key))
+}
+
+/// All archive files, oldest first: the legacy file (if any), then the
+/// monthly partitions in order.
+pub fn files(workgraph_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut out = Vec::new();
+    let legacy = legacy_path(workgraph_dir);
+    if legacy.exists() {
+        out.push(legacy);
+    }
+    let dir = archive_dir(workgraph_dir);
+    if dir.is_dir() {
+        let mut partitions: Vec<PathBuf> = fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|e| e == "jsonl"))
+            .collect();
+        partitions.sort();
+        out.extend(partitions);
+    }
+    Ok(out)
+}
+
+/// Load the tasks stored in one archive file.
+pub fn load_file(path: &Path) -> Result<Vec<Task>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file =
+        File::open(path).with_context(|| format!("Failed to open archive file: {:?}", path))?;
+    let reader = BufReader::new(file);
+    let mut tasks = Vec::new();
+
+    for (line_num, line) in reader.lines().enumerate() {
+        let line = line?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let node: Node = serde_json::from_str(trimmed).with_context(|| {
+            format!("Failed to parse archive line {}: {}", line_num + 1, trimmed)
+        })?;
+        if let Node::Task(task) = node {
+            tasks.push(task);
+        }
+    }
+
+    Ok(tasks)
+}
+
+/// Append tasks to one archive file.
+pub fn append_file(tasks: &[Task], path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open archive file: {:?}", path))?;
+
+    for task in tasks {
+        let node = Node::Task(task.clone());
+        let json = serde_json::to_string(&node)
+            .with_context(|| format!("Failed to serialize task: {}", task.id))?;
+        writeln!(file, "{}", json)?;
+    }
+
+    Ok(())
+}
+
+/// Append tasks to their monthly partitions. Returns the partition keys
+/// written to.
+pub fn append(workgraph_dir: &Path, tasks: &[Task]) -> Result<Vec<String>> {
+    let mut by_month: BTreeMap<String, Vec<Task>> = BTreeMap::new();
+    for task in tasks {
+        by_month
+            .entry(partition_key(task))
+            .or_default()
+            .push(task.clone());
+    }
+    for (key, tasks) in &by_month {
+        append_file(tasks, &partition_path(workgraph_dir, key))?;
+    }
+    Ok(by_month.into_keys().collect())
+}
+
+/// Load every archived task, oldest archive first.
+pub fn load_all(workgraph_dir: &Path) -> Result<Vec<Task>> {
+    let mut tasks = Vec::new();
+    for path in files(workgraph_dir)? {
+        tasks.extend(load_file(&path)?);
+    }
+    Ok(tasks)
+}
+
+/// Find an archived task by ID. If it was archived more than once, the most
+/// recent copy wins.
+pub fn find(workgraph_dir: &Path, id: &str) -> Result<Option<Task>> {
+    for path in files(workgraph_dir)?.iter().rev() {
+        if let Some(task) = load_file(path)?.into_iter().rev().find(|t| t.id == id) {
+            return Ok(Some(task));
+        }
+    }
+    Ok(None)
+}
+
+/// Remove tasks from whichever archive files hold them. Files that end up
+/// empty are deleted. Returns the number of entries removed.
+pub fn remove(workgraph_dir: &Path, ids: &[String]) -> Result<usize> {
+    let ids: HashSet<&str> = ids.iter().map(String::as_str).collect();
+    let mut removed = 0;
+    for path in files(workgraph_dir)? {
+        let tasks = load_file(&path)?;
+        let keep: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| !ids.contains(t.id.as_str()))
+            .collect();
+        if keep.len() == tasks.len() {
+            continue;
+        }
+        removed += tasks.len() - keep.len();
+        if keep.is_empty() && path != legacy_path(workgraph_dir) {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            continue;
+        }
+        let file = File::create(&path)
+            .with_context(|| format!("Failed to open archive file for writing: {:?}", path))?;
+        let mut writer = BufWriter::new(file);
+        for task in keep {
+            let json = serde_json::to_string(&Node::Task(task.clone()))
+                .with_context(|| format!("Failed to serialize task: {}", task.id))?;
+            writeln!(writer, "{}", json)?;
+        }
+        writer.flush()?;
+    }
+    Ok(removed)
+}
+
+/// Add archived tasks that are not in `graph` to it, for commands that need
+/// to see completed history. Returns how many were added.
+pub fn include_archived(graph: &mut WorkGraph, workgraph_dir: &Path) -> Result<usize> {
+    let mut added = 0;
+    // Newest copies first so a re-archived task resolves to its latest state.
+    for task in load_all(workgraph_dir)?.into_iter().rev() {
+        if graph.get_node(&task.id).is_none() {
+            graph.add_node(Node::Task(task));
+            added += 1;
+        }
+    }
+    Ok(added)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Status;
+    use tempfile::TempDir;
+
+    fn done(id: &str, completed_at: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            title: id.to_uppercase(),
+            status: Status::Done,
+            completed_at: Some(completed_at.to_string()),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn test_append_partitions_by_completion_month() {
+        let tmp = TempDir::new().unwrap();
+        let keys = append(
+            tmp.path(),
+            &[
+                done("a", "2025-01-15T10:00:00Z"),
+                done("b", "2025-03-01T00:00:00Z"),
+                done("c", "2025-01-31T23:00:00Z"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(keys, vec!["2025-01", "2025-03"]);
+        assert_eq!(
+            load_file(&partition_path(tmp.path(), "2025-01"))
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(load_all(tmp.path()).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_find_and_remove_span_legacy_and_partitions() {
+        let tmp = TempDir::new().unwrap();
+        append_file(
+            &[done("old", "2024-06-01T00:00:00Z")],
+            &legacy_path(tmp.path()),
+        )
+        .unwrap();
+        append(tmp.path(), &[done("new", "2025-02-01T00:00:00Z")]).unwrap();
+
+        assert_eq!(find(tmp.path(), "old").unwrap().unwrap().title, "OLD");
+        assert!(find(tmp.path(), "missing").unwrap().is_none());
+
+        let removed = remove(tmp.path(), &["old".to_string(), "new".to_string()]).unwrap();
+        assert_eq!(removed, 2);
+        assert!(load_all(tmp.path()).unwrap().is_empty());
+        // Emptied partitions are deleted; the legacy file is kept.
+        assert!(!partition_path(tmp.path(), "2025-02").exists());
+        assert!(legacy_path(tmp.path()).exists());
+    }
+
+    #[test]
+    fn test_include_archived_skips_live_tasks() {
+        let tmp = TempDir::new().unwrap();
+        append(
+            tmp.path(),
+            &[
+                done("a", "2025-01-01T00:00:00Z"),
+                done("b", "2025-01-01T00:00:00Z"),
+            ],
+        )
+        .unwrap();
+        let mut graph = WorkGraph::new();
+        let mut live = done("a", "2025-01-01T00:00:00Z");
+        live.title = "live".to_string();
+        graph.add_node(Node::Task(live));
+
+        assert_eq!(include_archived(&mut graph, tmp.path()).unwrap(), 1);
+        assert_eq!(graph.get_task("a").unwrap().title, "live");
+        assert!(graph.get_task("b").is_some());
+    }
+}
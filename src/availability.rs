@@ -0,0 +1,314 @@
+//! Availability calendar: vacations for human participants and scheduled
+//! downtime for machine agents.
+//!
+//! Windows are stored in `.wg/availability.yaml`. A window names a
+//! participant (`who`) — an agent hash or hash prefix, an agent name, or the
+//! free-form actor name used in a task's `assigned` field — and the period
+//! during which they are unavailable. Assignment, forecasting, and escalation
+//! consult the calendar so work is not routed to someone who is away.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::agency::Agent;
+
+const AVAILABILITY_FILE: &str = "availability.yaml";
+
+/// A period during which a participant is unavailable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AvailabilityWindow {
+    pub id: u32,
+    pub who: String,
+    /// Start of the window (RFC 3339).
+    pub from: String,
+    /// End of the window, exclusive (RFC 3339).
+    pub until: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl AvailabilityWindow {
+    fn bounds(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let from = self.from.parse::<DateTime<Utc>>().ok()?;
+        let until = self.until.parse::<DateTime<Utc>>().ok()?;
+        Some((from, until))
+    }
+
+    /// Whether the window covers the given instant.
+    pub fn covers(&self, at: DateTime<Utc>) -> bool {
+        self.bounds()
+            .is_some_and(|(from, until)| from <= at && at < until)
+    }
+
+    /// Whether the window has already ended.
+    pub fn is_past(&self, at: DateTime<Utc>) -> bool {
+        self.bounds().is_some_and(|(_, until)| until <= at)
+    }
+}
+
+/// Top-level availability.yaml structure.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AvailabilityCalendar {
+    #[serde(default)]
+    pub windows: Vec<AvailabilityWindow>,
+}
+
+impl AvailabilityCalendar {
+    /// Load from `.wg/availability.yaml`. Returns an empty calendar if the
+    /// file doesn't exist.
+    pub fn load(workgraph_dir: &Path) -> Result<Self> {
+        let path = workgraph_dir.join(AVAILABILITY_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        serde_yaml::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// Load, treating an unreadable calendar as empty (with a warning).
+    pub fn load_or_warn(workgraph_dir: &Path) -> Self {
+        Self::load(workgraph_dir).unwrap_or_else(|e| {
+            eprintln!("Warning: {:#}", e);
+            Self::default()
+        })
+    }
+
+    /// Save to `.wg/availability.yaml`.
+    pub fn save(&self, workgraph_dir: &Path) -> Result<()> {
+        let path = workgraph_dir.join(AVAILABILITY_FILE);
+        let content = serde_yaml::to_string(self)?;
+        std::fs::write(&path, content).with_context(|| format!("writing {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Add a window and return its id.
+    pub fn add(
+        &mut self,
+        who: &str,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+        reason: Option<&str>,
+    ) -> u32 {
+        let id = self.windows.iter().map(|w| w.id).max().unwrap_or(0) + 1;
+        self.windows.push(AvailabilityWindow {
+            id,
+            who: who.to_string(),
+            from: from.to_rfc3339(),
+            until: until.to_rfc3339(),
+            reason: reason.map(String::from),
+        });
+        id
+    }
+
+    /// Remove a window by id. Returns whether it existed.
+    pub fn remove(&mut self, id: u32) -> bool {
+        let before = self.windows.len();
+        self.windows.retain(|w| w.id != id);
+        self.windows.len() != before
+    }
+
+    /// If any of `names` is unavailable at `at`, when they are back.
+    ///
+    /// A window matches a name exactly, or as a prefix of it when the name
+    /// is an agent hash. Overlapping windows are chained, so the result is
+    /// the end of the whole unavailable stretch.
+    pub fn unavailable_until(&self, names: &[&str], at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mine: Vec<(DateTime<Utc>, DateTime<Utc>)> = self
+            .windows
+            .iter()
+            .filter(|w| names.iter().any(|n| window_matches(&w.who, n)))
+            .filter_map(AvailabilityWindow::bounds)
+            .collect();
+
+        let mut back: Option<DateTime<Utc>> = None;
+        let mut cursor = at;
+        while let Some(end) = mine
+            .iter()
+            .filter(|(from, until)| *from <= cursor && cursor < *until)
+            .map(|(_, until)| *until)
+            .max()
+        {
+            back = Some(end);
+            cursor = end;
+        }
+        back
+    }
+
+    /// Fraction of `[start, end)` during which any of `names` is unavailable.
+    pub fn unavailable_fraction(
+        &self,
+        names: &[&str],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> f64 {
+        let total = (end - start).num_seconds();
+        if total <= 0 {
+            return 0.0;
+        }
+        let mut spans: Vec<(DateTime<Utc>, DateTime<Utc>)> = self
+            .windows
+            .iter()
+            .filter(|w| names.iter().any(|n| window_matches(&w.who, n)))
+            .filter_map(AvailabilityWindow::bounds)
+            .map(|(from, until)| (from.max(start), until.min(end)))
+            .filter(|(from, until)| from < until)
+            .collect();
+        spans.sort();
+
+        let mut covered = 0i64;
+        let mut cursor = start;
+        for (from, until) in spans {
+            let from = from.max(cursor);
+            if from < until {
+                covered += (until - from).num_seconds();
+                cursor = until;
+            }
+        }
+        covered as f64 / total as f64
+    }
+
+    /// Whether a free-form participant name (e.g. a task's `assigned`) is
+    /// available at `at`.
+    pub fn is_available(&self, who: &str, at: DateTime<Utc>) -> bool {
+        self.unavailable_until(&[who], at).is_none()
+    }
+
+    /// If an agency agent is unavailable at `at` (matched by hash or name),
+    /// when it is back.
+    pub fn agent_unavailable_until(
+        &self,
+        agent: &Agent,
+        at: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        self.unavailable_until(&[agent.id.as_str(), agent.name.as_str()], at)
+    }
+}
+
+fn window_matches(who: &str, name: &str) -> bool {
+    who == name || (is_hex(name) && name.len() >= 8 && who.len() >= 4 && name.starts_with(who))
+}
+
+/// Agent IDs are hex content hashes; only those are prefix-matched.
+fn is_hex(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Parse a window boundary: RFC 3339, or a bare `YYYY-MM-DD` date.
+///
+/// A bare date means the start of that day (UTC); with `end_of_day` it
+/// means the start of the following day, so `--until 2026-03-14` keeps the
+/// 14th inside the window.
+pub fn parse_boundary(s: &str, end_of_day: bool) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(s, "%Y-%m-%d").with_context(|| {
+        format!(
+            "Invalid date '{}': expected YYYY-MM-DD or an RFC 3339 timestamp",
+            s
+        )
+    })?;
+    let date = if end_of_day {
+        date.succ_opt().context("date out of range")?
+    } else {
+        date
+    };
+    Ok(date.and_hms_opt(0, 0, 0).context("invalid time")?.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        parse_boundary(s, false).unwrap()
+    }
+
+    #[test]
+    fn test_parse_boundary_date_until_is_inclusive() {
+        assert_eq!(
+            parse_boundary("2026-03-14", true).unwrap(),
+            at("2026-03-15")
+        );
+        assert_eq!(
+            parse_boundary("2026-03-14T09:00:00Z", true).unwrap(),
+            at("2026-03-14") + Duration::hours(9)
+        );
+        assert!(parse_boundary("next tuesday", false).is_err());
+    }
+
+    #[test]
+    fn test_unavailable_until_chains_overlapping_windows() {
+        let mut cal = AvailabilityCalendar::default();
+        cal.add(
+            "alice",
+            at("2026-03-01"),
+            at("2026-03-08"),
+            Some("vacation"),
+        );
+        cal.add("alice", at("2026-03-07"), at("2026-03-10"), None);
+        cal.add("bob", at("2026-03-01"), at("2026-03-02"), None);
+
+        assert_eq!(
+            cal.unavailable_until(&["alice"], at("2026-03-03")),
+            Some(at("2026-03-10"))
+        );
+        assert!(cal.is_available("alice", at("2026-03-10")));
+        assert!(cal.is_available("carol", at("2026-03-03")));
+        assert!(!cal.is_available("bob", at("2026-03-01")));
+    }
+
+    #[test]
+    fn test_unavailable_fraction_merges_overlaps() {
+        let mut cal = AvailabilityCalendar::default();
+        cal.add("alice", at("2026-03-01"), at("2026-03-04"), None);
+        cal.add("alice", at("2026-03-03"), at("2026-03-05"), None);
+        cal.add("alice", at("2026-03-20"), at("2026-03-30"), None);
+
+        let f = cal.unavailable_fraction(&["alice"], at("2026-03-02"), at("2026-03-12"));
+        assert!((f - 0.3).abs() < 1e-9);
+        assert_eq!(
+            cal.unavailable_fraction(&["bob"], at("2026-03-02"), at("2026-03-12")),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_window_matches_agent_hash_prefix() {
+        let mut cal = AvailabilityCalendar::default();
+        cal.add("abcd1234", at("2026-03-01"), at("2026-03-02"), None);
+        let hash = "abcd1234ef567890";
+        assert!(!cal.is_available(hash, at("2026-03-01")));
+        // Short names never prefix-match
+        assert!(cal.is_available("abcd", at("2026-03-01")));
+
+        // Names match exactly, however long
+        cal.add("alexande", at("2026-03-01"), at("2026-03-02"), None);
+        assert!(!cal.is_available("alexande", at("2026-03-01")));
+        assert!(cal.is_available("alexander", at("2026-03-01")));
+        assert!(cal.is_available("alexandra", at("2026-03-01")));
+    }
+
+    #[test]
+    fn test_save_load_roundtrip_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cal = AvailabilityCalendar::default();
+        let id = cal.add("alice", at("2026-03-01"), at("2026-03-02"), None);
+        cal.add("bob", at("2026-03-01"), at("2026-03-02"), None);
+        cal.save(dir.path()).unwrap();
+
+        let mut loaded = AvailabilityCalendar::load(dir.path()).unwrap();
+        assert_eq!(loaded.windows, cal.windows);
+        assert!(loaded.remove(id));
+        assert!(!loaded.remove(id));
+        assert_eq!(loaded.windows.len(), 1);
+        assert_eq!(
+            loaded.add("carol", at("2026-03-01"), at("2026-03-02"), None),
+            3
+        );
+    }
+}
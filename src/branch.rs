@@ -0,0 +1,356 @@
+//! Scenario branches of the planning graph for `wg branch`.
+//!
+//! A branch is a full copy of the live graph stored under
+//! `.wg/branches/<name>/`. Each branch directory is itself a usable
+//! workgraph directory (`graph.jsonl` plus `config.toml`), so it can be
+//! edited independently with the normal commands via `wg --dir`. The graph
+//! as it was when the branch was taken is kept in `base.jsonl`, which lets
+//! a merge detect whether the live graph has moved on in the meantime.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::graph::WorkGraph;
+use crate::parser::{load_graph, modify_graph};
+use crate::snapshot::{self, SnapshotMeta, validate_name};
+
+/// Name that refers to the live graph rather than a branch.
+pub const LIVE: &str = "main";
+
+const META_FILE: &str = "branch.json";
+const BASE_FILE: &str = "base.jsonl";
+
+/// Metadata for a single branch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchMeta {
+    pub name: String,
+    /// ISO 8601 timestamp when the branch was created
+    pub created: String,
+    /// Branch this one was taken from (`main` for the live graph)
+    pub from: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Directory where all branches live.
+pub fn branches_dir(workgraph_dir: &Path) -> PathBuf {
+    workgraph_dir.join("branches")
+}
+
+/// Workgraph directory for a branch; `main` resolves to the live directory.
+pub fn branch_dir(workgraph_dir: &Path, name: &str) -> PathBuf {
+    if name == LIVE {
+        workgraph_dir.to_path_buf()
+    } else {
+        branches_dir(workgraph_dir).join(name)
+    }
+}
+
+fn require_branch(workgraph_dir: &Path, name: &str) -> Result<PathBuf> {
+    if name != LIVE {
+        validate_name("branch", name)?;
+    }
+    let dir = branch_dir(workgraph_dir, name);
+    if name != LIVE && !dir.join(META_FILE).exists() {
+        anyhow::bail!("Branch '{}' not found", name);
+    }
+    Ok(dir)
+}
+
+/// Create a branch from the live graph or from another branch.
+pub fn create(
+    workgraph_dir: &Path,
+    name: &str,
+    from: Option<&str>,
+    message: Option<&str>,
+) -> Result<BranchMeta> {
+    validate_name("branch", name)?;
+    if name == LIVE {
+        anyhow::bail!(
+            "'{}' refers to the live graph and cannot be a branch name",
+            LIVE
+        );
+    }
+    let dest = branch_dir(workgraph_dir, name);
+    if dest.exists() {
+        anyhow::bail!("Branch '{}' already exists", name);
+    }
+    let from = from.unwrap_or(LIVE);
+    let src = require_branch(workgraph_dir, from)?;
+    let graph_src = src.join("graph.jsonl");
+    if !graph_src.exists() {
+        anyhow::bail!("No graph found at {}", graph_src.display());
+    }
+
+    fs::create_dir_all(&dest).context("Failed to create branch directory")?;
+    fs::copy(&graph_src, dest.join("graph.jsonl")).context("Failed to copy graph to branch")?;
+    // The base is always the live graph: that is what a merge lands on.
+    fs::copy(workgraph_dir.join("graph.jsonl"), dest.join(BASE_FILE))
+        .context("Failed to record branch base")?;
+    let config_src = workgraph_dir.join("config.toml");
+    if config_src.exists() {
+        fs::copy(&config_src, dest.join("config.toml"))
+            .context("Failed to copy config.toml to branch")?;
+    }
+
+    let meta = BranchMeta {
+        name: name.to_string(),
+        created: Utc::now().to_rfc3339(),
+        from: from.to_string(),
+        message: message.map(String::from),
+    };
+    fs::write(
+        dest.join(META_FILE),
+        serde_json::to_string_pretty(&meta).context("Failed to serialize branch metadata")?,
+    )
+    .context("Failed to write branch metadata")?;
+    Ok(meta)
+}
+
+/// Load metadata for a branch.
+pub fn load_meta(workgraph_dir: &Path, name: &str) -> Result<BranchMeta> {
+    let dir = require_branch(workgraph_dir, name)?;
+    let content =
+        fs::read_to_string(dir.join(META_FILE)).context("Failed to read branch metadata")?;
+    serde_json::from_str(&content).context("Failed to parse branch metadata")
+}
+
+/// List all branches, oldest first.
+pub fn list(workgraph_dir: &Path) -> Result<Vec<BranchMeta>> {
+    let dir = branches_dir(workgraph_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut metas = Vec::new();
+    for entry in fs::read_dir(&dir).context("Failed to read branches directory")? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            match load_meta(workgraph_dir, &name) {
+                Ok(meta) => metas.push(meta),
+                Err(e) => eprintln!("Warning: skipping branch '{}': {}", name, e),
+            }
+        }
+    }
+    metas.sort_by(|a, b| a.created.cmp(&b.created).then(a.name.cmp(&b.name)));
+    Ok(metas)
+}
+
+/// Load the graph of a branch (`main` loads the live graph).
+pub fn load_branch_graph(workgraph_dir: &Path, name: &str) -> Result<WorkGraph> {
+    let dir = require_branch(workgraph_dir, name)?;
+    load_graph(dir.join("graph.jsonl"))
+        .with_context(|| format!("Failed to load graph of branch '{}'", name))
+}
+
+/// Task IDs whose content differs between two graphs (added, removed, or
+/// changed).
+pub fn changed_task_ids(a: &WorkGraph, b: &WorkGraph) -> BTreeSet<String> {
+    let ids: BTreeSet<&str> = a.tasks().chain(b.tasks()).map(|t| t.id.as_str()).collect();
+    ids.into_iter()
+        .filter(|id| {
+            let ta = a.get_task(id).and_then(|t| serde_json::to_value(t).ok());
+            let tb = b.get_task(id).and_then(|t| serde_json::to_value(t).ok());
+            ta != tb
+        })
+        .map(String::from)
+        .collect()
+}
+
+/// Outcome of merging a branch into the live graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeOutcome {
+    pub branch: String,
+    /// Tasks that differ between the live graph and the branch
+    pub changed_tasks: Vec<String>,
+    /// Live tasks that had changed since the branch was taken and were
+    /// overwritten (only non-empty with `force`)
+    pub overwritten_live_changes: Vec<String>,
+    /// Snapshot of the live graph taken before merging
+    pub safety_snapshot: SnapshotMeta,
+}
+
+/// Merge a branch back by making it the live graph.
+///
+/// Refuses if the live graph has changed since the branch was taken,
+/// unless `force` is set. A safety snapshot of the live state is taken
+/// first, so a merge can be rolled back with `wg snapshot restore`.
+pub fn merge(workgraph_dir: &Path, name: &str, force: bool) -> Result<MergeOutcome> {
+    if name == LIVE {
+        anyhow::bail!("Cannot merge '{}' into itself", LIVE);
+    }
+    let dir = require_branch(workgraph_dir, name)?;
+    let branch_graph = load_branch_graph(workgraph_dir, name)?;
+    let base = load_graph(dir.join(BASE_FILE)).context("Failed to load branch base")?;
+    let live = load_graph(workgraph_dir.join("graph.jsonl")).context("Failed to load graph")?;
+
+    let drifted: Vec<String> = changed_task_ids(&base, &live).into_iter().collect();
+    if !drifted.is_empty() && !force {
+        let shown: Vec<&str> = drifted.iter().take(10).map(String::as_str).collect();
+        anyhow::bail!(
+            "The live graph has changed since branch '{}' was taken ({} task(s): {}{}). \
+             Re-run with --force to overwrite those changes",
+            name,
+            drifted.len(),
+            shown.join(", "),
+            if drifted.len() > shown.len() {
+                ", …"
+            } else {
+                ""
+            }
+        );
+    }
+    let changed: Vec<String> = changed_task_ids(&live, &branch_graph).into_iter().collect();
+
+    let safety = snapshot::create(
+        workgraph_dir,
+        None,
+        Some(&format!(
+            "pre-merge safety snapshot (merging branch {})",
+            name
+        )),
+        false,
+        false,
+    )?;
+
+    modify_graph(workgraph_dir.join("graph.jsonl"), |graph| {
+        *graph = branch_graph;
+        true
+    })
+    .context("Failed to merge branch into the live graph")?;
+    // The branch now matches live, so rebase it for any further merges.
+    fs::copy(dir.join("graph.jsonl"), dir.join(BASE_FILE))
+        .context("Failed to update branch base")?;
+
+    Ok(MergeOutcome {
+        branch: name.to_string(),
+        changed_tasks: changed,
+        overwritten_live_changes: if force { drifted } else { Vec::new() },
+        safety_snapshot: safety,
+    })
+}
+
+/// Delete a branch.
+pub fn delete(workgraph_dir: &Path, name: &str) -> Result<()> {
+    if name == LIVE {
+        anyhow::bail!("Cannot delete the live graph");
+    }
+    let dir = require_branch(workgraph_dir, name)?;
+    fs::remove_dir_all(&dir).with_context(|| format!("Failed to delete branch '{}'", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Node, Task};
+    use crate::parser::save_graph;
+    use tempfile::TempDir;
+
+    fn setup_wg(tmp: &TempDir) -> PathBuf {
+        let dir = tmp.path().join(".wg");
+        fs::create_dir_all(&dir).unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(Task {
+            id: "t1".to_string(),
+            title: "Design".to_string(),
+            ..Task::default()
+        }));
+        save_graph(&graph, dir.join("graph.jsonl")).unwrap();
+        dir
+    }
+
+    fn add_task(dir: &Path, id: &str) {
+        modify_graph(dir.join("graph.jsonl"), |g| {
+            g.add_node(Node::Task(Task {
+                id: id.to_string(),
+                title: id.to_string(),
+                ..Task::default()
+            }));
+            true
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_create_edit_and_merge_branch() {
+        let tmp = TempDir::new().unwrap();
+        let dir = setup_wg(&tmp);
+
+        create(&dir, "plan-b", None, Some("split design")).unwrap();
+        assert!(create(&dir, "plan-b", None, None).is_err());
+        assert!(create(&dir, LIVE, None, None).is_err());
+
+        // Edits on the branch don't touch the live graph
+        add_task(&branch_dir(&dir, "plan-b"), "t2");
+        assert!(
+            load_branch_graph(&dir, LIVE)
+                .unwrap()
+                .get_task("t2")
+                .is_none()
+        );
+
+        let outcome = merge(&dir, "plan-b", false).unwrap();
+        assert_eq!(outcome.changed_tasks, vec!["t2".to_string()]);
+        assert!(
+            load_branch_graph(&dir, LIVE)
+                .unwrap()
+                .get_task("t2")
+                .is_some()
+        );
+        assert!(snapshot::load_meta(&dir, &outcome.safety_snapshot.name).is_ok());
+    }
+
+    #[test]
+    fn test_merge_refuses_when_live_drifted() {
+        let tmp = TempDir::new().unwrap();
+        let dir = setup_wg(&tmp);
+        create(&dir, "plan-b", None, None).unwrap();
+        add_task(&dir, "live-only");
+
+        let err = merge(&dir, "plan-b", false).unwrap_err();
+        assert!(err.to_string().contains("live-only"));
+
+        let outcome = merge(&dir, "plan-b", true).unwrap();
+        assert_eq!(
+            outcome.overwritten_live_changes,
+            vec!["live-only".to_string()]
+        );
+        assert!(
+            load_branch_graph(&dir, LIVE)
+                .unwrap()
+                .get_task("live-only")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_branch_from_branch_and_delete() {
+        let tmp = TempDir::new().unwrap();
+        let dir = setup_wg(&tmp);
+        create(&dir, "a", None, None).unwrap();
+        add_task(&branch_dir(&dir, "a"), "from-a");
+        let meta = create(&dir, "b", Some("a"), None).unwrap();
+        assert_eq!(meta.from, "a");
+        assert!(
+            load_branch_graph(&dir, "b")
+                .unwrap()
+                .get_task("from-a")
+                .is_some()
+        );
+        assert!(create(&dir, "c", Some("missing"), None).is_err());
+
+        delete(&dir, "a").unwrap();
+        let names: Vec<String> = list(&dir).unwrap().into_iter().map(|m| m.name).collect();
+        assert_eq!(names, vec!["b".to_string()]);
+        assert!(delete(&dir, LIVE).is_err());
+
+        // Names can't reach outside the branches directory
+        let err = delete(&dir, "../branches/b").unwrap_err();
+        assert!(err.to_string().contains("Invalid branch name"));
+        assert!(branch_dir(&dir, "b").exists());
+    }
+}
@@ -0,0 +1,174 @@
+//! Repository change metrics for tasks.
+//!
+//! When a task is claimed (by `wg claim` or a spawned agent) the commit
+//! checked out in its working directory is recorded as `claim_sha`. `wg done`
+//! diffs the working tree against it and stores the files touched and lines
+//! added and removed as the task's `changes`, so reviews and retros can see
+//! what a task actually changed rather than what it reported. The diff
+//! covers committed and uncommitted edits to tracked files plus new files
+//! git doesn't ignore, counted as all insertions; outside a worktree it also
+//! picks up anything else changed in the checkout since the claim.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::graph::ChangeSummary;
+
+/// The commit checked out in `dir`, or `None` outside a git repository.
+pub fn head_sha(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!sha.is_empty()).then_some(sha)
+}
+
+/// Changes in `dir`'s working tree since `base`. `None` when git can't
+/// produce the diff (not a repository, or `base` is unknown there).
+pub fn diff_since(dir: &Path, base: &str) -> Option<ChangeSummary> {
+    let output = Command::new("git")
+        .args(["diff", "--numstat", "--no-renames", base, "--"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut summary = parse_numstat(base, &String::from_utf8_lossy(&output.stdout));
+    add_untracked(dir, &mut summary);
+    Some(summary)
+}
+
+/// Add files git doesn't track or ignore to `summary`, every line an
+/// insertion. Best effort: a failure just leaves them out.
+fn add_untracked(dir: &Path, summary: &mut ChangeSummary) {
+    let git = |root: &Path, args: &[&str]| -> Option<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    // Paths relative to the top level, as `git diff` reports them.
+    let Some(top) = git(dir, &["rev-parse", "--show-toplevel"]) else {
+        return;
+    };
+    let top = Path::new(top.trim());
+    let Some(untracked) = git(top, &["ls-files", "--others", "--exclude-standard", "-z"]) else {
+        return;
+    };
+    for path in untracked.split('\0').filter(|p| !p.is_empty()) {
+        summary.insertions += std::fs::read(top.join(path)).map_or(0, |b| count_lines(&b));
+        summary.files.push(path.to_string());
+    }
+}
+
+/// Lines in a new file as `git diff --numstat` counts them; binary files
+/// (containing a NUL byte) have none.
+fn count_lines(content: &[u8]) -> u64 {
+    if content.contains(&0) {
+        return 0;
+    }
+    let newlines = content.iter().filter(|&&b| b == b'\n').count() as u64;
+    newlines + u64::from(content.last().is_some_and(|&b| b != b'\n'))
+}
+
+/// Parse `git diff --numstat` output. Binary files (`-` counts) are listed
+/// without lines.
+pub fn parse_numstat(base: &str, numstat: &str) -> ChangeSummary {
+    let mut summary = ChangeSummary {
+        base: base.to_string(),
+        ..Default::default()
+    };
+    for line in numstat.lines() {
+        let mut fields = line.splitn(3, '\t');
+        let (Some(added), Some(removed), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        summary.insertions += added.parse::<u64>().unwrap_or(0);
+        summary.deletions += removed.parse::<u64>().unwrap_or(0);
+        summary.files.push(path.to_string());
+    }
+    summary
+}
+
+/// One-line description, e.g. `3 files, +120 -14`.
+pub fn describe(changes: &ChangeSummary) -> String {
+    format!(
+        "{} file{}, +{} -{}",
+        changes.files.len(),
+        if changes.files.len() == 1 { "" } else { "s" },
+        changes.insertions,
+        changes.deletions
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_parse_numstat() {
+        let summary = parse_numstat(
+            "abc123",
+            "10\t2\tsrc/lib.rs\n-\t-\tassets/logo.png\n3\t0\tdocs/a b.md\n",
+        );
+        assert_eq!(summary.base, "abc123");
+        assert_eq!(
+            summary.files,
+            ["src/lib.rs", "assets/logo.png", "docs/a b.md"]
+        );
+        assert_eq!((summary.insertions, summary.deletions), (13, 2));
+        assert_eq!(describe(&summary), "3 files, +13 -2");
+    }
+
+    #[test]
+    fn test_diff_since_claim() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path();
+        git(dir, &["init", "-q"]);
+        git(dir, &["config", "user.email", "t@example.com"]);
+        git(dir, &["config", "user.name", "t"]);
+        std::fs::write(dir.join("a.txt"), "one\ntwo\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-qm", "init"]);
+        let base = head_sha(dir).unwrap();
+
+        // One committed edit and one still in the working tree.
+        std::fs::write(dir.join("a.txt"), "one\n2\nthree\n").unwrap();
+        git(dir, &["commit", "-qam", "edit"]);
+        std::fs::write(dir.join("b.txt"), "new\n").unwrap();
+        git(dir, &["add", "b.txt"]);
+        // New files count whether or not they were added; ignored ones don't.
+        std::fs::create_dir(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("sub/c.txt"), "x\ny").unwrap();
+        std::fs::write(dir.join(".gitignore"), "*.log\n").unwrap();
+        std::fs::write(dir.join("build.log"), "noise\n").unwrap();
+
+        let changes = diff_since(&dir.join("sub"), &base).unwrap();
+        assert_eq!(changes.files, ["a.txt", "b.txt", ".gitignore", "sub/c.txt"]);
+        assert_eq!((changes.insertions, changes.deletions), (6, 1));
+        assert!(diff_since(dir, "0000000").is_none());
+    }
+}
@@ -12,10 +12,20 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub dir: Option<PathBuf>,
 
+    /// Named graph to operate on (default: main; also $WG_GRAPH).
+    /// Goes before the subcommand: `wg --graph <name> <command>`. See `wg graphs`
+    #[arg(long)]
+    pub graph: Option<String>,
+
     /// Output as JSON for machine consumption
     #[arg(long, global = true)]
     pub json: bool,
 
+    /// Reject unknown fields in graph.jsonl and function YAML (catches typos
+    /// like `skils`). Also `project.strict_fields` or $WG_STRICT=1
+    #[arg(long, global = true)]
+    pub strict: bool,
+
     /// Show help (use --help-all for full command list)
     #[arg(long, short = 'h', global = true)]
     pub help: bool,
@@ -214,6 +224,16 @@ pub enum Commands {
         #[arg(long = "after", alias = "blocked-by", value_delimiter = ',', num_args = 1..)]
         after: Vec<String>,
 
+        /// Wait this long after a dependency completes: <dep>=<duration>
+        /// (e.g. deploy=2d). Repeatable
+        #[arg(long)]
+        lag: Vec<String>,
+
+        /// Extra hours a dependency edge adds to forecasts and the critical
+        /// path: <dep>=<hours>. Repeatable
+        #[arg(long = "edge-weight")]
+        edge_weight: Vec<String>,
+
         /// Assign to an actor
         #[arg(long)]
         assign: Option<String>,
@@ -226,6 +246,16 @@ pub enum Commands {
         #[arg(long)]
         cost: Option<f64>,
 
+        /// Abort a run that spends more than this (USD). Defaults to the
+        /// strictest `[cost_caps]` entry matching one of the task's tags
+        #[arg(long = "max-cost", value_name = "USD")]
+        max_cost: Option<f64>,
+
+        /// Context window (tokens) an agent needs for this task; agents whose
+        /// capability document declares a smaller `max_context` are skipped
+        #[arg(long = "min-context", value_name = "TOKENS")]
+        min_context: Option<u64>,
+
         /// Tags
         #[arg(long, short)]
         tag: Vec<String>,
@@ -242,6 +272,18 @@ pub enum Commands {
         #[arg(long)]
         deliverable: Vec<String>,
 
+        /// Input contract on upstream output, checked when the upstream completes:
+        /// <kind>:<glob>[@<upstream-id>] where kind is artifact, file, or json
+        /// (e.g. --expects "artifact:schemas/openapi.yaml@design-api"); write a
+        /// literal @ in the glob as [@]
+        #[arg(long)]
+        expects: Vec<String>,
+
+        /// Resource pools this task holds a slot in while running (e.g.
+        /// --requires gpu,staging-db); capacities come from `[pools]` in config
+        #[arg(long, value_delimiter = ',')]
+        requires: Vec<String>,
+
         /// Maximum number of retries allowed for this task
         #[arg(long)]
         max_retries: Option<u32>,
@@ -348,6 +390,17 @@ pub enum Commands {
         #[arg(long = "not-before")]
         not_before: Option<String>,
 
+        /// Deadline: ISO 8601 timestamp, date (end of day, UTC), or duration
+        /// from now (e.g. 3d). Overdue tasks are listed by `wg overdue` and
+        /// alerted on by the service
+        #[arg(long)]
+        due: Option<String>,
+
+        /// Not ready until an external event fires: webhook:<name>, released
+        /// by `POST /hooks/<name>` to the service or `wg event fire <name>`
+        #[arg(long = "waiting-on")]
+        waiting_on: Vec<String>,
+
         /// Allow phantom (forward-reference) dependencies without error
         #[arg(long = "allow-phantom")]
         allow_phantom: bool,
@@ -372,8 +425,9 @@ pub enum Commands {
         #[arg(long, short = 'p')]
         priority: Option<String>,
 
-        /// Cron schedule expression (6-field format: "sec min hour day month dow")
-        #[arg(long)]
+        /// Recurrence: cron expression (5- or 6-field, "sec min hour day month dow")
+        /// or iCalendar RRULE (e.g. "FREQ=WEEKLY;BYDAY=MO;BYHOUR=9")
+        #[arg(long, alias = "recurrence")]
         cron: Option<String>,
 
         /// Create as a blocking subtask: child is created, parent waits for child to complete
@@ -407,6 +461,14 @@ pub enum Commands {
         )]
         remove_after: Vec<String>,
 
+        /// Set the lag on a dependency edge: <dep>=<duration>; 0s clears
+        #[arg(long)]
+        lag: Vec<String>,
+
+        /// Set the weight (hours) on a dependency edge: <dep>=<hours>; 0 clears
+        #[arg(long = "edge-weight")]
+        edge_weight: Vec<String>,
+
         /// Add a tag
         #[arg(long = "add-tag")]
         add_tag: Vec<String>,
@@ -415,6 +477,14 @@ pub enum Commands {
         #[arg(long = "remove-tag")]
         remove_tag: Vec<String>,
 
+        /// Hold the task until an external event fires (e.g. webhook:ci-green)
+        #[arg(long = "add-waiting-on")]
+        add_waiting_on: Vec<String>,
+
+        /// Drop an external event gate without waiting for the event
+        #[arg(long = "remove-waiting-on")]
+        remove_waiting_on: Vec<String>,
+
         /// Update preferred model
         #[arg(long)]
         model: Option<String>,
@@ -475,13 +545,40 @@ pub enum Commands {
         #[arg(long = "not-before")]
         not_before: Option<String>,
 
+        /// Set deadline (ISO 8601 timestamp, date, or duration from now);
+        /// empty string "" clears
+        #[arg(long)]
+        due: Option<String>,
+
+        /// Mark the task blocked on a team or vendor outside the project
+        /// (free text); empty string "" clears. Listed by `wg waiting`
+        #[arg(long = "external-blocker")]
+        external_blocker: Option<String>,
+
+        /// Who to follow up with about the external blocker; "" clears
+        #[arg(long = "blocker-contact")]
+        blocker_contact: Option<String>,
+
+        /// When the external blocker is expected to clear (ISO 8601
+        /// timestamp, date, or duration from now); "" clears
+        #[arg(long = "blocker-expected")]
+        blocker_expected: Option<String>,
+
+        /// Abort runs that spend more than this (USD); 0 clears
+        #[arg(long = "max-cost", value_name = "USD")]
+        max_cost: Option<f64>,
+
+        /// Context window (tokens) an agent needs for this task; 0 clears
+        #[arg(long = "min-context", value_name = "TOKENS")]
+        min_context: Option<u64>,
+
         /// [DEPRECATED] Put validation criteria in a `## Validation` section of the
         /// task description; the agency evaluator scores against it.
         #[arg(long, hide = true)]
         verify: Option<String>,
 
-        /// Set or clear cron schedule (empty string "" clears; 6-field: "sec min hour day month dow")
-        #[arg(long)]
+        /// Set or clear recurrence (empty string "" clears; cron "sec min hour day month dow" or RRULE)
+        #[arg(long, alias = "recurrence")]
         cron: Option<String>,
 
         /// Allow phantom (forward-reference) dependencies without error
@@ -491,6 +588,10 @@ pub enum Commands {
         /// Allow cycle creation without CycleConfig (overrides cycle detection guard)
         #[arg(long = "allow-cycle")]
         allow_cycle: bool,
+
+        /// Set priority: critical (100), high (50), normal (10), low (5), idle (0), or a number
+        #[arg(long, short = 'p')]
+        priority: Option<String>,
     },
 
     /// Mark a task as done
@@ -521,6 +622,12 @@ pub enum Commands {
         /// unless WG_SMOKE_AGENT_OVERRIDE=1 is also exported.
         #[arg(long = "skip-smoke")]
         skip_smoke: bool,
+
+        /// How the work was done, stored on the task and carried into
+        /// `wg func extract` templates. Plain text, a JSON object
+        /// {"approach", "steps", "pitfalls"}, or @FILE
+        #[arg(long)]
+        report: Option<String>,
     },
 
     /// Mark a task as failed (can be retried)
@@ -536,7 +643,8 @@ pub enum Commands {
         /// Machine-readable failure class (set by wrapper; pairs with --reason).
         /// One of: api-error-400-document, api-error-429-rate-limit,
         ///         api-error-5xx-transient, agent-hard-timeout,
-        ///         agent-exit-nonzero, wrapper-internal.
+        ///         agent-exit-nonzero, wrapper-internal, tool-policy-violation,
+        ///         cost-cap-exceeded.
         #[arg(long, value_name = "CLASS")]
         class: Option<String>,
 
@@ -690,12 +798,33 @@ pub enum Commands {
     /// Claim a task for work (sets status to InProgress)
     Claim {
         /// Task ID to claim
-        #[arg(value_name = "TASK")]
-        id: String,
+        #[arg(value_name = "TASK", required_unless_present_any = ["batch", "steal"])]
+        id: Option<String>,
 
         /// Assign to a specific actor
         #[arg(long)]
         actor: Option<String>,
+
+        /// Have the service dispatch this task now, pausing the
+        /// lowest-priority running agent if every slot is busy. The paused
+        /// agent resumes when this task finishes.
+        #[arg(long, conflicts_with_all = ["actor", "batch", "steal"])]
+        preempt: bool,
+
+        /// Claim up to N ready tasks at once (instead of TASK): the first is
+        /// claimed, the rest are queued for the actor
+        #[arg(long, value_name = "N", conflicts_with_all = ["id", "steal"])]
+        batch: Option<usize>,
+
+        /// Only take tasks whose required skills are all in this list
+        /// (comma-separated; defaults to an agency agent's capabilities)
+        #[arg(long, value_delimiter = ',')]
+        skills: Vec<String>,
+
+        /// Take over a task queued by the most overloaded actor (instead
+        /// of TASK)
+        #[arg(long, conflicts_with = "id")]
+        steal: bool,
     },
 
     /// Release a claimed task (sets status back to Open)
@@ -806,11 +935,15 @@ pub enum Commands {
         with_artifacts: bool,
     },
 
-    /// Show what's blocking a task
+    /// Show what's blocking a task, or every blocked task in a saved view
     Blocked {
         /// Task ID
-        #[arg(value_name = "TASK")]
-        id: String,
+        #[arg(value_name = "TASK", required_unless_present = "view")]
+        id: Option<String>,
+
+        /// Show blocked tasks in a saved view (see `wg view`)
+        #[arg(long, conflicts_with = "id")]
+        view: Option<String>,
     },
 
     /// Show the full transitive chain explaining why a task is blocked
@@ -850,9 +983,66 @@ pub enum Commands {
         #[arg(long)]
         cron: bool,
 
+        /// Filter expression, e.g. 'status=in-progress AND tag:backend AND
+        /// estimate>2d' (see `wg query --help`)
+        #[arg(long = "where", value_name = "EXPR")]
+        where_expr: Option<String>,
+
+        /// Start from a saved view (see `wg view`); other filters narrow it
+        #[arg(long)]
+        view: Option<String>,
+
         /// Show all tasks including dot-prefixed system tasks (hidden by default)
         #[arg(long)]
         all: bool,
+
+        /// Print an aligned table of these columns, e.g.
+        /// `id,status,assigned,estimate,age` (also: title, priority, tags,
+        /// after, created, completed, model)
+        #[arg(long, value_name = "LIST", conflicts_with = "format")]
+        columns: Option<String>,
+
+        /// Print one line per task from a template, e.g. '{{id}}\t{{title}}'.
+        /// Placeholders take the same names as --columns; \t and \n are expanded
+        #[arg(long, value_name = "TEMPLATE")]
+        format: Option<String>,
+
+        /// Omit the header row with --columns
+        #[arg(long, requires = "columns")]
+        no_header: bool,
+    },
+
+    /// Run a saved query from the `[views]` config section.
+    ///
+    /// Views use the `wg query` language plus `sort:FIELD` (`sort:-FIELD`
+    /// reverses) and `limit:N` directives, e.g.
+    /// `my-backlog = "status=open tag:backend sort:priority"`. With no name,
+    /// lists the defined views.
+    View {
+        /// View name
+        name: Option<String>,
+
+        /// Include dot-prefixed system tasks
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// List tasks matching a filter expression.
+    ///
+    /// Terms are `field OP value` with OP one of = != > >= < <= and `:`
+    /// (contains: tag/skill membership or substring). Combine with AND, OR,
+    /// NOT and parentheses; adjacent terms are ANDed. Fields: id, title,
+    /// description, status, tag, skill, assignee, model, estimate (2h, 3d,
+    /// 1w), cost, priority, and created/started/completed (YYYY-MM-DD,
+    /// RFC 3339, today, now, now-7d). `sort:FIELD`, `sort:-FIELD` and
+    /// `limit:N` order and trim the result.
+    Query {
+        /// The filter expression, e.g. 'status=open AND (tag:api OR skill:rust)'
+        expr: String,
+
+        /// Include dot-prefixed system tasks
+        #[arg(long)]
+        all: bool,
     },
 
     /// Visualize the dependency graph (ASCII tree by default)
@@ -874,17 +1064,27 @@ pub enum Commands {
         critical_path: bool,
 
         /// Output Graphviz DOT format
-        #[arg(long, conflicts_with_all = ["mermaid", "graph"])]
+        #[arg(long, conflicts_with_all = ["mermaid", "graph", "gantt"])]
         dot: bool,
 
         /// Output Mermaid diagram format
-        #[arg(long, conflicts_with_all = ["dot", "graph"])]
+        #[arg(long, conflicts_with_all = ["dot", "graph", "gantt"])]
         mermaid: bool,
 
         /// Output 2D spatial graph with box-drawing characters
-        #[arg(long, conflicts_with_all = ["dot", "mermaid"])]
+        #[arg(long, conflicts_with_all = ["dot", "mermaid", "gantt"])]
         graph: bool,
 
+        /// Output a Mermaid gantt timeline scheduled from estimates,
+        /// not_before, started/completed timestamps and dependencies
+        #[arg(long, conflicts_with_all = ["dot", "mermaid", "graph"])]
+        gantt: bool,
+
+        /// Output format: ascii, dot, mermaid, graph, or gantt (alternative
+        /// to the --dot/--mermaid/--graph/--gantt flags)
+        #[arg(long, value_name = "FORMAT", conflicts_with_all = ["dot", "mermaid", "graph", "gantt"])]
+        format: Option<String>,
+
         /// Render directly to file (requires dot installed)
         #[arg(long, short)]
         output: Option<String>,
@@ -894,7 +1094,7 @@ pub enum Commands {
         show_internal: bool,
 
         /// Launch interactive TUI mode instead of static output
-        #[arg(long, conflicts_with_all = ["dot", "mermaid", "graph", "output", "no_tui"])]
+        #[arg(long, conflicts_with_all = ["dot", "mermaid", "graph", "gantt", "format", "output", "no_tui"])]
         tui: bool,
 
         /// Force static output even when stdout is an interactive terminal
@@ -923,13 +1123,17 @@ pub enum Commands {
         columns: Option<u16>,
     },
 
-    /// Output the full graph data (DOT format with archive support)
+    /// Output the full graph data (DOT or Mermaid, with archive support)
     #[command(hide = true)]
     GraphExport {
         /// Include archived tasks
         #[arg(long)]
         archive: bool,
 
+        /// Output format: dot, graphml or mermaid
+        #[arg(long, default_value = "dot")]
+        format: String,
+
         /// Only show tasks completed/archived after this date (YYYY-MM-DD)
         #[arg(long)]
         since: Option<String>,
@@ -941,9 +1145,25 @@ pub enum Commands {
 
     /// Calculate cost of a task including dependencies
     Cost {
-        /// Task ID
+        /// Task ID (omit for a monthly usage report)
         #[arg(value_name = "TASK")]
-        id: String,
+        id: Option<String>,
+
+        /// Report usage for this month instead of a task (default: this month)
+        #[arg(long, value_name = "YYYY-MM", conflicts_with = "id")]
+        month: Option<String>,
+
+        /// Group the report by tag, milestone, model and/or agent (default: all)
+        #[arg(long, value_name = "GROUP", value_delimiter = ',', conflicts_with = "id")]
+        by: Vec<String>,
+
+        /// Export format for the report: csv or json
+        #[arg(long, conflicts_with = "id")]
+        format: Option<String>,
+
+        /// Write the report to a file (default: stdout)
+        #[arg(long, short = 'o', conflicts_with = "id")]
+        output: Option<PathBuf>,
     },
 
     /// Show coordination status: ready tasks, in-progress tasks, and opportunities
@@ -964,6 +1184,9 @@ pub enum Commands {
         /// Available hours
         #[arg(long)]
         hours: Option<f64>,
+
+        #[command(subcommand)]
+        command: Option<PlanCommands>,
     },
 
     /// Reschedule a task (set not_before timestamp)
@@ -979,6 +1202,11 @@ pub enum Commands {
         /// Specific timestamp when task becomes ready (ISO 8601)
         #[arg(long)]
         at: Option<String>,
+
+        /// Count --after in the assignee's working hours ([working_hours]
+        /// in config.toml) instead of wall-clock hours
+        #[arg(long, requires = "after")]
+        working: bool,
     },
 
     /// Change a task's priority level (critical, high, normal, low, idle)
@@ -1016,17 +1244,171 @@ pub enum Commands {
         weeks: Option<usize>,
     },
 
+    /// Full-text search over task titles, descriptions, log messages and
+    /// artifacts. Every term must match; quote a phrase to match it exactly.
+    Search {
+        /// Search terms; an argument containing spaces is matched as a phrase
+        #[arg(required = true, num_args = 1..)]
+        terms: Vec<String>,
+
+        /// Only tasks with this status
+        #[arg(long)]
+        status: Option<String>,
+
+        /// Only tasks carrying this tag (repeatable; all must match)
+        #[arg(long)]
+        tag: Vec<String>,
+
+        /// Restrict matches to a field: title, description, log, artifact (repeatable)
+        #[arg(long = "in", value_name = "FIELD")]
+        fields: Vec<String>,
+
+        /// Include archived tasks
+        #[arg(long)]
+        archive: bool,
+
+        /// Maximum number of results
+        #[arg(long, default_value = "20")]
+        limit: usize,
+
+        /// Rebuild the search index before querying
+        #[arg(long)]
+        reindex: bool,
+    },
+
+    /// Write a self-contained HTML status report (status summary, critical
+    /// path, velocity chart, per-agent workload, recent completions)
+    Report {
+        /// Output directory; the report is written to <DIR>/index.html
+        #[arg(long, value_name = "DIR")]
+        html: PathBuf,
+
+        /// Weeks of history in the velocity chart
+        #[arg(long, default_value = "8")]
+        weeks: usize,
+    },
+
     /// Show task age distribution: how long open/in-progress tasks have
     /// been waiting. Highlights stale work that may need attention.
-    Aging,
+    Aging {
+        /// Also run the escalation chain (notify.toml `[[escalation.chain]]`)
+        /// for tasks stuck in-progress or blocked
+        #[arg(long)]
+        escalate: bool,
+
+        /// With --escalate, show the steps that would fire without sending
+        #[arg(long, requires = "escalate")]
+        dry_run: bool,
+    },
+
+    /// List unfinished tasks past their deadline (`--due`) and milestones
+    /// whose critical-path forecast slips past theirs
+    Overdue {
+        /// Also list tasks due within this window (e.g. 2d, 12h)
+        #[arg(long)]
+        soon: Option<String>,
+
+        /// Send alerts for new breaches now instead of waiting for the
+        /// service sweep
+        #[arg(long)]
+        notify: bool,
+    },
+
+    /// List anomalies in task metrics: tasks far over their estimate, agents
+    /// finishing tasks suspiciously fast with low scores, and failure spikes
+    /// per tag (thresholds in `[anomaly]`)
+    Anomalies {
+        /// Send alerts for new anomalies now instead of waiting for the
+        /// service sweep
+        #[arg(long)]
+        notify: bool,
+    },
+
+    /// Show estimate-weighted progress for a task's subtree (the task and
+    /// everything it depends on) or a tag: percent complete, remaining
+    /// effort, and the change since last week
+    Progress {
+        /// Root task of the subtree
+        #[arg(value_name = "TASK", required_unless_present = "tag")]
+        id: Option<String>,
+
+        /// Measure all tasks carrying this tag instead of a subtree
+        #[arg(long, conflicts_with = "id")]
+        tag: Option<String>,
+    },
+
+    /// List tasks blocked on an external team or vendor
+    /// (`wg edit --external-blocker`) with contacts, expected dates and
+    /// follow-up reminders
+    Waiting {
+        /// Send follow-up reminders that are due now instead of waiting for
+        /// the service sweep
+        #[arg(long)]
+        notify: bool,
+    },
 
     /// Forecast project completion date based on recent velocity and
     /// remaining open tasks. Uses linear extrapolation.
     Forecast,
 
+    /// Aggregate status, forecasts and risk across several independent
+    /// workgraphs, riskiest projects first, with a per-project drill-down
+    Portfolio {
+        /// Project directories (or their .wg directories), comma-separated
+        #[arg(long, value_delimiter = ',', required = true)]
+        roots: Vec<String>,
+
+        /// Show the full drill-down for one project (by directory name)
+        #[arg(long)]
+        project: Option<String>,
+    },
+
     /// Show agent workload balance: how many tasks each agent has claimed
     /// or completed, to identify over/under-utilization.
-    Workload,
+    Workload {
+        #[command(subcommand)]
+        command: Option<WorkloadCommands>,
+    },
+
+    /// Availability calendar: vacations for humans, scheduled downtime for
+    /// agents. Assignment, forecast, and escalation skip unavailable assignees.
+    Availability {
+        #[command(subcommand)]
+        command: AvailabilityCommands,
+    },
+
+    /// Evaluation rubrics (`.wg/rubrics/*.yaml`) shared by evaluate, the
+    /// LLM verification gate, and review
+    Rubric {
+        #[command(subcommand)]
+        command: RubricCommands,
+    },
+
+    /// Calendar feeds of scheduled work (start, due, and forecast dates)
+    Calendar {
+        #[command(subcommand)]
+        command: CalendarCommands,
+    },
+
+    /// Feed of recent activity (new, completed and failed tasks) for
+    /// external subscribers, filtered by task visibility
+    Feed {
+        /// Output format: json or atom
+        #[arg(long, default_value = "json")]
+        format: String,
+
+        /// How far back to look (e.g. 24h, 7d, 2w)
+        #[arg(long, default_value = "7d")]
+        since: String,
+
+        /// Most private tasks to include: public, peer or internal
+        #[arg(long, default_value = "public")]
+        visibility: String,
+
+        /// Write to this file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<std::path::PathBuf>,
+    },
 
     /// Manage agent worktrees (list, archive, inspect)
     #[command(subcommand, name = "worktree")]
@@ -1113,6 +1495,10 @@ pub enum Commands {
         /// Task ID
         #[arg(value_name = "TASK")]
         id: String,
+
+        /// Look the task up in the archive (cold storage) instead of the live graph
+        #[arg(long)]
+        archived: bool,
     },
 
     /// Trace commands: execution history, export, import
@@ -1164,6 +1550,155 @@ pub enum Commands {
         command: RunsCommands,
     },
 
+    /// Scenario branches of the graph: plan alternatives without touching
+    /// the live plan, compare their forecasts, merge the chosen one back
+    Branch {
+        #[command(subcommand)]
+        command: BranchCommands,
+    },
+
+    /// Diff the graph against a snapshot, branch, file, or git revision:
+    /// added/removed tasks, status transitions, dependency changes
+    Diff {
+        /// What to compare from: snapshot:<name>, branch:<name>,
+        /// file:<path>, git:<rev>, or a bare name/path/revision
+        from: String,
+
+        /// What to compare to (same forms; default: the current graph)
+        #[arg(long)]
+        to: Option<String>,
+    },
+
+    /// Three-way merge another copy of the graph into this one, task by
+    /// task, reporting conflicts instead of overwriting
+    Merge {
+        /// Graph to merge in: a graph.jsonl path, snapshot:<name>,
+        /// branch:<name>, or git:<rev>
+        other: String,
+
+        /// Common ancestor (same forms). Without it the merge is two-way
+        /// and every disagreement is a conflict
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Resolve conflicts by keeping this graph's version of each task
+        #[arg(long, conflicts_with = "theirs")]
+        ours: bool,
+
+        /// Resolve conflicts by taking the other graph's version of each task
+        #[arg(long)]
+        theirs: bool,
+
+        /// Report what would merge and conflict without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Git merge driver for graph.jsonl: merges at the task level and
+    /// reports true conflicts task-by-task
+    #[command(name = "merge-driver")]
+    MergeDriver {
+        /// Common ancestor version (%O)
+        #[arg(required_unless_present = "install")]
+        base: Option<PathBuf>,
+
+        /// Current version; the merge result is written here (%A)
+        #[arg(required_unless_present = "install")]
+        ours: Option<PathBuf>,
+
+        /// Other branch's version (%B)
+        #[arg(required_unless_present = "install")]
+        theirs: Option<PathBuf>,
+
+        /// Path of the file being merged, for messages (%P)
+        path: Option<String>,
+
+        /// Register the driver in git config and .gitattributes
+        #[arg(long, conflicts_with_all = ["base", "ours", "theirs", "path"])]
+        install: bool,
+    },
+
+    /// Inspect and verify the operations log (provenance)
+    Provenance {
+        #[command(subcommand)]
+        command: ProvenanceCommands,
+    },
+
+    /// Revert your most recent graph change (see `wg redo`)
+    Undo {
+        /// Revert even if the affected tasks were changed outside the journal since
+        #[arg(long)]
+        force: bool,
+
+        /// Include changes made by agents and the service, not just your own
+        #[arg(long)]
+        any: bool,
+
+        /// List the changes that can be undone/redone instead of undoing
+        #[arg(long)]
+        list: bool,
+
+        /// Number of entries to show with --list
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
+
+    /// Re-apply your most recently undone graph change
+    Redo {
+        /// Re-apply even if the affected tasks were changed since the undo
+        #[arg(long)]
+        force: bool,
+
+        /// Include changes made by agents and the service, not just your own
+        #[arg(long)]
+        any: bool,
+    },
+
+    /// Show dispatch adjustments learned from function run summaries
+    /// (stronger tier / fewer concurrent agents for weak templates and skills)
+    Learning {
+        /// Recompute adjustments now instead of showing the daemon's last result
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Benchmark models by replaying completed tasks in sandbox mode
+    Bench {
+        #[command(subcommand)]
+        command: BenchCommands,
+    },
+
+    /// Purge logs, runs, provenance, archives, and telemetry older than the
+    /// [retention] policies (the service also does this periodically)
+    Retention {
+        /// Report what would be purged without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Show the report from the most recent retention pass
+        #[arg(long)]
+        last: bool,
+    },
+
+    /// Point-in-time copies of the graph you can roll back to
+    /// (create before bulk edits, restore when they go wrong)
+    Snapshot {
+        #[command(subcommand)]
+        command: SnapshotCommands,
+    },
+
+    /// Show the graph as it was at a point in time, reconstructed from the
+    /// undo journal (for retros comparing the plan with how it turned out)
+    At {
+        /// YYYY-MM-DD (start of day, UTC), an RFC 3339 timestamp, or a
+        /// duration ago (e.g. 3d, 2w)
+        #[arg(value_name = "TIME")]
+        time: String,
+
+        #[command(subcommand)]
+        view: AtCommands,
+    },
+
     /// Add progress log/notes to a task
     Log {
         /// Task ID (not required with --operations)
@@ -1188,6 +1723,10 @@ pub enum Commands {
         /// Show the operations log (reads current and rotated files)
         #[arg(long)]
         operations: bool,
+
+        /// Stream the output of the task's running agent until it exits
+        #[arg(long, short = 'f', conflicts_with_all = ["message", "list", "agent", "operations"])]
+        follow: bool,
     },
 
     /// Set or accumulate token usage on a task
@@ -1217,6 +1756,12 @@ pub enum Commands {
         command: OpenRouterCommands,
     },
 
+    /// Manage the on-disk cache of one-shot LLM responses
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+
     /// Send and receive messages to/from tasks and agents
     Msg {
         #[command(subcommand)]
@@ -1353,6 +1898,14 @@ pub enum Commands {
         command: ResourceCommands,
     },
 
+    /// Manage phase gates: named gate nodes whose conditions (phase done,
+    /// average score, lint command, human approval) must hold before the
+    /// next phase becomes ready
+    Gate {
+        #[command(subcommand)]
+        command: GateCommands,
+    },
+
     /// Manage nex chat sessions (list, attach, alias).
     ///
     /// Every `wg nex` session — interactive, coordinator,
@@ -1365,6 +1918,7 @@ pub enum Commands {
     },
 
     /// Manage skills (Claude Code skill installation, task skill queries)
+    #[command(alias = "skills")]
     Skill {
         #[command(subcommand)]
         command: SkillCommands,
@@ -1376,46 +1930,103 @@ pub enum Commands {
         command: AgencyCommands,
     },
 
-    /// Manage peer WG projects for cross-repo communication
-    Peer {
+    /// Export or import the task graph (DOT, GraphML, Mermaid)
+    Graph {
         #[command(subcommand)]
-        command: PeerCommands,
+        command: GraphCommands,
     },
 
-    /// Manage agency roles (what an agent does)
-    Role {
-        #[command(subcommand)]
-        command: RoleCommands,
-    },
+    /// Export tasks as CSV (id, title, after, tags, estimate, status)
+    Export {
+        /// Write CSV (the only supported format)
+        #[arg(long)]
+        csv: bool,
 
-    /// Manage agency tradeoffs (acceptable/unacceptable constraints)
-    #[command(alias = "motivation")]
-    Tradeoff {
-        #[command(subcommand)]
-        command: TradeoffCommands,
+        /// Output file path (default: stdout)
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
     },
 
-    /// Assign an agent to a task
-    Assign {
-        /// Task ID to assign agent to
-        task: String,
-
-        /// Agent hash (or prefix) to assign
-        agent_hash: Option<String>,
+    /// Create or update tasks from a CSV file written by `wg export --csv`
+    Import {
+        /// CSV file to import
+        file: PathBuf,
 
-        /// Clear the agent assignment from the task
+        /// Read CSV (the only supported format)
         #[arg(long)]
-        clear: bool,
+        csv: bool,
 
-        /// Automatically select an agent using LLM
+        /// Show what would change without modifying the graph
         #[arg(long)]
-        auto: bool,
+        dry_run: bool,
+    },
+
+    /// List externally submitted requests awaiting triage, or file one
+    Inbox {
+        #[command(subcommand)]
+        command: Option<InboxCommands>,
+    },
+
+    /// Triage queues: the request inbox, or step through ungroomed tasks
+    Triage {
+        /// Step through open untagged/unestimated tasks with single-key actions
+        #[arg(long, short = 'i')]
+        interactive: bool,
+
+        #[command(subcommand)]
+        command: Option<TriageCommands>,
+    },
+
+    /// Manage named graphs in this workspace (select one with --graph)
+    Graphs {
+        #[command(subcommand)]
+        command: GraphsCommands,
+    },
+
+    /// Manage peer WG projects for cross-repo communication
+    Peer {
+        #[command(subcommand)]
+        command: PeerCommands,
+    },
+
+    /// Manage agency roles (what an agent does)
+    Role {
+        #[command(subcommand)]
+        command: RoleCommands,
+    },
+
+    /// Manage agency tradeoffs (acceptable/unacceptable constraints)
+    #[command(alias = "motivation")]
+    Tradeoff {
+        #[command(subcommand)]
+        command: TradeoffCommands,
+    },
+
+    /// Assign an agent to a task
+    Assign {
+        /// Task ID to assign agent to
+        task: String,
+
+        /// Agent hash (or prefix) to assign
+        agent_hash: Option<String>,
+
+        /// Clear the agent assignment from the task
+        #[arg(long)]
+        clear: bool,
+
+        /// Automatically select an agent using LLM
+        #[arg(long)]
+        auto: bool,
     },
 
     /// Find agents capable of performing a task
     Match {
         /// Task ID to match agents against
         task: String,
+
+        /// Show each skill's closest capability and similarity score
+        #[arg(long)]
+        explain: bool,
     },
 
     /// Record agent heartbeat or check for stale agents
@@ -1446,6 +2057,18 @@ pub enum Commands {
         remove: bool,
     },
 
+    /// External references on a task: issues, PRs, docs, designs
+    Link {
+        #[command(subcommand)]
+        command: LinkCommands,
+    },
+
+    /// External event gates: fire or list `waiting_on: webhook:<name>`
+    Event {
+        #[command(subcommand)]
+        command: EventCommands,
+    },
+
     /// Show available context for a task from its dependencies
     Context {
         /// Task ID
@@ -1461,6 +2084,25 @@ pub enum Commands {
         /// Agent ID to find tasks for
         #[arg(long)]
         actor: String,
+
+        /// Only consider tasks in a saved view (see `wg view`)
+        #[arg(long)]
+        view: Option<String>,
+    },
+
+    /// Show and pin your next few tasks (claimable, skill-matched, by
+    /// priority and critical path)
+    Focus {
+        /// Person or agent the queue is for (defaults to the current user)
+        #[arg(long, global = true)]
+        actor: Option<String>,
+
+        /// Number of tasks to queue
+        #[arg(long, default_value = "5")]
+        limit: usize,
+
+        #[command(subcommand)]
+        command: Option<FocusCommands>,
     },
 
     /// Show context-efficient task trajectory (claim order for minimal context switching)
@@ -1525,7 +2167,7 @@ pub enum Commands {
         /// Task ID to spawn an agent for
         task: String,
 
-        /// Executor to use (claude, codex, native, shell, or custom config name)
+        /// Executor to use (claude, codex, native, shell, webhook, or custom config name)
         #[arg(long)]
         executor: String,
 
@@ -1591,7 +2233,7 @@ pub enum Commands {
         #[arg(long)]
         list: bool,
 
-        /// Set executor (claude, codex, native, shell, or custom config name)
+        /// Set executor (claude, codex, native, shell, webhook, or custom config name)
         #[arg(long)]
         executor: Option<String>,
 
@@ -2155,12 +2797,40 @@ pub enum Commands {
     /// Print a concise cheat sheet for agent onboarding
     Quickstart,
 
+    /// Generate an onboarding brief for a new agent or contributor: project
+    /// summary, conventions, ready work matching their skills, and how to
+    /// claim and complete tasks.
+    Onboard {
+        /// Skills of the newcomer, used to rank ready work (comma-separated or repeated)
+        #[arg(long = "skill", value_delimiter = ',')]
+        skills: Vec<String>,
+
+        /// Agent ID (prefix) whose capabilities to use as skills
+        #[arg(long)]
+        agent: Option<String>,
+
+        /// Maximum number of ready tasks to list
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
+
     /// Check local development checkout and installed wg binary freshness
     DevCheck,
 
     /// Print the universal agent / chat-agent role contract bundled with this binary
     AgentGuide,
 
+    /// Print the JSON Schema for an on-disk format (task, function, config,
+    /// run-summary). Without a kind, list the available schemas.
+    Schema {
+        /// Format to describe
+        kind: Option<String>,
+
+        /// Write the schema to this file instead of stdout
+        #[arg(long, short = 'o', requires = "kind")]
+        output: Option<PathBuf>,
+    },
+
     /// Quick one-screen status overview
     Status {
         /// Include dot-prefixed system tasks in counts (hidden by default)
@@ -2193,7 +2863,8 @@ pub enum Commands {
         message: Option<String>,
     },
 
-    /// Stream WG events as JSON lines
+    /// Stream WG events as JSON lines, or keep a view (list, status,
+    /// blocked) on screen, re-rendered whenever the graph changes
     Watch {
         /// Filter events by type (repeatable). Types: task_state, evaluation, agent, all.
         #[arg(long = "event", default_value = "all")]
@@ -2204,6 +2875,10 @@ pub enum Commands {
         /// Include N most recent historical events before streaming (default: 0)
         #[arg(long, default_value = "0")]
         replay: usize,
+        /// Re-render this view on every graph change instead of streaming
+        /// events: list, status, or blocked
+        #[arg(long, conflicts_with_all = ["event_types", "task", "replay"])]
+        view: Option<String>,
     },
 
     /// Matrix integration commands
@@ -2550,6 +3225,19 @@ pub enum Commands {
         all: bool,
     },
 
+    /// Hand a task to the webhook executor's endpoint and wait for its
+    /// report (internal, called by spawn)
+    #[command(name = "webhook-exec", hide = true)]
+    WebhookExec {
+        /// Path to the prompt file
+        #[arg(long)]
+        prompt_file: String,
+
+        /// Task ID being worked on
+        #[arg(long)]
+        task_id: String,
+    },
+
     /// Run the native executor agent loop (internal, called by spawn)
     #[command(name = "native-exec", hide = true)]
     NativeExec {
@@ -3199,6 +3887,12 @@ pub enum OpenRouterCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum CacheCommands {
+    /// Delete every cached LLM response
+    Clear,
+}
+
 #[derive(Subcommand)]
 pub enum MsgCommands {
     /// Send a message to a task/agent
@@ -3635,8 +4329,12 @@ pub enum TraceCommands {
     // Hidden aliases for backward compatibility (wg trace <cmd> → wg func <cmd>)
     #[command(name = "extract", hide = true)]
     ExtractAlias {
-        #[arg(required = true, num_args = 1..)]
+        #[arg(required_unless_present_any = ["tag", "between"], num_args = 1..)]
         task_ids: Vec<String>,
+        #[arg(long, conflicts_with_all = ["task_ids", "subgraph", "recursive", "generative"])]
+        tag: Vec<String>,
+        #[arg(long, conflicts_with_all = ["task_ids", "subgraph", "recursive", "generative"])]
+        between: Option<String>,
         #[arg(long)]
         name: Option<String>,
         #[arg(long)]
@@ -3653,6 +4351,8 @@ pub enum TraceCommands {
         force: bool,
         #[arg(long)]
         include_evaluations: bool,
+        #[arg(long)]
+        review: bool,
     },
 
     #[command(name = "instantiate", hide = true)]
@@ -3672,6 +4372,10 @@ pub enum TraceCommands {
         after: Vec<String>,
         #[arg(long)]
         model: Option<String>,
+        #[arg(long = "not-before")]
+        not_before: Option<String>,
+        #[arg(long)]
+        stagger: Option<String>,
     },
 
     #[command(name = "list-functions", hide = true)]
@@ -3724,12 +4428,37 @@ pub enum FuncCommands {
         id: String,
     },
 
+    /// Render a function as a document: purpose, inputs, task flow
+    /// diagram, constraints, and run history
+    Docs {
+        /// Function ID (prefix match supported)
+        id: String,
+
+        /// Output format (markdown)
+        #[arg(long, default_value = "markdown")]
+        format: String,
+
+        /// Write to this file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+    },
+
     /// Extract a function from completed task(s)
     Extract {
         /// Task ID(s) to extract from (multiple IDs with --generative)
-        #[arg(required = true, num_args = 1..)]
+        #[arg(required_unless_present_any = ["tag", "between"], num_args = 1..)]
         task_ids: Vec<String>,
 
+        /// Extract every completed task carrying this tag instead of a root
+        /// task (repeatable; tasks must match all)
+        #[arg(long, conflicts_with_all = ["task_ids", "subgraph", "recursive", "generative"])]
+        tag: Vec<String>,
+
+        /// Extract completed tasks finished in FROM..TO (YYYY-MM-DD or RFC 3339;
+        /// either end may be omitted). Combines with --tag
+        #[arg(long, value_name = "FROM..TO", conflicts_with_all = ["task_ids", "subgraph", "recursive", "generative"])]
+        between: Option<String>,
+
         /// Function name/ID (default: derived from task ID)
         #[arg(long)]
         name: Option<String>,
@@ -3762,6 +4491,11 @@ pub enum FuncCommands {
         /// (evaluate-*, assign-*) that are normally filtered out
         #[arg(long)]
         include_evaluations: bool,
+
+        /// Review suggested parameters and generalized descriptions
+        /// interactively (accept/reject/edit per field) before saving
+        #[arg(long, conflicts_with = "generative")]
+        review: bool,
     },
 
     /// Create tasks from a function with provided inputs
@@ -3796,6 +4530,15 @@ pub enum FuncCommands {
         /// Set model for all created tasks
         #[arg(long)]
         model: Option<String>,
+
+        /// Don't dispatch any created task before this timestamp (ISO 8601)
+        #[arg(long = "not-before")]
+        not_before: Option<String>,
+
+        /// Space created tasks' start times by this interval in creation
+        /// order (e.g. 10m), starting at --not-before or now
+        #[arg(long)]
+        stagger: Option<String>,
     },
 
     /// Bootstrap the extract-function meta-function
@@ -3841,6 +4584,125 @@ pub enum RunsCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum BranchCommands {
+    /// Create a branch as a copy of the live graph (or another branch)
+    Create {
+        /// Branch name
+        name: String,
+
+        /// Branch to copy from (default: main, the live graph)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Note describing the scenario
+        #[arg(long, short)]
+        message: Option<String>,
+    },
+
+    /// List branches
+    List,
+
+    /// Compare forecasts between two branches
+    Compare {
+        /// Branch to compare
+        branch: String,
+
+        /// Branch to compare against (default: main, the live graph)
+        other: Option<String>,
+    },
+
+    /// Make a branch the live graph (takes a safety snapshot first)
+    Merge {
+        /// Branch name
+        name: String,
+
+        /// Merge even if the live graph changed since the branch was taken
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Delete a branch
+    Delete {
+        /// Branch name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ProvenanceCommands {
+    /// Check the hash chain for edited, inserted, deleted, or truncated entries
+    Verify,
+
+    /// Show how a task came to be: origin, edits, retries, and artifacts per attempt
+    Show {
+        /// Task ID
+        id: String,
+
+        /// Output a Graphviz DOT lineage graph instead of a timeline
+        #[arg(long)]
+        graph: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SnapshotCommands {
+    /// Save the current graph (and config) as a snapshot
+    Create {
+        /// Snapshot name (default: snap-NNN)
+        name: Option<String>,
+
+        /// Note describing why the snapshot was taken
+        #[arg(long, short)]
+        message: Option<String>,
+
+        /// Also capture the agency/ directory
+        #[arg(long)]
+        agency: bool,
+
+        /// Also capture the functions/ directory
+        #[arg(long)]
+        functions: bool,
+    },
+
+    /// List snapshots
+    List,
+
+    /// Restore a snapshot (takes a safety snapshot of the current state first)
+    Restore {
+        /// Snapshot name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AtCommands {
+    /// Status summary, as `wg status`
+    Status {
+        /// Include dot-prefixed system tasks in counts
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Task list, as `wg list`
+    List {
+        /// Filter by status
+        #[arg(long)]
+        status: Option<String>,
+
+        /// Include dot-prefixed system tasks
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Graph view, as `wg viz`
+    Viz {
+        /// Show all tasks including fully-done trees (default: active trees only)
+        #[arg(long)]
+        all: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum ResourceCommands {
     /// Add a new resource
@@ -3869,6 +4731,241 @@ pub enum ResourceCommands {
     List,
 }
 
+#[derive(Subcommand)]
+pub enum GateCommands {
+    /// Create a gate node guarding the phase formed by its --after tasks
+    Create {
+        /// Gate ID
+        id: String,
+
+        /// Display title (default: "Gate: <id>")
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Tasks in the phase this gate guards
+        #[arg(long, value_delimiter = ',')]
+        after: Vec<String>,
+
+        /// Require every task in the phase to be done
+        #[arg(long)]
+        phase_done: bool,
+
+        /// Require the phase's average evaluation score to be at least this value
+        #[arg(long)]
+        min_score: Option<f64>,
+
+        /// Require a shell command (e.g. a linter) to exit successfully (repeatable)
+        #[arg(long)]
+        command: Vec<String>,
+
+        /// Require human approval via `wg gate approve`
+        #[arg(long)]
+        approval: bool,
+    },
+
+    /// Evaluate gate conditions and open gates whose conditions all hold
+    Check {
+        /// Gate ID (default: every gate whose phase has settled)
+        id: Option<String>,
+    },
+
+    /// Approve a gate (satisfies its human-approval condition)
+    Approve {
+        /// Gate ID
+        id: String,
+    },
+
+    /// List gates and how many of their conditions are met
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum WorkloadCommands {
+    /// Project each agent's queue over the coming weeks (assigned plus
+    /// likely-matched tasks) and flag overcommitment
+    Forecast {
+        /// Only this agent (hash prefix)
+        #[arg(long)]
+        agent: Option<String>,
+
+        /// Number of weeks to project
+        #[arg(long, default_value = "4")]
+        weeks: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum BenchCommands {
+    /// Replay a sample of completed tasks against several models, score the
+    /// answers with the evaluator, and compare cost against quality
+    Models {
+        /// Number of completed tasks to replay (most recent first)
+        #[arg(long, default_value = "10")]
+        sample: usize,
+
+        /// Only tasks requiring one of these skills (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        skills: Vec<String>,
+
+        /// Models to compare (comma-separated; default: the configured
+        /// fast, standard, and premium tiers)
+        #[arg(long, value_delimiter = ',')]
+        models: Vec<String>,
+
+        /// Show the sampled tasks and models without calling any model
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AvailabilityCommands {
+    /// Mark a participant unavailable for a period
+    Add {
+        /// Agent hash/prefix, agent name, or human name as used in `assigned`
+        who: String,
+
+        /// Start of the window: YYYY-MM-DD or RFC 3339 (default: now)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// End of the window: YYYY-MM-DD (inclusive) or RFC 3339
+        #[arg(long)]
+        until: String,
+
+        /// Why (e.g. "vacation", "maintenance")
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// List availability windows
+    List {
+        /// Only windows for this participant
+        #[arg(long)]
+        who: Option<String>,
+
+        /// Include windows that have already ended
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Remove a window by ID
+    Remove {
+        /// Window ID (from `wg availability list`)
+        id: u32,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RubricCommands {
+    /// List rubrics and what they attach to
+    List,
+
+    /// Show a rubric's criteria and thresholds
+    Show {
+        /// Rubric ID (file stem in .wg/rubrics/)
+        id: String,
+    },
+
+    /// Score a task against a rubric with the evaluator model (not recorded)
+    Test {
+        /// Rubric ID
+        rubric: String,
+
+        /// Task to score
+        task: String,
+
+        /// Print the scoring prompt without calling the model
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum LinkCommands {
+    /// Attach a link (re-adding a URL updates its type and label)
+    Add {
+        /// Task ID
+        task: String,
+
+        /// Link type: issue, pr, doc, design, or other
+        #[arg(value_name = "TYPE")]
+        kind: String,
+
+        /// http(s) URL
+        url: String,
+
+        /// Short description shown next to the URL
+        #[arg(long)]
+        label: Option<String>,
+    },
+
+    /// Remove a link by URL
+    #[command(alias = "rm")]
+    Remove {
+        /// Task ID
+        task: String,
+
+        /// URL of the link to remove
+        url: String,
+    },
+
+    /// List a task's links
+    List {
+        /// Task ID
+        task: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum EventCommands {
+    /// Fire webhook:<name>, releasing every task waiting on it (same as
+    /// `POST /hooks/<name>` to the service)
+    Fire {
+        /// Event name
+        name: String,
+    },
+
+    /// List pending gates and the tasks waiting on them
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum PlanCommands {
+    /// Propose today's plan for a person or agent from their focus queue,
+    /// estimates, working hours and availability
+    Today {
+        /// Person or agent to plan for (defaults to the current user)
+        #[arg(long)]
+        actor: Option<String>,
+
+        /// Also post the plan to the Matrix default room
+        #[arg(long)]
+        post: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum FocusCommands {
+    /// Claim the top task of your pinned focus queue
+    Next,
+}
+
+#[derive(Subcommand)]
+pub enum CalendarCommands {
+    /// Export an iCalendar (.ics) feed of unfinished tasks' not-before,
+    /// due, and estimated completion dates
+    Export {
+        /// Write to this file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<std::path::PathBuf>,
+
+        /// Only tasks with this tag (repeatable; all must match)
+        #[arg(long)]
+        tag: Vec<String>,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum AgentsCommand {
     /// SIGTERM (or SIGKILL with --force) the named agent process.
@@ -3909,6 +5006,14 @@ pub enum SkillCommands {
 
     /// Install the wg Claude Code skill to ~/.claude/skills/wg/
     Install,
+
+    /// Compare skills demanded by unfinished tasks against skills offered
+    /// by agents and roles, and suggest what would unblock the most work
+    Gaps {
+        /// Horizon in weeks over which agent capacity is counted
+        #[arg(long, default_value = "4")]
+        weeks: usize,
+    },
 }
 
 #[derive(Subcommand)]
@@ -4371,6 +5476,131 @@ pub enum SessionAliasCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum GraphCommands {
+    /// Print the task graph as DOT, GraphML or Mermaid
+    Export {
+        /// Include archived tasks
+        #[arg(long)]
+        archive: bool,
+
+        /// Output format: dot, graphml or mermaid
+        #[arg(long, default_value = "dot")]
+        format: String,
+
+        /// Only show tasks completed/archived after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only show tasks completed/archived before this date (YYYY-MM-DD)
+        #[arg(long)]
+        until: Option<String>,
+    },
+
+    /// Merge tasks and dependencies from a DOT or GraphML file
+    Import {
+        /// File to import (e.g. a layout saved from Gephi or yEd)
+        file: PathBuf,
+
+        /// Input format: dot or graphml (default: from extension or content)
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Show what would be imported without changing the graph
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum InboxCommands {
+    /// File a request as an inbox draft
+    Add {
+        /// Request title (taken from the Subject header with --email)
+        title: Option<String>,
+
+        /// Request details
+        #[arg(long)]
+        body: Option<String>,
+
+        /// Channel the request arrived on
+        #[arg(long, default_value = "cli")]
+        source: String,
+
+        /// Who submitted it
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Read an RFC 822 email from stdin (Subject, From and body)
+        #[arg(long)]
+        email: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TriageCommands {
+    /// Review inbox drafts: list, accept, reject or merge
+    Inbox {
+        #[command(subcommand)]
+        action: Option<TriageInboxCommands>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TriageInboxCommands {
+    /// Accept a draft so it can be dispatched
+    Accept {
+        /// Draft task ID
+        id: String,
+
+        /// Decompose into a subtask with this title (repeatable); the draft waits on them
+        #[arg(long = "split")]
+        split: Vec<String>,
+    },
+
+    /// Reject a draft (marks it abandoned)
+    Reject {
+        /// Draft task ID
+        id: String,
+
+        /// Why it was rejected
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Merge a draft into an existing task's description
+    Merge {
+        /// Draft task ID
+        id: String,
+
+        /// Task that absorbs the request
+        #[arg(long)]
+        into: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum GraphsCommands {
+    /// List graphs with task counts (* marks the current one)
+    List,
+
+    /// Create an empty named graph
+    Create {
+        /// Graph name (letters, digits, '-' and '_')
+        name: String,
+    },
+
+    /// Delete a named graph and everything stored with it
+    Remove {
+        /// Graph name
+        name: String,
+
+        /// Remove even if tasks in other graphs depend on it
+        #[arg(long)]
+        force: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum PeerCommands {
     /// Register a peer WG project
@@ -4576,6 +5806,63 @@ pub enum AgentCommands {
         id: String,
     },
 
+    /// Show an agent's track record: success rate, avg score, duration vs estimate
+    Stats {
+        /// Agent ID (or prefix)
+        id: String,
+    },
+
+    /// Register an agent's capability document (skills, models, max context,
+    /// cost ceiling, resource access), checked against task requirements at
+    /// assignment
+    Register {
+        /// Agent ID (or prefix)
+        id: String,
+
+        /// Read the document from a YAML or JSON file; flags override its fields
+        #[arg(long)]
+        file: Option<PathBuf>,
+
+        /// Skills (comma-separated or repeated); replace the agent's capabilities
+        #[arg(long, value_delimiter = ',')]
+        skill: Vec<String>,
+
+        /// Models the agent can run (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        model: Vec<String>,
+
+        /// Largest context window the agent can work in
+        #[arg(long, value_name = "TOKENS")]
+        max_context: Option<u64>,
+
+        /// Most the agent may spend on one task
+        #[arg(long, value_name = "USD")]
+        cost_ceiling: Option<f64>,
+
+        /// Graph resources the agent can use (comma-separated or repeated)
+        #[arg(long, value_delimiter = ',')]
+        resource: Vec<String>,
+
+        /// Remove the capability document
+        #[arg(long, conflicts_with_all = ["file", "skill", "model", "max_context", "cost_ceiling", "resource"])]
+        clear: bool,
+    },
+
+    /// Set an agent's daily cost budget. The service stops dispatching to an
+    /// agent once its spend for the day (UTC) passes the budget
+    SetBudget {
+        /// Agent ID (or prefix)
+        id: String,
+
+        /// Most the agent may spend per day
+        #[arg(long, value_name = "USD", required_unless_present = "clear")]
+        daily: Option<f64>,
+
+        /// Remove the budget
+        #[arg(long, conflicts_with = "daily")]
+        clear: bool,
+    },
+
     /// Run autonomous agent loop (wake/check/work/sleep cycle)
     Run {
         /// Actor ID for this agent
@@ -4598,6 +5885,12 @@ pub enum AgentCommands {
         #[arg(long)]
         reset_state: bool,
     },
+
+    /// Open a shell in the environment a spawned agent ran with (cwd, env, worktree)
+    Shell {
+        /// Spawned agent ID (e.g. agent-42), or a task ID for its latest agent
+        target: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -4740,6 +6033,18 @@ pub enum MigrateCommands {
         dry_run: bool,
     },
 
+    /// Rewrite renamed fields in `graph.jsonl` and the function run logs
+    /// to their canonical names (`blocked_by` → `after`, `blocks` →
+    /// `before`, `instantiated_at` → `applied_at`). Loading a file that
+    /// still uses the old names prints a deprecation warning.
+    ///
+    /// Safe to run multiple times — idempotent.
+    Fields {
+        /// Only report what would change, don't write.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Walk existing configs that use `api_key_env` and migrate them to
     /// `api_key_ref = "keyring:<name>"`, prompting before each change.
     ///
@@ -4832,16 +6137,40 @@ pub enum ConfigSubcommand {
         #[arg(long, conflicts_with_all = ["global", "local"])]
         merged: bool,
     },
+
+    /// Show how the effective config (global + local) differs from a
+    /// baseline, key by key.
+    ///
+    /// The default baseline is `workgraph.toml` at the project root, a
+    /// config file a team commits so every machine runs the same
+    /// settings. The service warns at startup when the effective config
+    /// has drifted from it.
+    Diff {
+        /// `defaults` for the built-in defaults, or a path to a config
+        /// file. Default: the project's `workgraph.toml`.
+        #[arg(long, value_name = "defaults|FILE")]
+        against: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum ServiceCommands {
     /// Start the agent service daemon
     Start {
-        /// Port to listen on (optional, for HTTP API)
-        #[arg(long)]
+        /// Serve the HTTP API on 127.0.0.1:<PORT> (shorthand for --http)
+        #[arg(long, conflicts_with = "http")]
         port: Option<u16>,
 
+        /// Serve a JSON REST API on this address (e.g. 127.0.0.1:7700).
+        /// Requests need the bearer token in .wg/service/http.token
+        #[arg(long, value_name = "ADDR")]
+        http: Option<String>,
+
+        /// Serve the gRPC control API on this address (build with
+        /// `--features grpc`). Uses the same token as --http
+        #[arg(long, value_name = "ADDR")]
+        grpc: Option<String>,
+
         /// Unix socket path (default: .wg/service/daemon.sock)
         #[arg(long)]
         socket: Option<String>,
@@ -4869,6 +6198,11 @@ pub enum ServiceCommands {
         /// Disable the persistent chat agent (LLM session); legacy alias: --no-coordinator-agent
         #[arg(long, alias = "no-coordinator-agent")]
         no_chat_agent: bool,
+
+        /// Start a standby next to the running service. It stays idle and
+        /// takes over dispatch if the leader dies
+        #[arg(long, conflicts_with = "force")]
+        standby: bool,
     },
 
     /// Stop the agent service daemon
@@ -4882,6 +6216,18 @@ pub enum ServiceCommands {
         kill_agents: bool,
     },
 
+    /// Stop dispatching, wait for running agents to finish, then stop
+    ///
+    /// Pauses dispatch, waits until every agent in the registry has exited
+    /// (or the timeout passes), writes a registry checkpoint to
+    /// .wg/service/drain-checkpoint.json and stops the daemon. Agents still
+    /// running at the timeout are left running, as with `wg service stop`.
+    Drain {
+        /// How long to wait for running agents (e.g. 90s, 30m, 2h; default: 30m)
+        #[arg(long)]
+        timeout: Option<String>,
+    },
+
     /// Show service status
     Status,
 
@@ -4914,10 +6260,31 @@ pub enum ServiceCommands {
     Restart,
 
     /// Pause the coordinator (running agents continue, no new spawns)
-    Pause,
+    ///
+    /// With --tag or --queue, only tasks carrying that tag or requiring that
+    /// resource pool are held; everything else keeps dispatching.
+    Pause {
+        /// Hold only tasks with this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Hold only tasks that require this resource pool (repeatable)
+        #[arg(long = "queue", value_name = "POOL")]
+        queues: Vec<String>,
+    },
 
     /// Resume the coordinator
-    Resume,
+    ///
+    /// With --tag or --queue, lifts only that scoped pause.
+    Resume {
+        /// Release tasks with this tag (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Release tasks that require this resource pool (repeatable)
+        #[arg(long = "queue", value_name = "POOL")]
+        queues: Vec<String>,
+    },
 
     /// Freeze all agents (SIGSTOP) and pause the service
     ///
@@ -4938,6 +6305,28 @@ pub enum ServiceCommands {
     /// Generate a systemd user service file for the wg service daemon
     Install,
 
+    /// Show how long ready tasks waited for an agent to start
+    ///
+    /// Histogram and percentiles of ready-to-started latency recorded by
+    /// the daemon, plus the tasks waiting right now. Alert thresholds are
+    /// set with `coordinator.dispatch_latency_alerts`.
+    Latency {
+        /// Only count agents started within this window (e.g. 24h, 7d)
+        #[arg(long)]
+        since: Option<String>,
+    },
+
+    /// Pause dispatch for planned maintenance, with a reason
+    ///
+    /// Running agents continue; no new ones are started until maintenance
+    /// is turned off or its `--for` runs out. The reason shows in `wg
+    /// service status` and in notifications sent meanwhile. Recurring
+    /// windows are configured with `[[coordinator.pause_windows]]`.
+    Maintenance {
+        #[command(subcommand)]
+        command: MaintenanceCommands,
+    },
+
     /// Run a single coordinator tick and exit (debug mode)
     Tick {
         /// Maximum number of parallel agents (overrides config.toml)
@@ -5064,10 +6453,38 @@ pub enum ServiceCommands {
         /// Disable the persistent chat agent (LLM session); legacy alias: --no-coordinator-agent
         #[arg(long, alias = "no-coordinator-agent")]
         no_chat_agent: bool,
+
+        /// Serve the REST API on this address
+        #[arg(long, value_name = "ADDR")]
+        http: Option<String>,
+
+        /// Serve the gRPC control API on this address
+        #[arg(long, value_name = "ADDR")]
+        grpc: Option<String>,
     },
 }
 
 #[cfg(any(feature = "matrix", feature = "matrix-lite"))]
+#[derive(Subcommand)]
+pub enum MaintenanceCommands {
+    /// Enter maintenance: stop dispatching new agents
+    On {
+        /// Why (shown in status output and notifications)
+        #[arg(long)]
+        reason: String,
+
+        /// End maintenance automatically after this long (e.g. 30m, 2h)
+        #[arg(long = "for", value_name = "DURATION")]
+        duration: Option<String>,
+    },
+
+    /// Leave maintenance entered with `on` (pause windows still apply)
+    Off,
+
+    /// Show active maintenance and upcoming pause windows
+    Status,
+}
+
 #[derive(Subcommand)]
 pub enum MatrixCommands {
     /// Start the Matrix message listener
@@ -5215,9 +6632,16 @@ pub fn command_name(cmd: &Commands) -> &'static str {
         Commands::Structure => "structure",
         Commands::Bottlenecks => "bottlenecks",
         Commands::Velocity { .. } => "velocity",
-        Commands::Aging => "aging",
+        Commands::Report { .. } => "report",
+        Commands::Search { .. } => "search",
+        Commands::Aging { .. } => "aging",
+        Commands::Overdue { .. } => "overdue",
+        Commands::Anomalies { .. } => "anomalies",
+        Commands::Waiting { .. } => "waiting",
+        Commands::Progress { .. } => "progress",
+        Commands::Portfolio { .. } => "portfolio",
         Commands::Forecast => "forecast",
-        Commands::Workload => "workload",
+        Commands::Workload { .. } => "workload",
         Commands::Worktree(_) => "worktree",
         Commands::Resources => "resources",
         Commands::CriticalPath => "critical-path",
@@ -5230,13 +6654,38 @@ pub fn command_name(cmd: &Commands) -> &'static str {
         Commands::Func { .. } => "func",
         Commands::Replay { .. } => "replay",
         Commands::Runs { .. } => "runs",
+        Commands::Branch { .. } => "branch",
+        Commands::Diff { .. } => "diff",
+        Commands::Merge { .. } => "merge",
+        Commands::MergeDriver { .. } => "merge-driver",
+        Commands::Provenance { .. } => "provenance",
+        Commands::Undo { .. } => "undo",
+        Commands::Learning { .. } => "learning",
+        Commands::Query { .. } => "query",
+        Commands::View { .. } => "view",
+        Commands::Bench { .. } => "bench",
+        Commands::Retention { .. } => "retention",
+        Commands::Redo { .. } => "redo",
+        Commands::Snapshot { .. } => "snapshot",
+        Commands::At { .. } => "at",
         Commands::Log { .. } => "log",
         Commands::Tokens { .. } => "tokens",
         Commands::Msg { .. } => "msg",
         Commands::User { .. } => "user",
         Commands::Resource { .. } => "resource",
+        Commands::Gate { .. } => "gate",
+        Commands::Availability { .. } => "availability",
+        Commands::Rubric { .. } => "rubric",
+        Commands::Calendar { .. } => "calendar",
+        Commands::Feed { .. } => "feed",
         Commands::Skill { .. } => "skill",
         Commands::Agency { .. } => "agency",
+        Commands::Graph { .. } => "graph",
+        Commands::Export { .. } => "export",
+        Commands::Import { .. } => "import",
+        Commands::Inbox { .. } => "inbox",
+        Commands::Triage { .. } => "triage",
+        Commands::Graphs { .. } => "graphs",
         Commands::Peer { .. } => "peer",
         Commands::Role { .. } => "role",
         Commands::Tradeoff { .. } => "tradeoff",
@@ -5245,8 +6694,11 @@ pub fn command_name(cmd: &Commands) -> &'static str {
         Commands::Heartbeat { .. } => "heartbeat",
         Commands::Checkpoint { .. } => "checkpoint",
         Commands::Artifact { .. } => "artifact",
+        Commands::Link { .. } => "link",
+        Commands::Event { .. } => "event",
         Commands::Context { .. } => "context",
         Commands::Next { .. } => "next",
+        Commands::Focus { .. } => "focus",
         Commands::Trajectory { .. } => "trajectory",
         Commands::Exec { .. } => "exec",
         Commands::Agent { .. } => "agent",
@@ -5270,8 +6722,10 @@ pub fn command_name(cmd: &Commands) -> &'static str {
         Commands::TuiDump { .. } => "tui-dump",
         Commands::Setup { .. } => "setup",
         Commands::Quickstart => "quickstart",
+        Commands::Onboard { .. } => "onboard",
         Commands::DevCheck => "dev-check",
         Commands::AgentGuide => "agent-guide",
+        Commands::Schema { .. } => "schema",
         Commands::Status { .. } => "status",
         Commands::Stats => "stats",
         Commands::Metrics { .. } => "metrics",
@@ -5293,10 +6747,12 @@ pub fn command_name(cmd: &Commands) -> &'static str {
         Commands::ClaudeHandler { .. } => "claude-handler",
         Commands::CodexHandler { .. } => "codex-handler",
         Commands::NativeExec { .. } => "native-exec",
+        Commands::WebhookExec { .. } => "webhook-exec",
         Commands::Which { .. } => "which",
         Commands::Executors { .. } => "executors",
         Commands::Spend { .. } => "spend",
         Commands::Openrouter { .. } => "openrouter",
+        Commands::Cache { .. } => "cache",
         Commands::ApplyPlacement { .. } => "apply-placement",
         Commands::Session { .. } => "session",
     }
@@ -5311,15 +6767,23 @@ pub fn supports_json(cmd: &Commands) -> bool {
             | Commands::Blocked { .. }
             | Commands::WhyBlocked { .. }
             | Commands::List { .. }
+            | Commands::Query { .. }
+            | Commands::View { .. }
             | Commands::Coordinate { .. }
             | Commands::Plan { .. }
             | Commands::Impact { .. }
             | Commands::Structure
             | Commands::Bottlenecks
             | Commands::Velocity { .. }
-            | Commands::Aging
+            | Commands::Search { .. }
+            | Commands::Aging { .. }
+            | Commands::Overdue { .. }
+            | Commands::Anomalies { .. }
+            | Commands::Waiting { .. }
+            | Commands::Progress { .. }
+            | Commands::Portfolio { .. }
             | Commands::Forecast
-            | Commands::Workload
+            | Commands::Workload { .. }
             | Commands::Worktree(_)
             | Commands::Resources
             | Commands::CriticalPath
@@ -5332,13 +6796,32 @@ pub fn supports_json(cmd: &Commands) -> bool {
             | Commands::Func { .. }
             | Commands::Replay { .. }
             | Commands::Runs { .. }
+            | Commands::Branch { .. }
+            | Commands::Diff { .. }
+            | Commands::Merge { .. }
+            | Commands::Provenance { .. }
+            | Commands::Undo { .. }
+            | Commands::Learning { .. }
+            | Commands::Bench { .. }
+            | Commands::Retention { .. }
+            | Commands::Redo { .. }
+            | Commands::Snapshot { .. }
+            | Commands::At { .. }
             | Commands::Log { .. }
             | Commands::Tokens { .. }
             | Commands::Msg { .. }
             | Commands::User { .. }
             | Commands::Resource { .. }
+            | Commands::Gate { .. }
+            | Commands::Availability { .. }
+            | Commands::Rubric { .. }
             | Commands::Skill { .. }
             | Commands::Agency { .. }
+            | Commands::Graph { .. }
+            | Commands::Import { .. }
+            | Commands::Inbox { .. }
+            | Commands::Triage { .. }
+            | Commands::Graphs { .. }
             | Commands::Peer { .. }
             | Commands::Role { .. }
             | Commands::Tradeoff { .. }
@@ -5346,8 +6829,11 @@ pub fn supports_json(cmd: &Commands) -> bool {
             | Commands::Heartbeat { .. }
             | Commands::Checkpoint { .. }
             | Commands::Artifact { .. }
+            | Commands::Link { .. }
+            | Commands::Event { .. }
             | Commands::Context { .. }
             | Commands::Next { .. }
+            | Commands::Focus { .. }
             | Commands::Trajectory { .. }
             | Commands::Agent { .. }
             | Commands::Evaluate { .. }
@@ -5369,7 +6855,9 @@ pub fn supports_json(cmd: &Commands) -> bool {
             | Commands::Cycles
             | Commands::Viz { .. }
             | Commands::Quickstart
+            | Commands::Onboard { .. }
             | Commands::DevCheck
+            | Commands::Schema { .. }
             | Commands::Status { .. }
             | Commands::Stats
             | Commands::Metrics { .. }
@@ -5381,6 +6869,7 @@ pub fn supports_json(cmd: &Commands) -> bool {
             | Commands::Model { .. }
             | Commands::Key { .. }
             | Commands::Secret { .. }
+            | Commands::Cache { .. }
             | Commands::TuiDump { .. }
     ) || {
         #[cfg(any(feature = "matrix", feature = "matrix-lite"))]
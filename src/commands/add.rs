@@ -1,10 +1,12 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
+use std::collections::BTreeMap;
 use std::path::Path;
 use workgraph::cron::{calculate_next_fire, parse_cron_expression};
 use workgraph::graph::{
-    CycleConfig, Estimate, Node, PRIORITY_CRITICAL, PRIORITY_DEFAULT, PRIORITY_HIGH, PRIORITY_IDLE,
-    PRIORITY_LOW, PRIORITY_NORMAL, Priority, Status, Task, boost_priority, parse_delay,
+    CycleConfig, DependencyEdge, Estimate, Node, PRIORITY_CRITICAL, PRIORITY_DEFAULT,
+    PRIORITY_HIGH, PRIORITY_IDLE, PRIORITY_LOW, PRIORITY_NORMAL, Priority, Status, Task,
+    boost_priority, parse_delay,
 };
 use workgraph::parser::modify_graph;
 
@@ -172,6 +174,67 @@ pub fn parse_guard_expr(expr: &str) -> Result<workgraph::graph::LoopGuard> {
     );
 }
 
+/// Apply `--lag` / `--edge-weight` values (`<dep>=<value>`) to a task's
+/// edge annotations. Each `<dep>` must be one of `after`. A lag of `0s` or
+/// a weight of `0` clears that attribute. Returns whether anything changed.
+pub(crate) fn apply_edge_specs(
+    edges: &mut BTreeMap<String, DependencyEdge>,
+    after: &[String],
+    lag: &[String],
+    weight: &[String],
+) -> Result<bool> {
+    let split = |flag: &str, spec: &str| -> Result<(String, String)> {
+        let (dep, value) = spec
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid --{} '{}'. Use <dep>=<value>", flag, spec))?;
+        let dep = dep.trim();
+        if !after.iter().any(|a| a == dep) {
+            anyhow::bail!(
+                "Invalid --{} '{}': '{}' is not a dependency (add it with --after)",
+                flag,
+                spec,
+                dep
+            );
+        }
+        Ok((dep.to_string(), value.trim().to_string()))
+    };
+
+    let mut changed = false;
+    for spec in lag {
+        let (dep, value) = split("lag", spec)?;
+        let secs = parse_delay(&value).ok_or_else(|| {
+            anyhow::anyhow!("Invalid --lag '{}'. Use a duration like 30m, 12h, 2d", spec)
+        })?;
+        let new = (secs > 0).then_some(value);
+        let edge = edges.entry(dep).or_default();
+        if edge.lag != new {
+            edge.lag = new;
+            changed = true;
+        }
+    }
+    for spec in weight {
+        let (dep, value) = split("edge-weight", spec)?;
+        let hours = value
+            .parse::<f64>()
+            .ok()
+            .filter(|h| h.is_finite() && *h >= 0.0)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid --edge-weight '{}'. Use a non-negative number of hours",
+                    spec
+                )
+            })?;
+        let new = (hours > 0.0).then_some(hours);
+        let edge = edges.entry(dep).or_default();
+        if edge.weight != new {
+            edge.weight = new;
+            changed = true;
+        }
+    }
+    edges.retain(|_, e| e.lag.is_some() || e.weight.is_some());
+    Ok(changed)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn run(
     dir: &Path,
@@ -218,11 +281,23 @@ pub fn run(
     priority: Option<&str>,
     cron: Option<&str>,
     subtask: bool,
+    expects: &[String],
+    requires: &[String],
+    due: Option<&str>,
+    waiting_on: &[String],
+    lag: &[String],
+    edge_weight: &[String],
+    max_cost: Option<f64>,
+    min_context: Option<u64>,
 ) -> Result<()> {
     if title.trim().is_empty() {
         anyhow::bail!("Task title cannot be empty");
     }
 
+    for spec in expects {
+        workgraph::contract::parse_expectation(spec)?;
+    }
+
     // Validate --subtask: requires WG_TASK_ID (must be called from within an agent context)
     let subtask_parent_id = if subtask {
         let parent_id = std::env::var("WG_TASK_ID").map_err(|_| {
@@ -331,6 +406,15 @@ pub fn run(
         None
     };
 
+    if let Some(cap) = max_cost
+        && !(cap.is_finite() && cap > 0.0)
+    {
+        anyhow::bail!("Invalid --max-cost {}. Use a positive amount in USD", cap);
+    }
+    if min_context == Some(0) {
+        anyhow::bail!("Invalid --min-context 0. Use a positive number of tokens");
+    }
+
     // Build cycle config if --max-iterations specified
     let cycle_config = if let Some(max_iter) = max_iterations {
         let guard = match cycle_guard {
@@ -393,6 +477,27 @@ pub fn run(
         None
     };
 
+    let due = due
+        .map(|d| {
+            workgraph::deadline::parse_due(d, Utc::now())
+                .map(|dt| dt.to_rfc3339())
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid due date '{}'. Use ISO 8601, YYYY-MM-DD, or a duration like 3d",
+                        d
+                    )
+                })
+        })
+        .transpose()?;
+
+    let waiting_on = waiting_on
+        .iter()
+        .map(|g| workgraph::event_gate::parse_gate(g))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut edges = BTreeMap::new();
+    apply_edge_specs(&mut edges, after, lag, edge_weight)?;
+
     // --verify is deprecated: error out with migration guidance
     if verify.is_some() {
         anyhow::bail!(
@@ -486,8 +591,26 @@ pub fn run(
             error = Some(anyhow::anyhow!("Task '{}' cannot block itself", task_id));
             return false;
         }
-        if workgraph::federation::parse_remote_ref(blocker_id).is_some() {
-            // Cross-repo dependency — validated at resolution time, not here
+        if let Some((peer, remote_id)) = workgraph::federation::parse_remote_ref(blocker_id) {
+            // Cross-repo dependency — validated at resolution time, not here.
+            // Sibling named graphs are local, so catch typos now.
+            let sibling = workgraph::graphs::resolve(dir, peer).filter(|_| {
+                workgraph::federation::load_federation_config(dir)
+                    .map(|c| !c.peers.contains_key(peer))
+                    .unwrap_or(true)
+            });
+            if let Some(sibling) = sibling
+                && !(paused || allow_phantom)
+                && workgraph::parser::load_graph(sibling.join("graph.jsonl"))
+                    .is_ok_and(|g| g.get_node(remote_id).is_none())
+            {
+                error = Some(anyhow::anyhow!(
+                    "Dependency '{}' does not exist in graph '{}'.\n  Hint: Use --paused to defer validation, or --allow-phantom to allow forward references.",
+                    remote_id,
+                    peer
+                ));
+                return false;
+            }
         } else if graph.get_node(blocker_id).is_none() {
             if paused || allow_phantom {
                 // Deferred validation: paused tasks validate at publish time,
@@ -547,19 +670,25 @@ pub fn run(
         estimate: estimate.clone(),
         before: vec![],
         after: effective_after.clone(),
-        requires: vec![],
+        edges: edges.clone(),
+        requires: requires.to_vec(),
         tags: tags.to_vec(),
         skills: skills.to_vec(),
         inputs: inputs.to_vec(),
         deliverables: deliverables.to_vec(),
+        expects: expects.to_vec(),
+        gate: None,
         artifacts: vec![],
+        self_report: None,
         exec: exec.map(String::from),
         timeout: timeout.map(String::from),
         not_before: computed_not_before.clone(),
+        due: due.clone(),
         created_at: Some(Utc::now().to_rfc3339()),
         started_at: None,
         completed_at: None,
         last_interaction_at: None,
+        revision: 0,
         log: log.clone(),
         retry_count: 0,
         max_retries,
@@ -584,10 +713,18 @@ pub fn run(
         context_scope: context_scope.map(String::from),
         exec_mode: effective_exec_mode.map(String::from),
         token_usage: None,
+        max_cost,
+        min_context,
         session_id: None,
         wait_condition: None,
         checkpoint: None,
         triage_count: 0,
+        escalation_level: 0,
+        blocked_at: None,
+        deadline_alert: None,
+        external_blocker: None,
+        waiting_on: waiting_on.clone(),
+        links: vec![],
         resurrection_count: 0,
         last_resurrected_at: None,
         validation: validation.map(String::from),
@@ -1022,19 +1159,25 @@ fn add_task_directly(
             estimate: None,
             before: vec![],
             after: after.to_vec(),
+            edges: Default::default(),
             requires: vec![],
             tags: tags.to_vec(),
             skills: skills.to_vec(),
             inputs: vec![],
             deliverables: deliverables.to_vec(),
+            expects: vec![],
+            gate: None,
             artifacts: vec![],
+            self_report: None,
             exec: None,
             timeout: None,
             not_before: None,
+            due: None,
             created_at: Some(chrono::Utc::now().to_rfc3339()),
             started_at: None,
             completed_at: None,
             last_interaction_at: None,
+            revision: 0,
             log: vec![],
             retry_count: 0,
             max_retries: None,
@@ -1059,10 +1202,20 @@ fn add_task_directly(
             cycle_config: None,
             exec_mode: None,
             token_usage: None,
+            max_cost: None,
+            min_context: None,
+            claim_sha: None,
+            changes: None,
             session_id: None,
             wait_condition: None,
             checkpoint: None,
             triage_count: 0,
+            escalation_level: 0,
+            blocked_at: None,
+            deadline_alert: None,
+            external_blocker: None,
+            waiting_on: vec![],
+            links: vec![],
             resurrection_count: 0,
             last_resurrected_at: None,
             validation: None,
@@ -1167,7 +1320,7 @@ fn default_parent_after(graph: &workgraph::WorkGraph, after: &[String]) -> Vec<S
     }
 }
 
-fn generate_id(title: &str, graph: &workgraph::WorkGraph) -> String {
+pub(crate) fn generate_id(title: &str, graph: &workgraph::WorkGraph) -> String {
     // Generate a slug from the title: take up to 3 non-numeric words,
     // plus any trailing numeric tokens (so "task 1" -> "task-1", not "task").
     let normalized: String = title
@@ -1300,6 +1453,9 @@ mod tests {
             None,
             None,
             false,
+            &[],
+            &[],
+            &[],
         )
     }
 
@@ -1311,6 +1467,33 @@ mod tests {
         .unwrap();
     }
 
+    // ---- apply_edge_specs tests ----
+
+    #[test]
+    fn edge_specs_set_and_clear() {
+        let after = vec!["build".to_string(), "deploy".to_string()];
+        let mut edges = BTreeMap::new();
+        let changed = apply_edge_specs(
+            &mut edges,
+            &after,
+            &["deploy=2d".to_string()],
+            &["build=1.5".to_string()],
+        )
+        .unwrap();
+        assert!(changed);
+        assert_eq!(edges["deploy"].lag.as_deref(), Some("2d"));
+        assert_eq!(edges["build"].weight, Some(1.5));
+
+        // Re-applying the same values is not a change; zero clears
+        assert!(!apply_edge_specs(&mut edges, &after, &["deploy=2d".to_string()], &[]).unwrap());
+        assert!(apply_edge_specs(&mut edges, &after, &[], &["build=0".to_string()]).unwrap());
+        assert!(!edges.contains_key("build"));
+
+        assert!(apply_edge_specs(&mut edges, &after, &["other=1h".to_string()], &[]).is_err());
+        assert!(apply_edge_specs(&mut edges, &after, &["deploy=soon".to_string()], &[]).is_err());
+        assert!(apply_edge_specs(&mut edges, &after, &[], &["build=-1".to_string()]).is_err());
+    }
+
     // ---- parse_guard_expr tests ----
 
     #[test]
@@ -1642,6 +1825,7 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
         );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("cannot be empty"));
@@ -1701,6 +1885,7 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
         );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("cannot be empty"));
@@ -1760,6 +1945,7 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
         );
         assert!(result.is_err());
         assert!(
@@ -1826,6 +2012,7 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
         );
         assert!(result.is_err());
         assert!(
@@ -1889,6 +2076,7 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
         );
         assert!(result.is_ok());
     }
@@ -1948,6 +2136,7 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
         );
         assert!(result.is_ok());
     }
@@ -2011,6 +2200,7 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
         );
         assert!(result.is_ok());
 
@@ -2289,6 +2479,7 @@ tier = "standard"
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
         );
         assert!(result.is_ok(), "wg add --exec should succeed: {:?}", result);
 
@@ -2355,6 +2546,7 @@ tier = "standard"
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
         );
         assert!(result.is_ok());
 
@@ -2421,6 +2613,7 @@ tier = "standard"
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
         );
         assert!(result.is_ok());
 
@@ -2528,6 +2721,7 @@ tier = "standard"
             None,
             None,
             true, // subtask
+            &[],  // expects
         );
         assert!(result.is_err());
         assert!(
@@ -2597,6 +2791,7 @@ tier = "standard"
             None,
             None,
             true, // subtask
+            &[],  // expects
         );
 
         unsafe { std::env::remove_var("WG_TASK_ID") };
@@ -2695,6 +2890,7 @@ tier = "standard"
             None,
             None,
             true, // subtask
+            &[],  // expects
         );
 
         unsafe { std::env::remove_var("WG_TASK_ID") };
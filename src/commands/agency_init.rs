@@ -83,6 +83,8 @@ pub fn run(workgraph_dir: &Path) -> Result<()> {
             deployment_history: vec![],
             attractor_weight: 0.5,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
 
         agency::save_agent(&agent, &agents_dir).context("Failed to save default agent")?;
@@ -151,6 +153,8 @@ pub fn run(workgraph_dir: &Path) -> Result<()> {
                 deployment_history: vec![],
                 attractor_weight: 0.5,
                 staleness_flags: vec![],
+                capability_doc: None,
+                daily_budget: None,
             };
 
             agency::save_agent(&agent, &agents_dir)
@@ -173,6 +173,7 @@ mod tests {
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         }
     }
 
@@ -218,6 +219,8 @@ mod tests {
             deployment_history: vec![],
             attractor_weight: 0.5,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         }
     }
 
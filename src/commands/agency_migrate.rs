@@ -373,6 +373,7 @@ fn migrate_roles(
                 lineage: old_role.lineage.clone(),
                 default_context_scope: old_role.default_context_scope.clone(),
                 default_exec_mode: None,
+                tool_policy: Default::default(),
             };
             save_role(&new_role, &roles_dir)
                 .with_context(|| format!("Failed to save role {}", short_hash(&new_role_id)))?;
@@ -503,6 +504,8 @@ fn migrate_agents(
                 deployment_history: vec![],
                 attractor_weight: 0.5,
                 staleness_flags: vec![],
+                capability_doc: old_agent.capability_doc.clone(),
+                daily_budget: old_agent.daily_budget,
             };
             save_agent(&new_agent, &agents_dir)
                 .with_context(|| format!("Failed to save agent {}", short_hash(&new_agent_id)))?;
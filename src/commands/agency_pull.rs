@@ -157,6 +157,7 @@ mod tests {
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         }
     }
 
@@ -202,6 +203,8 @@ mod tests {
             deployment_history: vec![],
             attractor_weight: 0.5,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         }
     }
 
@@ -570,6 +570,7 @@ role_component,verify-install-path,Run install command from documentation in cle
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         }
     }
 
@@ -615,6 +616,8 @@ role_component,verify-install-path,Run install command from documentation in cle
             deployment_history: vec![],
             attractor_weight: 0.5,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         }
     }
 
@@ -298,6 +298,7 @@ mod tests {
                 lineage: Lineage::default(),
                 default_context_scope: None,
                 default_exec_mode: None,
+                tool_policy: Default::default(),
             })
             .unwrap();
 
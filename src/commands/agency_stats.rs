@@ -1078,6 +1078,7 @@ mod tests {
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         }];
         let tradeoffs = vec![TradeoffConfig {
             id: "m1".into(),
@@ -7,7 +7,8 @@
 
 use anyhow::{Context, Result};
 use std::path::Path;
-use workgraph::agency::{self, Agent, Lineage, PerformanceRecord};
+use workgraph::agency::{self, Agent, CapabilityDocument, Lineage, PerformanceRecord, reputation};
+use workgraph::dispatch::budget;
 use workgraph::graph::TrustLevel;
 
 /// Get the agency agents subdirectory (creates agency structure if needed).
@@ -140,6 +141,8 @@ pub fn run_create(
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
 
     let path = agency::save_agent(&agent, &agents_dir).context("Failed to save agent")?;
@@ -284,6 +287,8 @@ pub fn run_show(workgraph_dir: &Path, id: &str, json: bool) -> Result<()> {
             "capacity": agent.capacity,
             "trust_level": agent.trust_level,
             "contact": agent.contact,
+            "capability_doc": agent.capability_doc,
+            "daily_budget": agent.daily_budget,
             "performance": {
                 "task_count": agent.performance.task_count,
                 "avg_score": agent.performance.avg_score,
@@ -346,6 +351,14 @@ pub fn run_show(workgraph_dir: &Path, id: &str, json: bool) -> Result<()> {
         if let Some(contact) = &agent.contact {
             println!("Contact: {}", contact);
         }
+        if let Some(budget) = describe_budget(workgraph_dir, &agent) {
+            println!("Daily budget: {}", budget);
+        }
+        if agent.capability_doc.is_some() {
+            println!();
+            println!("Capability document:");
+            print_capability_doc(&agent, "  ");
+        }
 
         println!();
         println!("Performance:");
@@ -669,6 +682,270 @@ pub fn run_performance(workgraph_dir: &Path, id: &str, json: bool) -> Result<()>
     Ok(())
 }
 
+/// `wg agent stats <hash> [--json]`
+///
+/// Shows the agent's track record: success rate, average evaluation score,
+/// duration vs estimate, and the reputation `reputation` dispatch ranks by.
+pub fn run_stats(workgraph_dir: &Path, id: &str, json: bool) -> Result<()> {
+    let agency_dir = workgraph_dir.join("agency");
+    let agents_dir = agency_dir.join("cache/agents");
+
+    let agent = agency::find_agent_by_prefix(&agents_dir, id)
+        .with_context(|| format!("Failed to find agent '{}'", id))?;
+
+    // Record outcomes from the current graph first, so the stats are fresh.
+    let store = match workgraph::parser::load_graph(super::graph_path(workgraph_dir)) {
+        Ok(graph) => reputation::refresh(&agency_dir, &graph),
+        Err(_) => reputation::ReputationStore::load(&agency_dir),
+    }
+    .context("Failed to load agent reputation")?;
+    let stats = reputation::agent_stats(&store, &agent);
+
+    if json {
+        let mut output = serde_json::to_value(&stats)?;
+        output["agent_name"] = serde_json::json!(agent.name);
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let pct = |v: Option<f64>| {
+        v.map(|v| format!("{:.0}%", v * 100.0))
+            .unwrap_or_else(|| "n/a".to_string())
+    };
+    println!(
+        "Stats for agent: {} ({})",
+        agent.name,
+        agency::short_hash(&agent.id)
+    );
+    println!(
+        "  Finished tasks: {} ({} succeeded, {} failed)",
+        stats.finished(),
+        stats.succeeded,
+        stats.failed
+    );
+    println!("  Success rate: {}", pct(stats.success_rate));
+    println!(
+        "  Avg score: {} ({} evaluations)",
+        stats
+            .avg_score
+            .map(|s| format!("{:.2}", s))
+            .unwrap_or_else(|| "n/a".to_string()),
+        stats.evaluations
+    );
+    println!(
+        "  Duration vs estimate: {}",
+        stats
+            .avg_duration_ratio
+            .map(|r| format!("{:.2}x", r))
+            .unwrap_or_else(|| "n/a (no estimated tasks)".to_string())
+    );
+    println!(
+        "  Reputation: {}",
+        stats
+            .reputation
+            .map(|r| format!("{:.2}", r))
+            .unwrap_or_else(|| format!("n/a (ranked as {:.2})", reputation::NEUTRAL))
+    );
+
+    Ok(())
+}
+
+/// Fields of a capability document given on the command line; each one set
+/// overrides the same field of the base document.
+#[derive(Debug, Default)]
+pub struct CapabilityFlags {
+    pub skills: Vec<String>,
+    pub models: Vec<String>,
+    pub max_context: Option<u64>,
+    pub cost_ceiling: Option<f64>,
+    pub resources: Vec<String>,
+}
+
+/// `wg agent register <hash> [--file doc.yaml] [--skill ...] [--model ...] [--max-context N] [--cost-ceiling USD] [--resource ...] [--clear]`
+///
+/// Stores the agent's capability document, which `wg assign` and the
+/// coordinator check task requirements against. The document's skills
+/// replace the agent's capabilities.
+pub fn run_register(
+    workgraph_dir: &Path,
+    id: &str,
+    file: Option<&Path>,
+    flags: CapabilityFlags,
+    clear: bool,
+    json: bool,
+) -> Result<()> {
+    let dir = agents_dir(workgraph_dir)?;
+    let mut agent = agency::find_agent_by_prefix(&dir, id)
+        .with_context(|| format!("Failed to find agent '{}'", id))?;
+
+    if clear {
+        agent.capability_doc = None;
+        agency::save_agent(&agent, &dir).context("Failed to save agent")?;
+        if json {
+            println!(
+                "{}",
+                serde_json::json!({ "id": agent.id, "capability_doc": null })
+            );
+        } else {
+            println!(
+                "Cleared capability document for agent '{}' ({})",
+                agent.name,
+                agency::short_hash(&agent.id)
+            );
+        }
+        return Ok(());
+    }
+
+    let mut doc = match file {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_yaml::from_str::<CapabilityDocument>(&text)
+                .with_context(|| format!("Invalid capability document {}", path.display()))?
+        }
+        None => agent.capability_doc.clone().unwrap_or_default(),
+    };
+    if !flags.skills.is_empty() {
+        doc.skills = flags.skills;
+    }
+    if !flags.models.is_empty() {
+        doc.models = flags.models;
+    }
+    if flags.max_context.is_some() {
+        doc.max_context = flags.max_context;
+    }
+    if flags.cost_ceiling.is_some() {
+        doc.cost_ceiling = flags.cost_ceiling;
+    }
+    if !flags.resources.is_empty() {
+        doc.resources = flags.resources;
+    }
+
+    if doc.max_context == Some(0) {
+        anyhow::bail!("Invalid max_context 0. Use a positive number of tokens");
+    }
+    if let Some(ceiling) = doc.cost_ceiling
+        && !(ceiling.is_finite() && ceiling > 0.0)
+    {
+        anyhow::bail!(
+            "Invalid cost_ceiling {}. Use a positive amount in USD",
+            ceiling
+        );
+    }
+
+    // Skills live in the agent's capabilities, where skill matching reads them.
+    if !doc.skills.is_empty() {
+        agent.capabilities = std::mem::take(&mut doc.skills);
+    }
+    agent.capability_doc = Some(doc);
+    agency::save_agent(&agent, &dir).context("Failed to save agent")?;
+
+    if json {
+        let output = serde_json::json!({
+            "id": agent.id,
+            "name": agent.name,
+            "capabilities": agent.capabilities,
+            "capability_doc": agent.capability_doc,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!(
+            "Registered capabilities for agent '{}' ({})",
+            agent.name,
+            agency::short_hash(&agent.id)
+        );
+        print_capability_doc(&agent, "  ");
+    }
+    Ok(())
+}
+
+/// `wg agent set-budget <id> --daily <USD> | --clear`
+///
+/// The service stops dispatching to the agent once its spend for the day
+/// passes the budget; see `workgraph::dispatch::budget`.
+pub fn run_set_budget(
+    workgraph_dir: &Path,
+    id: &str,
+    daily: Option<f64>,
+    clear: bool,
+    json: bool,
+) -> Result<()> {
+    if let Some(amount) = daily
+        && !(amount.is_finite() && amount > 0.0)
+    {
+        anyhow::bail!(
+            "Invalid daily budget {}. Use a positive amount in USD",
+            amount
+        );
+    }
+    let dir = agents_dir(workgraph_dir)?;
+    let mut agent = agency::find_agent_by_prefix(&dir, id)
+        .with_context(|| format!("Failed to find agent '{}'", id))?;
+    agent.daily_budget = if clear { None } else { daily };
+    agency::save_agent(&agent, &dir).context("Failed to save agent")?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({ "id": agent.id, "daily_budget": agent.daily_budget })
+        );
+    } else {
+        match describe_budget(workgraph_dir, &agent) {
+            Some(budget) => println!(
+                "Set daily budget for agent '{}' ({}): {}",
+                agent.name,
+                agency::short_hash(&agent.id),
+                budget
+            ),
+            None => println!(
+                "Cleared daily budget for agent '{}' ({})",
+                agent.name,
+                agency::short_hash(&agent.id)
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// The agent's daily budget with what it spent today, if it has one.
+fn describe_budget(workgraph_dir: &Path, agent: &Agent) -> Option<String> {
+    let budget = agent.daily_budget?;
+    let spent = workgraph::parser::load_graph(super::graph_path(workgraph_dir))
+        .map(|graph| budget::DailySpend::today(&graph, workgraph_dir).of(&agent.id))
+        .unwrap_or(0.0);
+    let over = if spent > budget { ", over budget" } else { "" };
+    Some(format!(
+        "${:.2} (${:.2} spent today{})",
+        budget, spent, over
+    ))
+}
+
+fn print_capability_doc(agent: &Agent, indent: &str) {
+    let Some(ref doc) = agent.capability_doc else {
+        return;
+    };
+    let or_none = |items: &[String], none: &str| {
+        if items.is_empty() {
+            none.to_string()
+        } else {
+            items.join(", ")
+        }
+    };
+    println!(
+        "{}Skills: {}",
+        indent,
+        or_none(&agent.capabilities, "(none)")
+    );
+    println!("{}Models: {}", indent, or_none(&doc.models, "any"));
+    if let Some(tokens) = doc.max_context {
+        println!("{}Max context: {} tokens", indent, tokens);
+    }
+    if let Some(ceiling) = doc.cost_ceiling {
+        println!("{}Cost ceiling: ${:.2} per task", indent, ceiling);
+    }
+    println!("{}Resources: {}", indent, or_none(&doc.resources, "(none)"));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -966,4 +1243,119 @@ mod tests {
         run_performance(tmp.path(), agent_id, false).unwrap();
         run_performance(tmp.path(), agent_id, true).unwrap();
     }
+
+    #[test]
+    fn test_stats_records_outcomes() {
+        use workgraph::graph::{Node, Status, Task, WorkGraph};
+
+        let tmp = setup();
+        let role_id = create_role(tmp.path());
+        let mot_id = create_tradeoff(tmp.path());
+        create_agent(tmp.path(), "Stats Agent", &role_id, &mot_id).unwrap();
+        let agents_dir = tmp.path().join("agency").join("cache/agents");
+        let agent = agency::load_all_agents(&agents_dir).unwrap().remove(0);
+
+        // No graph yet: stats still work, with no history.
+        run_stats(tmp.path(), &agent.id, false).unwrap();
+
+        let mut graph = WorkGraph::new();
+        for (id, status) in [("a", Status::Done), ("b", Status::Failed)] {
+            graph.add_node(Node::Task(Task {
+                id: id.to_string(),
+                title: id.to_string(),
+                status,
+                agent: Some(agent.id.clone()),
+                ..Task::default()
+            }));
+        }
+        workgraph::parser::save_graph(&graph, super::super::graph_path(tmp.path())).unwrap();
+
+        run_stats(tmp.path(), &agent.id, true).unwrap();
+        let store = reputation::ReputationStore::load(&tmp.path().join("agency")).unwrap();
+        let stats = reputation::agent_stats(&store, &agent);
+        assert_eq!((stats.succeeded, stats.failed), (1, 1));
+        assert_eq!(stats.success_rate, Some(0.5));
+    }
+
+    #[test]
+    fn test_register_capability_document() {
+        let tmp = setup();
+        let role_id = create_role(tmp.path());
+        let mot_id = create_tradeoff(tmp.path());
+        create_agent(tmp.path(), "Doc Agent", &role_id, &mot_id).unwrap();
+        let agents_dir = tmp.path().join("agency").join("cache/agents");
+        let agent_id = agency::load_all_agents(&agents_dir).unwrap()[0].id.clone();
+
+        let file = tmp.path().join("caps.yaml");
+        std::fs::write(
+            &file,
+            "skills: [rust, sql]\nmodels: [opus]\nmax_context: 200000\nresources: [prod-db]\n",
+        )
+        .unwrap();
+        let flags = CapabilityFlags {
+            cost_ceiling: Some(5.0),
+            ..Default::default()
+        };
+        run_register(tmp.path(), &agent_id, Some(&file), flags, false, false).unwrap();
+
+        let agent = agency::find_agent_by_prefix(&agents_dir, &agent_id).unwrap();
+        assert_eq!(agent.capabilities, vec!["rust", "sql"]);
+        let doc = agent.capability_doc.clone().unwrap();
+        assert!(doc.skills.is_empty());
+        assert_eq!(doc.models, vec!["opus"]);
+        assert_eq!(doc.max_context, Some(200_000));
+        assert_eq!(doc.cost_ceiling, Some(5.0));
+        assert_eq!(doc.resources, vec!["prod-db"]);
+        run_show(tmp.path(), &agent_id, false).unwrap();
+
+        // Flags alone update the stored document.
+        let flags = CapabilityFlags {
+            models: vec!["sonnet".to_string()],
+            ..Default::default()
+        };
+        run_register(tmp.path(), &agent_id, None, flags, false, true).unwrap();
+        let agent = agency::find_agent_by_prefix(&agents_dir, &agent_id).unwrap();
+        let doc = agent.capability_doc.unwrap();
+        assert_eq!(doc.models, vec!["sonnet"]);
+        assert_eq!(doc.resources, vec!["prod-db"]);
+
+        let flags = CapabilityFlags {
+            cost_ceiling: Some(-1.0),
+            ..Default::default()
+        };
+        assert!(run_register(tmp.path(), &agent_id, None, flags, false, false).is_err());
+
+        run_register(
+            tmp.path(),
+            &agent_id,
+            None,
+            CapabilityFlags::default(),
+            true,
+            false,
+        )
+        .unwrap();
+        let agent = agency::find_agent_by_prefix(&agents_dir, &agent_id).unwrap();
+        assert!(agent.capability_doc.is_none());
+    }
+
+    #[test]
+    fn test_set_budget() {
+        let tmp = setup();
+        let role_id = create_role(tmp.path());
+        let mot_id = create_tradeoff(tmp.path());
+        create_agent(tmp.path(), "Budget Agent", &role_id, &mot_id).unwrap();
+        let agents_dir = tmp.path().join("agency").join("cache/agents");
+        let agent_id = agency::load_all_agents(&agents_dir).unwrap()[0].id.clone();
+
+        run_set_budget(tmp.path(), &agent_id, Some(5.0), false, false).unwrap();
+        let agent = agency::find_agent_by_prefix(&agents_dir, &agent_id).unwrap();
+        assert_eq!(agent.daily_budget, Some(5.0));
+        run_show(tmp.path(), &agent_id, false).unwrap();
+
+        assert!(run_set_budget(tmp.path(), &agent_id, Some(0.0), false, false).is_err());
+
+        run_set_budget(tmp.path(), &agent_id, None, true, true).unwrap();
+        let agent = agency::find_agent_by_prefix(&agents_dir, &agent_id).unwrap();
+        assert_eq!(agent.daily_budget, None);
+    }
 }
@@ -0,0 +1,99 @@
+//! `wg agent shell` — open an interactive shell in the environment an agent
+//! ran with.
+//!
+//! Usage:
+//!   wg agent shell <task-id>     # the task's most recent agent
+//!   wg agent shell agent-42
+//!
+//! The environment comes from the `env.json` recorded when the agent was
+//! spawned: the same working directory (usually the task's worktree), the
+//! variables the executor set, and the environment the daemon passed down.
+//! Endpoint API keys are resolved again from their endpoint; other
+//! secret-looking variables are taken from the caller's environment.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use workgraph::service::registry::AgentRegistry;
+
+use super::spawn::agent_env::AgentEnv;
+use super::spawn::agent_output_dir;
+
+/// Resolve an agent ID, or a task ID to the agent that last ran it.
+fn resolve_agent(dir: &Path, target: &str) -> Result<String> {
+    let registry = AgentRegistry::load_or_warn(dir);
+    if registry.agents.contains_key(target) || agent_output_dir(dir, target).is_dir() {
+        return Ok(target.to_string());
+    }
+    registry
+        .agents
+        .values()
+        .filter(|a| a.task_id == target)
+        .max_by(|a, b| a.started_at.cmp(&b.started_at))
+        .map(|a| a.id.clone())
+        .with_context(|| format!("No agent or task '{}' with a spawned agent found", target))
+}
+
+pub fn run(dir: &Path, target: &str, json: bool) -> Result<()> {
+    let agent_id = resolve_agent(dir, target)?;
+    let mut env = AgentEnv::load(&agent_output_dir(dir, &agent_id))?.with_context(|| {
+        format!(
+            "{} has no recorded environment (it was spawned by an older wg). \
+             Retry the task to record one.",
+            agent_id
+        )
+    })?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&env)?);
+        return Ok(());
+    }
+
+    if let Some(ref cwd) = env.cwd
+        && !cwd.is_dir()
+    {
+        anyhow::bail!(
+            "{}'s working directory {} no longer exists (its worktree was cleaned up)",
+            agent_id,
+            cwd.display()
+        );
+    }
+    if let Err(e) = env.resolve_secrets(dir) {
+        eprintln!("Warning: endpoint API key not set in the shell: {:#}", e);
+    }
+
+    let shell = env
+        .get("SHELL")
+        .map(String::from)
+        .or_else(|| std::env::var("SHELL").ok())
+        .unwrap_or_else(|| "bash".to_string());
+    let mut cmd = Command::new(&shell);
+    let missing = env.apply_isolated(&mut cmd);
+    cmd.env("WG_AGENT_SHELL", &agent_id);
+
+    println!(
+        "Entering {}'s environment (task {}) in {}",
+        agent_id,
+        env.get("WG_TASK_ID").unwrap_or("?"),
+        env.cwd.as_deref().map_or_else(
+            || "the current directory".to_string(),
+            |p| p.display().to_string()
+        )
+    );
+    if !missing.is_empty() {
+        println!(
+            "Not set (redacted when recorded, and absent from your environment): {}",
+            missing.join(", ")
+        );
+    }
+    println!("Exit the shell to return.");
+
+    let status = cmd
+        .status()
+        .with_context(|| format!("Failed to start {}", shell))?;
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
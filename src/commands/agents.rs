@@ -426,6 +426,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
 
         let status = effective_status(&agent);
@@ -450,6 +452,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
 
         let status = effective_status(&agent);
@@ -473,6 +477,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
 
         let status = effective_status(&agent);
@@ -2,7 +2,9 @@ use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
 use std::path::Path;
+use workgraph::escalation::{self, EscalationAction};
 use workgraph::graph::{Status, Task, WorkGraph};
+use workgraph::notify::config::NotifyConfig;
 
 /// Age bucket categories
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -105,10 +107,12 @@ struct AgingOutput {
     oldest_tasks: Vec<OldTaskJson>,
     stale_in_progress: Vec<StaleTaskJson>,
     tasks_with_unknown_age: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    escalations: Option<Vec<EscalationAction>>,
 }
 
-pub fn run(dir: &Path, json: bool) -> Result<()> {
-    let (graph, _path) = super::load_workgraph(dir)?;
+pub fn run(dir: &Path, escalate: bool, dry_run: bool, json: bool) -> Result<()> {
+    let (graph, path) = super::load_workgraph(dir)?;
     let now = Utc::now();
 
     // Collect open/in-progress tasks with their ages
@@ -123,12 +127,27 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
     // Find stale in-progress tasks (started > 14 days ago)
     let stale_in_progress = find_stale_in_progress(&graph, &now, 14);
 
+    let escalations = if escalate {
+        let config = NotifyConfig::load(dir.parent())?.unwrap_or_default();
+        if config.escalation.chain.is_empty() && !json {
+            eprintln!("No escalation chain configured (add [[escalation.chain]] to notify.toml)");
+        }
+        let actions = escalation::run_sweep(dir, &path, &config, dry_run)?;
+        if !actions.is_empty() && !dry_run {
+            super::notify_graph_changed(dir);
+        }
+        Some(actions)
+    } else {
+        None
+    };
+
     if json {
         output_json(
             &distribution,
             &oldest_tasks,
             &stale_in_progress,
             unknown_age_count,
+            escalations,
         );
     } else {
         output_text(
@@ -137,6 +156,9 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
             &stale_in_progress,
             unknown_age_count,
         );
+        if let Some(ref actions) = escalations {
+            output_escalations(actions, dry_run);
+        }
     }
 
     Ok(())
@@ -155,6 +177,10 @@ fn collect_task_ages<'a>(
         if task.status != Status::Open && task.status != Status::InProgress {
             continue;
         }
+        // Tasks blocked outside the project are tracked by `wg waiting`
+        if task.external_blocker.is_some() {
+            continue;
+        }
 
         if let Some(ref created_at_str) = task.created_at {
             if let Ok(created_at) = DateTime::parse_from_rfc3339(created_at_str) {
@@ -221,7 +247,7 @@ fn find_stale_in_progress<'a>(
     let threshold = Duration::days(threshold_days);
 
     for task in graph.tasks() {
-        if task.status != Status::InProgress {
+        if task.status != Status::InProgress || task.external_blocker.is_some() {
             continue;
         }
 
@@ -342,11 +368,36 @@ fn output_text(
     }
 }
 
+fn output_escalations(actions: &[EscalationAction], dry_run: bool) {
+    if actions.is_empty() {
+        println!("\nEscalations: none due");
+        return;
+    }
+    let verb = if dry_run {
+        "would escalate"
+    } else {
+        "escalated"
+    };
+    println!("\nEscalations:");
+    for action in actions {
+        let who = action
+            .recipient
+            .as_ref()
+            .map(|r| format!(" {}", r))
+            .unwrap_or_default();
+        println!(
+            "  - {} ({} {}/{}): {} to {}{}",
+            action.task_id, action.status, action.level, action.chain_len, verb, action.target, who
+        );
+    }
+}
+
 fn output_json(
     distribution: &[(AgeBucket, usize); 5],
     oldest_tasks: &[&TaskAgeInfo],
     stale_in_progress: &[StaleInProgressInfo],
     unknown_age_count: usize,
+    escalations: Option<Vec<EscalationAction>>,
 ) {
     let distribution_json: Vec<AgeBucketJson> = distribution
         .iter()
@@ -383,6 +434,7 @@ fn output_json(
         oldest_tasks: oldest_json,
         stale_in_progress: stale_json,
         tasks_with_unknown_age: unknown_age_count,
+        escalations,
     };
 
     match serde_json::to_string_pretty(&output) {
@@ -616,7 +668,7 @@ mod tests {
         let graph = WorkGraph::new();
         save_graph(&graph, &graph_file).unwrap();
 
-        let result = run(graph_file.parent().unwrap(), false);
+        let result = run(graph_file.parent().unwrap(), false, false, false);
         assert!(result.is_ok());
     }
 
@@ -632,7 +684,7 @@ mod tests {
 
         save_graph(&graph, &graph_file).unwrap();
 
-        let result = run(graph_file.parent().unwrap(), false);
+        let result = run(graph_file.parent().unwrap(), false, false, false);
         assert!(result.is_ok());
     }
 
@@ -648,17 +700,43 @@ mod tests {
 
         save_graph(&graph, &graph_file).unwrap();
 
-        let result = run(graph_file.parent().unwrap(), true);
+        let result = run(graph_file.parent().unwrap(), false, false, true);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_run_escalate_fires_chain_step() {
+        let (_tmp, graph_file) = setup_test_graph();
+        let wg_dir = graph_file.parent().unwrap();
+        std::fs::write(
+            wg_dir.join("notify.toml"),
+            "[[escalation.chain]]\nafter = \"1h\"\nnotify = \"assignee\"\n",
+        )
+        .unwrap();
+        let mut graph = WorkGraph::new();
+        let mut t1 = make_task("t1", "Task 1");
+        t1.status = Status::InProgress;
+        t1.assigned = Some("agent-1".to_string());
+        t1.started_at = Some((Utc::now() - Duration::hours(3)).to_rfc3339());
+        graph.add_node(Node::Task(t1));
+        save_graph(&graph, &graph_file).unwrap();
+
+        run(wg_dir, true, true, false).unwrap();
+        let graph = workgraph::parser::load_graph(&graph_file).unwrap();
+        assert_eq!(graph.get_task("t1").unwrap().escalation_level, 0);
+
+        run(wg_dir, true, false, false).unwrap();
+        let graph = workgraph::parser::load_graph(&graph_file).unwrap();
+        assert_eq!(graph.get_task("t1").unwrap().escalation_level, 1);
+    }
+
     #[test]
     fn test_run_no_workgraph_initialized() {
         let tmp = TempDir::new().unwrap();
         let workgraph_dir = tmp.path().join(".wg");
         // Don't create the directory
 
-        let result = run(&workgraph_dir, false);
+        let result = run(&workgraph_dir, false, false, false);
         assert!(result.is_err());
     }
 }
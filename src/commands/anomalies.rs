@@ -0,0 +1,84 @@
+//! `wg anomalies` — overruns, fast low-scored work and failure spikes.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+use workgraph::anomaly::{self, Anomaly, AnomalyKind};
+use workgraph::notify::config::NotifyConfig;
+
+#[derive(Debug, Serialize)]
+struct AlertSent {
+    #[serde(flatten)]
+    anomaly: Anomaly,
+    outcome: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AnomaliesOutput {
+    anomalies: Vec<Anomaly>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alerts_sent: Option<Vec<AlertSent>>,
+}
+
+pub fn run(dir: &Path, notify: bool, json: bool) -> Result<()> {
+    let (_graph, path) = super::load_workgraph(dir)?;
+    let anomalies = anomaly::detect_in(dir, &path)?;
+
+    let alerts_sent = if notify {
+        let config = NotifyConfig::load(dir.parent())?;
+        let sent = anomaly::run_sweep(dir, &path, config.as_ref(), false)?;
+        Some(
+            sent.into_iter()
+                .map(|(anomaly, outcome)| AlertSent { anomaly, outcome })
+                .collect::<Vec<_>>(),
+        )
+    } else {
+        None
+    };
+
+    if json {
+        let output = AnomaliesOutput {
+            anomalies,
+            alerts_sent,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if anomalies.is_empty() {
+        println!("No anomalies");
+    }
+    for (kind, heading) in [
+        (AnomalyKind::Overrun, "Tasks over their estimate"),
+        (
+            AnomalyKind::FastLowScore,
+            "Agents finishing fast with low scores",
+        ),
+        (AnomalyKind::FailureSpike, "Failure spikes by tag"),
+    ] {
+        let found: Vec<&Anomaly> = anomalies.iter().filter(|a| a.kind == kind).collect();
+        if found.is_empty() {
+            continue;
+        }
+        println!("{} ({}):", heading, found.len());
+        for a in found {
+            println!("  {} — {}", a.subject, a.detail);
+            if kind != AnomalyKind::Overrun {
+                println!("    tasks: {}", a.tasks.join(", "));
+            }
+        }
+    }
+
+    if let Some(ref sent) = alerts_sent {
+        if sent.is_empty() {
+            println!("\nAlerts: none new");
+        } else {
+            println!("\nAlerts sent:");
+            for s in sent {
+                println!("  - {} ({})", s.anomaly.key(), s.outcome);
+            }
+        }
+    }
+
+    Ok(())
+}
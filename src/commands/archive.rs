@@ -1,17 +1,12 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
-use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader, Write};
 use std::path::Path;
+use workgraph::archive;
 use workgraph::graph::{Node, Status, Task};
 use workgraph::parser::{load_graph, modify_graph};
 
 use super::graph_path;
 
-fn archive_path(dir: &Path) -> std::path::PathBuf {
-    dir.join("archive.jsonl")
-}
-
 fn last_batch_path(dir: &Path) -> std::path::PathBuf {
     dir.join("archive-last-batch.json")
 }
@@ -114,78 +109,9 @@ pub fn has_active_dependents(task: &Task, graph: &workgraph::graph::WorkGraph) -
     false
 }
 
-/// Append tasks to the archive file
-fn append_to_archive(tasks: &[Task], archive_path: &Path) -> Result<()> {
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(archive_path)
-        .with_context(|| format!("Failed to open archive file: {:?}", archive_path))?;
-
-    for task in tasks {
-        let node = Node::Task(task.clone());
-        let json = serde_json::to_string(&node)
-            .with_context(|| format!("Failed to serialize task: {}", task.id))?;
-        writeln!(file, "{}", json)?;
-    }
-
-    Ok(())
-}
-
-/// Load archived tasks from the archive file
-fn load_archive(archive_path: &Path) -> Result<Vec<Task>> {
-    if !archive_path.exists() {
-        return Ok(Vec::new());
-    }
-
-    let file = File::open(archive_path)
-        .with_context(|| format!("Failed to open archive file: {:?}", archive_path))?;
-    let reader = BufReader::new(file);
-    let mut tasks = Vec::new();
-
-    for (line_num, line) in reader.lines().enumerate() {
-        let line = line?;
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-        let node: Node = serde_json::from_str(trimmed).with_context(|| {
-            format!("Failed to parse archive line {}: {}", line_num + 1, trimmed)
-        })?;
-        if let Node::Task(task) = node {
-            tasks.push(task);
-        }
-    }
-
-    Ok(tasks)
-}
-
-/// Rewrite the archive file, excluding a specific task by ID.
-fn remove_from_archive(archive_path: &Path, task_id: &str) -> Result<()> {
-    let tasks = load_archive(archive_path)?;
-    // Rewrite the file with all tasks except the one being restored
-    let file = File::create(archive_path).with_context(|| {
-        format!(
-            "Failed to open archive file for writing: {:?}",
-            archive_path
-        )
-    })?;
-    let mut writer = std::io::BufWriter::new(file);
-    for task in &tasks {
-        if task.id != task_id {
-            let node = Node::Task(task.clone());
-            let json = serde_json::to_string(&node)
-                .with_context(|| format!("Failed to serialize task: {}", task.id))?;
-            writeln!(writer, "{}", json)?;
-        }
-    }
-    Ok(())
-}
-
 /// Search archived tasks by title, description, and tags.
 pub fn search(dir: &Path, query: &str, limit: usize, json: bool) -> Result<()> {
-    let arch_path = archive_path(dir);
-    let tasks = load_archive(&arch_path)?;
+    let tasks = archive::load_all(dir)?;
     let query_lower = query.to_lowercase();
 
     let matches: Vec<&Task> = tasks
@@ -246,17 +172,12 @@ pub fn search(dir: &Path, query: &str, limit: usize, json: bool) -> Result<()> {
 /// Restore an archived task back into the active graph.
 pub fn restore(dir: &Path, task_id: &str, reopen: bool) -> Result<()> {
     let path = graph_path(dir);
-    let arch_path = archive_path(dir);
 
     if !path.exists() {
         anyhow::bail!("WG not initialized. Run 'wg init' first.");
     }
 
-    let tasks = load_archive(&arch_path)?;
-    let task = tasks
-        .iter()
-        .find(|t| t.id == task_id)
-        .cloned()
+    let task = archive::find(dir, task_id)?
         .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in archive", task_id))?;
 
     let mut restored_task = task;
@@ -286,7 +207,7 @@ pub fn restore(dir: &Path, task_id: &str, reopen: bool) -> Result<()> {
     }
 
     // Remove from archive
-    remove_from_archive(&arch_path, task_id)?;
+    archive::remove(dir, &[task_id.to_string()])?;
 
     super::notify_graph_changed(dir);
 
@@ -302,7 +223,6 @@ pub fn restore(dir: &Path, task_id: &str, reopen: bool) -> Result<()> {
 /// Undo the last archive operation by restoring all tasks from the last batch.
 pub fn undo(dir: &Path) -> Result<()> {
     let path = graph_path(dir);
-    let arch_path = archive_path(dir);
 
     if !path.exists() {
         anyhow::bail!("WG not initialized. Run 'wg init' first.");
@@ -313,7 +233,7 @@ pub fn undo(dir: &Path) -> Result<()> {
         anyhow::bail!("No tasks in the last archive batch to restore.");
     }
 
-    let archived_tasks = load_archive(&arch_path)?;
+    let archived_tasks = archive::load_all(dir)?;
 
     let mut restored_count = 0;
     let mut skipped = Vec::new();
@@ -321,7 +241,7 @@ pub fn undo(dir: &Path) -> Result<()> {
     // Pre-compute which tasks to restore (need archive removal outside closure)
     let mut to_restore: Vec<(String, Task)> = Vec::new();
     for task_id in &task_ids {
-        if let Some(task) = archived_tasks.iter().find(|t| &t.id == task_id) {
+        if let Some(task) = archived_tasks.iter().rev().find(|t| &t.id == task_id) {
             to_restore.push((task_id.clone(), task.clone()));
         } else {
             skipped.push(task_id.clone());
@@ -344,11 +264,12 @@ pub fn undo(dir: &Path) -> Result<()> {
     .context("Failed to modify graph")?;
 
     // Remove restored tasks from archive
-    for (task_id, _) in &to_restore {
-        if !skipped.contains(task_id) {
-            remove_from_archive(&arch_path, task_id)?;
-        }
-    }
+    let restored_ids: Vec<String> = to_restore
+        .iter()
+        .map(|(task_id, _)| task_id.clone())
+        .filter(|task_id| !skipped.contains(task_id))
+        .collect();
+    archive::remove(dir, &restored_ids)?;
     super::notify_graph_changed(dir);
 
     // Remove the batch metadata file since undo is done
@@ -379,7 +300,6 @@ pub fn run(
     json: bool,
 ) -> Result<()> {
     let path = graph_path(dir);
-    let arch_path = archive_path(dir);
 
     if !path.exists() {
         anyhow::bail!("WG not initialized. Run 'wg init' first.");
@@ -387,7 +307,7 @@ pub fn run(
 
     // Handle --list: show archived tasks
     if list {
-        let tasks = load_archive(&arch_path)?;
+        let tasks = archive::load_all(dir)?;
         if json {
             let items: Vec<serde_json::Value> = tasks
                 .iter()
@@ -486,8 +406,8 @@ pub fn run(
     }
 
     // Perform the archive operation
-    // 1. Append tasks to archive file
-    append_to_archive(&tasks_to_archive, &arch_path)?;
+    // 1. Append tasks to their monthly archive partitions
+    let partitions = archive::append(dir, &tasks_to_archive)?;
 
     // 2. Save batch metadata for undo
     let archived_ids: Vec<String> = tasks_to_archive.iter().map(|t| t.id.clone()).collect();
@@ -512,13 +432,14 @@ pub fn run(
         "archive",
         None,
         None,
-        serde_json::json!({ "task_ids": task_ids }),
+        serde_json::json!({ "task_ids": task_ids, "partitions": partitions }),
         config.log.rotation_threshold,
     );
 
     println!(
-        "Archived {} tasks. Use `wg archive --undo` to reverse.",
+        "Archived {} tasks to archive/{{{}}}.jsonl. Use `wg archive --undo` to reverse.",
         tasks_to_archive.len(),
+        partitions.join(","),
     );
 
     Ok(())
@@ -539,7 +460,6 @@ pub fn run_automatic(dir: &Path, retention_days: u64) -> Result<usize> {
 
     let older_duration = Duration::days(retention_days as i64);
     let graph = load_graph(&path).context("Failed to load graph")?;
-    let arch_path = archive_path(dir);
 
     // Find archivable tasks: done/abandoned, old enough, no active dependents, not system tasks
     let tasks_to_archive: Vec<Task> = graph
@@ -554,8 +474,8 @@ pub fn run_automatic(dir: &Path, retention_days: u64) -> Result<usize> {
         return Ok(0);
     }
 
-    // Append to archive
-    append_to_archive(&tasks_to_archive, &arch_path)?;
+    // Append to the monthly archive partitions
+    let partitions = archive::append(dir, &tasks_to_archive)?;
 
     // Save batch metadata for undo
     let archived_ids: Vec<String> = tasks_to_archive.iter().map(|t| t.id.clone()).collect();
@@ -580,7 +500,7 @@ pub fn run_automatic(dir: &Path, retention_days: u64) -> Result<usize> {
         "archive",
         None,
         None,
-        serde_json::json!({ "task_ids": task_ids, "automatic": true, "retention_days": retention_days }),
+        serde_json::json!({ "task_ids": task_ids, "partitions": partitions, "automatic": true, "retention_days": retention_days }),
         config.log.rotation_threshold,
     );
 
@@ -668,9 +588,9 @@ mod tests {
             make_task("t2", "Task 2", Status::Done, Some("2024-01-02T00:00:00Z")),
         ];
 
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
-        let loaded = load_archive(&arch_path).unwrap();
+        let loaded = archive::load_file(&arch_path).unwrap();
         assert_eq!(loaded.len(), 2);
         assert_eq!(loaded[0].id, "t1");
         assert_eq!(loaded[1].id, "t2");
@@ -703,9 +623,8 @@ mod tests {
         let loaded = load_graph(&graph_file).unwrap();
         assert_eq!(loaded.tasks().count(), 2);
 
-        // Verify no archive file created
-        let arch_path = wg_dir.join("archive.jsonl");
-        assert!(!arch_path.exists());
+        // Verify nothing was archived
+        assert!(archive::load_all(wg_dir).unwrap().is_empty());
     }
 
     #[test]
@@ -738,8 +657,7 @@ mod tests {
         assert!(loaded.get_task("t2").is_some());
 
         // Verify done task is in archive
-        let arch_path = wg_dir.join("archive.jsonl");
-        let archived = load_archive(&arch_path).unwrap();
+        let archived = archive::load_all(&wg_dir).unwrap();
         assert_eq!(archived.len(), 1);
         assert_eq!(archived[0].id, "t1");
     }
@@ -765,7 +683,7 @@ mod tests {
             Status::Done,
             Some("2024-01-01T00:00:00Z"),
         )];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Run list - should not error
         run(wg_dir, false, None, true, false, &[], false).unwrap();
@@ -800,7 +718,7 @@ mod tests {
                 Some("2024-02-15T12:00:00Z"),
             ),
         ];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Run list with json=true (output goes to stdout, just verify no error)
         run(wg_dir, false, None, true, false, &[], true).unwrap();
@@ -854,13 +772,13 @@ mod tests {
                 Some("2024-01-03T00:00:00Z"),
             ),
         ];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Search should find tasks matching by title
         search(wg_dir, "login", 20, false).unwrap();
 
         // Verify by loading and filtering manually
-        let loaded = load_archive(&arch_path).unwrap();
+        let loaded = archive::load_all(&wg_dir).unwrap();
         let matches: Vec<_> = loaded
             .iter()
             .filter(|t| t.title.to_lowercase().contains("login"))
@@ -897,12 +815,12 @@ mod tests {
                 vec![],
             ),
         ];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Should find by description content
         search(wg_dir, "authentication", 20, false).unwrap();
 
-        let loaded = load_archive(&arch_path).unwrap();
+        let loaded = archive::load_all(&wg_dir).unwrap();
         let matches: Vec<_> = loaded
             .iter()
             .filter(|t| {
@@ -944,12 +862,12 @@ mod tests {
                 vec!["backend"],
             ),
         ];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Search by tag
         search(wg_dir, "frontend", 20, false).unwrap();
 
-        let loaded = load_archive(&arch_path).unwrap();
+        let loaded = archive::load_all(&wg_dir).unwrap();
         let matches: Vec<_> = loaded
             .iter()
             .filter(|t| {
@@ -977,12 +895,12 @@ mod tests {
             Status::Done,
             Some("2024-01-01T00:00:00Z"),
         )];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Case-insensitive search
         search(wg_dir, "important", 20, false).unwrap();
 
-        let loaded = load_archive(&arch_path).unwrap();
+        let loaded = archive::load_all(&wg_dir).unwrap();
         let matches: Vec<_> = loaded
             .iter()
             .filter(|t| t.title.to_lowercase().contains("important"))
@@ -1019,7 +937,7 @@ mod tests {
                 Some("2024-01-03T00:00:00Z"),
             ),
         ];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Search with limit=1 should not error
         search(wg_dir, "test", 1, false).unwrap();
@@ -1040,7 +958,7 @@ mod tests {
             Status::Done,
             Some("2024-01-01T00:00:00Z"),
         )];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // JSON output should not error
         search(wg_dir, "test", 20, true).unwrap();
@@ -1061,7 +979,7 @@ mod tests {
             Status::Done,
             Some("2024-01-01T00:00:00Z"),
         )];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // No matches
         search(wg_dir, "nonexistent", 20, false).unwrap();
@@ -1093,7 +1011,7 @@ mod tests {
                 Some("2024-01-02T00:00:00Z"),
             ),
         ];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Restore t1 without --reopen
         restore(wg_dir, "t1", false).unwrap();
@@ -1105,7 +1023,7 @@ mod tests {
         assert_eq!(task.title, "Archived Task");
 
         // Verify task is removed from archive
-        let archived = load_archive(&arch_path).unwrap();
+        let archived = archive::load_all(&wg_dir).unwrap();
         assert_eq!(archived.len(), 1);
         assert_eq!(archived[0].id, "t2");
     }
@@ -1126,7 +1044,7 @@ mod tests {
             Status::Done,
             Some("2024-01-01T00:00:00Z"),
         )];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Restore with --reopen
         restore(wg_dir, "t1", true).unwrap();
@@ -1138,7 +1056,7 @@ mod tests {
         assert!(task.completed_at.is_none());
 
         // Verify archive is now empty
-        let archived = load_archive(&arch_path).unwrap();
+        let archived = archive::load_all(&wg_dir).unwrap();
         assert!(archived.is_empty());
     }
 
@@ -1158,7 +1076,7 @@ mod tests {
             Status::Done,
             Some("2024-01-01T00:00:00Z"),
         )];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Restoring a nonexistent task should fail
         let result = restore(wg_dir, "nonexistent", false);
@@ -1196,7 +1114,7 @@ mod tests {
             Status::Done,
             Some("2024-01-01T00:00:00Z"),
         )];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Should fail because t1 already exists in graph
         let result = restore(wg_dir, "t1", false);
@@ -1214,12 +1132,12 @@ mod tests {
             make_task("t2", "Task 2", Status::Done, Some("2024-01-02T00:00:00Z")),
             make_task("t3", "Task 3", Status::Done, Some("2024-01-03T00:00:00Z")),
         ];
-        append_to_archive(&tasks, &arch_path).unwrap();
+        archive::append_file(&tasks, &arch_path).unwrap();
 
         // Remove t2
-        remove_from_archive(&arch_path, "t2").unwrap();
+        archive::remove(dir.path(), &["t2".to_string()]).unwrap();
 
-        let remaining = load_archive(&arch_path).unwrap();
+        let remaining = archive::load_file(&arch_path).unwrap();
         assert_eq!(remaining.len(), 2);
         assert_eq!(remaining[0].id, "t1");
         assert_eq!(remaining[1].id, "t3");
@@ -1266,8 +1184,7 @@ mod tests {
         assert!(loaded.get_task("t3").is_none());
         assert!(loaded.get_task("t4").is_some());
 
-        let arch_path = wg_dir.join("archive.jsonl");
-        let archived = load_archive(&arch_path).unwrap();
+        let archived = archive::load_all(&wg_dir).unwrap();
         assert_eq!(archived.len(), 2);
         let archived_ids: Vec<&str> = archived.iter().map(|t| t.id.as_str()).collect();
         assert!(archived_ids.contains(&"t1"));
@@ -1377,8 +1294,7 @@ mod tests {
         assert!(loaded.get_task("t3").is_some());
 
         // Verify archive is now empty for those tasks
-        let arch_path = wg_dir.join("archive.jsonl");
-        let archived = load_archive(&arch_path).unwrap();
+        let archived = archive::load_all(&wg_dir).unwrap();
         assert!(archived.is_empty());
 
         // Verify batch metadata file is removed
@@ -1527,8 +1443,7 @@ mod tests {
         assert!(loaded.get_task("t3").is_some()); // recent done, not archived
 
         // Verify archived task is in archive file
-        let arch_path = wg_dir.join("archive.jsonl");
-        let archived = load_archive(&arch_path).unwrap();
+        let archived = archive::load_all(&wg_dir).unwrap();
         assert_eq!(archived.len(), 1);
         assert_eq!(archived[0].id, "t1");
     }
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use std::path::Path;
 use workgraph::parser::modify_graph;
+use workgraph::test_results;
 
 #[cfg(test)]
 use super::graph_path;
@@ -33,6 +34,8 @@ pub fn run_add(dir: &Path, task_id: &str, artifact_path: &str) -> Result<()> {
     if let Some(e) = error {
         return Err(e);
     }
+    // Re-adding a test report refreshes its recorded results.
+    ingest_test_report(dir, task_id, artifact_path);
     if already_registered {
         println!(
             "Artifact '{}' already registered for task '{}'",
@@ -60,6 +63,45 @@ pub fn run_add(dir: &Path, task_id: &str, artifact_path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Parse a JUnit XML or cargo test report artifact and record its results
+/// for the task. Paths are tried relative to the current directory first
+/// (agents run in worktrees), then to the project root.
+fn ingest_test_report(dir: &Path, task_id: &str, artifact_path: &str) {
+    if !test_results::is_test_report_path(artifact_path) {
+        return;
+    }
+    let project_root = dir.parent().unwrap_or(dir);
+    let candidate = Path::new(artifact_path);
+    let file = if candidate.exists() {
+        candidate.to_path_buf()
+    } else {
+        project_root.join(artifact_path)
+    };
+    let Ok(content) = std::fs::read_to_string(&file) else {
+        return;
+    };
+    let Some(cases) = test_results::parse(&content) else {
+        return;
+    };
+    let report = test_results::TestReport::new(
+        artifact_path,
+        test_results::working_tree_fingerprint(project_root),
+        cases,
+    );
+    match test_results::ingest(dir, task_id, &report) {
+        Ok(flips) => {
+            println!("Recorded test results: {}", report.summary());
+            if !flips.is_empty() {
+                println!(
+                    "Flaky tests detected (outcome changed on unchanged code): {}",
+                    flips.join(", ")
+                );
+            }
+        }
+        Err(e) => eprintln!("Warning: failed to record test results: {}", e),
+    }
+}
+
 /// Remove an artifact from a task
 pub fn run_remove(dir: &Path, task_id: &str, artifact_path: &str) -> Result<()> {
     let path = super::graph_path(dir);
@@ -211,6 +253,22 @@ mod tests {
         assert_eq!(task.artifacts.len(), 1);
     }
 
+    #[test]
+    fn test_add_test_report_artifact_records_results() {
+        let temp_dir = setup_graph();
+        let report = temp_dir.path().join("junit.xml");
+        std::fs::write(
+            &report,
+            r#"<testsuite><testcase name="a"/><testcase name="b"><failure/></testcase></testsuite>"#,
+        )
+        .unwrap();
+
+        run_add(temp_dir.path(), "t1", report.to_str().unwrap()).unwrap();
+
+        let latest = test_results::latest(temp_dir.path(), "t1").unwrap();
+        assert_eq!(latest.summary(), "1 passed, 1 failed");
+    }
+
     #[test]
     fn test_remove_artifact() {
         let temp_dir = setup_graph();
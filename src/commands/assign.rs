@@ -4,7 +4,13 @@ use workgraph::agency;
 use workgraph::agency::composition_rules::{
     CompositionRulesOverlay, default_overlay_path, load_composition_rules,
 };
-use workgraph::config::Config;
+use workgraph::agency::reputation;
+use workgraph::availability::AvailabilityCalendar;
+use workgraph::config::{Config, DispatchPolicy};
+use workgraph::dispatch::budget;
+use workgraph::dispatch::capability;
+use workgraph::dispatch::fair_share;
+use workgraph::dispatch::skill_match::SkillMatcher;
 use workgraph::parser::{load_graph, modify_graph};
 
 use super::graph_path;
@@ -276,30 +282,74 @@ fn run_auto_assign(dir: &Path, path: &Path, task_id: &str) -> Result<()> {
         );
     }
 
+    // Skip agents on vacation or in scheduled downtime.
+    let calendar = AvailabilityCalendar::load_or_warn(dir);
+    let now = chrono::Utc::now();
+    let all_agents: Vec<_> = all_agents
+        .into_iter()
+        .filter(|a| calendar.agent_unavailable_until(a, now).is_none())
+        .collect();
+    if all_agents.is_empty() {
+        anyhow::bail!(
+            "No agents available for automatic assignment: all are unavailable \
+             (see 'wg availability list')."
+        );
+    }
+
+    // Skip agents that spent their daily budget.
+    let spend = budget::DailySpend::new(&graph, now.date_naive(), &budget::live_usage(dir));
+    let (all_agents, over_budget) = spend.within_budget(all_agents);
+    if all_agents.is_empty() {
+        let lines: Vec<String> = over_budget
+            .iter()
+            .map(|o| format!("  {}", o.message()))
+            .collect();
+        anyhow::bail!(
+            "No agents available for automatic assignment: all are over their daily budget:\n{}",
+            lines.join("\n")
+        );
+    }
+
     // Apply composition-rules caps from ~/.agency/composition-rules.csv
     // (re-read on every assignment so edits take effect without restart).
     let overlay = load_overlay();
     let roles_dir = agency_dir.join("cache/roles");
     let all_agents = apply_caps(&overlay, &all_agents, &roles_dir);
 
-    // Select the agent with the highest performance score, defaulting to the first agent
-    let selected_agent = all_agents
-        .iter()
-        .max_by(|a, b| {
-            let a_score = a.performance.avg_score.unwrap_or(0.0);
-            let b_score = b.performance.avg_score.unwrap_or(0.0);
-            a_score
-                .partial_cmp(&b_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        })
-        .ok_or_else(|| anyhow::anyhow!("No agents found"))?
-        .id
-        .clone();
+    // Skip agents whose capability document rules the task out.
+    let matcher = SkillMatcher::for_task(dir, &config, task, &all_agents);
+    let (all_agents, rejected) = capability::eligible_agents(all_agents, task, &matcher);
+    if all_agents.is_empty() {
+        anyhow::bail!(
+            "No agent can take task '{}'; their capability documents rule it out:\n{}",
+            task_id,
+            capability::describe_rejections(&rejected)
+        );
+    }
+
+    // Pick per `coordinator.dispatch`: best score (greedy), least loaded
+    // (fair), best skill match (skill-first), or best track record
+    // (reputation).
+    let policy = config.coordinator.dispatch;
+    let loads = fair_share::agent_loads(&graph, now);
+    let reputations = if policy == DispatchPolicy::Reputation {
+        let store = reputation::refresh(&agency_dir, &graph)?;
+        reputation::reputation_scores(&store, &all_agents)
+    } else {
+        std::collections::HashMap::new()
+    };
+    let selected_agent =
+        fair_share::rank_agents(policy, &all_agents, task, &loads, &matcher, &reputations)
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No agents found"))?
+            .id
+            .clone();
 
     eprintln!(
-        "[assign] Auto-selecting agent: {} for task '{}'",
+        "[assign] Auto-selecting agent: {} for task '{}' (dispatch: {})",
         agency::short_hash(&selected_agent),
-        task_id
+        task_id,
+        policy
     );
 
     // Perform the explicit assignment with the selected agent
@@ -322,6 +372,42 @@ fn run_explicit_assign(dir: &Path, path: &Path, task_id: &str, agent_hash: &str)
         format!("No agent matching '{}'. {}", agent_hash, hint)
     })?;
 
+    // An agent that registered a capability document must meet the task's
+    // requirements.
+    let config = Config::load_or_default(dir);
+    let graph = load_graph(path).context("Failed to load graph")?;
+    if let Some(task) = graph.get_task(task_id) {
+        let matcher = SkillMatcher::for_task(dir, &config, task, std::slice::from_ref(&agent));
+        let unmet = capability::unmet_requirements(&agent, task, &matcher);
+        if !unmet.is_empty() {
+            let reasons: Vec<String> = unmet.iter().map(|u| u.to_string()).collect();
+            anyhow::bail!(
+                "Agent '{}' can't take task '{}': {}. Update its capability document \
+                 with 'wg agent register' or pick another agent.",
+                agent.name,
+                task_id,
+                reasons.join("; ")
+            );
+        }
+    }
+
+    if let Some(back) =
+        AvailabilityCalendar::load_or_warn(dir).agent_unavailable_until(&agent, chrono::Utc::now())
+    {
+        eprintln!(
+            "Warning: agent '{}' is unavailable until {}",
+            agent.name,
+            back.format("%Y-%m-%d %H:%M")
+        );
+    }
+    if let Some(over) = budget::DailySpend::today(&graph, dir).check(&agent) {
+        eprintln!(
+            "Warning: agent '{}' is over its daily budget (${:.2} of ${:.2} spent today); \
+             the service won't dispatch this task until tomorrow (UTC)",
+            agent.name, over.spent, over.budget
+        );
+    }
+
     let agent_id_clone = agent.id.clone();
     let task_id_owned = task_id.to_string();
     let mut error: Option<anyhow::Error> = None;
@@ -343,7 +429,6 @@ fn run_explicit_assign(dir: &Path, path: &Path, task_id: &str, agent_hash: &str)
     super::notify_graph_changed(dir);
 
     // Record operation
-    let config = Config::load_or_default(dir);
     let _ = workgraph::provenance::record(
         dir,
         "assign",
@@ -553,6 +638,8 @@ mod tests {
             attractor_weight: 1.0,
             deployment_history: vec![],
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         agency::save_agent(&agent, &agency_dir.join("cache/agents")).unwrap();
 
@@ -707,6 +794,8 @@ mod tests {
                 attractor_weight: 0.5,
                 deployment_history: vec![],
                 staleness_flags: vec![],
+                capability_doc: None,
+                daily_budget: None,
             };
             agency::save_agent(&assigner_agent, &agents_dir).unwrap();
         }
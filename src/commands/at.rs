@@ -0,0 +1,145 @@
+//! `wg at <TIME> status|list|viz` — the graph as it was at a point in time.
+//!
+//! Winds the current graph back through the undo journal (see
+//! [`workgraph::journal::state_at`]) and shows the result with the regular
+//! `status`, `list` or `viz` output, so a retro can compare how the plan
+//! looked then with how it turned out. The reconstruction is written to a
+//! scratch directory for the duration of the command; the live graph is
+//! never touched.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::path::{Path, PathBuf};
+use workgraph::journal;
+use workgraph::parser::save_graph;
+
+/// Parse `2025-06-01` (start of that day, UTC), an RFC 3339 timestamp, or
+/// a duration ago such as `3d` or `2w`.
+pub fn parse_time(s: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    if let Ok(ts) = s.parse::<DateTime<Utc>>() {
+        return Ok(ts);
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is always valid")
+            .and_utc());
+    }
+    workgraph::html::parse_since(s)
+        .map(|ago| now - ago)
+        .with_context(|| {
+            format!(
+                "Invalid time '{}': use YYYY-MM-DD, an RFC 3339 timestamp, or a duration ago (e.g. 3d)",
+                s
+            )
+        })
+}
+
+/// Scratch workgraph directory, removed on drop.
+struct Scratch(PathBuf);
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Write the graph as of `at` to a scratch workgraph directory (with the
+/// project's config, so output settings carry over).
+fn materialize(dir: &Path, at: DateTime<Utc>, json: bool) -> Result<Scratch> {
+    let (current, _path) = super::load_workgraph(dir)?;
+    let past = journal::state_at(dir, &current, at)?;
+
+    if !json {
+        eprintln!(
+            "Graph as of {} ({} change(s) unwound)",
+            at.to_rfc3339(),
+            past.unwound
+        );
+        if !past.complete {
+            match past.journal_start {
+                Some(ref start) => eprintln!(
+                    "Warning: the journal only reaches back to {}; changes before then are not reflected",
+                    start
+                ),
+                None => eprintln!(
+                    "Warning: no journal recorded yet; only tasks created later are left out"
+                ),
+            }
+        }
+        eprintln!();
+    }
+
+    let scratch = Scratch(std::env::temp_dir().join(format!("wg-at-{}", uuid::Uuid::new_v4())));
+    std::fs::create_dir_all(&scratch.0)
+        .with_context(|| format!("Failed to create {}", scratch.0.display()))?;
+    save_graph(&past.graph, super::graph_path(&scratch.0))?;
+    let config = dir.join("config.toml");
+    if config.exists() {
+        std::fs::copy(&config, scratch.0.join("config.toml"))?;
+    }
+    Ok(scratch)
+}
+
+pub fn run_status(dir: &Path, at: &str, all: bool, json: bool) -> Result<()> {
+    let at = parse_time(at, Utc::now())?;
+    let scratch = materialize(dir, at, json)?;
+    super::status::run(&scratch.0, json, all)
+}
+
+pub fn run_list(dir: &Path, at: &str, status: Option<&str>, all: bool, json: bool) -> Result<()> {
+    let at = parse_time(at, Utc::now())?;
+    let scratch = materialize(dir, at, json)?;
+    super::list::run(
+        &scratch.0,
+        status,
+        false,
+        &[],
+        None,
+        false,
+        None,
+        None,
+        json,
+        all,
+    )
+}
+
+pub fn run_viz(dir: &Path, at: &str, all: bool, json: bool) -> Result<()> {
+    let at = parse_time(at, Utc::now())?;
+    let scratch = materialize(dir, at, json)?;
+    let options = super::viz::VizOptions {
+        all,
+        max_columns: crossterm::terminal::size().ok().map(|(cols, _)| cols),
+        ..Default::default()
+    };
+    if json {
+        super::viz::run_json(&scratch.0, &options)
+    } else {
+        super::viz::run(&scratch.0, &options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_forms() {
+        let now = "2025-06-10T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            parse_time("2025-06-01", now).unwrap().to_rfc3339(),
+            "2025-06-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_time("2025-06-01T09:30:00+02:00", now)
+                .unwrap()
+                .to_rfc3339(),
+            "2025-06-01T07:30:00+00:00"
+        );
+        assert_eq!(
+            parse_time("3d", now).unwrap().to_rfc3339(),
+            "2025-06-07T12:00:00+00:00"
+        );
+        assert!(parse_time("last tuesday", now).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! `wg availability` — vacations and scheduled downtime.
+//!
+//! Usage:
+//!   wg availability add alice --from 2026-03-02 --until 2026-03-13 --reason vacation
+//!   wg availability add a1b2c3d4 --until 2026-03-01T06:00:00Z --reason maintenance
+//!   wg availability list [--who alice] [--all]
+//!   wg availability remove 3
+
+use anyhow::Result;
+use chrono::Utc;
+use std::path::Path;
+use workgraph::availability::{AvailabilityCalendar, AvailabilityWindow, parse_boundary};
+
+pub fn run_add(
+    dir: &Path,
+    who: &str,
+    from: Option<&str>,
+    until: &str,
+    reason: Option<&str>,
+) -> Result<()> {
+    if !dir.exists() {
+        anyhow::bail!("WG not initialized. Run 'wg init' first.");
+    }
+    let from = match from {
+        Some(s) => parse_boundary(s, false)?,
+        None => Utc::now(),
+    };
+    let until = parse_boundary(until, true)?;
+    if until <= from {
+        anyhow::bail!("--until must be after --from");
+    }
+
+    let mut calendar = AvailabilityCalendar::load(dir)?;
+    let id = calendar.add(who, from, until, reason);
+    calendar.save(dir)?;
+
+    let config = workgraph::config::Config::load_or_default(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "availability_add",
+        None,
+        None,
+        serde_json::json!({
+            "id": id,
+            "who": who,
+            "from": from.to_rfc3339(),
+            "until": until.to_rfc3339(),
+            "reason": reason,
+        }),
+        config.log.rotation_threshold,
+    );
+
+    println!(
+        "Added window {}: {} unavailable {} → {}",
+        id,
+        who,
+        from.format("%Y-%m-%d %H:%M"),
+        until.format("%Y-%m-%d %H:%M")
+    );
+    Ok(())
+}
+
+pub fn run_list(dir: &Path, who: Option<&str>, all: bool, json: bool) -> Result<()> {
+    let calendar = AvailabilityCalendar::load(dir)?;
+    let now = Utc::now();
+    let windows: Vec<&AvailabilityWindow> = calendar
+        .windows
+        .iter()
+        .filter(|w| who.is_none_or(|name| w.who == name))
+        .filter(|w| all || !w.is_past(now))
+        .collect();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&windows)?);
+        return Ok(());
+    }
+    if windows.is_empty() {
+        println!("No availability windows");
+        return Ok(());
+    }
+    for w in windows {
+        let state = if w.covers(now) {
+            " [active]"
+        } else if w.is_past(now) {
+            " [past]"
+        } else {
+            ""
+        };
+        let reason = w
+            .reason
+            .as_ref()
+            .map(|r| format!(" ({})", r))
+            .unwrap_or_default();
+        println!(
+            "{:>3}  {:<20} {} → {}{}{}",
+            w.id,
+            w.who,
+            short_time(&w.from),
+            short_time(&w.until),
+            reason,
+            state
+        );
+    }
+    Ok(())
+}
+
+pub fn run_remove(dir: &Path, id: u32) -> Result<()> {
+    let mut calendar = AvailabilityCalendar::load(dir)?;
+    if !calendar.remove(id) {
+        anyhow::bail!("No availability window with ID {}", id);
+    }
+    calendar.save(dir)?;
+    println!("Removed window {}", id);
+    Ok(())
+}
+
+fn short_time(ts: &str) -> String {
+    ts.parse::<chrono::DateTime<Utc>>()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| ts.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_add_list_remove() {
+        let dir = tempdir().unwrap();
+        run_add(
+            dir.path(),
+            "alice",
+            Some("2099-01-01"),
+            "2099-01-14",
+            Some("vacation"),
+        )
+        .unwrap();
+        let cal = AvailabilityCalendar::load(dir.path()).unwrap();
+        assert_eq!(cal.windows.len(), 1);
+        assert_eq!(cal.windows[0].reason.as_deref(), Some("vacation"));
+        assert!(run_list(dir.path(), Some("alice"), false, true).is_ok());
+
+        run_remove(dir.path(), 1).unwrap();
+        assert!(run_remove(dir.path(), 1).is_err());
+        assert!(
+            AvailabilityCalendar::load(dir.path())
+                .unwrap()
+                .windows
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_add_rejects_inverted_window() {
+        let dir = tempdir().unwrap();
+        let err = run_add(dir.path(), "bob", Some("2099-02-01"), "2099-01-01", None).unwrap_err();
+        assert!(err.to_string().contains("--until must be after --from"));
+    }
+}
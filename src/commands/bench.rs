@@ -0,0 +1,475 @@
+//! `wg bench models` — replay a sample of completed tasks against several
+//! models and compare cost against evaluated quality.
+//!
+//! Replays run in sandbox mode: each candidate model gets a single no-tool
+//! LLM call with the task's title, description, and verification criteria and
+//! answers with the change it would make. Nothing touches the working tree,
+//! the graph, or the agency performance records. Each answer is scored with
+//! the regular evaluator prompt, and the per-model results are written to
+//! `.wg/bench/` so routing decisions (e.g. `[[tag_routing]]` rules) can point
+//! at them.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use workgraph::agency::{EvaluatorInput, render_evaluator_prompt};
+use workgraph::config::{Config, DispatchRole};
+use workgraph::graph::{Status, Task, TokenUsage, WorkGraph, is_system_task};
+use workgraph::json_extract::extract_json;
+use workgraph::service::llm::run_lightweight_llm_call;
+
+/// Directory under `.wg/` holding benchmark reports.
+pub const BENCH_DIR: &str = "bench";
+
+/// A model whose mean score is within this much of the best is considered
+/// equivalent when recommending the cheapest option.
+const SCORE_TOLERANCE: f64 = 0.05;
+
+/// One candidate answer, scored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub task_id: String,
+    pub model: String,
+    /// Evaluator score in [0, 1]; `None` when the replay or evaluation failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    #[serde(default)]
+    pub cost_usd: f64,
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Aggregate over all sampled tasks for one model.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModelSummary {
+    pub model: String,
+    pub tasks: usize,
+    pub failures: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mean_score: Option<f64>,
+    pub total_cost_usd: f64,
+    /// Mean score per dollar spent; `None` for free or unscored models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_per_usd: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<String>,
+    pub tasks: Vec<String>,
+    pub models: Vec<ModelSummary>,
+    /// Cheapest model scoring within [`SCORE_TOLERANCE`] of the best.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recommended: Option<String>,
+    pub results: Vec<BenchResult>,
+}
+
+#[derive(Deserialize)]
+struct EvalOutput {
+    score: f64,
+}
+
+/// Pick up to `sample` completed, non-system tasks, most recently completed
+/// first. With `skills`, only tasks requiring at least one of them (matched
+/// case-insensitively against `skills` and `tags`) are considered.
+pub fn sample_tasks<'a>(graph: &'a WorkGraph, skills: &[String], sample: usize) -> Vec<&'a Task> {
+    let mut tasks: Vec<&Task> = graph
+        .tasks()
+        .filter(|t| t.status == Status::Done && !is_system_task(&t.id))
+        .filter(|t| {
+            skills.is_empty()
+                || t.skills
+                    .iter()
+                    .chain(&t.tags)
+                    .any(|s| skills.iter().any(|want| want.eq_ignore_ascii_case(s)))
+        })
+        .collect();
+    tasks.sort_by(|a, b| {
+        b.completed_at
+            .cmp(&a.completed_at)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    tasks.truncate(sample);
+    tasks
+}
+
+/// Models to compare: the explicit list, or else the distinct models
+/// configured for the fast, standard, and premium tiers.
+fn candidate_models(config: &Config, explicit: &[String]) -> Vec<String> {
+    let mut models: Vec<String> = if explicit.is_empty() {
+        let tiers = config.effective_tiers_public();
+        [tiers.fast, tiers.standard, tiers.premium]
+            .into_iter()
+            .flatten()
+            .collect()
+    } else {
+        explicit.to_vec()
+    };
+    let mut seen = std::collections::HashSet::new();
+    models.retain(|m| seen.insert(m.clone()));
+    models
+}
+
+/// The sandbox prompt a candidate model answers for one task.
+fn replay_prompt(task: &Task) -> String {
+    let mut prompt = String::from(
+        "You are being benchmarked on a task that was already completed in this project.\n\
+         You have no tools and cannot read or modify files. Describe exactly how you would\n\
+         complete the task: the files you would change and the full changes as unified diffs,\n\
+         followed by how you would verify the result.\n\n",
+    );
+    prompt.push_str(&format!("## Task: {}\n\n", task.title));
+    if let Some(desc) = &task.description {
+        prompt.push_str(desc);
+        prompt.push_str("\n\n");
+    }
+    if !task.skills.is_empty() {
+        prompt.push_str(&format!("Skills: {}\n\n", task.skills.join(", ")));
+    }
+    if let Some(verify) = &task.verify {
+        prompt.push_str(&format!("## Verification criteria\n\n{}\n", verify));
+    }
+    prompt
+}
+
+/// Cost of one call: the provider-reported cost when present, else an
+/// estimate from the model's registry pricing.
+fn call_cost(config: &Config, usage: &TokenUsage) -> f64 {
+    if usage.cost_usd > 0.0 {
+        return usage.cost_usd;
+    }
+    config
+        .resolve_model_for_role(DispatchRole::TaskAgent)
+        .registry_entry
+        .map(|entry| {
+            (usage.input_tokens as f64 * entry.cost_per_input_mtok
+                + usage.output_tokens as f64 * entry.cost_per_output_mtok)
+                / 1_000_000.0
+        })
+        .unwrap_or(0.0)
+}
+
+fn replay_one(config: &Config, task: &Task, model: &str, timeout_secs: u64) -> BenchResult {
+    let mut result = BenchResult {
+        task_id: task.id.clone(),
+        model: model.to_string(),
+        score: None,
+        cost_usd: 0.0,
+        input_tokens: 0,
+        output_tokens: 0,
+        error: None,
+    };
+
+    let mut candidate_config = config.clone();
+    candidate_config
+        .models
+        .set_model(DispatchRole::TaskAgent, model);
+    let answer = match run_lightweight_llm_call(
+        &candidate_config,
+        DispatchRole::TaskAgent,
+        &replay_prompt(task),
+        timeout_secs,
+    ) {
+        Ok(answer) => answer,
+        Err(e) => {
+            result.error = Some(format!("replay failed: {:#}", e));
+            return result;
+        }
+    };
+    if let Some(usage) = &answer.token_usage {
+        result.cost_usd = call_cost(&candidate_config, usage);
+        result.input_tokens = usage.input_tokens;
+        result.output_tokens = usage.output_tokens;
+    }
+
+    let input = EvaluatorInput {
+        task_title: &task.title,
+        task_description: task.description.as_deref(),
+        task_skills: &task.skills,
+        verify: task.verify.as_deref(),
+        agent: None,
+        role: None,
+        tradeoff: None,
+        artifacts: &[],
+        log_entries: &[],
+        started_at: None,
+        completed_at: None,
+        artifact_diff: Some(&answer.text),
+        evaluator_identity: None,
+        downstream_tasks: &[],
+        flip_score: None,
+        verify_status: None,
+        verify_findings: None,
+        resolved_outcome_name: None,
+        child_tasks: &[],
+        constraint_fidelity_score: None,
+        constraint_fidelity_unanchored: None,
+        test_results: None,
+        rubric: None,
+    };
+    let scored = run_lightweight_llm_call(
+        config,
+        DispatchRole::Evaluator,
+        &render_evaluator_prompt(&input),
+        timeout_secs,
+    )
+    .context("evaluation failed")
+    .and_then(|eval| {
+        let json = extract_json(&eval.text).context("evaluator returned no JSON")?;
+        let parsed: EvalOutput = serde_json::from_str(&json).context("bad evaluator JSON")?;
+        Ok(parsed.score.clamp(0.0, 1.0))
+    });
+    match scored {
+        Ok(score) => result.score = Some(score),
+        Err(e) => result.error = Some(format!("{:#}", e)),
+    }
+    result
+}
+
+/// Aggregate results per model, in the order the models were given.
+pub fn summarize(models: &[String], results: &[BenchResult]) -> Vec<ModelSummary> {
+    models
+        .iter()
+        .map(|model| {
+            let mine: Vec<&BenchResult> = results.iter().filter(|r| &r.model == model).collect();
+            let scores: Vec<f64> = mine.iter().filter_map(|r| r.score).collect();
+            let mean_score =
+                (!scores.is_empty()).then(|| scores.iter().sum::<f64>() / scores.len() as f64);
+            let total_cost_usd: f64 = mine.iter().map(|r| r.cost_usd).sum();
+            ModelSummary {
+                model: model.clone(),
+                tasks: mine.len(),
+                failures: mine.len() - scores.len(),
+                mean_score,
+                total_cost_usd,
+                score_per_usd: mean_score
+                    .filter(|_| total_cost_usd > 0.0)
+                    .map(|s| s / total_cost_usd),
+            }
+        })
+        .collect()
+}
+
+/// The cheapest model whose mean score is within [`SCORE_TOLERANCE`] of the
+/// best mean score.
+pub fn recommend(summaries: &[ModelSummary]) -> Option<String> {
+    let best = summaries
+        .iter()
+        .filter_map(|s| s.mean_score)
+        .max_by(f64::total_cmp)?;
+    summaries
+        .iter()
+        .filter(|s| s.mean_score.is_some_and(|m| m >= best - SCORE_TOLERANCE))
+        .min_by(|a, b| a.total_cost_usd.total_cmp(&b.total_cost_usd))
+        .map(|s| s.model.clone())
+}
+
+fn save_report(dir: &Path, report: &BenchReport) -> Result<PathBuf> {
+    let bench_dir = dir.join(BENCH_DIR);
+    std::fs::create_dir_all(&bench_dir)
+        .with_context(|| format!("Failed to create {}", bench_dir.display()))?;
+    let stamp = report.created_at.replace(':', "-");
+    let path = bench_dir.join(format!("models-{}.json", stamp));
+    std::fs::write(&path, serde_json::to_string_pretty(report)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Run `wg bench models`.
+pub fn run_models(
+    dir: &Path,
+    sample: usize,
+    skills: &[String],
+    models: &[String],
+    dry_run: bool,
+    json: bool,
+) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let config = Config::load_or_default(dir);
+
+    let models = candidate_models(&config, models);
+    if models.is_empty() {
+        bail!("No models to compare. Pass --models or configure [tiers].");
+    }
+    let tasks = sample_tasks(&graph, skills, sample);
+    if tasks.is_empty() {
+        if skills.is_empty() {
+            bail!("No completed tasks to replay");
+        }
+        bail!("No completed tasks require skills: {}", skills.join(", "));
+    }
+
+    if dry_run {
+        if json {
+            let out = serde_json::json!({
+                "models": models,
+                "tasks": tasks.iter().map(|t| &t.id).collect::<Vec<_>>(),
+            });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        } else {
+            println!(
+                "Would replay {} task(s) against {} model(s):",
+                tasks.len(),
+                models.len()
+            );
+            for model in &models {
+                println!("  model: {}", model);
+            }
+            for task in &tasks {
+                println!("  task:  {} - {}", task.id, task.title);
+            }
+        }
+        return Ok(());
+    }
+
+    let timeout_secs = config.agency.triage_timeout.unwrap_or(60).max(300);
+    let mut results = Vec::new();
+    for task in &tasks {
+        for model in &models {
+            if !json {
+                eprintln!("[bench] {} on {}...", task.id, model);
+            }
+            results.push(replay_one(&config, task, model, timeout_secs));
+        }
+    }
+
+    let summaries = summarize(&models, &results);
+    let report = BenchReport {
+        created_at: chrono::Utc::now().to_rfc3339(),
+        skills: skills.to_vec(),
+        tasks: tasks.iter().map(|t| t.id.clone()).collect(),
+        recommended: recommend(&summaries),
+        models: summaries,
+        results,
+    };
+    let path = save_report(dir, &report)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "{:<40} {:>6} {:>6} {:>10} {:>10}",
+        "MODEL", "TASKS", "SCORE", "COST", "SCORE/$"
+    );
+    for s in &report.models {
+        println!(
+            "{:<40} {:>6} {:>6} {:>10} {:>10}",
+            s.model,
+            format!("{}/{}", s.tasks - s.failures, s.tasks),
+            s.mean_score
+                .map(|m| format!("{:.2}", m))
+                .unwrap_or_else(|| "-".into()),
+            format!("${:.4}", s.total_cost_usd),
+            s.score_per_usd
+                .map(|v| format!("{:.1}", v))
+                .unwrap_or_else(|| "-".into()),
+        );
+    }
+    let mut errors: BTreeMap<&str, usize> = BTreeMap::new();
+    for r in report.results.iter().filter(|r| r.error.is_some()) {
+        *errors.entry(r.model.as_str()).or_default() += 1;
+    }
+    for (model, count) in &errors {
+        println!("  {} replay(s) on {} failed (see report)", count, model);
+    }
+    if let Some(model) = &report.recommended {
+        println!();
+        println!(
+            "Recommended: {} (cheapest within {:.2} of the best score)",
+            model, SCORE_TOLERANCE
+        );
+        if let Some(skill) = skills.first() {
+            println!(
+                "  e.g. [[tag_routing]] tag = \"{}\" model = \"{}\"",
+                skill, model
+            );
+        }
+    }
+    println!("Report: {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workgraph::graph::Node;
+    use workgraph::test_helpers::make_task_with_status;
+
+    fn done(id: &str, completed_at: &str, skills: &[&str]) -> Node {
+        let mut t = make_task_with_status(id, id, Status::Done);
+        t.completed_at = Some(completed_at.to_string());
+        t.skills = skills.iter().map(|s| s.to_string()).collect();
+        Node::Task(t)
+    }
+
+    fn result(model: &str, score: Option<f64>, cost: f64) -> BenchResult {
+        BenchResult {
+            task_id: "t".into(),
+            model: model.into(),
+            score,
+            cost_usd: cost,
+            input_tokens: 0,
+            output_tokens: 0,
+            error: score.is_none().then(|| "failed".into()),
+        }
+    }
+
+    #[test]
+    fn test_sample_filters_by_skill_and_prefers_recent() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(done("old", "2026-01-01T00:00:00Z", &["rust"]));
+        graph.add_node(done("new", "2026-03-01T00:00:00Z", &["Rust"]));
+        graph.add_node(done("py", "2026-04-01T00:00:00Z", &["python"]));
+        graph.add_node(done(".evaluate-new", "2026-05-01T00:00:00Z", &["rust"]));
+        graph.add_node(Node::Task(make_task_with_status(
+            "open",
+            "open",
+            Status::Open,
+        )));
+
+        let ids = |tasks: Vec<&Task>| tasks.into_iter().map(|t| t.id.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            ids(sample_tasks(&graph, &["rust".into()], 10)),
+            vec!["new", "old"]
+        );
+        assert_eq!(ids(sample_tasks(&graph, &[], 2)), vec!["py", "new"]);
+    }
+
+    #[test]
+    fn test_summarize_and_recommend_cheapest_within_tolerance() {
+        let models = vec![
+            "opus".to_string(),
+            "haiku".to_string(),
+            "broken".to_string(),
+        ];
+        let results = vec![
+            result("opus", Some(0.9), 1.0),
+            result("opus", Some(0.9), 1.0),
+            result("haiku", Some(0.88), 0.05),
+            result("haiku", None, 0.01),
+            result("broken", None, 0.0),
+        ];
+        let summaries = summarize(&models, &results);
+        assert_eq!(summaries[0].mean_score, Some(0.9));
+        assert_eq!(summaries[0].total_cost_usd, 2.0);
+        assert_eq!(summaries[1].failures, 1);
+        assert_eq!(summaries[2].mean_score, None);
+        assert_eq!(recommend(&summaries), Some("haiku".to_string()));
+
+        let strict = summarize(&models, &results[..3]);
+        assert_eq!(strict[1].mean_score, Some(0.88));
+        let mut worse = strict.clone();
+        worse[1].mean_score = Some(0.5);
+        assert_eq!(recommend(&worse), Some("opus".to_string()));
+    }
+}
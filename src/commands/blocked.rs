@@ -43,6 +43,56 @@ pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Show every task in a saved view that is waiting on unfinished blockers,
+/// in the view's order.
+pub fn run_view(dir: &Path, view: &str, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let query = super::view::resolve(dir, view)?;
+
+    let blocked: Vec<_> = query
+        .run(&graph)
+        .into_iter()
+        .filter(|t| !t.status.is_terminal())
+        .map(|t| (t, after(&graph, &t.id)))
+        .filter(|(_, blockers)| !blockers.is_empty())
+        .collect();
+
+    if json {
+        let output: Vec<_> = blocked
+            .iter()
+            .map(|(t, blockers)| {
+                serde_json::json!({
+                    "id": t.id,
+                    "title": t.title,
+                    "blocked_by": blockers
+                        .iter()
+                        .map(|b| serde_json::json!({
+                            "id": b.id,
+                            "title": b.title,
+                            "status": b.status,
+                        }))
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else if blocked.is_empty() {
+        println!("No blocked tasks in view '{}'", view);
+    } else {
+        for (task, blockers) in blocked {
+            println!("{} - {}", task.id, task.title);
+            for blocker in blockers {
+                println!(
+                    "  blocked by {} - {} [{:?}]",
+                    blocker.id, blocker.title, blocker.status
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +150,24 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_run_view() {
+        let dir = tempdir().unwrap();
+        let blocker = make_task("blocker", "Blocker task", Status::Open);
+        let mut blocked = make_task("blocked", "Blocked task", Status::Open);
+        blocked.after = vec!["blocker".to_string()];
+        blocked.tags = vec!["backend".to_string()];
+        setup_workgraph(dir.path(), vec![blocker, blocked]);
+        fs::write(
+            dir.path().join("config.toml"),
+            "[views]\nbackend = \"tag:backend\"\n",
+        )
+        .unwrap();
+
+        assert!(run_view(dir.path(), "backend", false).is_ok());
+        assert!(run_view(dir.path(), "frontend", false).is_err());
+    }
+
     #[test]
     fn test_run_unblocked_task() {
         let dir = tempdir().unwrap();
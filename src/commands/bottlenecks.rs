@@ -37,6 +37,11 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
     let mut bottlenecks: Vec<BottleneckInfo> = Vec::new();
 
     for task in graph.tasks() {
+        // Tasks blocked outside the project are tracked by `wg waiting`
+        if task.external_blocker.is_some() {
+            continue;
+        }
+
         // Count direct dependents
         let direct_blocks = reverse_index.get(&task.id).map(Vec::len).unwrap_or(0);
 
@@ -0,0 +1,281 @@
+//! `wg branch` — scenario branches of the planning graph.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+use workgraph::branch::{self, LIVE};
+use workgraph::config::Config;
+use workgraph::graph::WorkGraph;
+
+use super::forecast::calculate_forecast;
+
+/// Create a branch from the live graph (or from another branch).
+pub fn run_create(
+    dir: &Path,
+    name: &str,
+    from: Option<&str>,
+    message: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let meta = branch::create(dir, name, from, message)?;
+
+    let config = Config::load_or_default(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "branch_create",
+        None,
+        None,
+        serde_json::json!({ "name": meta.name, "from": meta.from }),
+        config.log.rotation_threshold,
+    );
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&meta)?);
+    } else {
+        println!("Created branch {} from {}", meta.name, meta.from);
+        println!(
+            "  Edit it with: wg --dir {} <command>",
+            branch::branch_dir(dir, &meta.name).display()
+        );
+    }
+    Ok(())
+}
+
+/// List branches.
+pub fn run_list(dir: &Path, json: bool) -> Result<()> {
+    let metas = branch::list(dir)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&metas)?);
+        return Ok(());
+    }
+    if metas.is_empty() {
+        println!("No branches. Create one with: wg branch create <name>");
+        return Ok(());
+    }
+
+    println!("Branches:\n");
+    for meta in &metas {
+        let tasks = branch::load_branch_graph(dir, &meta.name)
+            .map(|g| g.tasks().count().to_string())
+            .unwrap_or_else(|_| "?".to_string());
+        println!(
+            "  {} (from {}, {}) — {} task(s)",
+            meta.name, meta.from, meta.created, tasks
+        );
+        if let Some(ref message) = meta.message {
+            println!("    {}", message);
+        }
+    }
+    Ok(())
+}
+
+/// Forecast summary of one side of a comparison.
+#[derive(Debug, Serialize)]
+struct BranchSummary {
+    branch: String,
+    tasks: usize,
+    open_tasks: usize,
+    remaining_hours: f64,
+    critical_path_hours: f64,
+    realistic_completion: Option<String>,
+    realistic_weeks: Option<f64>,
+}
+
+fn summarize(name: &str, graph: &WorkGraph) -> BranchSummary {
+    let forecast = calculate_forecast(graph);
+    let realistic = forecast.scenarios.get(1);
+    BranchSummary {
+        branch: name.to_string(),
+        tasks: graph.tasks().count(),
+        open_tasks: forecast.remaining_work.open_tasks
+            + forecast.remaining_work.blocked_tasks
+            + forecast.remaining_work.in_progress_tasks,
+        remaining_hours: forecast.remaining_work.total_hours,
+        critical_path_hours: forecast
+            .critical_path
+            .as_ref()
+            .map(|cp| cp.total_hours)
+            .unwrap_or(0.0),
+        realistic_completion: realistic.and_then(|s| s.completion_date.clone()),
+        realistic_weeks: realistic.and_then(|s| s.weeks_to_complete),
+    }
+}
+
+/// Compare forecasts between two branches (`main` is the live graph).
+pub fn run_compare(dir: &Path, a: &str, b: Option<&str>, json: bool) -> Result<()> {
+    let b = b.unwrap_or(LIVE);
+    let graph_a = branch::load_branch_graph(dir, a)?;
+    let graph_b = branch::load_branch_graph(dir, b)?;
+    let changed: Vec<String> = branch::changed_task_ids(&graph_b, &graph_a)
+        .into_iter()
+        .collect();
+    let left = summarize(a, &graph_a);
+    let right = summarize(b, &graph_b);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "branches": [left, right],
+                "changed_tasks": changed,
+            }))?
+        );
+        return Ok(());
+    }
+
+    let fmt_opt = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+    let fmt_weeks = |v: Option<f64>| {
+        v.map(|w| format!("{:.1}", w))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let width = a.len().max(b.len()).max(12);
+
+    println!("{:<24} {:>w$} {:>w$}", "", a, b, w = width);
+    println!(
+        "{:<24} {:>w$} {:>w$}",
+        "Tasks",
+        left.tasks,
+        right.tasks,
+        w = width
+    );
+    println!(
+        "{:<24} {:>w$} {:>w$}",
+        "Open tasks",
+        left.open_tasks,
+        right.open_tasks,
+        w = width
+    );
+    println!(
+        "{:<24} {:>w$.1} {:>w$.1}",
+        "Remaining hours",
+        left.remaining_hours,
+        right.remaining_hours,
+        w = width
+    );
+    println!(
+        "{:<24} {:>w$.1} {:>w$.1}",
+        "Critical path hours",
+        left.critical_path_hours,
+        right.critical_path_hours,
+        w = width
+    );
+    println!(
+        "{:<24} {:>w$} {:>w$}",
+        "Weeks (realistic)",
+        fmt_weeks(left.realistic_weeks),
+        fmt_weeks(right.realistic_weeks),
+        w = width
+    );
+    println!(
+        "{:<24} {:>w$} {:>w$}",
+        "Completion (realistic)",
+        fmt_opt(&left.realistic_completion),
+        fmt_opt(&right.realistic_completion),
+        w = width
+    );
+    println!();
+    if changed.is_empty() {
+        println!("No task differences between {} and {}.", a, b);
+    } else {
+        println!(
+            "{} task(s) differ between {} and {}: {}",
+            changed.len(),
+            a,
+            b,
+            changed.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Merge a branch back into the live graph.
+pub fn run_merge(dir: &Path, name: &str, force: bool, json: bool) -> Result<()> {
+    let outcome = branch::merge(dir, name, force)?;
+    super::notify_graph_changed(dir);
+
+    let config = Config::load_or_default(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "branch_merge",
+        None,
+        None,
+        serde_json::json!({
+            "branch": outcome.branch,
+            "changed_tasks": outcome.changed_tasks,
+            "overwritten_live_changes": outcome.overwritten_live_changes,
+            "safety_snapshot": outcome.safety_snapshot.name,
+        }),
+        config.log.rotation_threshold,
+    );
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&outcome)?);
+    } else {
+        println!(
+            "Merged branch {} into the live graph ({} task(s) changed)",
+            outcome.branch,
+            outcome.changed_tasks.len()
+        );
+        if !outcome.overwritten_live_changes.is_empty() {
+            println!(
+                "  Overwrote live changes to: {}",
+                outcome.overwritten_live_changes.join(", ")
+            );
+        }
+        println!(
+            "  Safety snapshot: {} (wg snapshot restore {} to undo)",
+            outcome.safety_snapshot.name, outcome.safety_snapshot.name
+        );
+    }
+    Ok(())
+}
+
+/// Delete a branch.
+pub fn run_delete(dir: &Path, name: &str, json: bool) -> Result<()> {
+    branch::delete(dir, name)?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ "deleted": name }))?
+        );
+    } else {
+        println!("Deleted branch {}", name);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::test_helpers::{make_task, setup_workgraph};
+
+    #[test]
+    fn test_compare_summarizes_estimates() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        let mut t1 = make_task("t1", "Build");
+        t1.estimate = Some(workgraph::graph::Estimate {
+            hours: Some(10.0),
+            cost: None,
+        });
+        setup_workgraph(&dir, vec![t1]);
+
+        run_create(&dir, "lean", None, None, false).unwrap();
+        let mut lean = branch::load_branch_graph(&dir, "lean").unwrap();
+        lean.get_task_mut("t1").unwrap().estimate = Some(workgraph::graph::Estimate {
+            hours: Some(4.0),
+            cost: None,
+        });
+        workgraph::parser::save_graph(&lean, branch::branch_dir(&dir, "lean").join("graph.jsonl"))
+            .unwrap();
+
+        let a = summarize("lean", &lean);
+        let b = summarize(LIVE, &branch::load_branch_graph(&dir, LIVE).unwrap());
+        assert_eq!(a.remaining_hours, 4.0);
+        assert_eq!(b.remaining_hours, 10.0);
+        run_compare(&dir, "lean", None, false).unwrap();
+        run_compare(&dir, "lean", None, true).unwrap();
+    }
+}
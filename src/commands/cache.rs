@@ -0,0 +1,35 @@
+//! `wg cache`: manage the on-disk cache of one-shot LLM responses.
+
+use crate::cli::CacheCommands;
+use anyhow::Result;
+use workgraph::service::response_cache::ResponseCache;
+
+/// Run a cache subcommand.
+pub fn run(command: &CacheCommands, json: bool) -> Result<()> {
+    match command {
+        CacheCommands::Clear => run_clear(json),
+    }
+}
+
+/// Delete every cached response, whether or not it has expired.
+fn run_clear(json: bool) -> Result<()> {
+    let root = ResponseCache::default_root()?;
+    // The TTL only matters for lookups.
+    let removed = ResponseCache::new(&root, 0).clear()?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "path": root,
+                "removed": removed,
+            }))?
+        );
+    } else {
+        println!(
+            "Removed {} cached response(s) from {}",
+            removed,
+            root.display()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,259 @@
+//! `wg calendar export` — iCalendar (.ics) feed of scheduled work
+//!
+//! Each unfinished task contributes up to three events: when it may start
+//! (`not_before`), when it is due (`due`), and when it is forecast to finish
+//! (the critical-path forecast from [`workgraph::deadline::forecast_finish`];
+//! only when estimates or `not_before` put that in the future). Subscribing a
+//! team calendar to the exported file makes scheduled work show up next to
+//! meetings.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::fmt::Write as _;
+use std::path::Path;
+use workgraph::deadline;
+use workgraph::graph::{Task, WorkGraph};
+use workgraph::working_hours::WorkingHours;
+
+/// Which task date an event marks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventKind {
+    Start,
+    Due,
+    Eta,
+}
+
+impl EventKind {
+    fn uid_suffix(&self) -> &'static str {
+        match self {
+            Self::Start => "start",
+            Self::Due => "due",
+            Self::Eta => "eta",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Start => "Start",
+            Self::Due => "Due",
+            Self::Eta => "ETA",
+        }
+    }
+}
+
+struct CalendarEvent<'a> {
+    task: &'a Task,
+    kind: EventKind,
+    at: DateTime<Utc>,
+}
+
+fn parse_ts(ts: Option<&str>) -> Option<DateTime<Utc>> {
+    ts?.parse::<DateTime<Utc>>().ok()
+}
+
+/// Events for unfinished tasks, optionally limited to those carrying every
+/// tag in `tags`, in time order.
+fn collect_events<'a>(
+    graph: &'a WorkGraph,
+    tags: &[String],
+    now: DateTime<Utc>,
+    calendars: &WorkingHours,
+) -> Vec<CalendarEvent<'a>> {
+    let mut events = Vec::new();
+    for task in graph.tasks() {
+        if task.status.is_terminal() || !tags.iter().all(|t| task.tags.contains(t)) {
+            continue;
+        }
+        if let Some(at) = parse_ts(task.not_before.as_deref()) {
+            events.push(CalendarEvent {
+                task,
+                kind: EventKind::Start,
+                at,
+            });
+        }
+        if let Some(at) = deadline::due_at(task) {
+            events.push(CalendarEvent {
+                task,
+                kind: EventKind::Due,
+                at,
+            });
+        }
+        let eta = deadline::forecast_finish(graph, &task.id, now, calendars);
+        if eta > now {
+            events.push(CalendarEvent {
+                task,
+                kind: EventKind::Eta,
+                at: eta,
+            });
+        }
+    }
+    events.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.task.id.cmp(&b.task.id)));
+    events
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11).
+fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Append a content line, folded at 75 octets (RFC 5545 §3.1).
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+fn ics_time(dt: DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Render the feed.
+fn render_ics(
+    graph: &WorkGraph,
+    tags: &[String],
+    now: DateTime<Utc>,
+    calendars: &WorkingHours,
+) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(
+        &mut out,
+        &format!("PRODID:-//workgraph//wg {}//EN", env!("CARGO_PKG_VERSION")),
+    );
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "X-WR-CALNAME:workgraph");
+    for event in collect_events(graph, tags, now, calendars) {
+        let task = event.task;
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(
+            &mut out,
+            &format!("UID:{}-{}@workgraph", task.id, event.kind.uid_suffix()),
+        );
+        push_line(&mut out, &format!("DTSTAMP:{}", ics_time(now)));
+        push_line(&mut out, &format!("DTSTART:{}", ics_time(event.at)));
+        push_line(
+            &mut out,
+            &format!(
+                "SUMMARY:{}",
+                escape_text(&format!("{}: {}", event.kind.label(), task.title))
+            ),
+        );
+        let mut description = format!("Task: {}\nStatus: {}", task.id, task.status);
+        if let Some(ref assigned) = task.assigned {
+            let _ = write!(description, "\nAssigned: {}", assigned);
+        }
+        if let Some(hours) = task.estimate.as_ref().and_then(|e| e.hours) {
+            let _ = write!(description, "\nEstimate: {}h", hours);
+        }
+        push_line(
+            &mut out,
+            &format!("DESCRIPTION:{}", escape_text(&description)),
+        );
+        if !task.tags.is_empty() {
+            let categories: Vec<String> = task.tags.iter().map(|t| escape_text(t)).collect();
+            push_line(&mut out, &format!("CATEGORIES:{}", categories.join(",")));
+        }
+        push_line(&mut out, "END:VEVENT");
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+pub fn run_export(dir: &Path, output: Option<&Path>, tags: &[String]) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let content = render_ics(&graph, tags, Utc::now(), &WorkingHours::load_or_warn(dir));
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)
+                .with_context(|| format!("Failed to write '{}'", path.display()))?;
+            eprintln!("Exported calendar to {}", path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use workgraph::graph::{Estimate, Node, Status};
+    use workgraph::test_helpers::make_task;
+
+    #[test]
+    fn test_render_ics_events() {
+        let now = "2026-03-02T08:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut graph = WorkGraph::new();
+        let mut launch = make_task("launch", "Launch, phase 1; go");
+        launch.not_before = Some("2026-03-03T09:00:00Z".to_string());
+        launch.due = Some("2026-03-06T17:00:00+00:00".to_string());
+        launch.estimate = Some(Estimate {
+            hours: Some(4.0),
+            cost: None,
+        });
+        launch.tags = vec!["release".to_string()];
+        graph.add_node(Node::Task(launch));
+        let mut done = make_task("old", "Old");
+        done.status = Status::Done;
+        done.due = Some((now - Duration::days(1)).to_rfc3339());
+        graph.add_node(Node::Task(done));
+        graph.add_node(Node::Task(make_task("unscheduled", "Unscheduled")));
+
+        let ics = render_ics(&graph, &[], now, &WorkingHours::default());
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+        assert!(ics.contains(
+            "UID:launch-start@workgraph\r\nDTSTAMP:20260302T080000Z\r\nDTSTART:20260303T090000Z\r\n"
+        ));
+        assert!(ics.contains("UID:launch-due@workgraph"));
+        // ETA: not_before plus the 4h estimate
+        assert!(ics.contains(
+            "UID:launch-eta@workgraph\r\nDTSTAMP:20260302T080000Z\r\nDTSTART:20260303T130000Z\r\n"
+        ));
+        assert!(ics.contains("SUMMARY:Due: Launch\\, phase 1\\; go\r\n"));
+        assert!(ics.contains("CATEGORIES:release\r\n"));
+        assert!(!ics.contains("UID:old-"));
+
+        assert_eq!(
+            render_ics(
+                &graph,
+                &["other".to_string()],
+                now,
+                &WorkingHours::default()
+            )
+            .matches("BEGIN:VEVENT")
+            .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_push_line_folds_long_lines() {
+        let mut out = String::new();
+        push_line(&mut out, &"x".repeat(100));
+        let lines: Vec<&str> = out.split("\r\n").collect();
+        assert_eq!(lines[0].len(), 75);
+        assert_eq!(lines[1], format!(" {}", "x".repeat(25)));
+    }
+}
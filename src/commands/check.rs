@@ -25,7 +25,13 @@ struct CheckJsonOutput {
 
 pub fn run(dir: &Path, json: bool) -> Result<()> {
     let (graph, _path) = super::load_workgraph(dir)?;
-    let result = check_all(&graph);
+    let mut result = check_all(&graph);
+    // A `requires` entry may name a resource pool from config rather than a node
+    let pools = workgraph::config::Config::load_or_default(dir).pools;
+    result
+        .orphan_refs
+        .retain(|o| !(o.relation == "requires" && pools.contains_key(&o.to)));
+    result.ok = result.orphan_refs.is_empty();
     let cycle_analysis = graph.compute_cycle_analysis();
     let irreducible_count = cycle_analysis
         .cycles
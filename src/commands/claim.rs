@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use std::path::Path;
+use workgraph::changes;
+use workgraph::focus::Focuser;
 use workgraph::graph::{LogEntry, Status};
 use workgraph::parser::modify_graph;
+use workgraph::work_stealing;
 
 #[cfg(test)]
 use super::graph_path;
@@ -19,6 +22,8 @@ pub fn claim(dir: &Path, id: &str, actor: Option<&str>) -> Result<()> {
     let mut error: Option<anyhow::Error> = None;
     let mut prev_status = String::new();
     let mut prev_assigned: Option<String> = None;
+    // Diff base for the change summary `wg done` records.
+    let claim_sha = dir.parent().and_then(changes::head_sha);
 
     let _graph = modify_graph(&path, |graph| {
         let task = match graph.get_task_mut(id) {
@@ -111,6 +116,7 @@ pub fn claim(dir: &Path, id: &str, actor: Option<&str>) -> Result<()> {
 
         task.status = Status::InProgress;
         task.started_at = Some(Utc::now().to_rfc3339());
+        task.claim_sha = claim_sha.clone();
         if let Some(actor_id) = actor {
             task.assigned = Some(actor_id.to_string());
         }
@@ -155,6 +161,107 @@ pub fn claim(dir: &Path, id: &str, actor: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// The claiming actor (default: current user). `skills`, when given,
+/// replace the capabilities of an agency agent for matching.
+fn claimant(dir: &Path, actor: Option<&str>, skills: &[String]) -> Focuser {
+    let name = actor
+        .map(String::from)
+        .unwrap_or_else(workgraph::current_user);
+    let mut who = Focuser::resolve(dir, &name);
+    if !skills.is_empty() {
+        who.skills = Some(skills.to_vec());
+    }
+    who
+}
+
+/// Claim up to `n` ready tasks in one graph write: the first is claimed,
+/// the rest are queued for the actor (see [`workgraph::work_stealing`]).
+pub fn run_batch(dir: &Path, actor: Option<&str>, n: usize, skills: &[String]) -> Result<()> {
+    let path = super::graph_path(dir);
+    if !path.exists() {
+        anyhow::bail!("WG not initialized. Run 'wg init' first.");
+    }
+    let who = claimant(dir, actor, skills);
+    let config = workgraph::config::Config::load_or_default(dir);
+    let aging_hours = config.coordinator.priority_aging_hours;
+
+    let claim_sha = dir.parent().and_then(changes::head_sha);
+
+    let mut ids = Vec::new();
+    modify_graph(&path, |graph| {
+        ids = work_stealing::claim_batch(
+            graph,
+            &who,
+            n,
+            Utc::now(),
+            aging_hours,
+            claim_sha.as_deref(),
+        );
+        !ids.is_empty()
+    })
+    .context("Failed to save graph")?;
+
+    let Some((first, queued)) = ids.split_first() else {
+        println!("No ready tasks for '{}'", who.name);
+        return Ok(());
+    };
+    super::notify_graph_changed(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "claim_batch",
+        None,
+        Some(&who.name),
+        serde_json::json!({ "claimed": first, "queued": queued }),
+        config.log.rotation_threshold,
+    );
+
+    println!("Claimed '{}' for '{}'", first, who.name);
+    if !queued.is_empty() {
+        println!("Queued: {}", queued.join(", "));
+    }
+    Ok(())
+}
+
+/// Take over a queued task from the most overloaded actor.
+pub fn run_steal(dir: &Path, actor: Option<&str>, skills: &[String]) -> Result<()> {
+    let path = super::graph_path(dir);
+    if !path.exists() {
+        anyhow::bail!("WG not initialized. Run 'wg init' first.");
+    }
+    let who = claimant(dir, actor, skills);
+    let config = workgraph::config::Config::load_or_default(dir);
+    let aging_hours = config.coordinator.priority_aging_hours;
+
+    let claim_sha = dir.parent().and_then(changes::head_sha);
+
+    let mut stolen = None;
+    modify_graph(&path, |graph| {
+        stolen = work_stealing::steal(graph, &who, Utc::now(), aging_hours, claim_sha.as_deref());
+        stolen.is_some()
+    })
+    .context("Failed to save graph")?;
+
+    let Some(stolen) = stolen else {
+        println!("No queued tasks to take over for '{}'", who.name);
+        return Ok(());
+    };
+    super::notify_graph_changed(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "steal",
+        Some(&stolen.task_id),
+        Some(&who.name),
+        serde_json::json!({ "from": stolen.from }),
+        config.log.rotation_threshold,
+    );
+
+    println!(
+        "Claimed '{}' for '{}' (taken from @{}'s queue)",
+        stolen.task_id, who.name, stolen.from
+    );
+    Ok(())
+}
+
 /// Unclaim a task: sets status back to Open and clears assigned
 pub fn unclaim(dir: &Path, id: &str) -> Result<()> {
     let path = super::graph_path(dir);
@@ -523,4 +630,31 @@ mod tests {
         let err = result.unwrap_err();
         assert!(err.to_string().contains("not initialized"));
     }
+
+    #[test]
+    fn test_batch_claim_then_steal() {
+        let dir = tempdir().unwrap();
+        let dir_path = dir.path();
+        let mut rust = make_task("r1", "Rust one", Status::Open);
+        rust.skills = vec!["rust".to_string()];
+        let mut rust2 = make_task("r2", "Rust two", Status::Open);
+        rust2.skills = vec!["rust".to_string()];
+        let mut python = make_task("py", "Python", Status::Open);
+        python.skills = vec!["python".to_string()];
+        let path = setup_workgraph(dir_path, vec![rust, rust2, python]);
+
+        run_batch(dir_path, Some("bot"), 5, &["rust".to_string()]).unwrap();
+        let graph = load_graph(&path).unwrap();
+        assert_eq!(graph.get_task("r1").unwrap().status, Status::InProgress);
+        let r2 = graph.get_task("r2").unwrap();
+        assert_eq!(r2.status, Status::Open);
+        assert_eq!(r2.assigned.as_deref(), Some("bot"));
+        assert!(graph.get_task("py").unwrap().assigned.is_none());
+
+        run_steal(dir_path, Some("idle"), &[]).unwrap();
+        let graph = load_graph(&path).unwrap();
+        let r2 = graph.get_task("r2").unwrap();
+        assert_eq!(r2.status, Status::InProgress);
+        assert_eq!(r2.assigned.as_deref(), Some("idle"));
+    }
 }
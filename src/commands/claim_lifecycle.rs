@@ -238,6 +238,8 @@ mod tests {
                 output_file: "/tmp/output.log".to_string(),
                 model: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
         reg.save(dir).unwrap();
@@ -353,6 +355,8 @@ mod tests {
                 output_file: "/tmp/output.log".to_string(),
                 model: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
         reg.save(dir.path()).unwrap();
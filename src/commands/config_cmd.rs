@@ -2422,6 +2422,9 @@ fn apply_setting(config: &mut Config, key: &str, value: &str) -> Result<()> {
                 .parse::<u64>()
                 .map_err(|_| anyhow::anyhow!("expected positive integer (seconds)"))?;
         }
+        "agent.onboarding_brief" => {
+            config.agent.onboarding_brief = parse_bool(v)?;
+        }
         "coordinator.max_agents" | "dispatcher.max_agents" => {
             config.coordinator.max_agents = v
                 .parse::<usize>()
@@ -2661,6 +2664,80 @@ fn print_lint_one(r: &crate::commands::migrate::ConfigMigrateResult) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// `wg config diff` — effective config vs a baseline.
+// ---------------------------------------------------------------------------
+
+/// Print how the effective config differs from `against`: `"defaults"`, a
+/// config file path, or (when `None`) the project's `workgraph.toml`.
+pub fn diff_config(workgraph_dir: &Path, against: Option<&str>, json: bool) -> Result<()> {
+    use workgraph::config_drift;
+
+    let (baseline, label) = match against {
+        Some("defaults") => (config_drift::defaults()?, "built-in defaults".to_string()),
+        Some(file) => {
+            let path = Path::new(file);
+            (
+                config_drift::load_baseline(path)?,
+                path.display().to_string(),
+            )
+        }
+        None => {
+            let path = config_drift::baseline_path(workgraph_dir);
+            if !path.exists() {
+                anyhow::bail!(
+                    "No baseline at {}. Commit one there, or pass --against defaults|<file>",
+                    path.display()
+                );
+            }
+            (
+                config_drift::load_baseline(&path)?,
+                path.display().to_string(),
+            )
+        }
+    };
+    let effective = Config::load_merged(workgraph_dir)?;
+    let changes = config_drift::diff(&baseline, &effective)?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "against": label,
+                "changes": changes,
+            }))?
+        );
+        return Ok(());
+    }
+
+    if changes.is_empty() {
+        println!("Effective config matches {}.", label);
+        return Ok(());
+    }
+    println!(
+        "Effective config differs from {} in {} key{}:",
+        label,
+        changes.len(),
+        if changes.len() == 1 { "" } else { "s" }
+    );
+    let width = changes.iter().map(|c| c.key.len()).max().unwrap_or(0);
+    let show = |v: &Option<serde_json::Value>| {
+        v.as_ref()
+            .map(format_toml_value)
+            .unwrap_or_else(|| "(unset)".to_string())
+    };
+    for change in &changes {
+        println!(
+            "  {:width$}  {} → {}",
+            change.key,
+            show(&change.baseline),
+            show(&change.effective),
+            width = width
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,8 @@
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use std::path::Path;
+use workgraph::config::Config;
+use workgraph::cost_report::{self, GroupBy, UsageLine};
+use workgraph::graph::format_tokens;
 use workgraph::query::cost_of;
 
 pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
@@ -29,6 +32,85 @@ pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Monthly usage report for chargeback: spend per tag, milestone, model and
+/// agent, printed or exported as CSV/JSON.
+pub fn run_report(
+    dir: &Path,
+    month: Option<&str>,
+    by: &[String],
+    format: Option<&str>,
+    output: Option<&Path>,
+    json: bool,
+) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let config = Config::load_or_default(dir);
+
+    let month = match month {
+        Some(m) => cost_report::parse_month(m)?,
+        None => chrono::Utc::now().format("%Y-%m").to_string(),
+    };
+    let groups: Vec<GroupBy> = if by.is_empty() {
+        GroupBy::ALL.to_vec()
+    } else {
+        by.iter().map(|g| g.parse()).collect::<Result<_>>()?
+    };
+    let lines = cost_report::monthly_usage(&graph, &config, &month, &groups);
+
+    let content = match format.or(json.then_some("json")) {
+        Some("csv") => cost_report::to_csv(&lines)?,
+        Some("json") => serde_json::to_string_pretty(&lines)? + "\n",
+        Some(other) => bail!("Unknown format '{}' (expected csv or json)", other),
+        None if output.is_some() => bail!("--output needs --format csv or --format json"),
+        None => {
+            print_report(&month, &groups, &lines);
+            return Ok(());
+        }
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)
+                .with_context(|| format!("Failed to write '{}'", path.display()))?;
+            eprintln!("Exported {} usage to {}", month, path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+fn print_report(month: &str, groups: &[GroupBy], lines: &[UsageLine]) {
+    println!("Usage for {}", month);
+    if lines.is_empty() {
+        println!("  No recorded token usage.");
+        return;
+    }
+    for group in groups {
+        let group = group.to_string();
+        println!();
+        println!("By {}:", group);
+        let rows: Vec<_> = lines.iter().filter(|l| l.group == group).collect();
+        let width = rows.iter().map(|l| l.key.len()).max().unwrap_or(0);
+        for l in rows {
+            let tokens = l.input_tokens
+                + l.output_tokens
+                + l.cache_read_input_tokens
+                + l.cache_creation_input_tokens;
+            let charge = if l.currency == "USD" {
+                format!("${:.2}", l.charge)
+            } else {
+                format!("{:.2} {}", l.charge, l.currency)
+            };
+            println!(
+                "  {:<width$}  {:>4} tasks  {:>7} tokens  {}",
+                l.key,
+                l.tasks,
+                format_tokens(tokens),
+                charge,
+                width = width
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::graph_path;
@@ -272,6 +354,41 @@ mod tests {
         assert_eq!(cost_of(&graph, "t1"), 300.0);
     }
 
+    #[test]
+    fn test_run_report_exports_csv() {
+        let dir = tempdir().unwrap();
+        let mut task = make_task("t1", "Task");
+        task.status = workgraph::graph::Status::Done;
+        task.tags = vec!["billing".to_string()];
+        task.completed_at = Some("2026-03-10T10:00:00Z".to_string());
+        task.token_usage = Some(workgraph::graph::TokenUsage {
+            cost_usd: 1.5,
+            input_tokens: 100,
+            output_tokens: 50,
+            cache_read_input_tokens: 0,
+            cache_creation_input_tokens: 0,
+        });
+        setup_workgraph(dir.path(), vec![task]);
+
+        let out = dir.path().join("usage.csv");
+        let by = vec!["tag".to_string()];
+        run_report(
+            dir.path(),
+            Some("2026-03"),
+            &by,
+            Some("csv"),
+            Some(&out),
+            false,
+        )
+        .unwrap();
+        let csv = fs::read_to_string(&out).unwrap();
+        assert!(csv.contains("2026-03,tag,billing,1,100,50,0,0,1.5000"));
+
+        assert!(run_report(dir.path(), Some("2026-03"), &[], Some("xml"), None, false).is_err());
+        assert!(run_report(dir.path(), Some("March"), &[], None, None, false).is_err());
+        assert!(run_report(dir.path(), None, &[], None, None, false).is_ok());
+    }
+
     #[test]
     fn test_run_task_no_cost() {
         let dir = tempdir().unwrap();
@@ -3,7 +3,7 @@ use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use workgraph::format_hours;
-use workgraph::graph::{Status, WorkGraph};
+use workgraph::graph::{Status, Task, WorkGraph};
 
 /// Information about a task on the critical path
 #[derive(Debug, Clone, Serialize)]
@@ -13,6 +13,9 @@ struct CriticalTask {
     status: Status,
     hours: Option<f64>,
     after: Option<String>,
+    /// Hours the edge from `after` adds (its lag plus weight)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    edge_hours: Option<f64>,
 }
 
 /// Slack information for non-critical tasks
@@ -63,47 +66,7 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
     let cycles = detect_cycles_among_active(&graph, &active_ids);
     let cycle_nodes: HashSet<&str> = cycles.iter().flatten().map(String::as_str).collect();
 
-    // Build dependency graph (task_id -> list of tasks it blocks)
-    // This is the "forward" direction for finding paths
-    let forward_index = build_forward_index(&graph, &active_ids, &cycle_nodes);
-
-    // Find tasks with no active blockers (entry points)
-    let entry_points: Vec<&str> = active_tasks
-        .iter()
-        .filter(|t| !cycle_nodes.contains(t.id.as_str()))
-        .filter(|t| {
-            t.after.iter().all(|blocker_id| {
-                // Not blocked by any active non-terminal task
-                !active_ids.contains(blocker_id.as_str())
-                    || cycle_nodes.contains(blocker_id.as_str())
-                    || graph
-                        .get_task(blocker_id)
-                        .map(|bt| bt.status.is_terminal())
-                        .unwrap_or(true)
-            })
-        })
-        .map(|t| t.id.as_str())
-        .collect();
-
-    // Calculate longest path from each entry point using dynamic programming
-    // longest_path[task_id] = (total_hours, path_as_vec)
-    let mut memo: HashMap<&str, (f64, Vec<String>)> = HashMap::new();
-
-    for entry in &entry_points {
-        calculate_longest_path(entry, &graph, &forward_index, &mut memo, &cycle_nodes);
-    }
-
-    // Find the overall longest path
-    let (critical_path, total_hours) = if let Some((_, (hours, path))) =
-        memo.iter().max_by(|a, b| {
-            a.1.0
-                .partial_cmp(&b.1.0)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        }) {
-        (path.clone(), *hours)
-    } else {
-        (vec![], 0.0)
-    };
+    let (critical_path, total_hours) = longest_active_path(&graph, &active_ids, &cycle_nodes);
 
     // Build critical task info
     let critical_set: HashSet<&str> = critical_path.iter().map(String::as_str).collect();
@@ -122,6 +85,7 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
                     title: t.title.clone(),
                     status: t.status,
                     hours: t.estimate.as_ref().and_then(|e| e.hours),
+                    edge_hours: after.as_ref().and_then(|a| edge_hours(t, a)),
                     after,
                 }
             })
@@ -199,7 +163,11 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
                 let blocked_str = if i == 0 {
                     String::new()
                 } else {
-                    format!(" <- blocked by {}", critical_path[i - 1])
+                    let upstream = &critical_path[i - 1];
+                    match edge_hours(task, upstream) {
+                        Some(h) => format!(" <- blocked by {} (+{}h)", upstream, format_hours(h)),
+                        None => format!(" <- blocked by {}", upstream),
+                    }
                 };
 
                 println!(
@@ -236,6 +204,77 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Hours the edge from `upstream` to `task` adds to a path, if any.
+fn edge_hours(task: &Task, upstream: &str) -> Option<f64> {
+    task.edges
+        .get(upstream)
+        .map(|e| e.hours())
+        .filter(|h| *h > 0.0)
+}
+
+/// Critical path over the graph's active tasks: the longest dependency chain
+/// by estimated hours (plus edge lag and weight), upstream first, with tasks
+/// in cycles skipped.
+pub(crate) fn critical_path(graph: &WorkGraph) -> (Vec<String>, f64) {
+    let active_ids: HashSet<&str> = graph
+        .tasks()
+        .filter(|t| !t.status.is_terminal())
+        .map(|t| t.id.as_str())
+        .collect();
+    let cycles = detect_cycles_among_active(graph, &active_ids);
+    let cycle_nodes: HashSet<&str> = cycles.iter().flatten().map(String::as_str).collect();
+    longest_active_path(graph, &active_ids, &cycle_nodes)
+}
+
+/// Longest path among `active_ids` (excluding `cycle_nodes`) and its hours.
+fn longest_active_path(
+    graph: &WorkGraph,
+    active_ids: &HashSet<&str>,
+    cycle_nodes: &HashSet<&str>,
+) -> (Vec<String>, f64) {
+    // Build dependency graph (task_id -> list of tasks it blocks)
+    // This is the "forward" direction for finding paths
+    let forward_index = build_forward_index(graph, active_ids, cycle_nodes);
+
+    // Find tasks with no active blockers (entry points)
+    let entry_points: Vec<&str> = graph
+        .tasks()
+        .filter(|t| active_ids.contains(t.id.as_str()))
+        .filter(|t| !cycle_nodes.contains(t.id.as_str()))
+        .filter(|t| {
+            t.after.iter().all(|blocker_id| {
+                // Not blocked by any active non-terminal task
+                !active_ids.contains(blocker_id.as_str())
+                    || cycle_nodes.contains(blocker_id.as_str())
+                    || graph
+                        .get_task(blocker_id)
+                        .map(|bt| bt.status.is_terminal())
+                        .unwrap_or(true)
+            })
+        })
+        .map(|t| t.id.as_str())
+        .collect();
+
+    // Calculate longest path from each entry point using dynamic programming
+    // longest_path[task_id] = (total_hours, path_as_vec)
+    let mut memo: HashMap<&str, (f64, Vec<String>)> = HashMap::new();
+
+    for entry in &entry_points {
+        calculate_longest_path(entry, graph, &forward_index, &mut memo, cycle_nodes);
+    }
+
+    // Find the overall longest path
+    if let Some((_, (hours, path))) = memo.iter().max_by(|a, b| {
+        a.1.0
+            .partial_cmp(&b.1.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }) {
+        (path.clone(), *hours)
+    } else {
+        (vec![], 0.0)
+    }
+}
+
 /// Build forward index: task_id -> tasks that it blocks (among active non-cycle tasks)
 fn build_forward_index<'a>(
     graph: &'a WorkGraph,
@@ -302,7 +341,13 @@ fn calculate_longest_path<'a>(
         children
             .iter()
             .map(|child_id| {
-                calculate_longest_path(child_id, graph, forward_index, memo, cycle_nodes)
+                let (hours, path) =
+                    calculate_longest_path(child_id, graph, forward_index, memo, cycle_nodes);
+                let edge = graph
+                    .get_task(child_id)
+                    .and_then(|child| edge_hours(child, task_id))
+                    .unwrap_or(0.0);
+                (hours + edge, path)
             })
             .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
             .unwrap_or((0.0, vec![]))
@@ -404,19 +449,25 @@ mod tests {
             }),
             before: vec![],
             after: vec![],
+            edges: Default::default(),
             requires: vec![],
             tags: vec![],
             skills: vec![],
             inputs: vec![],
             deliverables: vec![],
+            expects: vec![],
+            gate: None,
             artifacts: vec![],
+            self_report: None,
             exec: None,
             timeout: None,
             not_before: None,
+            due: None,
             created_at: None,
             started_at: None,
             completed_at: None,
             last_interaction_at: None,
+            revision: 0,
             log: vec![],
             retry_count: 0,
             max_retries: None,
@@ -441,10 +492,20 @@ mod tests {
             exec_mode: None,
             cycle_config: None,
             token_usage: None,
+            max_cost: None,
+            min_context: None,
+            claim_sha: None,
+            changes: None,
             session_id: None,
             wait_condition: None,
             checkpoint: None,
             triage_count: 0,
+            escalation_level: 0,
+            blocked_at: None,
+            deadline_alert: None,
+            external_blocker: None,
+            waiting_on: vec![],
+            links: vec![],
             resurrection_count: 0,
             last_resurrected_at: None,
             validation: None,
@@ -584,6 +645,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_edge_lag_and_weight_lengthen_path() {
+        let mut graph = WorkGraph::new();
+
+        // Same shape as above, but t3 -> t4 waits a 1-day soak plus 2h:
+        // t1 -> t3 -> t4 = 8 + 2 + 26 + 4 = 40h beats t1 -> t2 -> t4 = 28h
+        let t1 = make_task_with_hours("t1", "Task 1", 8.0);
+        let mut t2 = make_task_with_hours("t2", "Task 2", 16.0);
+        t2.after = vec!["t1".to_string()];
+        let mut t3 = make_task_with_hours("t3", "Task 3", 2.0);
+        t3.after = vec!["t1".to_string()];
+        let mut t4 = make_task_with_hours("t4", "Task 4", 4.0);
+        t4.after = vec!["t2".to_string(), "t3".to_string()];
+        t4.edges.insert(
+            "t3".to_string(),
+            workgraph::graph::DependencyEdge {
+                lag: Some("1d".to_string()),
+                weight: Some(2.0),
+            },
+        );
+
+        graph.add_node(Node::Task(t1));
+        graph.add_node(Node::Task(t2));
+        graph.add_node(Node::Task(t3));
+        graph.add_node(Node::Task(t4));
+
+        let (path, hours) = critical_path(&graph);
+        assert_eq!(hours, 40.0);
+        assert_eq!(
+            path,
+            vec!["t1".to_string(), "t3".to_string(), "t4".to_string()]
+        );
+    }
+
     #[test]
     fn test_done_tasks_excluded() {
         let mut graph = WorkGraph::new();
@@ -0,0 +1,243 @@
+//! `wg diff` — compare the graph against a snapshot, branch, file, or git
+//! revision of `graph.jsonl`.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use workgraph::graph::WorkGraph;
+use workgraph::graph_diff::{self, GraphDiff};
+use workgraph::parser::{load_graph, parse_graph};
+use workgraph::{branch, snapshot};
+
+/// Resolve a graph reference to a label and the graph it names.
+///
+/// Accepts `snapshot:<name>`, `branch:<name>`, `file:<path>`, `git:<rev>`,
+/// or a bare value, which is tried as a file path, then a snapshot name,
+/// then a branch name, then a git revision.
+pub fn resolve_ref(dir: &Path, spec: &str) -> Result<(String, WorkGraph)> {
+    if let Some((kind, value)) = spec.split_once(':') {
+        match kind {
+            "snapshot" => return load_snapshot(dir, value),
+            "branch" => return load_branch(dir, value),
+            "file" => return load_file(Path::new(value)),
+            "git" => return load_git(dir, value),
+            _ => {}
+        }
+    }
+    if Path::new(spec).is_file() {
+        return load_file(Path::new(spec));
+    }
+    if snapshot::load_meta(dir, spec).is_ok() {
+        return load_snapshot(dir, spec);
+    }
+    if branch::load_meta(dir, spec).is_ok() {
+        return load_branch(dir, spec);
+    }
+    load_git(dir, spec).with_context(|| {
+        format!(
+            "'{}' is not a file, snapshot, branch, or git revision",
+            spec
+        )
+    })
+}
+
+fn load_snapshot(dir: &Path, name: &str) -> Result<(String, WorkGraph)> {
+    snapshot::load_meta(dir, name)?;
+    let graph = load_graph(snapshot::snapshot_dir(dir, name).join("graph.jsonl"))
+        .context("Failed to load snapshot graph")?;
+    Ok((format!("snapshot {}", name), graph))
+}
+
+fn load_branch(dir: &Path, name: &str) -> Result<(String, WorkGraph)> {
+    let graph = branch::load_branch_graph(dir, name)?;
+    Ok((format!("branch {}", name), graph))
+}
+
+fn load_file(path: &Path) -> Result<(String, WorkGraph)> {
+    let graph = load_graph(path)
+        .with_context(|| format!("Failed to load graph from {}", path.display()))?;
+    Ok((path.display().to_string(), graph))
+}
+
+fn load_git(dir: &Path, rev: &str) -> Result<(String, WorkGraph)> {
+    let graph_path = dir.join("graph.jsonl");
+    let graph_path = graph_path.canonicalize().unwrap_or(graph_path);
+    let cwd = graph_path.parent().unwrap_or(dir);
+
+    let toplevel = Command::new("git")
+        .arg("-C")
+        .arg(cwd)
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .context("Failed to run git")?;
+    if !toplevel.status.success() {
+        anyhow::bail!("{} is not inside a git repository", cwd.display());
+    }
+    let toplevel = String::from_utf8_lossy(&toplevel.stdout).trim().to_string();
+    let rel = graph_path
+        .strip_prefix(&toplevel)
+        .with_context(|| format!("{} is outside the git work tree", graph_path.display()))?;
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&toplevel)
+        .arg("show")
+        .arg(format!("{}:{}", rev, rel.display()))
+        .output()
+        .context("Failed to run git show")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git show {}:{} failed: {}",
+            rev,
+            rel.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let graph = parse_graph(&String::from_utf8_lossy(&output.stdout))
+        .with_context(|| format!("Failed to parse graph.jsonl at {}", rev))?;
+    Ok((format!("git {}", rev), graph))
+}
+
+/// Diff `from` against `to` (default: the current graph).
+pub fn run(dir: &Path, from: &str, to: Option<&str>, json: bool) -> Result<()> {
+    let (from_label, old) = resolve_ref(dir, from)?;
+    let (to_label, new) = match to {
+        Some(spec) => resolve_ref(dir, spec)?,
+        None => {
+            let (graph, _) = super::load_workgraph(dir)?;
+            ("current".to_string(), graph)
+        }
+    };
+    let diff = graph_diff::diff(&old, &new);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "from": from_label,
+                "to": to_label,
+                "diff": diff,
+            }))?
+        );
+    } else {
+        print_diff(&from_label, &to_label, &diff);
+    }
+    Ok(())
+}
+
+fn print_diff(from: &str, to: &str, diff: &GraphDiff) {
+    println!("Diff {} → {}", from, to);
+    if diff.is_empty() {
+        println!("\nNo task changes.");
+        return;
+    }
+    if !diff.added.is_empty() {
+        println!("\nAdded ({}):", diff.added.len());
+        for t in &diff.added {
+            println!("  + {} [{}] {}", t.id, t.status, t.title);
+        }
+    }
+    if !diff.removed.is_empty() {
+        println!("\nRemoved ({}):", diff.removed.len());
+        for t in &diff.removed {
+            println!("  - {} [{}] {}", t.id, t.status, t.title);
+        }
+    }
+    if !diff.status_changes.is_empty() {
+        println!("\nStatus changes ({}):", diff.status_changes.len());
+        for c in &diff.status_changes {
+            println!("  {} {} → {}  {}", c.id, c.from, c.to, c.title);
+        }
+    }
+    if !diff.dependency_changes.is_empty() {
+        println!("\nDependency changes ({}):", diff.dependency_changes.len());
+        for c in &diff.dependency_changes {
+            let mut parts = Vec::new();
+            if !c.added.is_empty() {
+                parts.push(format!("+after {}", c.added.join(", ")));
+            }
+            if !c.removed.is_empty() {
+                parts.push(format!("-after {}", c.removed.join(", ")));
+            }
+            println!("  {} {}", c.id, parts.join("; "));
+        }
+    }
+    if !diff.modified.is_empty() {
+        println!("\nOther edits ({}):", diff.modified.len());
+        for c in &diff.modified {
+            println!("  {} ({})", c.id, c.fields.join(", "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::graph::Status;
+    use workgraph::test_helpers::{make_task, make_task_with_status, setup_workgraph};
+
+    #[test]
+    fn test_resolve_snapshot_branch_and_file() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("t1", "One")]);
+        snapshot::create(&dir, Some("before"), None, false, false).unwrap();
+        branch::create(&dir, "plan-b", None, None).unwrap();
+        setup_workgraph(&dir, vec![make_task_with_status("t1", "One", Status::Done)]);
+
+        let (label, g) = resolve_ref(&dir, "before").unwrap();
+        assert_eq!(label, "snapshot before");
+        assert_eq!(g.get_task("t1").unwrap().status, Status::Open);
+        assert_eq!(
+            resolve_ref(&dir, "branch:plan-b").unwrap().0,
+            "branch plan-b"
+        );
+
+        let file = dir.join("graph.jsonl");
+        let (_, g) = resolve_ref(&dir, file.to_str().unwrap()).unwrap();
+        assert_eq!(g.get_task("t1").unwrap().status, Status::Done);
+
+        run(&dir, "before", None, false).unwrap();
+        run(&dir, "snapshot:before", Some("branch:plan-b"), true).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_git_revision() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("t1", "One")]);
+        let git = |args: &[&str]| {
+            let ok = Command::new("git")
+                .arg("-C")
+                .arg(tmp.path())
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .output()
+                .unwrap()
+                .status
+                .success();
+            assert!(ok, "git {:?} failed", args);
+        };
+        git(&["init", "-q"]);
+        git(&["add", "-A"]);
+        git(&["commit", "-qm", "init"]);
+        setup_workgraph(&dir, vec![make_task("t1", "One"), make_task("t2", "Two")]);
+
+        let (label, old) = resolve_ref(&dir, "HEAD").unwrap();
+        assert_eq!(label, "git HEAD");
+        let (current, _) = crate::commands::load_workgraph(&dir).unwrap();
+        let diff = graph_diff::diff(&old, &current);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].id, "t2");
+    }
+
+    #[test]
+    fn test_unknown_ref_errors() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("t1", "One")]);
+        assert!(resolve_ref(&dir, "no-such-thing").is_err());
+    }
+}
@@ -38,6 +38,17 @@ pub fn run(
     cron: Option<&str>,
     allow_phantom: bool,
     allow_cycle: bool,
+    priority: Option<&str>,
+    due: Option<&str>,
+    add_waiting_on: &[String],
+    remove_waiting_on: &[String],
+    lag: &[String],
+    edge_weight: &[String],
+    external_blocker: Option<&str>,
+    blocker_contact: Option<&str>,
+    blocker_expected: Option<&str>,
+    max_cost: Option<f64>,
+    min_context: Option<u64>,
 ) -> Result<()> {
     let path = graph_path(dir);
 
@@ -225,6 +236,27 @@ pub fn run(
                 println!("Not blocked by: {}", dep);
             }
         }
+        task.edges.retain(|dep, _| task.after.contains(dep));
+
+        // Edge lag / weight (dependencies must already be in `after`)
+        match super::add::apply_edge_specs(&mut task.edges, &task.after, lag, edge_weight) {
+            Ok(true) => {
+                for (dep, edge) in &task.edges {
+                    println!(
+                        "Edge {}: lag {}, weight {}",
+                        dep,
+                        edge.lag.as_deref().unwrap_or("none"),
+                        edge.weight.map_or("none".to_string(), |w| format!("{}h", w))
+                    );
+                }
+                changed = true;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                error = Some(e);
+                return false;
+            }
+        }
 
         // Add tags
         for tag in add_tag {
@@ -248,6 +280,35 @@ pub fn run(
             }
         }
 
+        // Add external event gates
+        for gate in add_waiting_on {
+            let gate = match workgraph::event_gate::parse_gate(gate) {
+                Ok(g) => g,
+                Err(e) => {
+                    error = Some(e);
+                    return false;
+                }
+            };
+            if !task.waiting_on.contains(&gate) {
+                println!("Waiting on: {}", gate);
+                task.waiting_on.push(gate);
+                changed = true;
+            } else {
+                println!("Already waiting on: {}", gate);
+            }
+        }
+
+        // Remove external event gates
+        for gate in remove_waiting_on {
+            if let Some(pos) = task.waiting_on.iter().position(|x| x == gate) {
+                task.waiting_on.remove(pos);
+                println!("No longer waiting on: {}", gate);
+                changed = true;
+            } else {
+                println!("Not waiting on: {}", gate);
+            }
+        }
+
         // Update model
         if let Some(new_model) = model {
             task.model = Some(new_model.to_string());
@@ -533,6 +594,152 @@ pub fn run(
             }
         }
 
+        // Update priority
+        if let Some(p) = priority {
+            let new_priority = super::add::parse_priority(Some(p));
+            if task.priority != new_priority {
+                println!("Changed priority: {} -> {}", task.priority, new_priority);
+                task.priority = new_priority;
+                changed = true;
+            }
+        }
+
+        // Update deadline; a new deadline starts its alerts afresh
+        if let Some(d) = due {
+            let new_due = if d.is_empty() {
+                None
+            } else {
+                match workgraph::deadline::parse_due(d, chrono::Utc::now()) {
+                    Some(dt) => Some(dt.to_rfc3339()),
+                    None => {
+                        error = Some(anyhow::anyhow!(
+                            "Invalid due date '{}'. Use ISO 8601, YYYY-MM-DD, or a duration like 3d",
+                            d
+                        ));
+                        return false;
+                    }
+                }
+            };
+            if task.due != new_due {
+                let old = task.due.clone();
+                match new_due {
+                    Some(ref ts) => println!("Set due: {}", ts),
+                    None => println!("Cleared due date"),
+                }
+                field_changes.push(serde_json::json!({"field": "due", "old": old, "new": new_due}));
+                task.due = new_due;
+                task.deadline_alert = None;
+                changed = true;
+            }
+        }
+
+        // Update the external blocker; a new expected date restarts follow-ups
+        if let Some(reason) = external_blocker {
+            let old = task.external_blocker.clone();
+            if reason.is_empty() {
+                if task.external_blocker.take().is_some() {
+                    println!("Cleared external blocker");
+                }
+            } else if let Some(ref mut blocker) = task.external_blocker {
+                blocker.reason = reason.to_string();
+            } else {
+                task.external_blocker = Some(workgraph::graph::ExternalBlocker {
+                    reason: reason.to_string(),
+                    contact: None,
+                    expected: None,
+                    since: chrono::Utc::now().to_rfc3339(),
+                    last_reminded_at: None,
+                });
+                println!("Set external blocker: {}", reason);
+            }
+            if task.external_blocker != old {
+                field_changes.push(serde_json::json!({"field": "external_blocker", "old": old, "new": task.external_blocker}));
+                changed = true;
+            }
+        }
+        if blocker_contact.is_some() || blocker_expected.is_some() {
+            let Some(ref mut blocker) = task.external_blocker else {
+                error = Some(anyhow::anyhow!(
+                    "Task '{}' has no external blocker. Set one with --external-blocker",
+                    task_id
+                ));
+                return false;
+            };
+            if let Some(c) = blocker_contact {
+                let new_contact = (!c.is_empty()).then(|| c.to_string());
+                if blocker.contact != new_contact {
+                    blocker.contact = new_contact;
+                    changed = true;
+                }
+            }
+            if let Some(d) = blocker_expected {
+                let new_expected = if d.is_empty() {
+                    None
+                } else {
+                    match workgraph::deadline::parse_due(d, chrono::Utc::now()) {
+                        Some(dt) => Some(dt.to_rfc3339()),
+                        None => {
+                            error = Some(anyhow::anyhow!(
+                                "Invalid expected date '{}'. Use ISO 8601, YYYY-MM-DD, or a duration like 3d",
+                                d
+                            ));
+                            return false;
+                        }
+                    }
+                };
+                if blocker.expected != new_expected {
+                    match new_expected {
+                        Some(ref ts) => println!("Set blocker expected: {}", ts),
+                        None => println!("Cleared blocker expected date"),
+                    }
+                    blocker.expected = new_expected;
+                    blocker.last_reminded_at = None;
+                    changed = true;
+                }
+            }
+        }
+
+        // Update the per-run spend limit; 0 clears
+        if let Some(cap) = max_cost {
+            if !(cap.is_finite() && cap >= 0.0) {
+                error = Some(anyhow::anyhow!(
+                    "Invalid --max-cost {}. Use an amount in USD, or 0 to clear",
+                    cap
+                ));
+                return false;
+            }
+            let new_cap = (cap > 0.0).then_some(cap);
+            if task.max_cost != new_cap {
+                match new_cap {
+                    Some(c) => println!("Set max cost: ${:.2}", c),
+                    None => println!("Cleared max cost"),
+                }
+                field_changes.push(
+                    serde_json::json!({"field": "max_cost", "old": task.max_cost, "new": new_cap}),
+                );
+                task.max_cost = new_cap;
+                changed = true;
+            }
+        }
+
+        // Update the context window an agent needs; 0 clears
+        if let Some(tokens) = min_context {
+            let new_min = (tokens > 0).then_some(tokens);
+            if task.min_context != new_min {
+                match new_min {
+                    Some(t) => println!("Set min context: {} tokens", t),
+                    None => println!("Cleared min context"),
+                }
+                field_changes.push(serde_json::json!({
+                    "field": "min_context",
+                    "old": task.min_context,
+                    "new": new_min,
+                }));
+                task.min_context = new_min;
+                changed = true;
+            }
+        }
+
         // Reset spawn failure counter on any edit — the user may have fixed
         // the root cause (e.g., exec_mode mismatch), so the circuit breaker
         // should give the task a fresh set of attempts.
@@ -688,6 +895,14 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )?;
 
         Ok(())
@@ -744,6 +959,14 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )?;
 
         crate::commands::add::run(
@@ -791,6 +1014,14 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )?;
 
         Ok(())
@@ -829,6 +1060,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
 
@@ -838,6 +1080,251 @@ mod tests {
         assert_eq!(task.title, "New Title");
     }
 
+    #[test]
+    fn test_edit_priority() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_graph(temp_dir.path()).unwrap();
+
+        let result = run(
+            temp_dir.path(),
+            "test-task",
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None, // cron
+            false,
+            false,
+            Some("high"),
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert!(result.is_ok());
+
+        let path = graph_path(temp_dir.path());
+        let graph = load_graph(&path).unwrap();
+        let task = graph.get_task("test-task").unwrap();
+        assert_eq!(task.priority, workgraph::graph::PRIORITY_HIGH);
+    }
+
+    #[test]
+    fn test_edit_due() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_graph(temp_dir.path()).unwrap();
+        let edit_due = |due: &str| {
+            run(
+                temp_dir.path(),
+                "test-task",
+                None,
+                None,
+                &[],
+                &[],
+                &[],
+                &[],
+                None,
+                None,
+                &[],
+                &[],
+                None,
+                None,
+                None,
+                false,
+                false,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None, // cron
+                false,
+                false,
+                None,
+                Some(due),
+                &[],
+                &[],
+                &[],
+                &[],
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+        };
+
+        edit_due("2026-03-05").unwrap();
+        let path = graph_path(temp_dir.path());
+        let graph = load_graph(&path).unwrap();
+        assert_eq!(
+            graph.get_task("test-task").unwrap().due.as_deref(),
+            Some("2026-03-05T23:59:59+00:00")
+        );
+
+        assert!(edit_due("someday").is_err());
+        edit_due("").unwrap();
+        let graph = load_graph(&path).unwrap();
+        assert_eq!(graph.get_task("test-task").unwrap().due, None);
+    }
+
+    #[test]
+    fn test_edit_max_cost() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_graph(temp_dir.path()).unwrap();
+        let edit_cap = |cap: f64| {
+            run(
+                temp_dir.path(),
+                "test-task",
+                None,
+                None,
+                &[],
+                &[],
+                &[],
+                &[],
+                None,
+                None,
+                &[],
+                &[],
+                None,
+                None,
+                None,
+                false,
+                false,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None, // cron
+                false,
+                false,
+                None,
+                None,
+                &[],
+                &[],
+                &[],
+                &[],
+                None,
+                None,
+                None,
+                Some(cap),
+                None,
+            )
+        };
+
+        edit_cap(2.5).unwrap();
+        let path = graph_path(temp_dir.path());
+        let graph = load_graph(&path).unwrap();
+        assert_eq!(graph.get_task("test-task").unwrap().max_cost, Some(2.5));
+
+        assert!(edit_cap(-1.0).is_err());
+        edit_cap(0.0).unwrap();
+        let graph = load_graph(&path).unwrap();
+        assert_eq!(graph.get_task("test-task").unwrap().max_cost, None);
+    }
+
+    #[test]
+    fn test_edit_external_blocker() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_graph(temp_dir.path()).unwrap();
+        let edit_blocker = |reason: Option<&str>, contact: Option<&str>, expected: Option<&str>| {
+            run(
+                temp_dir.path(),
+                "test-task",
+                None,
+                None,
+                &[],
+                &[],
+                &[],
+                &[],
+                None,
+                None,
+                &[],
+                &[],
+                None,
+                None,
+                None,
+                false,
+                false,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None, // cron
+                false,
+                false,
+                None,
+                None,
+                &[],
+                &[],
+                &[],
+                &[],
+                reason,
+                contact,
+                expected,
+                None,
+                None,
+            )
+        };
+        let path = graph_path(temp_dir.path());
+
+        // Contact needs a blocker to attach to
+        assert!(edit_blocker(None, Some("payments team"), None).is_err());
+
+        edit_blocker(Some("Waiting on payments API v2"), None, None).unwrap();
+        edit_blocker(None, Some("payments team"), Some("2026-03-05")).unwrap();
+        let graph = load_graph(&path).unwrap();
+        let blocker = graph
+            .get_task("test-task")
+            .unwrap()
+            .external_blocker
+            .clone()
+            .unwrap();
+        assert_eq!(blocker.reason, "Waiting on payments API v2");
+        assert_eq!(blocker.contact.as_deref(), Some("payments team"));
+        assert_eq!(
+            blocker.expected.as_deref(),
+            Some("2026-03-05T23:59:59+00:00")
+        );
+
+        assert!(edit_blocker(None, None, Some("someday")).is_err());
+        edit_blocker(Some(""), None, None).unwrap();
+        let graph = load_graph(&path).unwrap();
+        assert_eq!(graph.get_task("test-task").unwrap().external_blocker, None);
+    }
+
     #[test]
     fn test_edit_description() {
         let temp_dir = TempDir::new().unwrap();
@@ -871,6 +1358,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
 
@@ -913,6 +1411,17 @@ mod tests {
             None,  // cron
             true,  // allow_phantom: dep2 doesn't exist in test graph
             false, // allow_cycle: tests should not allow cycles by default
+            None,  // priority
+            None,  // due
+            &[],   // add_waiting_on
+            &[],   // remove_waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // external_blocker
+            None,  // blocker_contact
+            None,  // blocker_expected
+            None,  // max_cost
+            None,  // min_context
         );
         assert!(result.is_ok());
 
@@ -956,6 +1465,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
 
@@ -998,6 +1518,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
 
@@ -1041,6 +1572,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
 
@@ -1083,6 +1625,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
 
@@ -1125,6 +1678,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
 
@@ -1168,6 +1732,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
 
@@ -1210,6 +1785,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
@@ -1248,6 +1834,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
     }
@@ -1285,6 +1882,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_err());
         assert!(
@@ -1329,6 +1937,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
 
@@ -1377,6 +1996,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1409,6 +2039,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         );
         assert!(result.is_ok());
 
@@ -1466,6 +2107,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1531,6 +2183,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1577,6 +2240,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1618,6 +2292,17 @@ mod tests {
             None, // cron
             false,
             false,
+            None,
+            None,
+            &[],
+            &[],
+            &[],
+            &[],
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1687,6 +2372,17 @@ mod tests {
             None, // cron
             false,
             false, // allow_cycle = false
+            None,  // priority
+            None,  // due
+            &[],   // add_waiting_on
+            &[],   // remove_waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // external_blocker
+            None,  // blocker_contact
+            None,  // blocker_expected
+            None,  // max_cost
+            None,  // min_context
         );
 
         // Should fail with cycle detection message
@@ -1753,6 +2449,17 @@ mod tests {
             None, // cron
             false,
             true, // allow_cycle = true
+            None, // priority
+            None, // due
+            &[],  // add_waiting_on
+            &[],  // remove_waiting_on
+            &[],
+            &[],
+            None, // external_blocker
+            None, // blocker_contact
+            None, // blocker_expected
+            None, // max_cost
+            None, // min_context
         );
 
         // Should succeed when allow_cycle is true
@@ -14,6 +14,8 @@ use workgraph::config::Config;
 use workgraph::graph::{LogEntry, Status, TokenUsage};
 use workgraph::parser::load_graph;
 use workgraph::provenance;
+use workgraph::rubric::{self, RubricResult};
+use workgraph::test_results;
 
 /// Extract the model from a task's spawn log entry.
 ///
@@ -42,7 +44,7 @@ const MAX_DIFF_BYTES: usize = 30_000;
 /// Compute a git diff of artifact files, diffing from the commit closest to
 /// `started_at` up to HEAD. Returns `None` if git is unavailable, there are no
 /// artifacts, or no diff could be computed.
-fn compute_artifact_diff(artifacts: &[String], started_at: Option<&str>) -> Option<String> {
+pub(crate) fn compute_artifact_diff(artifacts: &[String], started_at: Option<&str>) -> Option<String> {
     if artifacts.is_empty() {
         return None;
     }
@@ -384,6 +386,20 @@ pub fn run(
         .filter(|cf| cf.total_constraints > 0)
         .map(|cf| cf.unanchored_constraints);
 
+    // Step 3.10: Latest recorded test run, failures split into real and known-flaky.
+    let test_results_owned: Option<String> = test_results::latest(dir, task_id).map(|report| {
+        let triage = test_results::triage(
+            &report,
+            &test_results::FlakyRegistry::load(dir),
+            &config.coordinator.flaky_tests,
+        );
+        test_results::render_summary(&report, &triage)
+    });
+
+    // Step 3.11: Project rubrics attached to the task by tag or function.
+    let rubrics = rubric::for_task(dir, task);
+    let rubric_section = (!rubrics.is_empty()).then(|| rubric::render_prompt_section(&rubrics));
+
     // Step 4: Build evaluator prompt
     let evaluated_outcome = role
         .as_ref()
@@ -411,6 +427,8 @@ pub fn run(
         child_tasks: &child_tasks,
         constraint_fidelity_score: cf_score,
         constraint_fidelity_unanchored: cf_unanchored,
+        test_results: test_results_owned.as_deref(),
+        rubric: rubric_section.as_deref(),
     };
 
     let prompt = render_evaluator_prompt(&evaluator_input);
@@ -445,6 +463,10 @@ pub fn run(
         );
         println!("Artifacts: {}", artifacts.len());
         println!("Log entries: {}", log_entries.len());
+        if !rubrics.is_empty() {
+            let ids: Vec<&str> = rubrics.iter().map(|r| r.id.as_str()).collect();
+            println!("Rubrics: {}", ids.join(", "));
+        }
         println!("Evaluator model: {}", model);
         println!("\n--- Evaluator Prompt ---\n");
         println!("{}", prompt);
@@ -522,6 +544,9 @@ pub fn run(
         dimensions.insert("constraint_fidelity".to_string(), score);
     }
 
+    // Step 7.6: Check the rubric criteria the evaluator scored.
+    let rubric_results: Vec<RubricResult> = rubrics.iter().map(|r| r.assess(&dimensions)).collect();
+
     let evaluation = Evaluation {
         id: eval_id,
         task_id: task_id.to_string(),
@@ -552,6 +577,7 @@ pub fn run(
                 "notes": evaluation.notes,
                 "evaluator": evaluation.evaluator,
                 "model": evaluation.model,
+                "rubrics": rubric_results,
                 "path": eval_path.display().to_string(),
             });
             println!("{}", serde_json::to_string_pretty(&out)?);
@@ -596,6 +622,7 @@ pub fn run(
             if let Some(b) = evaluation.dimensions.get("blocking_impact") {
                 println!("  blocking_impact:        {:.2}", b);
             }
+            print_rubric_results(&rubric_results);
             println!("Notes:      {}", evaluation.notes);
             println!("Evaluator:  {}", evaluation.evaluator);
             println!("Saved to:   {}", eval_path.display());
@@ -615,6 +642,7 @@ pub fn run(
                 "notes": evaluation.notes,
                 "evaluator": evaluation.evaluator,
                 "model": evaluation.model,
+                "rubrics": rubric_results,
                 "path": eval_path.display().to_string(),
                 "warning": "No identity assigned — performance records not updated",
             });
@@ -626,6 +654,7 @@ pub fn run(
                 println!("Model:      {}", m);
             }
             println!("Score:      {:.2}", evaluation.score);
+            print_rubric_results(&rubric_results);
             println!("Notes:      {}", evaluation.notes);
             println!("Evaluator:  {}", evaluation.evaluator);
             println!("Saved to:   {}", eval_path.display());
@@ -678,7 +707,8 @@ pub fn run(
             .map(|t| t.status == Status::PendingValidation)
             .unwrap_or(false);
         if still_pending {
-            let gate = GateDecision::from_evaluation(&evaluation, &config);
+            let gate = GateDecision::from_evaluation(&evaluation, &config)
+                .with_rubric_results(&rubric_results);
             match apply_gate_decision(dir, task_id, &gate, &config) {
                 Ok(action) => {
                     if !json {
@@ -761,6 +791,25 @@ pub fn run(
     Ok(())
 }
 
+/// Print one line per rubric with its weighted score, then the criteria.
+fn print_rubric_results(results: &[RubricResult]) {
+    for result in results {
+        println!(
+            "Rubric:     {} {:.2} / {:.2} — {}",
+            result.rubric,
+            result.score,
+            result.pass_threshold,
+            if result.passed { "pass" } else { "FAIL" }
+        );
+        for (name, score) in &result.criteria {
+            match score {
+                Some(s) => println!("  {:<22}{:.2}", format!("{}:", name), s),
+                None => println!("  {:<22}(not scored)", format!("{}:", name)),
+            }
+        }
+    }
+}
+
 /// Run FLIP (Fidelity via Latent Intent Probing) evaluation of a completed task.
 ///
 /// Two-phase roundtrip intent fidelity evaluation:
@@ -1791,6 +1840,20 @@ impl GateDecision {
             }
         }
     }
+
+    /// Fail the gate when any applicable rubric did not pass. The rubric is a
+    /// deterministic check over the evaluator's scores, so the failure is
+    /// reported with full confidence and its reasons become `must_fix` items.
+    pub fn with_rubric_results(mut self, results: &[RubricResult]) -> Self {
+        let failures: Vec<String> = results.iter().flat_map(RubricResult::failures).collect();
+        if failures.is_empty() {
+            return self;
+        }
+        self.decision = GateVerdict::Fail;
+        self.confidence = 1.0;
+        self.must_fix = failures;
+        self
+    }
 }
 
 /// Apply a gate decision to a source task that is in PendingValidation
@@ -2216,6 +2279,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gate_decision_failed_rubric_overrides_pass() {
+        let pass = GateDecision {
+            decision: GateVerdict::Pass,
+            confidence: 0.9,
+            must_fix: vec![],
+            rationale: "looks good".to_string(),
+        };
+        let failed = RubricResult {
+            rubric: "api-change".to_string(),
+            score: 0.5,
+            pass_threshold: 0.7,
+            passed: false,
+            criteria: vec![("compat".to_string(), Some(0.5))],
+            below_min: vec![],
+            missing: vec![],
+        };
+        let passed = RubricResult {
+            passed: true,
+            score: 0.8,
+            ..failed.clone()
+        };
+
+        let gate = pass.clone().with_rubric_results(&[passed]);
+        assert_eq!(gate.decision, GateVerdict::Pass);
+
+        let gate = pass.with_rubric_results(&[failed]);
+        assert_eq!(gate.decision, GateVerdict::Fail);
+        assert_eq!(gate.must_fix.len(), 1);
+        assert!(gate.must_fix[0].contains("api-change"));
+    }
+
     // -------------------------------------------------------------------
     // In-place eval-fail iteration tests (in-place-eval task)
     // -------------------------------------------------------------------
@@ -0,0 +1,95 @@
+//! `wg event` — external event gates (`waiting_on: webhook:<name>`).
+//!
+//! `wg event fire` is the local equivalent of `POST /hooks/<name>` to the
+//! service's webhook listener, for CI scripts running on the same machine.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Fire `webhook:<name>`, releasing every task waiting on it.
+pub fn run_fire(dir: &Path, name: &str, json: bool) -> Result<()> {
+    let released = workgraph::event_gate::fire_at(&super::graph_path(dir), name, "cli")?;
+    if !released.is_empty() {
+        super::notify_graph_changed(dir);
+
+        let config = workgraph::config::Config::load_or_default(dir);
+        let _ = workgraph::provenance::record(
+            dir,
+            "event_fire",
+            None,
+            None,
+            serde_json::json!({ "event": name, "released": released }),
+            config.log.rotation_threshold,
+        );
+    }
+
+    if json {
+        let output = serde_json::json!({ "event": name, "released": released });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else if released.is_empty() {
+        println!("No tasks waiting on webhook:{}", name);
+    } else {
+        println!(
+            "Fired webhook:{} for {} task(s): {}",
+            name,
+            released.len(),
+            released.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// List pending gates and the unfinished tasks waiting on each.
+pub fn run_list(dir: &Path, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let mut gates: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for task in graph.tasks().filter(|t| !t.status.is_terminal()) {
+        for gate in &task.waiting_on {
+            gates.entry(gate).or_default().push(&task.id);
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&gates)?);
+        return Ok(());
+    }
+    if gates.is_empty() {
+        println!("No tasks are waiting on external events");
+        return Ok(());
+    }
+    for (gate, tasks) in &gates {
+        println!("{}  ({})", gate, tasks.join(", "));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::graph::{Node, WorkGraph};
+    use workgraph::parser::{load_graph, save_graph};
+    use workgraph::query::ready_tasks;
+    use workgraph::test_helpers::make_task;
+
+    #[test]
+    fn test_fire_makes_task_ready() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let mut graph = WorkGraph::new();
+        let mut task = make_task("deploy", "Deploy");
+        task.waiting_on = vec!["webhook:ci-green".to_string()];
+        graph.add_node(Node::Task(task));
+        save_graph(&graph, super::super::graph_path(dir)).unwrap();
+        assert!(ready_tasks(&graph).is_empty());
+
+        assert!(run_fire(dir, "bad/name", false).is_err());
+        run_fire(dir, "other", false).unwrap();
+        run_fire(dir, "ci-green", false).unwrap();
+
+        let graph = load_graph(super::super::graph_path(dir)).unwrap();
+        let ready: Vec<_> = ready_tasks(&graph).iter().map(|t| t.id.clone()).collect();
+        assert_eq!(ready, ["deploy"]);
+    }
+}
@@ -231,6 +231,7 @@ fn apply_role_with_direct_hashes(
         lineage,
         default_context_scope: None,
         default_exec_mode: None,
+        tool_policy: Default::default(),
     };
 
     let path = agency::save_role(&role, roles_dir).context("Failed to save role")?;
@@ -329,6 +330,7 @@ fn apply_create_role_from_skill_names(
         },
         default_context_scope: None,
         default_exec_mode: None,
+        tool_policy: Default::default(),
     };
 
     let path = agency::save_role(&role, roles_dir).context("Failed to save new role")?;
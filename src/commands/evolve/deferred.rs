@@ -97,19 +97,25 @@ pub(crate) fn defer_self_mutation(
         estimate: None,
         before: vec![],
         after: vec![],
+        edges: Default::default(),
         requires: vec![],
         tags: vec!["evolution".to_string(), "agency".to_string()],
         skills: vec![],
         inputs: vec![],
         deliverables: vec![],
+        expects: vec![],
+        gate: None,
         artifacts: vec![],
+        self_report: None,
         exec: None,
         timeout: None,
         not_before: None,
+        due: None,
         created_at: Some(Utc::now().to_rfc3339()),
         started_at: None,
         completed_at: None,
         last_interaction_at: None,
+        revision: 0,
         log: vec![],
         retry_count: 0,
         max_retries: None,
@@ -133,10 +139,20 @@ pub(crate) fn defer_self_mutation(
         context_scope: None,
         cycle_config: None,
         token_usage: None,
+        max_cost: None,
+        min_context: None,
+        claim_sha: None,
+        changes: None,
         session_id: None,
         wait_condition: None,
         checkpoint: None,
         triage_count: 0,
+        escalation_level: 0,
+        blocked_at: None,
+        deadline_alert: None,
+        external_blocker: None,
+        waiting_on: vec![],
+        links: vec![],
         resurrection_count: 0,
         last_resurrected_at: None,
         validation: None,
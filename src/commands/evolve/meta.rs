@@ -88,6 +88,7 @@ pub(crate) fn apply_random_compose_role(
         },
         default_context_scope: None,
         default_exec_mode: None,
+        tool_policy: Default::default(),
     };
 
     let path = agency::save_role(&new_role, &roles_dir)?;
@@ -174,6 +175,8 @@ pub(crate) fn apply_random_compose_agent(
         deployment_history: vec![],
         attractor_weight: 0.3,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
 
     let path = agency::save_agent(&new_agent, &agents_dir)?;
@@ -410,6 +413,8 @@ pub(crate) fn apply_meta_swap_role(
         deployment_history: vec![],
         attractor_weight: 0.3,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
 
     let path = agency::save_agent(&new_agent, &agents_dir)?;
@@ -502,6 +507,8 @@ pub(crate) fn apply_meta_swap_tradeoff(
         deployment_history: vec![],
         attractor_weight: 0.3,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
 
     let path = agency::save_agent(&new_agent, &agents_dir)?;
@@ -602,6 +609,8 @@ pub(crate) fn apply_meta_compose_agent(
             deployment_history: vec![],
             attractor_weight: 0.3,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         agency::save_agent(&new_agent, &agents_dir)?
     };
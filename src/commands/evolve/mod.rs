@@ -426,6 +426,8 @@ pub fn run(
                 deployment_history: vec![],
                 attractor_weight: 0.3,
                 staleness_flags: vec![],
+                capability_doc: None,
+                daily_budget: None,
             };
 
             match agency::save_agent(&new_agent, &agents_dir) {
@@ -841,6 +843,7 @@ mod tests {
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         }];
         let motivations = vec![TradeoffConfig {
             id: "m1".into(),
@@ -943,6 +946,7 @@ mod tests {
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         };
 
         let op = EvolverOperation {
@@ -1000,6 +1004,7 @@ mod tests {
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         };
         let role_b = Role {
             id: "role-b".into(),
@@ -1011,6 +1016,7 @@ mod tests {
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         };
 
         agency::save_role(&role_a, &roles_dir).unwrap();
@@ -1064,6 +1070,7 @@ mod tests {
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         };
         agency::save_role(&role, &roles_dir).unwrap();
 
@@ -1935,6 +1942,7 @@ Let me know if you'd like me to adjust anything."#;
             },
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         };
 
         let parent_b = Role {
@@ -1957,6 +1965,7 @@ Let me know if you'd like me to adjust anything."#;
             },
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         };
 
         let op = EvolverOperation {
@@ -2282,6 +2291,7 @@ Let me know if you'd like me to adjust anything."#;
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         }]
     }
 
@@ -2530,6 +2540,7 @@ Let me know if you'd like me to adjust anything."#;
                 lineage: Lineage::default(),
                 default_context_scope: None,
                 default_exec_mode: None,
+                tool_policy: Default::default(),
             },
             Role {
                 id: "r2".into(),
@@ -2545,6 +2556,7 @@ Let me know if you'd like me to adjust anything."#;
                 lineage: Lineage::default(),
                 default_context_scope: None,
                 default_exec_mode: None,
+                tool_policy: Default::default(),
             },
         ];
         let motivations = vec![TradeoffConfig {
@@ -2674,6 +2686,7 @@ Let me know if you'd like me to adjust anything."#;
             },
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         };
 
         let op = EvolverOperation {
@@ -2734,6 +2747,7 @@ Let me know if you'd like me to adjust anything."#;
             },
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         };
         let parent_b = Role {
             id: "pb".into(),
@@ -2751,6 +2765,7 @@ Let me know if you'd like me to adjust anything."#;
             },
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         };
 
         let op = EvolverOperation {
@@ -2930,6 +2945,8 @@ Let me know if you'd like me to adjust anything."#;
                 deployment_history: vec![],
                 attractor_weight: 0.3,
                 staleness_flags: vec![],
+                capability_doc: None,
+                daily_budget: None,
             };
             agency::save_agent(&new_agent, &agents_dir).unwrap();
         }
@@ -2968,6 +2985,8 @@ Let me know if you'd like me to adjust anything."#;
             deployment_history: vec![],
             attractor_weight: 0.3,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         agency::save_agent(&existing_agent, &agents_dir).unwrap();
 
@@ -185,6 +185,7 @@ fn apply_create_role(
         },
         default_context_scope: None,
         default_exec_mode: None,
+        tool_policy: Default::default(),
     };
 
     let path = agency::save_role(&role, roles_dir).context("Failed to save new role")?;
@@ -270,6 +271,7 @@ fn apply_modify_role(
         lineage,
         default_context_scope: None,
         default_exec_mode: None,
+        tool_policy: Default::default(),
     };
 
     let path = agency::save_role(&role, roles_dir).context("Failed to save modified role")?;
@@ -740,6 +742,7 @@ fn apply_component_substitution(
         lineage: Lineage::mutation(target_id, old_role.lineage.generation, run_id),
         default_context_scope: old_role.default_context_scope.clone(),
         default_exec_mode: old_role.default_exec_mode.clone(),
+        tool_policy: old_role.tool_policy.clone(),
     };
 
     let path = agency::save_role(&new_role, roles_dir)?;
@@ -799,6 +802,7 @@ fn apply_config_add_component(
         lineage: Lineage::mutation(target_id, old_role.lineage.generation, run_id),
         default_context_scope: old_role.default_context_scope.clone(),
         default_exec_mode: old_role.default_exec_mode.clone(),
+        tool_policy: old_role.tool_policy.clone(),
     };
 
     let path = agency::save_role(&new_role, roles_dir)?;
@@ -859,6 +863,7 @@ fn apply_config_remove_component(
         lineage: Lineage::mutation(target_id, old_role.lineage.generation, run_id),
         default_context_scope: old_role.default_context_scope.clone(),
         default_exec_mode: old_role.default_exec_mode.clone(),
+        tool_policy: old_role.tool_policy.clone(),
     };
 
     let path = agency::save_role(&new_role, roles_dir)?;
@@ -912,6 +917,7 @@ fn apply_config_swap_outcome(
         lineage: Lineage::mutation(target_id, old_role.lineage.generation, run_id),
         default_context_scope: old_role.default_context_scope.clone(),
         default_exec_mode: old_role.default_exec_mode.clone(),
+        tool_policy: old_role.tool_policy.clone(),
     };
 
     let path = agency::save_role(&new_role, roles_dir)?;
@@ -971,6 +977,8 @@ fn apply_config_swap_tradeoff(
         deployment_history: vec![],
         attractor_weight: 0.3, // untested new config
         staleness_flags: vec![],
+        capability_doc: old_agent.capability_doc.clone(),
+        daily_budget: old_agent.daily_budget,
     };
 
     let path = agency::save_agent(&new_agent, &agents_dir)?;
@@ -6,7 +6,7 @@ use std::process::Command;
 use workgraph::config::Config;
 use workgraph::graph::{LogEntry, Status};
 use workgraph::parser::{load_graph, modify_graph};
-use workgraph::service::executor::{TemplateVars, build_prompt};
+use workgraph::service::executor::{SystemFacts, TemplateVars, build_prompt};
 
 use super::spawn::context::{
     build_scope_context, build_task_context, discover_test_files, format_test_discovery_context,
@@ -234,6 +234,7 @@ pub fn run_interactive(
 
     // Build template vars
     let mut vars = TemplateVars::from_task(task, Some(&task_context), Some(dir));
+    vars.facts.related_done = SystemFacts::count_related_done(&graph, task);
 
     // Detect failed dependencies for triage mode
     let mut failed_deps_lines = Vec::new();
@@ -269,6 +270,8 @@ pub fn run_interactive(
         ("WG_AGENT_ID".into(), format!("exec-{}", agent_label)),
         ("WG_EXECUTOR_TYPE".into(), "claude".into()),
         ("WG_USER".into(), user.clone()),
+        ("WG_DIR".into(), dir.display().to_string()),
+        ("WG_GRAPH".into(), workgraph::graphs::current_name(dir)),
     ];
     if let Some(m) = model {
         env_vars.push(("WG_MODEL".into(), m.to_string()));
@@ -0,0 +1,224 @@
+//! `wg link` — external references (issues, PRs, docs, designs) on tasks.
+//!
+//! Not to be confused with [`super::link`], which manages dependency edges
+//! for `wg add-dep` / `wg rm-dep`.
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use workgraph::graph::{LinkKind, TaskLink};
+use workgraph::parser::modify_graph;
+
+#[cfg(test)]
+use super::graph_path;
+#[cfg(test)]
+use workgraph::parser::load_graph;
+
+/// Parse a link type name.
+pub fn parse_kind(kind: &str) -> Result<LinkKind> {
+    LinkKind::parse(kind).with_context(|| {
+        let kinds: Vec<String> = LinkKind::ALL.iter().map(|k| k.to_string()).collect();
+        format!(
+            "Unknown link type '{}'. Use one of: {}",
+            kind,
+            kinds.join(", ")
+        )
+    })
+}
+
+/// Links are rendered as hyperlinks, so only web URLs are accepted.
+pub fn validate_url(url: &str) -> Result<()> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"));
+    match rest {
+        Some(rest) if !rest.is_empty() && !url.chars().any(char::is_whitespace) => Ok(()),
+        _ => bail!("Invalid URL '{}': expected http:// or https://", url),
+    }
+}
+
+/// Attach a link to a task. Re-adding a URL updates its type and label.
+pub fn run_add(
+    dir: &Path,
+    task_id: &str,
+    kind: &str,
+    url: &str,
+    label: Option<&str>,
+) -> Result<()> {
+    let kind = parse_kind(kind)?;
+    validate_url(url)?;
+    let path = super::graph_path(dir);
+    let mut error: Option<anyhow::Error> = None;
+    let mut unchanged = false;
+    let mut updated = false;
+    let link = TaskLink {
+        kind,
+        url: url.to_string(),
+        label: label.map(String::from),
+    };
+    modify_graph(&path, |graph| {
+        let task = match graph.get_task_mut(task_id) {
+            Some(t) => t,
+            None => {
+                error = Some(anyhow::anyhow!("Task '{}' not found", task_id));
+                return false;
+            }
+        };
+        match task.links.iter_mut().find(|l| l.url == link.url) {
+            Some(existing) if *existing == link => {
+                unchanged = true;
+                return false;
+            }
+            Some(existing) => {
+                *existing = link.clone();
+                updated = true;
+            }
+            None => task.links.push(link.clone()),
+        }
+        true
+    })
+    .context("Failed to modify graph")?;
+    if let Some(e) = error {
+        return Err(e);
+    }
+    if unchanged {
+        println!("Link {} already on task '{}'", url, task_id);
+        return Ok(());
+    }
+    super::notify_graph_changed(dir);
+
+    // Record operation
+    let config = workgraph::config::Config::load_or_default(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "link_add",
+        Some(task_id),
+        None,
+        serde_json::json!({ "type": kind, "url": url, "label": label }),
+        config.log.rotation_threshold,
+    );
+
+    let verb = if updated { "Updated" } else { "Linked" };
+    println!("{} {} {} on task '{}'", verb, kind, url, task_id);
+    Ok(())
+}
+
+/// Remove a link by URL.
+pub fn run_remove(dir: &Path, task_id: &str, url: &str) -> Result<()> {
+    let path = super::graph_path(dir);
+    let mut error: Option<anyhow::Error> = None;
+    modify_graph(&path, |graph| {
+        let task = match graph.get_task_mut(task_id) {
+            Some(t) => t,
+            None => {
+                error = Some(anyhow::anyhow!("Task '{}' not found", task_id));
+                return false;
+            }
+        };
+        let original_len = task.links.len();
+        task.links.retain(|l| l.url != url);
+        if task.links.len() == original_len {
+            error = Some(anyhow::anyhow!(
+                "Link '{}' not found on task '{}'",
+                url,
+                task_id
+            ));
+            return false;
+        }
+        true
+    })
+    .context("Failed to modify graph")?;
+    if let Some(e) = error {
+        return Err(e);
+    }
+    super::notify_graph_changed(dir);
+
+    // Record operation
+    let config = workgraph::config::Config::load_or_default(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "link_rm",
+        Some(task_id),
+        None,
+        serde_json::json!({ "url": url }),
+        config.log.rotation_threshold,
+    );
+
+    println!("Removed link {} from task '{}'", url, task_id);
+    Ok(())
+}
+
+/// List a task's links.
+pub fn run_list(dir: &Path, task_id: &str, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let task = graph.get_task_or_err(task_id)?;
+
+    if json {
+        let output = serde_json::json!({
+            "task_id": task_id,
+            "links": task.links,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if task.links.is_empty() {
+        println!("No links on task '{}'", task_id);
+        return Ok(());
+    }
+    println!("Task: {} - {}", task.id, task.title);
+    for link in &task.links {
+        match link.label {
+            Some(ref label) => println!("  [{}] {} — {}", link.kind, link.url, label),
+            None => println!("  [{}] {}", link.kind, link.url),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::graph::{Node, WorkGraph};
+    use workgraph::parser::save_graph;
+    use workgraph::test_helpers::make_task;
+
+    fn setup_graph() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("t1", "Test Task")));
+        save_graph(&graph, graph_path(temp_dir.path())).unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_add_update_remove_link() {
+        let temp_dir = setup_graph();
+        let dir = temp_dir.path();
+        let url = "https://github.com/org/repo/issues/42";
+
+        run_add(dir, "t1", "issue", url, None).unwrap();
+        run_add(dir, "t1", "Issue", url, None).unwrap();
+        run_add(dir, "t1", "pr", url, Some("Fix")).unwrap();
+        let graph = load_graph(graph_path(dir)).unwrap();
+        let links = &graph.get_task("t1").unwrap().links;
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].kind, LinkKind::Pr);
+        assert_eq!(links[0].label.as_deref(), Some("Fix"));
+
+        assert!(run_remove(dir, "t1", "https://example.com").is_err());
+        run_remove(dir, "t1", url).unwrap();
+        let graph = load_graph(graph_path(dir)).unwrap();
+        assert!(graph.get_task("t1").unwrap().links.is_empty());
+    }
+
+    #[test]
+    fn test_add_link_rejects_bad_input() {
+        let temp_dir = setup_graph();
+        let dir = temp_dir.path();
+        assert!(run_add(dir, "t1", "ticket", "https://example.com", None).is_err());
+        assert!(run_add(dir, "t1", "doc", "javascript:alert(1)", None).is_err());
+        assert!(run_add(dir, "t1", "doc", "https://", None).is_err());
+        assert!(run_add(dir, "missing", "doc", "https://example.com", None).is_err());
+    }
+}
@@ -7,6 +7,7 @@ use workgraph::graph::{
     FailureClass, LogEntry, Status, evaluate_cycle_on_failure, parse_token_usage, parse_wg_tokens,
 };
 use workgraph::parser::modify_graph;
+use workgraph::service::executor::{ExecutorRegistry, RetryOn};
 use workgraph::service::registry::AgentRegistry;
 
 #[cfg(test)]
@@ -80,7 +81,8 @@ fn run_inner(
         && Config::load_or_default(dir).agency.auto_evaluate;
 
     // Resolve token usage outside the lock (registry read + file I/O).
-    let token_usage = AgentRegistry::load(dir).ok().and_then(|registry| {
+    let registry = AgentRegistry::load(dir).ok();
+    let token_usage = registry.as_ref().and_then(|registry| {
         let agent = registry.get_agent_by_task(id)?;
         let output_path = std::path::Path::new(&agent.output_file);
         let abs_path = if output_path.is_absolute() {
@@ -91,6 +93,18 @@ fn run_inner(
         parse_token_usage(&abs_path).or_else(|| parse_wg_tokens(&abs_path))
     });
 
+    // Retry policy of the executor that ran the task. Tasks no agent ran
+    // (a person failing an unclaimed task) have none and fail for good.
+    let retry_policy = registry.as_ref().and_then(|registry| {
+        let agent = registry.get_agent_by_task(id)?;
+        ExecutorRegistry::new(dir)
+            .load_config(&agent.executor)
+            .ok()?
+            .executor
+            .retry
+    });
+    let retry_kind = RetryOn::classify(class, eval_reject);
+
     // Atomically load the freshest graph, apply the mutation, and save.
     // Using modify_graph prevents lost updates from concurrent graph writers.
     let mut retry_count = 0u32;
@@ -98,6 +112,7 @@ fn run_inner(
     let mut agent_id_for_archive = None;
     let mut cycle_reactivated = Vec::new();
     let mut already_failed = false;
+    let mut retry_in = None;
 
     let id_owned = id.to_string();
     let reason_owned = reason.map(String::from);
@@ -188,6 +203,33 @@ fn run_inner(
         max_retries = task.max_retries;
         agent_id_for_archive = task.assigned.clone();
 
+        // The executor's retry policy puts the task back in the queue after
+        // a backoff instead of leaving it failed.
+        if let Some(policy) = retry_policy.as_ref()
+            && let Some(secs) = policy.next_retry(task, retry_kind)
+        {
+            let now = Utc::now();
+            task.status = Status::Open;
+            task.assigned = None;
+            task.failure_reason = None;
+            task.failure_class = None;
+            task.ready_after = Some((now + chrono::Duration::seconds(secs as i64)).to_rfc3339());
+            task.log.push(LogEntry {
+                timestamp: now.to_rfc3339(),
+                actor: Some("retry".to_string()),
+                user: Some(workgraph::current_user()),
+                message: format!(
+                    "Retrying in {} ({} failure, attempt {}/{})",
+                    workgraph::format_duration(secs as i64, true),
+                    retry_kind.map(|k| k.to_string()).unwrap_or_default(),
+                    task.retry_count + 1,
+                    policy.max_attempts
+                ),
+            });
+            retry_in = Some(secs);
+            return true;
+        }
+
         // Evaluate cycle failure restart — if this task is part of a cycle with
         // restart_on_failure (default true), reset all cycle members to Open.
         let cycle_analysis = graph.compute_cycle_analysis();
@@ -250,8 +292,13 @@ fn run_inner(
         id, reason_msg, retry_count
     );
 
-    // Show retry info if max_retries is set
-    if let Some(max) = max_retries {
+    if let Some(secs) = retry_in {
+        println!(
+            "  Executor retry policy: re-queued, ready in {}",
+            workgraph::format_duration(secs as i64, true)
+        );
+    } else if let Some(max) = max_retries {
+        // Show retry info if max_retries is set
         if retry_count >= max {
             println!(
                 "  Warning: Max retries ({}) reached. Consider abandoning or increasing limit.",
@@ -580,4 +627,57 @@ mod tests {
             "Agent should have a completed_at timestamp"
         );
     }
+
+    #[test]
+    fn test_fail_schedules_executor_retry() {
+        use workgraph::service::registry::AgentRegistry;
+
+        let dir = tempdir().unwrap();
+        let dir_path = dir.path();
+
+        let mut task = make_task("t1", "Test task", Status::InProgress);
+        task.assigned = Some("agent-1".to_string());
+        setup_workgraph(dir_path, vec![task]);
+
+        let mut registry = AgentRegistry::new();
+        registry.register_agent(99999, "t1", "claude", "/tmp/output.log");
+        registry.save(dir_path).unwrap();
+
+        std::fs::create_dir_all(dir_path.join("executors")).unwrap();
+        std::fs::write(
+            dir_path.join("executors/claude.toml"),
+            "[executor]\ntype = \"claude\"\ncommand = \"claude\"\n\n[executor.retry]\nmax_attempts = 2\nbackoff = \"5m\"\n",
+        )
+        .unwrap();
+
+        // A timeout is retried after the backoff
+        run(
+            dir_path,
+            "t1",
+            Some("timed out"),
+            Some(FailureClass::AgentHardTimeout),
+        )
+        .unwrap();
+        let graph = load_graph(graph_path(dir_path)).unwrap();
+        let task = graph.get_task("t1").unwrap();
+        assert_eq!(task.status, Status::Open);
+        assert_eq!(task.retry_count, 1);
+        assert!(task.assigned.is_none());
+        assert!(task.failure_reason.is_none());
+        assert!(task.ready_after.is_some());
+
+        // A policy violation is not in retry_on and stays failed
+        let mut graph = load_graph(graph_path(dir_path)).unwrap();
+        graph.get_task_mut("t1").unwrap().status = Status::InProgress;
+        workgraph::parser::save_graph(&graph, graph_path(dir_path)).unwrap();
+        run(
+            dir_path,
+            "t1",
+            Some("blocked tool"),
+            Some(FailureClass::ToolPolicyViolation),
+        )
+        .unwrap();
+        let graph = load_graph(graph_path(dir_path)).unwrap();
+        assert_eq!(graph.get_task("t1").unwrap().status, Status::Failed);
+    }
 }
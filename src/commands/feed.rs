@@ -0,0 +1,347 @@
+//! `wg feed` — recent project activity as JSON or an Atom feed
+//!
+//! Lists tasks created, completed and failed within a window so external
+//! systems (newsletters, team wikis, feed readers) can follow a project.
+//! Task visibility decides what is included, as in `wg trace export`:
+//! `public` (the default) lists only public tasks and leaves out failure
+//! reasons, `peer` adds peer tasks and failure reasons, and `internal`
+//! lists everything. System tasks are never included.
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::Path;
+use workgraph::graph::{Status, Task, WorkGraph, is_system_task};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedEventKind {
+    Created,
+    Completed,
+    Failed,
+}
+
+impl FeedEventKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Created => "New",
+            Self::Completed => "Completed",
+            Self::Failed => "Failed",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedEntry {
+    pub kind: FeedEventKind,
+    pub task_id: String,
+    pub title: String,
+    pub at: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Failure reason; left out of public feeds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Feed {
+    pub title: String,
+    pub generated_at: String,
+    pub since: String,
+    pub visibility: String,
+    /// Newest first
+    pub entries: Vec<FeedEntry>,
+}
+
+fn parse_ts(ts: Option<&str>) -> Option<DateTime<Utc>> {
+    ts?.parse::<DateTime<Utc>>().ok()
+}
+
+fn visible(task: &Task, visibility: &str) -> bool {
+    match visibility {
+        "public" => task.visibility == "public",
+        "peer" => task.visibility == "public" || task.visibility == "peer",
+        _ => true,
+    }
+}
+
+/// When a failed task failed: its last log entry, which `wg fail` writes
+fn failed_at(task: &Task) -> Option<DateTime<Utc>> {
+    task.log
+        .last()
+        .and_then(|e| parse_ts(Some(&e.timestamp)))
+        .or_else(|| parse_ts(task.last_interaction_at.as_deref()))
+}
+
+/// Activity since `since`, newest first
+pub fn collect_entries(
+    graph: &WorkGraph,
+    since: DateTime<Utc>,
+    visibility: &str,
+) -> Vec<FeedEntry> {
+    let mut events: Vec<(DateTime<Utc>, FeedEventKind, &Task)> = Vec::new();
+    for task in graph.tasks() {
+        if is_system_task(&task.id) || !visible(task, visibility) {
+            continue;
+        }
+        if let Some(at) = parse_ts(task.created_at.as_deref()) {
+            events.push((at, FeedEventKind::Created, task));
+        }
+        match task.status {
+            Status::Done => {
+                if let Some(at) = parse_ts(task.completed_at.as_deref()) {
+                    events.push((at, FeedEventKind::Completed, task));
+                }
+            }
+            Status::Failed => {
+                if let Some(at) = failed_at(task) {
+                    events.push((at, FeedEventKind::Failed, task));
+                }
+            }
+            _ => {}
+        }
+    }
+    events.retain(|(at, _, _)| *at >= since);
+    events.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.id.cmp(&b.2.id)));
+
+    events
+        .into_iter()
+        .map(|(at, kind, task)| FeedEntry {
+            kind,
+            task_id: task.id.clone(),
+            title: task.title.clone(),
+            at: at.to_rfc3339(),
+            tags: task.tags.clone(),
+            detail: match kind {
+                FeedEventKind::Failed if visibility != "public" => task.failure_reason.clone(),
+                _ => None,
+            },
+        })
+        .collect()
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Atom (RFC 4287) document for `feed`. Entry ids are stable per event so
+/// readers don't show an event twice when the feed is regenerated.
+fn render_atom(feed: &Feed, project: &str) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    out.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    out.push_str(&format!("  <title>{}</title>\n", xml_escape(&feed.title)));
+    out.push_str(&format!(
+        "  <id>urn:workgraph:{}:feed</id>\n",
+        xml_escape(project)
+    ));
+    out.push_str(&format!(
+        "  <updated>{}</updated>\n",
+        feed.entries
+            .first()
+            .map(|e| e.at.as_str())
+            .unwrap_or(&feed.generated_at)
+    ));
+    out.push_str("  <author><name>workgraph</name></author>\n");
+    out.push_str("  <generator>workgraph</generator>\n");
+    for entry in &feed.entries {
+        out.push_str("  <entry>\n");
+        out.push_str(&format!(
+            "    <title>{}: {}</title>\n",
+            entry.kind.label(),
+            xml_escape(&entry.title)
+        ));
+        out.push_str(&format!(
+            "    <id>urn:workgraph:{}:{}:{}</id>\n",
+            xml_escape(project),
+            xml_escape(&entry.task_id),
+            entry.kind.as_str()
+        ));
+        out.push_str(&format!("    <updated>{}</updated>\n", entry.at));
+        for tag in &entry.tags {
+            out.push_str(&format!("    <category term=\"{}\"/>\n", xml_escape(tag)));
+        }
+        let summary = match &entry.detail {
+            Some(detail) => format!("{} ({}): {}", entry.task_id, entry.kind.label(), detail),
+            None => format!("{} ({})", entry.task_id, entry.kind.label()),
+        };
+        out.push_str(&format!(
+            "    <summary>{}</summary>\n",
+            xml_escape(&summary)
+        ));
+        out.push_str("  </entry>\n");
+    }
+    out.push_str("</feed>\n");
+    out
+}
+
+pub fn run(
+    dir: &Path,
+    format: &str,
+    since: &str,
+    visibility: &str,
+    output: Option<&Path>,
+) -> Result<()> {
+    if !matches!(format, "json" | "atom") {
+        bail!("Unknown format '{}'. Use 'json' or 'atom'.", format);
+    }
+    if !matches!(visibility, "internal" | "public" | "peer") {
+        bail!(
+            "Invalid visibility '{}'. Valid values: internal, public, peer",
+            visibility
+        );
+    }
+    let window = workgraph::html::parse_since(since)?;
+    let (graph, _path) = super::load_workgraph(dir)?;
+
+    let now = Utc::now();
+    let project = dir
+        .canonicalize()
+        .ok()
+        .and_then(|d| {
+            d.parent()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "workgraph".to_string());
+    let feed = Feed {
+        title: format!("{} activity", project),
+        generated_at: now.to_rfc3339(),
+        since: (now - window).to_rfc3339(),
+        visibility: visibility.to_string(),
+        entries: collect_entries(&graph, now - window, visibility),
+    };
+
+    let content = match format {
+        "atom" => render_atom(&feed, &project),
+        _ => format!("{}\n", serde_json::to_string_pretty(&feed)?),
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)
+                .with_context(|| format!("Failed to write '{}'", path.display()))?;
+            eprintln!(
+                "Wrote {} feed entries to {}",
+                feed.entries.len(),
+                path.display()
+            );
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use workgraph::graph::{LogEntry, Node};
+    use workgraph::test_helpers::make_task_with_status as make_task;
+
+    fn feed_graph(now: DateTime<Utc>) -> WorkGraph {
+        let ago = |days: i64| Some((now - Duration::days(days)).to_rfc3339());
+        let mut graph = WorkGraph::new();
+
+        let mut shipped = make_task("ship", "Ship <v2>", Status::Done);
+        shipped.visibility = "public".to_string();
+        shipped.created_at = ago(20);
+        shipped.completed_at = ago(1);
+        shipped.tags = vec!["release".to_string()];
+        graph.add_node(Node::Task(shipped));
+
+        let mut broken = make_task("deploy", "Deploy", Status::Failed);
+        broken.visibility = "peer".to_string();
+        broken.created_at = ago(3);
+        broken.failure_reason = Some("disk full".to_string());
+        broken.log.push(LogEntry {
+            timestamp: ago(2).unwrap(),
+            actor: None,
+            user: None,
+            message: "Task marked as failed".to_string(),
+        });
+        graph.add_node(Node::Task(broken));
+
+        let mut secret = make_task("secret", "Secret", Status::Open);
+        secret.created_at = ago(1);
+        graph.add_node(Node::Task(secret));
+
+        let mut system = make_task(".evaluate-ship", "Evaluate", Status::Done);
+        system.visibility = "public".to_string();
+        system.completed_at = ago(1);
+        graph.add_node(Node::Task(system));
+        graph
+    }
+
+    #[test]
+    fn test_collect_entries_filters_by_window_and_visibility() {
+        let now = Utc::now();
+        let graph = feed_graph(now);
+        let since = now - Duration::days(7);
+
+        let public = collect_entries(&graph, since, "public");
+        assert_eq!(public.len(), 1);
+        assert_eq!(public[0].kind, FeedEventKind::Completed);
+
+        let peer = collect_entries(&graph, since, "peer");
+        let kinds: Vec<(&str, FeedEventKind)> =
+            peer.iter().map(|e| (e.task_id.as_str(), e.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("ship", FeedEventKind::Completed),
+                ("deploy", FeedEventKind::Failed),
+                ("deploy", FeedEventKind::Created),
+            ]
+        );
+        assert_eq!(peer[1].detail.as_deref(), Some("disk full"));
+
+        let internal = collect_entries(&graph, since, "internal");
+        assert_eq!(internal.len(), 4);
+        assert!(internal.iter().all(|e| !e.task_id.starts_with('.')));
+    }
+
+    #[test]
+    fn test_public_feed_omits_failure_reason() {
+        let now = Utc::now();
+        let mut graph = feed_graph(now);
+        graph.get_task_mut("deploy").unwrap().visibility = "public".to_string();
+        let entries = collect_entries(&graph, now - Duration::days(7), "public");
+        let failed = entries
+            .iter()
+            .find(|e| e.kind == FeedEventKind::Failed)
+            .unwrap();
+        assert_eq!(failed.detail, None);
+    }
+
+    #[test]
+    fn test_render_atom() {
+        let now = Utc::now();
+        let graph = feed_graph(now);
+        let feed = Feed {
+            title: "demo activity".to_string(),
+            generated_at: now.to_rfc3339(),
+            since: (now - Duration::days(7)).to_rfc3339(),
+            visibility: "public".to_string(),
+            entries: collect_entries(&graph, now - Duration::days(7), "public"),
+        };
+        let atom = render_atom(&feed, "demo");
+        assert!(atom.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns="));
+        assert!(atom.ends_with("</feed>\n"));
+        assert_eq!(atom.matches("<entry>").count(), 1);
+        assert!(atom.contains("<title>Completed: Ship &lt;v2&gt;</title>"));
+        assert!(atom.contains("<id>urn:workgraph:demo:ship:completed</id>"));
+        assert!(atom.contains("<category term=\"release\"/>"));
+    }
+}
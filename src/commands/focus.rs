@@ -0,0 +1,142 @@
+//! `wg focus` — a personal queue of the next few tasks to pick up.
+
+use anyhow::Result;
+use chrono::Utc;
+use std::path::Path;
+use workgraph::config::Config;
+use workgraph::focus::{self, FocusItem, FocusQueue, Focuser};
+use workgraph::graph::WorkGraph;
+
+/// The queue owner: `actor`, or the current user.
+pub fn focuser(dir: &Path, actor: Option<&str>) -> Focuser {
+    let name = actor
+        .map(String::from)
+        .unwrap_or_else(workgraph::current_user);
+    Focuser::resolve(dir, &name)
+}
+
+fn recompute(dir: &Path, graph: &WorkGraph, who: &Focuser, limit: usize) -> Result<FocusQueue> {
+    let aging_hours = Config::load_or_default(dir)
+        .coordinator
+        .priority_aging_hours;
+    let now = Utc::now();
+    let queue = FocusQueue {
+        who: who.name.clone(),
+        computed_at: now.to_rfc3339(),
+        items: focus::compute(graph, who, limit, now, aging_hours),
+    };
+    focus::save(dir, &queue)?;
+    Ok(queue)
+}
+
+fn print_item(rank: usize, item: &FocusItem) {
+    let mut extra = Vec::new();
+    if let Some(h) = item.hours {
+        extra.push(format!("{}h", workgraph::format_hours(h)));
+    }
+    if item.tail_hours > 0.0 {
+        extra.push(format!(
+            "unblocks {}h",
+            workgraph::format_hours(item.tail_hours)
+        ));
+    }
+    if let Some(ref due) = item.due {
+        extra.push(format!("due {}", due));
+    }
+    let extra = if extra.is_empty() {
+        String::new()
+    } else {
+        format!("  ({})", extra.join(", "))
+    };
+    println!(
+        "{:>2}. [p{}] {} - {}{}",
+        rank, item.priority, item.id, item.title, extra
+    );
+}
+
+/// Compute, pin and print the queue for `actor` (default: current user).
+pub fn run(dir: &Path, actor: Option<&str>, limit: usize, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let who = focuser(dir, actor);
+    let queue = recompute(dir, &graph, &who, limit)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&queue)?);
+        return Ok(());
+    }
+    if queue.items.is_empty() {
+        println!("Nothing to pick up for {}.", who.name);
+        return Ok(());
+    }
+    println!("Focus for {}:", who.name);
+    for (i, item) in queue.items.iter().enumerate() {
+        print_item(i + 1, item);
+    }
+    println!();
+    println!("Run 'wg focus next' to claim the top task.");
+    Ok(())
+}
+
+/// Claim the top task of the pinned queue, skipping items that have since
+/// been taken or blocked. Recomputes when the pinned queue is used up.
+pub fn run_next(dir: &Path, actor: Option<&str>, limit: usize) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let who = focuser(dir, actor);
+
+    let mut items = focus::load(dir, &who.name)?
+        .map(|q| q.items)
+        .unwrap_or_default();
+    focus::prune(&graph, &who, &mut items);
+    if items.is_empty() {
+        items = recompute(dir, &graph, &who, limit)?.items;
+    }
+    let Some(top) = items.first().cloned() else {
+        println!("Nothing to pick up for {}.", who.name);
+        return Ok(());
+    };
+
+    super::claim::claim(dir, &top.id, Some(&who.name))?;
+    items.remove(0);
+    focus::save(
+        dir,
+        &FocusQueue {
+            who: who.name.clone(),
+            computed_at: Utc::now().to_rfc3339(),
+            items,
+        },
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::graph::{Node, Status};
+    use workgraph::parser::{load_graph, save_graph};
+    use workgraph::test_helpers::make_task;
+
+    #[test]
+    fn test_focus_next_claims_in_pinned_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let mut graph = WorkGraph::new();
+        let mut high = make_task("high", "High");
+        high.priority = 50;
+        graph.add_node(Node::Task(high));
+        graph.add_node(Node::Task(make_task("low", "Low")));
+        save_graph(&graph, super::super::graph_path(dir)).unwrap();
+
+        run(dir, Some("erin"), 5, false).unwrap();
+        let pinned = focus::load(dir, "erin").unwrap().unwrap();
+        assert_eq!(pinned.items.len(), 2);
+
+        run_next(dir, Some("erin"), 5).unwrap();
+        let graph = load_graph(super::super::graph_path(dir)).unwrap();
+        let high = graph.get_task("high").unwrap();
+        assert_eq!(high.status, Status::InProgress);
+        assert_eq!(high.assigned.as_deref(), Some("erin"));
+        let pinned = focus::load(dir, "erin").unwrap().unwrap();
+        assert_eq!(pinned.items[0].id, "low");
+    }
+}
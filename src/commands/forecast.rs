@@ -1,10 +1,12 @@
 use anyhow::Result;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
+use workgraph::availability::AvailabilityCalendar;
 use workgraph::graph::{Status, WorkGraph};
 use workgraph::query::build_reverse_index;
+use workgraph::working_hours::WorkingHours;
 
 use super::collect_transitive_dependents;
 use super::velocity::calculate_velocity;
@@ -55,6 +57,15 @@ pub struct CriticalPath {
     pub total_hours: f64,
 }
 
+/// Open work held by an assignee who is currently unavailable
+#[derive(Debug, Clone, Serialize)]
+pub struct UnavailableAssignee {
+    pub who: String,
+    pub back_at: String,
+    pub tasks: Vec<String>,
+    pub hours: f64,
+}
+
 /// Full forecast output
 #[derive(Debug, Serialize)]
 pub struct ForecastOutput {
@@ -65,11 +76,29 @@ pub struct ForecastOutput {
     pub velocity_hours_per_week: f64,
     pub has_velocity_data: bool,
     pub has_estimates: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unavailable_assignees: Vec<UnavailableAssignee>,
+    /// When the critical path finishes if worked end to end from now,
+    /// following assignees' working calendars
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub critical_path_finish: Option<String>,
+    /// Whether `[working_hours]` calendars shaped the dates
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub working_hours: bool,
 }
 
 pub fn run(dir: &Path, json: bool) -> Result<()> {
     let (graph, _path) = super::load_workgraph(dir)?;
-    let forecast = calculate_forecast(&graph);
+    let mut forecast = calculate_forecast(&graph);
+    let calendar = AvailabilityCalendar::load_or_warn(dir);
+    forecast.unavailable_assignees = find_unavailable_assignees(&graph, &calendar, Utc::now());
+    let calendars = WorkingHours::load_or_warn(dir);
+    forecast.working_hours = calendars.is_configured();
+    forecast.critical_path_finish = forecast
+        .critical_path
+        .as_ref()
+        .and_then(|cp| critical_path_finish(&graph, &cp.path, Utc::now(), &calendars))
+        .map(|dt| dt.format("%b %d, %Y %H:%M UTC").to_string());
 
     if json {
         println!("{}", serde_json::to_string_pretty(&forecast)?);
@@ -109,7 +138,73 @@ pub fn calculate_forecast(graph: &WorkGraph) -> ForecastOutput {
         velocity_hours_per_week: velocity.average_hours_per_week,
         has_velocity_data,
         has_estimates,
+        unavailable_assignees: Vec::new(),
+        critical_path_finish: None,
+        working_hours: false,
+    }
+}
+
+/// Forecast finish of a critical path worked in order from `now`. Each task
+/// waits for its `not_before` and for the lag and weight of the edge from
+/// the previous task, and its estimate counts only working time when its
+/// assignee has a working calendar.
+fn critical_path_finish(
+    graph: &WorkGraph,
+    path: &[String],
+    now: DateTime<Utc>,
+    calendars: &WorkingHours,
+) -> Option<DateTime<Utc>> {
+    let mut at = now;
+    let mut previous: Option<&str> = None;
+    for id in path {
+        let task = graph.get_task(id)?;
+        if let Some(edge) = previous.and_then(|p| task.edges.get(p)) {
+            at += Duration::seconds((edge.hours() * 3600.0) as i64);
+        }
+        previous = Some(id.as_str());
+        if let Some(nb) = task
+            .not_before
+            .as_deref()
+            .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+        {
+            at = at.max(nb);
+        }
+        let hours = task.estimate.as_ref().and_then(|e| e.hours).unwrap_or(0.0);
+        at = calendars.finish(task, at, hours);
+    }
+    (at > now).then_some(at)
+}
+
+/// Find unfinished work whose assignee (actor or agency agent) is currently
+/// unavailable — it will sit until they are back.
+fn find_unavailable_assignees(
+    graph: &WorkGraph,
+    calendar: &AvailabilityCalendar,
+    now: DateTime<Utc>,
+) -> Vec<UnavailableAssignee> {
+    let mut by_who: BTreeMap<String, UnavailableAssignee> = BTreeMap::new();
+    for task in graph.tasks().filter(|t| !t.status.is_terminal()) {
+        for who in [task.assigned.as_deref(), task.agent.as_deref()]
+            .into_iter()
+            .flatten()
+        {
+            let Some(back) = calendar.unavailable_until(&[who], now) else {
+                continue;
+            };
+            let entry = by_who
+                .entry(who.to_string())
+                .or_insert_with(|| UnavailableAssignee {
+                    who: who.to_string(),
+                    back_at: back.format("%b %d, %Y").to_string(),
+                    tasks: Vec::new(),
+                    hours: 0.0,
+                });
+            entry.tasks.push(task.id.clone());
+            entry.hours += task.estimate.as_ref().and_then(|e| e.hours).unwrap_or(0.0);
+            break;
+        }
     }
+    by_who.into_values().collect()
 }
 
 /// Calculate remaining work breakdown by status
@@ -398,7 +493,12 @@ fn find_longest_path_from(
     let mut best_hours: f64 = 0.0;
 
     for dep_id in deps {
-        let (path, hours) = find_longest_path_from(graph, reverse_index, dep_id, visited);
+        let (path, mut hours) = find_longest_path_from(graph, reverse_index, dep_id, visited);
+        if !path.is_empty()
+            && let Some(edge) = graph.get_task(dep_id).and_then(|t| t.edges.get(start_id))
+        {
+            hours += edge.hours();
+        }
         if hours > best_hours || (hours == best_hours && path.len() > best_path.len()) {
             best_path = path;
             best_hours = hours;
@@ -479,10 +579,32 @@ fn print_human_output(forecast: &ForecastOutput) {
             let last = &critical.path[critical.path.len() - 1];
             format!("{} -> ... -> {}", first_three.join(" -> "), last)
         };
-        println!(
-            "Critical path ({:.0}h): {}\n",
-            critical.total_hours, path_str
-        );
+        println!("Critical path ({:.0}h): {}", critical.total_hours, path_str);
+        if let Some(ref finish) = forecast.critical_path_finish {
+            let basis = if forecast.working_hours {
+                "working hours"
+            } else {
+                "around the clock"
+            };
+            println!("  Earliest finish: {} ({})", finish, basis);
+        }
+        println!();
+    }
+
+    // Work parked on unavailable assignees
+    if !forecast.unavailable_assignees.is_empty() {
+        println!("Held by unavailable assignees:");
+        for ua in &forecast.unavailable_assignees {
+            println!(
+                "  - {} (back {}): {} tasks, {:.0}h — {}",
+                ua.who,
+                ua.back_at,
+                ua.tasks.len(),
+                ua.hours,
+                ua.tasks.join(", ")
+            );
+        }
+        println!();
     }
 
     // Blockers
@@ -825,4 +947,89 @@ mod tests {
         // Should gracefully return None, not panic or produce garbage date
         assert!(scenario.completion_date.is_none());
     }
+
+    #[test]
+    fn test_unavailable_assignees_hold_work() {
+        let mut graph = WorkGraph::new();
+        let mut t1 = make_task("t1", "Task 1");
+        t1.assigned = Some("alice".to_string());
+        t1.estimate = Some(Estimate {
+            hours: Some(6.0),
+            cost: None,
+        });
+        let mut t2 = make_task("t2", "Task 2");
+        t2.assigned = Some("bob".to_string());
+        graph.add_node(Node::Task(t1));
+        graph.add_node(Node::Task(t2));
+
+        let now = Utc::now();
+        let mut calendar = AvailabilityCalendar::default();
+        calendar.add(
+            "alice",
+            now - Duration::days(1),
+            now + Duration::days(5),
+            None,
+        );
+
+        let held = find_unavailable_assignees(&graph, &calendar, now);
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].who, "alice");
+        assert_eq!(held[0].tasks, vec!["t1".to_string()]);
+        assert_eq!(held[0].hours, 6.0);
+    }
+
+    #[test]
+    fn test_critical_path_finish_follows_working_hours() {
+        let mut graph = WorkGraph::new();
+        let mut spec = make_task_with_hours("spec", "Spec", 6.0);
+        spec.assigned = Some("alice".to_string());
+        graph.add_node(Node::Task(spec));
+        let mut build = make_task_with_hours("build", "Build", 2.0);
+        build.after = vec!["spec".to_string()];
+        graph.add_node(Node::Task(build));
+        let path = vec!["spec".to_string(), "build".to_string()];
+
+        // Thursday 14:00 UTC
+        let now = "2026-03-05T14:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            critical_path_finish(&graph, &path, now, &WorkingHours::default()),
+            Some(now + Duration::hours(8))
+        );
+
+        let config: workgraph::working_hours::WorkingHoursConfig =
+            toml::from_str("hours = \"09:00-13:00\"\n[participants.alice]").unwrap();
+        let calendars = WorkingHours::from_config(&config).unwrap();
+        // alice: 4h Friday morning, 2h Monday morning; build then runs 2h
+        assert_eq!(
+            critical_path_finish(&graph, &path, now, &calendars)
+                .unwrap()
+                .to_rfc3339(),
+            "2026-03-09T13:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_edge_lag_counts_toward_forecast() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task_with_hours("deploy", "Deploy", 2.0)));
+        let mut rollout = make_task_with_hours("rollout", "Rollout", 1.0);
+        rollout.after = vec!["deploy".to_string()];
+        rollout.edges.insert(
+            "deploy".to_string(),
+            workgraph::graph::DependencyEdge {
+                lag: Some("2d".to_string()),
+                weight: None,
+            },
+        );
+        graph.add_node(Node::Task(rollout));
+
+        let cp = find_critical_path(&graph).unwrap();
+        assert_eq!(cp.total_hours, 51.0);
+
+        let now = "2026-03-05T14:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            critical_path_finish(&graph, &cp.path, now, &WorkingHours::default()),
+            Some(now + Duration::hours(51))
+        );
+    }
 }
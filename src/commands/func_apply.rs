@@ -1,11 +1,11 @@
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use workgraph::function::{
     self, FunctionInput, InputType, PlanningConfig, TaskTemplate, TraceFunction,
 };
-use workgraph::graph::{Node, PRIORITY_DEFAULT, Status, Task};
+use workgraph::graph::{Node, PRIORITY_DEFAULT, Status, Task, parse_delay};
 use workgraph::parser::{load_graph, modify_graph};
 
 use super::graph_path;
@@ -64,6 +64,51 @@ fn resolve_file_path(path_str: &str) -> Result<PathBuf> {
     Ok(abs)
 }
 
+/// When the generated tasks may start. `--not-before` opens a start window;
+/// `--stagger` spaces tasks out in creation order so a large function does
+/// not saturate every agent at once.
+#[derive(Debug, Clone, Default)]
+pub struct ApplySchedule {
+    pub not_before: Option<DateTime<Utc>>,
+    pub stagger_secs: u64,
+}
+
+impl ApplySchedule {
+    pub fn parse(not_before: Option<&str>, stagger: Option<&str>) -> Result<Self> {
+        let not_before = not_before
+            .map(|ts| {
+                ts.parse::<DateTime<Utc>>()
+                    .or_else(|_| {
+                        chrono::NaiveDateTime::parse_from_str(ts, "%Y-%m-%dT%H:%M:%S")
+                            .map(|ndt| ndt.and_utc())
+                    })
+                    .map_err(|_| anyhow::anyhow!("Invalid timestamp '{}'. Use ISO 8601 format", ts))
+            })
+            .transpose()?;
+        let stagger_secs = match stagger {
+            Some(d) => parse_delay(d).ok_or_else(|| {
+                anyhow::anyhow!("Invalid stagger '{}'. Use format: 30s, 5m, 1h, 1d", d)
+            })?,
+            None => 0,
+        };
+        Ok(Self {
+            not_before,
+            stagger_secs,
+        })
+    }
+
+    /// `not_before` for the task at `index` in creation order. Without a
+    /// start window the first task is left unscheduled.
+    fn slot(&self, index: usize, now: DateTime<Utc>) -> Option<String> {
+        let offset = self.stagger_secs * index as u64;
+        if self.not_before.is_none() && offset == 0 {
+            return None;
+        }
+        let start = self.not_before.unwrap_or(now);
+        Some((start + chrono::Duration::seconds(offset as i64)).to_rfc3339())
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn run(
     dir: &Path,
@@ -76,6 +121,36 @@ pub fn run(
     after: &[String],
     model: Option<&str>,
     json: bool,
+) -> Result<()> {
+    run_with_schedule(
+        dir,
+        function_id,
+        from,
+        inputs,
+        input_file,
+        prefix,
+        dry_run,
+        after,
+        model,
+        &ApplySchedule::default(),
+        json,
+    )
+}
+
+/// [`run`], with `not_before` times for the generated tasks.
+#[allow(clippy::too_many_arguments)]
+pub fn run_with_schedule(
+    dir: &Path,
+    function_id: &str,
+    from: Option<&str>,
+    inputs: &[String],
+    input_file: Option<&str>,
+    prefix: Option<&str>,
+    dry_run: bool,
+    after: &[String],
+    model: Option<&str>,
+    schedule: &ApplySchedule,
+    json: bool,
 ) -> Result<()> {
     // 1. Load trace function: from --from source or local functions dir
     let func = if let Some(source) = from {
@@ -187,7 +262,8 @@ pub fn run(
         id_map.insert(template.template_id.clone(), task_id);
     }
 
-    for template in &task_templates {
+    let now = Utc::now();
+    for (index, template) in task_templates.iter().enumerate() {
         let mut rendered = function::substitute_task_template(template, &final_inputs);
         if !memory_text.is_empty() {
             rendered.description = rendered
@@ -224,9 +300,14 @@ pub fn run(
         if let Some(ref role) = rendered.role_hint {
             tags.push(format!("role:{}", role));
         }
+        tags.push(workgraph::learning::template_tag(
+            &func.id,
+            &template.template_id,
+        ));
 
         // Apply model: --model flag overrides everything
         let task_model = model.map(String::from);
+        let not_before = schedule.slot(index, now);
 
         if dry_run {
             // Show plan without creating tasks
@@ -236,6 +317,7 @@ pub fn run(
                 &real_after,
                 &tags,
                 task_model.as_deref(),
+                not_before.as_deref(),
             );
         } else {
             let task = Task {
@@ -248,19 +330,25 @@ pub fn run(
                 estimate: None,
                 before: vec![],
                 after: real_after.clone(),
+                edges: Default::default(),
                 requires: vec![],
                 tags,
                 skills: rendered.skills.clone(),
                 inputs: vec![],
                 deliverables: rendered.deliverables.clone(),
+                expects: vec![],
+                gate: None,
                 artifacts: vec![],
+                self_report: None,
                 exec: None,
                 timeout: None,
-                not_before: None,
+                not_before,
+                due: None,
                 created_at: Some(Utc::now().to_rfc3339()),
                 started_at: None,
                 completed_at: None,
                 last_interaction_at: None,
+                revision: 0,
                 log: vec![],
                 retry_count: 0,
                 max_retries: None,
@@ -284,10 +372,20 @@ pub fn run(
                 context_scope: None,
                 cycle_config: None,
                 token_usage: None,
+                max_cost: None,
+                min_context: None,
+                claim_sha: None,
+                changes: None,
                 session_id: None,
                 wait_condition: None,
                 checkpoint: None,
                 triage_count: 0,
+                escalation_level: 0,
+                blocked_at: None,
+                deadline_alert: None,
+                external_blocker: None,
+                waiting_on: vec![],
+                links: vec![],
                 resurrection_count: 0,
                 last_resurrected_at: None,
                 validation: None,
@@ -448,7 +546,12 @@ pub fn run(
             } else {
                 format!(" (blocked by {})", task.after.join(", "))
             };
-            println!("  {} (Open{})", task_id, blocked_str);
+            let start_str = task
+                .not_before
+                .as_deref()
+                .map(|ts| format!(", not before {}", ts))
+                .unwrap_or_default();
+            println!("  {} (Open{}{})", task_id, blocked_str, start_str);
         }
         println!();
         super::print_service_hint(dir);
@@ -561,6 +664,7 @@ fn print_dry_run_task(
     after: &[String],
     tags: &[String],
     model: Option<&str>,
+    not_before: Option<&str>,
 ) {
     println!("  Task: {} (Open)", task_id);
     println!("    Title: {}", rendered.title);
@@ -576,6 +680,9 @@ fn print_dry_run_task(
     if let Some(m) = model {
         println!("    Model: {}", m);
     }
+    if let Some(ts) = not_before {
+        println!("    Not before: {}", ts);
+    }
     // Show first few lines of description
     let desc_lines: Vec<&str> = rendered.description.lines().take(3).collect();
     if !desc_lines.is_empty() {
@@ -691,6 +798,10 @@ fn append_run_record(
     record: &serde_json::Value,
 ) -> Result<()> {
     use std::io::Write;
+    let mut record = record.clone();
+    if let Some(scrubber) = workgraph::scrub::Scrubber::load(workgraph_dir) {
+        scrubber.scrub_json(&mut record);
+    }
     let func_dir = workgraph_dir.join("functions");
     std::fs::create_dir_all(&func_dir)?;
     let runs_path = func_dir.join(format!("{}.runs.jsonl", function_id));
@@ -698,7 +809,7 @@ fn append_run_record(
         .create(true)
         .append(true)
         .open(&runs_path)?;
-    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    writeln!(file, "{}", serde_json::to_string(&record)?)?;
     Ok(())
 }
 
@@ -1010,6 +1121,56 @@ mod tests {
         assert!(implement.tags.contains(&"role:programmer".to_string()));
     }
 
+    #[test]
+    fn instantiate_staggers_not_before() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        setup_workgraph(dir);
+        setup_function(dir, &sample_function());
+
+        assert!(ApplySchedule::parse(None, Some("soon")).is_err());
+        let schedule = ApplySchedule::parse(Some("2030-01-01T09:00:00Z"), Some("10m")).unwrap();
+        run_with_schedule(
+            dir,
+            "impl-feature",
+            None,
+            &["feature_name=auth".to_string()],
+            None,
+            None,
+            false,
+            &[],
+            None,
+            &schedule,
+            false,
+        )
+        .unwrap();
+
+        let graph = load_graph(dir.join("graph.jsonl")).unwrap();
+        let start = |id: &str| {
+            graph
+                .get_task(id)
+                .unwrap()
+                .not_before
+                .as_deref()
+                .unwrap()
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+                .to_rfc3339()
+        };
+        assert_eq!(start("auth-plan"), "2030-01-01T09:00:00+00:00");
+        assert_eq!(start("auth-implement"), "2030-01-01T09:10:00+00:00");
+        assert_eq!(start("auth-validate"), "2030-01-01T09:20:00+00:00");
+
+        // Stagger alone leaves the first task free to start now
+        let now = Utc::now();
+        let stagger_only = ApplySchedule::parse(None, Some("5m")).unwrap();
+        assert_eq!(stagger_only.slot(0, now), None);
+        assert_eq!(
+            stagger_only.slot(2, now),
+            Some((now + chrono::Duration::minutes(10)).to_rfc3339())
+        );
+    }
+
     #[test]
     fn instantiate_substitutes_template_values() {
         let tmp = TempDir::new().unwrap();
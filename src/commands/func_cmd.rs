@@ -398,7 +398,7 @@ fn print_function_details(func: &TraceFunction, func_dir: &Path) {
     }
 }
 
-fn format_input_type(t: &InputType) -> &'static str {
+pub(crate) fn format_input_type(t: &InputType) -> &'static str {
     match t {
         InputType::String => "string",
         InputType::Text => "text",
@@ -525,7 +525,7 @@ fn print_template_detail(template: &TaskTemplate) {
     }
 }
 
-fn format_yaml_value(v: &serde_yaml::Value) -> String {
+pub(crate) fn format_yaml_value(v: &serde_yaml::Value) -> String {
     match v {
         serde_yaml::Value::Null => "null".to_string(),
         serde_yaml::Value::Bool(b) => b.to_string(),
@@ -0,0 +1,540 @@
+//! `wg func docs`: render a function as a human-readable document.
+//!
+//! The markdown output is meant for a team catalog of reusable workflows:
+//! what the function is for, the inputs it takes (with examples), how its
+//! tasks flow (as a mermaid diagram), the constraints it is held to, and how
+//! it has fared across recorded runs.
+
+use anyhow::{Result, bail};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use workgraph::format_duration;
+use workgraph::function::{self, RunSummary, TraceFunction};
+
+use super::func_cmd::{format_input_type, format_yaml_value};
+use super::viz::mermaid_id;
+
+/// Render documentation for a function and print it or write it to `output`.
+pub fn run(dir: &Path, id: &str, format: &str, output: Option<&Path>) -> Result<()> {
+    let func_dir = function::functions_dir(dir);
+    let func =
+        function::find_function_by_prefix(&func_dir, id).map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let doc = match format {
+        "markdown" | "md" => render_markdown(&func, &function::load_runs(&func_dir, &func.id)),
+        other => bail!("Unknown format '{}'. Use: markdown", other),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &doc)?;
+            eprintln!("Wrote docs for '{}' to {}", func.id, path.display());
+        }
+        None => print!("{}", doc),
+    }
+    Ok(())
+}
+
+/// Escape a value for use inside a markdown table cell.
+fn cell(s: &str) -> String {
+    s.trim().replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Render a function and its run history as a markdown document.
+pub fn render_markdown(func: &TraceFunction, runs: &[RunSummary]) -> String {
+    let mut md = String::new();
+
+    let _ = writeln!(md, "# {}", func.name);
+    md.push('\n');
+    let _ = write!(
+        md,
+        "`{}` · version {} · {}",
+        func.id, func.version, func.visibility
+    );
+    if !func.tags.is_empty() {
+        let tags: Vec<String> = func.tags.iter().map(|t| format!("`{}`", t)).collect();
+        let _ = write!(md, " · tags: {}", tags.join(", "));
+    }
+    md.push_str("\n\n");
+
+    md.push_str("## Purpose\n\n");
+    if func.description.trim().is_empty() {
+        md.push_str("_No description._\n\n");
+    } else {
+        let _ = writeln!(md, "{}\n", func.description.trim());
+    }
+
+    render_inputs(&mut md, func);
+    render_flow(&mut md, func);
+    render_outputs(&mut md, func);
+    render_constraints(&mut md, func);
+    render_history(&mut md, runs);
+    render_usage(&mut md, func);
+
+    md
+}
+
+fn render_inputs(md: &mut String, func: &TraceFunction) {
+    md.push_str("## Inputs\n\n");
+    if func.inputs.is_empty() {
+        md.push_str("This function takes no inputs.\n\n");
+        return;
+    }
+    md.push_str("| Name | Type | Required | Default | Example | Description |\n");
+    md.push_str("|------|------|----------|---------|---------|-------------|\n");
+    for input in &func.inputs {
+        let mut description = input.description.clone();
+        if let Some(ref values) = input.values {
+            let _ = write!(description, " (one of: {})", values.join(", "));
+        }
+        match (input.min, input.max) {
+            (Some(min), Some(max)) => {
+                let _ = write!(description, " (range: {}–{})", min, max);
+            }
+            (Some(min), None) => {
+                let _ = write!(description, " (min: {})", min);
+            }
+            (None, Some(max)) => {
+                let _ = write!(description, " (max: {})", max);
+            }
+            (None, None) => {}
+        }
+        let value = |v: &Option<serde_yaml::Value>| {
+            v.as_ref()
+                .map(|v| format!("`{}`", cell(&format_yaml_value(v))))
+                .unwrap_or_default()
+        };
+        let _ = writeln!(
+            md,
+            "| `{}` | {} | {} | {} | {} | {} |",
+            input.name,
+            format_input_type(&input.input_type),
+            if input.required { "yes" } else { "no" },
+            value(&input.default),
+            value(&input.example),
+            cell(&description),
+        );
+    }
+    md.push('\n');
+}
+
+fn render_flow(md: &mut String, func: &TraceFunction) {
+    md.push_str("## Task flow\n\n");
+    if let Some(ref planning) = func.planning {
+        let _ = writeln!(
+            md,
+            "This is a generative function: the planner task `{}` produces the task \
+             graph at apply time ({}).{}\n",
+            planning.planner_template.template_id,
+            planning.output_format,
+            if planning.static_fallback {
+                " The tasks below are used if planning fails."
+            } else {
+                ""
+            }
+        );
+    }
+    if func.tasks.is_empty() {
+        md.push_str("_No task templates._\n\n");
+        return;
+    }
+
+    md.push_str("```mermaid\ngraph TD\n");
+    for t in &func.tasks {
+        let _ = writeln!(
+            md,
+            "  {}[\"{}\"]",
+            mermaid_id(&t.template_id),
+            t.title.replace('"', "#quot;")
+        );
+    }
+    for t in &func.tasks {
+        for after in &t.after {
+            let _ = writeln!(
+                md,
+                "  {} --> {}",
+                mermaid_id(after),
+                mermaid_id(&t.template_id)
+            );
+        }
+        for edge in &t.loops_to {
+            let _ = writeln!(
+                md,
+                "  {} -. \"loop ≤{}\" .-> {}",
+                mermaid_id(&t.template_id),
+                edge.max_iterations,
+                mermaid_id(&edge.target)
+            );
+        }
+    }
+    md.push_str("```\n\n");
+
+    for t in &func.tasks {
+        let _ = writeln!(md, "### `{}`: {}\n", t.template_id, t.title);
+        let desc = t.description.trim();
+        if !desc.is_empty() {
+            let _ = writeln!(md, "{}\n", desc);
+        }
+        let mut facts = Vec::new();
+        if !t.after.is_empty() {
+            facts.push(format!("After: {}", code_list(&t.after)));
+        }
+        if !t.skills.is_empty() {
+            facts.push(format!("Skills: {}", t.skills.join(", ")));
+        }
+        if let Some(ref role) = t.role_hint {
+            facts.push(format!("Role: {}", role));
+        }
+        if !t.deliverables.is_empty() {
+            facts.push(format!("Deliverables: {}", code_list(&t.deliverables)));
+        }
+        if let Some(ref verify) = t.verify {
+            facts.push(format!("Verify: {}", verify));
+        }
+        if !facts.is_empty() {
+            for fact in facts {
+                let _ = writeln!(md, "- {}", fact);
+            }
+            md.push('\n');
+        }
+    }
+}
+
+fn code_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|i| format!("`{}`", i))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_outputs(md: &mut String, func: &TraceFunction) {
+    if func.outputs.is_empty() {
+        return;
+    }
+    md.push_str("## Outputs\n\n");
+    md.push_str("| Name | From | Description |\n");
+    md.push_str("|------|------|-------------|\n");
+    for output in &func.outputs {
+        let _ = writeln!(
+            md,
+            "| `{}` | `{}.{}` | {} |",
+            output.name,
+            output.from_task,
+            output.field,
+            cell(&output.description)
+        );
+    }
+    md.push('\n');
+}
+
+fn render_constraints(md: &mut String, func: &TraceFunction) {
+    let Some(ref c) = func.constraints else {
+        return;
+    };
+    let mut rules = Vec::new();
+    match (c.min_tasks, c.max_tasks) {
+        (Some(min), Some(max)) => rules.push(format!("Between {} and {} tasks", min, max)),
+        (Some(min), None) => rules.push(format!("At least {} tasks", min)),
+        (None, Some(max)) => rules.push(format!("At most {} tasks", max)),
+        (None, None) => {}
+    }
+    if let Some(depth) = c.max_depth {
+        rules.push(format!("Dependency depth at most {}", depth));
+    }
+    if c.allow_cycles {
+        match c.max_total_iterations {
+            Some(n) => rules.push(format!("Cycles allowed, at most {} iterations in total", n)),
+            None => rules.push("Cycles allowed".to_string()),
+        }
+    } else {
+        rules.push("No cycles".to_string());
+    }
+    if !c.required_skills.is_empty() {
+        rules.push(format!("Required skills: {}", c.required_skills.join(", ")));
+    }
+    if !c.required_phases.is_empty() {
+        rules.push(format!("Required phases: {}", c.required_phases.join(", ")));
+    }
+    for p in &c.forbidden_patterns {
+        rules.push(format!(
+            "Forbidden: tasks tagged {} ({})",
+            code_list(&p.tags),
+            p.reason
+        ));
+    }
+
+    md.push_str("## Constraints\n\n");
+    for rule in rules {
+        let _ = writeln!(md, "- {}", rule);
+    }
+    md.push('\n');
+}
+
+/// Per-template aggregates over recorded runs.
+#[derive(Default)]
+struct TemplateStats {
+    runs: u32,
+    succeeded: u32,
+    durations: Vec<i64>,
+    retries: u32,
+    /// Lines added and removed, over runs that recorded changes.
+    changes: Option<(u64, u64)>,
+}
+
+fn render_history(md: &mut String, runs: &[RunSummary]) {
+    md.push_str("## Run history\n\n");
+    if runs.is_empty() {
+        md.push_str("No recorded runs yet.\n\n");
+        return;
+    }
+
+    let succeeded = runs.iter().filter(|r| r.all_succeeded).count();
+    let _ = writeln!(
+        md,
+        "- Runs: {} ({} fully succeeded, {:.0}%)",
+        runs.len(),
+        succeeded,
+        succeeded as f64 * 100.0 / runs.len() as f64
+    );
+    let scores: Vec<f64> = runs.iter().filter_map(|r| r.avg_score).collect();
+    if !scores.is_empty() {
+        let _ = writeln!(
+            md,
+            "- Average score: {:.2}",
+            scores.iter().sum::<f64>() / scores.len() as f64
+        );
+    }
+    let mut walls: Vec<i64> = runs.iter().filter_map(|r| r.wall_clock_secs).collect();
+    if !walls.is_empty() {
+        walls.sort_unstable();
+        let _ = writeln!(
+            md,
+            "- Wall clock: median {}, longest {}",
+            format_duration(walls[walls.len() / 2], false),
+            format_duration(walls[walls.len() - 1], false)
+        );
+    }
+    let interventions: usize = runs.iter().map(|r| r.interventions.len()).sum();
+    if interventions > 0 {
+        let _ = writeln!(md, "- Interventions: {}", interventions);
+    }
+    if let Some(last) = runs.iter().map(|r| r.applied_at.as_str()).max() {
+        let _ = writeln!(md, "- Last run: {}", last);
+    }
+    md.push('\n');
+
+    let mut by_template: BTreeMap<&str, TemplateStats> = BTreeMap::new();
+    for outcome in runs.iter().flat_map(|r| &r.task_outcomes) {
+        let stats = by_template.entry(&outcome.template_id).or_default();
+        stats.runs += 1;
+        if outcome.status.eq_ignore_ascii_case("done") {
+            stats.succeeded += 1;
+        }
+        stats.durations.extend(outcome.duration_secs);
+        stats.retries += outcome.retry_count;
+        if let Some(c) = &outcome.changes {
+            let (added, removed) = stats.changes.get_or_insert((0, 0));
+            *added += c.insertions;
+            *removed += c.deletions;
+        }
+    }
+    if by_template.is_empty() {
+        return;
+    }
+    md.push_str("| Task | Runs | Succeeded | Avg duration | Retries | Lines changed |\n");
+    md.push_str("|------|------|-----------|--------------|---------|---------------|\n");
+    for (template_id, stats) in by_template {
+        let avg = if stats.durations.is_empty() {
+            "-".to_string()
+        } else {
+            let total: i64 = stats.durations.iter().sum();
+            format_duration(total / stats.durations.len() as i64, false)
+        };
+        let changed = match stats.changes {
+            Some((added, removed)) => format!("+{} -{}", added, removed),
+            None => "-".to_string(),
+        };
+        let _ = writeln!(
+            md,
+            "| `{}` | {} | {} | {} | {} | {} |",
+            template_id, stats.runs, stats.succeeded, avg, stats.retries, changed
+        );
+    }
+    md.push('\n');
+}
+
+fn render_usage(md: &mut String, func: &TraceFunction) {
+    let mut cmd = format!("wg func apply {}", func.id);
+    for input in func.inputs.iter().filter(|i| i.required) {
+        let value = input
+            .example
+            .as_ref()
+            .or(input.default.as_ref())
+            .map(|v| match v {
+                serde_yaml::Value::String(s) => s.clone(),
+                other => format_yaml_value(other),
+            })
+            .unwrap_or_else(|| format!("<{}>", input.name));
+        if value.contains(char::is_whitespace) || value.contains('<') {
+            let _ = write!(
+                cmd,
+                " --input {}='{}'",
+                input.name,
+                value.replace('\'', "'\\''")
+            );
+        } else {
+            let _ = write!(cmd, " --input {}={}", input.name, value);
+        }
+    }
+    md.push_str("## Usage\n\n");
+    let _ = writeln!(md, "```bash\n{}\n```", cmd);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use workgraph::function::*;
+
+    fn sample_function() -> TraceFunction {
+        let template = |id: &str, after: &[&str]| TaskTemplate {
+            template_id: id.to_string(),
+            title: format!("{} {{{{input.feature}}}}", id),
+            description: format!("Do the {} step", id),
+            skills: vec!["rust".to_string()],
+            after: after.iter().map(|s| s.to_string()).collect(),
+            loops_to: vec![],
+            role_hint: None,
+            deliverables: vec![],
+            verify: None,
+            tags: vec![],
+        };
+        let mut review = template("review", &["implement"]);
+        review.loops_to = vec![LoopEdgeTemplate {
+            target: "implement".to_string(),
+            max_iterations: 3,
+            guard: None,
+            delay: None,
+        }];
+        TraceFunction {
+            kind: "trace-function".to_string(),
+            version: 2,
+            id: "impl-feature".to_string(),
+            name: "Implement Feature".to_string(),
+            description: "Plan, implement and review a feature".to_string(),
+            extracted_from: vec![],
+            extracted_by: None,
+            extracted_at: None,
+            tags: vec!["implementation".to_string()],
+            inputs: vec![FunctionInput {
+                name: "feature".to_string(),
+                input_type: InputType::String,
+                description: "Feature name | short".to_string(),
+                required: true,
+                default: None,
+                example: Some(serde_yaml::Value::String("global-config".to_string())),
+                min: None,
+                max: None,
+                values: None,
+            }],
+            tasks: vec![
+                template("plan", &[]),
+                template("implement", &["plan"]),
+                review,
+            ],
+            outputs: vec![],
+            planning: None,
+            constraints: Some(StructuralConstraints {
+                min_tasks: None,
+                max_tasks: Some(5),
+                required_skills: vec![],
+                max_depth: None,
+                allow_cycles: true,
+                max_total_iterations: Some(6),
+                required_phases: vec![],
+                forbidden_patterns: vec![],
+            }),
+            memory: None,
+            visibility: FunctionVisibility::Internal,
+            redacted_fields: vec![],
+        }
+    }
+
+    fn sample_run(all_succeeded: bool, secs: i64) -> RunSummary {
+        RunSummary {
+            applied_at: "2026-02-20T12:00:00Z".to_string(),
+            inputs: HashMap::new(),
+            prefix: "impl-feature/".to_string(),
+            task_outcomes: vec![TaskOutcome {
+                template_id: "plan".to_string(),
+                task_id: "impl-feature/plan".to_string(),
+                status: if all_succeeded { "Done" } else { "Failed" }.to_string(),
+                score: Some(0.8),
+                duration_secs: Some(secs),
+                retry_count: 1,
+                changes: None,
+            }],
+            interventions: vec![],
+            wall_clock_secs: Some(secs),
+            all_succeeded,
+            avg_score: Some(0.8),
+        }
+    }
+
+    #[test]
+    fn renders_sections_and_inputs() {
+        let md = render_markdown(&sample_function(), &[]);
+        assert!(md.starts_with("# Implement Feature\n"));
+        assert!(md.contains("## Purpose\n\nPlan, implement and review a feature"));
+        assert!(md.contains(
+            "| `feature` | string | yes |  | `\"global-config\"` | Feature name \\| short |"
+        ));
+        assert!(md.contains("- At most 5 tasks\n- Cycles allowed, at most 6 iterations in total"));
+        assert!(md.contains("No recorded runs yet."));
+        assert!(md.contains("wg func apply impl-feature --input feature=global-config"));
+    }
+
+    #[test]
+    fn renders_mermaid_flow() {
+        let md = render_markdown(&sample_function(), &[]);
+        assert!(md.contains("```mermaid\ngraph TD\n"));
+        assert!(md.contains("  plan --> implement\n"));
+        assert!(md.contains("  implement --> review\n"));
+        assert!(md.contains("  review -. \"loop ≤3\" .-> implement\n"));
+    }
+
+    #[test]
+    fn renders_run_history_stats() {
+        let runs = [
+            sample_run(true, 60),
+            sample_run(false, 180),
+            sample_run(true, 120),
+        ];
+        let md = render_markdown(&sample_function(), &runs);
+        assert!(md.contains("- Runs: 3 (2 fully succeeded, 67%)"));
+        assert!(md.contains("- Average score: 0.80"));
+        assert!(md.contains("- Wall clock: median 2m, longest 3m"));
+        assert!(md.contains("| `plan` | 3 | 2 | 2m | 3 |"));
+    }
+
+    #[test]
+    fn run_writes_file_and_rejects_unknown_format() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        save_function(&sample_function(), &dir.join("functions")).unwrap();
+
+        let out = dir.join("impl-feature.md");
+        run(dir, "impl-f", "markdown", Some(&out)).unwrap();
+        assert!(
+            std::fs::read_to_string(&out)
+                .unwrap()
+                .contains("# Implement Feature")
+        );
+
+        let err = run(dir, "impl-feature", "html", None).unwrap_err();
+        assert!(err.to_string().contains("Unknown format"));
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result, bail};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use std::collections::{HashMap, HashSet};
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 
 use workgraph::agency;
@@ -25,6 +26,16 @@ fn is_coordinator_noise(task: &Task) -> bool {
     false
 }
 
+/// Load the graph, pulling in archived tasks when a requested task (or, for
+/// subgraph extraction, its descendants) may have moved to cold storage.
+fn load_with_archive(dir: &Path, task_ids: &[String], subgraph: bool) -> Result<WorkGraph> {
+    let (mut graph, _path) = super::load_workgraph(dir)?;
+    if subgraph || task_ids.iter().any(|id| graph.get_task(id).is_none()) {
+        workgraph::archive::include_archived(&mut graph, dir)?;
+    }
+    Ok(graph)
+}
+
 /// Run the `wg trace extract <task-id>` command.
 #[allow(clippy::too_many_arguments)]
 pub fn run(
@@ -37,7 +48,37 @@ pub fn run(
     force: bool,
     include_evaluations: bool,
 ) -> Result<()> {
-    let (graph, _path) = super::load_workgraph(dir)?;
+    run_with_review(
+        dir,
+        task_id,
+        name,
+        subgraph,
+        generalize,
+        output,
+        force,
+        include_evaluations,
+        false,
+    )
+}
+
+/// [`run`], optionally stopping for an interactive review (see
+/// [`super::func_review`]) before the function is saved.
+#[allow(clippy::too_many_arguments)]
+pub fn run_with_review(
+    dir: &Path,
+    task_id: &str,
+    name: Option<&str>,
+    subgraph: bool,
+    generalize: bool,
+    output: Option<&str>,
+    force: bool,
+    include_evaluations: bool,
+    review: bool,
+) -> Result<()> {
+    if review && !std::io::stdin().is_terminal() {
+        bail!("--review needs an interactive terminal");
+    }
+    let graph = load_with_archive(dir, &[task_id.to_string()], subgraph)?;
     let task = graph.get_task_or_err(task_id)?;
 
     // Task must be Done
@@ -54,6 +95,227 @@ pub fn run(
         .map(|s| s.to_string())
         .unwrap_or_else(|| sanitize_id(task_id));
 
+    // Collect tasks to include
+    let tasks_to_extract: Vec<&Task> = if subgraph {
+        collect_subgraph(task_id, &graph)
+    } else {
+        vec![task]
+    };
+
+    let source = Source {
+        root_id: task_id,
+        label: format!("task '{}'", task_id),
+        description: task
+            .description
+            .clone()
+            .unwrap_or_else(|| task.title.clone()),
+        extracted_from: vec![task_id.to_string()],
+        extracted_by: task.assigned.clone(),
+        tags: task.tags.clone(),
+    };
+    extract(
+        dir,
+        &graph,
+        source,
+        tasks_to_extract,
+        &func_id,
+        generalize,
+        output,
+        force,
+        include_evaluations,
+        review,
+    )
+}
+
+/// Which completed tasks to extract when there is no single root task.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractFilter {
+    /// Tasks must carry every one of these tags
+    pub tags: Vec<String>,
+    /// Completed at or after this instant
+    pub since: Option<DateTime<Utc>>,
+    /// Completed before this instant
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl ExtractFilter {
+    /// Build a filter from `--tag` values and a `--between FROM..TO` range.
+    /// Either end of the range may be omitted. Ends are `YYYY-MM-DD` dates
+    /// or RFC 3339 timestamps; a date `TO` includes that whole day.
+    pub fn new(tags: &[String], between: Option<&str>) -> Result<Self> {
+        let mut filter = ExtractFilter {
+            tags: tags.to_vec(),
+            ..Default::default()
+        };
+        if let Some(range) = between {
+            let (from, to) = range
+                .split_once("..")
+                .with_context(|| format!("Invalid --between '{}', expected FROM..TO", range))?;
+            if !from.is_empty() {
+                filter.since = Some(parse_range_end(from, false)?);
+            }
+            if !to.is_empty() {
+                filter.until = Some(parse_range_end(to, true)?);
+            }
+        }
+        if filter.tags.is_empty() && filter.since.is_none() && filter.until.is_none() {
+            bail!("Give a task ID, --tag or --between to choose what to extract");
+        }
+        Ok(filter)
+    }
+
+    fn matches(&self, task: &Task) -> bool {
+        if task.status != Status::Done || !self.tags.iter().all(|t| task.tags.contains(t)) {
+            return false;
+        }
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        let Some(completed) = task
+            .completed_at
+            .as_deref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+        else {
+            return false;
+        };
+        self.since.is_none_or(|since| completed >= since)
+            && self.until.is_none_or(|until| completed < until)
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.tags.is_empty() {
+            parts.push(format!("tagged {}", self.tags.join(", ")));
+        }
+        let fmt = |dt: Option<DateTime<Utc>>| {
+            dt.map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "…".to_string())
+        };
+        if self.since.is_some() || self.until.is_some() {
+            parts.push(format!(
+                "completed {} to {}",
+                fmt(self.since),
+                fmt(self.until)
+            ));
+        }
+        parts.join(", ")
+    }
+}
+
+fn parse_range_end(s: &str, end_of_range: bool) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    let date = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").with_context(|| {
+        format!(
+            "Invalid date '{}', expected YYYY-MM-DD or an RFC 3339 timestamp",
+            s
+        )
+    })?;
+    let date = if end_of_range {
+        date.succ_opt().context("Date out of range")?
+    } else {
+        date
+    };
+    Ok(date
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is always valid")
+        .and_utc())
+}
+
+/// Run `wg func extract --tag/--between`: extract every completed task
+/// matching `filter`, for work that wasn't rooted under a single task.
+#[allow(clippy::too_many_arguments)]
+pub fn run_filtered(
+    dir: &Path,
+    filter: &ExtractFilter,
+    name: Option<&str>,
+    generalize: bool,
+    output: Option<&str>,
+    force: bool,
+    include_evaluations: bool,
+    review: bool,
+) -> Result<()> {
+    if review && !std::io::stdin().is_terminal() {
+        bail!("--review needs an interactive terminal");
+    }
+    let func_id = match (name, filter.tags.first()) {
+        (Some(name), _) => name.to_string(),
+        (None, Some(tag)) => sanitize_id(tag),
+        (None, None) => bail!("--name is required when extracting by date range alone"),
+    };
+
+    let (mut graph, _path) = super::load_workgraph(dir)?;
+    workgraph::archive::include_archived(&mut graph, dir)?;
+    let mut tasks_to_extract: Vec<&Task> = graph.tasks().filter(|t| filter.matches(t)).collect();
+    if tasks_to_extract.is_empty() {
+        bail!("No completed tasks match ({})", filter.describe());
+    }
+    tasks_to_extract.sort_by(|a, b| a.completed_at.cmp(&b.completed_at).then(a.id.cmp(&b.id)));
+
+    // Record the tasks nothing else in the selection depends on as sources
+    let selected: HashSet<&str> = tasks_to_extract.iter().map(|t| t.id.as_str()).collect();
+    let roots: Vec<String> = tasks_to_extract
+        .iter()
+        .filter(|t| !t.after.iter().any(|a| selected.contains(a.as_str())))
+        .map(|t| t.id.clone())
+        .collect();
+
+    let source = Source {
+        root_id: "",
+        label: format!("{} task(s) {}", tasks_to_extract.len(), filter.describe()),
+        description: format!(
+            "Extracted from {} completed task(s) {}",
+            tasks_to_extract.len(),
+            filter.describe()
+        ),
+        extracted_from: roots,
+        extracted_by: None,
+        tags: filter.tags.clone(),
+    };
+    extract(
+        dir,
+        &graph,
+        source,
+        tasks_to_extract,
+        &func_id,
+        generalize,
+        output,
+        force,
+        include_evaluations,
+        review,
+    )
+}
+
+/// Where an extraction came from; everything that differs between
+/// extracting from a root task and from a filter.
+struct Source<'a> {
+    /// Prefix stripped from task IDs to form template IDs
+    root_id: &'a str,
+    /// Origin shown in the summary line
+    label: String,
+    description: String,
+    extracted_from: Vec<String>,
+    extracted_by: Option<String>,
+    tags: Vec<String>,
+}
+
+/// Build, optionally generalize and review, validate and save a function
+/// from `tasks_to_extract`.
+#[allow(clippy::too_many_arguments)]
+fn extract(
+    dir: &Path,
+    graph: &WorkGraph,
+    source: Source,
+    mut tasks_to_extract: Vec<&Task>,
+    func_id: &str,
+    generalize: bool,
+    output: Option<&str>,
+    force: bool,
+    include_evaluations: bool,
+    review: bool,
+) -> Result<()> {
     // Check for existing function
     let functions_dir = if let Some(out) = output {
         PathBuf::from(out)
@@ -83,13 +345,6 @@ pub fn run(
         }
     }
 
-    // Collect tasks to include
-    let mut tasks_to_extract: Vec<&Task> = if subgraph {
-        collect_subgraph(task_id, &graph)
-    } else {
-        vec![task]
-    };
-
     // Filter out coordinator-generated noise (evaluate-*, assign-*) unless opted in
     if !include_evaluations {
         tasks_to_extract.retain(|t| !is_coordinator_noise(t));
@@ -99,15 +354,12 @@ pub fn run(
     let subgraph_ids: HashSet<&str> = tasks_to_extract.iter().map(|t| t.id.as_str()).collect();
     let templates: Vec<TaskTemplate> = tasks_to_extract
         .iter()
-        .map(|t| build_template(t, task_id, &subgraph_ids, dir, &graph))
+        .map(|t| build_template(t, source.root_id, &subgraph_ids, dir, graph))
         .collect();
 
     // Collect artifacts for outputs
     let outputs = build_outputs(&tasks_to_extract);
 
-    // Detect parameters
-    let suggested_inputs = detect_parameters(&tasks_to_extract);
-
     // Read provenance operations
     let all_ops = provenance::read_all_operations(dir).unwrap_or_default();
     let task_ops: Vec<_> = all_ops
@@ -120,28 +372,31 @@ pub fn run(
         })
         .collect();
 
+    // Detect parameters, preferring what the runs actually did
+    let evidence = collect_run_evidence(dir, &tasks_to_extract, &task_ops);
+    let suggested_inputs = detect_parameters(&tasks_to_extract, &evidence);
+
     // Build the trace function
     let now = Utc::now().to_rfc3339();
     let func = TraceFunction {
         kind: "trace-function".to_string(),
         version: 1,
-        id: func_id.clone(),
-        name: name
-            .map(title_case)
-            .unwrap_or_else(|| title_case(&sanitize_id(task_id))),
-        description: task
-            .description
-            .clone()
-            .unwrap_or_else(|| task.title.clone()),
-        extracted_from: vec![ExtractionSource {
-            task_id: task_id.to_string(),
-            run_id: None,
-            timestamp: now.clone(),
-        }],
-        extracted_by: task.assigned.clone(),
+        id: func_id.to_string(),
+        name: title_case(func_id),
+        description: source.description,
+        extracted_from: source
+            .extracted_from
+            .into_iter()
+            .map(|task_id| ExtractionSource {
+                task_id,
+                run_id: None,
+                timestamp: now.clone(),
+            })
+            .collect(),
+        extracted_by: source.extracted_by,
         extracted_at: Some(now),
-        tags: task.tags.clone(),
-        inputs: suggested_inputs.clone(),
+        tags: source.tags,
+        inputs: suggested_inputs,
         tasks: templates,
         outputs,
         planning: None,
@@ -151,6 +406,9 @@ pub fn run(
         redacted_fields: vec![],
     };
 
+    // Keep the raw extraction so --review can diff against it
+    let raw = review.then(|| func.clone());
+
     // Handle --generalize: invoke executor for generalization pass
     let func = if generalize {
         match generalize_with_executor(dir, &func) {
@@ -170,6 +428,26 @@ pub fn run(
         func
     };
 
+    let func = match raw {
+        Some(raw) => {
+            let stdin = std::io::stdin();
+            match super::func_review::review(
+                &raw,
+                func,
+                &mut stdin.lock(),
+                &mut std::io::stdout(),
+                &super::func_review::edit_in_editor,
+            )? {
+                Some(reviewed) => reviewed,
+                None => {
+                    println!("Review ended; nothing saved.");
+                    return Ok(());
+                }
+            }
+        }
+        None => func,
+    };
+
     // Validate
     function::validate_function(&func).context("Extracted function failed validation")?;
 
@@ -188,8 +466,8 @@ pub fn run(
 
     // Print summary
     println!(
-        "Extracted trace function '{}' from task '{}'",
-        func_id, task_id
+        "Extracted trace function '{}' from {}",
+        func_id, source.label
     );
     println!();
     println!(
@@ -202,10 +480,10 @@ pub fn run(
         }
     );
 
-    if !suggested_inputs.is_empty() {
+    if !func.inputs.is_empty() {
         println!();
         println!("Suggested parameters:");
-        for input in &suggested_inputs {
+        for input in &func.inputs {
             let req = if input.required { ", required" } else { "" };
             let example_str = input
                 .example
@@ -302,10 +580,7 @@ fn build_template(
     TaskTemplate {
         template_id,
         title: task.title.clone(),
-        description: task
-            .description
-            .clone()
-            .unwrap_or_else(|| task.title.clone()),
+        description: template_description(task),
         skills: task.skills.clone(),
         after,
         loops_to,
@@ -316,6 +591,31 @@ fn build_template(
     }
 }
 
+/// Template description: the task description, followed by the completing
+/// agent's self-report (if any) so the method travels with the template.
+fn template_description(task: &Task) -> String {
+    let mut description = task
+        .description
+        .clone()
+        .unwrap_or_else(|| task.title.clone());
+    let Some(ref report) = task.self_report else {
+        return description;
+    };
+    description.push_str("\n\n## Method (from the original run)\n");
+    description.push_str(&report.approach);
+    description.push('\n');
+    for (i, step) in report.steps.iter().enumerate() {
+        description.push_str(&format!("{}. {}\n", i + 1, step));
+    }
+    if !report.pitfalls.is_empty() {
+        description.push_str("\nPitfalls:\n");
+        for pitfall in &report.pitfalls {
+            description.push_str(&format!("- {}\n", pitfall));
+        }
+    }
+    description.trim_end().to_string()
+}
+
 /// Look up the role name for a task's agent from the agency storage.
 fn lookup_role_hint(task: &Task, dir: &Path) -> Option<String> {
     let agent_hash = task.agent.as_ref()?;
@@ -835,7 +1135,7 @@ pub fn run_generative(
         bail!("--generative requires at least 2 task IDs for multi-trace extraction");
     }
 
-    let (graph, _path) = super::load_workgraph(dir)?;
+    let graph = load_with_archive(dir, task_ids, false)?;
 
     for tid in task_ids {
         let task = graph.get_task_or_err(tid)?;
@@ -1029,7 +1329,8 @@ pub fn run_generative(
     };
 
     let all_tasks_flat: Vec<&Task> = traces.iter().flat_map(|t| t.iter().copied()).collect();
-    let suggested_inputs = detect_parameters(&all_tasks_flat);
+    let evidence = collect_run_evidence(dir, &all_tasks_flat, &[]);
+    let suggested_inputs = detect_parameters(&all_tasks_flat, &evidence);
 
     let func_id = name
         .map(|s| s.to_string())
@@ -1184,7 +1485,164 @@ fn build_outputs(tasks: &[&Task]) -> Vec<FunctionOutput> {
 /// - URLs → suggest as url parameters
 /// - Numbers → suggest as numeric parameters
 /// - Commands (cargo test, npm test, etc.) → suggest as test_command
-fn detect_parameters(tasks: &[&Task]) -> Vec<FunctionInput> {
+/// What the extracted runs actually did, as opposed to what their
+/// descriptions said: commands executed and files written.
+#[derive(Debug, Default)]
+struct RunEvidence {
+    /// Executed shell commands, in run order (duplicates kept)
+    commands: Vec<String>,
+    /// Project-relative paths written or edited, deduplicated
+    files_written: Vec<String>,
+}
+
+/// Tool names (Claude and native executors) whose input names a file written
+const WRITE_TOOLS: &[&str] = &["Write", "Edit", "MultiEdit", "write_file", "edit_file"];
+/// Tool names whose input is a shell command
+const SHELL_TOOLS: &[&str] = &["Bash", "bash"];
+
+/// Gather [`RunEvidence`] from `artifact_add` provenance entries and the
+/// tool calls in each task's archived agent output
+/// (`log/agents/<task>/<attempt>/output.txt`).
+fn collect_run_evidence(
+    dir: &Path,
+    tasks: &[&Task],
+    ops: &[&provenance::OperationEntry],
+) -> RunEvidence {
+    let project_root = dir.parent().unwrap_or(dir);
+    let mut evidence = RunEvidence::default();
+    let mut files = Vec::new();
+
+    for op in ops.iter().filter(|op| op.op == "artifact_add") {
+        if let Some(path) = op.detail.get("path").and_then(|p| p.as_str()) {
+            files.push(path.to_string());
+        }
+    }
+
+    for task in tasks {
+        let archive = dir.join("log").join("agents").join(&task.id);
+        let Ok(entries) = std::fs::read_dir(&archive) else {
+            continue;
+        };
+        let mut attempts: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        attempts.sort();
+        for attempt in attempts {
+            if let Ok(output) = std::fs::read_to_string(attempt.join("output.txt")) {
+                scan_tool_calls(&output, &mut evidence.commands, &mut files);
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    evidence.files_written = files
+        .iter()
+        .filter_map(|f| project_relative(f, project_root))
+        .filter(|f| seen.insert(f.clone()))
+        .collect();
+    evidence
+}
+
+/// Collect shell commands and written file paths from stream-json output.
+/// Handles both top-level `tool_use` events and `tool_use` blocks inside
+/// assistant messages.
+fn scan_tool_calls(output: &str, commands: &mut Vec<String>, files: &mut Vec<String>) {
+    let mut visit = |call: &serde_json::Value| {
+        let name = call.get("name").and_then(|n| n.as_str()).unwrap_or("");
+        let input = call.get("input");
+        let field = |key: &str| input.and_then(|i| i.get(key)).and_then(|v| v.as_str());
+        if SHELL_TOOLS.contains(&name) {
+            if let Some(cmd) = field("command") {
+                commands.push(cmd.to_string());
+            }
+        } else if WRITE_TOOLS.contains(&name)
+            && let Some(path) = field("file_path").or_else(|| field("path"))
+        {
+            files.push(path.to_string());
+        }
+    };
+
+    for line in output.lines() {
+        let Ok(val) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+            continue;
+        };
+        if val.get("type").and_then(|t| t.as_str()) == Some("tool_use") {
+            visit(&val);
+        }
+        let blocks = val
+            .get("message")
+            .and_then(|m| m.get("content"))
+            .and_then(|c| c.as_array());
+        for block in blocks.into_iter().flatten() {
+            if block.get("type").and_then(|t| t.as_str()) == Some("tool_use") {
+                visit(block);
+            }
+        }
+    }
+}
+
+/// Make `path` relative to the project, dropping the `.wg-worktrees/<agent>/`
+/// prefix of agent worktrees. Paths outside the project or inside `.wg/`
+/// are not source files and yield `None`.
+fn project_relative(path: &str, project_root: &Path) -> Option<String> {
+    let path = Path::new(path);
+    let rel = if path.is_absolute() {
+        path.strip_prefix(project_root).ok()?
+    } else {
+        path
+    };
+    let mut components = rel.components();
+    let rel = if rel.starts_with(".wg-worktrees") {
+        components.nth(1)?;
+        components.as_path()
+    } else {
+        rel
+    };
+    if rel.as_os_str().is_empty() || rel.starts_with(".wg") {
+        return None;
+    }
+    Some(rel.to_string_lossy().to_string())
+}
+
+/// Pull verification commands (tests, lints, checks) out of executed shell
+/// commands, splitting `&&`/`;` chains and dropping pipes and redirects.
+/// Returns `(command, times_run)`, most-run first; ties go to the latest.
+fn verification_commands(executed: &[String]) -> Vec<(String, usize)> {
+    const VERIFY_PREFIXES: &[&str] = &[
+        "cargo test",
+        "cargo nextest",
+        "cargo clippy",
+        "cargo check",
+        "pytest",
+        "python -m pytest",
+        "npm test",
+        "npm run test",
+        "yarn test",
+        "go test",
+        "make test",
+        "make check",
+    ];
+    let mut counts: Vec<(String, usize, usize)> = Vec::new();
+    let segments = executed
+        .iter()
+        .flat_map(|c| c.split("&&").flat_map(|s| s.split(';')));
+    for (order, segment) in segments.enumerate() {
+        let cmd = segment.split('|').next().unwrap_or("");
+        let cmd = cmd.split(" 2>").next().unwrap_or("").trim();
+        if !VERIFY_PREFIXES.iter().any(|p| cmd.starts_with(p)) {
+            continue;
+        }
+        match counts.iter_mut().find(|(c, _, _)| c == cmd) {
+            Some(entry) => {
+                entry.1 += 1;
+                entry.2 = order;
+            }
+            None => counts.push((cmd.to_string(), 1, order)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)));
+    counts.into_iter().map(|(cmd, n, _)| (cmd, n)).collect()
+}
+
+fn detect_parameters(tasks: &[&Task], evidence: &RunEvidence) -> Vec<FunctionInput> {
     let mut inputs = Vec::new();
     let mut seen_names = HashSet::new();
 
@@ -1219,12 +1677,14 @@ fn detect_parameters(tasks: &[&Task]) -> Vec<FunctionInput> {
         }
     }
 
-    // Detect source_files only from task artifacts (explicitly recorded output files),
-    // not from every file path mentioned in descriptions.
+    // Detect source_files only from task artifacts (explicitly recorded output files)
+    // and files the runs actually wrote, not from every file path mentioned in
+    // descriptions.
     if !seen_names.contains("source_files") {
         let mut artifact_paths: Vec<String> = tasks
             .iter()
             .flat_map(|t| t.artifacts.iter().cloned())
+            .chain(evidence.files_written.iter().cloned())
             .collect();
         artifact_paths.sort();
         artifact_paths.dedup();
@@ -1273,15 +1733,31 @@ fn detect_parameters(tasks: &[&Task]) -> Vec<FunctionInput> {
         }
     }
 
-    // Detect test/build commands — only actual CLI commands (known prefixes or backtick-quoted)
-    let commands = extract_cli_commands(&all_text);
-    if !commands.is_empty() && !seen_names.contains("test_command") {
+    // Detect test/build commands — prefer the verification command the runs
+    // executed most; fall back to actual CLI commands mentioned in text
+    // (known prefixes or backtick-quoted)
+    let (test_command, description) = match verification_commands(&evidence.commands).first() {
+        Some((cmd, runs)) => (
+            Some(cmd.clone()),
+            format!(
+                "Command to verify the implementation (run {} time(s) in the original trace)",
+                runs
+            ),
+        ),
+        None => (
+            extract_cli_commands(&all_text).into_iter().next(),
+            "Command to verify the implementation".to_string(),
+        ),
+    };
+    if let Some(test_command) = test_command
+        && !seen_names.contains("test_command")
+    {
         inputs.push(FunctionInput {
             name: "test_command".to_string(),
             input_type: InputType::String,
-            description: "Command to verify the implementation".to_string(),
+            description,
             required: false,
-            default: Some(serde_yaml::Value::String(commands.first().unwrap().clone())),
+            default: Some(serde_yaml::Value::String(test_command)),
             example: None,
             min: None,
             max: None,
@@ -1292,8 +1768,11 @@ fn detect_parameters(tasks: &[&Task]) -> Vec<FunctionInput> {
 
     // Detect numbers only when they appear near parameterizable keywords
     // (e.g., "--max-iterations 3", "threshold 0.8", "limit 10").
-    // Skip standalone numbers with no semantic context.
-    let contextual_numbers = extract_contextual_numbers(&all_text);
+    // Skip standalone numbers with no semantic context. Executed commands
+    // come first so the values actually used win over prose.
+    let executed_text = evidence.commands.join("\n");
+    let contextual_numbers =
+        extract_contextual_numbers(&format!("{}\n{}", executed_text, all_text));
     for (param_name, num) in contextual_numbers.iter() {
         if !seen_names.contains(param_name) {
             inputs.push(FunctionInput {
@@ -1869,6 +2348,48 @@ mod tests {
         assert_eq!(child2_tmpl.after, vec!["child1"]);
     }
 
+    #[test]
+    fn test_extract_by_tag_and_date_range() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+
+        let mut graph = WorkGraph::new();
+        for (id, day, tagged) in [
+            ("cut-branch", "2026-03-01", true),
+            ("write-notes", "2026-03-02", true),
+            ("publish", "2026-03-05", true),
+            ("unrelated", "2026-03-02", false),
+        ] {
+            let mut task = make_task(id, id);
+            task.completed_at = Some(format!("{}T12:00:00Z", day));
+            if tagged {
+                task.tags = vec!["release-1.4".to_string()];
+            }
+            if id == "write-notes" {
+                task.after = vec!["cut-branch".to_string()];
+            }
+            graph.add_node(Node::Task(task));
+        }
+        setup_graph(&dir, &graph);
+
+        let filter =
+            ExtractFilter::new(&["release-1.4".to_string()], Some("2026-03-01..2026-03-02"))
+                .unwrap();
+        run_filtered(&dir, &filter, None, false, None, false, false, false).unwrap();
+
+        let func =
+            function::load_function(&dir.join("functions").join("release-1-4.yaml")).unwrap();
+        let ids: Vec<&str> = func.tasks.iter().map(|t| t.template_id.as_str()).collect();
+        assert_eq!(ids, vec!["cut-branch", "write-notes"]);
+        assert_eq!(func.extracted_from.len(), 1);
+        assert_eq!(func.extracted_from[0].task_id, "cut-branch");
+
+        assert!(ExtractFilter::new(&[], None).is_err());
+        assert!(ExtractFilter::new(&[], Some("2026-03-01")).is_err());
+        let range_only = ExtractFilter::new(&[], Some("2026-03-05..")).unwrap();
+        assert!(run_filtered(&dir, &range_only, None, false, None, false, false, false).is_err());
+    }
+
     #[test]
     fn test_extract_force_overwrite() {
         let tmp = TempDir::new().unwrap();
@@ -1980,7 +2501,7 @@ mod tests {
             ..Task::default()
         };
 
-        let params = detect_parameters(&[&task]);
+        let params = detect_parameters(&[&task], &RunEvidence::default());
 
         // Should detect feature_name (derived: "auth" from "impl-auth")
         let feature = params.iter().find(|p| p.name == "feature_name");
@@ -2008,7 +2529,7 @@ mod tests {
             ..Task::default()
         };
 
-        let params = detect_parameters(&[&task]);
+        let params = detect_parameters(&[&task], &RunEvidence::default());
         assert!(
             !params.iter().any(|p| p.name == "source_files"),
             "source_files should NOT be detected from description text alone"
@@ -2028,7 +2549,7 @@ mod tests {
             ..Task::default()
         };
 
-        let params = detect_parameters(&[&task]);
+        let params = detect_parameters(&[&task], &RunEvidence::default());
         // Should NOT extract random numbers like 5, 3, 42, 7 as parameters
         assert!(
             !params.iter().any(|p| p.input_type == InputType::Number),
@@ -2053,7 +2574,7 @@ mod tests {
             ..Task::default()
         };
 
-        let params = detect_parameters(&[&task]);
+        let params = detect_parameters(&[&task], &RunEvidence::default());
         assert!(
             params
                 .iter()
@@ -2061,6 +2582,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_parameters_from_run_evidence() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        let attempt = dir.join("log/agents/impl-auth/2026-03-01T00:00:00Z");
+        std::fs::create_dir_all(&attempt).unwrap();
+        let root = tmp.path().display();
+        let output = [
+            r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo test auth 2>&1 | tail -20"}}]}}"#.to_string(),
+            format!(
+                r#"{{"type":"assistant","message":{{"content":[{{"type":"tool_use","name":"Edit","input":{{"file_path":"{}/.wg-worktrees/agent-7/src/auth.rs"}}}}]}}}}"#,
+                root
+            ),
+            r#"{"type":"tool_use","name":"bash","input":{"command":"cargo build && cargo test auth --retries 4"}}"#.to_string(),
+            r#"{"type":"tool_use","name":"Bash","input":{"command":"cargo test auth"}}"#.to_string(),
+            r#"{"type":"tool_use","name":"Write","input":{"file_path":"/tmp/scratch.txt"}}"#.to_string(),
+        ]
+        .join("\n");
+        std::fs::write(attempt.join("output.txt"), output).unwrap();
+
+        // The description mentions a different command than the one run
+        let task = Task {
+            id: "impl-auth".to_string(),
+            title: "Implement auth".to_string(),
+            description: Some("Verify with `make check`.".to_string()),
+            status: Status::Done,
+            ..Task::default()
+        };
+        let evidence = collect_run_evidence(&dir, &[&task], &[]);
+        assert_eq!(evidence.files_written, vec!["src/auth.rs"]);
+
+        let params = detect_parameters(&[&task], &evidence);
+        let test_command = params.iter().find(|p| p.name == "test_command").unwrap();
+        assert_eq!(
+            test_command.default,
+            Some(serde_yaml::Value::String("cargo test auth".to_string()))
+        );
+        let source_files = params.iter().find(|p| p.name == "source_files").unwrap();
+        assert_eq!(
+            source_files.example,
+            Some(serde_yaml::Value::Sequence(vec![
+                serde_yaml::Value::String("src/auth.rs".to_string())
+            ]))
+        );
+        assert!(params.iter().any(|p| p.name == "retries"));
+    }
+
+    #[test]
+    fn test_template_description_includes_self_report() {
+        let mut task = make_task("fix-login", "Fix login");
+        task.description = Some("Login fails after upgrade".to_string());
+        assert_eq!(template_description(&task), "Login fails after upgrade");
+
+        task.self_report = Some(workgraph::graph::SelfReport {
+            approach: "Bisected the upgrade".to_string(),
+            steps: vec!["git bisect".to_string(), "pin the crate".to_string()],
+            pitfalls: vec!["cached session cookies".to_string()],
+        });
+        let description = template_description(&task);
+        assert!(description.starts_with("Login fails after upgrade\n\n## Method"));
+        assert!(description.contains("Bisected the upgrade\n1. git bisect\n2. pin the crate"));
+        assert!(description.ends_with("Pitfalls:\n- cached session cookies"));
+    }
+
     #[test]
     fn test_collect_subgraph_standalone() {
         let mut graph = WorkGraph::new();
@@ -130,6 +130,7 @@ pub fn run(dir: &Path, function_id: &str, max_runs: u32) -> Result<()> {
                         score: None,
                         duration_secs,
                         retry_count: task.retry_count,
+                        changes: task.changes.clone(),
                     });
                 }
             }
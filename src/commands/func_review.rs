@@ -0,0 +1,313 @@
+//! `wg func extract --review` — interactive review of an extracted function
+//! before it is saved.
+//!
+//! Walks the suggested parameters and every title/description the
+//! generalization pass rewrote, showing a `-`/`+` diff against the raw
+//! extraction. Each field can be accepted, rejected (raw text restored or
+//! parameter dropped), or edited in `$EDITOR`.
+
+use anyhow::{Context, Result, bail};
+use std::io::{BufRead, Write};
+use workgraph::function::{self, FunctionInput, TraceFunction};
+
+/// Per-field decision
+#[derive(Debug, Clone, PartialEq)]
+enum Decision {
+    Accept,
+    Reject,
+    Edit,
+    /// Accept this and every remaining field
+    AcceptAll,
+    Quit,
+}
+
+struct Session<'a, R, W> {
+    input: &'a mut R,
+    out: &'a mut W,
+    editor: &'a dyn Fn(&str) -> Result<String>,
+    accept_rest: bool,
+}
+
+impl<R: BufRead, W: Write> Session<'_, R, W> {
+    fn ask(&mut self) -> Result<Decision> {
+        if self.accept_rest {
+            return Ok(Decision::Accept);
+        }
+        loop {
+            write!(
+                self.out,
+                "  [a]ccept, [r]eject, [e]dit, accept [A]ll, [q]uit > "
+            )?;
+            self.out.flush()?;
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                return Ok(Decision::Quit);
+            }
+            match line.trim() {
+                "" | "a" | "y" => return Ok(Decision::Accept),
+                "r" | "n" => return Ok(Decision::Reject),
+                "e" => return Ok(Decision::Edit),
+                "A" => {
+                    self.accept_rest = true;
+                    return Ok(Decision::AcceptAll);
+                }
+                "q" => return Ok(Decision::Quit),
+                other => writeln!(self.out, "  Unknown choice '{}'", other)?,
+            }
+        }
+    }
+
+    /// Review one text field. Returns `None` if the user quit.
+    fn text(&mut self, label: &str, raw: &str, proposed: &str) -> Result<Option<String>> {
+        writeln!(self.out, "\n{}", label)?;
+        for line in raw.lines() {
+            writeln!(self.out, "  - {}", line)?;
+        }
+        for line in proposed.lines() {
+            writeln!(self.out, "  + {}", line)?;
+        }
+        Ok(match self.ask()? {
+            Decision::Accept | Decision::AcceptAll => Some(proposed.to_string()),
+            Decision::Reject => Some(raw.to_string()),
+            Decision::Edit => Some((self.editor)(proposed)?.trim_end().to_string()),
+            Decision::Quit => None,
+        })
+    }
+
+    /// Review one suggested parameter. `Ok(Some(None))` drops it.
+    fn parameter(&mut self, input: &FunctionInput) -> Result<Option<Option<FunctionInput>>> {
+        writeln!(
+            self.out,
+            "\nParameter '{}' ({:?}{})",
+            input.name,
+            input.input_type,
+            if input.required { ", required" } else { "" }
+        )?;
+        writeln!(self.out, "  {}", input.description)?;
+        if let Some(ref example) = input.example {
+            writeln!(self.out, "  e.g. {}", function::render_value(example))?;
+        }
+        Ok(match self.ask()? {
+            Decision::Accept | Decision::AcceptAll => Some(Some(input.clone())),
+            Decision::Reject => Some(None),
+            Decision::Edit => {
+                let yaml = serde_yaml::to_string(input)?;
+                let edited = (self.editor)(&yaml)?;
+                let parsed: FunctionInput =
+                    serde_yaml::from_str(&edited).context("Edited parameter is not valid YAML")?;
+                Some(Some(parsed))
+            }
+            Decision::Quit => None,
+        })
+    }
+}
+
+/// Review `proposed` against the `raw` extraction. Returns the function to
+/// save, or `None` if the user quit or declined to save.
+pub fn review<R: BufRead, W: Write>(
+    raw: &TraceFunction,
+    mut proposed: TraceFunction,
+    input: &mut R,
+    out: &mut W,
+    editor: &dyn Fn(&str) -> Result<String>,
+) -> Result<Option<TraceFunction>> {
+    let mut session = Session {
+        input,
+        out,
+        editor,
+        accept_rest: false,
+    };
+    writeln!(
+        session.out,
+        "Reviewing function '{}' ({} task(s), {} suggested parameter(s))",
+        proposed.id,
+        proposed.tasks.len(),
+        proposed.inputs.len()
+    )?;
+
+    if proposed.description != raw.description {
+        match session.text(
+            "Function description",
+            &raw.description,
+            &proposed.description,
+        )? {
+            Some(text) => proposed.description = text,
+            None => return Ok(None),
+        }
+    }
+
+    for template in proposed.tasks.iter_mut() {
+        let Some(original) = raw
+            .tasks
+            .iter()
+            .find(|t| t.template_id == template.template_id)
+        else {
+            continue;
+        };
+        if template.title != original.title {
+            let label = format!("Task '{}' title", template.template_id);
+            match session.text(&label, &original.title, &template.title)? {
+                Some(text) => template.title = text,
+                None => return Ok(None),
+            }
+        }
+        if template.description != original.description {
+            let label = format!("Task '{}' description", template.template_id);
+            match session.text(&label, &original.description, &template.description)? {
+                Some(text) => template.description = text,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    let mut kept = Vec::new();
+    for param in &proposed.inputs {
+        match session.parameter(param)? {
+            Some(Some(p)) => kept.push(p),
+            Some(None) => {
+                let placeholder = format!("{{{{input.{}}}}}", param.name);
+                if proposed
+                    .tasks
+                    .iter()
+                    .any(|t| t.title.contains(&placeholder) || t.description.contains(&placeholder))
+                {
+                    writeln!(
+                        session.out,
+                        "  Note: {} is still used in task templates",
+                        placeholder
+                    )?;
+                }
+            }
+            None => return Ok(None),
+        }
+    }
+    proposed.inputs = kept;
+
+    write!(
+        session.out,
+        "\nSave function '{}' with {} parameter(s)? [Y/n] ",
+        proposed.id,
+        proposed.inputs.len()
+    )?;
+    session.out.flush()?;
+    let mut line = String::new();
+    session.input.read_line(&mut line)?;
+    Ok(matches!(line.trim(), "" | "y" | "Y" | "yes").then_some(proposed))
+}
+
+/// Edit `text` in `$EDITOR` (falling back to `$VISUAL`, then `vi`).
+pub fn edit_in_editor(text: &str) -> Result<String> {
+    let editor = std::env::var("EDITOR")
+        .or_else(|_| std::env::var("VISUAL"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let path = std::env::temp_dir().join(format!("wg-review-{}.txt", std::process::id()));
+    std::fs::write(&path, text).with_context(|| format!("Failed to write {}", path.display()))?;
+    let status = std::process::Command::new(&editor)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{}'", editor));
+    let edited = std::fs::read_to_string(&path);
+    std::fs::remove_file(&path).ok();
+    if !status?.success() {
+        bail!("Editor exited with non-zero status");
+    }
+    Ok(edited?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use workgraph::function::{FunctionVisibility, InputType, TaskTemplate};
+
+    fn func(description: &str, task_desc: &str, inputs: &[&str]) -> TraceFunction {
+        TraceFunction {
+            kind: "trace-function".to_string(),
+            version: 1,
+            id: "ship".to_string(),
+            name: "Ship".to_string(),
+            description: description.to_string(),
+            extracted_from: vec![],
+            extracted_by: None,
+            extracted_at: None,
+            tags: vec![],
+            inputs: inputs
+                .iter()
+                .map(|name| FunctionInput {
+                    name: name.to_string(),
+                    input_type: InputType::String,
+                    description: format!("The {}", name),
+                    required: true,
+                    default: None,
+                    example: None,
+                    min: None,
+                    max: None,
+                    values: None,
+                })
+                .collect(),
+            tasks: vec![TaskTemplate {
+                template_id: "build".to_string(),
+                title: "Build".to_string(),
+                description: task_desc.to_string(),
+                skills: vec![],
+                after: vec![],
+                loops_to: vec![],
+                role_hint: None,
+                deliverables: vec![],
+                verify: None,
+                tags: vec![],
+            }],
+            outputs: vec![],
+            planning: None,
+            constraints: None,
+            memory: None,
+            visibility: FunctionVisibility::Internal,
+            redacted_fields: vec![],
+        }
+    }
+
+    fn run_review(
+        raw: &TraceFunction,
+        proposed: TraceFunction,
+        answers: &str,
+    ) -> Option<TraceFunction> {
+        let mut input = Cursor::new(answers.as_bytes().to_vec());
+        let mut out = Vec::new();
+        let editor = |_: &str| Ok("edited by hand\n".to_string());
+        review(raw, proposed, &mut input, &mut out, &editor).unwrap()
+    }
+
+    #[test]
+    fn test_review_per_field_decisions() {
+        let raw = func("Ship login v2", "Build login v2", &[]);
+        let proposed = func(
+            "Ship {{input.feature}}",
+            "Build {{input.feature}}",
+            &["feature", "owner"],
+        );
+
+        // reject description, edit task description, keep feature, drop owner, save
+        let saved = run_review(&raw, proposed, "r\ne\na\nr\ny\n").unwrap();
+        assert_eq!(saved.description, "Ship login v2");
+        assert_eq!(saved.tasks[0].description, "edited by hand");
+        assert_eq!(saved.inputs.len(), 1);
+        assert_eq!(saved.inputs[0].name, "feature");
+    }
+
+    #[test]
+    fn test_review_accept_all_and_quit() {
+        let raw = func("Ship login v2", "Build login v2", &[]);
+        let proposed = func(
+            "Ship {{input.feature}}",
+            "Build {{input.feature}}",
+            &["feature"],
+        );
+
+        let saved = run_review(&raw, proposed.clone(), "A\n\n").unwrap();
+        assert_eq!(saved.description, "Ship {{input.feature}}");
+        assert_eq!(saved.inputs.len(), 1);
+
+        assert!(run_review(&raw, proposed.clone(), "q\n").is_none());
+        assert!(run_review(&raw, proposed, "A\nn\n").is_none());
+    }
+}
@@ -0,0 +1,281 @@
+//! `wg gate` — phase gates between graph phases.
+//!
+//! Usage:
+//!   wg gate create release-gate --after impl-a,impl-b --phase-done --min-score 0.8 \
+//!       --command "cargo clippy -- -D warnings" --approval
+//!   wg gate check [release-gate]
+//!   wg gate approve release-gate
+//!   wg gate list
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::path::Path;
+use workgraph::gate::{self, GateCondition, GateReport, PhaseGate};
+use workgraph::graph::{LogEntry, Node, Status, Task};
+use workgraph::parser::modify_graph;
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_create(
+    dir: &Path,
+    id: &str,
+    title: Option<&str>,
+    after: &[String],
+    phase_done: bool,
+    min_score: Option<f64>,
+    commands: &[String],
+    approval: bool,
+) -> Result<()> {
+    let (graph, path) = super::load_workgraph(dir)?;
+    if graph.get_node(id).is_some() {
+        anyhow::bail!("Node with ID '{}' already exists", id);
+    }
+    for dep in after {
+        if graph.get_task(dep).is_none() {
+            anyhow::bail!("Task '{}' not found", dep);
+        }
+    }
+
+    let mut conditions = Vec::new();
+    if phase_done {
+        conditions.push(GateCondition::PhaseDone);
+    }
+    if let Some(min) = min_score {
+        conditions.push(GateCondition::MinScore { min });
+    }
+    for command in commands {
+        conditions.push(GateCondition::Command {
+            command: command.clone(),
+            passed: None,
+            failures: 0,
+            retry_at: None,
+        });
+    }
+    if approval {
+        conditions.push(GateCondition::Approval);
+    }
+    if conditions.is_empty() {
+        anyhow::bail!(
+            "A gate needs at least one condition (--phase-done, --min-score, --command, --approval)"
+        );
+    }
+
+    let task = Task {
+        id: id.to_string(),
+        title: title
+            .map(String::from)
+            .unwrap_or_else(|| format!("Gate: {}", id)),
+        after: after.to_vec(),
+        tags: vec!["gate".to_string()],
+        created_at: Some(Utc::now().to_rfc3339()),
+        gate: Some(PhaseGate {
+            conditions,
+            approved_by: None,
+        }),
+        ..Task::default()
+    };
+
+    modify_graph(&path, |graph| {
+        graph.add_node(Node::Task(task.clone()));
+        true
+    })
+    .context("Failed to save graph")?;
+    super::notify_graph_changed(dir);
+
+    let config = workgraph::config::Config::load_or_default(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "add_task",
+        Some(id),
+        None,
+        serde_json::json!({ "title": task.title, "after": after, "gate": true }),
+        config.log.rotation_threshold,
+    );
+
+    let n = task.gate.as_ref().map_or(0, |g| g.conditions.len());
+    println!("Created gate '{}' with {} condition(s)", id, n);
+    if !after.is_empty() {
+        println!(
+            "Downstream phase tasks should depend on it: wg add \"...\" --after {}",
+            id
+        );
+    }
+    Ok(())
+}
+
+pub fn run_check(dir: &Path, id: Option<&str>, json: bool) -> Result<()> {
+    let (graph, path) = super::load_workgraph(dir)?;
+    if let Some(id) = id {
+        let task = graph.get_task_or_err(id)?;
+        if task.gate.is_none() {
+            anyhow::bail!("Task '{}' is not a gate", id);
+        }
+    }
+
+    let reports = gate::check_gates(dir, &path, id)?;
+    if reports.iter().any(GateReport::is_open) {
+        super::notify_graph_changed(dir);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+        return Ok(());
+    }
+    if reports.is_empty() {
+        println!("No gates ready to check");
+        return Ok(());
+    }
+    for report in &reports {
+        print_report(report);
+        if report.is_open() {
+            println!("  → gate opened");
+        }
+    }
+    Ok(())
+}
+
+pub fn run_approve(dir: &Path, id: &str) -> Result<()> {
+    let (_graph, path) = super::load_workgraph(dir)?;
+    let user = workgraph::current_user();
+
+    let mut error: Option<anyhow::Error> = None;
+    modify_graph(&path, |graph| {
+        let task = match graph.get_task_mut(id) {
+            Some(t) => t,
+            None => {
+                error = Some(anyhow::anyhow!("Task '{}' not found", id));
+                return false;
+            }
+        };
+        let Some(ref mut gate) = task.gate else {
+            error = Some(anyhow::anyhow!("Task '{}' is not a gate", id));
+            return false;
+        };
+        gate.approved_by = Some(user.clone());
+        task.log.push(LogEntry {
+            timestamp: Utc::now().to_rfc3339(),
+            actor: None,
+            user: Some(user.clone()),
+            message: format!("Gate approved by {}", user),
+        });
+        true
+    })
+    .context("Failed to save graph")?;
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    println!("Approved gate '{}'", id);
+    // Opening is decided by the full condition set, not approval alone.
+    run_check(dir, Some(id), false)
+}
+
+pub fn run_list(dir: &Path, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let reports: Vec<(GateReport, Status)> = graph
+        .tasks()
+        .filter_map(|t| gate::evaluate(dir, &graph, t, false).map(|r| (r, t.status)))
+        .collect();
+
+    if json {
+        let out: Vec<_> = reports
+            .iter()
+            .map(|(r, status)| {
+                serde_json::json!({
+                    "gate_id": r.gate_id,
+                    "status": status,
+                    "met": r.met(),
+                    "total": r.total(),
+                    "results": r.results,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    if reports.is_empty() {
+        println!("No gates defined");
+        return Ok(());
+    }
+    for (report, status) in &reports {
+        if *status == Status::Done {
+            println!("{}: open", report.gate_id);
+        } else {
+            print_report(report);
+        }
+    }
+    Ok(())
+}
+
+fn print_report(report: &GateReport) {
+    println!("{}: {}", report.gate_id, report.summary());
+    for r in &report.results {
+        let mark = if r.met { "✓" } else { "✗" };
+        match r.detail {
+            Some(ref d) => println!("  {} {} ({})", mark, r.condition, d),
+            None => println!("  {} {}", mark, r.condition),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use workgraph::graph::WorkGraph;
+    use workgraph::parser::{load_graph, save_graph};
+
+    fn setup(dir: &Path) {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(Task {
+            id: "impl".to_string(),
+            title: "Implement".to_string(),
+            status: Status::Done,
+            ..Task::default()
+        }));
+        save_graph(&graph, super::super::graph_path(dir)).unwrap();
+    }
+
+    #[test]
+    fn test_create_requires_condition() {
+        let dir = tempdir().unwrap();
+        setup(dir.path());
+        let err = run_create(dir.path(), "g", None, &[], false, None, &[], false).unwrap_err();
+        assert!(err.to_string().contains("at least one condition"));
+    }
+
+    #[test]
+    fn test_approve_opens_gate_when_all_conditions_met() {
+        let dir = tempdir().unwrap();
+        setup(dir.path());
+        run_create(
+            dir.path(),
+            "release",
+            None,
+            &["impl".to_string()],
+            true,
+            None,
+            &[],
+            true,
+        )
+        .unwrap();
+
+        let graph = load_graph(super::super::graph_path(dir.path())).unwrap();
+        let gate = graph.get_task("release").unwrap();
+        assert_eq!(gate.status, Status::Open);
+        assert!(gate.tags.contains(&"gate".to_string()));
+
+        run_approve(dir.path(), "release").unwrap();
+        let graph = load_graph(super::super::graph_path(dir.path())).unwrap();
+        let gate = graph.get_task("release").unwrap();
+        assert_eq!(gate.status, Status::Done);
+        assert!(gate.gate.as_ref().unwrap().approved_by.is_some());
+    }
+
+    #[test]
+    fn test_approve_rejects_non_gate() {
+        let dir = tempdir().unwrap();
+        setup(dir.path());
+        assert!(run_approve(dir.path(), "impl").is_err());
+    }
+}
@@ -8,7 +8,7 @@ use workgraph::parser::modify_graph;
 use super::graph_path;
 
 /// Auto-generated task prefixes that should be gc'd alongside their parent task.
-const INTERNAL_PREFIXES: &[&str] = &[
+pub(super) const INTERNAL_PREFIXES: &[&str] = &[
     ".assign-",
     ".evaluate-",
     ".verify-",
@@ -1,45 +1,13 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDate, Utc};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use workgraph::archive;
 use workgraph::graph::{Node, Status, Task};
 use workgraph::parser::load_graph;
 
 use super::graph_path;
 
-fn archive_path(dir: &Path) -> std::path::PathBuf {
-    dir.join("archive.jsonl")
-}
-
-/// Load archived tasks from the archive file
-fn load_archive(archive_path: &Path) -> Result<Vec<Task>> {
-    if !archive_path.exists() {
-        return Ok(Vec::new());
-    }
-
-    let file = File::open(archive_path)
-        .with_context(|| format!("Failed to open archive file: {:?}", archive_path))?;
-    let reader = BufReader::new(file);
-    let mut tasks = Vec::new();
-
-    for (line_num, line) in reader.lines().enumerate() {
-        let line = line?;
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-        let node: Node = serde_json::from_str(trimmed).with_context(|| {
-            format!("Failed to parse archive line {}: {}", line_num + 1, trimmed)
-        })?;
-        if let Node::Task(task) = node {
-            tasks.push(task);
-        }
-    }
-
-    Ok(tasks)
-}
-
 /// Parse a date string (YYYY-MM-DD) to DateTime<Utc>
 fn parse_date(s: &str) -> Result<DateTime<Utc>> {
     let date = NaiveDate::parse_from_str(s, "%Y-%m-%d")
@@ -110,9 +78,91 @@ fn status_style(status: &Status, is_archived: bool) -> &'static str {
     }
 }
 
+/// Escape a string for use inside a quoted DOT label.
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// GraphML document for the given tasks (Gephi, yEd and networkx all read
+/// it). `label` carries the title so tools show it without configuration;
+/// `after` edges point from the dependency to the dependent task.
+fn generate_graphml(all_tasks: &[(Task, bool)]) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    for (id, domain, kind) in [
+        ("label", "node", "string"),
+        ("status", "node", "string"),
+        ("assigned", "node", "string"),
+        ("tags", "node", "string"),
+        ("priority", "node", "int"),
+        ("archived", "node", "boolean"),
+        ("kind", "edge", "string"),
+    ] {
+        out.push_str(&format!(
+            "  <key id=\"{id}\" for=\"{domain}\" attr.name=\"{id}\" attr.type=\"{kind}\"/>\n"
+        ));
+    }
+    out.push_str("  <graph id=\"wg\" edgedefault=\"directed\">\n");
+
+    for (task, is_archived) in all_tasks {
+        out.push_str(&format!("    <node id=\"{}\">\n", xml_escape(&task.id)));
+        let mut data = vec![
+            ("label", task.title.clone()),
+            ("status", task.status.to_string()),
+        ];
+        if let Some(ref assigned) = task.assigned {
+            data.push(("assigned", assigned.clone()));
+        }
+        if !task.tags.is_empty() {
+            data.push(("tags", task.tags.join(",")));
+        }
+        data.push(("priority", task.priority.to_string()));
+        data.push(("archived", is_archived.to_string()));
+        for (key, value) in data {
+            out.push_str(&format!(
+                "      <data key=\"{}\">{}</data>\n",
+                key,
+                xml_escape(&value)
+            ));
+        }
+        out.push_str("    </node>\n");
+    }
+
+    let task_ids: HashSet<&str> = all_tasks.iter().map(|(t, _)| t.id.as_str()).collect();
+    for (task, _) in all_tasks {
+        for (kind, targets) in [("after", &task.after), ("requires", &task.requires)] {
+            for other in targets.iter().filter(|o| task_ids.contains(o.as_str())) {
+                let (source, target) = if kind == "after" {
+                    (other.as_str(), task.id.as_str())
+                } else {
+                    (task.id.as_str(), other.as_str())
+                };
+                out.push_str(&format!(
+                    "    <edge source=\"{}\" target=\"{}\"><data key=\"kind\">{}</data></edge>\n",
+                    xml_escape(source),
+                    xml_escape(target),
+                    kind
+                ));
+            }
+        }
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
 pub fn run(
     dir: &Path,
     include_archive: bool,
+    format: &str,
     since: Option<&str>,
     until: Option<&str>,
 ) -> Result<()> {
@@ -121,6 +171,12 @@ pub fn run(
     if !path.exists() {
         anyhow::bail!("WG not initialized. Run 'wg init' first.");
     }
+    if !matches!(format, "dot" | "graphml" | "mermaid") {
+        anyhow::bail!(
+            "Unknown format '{}'. Use 'dot', 'graphml' or 'mermaid'.",
+            format
+        );
+    }
 
     // Parse date filters
     let since_dt = since.map(parse_date).transpose()?;
@@ -137,8 +193,7 @@ pub fn run(
 
     // Load archived tasks if requested
     if include_archive {
-        let arch_path = archive_path(dir);
-        let archived = load_archive(&arch_path)?;
+        let archived = archive::load_all(dir)?;
         for task in archived {
             if in_date_range(&task, since_dt.as_ref(), until_dt.as_ref()) {
                 all_tasks.push((task, true));
@@ -146,6 +201,34 @@ pub fn run(
         }
     }
 
+    if format == "mermaid" {
+        // Archived tasks join the live graph so their edges and cycles resolve.
+        let mut export_graph = graph.clone();
+        for (task, is_archived) in &all_tasks {
+            if *is_archived {
+                export_graph.add_node(Node::Task(task.clone()));
+            }
+        }
+        let tasks: Vec<&Task> = all_tasks.iter().map(|(t, _)| t).collect();
+        let task_ids: HashSet<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        println!(
+            "{}",
+            super::viz::generate_mermaid(
+                &export_graph,
+                &tasks,
+                &task_ids,
+                &HashSet::new(),
+                &HashMap::new(),
+            )
+        );
+        return Ok(());
+    }
+
+    if format == "graphml" {
+        print!("{}", generate_graphml(&all_tasks));
+        return Ok(());
+    }
+
     // Print DOT format for visualization
     println!("digraph wg {{");
     println!("  rankdir=LR;");
@@ -183,14 +266,16 @@ pub fn run(
 
         // Add assigned actor to label if claimed
         let label = if let Some(ref assigned) = task.assigned {
-            format!("{}\\n{}\\n[{}]", task.id, task.title, assigned)
+            format!("{}\\n{}\\n[{}]", task.id, dot_escape(&task.title), assigned)
         } else {
-            format!("{}\\n{}", task.id, task.title)
+            format!("{}\\n{}", task.id, dot_escape(&task.title))
         };
 
+        // `status` is not a Graphviz attribute; it is ignored by renderers
+        // and read back by `wg graph import`.
         println!(
-            "  \"{}\" [label=\"{}\", style=\"{}\", fillcolor={}];",
-            task.id, label, style, color
+            "  \"{}\" [label=\"{}\", style=\"{}\", fillcolor={}, status=\"{}\"];",
+            task.id, label, style, color, task.status
         );
     }
 
@@ -206,7 +291,7 @@ pub fn run(
     println!();
 
     // Print edges (only for tasks in our filtered set)
-    let task_ids: std::collections::HashSet<_> = all_tasks.iter().map(|(t, _)| &t.id).collect();
+    let task_ids: HashSet<_> = all_tasks.iter().map(|(t, _)| &t.id).collect();
 
     for (task, _) in &all_tasks {
         for blocked in &task.after {
@@ -371,7 +456,7 @@ mod tests {
     fn test_run_not_initialized() {
         let tmp = TempDir::new().unwrap();
         let dir = tmp.path().join(".wg");
-        let result = run(&dir, false, None, None);
+        let result = run(&dir, false, "dot", None, None);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not initialized"));
     }
@@ -387,7 +472,7 @@ mod tests {
         graph.add_node(Node::Task(make_task("t2", "Second task")));
         setup_graph(&dir, &graph);
 
-        let result = run(&dir, false, None, None);
+        let result = run(&dir, false, "dot", None, None);
         assert!(result.is_ok());
     }
 
@@ -404,7 +489,7 @@ mod tests {
         graph.add_node(Node::Task(t2));
         setup_graph(&dir, &graph);
 
-        let result = run(&dir, false, None, None);
+        let result = run(&dir, false, "dot", None, None);
         assert!(result.is_ok());
     }
 
@@ -424,7 +509,7 @@ mod tests {
         }));
         setup_graph(&dir, &graph);
 
-        let result = run(&dir, false, None, None);
+        let result = run(&dir, false, "dot", None, None);
         assert!(result.is_ok());
     }
 
@@ -445,7 +530,7 @@ mod tests {
         setup_graph(&dir, &graph);
 
         // Both should pass with --since filtering
-        let result = run(&dir, false, Some("2024-06-01"), None);
+        let result = run(&dir, false, "dot", Some("2024-06-01"), None);
         assert!(result.is_ok());
     }
 
@@ -459,7 +544,7 @@ mod tests {
         setup_graph(&dir, &graph);
 
         // include_archive=true but no archive.jsonl exists — should still succeed
-        let result = run(&dir, true, None, None);
+        let result = run(&dir, true, "dot", None, None);
         assert!(result.is_ok());
     }
 
@@ -480,10 +565,10 @@ mod tests {
             "status": "done",
             "completed_at": "2024-03-01T00:00:00+00:00"
         });
-        let arch = archive_path(&dir);
+        let arch = archive::legacy_path(&dir);
         std::fs::write(&arch, format!("{}\n", archived_task)).unwrap();
 
-        let result = run(&dir, true, None, None);
+        let result = run(&dir, true, "dot", None, None);
         assert!(result.is_ok());
     }
 
@@ -496,17 +581,68 @@ mod tests {
         graph.add_node(Node::Task(make_task("t1", "Task")));
         setup_graph(&dir, &graph);
 
-        let result = run(&dir, false, Some("bad-date"), None);
+        let result = run(&dir, false, "dot", Some("bad-date"), None);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_run_mermaid_format() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("t1", "Task")));
+        setup_graph(&dir, &graph);
+
+        assert!(run(&dir, false, "mermaid", None, None).is_ok());
+        let err = run(&dir, false, "svg", None, None).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Use 'dot', 'graphml' or 'mermaid'")
+        );
+    }
+
+    #[test]
+    fn test_generate_graphml() {
+        let mut t1 = make_task("t1", "Parse <input> & \"quotes\"");
+        t1.tags = vec!["a".to_string(), "b".to_string()];
+        let mut t2 = make_task("t2", "Second");
+        t2.status = Status::Done;
+        t2.after = vec!["t1".to_string(), "missing".to_string()];
+        let out = generate_graphml(&[(t1, false), (t2, true)]);
+
+        assert!(out.contains("<graph id=\"wg\" edgedefault=\"directed\">"));
+        assert!(
+            out.contains("<data key=\"label\">Parse &lt;input&gt; &amp; &quot;quotes&quot;</data>")
+        );
+        assert!(out.contains("<data key=\"tags\">a,b</data>"));
+        assert!(out.contains("<data key=\"status\">done</data>"));
+        assert!(out.contains("<data key=\"archived\">true</data>"));
+        assert!(
+            out.contains(
+                "<edge source=\"t1\" target=\"t2\"><data key=\"kind\">after</data></edge>"
+            )
+        );
+        assert!(!out.contains("missing"));
+    }
+
+    #[test]
+    fn test_run_graphml_format() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("t1", "Task")));
+        setup_graph(&dir, &graph);
+        assert!(run(&dir, false, "graphml", None, None).is_ok());
+    }
+
     // --- load_archive tests ---
 
     #[test]
     fn test_load_archive_missing_file() {
         let tmp = TempDir::new().unwrap();
         let path = tmp.path().join("nonexistent.jsonl");
-        let tasks = load_archive(&path).unwrap();
+        let tasks = archive::load_file(&path).unwrap();
         assert!(tasks.is_empty());
     }
 
@@ -522,7 +658,7 @@ mod tests {
         });
         std::fs::write(&path, format!("{}\n", task_json)).unwrap();
 
-        let tasks = load_archive(&path).unwrap();
+        let tasks = archive::load_file(&path).unwrap();
         assert_eq!(tasks.len(), 1);
         assert_eq!(tasks[0].id, "archived-1");
     }
@@ -540,7 +676,7 @@ mod tests {
         let content = format!("# comment\n\n{}\n\n", task_json);
         std::fs::write(&path, content).unwrap();
 
-        let tasks = load_archive(&path).unwrap();
+        let tasks = archive::load_file(&path).unwrap();
         assert_eq!(tasks.len(), 1);
     }
 }
@@ -0,0 +1,727 @@
+//! `wg graph import` — merge tasks and dependencies from a DOT or GraphML
+//! file, typically one exported with `wg graph export` and edited in Gephi
+//! or yEd.
+//!
+//! Import only adds: unknown nodes become new tasks and unknown edges become
+//! `after` dependencies. Existing tasks keep their title and status.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::OnceLock;
+use workgraph::graph::{Node, Status, Task};
+use workgraph::parser::{load_graph, modify_graph};
+
+use super::graph_path;
+
+/// A node read from an interchange file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportedTask {
+    pub id: String,
+    pub title: Option<String>,
+    pub status: Option<Status>,
+    pub after: Vec<String>,
+}
+
+fn parse_status(s: &str) -> Option<Status> {
+    serde_json::from_value(serde_json::Value::String(s.trim().to_lowercase())).ok()
+}
+
+/// Collect nodes and `from -> to` edges into tasks, in first-seen order.
+/// Edges touching a skipped node (legend, resource) are dropped.
+fn assemble(
+    nodes: Vec<ImportedTask>,
+    edges: Vec<(String, String)>,
+    skipped: &HashSet<String>,
+) -> Vec<ImportedTask> {
+    let mut order: Vec<String> = Vec::new();
+    let mut by_id: HashMap<String, ImportedTask> = HashMap::new();
+    let mut touch = |id: &str, order: &mut Vec<String>| {
+        by_id.entry(id.to_string()).or_insert_with(|| {
+            order.push(id.to_string());
+            ImportedTask {
+                id: id.to_string(),
+                ..ImportedTask::default()
+            }
+        });
+    };
+    for node in &nodes {
+        touch(&node.id, &mut order);
+    }
+    for (from, to) in &edges {
+        if !skipped.contains(from) && !skipped.contains(to) {
+            touch(from, &mut order);
+            touch(to, &mut order);
+        }
+    }
+    for node in nodes {
+        let entry = by_id.get_mut(&node.id).expect("touched above");
+        entry.title = node.title.or(entry.title.take());
+        entry.status = node.status.or(entry.status);
+    }
+    for (from, to) in edges {
+        if let Some(task) = by_id.get_mut(&to)
+            && !skipped.contains(&from)
+            && !task.after.contains(&from)
+        {
+            task.after.push(from);
+        }
+    }
+    order
+        .into_iter()
+        .filter_map(|id| by_id.remove(&id))
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Id(String),
+    Arrow,
+    Open,
+    Close,
+    AttrOpen,
+    AttrClose,
+    Eq,
+    Sep,
+}
+
+fn tokenize_dot(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+    let mut line_start = true;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                line_start = true;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            // `#` lines are C preprocessor output and ignored by Graphviz
+            '#' if line_start => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '-' if matches!(chars.peek(), Some(&'>') | Some(&'-')) => {
+                chars.next();
+                tokens.push(Token::Arrow);
+            }
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '[' => tokens.push(Token::AttrOpen),
+            ']' => tokens.push(Token::AttrClose),
+            '=' => tokens.push(Token::Eq),
+            ';' | ',' => tokens.push(Token::Sep),
+            '"' => {
+                let mut s = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            s.push('"');
+                        }
+                        // Keep `\\` paired so a trailing backslash can't escape the quote
+                        '\\' if chars.peek() == Some(&'\\') => {
+                            chars.next();
+                            s.push_str("\\\\");
+                        }
+                        '"' => break,
+                        c => s.push(c),
+                    }
+                }
+                tokens.push(Token::Id(s));
+            }
+            '<' => {
+                // HTML-like label: keep the raw markup as the id text
+                let mut depth = 1;
+                let mut s = String::new();
+                for c in chars.by_ref() {
+                    match c {
+                        '<' => depth += 1,
+                        '>' => depth -= 1,
+                        _ => {}
+                    }
+                    if depth == 0 {
+                        break;
+                    }
+                    s.push(c);
+                }
+                tokens.push(Token::Id(s));
+            }
+            c => {
+                let mut s = c.to_string();
+                while let Some(&n) = chars.peek() {
+                    if n.is_alphanumeric() || matches!(n, '_' | '.') || n as u32 > 127 {
+                        s.push(n);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Id(s));
+            }
+        }
+        line_start = false;
+    }
+    tokens
+}
+
+/// Title from a DOT label. `wg graph export` labels are `id\ntitle[\n[agent]]`.
+fn title_from_label(id: &str, label: &str) -> String {
+    let parts: Vec<&str> = label.split("\\n").collect();
+    let title = if parts.len() > 1 && parts[0] == id {
+        parts[1]
+    } else {
+        label
+    };
+    title.replace("\\\\", "\\")
+}
+
+/// Parse a DOT digraph into tasks. Nodes inside a `cluster_legend`
+/// subgraph and diamond-shaped (resource) nodes are skipped, as are
+/// `assigned` and `requires` edges.
+pub fn parse_dot(content: &str) -> Result<Vec<ImportedTask>> {
+    let tokens = tokenize_dot(content);
+    let header = tokens
+        .iter()
+        .filter_map(|t| match t {
+            Token::Id(s) => Some(s.to_lowercase()),
+            _ => None,
+        })
+        .find(|s| s != "strict");
+    if !matches!(header.as_deref(), Some("digraph") | Some("graph")) {
+        anyhow::bail!("Not a DOT graph (expected 'digraph {{ ... }}')");
+    }
+
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    let mut skipped = HashSet::new();
+    // Subgraph names on the brace stack; the root graph is "".
+    let mut scopes: Vec<String> = Vec::new();
+    let mut pending_name: Option<String> = None;
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            Token::Open => {
+                scopes.push(pending_name.take().unwrap_or_default());
+                i += 1;
+            }
+            Token::Close => {
+                scopes.pop();
+                i += 1;
+            }
+            Token::Sep => i += 1,
+            Token::Id(kw) if kw.eq_ignore_ascii_case("strict") => i += 1,
+            Token::Id(kw)
+                if matches!(kw.to_lowercase().as_str(), "digraph" | "graph" | "subgraph")
+                    && !matches!(tokens.get(i + 1), Some(Token::AttrOpen) | Some(Token::Eq)) =>
+            {
+                if let Some(Token::Id(name)) = tokens.get(i + 1) {
+                    pending_name = Some(name.clone());
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+            }
+            Token::Id(first) => {
+                // Statement: id (-> id)* [attrs]?  |  id = id  |  node/edge/graph [attrs]
+                if tokens.get(i + 1) == Some(&Token::Eq) {
+                    i += 3;
+                    continue;
+                }
+                let mut chain = vec![first.clone()];
+                i += 1;
+                while tokens.get(i) == Some(&Token::Arrow) {
+                    match tokens.get(i + 1) {
+                        Some(Token::Id(next)) => chain.push(next.clone()),
+                        _ => break,
+                    }
+                    i += 2;
+                }
+                let mut attrs: HashMap<String, String> = HashMap::new();
+                if tokens.get(i) == Some(&Token::AttrOpen) {
+                    i += 1;
+                    while i < tokens.len() && tokens[i] != Token::AttrClose {
+                        if let (Token::Id(k), Some(Token::Eq), Some(Token::Id(v))) =
+                            (&tokens[i], tokens.get(i + 1), tokens.get(i + 2))
+                        {
+                            attrs.insert(k.to_lowercase(), v.clone());
+                            i += 3;
+                        } else {
+                            i += 1;
+                        }
+                    }
+                    i += 1;
+                }
+
+                let keyword = chain.len() == 1
+                    && matches!(first.to_lowercase().as_str(), "node" | "edge" | "graph");
+                let in_legend = scopes.iter().any(|s| s.starts_with("cluster_legend"));
+                if keyword {
+                    continue;
+                }
+                if chain.len() == 1 {
+                    let id = chain.remove(0);
+                    if in_legend || attrs.get("shape").is_some_and(|s| s == "diamond") {
+                        skipped.insert(id);
+                        continue;
+                    }
+                    nodes.push(ImportedTask {
+                        title: attrs
+                            .get("title")
+                            .cloned()
+                            .or_else(|| attrs.get("label").map(|l| title_from_label(&id, l))),
+                        status: attrs
+                            .get("status")
+                            .map(String::as_str)
+                            .and_then(parse_status),
+                        id,
+                        after: Vec::new(),
+                    });
+                } else if !in_legend
+                    && !matches!(
+                        attrs.get("label").map(String::as_str),
+                        Some("assigned") | Some("requires")
+                    )
+                {
+                    for pair in chain.windows(2) {
+                        edges.push((pair[0].clone(), pair[1].clone()));
+                    }
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    Ok(assemble(nodes, edges, &skipped))
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&amp;", "&")
+}
+
+fn xml_attrs(s: &str) -> HashMap<String, String> {
+    static ATTR: OnceLock<Regex> = OnceLock::new();
+    let re = ATTR.get_or_init(|| {
+        Regex::new(r#"([\w.:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("attr regex")
+    });
+    re.captures_iter(s)
+        .map(|c| {
+            let v = c.get(2).or(c.get(3)).map(|m| m.as_str()).unwrap_or("");
+            (c[1].to_string(), xml_unescape(v))
+        })
+        .collect()
+}
+
+/// Parse a GraphML document into tasks. The title is read from a `title`
+/// or `label` data key, falling back to a yEd `<y:NodeLabel>`; edges with
+/// `kind = requires` are skipped.
+pub fn parse_graphml(content: &str) -> Result<Vec<ImportedTask>> {
+    static KEY: OnceLock<Regex> = OnceLock::new();
+    static NODE: OnceLock<Regex> = OnceLock::new();
+    static EDGE: OnceLock<Regex> = OnceLock::new();
+    static DATA: OnceLock<Regex> = OnceLock::new();
+    static YLABEL: OnceLock<Regex> = OnceLock::new();
+    let key_re = KEY.get_or_init(|| Regex::new(r"<key\b([^>]*)>").expect("key regex"));
+    let node_re = NODE.get_or_init(|| {
+        Regex::new(r"(?s)<node\b([^>]*?)(?:/>|>(.*?)</node>)").expect("node regex")
+    });
+    let edge_re = EDGE.get_or_init(|| {
+        Regex::new(r"(?s)<edge\b([^>]*?)(?:/>|>(.*?)</edge>)").expect("edge regex")
+    });
+    let data_re =
+        DATA.get_or_init(|| Regex::new(r"(?s)<data\b([^>]*)>(.*?)</data>").expect("data regex"));
+    let ylabel_re =
+        YLABEL.get_or_init(|| Regex::new(r"(?s)<y:NodeLabel\b[^>]*>([^<]*)").expect("label regex"));
+
+    if !content.contains("<graphml") {
+        anyhow::bail!("Not a GraphML document (missing <graphml> root)");
+    }
+
+    // Data key id -> attribute name
+    let keys: HashMap<String, String> = key_re
+        .captures_iter(content)
+        .filter_map(|c| {
+            let attrs = xml_attrs(&c[1]);
+            let id = attrs.get("id")?.clone();
+            let name = attrs
+                .get("attr.name")
+                .cloned()
+                .unwrap_or_else(|| id.clone());
+            Some((id, name.to_lowercase()))
+        })
+        .collect();
+    let data = |body: &str| -> HashMap<String, String> {
+        data_re
+            .captures_iter(body)
+            .filter_map(|c| {
+                let key = xml_attrs(&c[1]).get("key")?.clone();
+                let name = keys.get(&key).cloned().unwrap_or(key);
+                Some((name, xml_unescape(c[2].trim())))
+            })
+            .collect()
+    };
+
+    let mut nodes = Vec::new();
+    for c in node_re.captures_iter(content) {
+        let Some(id) = xml_attrs(&c[1]).get("id").cloned() else {
+            continue;
+        };
+        let body = c.get(2).map(|m| m.as_str()).unwrap_or("");
+        let fields = data(body);
+        let title = fields
+            .get("title")
+            .or_else(|| fields.get("label"))
+            .cloned()
+            .or_else(|| {
+                ylabel_re
+                    .captures(body)
+                    .map(|l| xml_unescape(l[1].trim()))
+                    .filter(|l| !l.is_empty())
+            });
+        nodes.push(ImportedTask {
+            status: fields
+                .get("status")
+                .map(String::as_str)
+                .and_then(parse_status),
+            title,
+            id,
+            after: Vec::new(),
+        });
+    }
+
+    // GraphML edges must reference declared nodes
+    let declared: HashSet<String> = nodes.iter().map(|n| n.id.clone()).collect();
+    let mut edges = Vec::new();
+    for c in edge_re.captures_iter(content) {
+        let attrs = xml_attrs(&c[1]);
+        let (Some(source), Some(target)) = (attrs.get("source"), attrs.get("target")) else {
+            continue;
+        };
+        if !declared.contains(source) || !declared.contains(target) {
+            continue;
+        }
+        let body = c.get(2).map(|m| m.as_str()).unwrap_or("");
+        if data(body).get("kind").is_some_and(|k| k != "after") {
+            continue;
+        }
+        edges.push((source.clone(), target.clone()));
+    }
+
+    Ok(assemble(nodes, edges, &HashSet::new()))
+}
+
+/// Pick the format from `--format`, the file extension, or the content.
+fn detect_format(file: &Path, content: &str, format: Option<&str>) -> Result<&'static str> {
+    if let Some(format) = format {
+        return match format.to_lowercase().as_str() {
+            "dot" | "gv" => Ok("dot"),
+            "graphml" => Ok("graphml"),
+            other => anyhow::bail!("Unknown format '{}'. Use 'dot' or 'graphml'.", other),
+        };
+    }
+    let ext = file
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    Ok(match ext.as_deref() {
+        Some("dot") | Some("gv") => "dot",
+        Some("graphml") => "graphml",
+        _ if content.trim_start().starts_with('<') => "graphml",
+        _ => "dot",
+    })
+}
+
+/// Summary of an import.
+#[derive(Debug, Default, serde::Serialize)]
+struct ImportSummary {
+    created: Vec<String>,
+    dependencies_added: usize,
+    unchanged: usize,
+}
+
+fn plan(
+    graph: &workgraph::graph::WorkGraph,
+    imported: &[ImportedTask],
+) -> (Vec<Task>, Vec<(String, String)>, ImportSummary) {
+    let now = Utc::now().to_rfc3339();
+    let mut new_tasks = Vec::new();
+    let mut new_edges = Vec::new();
+    let mut summary = ImportSummary::default();
+
+    for item in imported {
+        let after = item.after.clone();
+        match graph.get_task(&item.id) {
+            Some(existing) => {
+                let added: Vec<String> = after
+                    .into_iter()
+                    .filter(|d| !existing.after.contains(d))
+                    .collect();
+                if added.is_empty() {
+                    summary.unchanged += 1;
+                }
+                summary.dependencies_added += added.len();
+                new_edges.extend(added.into_iter().map(|d| (item.id.clone(), d)));
+            }
+            None => {
+                // Statuses that imply a live agent or pending check restart as open.
+                let status = item
+                    .status
+                    .filter(|s| s.is_terminal())
+                    .unwrap_or(Status::Open);
+                summary.dependencies_added += after.len();
+                summary.created.push(item.id.clone());
+                new_tasks.push(Task {
+                    id: item.id.clone(),
+                    title: item.title.clone().unwrap_or_else(|| item.id.clone()),
+                    status,
+                    after,
+                    created_at: Some(now.clone()),
+                    ..Task::default()
+                });
+            }
+        }
+    }
+    (new_tasks, new_edges, summary)
+}
+
+pub fn run(dir: &Path, file: &Path, format: Option<&str>, dry_run: bool, json: bool) -> Result<()> {
+    let path = graph_path(dir);
+    if !path.exists() {
+        anyhow::bail!("WG not initialized. Run 'wg init' first.");
+    }
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read '{}'", file.display()))?;
+    let imported = match detect_format(file, &content, format)? {
+        "graphml" => parse_graphml(&content),
+        _ => parse_dot(&content),
+    }
+    .with_context(|| format!("Failed to parse '{}'", file.display()))?;
+
+    let graph = load_graph(&path).context("Failed to load graph")?;
+    let (new_tasks, new_edges, summary) = plan(&graph, &imported);
+
+    if !dry_run && (!new_tasks.is_empty() || !new_edges.is_empty()) {
+        modify_graph(&path, |g| {
+            for task in &new_tasks {
+                if g.get_node(&task.id).is_none() {
+                    g.add_node(Node::Task(task.clone()));
+                }
+            }
+            for (id, dep) in &new_edges {
+                if let Some(t) = g.get_task_mut(id)
+                    && !t.after.contains(dep)
+                {
+                    t.after.push(dep.clone());
+                }
+            }
+            true
+        })
+        .context("Failed to save graph")?;
+        super::notify_graph_changed(dir);
+    }
+
+    if json {
+        let mut out = serde_json::to_value(&summary)?;
+        out["dry_run"] = serde_json::Value::Bool(dry_run);
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    let verb = if dry_run { "Would import" } else { "Imported" };
+    println!(
+        "{} {} new task(s) and {} new dependency edge(s) from {} ({} existing task(s) unchanged)",
+        verb,
+        summary.created.len(),
+        summary.dependencies_added,
+        file.display(),
+        summary.unchanged
+    );
+    for id in &summary.created {
+        println!("  + {}", id);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::graph::WorkGraph;
+    use workgraph::parser::save_graph;
+
+    fn make_task(id: &str, title: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            title: title.to_string(),
+            ..Task::default()
+        }
+    }
+
+    fn setup(graph: &WorkGraph) -> (TempDir, std::path::PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        std::fs::create_dir_all(&dir).unwrap();
+        save_graph(graph, graph_path(&dir)).unwrap();
+        (tmp, dir)
+    }
+
+    #[test]
+    fn test_parse_dot_wg_export() {
+        let dot = r#"digraph wg {
+  rankdir=LR;
+  node [shape=box];
+  // Legend
+  subgraph cluster_legend {
+    label="Legend";
+    legend_open [label="Open", style=filled, fillcolor=white];
+    legend_open -> legend_done [style=invis];
+  }
+  "build" [label="build\nBuild \"it\"\n[agent-1]", style="filled", fillcolor=coral, status="in-progress"];
+  "ship" [label="ship\nShip", style="filled", fillcolor=white, status="open"];
+  "gpu" [label="GPU", shape=diamond, style=filled, fillcolor=lightyellow];
+  "build" -> "ship" [label="blocks"];
+  "ship" -> "agent-1" [style=dashed, label="assigned"];
+  "ship" -> "gpu" [style=dotted, label="requires"];
+}"#;
+        let tasks = parse_dot(dot).unwrap();
+        assert_eq!(
+            tasks,
+            vec![
+                ImportedTask {
+                    id: "build".to_string(),
+                    title: Some("Build \"it\"".to_string()),
+                    status: Some(Status::InProgress),
+                    after: vec![],
+                },
+                ImportedTask {
+                    id: "ship".to_string(),
+                    title: Some("Ship".to_string()),
+                    status: Some(Status::Open),
+                    after: vec!["build".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dot_generic_chain() {
+        let tasks = parse_dot("digraph { a -> b -> c; d [label=\"Dee\"] /* note */ }").unwrap();
+        let ids: Vec<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["d", "a", "b", "c"]);
+        assert_eq!(tasks[0].title.as_deref(), Some("Dee"));
+        assert_eq!(tasks[3].after, vec!["b"]);
+        assert!(parse_dot("not a graph").is_err());
+    }
+
+    #[test]
+    fn test_parse_graphml_with_yed_labels() {
+        let xml = r#"<?xml version="1.0"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:y="http://www.yworks.com/xml/graphml">
+  <key id="d0" for="node" attr.name="status" attr.type="string"/>
+  <key id="d1" for="node" yfiles.type="nodegraphics"/>
+  <key id="d2" for="edge" attr.name="kind" attr.type="string"/>
+  <graph id="G" edgedefault="directed">
+    <node id="a"><data key="d0">done</data><data key="d1"><y:ShapeNode><y:NodeLabel x="1">Design &amp; plan</y:NodeLabel></y:ShapeNode></data></node>
+    <node id="b"/>
+    <edge source="a" target="b"><data key="d2">after</data></edge>
+    <edge id="e2" source="b" target="a"><data key="d2">requires</data></edge>
+  </graph>
+</graphml>"#;
+        let tasks = parse_graphml(xml).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].title.as_deref(), Some("Design & plan"));
+        assert_eq!(tasks[0].status, Some(Status::Done));
+        assert!(tasks[0].after.is_empty());
+        assert_eq!(tasks[1].after, vec!["a"]);
+        assert!(parse_graphml("digraph {}").is_err());
+    }
+
+    #[test]
+    fn test_run_merges_into_existing_graph() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("a", "Existing A")));
+        let (tmp, dir) = setup(&graph);
+
+        let file = tmp.path().join("layout.graphml");
+        std::fs::write(
+            &file,
+            r#"<graphml><key id="label" for="node" attr.name="label"/><graph edgedefault="directed">
+<node id="a"><data key="label">Renamed</data></node>
+<node id="b"><data key="label">New B</data></node>
+<edge source="a" target="b"/><edge source="b" target="a"/><edge source="ghost" target="b"/>
+</graph></graphml>"#,
+        )
+        .unwrap();
+
+        run(&dir, &file, None, true, false).unwrap();
+        assert!(
+            load_graph(graph_path(&dir))
+                .unwrap()
+                .get_task("b")
+                .is_none()
+        );
+
+        run(&dir, &file, None, false, false).unwrap();
+        let graph = load_graph(graph_path(&dir)).unwrap();
+        let a = graph.get_task("a").unwrap();
+        assert_eq!(a.title, "Existing A");
+        assert_eq!(a.after, vec!["b"]);
+        let b = graph.get_task("b").unwrap();
+        assert_eq!(b.title, "New B");
+        assert_eq!(b.status, Status::Open);
+        assert_eq!(b.after, vec!["a"]);
+    }
+
+    #[test]
+    fn test_dot_round_trip_through_export() {
+        let mut graph = WorkGraph::new();
+        let mut done = make_task("t1", "First \"quoted\"");
+        done.status = Status::Done;
+        graph.add_node(Node::Task(done));
+        let mut t2 = make_task("t2", "Second");
+        t2.after = vec!["t1".to_string()];
+        graph.add_node(Node::Task(t2));
+        let (tmp, dir) = setup(&graph);
+
+        let file = tmp.path().join("graph.dot");
+        std::fs::write(
+            &file,
+            "digraph wg {\n  \"t1\" [label=\"t1\\nFirst \\\"quoted\\\"\", status=\"done\"];\n  \"t2\" [label=\"t2\\nSecond\", status=\"open\"];\n  \"t1\" -> \"t2\" [label=\"blocks\"];\n}\n",
+        )
+        .unwrap();
+        let imported = parse_dot(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(imported[0].title.as_deref(), Some("First \"quoted\""));
+
+        let fresh = WorkGraph::new();
+        let (new_tasks, _, summary) = plan(&fresh, &imported);
+        assert_eq!(summary.created, vec!["t1", "t2"]);
+        assert_eq!(new_tasks[0].status, Status::Done);
+        assert_eq!(new_tasks[1].after, vec!["t1"]);
+
+        run(&dir, &file, Some("dot"), false, false).unwrap();
+        let after = load_graph(graph_path(&dir)).unwrap();
+        assert_eq!(after.tasks().count(), 2);
+    }
+}
@@ -0,0 +1,140 @@
+//! `wg graphs` — list, create, and remove the named graphs of a workspace.
+
+use anyhow::Result;
+use std::path::Path;
+
+use workgraph::config::Config;
+use workgraph::graph::Status;
+use workgraph::graphs;
+use workgraph::parser::load_graph;
+
+/// List every graph in the workspace with task counts.
+pub fn run_list(dir: &Path, json: bool) -> Result<()> {
+    let current = graphs::current_name(dir);
+    let mut rows = Vec::new();
+    for (name, path) in graphs::list(dir)? {
+        let graph = load_graph(path.join("graph.jsonl")).ok();
+        let total = graph.as_ref().map(|g| g.tasks().count()).unwrap_or(0);
+        let open = graph
+            .as_ref()
+            .map(|g| g.tasks().filter(|t| !t.status.is_terminal()).count())
+            .unwrap_or(0);
+        rows.push((name, path, total, open));
+    }
+
+    if json {
+        let out: Vec<_> = rows
+            .iter()
+            .map(|(name, path, total, open)| {
+                serde_json::json!({
+                    "name": name,
+                    "path": path,
+                    "current": *name == current,
+                    "tasks": total,
+                    "open": open,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    for (name, _, total, open) in &rows {
+        let marker = if *name == current { "*" } else { " " };
+        println!("{} {:<20} {:>4} tasks ({} open)", marker, name, total, open);
+    }
+    Ok(())
+}
+
+/// Create an empty named graph.
+pub fn run_create(dir: &Path, name: &str) -> Result<()> {
+    let path = graphs::create(dir, name)?;
+    record(dir, "graph_create", name);
+    println!("Created graph '{}' at {}", name, path.display());
+    println!("  Add tasks with: wg --graph {} add \"...\"", name);
+    println!(
+        "  Depend on it from another graph with: --after {}:<task-id>",
+        name
+    );
+    Ok(())
+}
+
+/// Remove a named graph. Refuses while tasks in other graphs still
+/// depend on it, unless `force`.
+pub fn run_remove(dir: &Path, name: &str, force: bool) -> Result<()> {
+    let prefix = format!("{}:", name);
+    let mut dependents = Vec::new();
+    for (other, path) in graphs::list(dir)? {
+        if other == name {
+            continue;
+        }
+        let Ok(graph) = load_graph(path.join("graph.jsonl")) else {
+            continue;
+        };
+        for task in graph.tasks() {
+            if task.status == Status::Abandoned {
+                continue;
+            }
+            for dep in task.after.iter().filter(|a| a.starts_with(&prefix)) {
+                dependents.push(format!("{}:{} (after {})", other, task.id, dep));
+            }
+        }
+    }
+    if !dependents.is_empty() && !force {
+        anyhow::bail!(
+            "Tasks in other graphs depend on '{}':\n  {}\nUse --force to remove it anyway.",
+            name,
+            dependents.join("\n  ")
+        );
+    }
+
+    let path = graphs::remove(dir, name)?;
+    record(dir, "graph_remove", name);
+    println!("Removed graph '{}' ({})", name, path.display());
+    Ok(())
+}
+
+/// Graph lifecycle events are recorded in the root graph's provenance log.
+fn record(dir: &Path, op: &str, name: &str) {
+    let Ok(root) = graphs::graph_dir(dir, graphs::MAIN_GRAPH) else {
+        return;
+    };
+    let config = Config::load_or_default(&root);
+    let _ = workgraph::provenance::record(
+        &root,
+        op,
+        None,
+        None,
+        serde_json::json!({ "graph": name }),
+        config.log.rotation_threshold,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::test_helpers::{make_task, setup_workgraph};
+
+    #[test]
+    fn test_remove_refuses_while_depended_on() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join(".wg");
+        let mut t = make_task("ship", "Ship it");
+        t.after = vec!["refactor:cleanup".to_string()];
+        setup_workgraph(&root, vec![t]);
+
+        run_create(&root, "refactor").unwrap();
+        run_list(&root, true).unwrap();
+        assert!(run_remove(&root, "refactor", false).is_err());
+        run_remove(&root, "refactor", true).unwrap();
+        assert!(graphs::resolve(&root, "refactor").is_none());
+
+        let ops: Vec<String> = workgraph::provenance::read_all_operations(&root)
+            .unwrap()
+            .into_iter()
+            .map(|o| o.op)
+            .collect();
+        assert_eq!(ops, vec!["graph_create", "graph_remove"]);
+    }
+}
@@ -0,0 +1,158 @@
+//! `wg inbox` and `wg triage inbox` — review externally submitted requests.
+
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::path::Path;
+
+use workgraph::inbox::{self, Submission};
+
+/// List untriaged drafts, oldest first.
+pub fn run_list(dir: &Path, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let drafts = inbox::drafts(&graph);
+
+    if json {
+        let out: Vec<_> = drafts
+            .iter()
+            .map(|t| {
+                serde_json::json!({
+                    "id": t.id,
+                    "title": t.title,
+                    "source": inbox::source_of(t),
+                    "created_at": t.created_at,
+                    "description": t.description,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    if drafts.is_empty() {
+        println!("Inbox is empty");
+        return Ok(());
+    }
+    println!("Inbox ({} awaiting triage):", drafts.len());
+    for t in &drafts {
+        let when = t
+            .created_at
+            .as_deref()
+            .and_then(|s| s.get(..10))
+            .unwrap_or("?");
+        println!(
+            "  {}  {}  [{}, {}]",
+            t.id,
+            t.title,
+            inbox::source_of(t).unwrap_or("unknown"),
+            when
+        );
+    }
+    println!();
+    println!("Triage with: wg triage inbox accept|reject|merge <id>");
+    Ok(())
+}
+
+/// File a request by hand or from a piped email.
+pub fn run_add(
+    dir: &Path,
+    title: Option<&str>,
+    body: Option<&str>,
+    source: &str,
+    from: Option<&str>,
+    email: bool,
+    json: bool,
+) -> Result<()> {
+    let submission = if email {
+        let mut raw = String::new();
+        std::io::stdin()
+            .read_to_string(&mut raw)
+            .context("Failed to read email from stdin")?;
+        let mut s = inbox::parse_email(&raw).context("Email has no subject or body")?;
+        if let Some(title) = title {
+            s.title = title.to_string();
+        }
+        if from.is_some() {
+            s.submitter = from.map(String::from);
+        }
+        s
+    } else {
+        let title = title.context("A title is required (or pipe an email with --email)")?;
+        Submission {
+            source: source.to_string(),
+            submitter: from.map(String::from),
+            title: title.to_string(),
+            body: body.map(String::from),
+        }
+    };
+
+    let id = inbox::submit(dir, &submission)?;
+    super::notify_graph_changed(dir);
+    if json {
+        println!("{}", serde_json::json!({ "id": id }));
+    } else {
+        println!("Filed '{}' in the inbox", id);
+    }
+    Ok(())
+}
+
+pub fn run_accept(dir: &Path, id: &str, split: &[String]) -> Result<()> {
+    let created = inbox::accept(dir, id, split)?;
+    super::notify_graph_changed(dir);
+    if created.is_empty() {
+        println!("Accepted '{}'", id);
+    } else {
+        println!("Accepted '{}', split into:", id);
+        for child in &created {
+            println!("  {}", child);
+        }
+    }
+    Ok(())
+}
+
+pub fn run_reject(dir: &Path, id: &str, reason: Option<&str>) -> Result<()> {
+    inbox::reject(dir, id, reason)?;
+    super::notify_graph_changed(dir);
+    println!("Rejected '{}'", id);
+    Ok(())
+}
+
+pub fn run_merge(dir: &Path, id: &str, into: &str) -> Result<()> {
+    inbox::merge(dir, id, into)?;
+    super::notify_graph_changed(dir);
+    println!("Merged '{}' into '{}'", id, into);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::graph_path;
+    use workgraph::graph::WorkGraph;
+    use workgraph::parser::{load_graph, save_graph};
+
+    #[test]
+    fn test_add_then_accept() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        save_graph(&WorkGraph::new(), graph_path(dir)).unwrap();
+
+        run_add(
+            dir,
+            Some("Rate limit API"),
+            None,
+            "cli",
+            Some("dana"),
+            false,
+            false,
+        )
+        .unwrap();
+        assert!(run_add(dir, None, None, "cli", None, false, false).is_err());
+        run_list(dir, false).unwrap();
+
+        run_accept(dir, "req-rate-limit-api", &[]).unwrap();
+        let graph = load_graph(graph_path(dir)).unwrap();
+        assert!(inbox::drafts(&graph).is_empty());
+        assert!(!graph.get_task("req-rate-limit-api").unwrap().paused);
+        assert!(run_reject(dir, "req-rate-limit-api", None).is_err());
+    }
+}
@@ -11,6 +11,10 @@ agents/
 # Service files
 service/
 
+# Derived indexes (rebuilt on demand)
+graph.index.json
+search.index.json
+
 # Never commit credentials (Matrix config should be in ~/.config/workgraph/)
 matrix.toml
 *.secret
@@ -0,0 +1,133 @@
+//! `wg learning` — show what the coordinator learning loop has concluded
+//! from function run summaries.
+
+use anyhow::Result;
+use std::path::Path;
+
+use workgraph::config::Config;
+use workgraph::learning::{self, Source};
+
+/// Print per-key stats and the active adjustments. With `refresh`,
+/// recompute the adjustments first (the daemon does this every tick).
+pub fn run(dir: &Path, refresh: bool, json: bool) -> Result<()> {
+    let config = Config::load_or_default(dir);
+    let (graph, _) = super::load_workgraph(dir)?;
+
+    let changes = if refresh {
+        let changes = learning::refresh(dir, &graph, &config.learning)?;
+        if !changes.is_empty() {
+            let _ = workgraph::provenance::record(
+                dir,
+                "learning_adjust",
+                None,
+                None,
+                serde_json::json!({ "changes": changes }),
+                config.log.rotation_threshold,
+            );
+        }
+        changes
+    } else {
+        Vec::new()
+    };
+    let stats = learning::collect_stats(dir, &graph, config.learning.window);
+    let state = learning::load_state(dir);
+
+    if json {
+        let out = serde_json::json!({
+            "enabled": config.learning.enabled,
+            "updated_at": state.updated_at,
+            "adjustments": state.adjustments,
+            "stats": stats
+                .iter()
+                .map(|(k, s)| (k.clone(), serde_json::json!({
+                    "runs": s.runs,
+                    "failures": s.failures,
+                    "mean_score": s.mean_score(),
+                })))
+                .collect::<serde_json::Map<_, _>>(),
+            "changes": changes,
+        });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    if !config.learning.enabled {
+        println!("Learning loop is disabled (set [learning] enabled = true to apply adjustments).");
+        println!();
+    }
+    for change in &changes {
+        println!("  {}", change);
+    }
+    if !changes.is_empty() {
+        println!();
+    }
+
+    println!(
+        "Adjustments{}:",
+        if state.updated_at.is_empty() {
+            String::new()
+        } else {
+            format!(" (updated {})", state.updated_at)
+        }
+    );
+    if state.adjustments.is_empty() {
+        println!("  (none)");
+    }
+    for adj in &state.adjustments {
+        let target = adj
+            .model
+            .as_deref()
+            .map(|m| format!("model {}", m))
+            .or_else(|| adj.tier.as_deref().map(|t| format!("tier {}", t)))
+            .unwrap_or_else(|| "default model".to_string());
+        let cap = adj
+            .max_concurrent
+            .map(|c| format!(", max {} concurrent", c))
+            .unwrap_or_default();
+        let source = match adj.source {
+            Source::Learned => "learned",
+            Source::Override => "override",
+        };
+        println!(
+            "  {:<32} {}{} [{}] — {}",
+            adj.key, target, cap, source, adj.reason
+        );
+    }
+
+    println!();
+    println!(
+        "Track record (last {} runs per function):",
+        config.learning.window
+    );
+    if stats.is_empty() {
+        println!("  (no function run summaries yet)");
+    }
+    for (key, s) in &stats {
+        let score = s
+            .mean_score()
+            .map(|m| format!("{:.2}", m))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "  {:<32} runs {:>3}  failed {:>3}  mean score {}",
+            key, s.runs, s.failures, score
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::test_helpers::setup_workgraph;
+
+    #[test]
+    fn test_run_with_no_history() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![]);
+        run(&dir, true, false).unwrap();
+        run(&dir, false, true).unwrap();
+        assert!(learning::state_path(&dir).exists());
+    }
+}
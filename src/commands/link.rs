@@ -234,6 +234,14 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -282,6 +290,14 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -330,6 +346,14 @@ mod tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
     }
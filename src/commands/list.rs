@@ -3,9 +3,200 @@ use chrono::{DateTime, Utc};
 use std::path::Path;
 use workgraph::graph::{
     PRIORITY_CRITICAL, PRIORITY_DEFAULT, PRIORITY_HIGH, PRIORITY_IDLE, PRIORITY_LOW,
-    PRIORITY_NORMAL, Priority, Status,
+    PRIORITY_NORMAL, Priority, Status, Task,
 };
+use workgraph::query::TaskQuery;
+
+/// Columns accepted by `--columns` and `{{field}}` placeholders in `--format`.
+pub const LIST_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "status",
+    "assigned",
+    "priority",
+    "estimate",
+    "age",
+    "tags",
+    "after",
+    "created",
+    "completed",
+    "model",
+];
+
+/// How `wg list` renders tasks in text mode.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Layout {
+    /// The status-box line with hints
+    #[default]
+    Default,
+    /// Aligned table of the chosen columns
+    Columns { columns: Vec<String>, header: bool },
+    /// One line per task from a `{{field}}` template
+    Template(String),
+}
+
+impl Layout {
+    /// Build from `--columns id,status,...` or `--format '{{id}}\t{{title}}'`.
+    /// Unknown column names are rejected up front rather than rendered empty.
+    pub fn parse(columns: Option<&str>, format: Option<&str>, header: bool) -> Result<Self> {
+        let check = |name: &str| -> Result<()> {
+            if !LIST_COLUMNS.contains(&name) {
+                anyhow::bail!(
+                    "Unknown column '{}'. Valid columns: {}",
+                    name,
+                    LIST_COLUMNS.join(", ")
+                );
+            }
+            Ok(())
+        };
+        if let Some(format) = format {
+            for name in template_fields(format) {
+                check(name)?;
+            }
+            return Ok(Layout::Template(unescape(format)));
+        }
+        if let Some(columns) = columns {
+            let columns: Vec<String> = columns
+                .split(',')
+                .map(|c| c.trim().to_lowercase())
+                .filter(|c| !c.is_empty())
+                .collect();
+            if columns.is_empty() {
+                anyhow::bail!("--columns needs at least one column");
+            }
+            for c in &columns {
+                check(c)?;
+            }
+            return Ok(Layout::Columns { columns, header });
+        }
+        Ok(Layout::Default)
+    }
+}
+
+/// Field names referenced as `{{name}}` in a template.
+fn template_fields(template: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push(rest[start + 2..start + 2 + len].trim());
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out
+}
+
+/// Substitute each `{{name}}` in `template` with `value(name)`.
+fn fill_template(template: &str, value: impl Fn(&str) -> String) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        out.push_str(&value(rest[start + 2..start + 2 + len].trim()));
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Expand `\t`, `\n` and `\\` so shell-quoted templates can hold tabs.
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Plain-text value of one column for a task; empty when unset.
+fn column_value(task: &Task, column: &str, now: DateTime<Utc>) -> String {
+    let since = |ts: Option<&str>| {
+        ts.and_then(|s| s.parse::<DateTime<Utc>>().ok())
+            .map(|t| workgraph::format_duration((now - t).num_seconds().max(0), true))
+            .unwrap_or_default()
+    };
+    match column {
+        "id" => task.id.clone(),
+        "title" => task.title.clone(),
+        "status" => task.status.to_string(),
+        "assigned" => task.assigned.clone().unwrap_or_default(),
+        "priority" => task.priority.to_string(),
+        "estimate" => task
+            .estimate
+            .as_ref()
+            .and_then(|e| e.hours)
+            .map(|h| format!("{}h", workgraph::format_hours(h)))
+            .unwrap_or_default(),
+        "age" => since(task.created_at.as_deref()),
+        "tags" => task.tags.join(","),
+        "after" => task.after.join(","),
+        "created" => task.created_at.clone().unwrap_or_default(),
+        "completed" => task.completed_at.clone().unwrap_or_default(),
+        "model" => task.model.clone().unwrap_or_default(),
+        _ => String::new(),
+    }
+}
 
+/// Render tasks with a non-default layout, one string per output line.
+fn render_layout(tasks: &[&Task], layout: &Layout, now: DateTime<Utc>) -> Vec<String> {
+    match layout {
+        Layout::Default => Vec::new(),
+        Layout::Template(template) => tasks
+            .iter()
+            .map(|t| fill_template(template, |name| column_value(t, name, now)))
+            .collect(),
+        Layout::Columns { columns, header } => {
+            let mut rows: Vec<Vec<String>> = Vec::new();
+            if *header {
+                rows.push(columns.iter().map(|c| c.to_uppercase()).collect());
+            }
+            rows.extend(
+                tasks
+                    .iter()
+                    .map(|t| columns.iter().map(|c| column_value(t, c, now)).collect()),
+            );
+            let widths: Vec<usize> = (0..columns.len())
+                .map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0))
+                .collect();
+            rows.iter()
+                .map(|row| {
+                    let last = row.len() - 1;
+                    row.iter()
+                        .enumerate()
+                        .map(|(i, cell)| {
+                            if i == last {
+                                cell.clone()
+                            } else {
+                                format!("{:<width$}", cell, width = widths[i])
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join("  ")
+                })
+                .collect()
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     dir: &Path,
     status_filter: Option<&str>,
@@ -13,8 +204,41 @@ pub fn run(
     tags: &[String],
     priority_filter: Option<&str>,
     cron_only: bool,
+    where_expr: Option<&str>,
+    view: Option<&str>,
+    json: bool,
+    show_all: bool,
+) -> Result<()> {
+    run_with_layout(
+        dir,
+        status_filter,
+        paused_only,
+        tags,
+        priority_filter,
+        cron_only,
+        where_expr,
+        view,
+        json,
+        show_all,
+        &Layout::Default,
+    )
+}
+
+/// [`run`] with a custom text layout (`--columns` / `--format`).
+/// JSON output is unaffected by the layout.
+#[allow(clippy::too_many_arguments)]
+pub fn run_with_layout(
+    dir: &Path,
+    status_filter: Option<&str>,
+    paused_only: bool,
+    tags: &[String],
+    priority_filter: Option<&str>,
+    cron_only: bool,
+    where_expr: Option<&str>,
+    view: Option<&str>,
     json: bool,
     show_all: bool,
+    layout: &Layout,
 ) -> Result<()> {
     let (graph, _path) = super::load_workgraph(dir)?;
 
@@ -54,6 +278,15 @@ pub fn run(
         None => None,
     };
 
+    let mut query = where_expr
+        .map(TaskQuery::parse)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Invalid --where expression: {}", e))?
+        .unwrap_or_default();
+    if let Some(name) = view {
+        query = super::view::resolve(dir, name)?.and(query);
+    }
+
     let tasks: Vec<_> = graph
         .tasks()
         .filter(|t| show_all || !t.id.starts_with('.'))
@@ -62,7 +295,9 @@ pub fn run(
         .filter(|t| tags.iter().all(|tag| t.tags.contains(tag)))
         .filter(|t| priority_filter.as_ref().is_none_or(|p| &t.priority == p))
         .filter(|t| !cron_only || t.cron_enabled)
+        .filter(|t| query.matches(t))
         .collect();
+    let tasks = query.order(tasks);
 
     if json {
         let output: Vec<_> = tasks
@@ -104,6 +339,11 @@ pub fn run(
             })
             .collect();
         println!("{}", serde_json::to_string_pretty(&output)?);
+    } else if *layout != Layout::Default {
+        // Scriptable layouts print nothing for an empty result.
+        for line in render_layout(&tasks, layout, Utc::now()) {
+            println!("{}", line);
+        }
     } else if tasks.is_empty() {
         println!("No tasks found");
     } else {
@@ -246,6 +486,61 @@ mod tests {
         path
     }
 
+    // --- layout tests ---
+
+    #[test]
+    fn test_layout_parse() {
+        assert_eq!(Layout::parse(None, None, true).unwrap(), Layout::Default);
+        assert_eq!(
+            Layout::parse(Some("id, Status"), None, false).unwrap(),
+            Layout::Columns {
+                columns: vec!["id".to_string(), "status".to_string()],
+                header: false
+            }
+        );
+        assert_eq!(
+            Layout::parse(None, Some(r"{{id}}\t{{title}}"), true).unwrap(),
+            Layout::Template("{{id}}\t{{title}}".to_string())
+        );
+        assert!(Layout::parse(Some("id,bogus"), None, true).is_err());
+        assert!(Layout::parse(None, Some("{{nope}}"), true).is_err());
+    }
+
+    #[test]
+    fn test_render_layout() {
+        let now = Utc::now();
+        let mut a = make_task("a", "Alpha", Status::Open);
+        a.assigned = Some("agent-7".to_string());
+        a.created_at = Some((now - Duration::hours(3)).to_rfc3339());
+        a.estimate = Some(workgraph::graph::Estimate {
+            hours: Some(1.5),
+            cost: None,
+        });
+        let b = make_task("longer-id", "Beta", Status::Done);
+        let tasks = vec![&a, &b];
+
+        let template = Layout::parse(
+            None,
+            Some(r"{{id}}\t{{ status }}|{{estimate}}|{{age}}"),
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            render_layout(&tasks, &template, now),
+            vec!["a\topen|1.5h|3h", "longer-id\tdone||"]
+        );
+
+        let columns = Layout::parse(Some("id,assigned,status"), None, true).unwrap();
+        assert_eq!(
+            render_layout(&tasks, &columns, now),
+            vec![
+                "ID         ASSIGNED  STATUS",
+                "a          agent-7   open",
+                "longer-id            done",
+            ]
+        );
+    }
+
     // --- format_ready_after_hint tests ---
 
     #[test]
@@ -302,7 +597,18 @@ mod tests {
     #[test]
     fn test_run_uninitialized() {
         let dir = tempdir().unwrap();
-        let result = run(dir.path(), None, false, &[], None, false, false, false);
+        let result = run(
+            dir.path(),
+            None,
+            false,
+            &[],
+            None,
+            false,
+            None,
+            None,
+            false,
+            false,
+        );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not initialized"));
     }
@@ -311,7 +617,18 @@ mod tests {
     fn test_run_no_tasks() {
         let dir = tempdir().unwrap();
         setup_workgraph(dir.path(), vec![]);
-        let result = run(dir.path(), None, false, &[], None, false, false, false);
+        let result = run(
+            dir.path(),
+            None,
+            false,
+            &[],
+            None,
+            false,
+            None,
+            None,
+            false,
+            false,
+        );
         assert!(result.is_ok());
     }
 
@@ -333,6 +650,8 @@ mod tests {
             &[],
             None,
             false,
+            None,
+            None,
             false,
             false,
         );
@@ -356,6 +675,8 @@ mod tests {
             &[],
             None,
             false,
+            None,
+            None,
             false,
             false,
         );
@@ -376,6 +697,8 @@ mod tests {
             &[],
             None,
             false,
+            None,
+            None,
             false,
             false,
         );
@@ -396,12 +719,66 @@ mod tests {
             &[],
             None,
             false,
+            None,
+            None,
             false,
             false,
         );
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_run_where_expression() {
+        let dir = tempdir().unwrap();
+        setup_workgraph(dir.path(), vec![make_task("t1", "Task", Status::Open)]);
+        let run_where = |expr| {
+            run(
+                dir.path(),
+                None,
+                false,
+                &[],
+                None,
+                false,
+                Some(expr),
+                None,
+                false,
+                false,
+            )
+        };
+        assert!(run_where("status=open AND NOT tag:backend").is_ok());
+        let err = run_where("colour=red").unwrap_err();
+        assert!(err.to_string().contains("Invalid --where expression"));
+        assert!(run_where("status=open sort:-priority limit:1").is_ok());
+    }
+
+    #[test]
+    fn test_run_view() {
+        let dir = tempdir().unwrap();
+        setup_workgraph(dir.path(), vec![make_task("t1", "Task", Status::Open)]);
+        fs::write(
+            dir.path().join("config.toml"),
+            "[views]\nbacklog = \"status=open sort:priority\"\n",
+        )
+        .unwrap();
+        let run_view = |view| {
+            run(
+                dir.path(),
+                None,
+                false,
+                &[],
+                None,
+                false,
+                Some("tag:none"),
+                Some(view),
+                false,
+                false,
+            )
+        };
+        assert!(run_view("backlog").is_ok());
+        let err = run_view("missing").unwrap_err();
+        assert!(err.to_string().contains("Unknown view 'missing'"));
+    }
+
     #[test]
     fn test_run_unknown_status_filter() {
         let dir = tempdir().unwrap();
@@ -413,6 +790,8 @@ mod tests {
             &[],
             None,
             false,
+            None,
+            None,
             false,
             false,
         );
@@ -468,7 +847,18 @@ mod tests {
         task.ready_after = Some(future.to_rfc3339());
         setup_workgraph(dir.path(), vec![task]);
 
-        let result = run(dir.path(), None, false, &[], None, false, false, false);
+        let result = run(
+            dir.path(),
+            None,
+            false,
+            &[],
+            None,
+            false,
+            None,
+            None,
+            false,
+            false,
+        );
         assert!(result.is_ok());
     }
 
@@ -482,7 +872,18 @@ mod tests {
         task.after = vec!["dep-1".to_string()];
         setup_workgraph(dir.path(), vec![task]);
 
-        let result = run(dir.path(), None, false, &[], None, false, true, false);
+        let result = run(
+            dir.path(),
+            None,
+            false,
+            &[],
+            None,
+            false,
+            None,
+            None,
+            true,
+            false,
+        );
         assert!(result.is_ok());
     }
 
@@ -561,6 +962,8 @@ mod tests {
             &[],
             None,
             false,
+            None,
+            None,
             false,
             false,
         );
@@ -584,6 +987,8 @@ mod tests {
             &[],
             None,
             false,
+            None,
+            None,
             false,
             false,
         );
@@ -601,6 +1006,8 @@ mod tests {
             &[],
             None,
             false,
+            None,
+            None,
             false,
             false,
         );
@@ -654,6 +1061,8 @@ mod tests {
             &[],
             None,
             false,
+            None,
+            None,
             true,
             false,
         );
@@ -687,7 +1096,18 @@ mod tests {
         assert_eq!(paused_open[0].id, "t-paused");
 
         // run() with paused_only=true should succeed
-        let result = run(dir.path(), None, true, &[], None, false, false, false);
+        let result = run(
+            dir.path(),
+            None,
+            true,
+            &[],
+            None,
+            false,
+            None,
+            None,
+            false,
+            false,
+        );
         assert!(result.is_ok());
 
         // run() with paused_only=true and status filter should succeed
@@ -698,6 +1118,8 @@ mod tests {
             &[],
             None,
             false,
+            None,
+            None,
             false,
             false,
         );
@@ -724,7 +1146,18 @@ mod tests {
         assert_eq!(filtered[0].id, "t1");
 
         // run() with tag filter should succeed
-        let result = run(dir.path(), None, false, &tags, None, false, false, false);
+        let result = run(
+            dir.path(),
+            None,
+            false,
+            &tags,
+            None,
+            false,
+            None,
+            None,
+            false,
+            false,
+        );
         assert!(result.is_ok());
     }
 
@@ -755,7 +1188,18 @@ mod tests {
         setup_workgraph(dir.path(), vec![task]);
 
         let tags = vec!["nonexistent".to_string()];
-        let result = run(dir.path(), None, false, &tags, None, false, false, false);
+        let result = run(
+            dir.path(),
+            None,
+            false,
+            &tags,
+            None,
+            false,
+            None,
+            None,
+            false,
+            false,
+        );
         assert!(result.is_ok());
     }
 
@@ -787,6 +1231,8 @@ mod tests {
             &tags,
             None,
             false,
+            None,
+            None,
             false,
             false,
         );
@@ -803,7 +1249,18 @@ mod tests {
         setup_workgraph(dir.path(), vec![task, normal]);
 
         // Should succeed — verify indicator is displayed for t1
-        let result = run(dir.path(), None, false, &[], None, false, false, false);
+        let result = run(
+            dir.path(),
+            None,
+            false,
+            &[],
+            None,
+            false,
+            None,
+            None,
+            false,
+            false,
+        );
         assert!(result.is_ok());
     }
 
@@ -904,7 +1361,18 @@ mod tests {
             ],
         );
         // show_all=true should show all 3 tasks
-        let result = run(dir.path(), None, false, &[], None, false, false, true);
+        let result = run(
+            dir.path(),
+            None,
+            false,
+            &[],
+            None,
+            false,
+            None,
+            None,
+            false,
+            true,
+        );
         assert!(result.is_ok());
 
         let path = graph_path(dir.path());
@@ -23,6 +23,22 @@ pub fn run_add(
     }
 
     let mut error: Option<anyhow::Error> = None;
+    let mut message = workgraph::scrub::scrub_text(dir, message);
+    let max_bytes = workgraph::config::Config::load_or_default(dir)
+        .guardrails
+        .max_log_message_bytes;
+    if max_bytes > 0 && message.len() > max_bytes {
+        eprintln!(
+            "Warning: log message is {} bytes; truncated to max_log_message_bytes={}",
+            message.len(),
+            max_bytes
+        );
+        message = workgraph::size_guard::truncate_text(
+            &message,
+            max_bytes,
+            workgraph::size_guard::Truncation::Head,
+        );
+    }
 
     let _graph = modify_graph(&path, |graph| {
         let task = match graph.get_task_mut(id) {
@@ -37,7 +53,7 @@ pub fn run_add(
             timestamp: Utc::now().to_rfc3339(),
             actor: actor.map(String::from),
             user: Some(workgraph::current_user()),
-            message: message.to_string(),
+            message: message.clone(),
         };
 
         task.log.push(entry);
@@ -124,6 +140,65 @@ pub fn run_list(dir: &Path, id: &str, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Follow the output of the agent running a task until it exits.
+///
+/// Streams through the service daemon (`follow_output` IPC request) when it
+/// is running, and tails the agent's output file directly otherwise. With
+/// `json`, prints one `{"line": ...}` object per output line.
+pub fn run_follow(dir: &Path, task_id: &str, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    graph.get_task_or_err(task_id)?;
+
+    let print_line = |line: &str| {
+        if json {
+            println!("{}", serde_json::json!({ "line": line }));
+        } else {
+            println!("{}", line);
+        }
+    };
+
+    let request = super::service::IpcRequest::FollowOutput {
+        task_id: task_id.to_string(),
+    };
+    let mut connected = false;
+    let streamed = super::service::stream_request(dir, &request, |response| {
+        connected = true;
+        if !response.ok {
+            anyhow::bail!("{}", response.error.unwrap_or_default());
+        }
+        let data = response.data.unwrap_or_default();
+        if let Some(line) = data.get("line").and_then(|l| l.as_str()) {
+            print_line(line);
+        } else if let Some(agent_id) = data.get("agent_id").and_then(|a| a.as_str()) {
+            eprintln!("Following {} on '{}' (Ctrl-C to stop)", agent_id, task_id);
+        } else if data.get("done").is_some() {
+            eprintln!("Agent exited");
+        }
+        Ok(())
+    });
+    match streamed {
+        Ok(()) => return Ok(()),
+        Err(e) if connected => return Err(e),
+        // No service to stream through: read the file ourselves.
+        Err(_) => {}
+    }
+
+    let Some(agent) = workgraph::service::output_stream::running_agent(dir, task_id)? else {
+        anyhow::bail!(
+            "No running agent for task '{}'. Use 'wg log {} --agent' for archived output.",
+            task_id,
+            task_id
+        );
+    };
+    eprintln!("Following {} on '{}' (Ctrl-C to stop)", agent.id, task_id);
+    workgraph::service::output_stream::follow(dir, &agent, |line| {
+        print_line(line);
+        Ok(())
+    })?;
+    eprintln!("Agent exited");
+    Ok(())
+}
+
 /// Archive directory for agent conversations: .wg/log/agents/<task-id>/
 fn agent_archive_dir(dir: &Path, task_id: &str) -> PathBuf {
     dir.join("log").join("agents").join(task_id)
@@ -150,18 +225,28 @@ pub fn archive_agent(dir: &Path, task_id: &str, agent_id: &str) -> Result<PathBu
         )
     })?;
 
-    // Copy prompt.txt if it exists
-    let prompt_src = agent_dir.join("prompt.txt");
-    if prompt_src.exists() {
-        fs::copy(&prompt_src, archive_dir.join("prompt.txt"))
-            .with_context(|| format!("Failed to copy prompt.txt from {}", prompt_src.display()))?;
-    }
-
-    // Copy output.log as output.txt
-    let output_src = agent_dir.join("output.log");
-    if output_src.exists() {
-        fs::copy(&output_src, archive_dir.join("output.txt"))
-            .with_context(|| format!("Failed to copy output.log from {}", output_src.display()))?;
+    // Copy prompt.txt and output.log (as output.txt), scrubbed if configured
+    let scrubber = workgraph::scrub::Scrubber::load(dir);
+    for (src_name, dest_name) in [("prompt.txt", "prompt.txt"), ("output.log", "output.txt")] {
+        let src = agent_dir.join(src_name);
+        if !src.exists() {
+            continue;
+        }
+        let dest = archive_dir.join(dest_name);
+        match &scrubber {
+            Some(scrubber) => {
+                let content =
+                    fs::read(&src).with_context(|| format!("Failed to read {}", src.display()))?;
+                let text = String::from_utf8_lossy(&content);
+                fs::write(&dest, scrubber.scrub(&text).as_bytes())
+                    .with_context(|| format!("Failed to write {}", dest.display()))?;
+            }
+            None => {
+                fs::copy(&src, &dest).with_context(|| {
+                    format!("Failed to copy {} from {}", src_name, src.display())
+                })?;
+            }
+        }
     }
 
     Ok(archive_dir)
@@ -424,6 +509,57 @@ mod tests {
         assert_eq!(task.log[0].message, "");
     }
 
+    #[test]
+    fn test_log_add_scrubs_when_enabled() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("t1", "Task 1")));
+        setup_graph(&dir, &graph);
+        std::fs::write(
+            dir.join("config.toml"),
+            "[scrub]\nenabled = true\nwords = [\"nightingale\"]\n",
+        )
+        .unwrap();
+
+        run_add(
+            &dir,
+            "t1",
+            "Nightingale creds: API_KEY=abc123, owner dev@example.com",
+            None,
+            None,
+        )
+        .unwrap();
+
+        let graph = load_graph(graph_path(&dir)).unwrap();
+        assert_eq!(
+            graph.get_task("t1").unwrap().log[0].message,
+            "[redacted] creds: API_KEY=[redacted], owner [redacted]"
+        );
+    }
+
+    #[test]
+    fn test_log_add_caps_message_size() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("t1", "Task 1")));
+        setup_graph(&dir, &graph);
+        std::fs::write(
+            dir.join("config.toml"),
+            "[guardrails]\nmax_log_message_bytes = 8\n",
+        )
+        .unwrap();
+
+        run_add(&dir, "t1", &"x".repeat(100), None, None).unwrap();
+
+        let graph = load_graph(graph_path(&dir)).unwrap();
+        let message = &graph.get_task("t1").unwrap().log[0].message;
+        assert!(message.starts_with("xxxxxxxx\n[... 92 bytes truncated"));
+    }
+
     #[test]
     fn test_log_add_task_not_found() {
         let tmp = TempDir::new().unwrap();
@@ -2,6 +2,8 @@ use anyhow::{Context, Result};
 use serde::Serialize;
 use std::path::Path;
 use workgraph::agency;
+use workgraph::config::Config;
+use workgraph::dispatch::skill_match::{SkillMatch, SkillMatcher};
 use workgraph::graph::TrustLevel;
 
 /// Match result for an agent
@@ -14,34 +16,31 @@ struct MatchResult {
     missing_skills: Vec<String>,
     trust_level: TrustLevel,
     available: bool,
+    /// Per-skill closest capability and similarity (`--explain`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    explanation: Vec<SkillMatch>,
 }
 
 /// Find agents capable of performing a task
-pub fn run(dir: &Path, task_id: &str, json: bool) -> Result<()> {
+pub fn run(dir: &Path, task_id: &str, explain: bool, json: bool) -> Result<()> {
     let (graph, _path) = super::load_workgraph(dir)?;
 
     let task = graph.get_task_or_err(task_id)?;
 
-    let required_skills: std::collections::HashSet<_> = task.skills.iter().collect();
-
     // Load agents from .wg/agency/agents/
     let agents_dir = dir.join("agency").join("cache/agents");
     let agents = agency::load_all_agents(&agents_dir).context("Failed to load agents")?;
 
+    let config = Config::load_or_default(dir);
+    let matcher = SkillMatcher::for_task(dir, &config, task, &agents);
+
     let mut matches: Vec<MatchResult> = agents
         .iter()
         .map(|agent| {
-            let agent_skills: std::collections::HashSet<_> = agent.capabilities.iter().collect();
-
-            let matched: Vec<_> = required_skills
-                .intersection(&agent_skills)
-                .map(|s| (*s).clone())
-                .collect();
-
-            let missing: Vec<_> = required_skills
-                .difference(&agent_skills)
-                .map(|s| (*s).clone())
-                .collect();
+            let results = matcher.match_skills(&task.skills, &agent.capabilities);
+            let (matched, missing): (Vec<_>, Vec<_>) = results.iter().partition(|m| m.matched);
+            let matched: Vec<_> = matched.into_iter().map(|m| m.skill.clone()).collect();
+            let missing: Vec<_> = missing.into_iter().map(|m| m.skill.clone()).collect();
 
             // Score: matched skills count, bonus for verified trust
             let mut score = matched.len() as u32;
@@ -63,6 +62,7 @@ pub fn run(dir: &Path, task_id: &str, json: bool) -> Result<()> {
                 missing_skills: missing,
                 trust_level: agent.trust_level.clone(),
                 available,
+                explanation: if explain { results } else { Vec::new() },
             }
         })
         .collect();
@@ -75,7 +75,7 @@ pub fn run(dir: &Path, task_id: &str, json: bool) -> Result<()> {
     });
 
     // Filter to only include agents with at least partial match (or all if no skills required)
-    let matches: Vec<_> = if required_skills.is_empty() {
+    let matches: Vec<_> = if task.skills.is_empty() {
         matches
     } else {
         matches.into_iter().filter(|m| m.score > 0).collect()
@@ -90,10 +90,13 @@ pub fn run(dir: &Path, task_id: &str, json: bool) -> Result<()> {
         } else {
             println!("Required skills: {}", task.skills.join(", "));
         }
+        if explain {
+            println!("Matching: {}", matcher.describe());
+        }
         println!();
 
         if matches.is_empty() {
-            if required_skills.is_empty() {
+            if task.skills.is_empty() {
                 println!("No agents registered.");
             } else {
                 println!("No agents with matching capabilities found.");
@@ -109,7 +112,7 @@ pub fn run(dir: &Path, task_id: &str, json: bool) -> Result<()> {
                 };
                 let short_id = agency::short_hash(&m.agent_id);
 
-                if required_skills.is_empty() {
+                if task.skills.is_empty() {
                     println!(
                         "  {} - {}{}{}",
                         short_id, m.agent_name, trust_str, available_str
@@ -129,6 +132,9 @@ pub fn run(dir: &Path, task_id: &str, json: bool) -> Result<()> {
                         available_str
                     );
                 }
+                for skill in &m.explanation {
+                    println!("      {}", explain_line(skill));
+                }
             }
         }
     }
@@ -136,6 +142,18 @@ pub fn run(dir: &Path, task_id: &str, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// One `--explain` line: the skill, its closest capability, and the score.
+fn explain_line(m: &SkillMatch) -> String {
+    let mark = if m.matched { "+" } else { "-" };
+    match m.capability {
+        Some(ref capability) => format!(
+            "{} {} ~ {} ({:.2})",
+            mark, m.skill, capability, m.similarity
+        ),
+        None => format!("{} {} (no comparable capability)", mark, m.skill),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,6 +192,8 @@ mod tests {
             deployment_history: vec![],
             attractor_weight: 0.5,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         }
     }
 
@@ -203,7 +223,7 @@ mod tests {
         let agent2 = make_agent("python-dev", vec!["python", "testing"]);
         setup_agents(temp_dir.path(), &[agent1, agent2]);
 
-        let result = run(temp_dir.path(), "t1", false);
+        let result = run(temp_dir.path(), "t1", false, false);
         assert!(result.is_ok());
     }
 
@@ -214,7 +234,7 @@ mod tests {
         let graph = WorkGraph::new();
         save_graph(&graph, &path).unwrap();
 
-        let result = run(temp_dir.path(), "no-such-task", false);
+        let result = run(temp_dir.path(), "no-such-task", false, false);
         assert!(result.is_err());
     }
 
@@ -231,7 +251,7 @@ mod tests {
         setup_agents(temp_dir.path(), &[agent]);
 
         // No skills required — all agents should match
-        let result = run(temp_dir.path(), "t1", false);
+        let result = run(temp_dir.path(), "t1", false, false);
         assert!(result.is_ok());
     }
 
@@ -250,7 +270,7 @@ mod tests {
         let agency_dir = temp_dir.path().join("agency");
         agency::init(&agency_dir).unwrap();
 
-        let result = run(temp_dir.path(), "t1", false);
+        let result = run(temp_dir.path(), "t1", false, false);
         assert!(result.is_ok());
     }
 
@@ -268,7 +288,43 @@ mod tests {
         let agent = make_agent("rust-dev", vec!["rust"]);
         setup_agents(temp_dir.path(), &[agent]);
 
-        let result = run(temp_dir.path(), "t1", true);
+        let result = run(temp_dir.path(), "t1", false, true);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_match_explain() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph.jsonl");
+
+        let mut graph = WorkGraph::new();
+        let mut task = make_task("t1", "Rust task");
+        task.skills = vec!["rust".to_string(), "postgres".to_string()];
+        graph.add_node(Node::Task(task));
+        save_graph(&graph, &path).unwrap();
+
+        let agent = make_agent("rust-dev", vec!["rust"]);
+        setup_agents(temp_dir.path(), &[agent]);
+
+        assert!(run(temp_dir.path(), "t1", true, false).is_ok());
+        assert!(run(temp_dir.path(), "t1", true, true).is_ok());
+    }
+
+    #[test]
+    fn test_explain_line() {
+        let line = explain_line(&SkillMatch {
+            skill: "postgres".to_string(),
+            capability: Some("databases".to_string()),
+            similarity: 0.834,
+            matched: true,
+        });
+        assert_eq!(line, "+ postgres ~ databases (0.83)");
+        let line = explain_line(&SkillMatch {
+            skill: "postgres".to_string(),
+            capability: None,
+            similarity: 0.0,
+            matched: false,
+        });
+        assert_eq!(line, "- postgres (no comparable capability)");
+    }
 }
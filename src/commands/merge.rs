@@ -0,0 +1,289 @@
+//! `wg merge` — three-way merge another copy of the graph into this one.
+//!
+//! Tasks are merged individually against a common ancestor (see
+//! [`workgraph::graph_merge`]): disjoint edits combine, `after` edges and
+//! log entries from both sides are kept, and only true conflicts are
+//! reported. Nothing is written while conflicts remain unless a side is
+//! chosen to resolve them.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+use workgraph::config::Config;
+use workgraph::graph::WorkGraph;
+use workgraph::graph_merge::{self, NodeConflict, Side};
+use workgraph::parser::modify_graph;
+use workgraph::snapshot;
+
+use super::diff::resolve_ref;
+use super::merge_driver::describe;
+
+#[derive(Debug, Serialize)]
+struct MergeSummary {
+    other: String,
+    base: Option<String>,
+    applied: bool,
+    changed_tasks: usize,
+    conflicts: Vec<NodeConflict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved_with: Option<Side>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    safety_snapshot: Option<String>,
+}
+
+/// Merge `other` into the live graph.
+///
+/// `other` and `base` accept the same references as `wg diff` (a file
+/// path, `snapshot:<name>`, `branch:<name>`, or `git:<rev>`). Without a
+/// base, nodes are merged two-way: anything both sides have but disagree on
+/// is a conflict.
+pub fn run(
+    dir: &Path,
+    other: &str,
+    base: Option<&str>,
+    resolve: Option<Side>,
+    dry_run: bool,
+    json: bool,
+) -> Result<()> {
+    let (other_label, other_graph) = resolve_ref(dir, other)?;
+    let base = match base {
+        Some(spec) => Some(resolve_ref(dir, spec)?),
+        None => None,
+    };
+    let base_nodes = base
+        .as_ref()
+        .map(|(_, g)| graph_merge::graph_nodes(g))
+        .unwrap_or_default();
+    let theirs = graph_merge::graph_nodes(&other_graph);
+
+    let graph_path = super::graph_path(dir);
+    let mut conflicts = Vec::new();
+    let mut changed = 0;
+    let mut applied = false;
+    let mut safety = None;
+    let mut error = None;
+
+    modify_graph(&graph_path, |graph| {
+        let ours = graph_merge::graph_nodes(graph);
+        let merged = graph_merge::merge(&base_nodes, &ours, &theirs);
+        conflicts = merged.conflicts().cloned().collect();
+        if dry_run || (!conflicts.is_empty() && resolve.is_none()) {
+            return false;
+        }
+        let result = merged.into_graph(resolve).and_then(|new_graph| {
+            let snap = snapshot::create(
+                dir,
+                None,
+                Some(&format!(
+                    "pre-merge safety snapshot (merging {})",
+                    other_label
+                )),
+                false,
+                false,
+            )?;
+            Ok((new_graph, snap.name))
+        });
+        match result {
+            Ok((new_graph, snap_name)) => {
+                changed = count_changed(graph, &new_graph);
+                *graph = new_graph;
+                safety = Some(snap_name);
+                applied = true;
+                true
+            }
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        }
+    })
+    .context("Failed to merge into the graph")?;
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    if applied {
+        super::notify_graph_changed(dir);
+        let config = Config::load_or_default(dir);
+        let _ = workgraph::provenance::record(
+            dir,
+            "merge",
+            None,
+            None,
+            serde_json::json!({
+                "other": other_label,
+                "base": base.as_ref().map(|(l, _)| l),
+                "changed_tasks": changed,
+                "conflicts": conflicts.iter().map(|c| &c.id).collect::<Vec<_>>(),
+                "resolved_with": resolve,
+                "safety_snapshot": safety,
+            }),
+            config.log.rotation_threshold,
+        );
+    }
+
+    let summary = MergeSummary {
+        other: other_label,
+        base: base.map(|(l, _)| l),
+        applied,
+        changed_tasks: changed,
+        conflicts,
+        resolved_with: if applied { resolve } else { None },
+        safety_snapshot: safety,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+    } else {
+        print_summary(&summary, dry_run);
+    }
+
+    if !summary.applied && !summary.conflicts.is_empty() && !dry_run {
+        anyhow::bail!(
+            "{} conflict(s); nothing was merged. Resolve with --ours or --theirs, or edit the tasks and retry",
+            summary.conflicts.len()
+        );
+    }
+    Ok(())
+}
+
+fn count_changed(old: &WorkGraph, new: &WorkGraph) -> usize {
+    let old_nodes: std::collections::HashMap<String, serde_json::Value> =
+        graph_merge::graph_nodes(old).into_iter().collect();
+    let new_nodes = graph_merge::graph_nodes(new);
+    let added_or_updated = new_nodes
+        .iter()
+        .filter(|(id, v)| old_nodes.get(id) != Some(v))
+        .count();
+    let new_ids: std::collections::HashSet<&String> = new_nodes.iter().map(|(id, _)| id).collect();
+    let removed = old_nodes.keys().filter(|id| !new_ids.contains(id)).count();
+    added_or_updated + removed
+}
+
+fn print_summary(summary: &MergeSummary, dry_run: bool) {
+    match &summary.base {
+        Some(base) => println!("Merging {} (base: {})", summary.other, base),
+        None => println!("Merging {} (no base: two-way merge)", summary.other),
+    }
+    if !summary.conflicts.is_empty() {
+        println!("\nConflicts ({}):", summary.conflicts.len());
+        for c in &summary.conflicts {
+            println!("  {}", describe(c));
+        }
+    }
+    println!();
+    if summary.applied {
+        println!("Merged: {} task(s) changed", summary.changed_tasks);
+        if let Some(side) = summary.resolved_with
+            && !summary.conflicts.is_empty()
+        {
+            let side = match side {
+                Side::Ours => "ours",
+                Side::Theirs => "theirs",
+            };
+            println!("  Conflicts resolved with {} version", side);
+        }
+        if let Some(ref snap) = summary.safety_snapshot {
+            println!(
+                "  Safety snapshot: {} (wg snapshot restore {} to undo)",
+                snap, snap
+            );
+        }
+    } else if dry_run {
+        if summary.conflicts.is_empty() {
+            println!("Dry run: merge is clean, nothing written");
+        } else {
+            println!("Dry run: nothing written");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::graph::Status;
+    use workgraph::parser::{load_graph, save_graph};
+    use workgraph::test_helpers::{make_task, make_task_with_status, setup_workgraph};
+
+    fn write_graph(path: &Path, tasks: Vec<workgraph::graph::Task>) {
+        let mut g = WorkGraph::new();
+        for t in tasks {
+            g.add_node(workgraph::graph::Node::Task(t));
+        }
+        save_graph(&g, path).unwrap();
+    }
+
+    #[test]
+    fn test_three_way_merge_combines_disjoint_edits() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("a", "A"), make_task("b", "B")]);
+        snapshot::create(&dir, Some("base"), None, false, false).unwrap();
+
+        // Live: a is done. Other machine: b failed, c added.
+        setup_workgraph(
+            &dir,
+            vec![
+                make_task_with_status("a", "A", Status::Done),
+                make_task("b", "B"),
+            ],
+        );
+        let other = tmp.path().join("other.jsonl");
+        write_graph(
+            &other,
+            vec![
+                make_task("a", "A"),
+                make_task_with_status("b", "B", Status::Failed),
+                make_task("c", "C"),
+            ],
+        );
+
+        run(
+            &dir,
+            other.to_str().unwrap(),
+            Some("snapshot:base"),
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+        let g = load_graph(dir.join("graph.jsonl")).unwrap();
+        assert_eq!(g.get_task("a").unwrap().status, Status::Done);
+        assert_eq!(g.get_task("b").unwrap().status, Status::Failed);
+        assert!(g.get_task("c").is_some());
+    }
+
+    #[test]
+    fn test_conflicts_block_merge_unless_side_chosen() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("a", "A")]);
+        snapshot::create(&dir, Some("base"), None, false, false).unwrap();
+        setup_workgraph(&dir, vec![make_task_with_status("a", "A", Status::Done)]);
+        let other = tmp.path().join("other.jsonl");
+        write_graph(
+            &other,
+            vec![make_task_with_status("a", "A", Status::Failed)],
+        );
+        let other = other.to_str().unwrap();
+
+        assert!(run(&dir, other, Some("snapshot:base"), None, false, false).is_err());
+        let g = load_graph(dir.join("graph.jsonl")).unwrap();
+        assert_eq!(g.get_task("a").unwrap().status, Status::Done);
+
+        run(&dir, other, Some("snapshot:base"), None, true, false).unwrap();
+        run(
+            &dir,
+            other,
+            Some("snapshot:base"),
+            Some(Side::Theirs),
+            false,
+            false,
+        )
+        .unwrap();
+        let g = load_graph(dir.join("graph.jsonl")).unwrap();
+        assert_eq!(g.get_task("a").unwrap().status, Status::Failed);
+    }
+}
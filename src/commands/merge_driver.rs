@@ -0,0 +1,208 @@
+//! `wg merge-driver` — git merge driver for `graph.jsonl`.
+//!
+//! Git calls the driver with the ancestor, current, and other versions of
+//! the file (`%O %A %B`). The merged result is written over the current
+//! version. Disjoint changes are resolved at the task level; true conflicts
+//! are left as one conflict-marker block per task and listed on stderr, and
+//! the driver exits non-zero so git reports the file as conflicted.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use workgraph::graph_merge::{self, ConflictKind, NodeConflict, Side};
+
+const DRIVER_NAME: &str = "workgraph";
+
+/// Merge `ours` with `theirs` against `base`, writing the result to `ours`.
+///
+/// Returns the number of conflicting tasks.
+pub fn run(base: &Path, ours: &Path, theirs: &Path, path: Option<&str>) -> Result<usize> {
+    let read = |p: &Path| -> Result<Vec<(String, serde_json::Value)>> {
+        let content =
+            std::fs::read_to_string(p).with_context(|| format!("reading {}", p.display()))?;
+        graph_merge::parse_nodes(&content).with_context(|| format!("parsing {}", p.display()))
+    };
+    let merged = graph_merge::merge(&read(base)?, &read(ours)?, &read(theirs)?);
+    std::fs::write(ours, merged.to_jsonl("ours", "theirs"))
+        .with_context(|| format!("writing {}", ours.display()))?;
+
+    let conflicts: Vec<&NodeConflict> = merged.conflicts().collect();
+    if !conflicts.is_empty() {
+        eprintln!(
+            "wg merge-driver: {} task conflict(s) in {}",
+            conflicts.len(),
+            path.unwrap_or("graph.jsonl")
+        );
+        for conflict in &conflicts {
+            eprintln!("  {}", describe(conflict));
+        }
+    }
+    Ok(conflicts.len())
+}
+
+/// One-line human description of a conflict.
+pub fn describe(conflict: &NodeConflict) -> String {
+    match &conflict.kind {
+        ConflictKind::BothModified { fields } => {
+            let details: Vec<String> = fields
+                .iter()
+                .map(|f| {
+                    format!(
+                        "{} (ours: {}, theirs: {})",
+                        f,
+                        field_preview(conflict.ours.as_ref(), f),
+                        field_preview(conflict.theirs.as_ref(), f)
+                    )
+                })
+                .collect();
+            format!(
+                "{}: changed on both sides: {}",
+                conflict.id,
+                details.join("; ")
+            )
+        }
+        ConflictKind::DeletedModified { deleted_in } => {
+            let (deleted, modified) = match deleted_in {
+                Side::Ours => ("ours", "theirs"),
+                Side::Theirs => ("theirs", "ours"),
+            };
+            format!(
+                "{}: deleted in {}, modified in {}",
+                conflict.id, deleted, modified
+            )
+        }
+        ConflictKind::BothAdded => {
+            format!(
+                "{}: added on both sides with different content",
+                conflict.id
+            )
+        }
+    }
+}
+
+fn field_preview(node: Option<&serde_json::Value>, field: &str) -> String {
+    let text = match node.and_then(|n| n.get(field)) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+        None => "<unset>".to_string(),
+    };
+    if text.chars().count() > 40 {
+        format!("{}…", text.chars().take(40).collect::<String>())
+    } else {
+        text
+    }
+}
+
+/// Register the driver in the repository's git config and `.gitattributes`.
+pub fn run_install(dir: &Path) -> Result<()> {
+    let root = dir
+        .parent()
+        .context("Cannot determine project root from workgraph directory")?;
+    let git = |args: &[&str]| -> Result<()> {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(args)
+            .status()
+            .context("Failed to run git")?;
+        if !status.success() {
+            anyhow::bail!("git {} failed", args.join(" "));
+        }
+        Ok(())
+    };
+    git(&[
+        "config",
+        &format!("merge.{}.name", DRIVER_NAME),
+        "workgraph task-level graph merge",
+    ])?;
+    git(&[
+        "config",
+        &format!("merge.{}.driver", DRIVER_NAME),
+        "wg merge-driver %O %A %B %P",
+    ])?;
+
+    let dir_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| ".wg".to_string());
+    let line = format!("{}/graph.jsonl merge={}", dir_name, DRIVER_NAME);
+    let attributes = root.join(".gitattributes");
+    let existing = std::fs::read_to_string(&attributes).unwrap_or_default();
+    if !existing.lines().any(|l| l.trim() == line) {
+        let mut content = existing;
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&line);
+        content.push('\n');
+        std::fs::write(&attributes, content).context("Failed to write .gitattributes")?;
+    }
+
+    println!("Installed git merge driver '{}'", DRIVER_NAME);
+    println!("  {}", line);
+    println!(
+        "  Commit .gitattributes so collaborators pick it up (each clone also needs `wg merge-driver --install`)."
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_driver_merges_disjoint_and_reports_conflicts() {
+        let tmp = TempDir::new().unwrap();
+        let write = |name: &str, content: &str| {
+            let p = tmp.path().join(name);
+            std::fs::write(&p, content).unwrap();
+            p
+        };
+        let base = write(
+            "base",
+            "{\"id\":\"a\",\"status\":\"open\"}\n{\"id\":\"b\",\"status\":\"open\"}\n",
+        );
+        let ours = write(
+            "ours",
+            "{\"id\":\"a\",\"status\":\"done\"}\n{\"id\":\"b\",\"status\":\"open\"}\n",
+        );
+        let theirs = write(
+            "theirs",
+            "{\"id\":\"a\",\"status\":\"open\"}\n{\"id\":\"b\",\"status\":\"failed\"}\n",
+        );
+        assert_eq!(run(&base, &ours, &theirs, None).unwrap(), 0);
+        let merged = std::fs::read_to_string(&ours).unwrap();
+        assert!(merged.contains("\"done\""));
+        assert!(merged.contains("\"failed\""));
+
+        let theirs = write(
+            "theirs2",
+            "{\"id\":\"a\",\"status\":\"failed\"}\n{\"id\":\"b\",\"status\":\"open\"}\n",
+        );
+        let ours = write(
+            "ours2",
+            "{\"id\":\"a\",\"status\":\"done\"}\n{\"id\":\"b\",\"status\":\"open\"}\n",
+        );
+        assert_eq!(run(&base, &ours, &theirs, None).unwrap(), 1);
+        let merged = std::fs::read_to_string(&ours).unwrap();
+        assert!(merged.contains("<<<<<<< ours"));
+    }
+
+    #[test]
+    fn test_describe_both_modified() {
+        let conflict = NodeConflict {
+            id: "a".to_string(),
+            kind: ConflictKind::BothModified {
+                fields: vec!["status".to_string()],
+            },
+            ours: Some(serde_json::json!({"id": "a", "status": "done"})),
+            theirs: Some(serde_json::json!({"id": "a", "status": "failed"})),
+        };
+        assert_eq!(
+            describe(&conflict),
+            "a: changed on both sides: status (ours: done, theirs: failed)"
+        );
+    }
+}
@@ -4,13 +4,17 @@ use anyhow::Result;
 use std::path::Path;
 
 use workgraph::metrics::get_metrics_snapshot;
+use workgraph::service::ExecutorHealth;
 
 /// Run the metrics command to display cleanup monitoring statistics.
-pub fn run(_dir: &Path, json: bool) -> Result<()> {
+pub fn run(dir: &Path, json: bool) -> Result<()> {
     let metrics = get_metrics_snapshot();
+    let executor_health = ExecutorHealth::load(dir);
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&metrics)?);
+        let mut output = serde_json::to_value(&metrics)?;
+        output["executor_health"] = serde_json::to_value(&executor_health.executors)?;
+        println!("{}", serde_json::to_string_pretty(&output)?);
     } else {
         println!("=== WG Cleanup Metrics ===");
         println!();
@@ -77,6 +81,18 @@ pub fn run(_dir: &Path, json: bool) -> Result<()> {
         } else {
             println!("No cleanup timing data available.");
         }
+
+        if !executor_health.executors.is_empty() {
+            println!();
+            println!("Executor Health:");
+            for (key, check) in &executor_health.executors {
+                if check.healthy {
+                    println!("  ✓ {}", key);
+                } else {
+                    println!("  ✗ {}: {}", key, check.problems.join("; "));
+                }
+            }
+        }
     }
 
     Ok(())
@@ -1,7 +1,8 @@
 //! Migration commands. Currently supports the chat-rename migration:
 //! rewrites legacy `.coordinator-N` task ids to `.chat-N`, fixes up
 //! after-edges, renames `coordinator-loop` tags to `chat-loop`, and
-//! rewrites `Coordinator: <name>` / `Coordinator N` titles.
+//! rewrites `Coordinator: <name>` / `Coordinator N` titles. Also rewrites
+//! stale config files and renamed record fields to canonical form.
 
 use anyhow::Result;
 use std::collections::HashMap;
@@ -10,8 +11,10 @@ use std::path::Path;
 use workgraph::chat_id::{
     CHAT_LOOP_TAG, CHAT_PREFIX, LEGACY_COORDINATOR_LOOP_TAG, LEGACY_COORDINATOR_PREFIX,
 };
+use workgraph::deprecation::{self, Tally};
+use workgraph::function::functions_dir;
 use workgraph::graph::LogEntry;
-use workgraph::parser::modify_graph;
+use workgraph::parser::{modify_graph, modify_graph_without_journal};
 
 use super::graph_path;
 
@@ -1303,3 +1306,174 @@ max_agents = 4
         assert!(!migrated.contains("[coordinator]"));
     }
 }
+
+// ---------------------------------------------------------------------------
+// `wg migrate fields` — rewrite renamed record fields to canonical names.
+// ---------------------------------------------------------------------------
+
+/// Renamed fields found (and, unless dry-run, rewritten) in one file.
+#[derive(Debug, Clone)]
+pub struct FieldMigrateResult {
+    pub path: std::path::PathBuf,
+    pub tally: Tally,
+    pub wrote: bool,
+}
+
+/// Rewrite old field names (`blocked_by`, `blocks`, `instantiated_at`, ...)
+/// in `graph.jsonl` and the function run logs to their canonical names.
+/// Idempotent — running twice is a no-op.
+pub fn run_fields(dir: &Path, dry_run: bool, json: bool) -> Result<()> {
+    // This command reports the counts itself.
+    deprecation::quiet();
+
+    let mut results = Vec::new();
+    let graph_path = graph_path(dir);
+    if graph_path.exists() {
+        results.push(migrate_graph_fields(&graph_path, dry_run)?);
+    }
+    let mut runs: Vec<_> = std::fs::read_dir(functions_dir(dir))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.to_string_lossy().ends_with(".runs.jsonl"))
+        .collect();
+    runs.sort();
+    for path in runs {
+        results.push(migrate_run_fields(&path, dry_run)?);
+    }
+
+    if json {
+        let payload: Vec<serde_json::Value> = results
+            .iter()
+            .map(|r| {
+                let renamed: Vec<_> = r
+                    .tally
+                    .uses()
+                    .map(|(rename, count)| {
+                        serde_json::json!({
+                            "old": rename.old,
+                            "new": rename.new,
+                            "count": count,
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "path": r.path.display().to_string(),
+                    "renamed": renamed,
+                    "wrote": r.wrote,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(());
+    }
+
+    let pending: Vec<_> = results.iter().filter(|r| !r.tally.is_empty()).collect();
+    if pending.is_empty() {
+        println!("No deprecated field names found — files are already canonical.");
+        return Ok(());
+    }
+    let prefix = if dry_run { "[dry-run] " } else { "" };
+    for r in pending {
+        println!("{}{}: {}", prefix, r.path.display(), r.tally);
+        if r.wrote {
+            println!("  ✓ rewrote with canonical names");
+        }
+    }
+    if dry_run {
+        println!("(dry-run — files not modified; rerun without --dry-run to apply)");
+    }
+    Ok(())
+}
+
+fn migrate_graph_fields(path: &Path, dry_run: bool) -> Result<FieldMigrateResult> {
+    let mut tally = Tally::new(&deprecation::TASKS);
+    for line in std::fs::read_to_string(path)?.lines() {
+        tally.scan_line(line.trim());
+    }
+    let wrote = !tally.is_empty() && !dry_run;
+    if wrote {
+        // Saving always writes canonical names, so an unchanged graph is
+        // enough. No task content changes, so there is nothing to journal.
+        modify_graph_without_journal(path, |_| true)?;
+    }
+    Ok(FieldMigrateResult {
+        path: path.to_path_buf(),
+        tally,
+        wrote,
+    })
+}
+
+fn migrate_run_fields(path: &Path, dry_run: bool) -> Result<FieldMigrateResult> {
+    let content = std::fs::read_to_string(path)?;
+    let mut tally = Tally::new(&deprecation::RUN_SUMMARIES);
+    let mut rewritten = String::with_capacity(content.len());
+    for line in content.lines() {
+        tally.scan_line(line.trim());
+        let renamed = serde_json::from_str::<serde_json::Value>(line)
+            .ok()
+            .and_then(|mut value| {
+                let changed =
+                    deprecation::rename_fields(value.as_object_mut()?, &deprecation::RUN_SUMMARIES);
+                changed.then(|| value.to_string())
+            });
+        rewritten.push_str(renamed.as_deref().unwrap_or(line));
+        rewritten.push('\n');
+    }
+    let wrote = !tally.is_empty() && !dry_run;
+    if wrote {
+        std::fs::write(path, rewritten)?;
+    }
+    Ok(FieldMigrateResult {
+        path: path.to_path_buf(),
+        tally,
+        wrote,
+    })
+}
+
+#[cfg(test)]
+mod fields_migrate_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn rewrites_aliased_task_and_run_fields() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        std::fs::write(
+            dir.join("graph.jsonl"),
+            concat!(
+                r#"{"kind":"task","id":"a","title":"A","status":"open","blocks":["b"]}"#,
+                "\n",
+                r#"{"kind":"task","id":"b","title":"B","status":"open","blocked_by":["a"]}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.join("functions")).unwrap();
+        let runs = dir.join("functions").join("f.runs.jsonl");
+        std::fs::write(
+            &runs,
+            "{\"instantiated_at\":\"2026-01-01T00:00:00Z\",\"prefix\":\"p-\"}\nnot json\n",
+        )
+        .unwrap();
+
+        run_fields(dir, true, false).unwrap();
+        assert!(
+            std::fs::read_to_string(dir.join("graph.jsonl"))
+                .unwrap()
+                .contains("blocked_by")
+        );
+
+        run_fields(dir, false, false).unwrap();
+        let graph = std::fs::read_to_string(dir.join("graph.jsonl")).unwrap();
+        assert!(!graph.contains("blocked_by") && !graph.contains("\"blocks\""));
+        let loaded = workgraph::parser::load_graph(dir.join("graph.jsonl")).unwrap();
+        assert_eq!(loaded.get_task("a").unwrap().before, vec!["b"]);
+        assert_eq!(loaded.get_task("b").unwrap().after, vec!["a"]);
+
+        let runs = std::fs::read_to_string(&runs).unwrap();
+        assert!(runs.contains("\"applied_at\"") && !runs.contains("instantiated_at"));
+        assert!(runs.ends_with("not json\n"));
+    }
+}
@@ -13,15 +13,23 @@ pub mod agency_stats;
 pub mod agent;
 pub mod agent_crud;
 pub mod agent_guide;
+pub mod agent_shell;
 pub mod agents;
 pub mod aging;
 pub mod analyze;
+pub mod anomalies;
 pub mod approve;
 pub mod archive;
 pub mod artifact;
 pub mod assign;
+pub mod at;
+pub mod availability;
+pub mod bench;
 pub mod blocked;
 pub mod bottlenecks;
+pub mod branch;
+pub mod cache;
+pub mod calendar;
 pub mod chat;
 pub mod chat_cmd;
 pub mod chat_session;
@@ -43,36 +51,50 @@ pub mod critical_path;
 pub mod cycles;
 pub mod dead_agents;
 pub mod dev_check;
+pub mod diff;
 pub mod discover;
 pub mod done;
 pub mod edit;
 pub mod endpoints;
 pub mod eval_scaffold;
 pub mod evaluate;
+pub mod event;
 pub mod evolve;
 pub mod exec;
+pub mod external_link;
 pub mod fail;
+pub mod feed;
+pub mod focus;
 pub mod forecast;
 pub mod func_apply;
 pub mod func_bootstrap;
 pub mod func_cmd;
+pub mod func_docs;
 pub mod func_extract;
 pub mod func_make_adaptive;
+pub mod func_review;
+pub mod gate;
 pub mod gc;
 pub mod graph;
+pub mod graph_import;
+pub mod graphs;
 pub mod heartbeat;
 pub mod impact;
+pub mod inbox;
 pub mod incomplete;
 pub mod init;
 pub mod insert;
 pub mod key;
 pub mod kill;
+pub mod learning;
 pub mod link;
 pub mod list;
 pub mod log;
 pub mod match_cmd;
 #[cfg(any(feature = "matrix", feature = "matrix-lite"))]
 pub mod matrix;
+pub mod merge;
+pub mod merge_driver;
 pub mod metrics;
 pub mod migrate;
 pub mod model_cmd;
@@ -83,12 +105,17 @@ pub mod nex;
 pub mod next;
 #[cfg(any(feature = "matrix", feature = "matrix-lite"))]
 pub mod notify;
+pub mod onboard;
 pub mod openrouter;
+pub mod overdue;
 pub mod pause;
 pub mod peer;
 pub mod placement;
 pub mod plan;
+pub mod portfolio;
 pub mod profile_cmd;
+pub mod progress;
+pub mod provenance_cmd;
 pub mod publish;
 pub mod quickstart;
 pub mod ready;
@@ -97,6 +124,7 @@ pub mod reclaim;
 pub mod recover;
 pub mod reject;
 pub mod replay;
+pub mod report;
 pub mod reprioritize;
 pub mod requeue;
 pub mod reschedule;
@@ -105,17 +133,22 @@ pub mod reset;
 pub mod resource;
 pub mod resources;
 pub mod resume;
+pub mod retention;
 pub mod retry;
 pub mod role;
+pub mod rubric;
 pub mod runs_cmd;
+pub mod schema_cmd;
 pub mod screencast_autopilot;
 pub mod screencast_render;
+pub mod search;
 pub mod secret_cmd;
 pub mod server;
 pub mod service;
 pub mod setup;
 pub mod show;
 pub mod skills;
+pub mod snapshot;
 pub mod spawn;
 pub mod spawn_task;
 pub mod spend;
@@ -123,6 +156,7 @@ pub mod stats;
 pub mod status;
 pub mod structure;
 pub mod sweep;
+pub mod task_csv;
 pub mod telegram;
 pub mod tokens;
 pub mod trace;
@@ -131,13 +165,18 @@ pub mod trace_export;
 pub mod trace_import;
 pub mod tradeoff;
 pub mod trajectory;
+pub mod triage;
 pub mod tui_nex;
 pub mod tui_pty;
+pub mod undo;
 pub mod user;
 pub mod velocity;
+pub mod view;
 pub mod viz;
 pub mod wait;
+pub mod waiting;
 pub mod watch;
+pub mod webhook_exec;
 pub mod why_blocked;
 pub mod workload;
 pub mod worktree_cmd;
@@ -160,6 +199,19 @@ pub fn load_workgraph(dir: &Path) -> Result<(workgraph::graph::WorkGraph, PathBu
     Ok((graph, path))
 }
 
+/// Load only some nodes of the WG task graph, chosen from its on-disk index
+/// (see `workgraph::parser::load_graph_subset`). For read-only commands.
+pub fn load_workgraph_subset<F>(dir: &Path, select: F) -> Result<workgraph::graph::WorkGraph>
+where
+    F: FnOnce(&workgraph::graph_index::GraphIndex) -> HashSet<String>,
+{
+    let path = graph_path(dir);
+    if !path.exists() {
+        anyhow::bail!("WG not initialized. Run 'wg init' first.");
+    }
+    workgraph::parser::load_graph_subset(&path, select).context("Failed to load graph")
+}
+
 /// Load the WG task graph (mutable) from the given directory.
 /// Returns the graph and the path to the graph file (needed for save_graph).
 pub fn load_workgraph_mut(dir: &Path) -> Result<(workgraph::graph::WorkGraph, PathBuf)> {
@@ -312,6 +364,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -369,6 +429,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -400,6 +468,17 @@ mod provenance_coverage_tests {
             None,  // cron
             false, // allow_phantom
             false, // allow_cycle
+            None,  // priority
+            None,  // due
+            &[],   // add_waiting_on
+            &[],   // remove_waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // external_blocker
+            None,  // blocker_contact
+            None,  // blocker_expected
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -460,6 +539,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -524,6 +611,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -582,6 +677,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -640,6 +743,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -698,6 +809,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -759,6 +878,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -822,6 +949,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
 
@@ -885,6 +1020,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
         super::done::run(dir, "prov-archive", false, false, false, false, false).unwrap();
@@ -945,6 +1088,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
         super::fail::run(dir, "prov-gc", Some("oops"), None).unwrap();
@@ -1018,6 +1169,14 @@ mod provenance_coverage_tests {
             None,  // priority
             None,  // cron
             false, // subtask
+            &[],   // expects
+            &[],   // requires
+            None,  // due
+            &[],   // waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
         // edit
@@ -1049,6 +1208,17 @@ mod provenance_coverage_tests {
             None,  // cron
             false, // allow_phantom
             false, // allow_cycle
+            None,  // priority
+            None,  // due
+            &[],   // add_waiting_on
+            &[],   // remove_waiting_on
+            &[],   // lag
+            &[],   // edge_weight
+            None,  // external_blocker
+            None,  // blocker_contact
+            None,  // blocker_expected
+            None,  // max_cost
+            None,  // min_context
         )
         .unwrap();
         // pause
@@ -3,14 +3,17 @@ use serde::Serialize;
 use std::collections::HashSet;
 use std::path::Path;
 use workgraph::agency;
-use workgraph::graph::TrustLevel;
-use workgraph::query::ready_tasks;
+use workgraph::config::Config;
+use workgraph::graph::{Priority, TrustLevel, aged_priority};
+use workgraph::query::{TaskQuery, ready_tasks};
 
 /// Candidate task for an agent
 #[derive(Debug, Serialize)]
 struct TaskCandidate {
     id: String,
     title: String,
+    /// Priority after aging
+    priority: Priority,
     score: i32,
     matched_skills: Vec<String>,
     missing_skills: Vec<String>,
@@ -28,9 +31,17 @@ struct NextTaskResult {
     alternatives: Vec<TaskCandidate>,
 }
 
-/// Find the best next task for an agent based on capabilities and readiness
-pub fn run(dir: &Path, agent_id: &str, json: bool) -> Result<()> {
+/// Find the best next task for an agent based on priority, capabilities and
+/// readiness. Priority (aged by `coordinator.priority_aging_hours`) orders
+/// candidates first; skill score breaks ties. With `view`, only tasks
+/// matching that saved view are considered; the view's sort order breaks
+/// remaining ties.
+pub fn run(dir: &Path, agent_id: &str, view: Option<&str>, json: bool) -> Result<()> {
     let (graph, _path) = super::load_workgraph(dir)?;
+    let aging_hours = Config::load_or_default(dir)
+        .coordinator
+        .priority_aging_hours;
+    let now = chrono::Utc::now();
 
     // Load agent from .wg/agency/agents/
     let agents_dir = dir.join("agency").join("cache/agents");
@@ -40,7 +51,16 @@ pub fn run(dir: &Path, agent_id: &str, json: bool) -> Result<()> {
     let agent_skills: HashSet<&String> = agent.capabilities.iter().collect();
 
     // Get ready tasks
-    let ready = ready_tasks(&graph);
+    let mut ready = ready_tasks(&graph);
+    if let Some(name) = view {
+        let query = super::view::resolve(dir, name)?;
+        ready.retain(|t| query.matches(t));
+        ready = TaskQuery {
+            limit: None,
+            ..query
+        }
+        .order(ready);
+    }
 
     // Score each task for this agent
     let mut candidates: Vec<TaskCandidate> = ready
@@ -95,6 +115,7 @@ pub fn run(dir: &Path, agent_id: &str, json: bool) -> Result<()> {
             TaskCandidate {
                 id: task.id.clone(),
                 title: task.title.clone(),
+                priority: aged_priority(task, now, aging_hours),
                 score,
                 matched_skills: matched,
                 missing_skills: missing,
@@ -104,8 +125,8 @@ pub fn run(dir: &Path, agent_id: &str, json: bool) -> Result<()> {
         })
         .collect();
 
-    // Sort by score descending
-    candidates.sort_by(|a, b| b.score.cmp(&a.score));
+    // Sort by priority, then score, descending
+    candidates.sort_by(|a, b| b.priority.cmp(&a.priority).then(b.score.cmp(&a.score)));
 
     // Filter to only tasks with non-negative score (at least partial capability match)
     // But include tasks with no skill requirements
@@ -169,7 +190,7 @@ fn print_candidate(task: &TaskCandidate) {
     };
 
     println!("  {} - {}{}{}", task.id, task.title, hours_str, inputs_str);
-    println!("    Score: {}", task.score);
+    println!("    Priority: {}  Score: {}", task.priority, task.score);
 
     if !task.matched_skills.is_empty() {
         println!("    Matched: {}", task.matched_skills.join(", "));
@@ -217,6 +238,8 @@ mod tests {
             deployment_history: vec![],
             attractor_weight: 0.5,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         }
     }
 
@@ -246,7 +269,7 @@ mod tests {
         let agent_id = agent.id.clone();
         setup_agents(temp_dir.path(), &[agent]);
 
-        let result = run(temp_dir.path(), &agent_id, false);
+        let result = run(temp_dir.path(), &agent_id, None, false);
         assert!(result.is_ok());
     }
 
@@ -267,7 +290,7 @@ mod tests {
         let agent_id = agent.id.clone();
         setup_agents(temp_dir.path(), &[agent]);
 
-        let result = run(temp_dir.path(), &agent_id, false);
+        let result = run(temp_dir.path(), &agent_id, None, false);
         assert!(result.is_ok()); // Should work but recommend nothing
     }
 
@@ -292,7 +315,7 @@ mod tests {
         let agent_id = agent.id.clone();
         setup_agents(temp_dir.path(), &[agent]);
 
-        let result = run(temp_dir.path(), &agent_id, true);
+        let result = run(temp_dir.path(), &agent_id, None, true);
         assert!(result.is_ok());
     }
 
@@ -311,7 +334,7 @@ mod tests {
         let agent_id = agent.id.clone();
         setup_agents(temp_dir.path(), &[agent]);
 
-        let result = run(temp_dir.path(), &agent_id, true);
+        let result = run(temp_dir.path(), &agent_id, None, true);
         assert!(result.is_ok());
     }
 }
@@ -242,6 +242,13 @@ fn format_notification(task: &Task, custom_message: Option<&str>) -> (String, St
         plain.push_str(&format!("\nFailure reason: {}\n", reason));
     }
 
+    if !task.links.is_empty() {
+        plain.push_str("\nLinks:\n");
+        for link in &task.links {
+            plain.push_str(&format!("  {}: {}\n", link.kind, link.url));
+        }
+    }
+
     // Action hints
     plain.push_str("\n---\n");
     plain.push_str("Reply with: claim | done | input <info> | help\n");
@@ -300,6 +307,26 @@ fn format_notification(task: &Task, custom_message: Option<&str>) -> (String, St
         ));
     }
 
+    if !task.links.is_empty() {
+        let links: Vec<String> = task
+            .links
+            .iter()
+            .filter(|l| l.url.starts_with("https://") || l.url.starts_with("http://"))
+            .map(|l| {
+                format!(
+                    "{}: <a href=\"{}\">{}</a>",
+                    l.kind,
+                    escape_html(&l.url),
+                    escape_html(l.label.as_deref().unwrap_or(&l.url))
+                )
+            })
+            .collect();
+        html.push_str(&format!(
+            "<p><strong>Links:</strong> {}</p>",
+            links.join(" | ")
+        ));
+    }
+
     // Action hints
     html.push_str("<hr>");
     html.push_str("<p><em>Reply with:</em> <code>claim</code> | <code>done</code> | <code>input &lt;info&gt;</code> | <code>help</code></p>");
@@ -317,7 +344,7 @@ fn escape_html(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use workgraph::graph::{PRIORITY_DEFAULT, Task};
+    use workgraph::graph::{LinkKind, PRIORITY_DEFAULT, Task, TaskLink};
 
     fn make_test_task() -> Task {
         Task {
@@ -330,19 +357,25 @@ mod tests {
             estimate: None,
             before: vec![],
             after: vec!["blocker-1".to_string()],
+            edges: Default::default(),
             requires: vec![],
             tags: vec![],
             skills: vec![],
             inputs: vec![],
             deliverables: vec![],
+            expects: vec![],
+            gate: None,
             artifacts: vec![],
+            self_report: None,
             exec: None,
             timeout: None,
             not_before: None,
+            due: None,
             created_at: None,
             started_at: None,
             completed_at: None,
             last_interaction_at: None,
+            revision: 0,
             log: vec![],
             retry_count: 0,
             max_retries: None,
@@ -367,10 +400,20 @@ mod tests {
             exec_mode: None,
             cycle_config: None,
             token_usage: None,
+            max_cost: None,
+            min_context: None,
+            claim_sha: None,
+            changes: None,
             session_id: None,
             wait_condition: None,
             checkpoint: None,
             triage_count: 0,
+            escalation_level: 0,
+            blocked_at: None,
+            deadline_alert: None,
+            external_blocker: None,
+            waiting_on: vec![],
+            links: vec![],
             resurrection_count: 0,
             last_resurrected_at: None,
             validation: None,
@@ -444,6 +487,21 @@ mod tests {
         assert!(html.contains("Build failed"));
     }
 
+    #[test]
+    fn test_format_notification_links() {
+        let mut task = make_test_task();
+        task.links = vec![TaskLink {
+            kind: LinkKind::Pr,
+            url: "https://example.com/pull/7".to_string(),
+            label: Some("Fix parser".to_string()),
+        }];
+
+        let (plain, html) = format_notification(&task, None);
+
+        assert!(plain.contains("pr: https://example.com/pull/7"));
+        assert!(html.contains("pr: <a href=\"https://example.com/pull/7\">Fix parser</a>"));
+    }
+
     #[test]
     fn test_escape_html() {
         assert_eq!(escape_html("<script>"), "&lt;script&gt;");
@@ -0,0 +1,335 @@
+//! `wg onboard` — generate an onboarding brief for a new agent or contributor.
+//!
+//! The brief collects, in one place, what a newcomer needs before picking up
+//! work: a project summary, conventions pulled from config and the project's
+//! instruction files, ready work matching their skills, and the claim/complete
+//! workflow. The same brief can be injected into executor prompts when
+//! `agent.onboarding_brief` is enabled.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+use workgraph::agency;
+use workgraph::config::Config;
+use workgraph::graph::{Task, WorkGraph, is_system_task};
+use workgraph::query::{project_summary, ready_tasks};
+
+/// Instruction files (relative to the project root) scanned for conventions.
+const CONVENTION_SOURCES: &[&str] = &["CLAUDE.md", "AGENTS.md", "CONTRIBUTING.md"];
+
+/// Maximum number of convention bullets taken from a single file.
+const MAX_BULLETS_PER_SOURCE: usize = 10;
+
+#[derive(Debug, Serialize)]
+pub struct OnboardingBrief {
+    pub project: ProjectInfo,
+    pub conventions: Vec<String>,
+    pub skills: Vec<String>,
+    pub ready_work: Vec<ReadyItem>,
+    pub workflow: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub open: usize,
+    pub in_progress: usize,
+    pub ready: usize,
+    pub blocked: usize,
+    pub done: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadyItem {
+    pub id: String,
+    pub title: String,
+    pub priority: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub matched_skills: Vec<String>,
+}
+
+/// Build an onboarding brief for a newcomer with the given skills.
+///
+/// Ready tasks are ranked by skill overlap, then priority. Tasks with no
+/// required skills are always eligible; tasks whose skills do not overlap
+/// at all are omitted when the newcomer declared any skills.
+pub fn build_brief(
+    dir: &Path,
+    graph: &WorkGraph,
+    config: &Config,
+    skills: &[String],
+    limit: usize,
+) -> OnboardingBrief {
+    let summary = project_summary(graph);
+
+    let mut ready: Vec<(&Task, Vec<String>)> = ready_tasks(graph)
+        .into_iter()
+        .filter(|t| !is_system_task(&t.id))
+        .filter_map(|t| {
+            let matched: Vec<String> = t
+                .skills
+                .iter()
+                .filter(|s| skills.iter().any(|k| k.eq_ignore_ascii_case(s)))
+                .cloned()
+                .collect();
+            if skills.is_empty() || t.skills.is_empty() || !matched.is_empty() {
+                Some((t, matched))
+            } else {
+                None
+            }
+        })
+        .collect();
+    ready.sort_by(|(a, am), (b, bm)| {
+        bm.len()
+            .cmp(&am.len())
+            .then(b.priority.cmp(&a.priority))
+            .then(a.id.cmp(&b.id))
+    });
+    ready.truncate(limit);
+
+    OnboardingBrief {
+        project: ProjectInfo {
+            name: config.project.name.clone(),
+            description: config.project.description.clone(),
+            open: summary.open,
+            in_progress: summary.in_progress,
+            ready: summary.ready,
+            blocked: summary.blocked,
+            done: summary.done,
+        },
+        conventions: collect_conventions(dir, config),
+        skills: skills.to_vec(),
+        ready_work: ready
+            .into_iter()
+            .map(|(t, matched)| ReadyItem {
+                id: t.id.clone(),
+                title: t.title.clone(),
+                priority: t.priority,
+                matched_skills: matched,
+            })
+            .collect(),
+        workflow: workflow_steps(),
+    }
+}
+
+/// Conventions derived from config plus bullets under "Conventions"-style
+/// headings in the project's instruction files.
+fn collect_conventions(dir: &Path, config: &Config) -> Vec<String> {
+    let mut out = Vec::new();
+
+    if config.coordinator.worktree_isolation {
+        out.push("Agents work in isolated git worktrees; commit only your own files.".to_string());
+    }
+    if !config.project.default_skills.is_empty() {
+        out.push(format!(
+            "Default skills for new tasks: {}",
+            config.project.default_skills.join(", ")
+        ));
+    }
+    out.push(format!(
+        "Decompose with at most {} subtasks per agent, {} levels deep.",
+        config.guardrails.max_child_tasks_per_agent, config.guardrails.max_task_depth
+    ));
+
+    let project_root = dir.parent().unwrap_or(dir);
+    for name in CONVENTION_SOURCES {
+        if let Ok(content) = std::fs::read_to_string(project_root.join(name)) {
+            out.extend(extract_convention_bullets(&content));
+        }
+    }
+    if let Ok(content) = std::fs::read_to_string(dir.join("wg-guide.md")) {
+        out.extend(extract_convention_bullets(&content));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    out.retain(|c| seen.insert(c.clone()));
+    out
+}
+
+/// Extract top-level bullet items from markdown sections whose heading
+/// mentions conventions, rules, or guidelines.
+pub fn extract_convention_bullets(markdown: &str) -> Vec<String> {
+    let mut bullets = Vec::new();
+    let mut in_section = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_end();
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim().to_ascii_lowercase();
+            in_section = ["convention", "rule", "guideline", "style"]
+                .iter()
+                .any(|k| heading.contains(k));
+            continue;
+        }
+        if !in_section || bullets.len() >= MAX_BULLETS_PER_SOURCE {
+            continue;
+        }
+        if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            let item = item.trim();
+            if !item.is_empty() {
+                bullets.push(item.to_string());
+            }
+        }
+    }
+    bullets
+}
+
+fn workflow_steps() -> Vec<String> {
+    [
+        "Pick a task: `wg show <id>` to read its description and context",
+        "Claim it: `wg claim <id>` (marks it in-progress under your name)",
+        "Record progress: `wg log <id> \"message\"` as you go",
+        "Attach outputs: `wg artifact <id> <path>` for files you produce",
+        "Finish: `wg done <id>` — or `wg fail <id> --reason \"...\"` if blocked",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+/// Render the brief as markdown. Used for human output and for prompt injection.
+pub fn render_markdown(brief: &OnboardingBrief) -> String {
+    let mut out = String::new();
+
+    out.push_str("## Onboarding Brief\n\n### Project\n\n");
+    if let Some(ref name) = brief.project.name {
+        out.push_str(&format!("**{}**\n\n", name));
+    }
+    if let Some(ref desc) = brief.project.description {
+        out.push_str(&format!("{}\n\n", desc));
+    }
+    out.push_str(&format!(
+        "{} open ({} ready, {} blocked), {} in progress, {} done\n",
+        brief.project.open,
+        brief.project.ready,
+        brief.project.blocked,
+        brief.project.in_progress,
+        brief.project.done
+    ));
+
+    if !brief.conventions.is_empty() {
+        out.push_str("\n### Conventions\n\n");
+        for c in &brief.conventions {
+            out.push_str(&format!("- {}\n", c));
+        }
+    }
+
+    out.push_str("\n### Ready work");
+    if !brief.skills.is_empty() {
+        out.push_str(&format!(" matching {}", brief.skills.join(", ")));
+    }
+    out.push_str("\n\n");
+    if brief.ready_work.is_empty() {
+        out.push_str("Nothing ready right now.\n");
+    }
+    for item in &brief.ready_work {
+        out.push_str(&format!(
+            "- **{}**: {} (priority {})",
+            item.id, item.title, item.priority
+        ));
+        if !item.matched_skills.is_empty() {
+            out.push_str(&format!(" [{}]", item.matched_skills.join(", ")));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("\n### How to work\n\n");
+    for (i, step) in brief.workflow.iter().enumerate() {
+        out.push_str(&format!("{}. {}\n", i + 1, step));
+    }
+    out
+}
+
+pub fn run(
+    dir: &Path,
+    skills: &[String],
+    agent: Option<&str>,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let config = Config::load_or_default(dir);
+
+    let mut skills = skills.to_vec();
+    if let Some(agent_ref) = agent {
+        let agents_dir = dir.join("agency").join("cache/agents");
+        let agent = agency::find_agent_by_prefix(&agents_dir, agent_ref)?;
+        for cap in agent.capabilities {
+            if !skills.contains(&cap) {
+                skills.push(cap);
+            }
+        }
+    }
+
+    let brief = build_brief(dir, &graph, &config, &skills, limit);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&brief)?);
+    } else {
+        print!("{}", render_markdown(&brief));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use workgraph::graph::{Node, Status};
+
+    fn make_task(id: &str, skills: &[&str], priority: u32) -> Task {
+        Task {
+            id: id.to_string(),
+            title: format!("Task {}", id),
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            priority,
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn test_extract_convention_bullets() {
+        let md = "# Intro\n- not this\n## Code Conventions\n- Use anyhow\n* Run clippy\n\n## Other\n- nope\n";
+        assert_eq!(
+            extract_convention_bullets(md),
+            vec!["Use anyhow".to_string(), "Run clippy".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_ready_work_ranked_by_skill_overlap() {
+        let dir = tempdir().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("a", &["python"], 50)));
+        graph.add_node(Node::Task(make_task("b", &["rust", "cli"], 10)));
+        graph.add_node(Node::Task(make_task("c", &["rust"], 10)));
+        graph.add_node(Node::Task(make_task("d", &[], 5)));
+        let mut done = make_task("e", &["rust"], 10);
+        done.status = Status::Done;
+        graph.add_node(Node::Task(done));
+
+        let skills = vec!["rust".to_string(), "cli".to_string()];
+        let brief = build_brief(dir.path(), &graph, &Config::default(), &skills, 10);
+        let ids: Vec<&str> = brief.ready_work.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c", "d"]);
+        assert_eq!(brief.ready_work[0].matched_skills, vec!["rust", "cli"]);
+    }
+
+    #[test]
+    fn test_render_markdown_includes_sections() {
+        let dir = tempdir().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("t1", &[], 10)));
+        let brief = build_brief(dir.path(), &graph, &Config::default(), &[], 5);
+        let md = render_markdown(&brief);
+        assert!(md.contains("## Onboarding Brief"));
+        assert!(md.contains("### Conventions"));
+        assert!(md.contains("**t1**"));
+        assert!(md.contains("wg claim <id>"));
+    }
+}
@@ -0,0 +1,187 @@
+//! `wg overdue` — tasks past their deadline and milestones at risk.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::Path;
+use workgraph::deadline::{self, DeadlineAlert, DeadlineBreach};
+use workgraph::graph::{Status, parse_delay};
+use workgraph::notify::config::NotifyConfig;
+use workgraph::working_hours::WorkingHours;
+
+/// A task due inside the `--soon` window
+#[derive(Debug, Serialize)]
+struct DueSoon {
+    task_id: String,
+    title: String,
+    status: Status,
+    due: String,
+    remaining_secs: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct OverdueOutput {
+    overdue: Vec<DeadlineBreach>,
+    at_risk: Vec<DeadlineBreach>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due_soon: Option<Vec<DueSoon>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alerts_sent: Option<Vec<DeadlineBreach>>,
+}
+
+pub fn run(dir: &Path, soon: Option<&str>, notify: bool, json: bool) -> Result<()> {
+    let (graph, path) = super::load_workgraph(dir)?;
+    let now = Utc::now();
+
+    let window = soon
+        .map(|s| {
+            parse_delay(s)
+                .ok_or_else(|| anyhow::anyhow!("Invalid window '{}'. Use format: 30m, 12h, 2d", s))
+        })
+        .transpose()?;
+
+    let (overdue, at_risk): (Vec<_>, Vec<_>) =
+        deadline::check(&graph, now, &WorkingHours::load_or_warn(dir))
+            .into_iter()
+            .partition(|b| b.kind == DeadlineAlert::Overdue);
+    let due_soon = window.map(|secs| {
+        deadline::due_within(&graph, now, secs)
+            .into_iter()
+            .filter_map(|t| {
+                let due = deadline::due_at(t)?;
+                Some(DueSoon {
+                    task_id: t.id.clone(),
+                    title: t.title.clone(),
+                    status: t.status,
+                    due: due.to_rfc3339(),
+                    remaining_secs: (due - now).num_seconds(),
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let alerts_sent = if notify {
+        let config = NotifyConfig::load(dir.parent())?;
+        let sent = deadline::run_sweep(dir, &path, config.as_ref(), false)?;
+        if !sent.is_empty() {
+            super::notify_graph_changed(dir);
+        }
+        Some(sent)
+    } else {
+        None
+    };
+
+    if json {
+        let output = OverdueOutput {
+            overdue,
+            at_risk,
+            due_soon,
+            alerts_sent,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if overdue.is_empty() {
+        println!("No overdue tasks");
+    } else {
+        println!("Overdue ({}):", overdue.len());
+        for b in &overdue {
+            println!(
+                "  {} [{}] due {} — {} overdue",
+                b.task_id,
+                b.status,
+                short_time(&b.due),
+                workgraph::format_duration(b.late_secs, true)
+            );
+            println!("    {}", b.title);
+        }
+    }
+
+    if !at_risk.is_empty() {
+        println!("\nMilestones at risk ({}):", at_risk.len());
+        for b in &at_risk {
+            println!(
+                "  {} due {} — forecast {} ({} late)",
+                b.task_id,
+                short_time(&b.due),
+                b.forecast.as_deref().map(short_time).unwrap_or_default(),
+                workgraph::format_duration(b.slip_secs.unwrap_or(0), true)
+            );
+            println!("    {}", b.title);
+        }
+    }
+
+    if let Some(ref due_soon) = due_soon {
+        println!("\nDue within {}:", soon.unwrap_or_default());
+        if due_soon.is_empty() {
+            println!("  (none)");
+        }
+        for t in due_soon {
+            println!(
+                "  {} [{}] due {} — in {}",
+                t.task_id,
+                t.status,
+                short_time(&t.due),
+                workgraph::format_duration(t.remaining_secs, true)
+            );
+        }
+    }
+
+    if let Some(ref sent) = alerts_sent {
+        if sent.is_empty() {
+            println!("\nAlerts: none new");
+        } else {
+            println!("\nAlerts sent:");
+            for b in sent {
+                println!("  - {} ({})", b.task_id, b.kind);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn short_time(ts: &str) -> String {
+    ts.parse::<DateTime<Utc>>()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| ts.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::TempDir;
+    use workgraph::graph::{Node, WorkGraph};
+    use workgraph::parser::{load_graph, save_graph};
+    use workgraph::test_helpers::make_task;
+
+    #[test]
+    fn test_run_lists_and_alerts_overdue() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut graph = WorkGraph::new();
+        let mut late = make_task("late", "Late task");
+        late.due = Some((Utc::now() - Duration::hours(2)).to_rfc3339());
+        graph.add_node(Node::Task(late));
+        let mut upcoming = make_task("upcoming", "Upcoming task");
+        upcoming.due = Some((Utc::now() + Duration::hours(6)).to_rfc3339());
+        graph.add_node(Node::Task(upcoming));
+        save_graph(&graph, dir.join("graph.jsonl")).unwrap();
+
+        assert!(run(&dir, Some("1x"), false, false).is_err());
+        run(&dir, Some("1d"), false, true).unwrap();
+        let graph = load_graph(dir.join("graph.jsonl")).unwrap();
+        assert_eq!(graph.get_task("late").unwrap().deadline_alert, None);
+
+        run(&dir, None, true, false).unwrap();
+        let graph = load_graph(dir.join("graph.jsonl")).unwrap();
+        assert_eq!(
+            graph.get_task("late").unwrap().deadline_alert,
+            Some(DeadlineAlert::Overdue)
+        );
+        assert_eq!(graph.get_task("upcoming").unwrap().deadline_alert, None);
+    }
+}
@@ -201,6 +201,28 @@ fn print_human_output(
     }
 }
 
+/// `wg plan today`: propose today's plan for `actor` (default: current user).
+pub fn run_today(dir: &Path, actor: Option<&str>, post: bool, json: bool) -> Result<()> {
+    super::load_workgraph(dir)?;
+    let who = actor
+        .map(String::from)
+        .unwrap_or_else(workgraph::current_user);
+    let plan = workgraph::day_plan::build_for(dir, &who, chrono::Utc::now())?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+    } else {
+        println!("{}", plan.message());
+    }
+    if post {
+        workgraph::day_plan::post(dir, &plan)?;
+        if !json {
+            println!("Posted to Matrix.");
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -376,6 +398,14 @@ mod tests {
         assert!(run(temp_dir.path(), None, None, false).is_err());
     }
 
+    #[test]
+    fn test_run_today() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_plan_graph(temp_dir.path());
+        assert!(run_today(temp_dir.path(), Some("alice"), false, false).is_ok());
+        assert!(run_today(temp_dir.path(), Some("alice"), false, true).is_ok());
+    }
+
     #[test]
     fn test_run_budget_and_hours() {
         let temp_dir = TempDir::new().unwrap();
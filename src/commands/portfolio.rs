@@ -0,0 +1,451 @@
+//! `wg portfolio` — status, forecast and risk across several workgraphs.
+//!
+//! Each root is a project directory containing `.wg/` (or the `.wg/`
+//! directory itself). Projects are read independently: one that fails to
+//! load is reported with its error and the rest still aggregate.
+
+use anyhow::Result;
+use chrono::Utc;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use workgraph::deadline::{self, DeadlineAlert};
+use workgraph::graph::{Status, Task, WorkGraph};
+use workgraph::parser::load_graph;
+use workgraph::working_hours::WorkingHours;
+
+use super::forecast::calculate_forecast;
+use super::progress::calculate_progress;
+
+/// How many items each drill-down list shows
+const DRILL_DOWN_LIMIT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl std::fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RiskLevel::Low => "low",
+            RiskLevel::Medium => "medium",
+            RiskLevel::High => "high",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A task named in a project's drill-down
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskRef {
+    pub id: String,
+    pub title: String,
+}
+
+/// One project of the portfolio
+#[derive(Debug, Serialize)]
+pub struct ProjectSummary {
+    pub name: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub total_tasks: usize,
+    pub open_tasks: usize,
+    pub in_progress_tasks: usize,
+    pub done_tasks: usize,
+    pub failed_tasks: usize,
+    /// Estimate-weighted percent complete (as `wg progress`)
+    pub percent_complete: f64,
+    pub remaining_hours: f64,
+    /// Realistic-scenario completion date from `wg forecast`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forecast_completion: Option<String>,
+    pub risk: RiskLevel,
+    pub risk_reasons: Vec<String>,
+    pub overdue: Vec<TaskRef>,
+    pub at_risk: Vec<TaskRef>,
+    pub failed: Vec<TaskRef>,
+    pub waiting_external: Vec<TaskRef>,
+    /// Tasks holding up the most downstream work
+    pub key_blockers: Vec<TaskRef>,
+}
+
+/// Totals across all readable projects
+#[derive(Debug, Default, Serialize)]
+pub struct PortfolioTotals {
+    pub projects: usize,
+    pub total_tasks: usize,
+    pub open_tasks: usize,
+    pub in_progress_tasks: usize,
+    pub done_tasks: usize,
+    pub failed_tasks: usize,
+    pub remaining_hours: f64,
+    pub overdue: usize,
+    pub at_risk: usize,
+    pub high_risk_projects: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PortfolioOutput {
+    pub projects: Vec<ProjectSummary>,
+    pub totals: PortfolioTotals,
+}
+
+/// The `.wg` directory of a root given on the command line. `~/` is
+/// expanded here since the shell only does so for the first of the
+/// comma-separated roots.
+fn resolve_root(root: &str) -> Option<PathBuf> {
+    let path = match (root.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(suffix), Some(home)) => home.join(suffix),
+        _ => PathBuf::from(root),
+    };
+    if path.join("graph.jsonl").is_file() {
+        return Some(path);
+    }
+    let wg_dir = path.join(".wg");
+    wg_dir.join("graph.jsonl").is_file().then_some(wg_dir)
+}
+
+/// Display name of a project: its directory, not the `.wg` inside it
+fn project_name(root: &str, wg_dir: Option<&Path>) -> String {
+    let dir = wg_dir
+        .filter(|d| d.file_name().is_some_and(|n| n == ".wg"))
+        .and_then(Path::parent)
+        .unwrap_or_else(|| Path::new(root));
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    dir.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| root.to_string())
+}
+
+fn task_ref(task: &Task) -> TaskRef {
+    TaskRef {
+        id: task.id.clone(),
+        title: task.title.clone(),
+    }
+}
+
+/// Summarize one project's graph
+pub fn summarize_project(
+    name: String,
+    path: String,
+    graph: &WorkGraph,
+    calendars: &WorkingHours,
+) -> ProjectSummary {
+    let now = Utc::now();
+    let tasks: Vec<&Task> = graph.tasks().collect();
+    let progress = calculate_progress(name.clone(), &tasks, now);
+    let forecast = calculate_forecast(graph);
+
+    let count = |f: fn(&Task) -> bool| tasks.iter().filter(|t| f(t)).count();
+    let refs = |f: &dyn Fn(&Task) -> bool| -> Vec<TaskRef> {
+        tasks.iter().filter(|t| f(t)).map(|t| task_ref(t)).collect()
+    };
+
+    let (overdue, at_risk): (Vec<_>, Vec<_>) = deadline::check(graph, now, calendars)
+        .into_iter()
+        .partition(|b| b.kind == DeadlineAlert::Overdue);
+    let as_refs = |breaches: Vec<deadline::DeadlineBreach>| -> Vec<TaskRef> {
+        breaches
+            .into_iter()
+            .map(|b| TaskRef {
+                id: b.task_id,
+                title: b.title,
+            })
+            .collect()
+    };
+    let overdue = as_refs(overdue);
+    let at_risk = as_refs(at_risk);
+    let failed = refs(&|t| t.status == Status::Failed);
+    let waiting_external = refs(&|t| t.external_blocker.is_some() && !t.status.is_terminal());
+
+    let mut risk_reasons = Vec::new();
+    if !overdue.is_empty() {
+        risk_reasons.push(format!("{} overdue", overdue.len()));
+    }
+    if !at_risk.is_empty() {
+        risk_reasons.push(format!("{} milestone(s) forecast to slip", at_risk.len()));
+    }
+    if !failed.is_empty() {
+        risk_reasons.push(format!("{} failed", failed.len()));
+    }
+    if !waiting_external.is_empty() {
+        risk_reasons.push(format!(
+            "{} waiting on external blockers",
+            waiting_external.len()
+        ));
+    }
+    let risk = if !overdue.is_empty() || !at_risk.is_empty() {
+        RiskLevel::High
+    } else if !failed.is_empty() || !waiting_external.is_empty() {
+        RiskLevel::Medium
+    } else {
+        RiskLevel::Low
+    };
+
+    ProjectSummary {
+        name,
+        path,
+        error: None,
+        total_tasks: tasks.len(),
+        open_tasks: count(|t| !t.status.is_terminal() && t.status != Status::InProgress),
+        in_progress_tasks: count(|t| t.status == Status::InProgress),
+        done_tasks: count(|t| t.status == Status::Done),
+        failed_tasks: failed.len(),
+        percent_complete: progress.percent_complete,
+        remaining_hours: progress.remaining_hours,
+        forecast_completion: forecast
+            .scenarios
+            .get(1)
+            .and_then(|s| s.completion_date.clone()),
+        risk,
+        risk_reasons,
+        overdue,
+        at_risk,
+        failed,
+        waiting_external,
+        key_blockers: forecast
+            .blockers
+            .iter()
+            .take(DRILL_DOWN_LIMIT)
+            .map(|b| TaskRef {
+                id: b.id.clone(),
+                title: b.title.clone(),
+            })
+            .collect(),
+    }
+}
+
+fn unreadable_project(name: String, path: String, error: String) -> ProjectSummary {
+    ProjectSummary {
+        name,
+        path,
+        error: Some(error),
+        total_tasks: 0,
+        open_tasks: 0,
+        in_progress_tasks: 0,
+        done_tasks: 0,
+        failed_tasks: 0,
+        percent_complete: 0.0,
+        remaining_hours: 0.0,
+        forecast_completion: None,
+        risk: RiskLevel::High,
+        risk_reasons: vec!["project could not be read".to_string()],
+        overdue: Vec::new(),
+        at_risk: Vec::new(),
+        failed: Vec::new(),
+        waiting_external: Vec::new(),
+        key_blockers: Vec::new(),
+    }
+}
+
+/// Build the portfolio report for `roots`, riskiest projects first
+pub fn calculate_portfolio(roots: &[String]) -> PortfolioOutput {
+    let mut projects: Vec<ProjectSummary> = roots
+        .iter()
+        .map(|root| {
+            let wg_dir = resolve_root(root);
+            let name = project_name(root, wg_dir.as_deref());
+            let Some(wg_dir) = wg_dir else {
+                return unreadable_project(
+                    name,
+                    root.clone(),
+                    format!("No workgraph at '{}'", root),
+                );
+            };
+            let path = wg_dir.display().to_string();
+            match load_graph(wg_dir.join("graph.jsonl")) {
+                Ok(graph) => {
+                    summarize_project(name, path, &graph, &WorkingHours::load_or_warn(&wg_dir))
+                }
+                Err(e) => unreadable_project(name, path, e.to_string()),
+            }
+        })
+        .collect();
+    projects.sort_by(|a, b| b.risk.cmp(&a.risk).then_with(|| a.name.cmp(&b.name)));
+
+    let mut totals = PortfolioTotals::default();
+    for p in projects.iter().filter(|p| p.error.is_none()) {
+        totals.projects += 1;
+        totals.total_tasks += p.total_tasks;
+        totals.open_tasks += p.open_tasks;
+        totals.in_progress_tasks += p.in_progress_tasks;
+        totals.done_tasks += p.done_tasks;
+        totals.failed_tasks += p.failed_tasks;
+        totals.remaining_hours += p.remaining_hours;
+        totals.overdue += p.overdue.len();
+        totals.at_risk += p.at_risk.len();
+        if p.risk == RiskLevel::High {
+            totals.high_risk_projects += 1;
+        }
+    }
+
+    PortfolioOutput { projects, totals }
+}
+
+pub fn run(roots: &[String], project: Option<&str>, json: bool) -> Result<()> {
+    if roots.is_empty() {
+        anyhow::bail!("Give at least one workgraph with --roots");
+    }
+    let mut portfolio = calculate_portfolio(roots);
+    if let Some(name) = project {
+        portfolio.projects.retain(|p| p.name == name);
+        if portfolio.projects.is_empty() {
+            anyhow::bail!("No project named '{}' in the portfolio", name);
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&portfolio)?);
+    } else {
+        print_human_output(&portfolio, project.is_some());
+    }
+    Ok(())
+}
+
+fn print_refs(label: &str, refs: &[TaskRef], limit: usize) {
+    if refs.is_empty() {
+        return;
+    }
+    println!("    {}:", label);
+    for r in refs.iter().take(limit) {
+        println!("      {} — {}", r.id, r.title);
+    }
+    if refs.len() > limit {
+        println!("      ... and {} more", refs.len() - limit);
+    }
+}
+
+fn print_human_output(portfolio: &PortfolioOutput, single: bool) {
+    if !single {
+        let t = &portfolio.totals;
+        println!("Portfolio ({} projects):\n", t.projects);
+        println!(
+            "  Tasks: {} total, {} open, {} in progress, {} done, {} failed",
+            t.total_tasks, t.open_tasks, t.in_progress_tasks, t.done_tasks, t.failed_tasks
+        );
+        println!("  Remaining: {:.1}h", t.remaining_hours);
+        println!(
+            "  Risk: {} high-risk project(s), {} overdue task(s), {} milestone(s) at risk",
+            t.high_risk_projects, t.overdue, t.at_risk
+        );
+        println!();
+        println!(
+            "  {:<24} {:>6} {:>9} {:>10} {:>12}  RISK",
+            "PROJECT", "DONE", "TASKS", "REMAINING", "FORECAST"
+        );
+        for p in &portfolio.projects {
+            if let Some(ref err) = p.error {
+                println!("  {:<24} error: {}", p.name, err);
+                continue;
+            }
+            println!(
+                "  {:<24} {:>5.0}% {:>9} {:>9.1}h {:>12}  {}",
+                p.name,
+                p.percent_complete,
+                format!("{}/{}", p.done_tasks, p.total_tasks),
+                p.remaining_hours,
+                p.forecast_completion.as_deref().unwrap_or("-"),
+                p.risk
+            );
+        }
+        println!();
+    }
+
+    // Drill-down, riskiest first
+    let limit = if single { usize::MAX } else { DRILL_DOWN_LIMIT };
+    for p in &portfolio.projects {
+        if p.error.is_some() || (!single && p.risk == RiskLevel::Low) {
+            continue;
+        }
+        println!("  {} [{} risk] — {}", p.name, p.risk, p.path);
+        if !p.risk_reasons.is_empty() {
+            println!("    {}", p.risk_reasons.join(", "));
+        }
+        print_refs("Overdue", &p.overdue, limit);
+        print_refs("Milestones at risk", &p.at_risk, limit);
+        print_refs("Failed", &p.failed, limit);
+        print_refs("Waiting on external blockers", &p.waiting_external, limit);
+        print_refs("Key blockers", &p.key_blockers, limit);
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workgraph::graph::Node;
+    use workgraph::parser::save_graph;
+    use workgraph::test_helpers::make_task_with_status as make_task;
+
+    fn write_project(root: &Path, tasks: Vec<Task>) {
+        let wg_dir = root.join(".wg");
+        std::fs::create_dir_all(&wg_dir).unwrap();
+        let mut graph = WorkGraph::new();
+        for task in tasks {
+            graph.add_node(Node::Task(task));
+        }
+        save_graph(&graph, wg_dir.join("graph.jsonl")).unwrap();
+    }
+
+    #[test]
+    fn test_portfolio_aggregates_and_ranks_by_risk() {
+        let tmp = tempfile::tempdir().unwrap();
+        let calm = tmp.path().join("calm");
+        let late = tmp.path().join("late");
+        write_project(
+            &calm,
+            vec![
+                make_task("a", "A", Status::Done),
+                make_task("b", "B", Status::Open),
+            ],
+        );
+        let mut overdue = make_task("ship", "Ship it", Status::Open);
+        overdue.due = Some((Utc::now() - chrono::Duration::days(1)).to_rfc3339());
+        write_project(
+            &late,
+            vec![overdue, make_task("broken", "Broken", Status::Failed)],
+        );
+
+        let roots = vec![
+            calm.display().to_string(),
+            late.display().to_string(),
+            tmp.path().join("missing").display().to_string(),
+        ];
+        let portfolio = calculate_portfolio(&roots);
+
+        let names: Vec<&str> = portfolio.projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["late", "missing", "calm"]);
+        let late = &portfolio.projects[0];
+        assert_eq!(late.risk, RiskLevel::High);
+        assert_eq!(late.overdue[0].id, "ship");
+        assert_eq!(late.failed_tasks, 1);
+        assert!(portfolio.projects[1].error.is_some());
+        assert_eq!(portfolio.projects[2].risk, RiskLevel::Low);
+        assert_eq!(portfolio.projects[2].percent_complete, 50.0);
+
+        assert_eq!(portfolio.totals.projects, 2);
+        assert_eq!(portfolio.totals.total_tasks, 4);
+        assert_eq!(portfolio.totals.overdue, 1);
+        assert_eq!(portfolio.totals.high_risk_projects, 1);
+    }
+
+    #[test]
+    fn test_resolve_root_accepts_project_or_wg_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_project(tmp.path(), vec![]);
+        let wg_dir = tmp.path().join(".wg");
+        assert_eq!(
+            resolve_root(&tmp.path().display().to_string()),
+            Some(wg_dir.clone())
+        );
+        assert_eq!(resolve_root(&wg_dir.display().to_string()), Some(wg_dir));
+        assert_eq!(
+            resolve_root(&tmp.path().join("nope").display().to_string()),
+            None
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! `wg progress` — estimate-weighted completion for a subtree or tag.
+//!
+//! A subtree is a task plus everything it transitively depends on, as in
+//! `wg cost`. Each task counts by its hour estimate; tasks without one count
+//! as the average estimate of the set (one hour if nothing is estimated).
+//! Abandoned tasks are left out. The trend compares against the same set as
+//! it stood a week ago, using `created_at` and `completed_at`.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use workgraph::graph::{Status, Task, WorkGraph};
+
+/// Weight of an unestimated task when no task in the set has an estimate
+const FALLBACK_HOURS: f64 = 1.0;
+
+/// Estimate-weighted progress of a set of tasks
+#[derive(Debug, Serialize)]
+pub struct ProgressSummary {
+    pub scope: String,
+    pub total_tasks: usize,
+    pub done_tasks: usize,
+    pub unestimated_tasks: usize,
+    pub total_hours: f64,
+    pub done_hours: f64,
+    pub remaining_hours: f64,
+    pub percent_complete: f64,
+    /// Percent complete a week ago, if any of the tasks existed then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent_last_week: Option<f64>,
+    /// Estimated hours completed in the last seven days
+    pub hours_done_this_week: f64,
+}
+
+/// The task and everything it transitively depends on
+fn subtree<'a>(graph: &'a WorkGraph, root: &str) -> Vec<&'a Task> {
+    let mut visited = HashSet::new();
+    let mut stack = vec![root.to_string()];
+    let mut tasks = Vec::new();
+    while let Some(id) = stack.pop() {
+        if !visited.insert(id.clone()) {
+            continue;
+        }
+        if let Some(task) = graph.get_task(&id) {
+            stack.extend(task.after.iter().cloned());
+            tasks.push(task);
+        }
+    }
+    tasks
+}
+
+fn parse_ts(ts: Option<&str>) -> Option<DateTime<Utc>> {
+    ts?.parse::<DateTime<Utc>>().ok()
+}
+
+/// Calculate progress over `tasks` as of `now`
+pub fn calculate_progress(scope: String, tasks: &[&Task], now: DateTime<Utc>) -> ProgressSummary {
+    let tasks: Vec<&Task> = tasks
+        .iter()
+        .copied()
+        .filter(|t| t.status != Status::Abandoned)
+        .collect();
+
+    let estimates: Vec<f64> = tasks
+        .iter()
+        .filter_map(|t| t.estimate.as_ref().and_then(|e| e.hours))
+        .collect();
+    let default_hours = if estimates.is_empty() {
+        FALLBACK_HOURS
+    } else {
+        estimates.iter().sum::<f64>() / estimates.len() as f64
+    };
+    let hours = |t: &Task| {
+        t.estimate
+            .as_ref()
+            .and_then(|e| e.hours)
+            .unwrap_or(default_hours)
+    };
+
+    let week_ago = now - Duration::days(7);
+    let completed_at = |t: &Task| {
+        if t.status == Status::Done {
+            // Done without a timestamp: assume it has been done for a while
+            Some(parse_ts(t.completed_at.as_deref()).unwrap_or(DateTime::<Utc>::MIN_UTC))
+        } else {
+            None
+        }
+    };
+
+    let mut total_hours = 0.0;
+    let mut done_hours = 0.0;
+    let mut done_tasks = 0;
+    let mut hours_then = 0.0;
+    let mut done_hours_then = 0.0;
+    let mut hours_done_this_week = 0.0;
+    for task in &tasks {
+        let h = hours(task);
+        total_hours += h;
+        let done_at = completed_at(task);
+        if done_at.is_some() {
+            done_tasks += 1;
+            done_hours += h;
+        }
+        let existed = parse_ts(task.created_at.as_deref()).is_none_or(|c| c <= week_ago);
+        if existed {
+            hours_then += h;
+        }
+        match done_at {
+            Some(at) if at <= week_ago => done_hours_then += h,
+            Some(_) => hours_done_this_week += h,
+            None => {}
+        }
+    }
+
+    let percent = |done: f64, total: f64| {
+        if total > 0.0 {
+            done / total * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    ProgressSummary {
+        scope,
+        total_tasks: tasks.len(),
+        done_tasks,
+        unestimated_tasks: tasks.len() - estimates.len(),
+        total_hours,
+        done_hours,
+        remaining_hours: total_hours - done_hours,
+        percent_complete: percent(done_hours, total_hours),
+        percent_last_week: (hours_then > 0.0).then(|| percent(done_hours_then, hours_then)),
+        hours_done_this_week,
+    }
+}
+
+pub fn run(dir: &Path, root: Option<&str>, tag: Option<&str>, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+
+    let (scope, tasks) = match (root, tag) {
+        (Some(id), _) => {
+            if graph.get_task(id).is_none() {
+                anyhow::bail!("Task '{}' not found", id);
+            }
+            (format!("task '{}'", id), subtree(&graph, id))
+        }
+        (None, Some(tag)) => {
+            let tasks: Vec<&Task> = graph
+                .tasks()
+                .filter(|t| t.tags.iter().any(|x| x == tag))
+                .collect();
+            if tasks.is_empty() {
+                anyhow::bail!("No tasks tagged '{}'", tag);
+            }
+            (format!("tag '{}'", tag), tasks)
+        }
+        (None, None) => anyhow::bail!("Give a task ID or --tag"),
+    };
+
+    let summary = calculate_progress(scope, &tasks, Utc::now());
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+    } else {
+        print_human_output(&summary);
+    }
+
+    Ok(())
+}
+
+fn print_human_output(summary: &ProgressSummary) {
+    println!("Progress for {}:\n", summary.scope);
+    println!(
+        "  {:.0}% complete ({:.1}h of {:.1}h)",
+        summary.percent_complete, summary.done_hours, summary.total_hours
+    );
+    println!(
+        "  Tasks: {}/{} done",
+        summary.done_tasks, summary.total_tasks
+    );
+    println!("  Remaining: {:.1}h", summary.remaining_hours);
+
+    match summary.percent_last_week {
+        Some(then) => {
+            let change = summary.percent_complete - then;
+            println!(
+                "  Trend: {:+.0} points vs last week ({:.0}% → {:.0}%, {:.1}h done this week)",
+                change, then, summary.percent_complete, summary.hours_done_this_week
+            );
+        }
+        None => println!("  Trend: no history (all tasks created this week)"),
+    }
+
+    if summary.unestimated_tasks > 0 {
+        println!();
+        println!(
+            "Note: {} task(s) have no hour estimate and are weighted as the average estimate.",
+            summary.unestimated_tasks
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workgraph::graph::{Estimate, Node};
+
+    fn make_task(id: &str, hours: Option<f64>, after: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            title: id.to_string(),
+            after: after.iter().map(|s| s.to_string()).collect(),
+            estimate: hours.map(|h| Estimate {
+                hours: Some(h),
+                cost: None,
+            }),
+            ..Task::default()
+        }
+    }
+
+    fn done(mut task: Task, days_ago: i64) -> Task {
+        task.status = Status::Done;
+        task.completed_at = Some((Utc::now() - Duration::days(days_ago)).to_rfc3339());
+        task
+    }
+
+    #[test]
+    fn test_subtree_follows_dependencies() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("root", None, &["a", "b"])));
+        graph.add_node(Node::Task(make_task("a", None, &["c"])));
+        graph.add_node(Node::Task(make_task("b", None, &["c"])));
+        graph.add_node(Node::Task(make_task("c", None, &[])));
+        graph.add_node(Node::Task(make_task("other", None, &[])));
+
+        let mut ids: Vec<&str> = subtree(&graph, "root")
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["a", "b", "c", "root"]);
+    }
+
+    #[test]
+    fn test_progress_weighted_by_estimate() {
+        let tasks = [
+            done(make_task("a", Some(6.0), &[]), 10),
+            make_task("b", Some(2.0), &[]),
+            // Unestimated: counts as the 4h average
+            make_task("c", None, &[]),
+        ];
+        let mut abandoned = make_task("d", Some(100.0), &[]);
+        abandoned.status = Status::Abandoned;
+        let refs: Vec<&Task> = tasks.iter().chain([&abandoned]).collect();
+
+        let summary = calculate_progress("test".to_string(), &refs, Utc::now());
+        assert_eq!(summary.total_tasks, 3);
+        assert_eq!(summary.done_tasks, 1);
+        assert_eq!(summary.unestimated_tasks, 1);
+        assert_eq!(summary.total_hours, 12.0);
+        assert_eq!(summary.remaining_hours, 6.0);
+        assert_eq!(summary.percent_complete, 50.0);
+    }
+
+    #[test]
+    fn test_progress_trend_vs_last_week() {
+        let mut new_task = make_task("d", Some(4.0), &[]);
+        new_task.created_at = Some(Utc::now().to_rfc3339());
+        let tasks = [
+            done(make_task("a", Some(4.0), &[]), 10),
+            done(make_task("b", Some(4.0), &[]), 2),
+            make_task("c", Some(4.0), &[]),
+            new_task,
+        ];
+        let refs: Vec<&Task> = tasks.iter().collect();
+
+        let summary = calculate_progress("test".to_string(), &refs, Utc::now());
+        assert_eq!(summary.percent_complete, 50.0);
+        // A week ago: a done out of a, b, c
+        let then = summary.percent_last_week.unwrap();
+        assert!((then - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(summary.hours_done_this_week, 4.0);
+    }
+}
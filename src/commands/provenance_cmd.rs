@@ -0,0 +1,559 @@
+//! `wg provenance` — inspect and verify the operations log, and trace task lineage.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+use workgraph::graph::Task;
+use workgraph::provenance::{self, ChainIssue, ChainProblem, OperationEntry};
+
+/// Operations that start a new attempt at a task.
+const NEW_ATTEMPT_OPS: &[&str] = &["retry", "requeue", "reset", "rescue"];
+
+/// Operations that end an attempt.
+const OUTCOME_OPS: &[&str] = &["done", "fail", "abandon", "incomplete", "approve", "reject"];
+
+fn short(hash: &str) -> &str {
+    &hash[..hash.len().min(12)]
+}
+
+fn describe(issue: &ChainIssue) -> String {
+    let at = match (&issue.index, &issue.timestamp, &issue.op) {
+        (Some(i), Some(ts), Some(op)) => format!("entry #{} ({} {})", i, ts, op),
+        _ => "end of log".to_string(),
+    };
+    let what = match &issue.problem {
+        ChainProblem::HashMismatch => "content does not match its hash (edited)".to_string(),
+        ChainProblem::BrokenLink { expected, found } => format!(
+            "does not link to the previous entry (expected {}, found {}): entries deleted, inserted, or reordered",
+            expected.as_deref().map(short).unwrap_or("none"),
+            found.as_deref().map(short).unwrap_or("none")
+        ),
+        ChainProblem::Unhashed => "unhashed entry inside the chain (inserted)".to_string(),
+        ChainProblem::MissingPrefix { prev_hash } => format!(
+            "chain starts mid-way (links to missing {}): older entries or rotated files deleted",
+            short(prev_hash)
+        ),
+        ChainProblem::Truncated { head } => format!(
+            "log ends before the recorded chain head {}: newest entries removed",
+            short(head)
+        ),
+    };
+    format!("{}: {}", at, what)
+}
+
+/// Verify the hash chain of the operations log.
+pub fn run_verify(dir: &Path, json: bool) -> Result<()> {
+    let report = provenance::verify_chain(dir)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if report.is_intact() {
+        println!(
+            "Provenance chain intact: {} entries ({} chained, {} legacy unchained)",
+            report.total_entries, report.chained_entries, report.legacy_entries
+        );
+    } else {
+        println!(
+            "Provenance chain BROKEN: {} problem(s) in {} entries",
+            report.issues.len(),
+            report.total_entries
+        );
+        for issue in &report.issues {
+            println!("  {}", describe(issue));
+        }
+    }
+
+    if !report.is_intact() {
+        anyhow::bail!("provenance verification failed");
+    }
+    Ok(())
+}
+
+/// How a task came to exist.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Origin {
+    /// `function`, `agent`, `command`, or `unknown`
+    pub via: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+/// One attempt at a task, from creation or a retry up to its outcome.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Attempt {
+    pub number: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>,
+    pub artifacts: Vec<String>,
+}
+
+/// A provenance operation touching the task.
+#[derive(Debug, Clone, Serialize)]
+pub struct LineageEvent {
+    pub timestamp: String,
+    pub op: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub summary: String,
+    pub attempt: usize,
+}
+
+/// Reconstructed history of a task.
+#[derive(Debug, Clone, Serialize)]
+pub struct Lineage {
+    pub task_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    pub origin: Origin,
+    pub attempts: Vec<Attempt>,
+    /// Artifacts on the task that no recorded operation accounts for
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unattributed_artifacts: Vec<String>,
+    pub events: Vec<LineageEvent>,
+}
+
+fn detail_str(op: &OperationEntry, key: &str) -> Option<String> {
+    op.detail
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
+fn created_by_apply(op: &OperationEntry, id: &str) -> bool {
+    op.op == "apply"
+        && op
+            .detail
+            .get("created_task_ids")
+            .and_then(|v| v.as_array())
+            .is_some_and(|ids| ids.iter().any(|v| v.as_str() == Some(id)))
+}
+
+/// Compact `key=value` rendering of an operation's scalar detail fields.
+fn summarize_detail(detail: &serde_json::Value) -> String {
+    let Some(obj) = detail.as_object() else {
+        return String::new();
+    };
+    obj.iter()
+        .filter_map(|(k, v)| {
+            let v = match v {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Number(n) => n.to_string(),
+                serde_json::Value::Bool(b) => b.to_string(),
+                _ => return None,
+            };
+            Some(format!("{}={}", k, v))
+        })
+        .take(4)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reconstruct a task's lineage from its current state (if it still
+/// exists) and the operations log.
+pub fn build_lineage(task: Option<&Task>, ops: &[OperationEntry], id: &str) -> Lineage {
+    let relevant: Vec<&OperationEntry> = ops
+        .iter()
+        .filter(|op| op.task_id.as_deref() == Some(id) || created_by_apply(op, id))
+        .collect();
+
+    let origin = if let Some(op) = relevant.iter().find(|op| created_by_apply(op, id)) {
+        Origin {
+            via: "function".to_string(),
+            created_at: Some(op.timestamp.clone()),
+            function_id: detail_str(op, "function_id"),
+            agent: op.actor.clone(),
+            user: op.user.clone(),
+        }
+    } else if let Some(op) = relevant.iter().find(|op| op.op == "add_task") {
+        let agent = detail_str(op, "agent_id").or_else(|| op.actor.clone());
+        Origin {
+            via: if agent.is_some() { "agent" } else { "command" }.to_string(),
+            created_at: Some(op.timestamp.clone()),
+            function_id: None,
+            agent,
+            user: op.user.clone(),
+        }
+    } else {
+        Origin {
+            via: "unknown".to_string(),
+            created_at: task.and_then(|t| t.created_at.clone()),
+            ..Origin::default()
+        }
+    };
+
+    let mut attempts = vec![Attempt {
+        number: 1,
+        started: origin.created_at.clone(),
+        ..Attempt::default()
+    }];
+    let mut events = Vec::new();
+    for op in &relevant {
+        if NEW_ATTEMPT_OPS.contains(&op.op.as_str()) {
+            attempts.push(Attempt {
+                number: attempts.len() + 1,
+                started: Some(op.timestamp.clone()),
+                ..Attempt::default()
+            });
+        }
+        let current = attempts.last_mut().expect("at least one attempt");
+        match op.op.as_str() {
+            "claim" => current.agent = op.actor.clone().or(current.agent.take()),
+            "assign" => {
+                if let Some(agent) = detail_str(op, "agent_hash") {
+                    current.agent = Some(agent);
+                }
+            }
+            "artifact_add" => {
+                if let Some(path) = detail_str(op, "path")
+                    && !current.artifacts.contains(&path)
+                {
+                    current.artifacts.push(path);
+                }
+            }
+            "artifact_rm" => {
+                if let Some(path) = detail_str(op, "path") {
+                    current.artifacts.retain(|a| a != &path);
+                }
+            }
+            o if OUTCOME_OPS.contains(&o) => {
+                current.outcome = Some(match detail_str(op, "status") {
+                    Some(status) => format!("{} ({})", o, status),
+                    None => o.to_string(),
+                });
+            }
+            _ => {}
+        }
+        events.push(LineageEvent {
+            timestamp: op.timestamp.clone(),
+            op: op.op.clone(),
+            actor: op.actor.clone(),
+            user: op.user.clone(),
+            summary: summarize_detail(&op.detail),
+            attempt: current.number,
+        });
+    }
+
+    let unattributed_artifacts = task
+        .map(|t| {
+            t.artifacts
+                .iter()
+                .filter(|a| !attempts.iter().any(|at| at.artifacts.contains(a)))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Lineage {
+        task_id: id.to_string(),
+        title: task.map(|t| t.title.clone()),
+        status: task.map(|t| t.status.to_string()),
+        origin,
+        attempts,
+        unattributed_artifacts,
+        events,
+    }
+}
+
+fn print_timeline(lineage: &Lineage) {
+    match (&lineage.title, &lineage.status) {
+        (Some(title), Some(status)) => {
+            println!("Lineage of {} — {} [{}]", lineage.task_id, title, status)
+        }
+        _ => println!("Lineage of {} (no longer in the graph)", lineage.task_id),
+    }
+
+    let o = &lineage.origin;
+    let mut created = format!(
+        "Created {}",
+        o.created_at.as_deref().unwrap_or("at an unknown time")
+    );
+    match o.via.as_str() {
+        "function" => created.push_str(&format!(
+            " by function {}",
+            o.function_id.as_deref().unwrap_or("?")
+        )),
+        "agent" => created.push_str(&format!(" by agent {}", o.agent.as_deref().unwrap_or("?"))),
+        "command" => created.push_str(" by command"),
+        _ => created.push_str(" (no creation record)"),
+    }
+    if let Some(ref user) = o.user {
+        created.push_str(&format!(" (user {})", user));
+    }
+    println!("{}", created);
+
+    println!("\nAttempts:");
+    for a in &lineage.attempts {
+        println!(
+            "  #{} {}  agent: {}  outcome: {}",
+            a.number,
+            a.started.as_deref().unwrap_or("-"),
+            a.agent.as_deref().unwrap_or("-"),
+            a.outcome.as_deref().unwrap_or("-")
+        );
+        for artifact in &a.artifacts {
+            println!("      artifact: {}", artifact);
+        }
+    }
+    if !lineage.unattributed_artifacts.is_empty() {
+        println!(
+            "  Artifacts with no recorded attempt: {}",
+            lineage.unattributed_artifacts.join(", ")
+        );
+    }
+
+    println!("\nTimeline:");
+    if lineage.events.is_empty() {
+        println!("  (no recorded operations)");
+    }
+    for e in &lineage.events {
+        let who = e.actor.as_deref().or(e.user.as_deref()).unwrap_or("-");
+        println!(
+            "  {}  #{}  {:<14} {:<16} {}",
+            e.timestamp, e.attempt, e.op, who, e.summary
+        );
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Render the lineage as a Graphviz DOT graph.
+pub fn lineage_dot(lineage: &Lineage) -> String {
+    let mut out = vec![
+        "digraph provenance {".to_string(),
+        "  rankdir=LR;".to_string(),
+        "  node [shape=box];".to_string(),
+    ];
+    let o = &lineage.origin;
+    let origin_label = match o.via.as_str() {
+        "function" => format!("function {}", o.function_id.as_deref().unwrap_or("?")),
+        "agent" => format!("agent {}", o.agent.as_deref().unwrap_or("?")),
+        "command" => format!("command ({})", o.user.as_deref().unwrap_or("user")),
+        _ => "unknown origin".to_string(),
+    };
+    out.push(format!(
+        "  origin [label=\"{}\", shape=ellipse];",
+        dot_escape(&origin_label)
+    ));
+    let task_label = match lineage.title {
+        Some(ref title) => format!("{}\\n{}", dot_escape(&lineage.task_id), dot_escape(title)),
+        None => dot_escape(&lineage.task_id),
+    };
+    out.push(format!("  task [label=\"{}\", style=bold];", task_label));
+    out.push("  origin -> task [label=\"created\"];".to_string());
+
+    for a in &lineage.attempts {
+        let node = format!("attempt{}", a.number);
+        out.push(format!(
+            "  {} [label=\"attempt {}\\n{}\\n{}\"];",
+            node,
+            a.number,
+            dot_escape(a.agent.as_deref().unwrap_or("unassigned")),
+            dot_escape(a.outcome.as_deref().unwrap_or("no outcome"))
+        ));
+        if a.number == 1 {
+            out.push(format!("  task -> {};", node));
+        } else {
+            out.push(format!(
+                "  attempt{} -> {} [label=\"retry\", style=dashed];",
+                a.number - 1,
+                node
+            ));
+        }
+        for (i, artifact) in a.artifacts.iter().enumerate() {
+            out.push(format!(
+                "  {}_artifact{} [label=\"{}\", shape=note];",
+                node,
+                i,
+                dot_escape(artifact)
+            ));
+            out.push(format!("  {} -> {}_artifact{};", node, node, i));
+        }
+    }
+    out.push("}".to_string());
+    out.join("\n")
+}
+
+/// Show the lineage of a task as a timeline, JSON, or DOT graph.
+pub fn run_show(dir: &Path, id: &str, graph: bool, json: bool) -> Result<()> {
+    let (wg, _) = super::load_workgraph(dir)?;
+    let ops = provenance::read_all_operations(dir)?;
+    let task = wg.get_task(id);
+    let lineage = build_lineage(task, &ops, id);
+    if task.is_none() && lineage.events.is_empty() {
+        anyhow::bail!("Task '{}' not found in the graph or the operations log", id);
+    }
+
+    if graph {
+        println!("{}", lineage_dot(&lineage));
+    } else if json {
+        println!("{}", serde_json::to_string_pretty(&lineage)?);
+    } else {
+        print_timeline(&lineage);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_verify_ok_and_tampered() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        for op in ["add_task", "done"] {
+            provenance::record(
+                &dir,
+                op,
+                Some("t1"),
+                None,
+                serde_json::Value::Null,
+                provenance::DEFAULT_ROTATION_THRESHOLD,
+            )
+            .unwrap();
+        }
+        run_verify(&dir, false).unwrap();
+
+        let path = provenance::operations_path(&dir);
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, content.replace("\"done\"", "\"abandon\"")).unwrap();
+        assert!(run_verify(&dir, false).is_err());
+    }
+
+    fn op(
+        op: &str,
+        task: Option<&str>,
+        actor: Option<&str>,
+        detail: serde_json::Value,
+    ) -> OperationEntry {
+        OperationEntry {
+            timestamp: format!(
+                "2026-01-01T00:00:{:02}Z",
+                detail["seq"].as_u64().unwrap_or(0)
+            ),
+            op: op.to_string(),
+            task_id: task.map(String::from),
+            actor: actor.map(String::from),
+            user: Some("alice".to_string()),
+            detail,
+            prev_hash: None,
+            hash: None,
+        }
+    }
+
+    #[test]
+    fn test_build_lineage_splits_attempts_and_artifacts() {
+        let ops = vec![
+            op(
+                "apply",
+                None,
+                None,
+                serde_json::json!({"seq": 1, "function_id": "plan-feature", "created_task_ids": ["t1", "t2"]}),
+            ),
+            op(
+                "claim",
+                Some("t1"),
+                Some("agent-a"),
+                serde_json::json!({"seq": 2}),
+            ),
+            op(
+                "artifact_add",
+                Some("t1"),
+                None,
+                serde_json::json!({"seq": 3, "path": "out/v1.txt"}),
+            ),
+            op(
+                "fail",
+                Some("t1"),
+                None,
+                serde_json::json!({"seq": 4, "reason": "tests"}),
+            ),
+            op(
+                "retry",
+                Some("t1"),
+                None,
+                serde_json::json!({"seq": 5, "attempt": 2}),
+            ),
+            op(
+                "claim",
+                Some("t1"),
+                Some("agent-b"),
+                serde_json::json!({"seq": 6}),
+            ),
+            op(
+                "artifact_add",
+                Some("t1"),
+                None,
+                serde_json::json!({"seq": 7, "path": "out/v2.txt"}),
+            ),
+            op("done", Some("t1"), None, serde_json::Value::Null),
+            op("done", Some("other"), None, serde_json::Value::Null),
+        ];
+        let mut task = workgraph::test_helpers::make_task("t1", "Build");
+        task.artifacts = vec!["out/v2.txt".to_string(), "manual.txt".to_string()];
+
+        let lineage = build_lineage(Some(&task), &ops, "t1");
+        assert_eq!(lineage.origin.via, "function");
+        assert_eq!(lineage.origin.function_id.as_deref(), Some("plan-feature"));
+        assert_eq!(lineage.attempts.len(), 2);
+        assert_eq!(lineage.attempts[0].agent.as_deref(), Some("agent-a"));
+        assert_eq!(lineage.attempts[0].outcome.as_deref(), Some("fail"));
+        assert_eq!(
+            lineage.attempts[0].artifacts,
+            vec!["out/v1.txt".to_string()]
+        );
+        assert_eq!(lineage.attempts[1].agent.as_deref(), Some("agent-b"));
+        assert_eq!(lineage.attempts[1].outcome.as_deref(), Some("done"));
+        assert_eq!(
+            lineage.unattributed_artifacts,
+            vec!["manual.txt".to_string()]
+        );
+        assert_eq!(lineage.events.len(), 8);
+
+        let dot = lineage_dot(&lineage);
+        assert!(dot.starts_with("digraph provenance {"));
+        assert!(dot.contains("attempt1 -> attempt2 [label=\"retry\", style=dashed];"));
+        assert!(dot.contains("label=\"out/v1.txt\""));
+    }
+
+    #[test]
+    fn test_lineage_of_agent_created_task() {
+        let ops = vec![op(
+            "add_task",
+            Some("sub"),
+            None,
+            serde_json::json!({"title": "Sub", "agent_id": "agent-x"}),
+        )];
+        let lineage = build_lineage(None, &ops, "sub");
+        assert_eq!(lineage.origin.via, "agent");
+        assert_eq!(lineage.origin.agent.as_deref(), Some("agent-x"));
+        assert_eq!(lineage.attempts.len(), 1);
+    }
+
+    #[test]
+    fn test_show_unknown_task_errors() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        workgraph::test_helpers::setup_workgraph(&dir, vec![]);
+        assert!(run_show(&dir, "missing", false, false).is_err());
+    }
+}
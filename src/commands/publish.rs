@@ -705,6 +705,14 @@ Edit via `wg html publish edit` or remove via `wg html publish remove {}`.\n",
         None,           // priority
         Some(schedule), // cron
         false,          // subtask
+        &[],            // expects
+        &[],            // requires
+        None,           // due
+        &[],            // waiting_on
+        &[],            // lag
+        &[],            // edge_weight
+        None,           // max_cost
+        None,           // min_context
     )
     .with_context(|| "failed to register cron task for publish deployment")?;
 
@@ -2,12 +2,12 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use std::path::Path;
 use workgraph::graph::{PRIORITY_DEFAULT, Status};
-use workgraph::query::ready_tasks_cycle_aware;
+use workgraph::query::ready_tasks_with_peers_cycle_aware;
 
 pub fn run(dir: &Path, json: bool) -> Result<()> {
-    let (graph, _path) = super::load_workgraph(dir)?;
+    let graph = super::load_workgraph_subset(dir, |index| index.ready_candidates())?;
     let cycle_analysis = graph.compute_cycle_analysis();
-    let ready = ready_tasks_cycle_aware(&graph, &cycle_analysis);
+    let ready = ready_tasks_with_peers_cycle_aware(&graph, dir, &cycle_analysis);
 
     // Find tasks that would be ready except they're waiting on ready_after
     let waiting: Vec<_> = graph
@@ -117,6 +117,7 @@ mod tests {
     use tempfile::tempdir;
     use workgraph::graph::{Node, Task, WorkGraph};
     use workgraph::parser::{load_graph, save_graph};
+    use workgraph::query::ready_tasks_cycle_aware;
 
     fn make_task(id: &str, title: &str, status: Status) -> Task {
         Task {
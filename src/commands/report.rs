@@ -0,0 +1,444 @@
+//! `wg report --html <dir>`: a self-contained HTML status report.
+//!
+//! Unlike `wg html` (an interactive graph viewer), the report is a single
+//! `index.html` with inline CSS and SVG charts and no scripts, meant to be
+//! mailed or dropped on a file share for people without the CLI.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::Path;
+use workgraph::agency;
+use workgraph::format_hours;
+use workgraph::graph::{Status, Task, WorkGraph, is_system_task};
+
+use super::velocity::{VelocitySummary, calculate_velocity};
+
+/// Completed tasks listed under "Recent completions"
+const RECENT_LIMIT: usize = 20;
+
+const STYLE: &str = r#"
+body { font: 14px/1.5 system-ui, sans-serif; margin: 2rem auto; max-width: 960px; color: #222; padding: 0 1rem; }
+h1 { margin-bottom: 0; } .meta { color: #666; margin-top: .25rem; }
+h2 { border-bottom: 1px solid #ddd; padding-bottom: .25rem; margin-top: 2rem; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: .3rem .6rem; border-bottom: 1px solid #eee; }
+th { background: #f6f6f6; } td.num, th.num { text-align: right; }
+code { font-family: ui-monospace, monospace; font-size: 90%; }
+.chips { display: flex; flex-wrap: wrap; gap: .5rem; }
+.chip { border-radius: 4px; padding: .3rem .7rem; color: #111; }
+.chip b { font-size: 1.3rem; margin-right: .3rem; }
+.progress { background: #eee; border-radius: 4px; height: 10px; margin-top: 1rem; }
+.progress div { background: #3a9a4a; height: 10px; border-radius: 4px; }
+.status { border-radius: 3px; padding: 0 .4rem; font-size: 85%; }
+.muted { color: #888; }
+"#;
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn status_color(status: Status) -> &'static str {
+    match status {
+        Status::Done => "#a6e3a1",
+        Status::InProgress => "#f5a97f",
+        Status::Open => "#e6e6e6",
+        Status::Blocked | Status::Waiting => "#f9e2af",
+        Status::Failed | Status::FailedPendingEval => "#f38ba8",
+        Status::Abandoned => "#cccccc",
+        Status::PendingValidation | Status::PendingEval => "#89dceb",
+        Status::Incomplete => "#fab387",
+    }
+}
+
+fn status_badge(status: Status) -> String {
+    format!(
+        "<span class=\"status\" style=\"background:{}\">{}</span>",
+        status_color(status),
+        status
+    )
+}
+
+fn parse_ts(s: Option<&str>) -> Option<DateTime<Utc>> {
+    s.and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn section_status(out: &mut String, tasks: &[&Task]) {
+    let mut counts: BTreeMap<String, (Status, usize)> = BTreeMap::new();
+    for t in tasks {
+        counts
+            .entry(t.status.to_string())
+            .or_insert((t.status, 0))
+            .1 += 1;
+    }
+    let done = tasks.iter().filter(|t| t.status == Status::Done).count();
+    let pct = if tasks.is_empty() {
+        0.0
+    } else {
+        done as f64 * 100.0 / tasks.len() as f64
+    };
+
+    out.push_str("<h2>Status</h2>\n<div class=\"chips\">\n");
+    for (name, (status, n)) in &counts {
+        let _ = writeln!(
+            out,
+            "<div class=\"chip\" style=\"background:{}\"><b>{}</b>{}</div>",
+            status_color(*status),
+            n,
+            name
+        );
+    }
+    let _ = writeln!(
+        out,
+        "</div>\n<div class=\"progress\"><div style=\"width:{:.1}%\"></div></div>\n\
+         <p>{} of {} tasks done ({:.0}%).</p>",
+        pct,
+        done,
+        tasks.len(),
+        pct
+    );
+}
+
+fn section_critical_path(out: &mut String, graph: &WorkGraph, path: &[String], hours: f64) {
+    out.push_str("<h2>Critical path</h2>\n");
+    if path.is_empty() {
+        out.push_str("<p class=\"muted\">No active dependency chain.</p>\n");
+        return;
+    }
+    let _ = writeln!(
+        out,
+        "<p>{} tasks, estimated {} hours.</p>\n<table>\n\
+         <tr><th>#</th><th>Task</th><th>Status</th><th class=\"num\">Hours</th></tr>",
+        path.len(),
+        format_hours(hours)
+    );
+    for (i, id) in path.iter().enumerate() {
+        let Some(t) = graph.get_task(id) else {
+            continue;
+        };
+        let est = t
+            .estimate
+            .as_ref()
+            .and_then(|e| e.hours)
+            .map(format_hours)
+            .unwrap_or_else(|| "—".to_string());
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td><code>{}</code> {}</td><td>{}</td><td class=\"num\">{}</td></tr>",
+            i + 1,
+            escape(&t.id),
+            escape(&t.title),
+            status_badge(t.status),
+            est
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+/// Vertical bar chart of tasks completed per week, oldest on the left,
+/// with the weekly average drawn as a dashed line.
+fn velocity_svg(summary: &VelocitySummary) -> String {
+    const W: f64 = 640.0;
+    const H: f64 = 180.0;
+    const PAD: f64 = 24.0;
+    let n = summary.weeks.len().max(1) as f64;
+    let max = summary
+        .weeks
+        .iter()
+        .map(|w| w.tasks_completed)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let slot = (W - 2.0 * PAD) / n;
+    let scale = (H - 2.0 * PAD) / max;
+
+    let mut svg = format!(
+        "<svg viewBox=\"0 0 {W} {H}\" width=\"100%\" role=\"img\" aria-label=\"Tasks completed per week\">\n"
+    );
+    for (i, week) in summary.weeks.iter().enumerate() {
+        let h = week.tasks_completed as f64 * scale;
+        let x = PAD + i as f64 * slot + slot * 0.15;
+        let y = H - PAD - h;
+        let weeks_ago = summary.weeks.len() - 1 - i;
+        let label = if weeks_ago == 0 {
+            "this wk".to_string()
+        } else {
+            format!("-{}w", weeks_ago)
+        };
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{:.1}\" height=\"{h:.1}\" fill=\"#3a9a4a\"><title>{} tasks</title></rect>\
+             <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"11\" text-anchor=\"middle\">{}</text>\
+             <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"10\" text-anchor=\"middle\" fill=\"#666\">{}</text>",
+            slot * 0.7,
+            week.tasks_completed,
+            x + slot * 0.35,
+            y - 4.0,
+            week.tasks_completed,
+            x + slot * 0.35,
+            H - PAD + 14.0,
+            label
+        );
+    }
+    let avg_y = H - PAD - summary.average_tasks_per_week * scale;
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{PAD}\" x2=\"{:.1}\" y1=\"{avg_y:.1}\" y2=\"{avg_y:.1}\" stroke=\"#888\" stroke-dasharray=\"4 3\"/>\n\
+         <line x1=\"{PAD}\" x2=\"{:.1}\" y1=\"{:.1}\" y2=\"{:.1}\" stroke=\"#ccc\"/>\n</svg>",
+        W - PAD,
+        W - PAD,
+        H - PAD,
+        H - PAD
+    );
+    svg
+}
+
+fn section_velocity(out: &mut String, summary: &VelocitySummary) {
+    out.push_str("<h2>Velocity</h2>\n");
+    out.push_str(&velocity_svg(summary));
+    let clear = summary
+        .weeks_to_clear
+        .map(|w| {
+            format!(
+                " At this pace the {} open tasks clear in about {:.1} weeks.",
+                summary.open_tasks, w
+            )
+        })
+        .unwrap_or_default();
+    let _ = writeln!(
+        out,
+        "<p>Average {:.1} tasks ({} hours) per week; trend: {}.{}</p>",
+        summary.average_tasks_per_week,
+        format_hours(summary.average_hours_per_week),
+        escape(&summary.trend),
+        clear
+    );
+}
+
+#[derive(Default)]
+struct AgentLoad {
+    open: usize,
+    in_progress: usize,
+    done: usize,
+    open_hours: f64,
+}
+
+fn section_workload(out: &mut String, tasks: &[&Task], names: &HashMap<String, String>) {
+    let mut loads: BTreeMap<&str, AgentLoad> = BTreeMap::new();
+    let mut unassigned = 0;
+    for t in tasks {
+        let Some(agent) = t.assigned.as_deref() else {
+            if !t.status.is_terminal() {
+                unassigned += 1;
+            }
+            continue;
+        };
+        let load = loads.entry(agent).or_default();
+        match t.status {
+            Status::Done => load.done += 1,
+            Status::InProgress => load.in_progress += 1,
+            s if !s.is_terminal() => {
+                load.open += 1;
+                load.open_hours += t.estimate.as_ref().and_then(|e| e.hours).unwrap_or(0.0);
+            }
+            _ => {}
+        }
+    }
+
+    out.push_str("<h2>Workload by agent</h2>\n");
+    if loads.is_empty() {
+        out.push_str("<p class=\"muted\">No assigned tasks.</p>\n");
+    } else {
+        let busiest = loads
+            .values()
+            .map(|l| l.open + l.in_progress)
+            .max()
+            .unwrap_or(0)
+            .max(1) as f64;
+        out.push_str(
+            "<table>\n<tr><th>Agent</th><th class=\"num\">In progress</th><th class=\"num\">Queued</th>\
+             <th class=\"num\">Queued hours</th><th class=\"num\">Done</th><th>Load</th></tr>\n",
+        );
+        for (agent, load) in &loads {
+            let name = names.get(*agent).map(String::as_str).unwrap_or(agent);
+            let width = (load.open + load.in_progress) as f64 * 100.0 / busiest;
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+                 <td class=\"num\">{}</td><td><svg width=\"120\" height=\"10\"><rect width=\"{:.0}\" height=\"10\" \
+                 fill=\"#f5a97f\"/></svg></td></tr>",
+                escape(name),
+                load.in_progress,
+                load.open,
+                format_hours(load.open_hours),
+                load.done,
+                width * 1.2
+            );
+        }
+        out.push_str("</table>\n");
+    }
+    let _ = writeln!(out, "<p>{} active tasks unassigned.</p>", unassigned);
+}
+
+fn section_recent(out: &mut String, tasks: &[&Task], names: &HashMap<String, String>) {
+    let mut done: Vec<(&Task, DateTime<Utc>)> = tasks
+        .iter()
+        .filter(|t| t.status == Status::Done)
+        .filter_map(|t| parse_ts(t.completed_at.as_deref()).map(|at| (*t, at)))
+        .collect();
+    done.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.id.cmp(&b.0.id)));
+    done.truncate(RECENT_LIMIT);
+
+    out.push_str("<h2>Recent completions</h2>\n");
+    if done.is_empty() {
+        out.push_str("<p class=\"muted\">Nothing completed yet.</p>\n");
+        return;
+    }
+    out.push_str("<table>\n<tr><th>Completed</th><th>Task</th><th>By</th></tr>\n");
+    for (t, at) in done {
+        let by = t
+            .assigned
+            .as_deref()
+            .map(|a| names.get(a).map(String::as_str).unwrap_or(a))
+            .unwrap_or("—");
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td><code>{}</code> {}</td><td>{}</td></tr>",
+            at.format("%Y-%m-%d %H:%M"),
+            escape(&t.id),
+            escape(&t.title),
+            escape(by)
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+/// Render the full report document.
+pub(crate) fn render_report(
+    graph: &WorkGraph,
+    project: &str,
+    names: &HashMap<String, String>,
+    weeks: usize,
+    now: DateTime<Utc>,
+) -> String {
+    let tasks: Vec<&Task> = graph.tasks().filter(|t| !is_system_task(&t.id)).collect();
+    let (path, hours) = super::critical_path::critical_path(graph);
+    let velocity = calculate_velocity(graph, weeks);
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{} — status report</title>\n<style>{}</style>\n</head>\n<body>\n\
+         <h1>{}</h1>\n<p class=\"meta\">Status report generated {}</p>\n",
+        escape(project),
+        STYLE,
+        escape(project),
+        now.format("%Y-%m-%d %H:%M UTC")
+    );
+    section_status(&mut out, &tasks);
+    section_critical_path(&mut out, graph, &path, hours);
+    section_velocity(&mut out, &velocity);
+    section_workload(&mut out, &tasks, names);
+    section_recent(&mut out, &tasks, names);
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+pub fn run(dir: &Path, html_out: &Path, weeks: usize) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let project_root = dir
+        .canonicalize()
+        .ok()
+        .and_then(|d| d.parent().map(Path::to_path_buf));
+    let project = project_root
+        .as_deref()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or("workgraph")
+        .to_string();
+    let names: HashMap<String, String> =
+        agency::load_all_agents_or_warn(&dir.join("agency").join("cache/agents"))
+            .into_iter()
+            .map(|a| (a.id, a.name))
+            .collect();
+
+    let html = render_report(&graph, &project, &names, weeks.max(1), Utc::now());
+    std::fs::create_dir_all(html_out)
+        .with_context(|| format!("Failed to create {}", html_out.display()))?;
+    let index = html_out.join("index.html");
+    std::fs::write(&index, html).with_context(|| format!("Failed to write {}", index.display()))?;
+    println!("Report written to {}", index.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workgraph::graph::{Estimate, Node};
+
+    fn task(id: &str, status: Status) -> Task {
+        Task {
+            id: id.to_string(),
+            title: format!("Task <{}>", id),
+            status,
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn test_render_report_sections() {
+        let now = Utc::now();
+        let mut graph = WorkGraph::new();
+        let mut done = task("shipped", Status::Done);
+        done.assigned = Some("agent-1".to_string());
+        done.completed_at = Some(now.to_rfc3339());
+        graph.add_node(Node::Task(done));
+        let mut a = task("a", Status::InProgress);
+        a.assigned = Some("agent-1".to_string());
+        a.estimate = Some(Estimate {
+            hours: Some(3.0),
+            cost: None,
+        });
+        graph.add_node(Node::Task(a));
+        let mut b = task("b", Status::Open);
+        b.after = vec!["a".to_string()];
+        graph.add_node(Node::Task(b));
+        graph.add_node(Node::Task(task(".evaluate-a", Status::Open)));
+
+        let names: HashMap<String, String> = [("agent-1".to_string(), "Builder".to_string())]
+            .into_iter()
+            .collect();
+        let html = render_report(&graph, "demo", &names, 4, now);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("<script"));
+        assert!(html.contains("1 of 3 tasks done"));
+        assert!(html.contains("<p>2 tasks, estimated 4 hours.</p>"));
+        assert!(html.contains("<code>a</code> Task &lt;a&gt;"));
+        assert!(html.contains("<svg viewBox"));
+        assert!(html.contains("<tr><td>Builder</td><td class=\"num\">1</td>"));
+        assert!(html.contains("<code>shipped</code>"));
+        assert!(!html.contains(".evaluate-a"));
+    }
+
+    #[test]
+    fn test_run_writes_index() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".wg");
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task("t1", Status::Open)));
+        workgraph::parser::save_graph(&graph, super::super::graph_path(&dir)).unwrap();
+
+        let out = tmp.path().join("out");
+        run(&dir, &out, 4).unwrap();
+        let html = std::fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(html.contains("Recent completions"));
+    }
+}
@@ -1,18 +1,23 @@
 use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
 use std::path::Path;
-use workgraph::parser::modify_graph;
+use workgraph::parser::{load_graph, modify_graph};
+use workgraph::working_hours::WorkingHours;
 
 #[cfg(test)]
 use super::graph_path;
-#[cfg(test)]
-use workgraph::parser::load_graph;
 
+/// Set or clear a task's `not_before`.
+///
+/// With `after_hours`, the time is pushed to the start of the assignee's
+/// next working period when a working calendar applies to them; with
+/// `working`, the hours themselves count only working time.
 pub fn run(
     dir: &Path,
     id: &str,
     after_hours: Option<f64>,
     at_timestamp: Option<&str>,
+    working: bool,
 ) -> Result<()> {
     let path = super::graph_path(dir);
     if !path.exists() {
@@ -25,8 +30,22 @@ pub fn run(
         if !secs.is_finite() || secs > i64::MAX as f64 || secs < i64::MIN as f64 {
             anyhow::bail!("Hours value {} is out of range", hours);
         }
-        let duration = Duration::seconds(secs as i64);
-        let future_time = Utc::now() + duration;
+        let now = Utc::now();
+        let graph = load_graph(&path).context("Failed to load graph")?;
+        let calendars = WorkingHours::load_or_warn(dir);
+        let future_time = match graph.get_task(id).and_then(|t| calendars.for_task(t)) {
+            Some(cal) if working => cal.add_working_hours(now, hours),
+            Some(cal) => cal.next_working_time(now + Duration::seconds(secs as i64)),
+            None => {
+                if working {
+                    eprintln!(
+                        "Note: no working calendar applies to '{}'; counting wall-clock hours",
+                        id
+                    );
+                }
+                now + Duration::seconds(secs as i64)
+            }
+        };
         Some(future_time.to_rfc3339())
     } else if let Some(timestamp) = at_timestamp {
         timestamp.parse::<chrono::DateTime<Utc>>().context(
@@ -74,7 +93,7 @@ pub fn run(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::DateTime;
+    use chrono::{DateTime, Datelike, Timelike};
     use std::fs;
     use tempfile::tempdir;
     use workgraph::graph::{Node, Task, WorkGraph};
@@ -105,7 +124,7 @@ mod tests {
         let task = make_task("t1", "Task 1");
         setup_workgraph(dir.path(), vec![task]);
 
-        run(dir.path(), "t1", Some(24.0), None).unwrap();
+        run(dir.path(), "t1", Some(24.0), None, false).unwrap();
 
         let graph = load_graph(graph_path(dir.path())).unwrap();
         let task = graph.get_task("t1").unwrap();
@@ -124,7 +143,7 @@ mod tests {
         let task = make_task("t1", "Task 1");
         setup_workgraph(dir.path(), vec![task]);
 
-        run(dir.path(), "t1", None, Some("2099-06-15T10:00:00Z")).unwrap();
+        run(dir.path(), "t1", None, Some("2099-06-15T10:00:00Z"), false).unwrap();
 
         let graph = load_graph(graph_path(dir.path())).unwrap();
         let task = graph.get_task("t1").unwrap();
@@ -139,7 +158,7 @@ mod tests {
         setup_workgraph(dir.path(), vec![task]);
 
         // Call with no duration or timestamp to clear
-        run(dir.path(), "t1", None, None).unwrap();
+        run(dir.path(), "t1", None, None, false).unwrap();
 
         let graph = load_graph(graph_path(dir.path())).unwrap();
         let task = graph.get_task("t1").unwrap();
@@ -151,7 +170,7 @@ mod tests {
         let dir = tempdir().unwrap();
         setup_workgraph(dir.path(), vec![]);
 
-        let result = run(dir.path(), "nonexistent", Some(24.0), None);
+        let result = run(dir.path(), "nonexistent", Some(24.0), None, false);
         assert!(result.is_err());
     }
 
@@ -161,14 +180,14 @@ mod tests {
         let task = make_task("t1", "Task 1");
         setup_workgraph(dir.path(), vec![task]);
 
-        let result = run(dir.path(), "t1", None, Some("not-a-timestamp"));
+        let result = run(dir.path(), "t1", None, Some("not-a-timestamp"), false);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_reschedule_uninitialized_workgraph() {
         let dir = tempdir().unwrap();
-        let result = run(dir.path(), "t1", Some(24.0), None);
+        let result = run(dir.path(), "t1", Some(24.0), None, false);
         assert!(result.is_err());
     }
 
@@ -178,10 +197,51 @@ mod tests {
         let task = make_task("t1", "Task 1");
         setup_workgraph(dir.path(), vec![task]);
 
-        let result = run(dir.path(), "t1", Some(f64::INFINITY), None);
+        let result = run(dir.path(), "t1", Some(f64::INFINITY), None, false);
         assert!(result.is_err());
 
-        let result = run(dir.path(), "t1", Some(f64::NAN), None);
+        let result = run(dir.path(), "t1", Some(f64::NAN), None, false);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_reschedule_follows_working_calendar() {
+        let dir = tempdir().unwrap();
+        let mut task = make_task("t1", "Task 1");
+        task.assigned = Some("alice".to_string());
+        setup_workgraph(dir.path(), vec![task]);
+        // Only Sundays, 10:00-11:00 UTC
+        fs::write(
+            dir.path().join("config.toml"),
+            "[working_hours]\nhours = \"10:00-11:00\"\ndays = [\"sun\"]\n\n[working_hours.participants.alice]\n",
+        )
+        .unwrap();
+
+        run(dir.path(), "t1", Some(1.0), None, false).unwrap();
+        let graph = load_graph(graph_path(dir.path())).unwrap();
+        let not_before: DateTime<Utc> = graph
+            .get_task("t1")
+            .unwrap()
+            .not_before
+            .as_ref()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(not_before.weekday(), chrono::Weekday::Sun);
+        assert_eq!(not_before.hour(), 10);
+
+        // Two working hours span two Sundays
+        run(dir.path(), "t1", Some(2.0), None, true).unwrap();
+        let graph = load_graph(graph_path(dir.path())).unwrap();
+        let not_before: DateTime<Utc> = graph
+            .get_task("t1")
+            .unwrap()
+            .not_before
+            .as_ref()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(not_before.weekday(), chrono::Weekday::Sun);
+        assert!(not_before - Utc::now() > Duration::days(7));
+    }
 }
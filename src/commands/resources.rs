@@ -1,7 +1,9 @@
 use anyhow::Result;
 use serde::Serialize;
 use std::path::Path;
+use workgraph::config::Config;
 use workgraph::graph::{Resource, Status, WorkGraph};
+use workgraph::resource_pool::{self, PoolUtilization};
 
 /// Resource utilization data
 #[derive(Debug, Clone, Serialize)]
@@ -29,6 +31,8 @@ pub struct ResourceUtilization {
 pub struct ResourcesOutput {
     pub resources: Vec<ResourceUtilization>,
     pub alerts: Vec<ResourceUtilization>,
+    /// Concurrency pools from config (`[pools]`)
+    pub pools: Vec<PoolUtilization>,
 }
 
 /// Calculate resource utilization from the graph
@@ -113,20 +117,7 @@ pub fn calculate_utilization(graph: &WorkGraph) -> Vec<ResourceUtilization> {
 pub fn run(dir: &Path, json: bool) -> Result<()> {
     let (graph, _path) = super::load_workgraph(dir)?;
     let utilizations = calculate_utilization(&graph);
-
-    if utilizations.is_empty() {
-        if json {
-            let output = ResourcesOutput {
-                resources: vec![],
-                alerts: vec![],
-            };
-            println!("{}", serde_json::to_string_pretty(&output)?);
-        } else {
-            println!("No resources with capacity defined.");
-            println!("Add resources with --available to track utilization.");
-        }
-        return Ok(());
-    }
+    let pools = resource_pool::utilization(&Config::load_or_default(dir).pools, &graph);
 
     if json {
         let alerts: Vec<_> = utilizations
@@ -137,15 +128,52 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
         let output = ResourcesOutput {
             resources: utilizations,
             alerts,
+            pools,
         };
         println!("{}", serde_json::to_string_pretty(&output)?);
-    } else {
+        return Ok(());
+    }
+
+    if utilizations.is_empty() && pools.is_empty() {
+        println!("No resources with capacity defined.");
+        println!("Add resources with --available to track utilization,");
+        println!("or declare concurrency pools under [pools] in config.");
+        return Ok(());
+    }
+
+    if !utilizations.is_empty() {
         print_human_output(&utilizations);
     }
+    if !pools.is_empty() {
+        print_pools(&pools);
+    }
 
     Ok(())
 }
 
+fn print_pools(pools: &[PoolUtilization]) {
+    println!("Resource Pools:");
+    println!();
+    for pool in pools {
+        let full = if pool.in_use >= pool.capacity {
+            " - FULL"
+        } else {
+            ""
+        };
+        println!(
+            "  {} ({}/{} in use{})",
+            pool.name, pool.in_use, pool.capacity, full
+        );
+        if !pool.holders.is_empty() {
+            println!("    Running: {}", pool.holders.join(", "));
+        }
+        if !pool.waiting.is_empty() {
+            println!("    Waiting: {}", pool.waiting.join(", "));
+        }
+        println!();
+    }
+}
+
 fn format_amount(amount: f64, unit: &Option<String>) -> String {
     match unit.as_deref() {
         Some("usd") | Some("USD") | Some("$") => format!("${:.0}", amount),
@@ -462,6 +490,7 @@ mod tests {
         let output = ResourcesOutput {
             resources: vec![util.clone()],
             alerts: vec![],
+            pools: vec![],
         };
 
         let json = serde_json::to_string_pretty(&output).unwrap();
@@ -491,6 +520,7 @@ mod tests {
         let output = ResourcesOutput {
             resources: vec![util.clone()],
             alerts: vec![util],
+            pools: vec![],
         };
 
         let json = serde_json::to_string_pretty(&output).unwrap();
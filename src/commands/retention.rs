@@ -0,0 +1,124 @@
+//! `wg retention` — apply the `[retention]` policies now, or show what the
+//! last pass purged.
+
+use anyhow::Result;
+use std::path::Path;
+
+use workgraph::config::Config;
+use workgraph::retention::{self, RetentionReport};
+
+/// Run a retention pass (or preview one with `dry_run`).
+pub fn run(dir: &Path, dry_run: bool, json: bool) -> Result<()> {
+    let config = Config::load_or_default(dir);
+    if config.retention.policies().is_empty() {
+        if json {
+            println!("{}", serde_json::json!({ "categories": [] }));
+        } else {
+            println!("No retention policies configured.");
+            println!("  Add e.g. `runs = \"90d\"` under [retention] in config.toml.");
+        }
+        return Ok(());
+    }
+
+    let report = retention::enforce(dir, &config.retention, dry_run)?;
+    if !dry_run {
+        let _ = workgraph::provenance::record(
+            dir,
+            "retention",
+            None,
+            None,
+            serde_json::json!({
+                "purged": report.total_purged(),
+                "bytes_freed": report.total_bytes(),
+                "categories": report
+                    .categories
+                    .iter()
+                    .map(|c| serde_json::json!({ "category": c.category, "purged": c.purged.len() }))
+                    .collect::<Vec<_>>(),
+            }),
+            config.log.rotation_threshold,
+        );
+    }
+    print_report(&report, json)
+}
+
+/// Show the report written by the most recent retention pass.
+pub fn run_last(dir: &Path, json: bool) -> Result<()> {
+    match retention::load_last_report(dir) {
+        Some(report) => print_report(&report, json),
+        None => {
+            if json {
+                println!("null");
+            } else {
+                println!("No retention pass has run yet.");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_report(report: &RetentionReport, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+        return Ok(());
+    }
+
+    let verb = if report.dry_run {
+        "Would purge"
+    } else {
+        "Purged"
+    };
+    println!(
+        "Retention pass at {}{}",
+        report.ran_at,
+        if report.dry_run { " (dry run)" } else { "" }
+    );
+    for c in &report.categories {
+        println!(
+            "  {:<11} max age {:<5} {} {} item(s), {} bytes",
+            c.category,
+            c.max_age,
+            verb.to_lowercase(),
+            c.purged.len(),
+            c.bytes_freed
+        );
+        for item in &c.purged {
+            println!("    - {}", item);
+        }
+    }
+    println!(
+        "{} {} item(s), {} bytes total",
+        verb,
+        report.total_purged(),
+        report.total_bytes()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_run_records_provenance_and_report() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        std::fs::write(
+            dir.join("config.toml"),
+            "[retention]\ntelemetry = \"30d\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("usage.log"), "2020-01-01T00:00:00Z list\n").unwrap();
+
+        run(dir, true, false).unwrap();
+        assert!(retention::load_last_report(dir).is_none());
+
+        run(dir, false, true).unwrap();
+        let report = retention::load_last_report(dir).unwrap();
+        assert_eq!(report.total_purged(), 1);
+        let ops = workgraph::provenance::read_all_operations(dir).unwrap();
+        assert_eq!(ops.last().unwrap().op, "retention");
+        run_last(dir, false).unwrap();
+    }
+}
@@ -1141,6 +1141,8 @@ mod tests {
                 output_file: "/tmp/output.log".to_string(),
                 model: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
         reg.save(dir).unwrap();
@@ -1230,6 +1232,8 @@ mod tests {
                 output_file: "/tmp/output.log".to_string(),
                 model: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
         reg.save(dir_path).unwrap();
@@ -0,0 +1,202 @@
+//! `wg rubric` — inspect and try out evaluation rubrics.
+//!
+//! Usage:
+//!   wg rubric list
+//!   wg rubric show api-change
+//!   wg rubric test api-change my-task [--dry-run]
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use workgraph::config::{Config, DispatchRole};
+use workgraph::graph::Task;
+use workgraph::json_extract::extract_json;
+use workgraph::rubric::{self, Rubric};
+
+use super::evaluate::compute_artifact_diff;
+
+/// Log entries included in a `wg rubric test` prompt.
+const TEST_LOG_TAIL: usize = 20;
+
+pub fn run_list(dir: &Path, json: bool) -> Result<()> {
+    let rubrics = rubric::load_all(dir)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rubrics)?);
+        return Ok(());
+    }
+    if rubrics.is_empty() {
+        println!(
+            "No rubrics. Add YAML files to {}",
+            rubric::rubrics_dir(dir).display()
+        );
+        return Ok(());
+    }
+    for r in &rubrics {
+        let mut selectors: Vec<String> = r.tags.iter().map(|t| format!("tag:{}", t)).collect();
+        selectors.extend(r.functions.iter().map(|f| format!("function:{}", f)));
+        println!(
+            "{:<24} {} criteria, passes at {:.2}{}",
+            r.id,
+            r.criteria.len(),
+            r.pass_threshold,
+            if selectors.is_empty() {
+                String::new()
+            } else {
+                format!("  [{}]", selectors.join(", "))
+            }
+        );
+    }
+    Ok(())
+}
+
+pub fn run_show(dir: &Path, id: &str, json: bool) -> Result<()> {
+    let r = rubric::load(dir, id)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&r)?);
+        return Ok(());
+    }
+    println!("Rubric: {}", r.id);
+    if let Some(ref desc) = r.description {
+        println!("Description: {}", desc);
+    }
+    if !r.tags.is_empty() {
+        println!("Tags: {}", r.tags.join(", "));
+    }
+    if !r.functions.is_empty() {
+        println!("Functions: {}", r.functions.join(", "));
+    }
+    println!("Pass threshold: {:.2}", r.pass_threshold);
+    println!("Criteria:");
+    for c in &r.criteria {
+        let min = c
+            .min_score
+            .map(|m| format!(", min {:.2}", m))
+            .unwrap_or_default();
+        println!(
+            "  {} (weight {}{}): {}",
+            c.name, c.weight, min, c.description
+        );
+    }
+    Ok(())
+}
+
+/// Prompt asking the evaluator model to score `task` against one rubric.
+fn render_test_prompt(r: &Rubric, task: &Task, artifact_diff: Option<&str>) -> String {
+    let mut out = String::new();
+    out.push_str(
+        "You are scoring completed work against a project rubric. Judge only the \
+         criteria listed below.\n\n",
+    );
+    let _ = writeln!(out, "## Task\n\n**{}** ({})\n", task.title, task.id);
+    if let Some(ref desc) = task.description {
+        let _ = writeln!(out, "{}\n", desc);
+    }
+    if !task.artifacts.is_empty() {
+        out.push_str("## Artifacts\n\n");
+        for a in &task.artifacts {
+            let _ = writeln!(out, "- {}", a);
+        }
+        out.push('\n');
+    }
+    if let Some(diff) = artifact_diff {
+        let _ = writeln!(out, "## Artifact Diff\n\n```diff\n{}\n```\n", diff);
+    }
+    if !task.log.is_empty() {
+        out.push_str("## Recent Log\n\n");
+        let skip = task.log.len().saturating_sub(TEST_LOG_TAIL);
+        for entry in task.log.iter().skip(skip) {
+            let _ = writeln!(out, "- [{}] {}", entry.timestamp, entry.message);
+        }
+        out.push('\n');
+    }
+    out.push_str("## Rubric\n\n");
+    out.push_str(&rubric::render_prompt_section(std::slice::from_ref(r)));
+    out.push_str(
+        "\n## Required Output\n\n\
+         Respond with **only** a JSON object (no markdown fences, no commentary):\n\n\
+         {\"dimensions\": {\"<criterion key>\": <0.0-1.0>, ...}, \"notes\": \"<one paragraph>\"}\n",
+    );
+    out
+}
+
+#[derive(serde::Deserialize)]
+struct TestOutput {
+    #[serde(default)]
+    dimensions: HashMap<String, f64>,
+    #[serde(default)]
+    notes: String,
+}
+
+/// Score one task against one rubric with the evaluator model and print the
+/// per-criterion result. Nothing is recorded: this is for tuning rubrics.
+pub fn run_test(
+    dir: &Path,
+    rubric_id: &str,
+    task_id: &str,
+    dry_run: bool,
+    json: bool,
+) -> Result<()> {
+    let r = rubric::load(dir, rubric_id)?;
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let task = graph.get_task_or_err(task_id)?;
+
+    let artifact_diff = compute_artifact_diff(&task.artifacts, task.started_at.as_deref());
+    let prompt = render_test_prompt(&r, task, artifact_diff.as_deref());
+    if dry_run {
+        println!("=== Dry Run: wg rubric test {} {} ===\n", r.id, task_id);
+        println!("{}", prompt);
+        return Ok(());
+    }
+
+    let config = Config::load_or_default(dir);
+    let timeout_secs = config.agency.triage_timeout.unwrap_or(60).max(300);
+    let response = workgraph::service::llm::run_lightweight_llm_call(
+        &config,
+        DispatchRole::Evaluator,
+        &prompt,
+        timeout_secs,
+    )
+    .context("Rubric scoring LLM call failed")?;
+    let raw = extract_json(&response.text).with_context(|| {
+        format!(
+            "Failed to extract JSON from evaluator output:\n{}",
+            response.text
+        )
+    })?;
+    let parsed: TestOutput = serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse evaluator JSON:\n{}", raw))?;
+    let result = r.assess(&parsed.dimensions);
+
+    if json {
+        let out = serde_json::json!({
+            "task_id": task_id,
+            "result": result,
+            "notes": parsed.notes,
+        });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    println!("Rubric: {}  Task: {}", r.id, task_id);
+    for (name, score) in &result.criteria {
+        match score {
+            Some(s) => println!("  {:<24}{:.2}", name, s),
+            None => println!("  {:<24}(not scored)", name),
+        }
+    }
+    println!(
+        "Score: {:.2} (threshold {:.2}) — {}",
+        result.score,
+        result.pass_threshold,
+        if result.passed { "PASS" } else { "FAIL" }
+    );
+    for reason in result.failures() {
+        println!("  {}", reason);
+    }
+    if !parsed.notes.is_empty() {
+        println!("Notes: {}", parsed.notes);
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! `wg schema` — print the JSON Schema for an on-disk format.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use workgraph::schema::SchemaKind;
+
+pub fn run(kind: Option<&str>, output: Option<&Path>, json: bool) -> Result<()> {
+    let Some(kind) = kind else {
+        return list(json);
+    };
+    let kind = SchemaKind::from_name(kind).with_context(|| {
+        format!(
+            "Unknown schema kind '{}' (expected one of: {})",
+            kind,
+            SchemaKind::ALL.map(SchemaKind::name).join(", ")
+        )
+    })?;
+    let text = serde_json::to_string_pretty(&kind.schema())?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, format!("{}\n", text))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("Wrote {} schema to {}", kind, path.display());
+        }
+        None => println!("{}", text),
+    }
+    Ok(())
+}
+
+fn list(json: bool) -> Result<()> {
+    if json {
+        let kinds: Vec<_> = SchemaKind::ALL
+            .iter()
+            .map(|k| serde_json::json!({ "kind": k.name(), "location": k.location() }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&kinds)?);
+        return Ok(());
+    }
+    println!("Available schemas (wg schema <kind>):");
+    for kind in SchemaKind::ALL {
+        println!("  {:<12} {}", kind.name(), kind.location());
+    }
+    Ok(())
+}
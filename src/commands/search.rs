@@ -0,0 +1,198 @@
+//! `wg search` — full-text search over titles, descriptions, logs and artifacts.
+
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::path::Path;
+
+use workgraph::archive;
+use workgraph::graph::{Status, Task};
+use workgraph::search::{self, Clause, Field, SearchIndex};
+
+/// Snippet width in characters
+const SNIPPET_WIDTH: usize = 80;
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    dir: &Path,
+    terms: &[String],
+    status: Option<&str>,
+    tags: &[String],
+    fields: &[String],
+    include_archive: bool,
+    limit: usize,
+    reindex: bool,
+    json: bool,
+) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+
+    let clauses = search::parse_query(terms);
+    if clauses.is_empty() {
+        bail!("Nothing to search for");
+    }
+    let fields: Vec<Field> = fields
+        .iter()
+        .map(|f| {
+            Field::parse(f).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown field '{}'. Use title, description, log or artifact.",
+                    f
+                )
+            })
+        })
+        .collect::<Result<_>>()?;
+    let status: Option<Status> = status
+        .map(|s| {
+            serde_json::from_value(serde_json::Value::String(s.to_string()))
+                .map_err(|_| anyhow::anyhow!("Unknown status: '{}'", s))
+        })
+        .transpose()?;
+
+    let index = SearchIndex::open(dir, reindex)?;
+    let archived: Vec<Task> = if include_archive {
+        archive::load_all(dir)?
+    } else {
+        Vec::new()
+    };
+    let archived_by_id: HashMap<&str, &Task> =
+        archived.iter().map(|t| (t.id.as_str(), t)).collect();
+
+    let words: Vec<&str> = clauses
+        .iter()
+        .map(|c| match c {
+            Clause::Term(w) => w.as_str(),
+            Clause::Phrase(ws) => ws[0].as_str(),
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for hit in index.search(&clauses, &fields) {
+        let (task, is_archived) = match graph.get_task(&hit.task) {
+            Some(t) => (t, false),
+            None => match archived_by_id.get(hit.task.as_str()) {
+                Some(t) => (*t, true),
+                None => continue,
+            },
+        };
+        if status.is_some_and(|s| task.status != s) {
+            continue;
+        }
+        if !tags.iter().all(|tag| task.tags.contains(tag)) {
+            continue;
+        }
+        let snippet = search::doc_text(task, &hit.best)
+            .map(|text| search::snippet(text, &words, SNIPPET_WIDTH))
+            .unwrap_or_default();
+        results.push((task, is_archived, hit, snippet));
+        if results.len() >= limit {
+            break;
+        }
+    }
+
+    if json {
+        let out: Vec<_> = results
+            .iter()
+            .map(|(task, is_archived, hit, snippet)| {
+                serde_json::json!({
+                    "id": task.id,
+                    "title": task.title,
+                    "status": task.status,
+                    "archived": is_archived,
+                    "score": (hit.score * 100.0).round() / 100.0,
+                    "field": hit.best.field.as_str(),
+                    "snippet": snippet,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    if results.is_empty() {
+        println!("No tasks match '{}'.", terms.join(" "));
+        return Ok(());
+    }
+    for (task, is_archived, hit, snippet) in &results {
+        println!(
+            "{} [{}{}] {}",
+            task.id,
+            task.status,
+            if *is_archived { ", archived" } else { "" },
+            task.title
+        );
+        if hit.best.field != Field::Title && !snippet.is_empty() {
+            println!("    {}: {}", hit.best.field.as_str(), snippet);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::graph_path;
+    use workgraph::graph::{LogEntry, Node, WorkGraph};
+    use workgraph::parser::save_graph;
+
+    #[test]
+    fn test_run_search_with_filters() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(Task {
+            id: "flaky".to_string(),
+            title: "Investigate CI".to_string(),
+            tags: vec!["ci".to_string()],
+            log: vec![LogEntry {
+                timestamp: "2026-01-01T00:00:00Z".to_string(),
+                actor: None,
+                user: None,
+                message: "Deadlock in the worker pool".to_string(),
+            }],
+            ..Task::default()
+        }));
+        save_graph(&graph, graph_path(dir)).unwrap();
+
+        let terms = vec!["worker pool".to_string()];
+        run(dir, &terms, None, &[], &[], false, 10, false, false).unwrap();
+        run(
+            dir,
+            &terms,
+            Some("done"),
+            &["ci".to_string()],
+            &[],
+            false,
+            10,
+            false,
+            true,
+        )
+        .unwrap();
+        assert!(
+            run(
+                dir,
+                &terms,
+                None,
+                &[],
+                &["body".to_string()],
+                false,
+                10,
+                false,
+                false
+            )
+            .is_err()
+        );
+        assert!(
+            run(
+                dir,
+                &["--".to_string()],
+                None,
+                &[],
+                &[],
+                false,
+                10,
+                false,
+                false
+            )
+            .is_err()
+        );
+    }
+}
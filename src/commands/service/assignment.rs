@@ -3,9 +3,11 @@
 //! Pattern follows `triage.rs`: build prompt → call `run_lightweight_llm_call` → parse JSON → apply.
 
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 
 use workgraph::agency::{self, Agent, short_hash};
-use workgraph::config::{Config, DispatchRole};
+use workgraph::config::{Config, DispatchPolicy, DispatchRole};
+use workgraph::dispatch::fair_share::{AgentLoad, RECENT_WINDOW_HOURS};
 use workgraph::graph::{Task, TokenUsage, WorkGraph, is_system_task};
 
 /// Placement decision: dependency edges to add to the source task.
@@ -123,6 +125,69 @@ fn render_agent_catalog(entries: &[AgentEntry]) -> String {
     out
 }
 
+/// Render the `coordinator.dispatch` guidance appended to the assigner's
+/// mode context. `agents` must already be ranked best-first; greedy dispatch
+/// adds nothing.
+pub(crate) fn render_dispatch_context(
+    policy: DispatchPolicy,
+    agents: &[Agent],
+    loads: &HashMap<String, AgentLoad>,
+) -> String {
+    let rule = match policy {
+        DispatchPolicy::Greedy => return String::new(),
+        DispatchPolicy::Fair => {
+            "Spread work across agents: among agents that fit the task, pick the least loaded."
+        }
+        DispatchPolicy::SkillFirst => {
+            "Prefer the agent whose capabilities best cover the task's skills; break ties by picking the least loaded."
+        }
+        DispatchPolicy::Reputation => {
+            "Prefer the agent with the best track record (success rate, evaluation scores, duration vs estimate); break ties by picking the least loaded."
+        }
+    };
+    let mut out = format!(
+        "\n## Dispatch Policy: {}\n{} Agents below are ranked best-first under this policy.\n\n",
+        policy, rule
+    );
+    for a in agents
+        .iter()
+        .filter(|a| !a.is_human() && a.staleness_flags.is_empty())
+    {
+        let load = loads.get(&a.id).copied().unwrap_or_default();
+        out.push_str(&format!(
+            "- {} ({}): in_flight={}, done_last_{}h={}\n",
+            a.name,
+            short_hash(&a.id),
+            load.in_flight,
+            RECENT_WINDOW_HOURS,
+            load.recent,
+        ));
+    }
+    out
+}
+
+/// Render the guidance appended to the assigner's mode context when the
+/// `coordinator.dispatch_strategy` ranks agents. `agents` must already be
+/// ranked best-first.
+pub(crate) fn render_strategy_context(strategy: &str, agents: &[Agent]) -> String {
+    let mut out = format!(
+        "\n## Dispatch Strategy: {}\nAgents below are ranked best-first under this strategy; \
+         among agents that fit the task, prefer the earliest.\n\n",
+        strategy
+    );
+    for a in agents
+        .iter()
+        .filter(|a| !a.is_human() && a.staleness_flags.is_empty())
+    {
+        out.push_str(&format!("- {} ({})", a.name, short_hash(&a.id)));
+        if let Some(rate) = a.rate {
+            out.push_str(&format!(": rate={}", rate));
+        }
+        out.push('\n');
+    }
+    out
+}
+
 /// Build the full assignment prompt for the lightweight LLM call.
 ///
 /// When `active_tasks_context` is non-empty, the prompt includes an "Active Tasks"
@@ -517,6 +582,50 @@ mod tests {
         assert!(result.contains("rust"));
     }
 
+    #[test]
+    fn test_render_dispatch_context() {
+        let agent = Agent {
+            id: "abc12345def".to_string(),
+            role_id: String::new(),
+            tradeoff_id: String::new(),
+            name: "TestAgent".to_string(),
+            performance: agency::PerformanceRecord::default(),
+            lineage: agency::Lineage::default(),
+            capabilities: vec![],
+            rate: None,
+            capacity: None,
+            trust_level: agency::TrustLevel::Provisional,
+            contact: None,
+            executor: "claude".to_string(),
+            preferred_model: None,
+            preferred_provider: None,
+            deployment_history: vec![],
+            attractor_weight: 0.5,
+            staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
+        };
+        let mut loads = HashMap::new();
+        loads.insert(
+            agent.id.clone(),
+            AgentLoad {
+                in_flight: 2,
+                recent: 1,
+            },
+        );
+        let agents = [agent];
+        assert!(render_dispatch_context(DispatchPolicy::Greedy, &agents, &loads).is_empty());
+        let context = render_dispatch_context(DispatchPolicy::Fair, &agents, &loads);
+        assert!(context.contains("## Dispatch Policy: fair"));
+        assert!(context.contains("TestAgent (abc12345): in_flight=2, done_last_24h=1"));
+
+        let mut agents = agents;
+        agents[0].rate = Some(40.0);
+        let context = render_strategy_context("cost-aware", &agents);
+        assert!(context.contains("## Dispatch Strategy: cost-aware"));
+        assert!(context.contains("TestAgent (abc12345): rate=40"));
+    }
+
     #[test]
     fn test_build_assignment_prompt_includes_placement_when_active_tasks() {
         let task = Task {
@@ -4,10 +4,12 @@ use anyhow::{Context, Result};
 use chrono::Utc;
 use std::fs;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
 use workgraph::agency;
 use workgraph::agency::evolver::{self, EvolutionTrigger, EvolverState};
+use workgraph::agency::reputation;
 use workgraph::agency::run_mode::{self, AssignmentPath};
 use workgraph::agency::{
     AssignerModeContext, AssignmentMode, AssignmentSource, Evaluation, TaskAssignmentRecord,
@@ -15,16 +17,21 @@ use workgraph::agency::{
     render_assigner_mode_context, save_assignment_record,
 };
 use workgraph::chat;
-use workgraph::config::{Config, DispatchRole};
+use workgraph::config::{Config, DispatchPolicy, DispatchRole};
+use workgraph::dispatch::skill_match::SkillMatcher;
+use workgraph::dispatch::strategy::{self, DispatchStrategy};
+use workgraph::dispatch::{budget, capability, fair_share, rate_limit};
 use workgraph::graph::{
     FailureClass, LogEntry, Node, PRIORITY_DEFAULT, PRIORITY_IDLE, PRIORITY_NORMAL, Priority,
-    Status, Task, WaitCondition, WaitSpec, boost_priority, evaluate_all_cycle_failure_restarts,
+    Status, Task, WaitCondition, WaitSpec, aged_priority, evaluate_all_cycle_failure_restarts,
     evaluate_all_cycle_iterations,
 };
 use workgraph::messages;
 use workgraph::parser::{load_graph, modify_graph};
 use workgraph::query::ready_tasks_with_peers_cycle_aware;
+use workgraph::service::dispatch_queue::{self, Resolution};
 use workgraph::service::registry::AgentRegistry;
+use workgraph::size_guard::{self, Truncation};
 
 use super::triage;
 use crate::commands::{graph_path, is_process_alive, kill_process_graceful, spawn};
@@ -56,6 +63,29 @@ fn cleanup_and_count_alive(
         );
     }
 
+    // Dispatches that died between claiming their task and registering the
+    // agent (daemon crash, kill -9): release the claims for re-dispatch.
+    match dispatch_queue::reconcile(dir, graph_path) {
+        Ok(settled) => {
+            for r in settled
+                .iter()
+                .filter(|r| r.resolution == Resolution::Requeued)
+            {
+                eprintln!(
+                    "[dispatcher] Interrupted dispatch of '{}' to {} released for re-dispatch{}",
+                    r.task_id,
+                    r.agent_id,
+                    r.stopped_pid
+                        .map(|p| format!(" (stopped unregistered PID {})", p))
+                        .unwrap_or_default()
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("[dispatcher] Dispatch queue reconcile warning: {}", e);
+        }
+    }
+
     // Reconciliation safety net: catch orphaned InProgress tasks whose agents
     // are Dead in registry but weren't unclaimed (split-save race condition).
     match crate::commands::sweep::reconcile_orphaned_tasks(dir, graph_path) {
@@ -151,10 +181,13 @@ const DAEMON_MANAGED_TAGS: &[&str] = &[
 ];
 
 /// Check whether a task is managed by the daemon (not spawned as a regular agent).
+/// Phase gates are opened by the daemon once their conditions hold.
 fn is_daemon_managed(task: &workgraph::graph::Task) -> bool {
-    task.tags
-        .iter()
-        .any(|tag| DAEMON_MANAGED_TAGS.contains(&tag.as_str()))
+    task.gate.is_some()
+        || task
+            .tags
+            .iter()
+            .any(|tag| DAEMON_MANAGED_TAGS.contains(&tag.as_str()))
 }
 
 /// Check whether any tasks are ready. Returns `None` with an early `TickResult`
@@ -876,8 +909,11 @@ fn migrate_pending_validation_tasks(graph: &mut workgraph::graph::WorkGraph) ->
 /// responsible for `run_eval_reject` (PendingEval → Failed) and creating a
 /// rescue. This phase only handles the success case.
 ///
+/// Input contracts of the promoted task's consumers are enforced on
+/// promotion, the same as on a direct `wg done`.
+///
 /// Returns true if any task was promoted.
-fn resolve_pending_eval_tasks(graph: &mut workgraph::graph::WorkGraph) -> bool {
+fn resolve_pending_eval_tasks(dir: &Path, graph: &mut workgraph::graph::WorkGraph) -> bool {
     let promotable: Vec<String> = graph
         .tasks()
         .filter(|t| t.status == Status::PendingEval)
@@ -922,10 +958,22 @@ fn resolve_pending_eval_tasks(graph: &mut workgraph::graph::WorkGraph) -> bool {
                 id
             );
         }
+        enforce_contracts(dir, graph, id);
     }
     true
 }
 
+/// Pause consumers of `completed_id` whose input contracts it left unmet.
+fn enforce_contracts(dir: &Path, graph: &mut workgraph::graph::WorkGraph, completed_id: &str) {
+    let project_root = dir.parent().unwrap_or(dir);
+    for v in workgraph::contract::enforce_on_completion(graph, completed_id, project_root) {
+        eprintln!(
+            "[dispatcher] Contract violation: {} (paused '{}')",
+            v, v.task_id
+        );
+    }
+}
+
 /// Resolve `FailedPendingEval` tasks: agent exited without `wg done`,
 /// dispatcher invokes `.evaluate-X` to assess the output, and this function
 /// promotes to `Done` (rescued) or demotes to `Failed` (terminal) based on the score.
@@ -1055,6 +1103,7 @@ fn resolve_failed_pending_eval_tasks(
                     );
                     modified = true;
                 }
+                enforce_contracts(dir, graph, source_id);
             }
             EvalAction::Reject(score) => {
                 if let Some(task) = graph.get_task_mut(source_id) {
@@ -1375,6 +1424,8 @@ fn build_auto_assign_tasks(
 
     let agency_dir = dir.join("agency");
     let total_assignments = count_assignment_records(&agency_dir.join("assignments")) as u32;
+    // Daily spend per agent, for the budget checks below.
+    let spend = budget::DailySpend::today(graph, dir);
 
     let assign_task_ids: Vec<String> = graph
         .tasks()
@@ -1465,6 +1516,35 @@ fn build_auto_assign_tasks(
         // Load all agents for the lightweight LLM assignment call
         let agents_dir = agency_dir.join("cache/agents");
         let all_agents = agency::load_all_agents_or_warn(&agents_dir);
+        // Agents on vacation or in scheduled downtime are not candidates.
+        let calendar = workgraph::availability::AvailabilityCalendar::load_or_warn(dir);
+        let all_agents: Vec<_> = all_agents
+            .into_iter()
+            .filter(|a| calendar.agent_unavailable_until(a, Utc::now()).is_none())
+            .collect();
+        // Nor are agents that spent their daily budget.
+        let (all_agents, over_budget) = spend.within_budget(all_agents);
+        if all_agents.is_empty() && !over_budget.is_empty() {
+            eprintln!(
+                "[dispatcher] Every available agent is over its daily budget, leaving '{}' unassigned",
+                source_id
+            );
+            continue;
+        }
+        // Nor are agents whose capability document rules the task out.
+        let Some(source) = graph.get_task(&source_id) else {
+            continue;
+        };
+        let matcher = SkillMatcher::for_task(dir, config, source, &all_agents);
+        let (all_agents, rejected) = capability::eligible_agents(all_agents, source, &matcher);
+        if all_agents.is_empty() && !rejected.is_empty() {
+            eprintln!(
+                "[dispatcher] No agent's capability document covers '{}', leaving it unassigned:\n{}",
+                source_id,
+                capability::describe_rejections(&rejected)
+            );
+            continue;
+        }
         let roles_dir = agency_dir.join("cache/roles");
         let tradeoffs_dir = agency_dir.join("primitives/tradeoffs");
 
@@ -1480,6 +1560,56 @@ fn build_auto_assign_tasks(
             ..Default::default()
         };
 
+        // Fair / skill-first / reputation dispatch: rank candidates (load is
+        // recomputed per task, so assignments made earlier this tick count)
+        // and tell the assigner about it. A dispatch strategy that ranks agents itself
+        // (cost-aware, scripts) takes precedence.
+        let policy = config.coordinator.dispatch;
+        let strategy = dispatch_strategy(dir, config);
+        let strategy_ranked: Option<Vec<_>> = strategy
+            .rank_agents(&task_snapshot, &all_agents)
+            .map(|ranked| ranked.into_iter().cloned().collect());
+        let (all_agents, mode_context) = if let Some(ranked) = strategy_ranked {
+            let context = format!(
+                "{}{}",
+                mode_context,
+                super::assignment::render_strategy_context(&strategy.name(), &ranked)
+            );
+            (ranked, context)
+        } else if policy == DispatchPolicy::Greedy {
+            (all_agents, mode_context)
+        } else {
+            let loads = fair_share::agent_loads(graph, Utc::now());
+            let reputations = if policy == DispatchPolicy::Reputation {
+                match reputation::refresh(&dir.join("agency"), graph) {
+                    Ok(store) => reputation::reputation_scores(&store, &all_agents),
+                    Err(e) => {
+                        eprintln!("[dispatcher] Failed to refresh agent reputation: {}", e);
+                        std::collections::HashMap::new()
+                    }
+                }
+            } else {
+                std::collections::HashMap::new()
+            };
+            let ranked: Vec<_> = fair_share::rank_agents(
+                policy,
+                &all_agents,
+                &task_snapshot,
+                &loads,
+                &matcher,
+                &reputations,
+            )
+            .into_iter()
+            .cloned()
+            .collect();
+            let context = format!(
+                "{}{}",
+                mode_context,
+                super::assignment::render_dispatch_context(policy, &ranked, &loads)
+            );
+            (ranked, context)
+        };
+
         // Try Agency assignment if configured
         if config.agency.assignment_source.as_deref() == Some("agency")
             && config.agency.agency_server_url.is_some()
@@ -1602,6 +1732,29 @@ fn build_auto_assign_tasks(
             }
         };
 
+        // The assigner may name an agent outside the catalog; hold it to the
+        // same capability check.
+        if let Some(source) = graph.get_task(&source_id) {
+            let unmet = capability::unmet_requirements(&resolved_agent, source, &matcher);
+            if !unmet.is_empty() {
+                let reasons: Vec<String> = unmet.iter().map(|u| u.to_string()).collect();
+                eprintln!(
+                    "[dispatcher] Assignment verdict agent '{}' can't take '{}' ({}), will retry next tick",
+                    resolved_agent.name,
+                    source_id,
+                    reasons.join("; ")
+                );
+                continue;
+            }
+        }
+        if let Some(over) = spend.check(&resolved_agent) {
+            eprintln!(
+                "[dispatcher] Assignment verdict agent '{}' is over its daily budget (${:.2} of ${:.2}), will retry next tick",
+                resolved_agent.name, over.spent, over.budget
+            );
+            continue;
+        }
+
         // Apply assignment to the source task
         if let Some(task) = graph.get_task_mut(&source_id) {
             task.agent = Some(resolved_agent.id.clone());
@@ -1805,19 +1958,25 @@ fn build_auto_assign_tasks(
                     estimate: None,
                     before: vec![],
                     after: causal_edge,
+                    edges: Default::default(),
                     requires: vec![],
                     tags: vec!["creation".to_string(), "agency".to_string()],
                     skills: vec![],
                     inputs: vec![],
                     deliverables: vec![],
+                    expects: vec![],
+                    gate: None,
                     artifacts: vec![],
+                    self_report: None,
                     exec: Some("wg agency create".to_string()),
                     timeout: None,
                     not_before: None,
+                    due: None,
                     created_at: Some(Utc::now().to_rfc3339()),
                     started_at: None,
                     completed_at: None,
                     last_interaction_at: None,
+                    revision: 0,
                     log: vec![],
                     retry_count: 0,
                     max_retries: Some(1),
@@ -1842,10 +2001,20 @@ fn build_auto_assign_tasks(
                     cycle_config: None,
                     exec_mode: Some("bare".to_string()),
                     token_usage: None,
+                    max_cost: None,
+                    min_context: None,
+                    claim_sha: None,
+                    changes: None,
                     session_id: None,
                     wait_condition: None,
                     checkpoint: None,
                     triage_count: 0,
+                    escalation_level: 0,
+                    blocked_at: None,
+                    deadline_alert: None,
+                    external_blocker: None,
+                    waiting_on: vec![],
+                    links: vec![],
                     resurrection_count: 0,
                     last_resurrected_at: None,
                     validation: None,
@@ -2201,19 +2370,25 @@ fn build_flip_verification_tasks(
             estimate: None,
             before: vec![],
             after: vec![source_task_id.clone()],
+            edges: Default::default(),
             requires: vec![],
             tags: vec!["verification".to_string(), "agency".to_string()],
             skills: vec![],
             inputs: vec![],
             deliverables: vec![],
+            expects: vec![],
+            gate: None,
             artifacts: vec![],
+            self_report: None,
             exec: None,
             timeout: None,
             not_before: None,
+            due: None,
             created_at: Some(Utc::now().to_rfc3339()),
             started_at: None,
             completed_at: None,
             last_interaction_at: None,
+            revision: 0,
             log: vec![],
             retry_count: 0,
             max_retries: Some(1),
@@ -2240,10 +2415,20 @@ fn build_flip_verification_tasks(
             // authority to edit files, run builds, and commit repairs.
             exec_mode: None,
             token_usage: None,
+            max_cost: None,
+            min_context: None,
+            claim_sha: None,
+            changes: None,
             session_id: None,
             wait_condition: None,
             checkpoint: None,
             triage_count: 0,
+            escalation_level: 0,
+            blocked_at: None,
+            deadline_alert: None,
+            external_blocker: None,
+            waiting_on: vec![],
+            links: vec![],
             resurrection_count: 0,
             last_resurrected_at: None,
             validation: None,
@@ -2476,19 +2661,25 @@ fn build_separate_verify_tasks(
             estimate: None,
             before: vec![],
             after: vec![source_task_id.clone()],
+            edges: Default::default(),
             requires: vec![],
             tags: vec!["verification".to_string(), "separate-verify".to_string()],
             skills: vec![],
             inputs: vec![],
             deliverables: vec![],
+            expects: vec![],
+            gate: None,
             artifacts: vec![],
+            self_report: None,
             exec: None,
             timeout: None,
             not_before: None,
+            due: None,
             created_at: Some(Utc::now().to_rfc3339()),
             started_at: None,
             completed_at: None,
             last_interaction_at: None,
+            revision: 0,
             log: vec![],
             retry_count: 0,
             max_retries: Some(1),
@@ -2513,10 +2704,20 @@ fn build_separate_verify_tasks(
             cycle_config: None,
             exec_mode: None,
             token_usage: None,
+            max_cost: None,
+            min_context: None,
+            claim_sha: None,
+            changes: None,
             session_id: None,
             wait_condition: None,
             checkpoint: None,
             triage_count: 0,
+            escalation_level: 0,
+            blocked_at: None,
+            deadline_alert: None,
+            external_blocker: None,
+            waiting_on: vec![],
+            links: vec![],
             resurrection_count: 0,
             last_resurrected_at: None,
             validation: None,
@@ -2681,19 +2882,25 @@ fn build_auto_evolve_task(
         estimate: None,
         before: vec![],
         after: causal_ids,
+        edges: Default::default(),
         requires: vec![],
         tags: vec!["evolution".to_string(), "agency".to_string()],
         skills: vec![],
         inputs: vec![],
         deliverables: vec![],
+        expects: vec![],
+        gate: None,
         artifacts: vec![],
+        self_report: None,
         exec: Some(format!("wg evolve --budget {}", budget)),
         timeout: None,
         not_before: None,
+        due: None,
         created_at: Some(Utc::now().to_rfc3339()),
         started_at: None,
         completed_at: None,
         last_interaction_at: None,
+        revision: 0,
         log: vec![],
         retry_count: 0,
         max_retries: Some(1),
@@ -2718,10 +2925,20 @@ fn build_auto_evolve_task(
         cycle_config: None,
         exec_mode: Some("bare".to_string()),
         token_usage: None,
+        max_cost: None,
+        min_context: None,
+        claim_sha: None,
+        changes: None,
         session_id: None,
         wait_condition: None,
         checkpoint: None,
         triage_count: 0,
+        escalation_level: 0,
+        blocked_at: None,
+        deadline_alert: None,
+        external_blocker: None,
+        waiting_on: vec![],
+        links: vec![],
         resurrection_count: 0,
         last_resurrected_at: None,
         validation: None,
@@ -2889,19 +3106,25 @@ fn build_auto_create_task(
         estimate: None,
         before: vec![],
         after: trigger_ids,
+        edges: Default::default(),
         requires: vec![],
         tags: vec!["creation".to_string(), "agency".to_string()],
         skills: vec![],
         inputs: vec![],
         deliverables: vec![],
+        expects: vec![],
+        gate: None,
         artifacts: vec![],
+        self_report: None,
         exec: Some("wg agency create".to_string()),
         timeout: None,
         not_before: None,
+        due: None,
         created_at: Some(Utc::now().to_rfc3339()),
         started_at: None,
         completed_at: None,
         last_interaction_at: None,
+        revision: 0,
         log: vec![],
         retry_count: 0,
         max_retries: Some(1),
@@ -2926,10 +3149,20 @@ fn build_auto_create_task(
         cycle_config: None,
         exec_mode: Some("bare".to_string()),
         token_usage: None,
+        max_cost: None,
+        min_context: None,
+        claim_sha: None,
+        changes: None,
         session_id: None,
         wait_condition: None,
         checkpoint: None,
         triage_count: 0,
+        escalation_level: 0,
+        blocked_at: None,
+        deadline_alert: None,
+        external_blocker: None,
+        waiting_on: vec![],
+        links: vec![],
         resurrection_count: 0,
         last_resurrected_at: None,
         validation: None,
@@ -3614,6 +3847,44 @@ exit $EXIT_CODE"#,
     Ok((agent_id, pid))
 }
 
+/// The dispatcher's default order (`coordinator.dispatch_strategy =
+/// "priority"`), see [`sort_tasks_by_priority_with_features`].
+struct PriorityStrategy<'c> {
+    config: &'c Config,
+}
+
+impl DispatchStrategy for PriorityStrategy<'_> {
+    fn name(&self) -> String {
+        strategy::DEFAULT_STRATEGY.to_string()
+    }
+
+    fn order_tasks<'a>(
+        &self,
+        graph: &workgraph::graph::WorkGraph,
+        ready: Vec<&'a Task>,
+    ) -> Vec<&'a Task> {
+        sort_tasks_by_priority_with_features(graph, ready, self.config)
+    }
+}
+
+/// The configured `coordinator.dispatch_strategy`; priority order when it
+/// is unset or invalid.
+fn dispatch_strategy<'c>(dir: &Path, config: &'c Config) -> Box<dyn DispatchStrategy + 'c> {
+    let spec = config
+        .coordinator
+        .dispatch_strategy
+        .as_deref()
+        .unwrap_or(strategy::DEFAULT_STRATEGY);
+    match strategy::load(spec, dir) {
+        Ok(Some(custom)) => custom,
+        Ok(None) => Box::new(PriorityStrategy { config }),
+        Err(e) => {
+            eprintln!("[dispatcher] {}; using priority order", e);
+            Box::new(PriorityStrategy { config })
+        }
+    }
+}
+
 /// Priority-aware task sorting with starvation prevention and priority inheritance.
 ///
 /// Features:
@@ -3623,37 +3894,21 @@ exit $EXIT_CODE"#,
 fn sort_tasks_by_priority_with_features<'a>(
     graph: &workgraph::graph::WorkGraph,
     tasks: Vec<&'a workgraph::graph::Task>,
-    _config: &Config,
+    config: &Config,
 ) -> Vec<&'a workgraph::graph::Task> {
-    use chrono::Utc;
-
-    // Starvation prevention threshold: tasks older than this get priority boost
-    let starvation_threshold_hours = 24; // Can be made configurable later
-    let now = Utc::now();
+    let aging_hours = config.coordinator.priority_aging_hours;
+    let now = chrono::Utc::now();
 
     let mut task_priorities: Vec<_> = tasks
         .into_iter()
         .map(|task| {
-            let mut effective_priority = task.priority;
-
-            // Starvation prevention: bump priority for old tasks
-            if let Some(ref created_at_str) = task.created_at
-                && let Ok(created_at) = chrono::DateTime::parse_from_rfc3339(created_at_str)
-            {
-                let age = now.signed_duration_since(created_at.with_timezone(&Utc));
-                let age_hours = age.num_hours();
-
-                if age_hours > starvation_threshold_hours {
-                    // Bump priority by one level for every 24 hours of waiting
-                    let bumps = (age_hours / starvation_threshold_hours) as usize;
-                    for _ in 0..bumps {
-                        effective_priority = boost_priority(effective_priority);
-                    }
-                    eprintln!(
-                        "[dispatcher] Priority bump: {} (age: {}h) -> {}",
-                        task.id, age_hours, effective_priority
-                    );
-                }
+            // Starvation prevention: one tier per `priority_aging_hours` waited
+            let mut effective_priority = aged_priority(task, now, aging_hours);
+            if effective_priority != task.priority {
+                eprintln!(
+                    "[dispatcher] Priority bump: {} (aged) {} -> {}",
+                    task.id, task.priority, effective_priority
+                );
             }
 
             // Priority inheritance: check if this task blocks any high-priority tasks
@@ -3913,6 +4168,40 @@ fn record_spawn_failure(
     tripped
 }
 
+/// Cap `slots` by what `coordinator.spawn_rate` still allows in the
+/// current window (see [`workgraph::dispatch::rate_limit`]).
+fn rate_limited_slots(dir: &Path, config: &Config, slots: usize, ready: usize) -> usize {
+    let Some(ref spec) = config.coordinator.spawn_rate else {
+        return slots;
+    };
+    let rate = match rate_limit::SpawnRate::parse(spec) {
+        Ok(rate) => rate,
+        Err(e) => {
+            eprintln!("[dispatcher] Ignoring coordinator.spawn_rate: {}", e);
+            return slots;
+        }
+    };
+    let registry = match AgentRegistry::load(dir) {
+        Ok(registry) => registry,
+        Err(e) => {
+            eprintln!(
+                "[dispatcher] Spawn rate: failed to load agent registry, holding spawns: {}",
+                e
+            );
+            return 0;
+        }
+    };
+    let allowed = rate.allowance(rate_limit::spawn_times(&registry), Utc::now());
+    let wanted = slots.min(ready);
+    if allowed < wanted {
+        eprintln!(
+            "[dispatcher] Spawn rate {} reached: starting {} of {} ready task(s) this tick",
+            rate, allowed, wanted
+        );
+    }
+    slots.min(allowed)
+}
+
 fn spawn_agents_for_ready_tasks(
     dir: &Path,
     graph: &workgraph::graph::WorkGraph,
@@ -3928,8 +4217,53 @@ fn spawn_agents_for_ready_tasks(
     let gp = graph_path(dir);
     let mut spawned = 0;
 
-    // Sort ready tasks by priority with starvation prevention and priority inheritance
-    let final_ready = sort_tasks_by_priority_with_features(graph, ready_tasks_raw, config);
+    // Order ready tasks under coordinator.dispatch_strategy (default: priority
+    // with starvation prevention and priority inheritance)
+    let strategy = dispatch_strategy(dir, config);
+    let mut final_ready = strategy.order_tasks(graph, ready_tasks_raw);
+    if strategy.name() != strategy::DEFAULT_STRATEGY && !final_ready.is_empty() {
+        let order: Vec<&str> = final_ready.iter().take(5).map(|t| t.id.as_str()).collect();
+        eprintln!(
+            "[dispatcher] {} dispatch order: [{}{}]",
+            strategy.name(),
+            order.join(", "),
+            if final_ready.len() > 5 { ", ..." } else { "" }
+        );
+    }
+
+    // Tasks that preempted a running agent take the freed slot first.
+    let preempt_targets = super::preempt::pending_targets(dir);
+    if !preempt_targets.is_empty() {
+        final_ready.sort_by_key(|t| !preempt_targets.contains(&t.id));
+    }
+
+    // Learning loop: keys with a poor track record run fewer agents at once.
+    let learned = if config.learning.enabled {
+        workgraph::learning::load_state(dir).adjustments
+    } else {
+        Vec::new()
+    };
+    let mut running_by_key: std::collections::HashMap<String, usize> =
+        std::collections::HashMap::new();
+    // Executor preflight: cached per executor/endpoint for the configured TTL.
+    let preflight_ttl = config.coordinator.executor_preflight_ttl;
+    let mut executor_health =
+        (preflight_ttl > 0).then(|| workgraph::service::ExecutorHealth::load(dir));
+    let mut held_executors: std::collections::HashSet<String> = std::collections::HashSet::new();
+    // Resource pools: slots held by in-progress tasks, plus any taken this tick
+    let mut pool_usage = workgraph::resource_pool::PoolUsage::from_graph(&config.pools, graph);
+    // Scoped pauses (`wg service pause --tag/--queue`)
+    let paused_scope = super::CoordinatorState::load_or_default(dir);
+    // Agents over their daily budget get no new work until the day rolls over
+    let over_budget = budget::DailySpend::today(graph, dir)
+        .over_budget(&agency::load_all_agents_or_warn(&agents_dir));
+    if !learned.is_empty() {
+        for t in graph.tasks().filter(|t| t.status == Status::InProgress) {
+            for key in workgraph::learning::task_keys(t) {
+                *running_by_key.entry(key).or_default() += 1;
+            }
+        }
+    }
 
     for task in final_ready.iter() {
         if spawned >= slots_available {
@@ -3940,6 +4274,62 @@ fn spawn_agents_for_ready_tasks(
             continue;
         }
 
+        if let Some(adj) = workgraph::learning::matching(&learned, task)
+            .into_iter()
+            .find(|a| {
+                a.max_concurrent
+                    .is_some_and(|cap| running_by_key.get(&a.key).copied().unwrap_or(0) >= cap)
+            })
+        {
+            eprintln!(
+                "[dispatcher] Holding '{}': learning loop caps {} at {} concurrent ({})",
+                task.id,
+                adj.key,
+                adj.max_concurrent.unwrap_or_default(),
+                adj.reason
+            );
+            continue;
+        }
+
+        if let Some(tag) = task
+            .tags
+            .iter()
+            .find(|t| paused_scope.paused_tags.contains(t))
+        {
+            eprintln!(
+                "[dispatcher] Holding '{}': tag '{}' is paused",
+                task.id, tag
+            );
+            continue;
+        }
+        if let Some(queue) = task
+            .requires
+            .iter()
+            .find(|r| paused_scope.paused_queues.contains(r))
+        {
+            eprintln!(
+                "[dispatcher] Holding '{}': queue '{}' is paused",
+                task.id, queue
+            );
+            continue;
+        }
+
+        if let Some((pool, cap)) = pool_usage.full_pool(task) {
+            eprintln!(
+                "[dispatcher] Holding '{}': resource pool '{}' is full ({} slot(s))",
+                task.id, pool, cap
+            );
+            continue;
+        }
+
+        if let Some(over) = task.agent.as_ref().and_then(|a| over_budget.get(a)) {
+            eprintln!(
+                "[dispatcher] Holding '{}': agent '{}' is over its daily budget (${:.2} of ${:.2})",
+                task.id, over.agent_name, over.spent, over.budget
+            );
+            continue;
+        }
+
         // Skip daemon-managed loop tasks — handled directly by the daemon, not spawned as agents
         if is_daemon_managed(task) {
             continue;
@@ -3990,6 +4380,7 @@ fn spawn_agents_for_ready_tasks(
             match spawn_shell_inline(dir, &task_id) {
                 Ok((agent_id, pid)) => {
                     eprintln!("[dispatcher] Spawned shell {} (PID {})", agent_id, pid);
+                    pool_usage.acquire(task);
                     spawned += 1;
                 }
                 Err(e) => {
@@ -4136,6 +4527,29 @@ fn spawn_agents_for_ready_tasks(
         };
         let effective_executor = plan.executor.as_str().to_string();
 
+        if let Some(health) = executor_health.as_mut() {
+            let check = health.check(
+                dir,
+                &effective_executor,
+                plan.endpoint.as_ref(),
+                preflight_ttl,
+            );
+            if !check.healthy {
+                let key = workgraph::service::executor_health::health_key(
+                    &effective_executor,
+                    plan.endpoint.as_ref(),
+                );
+                if held_executors.insert(key.clone()) {
+                    eprintln!(
+                        "[dispatcher] Executor '{}' unhealthy, holding its tasks: {}",
+                        key,
+                        check.problems.join("; ")
+                    );
+                }
+                continue;
+            }
+        }
+
         // Provenance: every spawn emits one line tracing each decision back to
         // the config knob that produced it. Eliminates silent-routing bugs.
         eprintln!(
@@ -4157,7 +4571,13 @@ fn spawn_agents_for_ready_tasks(
             Ok((agent_id, pid)) => {
                 eprintln!("[dispatcher] Spawned {} (PID {})", agent_id, pid);
                 record_dispatch(&gp, &task.id);
+                pool_usage.acquire(task);
                 spawned += 1;
+                if !learned.is_empty() {
+                    for key in workgraph::learning::task_keys(task) {
+                        *running_by_key.entry(key).or_default() += 1;
+                    }
+                }
             }
             Err(e) => {
                 eprintln!("[dispatcher] Failed to spawn for {}: {}", task.id, e);
@@ -4173,6 +4593,12 @@ fn spawn_agents_for_ready_tasks(
         }
     }
 
+    if let Some(health) = executor_health
+        && let Err(e) = health.save(dir)
+    {
+        eprintln!("[dispatcher] Failed to save executor health: {}", e);
+    }
+
     spawned
 }
 
@@ -4194,6 +4620,74 @@ fn record_dispatch(graph_path: &Path, task_id: &str) {
 
 /// Check alive agents and trigger auto-checkpoints when turn count or time
 /// thresholds are met. Calls haiku to summarize the agent's recent output.
+/// Trim agent output files that have grown past `guardrails.max_output_bytes`,
+/// recording a warning on the owning task the first time each file is cut.
+fn cap_agent_output(dir: &Path, config: &Config) {
+    let max_bytes = config.guardrails.max_output_bytes;
+    if max_bytes == 0 {
+        return;
+    }
+    let keep = match config.guardrails.output_truncation.as_str() {
+        "head" => Truncation::Head,
+        _ => Truncation::Tail,
+    };
+    let registry = match AgentRegistry::load(dir) {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+
+    let mut warnings: Vec<(String, String)> = Vec::new();
+    for agent in registry.agents.values() {
+        let output = Path::new(&agent.output_file);
+        let raw_stream = output.with_file_name("raw_stream.jsonl");
+        for path in [output, raw_stream.as_path()] {
+            match size_guard::cap_file(path, max_bytes, keep) {
+                Ok(Some(dropped)) => {
+                    let marker = path.with_extension("capped");
+                    if !marker.exists() {
+                        let _ = fs::write(&marker, "");
+                        warnings.push((
+                            agent.task_id.clone(),
+                            format!(
+                                "Output size guard: {} exceeded max_output_bytes={}; kept {} ({} bytes dropped)",
+                                path.file_name().unwrap_or_default().to_string_lossy(),
+                                max_bytes,
+                                keep.as_str(),
+                                dropped
+                            ),
+                        ));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!(
+                    "[dispatcher] Output cap failed for agent {}: {}",
+                    agent.id, e
+                ),
+            }
+        }
+    }
+
+    if warnings.is_empty() {
+        return;
+    }
+    for (task_id, warning) in &warnings {
+        eprintln!("[dispatcher] {} (task {})", warning, task_id);
+    }
+    let _ = modify_graph(&graph_path(dir), |graph| {
+        for (task_id, warning) in &warnings {
+            if let Some(task) = graph.get_task_mut(task_id) {
+                task.log.push(LogEntry {
+                    timestamp: Utc::now().to_rfc3339(),
+                    actor: Some("dispatcher".to_string()),
+                    user: None,
+                    message: warning.clone(),
+                });
+            }
+        }
+        true
+    });
+}
+
 fn auto_checkpoint_agents(dir: &Path, config: &Config) {
     let interval_turns = config.checkpoint.auto_interval_turns;
     let interval_mins = config.checkpoint.auto_interval_mins;
@@ -4374,6 +4868,33 @@ Respond with ONLY the summary text, no JSON or formatting."#
     Ok(result.text)
 }
 
+/// Set while a background gate check is running, so checks never overlap.
+static GATE_CHECK_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Run [`workgraph::gate::check_gates`] on a worker thread unless a previous
+/// check is still going.
+fn spawn_gate_check(dir: &Path) {
+    if GATE_CHECK_RUNNING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let dir = dir.to_path_buf();
+    std::thread::spawn(move || {
+        match workgraph::gate::check_gates(&dir, &graph_path(&dir), None) {
+            Ok(reports) => {
+                for report in reports.iter().filter(|r| r.is_open()) {
+                    eprintln!(
+                        "[dispatcher] Gate '{}' opened ({})",
+                        report.gate_id,
+                        report.summary()
+                    );
+                }
+            }
+            Err(e) => eprintln!("[dispatcher] Gate check warning: {}", e),
+        }
+        GATE_CHECK_RUNNING.store(false, Ordering::Release);
+    });
+}
+
 /// Single coordinator tick: spawn agents on ready tasks
 pub fn coordinator_tick(
     dir: &Path,
@@ -4391,6 +4912,19 @@ pub fn coordinator_tick(
     // below (max agents, no ready tasks) would skip chat processing otherwise.
     process_chat_inbox(dir);
 
+    // Phase 0.5: Resume agents preempted for urgent tasks that have finished,
+    // before counting so they reclaim their slots.
+    match super::preempt::resume_finished(dir) {
+        Ok(resumed) if !resumed.is_empty() => {
+            eprintln!(
+                "[dispatcher] Resumed preempted task(s): {}",
+                resumed.join(", ")
+            );
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("[dispatcher] Failed to resume preempted agents: {}", e),
+    }
+
     // Phase 1: Clean up dead agents and count alive ones
     let alive_count = match cleanup_and_count_alive(dir, &graph_path, max_agents)? {
         Ok(count) => count,
@@ -4452,11 +4986,27 @@ pub fn coordinator_tick(
         }
     }
 
+    // Phase 1.35: Cost caps — fail and stop runs that spent past their task's
+    // max_cost (or its tags' [cost_caps] default).
+    let over_cap = super::cost_cap::enforce_cost_caps(dir, &config);
+    if !over_cap.is_empty() {
+        eprintln!("[dispatcher] Cost caps: stopped {} run(s)", over_cap.len());
+    }
+
+    // Phase 1.4: Output size guard — keep agent logs under max_output_bytes
+    cap_agent_output(dir, &config);
+
     // Phase 1.5: Auto-checkpoint alive agents if thresholds are met
     auto_checkpoint_agents(dir, &config);
 
     let slots_available = max_agents.saturating_sub(alive_count);
 
+    // Phase 2.4: Phase gates — open gates whose upstream phase has settled and
+    // whose conditions all hold. Gate commands (e.g. linters) can run for
+    // minutes, so the check runs on a worker thread and dispatch carries on;
+    // a gate it opens unblocks its phase on a later tick.
+    spawn_gate_check(dir);
+
     // Phases 2.5–2.9: Graph maintenance (atomic load-modify-save).
     //
     // Each phase group uses `modify_graph` to hold the file lock across the
@@ -4480,7 +5030,7 @@ pub fn coordinator_tick(
         // (check_eval_gate would have already flipped it to Failed and spawned
         // a rescue), promote PendingEval → Done so downstream dependents
         // unblock. See docs in src/commands/done.rs::pick_done_target_status.
-        modified |= resolve_pending_eval_tasks(graph);
+        modified |= resolve_pending_eval_tasks(dir, graph);
 
         // Phase 2.47: FailedPendingEval resolution.
         // Tasks that exited without calling `wg done` enter FailedPendingEval;
@@ -4549,6 +5099,23 @@ pub fn coordinator_tick(
             }
         }
 
+        // Phase 2.96: Work stealing — idle slots take tasks queued by
+        // `wg claim --batch` back from busy actors.
+        if config.coordinator.work_stealing {
+            for stolen in workgraph::work_stealing::release_for_idle_slots(
+                graph,
+                slots_available,
+                chrono::Utc::now(),
+                config.coordinator.priority_aging_hours,
+            ) {
+                eprintln!(
+                    "[dispatcher] Work stealing: released '{}' from @{}'s queue",
+                    stolen.task_id, stolen.from
+                );
+                modified = true;
+            }
+        }
+
         // Phase 2.10: (极maps Removed) Placement is now merged into the assignment step.
         // No separate .place-* tasks are created or handled.
 
@@ -4602,6 +5169,21 @@ pub fn coordinator_tick(
         return Ok(early_result);
     }
 
+    // Phase 5.4: Check if spawning is paused for maintenance (manual or a
+    // scheduled pause window).
+    if let Some(m) = workgraph::maintenance::current(dir) {
+        eprintln!("[dispatcher] Spawning paused: maintenance ({})", m);
+        let cycle_analysis = graph.compute_cycle_analysis();
+        let final_ready = ready_tasks_with_peers_cycle_aware(&graph, dir, &cycle_analysis);
+        // Exclude daemon-managed loop tasks from ready count.
+        let ready_count = final_ready.iter().filter(|t| !is_daemon_managed(t)).count();
+        return Ok(TickResult {
+            agents_alive: alive_count,
+            tasks_ready: ready_count,
+            agents_spawned: 0,
+        });
+    }
+
     // Phase 5.5: Check if spawning is paused due to global API-down backoff.
     if super::zero_output::should_pause_spawning(dir) {
         eprintln!("[dispatcher] Spawning paused: global zero-output backoff active");
@@ -4660,7 +5242,7 @@ pub fn coordinator_tick(
         executor,
         &config,
         Some(effective_model.as_str()),
-        slots_available,
+        rate_limited_slots(dir, &config, slots_available, ready_count),
         config.agency.auto_assign,
     );
 
@@ -4841,6 +5423,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         }
     }
 
@@ -6663,6 +7247,17 @@ mod tests {
             None,         // cron
             false,        // allow_phantom
             false,        // allow_cycle
+            None,         // priority
+            None,         // due
+            &[],          // add_waiting_on
+            &[],          // remove_waiting_on
+            &[],          // lag
+            &[],          // edge_weight
+            None,         // external_blocker
+            None,         // blocker_contact
+            None,         // blocker_expected
+            None,         // max_cost
+            None,         // min_context
         )
         .unwrap();
 
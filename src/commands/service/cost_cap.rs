@@ -0,0 +1,192 @@
+//! Per-task cost caps.
+//!
+//! A task's `max_cost` (or the strictest `[cost_caps]` entry among its tags)
+//! bounds what a single run may spend. Every tick the dispatcher prices each
+//! running agent's output so far; once a run passes its cap the task is
+//! failed with `cost-cap-exceeded` and the agent is stopped. A run that
+//! blows its budget is usually a task that is too big for one agent, so the
+//! failure reason suggests decomposing it.
+
+use std::path::Path;
+
+use workgraph::config::{Config, resolve_cost_cap};
+use workgraph::graph::{FailureClass, Status, TokenUsage, format_tokens, parse_token_usage_live};
+use workgraph::parser::{load_graph, modify_graph};
+use workgraph::service::registry::AgentRegistry;
+
+use crate::commands::{graph_path, kill_process_graceful};
+
+/// A running agent whose spend has passed its task's cap.
+#[derive(Debug)]
+pub struct OverCap {
+    pub agent_id: String,
+    pub task_id: String,
+    pub pid: u32,
+    pub usage: TokenUsage,
+    pub cap: f64,
+}
+
+/// The failure reason recorded on a task whose run hit its cap.
+fn failure_reason(usage: &TokenUsage, cap: f64) -> String {
+    format!(
+        "Cost cap exceeded: run spent ${:.2} of its ${:.2} cap ({} tokens). \
+         The task is likely too big for one agent: decompose it into smaller \
+         subtasks, or raise the cap with 'wg edit --max-cost'",
+        usage.cost_usd,
+        cap,
+        format_tokens(usage.total_tokens())
+    )
+}
+
+/// Alive agents on in-progress tasks that have spent more than their cap.
+pub fn find_over_cap(dir: &Path, config: &Config) -> Vec<OverCap> {
+    let Ok(registry) = AgentRegistry::load(dir) else {
+        return Vec::new();
+    };
+    let Ok(graph) = load_graph(graph_path(dir)) else {
+        return Vec::new();
+    };
+
+    let mut over = Vec::new();
+    for agent in registry.list_alive_agents() {
+        let Some(task) = graph.get_task(&agent.task_id) else {
+            continue;
+        };
+        if task.status != Status::InProgress {
+            continue;
+        }
+        let Some(cap) = resolve_cost_cap(&config.cost_caps, task) else {
+            continue;
+        };
+        let Some(usage) = parse_token_usage_live(Path::new(&agent.output_file)) else {
+            continue;
+        };
+        if usage.cost_usd > cap {
+            over.push(OverCap {
+                agent_id: agent.id.clone(),
+                task_id: agent.task_id.clone(),
+                pid: agent.pid,
+                usage,
+                cap,
+            });
+        }
+    }
+    over
+}
+
+/// Fail and stop every run that has passed its cost cap. Returns the runs
+/// that were stopped.
+pub fn enforce_cost_caps(dir: &Path, config: &Config) -> Vec<OverCap> {
+    let mut stopped = Vec::new();
+    for run in find_over_cap(dir, config) {
+        eprintln!(
+            "[cost-cap] Task '{}' ({}) spent ${:.2}, over its ${:.2} cap; stopping it",
+            run.task_id, run.agent_id, run.usage.cost_usd, run.cap
+        );
+        // Fail before killing so the wrapper's exit handling finds the task
+        // already failed and leaves the structured reason in place.
+        let reason = failure_reason(&run.usage, run.cap);
+        if let Err(e) = crate::commands::fail::run(
+            dir,
+            &run.task_id,
+            Some(&reason),
+            Some(FailureClass::CostCapExceeded),
+        ) {
+            eprintln!("[cost-cap] Failed to fail task '{}': {}", run.task_id, e);
+            continue;
+        }
+        // A killed run never writes its final usage line; keep what it spent.
+        let _ = modify_graph(graph_path(dir), |graph| {
+            match graph.get_task_mut(&run.task_id) {
+                Some(task) if task.token_usage.is_none() => {
+                    task.token_usage = Some(run.usage.clone());
+                    true
+                }
+                _ => false,
+            }
+        });
+        if let Err(e) = kill_process_graceful(run.pid, 5) {
+            eprintln!(
+                "[cost-cap] Failed to stop {} (PID {}): {}",
+                run.agent_id, run.pid, e
+            );
+        }
+        stopped.push(run);
+    }
+    stopped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+    use workgraph::graph::{Node, Task, WorkGraph};
+    use workgraph::parser::save_graph;
+
+    fn setup(dir: &Path, max_cost: Option<f64>, tags: &[&str], spent: f64) {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(Task {
+            id: "big".to_string(),
+            title: "Big".to_string(),
+            status: Status::InProgress,
+            max_cost,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }));
+        save_graph(&graph, graph_path(dir)).unwrap();
+
+        let output = dir.join("output.log");
+        fs::write(
+            &output,
+            format!(
+                r#"{{"type":"result","total_cost_usd":{},"usage":{{"input_tokens":1000,"output_tokens":500}}}}"#,
+                spent
+            ),
+        )
+        .unwrap();
+        let mut registry = AgentRegistry::new();
+        registry.register_agent(
+            std::process::id(),
+            "big",
+            "claude",
+            output.to_str().unwrap(),
+        );
+        registry.save(dir).unwrap();
+    }
+
+    #[test]
+    fn test_task_cap_and_tag_default() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let mut config = Config::default();
+
+        setup(dir, Some(1.0), &[], 0.75);
+        assert!(find_over_cap(dir, &config).is_empty());
+
+        setup(dir, Some(1.0), &[], 1.25);
+        let over = find_over_cap(dir, &config);
+        assert_eq!(over.len(), 1);
+        assert_eq!(over[0].task_id, "big");
+        assert_eq!(over[0].cap, 1.0);
+
+        setup(dir, None, &["chore"], 1.25);
+        assert!(find_over_cap(dir, &config).is_empty());
+        config.cost_caps.insert("chore".to_string(), 0.5);
+        assert_eq!(find_over_cap(dir, &config).len(), 1);
+    }
+
+    #[test]
+    fn test_failure_reason_suggests_decomposition() {
+        let usage = TokenUsage {
+            cost_usd: 3.2,
+            input_tokens: 1000,
+            output_tokens: 500,
+            cache_read_input_tokens: 0,
+            cache_creation_input_tokens: 0,
+        };
+        let reason = failure_reason(&usage, 3.0);
+        assert!(reason.contains("$3.20 of its $3.00 cap"));
+        assert!(reason.contains("decompose"));
+    }
+}
@@ -0,0 +1,305 @@
+//! `wg service drain` — stop dispatching, let running agents finish, then
+//! stop the daemon.
+//!
+//! `wg service stop` leaves detached agents running with nobody to reap
+//! them or dispatch what they unblock. Draining pauses dispatch first,
+//! waits for the agents in the registry to exit (up to a timeout), writes a
+//! checkpoint of the registry to `.wg/service/drain-checkpoint.json`, and
+//! only then shuts the daemon down.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use workgraph::graph::parse_delay;
+use workgraph::service::registry::{AgentEntry, AgentRegistry};
+
+use super::{CoordinatorState, IpcRequest, ServiceState, send_request};
+use crate::commands::is_process_alive;
+
+/// How long `wg service drain` waits for agents without `--timeout`.
+pub const DEFAULT_DRAIN_TIMEOUT: &str = "30m";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Registry snapshot written when a drain finishes.
+#[derive(Debug, Serialize)]
+struct DrainCheckpoint<'a> {
+    drained_at: String,
+    /// True when agents were still running at the timeout
+    timed_out: bool,
+    /// Agents that outlived the timeout (they keep running detached)
+    still_running: &'a [&'a str],
+    registry: &'a AgentRegistry,
+}
+
+fn checkpoint_path(dir: &Path) -> PathBuf {
+    dir.join("service").join("drain-checkpoint.json")
+}
+
+/// Agents the registry considers alive whose process still exists.
+fn running_agents(registry: &AgentRegistry) -> Vec<&AgentEntry> {
+    let mut running: Vec<&AgentEntry> = registry
+        .list_alive_agents()
+        .into_iter()
+        .filter(|a| is_process_alive(a.pid))
+        .collect();
+    running.sort_by(|a, b| a.id.cmp(&b.id));
+    running
+}
+
+/// Poll the registry until no agent is running or `deadline` passes, and
+/// return the last registry read. `on_wait` is called with the running
+/// agents whenever their number changes.
+fn wait_for_agents(
+    dir: &Path,
+    deadline: Instant,
+    poll: Duration,
+    mut on_wait: impl FnMut(&[&AgentEntry]),
+) -> AgentRegistry {
+    let mut last_waiting: Option<usize> = None;
+    loop {
+        let registry = AgentRegistry::load_or_warn(dir);
+        let running = running_agents(&registry);
+        if running.is_empty() || Instant::now() >= deadline {
+            return registry;
+        }
+        if last_waiting != Some(running.len()) {
+            on_wait(&running);
+            last_waiting = Some(running.len());
+        }
+        std::thread::sleep(poll);
+    }
+}
+
+/// Write the registry checkpoint. Returns its path.
+fn write_checkpoint(
+    dir: &Path,
+    registry: &AgentRegistry,
+    still_running: &[&str],
+) -> Result<PathBuf> {
+    let checkpoint = DrainCheckpoint {
+        drained_at: Utc::now().to_rfc3339(),
+        timed_out: !still_running.is_empty(),
+        still_running,
+        registry,
+    };
+    let path = checkpoint_path(dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&checkpoint)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Drain the service: pause dispatch, wait up to `timeout` for running
+/// agents to exit, checkpoint the registry, then stop the daemon. Agents
+/// still running at the timeout are left running, as with `wg service stop`.
+#[cfg(unix)]
+pub fn run_drain(dir: &Path, timeout: Option<&str>, json: bool) -> Result<()> {
+    super::guard_agent_stop_pause()?;
+
+    let timeout_str = timeout.unwrap_or(DEFAULT_DRAIN_TIMEOUT);
+    let timeout_secs = parse_delay(timeout_str).with_context(|| {
+        format!(
+            "Invalid --timeout '{}'. Use a duration like 90s, 30m, 2h",
+            timeout_str
+        )
+    })?;
+
+    let Some(state) = ServiceState::load(dir)? else {
+        if json {
+            let output = serde_json::json!({ "error": "Service not running" });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            println!("Service not running");
+        }
+        return Ok(());
+    };
+
+    // Stop dispatching. The pause is persisted, so remember whether it was
+    // already set: a drained service should start up the way it was.
+    let was_paused = CoordinatorState::load_or_default(dir).paused;
+    let response = send_request(
+        dir,
+        &IpcRequest::Pause {
+            tags: Vec::new(),
+            queues: Vec::new(),
+        },
+    )?;
+    if !response.ok {
+        let msg = response
+            .error
+            .unwrap_or_else(|| "Unknown error".to_string());
+        anyhow::bail!("Failed to pause dispatch: {}", msg);
+    }
+    if !json {
+        println!("Draining: dispatch paused, no new agents will be spawned");
+    }
+
+    let started = Instant::now();
+    let deadline = started + Duration::from_secs(timeout_secs);
+    let registry = wait_for_agents(dir, deadline, POLL_INTERVAL, |running| {
+        if json {
+            return;
+        }
+        let tasks: Vec<String> = running
+            .iter()
+            .map(|a| format!("{} ({})", a.id, a.task_id))
+            .collect();
+        println!(
+            "Waiting for {} agent(s): {}",
+            running.len(),
+            tasks.join(", ")
+        );
+    });
+
+    let still_running: Vec<&str> = running_agents(&registry)
+        .iter()
+        .map(|a| a.id.as_str())
+        .collect();
+    let path = write_checkpoint(dir, &registry, &still_running)?;
+
+    let orphan_count = super::shutdown_daemon(dir, &state, false, false)?;
+
+    if !was_paused {
+        let mut coord_state = CoordinatorState::load_or_default(dir);
+        coord_state.paused = false;
+        coord_state.save(dir);
+    }
+
+    if json {
+        let output = serde_json::json!({
+            "status": "drained",
+            "pid": state.pid,
+            "waited_secs": started.elapsed().as_secs(),
+            "timed_out": !still_running.is_empty(),
+            "still_running": still_running,
+            "checkpoint": path,
+            "orphans_killed": orphan_count,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        if still_running.is_empty() {
+            println!(
+                "Service drained and stopped (PID {}) after {}",
+                state.pid,
+                workgraph::format_duration(started.elapsed().as_secs() as i64, true)
+            );
+        } else {
+            println!(
+                "Timed out after {}; stopped the service (PID {}) with {} agent(s) still running: {}",
+                timeout_str,
+                state.pid,
+                still_running.len(),
+                still_running.join(", ")
+            );
+        }
+        println!("Registry checkpoint: {}", path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn run_drain(_dir: &Path, _timeout: Option<&str>, _json: bool) -> Result<()> {
+    anyhow::bail!("Service daemon is only supported on Unix systems")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use workgraph::service::registry::AgentStatus;
+
+    /// PID of a process that has already exited and been reaped.
+    fn dead_pid() -> u32 {
+        let mut child = Command::new("true").spawn().unwrap();
+        child.wait().unwrap();
+        child.id()
+    }
+
+    fn registry_with(agents: &[(u32, &str, AgentStatus)]) -> AgentRegistry {
+        let mut registry = AgentRegistry::default();
+        for (pid, task_id, status) in agents {
+            let id = registry.register_agent(*pid, task_id, "claude", "");
+            registry.set_status(&id, *status);
+        }
+        registry
+    }
+
+    #[test]
+    fn test_running_agents_needs_live_status_and_process() {
+        let me = std::process::id();
+        let registry = registry_with(&[
+            (me, "b", AgentStatus::Working),
+            (dead_pid(), "c", AgentStatus::Working),
+            (me, "d", AgentStatus::Dead),
+            (me, "a", AgentStatus::Working),
+        ]);
+        let ids: Vec<&str> = running_agents(&registry)
+            .iter()
+            .map(|a| a.id.as_str())
+            .collect();
+        assert_eq!(ids, ["agent-1", "agent-4"]);
+    }
+
+    #[test]
+    fn test_wait_returns_once_agents_are_gone() {
+        let dir = tempfile::tempdir().unwrap();
+        registry_with(&[(dead_pid(), "a", AgentStatus::Working)])
+            .save(dir.path())
+            .unwrap();
+
+        let mut waits = 0;
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let registry = wait_for_agents(dir.path(), deadline, Duration::from_millis(10), |_| {
+            waits += 1
+        });
+        assert_eq!(waits, 0);
+        assert_eq!(registry.agents.len(), 1);
+    }
+
+    #[test]
+    fn test_wait_times_out_and_checkpoints_survivors() {
+        let dir = tempfile::tempdir().unwrap();
+        registry_with(&[(std::process::id(), "a", AgentStatus::Working)])
+            .save(dir.path())
+            .unwrap();
+
+        let mut waits = Vec::new();
+        let deadline = Instant::now() + Duration::from_millis(100);
+        let registry = wait_for_agents(dir.path(), deadline, Duration::from_millis(10), |r| {
+            waits.push(r.len())
+        });
+        // Reported once, not on every poll
+        assert_eq!(waits, [1]);
+
+        let still_running: Vec<&str> = running_agents(&registry)
+            .iter()
+            .map(|a| a.id.as_str())
+            .collect();
+        let path = write_checkpoint(dir.path(), &registry, &still_running).unwrap();
+        assert_eq!(path, checkpoint_path(dir.path()));
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["timed_out"], true);
+        assert_eq!(saved["still_running"], serde_json::json!(["agent-1"]));
+        assert_eq!(saved["registry"]["agents"]["agent-1"]["task_id"], "a");
+    }
+
+    #[test]
+    fn test_checkpoint_after_clean_drain() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = registry_with(&[(dead_pid(), "a", AgentStatus::Done)]);
+        let path = write_checkpoint(dir.path(), &registry, &[]).unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["timed_out"], false);
+        assert_eq!(saved["still_running"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,385 @@
+//! gRPC control API for the service daemon (`wg service start --grpc <addr>`).
+//!
+//! Implements the `Control` service of `proto/workgraph/v1/control.proto`.
+//! Coordinator requests (status, graph changed, kick, pause, resume, add
+//! task) are forwarded to the daemon over its own IPC socket, so they behave
+//! exactly like the CLI; claim and done run the same code as `wg claim` and
+//! `wg done`. Calls need `authorization: Bearer <token>` metadata with the
+//! HTTP API's token (see [`super::http_api::token_path`]). No TLS: keep it
+//! on localhost or behind a proxy.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use tonic::transport::Server;
+use tonic::transport::server::TcpIncoming;
+use tonic::{Request, Response, Status};
+use workgraph::graph::Task;
+use workgraph::grpc as pb;
+use workgraph::grpc::control_server::{Control, ControlServer};
+use workgraph::parser::load_graph;
+
+use super::DaemonLogger;
+use super::http_api::token_matches;
+use super::ipc::IpcRequest;
+use crate::commands::graph_path;
+
+struct ControlService {
+    dir: PathBuf,
+}
+
+/// Run blocking graph or IPC work off the async runtime.
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T, Status> + Send + 'static,
+) -> Result<T, Status> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+}
+
+/// Send `request` to the daemon and return its data.
+fn ipc(dir: &Path, request: &IpcRequest) -> Result<Value, Status> {
+    let resp =
+        super::send_request(dir, request).map_err(|e| Status::unavailable(format!("{:#}", e)))?;
+    if resp.ok {
+        Ok(resp.data.unwrap_or(Value::Null))
+    } else {
+        Err(Status::failed_precondition(resp.error.unwrap_or_default()))
+    }
+}
+
+/// Send a coordinator request that is answered with an action.
+async fn ack(dir: &Path, request: IpcRequest) -> Result<Response<pb::Ack>, Status> {
+    let dir = dir.to_path_buf();
+    let data = blocking(move || ipc(&dir, &request)).await?;
+    let action = data
+        .get("action")
+        .or_else(|| data.get("status"))
+        .and_then(Value::as_str)
+        .unwrap_or("ok");
+    Ok(Response::new(pb::Ack {
+        action: action.to_string(),
+    }))
+}
+
+fn to_message(task: &Task) -> pb::Task {
+    let json = serde_json::to_value(task).unwrap_or(Value::Null);
+    pb::Task {
+        id: task.id.clone(),
+        title: task.title.clone(),
+        status: json["status"].as_str().unwrap_or_default().to_string(),
+        description: task.description.clone(),
+        after: task.after.clone(),
+        tags: task.tags.clone(),
+        assigned: task.assigned.clone(),
+        created_at: task.created_at.clone(),
+        completed_at: task.completed_at.clone(),
+        json: json.to_string(),
+    }
+}
+
+fn task_reply(dir: &Path, id: &str) -> Result<pb::TaskReply, Status> {
+    let graph = load_graph(graph_path(dir)).map_err(|e| Status::internal(format!("{:#}", e)))?;
+    let task = graph
+        .get_task(id)
+        .ok_or_else(|| Status::not_found(format!("Task '{}' not found", id)))?;
+    Ok(pb::TaskReply {
+        task: Some(to_message(task)),
+    })
+}
+
+/// Run a task command and answer with the updated task. A command that
+/// refuses (already claimed, unmet dependencies, ...) is FAILED_PRECONDITION.
+async fn run_command(
+    dir: &Path,
+    id: String,
+    command: impl FnOnce(&Path, &str) -> Result<()> + Send + 'static,
+) -> Result<Response<pb::TaskReply>, Status> {
+    let dir = dir.to_path_buf();
+    blocking(move || {
+        task_reply(&dir, &id)?;
+        command(&dir, &id).map_err(|e| Status::failed_precondition(format!("{:#}", e)))?;
+        task_reply(&dir, &id)
+    })
+    .await
+    .map(Response::new)
+}
+
+fn count(data: &Value, pointer: &str) -> u32 {
+    data.pointer(pointer).and_then(Value::as_u64).unwrap_or(0) as u32
+}
+
+fn string(data: &Value, pointer: &str) -> Option<String> {
+    data.pointer(pointer)
+        .and_then(Value::as_str)
+        .map(String::from)
+}
+
+#[tonic::async_trait]
+impl Control for ControlService {
+    async fn status(
+        &self,
+        _request: Request<pb::StatusRequest>,
+    ) -> Result<Response<pb::StatusReply>, Status> {
+        let dir = self.dir.clone();
+        let data = blocking(move || ipc(&dir, &IpcRequest::Status)).await?;
+        Ok(Response::new(pb::StatusReply {
+            pid: count(&data, "/pid"),
+            socket: string(&data, "/socket").unwrap_or_default(),
+            started_at: string(&data, "/started_at").unwrap_or_default(),
+            agents_alive: count(&data, "/agents/alive"),
+            agents_idle: count(&data, "/agents/idle"),
+            agents_total: count(&data, "/agents/total"),
+            coordinator_enabled: data
+                .pointer("/coordinator/enabled")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            paused: data
+                .pointer("/coordinator/paused")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            max_agents: count(&data, "/coordinator/max_agents"),
+            poll_interval_secs: data
+                .pointer("/coordinator/poll_interval")
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+            executor: string(&data, "/coordinator/executor").unwrap_or_default(),
+            model: string(&data, "/coordinator/model"),
+            ticks: data
+                .pointer("/coordinator/ticks")
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+            tasks_ready: count(&data, "/coordinator/tasks_ready"),
+        }))
+    }
+
+    async fn graph_changed(
+        &self,
+        _request: Request<pb::GraphChangedRequest>,
+    ) -> Result<Response<pb::Ack>, Status> {
+        ack(&self.dir, IpcRequest::GraphChanged).await
+    }
+
+    async fn kick_dispatcher(
+        &self,
+        _request: Request<pb::KickDispatcherRequest>,
+    ) -> Result<Response<pb::Ack>, Status> {
+        ack(&self.dir, IpcRequest::KickDispatcher).await
+    }
+
+    async fn pause(
+        &self,
+        _request: Request<pb::PauseRequest>,
+    ) -> Result<Response<pb::Ack>, Status> {
+        ack(
+            &self.dir,
+            IpcRequest::Pause {
+                tags: Vec::new(),
+                queues: Vec::new(),
+            },
+        )
+        .await
+    }
+
+    async fn resume(
+        &self,
+        _request: Request<pb::ResumeRequest>,
+    ) -> Result<Response<pb::Ack>, Status> {
+        ack(
+            &self.dir,
+            IpcRequest::Resume {
+                tags: Vec::new(),
+                queues: Vec::new(),
+            },
+        )
+        .await
+    }
+
+    async fn add_task(
+        &self,
+        request: Request<pb::AddTaskRequest>,
+    ) -> Result<Response<pb::TaskReply>, Status> {
+        let req = request.into_inner();
+        if req.title.trim().is_empty() {
+            return Err(Status::invalid_argument("title is required"));
+        }
+        let dir = self.dir.clone();
+        blocking(move || {
+            let data = ipc(
+                &dir,
+                &IpcRequest::AddTask {
+                    title: req.title,
+                    id: req.id,
+                    description: req.description,
+                    after: req.after,
+                    tags: req.tags,
+                    skills: req.skills,
+                    deliverables: req.deliverables,
+                    model: req.model,
+                    verify: req.verify,
+                    verify_timeout: None,
+                    origin: Some("grpc".to_string()),
+                    cron: None,
+                },
+            )?;
+            let id = string(&data, "/task_id")
+                .ok_or_else(|| Status::internal("service did not return a task id"))?;
+            task_reply(&dir, &id)
+        })
+        .await
+        .map(Response::new)
+    }
+
+    async fn get_task(
+        &self,
+        request: Request<pb::GetTaskRequest>,
+    ) -> Result<Response<pb::TaskReply>, Status> {
+        let dir = self.dir.clone();
+        let id = request.into_inner().id;
+        blocking(move || task_reply(&dir, &id))
+            .await
+            .map(Response::new)
+    }
+
+    async fn claim(
+        &self,
+        request: Request<pb::ClaimRequest>,
+    ) -> Result<Response<pb::TaskReply>, Status> {
+        let req = request.into_inner();
+        let actor = req.actor;
+        run_command(&self.dir, req.id, move |dir, id| {
+            crate::commands::claim::claim(dir, id, actor.as_deref())
+        })
+        .await
+    }
+
+    async fn done(
+        &self,
+        request: Request<pb::DoneRequest>,
+    ) -> Result<Response<pb::TaskReply>, Status> {
+        run_command(&self.dir, request.into_inner().id, |dir, id| {
+            crate::commands::done::run(dir, id, false, false, false, false, false)
+        })
+        .await
+    }
+}
+
+/// Bind `addr` and serve the API on a background runtime.
+pub fn spawn(dir: PathBuf, addr: &str, token: String, logger: DaemonLogger) -> Result<()> {
+    let addr: SocketAddr = addr
+        .parse()
+        .with_context(|| format!("Invalid gRPC address '{}'", addr))?;
+    // Bind here so a taken port is reported at startup, not from the thread
+    let listener = std::net::TcpListener::bind(addr)
+        .with_context(|| format!("Failed to bind gRPC API on {}", addr))?;
+    listener.set_nonblocking(true)?;
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start gRPC runtime")?;
+
+    std::thread::spawn(move || {
+        runtime.block_on(async move {
+            let incoming = tokio::net::TcpListener::from_std(listener)
+                .map_err(|e| anyhow::anyhow!(e))
+                .and_then(|l| {
+                    TcpIncoming::from_listener(l, true, None).map_err(|e| anyhow::anyhow!(e))
+                });
+            let incoming = match incoming {
+                Ok(incoming) => incoming,
+                Err(e) => {
+                    logger.error(&format!("gRPC API: {:#}", e));
+                    return;
+                }
+            };
+            let service = ControlServer::with_interceptor(ControlService { dir }, move |req| {
+                authorize(req, &token)
+            });
+            if let Err(e) = Server::builder()
+                .add_service(service)
+                .serve_with_incoming(incoming)
+                .await
+            {
+                logger.error(&format!("gRPC API stopped: {}", e));
+            }
+        });
+    });
+    Ok(())
+}
+
+fn authorize(req: Request<()>, token: &str) -> Result<Request<()>, Status> {
+    let given = req
+        .metadata()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    match given {
+        Some(given) if token_matches(given, token) => Ok(req),
+        _ => Err(Status::unauthenticated("missing or invalid bearer token")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workgraph::graph::Status as TaskStatus;
+    use workgraph::test_helpers::{make_task_with_status, setup_workgraph};
+
+    #[test]
+    fn test_authorize_checks_bearer_token() {
+        let mut req = Request::new(());
+        assert!(authorize(Request::new(()), "s3cret").is_err());
+        req.metadata_mut()
+            .insert("authorization", "Bearer s3cret".parse().unwrap());
+        assert!(authorize(req, "s3cret").is_ok());
+        let mut req = Request::new(());
+        req.metadata_mut()
+            .insert("authorization", "Bearer wrong!".parse().unwrap());
+        assert_eq!(
+            authorize(req, "s3cret").unwrap_err().code(),
+            tonic::Code::Unauthenticated
+        );
+    }
+
+    #[tokio::test]
+    async fn test_claim_and_get_task() {
+        let dir = tempfile::tempdir().unwrap();
+        setup_workgraph(
+            dir.path(),
+            vec![make_task_with_status("a", "Task A", TaskStatus::Open)],
+        );
+        let service = ControlService {
+            dir: dir.path().to_path_buf(),
+        };
+
+        let reply = service
+            .claim(Request::new(pb::ClaimRequest {
+                id: "a".to_string(),
+                actor: Some("orchestrator".to_string()),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let task = reply.task.unwrap();
+        assert_eq!(task.status, "in-progress");
+        assert_eq!(task.assigned.as_deref(), Some("orchestrator"));
+
+        let err = service
+            .claim(Request::new(pb::ClaimRequest {
+                id: "a".to_string(),
+                actor: None,
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+        let err = service
+            .get_task(Request::new(pb::GetTaskRequest {
+                id: "missing".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+}
@@ -0,0 +1,533 @@
+//! JSON REST API for the service daemon (`wg service start --http <addr>`).
+//!
+//! Lets web frontends and other tools read the graph and drive the common
+//! task commands without shelling out to `wg`. Every request must carry
+//! `Authorization: Bearer <token>`; the token is the contents of
+//! `.wg/service/http.token`, created with a random value on first start.
+//!
+//! | Method | Path | Action |
+//! |--------|------|--------|
+//! | GET | `/api/status` | Service status, as `wg service status` |
+//! | GET | `/api/tasks` | All tasks; `?status=` and `?tag=` filter |
+//! | GET | `/api/tasks/ready` | Tasks ready to work on, as `wg ready` |
+//! | GET | `/api/tasks/<id>` | One task |
+//! | POST | `/api/tasks` | Add a task; body as the `add_task` IPC request |
+//! | POST | `/api/tasks/<id>/claim` | Claim a task; optional `{"actor": ...}` |
+//! | POST | `/api/tasks/<id>/done` | Mark a task done |
+//! | POST | `/api/inbox` | File a request for triage; `{"title", "body"?, "submitter"?}` |
+//! | GET | `/api/events` | Server-sent event stream of graph changes |
+//!
+//! `/api/events` stays open and sends one SSE message per
+//! [`GraphEvent`](workgraph::service::graph_events::GraphEvent), named by its
+//! type (`task_created`, `status_changed`, `agent_spawned`,
+//! `heartbeat_missed`) with the event as JSON data. Browsers' `EventSource`
+//! cannot set headers, so this endpoint also takes the token as
+//! `?access_token=`.
+//!
+//! Uses the same minimal HTTP handling as the webhook listener (see
+//! [`workgraph::service::http`]): no TLS, so keep it on localhost or behind
+//! a reverse proxy.
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use std::fs;
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
+use workgraph::graph::{Status, Task};
+use workgraph::parser::load_graph;
+use workgraph::query::ready_tasks_with_peers_cycle_aware;
+use workgraph::service::graph_events::{self, EventHub};
+use workgraph::service::http::{
+    ConnectionLimit, Request, read_request, reject_busy, write_response,
+};
+
+use super::DaemonLogger;
+use super::ipc::{self, IpcRequest};
+use crate::commands::graph_path;
+
+/// Comment line sent on an idle event stream so proxies keep it open and
+/// departed clients are noticed.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// File holding the bearer token, relative to the workgraph directory.
+pub fn token_path(dir: &Path) -> PathBuf {
+    dir.join("service").join("http.token")
+}
+
+/// Read the API token, creating one if the file does not exist yet.
+pub fn load_or_create_token(dir: &Path) -> Result<String> {
+    let path = token_path(dir);
+    if let Ok(token) = fs::read_to_string(&path) {
+        let token = token.trim().to_string();
+        if !token.is_empty() {
+            return Ok(token);
+        }
+    }
+    let token = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, format!("{}\n", token))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(token)
+}
+
+/// Bind `addr` and serve the API on background threads, one per connection,
+/// up to [`workgraph::service::http::MAX_CONNECTIONS`] at once.
+pub fn spawn(dir: PathBuf, addr: &str, token: String, logger: DaemonLogger) -> Result<()> {
+    let listener =
+        TcpListener::bind(addr).with_context(|| format!("Failed to bind HTTP API on {}", addr))?;
+    let config = workgraph::config::Config::load_or_default(&dir);
+    let hub = Arc::new(EventHub::default());
+    graph_events::spawn_publisher(
+        dir.clone(),
+        hub.clone(),
+        (config.agent.heartbeat_timeout * 60) as i64, // Config is in minutes
+    );
+    let limit = ConnectionLimit::default();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let Some(slot) = limit.try_acquire() else {
+                reject_busy(stream);
+                continue;
+            };
+            let (dir, token, logger, hub) =
+                (dir.clone(), token.clone(), logger.clone(), hub.clone());
+            std::thread::spawn(move || {
+                let _slot = slot;
+                serve(stream, &dir, &token, &logger, &hub)
+            });
+        }
+    });
+    Ok(())
+}
+
+fn serve(mut stream: TcpStream, dir: &Path, token: &str, logger: &DaemonLogger, hub: &EventHub) {
+    let (status, body) = match read_request(&mut stream) {
+        Ok(req) => {
+            let events = req.method == "GET" && req.route().trim_end_matches('/') == "/api/events";
+            let allowed = authorized(&req, token)
+                || (events
+                    && req
+                        .query("access_token")
+                        .is_some_and(|t| token_matches(&t, token)));
+            if events && allowed {
+                logger.info("HTTP GET /api/events -> 200 (stream)");
+                let _ = stream_events(&mut stream, hub);
+                return;
+            }
+            let (status, body) = if allowed {
+                route(&req, dir)
+            } else {
+                (401, json!({ "error": "missing or invalid bearer token" }))
+            };
+            logger.info(&format!(
+                "HTTP {} {} -> {}",
+                req.method,
+                req.route(),
+                status
+            ));
+            (status, body)
+        }
+        Err(e) => (400, json!({ "error": e.to_string() })),
+    };
+    let _ = write_response(&mut stream, status, &body);
+}
+
+fn authorized(req: &Request, token: &str) -> bool {
+    req.header("authorization")
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .is_some_and(|given| token_matches(given, token))
+}
+
+/// Compare tokens without short-circuiting on the first mismatched byte.
+pub(super) fn token_matches(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Serve `GET /api/events` until the client goes away.
+fn stream_events(stream: &mut TcpStream, hub: &EventHub) -> std::io::Result<()> {
+    let events = hub.subscribe();
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n"
+    )?;
+    stream.flush()?;
+    loop {
+        match events.recv_timeout(KEEPALIVE_INTERVAL) {
+            Ok(event) => {
+                let data = serde_json::to_string(&event).unwrap_or_default();
+                write!(stream, "event: {}\ndata: {}\n\n", event.name(), data)?;
+            }
+            Err(RecvTimeoutError::Timeout) => write!(stream, ": keep-alive\n\n")?,
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+        stream.flush()?;
+    }
+}
+
+fn route(req: &Request, dir: &Path) -> (u16, Value) {
+    let segments: Vec<&str> = req
+        .route()
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    match (req.method.as_str(), segments.as_slice()) {
+        ("GET", ["api", "status"]) => from_ipc(ipc::handle_status(dir)),
+        ("GET", ["api", "tasks"]) => list_tasks(
+            dir,
+            req.query("status").as_deref(),
+            req.query("tag").as_deref(),
+        ),
+        ("GET", ["api", "tasks", "ready"]) => ready_tasks(dir),
+        ("GET", ["api", "tasks", id]) => get_task(dir, id),
+        ("POST", ["api", "tasks"]) => add_task(dir, &req.body),
+        ("POST", ["api", "tasks", id, "claim"]) => {
+            let actor = serde_json::from_slice::<Value>(&req.body)
+                .ok()
+                .and_then(|b| b.get("actor").and_then(Value::as_str).map(String::from));
+            run_command(dir, id, |dir| {
+                crate::commands::claim::claim(dir, id, actor.as_deref())
+            })
+        }
+        ("POST", ["api", "tasks", id, "done"]) => run_command(dir, id, |dir| {
+            crate::commands::done::run(dir, id, false, false, false, false, false)
+        }),
+        ("POST", ["api", "inbox"]) => submit_to_inbox(dir, &req.body),
+        (_, ["api", ..]) => (
+            405,
+            json!({ "error": format!("{} {} is not supported", req.method, req.route()) }),
+        ),
+        _ => (404, json!({ "error": "not found" })),
+    }
+}
+
+fn from_ipc(resp: ipc::IpcResponse) -> (u16, Value) {
+    if resp.ok {
+        (200, resp.data.unwrap_or(Value::Null))
+    } else {
+        (500, json!({ "error": resp.error }))
+    }
+}
+
+fn load(dir: &Path) -> std::result::Result<workgraph::graph::WorkGraph, (u16, Value)> {
+    load_graph(graph_path(dir)).map_err(|e| (500, json!({ "error": format!("{:#}", e) })))
+}
+
+fn task_json(task: &Task) -> Value {
+    serde_json::to_value(task).unwrap_or(Value::Null)
+}
+
+fn list_tasks(dir: &Path, status: Option<&str>, tag: Option<&str>) -> (u16, Value) {
+    let status = match status.map(|s| serde_json::from_value::<Status>(json!(s))) {
+        Some(Ok(s)) => Some(s),
+        Some(Err(_)) => return (400, json!({ "error": "unknown status" })),
+        None => None,
+    };
+    let graph = match load(dir) {
+        Ok(g) => g,
+        Err(e) => return e,
+    };
+    let tasks: Vec<Value> = graph
+        .tasks()
+        .filter(|t| status.is_none_or(|s| t.status == s))
+        .filter(|t| tag.is_none_or(|tag| t.tags.iter().any(|x| x == tag)))
+        .map(task_json)
+        .collect();
+    (200, json!({ "tasks": tasks }))
+}
+
+fn ready_tasks(dir: &Path) -> (u16, Value) {
+    let graph = match load(dir) {
+        Ok(g) => g,
+        Err(e) => return e,
+    };
+    let cycle_analysis = graph.compute_cycle_analysis();
+    let tasks: Vec<Value> = ready_tasks_with_peers_cycle_aware(&graph, dir, &cycle_analysis)
+        .into_iter()
+        .map(task_json)
+        .collect();
+    (200, json!({ "tasks": tasks }))
+}
+
+fn get_task(dir: &Path, id: &str) -> (u16, Value) {
+    let graph = match load(dir) {
+        Ok(g) => g,
+        Err(e) => return e,
+    };
+    match graph.get_task(id) {
+        Some(task) => (200, task_json(task)),
+        None => (404, json!({ "error": format!("Task '{}' not found", id) })),
+    }
+}
+
+/// Create a task through the same path as the `add_task` IPC request.
+fn add_task(dir: &Path, body: &[u8]) -> (u16, Value) {
+    let mut request: Value = match serde_json::from_slice(body) {
+        Ok(Value::Object(map)) => Value::Object(map),
+        Ok(_) => return (400, json!({ "error": "body must be a JSON object" })),
+        Err(e) => return (400, json!({ "error": e.to_string() })),
+    };
+    request["cmd"] = json!("add_task");
+    let Ok(IpcRequest::AddTask {
+        title,
+        id,
+        description,
+        after,
+        tags,
+        skills,
+        deliverables,
+        model,
+        verify,
+        verify_timeout,
+        origin,
+        cron,
+    }) = serde_json::from_value::<IpcRequest>(request)
+    else {
+        return (400, json!({ "error": "invalid task: 'title' is required" }));
+    };
+    let resp = ipc::handle_add_task(
+        dir,
+        &title,
+        id.as_deref(),
+        description.as_deref(),
+        &after,
+        &tags,
+        &skills,
+        &deliverables,
+        model.as_deref(),
+        verify.as_deref(),
+        verify_timeout.as_deref(),
+        cron.as_deref(),
+        Some(origin.as_deref().unwrap_or("http")),
+    );
+    if !resp.ok {
+        return (400, json!({ "error": resp.error }));
+    }
+    crate::commands::notify_graph_changed(dir);
+    let task_id = resp
+        .data
+        .as_ref()
+        .and_then(|d| d.get("task_id"))
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    match get_task(dir, &task_id) {
+        (200, task) => (201, task),
+        other => other,
+    }
+}
+
+/// File a request in the triage inbox (see [`workgraph::inbox`]).
+fn submit_to_inbox(dir: &Path, body: &[u8]) -> (u16, Value) {
+    let request: Value = match serde_json::from_slice(body) {
+        Ok(v @ Value::Object(_)) => v,
+        Ok(_) => return (400, json!({ "error": "body must be a JSON object" })),
+        Err(e) => return (400, json!({ "error": e.to_string() })),
+    };
+    let field = |name: &str| request.get(name).and_then(Value::as_str).map(String::from);
+    let submission = workgraph::inbox::Submission {
+        source: "http".to_string(),
+        submitter: field("submitter"),
+        title: field("title").unwrap_or_default(),
+        body: field("body"),
+    };
+    match workgraph::inbox::submit(dir, &submission) {
+        Ok(id) => {
+            crate::commands::notify_graph_changed(dir);
+            (201, json!({ "task_id": id }))
+        }
+        Err(e) => (400, json!({ "error": e.to_string() })),
+    }
+}
+
+/// Run a task command and answer with the updated task. A command that
+/// refuses (already claimed, unmet dependencies, ...) is a 409.
+fn run_command(dir: &Path, id: &str, command: impl FnOnce(&Path) -> Result<()>) -> (u16, Value) {
+    match load(dir) {
+        Ok(graph) if graph.get_task(id).is_none() => {
+            return (404, json!({ "error": format!("Task '{}' not found", id) }));
+        }
+        Ok(_) => {}
+        Err(e) => return e,
+    }
+    if let Err(e) = command(dir) {
+        return (409, json!({ "error": format!("{:#}", e) }));
+    }
+    get_task(dir, id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use workgraph::test_helpers::{make_task_with_status, setup_workgraph};
+
+    fn request(method: &str, path: &str, token: Option<&str>, body: &str) -> Request {
+        let mut headers = Vec::new();
+        if let Some(token) = token {
+            headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+        }
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers,
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_token_is_created_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let token = load_or_create_token(dir.path()).unwrap();
+        assert_eq!(token.len(), 64);
+        assert_eq!(load_or_create_token(dir.path()).unwrap(), token);
+        assert!(authorized(&request("GET", "/", Some(&token), ""), &token));
+        assert!(!authorized(&request("GET", "/", Some("wrong"), ""), &token));
+        assert!(!authorized(&request("GET", "/", None, ""), &token));
+    }
+
+    #[test]
+    fn test_routes_list_add_and_claim() {
+        let dir = tempfile::tempdir().unwrap();
+        setup_workgraph(
+            dir.path(),
+            vec![
+                make_task_with_status("a", "Task A", Status::Open),
+                make_task_with_status("b", "Task B", Status::Done),
+            ],
+        );
+
+        let (status, body) = route(
+            &request("GET", "/api/tasks?status=open", None, ""),
+            dir.path(),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body["tasks"].as_array().unwrap().len(), 1);
+
+        let (status, body) = route(
+            &request(
+                "POST",
+                "/api/tasks",
+                None,
+                r#"{"title": "New work", "id": "c"}"#,
+            ),
+            dir.path(),
+        );
+        assert_eq!(status, 201);
+        assert_eq!(body["id"], "c");
+
+        let (status, _) = route(&request("GET", "/api/tasks/missing", None, ""), dir.path());
+        assert_eq!(status, 404);
+
+        let claim = request("POST", "/api/tasks/a/claim", None, r#"{"actor": "web"}"#);
+        let (status, body) = route(&claim, dir.path());
+        assert_eq!(status, 200);
+        assert_eq!(body["status"], "in-progress");
+        // A second claim conflicts
+        assert_eq!(route(&claim, dir.path()).0, 409);
+    }
+
+    #[test]
+    fn test_inbox_route_files_a_draft() {
+        let dir = tempfile::tempdir().unwrap();
+        setup_workgraph(dir.path(), vec![]);
+
+        let (status, body) = route(
+            &request(
+                "POST",
+                "/api/inbox",
+                None,
+                r#"{"title": "Rate limit the API", "submitter": "dana"}"#,
+            ),
+            dir.path(),
+        );
+        assert_eq!(status, 201, "{}", body);
+        let id = body["task_id"].as_str().unwrap();
+        let graph = load_graph(graph_path(dir.path())).unwrap();
+        let draft = graph.get_task(id).unwrap();
+        assert!(draft.paused);
+        assert!(draft.tags.contains(&"source:http".to_string()));
+
+        let (status, _) = route(
+            &request("POST", "/api/inbox", None, r#"{"body": "no title"}"#),
+            dir.path(),
+        );
+        assert_eq!(status, 400);
+    }
+
+    #[test]
+    fn test_unauthorized_request_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        setup_workgraph(dir.path(), vec![]);
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        let logger = DaemonLogger::open(dir.path()).unwrap();
+        spawn(dir.path().to_path_buf(), &addr, "s3cret".into(), logger).unwrap();
+
+        let mut stream = TcpStream::connect(&addr).unwrap();
+        write!(stream, "GET /api/tasks HTTP/1.1\r\nHost: x\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 401"), "{}", response);
+    }
+
+    #[test]
+    fn test_event_stream_sends_published_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hub = Arc::new(EventHub::default());
+        let server_hub = hub.clone();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream_events(&mut stream, &server_hub);
+        });
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        while !hub.has_subscribers() {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        hub.publish(&graph_events::GraphEvent::AgentSpawned {
+            agent_id: "agent-1".to_string(),
+            task_id: "a".to_string(),
+            executor: "shell".to_string(),
+        });
+
+        let mut received = String::new();
+        let mut buf = [0u8; 512];
+        while !received.ends_with("\n\n") || !received.contains("data:") {
+            let n = stream.read(&mut buf).unwrap();
+            assert!(n > 0, "stream closed early: {}", received);
+            received.push_str(&String::from_utf8_lossy(&buf[..n]));
+        }
+        assert!(received.starts_with("HTTP/1.1 200 OK\r\nContent-Type: text/event-stream"));
+        assert!(received.contains(
+            "event: agent_spawned\ndata: {\"type\":\"agent_spawned\",\"agent_id\":\"agent-1\""
+        ));
+    }
+}
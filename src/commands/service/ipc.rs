@@ -13,6 +13,8 @@ use workgraph::config::Config;
 use workgraph::cron::{calculate_next_fire, parse_cron_expression};
 use workgraph::graph::{Node, PRIORITY_DEFAULT, PRIORITY_HIGH, Status, Task};
 use workgraph::parser::{load_graph, modify_graph};
+#[cfg(unix)]
+use workgraph::service::chaos::Chaos;
 use workgraph::service::registry::AgentRegistry;
 
 use super::{CoordinatorState, DaemonConfig, DaemonLogger, ServiceState};
@@ -64,14 +66,30 @@ pub enum IpcRequest {
     /// `KickDispatcher` ticks on the next loop iteration. Safe to send to an
     /// offline daemon: `notify_kick` silently ignores socket errors.
     KickDispatcher,
-    /// Pause the coordinator (no new agent spawns, running agents unaffected)
-    Pause,
-    /// Resume the coordinator (triggers immediate tick)
-    Resume,
+    /// Pause the coordinator (no new agent spawns, running agents unaffected).
+    /// With `tags` or `queues`, only tasks carrying one of those tags or
+    /// requiring one of those resource pools are held.
+    Pause {
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        queues: Vec<String>,
+    },
+    /// Resume the coordinator (triggers immediate tick). With `tags` or
+    /// `queues`, lifts only those scoped pauses.
+    Resume {
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        queues: Vec<String>,
+    },
     /// Freeze all running agents (SIGSTOP) and pause the coordinator
     Freeze,
     /// Thaw all frozen agents (SIGCONT) and resume the coordinator
     Thaw,
+    /// Dispatch an urgent task now, freezing the lowest-priority running
+    /// agent if every slot is busy (`wg claim --preempt`)
+    Preempt { task_id: String },
     /// Reconfigure the coordinator at runtime.
     /// If all fields are None, re-read config.toml from disk.
     Reconfigure {
@@ -116,8 +134,23 @@ pub enum IpcRequest {
         #[serde(default)]
         cron: Option<String>,
     },
+    /// File an external request as a draft in the triage inbox
+    SubmitRequest {
+        title: String,
+        #[serde(default)]
+        body: Option<String>,
+        /// Channel the request arrived on (default: "ipc")
+        #[serde(default)]
+        source: Option<String>,
+        #[serde(default)]
+        submitter: Option<String>,
+    },
     /// Query a task's status (cross-repo query)
     QueryTask { task_id: String },
+    /// Stream the output of the agent running a task (`wg log --follow`).
+    /// The daemon answers on the same connection with one `{"line": ...}`
+    /// message per output line, then `{"done": true}` when the agent exits.
+    FollowOutput { task_id: String },
     /// Send a message to a task's message queue
     SendMessage {
         task_id: String,
@@ -278,6 +311,7 @@ pub(crate) fn handle_connection(
     delete_coordinator_ids: &mut Vec<u32>,
     interrupt_coordinator_ids: &mut Vec<u32>,
     daemon_cfg: &mut DaemonConfig,
+    mut chaos: Option<&mut Chaos>,
     logger: &DaemonLogger,
 ) -> Result<()> {
     stream.set_read_timeout(Some(Duration::from_secs(5)))?;
@@ -315,6 +349,13 @@ pub(crate) fn handle_connection(
             }
         };
 
+        // Streaming requests keep the connection: hand it to a thread so
+        // the daemon loop is not held up for the agent's lifetime.
+        if let IpcRequest::FollowOutput { task_id } = request {
+            logger.info(&format!("IPC FollowOutput: task_id={}", task_id));
+            return start_follow_output(dir, &task_id, write_stream);
+        }
+
         let response = handle_request(
             dir,
             request,
@@ -328,6 +369,10 @@ pub(crate) fn handle_connection(
             daemon_cfg,
             logger,
         );
+        if let Some(delay) = chaos.as_deref_mut().and_then(Chaos::ipc_delay) {
+            logger.info(&format!("Chaos: delaying IPC response by {:?}", delay));
+            std::thread::sleep(delay);
+        }
         write_response(&mut write_stream, &response)?;
 
         // Check if we should stop
@@ -339,6 +384,48 @@ pub(crate) fn handle_connection(
     Ok(())
 }
 
+/// Answer a `FollowOutput` request: stream the running agent's output on a
+/// background thread until the agent exits or the client disconnects.
+#[cfg(unix)]
+fn start_follow_output(dir: &Path, task_id: &str, mut stream: UnixStream) -> Result<()> {
+    use workgraph::service::output_stream;
+
+    let agent = match output_stream::running_agent(dir, task_id) {
+        Ok(Some(agent)) => agent,
+        Ok(None) => {
+            let msg = format!("No running agent for task '{}'", task_id);
+            return write_response(&mut stream, &IpcResponse::error(&msg));
+        }
+        Err(e) => {
+            return write_response(&mut stream, &IpcResponse::error(&e.to_string()));
+        }
+    };
+
+    let dir = dir.to_path_buf();
+    std::thread::spawn(move || {
+        let started = IpcResponse::success(serde_json::json!({
+            "agent_id": agent.id,
+            "output_file": agent.output_file,
+        }));
+        let result = write_response(&mut stream, &started).and_then(|()| {
+            output_stream::follow(&dir, &agent, |line| {
+                write_response(
+                    &mut stream,
+                    &IpcResponse::success(serde_json::json!({ "line": line })),
+                )
+            })
+        });
+        // A client that went away is the normal end of most follows.
+        if result.is_ok() {
+            let _ = write_response(
+                &mut stream,
+                &IpcResponse::success(serde_json::json!({ "done": true })),
+            );
+        }
+    });
+    Ok(())
+}
+
 #[cfg(unix)]
 fn write_response(stream: &mut UnixStream, response: &IpcResponse) -> Result<()> {
     let json = serde_json::to_string(response)?;
@@ -427,7 +514,30 @@ fn handle_request(
                 "action": "dispatcher_kicked",
             }))
         }
-        IpcRequest::Pause => {
+        IpcRequest::Pause { tags, queues } if !tags.is_empty() || !queues.is_empty() => {
+            logger.info(&format!(
+                "IPC Pause: holding tags={:?}, queues={:?}",
+                tags, queues
+            ));
+            let mut coord_state = CoordinatorState::load_or_default(dir);
+            for tag in tags {
+                if !coord_state.paused_tags.contains(&tag) {
+                    coord_state.paused_tags.push(tag);
+                }
+            }
+            for queue in queues {
+                if !coord_state.paused_queues.contains(&queue) {
+                    coord_state.paused_queues.push(queue);
+                }
+            }
+            coord_state.save(dir);
+            IpcResponse::success(serde_json::json!({
+                "status": "paused",
+                "paused_tags": coord_state.paused_tags,
+                "paused_queues": coord_state.paused_queues,
+            }))
+        }
+        IpcRequest::Pause { .. } => {
             logger.info("IPC Pause: pausing coordinator");
             daemon_cfg.paused = true;
             let mut coord_state = CoordinatorState::load_or_default(dir);
@@ -437,7 +547,23 @@ fn handle_request(
                 "status": "paused",
             }))
         }
-        IpcRequest::Resume => {
+        IpcRequest::Resume { tags, queues } if !tags.is_empty() || !queues.is_empty() => {
+            logger.info(&format!(
+                "IPC Resume: releasing tags={:?}, queues={:?}",
+                tags, queues
+            ));
+            let mut coord_state = CoordinatorState::load_or_default(dir);
+            coord_state.paused_tags.retain(|t| !tags.contains(t));
+            coord_state.paused_queues.retain(|q| !queues.contains(q));
+            coord_state.save(dir);
+            *kick_dispatcher = true;
+            IpcResponse::success(serde_json::json!({
+                "status": "resumed",
+                "paused_tags": coord_state.paused_tags,
+                "paused_queues": coord_state.paused_queues,
+            }))
+        }
+        IpcRequest::Resume { .. } => {
             logger.info("IPC Resume: resuming coordinator");
             daemon_cfg.paused = false;
             let mut coord_state = CoordinatorState::load_or_default(dir);
@@ -462,6 +588,14 @@ fn handle_request(
             }
             resp
         }
+        IpcRequest::Preempt { task_id } => {
+            logger.info(&format!("IPC Preempt: task_id={}", task_id));
+            let resp = handle_preempt(dir, &task_id, daemon_cfg, logger);
+            if resp.ok {
+                *kick_dispatcher = true;
+            }
+            resp
+        }
         IpcRequest::Reconfigure {
             max_agents,
             executor,
@@ -521,10 +655,35 @@ fn handle_request(
             }
             resp
         }
+        IpcRequest::SubmitRequest {
+            title,
+            body,
+            source,
+            submitter,
+        } => {
+            logger.info(&format!(
+                "IPC SubmitRequest: title='{}', submitter={:?}",
+                title, submitter
+            ));
+            let submission = workgraph::inbox::Submission {
+                source: source.unwrap_or_else(|| "ipc".to_string()),
+                submitter,
+                title,
+                body,
+            };
+            match workgraph::inbox::submit(dir, &submission) {
+                Ok(id) => IpcResponse::success(serde_json::json!({ "task_id": id })),
+                Err(e) => IpcResponse::error(&e.to_string()),
+            }
+        }
         IpcRequest::QueryTask { task_id } => {
             logger.info(&format!("IPC QueryTask: task_id={}", task_id));
             handle_query_task(dir, &task_id)
         }
+        // Handled in handle_connection, which owns the stream.
+        IpcRequest::FollowOutput { .. } => {
+            IpcResponse::error("follow_output is only served on a socket connection")
+        }
         IpcRequest::SendMessage {
             task_id,
             body,
@@ -826,7 +985,7 @@ fn handle_heartbeat(dir: &Path, agent_id: &str) -> IpcResponse {
 }
 
 /// Handle status request
-fn handle_status(dir: &Path) -> IpcResponse {
+pub(super) fn handle_status(dir: &Path) -> IpcResponse {
     let state = match ServiceState::load(dir) {
         Ok(Some(s)) => s,
         Ok(None) => return IpcResponse::error("No service state found"),
@@ -960,6 +1119,40 @@ fn handle_freeze(
     IpcResponse::error("Freeze is only supported on Unix systems")
 }
 
+/// Handle preempt: make room for an urgent task (see [`super::preempt`]).
+fn handle_preempt(
+    dir: &Path,
+    task_id: &str,
+    daemon_cfg: &DaemonConfig,
+    logger: &DaemonLogger,
+) -> IpcResponse {
+    if daemon_cfg.paused {
+        return IpcResponse::error("Coordinator is paused; run 'wg service resume' first");
+    }
+    match super::preempt::preempt_for(dir, task_id, daemon_cfg.max_agents) {
+        Ok(super::preempt::Preemption::SlotFree) => IpcResponse::success(serde_json::json!({
+            "status": "dispatching",
+            "task_id": task_id,
+        })),
+        Ok(super::preempt::Preemption::Paused {
+            agent_id,
+            task_id: paused_task,
+        }) => {
+            logger.info(&format!(
+                "Preempted agent {} ({}) for urgent task {}",
+                agent_id, paused_task, task_id
+            ));
+            IpcResponse::success(serde_json::json!({
+                "status": "preempted",
+                "task_id": task_id,
+                "agent_id": agent_id,
+                "paused_task": paused_task,
+            }))
+        }
+        Err(e) => IpcResponse::error(&format!("{:#}", e)),
+    }
+}
+
 /// Handle thaw: send SIGCONT to all frozen agent processes, resume coordinator,
 /// and update registry + coordinator state.
 #[cfg(unix)]
@@ -984,7 +1177,8 @@ fn handle_thaw(dir: &Path, daemon_cfg: &mut DaemonConfig, logger: &DaemonLogger)
     let mut failed_pids = Vec::new();
 
     for agent in locked_registry.registry.agents.values_mut() {
-        if agent.status != AgentStatus::Frozen {
+        // Preempted agents stay frozen until their urgent task finishes.
+        if agent.status != AgentStatus::Frozen || agent.preempted_by.is_some() {
             continue;
         }
 
@@ -1081,6 +1275,7 @@ fn handle_reconfigure(
         // No flags: re-read config.toml from disk
         match Config::load_merged(dir) {
             Ok(config) => {
+                super::warn_config_drift(dir, &config, logger);
                 daemon_cfg.max_agents = config.coordinator.max_agents;
                 daemon_cfg.executor = config.coordinator.effective_executor();
                 daemon_cfg.poll_interval = Duration::from_secs(config.coordinator.poll_interval);
@@ -1132,7 +1327,7 @@ fn handle_reconfigure(
 
 /// Handle AddTask IPC request — create a task in this WG project from a remote peer.
 #[allow(clippy::too_many_arguments)]
-fn handle_add_task(
+pub(super) fn handle_add_task(
     dir: &Path,
     title: &str,
     id: Option<&str>,
@@ -1233,19 +1428,25 @@ fn handle_add_task(
         estimate: None,
         before: vec![],
         after: after.to_vec(),
+        edges: Default::default(),
         requires: vec![],
         tags: tags.to_vec(),
         skills: skills.to_vec(),
         inputs: vec![],
         deliverables: deliverables.to_vec(),
+        expects: vec![],
+        gate: None,
         artifacts: vec![],
+        self_report: None,
         exec: None,
         timeout: None,
         not_before: None,
+        due: None,
         created_at: Some(chrono::Utc::now().to_rfc3339()),
         started_at: None,
         completed_at: None,
         last_interaction_at: None,
+        revision: 0,
         log: vec![],
         retry_count: 0,
         max_retries: None,
@@ -1269,10 +1470,20 @@ fn handle_add_task(
         context_scope: None,
         cycle_config: None,
         token_usage: None,
+        max_cost: None,
+        min_context: None,
+        claim_sha: None,
+        changes: None,
         session_id: None,
         wait_condition: None,
         checkpoint: None,
         triage_count: 0,
+        escalation_level: 0,
+        blocked_at: None,
+        deadline_alert: None,
+        external_blocker: None,
+        waiting_on: vec![],
+        links: vec![],
         resurrection_count: 0,
         last_resurrected_at: None,
         validation: None,
@@ -2968,7 +3179,10 @@ poll_interval = 120
 
         handle_request(
             dir,
-            IpcRequest::Resume,
+            IpcRequest::Resume {
+                tags: vec![],
+                queues: vec![],
+            },
             &mut running,
             &mut wake_coordinator,
             &mut kick_dispatcher,
@@ -2991,6 +3205,84 @@ poll_interval = 120
         );
     }
 
+    #[test]
+    fn test_scoped_pause_leaves_global_pause_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::create_dir_all(dir.join("service")).unwrap();
+
+        let mut running = true;
+        let mut wake_coordinator = false;
+        let mut kick_dispatcher = false;
+        let mut urgent_wake = false;
+        let mut pending_coordinator_ids = Vec::new();
+        let mut delete_coordinator_ids = Vec::new();
+        let mut interrupt_coordinator_ids = Vec::new();
+        let mut cfg = DaemonConfig {
+            max_agents: 4,
+            executor: "claude".to_string(),
+            poll_interval: Duration::from_secs(5),
+            model: None,
+            provider: None,
+            paused: false,
+            settling_delay: Duration::from_millis(2000),
+        };
+        let logger = DaemonLogger::open(dir).unwrap();
+
+        for request in [
+            IpcRequest::Pause {
+                tags: vec!["deploy".to_string()],
+                queues: vec!["gpu".to_string()],
+            },
+            IpcRequest::Pause {
+                tags: vec!["deploy".to_string()],
+                queues: vec![],
+            },
+        ] {
+            handle_request(
+                dir,
+                request,
+                &mut running,
+                &mut wake_coordinator,
+                &mut kick_dispatcher,
+                &mut urgent_wake,
+                &mut pending_coordinator_ids,
+                &mut delete_coordinator_ids,
+                &mut interrupt_coordinator_ids,
+                &mut cfg,
+                &logger,
+            );
+        }
+
+        assert!(!cfg.paused, "Scoped pause should not pause all dispatch");
+        let state = CoordinatorState::load_or_default(dir);
+        assert!(!state.paused);
+        assert_eq!(state.paused_tags, vec!["deploy"]);
+        assert_eq!(state.paused_queues, vec!["gpu"]);
+
+        handle_request(
+            dir,
+            IpcRequest::Resume {
+                tags: vec![],
+                queues: vec!["gpu".to_string()],
+            },
+            &mut running,
+            &mut wake_coordinator,
+            &mut kick_dispatcher,
+            &mut urgent_wake,
+            &mut pending_coordinator_ids,
+            &mut delete_coordinator_ids,
+            &mut interrupt_coordinator_ids,
+            &mut cfg,
+            &logger,
+        );
+
+        let state = CoordinatorState::load_or_default(dir);
+        assert_eq!(state.paused_tags, vec!["deploy"]);
+        assert!(state.paused_queues.is_empty());
+        assert!(kick_dispatcher, "Scoped resume should kick the dispatcher");
+    }
+
     #[test]
     fn test_handle_add_task_internal_no_focus_steal() {
         let temp_dir = TempDir::new().unwrap();
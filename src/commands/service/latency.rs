@@ -0,0 +1,72 @@
+//! `wg service latency` — how long ready tasks wait for an agent.
+
+use anyhow::Result;
+use chrono::Utc;
+use std::path::Path;
+
+use workgraph::dispatch::latency::{self, LatencyReport, LatencyState};
+
+const BAR_WIDTH: usize = 40;
+
+/// Print the ready-to-started histogram, percentiles, and the tasks
+/// currently waiting. `since` limits samples to a window (e.g. `24h`).
+pub fn run_latency(dir: &Path, since: Option<&str>, json: bool) -> Result<()> {
+    let now = Utc::now();
+    let since = since.map(|s| latency::parse_since(s, now)).transpose()?;
+    let state = LatencyState::load(dir)?;
+    let samples = state.samples_since(since);
+    let report = LatencyReport::from_samples(&samples);
+    let mut waiting: Vec<(&str, i64)> = state
+        .pending
+        .iter()
+        .filter_map(|(id, p)| {
+            let ready = p.ready_since.parse::<chrono::DateTime<Utc>>().ok()?;
+            Some((id.as_str(), (now - ready).num_seconds()))
+        })
+        .collect();
+    waiting.sort_by(|a, b| b.1.cmp(&a.1));
+
+    if json {
+        let out = serde_json::json!({
+            "since": since.map(|s| s.to_rfc3339()),
+            "report": report,
+            "waiting": waiting
+                .iter()
+                .map(|(id, secs)| serde_json::json!({ "task_id": id, "waited_secs": secs }))
+                .collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    let fmt = |secs: i64| workgraph::format_duration(secs, false);
+    if report.count == 0 {
+        println!("No dispatch latency samples recorded yet.");
+    } else {
+        println!(
+            "Dispatch latency (ready -> agent started), {} sample(s){}:",
+            report.count,
+            since.map_or(String::new(), |s| format!(" since {}", s.to_rfc3339()))
+        );
+        let widest = report.buckets.iter().map(|(_, c)| *c).max().unwrap_or(1);
+        for (label, count) in &report.buckets {
+            let bar = (count * BAR_WIDTH).div_ceil(widest.max(1));
+            println!("  {:>7} {:>5}  {}", label, count, "#".repeat(bar));
+        }
+        println!(
+            "  p50 {}  p90 {}  p99 {}  max {}",
+            fmt(report.p50_secs),
+            fmt(report.p90_secs),
+            fmt(report.p99_secs),
+            fmt(report.max_secs)
+        );
+    }
+    if !waiting.is_empty() {
+        println!();
+        println!("Waiting for an agent:");
+        for (id, secs) in &waiting {
+            println!("  {} ({})", id, fmt(*secs));
+        }
+    }
+    Ok(())
+}
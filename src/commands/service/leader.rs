@@ -0,0 +1,270 @@
+//! Leader election between service daemons sharing one `.wg/` directory.
+//!
+//! Every daemon takes an exclusive `flock` on `.wg/service/.leader.lock`
+//! before it binds the IPC socket, and holds it for its whole life. Only the
+//! holder dispatches. A second daemon started with `wg service start
+//! --standby` blocks on the lock instead; the kernel releases the lock when
+//! the leader dies (crash, OOM, SIGKILL), at which point the standby takes
+//! over: it rewrites `state.json` with its own PID, binds the socket, and
+//! adopts the agent registry under the registry lock. Long unattended runs
+//! then survive the loss of the leader process.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use workgraph::service::registry::AgentRegistry;
+
+use super::{DaemonLogger, ServiceState};
+use crate::commands::{is_process_alive, kill_process_force, kill_process_graceful};
+
+/// How often a standby retries the leader lock.
+const STANDBY_POLL: Duration = Duration::from_secs(2);
+
+fn lock_path(dir: &Path) -> PathBuf {
+    dir.join("service").join(".leader.lock")
+}
+
+fn standby_path(dir: &Path) -> PathBuf {
+    dir.join("service").join("standby.json")
+}
+
+/// The leader lock. Leadership lasts until this is dropped or the process
+/// exits.
+pub struct LeaderLock {
+    _file: File,
+}
+
+/// Take the leader lock if no other daemon holds it.
+#[cfg(unix)]
+pub fn try_acquire(dir: &Path) -> Result<Option<LeaderLock>> {
+    use std::os::unix::io::AsRawFd;
+
+    let path = lock_path(dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open leader lock at {:?}", path))?;
+    let result = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) };
+    if result == 0 {
+        return Ok(Some(LeaderLock { _file: file }));
+    }
+    let err = std::io::Error::last_os_error();
+    if err.kind() == std::io::ErrorKind::WouldBlock {
+        Ok(None)
+    } else {
+        Err(err).context("Failed to acquire leader lock")
+    }
+}
+
+/// Take the leader lock (non-Unix fallback: no actual locking).
+#[cfg(not(unix))]
+pub fn try_acquire(dir: &Path) -> Result<Option<LeaderLock>> {
+    let path = lock_path(dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)?;
+    Ok(Some(LeaderLock { _file: file }))
+}
+
+/// A daemon waiting for the leader lock, recorded in
+/// `.wg/service/standby.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandbyState {
+    pub pid: u32,
+    pub since: String,
+}
+
+impl StandbyState {
+    pub fn load(dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(standby_path(dir)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// The recorded standby, if its process is still alive.
+    pub fn load_alive(dir: &Path) -> Option<Self> {
+        Self::load(dir).filter(|s| is_process_alive(s.pid))
+    }
+
+    fn save(&self, dir: &Path) -> Result<()> {
+        let path = standby_path(dir);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {:?}", path))
+    }
+
+    fn remove(dir: &Path) {
+        let _ = fs::remove_file(standby_path(dir));
+    }
+}
+
+/// Block as a standby until the leader lock is free, then take over:
+/// record this daemon in `state.json` and adopt the agent registry.
+pub fn wait_for_leadership(
+    dir: &Path,
+    socket_path: &str,
+    http: Option<&str>,
+    grpc: Option<&str>,
+    logger: &DaemonLogger,
+) -> Result<LeaderLock> {
+    let pid = std::process::id();
+    StandbyState {
+        pid,
+        since: chrono::Utc::now().to_rfc3339(),
+    }
+    .save(dir)?;
+    let leader_pid = ServiceState::load(dir).ok().flatten().map(|s| s.pid);
+    logger.info(&format!(
+        "Standby (PID {}): leader {} holds the lock; waiting to take over",
+        pid,
+        leader_pid.map_or_else(|| "(unknown)".to_string(), |p| format!("PID {}", p)),
+    ));
+
+    let started = Instant::now();
+    let lock = loop {
+        if let Some(lock) = try_acquire(dir)? {
+            break lock;
+        }
+        std::thread::sleep(STANDBY_POLL);
+    };
+
+    StandbyState::remove(dir);
+    ServiceState {
+        pid,
+        socket_path: socket_path.to_string(),
+        started_at: chrono::Utc::now().to_rfc3339(),
+        http: http.map(String::from),
+        grpc: grpc.map(String::from),
+    }
+    .save(dir)?;
+
+    // Agents spawned by the old leader keep running detached. Take the
+    // registry lock so no stale writer from the old leader interleaves with
+    // the adoption; the normal tick reaps the ones that have since died.
+    let registry = AgentRegistry::load_locked(dir)?;
+    let inherited = registry.list_alive_agents().len();
+    drop(registry);
+    logger.warn(&format!(
+        "Took over leadership after {} in standby (previous leader {}); adopted {} live agent(s)",
+        workgraph::format_duration(started.elapsed().as_secs() as i64, true),
+        leader_pid.map_or_else(|| "(unknown)".to_string(), |p| format!("PID {}", p)),
+        inherited,
+    ));
+    Ok(lock)
+}
+
+/// Stop the standby daemon, if one is waiting, so it does not take over
+/// from a leader that is being stopped on purpose. Returns its PID.
+pub fn stop_standby(dir: &Path, force: bool) -> Result<Option<u32>> {
+    let Some(standby) = StandbyState::load_alive(dir) else {
+        StandbyState::remove(dir);
+        return Ok(None);
+    };
+    if force {
+        kill_process_force(standby.pid)?;
+    } else {
+        kill_process_graceful(standby.pid, 5)?;
+    }
+    StandbyState::remove(dir);
+    Ok(Some(standby.pid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn second_lock_is_refused_until_first_is_dropped() {
+        let tmp = TempDir::new().unwrap();
+        let first = try_acquire(tmp.path()).unwrap();
+        assert!(first.is_some());
+        assert!(try_acquire(tmp.path()).unwrap().is_none());
+        drop(first);
+        assert!(try_acquire(tmp.path()).unwrap().is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn standby_takes_over_when_leader_lock_is_released() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().to_path_buf();
+        let leader = try_acquire(&dir).unwrap().unwrap();
+        ServiceState {
+            pid: 999_999,
+            socket_path: "/tmp/old-leader.sock".to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            http: None,
+            grpc: None,
+        }
+        .save(&dir)
+        .unwrap();
+
+        let standby_dir = dir.clone();
+        let standby = std::thread::spawn(move || {
+            let logger = DaemonLogger::open(&standby_dir).unwrap();
+            wait_for_leadership(
+                &standby_dir,
+                "/tmp/new-leader.sock",
+                Some("127.0.0.1:7700"),
+                None,
+                &logger,
+            )
+        });
+
+        // The standby records itself while the leader holds the lock
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while StandbyState::load(&dir).is_none() {
+            assert!(Instant::now() < deadline, "standby never recorded itself");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(StandbyState::load(&dir).unwrap().pid, std::process::id());
+        assert_eq!(ServiceState::load(&dir).unwrap().unwrap().pid, 999_999);
+
+        // The leader dies: the standby takes the lock and the service state
+        drop(leader);
+        let lock = standby.join().unwrap().unwrap();
+        assert!(StandbyState::load(&dir).is_none());
+        let state = ServiceState::load(&dir).unwrap().unwrap();
+        assert_eq!(state.pid, std::process::id());
+        assert_eq!(state.socket_path, "/tmp/new-leader.sock");
+        assert_eq!(state.http.as_deref(), Some("127.0.0.1:7700"));
+        assert!(try_acquire(&dir).unwrap().is_none());
+        drop(lock);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stop_standby_kills_a_waiting_daemon() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("service")).unwrap();
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        StandbyState {
+            pid: child.id(),
+            since: chrono::Utc::now().to_rfc3339(),
+        }
+        .save(tmp.path())
+        .unwrap();
+
+        assert_eq!(stop_standby(tmp.path(), true).unwrap(), Some(child.id()));
+        assert!(!child.wait().unwrap().success());
+        assert!(StandbyState::load(tmp.path()).is_none());
+
+        // Nothing waiting: nothing to stop
+        assert_eq!(stop_standby(tmp.path(), true).unwrap(), None);
+    }
+}
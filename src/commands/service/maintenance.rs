@@ -0,0 +1,134 @@
+//! `wg service maintenance` — pause dispatch for planned work.
+//!
+//! Usage:
+//!   wg service maintenance on --reason "db migration" [--for 2h]
+//!   wg service maintenance off
+//!   wg service maintenance status
+//!
+//! Maintenance is a file (`.wg/service/maintenance.json`), not daemon
+//! memory, so it can be entered before the service starts and survives
+//! restarts. The dispatcher checks it every tick; see
+//! [`workgraph::maintenance`].
+
+use anyhow::Result;
+use chrono::Utc;
+use std::path::Path;
+
+use workgraph::config::Config;
+use workgraph::maintenance::{self, MaintenanceState};
+
+use super::{IpcRequest, ServiceState, send_request};
+
+/// Nudge a running daemon so the change takes effect (and is announced)
+/// without waiting for the next poll.
+fn kick_daemon(dir: &Path) {
+    if ServiceState::load(dir).ok().flatten().is_some() {
+        let _ = send_request(dir, &IpcRequest::KickDispatcher);
+    }
+}
+
+pub fn run_maintenance_on(
+    dir: &Path,
+    reason: &str,
+    duration: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let manual = maintenance::enter(dir, reason, duration)?;
+    kick_daemon(dir);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&manual)?);
+        return Ok(());
+    }
+    println!("Maintenance on: {}", manual.reason);
+    match manual.until {
+        Some(ref until) => println!("Dispatch paused until {} (running agents continue)", until),
+        None => {
+            println!("Dispatch paused until 'wg service maintenance off' (running agents continue)")
+        }
+    }
+    Ok(())
+}
+
+pub fn run_maintenance_off(dir: &Path, json: bool) -> Result<()> {
+    let cleared = maintenance::leave(dir)?;
+    kick_daemon(dir);
+    let still = maintenance::current(dir);
+    if json {
+        let out = serde_json::json!({
+            "cleared": cleared,
+            "active": still,
+        });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+    match cleared {
+        Some(m) => println!("Maintenance off (was: {})", m.reason),
+        None => println!("No manual maintenance was on"),
+    }
+    if let Some(m) = still {
+        println!("Still paused by a scheduled window: {}", m);
+    }
+    Ok(())
+}
+
+pub fn run_maintenance_status(dir: &Path, json: bool) -> Result<()> {
+    let state = MaintenanceState::load(dir)?;
+    let windows = Config::load_or_default(dir).coordinator.pause_windows;
+    let now = Utc::now();
+    let active = maintenance::active_at(&state, &windows, now);
+    let upcoming: Vec<_> = windows
+        .iter()
+        .map(|w| {
+            let next = maintenance::validate_window(w).and_then(|_| {
+                let schedule = workgraph::cron::parse_cron_expression(&w.cron)?;
+                Ok(workgraph::cron::calculate_next_fire(&schedule, now))
+            });
+            (w, next)
+        })
+        .collect();
+
+    if json {
+        let out = serde_json::json!({
+            "active": active,
+            "manual": state.manual,
+            "windows": upcoming
+                .iter()
+                .map(|(w, next)| serde_json::json!({
+                    "cron": w.cron,
+                    "duration": w.duration,
+                    "reason": w.reason,
+                    "next_start": next.as_ref().ok().and_then(|t| t.as_ref()).map(|t| t.to_rfc3339()),
+                    "error": next.as_ref().err().map(|e| format!("{:#}", e)),
+                }))
+                .collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    match active {
+        Some(m) => println!("Maintenance: {}", m),
+        None => println!("Maintenance: off"),
+    }
+    if !upcoming.is_empty() {
+        println!("Pause windows:");
+        for (w, next) in &upcoming {
+            let when = match next {
+                Ok(Some(t)) => format!("next {}", t.format("%Y-%m-%d %H:%M UTC")),
+                Ok(None) => "no future start".to_string(),
+                Err(e) => format!("invalid: {:#}", e),
+            };
+            println!(
+                "  '{}' for {} — {}{}",
+                w.cron,
+                w.duration,
+                when,
+                w.reason
+                    .as_deref()
+                    .map(|r| format!(" ({})", r))
+                    .unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
+}
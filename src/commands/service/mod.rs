@@ -19,12 +19,25 @@
 mod assignment;
 mod coordinator;
 pub(crate) mod coordinator_agent;
+mod cost_cap;
+mod drain;
+#[cfg(all(unix, feature = "grpc"))]
+mod grpc;
+#[cfg(unix)]
+mod http_api;
 pub mod ipc;
+mod latency;
+mod leader;
+mod maintenance;
+mod preempt;
 mod triage;
 pub(crate) mod worktree;
 pub(crate) mod zero_output;
 
+pub use drain::run_drain;
 pub use ipc::{IpcRequest, IpcResponse};
+pub use latency::run_latency;
+pub use maintenance::{run_maintenance_off, run_maintenance_on, run_maintenance_status};
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -433,6 +446,12 @@ pub struct ServiceState {
     pub pid: u32,
     pub socket_path: String,
     pub started_at: String,
+    /// Address of the REST API (`--http`), kept so a restart serves it again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http: Option<String>,
+    /// Address of the gRPC control API (`--grpc`), likewise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc: Option<String>,
 }
 
 impl ServiceState {
@@ -557,6 +576,13 @@ pub struct CoordinatorState {
     /// Whether the coordinator is paused (no new agent spawns)
     #[serde(default)]
     pub paused: bool,
+    /// Tags whose tasks are not dispatched (`wg service pause --tag`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paused_tags: Vec<String>,
+    /// Resource pools (queues) whose tasks are not dispatched
+    /// (`wg service pause --queue`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paused_queues: Vec<String>,
     /// Whether agents are frozen (SIGSTOP sent to all agent processes)
     #[serde(default)]
     pub frozen: bool,
@@ -982,7 +1008,9 @@ pub fn find_orphan_daemon_pids(_dir: &Path, _exclude_pid: Option<u32>) -> Vec<u3
 pub fn run_start(
     dir: &Path,
     socket_path: Option<&str>,
-    _port: Option<u16>,
+    port: Option<u16>,
+    http: Option<&str>,
+    grpc: Option<&str>,
     max_agents: Option<usize>,
     executor: Option<&str>,
     interval: Option<u64>,
@@ -990,9 +1018,30 @@ pub fn run_start(
     json: bool,
     force: bool,
     no_coordinator_agent: bool,
+    standby: bool,
 ) -> Result<()> {
     let config = Config::load_merged(dir)?;
 
+    // A standby only makes sense next to a live leader; without one, start
+    // normally.
+    let leader = ServiceState::load(dir)?.filter(|s| is_process_alive(s.pid));
+    if standby && let Some(ref leader) = leader {
+        return start_standby(
+            dir,
+            leader,
+            socket_path,
+            port,
+            http,
+            grpc,
+            max_agents,
+            executor,
+            interval,
+            model,
+            json,
+            no_coordinator_agent,
+        );
+    }
+
     // Check if service is already running
     if let Some(state) = ServiceState::load(dir)? {
         if is_process_alive(state.pid) {
@@ -1004,6 +1053,8 @@ pub fn run_start(
                         state.pid
                     );
                 }
+                // A standby would take over the moment the leader dies.
+                leader::stop_standby(dir, false)?;
                 // Send shutdown via IPC first (graceful)
                 let socket = PathBuf::from(&state.socket_path);
                 if socket.exists()
@@ -1093,37 +1144,25 @@ pub fn run_start(
     // Fork the daemon process
     let current_exe = std::env::current_exe().context("Failed to get current executable path")?;
 
-    let dir_str = dir.to_string_lossy().to_string();
     let socket_str = socket.to_string_lossy().to_string();
 
+    // `--port N` is shorthand for `--http 127.0.0.1:N`.
+    let http = http
+        .map(String::from)
+        .or_else(|| port.map(|p| format!("127.0.0.1:{}", p)));
+
     // Start daemon in background
-    let mut args = vec![
-        "--dir".to_string(),
-        dir_str,
-        "service".to_string(),
-        "daemon".to_string(),
-        "--socket".to_string(),
-        socket_str.clone(),
-    ];
-    if let Some(n) = max_agents {
-        args.push("--max-agents".to_string());
-        args.push(n.to_string());
-    }
-    if let Some(e) = executor {
-        args.push("--executor".to_string());
-        args.push(e.to_string());
-    }
-    if let Some(i) = interval {
-        args.push("--interval".to_string());
-        args.push(i.to_string());
-    }
-    if let Some(m) = model {
-        args.push("--model".to_string());
-        args.push(m.to_string());
-    }
-    if no_coordinator_agent {
-        args.push("--no-coordinator-agent".to_string());
-    }
+    let args = daemon_args(
+        dir,
+        &socket_str,
+        max_agents,
+        executor,
+        interval,
+        model,
+        no_coordinator_agent,
+        http.as_deref(),
+        grpc,
+    );
     // Redirect daemon stderr to the log file so early startup crashes and
     // unexpected panics that bypass the DaemonLogger are captured.
     let log_path = log_file_path(dir);
@@ -1153,6 +1192,8 @@ pub fn run_start(
         pid,
         socket_path: socket_str.clone(),
         started_at: chrono::Utc::now().to_rfc3339(),
+        http: http.clone(),
+        grpc: grpc.map(String::from),
     };
     state.save(dir)?;
 
@@ -1268,6 +1309,14 @@ pub fn run_start(
                 "model": eff_model,
             }
         });
+        if let Some(ref addr) = http {
+            output["http"] = serde_json::json!(format!("http://{}", addr));
+            output["http_token_file"] =
+                serde_json::json!(http_api::token_path(dir).to_string_lossy());
+        }
+        if let Some(addr) = grpc {
+            output["grpc"] = serde_json::json!(addr);
+        }
         if warn_no_agents {
             output["warning"] = serde_json::json!(
                 "auto_assign is enabled but no agents are defined. Run 'wg agency init' or 'wg agent create' to create agents."
@@ -1278,6 +1327,20 @@ pub fn run_start(
         println!("Service started (PID {})", pid);
         println!("Socket: {}", socket_str);
         println!("Log: {}", log_path_str);
+        if let Some(ref addr) = http {
+            println!(
+                "HTTP API: http://{} (token in {})",
+                addr,
+                http_api::token_path(dir).display()
+            );
+        }
+        if let Some(addr) = grpc {
+            println!(
+                "gRPC API: {} (token in {})",
+                addr,
+                http_api::token_path(dir).display()
+            );
+        }
         let model_str = eff_model.as_deref().unwrap_or("default");
         println!(
             "Dispatcher: max_agents={}, poll_interval={}s, executor={}, model={}",
@@ -1293,11 +1356,171 @@ pub fn run_start(
     Ok(())
 }
 
+/// Arguments for a `wg service daemon` child process.
+#[cfg(unix)]
+#[allow(clippy::too_many_arguments)]
+fn daemon_args(
+    dir: &Path,
+    socket: &str,
+    max_agents: Option<usize>,
+    executor: Option<&str>,
+    interval: Option<u64>,
+    model: Option<&str>,
+    no_coordinator_agent: bool,
+    http: Option<&str>,
+    grpc: Option<&str>,
+) -> Vec<String> {
+    let mut args = vec![
+        "--dir".to_string(),
+        dir.to_string_lossy().to_string(),
+        "service".to_string(),
+        "daemon".to_string(),
+        "--socket".to_string(),
+        socket.to_string(),
+    ];
+    if let Some(n) = max_agents {
+        args.push("--max-agents".to_string());
+        args.push(n.to_string());
+    }
+    if let Some(e) = executor {
+        args.push("--executor".to_string());
+        args.push(e.to_string());
+    }
+    if let Some(i) = interval {
+        args.push("--interval".to_string());
+        args.push(i.to_string());
+    }
+    if let Some(m) = model {
+        args.push("--model".to_string());
+        args.push(m.to_string());
+    }
+    if no_coordinator_agent {
+        args.push("--no-coordinator-agent".to_string());
+    }
+    if let Some(addr) = http {
+        args.push("--http".to_string());
+        args.push(addr.to_string());
+    }
+    if let Some(addr) = grpc {
+        args.push("--grpc".to_string());
+        args.push(addr.to_string());
+    }
+    args
+}
+
+/// Spawn a standby daemon next to the running `leader`. The standby waits
+/// on the leader lock and takes over the socket, state, and dispatch if the
+/// leader dies. Unset options default to the leader's socket and APIs.
+#[cfg(unix)]
+#[allow(clippy::too_many_arguments)]
+fn start_standby(
+    dir: &Path,
+    leader: &ServiceState,
+    socket_path: Option<&str>,
+    port: Option<u16>,
+    http: Option<&str>,
+    grpc: Option<&str>,
+    max_agents: Option<usize>,
+    executor: Option<&str>,
+    interval: Option<u64>,
+    model: Option<&str>,
+    json: bool,
+    no_coordinator_agent: bool,
+) -> Result<()> {
+    if let Some(existing) = leader::StandbyState::load_alive(dir) {
+        if json {
+            let output = serde_json::json!({
+                "error": "Standby already running",
+                "pid": existing.pid,
+                "leader_pid": leader.pid,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            println!(
+                "Standby already running (PID {}) for leader PID {}",
+                existing.pid, leader.pid
+            );
+        }
+        return Ok(());
+    }
+
+    let socket = socket_path
+        .map(String::from)
+        .unwrap_or_else(|| leader.socket_path.clone());
+    let http = http
+        .map(String::from)
+        .or_else(|| port.map(|p| format!("127.0.0.1:{}", p)))
+        .or_else(|| leader.http.clone());
+    let grpc = grpc.map(String::from).or_else(|| leader.grpc.clone());
+    let args = daemon_args(
+        dir,
+        &socket,
+        max_agents,
+        executor,
+        interval,
+        model,
+        no_coordinator_agent,
+        http.as_deref(),
+        grpc.as_deref(),
+    );
+
+    let current_exe = std::env::current_exe().context("Failed to get current executable path")?;
+    let log_path = log_file_path(dir);
+    let stderr_file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .with_context(|| format!("Failed to open daemon log at {:?}", log_path))?;
+    let child = process::Command::new(&current_exe)
+        .args(&args)
+        .stdin(process::Stdio::null())
+        .stdout(process::Stdio::null())
+        .stderr(stderr_file)
+        .spawn()
+        .context("Failed to spawn standby daemon")?;
+    let pid = child.id();
+
+    // The daemon records itself in standby.json once it finds the leader
+    // lock taken.
+    let start = Instant::now();
+    let mut waiting = false;
+    while start.elapsed() < Duration::from_millis(3000) && is_process_alive(pid) {
+        if leader::StandbyState::load(dir).is_some_and(|s| s.pid == pid) {
+            waiting = true;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    if !waiting {
+        anyhow::bail!("Standby daemon did not enter standby. Check logs.");
+    }
+
+    if json {
+        let output = serde_json::json!({
+            "status": "standby",
+            "pid": pid,
+            "leader_pid": leader.pid,
+            "socket": socket,
+            "log": log_path,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!(
+            "Standby started (PID {}); takes over if the leader (PID {}) exits",
+            pid, leader.pid
+        );
+        println!("Log: {}", log_path.display());
+    }
+    Ok(())
+}
+
 #[cfg(not(unix))]
 pub fn run_start(
     _dir: &Path,
     _socket_path: Option<&str>,
     _port: Option<u16>,
+    _http: Option<&str>,
+    _grpc: Option<&str>,
     _max_agents: Option<usize>,
     _executor: Option<&str>,
     _interval: Option<u64>,
@@ -1305,6 +1528,7 @@ pub fn run_start(
     _json: bool,
     _force: bool,
     _no_coordinator_agent: bool,
+    _standby: bool,
 ) -> Result<()> {
     anyhow::bail!("Service daemon is only supported on Unix systems")
 }
@@ -1505,10 +1729,8 @@ fn record_tick_events(
 /// notifications through the configured [`NotificationRouter`]. This is called
 /// after each coordinator tick.
 fn try_dispatch_notifications(dir: &Path, logger: &DaemonLogger) {
-    use workgraph::notify::NotificationRouter;
     use workgraph::notify::config::NotifyConfig;
     use workgraph::notify::dispatch::{TaskEvent, TaskEventKind};
-    use workgraph::notify::webhook::WebhookChannel;
 
     // Load notification config — if not present, notifications are disabled.
     let config = match NotifyConfig::load(Some(dir)) {
@@ -1520,54 +1742,18 @@ fn try_dispatch_notifications(dir: &Path, logger: &DaemonLogger) {
         }
     };
 
-    let rules = config.to_routing_rules();
-    let default_channels = config.default_channels().to_vec();
-
-    if rules.is_empty() && default_channels.is_empty() {
+    if config.to_routing_rules().is_empty() && config.default_channels().is_empty() {
         return; // No routing rules → nothing to dispatch
     }
 
-    // Build channels from config. Each channel type is constructed if its
-    // config section exists.
-    let mut channels: Vec<Box<dyn workgraph::notify::NotificationChannel>> = Vec::new();
-
-    // Webhook channel (always available, no external runtime deps)
-    if config.has_channel_config("webhook")
-        && let Some(val) = config.channels.get("webhook")
-    {
-        match val
-            .clone()
-            .try_into::<workgraph::notify::webhook::WebhookConfig>()
-        {
-            Ok(wh_config) => {
-                channels.push(Box::new(WebhookChannel::new(wh_config)));
-            }
-            Err(e) => {
-                logger.warn(&format!("Invalid webhook config: {}", e));
-            }
-        }
+    let (router, warnings) = config.build_router();
+    for w in &warnings {
+        logger.warn(w);
     }
-
-    // Telegram channel (if configured)
-    if config.has_channel_config("telegram") {
-        match workgraph::notify::telegram::TelegramConfig::from_notify_config(&config) {
-            Ok(tg_config) => {
-                channels.push(Box::new(workgraph::notify::telegram::TelegramChannel::new(
-                    tg_config,
-                )));
-            }
-            Err(e) => {
-                logger.warn(&format!("Invalid telegram config: {}", e));
-            }
-        }
-    }
-
-    if channels.is_empty() {
+    if router.available_channels().is_empty() {
         return; // No usable channels
     }
 
-    let router = NotificationRouter::new(channels, rules, default_channels);
-
     // Scan graph for recently changed tasks (last 10 seconds)
     let gp = graph_path(dir);
     let graph = match load_graph(&gp) {
@@ -1626,6 +1812,17 @@ fn try_dispatch_notifications(dir: &Path, logger: &DaemonLogger) {
         }
     };
 
+    // Failures during maintenance are usually caused by it; say so.
+    if let Some(m) = workgraph::maintenance::current(dir) {
+        for event in &mut events {
+            let note = format!("during maintenance: {}", m.reason);
+            event.detail = Some(match event.detail.take() {
+                Some(d) => format!("{} [{}]", d, note),
+                None => note,
+            });
+        }
+    }
+
     for event in &events {
         // Use task_id as the routing target (webhook will parse it)
         let target = &event.task_id;
@@ -1655,6 +1852,197 @@ fn try_dispatch_notifications(dir: &Path, logger: &DaemonLogger) {
     }
 }
 
+/// Fire due escalation-chain steps (see [`workgraph::escalation`]).
+///
+/// No-op unless `notify.toml` configures an `[[escalation.chain]]`.
+fn run_escalation_sweep(dir: &Path, logger: &DaemonLogger) {
+    let config = match workgraph::notify::config::NotifyConfig::load(dir.parent()) {
+        Ok(Some(c)) if !c.escalation.chain.is_empty() => c,
+        Ok(_) => return,
+        Err(e) => {
+            logger.warn(&format!("Failed to load notify config: {}", e));
+            return;
+        }
+    };
+    match workgraph::escalation::run_sweep(dir, &graph_path(dir), &config, false) {
+        Ok(actions) => {
+            for a in &actions {
+                logger.info(&format!(
+                    "Escalated '{}' (step {}/{}) to {}",
+                    a.task_id, a.level, a.chain_len, a.target
+                ));
+            }
+        }
+        Err(e) => logger.warn(&format!("Escalation sweep failed: {}", e)),
+    }
+}
+
+/// Send deadline alerts for overdue tasks and at-risk milestones (see
+/// [`workgraph::deadline`]).
+fn run_deadline_sweep(dir: &Path, logger: &DaemonLogger) {
+    let config = match workgraph::notify::config::NotifyConfig::load(dir.parent()) {
+        Ok(c) => c,
+        Err(e) => {
+            logger.warn(&format!("Failed to load notify config: {}", e));
+            None
+        }
+    };
+    match workgraph::deadline::run_sweep(dir, &graph_path(dir), config.as_ref(), false) {
+        Ok(alerts) => {
+            for a in &alerts {
+                logger.info(&format!("Deadline alert for '{}': {}", a.task_id, a.kind));
+            }
+        }
+        Err(e) => logger.warn(&format!("Deadline sweep failed: {}", e)),
+    }
+}
+
+/// Send follow-up reminders for tasks blocked on an external team (see
+/// [`workgraph::external_blocker`]).
+fn run_external_blocker_sweep(dir: &Path, logger: &DaemonLogger) {
+    let config = match workgraph::notify::config::NotifyConfig::load(dir.parent()) {
+        Ok(c) => c,
+        Err(e) => {
+            logger.warn(&format!("Failed to load notify config: {}", e));
+            None
+        }
+    };
+    match workgraph::external_blocker::run_sweep(dir, &graph_path(dir), config.as_ref(), false) {
+        Ok(reminders) => {
+            for r in &reminders {
+                logger.info(&format!("External blocker follow-up for '{}'", r.task_id));
+            }
+        }
+        Err(e) => logger.warn(&format!("External blocker sweep failed: {}", e)),
+    }
+}
+
+/// Record how long ready tasks wait for an agent and alert when they wait
+/// past `coordinator.dispatch_latency_alerts` (see
+/// [`workgraph::dispatch::latency`]).
+fn run_latency_sweep(dir: &Path, logger: &DaemonLogger) {
+    let thresholds = Config::load_or_default(dir)
+        .coordinator
+        .dispatch_latency_alerts;
+    let config = if thresholds.is_empty() {
+        None
+    } else {
+        match workgraph::notify::config::NotifyConfig::load(dir.parent()) {
+            Ok(c) => c,
+            Err(e) => {
+                logger.warn(&format!("Failed to load notify config: {}", e));
+                None
+            }
+        }
+    };
+    match workgraph::dispatch::latency::run_sweep(
+        dir,
+        &graph_path(dir),
+        &thresholds,
+        config.as_ref(),
+    ) {
+        Ok((breaches, outcome)) => {
+            for b in &breaches {
+                logger.warn(&format!(
+                    "Dispatch latency: '{}' ready for {}s without an agent",
+                    b.task_id, b.waited_secs
+                ));
+            }
+            if let Some(outcome) = outcome {
+                logger.info(&format!("Dispatch latency alert {}", outcome));
+            }
+        }
+        Err(e) => logger.warn(&format!("Dispatch latency sweep failed: {:#}", e)),
+    }
+}
+
+/// Announce maintenance starting and ending, and end manual maintenance
+/// whose `--for` is up (see [`workgraph::maintenance`]).
+fn run_maintenance_sweep(dir: &Path, logger: &DaemonLogger) {
+    let windows = Config::load_or_default(dir).coordinator.pause_windows;
+    let config = match workgraph::notify::config::NotifyConfig::load(dir.parent()) {
+        Ok(c) => c,
+        Err(e) => {
+            logger.warn(&format!("Failed to load notify config: {}", e));
+            None
+        }
+    };
+    match workgraph::maintenance::run_sweep(dir, &windows, config.as_ref()) {
+        Ok(transitions) => {
+            for (t, outcome) in &transitions {
+                logger.info(&format!("{} ({})", t.message(), outcome));
+            }
+        }
+        Err(e) => logger.warn(&format!("Maintenance sweep failed: {:#}", e)),
+    }
+}
+
+/// Alert on new anomalies in task metrics (see [`workgraph::anomaly`]).
+///
+/// No-op when `anomaly.alerts` is off.
+fn run_anomaly_sweep(dir: &Path, logger: &DaemonLogger) {
+    if !Config::load_or_default(dir).anomaly.alerts {
+        return;
+    }
+    let config = match workgraph::notify::config::NotifyConfig::load(dir.parent()) {
+        Ok(c) => c,
+        Err(e) => {
+            logger.warn(&format!("Failed to load notify config: {}", e));
+            None
+        }
+    };
+    match workgraph::anomaly::run_sweep(dir, &graph_path(dir), config.as_ref(), false) {
+        Ok(alerted) => {
+            for (a, outcome) in &alerted {
+                logger.warn(&format!("{} ({})", a.message(), outcome));
+            }
+        }
+        Err(e) => logger.warn(&format!("Anomaly sweep failed: {:#}", e)),
+    }
+}
+
+/// Alert once a day on each agent over its daily budget (see
+/// [`workgraph::dispatch::budget`]).
+fn run_budget_sweep(dir: &Path, logger: &DaemonLogger) {
+    let config = match workgraph::notify::config::NotifyConfig::load(dir.parent()) {
+        Ok(c) => c,
+        Err(e) => {
+            logger.warn(&format!("Failed to load notify config: {}", e));
+            None
+        }
+    };
+    match workgraph::dispatch::budget::run_sweep(dir, &graph_path(dir), config.as_ref()) {
+        Ok(alerted) => {
+            for (over, outcome) in &alerted {
+                logger.warn(&format!("{} ({})", over.message(), outcome));
+            }
+        }
+        Err(e) => logger.warn(&format!("Budget sweep failed: {:#}", e)),
+    }
+}
+
+/// Post morning day plans to Matrix (see [`workgraph::day_plan`]).
+///
+/// No-op unless `[day_plan]` sets `post_at` and `post_for`.
+fn run_day_plan_posts(dir: &Path, logger: &DaemonLogger) {
+    let plans = match workgraph::day_plan::due_morning_plans(dir, chrono::Utc::now()) {
+        Ok(plans) => plans,
+        Err(e) => {
+            logger.warn(&format!("Day plan check failed: {:#}", e));
+            return;
+        }
+    };
+    for plan in &plans {
+        match workgraph::day_plan::post(dir, plan) {
+            Ok(()) => logger.info(&format!("Posted day plan for '{}'", plan.who)),
+            Err(e) => logger.warn(&format!(
+                "Failed to post day plan for '{}': {:#}",
+                plan.who, e
+            )),
+        }
+    }
+}
+
 /// Mark legacy daemon-managed graph tasks as abandoned.
 ///
 /// Older coordinator implementations represented daemon control flow as
@@ -1825,6 +2213,117 @@ fn run_automatic_archival(dir: &Path, archival_error_count: &mut u64, logger: &D
     }
 }
 
+/// Enforce `[retention]` policies directly from the daemon.
+///
+/// Time-gated by `retention.interval_hours` (measured from the last saved
+/// report), so most ticks return without touching the disk beyond one read.
+fn run_retention(dir: &Path, logger: &DaemonLogger) {
+    let config = workgraph::config::Config::load_or_default(dir);
+    if !workgraph::retention::is_due(dir, &config.retention) {
+        return;
+    }
+    match workgraph::retention::enforce(dir, &config.retention, false) {
+        Ok(report) => {
+            let summary: Vec<String> = report
+                .categories
+                .iter()
+                .map(|c| format!("{}={}", c.category, c.purged.len()))
+                .collect();
+            logger.info(&format!(
+                "Retention complete: {} item(s) purged, {} bytes freed ({})",
+                report.total_purged(),
+                report.total_bytes(),
+                summary.join(", ")
+            ));
+            if report.total_purged() > 0 {
+                let _ = workgraph::provenance::record(
+                    dir,
+                    "retention",
+                    None,
+                    Some("daemon"),
+                    serde_json::json!({
+                        "purged": report.total_purged(),
+                        "bytes_freed": report.total_bytes(),
+                    }),
+                    config.log.rotation_threshold,
+                );
+            }
+        }
+        Err(e) => logger.error(&format!("Retention error: {:#}", e)),
+    }
+}
+
+/// Most drifted keys named in the warning; `wg config diff` lists them all.
+const DRIFT_KEYS_SHOWN: usize = 8;
+
+/// Warn when the effective config differs from the project's committed
+/// `workgraph.toml` baseline. Silent when there is no baseline.
+pub(crate) fn warn_config_drift(dir: &Path, config: &Config, logger: &DaemonLogger) {
+    let path = workgraph::config_drift::baseline_path(dir);
+    if !path.exists() {
+        return;
+    }
+    let changes = match workgraph::config_drift::load_baseline(&path)
+        .and_then(|baseline| workgraph::config_drift::diff(&baseline, config))
+    {
+        Ok(changes) => changes,
+        Err(e) => {
+            logger.warn(&format!("Config baseline not checked: {:#}", e));
+            return;
+        }
+    };
+    if changes.is_empty() {
+        return;
+    }
+    let mut keys: Vec<&str> = changes
+        .iter()
+        .take(DRIFT_KEYS_SHOWN)
+        .map(|c| c.key.as_str())
+        .collect();
+    if changes.len() > DRIFT_KEYS_SHOWN {
+        keys.push("...");
+    }
+    logger.warn(&format!(
+        "Config drift: {} key(s) differ from {}: {}. Run `wg config diff` for details.",
+        changes.len(),
+        path.display(),
+        keys.join(", ")
+    ));
+}
+
+/// Recompute learning-loop adjustments from function run summaries and log
+/// every change, so tier/concurrency shifts are never silent.
+fn run_learning_refresh(dir: &Path, logger: &DaemonLogger) {
+    let config = workgraph::config::Config::load_or_default(dir);
+    if !config.learning.enabled {
+        return;
+    }
+    let graph = match load_graph(dir.join("graph.jsonl")) {
+        Ok(g) => g,
+        Err(e) => {
+            logger.warn(&format!("Learning refresh skipped: {:#}", e));
+            return;
+        }
+    };
+    match workgraph::learning::refresh(dir, &graph, &config.learning) {
+        Ok(changes) if changes.is_empty() => {}
+        Ok(changes) => {
+            for change in &changes {
+                logger.info(&format!("Learning loop: {}", change));
+            }
+            let _ = workgraph::provenance::record(
+                dir,
+                "learning_adjust",
+                None,
+                Some("daemon"),
+                serde_json::json!({ "changes": changes }),
+                config.log.rotation_threshold,
+            );
+        }
+        Err(e) => logger.error(&format!("Learning refresh error: {:#}", e)),
+    }
+}
+
 /// Daemon-side state for the model-registry refresh job: failure count
 /// and an optional cooldown window. After
 /// `REGISTRY_REFRESH_FAILURE_THRESHOLD` consecutive failures the daemon
@@ -1999,6 +2498,8 @@ pub fn run_daemon(
     cli_interval: Option<u64>,
     cli_model: Option<&str>,
     no_coordinator_agent: bool,
+    http: Option<&str>,
+    grpc: Option<&str>,
 ) -> Result<()> {
     let socket = PathBuf::from(socket_path);
 
@@ -2012,6 +2513,17 @@ pub fn run_daemon(
         socket_path,
     ));
 
+    // Graph writes made by the daemon are the service's, not the user's:
+    // a user's `wg undo` steps past them.
+    workgraph::journal::set_actor("service");
+
+    // Test-only fault injection, `chaos` feature builds only (see
+    // workgraph::service::chaos).
+    let mut chaos = workgraph::service::chaos::Chaos::from_env()?;
+    if let Some(ref chaos) = chaos {
+        logger.warn(&format!("Chaos mode enabled: {}", chaos.config));
+    }
+
     // --- Binary self-restart detection ---
     // Record the exe path and its metadata at startup so we can detect when
     // `cargo install` (or similar) replaces the binary on disk.  We use
@@ -2046,6 +2558,14 @@ pub fn run_daemon(
         ));
     }
 
+    // --- Leader election ---
+    // Only the holder of the leader lock dispatches. A standby daemon
+    // (`wg service start --standby`) waits here until the leader exits.
+    let _leader_lock = match leader::try_acquire(dir)? {
+        Some(lock) => lock,
+        None => leader::wait_for_leadership(dir, socket_path, http, grpc, &logger)?,
+    };
+
     // Ensure socket directory exists
     if let Some(parent) = socket.parent()
         && !parent.exists()
@@ -2096,6 +2616,8 @@ pub fn run_daemon(
         }
     }
 
+    warn_config_drift(&dir, &config, &logger);
+
     // Validate configuration before starting
     let validation = config.validate_config();
     for diag in &validation.warnings {
@@ -2161,7 +2683,9 @@ pub fn run_daemon(
         }
     }
 
-    // Initialize coordinator state on disk
+    // Initialize coordinator state on disk. Scoped pauses (`--tag`,
+    // `--queue`) survive restarts; a full pause does not.
+    let previous_state = CoordinatorState::load(&dir);
     let mut coord_state = CoordinatorState {
         enabled: true,
         max_agents: daemon_cfg.max_agents,
@@ -2174,9 +2698,18 @@ pub fn run_daemon(
         tasks_ready: 0,
         agents_spawned: 0,
         paused: false,
+        paused_tags: previous_state
+            .as_ref()
+            .map(|cs| cs.paused_tags.clone())
+            .unwrap_or_default(),
+        paused_queues: previous_state
+            .as_ref()
+            .map(|cs| cs.paused_queues.clone())
+            .unwrap_or_default(),
         frozen: false,
         frozen_pids: Vec::new(),
-        accumulated_tokens: CoordinatorState::load(&dir)
+        accumulated_tokens: previous_state
+            .as_ref()
             .map(|cs| cs.accumulated_tokens)
             .unwrap_or(0),
         cost_tracking: SessionCostTracking::default(),
@@ -2412,6 +2945,51 @@ pub fn run_daemon(
     let mut urgent_wake = false;
     let mut pending_coordinator_ids: Vec<u32> = Vec::new();
 
+    // External event gates and webhook executor callbacks: serve
+    // `POST /hooks/<name>` and `POST /callbacks/<task-id>` when configured.
+    // A bind failure is logged, not fatal — the rest of the service still works.
+    if let Some(ref addr) = config.coordinator.webhook_listen {
+        let hook_logger = logger.clone();
+        match workgraph::service::webhook_listener::spawn(
+            dir.clone(),
+            addr,
+            config.coordinator.webhook_secret.clone(),
+            move |line| hook_logger.info(line),
+        ) {
+            Ok(_) => logger.info(&format!("Webhook listener on http://{}/hooks/<name>", addr)),
+            Err(e) => logger.error(&format!("{:#}", e)),
+        }
+    }
+
+    // REST API for web frontends and other tools (`--http`). Like the
+    // webhook listener, a bind failure is logged, not fatal.
+    if let Some(addr) = http {
+        let started = http_api::load_or_create_token(&dir)
+            .and_then(|token| http_api::spawn(dir.clone(), addr, token, logger.clone()));
+        match started {
+            Ok(()) => logger.info(&format!("HTTP API on http://{}/api", addr)),
+            Err(e) => logger.error(&format!("{:#}", e)),
+        }
+    }
+
+    // gRPC control API (`--grpc`), sharing the HTTP API's token.
+    if let Some(addr) = grpc {
+        #[cfg(feature = "grpc")]
+        {
+            let started = http_api::load_or_create_token(&dir)
+                .and_then(|token| grpc::spawn(dir.clone(), addr, token, logger.clone()));
+            match started {
+                Ok(()) => logger.info(&format!("gRPC API on {}", addr)),
+                Err(e) => logger.error(&format!("{:#}", e)),
+            }
+        }
+        #[cfg(not(feature = "grpc"))]
+        logger.error(&format!(
+            "--grpc {} ignored: this wg was built without the `grpc` feature",
+            addr
+        ));
+    }
+
     // Load max_coordinators limit from config
     let max_coordinators = config.coordinator.max_coordinators;
 
@@ -2533,6 +3111,7 @@ pub fn run_daemon(
                     &mut conn_delete_coordinator_ids,
                     &mut conn_interrupt_coordinator_ids,
                     &mut daemon_cfg,
+                    chaos.as_mut(),
                     &logger,
                 ) {
                     logger.error(&format!("Error handling connection: {}", e));
@@ -2752,6 +3331,17 @@ pub fn run_daemon(
                 }
             }
 
+            if let Some(ref mut chaos) = chaos {
+                match chaos.strike(&dir) {
+                    Ok(events) => {
+                        for event in &events {
+                            logger.warn(&format!("Chaos: {}", event));
+                        }
+                    }
+                    Err(e) => logger.warn(&format!("Chaos strike failed: {}", e)),
+                }
+            }
+
             logger.info(&format!(
                 "Coordinator tick #{} starting (max_agents={}, executor={})",
                 coord_state.ticks + 1,
@@ -2775,9 +3365,12 @@ pub fn run_daemon(
                     coord_state.tasks_ready = result.tasks_ready;
                     coord_state.agents_spawned = result.agents_spawned;
                     // Reload accumulated_tokens from disk before saving to avoid clobbering
-                    // increments written by the coordinator agent thread.
+                    // increments written by the coordinator agent thread, and the
+                    // scoped pauses written by IPC.
                     if let Some(disk) = CoordinatorState::load(&dir) {
                         coord_state.accumulated_tokens = disk.accumulated_tokens;
+                        coord_state.paused_tags = disk.paused_tags;
+                        coord_state.paused_queues = disk.paused_queues;
                     }
                     coord_state.save(&dir);
 
@@ -2792,12 +3385,42 @@ pub fn run_daemon(
                     // Dispatch notifications for task state changes (failures, blocks)
                     try_dispatch_notifications(&dir, &logger);
 
+                    // Walk escalation chains for tasks stuck in-progress or blocked.
+                    run_escalation_sweep(&dir, &logger);
+
+                    // Alert on missed deadlines and milestone forecast slips.
+                    run_deadline_sweep(&dir, &logger);
+
+                    // Remind about tasks blocked on external teams.
+                    run_external_blocker_sweep(&dir, &logger);
+
+                    // Expire and announce maintenance and pause windows.
+                    run_maintenance_sweep(&dir, &logger);
+
+                    // Track ready-to-started latency and alert on stalls.
+                    run_latency_sweep(&dir, &logger);
+
+                    // Alert on overruns, fast low-scored work and failure spikes.
+                    run_anomaly_sweep(&dir, &logger);
+
+                    // Alert on agents that went over their daily budget.
+                    run_budget_sweep(&dir, &logger);
+
+                    // Post the morning day plans that are due.
+                    run_day_plan_posts(&dir, &logger);
+
                     // Keep per-coordinator chat history compact without polluting the graph.
                     run_pending_chat_compactions(&dir, &logger);
 
                     // Automatic archival runs directly in the daemon.
                     run_automatic_archival(&dir, &mut archival_error_count, &logger);
 
+                    // Retention purges old logs/runs/provenance/archives/telemetry.
+                    run_retention(&dir, &logger);
+
+                    // Learning loop adjusts dispatch for chronically weak templates/skills.
+                    run_learning_refresh(&dir, &logger);
+
                     // Registry refresh runs directly in the daemon and is time-gated.
                     run_registry_refresh(&dir, &mut registry_refresh_state, &logger);
 
@@ -2813,6 +3436,8 @@ pub fn run_daemon(
                     coord_state.ticks += 1;
                     if let Some(disk) = CoordinatorState::load(&dir) {
                         coord_state.accumulated_tokens = disk.accumulated_tokens;
+                        coord_state.paused_tags = disk.paused_tags;
+                        coord_state.paused_queues = disk.paused_queues;
                     }
                     coord_state.save(&dir);
                     logger.error(&format!("Coordinator tick error: {}", e));
@@ -2864,6 +3489,10 @@ pub fn run_daemon(
                                         ));
 
                                         // Pre-exec cleanup: save coordinator state.
+                                        if let Some(disk) = CoordinatorState::load(&dir) {
+                                            coord_state.paused_tags = disk.paused_tags;
+                                            coord_state.paused_queues = disk.paused_queues;
+                                        }
                                         coord_state.save(&dir);
 
                                         // Shut down coordinator agents (LLM sessions).
@@ -2959,6 +3588,8 @@ pub fn run_daemon(
     _interval: Option<u64>,
     _model: Option<&str>,
     _no_coordinator_agent: bool,
+    _http: Option<&str>,
+    _grpc: Option<&str>,
 ) -> Result<()> {
     anyhow::bail!("Daemon is only supported on Unix systems")
 }
@@ -2979,21 +3610,17 @@ pub fn run_stop(dir: &Path, force: bool, kill_agents: bool, json: bool) -> Resul
     run_stop_inner(dir, force, kill_agents, json)
 }
 
-/// Inner stop logic (no agent guard) — used by `run_restart` to bypass the guard.
+/// Ask the daemon to shut down over IPC, kill it if it lingers, remove its
+/// state, and reap orphan daemons. Returns how many orphans were killed.
 #[cfg(unix)]
-fn run_stop_inner(dir: &Path, force: bool, kill_agents: bool, json: bool) -> Result<()> {
-    let state = match ServiceState::load(dir)? {
-        Some(s) => s,
-        None => {
-            if json {
-                let output = serde_json::json!({ "error": "Service not running" });
-                println!("{}", serde_json::to_string_pretty(&output)?);
-            } else {
-                println!("Service not running");
-            }
-            return Ok(());
-        }
-    };
+fn shutdown_daemon(
+    dir: &Path,
+    state: &ServiceState,
+    force: bool,
+    kill_agents: bool,
+) -> Result<usize> {
+    // Stop the standby first, or it would take over from the leader.
+    leader::stop_standby(dir, force)?;
 
     // Try to send shutdown command via socket
     let socket = PathBuf::from(&state.socket_path);
@@ -3042,6 +3669,27 @@ fn run_stop_inner(dir: &Path, force: bool, kill_agents: bool, json: bool) -> Res
         orphan_count += 1;
     }
 
+    Ok(orphan_count)
+}
+
+/// Inner stop logic (no agent guard) — used by `run_restart` to bypass the guard.
+#[cfg(unix)]
+fn run_stop_inner(dir: &Path, force: bool, kill_agents: bool, json: bool) -> Result<()> {
+    let state = match ServiceState::load(dir)? {
+        Some(s) => s,
+        None => {
+            if json {
+                let output = serde_json::json!({ "error": "Service not running" });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                println!("Service not running");
+            }
+            return Ok(());
+        }
+    };
+
+    let orphan_count = shutdown_daemon(dir, &state, force, kill_agents)?;
+
     if json {
         let output = serde_json::json!({
             "status": "stopped",
@@ -3082,6 +3730,9 @@ pub fn run_stop(_dir: &Path, _force: bool, _kill_agents: bool, _json: bool) -> R
 pub fn run_restart(dir: &Path, json: bool) -> Result<()> {
     // Capture the current daemon's effective config before stopping.
     let prior_config = CoordinatorState::load(dir);
+    let prior_state = ServiceState::load(dir).ok().flatten();
+    let prior_http = prior_state.as_ref().and_then(|s| s.http.clone());
+    let prior_grpc = prior_state.and_then(|s| s.grpc);
 
     // Stop gracefully — agents continue running independently.
     // Use inner variant to bypass the agent guard (agents may restart).
@@ -3100,11 +3751,19 @@ pub fn run_restart(dir: &Path, json: bool) -> Result<()> {
 
     // Start a new daemon with the same config.
     run_start(
-        dir, None, // socket — use default
+        dir,
+        None, // socket — use default
         None, // port
-        max_agents, executor, interval, model, json,
+        prior_http.as_deref(),
+        prior_grpc.as_deref(),
+        max_agents,
+        executor,
+        interval,
+        model,
+        json,
         true,  // force — clean up any leftover state
         false, // no_coordinator_agent — use default
+        false, // standby
     )
 }
 
@@ -3175,6 +3834,9 @@ pub fn run_status(dir: &Path, json: bool) -> Result<()> {
     let log_exists = log_path.exists();
     let recent_errors = tail_log(dir, 5, Some("ERROR"));
     let recent_fatals = tail_log(dir, 5, Some("FATAL"));
+    let executor_health = workgraph::service::ExecutorHealth::load(dir);
+    let standby = leader::StandbyState::load_alive(dir);
+    let maintenance = workgraph::maintenance::current(dir);
 
     if json {
         let mut output = serde_json::json!({
@@ -3192,6 +3854,8 @@ pub fn run_status(dir: &Path, json: bool) -> Result<()> {
             "coordinator": {
                 "enabled": coord.enabled,
                 "paused": coord.paused,
+                "paused_tags": coord.paused_tags,
+                "paused_queues": coord.paused_queues,
                 "frozen": coord.frozen,
                 "frozen_pids": coord.frozen_pids,
                 "max_agents": coord.max_agents,
@@ -3204,11 +3868,18 @@ pub fn run_status(dir: &Path, json: bool) -> Result<()> {
                 "tasks_ready": coord.tasks_ready,
                 "agents_spawned_last_tick": coord.agents_spawned,
             },
+            "executors": executor_health.executors,
             "log": {
                 "path": log_path_str,
                 "exists": log_exists,
             }
         });
+        if let Some(ref standby) = standby {
+            output["standby"] = serde_json::json!({ "pid": standby.pid, "since": standby.since });
+        }
+        if let Some(ref m) = maintenance {
+            output["maintenance"] = serde_json::json!(m);
+        }
         if !agency_agents_defined {
             output["warning"] =
                 serde_json::json!("No agents defined — run 'wg agency init' or 'wg agent create'");
@@ -3233,6 +3904,9 @@ pub fn run_status(dir: &Path, json: bool) -> Result<()> {
         println!("Service: running (PID {})", state.pid);
         println!("Socket: {}", state.socket_path);
         println!("Uptime: {}", uptime);
+        if let Some(ref standby) = standby {
+            println!("Standby: PID {} (waiting to take over)", standby.pid);
+        }
         if !agency_agents_defined {
             println!("Agents: No agents defined — run 'wg agency init' or 'wg agent create'");
         } else {
@@ -3257,6 +3931,8 @@ pub fn run_status(dir: &Path, json: bool) -> Result<()> {
             ", FROZEN"
         } else if coord.paused {
             ", PAUSED"
+        } else if maintenance.is_some() {
+            ", MAINTENANCE"
         } else {
             ""
         };
@@ -3267,6 +3943,15 @@ pub fn run_status(dir: &Path, json: bool) -> Result<()> {
         if coord.frozen && !coord.frozen_pids.is_empty() {
             println!("  Frozen PIDs: {:?}", coord.frozen_pids);
         }
+        if let Some(ref m) = maintenance {
+            println!("  Maintenance: {}", m);
+        }
+        if !coord.paused_tags.is_empty() || !coord.paused_queues.is_empty() {
+            println!(
+                "  Holding {}",
+                describe_pause_scope(&coord.paused_tags, &coord.paused_queues)
+            );
+        }
         if let Some(ref last) = coord.last_tick {
             println!(
                 "  Last tick: {} (#{}, agents_alive={}/{}, tasks_ready={}, spawned={})",
@@ -3280,6 +3965,24 @@ pub fn run_status(dir: &Path, json: bool) -> Result<()> {
         } else {
             println!("  No ticks yet");
         }
+        if !executor_health.executors.is_empty() {
+            let summary: Vec<String> = executor_health
+                .executors
+                .iter()
+                .map(|(key, check)| {
+                    format!("{} {}", key, if check.healthy { "ok" } else { "UNHEALTHY" })
+                })
+                .collect();
+            println!("Executors: {}", summary.join(", "));
+            for (key, check) in executor_health.unhealthy() {
+                println!(
+                    "  {}: {} (checked {})",
+                    key,
+                    check.problems.join("; "),
+                    check.checked_at
+                );
+            }
+        }
         println!("Log: {}", log_path_str);
         if !recent_errors.is_empty() || !recent_fatals.is_empty() {
             println!("  Recent errors:");
@@ -3383,12 +4086,33 @@ pub fn run_reload(
     anyhow::bail!("Service daemon is only supported on Unix systems")
 }
 
-/// Pause the coordinator (no new agent spawns, running agents unaffected)
+/// Describe a scoped pause, e.g. "tasks tagged deploy, or in queue gpu".
+#[cfg(unix)]
+fn describe_pause_scope(tags: &[String], queues: &[String]) -> String {
+    let mut parts = Vec::new();
+    if !tags.is_empty() {
+        parts.push(format!("tagged {}", tags.join(", ")));
+    }
+    if !queues.is_empty() {
+        parts.push(format!("in queue {}", queues.join(", ")));
+    }
+    format!("tasks {}", parts.join(", or "))
+}
+
+/// Pause the coordinator (no new agent spawns, running agents unaffected).
+/// With `tags` or `queues`, only matching tasks are held.
 #[cfg(unix)]
-pub fn run_pause(dir: &Path, json: bool) -> Result<()> {
+pub fn run_pause(dir: &Path, tags: &[String], queues: &[String], json: bool) -> Result<()> {
     guard_agent_stop_pause()?;
 
-    let response = send_request(dir, &IpcRequest::Pause)?;
+    let scoped = !tags.is_empty() || !queues.is_empty();
+    let response = send_request(
+        dir,
+        &IpcRequest::Pause {
+            tags: tags.to_vec(),
+            queues: queues.to_vec(),
+        },
+    )?;
 
     if !response.ok {
         let msg = response
@@ -3407,6 +4131,11 @@ pub fn run_pause(dir: &Path, json: bool) -> Result<()> {
         if let Some(data) = &response.data {
             println!("{}", serde_json::to_string_pretty(data)?);
         }
+    } else if scoped {
+        println!(
+            "Holding {} (running agents and other work continue)",
+            describe_pause_scope(tags, queues)
+        );
     } else {
         println!("Coordinator paused (running agents continue, no new spawns)");
     }
@@ -3415,50 +4144,61 @@ pub fn run_pause(dir: &Path, json: bool) -> Result<()> {
 }
 
 #[cfg(not(unix))]
-pub fn run_pause(_dir: &Path, _json: bool) -> Result<()> {
+pub fn run_pause(_dir: &Path, _tags: &[String], _queues: &[String], _json: bool) -> Result<()> {
     anyhow::bail!("Service daemon is only supported on Unix systems")
 }
 
-/// Resume the coordinator (triggers immediate tick) and clear provider health pauses
+/// Resume the coordinator (triggers immediate tick) and clear provider health pauses.
+/// With `tags` or `queues`, only those scoped pauses are lifted.
 #[cfg(unix)]
-pub fn run_resume(dir: &Path, json: bool) -> Result<()> {
-    // Clear provider health pause state before resuming coordinator
-    match workgraph::service::ProviderHealth::load(dir) {
-        Ok(mut provider_health) => {
-            let was_paused = provider_health.service_paused;
-            let paused_providers: Vec<_> = provider_health
-                .providers
-                .values()
-                .filter(|p| p.is_paused)
-                .map(|p| p.provider_id.clone())
-                .collect();
+pub fn run_resume(dir: &Path, tags: &[String], queues: &[String], json: bool) -> Result<()> {
+    let scoped = !tags.is_empty() || !queues.is_empty();
+    // Clear provider health pause state before resuming coordinator.
+    // A scoped resume only releases the named tags/queues.
+    if !scoped {
+        match workgraph::service::ProviderHealth::load(dir) {
+            Ok(mut provider_health) => {
+                let was_paused = provider_health.service_paused;
+                let paused_providers: Vec<_> = provider_health
+                    .providers
+                    .values()
+                    .filter(|p| p.is_paused)
+                    .map(|p| p.provider_id.clone())
+                    .collect();
 
-            provider_health.resume_service();
-            if let Err(e) = provider_health.save(dir) {
+                provider_health.resume_service();
+                if let Err(e) = provider_health.save(dir) {
+                    eprintln!(
+                        "[resume] Warning: failed to save provider health state: {}",
+                        e
+                    );
+                }
+
+                if !json && (was_paused || !paused_providers.is_empty()) {
+                    if was_paused {
+                        println!("Cleared service pause due to provider failures");
+                    }
+                    if !paused_providers.is_empty() {
+                        println!("Resumed providers: {}", paused_providers.join(", "));
+                    }
+                }
+            }
+            Err(e) => {
                 eprintln!(
-                    "[resume] Warning: failed to save provider health state: {}",
+                    "[resume] Warning: failed to load provider health state: {}",
                     e
                 );
             }
-
-            if !json && (was_paused || !paused_providers.is_empty()) {
-                if was_paused {
-                    println!("Cleared service pause due to provider failures");
-                }
-                if !paused_providers.is_empty() {
-                    println!("Resumed providers: {}", paused_providers.join(", "));
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!(
-                "[resume] Warning: failed to load provider health state: {}",
-                e
-            );
         }
     }
 
-    let response = send_request(dir, &IpcRequest::Resume)?;
+    let response = send_request(
+        dir,
+        &IpcRequest::Resume {
+            tags: tags.to_vec(),
+            queues: queues.to_vec(),
+        },
+    )?;
 
     if !response.ok {
         let msg = response
@@ -3477,6 +4217,8 @@ pub fn run_resume(dir: &Path, json: bool) -> Result<()> {
         if let Some(data) = &response.data {
             println!("{}", serde_json::to_string_pretty(data)?);
         }
+    } else if scoped {
+        println!("Released {}", describe_pause_scope(tags, queues));
     } else {
         println!("Coordinator resumed");
     }
@@ -3485,7 +4227,7 @@ pub fn run_resume(dir: &Path, json: bool) -> Result<()> {
 }
 
 #[cfg(not(unix))]
-pub fn run_resume(_dir: &Path, _json: bool) -> Result<()> {
+pub fn run_resume(_dir: &Path, _tags: &[String], _queues: &[String], _json: bool) -> Result<()> {
     anyhow::bail!("Service daemon is only supported on Unix systems")
 }
 
@@ -3604,6 +4346,52 @@ pub fn run_thaw(_dir: &Path, _json: bool) -> Result<()> {
     anyhow::bail!("Service daemon is only supported on Unix systems")
 }
 
+/// Dispatch an urgent task now, pausing the lowest-priority running agent if
+/// no slot is free (`wg claim --preempt`)
+#[cfg(unix)]
+pub fn run_preempt(dir: &Path, task_id: &str) -> Result<()> {
+    let response = send_request(
+        dir,
+        &IpcRequest::Preempt {
+            task_id: task_id.to_string(),
+        },
+    )?;
+
+    if !response.ok {
+        let msg = response
+            .error
+            .unwrap_or_else(|| "Unknown error".to_string());
+        anyhow::bail!("{}", msg);
+    }
+
+    let field = |key: &str| {
+        response
+            .data
+            .as_ref()
+            .and_then(|d| d.get(key))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    if field("status") == "preempted" {
+        println!(
+            "Paused '{}' (agent {}) to dispatch '{}'. It resumes when '{}' finishes.",
+            field("paused_task"),
+            field("agent_id"),
+            task_id,
+            task_id
+        );
+    } else {
+        println!("A slot is free; dispatching '{}' now.", task_id);
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn run_preempt(_dir: &Path, _task_id: &str) -> Result<()> {
+    anyhow::bail!("Service daemon is only supported on Unix systems")
+}
+
 /// Create a new coordinator session via IPC
 #[cfg(unix)]
 pub fn run_create_coordinator(
@@ -4104,13 +4892,9 @@ pub fn is_service_paused(dir: &Path) -> bool {
     CoordinatorState::load(dir).is_some_and(|c| c.paused)
 }
 
-/// Send an IPC request to the running service.
-///
-/// Retries transient connection failures (ECONNREFUSED, broken pipe) up to 2
-/// times with short exponential backoff (50ms, 100ms) before giving up.
-/// Distinguishes "daemon not running" from "daemon unreachable" in errors.
+/// Socket of the running service and the daemon PID.
 #[cfg(unix)]
-pub fn send_request(dir: &Path, request: &IpcRequest) -> Result<IpcResponse> {
+fn service_socket(dir: &Path) -> Result<(PathBuf, u32)> {
     let state = ServiceState::load(dir)?.ok_or_else(|| {
         anyhow::anyhow!("Service not running (no state file). Start it with 'wg service start'.")
     })?;
@@ -4133,6 +4917,17 @@ pub fn send_request(dir: &Path, request: &IpcRequest) -> Result<IpcResponse> {
             state.pid
         );
     }
+    Ok((socket, state.pid))
+}
+
+/// Send an IPC request to the running service.
+///
+/// Retries transient connection failures (ECONNREFUSED, broken pipe) up to 2
+/// times with short exponential backoff (50ms, 100ms) before giving up.
+/// Distinguishes "daemon not running" from "daemon unreachable" in errors.
+#[cfg(unix)]
+pub fn send_request(dir: &Path, request: &IpcRequest) -> Result<IpcResponse> {
+    let (socket, pid) = service_socket(dir)?;
 
     // Retry transient connection failures with short backoff.
     const MAX_RETRIES: u32 = 2;
@@ -4186,17 +4981,57 @@ pub fn send_request(dir: &Path, request: &IpcRequest) -> Result<IpcResponse> {
         "Could not connect to service at {:?} (PID {}, {} retries exhausted): {}. \
          The daemon may be overloaded — try again, or restart with 'wg service start --force'.",
         socket,
-        state.pid,
+        pid,
         MAX_RETRIES,
         err
     )
 }
 
+/// Send a streaming IPC request and pass every response on the connection
+/// to `on_response` until the service closes it. Used for requests that
+/// answer with a sequence of messages, like `follow_output`.
+#[cfg(unix)]
+pub fn stream_request(
+    dir: &Path,
+    request: &IpcRequest,
+    mut on_response: impl FnMut(IpcResponse) -> Result<()>,
+) -> Result<()> {
+    let (socket, _pid) = service_socket(dir)?;
+    let mut stream = UnixStream::connect(&socket)
+        .with_context(|| format!("Could not connect to service at {:?}", socket))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+
+    let json = serde_json::to_string(&request)?;
+    writeln!(stream, "{}", json)?;
+    stream.flush()?;
+
+    let reader = BufReader::new(&stream);
+    for line in reader.lines() {
+        let line = line.context("Failed to read response")?;
+        if line.is_empty() {
+            continue;
+        }
+        let response: IpcResponse =
+            serde_json::from_str(&line).context("Failed to parse response")?;
+        on_response(response)?;
+    }
+    Ok(())
+}
+
 #[cfg(not(unix))]
 pub fn send_request(_dir: &Path, _request: &IpcRequest) -> Result<IpcResponse> {
     anyhow::bail!("IPC is only supported on Unix systems")
 }
 
+#[cfg(not(unix))]
+pub fn stream_request(
+    _dir: &Path,
+    _request: &IpcRequest,
+    _on_response: impl FnMut(IpcResponse) -> Result<()>,
+) -> Result<()> {
+    anyhow::bail!("IPC is only supported on Unix systems")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4267,6 +5102,8 @@ mod tests {
             pid: 12345,
             socket_path: "/tmp/test.sock".to_string(),
             started_at: chrono::Utc::now().to_rfc3339(),
+            http: None,
+            grpc: None,
         };
 
         state.save(temp_dir.path()).unwrap();
@@ -4360,11 +5197,15 @@ mod tests {
                 .to_string_lossy()
                 .to_string(),
             started_at: chrono::Utc::now().to_rfc3339(),
+            http: None,
+            grpc: None,
         };
         state.save(dir).unwrap();
 
         // run_start should not start a new daemon
-        let result = run_start(dir, None, None, None, None, None, None, false, false, false);
+        let result = run_start(
+            dir, None, None, None, None, None, None, None, None, false, false, false, false,
+        );
         assert!(result.is_ok()); // returns Ok but prints "already running"
 
         // State should be unchanged (same PID)
@@ -4388,6 +5229,8 @@ mod tests {
                 .to_string_lossy()
                 .to_string(),
             started_at: chrono::Utc::now().to_rfc3339(),
+            http: None,
+            grpc: None,
         };
         state.save(dir).unwrap();
 
@@ -4426,6 +5269,8 @@ mod tests {
                 .to_string_lossy()
                 .to_string(),
             started_at: chrono::Utc::now().to_rfc3339(),
+            http: None,
+            grpc: None,
         };
         state.save(dir).unwrap();
 
@@ -0,0 +1,342 @@
+//! Task preemption for urgent work (`wg claim --preempt <task>`).
+//!
+//! When every agent slot is busy, the daemon freezes the agent running the
+//! lowest-priority task (SIGSTOP to its process tree), marks that agent
+//! `Frozen` with `preempted_by` set in the registry, and pauses the task so
+//! nothing else picks it up. The frozen agent no longer counts toward
+//! `max_agents`, so the next tick dispatches the urgent task into the freed
+//! slot. Once the urgent task reaches a terminal status, [`resume_finished`]
+//! thaws the agent and unpauses its task, unless the task was already paused
+//! before the preemption (`paused_task` in the registry records which).
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use std::collections::HashSet;
+use std::path::Path;
+
+use workgraph::graph::{LogEntry, Status, Task, WorkGraph, is_system_task};
+use workgraph::parser::{load_graph, modify_graph};
+use workgraph::query::ready_tasks;
+use workgraph::service::registry::{AgentEntry, AgentRegistry, AgentStatus};
+
+use crate::commands::{graph_path, is_process_alive};
+
+/// Outcome of a preemption request.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Preemption {
+    /// A slot is already free; the urgent task is dispatched without pausing anything.
+    SlotFree,
+    /// `agent_id` running `task_id` was frozen to make room.
+    Paused { agent_id: String, task_id: String },
+}
+
+/// Choose the agent to freeze: the running, non-system task with the lowest
+/// priority below the urgent task's, most recently started first (it has
+/// the least work to lose).
+fn pick_victim<'a>(
+    graph: &WorkGraph,
+    agents: impl Iterator<Item = &'a AgentEntry>,
+    urgent: &Task,
+) -> Option<&'a AgentEntry> {
+    agents
+        .filter(|a| a.is_alive() && a.preempted_by.is_none())
+        .filter_map(|a| {
+            let task = graph.get_task(&a.task_id)?;
+            (task.status == Status::InProgress
+                && !is_system_task(&task.id)
+                && task.priority < urgent.priority)
+                .then_some((a, task))
+        })
+        .min_by(|(_, x), (_, y)| {
+            x.priority
+                .cmp(&y.priority)
+                .then_with(|| y.started_at.cmp(&x.started_at))
+        })
+        .map(|(a, _)| a)
+}
+
+/// Make room for `urgent_id` when all `max_agents` slots are busy.
+pub(crate) fn preempt_for(dir: &Path, urgent_id: &str, max_agents: usize) -> Result<Preemption> {
+    let path = graph_path(dir);
+    let graph = load_graph(&path).context("Failed to load graph")?;
+    let urgent = graph.get_task_or_err(urgent_id)?;
+    if urgent.status == Status::InProgress || urgent.status.is_terminal() {
+        bail!("Task '{}' is already {}", urgent_id, urgent.status);
+    }
+    if !ready_tasks(&graph).iter().any(|t| t.id == urgent_id) {
+        bail!(
+            "Task '{}' is not ready (unfinished dependencies, paused, or scheduled later)",
+            urgent_id
+        );
+    }
+
+    let mut locked = AgentRegistry::load_locked(dir)?;
+    let alive = locked
+        .registry
+        .agents
+        .values()
+        .filter(|a| a.is_alive() && is_process_alive(a.pid))
+        .count();
+    if alive < max_agents {
+        return Ok(Preemption::SlotFree);
+    }
+
+    let victim = pick_victim(&graph, locked.registry.agents.values(), urgent)
+        .map(|a| (a.id.clone(), a.task_id.clone(), a.pid))
+        .with_context(|| {
+            format!(
+                "No running task has lower priority than '{}' (priority {})",
+                urgent_id, urgent.priority
+            )
+        })?;
+    let (agent_id, task_id, pid) = victim;
+
+    workgraph::service::suspend_process_tree(pid, true)
+        .with_context(|| format!("Failed to pause agent {} (PID {})", agent_id, pid))?;
+
+    let now = Utc::now().to_rfc3339();
+    let mut paused_here = false;
+    let saved = modify_graph(&path, |graph| {
+        if let Some(task) = graph.get_task_mut(&task_id) {
+            paused_here = !task.paused;
+            task.paused = true;
+            task.log.push(LogEntry {
+                timestamp: now.clone(),
+                actor: Some("coordinator".to_string()),
+                user: Some(workgraph::current_user()),
+                message: format!(
+                    "Preempted by '{}': agent {} paused until it finishes",
+                    urgent_id, agent_id
+                ),
+            });
+        }
+        if let Some(task) = graph.get_task_mut(urgent_id) {
+            task.log.push(LogEntry {
+                timestamp: now.clone(),
+                actor: Some("coordinator".to_string()),
+                user: Some(workgraph::current_user()),
+                message: format!("Preempting '{}' (agent {})", task_id, agent_id),
+            });
+        }
+        true
+    });
+    if let Err(e) = saved {
+        let _ = workgraph::service::suspend_process_tree(pid, false);
+        return Err(e).context("Failed to save graph");
+    }
+
+    if let Some(agent) = locked.get_agent_mut(&agent_id) {
+        agent.status = AgentStatus::Frozen;
+        agent.preempted_by = Some(urgent_id.to_string());
+        agent.paused_task = paused_here;
+    }
+    locked.save_ref()?;
+
+    Ok(Preemption::Paused { agent_id, task_id })
+}
+
+/// Urgent tasks that froze an agent and have not finished yet. The
+/// dispatcher spawns these ahead of other ready work.
+pub(crate) fn pending_targets(dir: &Path) -> HashSet<String> {
+    AgentRegistry::load_or_warn(dir)
+        .agents
+        .values()
+        .filter(|a| a.status == AgentStatus::Frozen)
+        .filter_map(|a| a.preempted_by.clone())
+        .collect()
+}
+
+/// Thaw agents whose urgent task has finished (or been deleted), and unpause
+/// the tasks the preemption paused. An agent that died while frozen has its
+/// task reopened.
+/// Returns the resumed task IDs.
+pub(crate) fn resume_finished(dir: &Path) -> Result<Vec<String>> {
+    let path = graph_path(dir);
+    let graph = load_graph(&path).context("Failed to load graph")?;
+    let mut locked = AgentRegistry::load_locked(dir)?;
+    let now = Utc::now().to_rfc3339();
+
+    // (agent, task, urgent, still running, unpause)
+    let mut resumed: Vec<(String, String, String, bool, bool)> = Vec::new();
+    for agent in locked.registry.agents.values_mut() {
+        let Some(urgent_id) = agent.preempted_by.clone() else {
+            continue;
+        };
+        if agent.status != AgentStatus::Frozen {
+            agent.preempted_by = None;
+            agent.paused_task = false;
+            continue;
+        }
+        if graph
+            .get_task(&urgent_id)
+            .is_some_and(|t| !t.status.is_terminal())
+        {
+            continue;
+        }
+        agent.preempted_by = None;
+        let unpause = std::mem::take(&mut agent.paused_task);
+        let running = is_process_alive(agent.pid)
+            && workgraph::service::suspend_process_tree(agent.pid, false).is_ok();
+        if running {
+            agent.status = AgentStatus::Working;
+            // The heartbeat went stale while frozen; don't let the reaper
+            // mistake the pause for a hang.
+            agent.last_heartbeat = now.clone();
+        } else {
+            agent.status = AgentStatus::Dead;
+            agent.completed_at.get_or_insert_with(|| now.clone());
+        }
+        resumed.push((
+            agent.id.clone(),
+            agent.task_id.clone(),
+            urgent_id,
+            running,
+            unpause,
+        ));
+    }
+    if resumed.is_empty() {
+        return Ok(Vec::new());
+    }
+    locked.save_ref()?;
+
+    modify_graph(&path, |graph| {
+        for (agent_id, task_id, urgent_id, running, unpause) in &resumed {
+            let Some(task) = graph.get_task_mut(task_id) else {
+                continue;
+            };
+            if *unpause {
+                task.paused = false;
+            }
+            let message = if *running {
+                format!("Resumed: '{}' finished (agent {})", urgent_id, agent_id)
+            } else {
+                if task.status == Status::InProgress {
+                    task.status = Status::Open;
+                    task.assigned = None;
+                }
+                format!(
+                    "Agent {} exited while preempted by '{}'; task reopened",
+                    agent_id, urgent_id
+                )
+            };
+            task.log.push(LogEntry {
+                timestamp: now.clone(),
+                actor: Some("coordinator".to_string()),
+                user: Some(workgraph::current_user()),
+                message,
+            });
+        }
+        true
+    })
+    .context("Failed to save graph")?;
+
+    Ok(resumed.into_iter().map(|(_, task, ..)| task).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workgraph::graph::Node;
+    use workgraph::test_helpers::make_task;
+
+    fn agent(id: &str, task_id: &str, status: AgentStatus) -> AgentEntry {
+        AgentEntry {
+            id: id.to_string(),
+            pid: 0,
+            task_id: task_id.to_string(),
+            executor: "claude".to_string(),
+            started_at: Utc::now().to_rfc3339(),
+            last_heartbeat: Utc::now().to_rfc3339(),
+            status,
+            output_file: String::new(),
+            model: None,
+            completed_at: None,
+            worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
+        }
+    }
+
+    fn running(id: &str, priority: u32, started_at: &str) -> Node {
+        let mut t = make_task(id, id);
+        t.status = Status::InProgress;
+        t.priority = priority;
+        t.started_at = Some(started_at.to_string());
+        Node::Task(t)
+    }
+
+    #[test]
+    fn test_pick_victim_lowest_priority_latest_start() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(running("normal", 10, "2026-03-02T08:00:00Z"));
+        graph.add_node(running("low-old", 5, "2026-03-02T08:00:00Z"));
+        graph.add_node(running("low-new", 5, "2026-03-02T09:00:00Z"));
+        graph.add_node(running(".evaluate-x", 0, "2026-03-02T09:00:00Z"));
+        let mut urgent = make_task("urgent", "Urgent");
+        urgent.priority = 50;
+
+        let agents = [
+            agent("agent-1", "normal", AgentStatus::Working),
+            agent("agent-2", "low-old", AgentStatus::Working),
+            agent("agent-3", "low-new", AgentStatus::Working),
+            agent("agent-4", ".evaluate-x", AgentStatus::Working),
+        ];
+        let victim = pick_victim(&graph, agents.iter(), &urgent).unwrap();
+        assert_eq!(victim.id, "agent-3");
+
+        // Nothing runs below a priority-5 urgent task.
+        urgent.priority = 5;
+        assert!(pick_victim(&graph, agents.iter(), &urgent).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resume_only_unpauses_what_preemption_paused() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path();
+        let mut graph = WorkGraph::new();
+        let mut urgent = make_task("urgent", "Urgent");
+        urgent.status = Status::Done;
+        graph.add_node(Node::Task(urgent));
+        for (id, paused) in [("held", true), ("preempted", false)] {
+            let Node::Task(mut t) = running(id, 5, "2026-03-02T08:00:00Z") else {
+                unreachable!()
+            };
+            t.paused = paused;
+            graph.add_node(Node::Task(t));
+        }
+        workgraph::parser::save_graph(&graph, graph_path(dir)).unwrap();
+
+        let mut registry = AgentRegistry::new();
+        for (id, task_id, paused_task) in
+            [("agent-1", "held", false), ("agent-2", "preempted", true)]
+        {
+            let mut a = agent(id, task_id, AgentStatus::Frozen);
+            // Not a live process: the thaw takes the "exited" path.
+            a.pid = 999_999_999;
+            a.preempted_by = Some("urgent".to_string());
+            a.paused_task = paused_task;
+            registry.agents.insert(id.to_string(), a);
+        }
+        registry.save(dir).unwrap();
+
+        let mut resumed = resume_finished(dir).unwrap();
+        resumed.sort();
+        assert_eq!(resumed, vec!["held", "preempted"]);
+        let graph = load_graph(graph_path(dir)).unwrap();
+        assert!(graph.get_task("held").unwrap().paused);
+        assert!(!graph.get_task("preempted").unwrap().paused);
+        let registry = AgentRegistry::load(dir).unwrap();
+        assert!(registry.agents.values().all(|a| !a.paused_task));
+    }
+
+    #[test]
+    fn test_pick_victim_skips_frozen_agents() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(running("low", 5, "2026-03-02T08:00:00Z"));
+        let mut urgent = make_task("urgent", "Urgent");
+        urgent.priority = 50;
+        let agents = [agent("agent-1", "low", AgentStatus::Frozen)];
+        assert!(pick_victim(&graph, agents.iter(), &urgent).is_none());
+    }
+}
@@ -1419,6 +1419,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
 
         assert!(
@@ -1444,6 +1446,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
 
         let reason = detect_dead_reason(&agent, DEFAULT_REAPER_GRACE_PERIOD_SECS, 60);
@@ -1473,6 +1477,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
 
         let reason = detect_dead_reason(&agent, 0, 60);
@@ -1501,6 +1507,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
 
         assert!(
@@ -1524,6 +1532,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
 
         // On Linux, verify_process_identity may detect PID reuse since our
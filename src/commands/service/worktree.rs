@@ -2304,6 +2304,8 @@ mod tests {
                 model: None,
                 completed_at: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
         registry.agents.insert(
@@ -2320,6 +2322,8 @@ mod tests {
                 model: None,
                 completed_at: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
         registry.save(&wg_dir).unwrap();
@@ -2396,6 +2400,8 @@ mod tests {
                 model: None,
                 completed_at: None,
                 worktree_path: worktree_path.map(|p| p.to_string_lossy().to_string()),
+                preempted_by: None,
+                paused_task: false,
             },
         );
         registry.save(wg_dir).unwrap();
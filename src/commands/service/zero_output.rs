@@ -628,6 +628,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
         // Dead agents should be ignored
         assert!(check_zero_output(&agent).is_none());
@@ -657,6 +659,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
         // Has content, so should return None
         assert!(check_zero_output(&agent).is_none());
@@ -686,6 +690,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
         // Too young, should return None
         assert!(check_zero_output(&agent).is_none());
@@ -715,6 +721,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
         // Old with zero output, should return Some
         let result = check_zero_output(&agent);
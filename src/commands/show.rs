@@ -5,19 +5,27 @@ use std::collections::HashMap;
 use std::path::Path;
 use workgraph::config::Config;
 use workgraph::graph::{
-    CycleConfig, FailureClass, LogEntry, LoopGuard, PRIORITY_DEFAULT, Priority, Status, Task,
-    TokenUsage, format_tokens, parse_token_usage_live,
+    ChangeSummary, CycleConfig, DependencyEdge, ExternalBlocker, FailureClass, LogEntry, LoopGuard,
+    Node, PRIORITY_DEFAULT, Priority, SelfReport, Status, Task, TaskLink, TokenUsage, WorkGraph,
+    format_tokens, parse_token_usage_live,
 };
 use workgraph::query::build_reverse_index;
+use workgraph::rubric::Rubric;
 use workgraph::service::AgentRegistry;
 
 use super::service::CoordinatorState;
 
+/// Changed files listed by the human-readable view; JSON has them all.
+const MAX_CHANGED_FILES: usize = 20;
+
 /// Blocker info with status
 #[derive(Debug, Serialize)]
 struct BlockerInfo {
     id: String,
     status: Status,
+    /// Lag / weight on the edge between this task and the blocker
+    #[serde(skip_serializing_if = "Option::is_none")]
+    edge: Option<DependencyEdge>,
 }
 
 fn is_zero(val: &u32) -> bool {
@@ -52,7 +60,13 @@ struct TaskDetails {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     deliverables: Vec<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    expects: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     artifacts: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    links: Vec<TaskLink>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    self_report: Option<SelfReport>,
     #[serde(skip_serializing_if = "Option::is_none")]
     exec: Option<String>,
     after: Vec<BlockerInfo>,
@@ -68,6 +82,12 @@ struct TaskDetails {
     #[serde(skip_serializing_if = "Option::is_none")]
     not_before: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    waiting_on: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_blocker: Option<ExternalBlocker>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     log: Vec<LogEntry>,
     #[serde(skip_serializing_if = "is_zero")]
     retry_count: u32,
@@ -101,6 +121,9 @@ struct TaskDetails {
     ready_after: Option<String>,
     #[serde(default, skip_serializing_if = "is_not_paused")]
     paused: bool,
+    /// Loaded from cold storage (`wg show --archived`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    archived: bool,
     #[serde(skip_serializing_if = "is_default_visibility")]
     visibility: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -109,6 +132,12 @@ struct TaskDetails {
     exec_mode: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     token_usage: Option<TokenUsage>,
+    /// Effective per-run spend limit: the task's own, or its tags' default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_cost: Option<f64>,
+    /// Repository changes recorded by `wg done`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    changes: Option<ChangeSummary>,
     #[serde(skip_serializing_if = "Option::is_none")]
     session_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -146,6 +175,9 @@ struct TaskDetails {
     /// resume in-place vs `wg retry --fresh`.
     #[serde(skip_serializing_if = "Option::is_none")]
     worktree_state: Option<WorktreeStateInfo>,
+    /// Rubrics that apply to the task, shown as a review checklist
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rubrics: Vec<Rubric>,
 }
 
 /// Snapshot of a task's worktree dir + branch.
@@ -387,12 +419,39 @@ fn gather_worktree_state(dir: &Path, task_id: &str) -> Option<WorktreeStateInfo>
 }
 
 pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
-    let (graph, _path) = super::load_workgraph(dir)?;
+    // Only the task and its blockers and dependents are read; an unknown ID
+    // falls back to the full graph for did-you-mean suggestions.
+    let mut graph = super::load_workgraph_subset(dir, |index| index.neighbourhood(id))?;
+    if graph.get_task(id).is_none() {
+        graph = super::load_workgraph(dir)?.0;
+    }
+    if graph.get_task(id).is_none() && workgraph::archive::find(dir, id)?.is_some() {
+        anyhow::bail!(
+            "Task '{}' is archived. Use `wg show {} --archived` to view it",
+            id,
+            id
+        );
+    }
+    show_task(dir, &graph, id, false, json)
+}
 
+/// Show a task from the archive (cold storage) instead of the live graph.
+pub fn run_archived(dir: &Path, id: &str, json: bool) -> Result<()> {
+    let (mut graph, _path) = super::load_workgraph(dir)?;
+    let task = workgraph::archive::find(dir, id)?
+        .ok_or_else(|| anyhow::anyhow!("Task '{}' not found in archive", id))?;
+    graph.remove_node(id);
+    graph.add_node(Node::Task(task));
+    // Dependencies of an archived task are usually archived too.
+    workgraph::archive::include_archived(&mut graph, dir)?;
+    show_task(dir, &graph, id, true, json)
+}
+
+fn show_task(dir: &Path, graph: &WorkGraph, id: &str, archived: bool, json: bool) -> Result<()> {
     let task = graph.get_task_or_err(id)?;
 
     // Build reverse index to find what this task blocks
-    let reverse_index = build_reverse_index(&graph);
+    let reverse_index = build_reverse_index(graph);
 
     // Get blocker info with statuses (supports cross-repo peer:task-id references)
     let after_info: Vec<BlockerInfo> = task
@@ -411,6 +470,7 @@ pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
                 BlockerInfo {
                     id: blocker_id.clone(),
                     status: remote.status,
+                    edge: task.edges.get(blocker_id).cloned(),
                 }
             } else {
                 let status = match graph.get_task(blocker_id) {
@@ -426,6 +486,7 @@ pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
                 BlockerInfo {
                     id: blocker_id.clone(),
                     status,
+                    edge: task.edges.get(blocker_id).cloned(),
                 }
             }
         })
@@ -438,13 +499,11 @@ pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
             dependents
                 .iter()
                 .map(|dep_id| {
-                    let status = graph
-                        .get_task(dep_id)
-                        .map(|t| t.status)
-                        .unwrap_or(Status::Open);
+                    let dependent = graph.get_task(dep_id);
                     BlockerInfo {
                         id: dep_id.clone(),
-                        status,
+                        status: dependent.map(|t| t.status).unwrap_or(Status::Open),
+                        edge: dependent.and_then(|t| t.edges.get(id).cloned()),
                     }
                 })
                 .collect()
@@ -514,7 +573,10 @@ pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
         skills: task.skills.clone(),
         inputs: task.inputs.clone(),
         deliverables: task.deliverables.clone(),
+        expects: task.expects.clone(),
         artifacts: task.artifacts.clone(),
+        links: task.links.clone(),
+        self_report: task.self_report.clone(),
         exec: task.exec.clone(),
         after: after_info,
         before: before_info,
@@ -523,6 +585,9 @@ pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
         completed_at: task.completed_at.clone(),
         last_interaction_at: task.last_interaction_at.clone(),
         not_before: task.not_before.clone(),
+        waiting_on: task.waiting_on.clone(),
+        external_blocker: task.external_blocker.clone(),
+        due: task.due.clone(),
         log: task.log.clone(),
         retry_count: task.retry_count,
         max_retries: task.max_retries,
@@ -540,10 +605,16 @@ pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
         cycle_config: task.cycle_config.clone(),
         ready_after: task.ready_after.clone(),
         paused: task.paused,
+        archived,
         visibility: task.visibility.clone(),
         context_scope: task.context_scope.clone(),
         exec_mode: task.exec_mode.clone(),
         token_usage,
+        max_cost: workgraph::config::resolve_cost_cap(
+            &Config::load_or_default(dir).cost_caps,
+            task,
+        ),
+        changes: task.changes.clone(),
         session_id: task.session_id.clone(),
         wait_condition: task.wait_condition.clone(),
         checkpoint: task.checkpoint.clone(),
@@ -561,6 +632,7 @@ pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
         meta_eval_attempts: task.meta_eval_attempts,
         evaluations,
         worktree_state: gather_worktree_state(dir, id),
+        rubrics: workgraph::rubric::for_task(dir, task),
     };
 
     if json {
@@ -578,7 +650,9 @@ pub fn run(dir: &Path, id: &str, json: bool) -> Result<()> {
 fn print_human_readable(details: &TaskDetails) {
     println!("Task: {}", details.id);
     println!("Title: {}", details.title);
-    if details.paused {
+    if details.archived {
+        println!("Status: {} (ARCHIVED)", details.status);
+    } else if details.paused {
         println!("Status: {} (PAUSED)", details.status);
     } else {
         println!("Status: {}", details.status);
@@ -702,6 +776,21 @@ fn print_human_readable(details: &TaskDetails) {
         }
     }
 
+    // Rubrics as a review checklist
+    if !details.rubrics.is_empty() {
+        println!();
+        println!("Rubrics:");
+        for rubric in &details.rubrics {
+            println!("  {} (passes at {:.2})", rubric.id, rubric.pass_threshold);
+            for c in &rubric.criteria {
+                println!(
+                    "    [ ] {} (weight {}): {}",
+                    c.name, c.weight, c.description
+                );
+            }
+        }
+    }
+
     // Rescue info (for tasks that were implicit-failed then eval-rescued)
     if details.rescued {
         println!("rescued: true  (↻ agent exited without wg done; eval approved output)");
@@ -737,6 +826,12 @@ fn print_human_readable(details: &TaskDetails) {
             AgentHardTimeout => "agent exceeded hard timeout — split task or raise timeout",
             AgentExitNonzero => "generic non-zero exit — inspect agent output for details",
             WrapperInternal => "wrapper-side issue — inspect the wrapper log (output.log)",
+            ToolPolicyViolation => {
+                "agent used a tool its role denies — see tool_violations.log in the agent output dir"
+            }
+            CostCapExceeded => {
+                "run spent past the task's max_cost — decompose it into smaller tasks or raise the cap"
+            }
         };
         println!("  hint: {}", hint);
     }
@@ -765,6 +860,20 @@ fn print_human_readable(details: &TaskDetails) {
         }
     }
 
+    if let Some(ref report) = details.self_report {
+        println!();
+        println!("Self-report:");
+        for line in report.approach.lines() {
+            println!("  {}", line);
+        }
+        for (i, step) in report.steps.iter().enumerate() {
+            println!("  {}. {}", i + 1, step);
+        }
+        for pitfall in &report.pitfalls {
+            println!("  Pitfall: {}", pitfall);
+        }
+    }
+
     println!();
 
     // Estimate section
@@ -800,6 +909,22 @@ fn print_human_readable(details: &TaskDetails) {
         println!("Deliverables: {}", details.deliverables.join(", "));
     }
 
+    // Input contracts
+    if !details.expects.is_empty() {
+        println!("Expects: {}", details.expects.join(", "));
+    }
+
+    // External references
+    if !details.links.is_empty() {
+        println!("Links:");
+        for link in &details.links {
+            match link.label {
+                Some(ref label) => println!("  [{}] {} — {}", link.kind, link.url, label),
+                None => println!("  [{}] {}", link.kind, link.url),
+            }
+        }
+    }
+
     println!();
 
     // After section
@@ -808,7 +933,12 @@ fn print_human_readable(details: &TaskDetails) {
         println!("  (none)");
     } else {
         for blocker in &details.after {
-            println!("  - {} ({})", blocker.id, blocker.status);
+            println!(
+                "  - {} ({}){}",
+                blocker.id,
+                blocker.status,
+                format_edge(blocker.edge.as_ref())
+            );
         }
     }
 
@@ -820,7 +950,12 @@ fn print_human_readable(details: &TaskDetails) {
         println!("  (none)");
     } else {
         for blocked in &details.before {
-            println!("  - {} ({})", blocked.id, blocked.status);
+            println!(
+                "  - {} ({}){}",
+                blocked.id,
+                blocked.status,
+                format_edge(blocked.edge.as_ref())
+            );
         }
     }
 
@@ -914,6 +1049,30 @@ fn print_human_readable(details: &TaskDetails) {
     if let Some(ref not_before) = details.not_before {
         println!("Not before: {}{}", not_before, format_countdown(not_before));
     }
+    if !details.waiting_on.is_empty() {
+        println!("Waiting on: {}", details.waiting_on.join(", "));
+    }
+    if let Some(ref blocker) = details.external_blocker {
+        println!(
+            "External blocker: {} (since {})",
+            blocker.reason, blocker.since
+        );
+        if let Some(ref contact) = blocker.contact {
+            println!("  Contact: {}", contact);
+        }
+        if let Some(ref expected) = blocker.expected {
+            println!("  Expected: {}{}", expected, format_countdown(expected));
+        }
+    }
+    if let Some(ref due) = details.due {
+        let overdue = due.parse::<DateTime<Utc>>().is_ok_and(|d| d <= Utc::now())
+            && !details.status.is_terminal();
+        if overdue {
+            println!("Due: {} (OVERDUE)", due);
+        } else {
+            println!("Due: {}{}", due, format_countdown(due));
+        }
+    }
     if let Some(ref ready_after) = details.ready_after {
         println!(
             "Ready after: {}{}",
@@ -946,6 +1105,27 @@ fn print_human_readable(details: &TaskDetails) {
             println!("Cost: ${:.2}", usage.cost_usd);
         }
     }
+    if let Some(cap) = details.max_cost {
+        if details.token_usage.is_none() {
+            println!();
+        }
+        println!("Max cost: ${:.2} per run", cap);
+    }
+
+    if let Some(ref changes) = details.changes {
+        println!();
+        println!(
+            "Changes: {} (since {})",
+            workgraph::changes::describe(changes),
+            &changes.base[..changes.base.len().min(7)]
+        );
+        for file in changes.files.iter().take(MAX_CHANGED_FILES) {
+            println!("  {}", file);
+        }
+        if changes.files.len() > MAX_CHANGED_FILES {
+            println!("  ... and {} more", changes.files.len() - MAX_CHANGED_FILES);
+        }
+    }
 
     // Evaluation data
     if let Some(wt) = &details.worktree_state {
@@ -1019,6 +1199,25 @@ fn format_evaluations(evals: &[EvalSummary]) -> Vec<String> {
     out
 }
 
+/// Format an edge's lag and weight as a suffix, e.g. " [lag 2d, +4h]".
+fn format_edge(edge: Option<&DependencyEdge>) -> String {
+    let Some(edge) = edge else {
+        return String::new();
+    };
+    let mut parts = Vec::new();
+    if let Some(ref lag) = edge.lag {
+        parts.push(format!("lag {}", lag));
+    }
+    if let Some(weight) = edge.weight {
+        parts.push(format!("+{}h", weight));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", parts.join(", "))
+    }
+}
+
 /// Format a timestamp as a countdown string if it's in the future, or "(elapsed)" if in the past.
 fn format_countdown(timestamp: &str) -> String {
     let Ok(ts) = timestamp.parse::<DateTime<Utc>>() else {
@@ -1291,18 +1490,25 @@ mod tests {
             skills: vec![],
             inputs: vec![],
             deliverables: vec![],
+            expects: vec![],
             artifacts: vec![],
+            links: vec![],
+            self_report: None,
             exec: None,
             after: vec![],
             before: vec![BlockerInfo {
                 id: "t2".to_string(),
                 status: Status::Open,
+                edge: None,
             }],
             created_at: Some("2026-01-20T15:35:50+00:00".to_string()),
             started_at: Some("2026-01-20T16:30:00+00:00".to_string()),
             completed_at: None,
             last_interaction_at: None,
             not_before: None,
+            waiting_on: vec![],
+            external_blocker: None,
+            due: None,
             log: vec![],
             retry_count: 0,
             max_retries: None,
@@ -1326,11 +1532,14 @@ mod tests {
             cycle_failure_restarts: 0,
             ready_after: None,
             paused: false,
+            archived: false,
             visibility: "internal".to_string(),
             context_scope: None,
             exec_mode: None,
             cycle_config: None,
             token_usage: None,
+            max_cost: None,
+            changes: None,
             session_id: None,
             wait_condition: None,
             checkpoint: None,
@@ -1348,6 +1557,7 @@ mod tests {
             meta_eval_attempts: 0,
             evaluations: vec![],
             worktree_state: None,
+            rubrics: Vec::new(),
         };
 
         let json = serde_json::to_string(&details).unwrap();
@@ -1391,6 +1601,23 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_run_archived_task() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph.jsonl");
+        workgraph::parser::save_graph(&WorkGraph::new(), &path).unwrap();
+        let mut task = make_task("old", "Archived task");
+        task.status = Status::Done;
+        task.completed_at = Some("2025-02-03T00:00:00Z".to_string());
+        workgraph::archive::append(temp_dir.path(), &[task]).unwrap();
+
+        let err = run(temp_dir.path(), "old", false).unwrap_err();
+        assert!(err.to_string().contains("--archived"));
+        run_archived(temp_dir.path(), "old", false).unwrap();
+        run_archived(temp_dir.path(), "old", true).unwrap();
+        assert!(run_archived(temp_dir.path(), "missing", false).is_err());
+    }
+
     #[test]
     fn test_run_basic_task() {
         let temp_dir = tempfile::TempDir::new().unwrap();
@@ -1436,6 +1663,8 @@ mod tests {
                 model: Some("openrouter/minimax".to_string()),
                 completed_at: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
         registry.save(temp_dir.path()).unwrap();
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
 use serde::Serialize;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
+use workgraph::agency::{self, Agent, Role, RoleComponent};
+use workgraph::graph::{Task, WorkGraph, is_system_task};
+use workgraph::query::build_reverse_index;
 
 /// Embedded SKILL.md content - baked into binary at compile time
 const SKILL_CONTENT: &str = include_str!("../../.claude/skills/wg/SKILL.md");
@@ -116,6 +119,223 @@ pub fn run_find(dir: &Path, skill: &str, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Hours assumed for a task without an estimate when sizing demand
+const DEFAULT_TASK_HOURS: f64 = 2.0;
+
+/// Supply and demand for one skill across unfinished work
+#[derive(Debug, Serialize)]
+struct SkillGap {
+    skill: String,
+    /// Unfinished tasks requiring the skill
+    tasks: Vec<String>,
+    demand_hours: f64,
+    /// Unfinished tasks downstream of those tasks, which wait on them
+    downstream: usize,
+    /// Agents offering the skill (via capabilities or their role)
+    agents: Vec<String>,
+    /// Weekly hours of agents offering the skill that have a capacity set
+    capacity_per_week: f64,
+    /// Demand not covered by capacity over the horizon
+    shortfall_hours: f64,
+    /// Roles whose components cover the skill
+    roles: Vec<String>,
+    suggestion: String,
+}
+
+/// Skills a role offers: the names and domains of its components
+fn role_skills(role: &Role, components: &HashMap<&str, &RoleComponent>) -> HashSet<String> {
+    role.component_ids
+        .iter()
+        .filter_map(|id| components.get(id.as_str()))
+        .flat_map(|c| std::iter::once(&c.name).chain(&c.domain))
+        .map(|s| s.to_lowercase())
+        .collect()
+}
+
+/// Compare skills demanded by unfinished tasks against skills offered by
+/// agents and roles. Returns only skills with a shortfall, the ones gating
+/// the most work first.
+fn compute_gaps(
+    graph: &WorkGraph,
+    agents: &[Agent],
+    roles: &[Role],
+    components: &[RoleComponent],
+    weeks: usize,
+) -> Vec<SkillGap> {
+    let components: HashMap<&str, &RoleComponent> =
+        components.iter().map(|c| (c.id.as_str(), c)).collect();
+    let roles: Vec<(&Role, HashSet<String>)> = roles
+        .iter()
+        .map(|r| (r, role_skills(r, &components)))
+        .collect();
+    let role_skill_map: HashMap<&str, &HashSet<String>> =
+        roles.iter().map(|(r, s)| (r.id.as_str(), s)).collect();
+
+    let open: Vec<&Task> = graph
+        .tasks()
+        .filter(|t| !t.status.is_terminal() && !is_system_task(&t.id))
+        .collect();
+    let mut demand: BTreeMap<String, Vec<&Task>> = BTreeMap::new();
+    for task in &open {
+        for skill in &task.skills {
+            demand.entry(skill.to_lowercase()).or_default().push(task);
+        }
+    }
+
+    let reverse = build_reverse_index(graph);
+    let open_ids: HashSet<&str> = open.iter().map(|t| t.id.as_str()).collect();
+
+    let mut gaps: Vec<SkillGap> = demand
+        .into_iter()
+        .filter_map(|(skill, tasks)| {
+            let demand_hours: f64 = tasks
+                .iter()
+                .map(|t| {
+                    t.estimate
+                        .as_ref()
+                        .and_then(|e| e.hours)
+                        .unwrap_or(DEFAULT_TASK_HOURS)
+                })
+                .sum();
+
+            // Transitive unfinished dependents outside the skill's own tasks
+            let own: HashSet<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+            let mut downstream: HashSet<&str> = HashSet::new();
+            let mut stack: Vec<&str> = own.iter().copied().collect();
+            while let Some(id) = stack.pop() {
+                for dep in reverse.get(id).into_iter().flatten() {
+                    if open_ids.contains(dep.as_str())
+                        && !own.contains(dep.as_str())
+                        && downstream.insert(dep.as_str())
+                    {
+                        stack.push(dep.as_str());
+                    }
+                }
+            }
+
+            let offering: Vec<&Agent> = agents
+                .iter()
+                .filter(|a| {
+                    a.capabilities.iter().any(|c| c.eq_ignore_ascii_case(&skill))
+                        || role_skill_map
+                            .get(a.role_id.as_str())
+                            .is_some_and(|s| s.contains(&skill))
+                })
+                .collect();
+            let capacity_per_week: f64 = offering.iter().filter_map(|a| a.capacity).sum();
+            let measured = offering.iter().any(|a| a.capacity.is_some());
+            let shortfall_hours = if offering.is_empty() {
+                demand_hours
+            } else if measured {
+                (demand_hours - capacity_per_week * weeks as f64).max(0.0)
+            } else {
+                0.0
+            };
+            if shortfall_hours <= 0.0 {
+                return None;
+            }
+
+            let covering_roles: Vec<&Role> = roles
+                .iter()
+                .filter(|(_, s)| s.contains(&skill))
+                .map(|(r, _)| *r)
+                .collect();
+            let suggestion = if let Some(role) = covering_roles.first()
+                && offering.is_empty()
+            {
+                format!(
+                    "create an agent with role '{}' ({})",
+                    role.name,
+                    agency::short_hash(&role.id)
+                )
+            } else if offering.is_empty() {
+                format!(
+                    "define a role with a '{}' component, or add an agent/executor with that capability",
+                    skill
+                )
+            } else {
+                let per_agent = capacity_per_week / offering.len() as f64 * weeks as f64;
+                let more = if per_agent > 0.0 {
+                    (shortfall_hours / per_agent).ceil() as usize
+                } else {
+                    1
+                };
+                format!(
+                    "add {} more agent(s) like '{}' or raise capacity by {:.0}h/week",
+                    more,
+                    offering[0].name,
+                    shortfall_hours / weeks.max(1) as f64
+                )
+            };
+
+            Some(SkillGap {
+                skill,
+                tasks: tasks.iter().map(|t| t.id.clone()).collect(),
+                demand_hours,
+                downstream: downstream.len(),
+                agents: offering.iter().map(|a| a.name.clone()).collect(),
+                capacity_per_week,
+                shortfall_hours,
+                roles: covering_roles.iter().map(|r| r.name.clone()).collect(),
+                suggestion,
+            })
+        })
+        .collect();
+
+    // Most work gated first: the skill's own tasks plus everything waiting on them
+    gaps.sort_by(|a, b| {
+        (b.tasks.len() + b.downstream)
+            .cmp(&(a.tasks.len() + a.downstream))
+            .then_with(|| b.shortfall_hours.total_cmp(&a.shortfall_hours))
+            .then_with(|| a.skill.cmp(&b.skill))
+    });
+    gaps
+}
+
+/// Show skills that unfinished work demands but agents and roles don't
+/// cover, or cover with too little capacity over the next `weeks`
+pub fn run_gaps(dir: &Path, weeks: usize, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+
+    let agency_dir = dir.join("agency");
+    let agents = agency::load_all_agents_or_warn(&agency_dir.join("cache/agents"));
+    let roles = agency::load_all_roles(&agency_dir.join("cache/roles")).unwrap_or_default();
+    let components =
+        agency::load_all_components(&agency_dir.join("primitives/components")).unwrap_or_default();
+
+    let gaps = compute_gaps(&graph, &agents, &roles, &components, weeks);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&gaps)?);
+    } else if gaps.is_empty() {
+        println!("No skill gaps: every skill in unfinished work is covered");
+    } else {
+        println!("Skill gaps (next {} weeks):\n", weeks);
+        for gap in &gaps {
+            println!(
+                "  {} — {} tasks ({:.0}h), {} downstream",
+                gap.skill,
+                gap.tasks.len(),
+                gap.demand_hours,
+                gap.downstream
+            );
+            if gap.agents.is_empty() {
+                println!("    Offered by: (no agents)");
+            } else {
+                println!(
+                    "    Offered by: {} ({:.0}h/week)",
+                    gap.agents.join(", "),
+                    gap.capacity_per_week
+                );
+            }
+            println!("    Shortfall: {:.0}h", gap.shortfall_hours);
+            println!("    Suggestion: {}", gap.suggestion);
+        }
+    }
+
+    Ok(())
+}
+
 /// Install the wg Claude Code skill to ~/.claude/skills/wg/
 pub fn run_install() -> Result<()> {
     let home = std::env::var("HOME").context("HOME environment variable not set")?;
@@ -293,4 +513,68 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         assert!(run_list(temp_dir.path(), false).is_err());
     }
+
+    fn gap_agent(name: &str, capabilities: &[&str], capacity: Option<f64>) -> Agent {
+        Agent {
+            id: format!("{}-id", name),
+            role_id: String::new(),
+            tradeoff_id: String::new(),
+            name: name.to_string(),
+            performance: workgraph::agency::PerformanceRecord::default(),
+            lineage: workgraph::agency::Lineage::default(),
+            capabilities: capabilities.iter().map(|s| s.to_string()).collect(),
+            rate: None,
+            capacity,
+            trust_level: workgraph::graph::TrustLevel::Provisional,
+            contact: None,
+            executor: "claude".to_string(),
+            preferred_model: None,
+            preferred_provider: None,
+            deployment_history: vec![],
+            attractor_weight: 0.5,
+            staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
+        }
+    }
+
+    #[test]
+    fn test_gaps_rank_uncovered_skills_by_gated_work() {
+        let mut graph = WorkGraph::new();
+        let mut ml = make_task("ml", "Train model");
+        ml.skills = vec!["ml".to_string()];
+        let mut after_ml = make_task("ship", "Ship it");
+        after_ml.after = vec!["ml".to_string()];
+        let mut rust = make_task("rs", "Rust work");
+        rust.skills = vec!["rust".to_string()];
+        let mut docs = make_task("docs", "Docs");
+        docs.skills = vec!["docs".to_string()];
+        let mut done = make_task("old", "Old ML");
+        done.skills = vec!["ml".to_string()];
+        done.status = workgraph::graph::Status::Done;
+        for t in [ml, after_ml, rust, docs, done] {
+            graph.add_node(Node::Task(t));
+        }
+
+        let agents = vec![
+            gap_agent("rusty", &["Rust"], Some(10.0)),
+            gap_agent("writer", &["docs"], None),
+        ];
+        let gaps = compute_gaps(&graph, &agents, &[], &[], 4);
+
+        // rust is covered by capacity; docs is covered by an agent with
+        // unmeasured capacity; ml has no agent at all.
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].skill, "ml");
+        assert_eq!(gaps[0].tasks, vec!["ml"]);
+        assert_eq!(gaps[0].downstream, 1);
+        assert_eq!(gaps[0].shortfall_hours, DEFAULT_TASK_HOURS);
+        assert!(gaps[0].suggestion.contains("define a role"));
+
+        let busy = vec![gap_agent("rusty", &["rust"], Some(0.25))];
+        let gaps = compute_gaps(&graph, &busy, &[], &[], 4);
+        let rust = gaps.iter().find(|g| g.skill == "rust").unwrap();
+        assert_eq!(rust.shortfall_hours, 1.0);
+        assert!(rust.suggestion.contains("1 more agent"));
+    }
 }
@@ -0,0 +1,143 @@
+//! `wg snapshot` — create, list, and restore named graph snapshots.
+
+use anyhow::Result;
+use std::path::Path;
+
+use workgraph::config::Config;
+use workgraph::snapshot;
+
+/// Take a snapshot of the current graph (and optionally agency/functions).
+pub fn run_create(
+    dir: &Path,
+    name: Option<&str>,
+    message: Option<&str>,
+    agency: bool,
+    functions: bool,
+    json: bool,
+) -> Result<()> {
+    let meta = snapshot::create(dir, name, message, agency, functions)?;
+
+    let config = Config::load_or_default(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "snapshot_create",
+        None,
+        None,
+        serde_json::json!({
+            "name": meta.name,
+            "message": meta.message,
+            "task_count": meta.task_count,
+        }),
+        config.log.rotation_threshold,
+    );
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&meta)?);
+    } else {
+        println!(
+            "Created snapshot {} ({} task(s))",
+            meta.name, meta.task_count
+        );
+        let extras: Vec<&str> = [("agency", meta.agency), ("functions", meta.functions)]
+            .iter()
+            .filter(|(_, on)| *on)
+            .map(|(n, _)| *n)
+            .collect();
+        if !extras.is_empty() {
+            println!("  Also captured: {}", extras.join(", "));
+        }
+    }
+    Ok(())
+}
+
+/// List all snapshots.
+pub fn run_list(dir: &Path, json: bool) -> Result<()> {
+    let metas = snapshot::list(dir)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&metas)?);
+        return Ok(());
+    }
+    if metas.is_empty() {
+        println!("No snapshots found.");
+        return Ok(());
+    }
+
+    println!("Snapshots:\n");
+    for meta in &metas {
+        println!(
+            "  {} ({}) — {} task(s)",
+            meta.name, meta.timestamp, meta.task_count
+        );
+        if let Some(ref message) = meta.message {
+            println!("    {}", message);
+        }
+    }
+    Ok(())
+}
+
+/// Restore a snapshot, taking a safety snapshot of the current state first.
+pub fn run_restore(dir: &Path, name: &str, json: bool) -> Result<()> {
+    let safety = snapshot::restore(dir, name)?;
+    super::notify_graph_changed(dir);
+
+    let config = Config::load_or_default(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "restore",
+        None,
+        None,
+        serde_json::json!({
+            "restored_from": name,
+            "safety_snapshot": safety.name,
+        }),
+        config.log.rotation_threshold,
+    );
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "restored_from": name,
+                "safety_snapshot": safety.name,
+            }))?
+        );
+    } else {
+        println!("Restored graph from snapshot {}", name);
+        println!("  Safety snapshot: {} (restore it to undo)", safety.name);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::parser::load_graph;
+    use workgraph::test_helpers::{make_task, setup_workgraph};
+
+    #[test]
+    fn test_create_restore_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("t1", "Keep")]);
+
+        run_create(&dir, Some("before"), Some("pre-edit"), false, false, false).unwrap();
+        setup_workgraph(&dir, vec![make_task("t2", "Mistake")]);
+        run_list(&dir, false).unwrap();
+        run_restore(&dir, "before", false).unwrap();
+
+        let graph = load_graph(dir.join("graph.jsonl")).unwrap();
+        assert!(graph.get_task("t1").is_some());
+        assert!(graph.get_task("t2").is_none());
+        assert_eq!(snapshot::list(&dir).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_list_empty() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        std::fs::create_dir_all(&dir).unwrap();
+        run_list(&dir, false).unwrap();
+        run_list(&dir, true).unwrap();
+    }
+}
@@ -0,0 +1,229 @@
+//! The environment an agent process starts in.
+//!
+//! Spawning builds an [`AgentEnv`], applies it to the executor command, and
+//! records it as `.wg/agents/<agent>/env.json` so `wg agent shell` can
+//! recreate the same environment for a human. Secrets never reach the file:
+//! endpoint API keys are stored as a reference to the endpoint and resolved
+//! again when the shell opens, and secret-looking variables, whether
+//! inherited from the daemon or set for the agent (e.g. `[executor.env]`),
+//! are recorded by name only. The file is readable by its owner alone.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use workgraph::config::Config;
+
+const ENV_FILE: &str = "env.json";
+
+/// Substrings that mark a variable as a secret.
+const SECRET_MARKERS: &[&str] = &["KEY", "TOKEN", "SECRET", "PASSWORD", "CREDENTIAL"];
+
+fn looks_secret(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    SECRET_MARKERS.iter().any(|m| upper.contains(m))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct AgentEnv {
+    /// Working directory (the task's worktree, or the executor's working_dir)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// Environment inherited from the spawning process, secrets removed
+    #[serde(default)]
+    pub inherited: BTreeMap<String, String>,
+    /// Variables that looked secret and were not recorded, inherited or set
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redacted: Vec<String>,
+    /// Variables set for the agent, in addition to the inherited ones
+    #[serde(default)]
+    pub vars: BTreeMap<String, String>,
+    /// Variables carrying an endpoint's API key, mapped to the endpoint name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub secrets: BTreeMap<String, String>,
+    #[serde(skip)]
+    secret_values: BTreeMap<String, String>,
+}
+
+impl AgentEnv {
+    /// Start from the current process environment.
+    pub fn inherit() -> Self {
+        let mut env = Self::default();
+        for (name, value) in std::env::vars() {
+            if looks_secret(&name) {
+                env.redacted.push(name);
+            } else {
+                env.inherited.insert(name, value);
+            }
+        }
+        env
+    }
+
+    /// Set a variable for the agent. Secret-looking ones are passed to the
+    /// agent but recorded by name only.
+    pub fn set(&mut self, name: &str, value: impl Into<String>) {
+        if !looks_secret(name) {
+            self.vars.insert(name.to_string(), value.into());
+            return;
+        }
+        if !self.redacted.iter().any(|n| n == name) {
+            self.redacted.push(name.to_string());
+        }
+        self.secret_values.insert(name.to_string(), value.into());
+    }
+
+    /// Set a variable to an endpoint's API key.
+    pub fn set_secret(&mut self, name: &str, endpoint: &str, value: &str) {
+        self.secrets.insert(name.to_string(), endpoint.to_string());
+        self.secret_values
+            .insert(name.to_string(), value.to_string());
+    }
+
+    /// The value a variable will have, if the agent sets or inherits it.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars
+            .get(name)
+            .or_else(|| self.inherited.get(name))
+            .map(String::as_str)
+    }
+
+    /// Apply the agent's own variables and working directory to `cmd`,
+    /// which inherits everything else from this process.
+    pub fn apply(&self, cmd: &mut Command) {
+        cmd.envs(&self.vars);
+        cmd.envs(&self.secret_values);
+        if let Some(ref cwd) = self.cwd {
+            cmd.current_dir(cwd);
+        }
+    }
+
+    /// Apply the whole recorded environment to `cmd`, replacing this
+    /// process's. Redacted variables are taken from this process when it has
+    /// them; the names that are still missing are returned.
+    pub fn apply_isolated(&self, cmd: &mut Command) -> Vec<String> {
+        cmd.env_clear();
+        cmd.envs(&self.inherited);
+        let mut missing = Vec::new();
+        for name in &self.redacted {
+            match std::env::var(name) {
+                Ok(value) => {
+                    cmd.env(name, value);
+                }
+                Err(_) => missing.push(name.clone()),
+            }
+        }
+        self.apply(cmd);
+        missing
+    }
+
+    /// Look up the API keys behind `secrets` again.
+    pub fn resolve_secrets(&mut self, dir: &Path) -> Result<()> {
+        let config = Config::load_or_default(dir);
+        for (name, endpoint) in &self.secrets {
+            let ep = config
+                .llm_endpoints
+                .find_by_name(endpoint)
+                .with_context(|| {
+                    format!("Endpoint '{}' (for ${}) is not configured", endpoint, name)
+                })?;
+            let key = ep
+                .resolve_api_key(Some(dir))?
+                .with_context(|| format!("Endpoint '{}' has no API key", endpoint))?;
+            self.secret_values.insert(name.clone(), key);
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, agent_dir: &Path) -> Result<()> {
+        let path = agent_dir.join(ENV_FILE);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {:?}", path))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+
+    pub fn load(agent_dir: &Path) -> Result<Option<Self>> {
+        let path = agent_dir.join(ENV_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        let env = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {:?}", path))?;
+        Ok(Some(env))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn secrets_are_not_written_to_disk() {
+        let tmp = TempDir::new().unwrap();
+        let mut env = AgentEnv::default();
+        env.set("WG_TASK_ID", "t1");
+        env.set_secret("WG_API_KEY", "openrouter", "sk-live-123");
+        env.save(tmp.path()).unwrap();
+
+        let raw = fs::read_to_string(tmp.path().join(ENV_FILE)).unwrap();
+        assert!(!raw.contains("sk-live-123"));
+
+        let loaded = AgentEnv::load(tmp.path()).unwrap().unwrap();
+        assert_eq!(loaded.get("WG_TASK_ID"), Some("t1"));
+        assert_eq!(
+            loaded.secrets.get("WG_API_KEY").map(String::as_str),
+            Some("openrouter")
+        );
+    }
+
+    #[test]
+    fn secret_looking_vars_are_recorded_by_name() {
+        let tmp = TempDir::new().unwrap();
+        let mut env = AgentEnv::default();
+        // As set from `[executor.env] FOO_TOKEN = "..."`
+        env.set("FOO_TOKEN", "tok-abc");
+        env.set("FOO_MODE", "fast");
+        env.save(tmp.path()).unwrap();
+
+        let path = tmp.path().join(ENV_FILE);
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("tok-abc"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // The agent still gets the value
+        let mut cmd = Command::new("true");
+        env.apply(&mut cmd);
+        let value = cmd
+            .get_envs()
+            .find(|(k, _)| *k == "FOO_TOKEN")
+            .and_then(|(_, v)| v);
+        assert_eq!(value, Some(std::ffi::OsStr::new("tok-abc")));
+
+        let loaded = AgentEnv::load(tmp.path()).unwrap().unwrap();
+        assert_eq!(loaded.redacted, vec!["FOO_TOKEN"]);
+        assert_eq!(loaded.get("FOO_MODE"), Some("fast"));
+        assert_eq!(loaded.get("FOO_TOKEN"), None);
+    }
+
+    #[test]
+    fn secret_looking_names() {
+        assert!(looks_secret("ANTHROPIC_API_KEY"));
+        assert!(looks_secret("github_token"));
+        assert!(!looks_secret("PATH"));
+    }
+}
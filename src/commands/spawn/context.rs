@@ -197,6 +197,13 @@ pub(crate) fn build_scope_context(
         ctx.telegram_available = is_telegram_configured(workgraph_dir);
     }
 
+    // Task+ scope: onboarding brief, ranked by this task's skills
+    if scope >= ContextScope::Task && config.agent.onboarding_brief {
+        let brief =
+            crate::commands::onboard::build_brief(workgraph_dir, graph, config, &task.skills, 5);
+        ctx.onboarding_brief = crate::commands::onboard::render_markdown(&brief);
+    }
+
     ctx
 }
 
@@ -382,6 +389,10 @@ fn inline_artifact_content(artifacts: &[String], workgraph_dir: &Path) -> String
 
     let mut lines = Vec::new();
     for artifact in artifacts {
+        if artifact.starts_with(super::worktree::BRANCH_ARTIFACT_PREFIX) {
+            lines.push(format!("  {} (git branch)", artifact));
+            continue;
+        }
         let path = project_root.join(artifact);
         match fs::metadata(&path) {
             Ok(meta) => {
@@ -1103,6 +1114,24 @@ pub(crate) fn resolve_task_exec_mode(
     "full".to_string()
 }
 
+/// Resolve the tool policy for a task from its agent's role. Tasks without
+/// an agent (or whose role can't be found) get an empty policy.
+pub(crate) fn resolve_task_tool_policy(
+    task: &workgraph::graph::Task,
+    workgraph_dir: &Path,
+) -> workgraph::agency::ToolPolicy {
+    let Some(ref agent_hash) = task.agent else {
+        return Default::default();
+    };
+    let agency_dir = workgraph_dir.join("agency");
+    let agents_dir = agency_dir.join("cache/agents");
+    let roles_dir = agency_dir.join("cache/roles");
+    workgraph::agency::find_agent_by_prefix(&agents_dir, agent_hash)
+        .and_then(|agent| workgraph::agency::find_role_by_prefix(&roles_dir, &agent.role_id))
+        .map(|role| role.tool_policy)
+        .unwrap_or_default()
+}
+
 /// Resolve the context scope for a task using the priority hierarchy:
 /// task > role > coordinator config > default ("task").
 pub(crate) fn resolve_task_scope(
@@ -2112,6 +2141,25 @@ mod tests {
         assert!(truncated.starts_with("... (truncated)"));
     }
 
+    #[test]
+    fn test_inline_artifact_content_branch_artifact() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let wg_dir = temp_dir.path().join(".wg");
+        std::fs::create_dir_all(&wg_dir).unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "hello").unwrap();
+
+        let content = inline_artifact_content(
+            &[
+                "notes.txt".to_string(),
+                "git-branch:wg/agent-3/fix-login".to_string(),
+            ],
+            &wg_dir,
+        );
+        assert!(content.contains("notes.txt (5 bytes)"));
+        assert!(content.contains("git-branch:wg/agent-3/fix-login (git branch)"));
+        assert!(!content.contains("not found"));
+    }
+
     #[test]
     fn test_format_previous_context_structure() {
         let result = format_previous_context("2026-03-07T10:00:00Z", "Some work done", 8000);
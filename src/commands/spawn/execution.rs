@@ -4,20 +4,28 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 use workgraph::agency;
+use workgraph::changes;
 use workgraph::config::{CapBehavior, Config, EndpointConfig};
+use workgraph::context_scope::ContextScope;
 use workgraph::dispatch::plan_spawn;
 use workgraph::graph::{LogEntry, Node, Status, Task, is_system_task};
 use workgraph::parser::{load_graph, modify_graph};
-use workgraph::service::executor::{ExecutorRegistry, PromptTemplate, TemplateVars, build_prompt};
+use workgraph::service::dispatch_queue::{self, PendingDispatch};
+use workgraph::service::executor::{
+    ExecutorRegistry, PromptTemplate, ScopeContext, SystemFacts, TemplateVars, build_prompt,
+};
 use workgraph::service::registry::AgentRegistry;
+use workgraph::size_guard::{self, Truncation};
 
+use super::agent_env::AgentEnv;
 use super::context::{
     build_previous_attempt_context, build_scope_context, build_task_context, discover_test_files,
     format_test_discovery_context, resolve_task_exec_mode, resolve_task_scope,
+    resolve_task_tool_policy,
 };
 use super::worktree;
 use super::{
@@ -170,6 +178,7 @@ pub(crate) fn spawn_agent_inner(
 
     // Create template variables
     let mut vars = TemplateVars::from_task(task, Some(&task_context), Some(dir));
+    vars.facts.related_done = SystemFacts::count_related_done(&graph, task);
 
     // Detect failed dependencies for triage mode
     let mut failed_deps_lines = Vec::new();
@@ -228,6 +237,14 @@ pub(crate) fn spawn_agent_inner(
                 }
             }
         }
+        // Adjustment from the coordinator learning loop (poor past runs)
+        if let Some((model, adj)) = workgraph::learning::model_for_task(dir, &config, task) {
+            eprintln!(
+                "[spawn] learning loop: {} on {} ({}: {})",
+                task_id, model, adj.key, adj.reason
+            );
+            return Some(model);
+        }
         workgraph::config::resolve_tag_routing(&config.tag_routing, &task.tags)
             .map(|rule| rule.model.clone())
     });
@@ -235,6 +252,8 @@ pub(crate) fn spawn_agent_inner(
     let resume_session_id = task.session_id.clone();
     // Resolve exec_mode: task.exec_mode > role.default_exec_mode > "full"
     let resolved_exec_mode = resolve_task_exec_mode(task, dir);
+    // Role-scoped tool permissions (empty when the role declares none)
+    let tool_policy = resolve_task_tool_policy(task, dir);
     // Load executor config using the registry
     let executor_registry = ExecutorRegistry::new(dir);
     let executor_config = executor_registry.load_config(executor_name)?;
@@ -244,6 +263,22 @@ pub(crate) fn spawn_agent_inner(
         anyhow::bail!("Task '{}' has no exec command for shell executor", task_id);
     }
 
+    // A shell task whose own command is denied by its role fails up front;
+    // commands it runs indirectly are caught by the PATH shims below.
+    if executor_config.executor.executor_type == "shell"
+        && let Some(ref exec) = task_exec
+        && let Some(denied) = tool_policy.check_command(exec)
+    {
+        let reason = format!("Role tool policy violation: exec runs '{}'", denied);
+        crate::commands::fail::run(
+            dir,
+            task_id,
+            Some(&reason),
+            Some(workgraph::graph::FailureClass::ToolPolicyViolation),
+        )?;
+        anyhow::bail!("Task '{}' not spawned: {}", task_id, reason);
+    }
+
     // --- Unified model + provider resolution ---
     // Resolves model and provider in a single pass through the precedence hierarchy.
     // At each tier, if the model uses `provider:model` format, the provider is
@@ -319,6 +354,7 @@ pub(crate) fn spawn_agent_inner(
 
     let output_file = output_dir.join("output.log");
     let output_file_str = output_file.to_string_lossy().to_string();
+    let tool_shim_dir = write_tool_policy_shims(&output_dir, &tool_policy)?;
 
     // --- Worktree isolation ---
     // See `should_create_worktree` for the gating rules.
@@ -409,8 +445,13 @@ pub(crate) fn spawn_agent_inner(
     // Scope-based prompt assembly for built-in executors.
     // When no custom prompt_template is defined (built-in defaults),
     // use build_prompt() to assemble the prompt based on context scope.
+    let mut prompt_size_warning: Option<String> = None;
     if settings.prompt_template.is_none() && executor_uses_auto_prompt(&settings.executor_type) {
-        let prompt = build_prompt(&vars, scope, &scope_ctx);
+        let (prompt, warning) = fit_prompt_to_budget(&config, &mut vars, scope, &scope_ctx);
+        if let Some(ref w) = warning {
+            eprintln!("[spawn] Warning: {} (task {})", w, task_id);
+        }
+        prompt_size_warning = warning;
 
         // Debug logging: capture spawn metadata if WG_DEBUG_PROMPTS is set
         if std::env::var("WG_DEBUG_PROMPTS").is_ok()
@@ -444,6 +485,14 @@ pub(crate) fn spawn_agent_inner(
         settings.prompt_template = Some(PromptTemplate { template: prompt });
     }
 
+    // Prompt-level statement of the role's tool policy. Enforcement happens
+    // in the executor flags and PATH shims; this tells the agent up front.
+    if !tool_policy.is_empty()
+        && let Some(ref mut pt) = settings.prompt_template
+    {
+        pt.template.push_str(&tool_policy.prompt_section());
+    }
+
     // Use resolved exec_mode (already accounts for role defaults)
     let exec_mode = resolved_exec_mode.as_str();
 
@@ -504,6 +553,7 @@ pub(crate) fn spawn_agent_inner(
         &vars,
         &task_exec,
         resume_session_id.as_deref(),
+        &tool_policy,
     )?;
 
     // Resolve effective timeout: CLI param > task.timeout > executor config > coordinator config.
@@ -566,20 +616,36 @@ pub(crate) fn spawn_agent_inner(
     let mut cmd = Command::new("bash");
     cmd.arg(&wrapper_path);
 
+    // Build the agent's environment. It is recorded in env.json so
+    // `wg agent shell` can recreate it.
+    let mut env = AgentEnv::inherit();
+
     // Set environment variables from executor config
     for (key, value) in &settings.env {
-        cmd.env(key, value);
+        env.set(key, value.clone());
+    }
+    // Denied shell commands resolve to the policy shims first.
+    if let Some(ref shim_dir) = tool_shim_dir {
+        let base_path = env.get("PATH").unwrap_or_default().to_string();
+        env.set("PATH", format!("{}:{}", shim_dir.display(), base_path));
     }
 
+    // Pin the agent's and wrapper's `wg` calls to the graph that spawned
+    // them; otherwise they resolve from the cwd and reach the main graph
+    // even when this is a named one.
+    let abs_dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    env.set("WG_DIR", abs_dir.display().to_string());
+    env.set("WG_GRAPH", workgraph::graphs::current_name(&abs_dir));
+
     // Add task ID and agent ID to environment
-    cmd.env("WG_TASK_ID", task_id);
-    cmd.env("WG_AGENT_ID", &temp_agent_id);
-    cmd.env("WG_EXECUTOR_TYPE", &settings.executor_type);
+    env.set("WG_TASK_ID", task_id);
+    env.set("WG_AGENT_ID", temp_agent_id.clone());
+    env.set("WG_EXECUTOR_TYPE", settings.executor_type.clone());
     // Time budget: inject timeout and spawn epoch for graceful completion
     if let Some(secs) = effective_timeout_secs {
-        cmd.env("WG_TASK_TIMEOUT_SECS", secs.to_string());
+        env.set("WG_TASK_TIMEOUT_SECS", secs.to_string());
     }
-    cmd.env(
+    env.set(
         "WG_SPAWN_EPOCH",
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -588,9 +654,9 @@ pub(crate) fn spawn_agent_inner(
             .to_string(),
     );
     // Propagate user identity to spawned agents
-    cmd.env("WG_USER", workgraph::current_user());
+    env.set("WG_USER", workgraph::current_user());
     if let Some(ref m) = effective_model {
-        cmd.env("WG_MODEL", m);
+        env.set("WG_MODEL", m.clone());
     }
     {
         let tier_str =
@@ -601,42 +667,47 @@ pub(crate) fn spawn_agent_inner(
                     workgraph::config::Tier::Premium => "premium",
                 },
             );
-        cmd.env("WG_TIER", tier_str);
+        env.set("WG_TIER", tier_str);
     }
     if let Some(ref ep) = effective_endpoint {
-        cmd.env("WG_ENDPOINT", ep);
-        cmd.env("WG_ENDPOINT_NAME", ep);
+        env.set("WG_ENDPOINT", ep.clone());
+        env.set("WG_ENDPOINT_NAME", ep.clone());
     }
     if let Some(ref provider) = effective_provider {
-        cmd.env("WG_LLM_PROVIDER", provider);
+        env.set("WG_LLM_PROVIDER", provider.clone());
     }
     if let Some(ref url) = effective_endpoint_url {
-        cmd.env("WG_ENDPOINT_URL", url);
+        env.set("WG_ENDPOINT_URL", url.clone());
     }
-    if let Some(ref key) = effective_api_key {
-        cmd.env("WG_API_KEY", key);
+    if let Some(ref key) = effective_api_key
+        && let Some(ep) = endpoint_config
+    {
+        env.set_secret("WG_API_KEY", &ep.name, key);
         // Also set the provider-specific env var (e.g. OPENROUTER_API_KEY) so the
         // native executor and any child processes can find the key via standard env vars.
-        if let Some(ep) = endpoint_config {
-            for var_name in EndpointConfig::env_var_names_for_provider(&ep.provider) {
-                cmd.env(var_name, key);
-            }
+        for var_name in EndpointConfig::env_var_names_for_provider(&ep.provider) {
+            env.set_secret(var_name, &ep.name, key);
         }
     }
 
     // Set working directory: worktree overrides settings.working_dir
     if let Some(ref wt) = worktree_info {
-        cmd.current_dir(&wt.path);
-        cmd.env("WG_WORKTREE_PATH", &wt.path);
-        cmd.env("WG_BRANCH", &wt.branch);
-        cmd.env("WG_PROJECT_ROOT", &wt.project_root);
+        env.cwd = Some(wt.path.clone());
+        env.set("WG_WORKTREE_PATH", wt.path.to_string_lossy());
+        env.set("WG_BRANCH", wt.branch.clone());
+        env.set("WG_PROJECT_ROOT", wt.project_root.to_string_lossy());
         // Signal to Claude Code (and other tools) that this session is already
         // inside a managed worktree — do not create a competing one.
-        cmd.env("WG_WORKTREE_ACTIVE", "1");
+        env.set("WG_WORKTREE_ACTIVE", "1");
         // Isolate cargo target directory to prevent file lock contention between agents
-        cmd.env("CARGO_TARGET_DIR", wt.path.join("target"));
+        env.set("CARGO_TARGET_DIR", wt.path.join("target").to_string_lossy());
     } else if let Some(ref wd) = settings.working_dir {
-        cmd.current_dir(wd);
+        env.cwd = Some(PathBuf::from(wd));
+    }
+
+    env.apply(&mut cmd);
+    if let Err(e) = env.save(&output_dir) {
+        eprintln!("[spawn] Warning: failed to record agent environment: {}", e);
     }
 
     // Wrapper script handles output redirect internally
@@ -669,6 +740,27 @@ pub(crate) fn spawn_agent_inner(
     let temp_agent_id_clone = temp_agent_id.clone();
     let task_id_str = task_id.to_string();
     let model_validation_warning_clone = model_validation_warning.clone();
+    let worktree_branch = worktree_info.as_ref().map(|wt| wt.branch.clone());
+    // Diff base for the change summary `wg done` records.
+    let claim_sha = match worktree_info {
+        Some(ref wt) => changes::head_sha(&wt.path),
+        None => dir.parent().and_then(changes::head_sha),
+    };
+
+    // Record the dispatch before claiming, so a crash before the agent is
+    // registered leaves a trace the dispatcher can reconcile.
+    dispatch_queue::record(
+        dir,
+        PendingDispatch {
+            task_id: task_id.to_string(),
+            agent_id: temp_agent_id.clone(),
+            executor: executor_name.to_string(),
+            claimed_at: Utc::now().to_rfc3339(),
+            wrapper: wrapper_path.clone(),
+            pid: None,
+        },
+    )
+    .context("Failed to record pending dispatch")?;
 
     let mut claim_error: Option<anyhow::Error> = None;
     modify_graph(&graph_path, |graph| {
@@ -681,6 +773,7 @@ pub(crate) fn spawn_agent_inner(
         };
         task.status = Status::InProgress;
         task.started_at = Some(Utc::now().to_rfc3339());
+        task.claim_sha = claim_sha.clone();
         task.assigned = Some(temp_agent_id_clone.clone());
         task.log.push(LogEntry {
             timestamp: Utc::now().to_rfc3339(),
@@ -697,6 +790,16 @@ pub(crate) fn spawn_agent_inner(
             ),
         });
 
+        // Record the worktree branch so the task's work can be found (and
+        // reviewed or merged by hand) after the agent is gone. Retries that
+        // reuse the worktree keep a single entry.
+        if let Some(ref branch) = worktree_branch {
+            let artifact = format!("{}{}", worktree::BRANCH_ARTIFACT_PREFIX, branch);
+            if !task.artifacts.contains(&artifact) {
+                task.artifacts.push(artifact);
+            }
+        }
+
         // Log pre-flight model validation result
         if let Some(ref warning) = model_validation_warning_clone {
             task.log.push(LogEntry {
@@ -707,6 +810,15 @@ pub(crate) fn spawn_agent_inner(
             });
         }
 
+        if let Some(ref warning) = prompt_size_warning {
+            task.log.push(LogEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                actor: Some("spawn".to_string()),
+                user: None,
+                message: warning.clone(),
+            });
+        }
+
         // Create .assign-* audit trail if missing (defense-in-depth).
         let assign_task_id = format!(".assign-{}", task_id_str);
         if !is_system_task(&task_id_str) && graph.get_task(&assign_task_id).is_none() {
@@ -747,6 +859,7 @@ pub(crate) fn spawn_agent_inner(
     })
     .context("Failed to save graph")?;
     if let Some(e) = claim_error {
+        let _ = dispatch_queue::complete(dir, &temp_agent_id);
         return Err(e);
     }
 
@@ -778,6 +891,8 @@ pub(crate) fn spawn_agent_inner(
                     "Warning: failed to rollback graph for task '{}': {}",
                     task_id, rollback_err
                 );
+            } else {
+                let _ = dispatch_queue::complete(dir, &temp_agent_id);
             }
             // Worktrees are sacred — preserved even on spawn failure so the
             // user can inspect what was set up. Remove via `wg worktree archive --remove`.
@@ -797,6 +912,9 @@ pub(crate) fn spawn_agent_inner(
     };
 
     let pid = child.id();
+    if let Err(e) = dispatch_queue::mark_started(dir, &temp_agent_id, pid) {
+        eprintln!("Warning: failed to record PID of {}: {}", temp_agent_id, e);
+    }
 
     // Register the agent (with model tracking)
     let agent_id = locked_registry.register_agent_with_model(
@@ -826,8 +944,16 @@ pub(crate) fn spawn_agent_inner(
                 libc::kill(pid as i32, libc::SIGKILL);
             }
         }
+        // The pending dispatch stays queued: the dispatcher's next
+        // reconcile releases the claim.
         return Err(save_err.context("Failed to persist agent registry after spawn"));
     }
+    if let Err(e) = dispatch_queue::complete(dir, &temp_agent_id) {
+        eprintln!(
+            "Warning: failed to clear pending dispatch for {}: {}",
+            agent_id, e
+        );
+    }
 
     // Advance message cursor for this agent so queued messages aren't re-read.
     // The queued messages were already included in the prompt via ScopeContext.
@@ -897,6 +1023,81 @@ pub(crate) fn should_create_worktree(
     true
 }
 
+/// Render the prompt, cutting the dependency context when the estimate
+/// exceeds `guardrails.max_prompt_tokens`. Returns the prompt plus a warning
+/// describing the cut, if one was made.
+fn fit_prompt_to_budget(
+    config: &Config,
+    vars: &mut TemplateVars,
+    scope: ContextScope,
+    scope_ctx: &ScopeContext,
+) -> (String, Option<String>) {
+    let prompt = build_prompt(vars, scope, scope_ctx);
+    let budget = config.guardrails.max_prompt_tokens;
+    let tokens = size_guard::estimate_tokens(&prompt);
+    if budget == 0 || tokens <= budget {
+        return (prompt, None);
+    }
+
+    let strategy =
+        Truncation::parse(&config.guardrails.prompt_truncation).unwrap_or(Truncation::Head);
+    let overflow = (tokens - budget) * size_guard::BYTES_PER_TOKEN;
+    let keep = vars.task_context.len().saturating_sub(overflow);
+    let mut how = strategy.as_str().to_string();
+    vars.task_context = match strategy {
+        Truncation::Summary => {
+            let input_bytes = budget * size_guard::BYTES_PER_TOKEN;
+            match summarize_context(config, &vars.task_context, input_bytes, keep) {
+                Ok(summary) => size_guard::truncate_text(&summary, keep, Truncation::Head),
+                Err(e) => {
+                    how = format!("head and tail; summary failed: {}", e);
+                    size_guard::truncate_middle(&vars.task_context, keep)
+                }
+            }
+        }
+        _ => size_guard::truncate_text(&vars.task_context, keep, strategy),
+    };
+
+    let prompt = build_prompt(vars, scope, scope_ctx);
+    let after = size_guard::estimate_tokens(&prompt);
+    let mut warning = format!(
+        "Prompt size guard: ~{} tokens exceeds max_prompt_tokens={}; dependency context cut ({}), prompt now ~{} tokens",
+        tokens, budget, how, after
+    );
+    if after > budget {
+        warning.push_str(" (still over budget)");
+    }
+    (prompt, Some(warning))
+}
+
+/// How long a spawn waits on the summarizer before cutting instead.
+const SUMMARY_TIMEOUT_SECS: u64 = 60;
+
+/// Condense oversize dependency context with a one-shot LLM call. The
+/// summarizer sees at most `input_bytes` of it, with the middle dropped.
+fn summarize_context(
+    config: &Config,
+    context: &str,
+    input_bytes: usize,
+    max_bytes: usize,
+) -> Result<String> {
+    let context = size_guard::truncate_middle(context, input_bytes);
+    let prompt = format!(
+        "Condense the following task context to under {} words. Keep file paths, \
+         decisions, and open issues; drop repetition and boilerplate. \
+         Reply with the condensed context only.\n\n{}",
+        max_bytes / 6,
+        context
+    );
+    let result = workgraph::service::llm::run_lightweight_llm_call(
+        config,
+        workgraph::config::DispatchRole::Compactor,
+        &prompt,
+        SUMMARY_TIMEOUT_SECS,
+    )?;
+    Ok(result.text)
+}
+
 /// Built-in executors that ship without a `prompt_template` and rely on
 /// `build_prompt()` to assemble the agent prompt at spawn time.
 ///
@@ -907,7 +1108,7 @@ pub(crate) fn should_create_worktree(
 /// here means the spawn writes no prompt.txt and the resulting subprocess
 /// receives empty stdin — exactly the codex bug.
 fn executor_uses_auto_prompt(executor_type: &str) -> bool {
-    matches!(executor_type, "claude" | "codex" | "native")
+    matches!(executor_type, "claude" | "codex" | "native" | "webhook")
 }
 
 /// Build the inner command string for the executor.
@@ -928,6 +1129,7 @@ fn build_inner_command(
     vars: &TemplateVars,
     task_exec: &Option<String>,
     resume_session_id: Option<&str>,
+    tool_policy: &agency::ToolPolicy,
 ) -> Result<(String, Option<String>)> {
     let inner_command = match settings.executor_type.as_str() {
         "claude" if resume_session_id.is_some() && exec_mode != "bare" => {
@@ -941,8 +1143,11 @@ fn build_inner_command(
             cmd_parts.push("--output-format".to_string());
             cmd_parts.push("stream-json".to_string());
             cmd_parts.push("--dangerously-skip-permissions".to_string());
-            cmd_parts.push("--disallowedTools".to_string());
-            cmd_parts.push(shell_escape("Agent,EnterWorktree,ExitWorktree"));
+            push_claude_policy_flags(
+                &mut cmd_parts,
+                "Agent,EnterWorktree,ExitWorktree",
+                tool_policy,
+            );
             cmd_parts.push("--disable-slash-commands".to_string());
             if let Some(m) = effective_model {
                 cmd_parts.push("--model".to_string());
@@ -960,8 +1165,14 @@ fn build_inner_command(
             // Build a fresh-session fallback command (same as the full-mode
             // "claude" arm below) so the wrapper can retry if the session is
             // gone. Write prompt.txt alongside resume_message.txt.
-            let fallback =
-                build_claude_fresh_command(settings, exec_mode, output_dir, effective_model, vars)?;
+            let fallback = build_claude_fresh_command(
+                settings,
+                exec_mode,
+                output_dir,
+                effective_model,
+                vars,
+                tool_policy,
+            )?;
 
             return Ok((resume_command, Some(fallback)));
         }
@@ -1022,9 +1233,10 @@ fn build_inner_command(
             cmd_parts.push("--allowedTools".to_string());
             cmd_parts.push(shell_escape("Bash(wg:*),Read,Glob,Grep,WebFetch,WebSearch"));
             cmd_parts.push("--disallowedTools".to_string());
-            cmd_parts.push(shell_escape(
+            cmd_parts.push(shell_escape(&claude_tool_list(
                 "Edit,Write,NotebookEdit,Agent,EnterWorktree,ExitWorktree",
-            ));
+                &tool_policy.claude_denied_tools(),
+            )));
 
             cmd_parts.push("--disable-slash-commands".to_string());
             // Add model flag if specified
@@ -1052,8 +1264,11 @@ fn build_inner_command(
                 cmd_parts.push(shell_escape(arg));
             }
             // Prevent agents from spawning sub-agents outside WG
-            cmd_parts.push("--disallowedTools".to_string());
-            cmd_parts.push(shell_escape("Agent,EnterWorktree,ExitWorktree"));
+            push_claude_policy_flags(
+                &mut cmd_parts,
+                "Agent,EnterWorktree,ExitWorktree",
+                tool_policy,
+            );
 
             cmd_parts.push("--disable-slash-commands".to_string());
             // Add model flag if specified
@@ -1139,6 +1354,26 @@ fn build_inner_command(
             }
             cmd_parts.join(" ")
         }
+        "webhook" => {
+            // Webhook executor: `wg webhook-exec` POSTs the task (with the
+            // rendered prompt) to the configured URL and waits for a report.
+            let prompt_content = settings
+                .prompt_template
+                .as_ref()
+                .map(|pt| pt.template.clone())
+                .unwrap_or_default();
+            let prompt_file = output_dir.join("prompt.txt");
+            fs::write(&prompt_file, &prompt_content)
+                .with_context(|| format!("Failed to write prompt file: {:?}", prompt_file))?;
+
+            let mut cmd_parts = vec![shell_escape(&settings.command)];
+            cmd_parts.push("webhook-exec".to_string());
+            cmd_parts.push("--prompt-file".to_string());
+            cmd_parts.push(shell_escape(&prompt_file.to_string_lossy()));
+            cmd_parts.push("--task-id".to_string());
+            cmd_parts.push(shell_escape(&vars.task_id));
+            cmd_parts.join(" ")
+        }
         "shell" => {
             format!(
                 "{} -c {}",
@@ -1167,6 +1402,7 @@ fn build_claude_fresh_command(
     output_dir: &Path,
     effective_model: &Option<String>,
     _vars: &TemplateVars,
+    tool_policy: &agency::ToolPolicy,
 ) -> Result<String> {
     match exec_mode {
         "light" => {
@@ -1179,9 +1415,10 @@ fn build_claude_fresh_command(
             cmd_parts.push("--allowedTools".to_string());
             cmd_parts.push(shell_escape("Bash(wg:*),Read,Glob,Grep,WebFetch,WebSearch"));
             cmd_parts.push("--disallowedTools".to_string());
-            cmd_parts.push(shell_escape(
+            cmd_parts.push(shell_escape(&claude_tool_list(
                 "Edit,Write,NotebookEdit,Agent,EnterWorktree,ExitWorktree",
-            ));
+                &tool_policy.claude_denied_tools(),
+            )));
             cmd_parts.push("--disable-slash-commands".to_string());
             if let Some(m) = effective_model {
                 cmd_parts.push("--model".to_string());
@@ -1206,8 +1443,11 @@ fn build_claude_fresh_command(
             for arg in &settings.args {
                 cmd_parts.push(shell_escape(arg));
             }
-            cmd_parts.push("--disallowedTools".to_string());
-            cmd_parts.push(shell_escape("Agent,EnterWorktree,ExitWorktree"));
+            push_claude_policy_flags(
+                &mut cmd_parts,
+                "Agent,EnterWorktree,ExitWorktree",
+                tool_policy,
+            );
             cmd_parts.push("--disable-slash-commands".to_string());
             if let Some(m) = effective_model {
                 cmd_parts.push("--model".to_string());
@@ -1229,6 +1469,77 @@ fn build_claude_fresh_command(
     }
 }
 
+/// Join a built-in comma-separated Claude tool list with role-policy patterns,
+/// skipping duplicates.
+fn claude_tool_list(base: &str, extra: &[String]) -> String {
+    let mut parts: Vec<&str> = base.split(',').filter(|p| !p.is_empty()).collect();
+    for pattern in extra {
+        if !parts.contains(&pattern.as_str()) {
+            parts.push(pattern);
+        }
+    }
+    parts.join(",")
+}
+
+/// Push `--disallowedTools` (built-in denies plus the role's) and, when the
+/// role declares an allow list, `--allowedTools` for a full-mode Claude run.
+/// The wg CLI is always allowed so the agent can report progress.
+fn push_claude_policy_flags(
+    cmd_parts: &mut Vec<String>,
+    base_denied: &str,
+    tool_policy: &agency::ToolPolicy,
+) {
+    cmd_parts.push("--disallowedTools".to_string());
+    cmd_parts.push(shell_escape(&claude_tool_list(
+        base_denied,
+        &tool_policy.claude_denied_tools(),
+    )));
+    let allowed = tool_policy.claude_allowed_tools();
+    if !allowed.is_empty() {
+        cmd_parts.push("--allowedTools".to_string());
+        cmd_parts.push(shell_escape(&claude_tool_list("Bash(wg:*)", &allowed)));
+    }
+}
+
+/// Write PATH shims for every program named in the role's denied commands.
+///
+/// Returns the shim directory to prepend to `PATH`, or `None` when the
+/// policy denies no shell commands.
+fn write_tool_policy_shims(
+    output_dir: &Path,
+    tool_policy: &agency::ToolPolicy,
+) -> Result<Option<std::path::PathBuf>> {
+    let denied = tool_policy.denied_commands();
+    if denied.is_empty() {
+        return Ok(None);
+    }
+    let shim_dir = output_dir.join("tool-shims");
+    fs::create_dir_all(&shim_dir)
+        .with_context(|| format!("Failed to create tool shim directory: {:?}", shim_dir))?;
+    let violations_file = output_dir.join(agency::TOOL_VIOLATIONS_FILE);
+
+    let mut programs: Vec<&str> = denied.iter().map(|rule| rule[0].as_str()).collect();
+    programs.sort_unstable();
+    programs.dedup();
+    for program in programs {
+        let shim = agency::render_command_shim(
+            program,
+            &denied,
+            &shim_dir.to_string_lossy(),
+            &violations_file.to_string_lossy(),
+        );
+        let shim_path = shim_dir.join(program);
+        fs::write(&shim_path, shim)
+            .with_context(|| format!("Failed to write tool shim: {:?}", shim_path))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&shim_path, fs::Permissions::from_mode(0o755))?;
+        }
+    }
+    Ok(Some(shim_dir))
+}
+
 /// Create and write the wrapper shell script that runs the agent command
 /// and handles completion/failure.
 ///
@@ -1368,6 +1679,7 @@ fi
         r#"#!/bin/bash
 TASK_ID={escaped_task_id}
 OUTPUT_FILE={escaped_output_file}
+VIOLATIONS_FILE={escaped_violations_file}
 {raw_stream_shell_var}
 
 # Allow nested Claude Code sessions (spawned agents are independent)
@@ -1392,6 +1704,18 @@ EXIT_CODE=$?
 kill $HEARTBEAT_PID 2>/dev/null; wait $HEARTBEAT_PID 2>/dev/null
 {stream_result}
 
+# Role tool policy: shims log denied invocations (and fail the task
+# themselves); this is the safety net if that 'wg fail' didn't land.
+if [ -s "$VIOLATIONS_FILE" ]; then
+    echo "" >> "$OUTPUT_FILE"
+    echo "[wrapper] Tool policy violations:" >> "$OUTPUT_FILE"
+    cat "$VIOLATIONS_FILE" >> "$OUTPUT_FILE"
+    V_STATUS=$(wg show "$TASK_ID" --json 2>/dev/null | grep -o '"status": *"[^"]*"' | head -1 | sed 's/.*"status": *"//;s/"//' || echo "unknown")
+    if [ "$V_STATUS" = "in-progress" ]; then
+        wg fail "$TASK_ID" --class "tool-policy-violation" --reason "Agent ran commands denied by its role tool policy (see tool_violations.log)" 2>> "$OUTPUT_FILE" || echo "[wrapper] WARNING: 'wg fail' failed with exit code $?" >> "$OUTPUT_FILE"
+    fi
+fi
+
 # Check if task is still in progress (agent didn't mark it done/failed)
 TASK_STATUS=$(wg show "$TASK_ID" --json 2>/dev/null | grep -o '"status": *"[^"]*"' | head -1 | sed 's/.*"status": *"//;s/"//' || echo "unknown")
 
@@ -1473,6 +1797,11 @@ exit $EXIT_CODE
 "#,
         escaped_task_id = shell_escape(task_id),
         escaped_output_file = shell_escape(output_file_str),
+        escaped_violations_file = shell_escape(
+            &output_dir
+                .join(agency::TOOL_VIOLATIONS_FILE)
+                .to_string_lossy()
+        ),
         raw_stream_shell_var = raw_stream_shell_var,
         run_command = run_command,
         session_fallback_block = session_fallback_block,
@@ -1912,7 +2241,7 @@ mod tests {
 
     #[test]
     fn test_executor_uses_auto_prompt_includes_all_builtins() {
-        for kind in ["claude", "codex", "native"] {
+        for kind in ["claude", "codex", "native", "webhook"] {
             assert!(
                 executor_uses_auto_prompt(kind),
                 "{} must auto-build prompt",
@@ -2743,6 +3072,7 @@ mod tests {
             working_dir: Some("/tmp".to_string()),
             timeout: None,
             model: None,
+            retry: None,
         };
         let vars = TemplateVars {
             task_id: "task-1".to_string(),
@@ -2760,6 +3090,7 @@ mod tests {
             has_failed_deps: false,
             failed_deps_info: String::new(),
             in_worktree: false,
+            facts: SystemFacts::default(),
         };
 
         let (command, fallback) = build_inner_command(
@@ -2774,6 +3105,7 @@ mod tests {
             &vars,
             &None,
             None,
+            &agency::ToolPolicy::default(),
         )
         .unwrap();
 
@@ -2834,6 +3166,7 @@ mod tests {
             working_dir: Some("/tmp".to_string()),
             timeout: None,
             model: None,
+            retry: None,
         };
         let vars = TemplateVars {
             task_id: "task-1".to_string(),
@@ -2851,6 +3184,7 @@ mod tests {
             has_failed_deps: false,
             failed_deps_info: String::new(),
             in_worktree: false,
+            facts: SystemFacts::default(),
         };
 
         let (command, fallback) = build_inner_command(
@@ -2865,6 +3199,7 @@ mod tests {
             &vars,
             &None,
             Some("fake-session-id-12345"),
+            &agency::ToolPolicy::default(),
         )
         .unwrap();
 
@@ -2925,6 +3260,7 @@ mod tests {
             working_dir: Some("/tmp".to_string()),
             timeout: None,
             model: None,
+            retry: None,
         };
         let vars = TemplateVars {
             task_id: "task-1".to_string(),
@@ -2942,6 +3278,7 @@ mod tests {
             has_failed_deps: false,
             failed_deps_info: String::new(),
             in_worktree: false,
+            facts: SystemFacts::default(),
         };
 
         let (command, fallback) = build_inner_command(
@@ -2956,6 +3293,7 @@ mod tests {
             &vars,
             &None,
             None, // No resume session
+            &agency::ToolPolicy::default(),
         )
         .unwrap();
 
@@ -3023,4 +3361,70 @@ mod tests {
             "Wrapper should NOT contain session fallback when no fallback provided"
         );
     }
+
+    #[test]
+    fn test_claude_policy_flags_merge_role_denies_and_allows() {
+        let policy = agency::ToolPolicy {
+            allow: vec!["Read".to_string(), "Grep".to_string()],
+            deny: vec!["git push".to_string(), "WebFetch".to_string()],
+        };
+        let mut parts = Vec::new();
+        push_claude_policy_flags(&mut parts, "Agent,EnterWorktree,ExitWorktree", &policy);
+        assert_eq!(
+            parts,
+            vec![
+                "--disallowedTools".to_string(),
+                "'Agent,EnterWorktree,ExitWorktree,Bash(git push:*),WebFetch'".to_string(),
+                "--allowedTools".to_string(),
+                "'Bash(wg:*),Read,Grep'".to_string(),
+            ]
+        );
+
+        let mut parts = Vec::new();
+        push_claude_policy_flags(
+            &mut parts,
+            "Agent,EnterWorktree,ExitWorktree",
+            &agency::ToolPolicy::default(),
+        );
+        assert_eq!(
+            parts.len(),
+            2,
+            "no --allowedTools without a role allow list"
+        );
+    }
+
+    #[test]
+    fn test_tool_policy_shims_and_wrapper_violation_check() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let output_dir = temp_dir.path();
+
+        let policy = agency::ToolPolicy {
+            allow: vec![],
+            deny: vec!["git push".to_string(), "Edit".to_string()],
+        };
+        let shim_dir = write_tool_policy_shims(output_dir, &policy)
+            .unwrap()
+            .expect("git push should produce a shim");
+        assert!(shim_dir.join("git").exists());
+        assert_eq!(std::fs::read_dir(&shim_dir).unwrap().count(), 1);
+        assert!(
+            write_tool_policy_shims(output_dir, &agency::ToolPolicy::default())
+                .unwrap()
+                .is_none()
+        );
+
+        let wrapper_path = write_wrapper_script(
+            output_dir,
+            "test-task",
+            "/tmp/output.log",
+            "claude --print",
+            None,
+            "claude",
+            None,
+        )
+        .unwrap();
+        let script = std::fs::read_to_string(&wrapper_path).unwrap();
+        assert!(script.contains(agency::TOOL_VIOLATIONS_FILE));
+        assert!(script.contains("--class \"tool-policy-violation\""));
+    }
 }
@@ -11,6 +11,7 @@
 //! 5. Prints agent info (ID, PID, output file)
 //! 6. Returns immediately (doesn't wait for completion)
 
+pub(crate) mod agent_env;
 pub(crate) mod context;
 mod execution;
 pub mod raw_stream_classifier;
@@ -110,7 +111,7 @@ fn parse_timeout(timeout_str: &str) -> Result<std::time::Duration> {
 }
 
 /// Get the output directory for an agent
-fn agent_output_dir(workgraph_dir: &Path, agent_id: &str) -> PathBuf {
+pub(crate) fn agent_output_dir(workgraph_dir: &Path, agent_id: &str) -> PathBuf {
     workgraph_dir.join("agents").join(agent_id)
 }
 
@@ -423,6 +424,28 @@ mod tests {
         assert_eq!(registry.agents.len(), 1);
     }
 
+    #[test]
+    fn test_spawn_pins_agent_to_named_graph() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_graph(temp_dir.path(), vec![]);
+        let side = workgraph::graphs::create(&temp_dir.path().join(".wg"), "side").unwrap();
+        let task_id = format!("t{}", get_unique_id());
+        let mut task = make_task(&task_id, "Test Task");
+        task.exec = Some("echo hello".to_string());
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task));
+        save_graph(&graph, graph_path(&side)).unwrap();
+
+        run(&side, &task_id, "shell", None, None, false).unwrap();
+
+        let env = agent_env::AgentEnv::load(&agent_output_dir(&side, "agent-1"))
+            .unwrap()
+            .unwrap();
+        let pinned = side.canonicalize().unwrap();
+        assert_eq!(env.get("WG_DIR"), Some(pinned.to_str().unwrap()));
+        assert_eq!(env.get("WG_GRAPH"), Some("side"));
+    }
+
     #[test]
     fn test_spawn_creates_output_directory() {
         let temp_dir = TempDir::new().unwrap();
@@ -3,11 +3,16 @@
 //! When worktree isolation is enabled, each agent gets its own git worktree
 //! at `.wg-worktrees/<agent-id>/`, branched from HEAD. The `.wg/`
 //! directory is symlinked into the worktree so the `wg` CLI works normally.
+//! The branch is recorded on the task as a `git-branch:` artifact.
 
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Prefix of the task artifact that records an agent's worktree branch,
+/// e.g. `git-branch:wg/agent-7/fix-login`.
+pub const BRANCH_ARTIFACT_PREFIX: &str = "git-branch:";
+
 /// Worktree paths and metadata for an isolated agent workspace.
 #[derive(Debug)]
 pub struct WorktreeInfo {
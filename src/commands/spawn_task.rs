@@ -252,6 +252,12 @@ pub fn resolve_handler(
                  not the handler-exec path"
             ));
         }
+        workgraph::dispatch::ExecutorKind::Webhook => {
+            return Err(anyhow!(
+                "webhook executor is not supported by spawn-task; \
+                 it hands tasks to an external service and has no chat session"
+            ));
+        }
     })
 }
 
@@ -109,6 +109,14 @@ struct CycleTimingInfo {
     delay: Option<String>,
 }
 
+/// A phase gate that has not opened yet
+#[derive(Debug, Clone, serde::Serialize)]
+struct GateStatusInfo {
+    gate_id: String,
+    met: usize,
+    total: usize,
+}
+
 /// Full status output
 #[derive(Debug, Clone, serde::Serialize)]
 struct StatusOutput {
@@ -123,6 +131,8 @@ struct StatusOutput {
     dangling_deps: Vec<DanglingDep>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     verify_failing: Vec<VerifyFailingTask>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    gates: Vec<GateStatusInfo>,
 }
 
 pub fn run(dir: &Path, json: bool, show_all: bool) -> Result<()> {
@@ -162,6 +172,9 @@ fn gather_status(dir: &Path, show_all: bool) -> Result<StatusOutput> {
     // 8. Verify-failing tasks
     let verify_failing = gather_verify_failing(dir, show_all);
 
+    // 9. Phase gates still closed
+    let gates = gather_gates(dir);
+
     Ok(StatusOutput {
         service,
         coordinator,
@@ -171,6 +184,7 @@ fn gather_status(dir: &Path, show_all: bool) -> Result<StatusOutput> {
         recent,
         dangling_deps,
         verify_failing,
+        gates,
     })
 }
 
@@ -527,6 +541,29 @@ fn gather_verify_failing(dir: &Path, show_all: bool) -> Vec<VerifyFailingTask> {
         .collect()
 }
 
+fn gather_gates(dir: &Path) -> Vec<GateStatusInfo> {
+    let path = super::graph_path(dir);
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    let graph = match load_graph(&path) {
+        Ok(g) => g,
+        Err(_) => return Vec::new(),
+    };
+
+    graph
+        .tasks()
+        .filter(|t| !t.status.is_terminal())
+        .filter_map(|t| workgraph::gate::evaluate(dir, &graph, t, false))
+        .map(|r| GateStatusInfo {
+            gate_id: r.gate_id.clone(),
+            met: r.met(),
+            total: r.total(),
+        })
+        .collect()
+}
+
 fn print_status(status: &StatusOutput) {
     // Line 1: Service status
     if status.service.running {
@@ -643,6 +680,18 @@ fn print_status(status: &StatusOutput) {
         }
     }
 
+    // Phase gates
+    if !status.gates.is_empty() {
+        println!();
+        println!("Gates:");
+        for gate in &status.gates {
+            println!(
+                "  {} — gate: {}/{} conditions met",
+                gate.gate_id, gate.met, gate.total
+            );
+        }
+    }
+
     // Attention: verify-failing tasks
     if !status.verify_failing.is_empty() {
         println!();
@@ -908,6 +957,32 @@ mod tests {
         assert_eq!(dangling[0].missing_dep, "nonexistent");
     }
 
+    #[test]
+    fn test_gather_gates_reports_conditions_met() {
+        use workgraph::gate::{GateCondition, PhaseGate};
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph.jsonl");
+
+        let mut graph = WorkGraph::new();
+        let mut t1 = make_task("t1", "Task 1");
+        t1.status = Status::Done;
+        graph.add_node(Node::Task(t1));
+        let mut gate = make_task("release", "Gate: release");
+        gate.after = vec!["t1".to_string()];
+        gate.gate = Some(PhaseGate {
+            conditions: vec![GateCondition::PhaseDone, GateCondition::Approval],
+            approved_by: None,
+        });
+        graph.add_node(Node::Task(gate));
+        save_graph(&graph, &path).unwrap();
+
+        let gates = gather_gates(temp_dir.path());
+        assert_eq!(gates.len(), 1);
+        assert_eq!(gates[0].gate_id, "release");
+        assert_eq!((gates[0].met, gates[0].total), (1, 2));
+    }
+
     #[test]
     fn test_gather_dangling_deps_resolves_when_created() {
         let temp_dir = TempDir::new().unwrap();
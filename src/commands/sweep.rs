@@ -540,6 +540,8 @@ mod tests {
                 output_file: "/tmp/output.log".to_string(),
                 model: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
 
@@ -802,6 +804,8 @@ mod tests {
                 output_file: "/tmp/output.log".to_string(),
                 model: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
         reg.save(dir).unwrap();
@@ -857,6 +861,8 @@ mod tests {
                 output_file: "/tmp/output.log".to_string(),
                 model: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
         reg.save(dir).unwrap();
@@ -0,0 +1,469 @@
+//! `wg export --csv` / `wg import --csv` — spreadsheet round-trip of tasks.
+//!
+//! Columns (header row required, any order, case-insensitive):
+//!
+//! | column     | meaning                                   |
+//! |------------|-------------------------------------------|
+//! | `id`       | task ID; blank creates a task from `title` |
+//! | `title`    | task title                                |
+//! | `after`    | `;`-separated dependency IDs              |
+//! | `tags`     | `;`-separated tags                        |
+//! | `estimate` | estimated hours                           |
+//! | `status`   | `open`, `in-progress`, `done`, ...        |
+//! | `links`    | `;`-separated `<type> <url> [label]`      |
+//!
+//! Import updates only the columns present in the file, so a sheet with just
+//! `id` and `status` is a bulk status change. System tasks (`.`-prefixed) are
+//! never exported or touched.
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use serde::Serialize;
+use std::path::Path;
+use workgraph::graph::{
+    Estimate, LogEntry, Node, Status, Task, TaskLink, WorkGraph, is_system_task,
+};
+use workgraph::parser::{load_graph, modify_graph};
+
+use super::graph_path;
+
+/// Export column order
+pub const CSV_COLUMNS: &[&str] = &[
+    "id", "title", "after", "tags", "estimate", "status", "links",
+];
+
+const LIST_SEPARATOR: char = ';';
+
+fn require_csv(csv: bool) -> Result<()> {
+    if !csv {
+        bail!("No format given. Use --csv (the only supported format).");
+    }
+    Ok(())
+}
+
+fn join_list(items: &[String]) -> String {
+    items.join(&format!("{} ", LIST_SEPARATOR))
+}
+
+fn format_link(link: &TaskLink) -> String {
+    match link.label {
+        Some(ref label) => format!("{} {} {}", link.kind, link.url, label),
+        None => format!("{} {}", link.kind, link.url),
+    }
+}
+
+/// Parse `<type> <url> [label]`.
+fn parse_link(item: &str) -> Result<TaskLink> {
+    let mut parts = item.split_whitespace();
+    let (Some(kind), Some(url)) = (parts.next(), parts.next()) else {
+        bail!("link '{}' is not '<type> <url> [label]'", item);
+    };
+    let kind = super::external_link::parse_kind(kind)?;
+    super::external_link::validate_url(url)?;
+    let label = parts.collect::<Vec<_>>().join(" ");
+    Ok(TaskLink {
+        kind,
+        url: url.to_string(),
+        label: (!label.is_empty()).then_some(label),
+    })
+}
+
+fn split_list(cell: &str) -> Vec<String> {
+    cell.split(LIST_SEPARATOR)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Render non-system tasks as CSV in [`CSV_COLUMNS`] order.
+pub fn export_csv(graph: &WorkGraph) -> Result<String> {
+    let mut out = Vec::new();
+    {
+        let mut writer = csv::Writer::from_writer(&mut out);
+        writer.write_record(CSV_COLUMNS)?;
+        for task in graph.tasks().filter(|t| !is_system_task(&t.id)) {
+            let estimate = task
+                .estimate
+                .as_ref()
+                .and_then(|e| e.hours)
+                .map(|h| h.to_string())
+                .unwrap_or_default();
+            let links: Vec<String> = task.links.iter().map(format_link).collect();
+            writer.write_record([
+                task.id.as_str(),
+                task.title.as_str(),
+                &join_list(&task.after),
+                &join_list(&task.tags),
+                &estimate,
+                &task.status.to_string(),
+                &join_list(&links),
+            ])?;
+        }
+        writer.flush()?;
+    }
+    Ok(String::from_utf8(out)?)
+}
+
+pub fn run_export(dir: &Path, csv: bool, output: Option<&Path>) -> Result<()> {
+    require_csv(csv)?;
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let content = export_csv(&graph)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)
+                .with_context(|| format!("Failed to write '{}'", path.display()))?;
+            eprintln!("Exported tasks to {}", path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// One parsed CSV row; `None` means the column is absent from the file.
+#[derive(Debug, Default, PartialEq)]
+struct CsvRow {
+    line: usize,
+    id: Option<String>,
+    title: Option<String>,
+    after: Option<Vec<String>>,
+    tags: Option<Vec<String>>,
+    estimate: Option<Option<f64>>,
+    status: Option<Status>,
+    links: Option<Vec<TaskLink>>,
+}
+
+fn parse_csv(content: &str) -> Result<Vec<CsvRow>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers = reader.headers().context("Invalid CSV header")?.clone();
+    let column = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+    let id_col = column("id");
+    let title_col = column("title");
+    let after_col = column("after");
+    let tags_col = column("tags");
+    let estimate_col = column("estimate");
+    let status_col = column("status");
+    let links_col = column("links");
+    if id_col.is_none() && title_col.is_none() {
+        bail!("CSV needs an 'id' or 'title' column");
+    }
+
+    let mut rows = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        // Header is line 1
+        let line = i + 2;
+        let record = record.with_context(|| format!("Line {}: invalid CSV", line))?;
+        let cell = |col: Option<usize>| col.map(|c| record.get(c).unwrap_or(""));
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        let estimate = match cell(estimate_col) {
+            None => None,
+            Some("") => Some(None),
+            Some(s) => Some(Some(s.parse::<f64>().map_err(|_| {
+                anyhow::anyhow!("Line {}: estimate '{}' is not a number of hours", line, s)
+            })?)),
+        };
+        let status = match cell(status_col) {
+            None | Some("") => None,
+            Some(s) => Some(
+                serde_json::from_value(serde_json::Value::String(s.to_lowercase()))
+                    .map_err(|_| anyhow::anyhow!("Line {}: unknown status '{}'", line, s))?,
+            ),
+        };
+        let links = cell(links_col)
+            .map(|c| {
+                split_list(c)
+                    .iter()
+                    .map(|item| parse_link(item))
+                    .collect::<Result<Vec<_>>>()
+                    .with_context(|| format!("Line {}: invalid links", line))
+            })
+            .transpose()?;
+        rows.push(CsvRow {
+            line,
+            id: cell(id_col).filter(|s| !s.is_empty()).map(String::from),
+            title: cell(title_col).filter(|s| !s.is_empty()).map(String::from),
+            after: cell(after_col).map(split_list),
+            tags: cell(tags_col).map(split_list),
+            estimate,
+            status,
+            links,
+        });
+    }
+    Ok(rows)
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: usize,
+}
+
+/// Apply rows to the graph. Fails (leaving `graph` partly modified) on rows
+/// that reference system tasks, lack both id and title, or depend on
+/// unknown tasks — callers apply to a scratch copy first.
+fn apply(graph: &mut WorkGraph, rows: &[CsvRow]) -> Result<ImportSummary> {
+    let now = Utc::now().to_rfc3339();
+    let mut summary = ImportSummary::default();
+
+    for row in rows {
+        let id = match (&row.id, &row.title) {
+            (Some(id), _) => id.clone(),
+            (None, Some(title)) => super::add::generate_id(title, graph),
+            (None, None) => bail!("Line {}: row has neither id nor title", row.line),
+        };
+        if is_system_task(&id) {
+            bail!("Line {}: system task '{}' cannot be imported", row.line, id);
+        }
+
+        let is_new = graph.get_task(&id).is_none();
+        if is_new {
+            if graph.get_node(&id).is_some() {
+                bail!("Line {}: '{}' exists and is not a task", row.line, id);
+            }
+            graph.add_node(Node::Task(Task {
+                id: id.clone(),
+                title: row.title.clone().unwrap_or_else(|| id.clone()),
+                created_at: Some(now.clone()),
+                ..Task::default()
+            }));
+        }
+        let task = graph.get_task_mut(&id).expect("present above");
+
+        let mut changed: Vec<&str> = Vec::new();
+        if let Some(ref title) = row.title
+            && *title != task.title
+        {
+            task.title = title.clone();
+            changed.push("title");
+        }
+        if let Some(ref after) = row.after
+            && *after != task.after
+        {
+            task.after = after.clone();
+            changed.push("after");
+        }
+        if let Some(ref tags) = row.tags
+            && *tags != task.tags
+        {
+            task.tags = tags.clone();
+            changed.push("tags");
+        }
+        if let Some(hours) = row.estimate
+            && hours != task.estimate.as_ref().and_then(|e| e.hours)
+        {
+            let cost = task.estimate.as_ref().and_then(|e| e.cost);
+            task.estimate = (hours.is_some() || cost.is_some()).then_some(Estimate { hours, cost });
+            changed.push("estimate");
+        }
+        if let Some(ref links) = row.links
+            && *links != task.links
+        {
+            task.links = links.clone();
+            changed.push("links");
+        }
+        if let Some(status) = row.status
+            && status != task.status
+        {
+            task.status = status;
+            if status == Status::Done && task.completed_at.is_none() {
+                task.completed_at = Some(now.clone());
+            }
+            changed.push("status");
+        }
+
+        if is_new {
+            task.log.push(LogEntry {
+                timestamp: now.clone(),
+                actor: None,
+                user: Some(workgraph::current_user()),
+                message: "Created by CSV import".to_string(),
+            });
+            summary.created.push(id);
+        } else if changed.is_empty() {
+            summary.unchanged += 1;
+        } else {
+            task.log.push(LogEntry {
+                timestamp: now.clone(),
+                actor: None,
+                user: Some(workgraph::current_user()),
+                message: format!("Updated by CSV import: {}", changed.join(", ")),
+            });
+            summary.updated.push(id);
+        }
+    }
+
+    for row in rows {
+        for dep in row.after.iter().flatten() {
+            if graph.get_node(dep).is_none() {
+                bail!("Line {}: unknown dependency '{}'", row.line, dep);
+            }
+        }
+    }
+    Ok(summary)
+}
+
+pub fn run_import(dir: &Path, file: &Path, csv: bool, dry_run: bool, json: bool) -> Result<()> {
+    require_csv(csv)?;
+    let path = graph_path(dir);
+    if !path.exists() {
+        bail!("WG not initialized. Run 'wg init' first.");
+    }
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read '{}'", file.display()))?;
+    let rows =
+        parse_csv(&content).with_context(|| format!("Failed to parse '{}'", file.display()))?;
+
+    // Validate against a scratch copy so a bad row never half-applies.
+    let mut scratch = load_graph(&path).context("Failed to load graph")?;
+    let mut summary = apply(&mut scratch, &rows)?;
+
+    if !dry_run && !(summary.created.is_empty() && summary.updated.is_empty()) {
+        let mut error = None;
+        modify_graph(&path, |graph| match apply(graph, &rows) {
+            Ok(s) => {
+                summary = s;
+                true
+            }
+            Err(e) => {
+                error = Some(e);
+                false
+            }
+        })
+        .context("Failed to save graph")?;
+        if let Some(e) = error {
+            return Err(e);
+        }
+        super::notify_graph_changed(dir);
+    }
+
+    if json {
+        let mut out = serde_json::to_value(&summary)?;
+        out["dry_run"] = serde_json::Value::Bool(dry_run);
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    let verb = if dry_run { "Would import" } else { "Imported" };
+    println!(
+        "{} {}: {} created, {} updated, {} unchanged",
+        verb,
+        file.display(),
+        summary.created.len(),
+        summary.updated.len(),
+        summary.unchanged
+    );
+    for id in &summary.created {
+        println!("  + {}", id);
+    }
+    for id in &summary.updated {
+        println!("  ~ {}", id);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::parser::save_graph;
+
+    fn setup() -> (TempDir, std::path::PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(Task {
+            id: "design".to_string(),
+            title: "Design, v2".to_string(),
+            tags: vec!["ux".to_string()],
+            estimate: Some(Estimate {
+                hours: Some(4.0),
+                cost: None,
+            }),
+            links: vec![TaskLink {
+                kind: workgraph::graph::LinkKind::Doc,
+                url: "https://example.com/spec".to_string(),
+                label: Some("Spec v2".to_string()),
+            }],
+            ..Task::default()
+        }));
+        graph.add_node(Node::Task(Task {
+            id: "build".to_string(),
+            title: "Build".to_string(),
+            after: vec!["design".to_string()],
+            ..Task::default()
+        }));
+        graph.add_node(Node::Task(Task {
+            id: ".assign-build".to_string(),
+            title: "Assign".to_string(),
+            ..Task::default()
+        }));
+        save_graph(&graph, graph_path(&dir)).unwrap();
+        (tmp, dir)
+    }
+
+    #[test]
+    fn test_export_then_import_round_trips() {
+        let (tmp, dir) = setup();
+        let graph = load_graph(graph_path(&dir)).unwrap();
+        let csv = export_csv(&graph).unwrap();
+        assert!(csv.starts_with("id,title,after,tags,estimate,status,links\n"));
+        assert!(
+            csv.contains("design,\"Design, v2\",,ux,4,open,doc https://example.com/spec Spec v2\n")
+        );
+        assert!(!csv.contains(".assign-build"));
+
+        let file = tmp.path().join("tasks.csv");
+        std::fs::write(&file, &csv).unwrap();
+        run_import(&dir, &file, true, false, false).unwrap();
+        let after = load_graph(graph_path(&dir)).unwrap();
+        assert!(after.get_task("design").unwrap().log.is_empty());
+    }
+
+    #[test]
+    fn test_import_updates_present_columns_and_creates_rows() {
+        let (tmp, dir) = setup();
+        let file = tmp.path().join("edit.csv");
+        std::fs::write(
+            &file,
+            "ID,Status,after,title\n\
+             design,done,,\n\
+             ,open,design; build,Write docs\n",
+        )
+        .unwrap();
+        run_import(&dir, &file, true, false, true).unwrap();
+
+        let graph = load_graph(graph_path(&dir)).unwrap();
+        let design = graph.get_task("design").unwrap();
+        assert_eq!(design.status, Status::Done);
+        assert!(design.completed_at.is_some());
+        assert_eq!(design.tags, vec!["ux"]);
+        assert_eq!(design.title, "Design, v2");
+        let docs = graph.get_task("write-docs").unwrap();
+        assert_eq!(docs.after, vec!["design", "build"]);
+    }
+
+    #[test]
+    fn test_import_rejects_bad_rows_without_writing() {
+        let (tmp, dir) = setup();
+        let file = tmp.path().join("bad.csv");
+        std::fs::write(&file, "id,after\nbuild,nowhere\n").unwrap();
+        let err = run_import(&dir, &file, true, false, false).unwrap_err();
+        assert!(err.to_string().contains("unknown dependency 'nowhere'"));
+        let graph = load_graph(graph_path(&dir)).unwrap();
+        assert_eq!(graph.get_task("build").unwrap().after, vec!["design"]);
+
+        std::fs::write(&file, "id,status\nbuild,sideways\n").unwrap();
+        assert!(run_import(&dir, &file, true, false, false).is_err());
+        std::fs::write(&file, "id,links\nbuild,doc ftp://example.com/spec\n").unwrap();
+        assert!(run_import(&dir, &file, true, false, false).is_err());
+        assert!(run_import(&dir, &file, false, false, false).is_err());
+    }
+}
@@ -7,7 +7,7 @@ use workgraph::agency::{Evaluation, load_all_evaluations_or_warn};
 use workgraph::function::{
     self, FunctionVisibility, TraceFunction, export_function, function_visible_at,
 };
-use workgraph::graph::{LogEntry, Status};
+use workgraph::graph::{LogEntry, Status, TaskLink};
 use workgraph::parser::load_graph;
 use workgraph::provenance;
 
@@ -51,6 +51,8 @@ pub struct ExportedTask {
     pub tags: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub artifacts: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<TaskLink>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -128,6 +130,7 @@ pub fn run(
                 before: t.before.clone(),
                 tags: t.tags.clone(),
                 artifacts: t.artifacts.clone(),
+                links: t.links.clone(),
                 created_at: t.created_at.clone(),
                 completed_at: t.completed_at.clone(),
                 agent,
@@ -345,6 +348,7 @@ mod tests {
             before: vec![],
             tags: vec!["tag1".to_string()],
             artifacts: vec!["output.txt".to_string()],
+            links: vec![],
             created_at: Some("2026-02-28T12:00:00Z".to_string()),
             completed_at: Some("2026-02-28T13:00:00Z".to_string()),
             agent: Some("agent-1".to_string()),
@@ -378,6 +382,7 @@ mod tests {
             before: vec![],
             tags: vec![],
             artifacts: vec![],
+            links: vec![],
             created_at: None,
             completed_at: None,
             agent: None,
@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use std::path::Path;
 
 use workgraph::agency;
+use workgraph::graph::TaskLink;
 use workgraph::provenance;
 
 use super::trace_export::TraceExport;
@@ -86,6 +87,7 @@ pub fn run(dir: &Path, file: &str, source: Option<&str>, dry_run: bool, json: bo
                 tags
             },
             artifacts: t.artifacts.clone(),
+            links: t.links.clone(),
             created_at: t.created_at.clone(),
             completed_at: t.completed_at.clone(),
             agent: t.agent.clone(),
@@ -180,6 +182,8 @@ struct ImportedTask {
     tags: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     artifacts: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    links: Vec<TaskLink>,
     #[serde(skip_serializing_if = "Option::is_none")]
     created_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -224,6 +228,7 @@ mod tests {
             before: vec![],
             tags: vec!["original-tag".to_string()],
             artifacts: vec![],
+            links: vec![],
             created_at: None,
             completed_at: None,
             agent: None,
@@ -298,6 +303,7 @@ mod tests {
             skills: vec!["rust".to_string()],
             tags: vec!["imported".to_string(), "source:src".to_string()],
             artifacts: vec![],
+            links: vec![],
             created_at: None,
             completed_at: None,
             agent: Some("agent-1".to_string()),
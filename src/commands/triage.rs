@@ -0,0 +1,426 @@
+//! `wg triage --interactive` — keyboard-driven backlog grooming.
+//!
+//! Steps through open tasks that have no tags or no estimate, one card at a
+//! time, with single-key actions: add a [s]kill, set an [e]stimate, add a
+//! [t]ag, [n]ext (skip), [d]elete, [q]uit. Each change is written to the
+//! graph immediately, so quitting part-way keeps everything done so far.
+
+use anyhow::{Context, Result, bail};
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use workgraph::graph::{Estimate, Status, Task, WorkGraph, is_system_task};
+use workgraph::parser::modify_graph;
+
+#[cfg(test)]
+use super::graph_path;
+
+/// What a keystroke asks for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Action {
+    Skill,
+    Estimate,
+    Tag,
+    Skip,
+    Delete,
+    Quit,
+}
+
+fn key_action(key: char) -> Option<Action> {
+    match key {
+        's' => Some(Action::Skill),
+        'e' => Some(Action::Estimate),
+        't' => Some(Action::Tag),
+        'n' | ' ' | '\n' => Some(Action::Skip),
+        'd' => Some(Action::Delete),
+        'q' => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+/// An edit to the task under review
+#[derive(Debug, Clone, PartialEq)]
+enum Change {
+    AddSkills(Vec<String>),
+    SetHours(f64),
+    AddTags(Vec<String>),
+    Delete,
+}
+
+/// Where keystrokes and typed values come from.
+trait Input {
+    /// Next keystroke; `None` at end of input.
+    fn key(&mut self) -> Result<Option<char>>;
+    /// A line of text; `None` at end of input.
+    fn line(&mut self) -> Result<Option<String>>;
+}
+
+/// Reads single keys in raw mode and values as ordinary lines.
+struct TerminalInput;
+
+impl Input for TerminalInput {
+    fn key(&mut self) -> Result<Option<char>> {
+        use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+        crossterm::terminal::enable_raw_mode()?;
+        let key = loop {
+            match event::read() {
+                Ok(Event::Key(k)) if k.kind == KeyEventKind::Press => match k.code {
+                    KeyCode::Char('c') if k.modifiers.contains(KeyModifiers::CONTROL) => {
+                        break Ok(Some('q'));
+                    }
+                    KeyCode::Char(c) => break Ok(Some(c)),
+                    KeyCode::Enter => break Ok(Some('\n')),
+                    KeyCode::Esc => break Ok(Some('q')),
+                    _ => {}
+                },
+                Ok(_) => {}
+                Err(e) => break Err(e.into()),
+            }
+        };
+        crossterm::terminal::disable_raw_mode()?;
+        key
+    }
+
+    fn line(&mut self) -> Result<Option<String>> {
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim().to_string()))
+    }
+}
+
+/// Open, non-system tasks missing tags or an estimate, in graph order.
+fn candidates(graph: &WorkGraph) -> Vec<String> {
+    graph
+        .tasks()
+        .filter(|t| {
+            t.status == Status::Open
+                && !is_system_task(&t.id)
+                && (t.tags.is_empty() || t.estimate.as_ref().and_then(|e| e.hours).is_none())
+        })
+        .map(|t| t.id.clone())
+        .collect()
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn write_card<W: Write>(out: &mut W, task: &Task, index: usize, total: usize) -> Result<()> {
+    writeln!(
+        out,
+        "\n[{}/{}] {} - {}",
+        index + 1,
+        total,
+        task.id,
+        task.title
+    )?;
+    if let Some(ref desc) = task.description {
+        for line in desc.lines().take(5) {
+            writeln!(out, "  {}", line)?;
+        }
+    }
+    let none = || "(none)".to_string();
+    let list = |v: &[String]| if v.is_empty() { none() } else { v.join(", ") };
+    writeln!(out, "  Tags:     {}", list(&task.tags))?;
+    writeln!(out, "  Skills:   {}", list(&task.skills))?;
+    writeln!(
+        out,
+        "  Estimate: {}",
+        task.estimate
+            .as_ref()
+            .and_then(|e| e.hours)
+            .map(|h| format!("{}h", h))
+            .unwrap_or_else(none)
+    )?;
+    if !task.before.is_empty() {
+        writeln!(out, "  Blocks:   {}", task.before.join(", "))?;
+    }
+    Ok(())
+}
+
+/// Apply one change to the graph. Returns false if the task is gone.
+fn apply(dir: &Path, task_id: &str, change: &Change) -> Result<bool> {
+    let path = super::graph_path(dir);
+    let mut found = false;
+    modify_graph(&path, |graph| {
+        if graph.get_task(task_id).is_none() {
+            return false;
+        }
+        found = true;
+        if *change == Change::Delete {
+            graph.remove_node(task_id);
+            let internal: Vec<String> = super::gc::INTERNAL_PREFIXES
+                .iter()
+                .map(|p| format!("{}{}", p, task_id))
+                .filter(|id| graph.get_task(id).is_some())
+                .collect();
+            for id in internal {
+                graph.remove_node(&id);
+            }
+            return true;
+        }
+        let task = graph.get_task_mut(task_id).unwrap();
+        match change {
+            Change::AddSkills(skills) => {
+                for s in skills {
+                    if !task.skills.contains(s) {
+                        task.skills.push(s.clone());
+                    }
+                }
+            }
+            Change::SetHours(hours) => {
+                task.estimate.get_or_insert_with(Estimate::default).hours = Some(*hours);
+            }
+            Change::AddTags(tags) => {
+                for t in tags {
+                    if !task.tags.contains(t) {
+                        task.tags.push(t.clone());
+                    }
+                }
+            }
+            Change::Delete => unreachable!(),
+        }
+        true
+    })
+    .context("Failed to modify graph")?;
+    if !found {
+        return Ok(false);
+    }
+
+    // Record operation
+    let detail = match change {
+        Change::AddSkills(skills) => serde_json::json!({ "action": "skill", "skills": skills }),
+        Change::SetHours(hours) => serde_json::json!({ "action": "estimate", "hours": hours }),
+        Change::AddTags(tags) => serde_json::json!({ "action": "tag", "tags": tags }),
+        Change::Delete => serde_json::json!({ "action": "delete" }),
+    };
+    let config = workgraph::config::Config::load_or_default(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        "triage",
+        Some(task_id),
+        None,
+        detail,
+        config.log.rotation_threshold,
+    );
+    Ok(true)
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Summary {
+    reviewed: usize,
+    edited: usize,
+    deleted: usize,
+}
+
+/// Ask for a value; `None` if the input ended or the answer was empty.
+fn ask<I: Input, W: Write>(input: &mut I, out: &mut W, prompt: &str) -> Result<Option<String>> {
+    write!(out, "  {}: ", prompt)?;
+    out.flush()?;
+    Ok(input.line()?.filter(|v| !v.is_empty()))
+}
+
+fn session<I: Input, W: Write>(dir: &Path, input: &mut I, out: &mut W) -> Result<Summary> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let ids = candidates(&graph);
+    let mut summary = Summary::default();
+    if ids.is_empty() {
+        writeln!(
+            out,
+            "Nothing to triage: every open task has tags and an estimate."
+        )?;
+        return Ok(summary);
+    }
+    writeln!(
+        out,
+        "{} task(s) to triage. Keys: [s]kill [e]stimate [t]ag [n]ext [d]elete [q]uit",
+        ids.len()
+    )?;
+
+    'tasks: for (index, id) in ids.iter().enumerate() {
+        let mut edited = false;
+        summary.reviewed += 1;
+        loop {
+            let (graph, _path) = super::load_workgraph(dir)?;
+            let Some(task) = graph.get_task(id) else {
+                continue 'tasks;
+            };
+            write_card(out, task, index, ids.len())?;
+            write!(out, "> ")?;
+            out.flush()?;
+            let Some(key) = input.key()? else {
+                writeln!(out)?;
+                break 'tasks;
+            };
+            writeln!(out, "{}", key.to_string().trim())?;
+            let change = match key_action(key) {
+                Some(Action::Skip) => break,
+                Some(Action::Quit) => break 'tasks,
+                Some(Action::Skill) => ask(input, out, "Skills (comma-separated)")?
+                    .map(|v| Change::AddSkills(split_list(&v))),
+                Some(Action::Tag) => ask(input, out, "Tags (comma-separated)")?
+                    .map(|v| Change::AddTags(split_list(&v))),
+                Some(Action::Estimate) => match ask(input, out, "Estimate (hours)")? {
+                    Some(v) => match v.trim_end_matches('h').parse::<f64>() {
+                        Ok(hours) if hours > 0.0 => Some(Change::SetHours(hours)),
+                        _ => {
+                            writeln!(out, "  Not a positive number of hours: '{}'", v)?;
+                            None
+                        }
+                    },
+                    None => None,
+                },
+                Some(Action::Delete) => {
+                    match ask(input, out, &format!("Delete '{}'? [y/N]", id))? {
+                        Some(v) if v.eq_ignore_ascii_case("y") => Some(Change::Delete),
+                        _ => None,
+                    }
+                }
+                None => {
+                    writeln!(out, "  Unknown key '{}'", key)?;
+                    None
+                }
+            };
+            let Some(change) = change else {
+                continue;
+            };
+            if apply(dir, id, &change)? {
+                if change == Change::Delete {
+                    summary.deleted += 1;
+                    writeln!(out, "  Deleted '{}'", id)?;
+                    break;
+                }
+                edited = true;
+            }
+        }
+        if edited {
+            summary.edited += 1;
+        }
+    }
+
+    writeln!(
+        out,
+        "\nTriaged {} task(s): {} edited, {} deleted.",
+        summary.reviewed, summary.edited, summary.deleted
+    )?;
+    Ok(summary)
+}
+
+pub fn run_interactive(dir: &Path) -> Result<()> {
+    if !crossterm::tty::IsTty::is_tty(&io::stdin()) {
+        bail!("wg triage --interactive needs a terminal");
+    }
+    let summary = session(dir, &mut TerminalInput, &mut io::stdout())?;
+    if summary.edited + summary.deleted > 0 {
+        super::notify_graph_changed(dir);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use tempfile::TempDir;
+    use workgraph::graph::Node;
+    use workgraph::parser::{load_graph, save_graph};
+    use workgraph::test_helpers::make_task;
+
+    /// Replays keys and lines in order.
+    struct Scripted {
+        keys: VecDeque<char>,
+        lines: VecDeque<&'static str>,
+    }
+
+    impl Input for Scripted {
+        fn key(&mut self) -> Result<Option<char>> {
+            Ok(self.keys.pop_front())
+        }
+
+        fn line(&mut self) -> Result<Option<String>> {
+            Ok(self.lines.pop_front().map(String::from))
+        }
+    }
+
+    fn setup() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("a", "Task A")));
+        graph.add_node(Node::Task(make_task("b", "Task B")));
+        let mut groomed = make_task("c", "Task C");
+        groomed.tags = vec!["ui".to_string()];
+        groomed.estimate = Some(Estimate {
+            hours: Some(1.0),
+            cost: None,
+        });
+        graph.add_node(Node::Task(groomed));
+        let mut done = make_task("d", "Task D");
+        done.status = Status::Done;
+        graph.add_node(Node::Task(done));
+        save_graph(&graph, graph_path(temp_dir.path())).unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_candidates_skip_groomed_and_closed() {
+        let temp_dir = setup();
+        let graph = load_graph(graph_path(temp_dir.path())).unwrap();
+        assert_eq!(candidates(&graph), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_session_applies_actions() {
+        let temp_dir = setup();
+        let dir = temp_dir.path();
+        let mut input = Scripted {
+            keys: "setxn".chars().chain("d".chars()).collect(),
+            lines: VecDeque::from(["rust, cli", "3h", "backend", "y"]),
+        };
+        let mut out = Vec::new();
+        let summary = session(dir, &mut input, &mut out).unwrap();
+        assert_eq!(
+            summary,
+            Summary {
+                reviewed: 2,
+                edited: 1,
+                deleted: 1
+            }
+        );
+
+        let graph = load_graph(graph_path(dir)).unwrap();
+        let a = graph.get_task("a").unwrap();
+        assert_eq!(a.skills, ["rust", "cli"]);
+        assert_eq!(a.tags, ["backend"]);
+        assert_eq!(a.estimate.as_ref().and_then(|e| e.hours), Some(3.0));
+        assert!(graph.get_task("b").is_none());
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Unknown key 'x'"));
+    }
+
+    #[test]
+    fn test_session_quit_and_bad_estimate() {
+        let temp_dir = setup();
+        let dir = temp_dir.path();
+        let mut input = Scripted {
+            keys: "eq".chars().collect(),
+            lines: VecDeque::from(["soon"]),
+        };
+        let mut out = Vec::new();
+        let summary = session(dir, &mut input, &mut out).unwrap();
+        assert_eq!(summary.reviewed, 1);
+        assert_eq!(summary.edited, 0);
+        let graph = load_graph(graph_path(dir)).unwrap();
+        assert!(graph.get_task("a").unwrap().estimate.is_none());
+        assert!(
+            String::from_utf8(out)
+                .unwrap()
+                .contains("Not a positive number of hours: 'soon'")
+        );
+    }
+}
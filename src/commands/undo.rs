@@ -0,0 +1,182 @@
+//! `wg undo` / `wg redo` — step backwards and forwards through the
+//! journal of graph mutations. Both act on the caller's own changes unless
+//! `--any` is given.
+
+use anyhow::Result;
+use std::path::Path;
+
+use workgraph::config::Config;
+use workgraph::journal::{self, EntryKind, JournalEntry, Owner, Scope};
+
+fn scope(any: bool) -> Scope {
+    if any {
+        Scope::Any
+    } else {
+        Scope::Own(Owner::current())
+    }
+}
+
+/// Revert the caller's most recent graph change (anyone's with `any`).
+pub fn run_undo(dir: &Path, any: bool, force: bool, json: bool) -> Result<()> {
+    let entry = journal::undo(dir, &scope(any), force)?;
+    report(dir, "undo", entry, any, json)
+}
+
+/// Re-apply the caller's most recently undone change (anyone's with `any`).
+pub fn run_redo(dir: &Path, any: bool, force: bool, json: bool) -> Result<()> {
+    let entry = journal::redo(dir, &scope(any), force)?;
+    report(dir, "redo", entry, any, json)
+}
+
+fn report(dir: &Path, op: &str, entry: Option<JournalEntry>, any: bool, json: bool) -> Result<()> {
+    let Some(entry) = entry else {
+        if json {
+            println!("{}", serde_json::json!({ op: null }));
+        } else if any {
+            println!("Nothing to {}", op);
+        } else {
+            println!(
+                "Nothing of yours to {} (use --any to include changes by agents and the service)",
+                op
+            );
+        }
+        return Ok(());
+    };
+
+    super::notify_graph_changed(dir);
+    let config = Config::load_or_default(dir);
+    let _ = workgraph::provenance::record(
+        dir,
+        op,
+        None,
+        None,
+        serde_json::json!({
+            "seq": entry.seq,
+            "command": entry.command,
+            "tasks": entry.task_ids(),
+        }),
+        config.log.rotation_threshold,
+    );
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ op: entry }))?
+        );
+        return Ok(());
+    }
+    let verb = if op == "undo" { "Undid" } else { "Redid" };
+    println!(
+        "{} #{} by {}: {}",
+        verb,
+        entry.seq,
+        entry.owner(),
+        entry.command.as_deref().unwrap_or("unknown command")
+    );
+    println!("  Tasks: {}", entry.task_ids().join(", "));
+    Ok(())
+}
+
+/// Show the changes that `wg undo` and `wg redo` would step through.
+pub fn run_history(dir: &Path, limit: usize, any: bool, json: bool) -> Result<()> {
+    let entries = journal::load(dir)?;
+    let (undo_stack, redo_stack) = journal::stacks(&entries);
+    let owner = Owner::current();
+    let find = |seq: &u64| {
+        entries.iter().find(|e| {
+            e.seq == *seq && e.kind == EntryKind::Change && (any || e.is_owned_by(&owner))
+        })
+    };
+    let undoable: Vec<&JournalEntry> = undo_stack
+        .iter()
+        .rev()
+        .filter_map(find)
+        .take(limit)
+        .collect();
+    let redoable: Vec<&JournalEntry> = redo_stack
+        .iter()
+        .rev()
+        .filter_map(find)
+        .take(limit)
+        .collect();
+
+    if json {
+        let summarize = |e: &&JournalEntry| {
+            serde_json::json!({
+                "seq": e.seq,
+                "timestamp": e.timestamp,
+                "user": e.user,
+                "actor": e.actor,
+                "command": e.command,
+                "tasks": e.task_ids(),
+            })
+        };
+        let out = serde_json::json!({
+            "undo": undoable.iter().map(summarize).collect::<Vec<_>>(),
+            "redo": redoable.iter().map(summarize).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    let print = |title: &str, list: &[&JournalEntry]| {
+        println!("{}:", title);
+        if list.is_empty() {
+            println!("  (none)");
+        }
+        for e in list {
+            println!(
+                "  #{:<5} {}  {}  {}  [{}]",
+                e.seq,
+                e.timestamp,
+                e.owner(),
+                e.command.as_deref().unwrap_or("-"),
+                e.task_ids().join(", ")
+            );
+        }
+    };
+    print("Undo (newest first)", &undoable);
+    if !redoable.is_empty() {
+        println!();
+        print("Redo", &redoable);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use workgraph::graph::Status;
+    use workgraph::parser::{load_graph, modify_graph};
+    use workgraph::test_helpers::{make_task, setup_workgraph};
+
+    #[test]
+    fn test_undo_and_redo_record_provenance() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("a", "A")]);
+        modify_graph(dir.join("graph.jsonl"), |g| {
+            g.get_task_mut("a").unwrap().status = Status::Done;
+            true
+        })
+        .unwrap();
+
+        run_undo(&dir, false, false, false).unwrap();
+        let g = load_graph(dir.join("graph.jsonl")).unwrap();
+        assert_eq!(g.get_task("a").unwrap().status, Status::Open);
+        run_history(&dir, 10, false, false).unwrap();
+        run_redo(&dir, false, false, true).unwrap();
+        let g = load_graph(dir.join("graph.jsonl")).unwrap();
+        assert_eq!(g.get_task("a").unwrap().status, Status::Done);
+
+        let ops: Vec<String> = workgraph::provenance::read_all_operations(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|o| o.op)
+            .collect();
+        assert_eq!(ops, vec!["undo", "redo"]);
+        // Nothing left to redo is not an error.
+        run_redo(&dir, true, false, false).unwrap();
+    }
+}
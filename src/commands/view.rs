@@ -0,0 +1,91 @@
+//! `wg view` — saved task queries from the `[views]` config section.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use workgraph::config::Config;
+use workgraph::query::TaskQuery;
+
+/// Look up a saved view by name and parse it.
+pub fn resolve(dir: &Path, name: &str) -> Result<TaskQuery> {
+    let config = Config::load_merged(dir)?;
+    let Some(src) = config.views.get(name) else {
+        let known: Vec<&str> = config.views.keys().map(String::as_str).collect();
+        if known.is_empty() {
+            anyhow::bail!(
+                "Unknown view '{}'. No views are defined; add one under [views] in config.toml, e.g.\n  \
+                 [views]\n  {} = \"status=open sort:priority\"",
+                name,
+                name
+            );
+        }
+        anyhow::bail!(
+            "Unknown view '{}'. Defined views: {}",
+            name,
+            known.join(", ")
+        );
+    };
+    TaskQuery::parse(src).with_context(|| format!("Invalid query in view '{}'", name))
+}
+
+/// Run a saved view as `wg list`, or list the defined views when no name
+/// is given.
+pub fn run(dir: &Path, name: Option<&str>, json: bool, show_all: bool) -> Result<()> {
+    let Some(name) = name else {
+        return run_list(dir, json);
+    };
+    super::list::run(
+        dir,
+        None,
+        false,
+        &[],
+        None,
+        false,
+        None,
+        Some(name),
+        json,
+        show_all,
+    )
+}
+
+fn run_list(dir: &Path, json: bool) -> Result<()> {
+    let config = Config::load_merged(dir)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&config.views)?);
+    } else if config.views.is_empty() {
+        println!("No views defined. Add one under [views] in config.toml:");
+        println!("  [views]");
+        println!("  my-backlog = \"status=open tag:backend sort:priority\"");
+    } else {
+        let width = config.views.keys().map(|k| k.len()).max().unwrap_or(0);
+        for (name, query) in &config.views {
+            println!("{:<width$}  {}", name, query, width = width);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_resolve_view() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("config.toml"),
+            "[views]\nmy-backlog = \"status=open tag:backend sort:priority\"\nbroken = \"colour=red\"\n",
+        )
+        .unwrap();
+
+        let q = resolve(dir.path(), "my-backlog").unwrap();
+        assert!(q.filter.is_some());
+        assert_eq!(q.sort.len(), 1);
+
+        let err = resolve(dir.path(), "nope").unwrap_err().to_string();
+        assert!(err.contains("broken, my-backlog"), "{}", err);
+        let err = format!("{:#}", resolve(dir.path(), "broken").unwrap_err());
+        assert!(err.contains("Invalid query in view 'broken'"), "{}", err);
+    }
+}
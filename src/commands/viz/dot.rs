@@ -170,8 +170,49 @@ pub(crate) fn generate_dot(
     lines.join("\n")
 }
 
+/// Mermaid class name and style for each task status.
+fn mermaid_status_class(status: Status) -> (&'static str, &'static str) {
+    match status {
+        Status::Done => ("done", "fill:#c8e6c9,stroke:#2e7d32"),
+        Status::InProgress => ("in-progress", "fill:#fff59d,stroke:#f9a825"),
+        Status::Blocked => ("blocked", "fill:#ffcdd2,stroke:#c62828"),
+        Status::Open => ("open", "fill:#fff,stroke:#616161"),
+        Status::Failed => ("failed", "fill:#ef9a9a,stroke:#b71c1c,stroke-width:2px"),
+        Status::Abandoned => ("abandoned", "fill:#e0e0e0,stroke:#9e9e9e,color:#757575"),
+        Status::Waiting | Status::PendingValidation => ("waiting", "fill:#fff9c4,stroke:#f9a825"),
+        Status::PendingEval => ("pending-eval", "fill:#dcedc8,stroke:#558b2f"),
+        Status::FailedPendingEval => ("failed-pending-eval", "fill:#ffcc80,stroke:#e65100"),
+        Status::Incomplete => ("incomplete", "fill:#ffe0b2,stroke:#ef6c00"),
+    }
+}
+
+/// Mermaid node ids may not contain `.` and other punctuation, and `end`
+/// is a keyword.
+pub(crate) fn mermaid_id(id: &str) -> String {
+    let sanitized: String = id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.eq_ignore_ascii_case("end") {
+        format!("{}_", sanitized)
+    } else {
+        sanitized
+    }
+}
+
+/// Generate a Mermaid `graph TD` flowchart.
+///
+/// Nodes are styled by status via `classDef`, critical-path edges are thick,
+/// and cycle back-edges (loop iterations) are drawn as dashed `loop` arrows so
+/// the output stays readable when pasted into Markdown.
 pub(crate) fn generate_mermaid(
-    _graph: &WorkGraph,
+    graph: &WorkGraph,
     tasks: &[&Task],
     task_ids: &HashSet<&str>,
     critical_path: &HashSet<String>,
@@ -179,9 +220,10 @@ pub(crate) fn generate_mermaid(
 ) -> String {
     let mut lines = Vec::new();
 
-    lines.push("flowchart LR".to_string());
+    lines.push("graph TD".to_string());
 
     // Print task nodes
+    let mut used_classes: Vec<(&str, &str)> = Vec::new();
     for task in tasks {
         let hours_str = task
             .estimate
@@ -191,7 +233,7 @@ pub(crate) fn generate_mermaid(
             .unwrap_or_default();
 
         // Sanitize title for mermaid (escape quotes)
-        let title = task.title.replace('"', "'");
+        let title = task.title.replace('"', "#quot;");
 
         // Add phase annotation if present
         let phase_str = annotations
@@ -199,52 +241,58 @@ pub(crate) fn generate_mermaid(
             .map(|a| format!(" {}", a.text))
             .unwrap_or_default();
 
-        let label = format!("{}: {}{}{}", task.id, title, hours_str, phase_str);
-
-        // Mermaid node shape based on status
-        let node = match task.status {
-            Status::Done => format!("  {}[/\"{}\"/]", task.id, label),
-            Status::InProgress => format!("  {}((\"{}\"))", task.id, label),
-            Status::Blocked => format!("  {}{{\"{}\"}}!", task.id, label),
-            Status::Open => format!("  {}[\"{}\"]", task.id, label),
-            Status::Failed => format!("  {}{{{{\"{}\"}}}}!", task.id, label),
-            Status::Abandoned => format!("  {}[\"{}\"]:::abandoned", task.id, label),
-            Status::Waiting | Status::PendingValidation => {
-                format!("  {}[\"{}\"]:::waiting", task.id, label)
-            }
-            Status::PendingEval => format!("  {}[\"{}\"]:::pending-eval", task.id, label),
-            Status::FailedPendingEval => {
-                format!("  {}[\"{}\"]:::failed-pending-eval", task.id, label)
-            }
-            Status::Incomplete => format!("  {}[\"{}\"]:::incomplete", task.id, label),
-        };
-        lines.push(node);
+        let pause_prefix = if task.paused { "‖ " } else { "" };
+        let label = format!(
+            "{}{}: {}{}{}",
+            pause_prefix, task.id, title, hours_str, phase_str
+        );
+
+        let (class, style) = mermaid_status_class(task.status);
+        if !used_classes.contains(&(class, style)) {
+            used_classes.push((class, style));
+        }
+        lines.push(format!(
+            "  {}[\"{}\"]:::{}",
+            mermaid_id(&task.id),
+            label,
+            class
+        ));
     }
 
     // Collect truly dangling dependency targets (don't exist in the graph at all)
     let mut dangling_targets: HashSet<String> = HashSet::new();
     for task in tasks {
         for after in &task.after {
-            if !task_ids.contains(after.as_str()) && _graph.get_node(after).is_none() {
+            if !task_ids.contains(after.as_str()) && graph.get_node(after).is_none() {
                 dangling_targets.insert(after.clone());
             }
         }
     }
 
     // Add phantom nodes for dangling dependencies
-    for target in &dangling_targets {
+    let mut dangling_sorted: Vec<&String> = dangling_targets.iter().collect();
+    dangling_sorted.sort();
+    for target in dangling_sorted {
         lines.push(format!(
             "  {}[\"⚠ {} (missing)\"]:::dangling",
-            target, target
+            mermaid_id(target),
+            target
         ));
     }
 
     lines.push(String::new());
 
     // Print edges
+    let back_edges = graph.compute_cycle_analysis().back_edges;
     for task in tasks {
         for after in &task.after {
+            let (from, to) = (mermaid_id(after), mermaid_id(&task.id));
             if task_ids.contains(after.as_str()) {
+                if back_edges.contains(&(after.clone(), task.id.clone())) {
+                    // Loop edge: iteration returns to the cycle header
+                    lines.push(format!("  {} -. loop .-> {}", from, to));
+                    continue;
+                }
                 // Check if this edge is on critical path
                 let arrow = if critical_path.contains(&task.id) && critical_path.contains(after) {
                     "==>" // thick arrow for critical path
@@ -252,36 +300,50 @@ pub(crate) fn generate_mermaid(
                     "-->"
                 };
 
-                lines.push(format!("  {} {} {}", after, arrow, task.id));
+                lines.push(format!("  {} {} {}", from, arrow, to));
             } else if dangling_targets.contains(after) {
                 // Dangling edge — dotted red
-                lines.push(format!("  {} -.-> {}", after, task.id));
+                lines.push(format!("  {} -.-> {}", from, to));
             }
         }
     }
 
     // Print actor assignments
-    let assigned_actors: HashSet<&str> =
+    let mut assigned_actors: Vec<&str> =
         tasks.iter().filter_map(|t| t.assigned.as_deref()).collect();
+    assigned_actors.sort_unstable();
+    assigned_actors.dedup();
 
     if !assigned_actors.is_empty() {
         lines.push(String::new());
         for actor_id in &assigned_actors {
-            lines.push(format!("  {}(({}))", actor_id, actor_id));
+            lines.push(format!("  {}(({}))", mermaid_id(actor_id), actor_id));
         }
 
         for task in tasks {
             if let Some(ref assigned) = task.assigned {
-                lines.push(format!("  {} -.-> {}", task.id, assigned));
+                lines.push(format!(
+                    "  {} -.-> {}",
+                    mermaid_id(&task.id),
+                    mermaid_id(assigned)
+                ));
             }
         }
     }
 
+    // Status styling
+    lines.push(String::new());
+    for (class, style) in &used_classes {
+        lines.push(format!("  classDef {} {}", class, style));
+    }
+
     // Add styling for critical path nodes
     if !critical_path.is_empty() {
         lines.push(String::new());
         lines.push("  %% Critical path styling".to_string());
-        let critical_nodes: Vec<&str> = critical_path.iter().map(String::as_str).collect();
+        let mut critical_nodes: Vec<String> =
+            critical_path.iter().map(|id| mermaid_id(id)).collect();
+        critical_nodes.sort();
         lines.push(format!(
             "  style {} stroke:#f00,stroke-width:3px",
             critical_nodes.join(",")
@@ -437,8 +499,38 @@ mod tests {
         let no_annots = HashMap::new();
 
         let mermaid = generate_mermaid(&graph, &tasks, &task_ids, &critical_path, &no_annots);
-        assert!(mermaid.contains("flowchart LR"));
-        assert!(mermaid.contains("t1"));
+        assert!(mermaid.starts_with("graph TD"));
+        assert!(mermaid.contains("  t1[\"t1: Task 1\"]:::open"));
+        assert!(mermaid.contains("classDef open "));
+        assert!(!mermaid.contains("classDef done"));
+    }
+
+    #[test]
+    fn test_generate_mermaid_loop_edges_dashed() {
+        let mut graph = WorkGraph::new();
+        let mut a = make_task("a", "Write");
+        a.after = vec!["b".to_string()];
+        let mut b = make_task("b", "Review");
+        b.after = vec!["a".to_string()];
+        b.status = Status::Done;
+        graph.add_node(Node::Task(a));
+        graph.add_node(Node::Task(b));
+
+        let tasks: Vec<_> = graph.tasks().collect();
+        let task_ids: HashSet<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        let mermaid = generate_mermaid(&graph, &tasks, &task_ids, &HashSet::new(), &HashMap::new());
+
+        assert_eq!(mermaid.matches(" -. loop .-> ").count(), 1, "{}", mermaid);
+        assert_eq!(mermaid.matches(" --> ").count(), 1, "{}", mermaid);
+        assert!(mermaid.contains(":::done"));
+        assert!(mermaid.contains("classDef done "));
+    }
+
+    #[test]
+    fn test_mermaid_id_sanitized() {
+        assert_eq!(mermaid_id(".assign-my-task"), "_assign-my-task");
+        assert_eq!(mermaid_id("end"), "end_");
+        assert_eq!(mermaid_id("a/b c"), "a_b_c");
     }
 
     #[test]
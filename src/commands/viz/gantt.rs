@@ -0,0 +1,261 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use workgraph::graph::{Status, Task, WorkGraph};
+
+/// Duration assumed for tasks without an hours estimate (matches the
+/// critical-path calculation).
+const DEFAULT_HOURS: f64 = 1.0;
+
+/// Scheduled start and end of a single task.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Span {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+fn parse_ts(s: Option<&str>) -> Option<DateTime<Utc>> {
+    s.and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn estimate(task: &Task) -> Duration {
+    let hours = task
+        .estimate
+        .as_ref()
+        .and_then(|e| e.hours)
+        .filter(|h| *h > 0.0)
+        .unwrap_or(DEFAULT_HOURS);
+    Duration::minutes((hours * 60.0).round() as i64)
+}
+
+/// Compute the schedule span for `id`.
+///
+/// Recorded timestamps win: `started_at` is the start and `completed_at` the
+/// end. Unstarted tasks begin at the latest of `now`, `not_before` and the
+/// end of each dependency; the end is start plus the hours estimate. Tasks
+/// still in progress past their estimate are stretched to `now`.
+pub(crate) fn schedule(
+    id: &str,
+    graph: &WorkGraph,
+    now: DateTime<Utc>,
+    memo: &mut HashMap<String, Span>,
+    visiting: &mut HashSet<String>,
+) -> Option<Span> {
+    if let Some(span) = memo.get(id) {
+        return Some(*span);
+    }
+    let task = graph.get_task(id)?;
+    // Cycle back-edges don't constrain the schedule.
+    if !visiting.insert(id.to_string()) {
+        return None;
+    }
+
+    let duration = estimate(task);
+    let started = parse_ts(task.started_at.as_deref());
+    let completed = parse_ts(task.completed_at.as_deref());
+
+    let start = match (started, completed) {
+        (Some(s), _) => s,
+        (None, Some(c)) => c - duration,
+        (None, None) => {
+            let mut start = now;
+            if let Some(nb) = parse_ts(task.not_before.as_deref()) {
+                start = start.max(nb);
+            }
+            for dep in &task.after {
+                if let Some(span) = schedule(dep, graph, now, memo, visiting) {
+                    start = start.max(span.end);
+                }
+            }
+            start
+        }
+    };
+    let end = match completed {
+        Some(c) => c.max(start),
+        None if task.status == Status::InProgress => (start + duration).max(now),
+        None => start + duration,
+    };
+
+    visiting.remove(id);
+    let span = Span { start, end };
+    memo.insert(id.to_string(), span);
+    Some(span)
+}
+
+/// Mermaid task ids may only contain word characters.
+fn gantt_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// `:`, `;` and `#` end a Mermaid gantt task title.
+fn gantt_title(task: &Task) -> String {
+    let raw = if task.title.is_empty() {
+        task.id.as_str()
+    } else {
+        task.title.as_str()
+    };
+    raw.replace([':', ';', '#'], " ")
+}
+
+fn section_name(status: Status) -> &'static str {
+    match status {
+        Status::Done => "Done",
+        Status::InProgress | Status::PendingValidation | Status::PendingEval => "In progress",
+        Status::Failed | Status::FailedPendingEval | Status::Abandoned | Status::Incomplete => {
+            "Stopped"
+        }
+        Status::Open | Status::Blocked | Status::Waiting => "Planned",
+    }
+}
+
+pub(crate) fn generate_gantt(
+    graph: &WorkGraph,
+    tasks: &[&Task],
+    critical_path: &HashSet<String>,
+    now: DateTime<Utc>,
+) -> String {
+    let mut memo = HashMap::new();
+    let mut visiting = HashSet::new();
+    let mut rows: Vec<(&Task, Span)> = tasks
+        .iter()
+        .filter_map(|t| {
+            schedule(&t.id, graph, now, &mut memo, &mut visiting).map(|span| (*t, span))
+        })
+        .collect();
+    rows.sort_by(|a, b| a.1.start.cmp(&b.1.start).then_with(|| a.0.id.cmp(&b.0.id)));
+
+    let mut lines = vec![
+        "gantt".to_string(),
+        "  title Workgraph schedule".to_string(),
+        "  dateFormat YYYY-MM-DDTHH:mm".to_string(),
+        "  axisFormat %m-%d %H:%M".to_string(),
+    ];
+
+    for section in ["Done", "In progress", "Stopped", "Planned"] {
+        let in_section: Vec<&(&Task, Span)> = rows
+            .iter()
+            .filter(|(t, _)| section_name(t.status) == section)
+            .collect();
+        if in_section.is_empty() {
+            continue;
+        }
+        lines.push(String::new());
+        lines.push(format!("  section {}", section));
+        for (task, span) in in_section {
+            let id = gantt_id(&task.id);
+            let mut tags = Vec::new();
+            if critical_path.contains(&task.id) {
+                tags.push("crit");
+            }
+            match task.status {
+                Status::Done => tags.push("done"),
+                Status::InProgress => tags.push("active"),
+                _ => {}
+            }
+            tags.push(id.as_str());
+            lines.push(format!(
+                "  {} :{}, {}, {}",
+                gantt_title(task),
+                tags.join(", "),
+                span.start.format("%Y-%m-%dT%H:%M"),
+                span.end.format("%Y-%m-%dT%H:%M"),
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workgraph::graph::{Estimate, Node};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-02T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn make_task(id: &str, hours: f64, after: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            title: format!("Task {}", id),
+            estimate: Some(Estimate {
+                hours: Some(hours),
+                cost: None,
+            }),
+            after: after.iter().map(|s| s.to_string()).collect(),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn test_schedule_follows_dependencies_and_not_before() {
+        let mut graph = WorkGraph::new();
+        let mut done = make_task("done", 2.0, &[]);
+        done.status = Status::Done;
+        done.started_at = Some("2026-03-01T10:00:00Z".to_string());
+        done.completed_at = Some("2026-03-01T13:30:00Z".to_string());
+        graph.add_node(Node::Task(done));
+        graph.add_node(Node::Task(make_task("a", 3.0, &["done"])));
+        graph.add_node(Node::Task(make_task("b", 2.0, &["a"])));
+        let mut later = make_task("later", 1.0, &["done"]);
+        later.not_before = Some("2026-03-05T08:00:00Z".to_string());
+        graph.add_node(Node::Task(later));
+
+        let mut memo = HashMap::new();
+        let mut visiting = HashSet::new();
+        let mut span = |id: &str| schedule(id, &graph, now(), &mut memo, &mut visiting).unwrap();
+
+        let d = span("done");
+        assert_eq!(d.start.to_rfc3339(), "2026-03-01T10:00:00+00:00");
+        assert_eq!(d.end.to_rfc3339(), "2026-03-01T13:30:00+00:00");
+        let a = span("a");
+        assert_eq!(a.start, now());
+        assert_eq!(a.end, now() + Duration::hours(3));
+        let b = span("b");
+        assert_eq!(b.start, a.end);
+        assert_eq!(b.end, a.end + Duration::hours(2));
+        assert_eq!(
+            span("later").start.to_rfc3339(),
+            "2026-03-05T08:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_schedule_tolerates_cycles() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("x", 1.0, &["y"])));
+        graph.add_node(Node::Task(make_task("y", 1.0, &["x"])));
+        let mut memo = HashMap::new();
+        let mut visiting = HashSet::new();
+        let x = schedule("x", &graph, now(), &mut memo, &mut visiting).unwrap();
+        let y = schedule("y", &graph, now(), &mut memo, &mut visiting).unwrap();
+        assert_eq!(x.start, y.end);
+    }
+
+    #[test]
+    fn test_generate_gantt_sections_and_tags() {
+        let mut graph = WorkGraph::new();
+        let mut running = make_task("run.me", 1.0, &[]);
+        running.status = Status::InProgress;
+        running.title = "Build: stage 1".to_string();
+        running.started_at = Some("2026-03-02T08:00:00Z".to_string());
+        graph.add_node(Node::Task(running));
+        graph.add_node(Node::Task(make_task("next", 2.0, &["run.me"])));
+
+        let tasks: Vec<&Task> = graph.tasks().collect();
+        let critical: HashSet<String> = ["next".to_string()].into_iter().collect();
+        let out = generate_gantt(&graph, &tasks, &critical, now());
+
+        assert!(out.starts_with("gantt\n"));
+        assert!(out.contains("  section In progress\n  Build  stage 1 :active, run_me, 2026-03-02T08:00, 2026-03-02T09:00"));
+        assert!(out.contains(
+            "  section Planned\n  Task next :crit, next, 2026-03-02T09:00, 2026-03-02T11:00"
+        ));
+        assert!(out.find("In progress").unwrap() < out.find("Planned").unwrap());
+    }
+}
@@ -1,5 +1,6 @@
 pub(crate) mod ascii;
 mod dot;
+mod gantt;
 mod graph;
 
 use anyhow::Result;
@@ -9,6 +10,7 @@ use workgraph::graph::{Status, Task, TokenUsage, WorkGraph, parse_token_usage_li
 use workgraph::messages::message_stats_pair_cached;
 
 // Re-export public API
+pub(crate) use dot::{generate_mermaid, mermaid_id};
 pub use graph::{generate_graph, generate_graph_with_overrides};
 
 /// Rich annotation info for a parent task, carrying both the display text
@@ -57,6 +59,7 @@ pub enum OutputFormat {
     Mermaid,
     Ascii,
     Graph,
+    Gantt,
 }
 
 impl std::str::FromStr for OutputFormat {
@@ -68,8 +71,9 @@ impl std::str::FromStr for OutputFormat {
             "mermaid" => Ok(OutputFormat::Mermaid),
             "ascii" | "dag" => Ok(OutputFormat::Ascii),
             "graph" => Ok(OutputFormat::Graph),
+            "gantt" => Ok(OutputFormat::Gantt),
             _ => Err(format!(
-                "Unknown format: {}. Use 'dot', 'mermaid', 'ascii', or 'graph'.",
+                "Unknown format: {}. Use 'dot', 'mermaid', 'ascii', 'graph', or 'gantt'.",
                 s
             )),
         }
@@ -793,6 +797,12 @@ pub fn generate_viz_output_from_graph(
                     &agency_token_usage,
                     &context_ids,
                 ),
+                OutputFormat::Gantt => gantt::generate_gantt(
+                    graph,
+                    &tasks_to_show,
+                    &critical_path_set,
+                    chrono::Utc::now(),
+                ),
                 OutputFormat::Ascii => unreachable!(),
             };
             VizOutput {
@@ -1034,6 +1044,10 @@ mod tests {
             OutputFormat::Mermaid
         );
         assert_eq!("DOT".parse::<OutputFormat>().unwrap(), OutputFormat::Dot);
+        assert_eq!(
+            "gantt".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Gantt
+        );
         assert!("invalid".parse::<OutputFormat>().is_err());
     }
 
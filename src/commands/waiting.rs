@@ -0,0 +1,97 @@
+//! `wg waiting` — tasks blocked on a team or vendor outside the project.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::Path;
+use workgraph::external_blocker::{self, WaitingTask};
+use workgraph::notify::config::NotifyConfig;
+
+#[derive(Debug, Serialize)]
+struct WaitingOutput {
+    waiting: Vec<WaitingTask>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reminders_sent: Option<Vec<WaitingTask>>,
+}
+
+pub fn run(dir: &Path, notify: bool, json: bool) -> Result<()> {
+    let (graph, path) = super::load_workgraph(dir)?;
+    let config = NotifyConfig::load(dir.parent())?;
+    let waiting = external_blocker::waiting(
+        &graph,
+        Utc::now(),
+        external_blocker::follow_up_secs(config.as_ref()),
+    );
+
+    let reminders_sent = if notify {
+        let sent = external_blocker::run_sweep(dir, &path, config.as_ref(), false)?;
+        if !sent.is_empty() {
+            super::notify_graph_changed(dir);
+        }
+        Some(sent)
+    } else {
+        None
+    };
+
+    if json {
+        let output = WaitingOutput {
+            waiting,
+            reminders_sent,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if waiting.is_empty() {
+        println!("No tasks waiting on external blockers");
+    } else {
+        println!("Waiting on external blockers ({}):", waiting.len());
+        for w in &waiting {
+            println!(
+                "  {} [{}] waiting {} — {}",
+                w.task_id,
+                w.status,
+                workgraph::format_duration(w.waiting_secs, true),
+                w.reason
+            );
+            let mut details = Vec::new();
+            if let Some(ref contact) = w.contact {
+                details.push(format!("contact: {}", contact));
+            }
+            if let Some(ref expected) = w.expected {
+                let late = match w.late_secs {
+                    Some(secs) if secs > 0 => {
+                        format!(" ({} late)", workgraph::format_duration(secs, true))
+                    }
+                    _ => String::new(),
+                };
+                details.push(format!("expected: {}{}", short_time(expected), late));
+            }
+            if let Some(ref next) = w.next_reminder {
+                details.push(format!("next follow-up: {}", short_time(next)));
+            }
+            if !details.is_empty() {
+                println!("    {}", details.join("  "));
+            }
+        }
+    }
+
+    if let Some(ref sent) = reminders_sent {
+        if sent.is_empty() {
+            println!("\nReminders: none due");
+        } else {
+            println!("\nReminders sent:");
+            for w in sent {
+                println!("  - {}", w.task_id);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn short_time(ts: &str) -> String {
+    ts.parse::<DateTime<Utc>>()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| ts.to_string())
+}
@@ -1,10 +1,18 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use serde::Serialize;
 use std::collections::HashSet;
 use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime};
 
+use workgraph::graph::Status;
 use workgraph::provenance;
+use workgraph::query::is_blocker_satisfied;
+use workgraph::service::graph_watcher::{DEFAULT_DEBOUNCE_MS, GraphWatcher};
+
+/// Views `wg watch --view` can keep on screen.
+pub const VIEWS: &[&str] = &["list", "status", "blocked"];
 
 #[derive(Debug, Serialize)]
 pub struct WatchEvent {
@@ -196,6 +204,150 @@ pub fn run(
     }
 }
 
+/// Unfinished tasks waiting on at least one unfinished blocker, with the
+/// blockers they're waiting on.
+fn render_blocked(dir: &Path, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let mut blocked: Vec<(&str, &str, Vec<&str>)> = graph
+        .tasks()
+        .filter(|t| {
+            matches!(
+                t.status,
+                Status::Open | Status::Blocked | Status::Incomplete
+            )
+        })
+        .filter_map(|t| {
+            let waiting: Vec<&str> = t
+                .after
+                .iter()
+                .filter(|b| !is_blocker_satisfied(b, &graph, Some(dir)))
+                .map(String::as_str)
+                .collect();
+            (!waiting.is_empty() || t.status == Status::Blocked).then_some((
+                t.id.as_str(),
+                t.title.as_str(),
+                waiting,
+            ))
+        })
+        .collect();
+    blocked.sort();
+
+    if json {
+        let out: Vec<_> = blocked
+            .iter()
+            .map(|(id, title, waiting)| {
+                serde_json::json!({ "id": id, "title": title, "waiting_on": waiting })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&out)?);
+    } else if blocked.is_empty() {
+        println!("No blocked tasks");
+    } else {
+        println!("Blocked tasks:");
+        for (id, title, waiting) in &blocked {
+            if waiting.is_empty() {
+                println!("  {} - {}", id, title);
+            } else {
+                println!("  {} - {} (waiting on {})", id, title, waiting.join(", "));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn render_view(dir: &Path, view: &str, json: bool) -> Result<()> {
+    match view {
+        "list" => super::list::run(dir, None, false, &[], None, false, None, None, json, false),
+        "status" => super::status::run(dir, json, false),
+        "blocked" => render_blocked(dir, json),
+        other => bail!(
+            "Unknown view '{}'. Available views: {}",
+            other,
+            VIEWS.join(", ")
+        ),
+    }
+}
+
+fn graph_mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Re-render `view` whenever the graph changes, instead of re-running it on
+/// a timer. Changes are picked up from filesystem notifications; where those
+/// aren't available the graph file's mtime is polled (every
+/// `WG_WATCH_POLL_MS`, default 500ms) and the view is only re-rendered when it
+/// moves.
+pub fn run_view(dir: &Path, view: &str, json: bool) -> Result<()> {
+    if !VIEWS.contains(&view) {
+        bail!(
+            "Unknown view '{}'. Available views: {}",
+            view,
+            VIEWS.join(", ")
+        );
+    }
+    let graph_path = super::graph_path(dir);
+    let poll_ms: u64 = std::env::var("WG_WATCH_POLL_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(500);
+
+    let (tx, rx) = mpsc::channel();
+    let watcher = match GraphWatcher::start(
+        &graph_path,
+        Duration::from_millis(DEFAULT_DEBOUNCE_MS),
+        move || {
+            let _ = tx.send(());
+        },
+    ) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            eprintln!(
+                "Warning: cannot watch {} ({}); polling instead",
+                graph_path.display(),
+                e
+            );
+            None
+        }
+    };
+
+    let mut last_mtime = graph_mtime(&graph_path);
+    loop {
+        // JSON snapshots are emitted one after another; text views redraw
+        // the screen in place.
+        if !json {
+            print!("\x1b[2J\x1b[H");
+            println!(
+                "Every change: wg {}    {}\n",
+                view,
+                chrono::Local::now().format("%H:%M:%S")
+            );
+        }
+        if let Err(e) = render_view(dir, view, json) {
+            println!("Error: {:#}", e);
+        }
+        if std::io::stdout().flush().is_err() {
+            return Ok(());
+        }
+
+        if watcher.is_some() {
+            if rx.recv().is_err() {
+                return Ok(());
+            }
+            // Coalesce anything that queued up while rendering.
+            while rx.try_recv().is_ok() {}
+        } else {
+            loop {
+                std::thread::sleep(Duration::from_millis(poll_ms));
+                let mtime = graph_mtime(&graph_path);
+                if mtime != last_mtime {
+                    last_mtime = mtime;
+                    break;
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,6 +485,8 @@ mod tests {
             task_id: Some("my-task".to_string()),
             actor: Some("agent-1".to_string()),
             detail: serde_json::json!({"reason": "completed"}),
+            prev_hash: None,
+            hash: None,
             user: None,
         };
         let event = op_to_watch_event(&op).unwrap();
@@ -350,6 +504,8 @@ mod tests {
             task_id: None,
             actor: None,
             detail: serde_json::Value::Null,
+            prev_hash: None,
+            hash: None,
             user: None,
         };
         assert!(op_to_watch_event(&op).is_none());
@@ -387,4 +543,30 @@ mod tests {
         // skip_serializing_if means the key is absent entirely
         assert!(!parsed.as_object().unwrap().contains_key("task_id"));
     }
+
+    // ── views ──
+
+    #[test]
+    fn test_run_view_rejects_unknown_view() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let err = run_view(tmp.path(), "graph", false).unwrap_err();
+        assert!(err.to_string().contains("list, status, blocked"));
+    }
+
+    #[test]
+    fn test_render_blocked_view() {
+        use workgraph::graph::{Node, WorkGraph};
+        use workgraph::test_helpers::make_task_with_status;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task_with_status("a", "A", Status::Open)));
+        let mut b = make_task_with_status("b", "B", Status::Open);
+        b.after = vec!["a".to_string()];
+        graph.add_node(Node::Task(b));
+        workgraph::parser::save_graph(&graph, super::super::graph_path(tmp.path())).unwrap();
+
+        assert!(render_view(tmp.path(), "blocked", false).is_ok());
+        assert!(render_view(tmp.path(), "blocked", true).is_ok());
+    }
 }
@@ -0,0 +1,153 @@
+//! Webhook executor CLI entry point.
+//!
+//! `wg webhook-exec` hands a task to an external HTTP service. It is called
+//! by the spawn wrapper script when the executor type is "webhook".
+//!
+//! This command:
+//! 1. POSTs the task and its rendered prompt to `[webhook_executor].url`
+//! 2. Waits for a report: a callback stored by the dispatcher's webhook
+//!    listener, or a poll of the status URL
+//! 3. Records the report's message and artifacts, then marks the task done
+//!    or failed itself, so the wrapper finds it no longer in progress
+//!
+//! It also stops waiting when the task leaves in-progress some other way
+//! (`wg done`, `wg fail`, `wg retry` by hand).
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+use workgraph::config::Config;
+use workgraph::executor::webhook::{self, Client, Report, ReportStatus};
+use workgraph::graph::Status;
+
+/// How often to look for a stored callback report.
+const CALLBACK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+pub fn run(workgraph_dir: &Path, prompt_file: &str, task_id: &str) -> Result<()> {
+    let prompt = std::fs::read_to_string(prompt_file)
+        .with_context(|| format!("Failed to read prompt file: {}", prompt_file))?;
+    let config = Config::load_or_default(workgraph_dir);
+    let (graph, _) = super::load_workgraph(workgraph_dir)?;
+    let task = graph.get_task_or_err(task_id)?;
+
+    let callback_url = webhook::callback_url(&config, task_id);
+    // A report left over from an earlier attempt must not finish this one.
+    let _ = webhook::take_callback(workgraph_dir, task_id);
+
+    let client = Client::new(&config.webhook_executor)?;
+    let payload = webhook::payload(task, &prompt, callback_url.as_deref());
+    let accepted = match client.submit(&payload) {
+        Ok(accepted) => accepted,
+        Err(e) => {
+            let reason = format!("Webhook delivery failed: {:#}", e);
+            return finish(workgraph_dir, task_id, &failed(reason));
+        }
+    };
+    eprintln!("[webhook-exec] Delivered task '{}'", task_id);
+    if let Some(report) = accepted.as_ref().filter(|r| r.is_final()) {
+        return finish(workgraph_dir, task_id, report);
+    }
+
+    let poll_url = webhook::poll_url(&config.webhook_executor, accepted.as_ref(), task_id);
+    if poll_url.is_none() && callback_url.is_none() {
+        let reason = "Webhook executor has no way to learn the result: set \
+                      coordinator.webhook_listen, [webhook_executor] callback_url, \
+                      or [webhook_executor] poll_url"
+            .to_string();
+        return finish(workgraph_dir, task_id, &failed(reason));
+    }
+    if let Some(url) = &callback_url {
+        eprintln!("[webhook-exec] Waiting for a report at {}", url);
+    }
+
+    let poll_interval = Duration::from_secs(config.webhook_executor.poll_interval_secs.max(1));
+    let mut next_poll = Instant::now() + poll_interval;
+    loop {
+        std::thread::sleep(CALLBACK_CHECK_INTERVAL);
+
+        match webhook::take_callback(workgraph_dir, task_id) {
+            Ok(Some(report)) if report.is_final() => {
+                return finish(workgraph_dir, task_id, &report);
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("[webhook-exec] Ignoring callback: {:#}", e),
+        }
+
+        if Instant::now() < next_poll {
+            continue;
+        }
+        next_poll = Instant::now() + poll_interval;
+        if let Some(url) = &poll_url {
+            match client.poll(url) {
+                Ok(report) if report.is_final() => {
+                    return finish(workgraph_dir, task_id, &report);
+                }
+                Ok(_) => {}
+                // The service may be briefly unavailable; keep waiting
+                // until the executor timeout ends the attempt.
+                Err(e) => eprintln!("[webhook-exec] Poll failed: {:#}", e),
+            }
+        }
+        if !still_in_progress(workgraph_dir, task_id)? {
+            eprintln!(
+                "[webhook-exec] Task '{}' is no longer in progress; stopping",
+                task_id
+            );
+            return Ok(());
+        }
+    }
+}
+
+fn failed(reason: String) -> Report {
+    Report {
+        status: ReportStatus::Failed,
+        reason: Some(reason),
+        message: None,
+        artifacts: Vec::new(),
+        status_url: None,
+    }
+}
+
+fn still_in_progress(workgraph_dir: &Path, task_id: &str) -> Result<bool> {
+    let graph =
+        super::load_workgraph_subset(workgraph_dir, |_| HashSet::from([task_id.to_string()]))?;
+    Ok(graph
+        .get_task(task_id)
+        .is_some_and(|t| t.status == Status::InProgress))
+}
+
+/// Record the report on the task and resolve it.
+fn finish(workgraph_dir: &Path, task_id: &str, report: &Report) -> Result<()> {
+    let agent_id = std::env::var("WG_AGENT_ID").ok();
+    if let Some(message) = &report.message {
+        super::log::run_add(
+            workgraph_dir,
+            task_id,
+            message,
+            Some("webhook"),
+            agent_id.as_deref(),
+        )?;
+    }
+    for artifact in &report.artifacts {
+        super::artifact::run_add(workgraph_dir, task_id, artifact)?;
+    }
+    match report.status {
+        ReportStatus::Done => {
+            super::done::run(workgraph_dir, task_id, false, false, false, false, false)
+        }
+        ReportStatus::Failed | ReportStatus::Running => super::fail::run(
+            workgraph_dir,
+            task_id,
+            Some(
+                report
+                    .reason
+                    .as_deref()
+                    .unwrap_or("Webhook executor reported failure"),
+            ),
+            None,
+        ),
+    }
+}
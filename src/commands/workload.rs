@@ -1,11 +1,16 @@
 use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
 use std::collections::HashMap;
 use std::path::Path;
-use workgraph::agency;
-use workgraph::graph::Status;
+use workgraph::agency::{self, Agent};
+use workgraph::availability::AvailabilityCalendar;
+use workgraph::graph::{Status, Task, WorkGraph, is_system_task};
 use workgraph::query::ready_tasks;
 
+/// Hours assumed for a task without an estimate when projecting queues
+const DEFAULT_TASK_HOURS: f64 = 2.0;
+
 /// Information about an agent's workload
 #[derive(Debug, Serialize)]
 struct AgentWorkload {
@@ -17,6 +22,8 @@ struct AgentWorkload {
     capacity: Option<f64>,
     load_percent: Option<f64>,
     is_overloaded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unavailable_until: Option<String>,
 }
 
 /// JSON output structure
@@ -37,6 +44,9 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
     // Build agent workload map
     let mut agent_workloads: HashMap<String, AgentWorkload> = HashMap::new();
 
+    let calendar = AvailabilityCalendar::load_or_warn(dir);
+    let now = chrono::Utc::now();
+
     // Initialize with known agents from the agency directory
     let agents_dir = dir.join("agency").join("cache/agents");
     if let Ok(agents) = agency::load_all_agents(&agents_dir) {
@@ -52,6 +62,9 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
                     capacity: agent.capacity,
                     load_percent: None,
                     is_overloaded: false,
+                    unavailable_until: calendar
+                        .agent_unavailable_until(&agent, now)
+                        .map(|t| t.to_rfc3339()),
                 },
             );
         }
@@ -82,6 +95,9 @@ pub fn run(dir: &Path, json: bool) -> Result<()> {
                             capacity: None,
                             load_percent: None,
                             is_overloaded: false,
+                            unavailable_until: calendar
+                                .unavailable_until(&[agent_id], now)
+                                .map(|t| t.to_rfc3339()),
                         });
 
                 workload.assigned_count += 1;
@@ -154,6 +170,14 @@ fn print_human_readable(
         for agent in agents {
             println!("  {} ({})", agent.name, agency::short_hash(&agent.id));
 
+            if let Some(ref until) = agent.unavailable_until {
+                let until = until
+                    .parse::<chrono::DateTime<chrono::Utc>>()
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|_| until.clone());
+                println!("    Unavailable until {}", until);
+            }
+
             // Assigned tasks and hours
             let hours_str = format!("{:.0}h estimated", agent.assigned_hours);
             println!(
@@ -205,6 +229,280 @@ fn print_human_readable(
     }
 }
 
+/// A task in an agent's projected queue
+#[derive(Debug, Serialize)]
+struct QueuedTask {
+    id: String,
+    title: String,
+    hours: f64,
+    /// Fraction of the task attributed to this agent (1.0 when assigned;
+    /// split evenly across every agent whose capabilities match otherwise)
+    share: f64,
+    likely: bool,
+}
+
+/// Projected load for one week
+#[derive(Debug, Serialize)]
+struct WeekProjection {
+    week: usize,
+    starts: String,
+    capacity_hours: f64,
+    planned_hours: f64,
+    /// Fraction of the week the agent is unavailable
+    away: f64,
+}
+
+/// Queue projection for one agent
+#[derive(Debug, Serialize)]
+struct AgentForecast {
+    id: String,
+    name: String,
+    capacity: Option<f64>,
+    assigned_hours: f64,
+    likely_hours: f64,
+    unestimated_tasks: usize,
+    queue: Vec<QueuedTask>,
+    weeks: Vec<WeekProjection>,
+    /// Hours still queued after the last projected week
+    backlog_hours: f64,
+    clears_in_week: Option<usize>,
+    overcommitted: bool,
+}
+
+/// `wg workload forecast`: project each agent's queue over the coming weeks.
+pub fn run_forecast(dir: &Path, agent: Option<&str>, weeks: usize, json: bool) -> Result<()> {
+    let (graph, _path) = super::load_workgraph(dir)?;
+    let agents_dir = dir.join("agency").join("cache/agents");
+    let agents = agency::load_all_agents_or_warn(&agents_dir);
+
+    let only = match agent {
+        Some(prefix) => Some(agency::find_agent_by_prefix(&agents_dir, prefix)?.id),
+        None => None,
+    };
+
+    let calendar = AvailabilityCalendar::load_or_warn(dir);
+    let forecasts: Vec<AgentForecast> =
+        forecast_agents(&graph, &agents, &calendar, Utc::now(), weeks)
+            .into_iter()
+            .filter(|f| only.as_ref().is_none_or(|id| &f.id == id))
+            .collect();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&forecasts)?);
+    } else {
+        print_forecast(&forecasts, weeks);
+    }
+    Ok(())
+}
+
+fn is_assigned_to(task: &Task, agent: &Agent) -> bool {
+    task.agent.as_deref() == Some(agent.id.as_str())
+        || task
+            .assigned
+            .as_deref()
+            .is_some_and(|a| a == agent.id || a == agent.name)
+}
+
+fn task_hours(task: &Task) -> Option<f64> {
+    task.estimate.as_ref().and_then(|e| e.hours)
+}
+
+/// Project every agent's queue: assigned unfinished work plus an even share
+/// of each unassigned task its capabilities match, drained week by week at
+/// the agent's capacity (reduced by availability-calendar downtime).
+fn forecast_agents(
+    graph: &WorkGraph,
+    agents: &[Agent],
+    calendar: &AvailabilityCalendar,
+    now: DateTime<Utc>,
+    weeks: usize,
+) -> Vec<AgentForecast> {
+    let open: Vec<&Task> = graph
+        .tasks()
+        .filter(|t| !t.status.is_terminal() && !is_system_task(&t.id))
+        .collect();
+
+    // Unassigned tasks and the agents whose capabilities could pick them up
+    let mut likely: Vec<(&Task, Vec<&str>)> = Vec::new();
+    for task in open
+        .iter()
+        .filter(|t| t.agent.is_none() && t.assigned.is_none() && !t.skills.is_empty())
+    {
+        let matching: Vec<&str> = agents
+            .iter()
+            .filter(|a| a.capabilities.iter().any(|c| task.skills.contains(c)))
+            .map(|a| a.id.as_str())
+            .collect();
+        if !matching.is_empty() {
+            likely.push((task, matching));
+        }
+    }
+
+    let mut forecasts: Vec<AgentForecast> = agents
+        .iter()
+        .map(|agent| {
+            let mut queue = Vec::new();
+            let mut unestimated_tasks = 0;
+            for task in open.iter().filter(|t| is_assigned_to(t, agent)) {
+                let hours = task_hours(task).unwrap_or_else(|| {
+                    unestimated_tasks += 1;
+                    DEFAULT_TASK_HOURS
+                });
+                queue.push(QueuedTask {
+                    id: task.id.clone(),
+                    title: task.title.clone(),
+                    hours,
+                    share: 1.0,
+                    likely: false,
+                });
+            }
+            for (task, matching) in likely
+                .iter()
+                .filter(|(_, m)| m.contains(&agent.id.as_str()))
+            {
+                let hours = task_hours(task).unwrap_or_else(|| {
+                    unestimated_tasks += 1;
+                    DEFAULT_TASK_HOURS
+                });
+                queue.push(QueuedTask {
+                    id: task.id.clone(),
+                    title: task.title.clone(),
+                    hours,
+                    share: 1.0 / matching.len() as f64,
+                    likely: true,
+                });
+            }
+
+            let assigned_hours: f64 = queue.iter().filter(|q| !q.likely).map(|q| q.hours).sum();
+            let likely_hours: f64 = queue
+                .iter()
+                .filter(|q| q.likely)
+                .map(|q| q.hours * q.share)
+                .sum();
+
+            let mut remaining = assigned_hours + likely_hours;
+            let mut week_rows = Vec::new();
+            let mut clears_in_week = (remaining == 0.0).then_some(0);
+            if let Some(capacity) = agent.capacity.filter(|c| *c > 0.0) {
+                for week in 0..weeks {
+                    let start = now + Duration::weeks(week as i64);
+                    let end = start + Duration::weeks(1);
+                    let away = calendar.unavailable_fraction(
+                        &[agent.id.as_str(), agent.name.as_str()],
+                        start,
+                        end,
+                    );
+                    let capacity_hours = capacity * (1.0 - away);
+                    let planned_hours = remaining.min(capacity_hours);
+                    remaining -= planned_hours;
+                    if remaining <= 0.0 && clears_in_week.is_none() {
+                        clears_in_week = Some(week + 1);
+                    }
+                    week_rows.push(WeekProjection {
+                        week: week + 1,
+                        starts: start.format("%Y-%m-%d").to_string(),
+                        capacity_hours,
+                        planned_hours,
+                        away,
+                    });
+                }
+            }
+
+            let has_capacity = !week_rows.is_empty();
+            AgentForecast {
+                id: agent.id.clone(),
+                name: agent.name.clone(),
+                capacity: agent.capacity,
+                assigned_hours,
+                likely_hours,
+                unestimated_tasks,
+                queue,
+                weeks: week_rows,
+                backlog_hours: if has_capacity {
+                    remaining.max(0.0)
+                } else {
+                    0.0
+                },
+                clears_in_week,
+                overcommitted: has_capacity && remaining > 0.0,
+            }
+        })
+        .collect();
+
+    // Most overcommitted first, then by queue size
+    forecasts.sort_by(|a, b| {
+        b.backlog_hours
+            .partial_cmp(&a.backlog_hours)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| {
+                (b.assigned_hours + b.likely_hours)
+                    .partial_cmp(&(a.assigned_hours + a.likely_hours))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    forecasts
+}
+
+fn print_forecast(forecasts: &[AgentForecast], weeks: usize) {
+    if forecasts.is_empty() {
+        println!("No agents defined.");
+        return;
+    }
+
+    println!("Workload Forecast (next {} weeks):\n", weeks);
+    for f in forecasts {
+        println!("  {} ({})", f.name, agency::short_hash(&f.id));
+        println!(
+            "    Queue: {:.0}h assigned + {:.0}h likely ({} tasks)",
+            f.assigned_hours,
+            f.likely_hours,
+            f.queue.len()
+        );
+        if f.unestimated_tasks > 0 {
+            println!(
+                "    ({} unestimated, counted as {:.0}h each)",
+                f.unestimated_tasks, DEFAULT_TASK_HOURS
+            );
+        }
+
+        if f.weeks.is_empty() {
+            println!("    No capacity set — cannot project weeks");
+            println!();
+            continue;
+        }
+        for w in &f.weeks {
+            let load = if w.capacity_hours > 0.0 {
+                format!("{:>3.0}%", w.planned_hours / w.capacity_hours * 100.0)
+            } else {
+                " --".to_string()
+            };
+            let away = if w.away > 0.0 {
+                format!("  (away {:.0}%)", w.away * 100.0)
+            } else {
+                String::new()
+            };
+            println!(
+                "    Week {} ({}): {:>5.1}h / {:>5.1}h  {}{}",
+                w.week, w.starts, w.planned_hours, w.capacity_hours, load, away
+            );
+        }
+        if f.overcommitted {
+            println!(
+                "    [WARNING: overcommitted] {:.0}h won't fit in the next {} weeks",
+                f.backlog_hours, weeks
+            );
+        } else if let Some(week) = f.clears_in_week {
+            if week == 0 {
+                println!("    Queue empty");
+            } else {
+                println!("    Queue clears in week {}", week);
+            }
+        }
+        println!();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,6 +536,8 @@ mod tests {
             deployment_history: vec![],
             attractor_weight: 0.5,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         }
     }
 
@@ -256,6 +556,7 @@ mod tests {
                     capacity: agent.capacity,
                     load_percent: None,
                     is_overloaded: false,
+                    unavailable_until: None,
                 },
             );
         }
@@ -280,6 +581,7 @@ mod tests {
                         capacity: None,
                         load_percent: None,
                         is_overloaded: false,
+                        unavailable_until: None,
                     });
                 workload.assigned_count += 1;
                 if let Some(ref estimate) = task.estimate
@@ -526,4 +828,80 @@ mod tests {
         assert_eq!(implicit.assigned_hours, 8.0);
         assert_eq!(implicit.name, "implicit-agent");
     }
+
+    #[test]
+    fn test_forecast_flags_overcommitted_agent() {
+        let mut graph = WorkGraph::new();
+        let mut t1 = make_task("t1", "Big assigned task");
+        t1.agent = Some("agent-a".to_string());
+        t1.estimate = Some(Estimate {
+            hours: Some(50.0),
+            cost: None,
+        });
+        graph.add_node(Node::Task(t1));
+
+        let mut t2 = make_task("t2", "Shared likely task");
+        t2.skills = vec!["rust".to_string()];
+        t2.estimate = Some(Estimate {
+            hours: Some(10.0),
+            cost: None,
+        });
+        graph.add_node(Node::Task(t2));
+
+        let mut a = make_agent("agent-a", "Alpha", Some(20.0));
+        a.capabilities = vec!["rust".to_string()];
+        let mut b = make_agent("agent-b", "Beta", Some(20.0));
+        b.capabilities = vec!["rust".to_string()];
+
+        let forecasts = forecast_agents(
+            &graph,
+            &[a, b],
+            &AvailabilityCalendar::default(),
+            Utc::now(),
+            2,
+        );
+        let alpha = forecasts.iter().find(|f| f.id == "agent-a").unwrap();
+        assert_eq!(alpha.assigned_hours, 50.0);
+        assert_eq!(alpha.likely_hours, 5.0);
+        assert!(alpha.overcommitted);
+        assert_eq!(alpha.backlog_hours, 15.0);
+        // Overcommitted agents sort first
+        assert_eq!(forecasts[0].id, "agent-a");
+
+        let beta = forecasts.iter().find(|f| f.id == "agent-b").unwrap();
+        assert!(!beta.overcommitted);
+        assert_eq!(beta.clears_in_week, Some(1));
+    }
+
+    #[test]
+    fn test_forecast_reduces_capacity_for_downtime() {
+        let mut graph = WorkGraph::new();
+        let mut t1 = make_task("t1", "Assigned");
+        t1.agent = Some("agent-a".to_string());
+        t1.estimate = Some(Estimate {
+            hours: Some(15.0),
+            cost: None,
+        });
+        graph.add_node(Node::Task(t1));
+
+        let now = Utc::now();
+        let mut calendar = AvailabilityCalendar::default();
+        calendar.add(
+            "agent-a",
+            now,
+            now + Duration::weeks(1),
+            Some("maintenance"),
+        );
+
+        let forecasts = forecast_agents(
+            &graph,
+            &[make_agent("agent-a", "Alpha", Some(10.0))],
+            &calendar,
+            now,
+            2,
+        );
+        assert_eq!(forecasts[0].weeks[0].capacity_hours, 0.0);
+        assert_eq!(forecasts[0].weeks[1].planned_hours, 10.0);
+        assert!(forecasts[0].overcommitted);
+    }
 }
@@ -437,6 +437,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         }
     }
 
@@ -6,6 +6,7 @@
 //! Sensitive credentials (like Matrix login) are stored separately in
 //! `~/.config/workgraph/matrix.toml` to avoid accidentally committing secrets.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fs;
@@ -18,7 +19,7 @@ pub const CLAUDE_SONNET_MODEL_ID: &str = "sonnet";
 pub const CLAUDE_OPUS_MODEL_ID: &str = "opus";
 
 /// Main configuration structure
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct Config {
     /// Agent configuration
     #[serde(default)]
@@ -44,6 +45,74 @@ pub struct Config {
     #[serde(default)]
     pub log: LogConfig,
 
+    /// Data retention policy (maximum ages for logs, runs, provenance, ...)
+    #[serde(default, skip_serializing_if = "RetentionConfig::is_default")]
+    pub retention: RetentionConfig,
+
+    /// Masking of emails, tokens, and custom patterns in persisted logs
+    #[serde(default, skip_serializing_if = "ScrubConfig::is_default")]
+    pub scrub: ScrubConfig,
+
+    /// Dispatch adjustments learned from function run summaries
+    #[serde(default, skip_serializing_if = "LearningConfig::is_default")]
+    pub learning: LearningConfig,
+
+    /// Follow-up tasks generated from coverage report artifacts
+    #[serde(default, skip_serializing_if = "CoverageConfig::is_default")]
+    pub coverage: CoverageConfig,
+
+    /// Cache for one-shot LLM responses (evaluation, triage, bench, ...)
+    #[serde(default, skip_serializing_if = "ResponseCacheConfig::is_default")]
+    pub response_cache: ResponseCacheConfig,
+
+    /// Daily plans (`wg plan today`) and their morning Matrix posts
+    #[serde(default, skip_serializing_if = "DayPlanConfig::is_default")]
+    pub day_plan: DayPlanConfig,
+
+    /// Thresholds for anomaly detection (`wg anomalies`)
+    #[serde(default, skip_serializing_if = "AnomalyConfig::is_default")]
+    pub anomaly: AnomalyConfig,
+
+    /// How task skills are matched against agent capabilities
+    #[serde(default, skip_serializing_if = "SkillMatchingConfig::is_default")]
+    pub skill_matching: SkillMatchingConfig,
+
+    /// Resource pools and their concurrency limits. Tasks claim a slot by
+    /// listing the pool in `requires`; the dispatcher won't run more tasks
+    /// at once than a pool allows.
+    ///
+    /// ```toml
+    /// [pools]
+    /// gpu = 2
+    /// staging-db = 1
+    /// ```
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub pools: std::collections::BTreeMap<String, u32>,
+
+    /// Default spend limits (USD per run) for tasks carrying a tag. A task's
+    /// own `max_cost` wins; otherwise the strictest matching tag applies.
+    ///
+    /// ```toml
+    /// [cost_caps]
+    /// research = 5.0
+    /// chore = 0.50
+    /// ```
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub cost_caps: std::collections::BTreeMap<String, f64>,
+
+    /// Currency and pricing for monthly usage reports (`wg cost --month`)
+    #[serde(default, skip_serializing_if = "CostReportConfig::is_default")]
+    pub cost_report: CostReportConfig,
+
+    /// Working calendars (hours, days, holidays, UTC offset) used to turn
+    /// estimated hours into dates for human-executed work. See
+    /// [`crate::working_hours`].
+    #[serde(
+        default,
+        skip_serializing_if = "crate::working_hours::WorkingHoursConfig::is_default"
+    )]
+    pub working_hours: crate::working_hours::WorkingHoursConfig,
+
     /// Replay configuration
     #[serde(default)]
     pub replay: ReplayConfig,
@@ -125,6 +194,10 @@ pub struct Config {
     #[serde(default)]
     pub native_executor: NativeExecutorConfig,
 
+    /// Webhook executor: where `executor = "webhook"` sends tasks
+    #[serde(default, skip_serializing_if = "WebhookExecutorConfig::is_default")]
+    pub webhook_executor: WebhookExecutorConfig,
+
     /// MCP (Model Context Protocol) server configuration. Each entry
     /// declares one server that will be spawned when a WGNEX session
     /// starts; its tools are auto-discovered and merged into the
@@ -132,6 +205,18 @@ pub struct Config {
     #[serde(default)]
     pub mcp: McpConfig,
 
+    /// Saved task queries, run with `wg view <name>` or `--view <name>`.
+    /// Values use the `wg query` filter language plus `sort:` and `limit:`
+    /// directives.
+    ///
+    /// ```toml
+    /// [views]
+    /// my-backlog = "status=open tag:backend sort:priority"
+    /// recent = "status=done sort:-completed limit:20"
+    /// ```
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub views: BTreeMap<String, String>,
+
     /// True when `agent.model` was explicitly set in local config.
     /// Used by `resolve_model_for_role` to skip tier defaults in favor of agent.model.
     #[serde(skip)]
@@ -147,7 +232,7 @@ pub struct Config {
 /// args = ["-y", "@modelcontextprotocol/server-filesystem", "/workspace"]
 /// enabled = true
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct McpConfig {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<McpServerEntry>,
@@ -155,7 +240,7 @@ pub struct McpConfig {
 
 /// One server declaration. Mirrors the wire shape expected by
 /// `executor::native::mcp::McpServerConfig`; conversion is trivial.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct McpServerEntry {
     pub name: String,
     pub command: String,
@@ -172,7 +257,7 @@ fn default_mcp_enabled() -> bool {
 }
 
 /// Chat archive rotation configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ChatConfig {
     /// Maximum size in bytes before rotating the active chat file (default: 1MB).
     #[serde(default = "default_chat_max_file_size")]
@@ -213,7 +298,7 @@ impl Default for ChatConfig {
 }
 
 /// Native executor configuration.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct NativeExecutorConfig {
     /// Web access settings (search + fetch).
     #[serde(default)]
@@ -246,7 +331,7 @@ pub struct NativeExecutorConfig {
 /// [native_executor.permissions]
 /// deny_tools = ["bash", "write_file"]
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct ToolPermissionsConfig {
     /// Tools that must NOT execute. A call to a denied tool returns
     /// `ToolOutput::error("permission denied: ...")` to the agent,
@@ -272,7 +357,7 @@ impl ToolPermissionsConfig {
 }
 
 /// Web access configuration for the native executor.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NativeWebConfig {
     /// API key for search backend (Serper, Brave, etc.). Supports env var syntax: "${VAR}".
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -314,7 +399,7 @@ impl Default for NativeWebConfig {
 }
 
 /// Background task configuration for the native executor.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NativeBackgroundConfig {
     /// Maximum concurrent background tasks per agent.
     #[serde(default = "default_max_background_tasks")]
@@ -342,7 +427,7 @@ impl Default for NativeBackgroundConfig {
 }
 
 /// Delegate (in-process subtask) configuration for the native executor.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NativeDelegateConfig {
     /// Maximum turns for delegated sub-agents.
     #[serde(default = "default_delegate_max_turns")]
@@ -367,7 +452,7 @@ impl Default for NativeDelegateConfig {
 }
 
 /// OpenRouter cost cap and monitoring configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct OpenRouterConfig {
     /// Global project cost cap in USD
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -415,7 +500,7 @@ pub struct OpenRouterConfig {
 }
 
 /// Cost cap enforcement behavior
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum CapBehavior {
     /// Fail the task/session immediately
@@ -471,7 +556,7 @@ impl Default for OpenRouterConfig {
 }
 
 /// Help display configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HelpConfig {
     /// Command ordering: "usage" (default), "alphabetical", or "curated"
     #[serde(default = "default_help_ordering")]
@@ -491,7 +576,7 @@ impl Default for HelpConfig {
 }
 
 /// Log configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LogConfig {
     /// Rotation threshold in bytes (default: 10 MB)
     #[serde(default = "default_rotation_threshold")]
@@ -510,8 +595,694 @@ impl Default for LogConfig {
     }
 }
 
+/// Data retention policy, enforced by the daemon and `wg retention`.
+///
+/// Each category takes a maximum age (`90d`, `12w`, `48h`; a bare number is
+/// days). Unset categories are kept forever.
+///
+/// ```toml
+/// [retention]
+/// runs = "90d"
+/// provenance = "365d"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RetentionConfig {
+    /// Archived agent logs (`log/agents/<task>/<timestamp>/`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logs: Option<String>,
+    /// Replay run snapshots (`runs/<run-id>/`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runs: Option<String>,
+    /// Rotated operation logs (`log/*.jsonl.zst`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<String>,
+    /// Monthly task archive partitions (`archive/YYYY-MM.jsonl`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archives: Option<String>,
+    /// Command usage telemetry (`usage.log`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<String>,
+    /// Hours between automatic retention passes in the daemon (0 = never)
+    #[serde(default = "default_retention_interval_hours")]
+    pub interval_hours: u64,
+}
+
+fn default_retention_interval_hours() -> u64 {
+    24
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            logs: None,
+            runs: None,
+            provenance: None,
+            archives: None,
+            telemetry: None,
+            interval_hours: default_retention_interval_hours(),
+        }
+    }
+}
+
+impl RetentionConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Configured `(category, max age)` pairs.
+    pub fn policies(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("logs", &self.logs),
+            ("runs", &self.runs),
+            ("provenance", &self.provenance),
+            ("archives", &self.archives),
+            ("telemetry", &self.telemetry),
+        ]
+        .into_iter()
+        .filter_map(|(name, age)| age.as_deref().map(|a| (name, a)))
+        .collect()
+    }
+}
+
+/// Scrubbing of sensitive text before it is persisted (task log entries,
+/// archived agent output, function run summaries). Off by default.
+///
+/// ```toml
+/// [scrub]
+/// enabled = true
+/// patterns = ["ACME-[0-9]{6}"]
+/// words = ["project-nightingale"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ScrubConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Mask email addresses
+    #[serde(default = "default_true")]
+    pub emails: bool,
+    /// Mask API keys, access tokens, and `SECRET=value` assignments
+    #[serde(default = "default_true")]
+    pub tokens: bool,
+    /// Extra regular expressions to mask
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+    /// Literal words or phrases to mask (case-insensitive)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<String>,
+    /// Text that replaces each match
+    #[serde(default = "default_scrub_replacement")]
+    pub replacement: String,
+}
+
+fn default_scrub_replacement() -> String {
+    "[redacted]".to_string()
+}
+
+impl Default for ScrubConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            emails: true,
+            tokens: true,
+            patterns: Vec::new(),
+            words: Vec::new(),
+            replacement: default_scrub_replacement(),
+        }
+    }
+}
+
+impl ScrubConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Webhook executor: tasks dispatched with `executor = "webhook"` are
+/// POSTed as JSON to `url`, and the task stays in progress until the
+/// receiving system reports back, either by calling the dispatcher's
+/// webhook listener at `POST /callbacks/<task-id>` or through `poll_url`.
+///
+/// ```toml
+/// [webhook_executor]
+/// url = "https://hooks.zapier.com/hooks/catch/123/abc"
+/// secret = "${WG_WEBHOOK_EXECUTOR_SECRET}"
+/// headers = { Authorization = "Bearer ${ZAPIER_TOKEN}" }
+/// ```
+///
+/// Header values and `secret` expand `${VAR}` from the environment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookExecutorConfig {
+    /// Endpoint that receives the task payload
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Extra request headers
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// HMAC-SHA256 secret; when set, the payload carries
+    /// `X-Webhook-Signature: sha256=<hex>` of the body
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// Base URL the receiver calls back on, ending before `/callbacks/<id>`.
+    /// Default: `http://<dispatcher.webhook_listen>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
+    /// Status URL to poll instead of (or as well as) waiting for a callback.
+    /// `{{task_id}}` is replaced with the task ID. A `status_url` in the
+    /// POST response takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll_url: Option<String>,
+    /// Seconds between polls of the status URL
+    #[serde(default = "default_webhook_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+}
+
+fn default_webhook_poll_interval_secs() -> u64 {
+    30
+}
+
+impl Default for WebhookExecutorConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            headers: BTreeMap::new(),
+            secret: None,
+            callback_url: None,
+            poll_url: None,
+            poll_interval_secs: default_webhook_poll_interval_secs(),
+        }
+    }
+}
+
+impl WebhookExecutorConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Coordinator learning loop: function templates and skills whose recent
+/// runs score poorly or fail often are dispatched on a stronger tier and
+/// with fewer concurrent agents. Off by default. See `learning`.
+///
+/// Overrides are keyed like adjustments (`<function>/<template>` or
+/// `skill:<name>`) and win over whatever was learned:
+///
+/// ```toml
+/// [learning]
+/// enabled = true
+///
+/// [learning.overrides."impl-feature/implement"]
+/// tier = "standard"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LearningConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Runs needed before a key is judged
+    #[serde(default = "default_learning_min_runs")]
+    pub min_runs: usize,
+    /// Most recent runs per function considered
+    #[serde(default = "default_learning_window")]
+    pub window: usize,
+    /// Mean evaluation score below which a key is adjusted
+    #[serde(default = "default_learning_min_score")]
+    pub min_score: f64,
+    /// Failure rate above which a key is adjusted
+    #[serde(default = "default_learning_max_failure_rate")]
+    pub max_failure_rate: f64,
+    /// Tier used for adjusted keys
+    #[serde(default = "default_learning_escalate_to")]
+    pub escalate_to: String,
+    /// Concurrent agents allowed per adjusted key (0 = no limit)
+    #[serde(default = "default_learning_max_concurrent")]
+    pub max_concurrent: usize,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub overrides: BTreeMap<String, LearningOverride>,
+}
+
+/// Manual adjustment for one learning key.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LearningOverride {
+    /// Never adjust this key
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub disabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    /// Exact model, takes precedence over `tier`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent: Option<usize>,
+}
+
+fn default_learning_min_runs() -> usize {
+    3
+}
+
+fn default_learning_window() -> usize {
+    10
+}
+
+fn default_learning_min_score() -> f64 {
+    0.6
+}
+
+fn default_learning_max_failure_rate() -> f64 {
+    0.5
+}
+
+fn default_learning_escalate_to() -> String {
+    "premium".to_string()
+}
+
+fn default_learning_max_concurrent() -> usize {
+    1
+}
+
+impl Default for LearningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_runs: default_learning_min_runs(),
+            window: default_learning_window(),
+            min_score: default_learning_min_score(),
+            max_failure_rate: default_learning_max_failure_rate(),
+            escalate_to: default_learning_escalate_to(),
+            max_concurrent: default_learning_max_concurrent(),
+            overrides: BTreeMap::new(),
+        }
+    }
+}
+
+impl LearningConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Coverage-driven follow-up task generation.
+///
+/// When a task completes with a coverage report artifact (LCOV, Cobertura
+/// XML or `cargo llvm-cov --json`), one follow-up task is created per file
+/// whose line coverage is below `threshold`, lowest coverage first. Files
+/// under `min_lines` instrumented lines are ignored. Off by default.
+///
+/// ```toml
+/// [coverage]
+/// followups = true
+/// threshold = 70.0
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CoverageConfig {
+    #[serde(default)]
+    pub followups: bool,
+    /// Line coverage percentage below which a file gets a follow-up
+    #[serde(default = "default_coverage_threshold")]
+    pub threshold: f64,
+    /// Files with fewer instrumented lines are skipped
+    #[serde(default = "default_coverage_min_lines")]
+    pub min_lines: u64,
+    /// Follow-ups created per completed task (0 = no limit)
+    #[serde(default = "default_coverage_max_followups")]
+    pub max_followups: usize,
+    /// Tag added to every follow-up
+    #[serde(default = "default_coverage_tag")]
+    pub tag: String,
+}
+
+fn default_coverage_threshold() -> f64 {
+    60.0
+}
+
+fn default_coverage_min_lines() -> u64 {
+    10
+}
+
+fn default_coverage_max_followups() -> usize {
+    5
+}
+
+fn default_coverage_tag() -> String {
+    "coverage".to_string()
+}
+
+impl Default for CoverageConfig {
+    fn default() -> Self {
+        Self {
+            followups: false,
+            threshold: default_coverage_threshold(),
+            min_lines: default_coverage_min_lines(),
+            max_followups: default_coverage_max_followups(),
+            tag: default_coverage_tag(),
+        }
+    }
+}
+
+impl CoverageConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Cache for one-shot LLM responses.
+///
+/// Identical calls — same prompt, same resolved model/provider/endpoint —
+/// within `ttl_secs` reuse the stored response instead of re-billing. Useful
+/// when replays or tests re-run evaluation and generalization calls. Set
+/// `WG_NO_RESPONSE_CACHE=1` to bypass for one invocation. Off by default.
+///
+/// ```toml
+/// [response_cache]
+/// enabled = true
+/// ttl_secs = 3600
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ResponseCacheConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds a cached response stays valid
+    #[serde(default = "default_response_cache_ttl")]
+    pub ttl_secs: u64,
+}
+
+fn default_response_cache_ttl() -> u64 {
+    86400
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: default_response_cache_ttl(),
+        }
+    }
+}
+
+impl ResponseCacheConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Daily plans (`wg plan today`).
+///
+/// With `post_at` set, the service posts each listed participant's plan to
+/// the Matrix default room once a day, at that local time (in the
+/// participant's working-hours timezone, UTC otherwise). Days off are
+/// skipped.
+///
+/// ```toml
+/// [day_plan]
+/// post_at = "08:30"
+/// post_for = ["alice", "bob"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DayPlanConfig {
+    /// Local time of the morning post, `HH:MM`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_at: Option<String>,
+    /// Participants whose plans are posted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_for: Vec<String>,
+    /// Hours assumed for tasks without an estimate
+    #[serde(default = "default_day_plan_unestimated_hours")]
+    pub unestimated_hours: f64,
+    /// Length of the day for participants without a working calendar
+    #[serde(default = "default_day_plan_day_hours")]
+    pub day_hours: f64,
+}
+
+fn default_day_plan_unestimated_hours() -> f64 {
+    1.0
+}
+
+fn default_day_plan_day_hours() -> f64 {
+    8.0
+}
+
+impl Default for DayPlanConfig {
+    fn default() -> Self {
+        Self {
+            post_at: None,
+            post_for: Vec::new(),
+            unestimated_hours: default_day_plan_unestimated_hours(),
+            day_hours: default_day_plan_day_hours(),
+        }
+    }
+}
+
+impl DayPlanConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Anomaly detection (`wg anomalies`).
+///
+/// The service checks for anomalies after each coordinator tick and sends
+/// one urgent notification per new anomaly. Durations use the `30m`, `12h`,
+/// `7d` format.
+///
+/// ```toml
+/// [anomaly]
+/// overrun_factor = 3.0
+/// failure_min = 5
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AnomalyConfig {
+    /// Check for anomalies and alert on them from the service
+    #[serde(default = "default_true")]
+    pub alerts: bool,
+    /// Flag in-progress tasks running this many times their estimate
+    #[serde(default = "default_anomaly_overrun_factor")]
+    pub overrun_factor: f64,
+    /// A completion is fast when it takes under this fraction of the estimate
+    #[serde(default = "default_anomaly_fast_fraction")]
+    pub fast_fraction: f64,
+    /// Fast threshold for tasks without an estimate
+    #[serde(default = "default_anomaly_fast_unestimated")]
+    pub fast_unestimated: String,
+    /// Evaluation scores below this count as low
+    #[serde(default = "default_anomaly_low_score")]
+    pub low_score: f64,
+    /// Fast, low-scored completions by one agent before it is flagged
+    #[serde(default = "default_anomaly_fast_min_tasks")]
+    pub fast_min_tasks: usize,
+    /// How far back evaluations are considered
+    #[serde(default = "default_anomaly_window")]
+    pub window: String,
+    /// Recent period in which failures per tag are counted
+    #[serde(default = "default_anomaly_failure_window")]
+    pub failure_window: String,
+    /// Period before `failure_window` that sets each tag's usual failure rate
+    #[serde(default = "default_anomaly_window")]
+    pub failure_baseline: String,
+    /// Flag a tag when recent failures reach this multiple of its usual rate
+    #[serde(default = "default_anomaly_failure_spike_factor")]
+    pub failure_spike_factor: f64,
+    /// Fewest recent failures that can count as a spike
+    #[serde(default = "default_anomaly_failure_min")]
+    pub failure_min: usize,
+}
+
+fn default_anomaly_overrun_factor() -> f64 {
+    5.0
+}
+
+fn default_anomaly_fast_fraction() -> f64 {
+    0.2
+}
+
+fn default_anomaly_fast_unestimated() -> String {
+    "5m".to_string()
+}
+
+fn default_anomaly_low_score() -> f64 {
+    0.5
+}
+
+fn default_anomaly_fast_min_tasks() -> usize {
+    2
+}
+
+fn default_anomaly_window() -> String {
+    "7d".to_string()
+}
+
+fn default_anomaly_failure_window() -> String {
+    "24h".to_string()
+}
+
+fn default_anomaly_failure_spike_factor() -> f64 {
+    3.0
+}
+
+fn default_anomaly_failure_min() -> usize {
+    3
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            alerts: true,
+            overrun_factor: default_anomaly_overrun_factor(),
+            fast_fraction: default_anomaly_fast_fraction(),
+            fast_unestimated: default_anomaly_fast_unestimated(),
+            low_score: default_anomaly_low_score(),
+            fast_min_tasks: default_anomaly_fast_min_tasks(),
+            window: default_anomaly_window(),
+            failure_window: default_anomaly_failure_window(),
+            failure_baseline: default_anomaly_window(),
+            failure_spike_factor: default_anomaly_failure_spike_factor(),
+            failure_min: default_anomaly_failure_min(),
+        }
+    }
+}
+
+impl AnomalyConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How a required skill is matched against agent capabilities.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkillMatchMode {
+    /// A skill matches only a capability with the same name
+    #[default]
+    Exact,
+    /// A skill also matches a capability whose embedding is similar enough
+    Embedding,
+}
+
+impl std::fmt::Display for SkillMatchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkillMatchMode::Exact => write!(f, "exact"),
+            SkillMatchMode::Embedding => write!(f, "embedding"),
+        }
+    }
+}
+
+/// Skill matching (`wg match`, skill-first dispatch).
+///
+/// In `embedding` mode skills and capabilities are embedded through an
+/// OpenAI-compatible `/embeddings` endpoint (a local Ollama or llama.cpp
+/// server works), so a `postgres` task can match an agent advertising
+/// `databases`. Embeddings are cached under `.wg/cache/`; if the endpoint
+/// can't be reached, matching falls back to exact names.
+///
+/// ```toml
+/// [skill_matching]
+/// mode = "embedding"
+/// endpoint = "ollama"
+/// model = "nomic-embed-text"
+/// threshold = 0.7
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SkillMatchingConfig {
+    /// `exact` (default) or `embedding`
+    #[serde(default)]
+    pub mode: SkillMatchMode,
+    /// Name of the `[[llm_endpoints.endpoints]]` entry that serves embeddings
+    /// (default: the default endpoint)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Embedding model
+    #[serde(default = "default_skill_matching_model")]
+    pub model: String,
+    /// Cosine similarity at which a capability counts as covering a skill
+    #[serde(default = "default_skill_matching_threshold")]
+    pub threshold: f64,
+}
+
+fn default_skill_matching_model() -> String {
+    "text-embedding-3-small".to_string()
+}
+
+fn default_skill_matching_threshold() -> f64 {
+    0.75
+}
+
+impl Default for SkillMatchingConfig {
+    fn default() -> Self {
+        Self {
+            mode: SkillMatchMode::default(),
+            endpoint: None,
+            model: default_skill_matching_model(),
+            threshold: default_skill_matching_threshold(),
+        }
+    }
+}
+
+impl SkillMatchingConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Which cost a usage report charges for a task.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CostPricing {
+    /// The cost the executor reported, or the registry price when it
+    /// reported none
+    #[default]
+    Recorded,
+    /// Always price tokens at the model registry's rates
+    Config,
+}
+
+/// Monthly usage reports (`wg cost --month`).
+///
+/// Reports always carry USD; `currency` and `exchange_rate` add a charge
+/// column in the currency finance books against.
+///
+/// ```toml
+/// [cost_report]
+/// currency = "EUR"
+/// exchange_rate = 0.92
+/// pricing = "config"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CostReportConfig {
+    /// Currency code for the charge column
+    #[serde(default = "default_cost_report_currency")]
+    pub currency: String,
+    /// Units of `currency` per USD
+    #[serde(default = "default_cost_report_exchange_rate")]
+    pub exchange_rate: f64,
+    /// `recorded` (default) or `config`
+    #[serde(default)]
+    pub pricing: CostPricing,
+}
+
+fn default_cost_report_currency() -> String {
+    "USD".to_string()
+}
+
+fn default_cost_report_exchange_rate() -> f64 {
+    1.0
+}
+
+impl Default for CostReportConfig {
+    fn default() -> Self {
+        Self {
+            currency: default_cost_report_currency(),
+            exchange_rate: default_cost_report_exchange_rate(),
+            pricing: CostPricing::default(),
+        }
+    }
+}
+
+impl CostReportConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Replay configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReplayConfig {
     /// Default threshold for --keep-done: preserve Done tasks scoring above this (0.0-1.0)
     #[serde(default = "default_keep_done_threshold")]
@@ -537,7 +1308,7 @@ impl Default for ReplayConfig {
 
 /// Guardrails for autopoietic task creation by agents.
 /// Prevents task explosion when agents create subtasks autonomously.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GuardrailsConfig {
     /// Maximum tasks a single agent execution can create via `wg add`.
     /// Enforced when WG_AGENT_ID env var is set. Default: 10.
@@ -560,6 +1331,34 @@ pub struct GuardrailsConfig {
     /// plus decomposition templates). Set to false to use the generic guidance.
     #[serde(default = "default_decomp_guidance")]
     pub decomp_guidance: bool,
+
+    /// Estimated token budget for a rendered agent prompt (~4 bytes/token).
+    /// Over budget, the dependency context is cut per `prompt_truncation`
+    /// and a warning is logged on the task. 0 = unlimited. Default: 0.
+    #[serde(default)]
+    pub max_prompt_tokens: usize,
+
+    /// How an oversize prompt's context is cut: "head" (keep the start),
+    /// "tail" (keep the end) or "summary" (condense via an LLM call,
+    /// falling back to the start and end). Default: "head".
+    #[serde(default = "default_prompt_truncation")]
+    pub prompt_truncation: String,
+
+    /// Maximum bytes kept per agent output file (`output.log`,
+    /// `raw_stream.jsonl`). Checked every coordinator tick. 0 = unlimited.
+    /// Default: 0.
+    #[serde(default)]
+    pub max_output_bytes: u64,
+
+    /// Which end of an oversize output file to keep: "head" or "tail".
+    /// Default: "tail".
+    #[serde(default = "default_output_truncation")]
+    pub output_truncation: String,
+
+    /// Maximum bytes for a single `wg log` message; longer messages are cut
+    /// (keeping the head) with a warning. 0 = unlimited. Default: 0.
+    #[serde(default)]
+    pub max_log_message_bytes: usize,
 }
 
 fn default_max_child_tasks_per_agent() -> u32 {
@@ -578,6 +1377,14 @@ fn default_decomp_guidance() -> bool {
     true
 }
 
+fn default_prompt_truncation() -> String {
+    "head".to_string()
+}
+
+fn default_output_truncation() -> String {
+    "tail".to_string()
+}
+
 impl Default for GuardrailsConfig {
     fn default() -> Self {
         Self {
@@ -585,12 +1392,17 @@ impl Default for GuardrailsConfig {
             max_task_depth: default_max_task_depth(),
             max_triage_attempts: default_max_triage_attempts(),
             decomp_guidance: default_decomp_guidance(),
+            max_prompt_tokens: 0,
+            prompt_truncation: default_prompt_truncation(),
+            max_output_bytes: 0,
+            output_truncation: default_output_truncation(),
+            max_log_message_bytes: 0,
         }
     }
 }
 
 /// Visualization configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VizConfig {
     /// Edge color style: "gray" (default), "white", or "mixed" (tree=white, arcs=gray)
     #[serde(default = "default_edge_color")]
@@ -618,7 +1430,7 @@ impl Default for VizConfig {
 }
 
 /// TUI-specific settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TuiConfig {
     /// Enable mouse support (default: auto-detected based on tmux)
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -737,7 +1549,7 @@ impl Default for TuiConfig {
 }
 
 /// A configured LLM endpoint (like a WiFi network entry).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EndpointConfig {
     /// Display name for this endpoint
     pub name: String,
@@ -988,7 +1800,7 @@ impl EndpointConfig {
 }
 
 /// LLM endpoints configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct EndpointsConfig {
     /// When `true`, local config inherits `[[llm_endpoints.endpoints]]` entries
     /// from the global config. When `false` (default), the local config's
@@ -1045,7 +1857,7 @@ impl EndpointsConfig {
 }
 
 /// Checkpoint configuration for agent context preservation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CheckpointConfig {
     /// Auto-checkpoint every N turns
     #[serde(default = "default_auto_interval_turns")]
@@ -1321,7 +2133,7 @@ impl ExecMode {
 // ---------------------------------------------------------------------------
 
 /// Quality tier for model selection.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Tier {
     Fast,
@@ -1375,7 +2187,7 @@ impl std::str::FromStr for Tier {
 }
 
 /// A model registry entry describing a provider+model combination.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModelRegistryEntry {
     /// Short identifier used in config references (e.g., "haiku", "sonnet", "gpt-4o")
     pub id: String,
@@ -1435,7 +2247,7 @@ impl Default for ModelRegistryEntry {
 }
 
 /// Tier routing configuration: which model ID each tier resolves to.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct TierConfig {
     /// Model ID for fast tier
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1452,7 +2264,7 @@ pub struct TierConfig {
 /// has no explicit `model`, the task's effective model becomes
 /// `model` (and optional `executor` hint). Rules are evaluated in
 /// declaration order; first match wins.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TagRoutingEntry {
     /// Tag name to match against `task.tags`.
     pub tag: String,
@@ -1470,6 +2282,18 @@ pub struct TagRoutingEntry {
     pub executor: Option<String>,
 }
 
+/// The spend limit for one run of a task: its own `max_cost`, else the
+/// lowest `[cost_caps]` entry among its tags.
+pub fn resolve_cost_cap(caps: &BTreeMap<String, f64>, task: &crate::graph::Task) -> Option<f64> {
+    task.max_cost.or_else(|| {
+        task.tags
+            .iter()
+            .filter_map(|t| caps.get(t).copied())
+            .filter(|c| *c > 0.0)
+            .reduce(f64::min)
+    })
+}
+
 /// Resolve a model (and optional executor override) for a task via
 /// tag-based routing. Returns the first matching rule's settings,
 /// or `None` when no rule matches.
@@ -1486,7 +2310,7 @@ pub fn resolve_tag_routing<'a>(
 }
 
 /// Per-role model+provider assignment.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RoleModelConfig {
     /// **Deprecated**: Use provider:model format in the `model` field instead.
     /// Kept for deserialization of old configs; never written back.
@@ -1505,7 +2329,7 @@ pub struct RoleModelConfig {
 
 /// Model routing: maps each dispatch role to a model+provider.
 /// Roles without explicit config fall back to `default`, then to `agent.model`.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct ModelRoutingConfig {
     /// Default model+provider for all roles
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -2625,7 +3449,7 @@ fn default_auto_assign_grace_seconds() -> u64 {
 }
 
 /// Agency (evolutionary identity system) configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AgencyConfig {
     /// Automatically trigger evaluation when a task completes
     #[serde(default)]
@@ -2901,7 +3725,7 @@ impl Default for AgencyConfig {
 }
 
 /// Agent-specific configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AgentConfig {
     /// **Deprecated**: handler is derived from the model spec's provider
     /// prefix. Kept for one release with a deprecation warning when set
@@ -2935,15 +3759,27 @@ pub struct AgentConfig {
     /// process hasn't fully started yet. Default: 30.
     #[serde(default = "default_reaper_grace_seconds")]
     pub reaper_grace_seconds: u64,
+
+    /// Inject the `wg onboard` brief (project summary, conventions, ready
+    /// work matching the task's skills) into spawned agents' prompts.
+    #[serde(default)]
+    pub onboarding_brief: bool,
 }
 
 /// Coordinator-specific configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CoordinatorConfig {
-    /// Maximum number of parallel agents
-    #[serde(default = "default_max_agents")]
+    /// Maximum number of parallel agents (`max_concurrent` is accepted as
+    /// an alias)
+    #[serde(default = "default_max_agents", alias = "max_concurrent")]
     pub max_agents: usize,
 
+    /// Most agents spawned per period, e.g. `2/min`, `30/h` or `1/30s`, so
+    /// a large ready set is started gradually. Unset: no limit beyond
+    /// `max_agents`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spawn_rate: Option<String>,
+
     /// Poll interval in seconds (used by standalone coordinator command)
     #[serde(default = "default_coordinator_interval")]
     pub interval: u64,
@@ -3069,7 +3905,7 @@ pub struct CoordinatorConfig {
 
     /// Archive tasks completed/abandoned more than this many days ago.
     /// The archive cycle (.archive-0) runs periodically and moves old
-    /// done/abandoned tasks to .wg/archive.jsonl. Default: 7 days.
+    /// done/abandoned tasks to .wg/archive/YYYY-MM.jsonl. Default: 7 days.
     /// Set to 0 to disable automatic archival.
     #[serde(default = "default_archive_retention_days")]
     pub archive_retention_days: u64,
@@ -3116,6 +3952,19 @@ pub struct CoordinatorConfig {
     #[serde(default = "default_max_verify_failures", alias = "max_eval_rescues")]
     pub max_verify_failures: u32,
 
+    /// Test names known to be flaky: exact names, `::`-suffixes of a test
+    /// path, or `prefix*` patterns. Tests that flip outcome on an unchanged
+    /// working tree are added to `test-results/flaky.json` automatically.
+    /// Verify failures confined to flaky tests are rerun and don't count
+    /// toward `max_verify_failures`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flaky_tests: Vec<String>,
+
+    /// How many times to rerun a verify command whose only failing tests are
+    /// known-flaky. Default: 1. Set to 0 to never rerun.
+    #[serde(default = "default_verify_flaky_reruns")]
+    pub verify_flaky_reruns: u32,
+
     /// Default verify timeout for tasks without specific override
     #[serde(default = "default_verify_default_timeout")]
     pub verify_default_timeout: Option<String>,
@@ -3182,6 +4031,66 @@ pub struct CoordinatorConfig {
     #[serde(default)]
     pub provider_failure_cooldown: String,
 
+    /// Seconds to cache executor preflight results (binary on PATH, endpoint
+    /// API key resolves, endpoint host reachable). Tasks routed to an executor
+    /// whose preflight fails are held in the ready queue rather than failed.
+    /// Default: 300. Set to 0 to disable preflight checks.
+    #[serde(default = "default_executor_preflight_ttl")]
+    pub executor_preflight_ttl: u64,
+
+    /// Hours a ready task waits before its dispatch priority is boosted one
+    /// tier (repeated every interval), so low-priority work is not starved.
+    /// Applies to the dispatcher and `wg next`. Default: 24. 0 disables aging.
+    #[serde(default = "default_priority_aging_hours")]
+    pub priority_aging_hours: u64,
+
+    /// How assignment picks among candidate agents:
+    /// - "greedy" (default): the best-scoring agent, every time
+    /// - "fair": the least-loaded agent (open work, then recent completions)
+    /// - "skill-first": best skill match, ties broken by load
+    /// - "reputation": best track record (`wg agent stats`), ties broken by load
+    #[serde(default)]
+    pub dispatch: DispatchPolicy,
+
+    /// Which ready task the dispatcher starts next (and which agent the
+    /// assigner should prefer):
+    /// - "priority" (default): effective priority, aging and inheritance
+    /// - "fifo": oldest task first
+    /// - "critical-path-first": longest chain of work behind the task first
+    /// - "cost-aware": cheaper estimates and lower-rate agents first
+    /// - "script:<path>": an external program ranks tasks and agents
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dispatch_strategy: Option<String>,
+
+    /// Address for the external-event webhook listener (e.g.
+    /// "127.0.0.1:8787"). `POST /hooks/<name>` releases tasks with
+    /// `waiting_on: webhook:<name>`. Unset (default): no listener.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_listen: Option<String>,
+
+    /// HMAC-SHA256 secret; when set, webhook requests must carry
+    /// `X-Webhook-Signature: sha256=<hex>` of the body. Webhook executor
+    /// callbacks are refused while it is unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_secret: Option<String>,
+
+    /// When dispatcher slots are idle and no unassigned work is ready, take
+    /// tasks queued by `wg claim --batch` back from busy actors (longest
+    /// queue first) so they can be dispatched. Default: false.
+    #[serde(default)]
+    pub work_stealing: bool,
+
+    /// Alert when a ready task has waited this long for an agent, e.g.
+    /// `["30m", "2h"]` (one alert per threshold crossed). Empty (default):
+    /// latency is still recorded for `wg service latency`, but never alerted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dispatch_latency_alerts: Vec<String>,
+
+    /// Recurring windows in which no new agents are dispatched, e.g. for
+    /// planned infrastructure work. See [`PauseWindow`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pause_windows: Vec<PauseWindow>,
+
     /// Resource management configuration for worktree cleanup and recovery.
     #[serde(default)]
     pub resource_management: ResourceManagementConfig,
@@ -3205,8 +4114,51 @@ pub struct CoordinatorConfig {
     pub escalate_on_retry: bool,
 }
 
+/// A recurring dispatch pause (`[[coordinator.pause_windows]]`).
+///
+/// Each time `cron` fires, dispatch stops for `duration`; running agents
+/// are left alone. Handled like `wg service maintenance on`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PauseWindow {
+    /// Cron expression (UTC) for the start of each window, e.g.
+    /// "0 2 * * SUN" for Sundays at 02:00
+    pub cron: String,
+    /// How long each window lasts, e.g. "90m" or "2h"
+    pub duration: String,
+    /// Reason shown in status output and notifications
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Agent selection policy for assignment (`coordinator.dispatch`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum DispatchPolicy {
+    /// Highest performance score wins
+    #[default]
+    Greedy,
+    /// Spread work across agents by load
+    Fair,
+    /// Prefer agents whose capabilities cover the task's skills
+    SkillFirst,
+    /// Prefer agents with the best reputation (success rate, evaluation
+    /// scores, duration vs estimate)
+    Reputation,
+}
+
+impl std::fmt::Display for DispatchPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Greedy => "greedy",
+            Self::Fair => "fair",
+            Self::SkillFirst => "skill-first",
+            Self::Reputation => "reputation",
+        })
+    }
+}
+
 /// Resource management configuration for cleanup operations and recovery branches.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ResourceManagementConfig {
     /// Enable cleanup verification to ensure worktrees are actually removed.
     /// When true, cleanup operations verify that worktree directories are
@@ -3337,6 +4289,10 @@ fn default_verify_mode() -> String {
     "inline".to_string()
 }
 
+fn default_verify_flaky_reruns() -> u32 {
+    1
+}
+
 fn default_max_verify_failures() -> u32 {
     3
 }
@@ -3361,6 +4317,14 @@ fn default_max_spawn_failures() -> u32 {
     5
 }
 
+fn default_executor_preflight_ttl() -> u64 {
+    300
+}
+
+fn default_priority_aging_hours() -> u64 {
+    24
+}
+
 fn default_max_escalation_depth() -> u32 {
     3
 }
@@ -3450,6 +4414,7 @@ impl Default for CoordinatorConfig {
     fn default() -> Self {
         Self {
             max_agents: default_max_agents(),
+            spawn_rate: None,
             interval: default_coordinator_interval(),
             poll_interval: default_poll_interval(),
             graph_watch_enabled: default_graph_watch_enabled(),
@@ -3467,6 +4432,15 @@ impl Default for CoordinatorConfig {
             on_provider_failure: default_on_provider_failure(),
             provider_failure_threshold: default_provider_failure_threshold(),
             provider_failure_cooldown: String::new(),
+            executor_preflight_ttl: default_executor_preflight_ttl(),
+            priority_aging_hours: default_priority_aging_hours(),
+            dispatch: DispatchPolicy::default(),
+            dispatch_strategy: None,
+            webhook_listen: None,
+            webhook_secret: None,
+            work_stealing: false,
+            dispatch_latency_alerts: Vec::new(),
+            pause_windows: Vec::new(),
             compaction_threshold_ratio: default_compaction_threshold_ratio(),
             eval_frequency: default_eval_frequency(),
             worktree_isolation: true,
@@ -3476,6 +4450,8 @@ impl Default for CoordinatorConfig {
             verify_mode: default_verify_mode(),
             verify_autospawn_enabled: false,
             max_verify_failures: default_max_verify_failures(),
+            flaky_tests: Vec::new(),
+            verify_flaky_reruns: default_verify_flaky_reruns(),
             max_spawn_failures: default_max_spawn_failures(),
             max_escalation_depth: default_max_escalation_depth(),
             auto_test_discovery: default_auto_test_discovery(),
@@ -3493,7 +4469,7 @@ impl Default for CoordinatorConfig {
 }
 
 /// Project metadata
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct ProjectConfig {
     /// Project name (legacy field; new code prefers `title`)
     #[serde(default)]
@@ -3517,6 +4493,12 @@ pub struct ProjectConfig {
     /// Default skills for new actors
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub default_skills: Vec<String>,
+
+    /// Reject unknown fields in graph.jsonl and function YAML instead of
+    /// silently dropping them, like `wg --strict`. Off by default so files
+    /// written by newer versions still load.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub strict_fields: bool,
 }
 
 fn default_executor() -> String {
@@ -3552,6 +4534,7 @@ impl Default for AgentConfig {
             max_tasks: None,
             heartbeat_timeout: default_heartbeat_timeout(),
             reaper_grace_seconds: default_reaper_grace_seconds(),
+            onboarding_brief: false,
         }
     }
 }
@@ -3991,7 +4974,7 @@ impl Config {
     /// keys regardless of which file used the legacy name.
     pub fn load_merged_toml_value(workgraph_dir: &Path) -> anyhow::Result<toml::Value> {
         let global_path = Self::global_config_path()?;
-        let local_path = workgraph_dir.join("config.toml");
+        let local_path = Self::local_config_path(workgraph_dir);
         let mut global_val = Self::load_toml_value(&global_path)?;
         let mut local_val = Self::load_toml_value(&local_path)?;
         let mut warnings = Vec::new();
@@ -4015,7 +4998,7 @@ impl Config {
     /// Local keys override global keys. Missing files are treated as empty.
     pub fn load_merged(workgraph_dir: &Path) -> anyhow::Result<Self> {
         let global_path = Self::global_config_path()?;
-        let local_path = workgraph_dir.join("config.toml");
+        let local_path = Self::local_config_path(workgraph_dir);
 
         let mut global_val = Self::load_toml_value(&global_path)?;
         let mut local_val = Self::load_toml_value(&local_path)?;
@@ -4144,10 +5127,23 @@ impl Config {
         ))
     }
 
+    /// Path of the local config file for `workgraph_dir`. A named graph
+    /// without its own config.toml uses its workspace root's.
+    pub fn local_config_path(workgraph_dir: &Path) -> PathBuf {
+        let own = workgraph_dir.join("config.toml");
+        if own.exists() {
+            return own;
+        }
+        match crate::graphs::workspace_root(workgraph_dir) {
+            Some(root) => root.join("config.toml"),
+            None => own,
+        }
+    }
+
     /// Load configuration from .wg/config.toml (local only).
     /// Returns default config if file doesn't exist.
     pub fn load(workgraph_dir: &Path) -> anyhow::Result<Self> {
-        let config_path = workgraph_dir.join("config.toml");
+        let config_path = Self::local_config_path(workgraph_dir);
 
         if !config_path.exists() {
             return Ok(Self::default());
@@ -4418,7 +5414,7 @@ impl Config {
         workgraph_dir: &Path,
     ) -> anyhow::Result<(Self, BTreeMap<String, ConfigSource>)> {
         let global_path = Self::global_config_path()?;
-        let local_path = workgraph_dir.join("config.toml");
+        let local_path = Self::local_config_path(workgraph_dir);
 
         let mut global_val = Self::load_toml_value(&global_path)?;
         let mut local_val = Self::load_toml_value(&local_path)?;
@@ -4797,6 +5793,31 @@ impl Config {
             }
         }
 
+        // Rule: coordinator.spawn_rate must parse, or the dispatcher would
+        // spawn without the intended limit.
+        if let Some(ref rate) = self.coordinator.spawn_rate
+            && let Err(e) = crate::dispatch::rate_limit::SpawnRate::parse(rate)
+        {
+            result.errors.push(ConfigDiagnostic {
+                rule: "invalid-spawn-rate".into(),
+                message: format!("coordinator.spawn_rate: {}", e),
+                fix: "Use <count>/<period>, e.g. spawn_rate = \"2/min\", \"30/h\" or \"1/30s\"."
+                    .into(),
+            });
+        }
+
+        // Rule: coordinator.dispatch_strategy must name a known strategy, or
+        // the dispatcher falls back to priority order.
+        if let Some(ref strategy) = self.coordinator.dispatch_strategy
+            && let Err(e) = crate::dispatch::strategy::validate(strategy)
+        {
+            result.errors.push(ConfigDiagnostic {
+                rule: "invalid-dispatch-strategy".into(),
+                message: format!("coordinator.dispatch_strategy: {}", e),
+                fix: "Use priority, fifo, critical-path-first, cost-aware or script:<path>.".into(),
+            });
+        }
+
         result
     }
 }
@@ -4859,6 +5880,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cost_cap_prefers_task_then_strictest_tag() {
+        let caps: BTreeMap<String, f64> =
+            [("research".to_string(), 5.0), ("chore".to_string(), 0.5)].into();
+        let mut task = crate::graph::Task {
+            tags: vec!["research".to_string(), "chore".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(resolve_cost_cap(&caps, &task), Some(0.5));
+        task.max_cost = Some(10.0);
+        assert_eq!(resolve_cost_cap(&caps, &task), Some(10.0));
+        task.max_cost = None;
+        task.tags = vec!["docs".to_string()];
+        assert_eq!(resolve_cost_cap(&caps, &task), None);
+    }
+
     #[test]
     fn test_default_config() {
         let config = Config::default();
@@ -6615,6 +7652,39 @@ model = "claude:haiku"
         );
     }
 
+    #[test]
+    fn test_validate_config_invalid_spawn_rate_errors() {
+        let mut config: Config = toml::from_str(
+            r#"
+[coordinator]
+max_concurrent = 8
+spawn_rate = "2 per minute"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.coordinator.max_agents, 8);
+        let v = config.validate_config();
+        assert!(v.errors.iter().any(|e| e.rule == "invalid-spawn-rate"));
+
+        config.coordinator.spawn_rate = Some("2/min".to_string());
+        assert!(config.validate_config().is_ok());
+    }
+
+    #[test]
+    fn test_validate_config_invalid_dispatch_strategy_errors() {
+        let mut config = Config::default();
+        config.coordinator.dispatch_strategy = Some("shortest-first".to_string());
+        let v = config.validate_config();
+        assert!(
+            v.errors
+                .iter()
+                .any(|e| e.rule == "invalid-dispatch-strategy")
+        );
+
+        config.coordinator.dispatch_strategy = Some("critical-path-first".to_string());
+        assert!(config.validate_config().is_ok());
+    }
+
     #[test]
     fn test_validate_config_claude_executor_with_slash_model_warns() {
         let mut config = Config::default();
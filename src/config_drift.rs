@@ -0,0 +1,164 @@
+//! Differences between the effective config and a baseline.
+//!
+//! A team can commit a `workgraph.toml` at the project root holding the
+//! config every machine should run with. `wg config diff` compares the
+//! effective (global + local) config key by key against that file, another
+//! file, or the built-in defaults. The service logs a warning at startup and
+//! on reload when the effective config has drifted from a committed baseline.
+//!
+//! A baseline is read like any config file: keys it leaves out take their
+//! defaults, so a machine that sets a key the baseline doesn't mention has
+//! drifted too.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, normalize_legacy_tables};
+
+/// Baseline file name, at the project root (next to `.wg/`).
+pub const BASELINE_FILE: &str = "workgraph.toml";
+
+/// Shown instead of credential values.
+const MASK: &str = "********";
+
+/// One key whose value differs.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigChange {
+    /// Dotted key, e.g. `dispatcher.max_agents`
+    pub key: String,
+    /// Value in the baseline; `None` when the key is unset there
+    pub baseline: Option<serde_json::Value>,
+    /// Value in the effective config; `None` when the key is unset
+    pub effective: Option<serde_json::Value>,
+}
+
+/// Where the committed baseline lives for a `.wg` directory.
+pub fn baseline_path(workgraph_dir: &Path) -> PathBuf {
+    workgraph_dir
+        .parent()
+        .unwrap_or(workgraph_dir)
+        .join(BASELINE_FILE)
+}
+
+/// The built-in defaults, as an empty config file deserializes.
+pub fn defaults() -> Result<Config> {
+    toml::Value::Table(toml::map::Map::new())
+        .try_into()
+        .context("Failed to build default config")
+}
+
+/// Load a baseline file. Legacy section names are accepted, as in config.toml.
+pub fn load_baseline(path: &Path) -> Result<Config> {
+    if !path.exists() {
+        bail!("Baseline {} does not exist", path.display());
+    }
+    let mut value = Config::load_toml_value(path)?;
+    normalize_legacy_tables(&mut value, &path.display().to_string(), &mut Vec::new());
+    value
+        .try_into()
+        .map_err(|e| anyhow::anyhow!("Failed to parse baseline {}: {}", path.display(), e))
+}
+
+/// Every key whose value differs between `baseline` and `effective`,
+/// sorted by key. Credential values are masked.
+pub fn diff(baseline: &Config, effective: &Config) -> Result<Vec<ConfigChange>> {
+    let mut before = BTreeMap::new();
+    leaves(&toml::Value::try_from(baseline)?, "", &mut before);
+    let mut after = BTreeMap::new();
+    leaves(&toml::Value::try_from(effective)?, "", &mut after);
+
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut changes = Vec::new();
+    for key in keys {
+        let (b, e) = (before.get(key), after.get(key));
+        if b == e {
+            continue;
+        }
+        let show = |v: Option<&toml::Value>| {
+            v.map(|v| {
+                if is_secret_key(key) {
+                    serde_json::Value::String(MASK.to_string())
+                } else {
+                    serde_json::to_value(v).unwrap_or(serde_json::Value::Null)
+                }
+            })
+        };
+        changes.push(ConfigChange {
+            key: key.clone(),
+            baseline: show(b),
+            effective: show(e),
+        });
+    }
+    Ok(changes)
+}
+
+/// Flatten tables into dotted keys. Arrays are compared whole.
+fn leaves(value: &toml::Value, prefix: &str, out: &mut BTreeMap<String, toml::Value>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, v) in table {
+                let full = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                leaves(v, &full, out);
+            }
+        }
+        other => {
+            out.insert(prefix.to_string(), other.clone());
+        }
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let last = key.rsplit('.').next().unwrap_or(key).to_ascii_lowercase();
+    last == "api_key"
+        || ["secret", "token", "password"]
+            .iter()
+            .any(|word| last.contains(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_against_defaults() {
+        let mut config = defaults().unwrap();
+        assert!(diff(&defaults().unwrap(), &config).unwrap().is_empty());
+
+        config.coordinator.max_agents = 12;
+        config.coordinator.webhook_secret = Some("hunter2".to_string());
+        let changes = diff(&defaults().unwrap(), &config).unwrap();
+        let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["dispatcher.max_agents", "dispatcher.webhook_secret"]
+        );
+        assert_eq!(changes[0].effective, Some(serde_json::json!(12)));
+        // Unset in the baseline, and the secret itself is never shown.
+        assert_eq!(changes[1].baseline, None);
+        assert_eq!(changes[1].effective, Some(serde_json::json!(MASK)));
+    }
+
+    #[test]
+    fn test_load_baseline_fills_defaults_and_accepts_legacy_sections() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(BASELINE_FILE);
+        std::fs::write(&path, "[coordinator]\nmax_agents = 3\n").unwrap();
+        let baseline = load_baseline(&path).unwrap();
+        assert_eq!(baseline.coordinator.max_agents, 3);
+
+        let changes = diff(&baseline, &defaults().unwrap()).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].baseline, Some(serde_json::json!(3)));
+
+        assert!(load_baseline(&tmp.path().join("missing.toml")).is_err());
+    }
+}
@@ -0,0 +1,360 @@
+//! Input contracts between producer and consumer tasks.
+//!
+//! A task can declare expectations on what its upstream tasks produce via the
+//! `expects` field. Each expectation is a spec string:
+//!
+//! ```text
+//! <kind>:<pattern>[@<upstream-id>]
+//! ```
+//!
+//! Kinds:
+//! - `artifact` — an upstream recorded an artifact whose path matches the glob
+//! - `file` — a file matching the glob exists in the project root
+//! - `json` — a matching upstream artifact exists and parses as JSON
+//!
+//! The text after the last `@` names the upstream only when it is a valid
+//! task id (letters, digits, `-`, `_`, `.`), so patterns such as
+//! `artifact:builds/*@*.tar` keep their `@`. To match a literal `@` followed by
+//! id-like text (`icon@2x.png`), write it as `[@]`: `artifact:icon[@]2x.png`.
+//!
+//! Expectations qualified with `@<upstream-id>` are checked as soon as that
+//! upstream completes. Unqualified expectations are checked once every
+//! upstream is done, against the union of their artifacts. A violation is
+//! flagged on the consumer before it gets dispatched, so an agent doesn't
+//! waste a run on inputs that were never produced. Contracts are enforced
+//! wherever a task becomes done: `wg done`, the coordinator's promotion of
+//! eval-gated and rescued tasks, and the opening of phase gates.
+
+use crate::graph::{LogEntry, Status, Task, WorkGraph};
+use chrono::Utc;
+use std::fmt;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ContractError {
+    #[error("Invalid contract spec '{0}': expected <kind>:<pattern>[@<upstream-id>]")]
+    InvalidSpec(String),
+    #[error("Unknown contract kind '{0}' (expected artifact, file, or json)")]
+    UnknownKind(String),
+    #[error("Invalid glob pattern '{0}': {1}")]
+    InvalidPattern(String, String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractKind {
+    Artifact,
+    File,
+    Json,
+}
+
+impl fmt::Display for ContractKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContractKind::Artifact => write!(f, "artifact"),
+            ContractKind::File => write!(f, "file"),
+            ContractKind::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// A parsed expectation on upstream output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expectation {
+    pub kind: ContractKind,
+    pub pattern: String,
+    /// Upstream task the expectation applies to (None = all upstreams).
+    pub from: Option<String>,
+}
+
+/// A contract that was not satisfied when its upstream(s) completed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The consumer task that declared the expectation.
+    pub task_id: String,
+    pub spec: String,
+    pub reason: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expects {} — {}",
+            self.task_id, self.spec, self.reason
+        )
+    }
+}
+
+/// Whether `s` can be a task id, as opposed to the tail of a glob pattern.
+fn is_task_id(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Parse an expectation spec string.
+pub fn parse_expectation(spec: &str) -> Result<Expectation, ContractError> {
+    let (body, from) = match spec.rsplit_once('@') {
+        Some((body, from)) if is_task_id(from) => (body, Some(from.to_string())),
+        _ => (spec, None),
+    };
+    let (kind, pattern) = body
+        .split_once(':')
+        .ok_or_else(|| ContractError::InvalidSpec(spec.to_string()))?;
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err(ContractError::InvalidSpec(spec.to_string()));
+    }
+    let kind = match kind.trim() {
+        "artifact" => ContractKind::Artifact,
+        "file" => ContractKind::File,
+        "json" => ContractKind::Json,
+        other => return Err(ContractError::UnknownKind(other.to_string())),
+    };
+    glob::Pattern::new(pattern)
+        .map_err(|e| ContractError::InvalidPattern(pattern.to_string(), e.to_string()))?;
+    Ok(Expectation {
+        kind,
+        pattern: pattern.to_string(),
+        from,
+    })
+}
+
+/// Check a single expectation against the given upstream tasks.
+/// Returns `Some(reason)` when the contract is violated.
+pub fn check_expectation(
+    exp: &Expectation,
+    upstreams: &[&Task],
+    project_root: &Path,
+) -> Option<String> {
+    let pattern = glob::Pattern::new(&exp.pattern).ok()?;
+    let matching: Vec<&String> = upstreams
+        .iter()
+        .flat_map(|t| t.artifacts.iter())
+        .filter(|a| pattern.matches(a))
+        .collect();
+
+    match exp.kind {
+        ContractKind::Artifact => {
+            if matching.is_empty() {
+                return Some(format!("no upstream artifact matches '{}'", exp.pattern));
+            }
+        }
+        ContractKind::File => {
+            let full = project_root.join(&exp.pattern);
+            let found = glob::glob(&full.to_string_lossy())
+                .map(|mut paths| paths.any(|p| p.is_ok()))
+                .unwrap_or(false);
+            if !found {
+                return Some(format!("no file matches '{}'", exp.pattern));
+            }
+        }
+        ContractKind::Json => {
+            if matching.is_empty() {
+                return Some(format!("no upstream artifact matches '{}'", exp.pattern));
+            }
+            for artifact in matching {
+                let content = match std::fs::read_to_string(project_root.join(artifact)) {
+                    Ok(c) => c,
+                    Err(e) => return Some(format!("cannot read '{}': {}", artifact, e)),
+                };
+                if let Err(e) = serde_json::from_str::<serde_json::Value>(&content) {
+                    return Some(format!("'{}' is not valid JSON: {}", artifact, e));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Check the contracts of every consumer of `completed_id` that are due now.
+///
+/// Expectations qualified with `@completed_id` are checked against that task
+/// alone; unqualified ones are checked only when all of the consumer's
+/// upstreams are done. Invalid specs are reported as violations too.
+pub fn check_on_completion(
+    graph: &WorkGraph,
+    completed_id: &str,
+    project_root: &Path,
+) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for consumer in graph.tasks() {
+        if consumer.expects.is_empty() || !consumer.after.iter().any(|a| a == completed_id) {
+            continue;
+        }
+        let upstreams: Vec<&Task> = consumer
+            .after
+            .iter()
+            .filter_map(|id| graph.get_task(id))
+            .collect();
+        let all_done = upstreams.iter().all(|t| t.status == Status::Done);
+
+        for spec in &consumer.expects {
+            let exp = match parse_expectation(spec) {
+                Ok(e) => e,
+                Err(e) => {
+                    violations.push(Violation {
+                        task_id: consumer.id.clone(),
+                        spec: spec.clone(),
+                        reason: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+            let reason = match exp.from.as_deref() {
+                Some(from) if from == completed_id => {
+                    let producer: Vec<&Task> = graph.get_task(from).into_iter().collect();
+                    check_expectation(&exp, &producer, project_root)
+                }
+                Some(_) => None,
+                None if all_done => check_expectation(&exp, &upstreams, project_root),
+                None => None,
+            };
+            if let Some(reason) = reason {
+                violations.push(Violation {
+                    task_id: consumer.id.clone(),
+                    spec: spec.clone(),
+                    reason,
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+/// Check the contracts due on `completed_id`'s completion and pause every
+/// consumer that violates one, logging why. Returns the violations found.
+pub fn enforce_on_completion(
+    graph: &mut WorkGraph,
+    completed_id: &str,
+    project_root: &Path,
+) -> Vec<Violation> {
+    let violations = check_on_completion(graph, completed_id, project_root);
+    for v in &violations {
+        if let Some(t) = graph.get_task_mut(&v.task_id) {
+            t.paused = true;
+            t.log.push(LogEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                actor: None,
+                user: Some(crate::current_user()),
+                message: format!(
+                    "Paused: contract violation on upstream '{}': expects {} — {}",
+                    completed_id, v.spec, v.reason
+                ),
+            });
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use tempfile::tempdir;
+
+    fn task(id: &str, after: &[&str], status: Status) -> Task {
+        Task {
+            id: id.to_string(),
+            title: id.to_string(),
+            after: after.iter().map(|s| s.to_string()).collect(),
+            status,
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_expectation() {
+        let exp = parse_expectation("artifact:schemas/*.yaml@design").unwrap();
+        assert_eq!(exp.kind, ContractKind::Artifact);
+        assert_eq!(exp.pattern, "schemas/*.yaml");
+        assert_eq!(exp.from.as_deref(), Some("design"));
+
+        let exp = parse_expectation("file:out/report.md").unwrap();
+        assert_eq!(exp.kind, ContractKind::File);
+        assert!(exp.from.is_none());
+
+        assert!(parse_expectation("nonsense").is_err());
+        assert!(parse_expectation("blob:x").is_err());
+        assert!(parse_expectation("artifact:").is_err());
+    }
+
+    #[test]
+    fn test_parse_expectation_with_at_in_pattern() {
+        let exp = parse_expectation("artifact:builds/*@*.tar").unwrap();
+        assert_eq!(exp.pattern, "builds/*@*.tar");
+        assert!(exp.from.is_none());
+
+        let exp = parse_expectation("file:users/*@host/*@ingest-2").unwrap();
+        assert_eq!(exp.pattern, "users/*@host/*");
+        assert_eq!(exp.from.as_deref(), Some("ingest-2"));
+
+        // A literal `@` before id-like text is written as a class
+        let exp = parse_expectation("artifact:icons/icon[@]2x.png").unwrap();
+        assert!(exp.from.is_none());
+        let pattern = glob::Pattern::new(&exp.pattern).unwrap();
+        assert!(pattern.matches("icons/icon@2x.png"));
+    }
+
+    #[test]
+    fn test_qualified_contract_checked_on_producer_completion() {
+        let dir = tempdir().unwrap();
+        let mut graph = WorkGraph::new();
+        let mut producer = task("design", &[], Status::Done);
+        producer.artifacts = vec!["docs/notes.md".to_string()];
+        graph.add_node(Node::Task(producer));
+        graph.add_node(Node::Task(task("other", &[], Status::Open)));
+        let mut consumer = task("impl", &["design", "other"], Status::Open);
+        consumer.expects = vec!["artifact:schemas/*.yaml@design".to_string()];
+        graph.add_node(Node::Task(consumer));
+
+        let violations = check_on_completion(&graph, "design", dir.path());
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].task_id, "impl");
+
+        // Unrelated upstream completing does not re-check a qualified contract
+        assert!(check_on_completion(&graph, "other", dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_unqualified_contract_waits_for_all_upstreams() {
+        let dir = tempdir().unwrap();
+        let mut graph = WorkGraph::new();
+        let mut a = task("a", &[], Status::Done);
+        a.artifacts = vec!["api.json".to_string()];
+        graph.add_node(Node::Task(a));
+        graph.add_node(Node::Task(task("b", &[], Status::InProgress)));
+        let mut consumer = task("c", &["a", "b"], Status::Open);
+        consumer.expects = vec!["json:*.json".to_string()];
+        graph.add_node(Node::Task(consumer));
+
+        // b not done yet: nothing to check
+        assert!(check_on_completion(&graph, "a", dir.path()).is_empty());
+
+        graph.get_task_mut("b").unwrap().status = Status::Done;
+        std::fs::write(dir.path().join("api.json"), "{\"ok\": true}").unwrap();
+        assert!(check_on_completion(&graph, "b", dir.path()).is_empty());
+
+        // YAML is not JSON
+        std::fs::write(dir.path().join("api.json"), "ok: true\n").unwrap();
+        let violations = check_on_completion(&graph, "b", dir.path());
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].reason.contains("not valid JSON"));
+
+        let violations = enforce_on_completion(&mut graph, "b", dir.path());
+        assert_eq!(violations.len(), 1);
+        let consumer = graph.get_task("c").unwrap();
+        assert!(consumer.paused);
+        assert!(
+            consumer
+                .log
+                .last()
+                .unwrap()
+                .message
+                .contains("upstream 'b'")
+        );
+    }
+}
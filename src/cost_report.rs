@@ -0,0 +1,319 @@
+//! Monthly usage reports for charging agent spend back to projects.
+//!
+//! Every finished task that recorded token usage is attributed to the month
+//! it completed in and summed per group: tag, milestone (the nearest
+//! downstream task tagged `milestone`), model, and agent. A task with several
+//! tags counts toward each of them, so per-tag totals can add up to more
+//! than the month's spend; the other groupings partition it.
+//!
+//! Each line carries the cost the executor recorded, the cost at the model
+//! registry's rates, and a charge in `[cost_report] currency`.
+
+use anyhow::{Result, bail};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::config::{Config, CostPricing};
+use crate::escalation::nearest_milestone;
+use crate::graph::{Task, WorkGraph, registry_cost_usd};
+
+/// Key used when a task has no value for a grouping.
+pub const NONE_KEY: &str = "(none)";
+
+/// What a report line is grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    Tag,
+    Milestone,
+    Model,
+    Agent,
+}
+
+impl GroupBy {
+    pub const ALL: [GroupBy; 4] = [
+        GroupBy::Tag,
+        GroupBy::Milestone,
+        GroupBy::Model,
+        GroupBy::Agent,
+    ];
+}
+
+impl std::fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupBy::Tag => write!(f, "tag"),
+            GroupBy::Milestone => write!(f, "milestone"),
+            GroupBy::Model => write!(f, "model"),
+            GroupBy::Agent => write!(f, "agent"),
+        }
+    }
+}
+
+impl std::str::FromStr for GroupBy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "tag" => Ok(GroupBy::Tag),
+            "milestone" => Ok(GroupBy::Milestone),
+            "model" => Ok(GroupBy::Model),
+            "agent" => Ok(GroupBy::Agent),
+            _ => bail!(
+                "Unknown grouping '{}' (expected tag, milestone, model or agent)",
+                s
+            ),
+        }
+    }
+}
+
+/// Spend for one group in one month.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageLine {
+    pub month: String,
+    pub group: String,
+    pub key: String,
+    pub tasks: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_input_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    /// Cost reported by the executors
+    pub recorded_usd: f64,
+    /// Cost at the model registry's rates (recorded cost for unpriced models)
+    pub priced_usd: f64,
+    /// Charged amount in `currency`
+    pub charge: f64,
+    pub currency: String,
+}
+
+/// CSV header, in [`UsageLine`] field order.
+pub const CSV_COLUMNS: [&str; 12] = [
+    "month",
+    "group",
+    "key",
+    "tasks",
+    "input_tokens",
+    "output_tokens",
+    "cache_read_input_tokens",
+    "cache_creation_input_tokens",
+    "recorded_usd",
+    "priced_usd",
+    "charge",
+    "currency",
+];
+
+/// Validate a `YYYY-MM` month.
+pub fn parse_month(month: &str) -> Result<String> {
+    match NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d") {
+        Ok(date) => Ok(date.format("%Y-%m").to_string()),
+        Err(_) => bail!("Invalid month '{}' (expected YYYY-MM)", month),
+    }
+}
+
+/// The `YYYY-MM` a finished task is billed to: when it completed, or when it
+/// started if it never recorded a completion.
+pub fn task_month(task: &Task) -> Option<String> {
+    let ts = task
+        .completed_at
+        .as_deref()
+        .or(task.started_at.as_deref())?;
+    let at = ts.parse::<DateTime<Utc>>().ok()?;
+    Some(at.format("%Y-%m").to_string())
+}
+
+fn group_keys(graph: &WorkGraph, task: &Task, group: GroupBy) -> Vec<String> {
+    let key = match group {
+        GroupBy::Tag if !task.tags.is_empty() => return task.tags.clone(),
+        GroupBy::Tag => None,
+        GroupBy::Milestone => nearest_milestone(graph, &task.id),
+        GroupBy::Model => task.model.clone(),
+        GroupBy::Agent => task.agent.clone().or_else(|| task.assigned.clone()),
+    };
+    vec![key.unwrap_or_else(|| NONE_KEY.to_string())]
+}
+
+/// Usage for `month` grouped by each of `groups`, sorted by group then key.
+pub fn monthly_usage(
+    graph: &WorkGraph,
+    config: &Config,
+    month: &str,
+    groups: &[GroupBy],
+) -> Vec<UsageLine> {
+    let settings = &config.cost_report;
+    let mut lines: BTreeMap<(GroupBy, String), UsageLine> = BTreeMap::new();
+
+    for task in graph.tasks() {
+        if !task.status.is_terminal() {
+            continue;
+        }
+        let Some(ref usage) = task.token_usage else {
+            continue;
+        };
+        if task_month(task).as_deref() != Some(month) {
+            continue;
+        }
+        let priced = task
+            .model
+            .as_deref()
+            .and_then(|m| registry_cost_usd(config, m, usage));
+        let charged_usd = match settings.pricing {
+            CostPricing::Config => priced.unwrap_or(usage.cost_usd),
+            CostPricing::Recorded if usage.cost_usd > 0.0 => usage.cost_usd,
+            CostPricing::Recorded => priced.unwrap_or(0.0),
+        };
+
+        for &group in groups {
+            for key in group_keys(graph, task, group) {
+                let line = lines
+                    .entry((group, key.clone()))
+                    .or_insert_with(|| UsageLine {
+                        month: month.to_string(),
+                        group: group.to_string(),
+                        key,
+                        currency: settings.currency.clone(),
+                        ..Default::default()
+                    });
+                line.tasks += 1;
+                line.input_tokens += usage.input_tokens;
+                line.output_tokens += usage.output_tokens;
+                line.cache_read_input_tokens += usage.cache_read_input_tokens;
+                line.cache_creation_input_tokens += usage.cache_creation_input_tokens;
+                line.recorded_usd += usage.cost_usd;
+                line.priced_usd += priced.unwrap_or(usage.cost_usd);
+                line.charge += charged_usd * settings.exchange_rate;
+            }
+        }
+    }
+    lines.into_values().collect()
+}
+
+/// Render report lines as CSV in [`CSV_COLUMNS`] order.
+pub fn to_csv(lines: &[UsageLine]) -> Result<String> {
+    let mut out = Vec::new();
+    {
+        let mut writer = csv::Writer::from_writer(&mut out);
+        writer.write_record(CSV_COLUMNS)?;
+        for l in lines {
+            writer.write_record([
+                l.month.clone(),
+                l.group.clone(),
+                l.key.clone(),
+                l.tasks.to_string(),
+                l.input_tokens.to_string(),
+                l.output_tokens.to_string(),
+                l.cache_read_input_tokens.to_string(),
+                l.cache_creation_input_tokens.to_string(),
+                format!("{:.4}", l.recorded_usd),
+                format!("{:.4}", l.priced_usd),
+                format!("{:.2}", l.charge),
+                l.currency.clone(),
+            ])?;
+        }
+        writer.flush()?;
+    }
+    Ok(String::from_utf8(out)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Node, Status, TokenUsage};
+    use crate::test_helpers::make_task;
+
+    fn spent(id: &str, tags: &[&str], model: Option<&str>, cost: f64, at: &str) -> Node {
+        let mut t = make_task(id, id);
+        t.status = Status::Done;
+        t.tags = tags.iter().map(|s| s.to_string()).collect();
+        t.model = model.map(String::from);
+        t.agent = Some("agent-a".to_string());
+        t.completed_at = Some(at.to_string());
+        t.token_usage = Some(TokenUsage {
+            cost_usd: cost,
+            input_tokens: 1_000_000,
+            output_tokens: 0,
+            cache_read_input_tokens: 0,
+            cache_creation_input_tokens: 0,
+        });
+        Node::Task(t)
+    }
+
+    fn graph() -> WorkGraph {
+        let mut graph = WorkGraph::new();
+        graph.add_node(spent(
+            "a",
+            &["billing", "api"],
+            Some("claude:opus"),
+            2.0,
+            "2026-03-10T10:00:00Z",
+        ));
+        graph.add_node(spent("b", &["billing"], None, 1.0, "2026-03-20T10:00:00Z"));
+        graph.add_node(spent("c", &[], None, 5.0, "2026-04-01T10:00:00Z"));
+        let mut release = make_task("release", "Release");
+        release.tags = vec!["milestone".to_string()];
+        release.after = vec!["a".to_string()];
+        graph.add_node(Node::Task(release));
+        graph
+    }
+
+    fn line<'a>(lines: &'a [UsageLine], group: &str, key: &str) -> &'a UsageLine {
+        lines
+            .iter()
+            .find(|l| l.group == group && l.key == key)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_groups_by_month_tag_and_milestone() {
+        let lines = monthly_usage(&graph(), &Config::default(), "2026-03", &GroupBy::ALL);
+
+        let billing = line(&lines, "tag", "billing");
+        assert_eq!(billing.tasks, 2);
+        assert_eq!(billing.recorded_usd, 3.0);
+        assert_eq!(line(&lines, "tag", "api").tasks, 1);
+        assert_eq!(line(&lines, "milestone", "release").tasks, 1);
+        assert_eq!(line(&lines, "milestone", NONE_KEY).tasks, 1);
+        assert_eq!(line(&lines, "model", "claude:opus").tasks, 1);
+        assert_eq!(line(&lines, "agent", "agent-a").tasks, 2);
+        // April's task is not in March's report.
+        assert!(lines.iter().all(|l| l.key != "c"));
+    }
+
+    #[test]
+    fn test_config_pricing_and_exchange_rate() {
+        let mut config = Config::default();
+        config.cost_report.currency = "EUR".to_string();
+        config.cost_report.exchange_rate = 0.5;
+
+        let lines = monthly_usage(&graph(), &config, "2026-03", &[GroupBy::Model]);
+        let opus = line(&lines, "model", "claude:opus");
+        assert_eq!(opus.recorded_usd, 2.0);
+        // 1M input tokens at the registry's opus rate.
+        assert_eq!(opus.priced_usd, 15.0);
+        assert_eq!(opus.charge, 1.0);
+        assert_eq!(opus.currency, "EUR");
+
+        config.cost_report.pricing = CostPricing::Config;
+        let lines = monthly_usage(&graph(), &config, "2026-03", &[GroupBy::Model]);
+        assert_eq!(line(&lines, "model", "claude:opus").charge, 7.5);
+        // Unpriced models keep their recorded cost.
+        assert_eq!(line(&lines, "model", NONE_KEY).charge, 0.5);
+    }
+
+    #[test]
+    fn test_parse_month_and_csv() {
+        assert_eq!(parse_month("2026-03").unwrap(), "2026-03");
+        assert!(parse_month("2026-13").is_err());
+        assert!(parse_month("March").is_err());
+
+        let lines = monthly_usage(&graph(), &Config::default(), "2026-03", &[GroupBy::Tag]);
+        let csv = to_csv(&lines).unwrap();
+        let mut rows = csv.lines();
+        assert_eq!(rows.next().unwrap(), CSV_COLUMNS.join(","));
+        assert_eq!(
+            rows.next().unwrap(),
+            "2026-03,tag,api,1,1000000,0,0,0,2.0000,15.0000,2.00,USD"
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Coverage reports and coverage-driven follow-up tasks.
+//!
+//! Line coverage is parsed per file from LCOV (`lcov.info`), Cobertura XML
+//! (`coverage.xml`) or `cargo llvm-cov --json` output. With
+//! `[coverage] followups = true`, completing a task that has a coverage
+//! report artifact creates one follow-up per under-covered file (see
+//! [`plan_followups`]).
+
+use chrono::Utc;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::config::CoverageConfig;
+use crate::graph::{PRIORITY_HIGH, PRIORITY_NORMAL, Task, WorkGraph};
+
+/// Prefix of generated follow-up task ids.
+pub const FOLLOWUP_ID_PREFIX: &str = "cover-";
+
+/// Line coverage for one source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileCoverage {
+    pub path: String,
+    pub lines_found: u64,
+    pub lines_hit: u64,
+}
+
+impl FileCoverage {
+    pub fn percent(&self) -> f64 {
+        if self.lines_found == 0 {
+            100.0
+        } else {
+            self.lines_hit as f64 * 100.0 / self.lines_found as f64
+        }
+    }
+}
+
+/// Sum entries for the same path, keeping first-seen order stable by path.
+fn merge(entries: impl IntoIterator<Item = FileCoverage>) -> Vec<FileCoverage> {
+    let mut by_path: BTreeMap<String, FileCoverage> = BTreeMap::new();
+    for e in entries {
+        let slot = by_path.entry(e.path.clone()).or_insert(FileCoverage {
+            path: e.path,
+            lines_found: 0,
+            lines_hit: 0,
+        });
+        slot.lines_found += e.lines_found;
+        slot.lines_hit += e.lines_hit;
+    }
+    by_path.into_values().collect()
+}
+
+/// Parse an LCOV tracefile. `LF`/`LH` totals are used when present,
+/// otherwise `DA` records are counted.
+pub fn parse_lcov(content: &str) -> Vec<FileCoverage> {
+    let mut out = Vec::new();
+    let mut path: Option<String> = None;
+    let (mut lf, mut lh) = (None::<u64>, None::<u64>);
+    let (mut da_found, mut da_hit) = (0u64, 0u64);
+    for line in content.lines().map(str::trim) {
+        if let Some(p) = line.strip_prefix("SF:") {
+            path = Some(p.to_string());
+            (lf, lh, da_found, da_hit) = (None, None, 0, 0);
+        } else if let Some(n) = line.strip_prefix("LF:") {
+            lf = n.parse().ok();
+        } else if let Some(n) = line.strip_prefix("LH:") {
+            lh = n.parse().ok();
+        } else if let Some(rest) = line.strip_prefix("DA:") {
+            da_found += 1;
+            let hits = rest.split(',').nth(1).and_then(|h| h.parse::<u64>().ok());
+            if hits.is_some_and(|h| h > 0) {
+                da_hit += 1;
+            }
+        } else if line == "end_of_record"
+            && let Some(p) = path.take()
+        {
+            out.push(FileCoverage {
+                path: p,
+                lines_found: lf.unwrap_or(da_found),
+                lines_hit: lh.unwrap_or(da_hit),
+            });
+        }
+    }
+    merge(out)
+}
+
+/// Parse Cobertura XML, counting `<line hits=..>` elements per class file.
+pub fn parse_cobertura(xml: &str) -> Vec<FileCoverage> {
+    static CLASS: OnceLock<Regex> = OnceLock::new();
+    static LINE: OnceLock<Regex> = OnceLock::new();
+    let class = CLASS.get_or_init(|| {
+        Regex::new(r#"(?s)<class\b[^>]*?\bfilename\s*=\s*"([^"]*)"[^>]*>(.*?)</class>"#)
+            .expect("class regex")
+    });
+    let line = LINE
+        .get_or_init(|| Regex::new(r#"<line\b[^>]*?\bhits\s*=\s*"(\d+)""#).expect("line regex"));
+    merge(class.captures_iter(xml).filter_map(|c| {
+        let hits: Vec<u64> = line
+            .captures_iter(&c[2])
+            .filter_map(|l| l[1].parse().ok())
+            .collect();
+        (!hits.is_empty()).then(|| FileCoverage {
+            path: c[1].to_string(),
+            lines_found: hits.len() as u64,
+            lines_hit: hits.iter().filter(|h| **h > 0).count() as u64,
+        })
+    }))
+}
+
+/// Parse `cargo llvm-cov --json` (LLVM coverage export) file summaries.
+pub fn parse_llvm_cov_json(content: &str) -> Vec<FileCoverage> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let files = value["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|d| d["files"].as_array())
+        .flatten();
+    merge(files.filter_map(|f| {
+        Some(FileCoverage {
+            path: f["filename"].as_str()?.to_string(),
+            lines_found: f["summary"]["lines"]["count"].as_u64()?,
+            lines_hit: f["summary"]["lines"]["covered"].as_u64()?,
+        })
+    }))
+}
+
+/// Parse a coverage report in any supported format. Returns `None` when no
+/// file coverage is recognised.
+pub fn parse(content: &str) -> Option<Vec<FileCoverage>> {
+    let files = if content.trim_start().starts_with('{') {
+        parse_llvm_cov_json(content)
+    } else if content.contains("<coverage") {
+        parse_cobertura(content)
+    } else {
+        parse_lcov(content)
+    };
+    (!files.is_empty()).then_some(files)
+}
+
+/// Whether an artifact path looks like a coverage report.
+pub fn is_coverage_report_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    let file = lower.rsplit('/').next().unwrap_or(&lower);
+    file.ends_with(".lcov")
+        || file == "lcov.info"
+        || file.ends_with(".info") && file.contains("lcov")
+        || (file.ends_with(".xml") || file.ends_with(".json"))
+            && (file.contains("coverage") || file.contains("cobertura"))
+}
+
+/// Make report paths relative to `project_root` where possible.
+pub fn relativize(files: &mut [FileCoverage], project_root: &Path) {
+    let root = project_root
+        .to_string_lossy()
+        .trim_end_matches('/')
+        .to_string();
+    for f in files {
+        if let Some(rest) = f.path.strip_prefix(&root) {
+            f.path = rest.trim_start_matches('/').to_string();
+        }
+    }
+}
+
+/// Task id of the follow-up for a file, e.g. `cover-src-parser-rs`.
+pub fn followup_id(path: &str) -> String {
+    let slug: String = path
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug: Vec<&str> = slug.split('-').filter(|s| !s.is_empty()).collect();
+    format!("{}{}", FOLLOWUP_ID_PREFIX, slug.join("-"))
+}
+
+/// Follow-up tasks for files below the coverage threshold, lowest coverage
+/// first, skipping files that already have a follow-up in the graph.
+///
+/// Files under half the threshold get high priority, the rest normal.
+pub fn plan_followups(
+    graph: &WorkGraph,
+    source: &Task,
+    report: &str,
+    files: &[FileCoverage],
+    config: &CoverageConfig,
+) -> Vec<Task> {
+    let mut low: Vec<&FileCoverage> = files
+        .iter()
+        .filter(|f| f.lines_found >= config.min_lines && f.percent() < config.threshold)
+        .filter(|f| graph.get_node(&followup_id(&f.path)).is_none())
+        .collect();
+    low.sort_by(|a, b| {
+        a.percent()
+            .partial_cmp(&b.percent())
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    if config.max_followups > 0 {
+        low.truncate(config.max_followups);
+    }
+
+    let now = Utc::now().to_rfc3339();
+    low.into_iter()
+        .map(|f| {
+            let pct = f.percent();
+            Task {
+                id: followup_id(&f.path),
+                title: format!("Improve test coverage for {}", f.path),
+                description: Some(format!(
+                    "Line coverage of `{}` is {:.1}% ({}/{} lines), below the {}% threshold.\n\
+                     Reported by `{}` from task `{}`.\n\n\
+                     Add tests for the uncovered code paths. Prefer behaviour-level tests \
+                     over tests that only execute lines.",
+                    f.path, pct, f.lines_hit, f.lines_found, config.threshold, report, source.id
+                )),
+                tags: vec![config.tag.clone()],
+                priority: if pct < config.threshold / 2.0 {
+                    PRIORITY_HIGH
+                } else {
+                    PRIORITY_NORMAL
+                },
+                after: vec![source.id.clone()],
+                created_at: Some(now.clone()),
+                ..Task::default()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+
+    fn file(path: &str, found: u64, hit: u64) -> FileCoverage {
+        FileCoverage {
+            path: path.to_string(),
+            lines_found: found,
+            lines_hit: hit,
+        }
+    }
+
+    #[test]
+    fn test_parse_lcov() {
+        let lcov = "TN:\nSF:/work/src/a.rs\nDA:1,1\nDA:2,0\nLF:40\nLH:10\nend_of_record\n\
+                    SF:/work/src/b.rs\nDA:1,3\nDA:2,0\nDA:3,0\nend_of_record\n";
+        let mut files = parse(lcov).unwrap();
+        relativize(&mut files, Path::new("/work/"));
+        assert_eq!(
+            files,
+            vec![file("src/a.rs", 40, 10), file("src/b.rs", 3, 1)]
+        );
+        assert_eq!(files[0].percent(), 25.0);
+    }
+
+    #[test]
+    fn test_parse_cobertura_and_llvm_json() {
+        let xml = r#"<?xml version="1.0"?><coverage line-rate="0.5"><packages><package>
+<classes><class name="a" filename="pkg/a.py" line-rate="0.5"><lines>
+<line number="1" hits="2"/><line number="2" hits="0"/></lines></class>
+<class name="a2" filename="pkg/a.py"><lines><line number="9" hits="1"/></lines></class>
+</classes></package></packages></coverage>"#;
+        assert_eq!(parse(xml).unwrap(), vec![file("pkg/a.py", 3, 2)]);
+
+        let json = r#"{"data":[{"files":[{"filename":"src/x.rs","summary":{"lines":{"count":20,"covered":5,"percent":25.0}}}]}],"type":"llvm.coverage.json.export"}"#;
+        assert_eq!(parse(json).unwrap(), vec![file("src/x.rs", 20, 5)]);
+        assert!(parse("not a report").is_none());
+    }
+
+    #[test]
+    fn test_is_coverage_report_path() {
+        assert!(is_coverage_report_path("target/lcov.info"));
+        assert!(is_coverage_report_path("out/coverage.xml"));
+        assert!(is_coverage_report_path("cobertura.xml"));
+        assert!(is_coverage_report_path("target/llvm-cov/coverage.json"));
+        assert!(!is_coverage_report_path("junit.xml"));
+        assert!(!is_coverage_report_path("src/coverage.rs"));
+    }
+
+    #[test]
+    fn test_plan_followups() {
+        let mut graph = WorkGraph::new();
+        let source = Task {
+            id: "impl".to_string(),
+            ..Task::default()
+        };
+        graph.add_node(Node::Task(Task {
+            id: followup_id("src/done.rs"),
+            ..Task::default()
+        }));
+        let files = vec![
+            file("src/ok.rs", 100, 90),
+            file("src/half.rs", 100, 45),
+            file("src/bad.rs", 100, 10),
+            file("src/tiny.rs", 4, 0),
+            file("src/done.rs", 100, 0),
+        ];
+        let config = CoverageConfig {
+            followups: true,
+            ..CoverageConfig::default()
+        };
+
+        let tasks = plan_followups(&graph, &source, "lcov.info", &files, &config);
+        let ids: Vec<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["cover-src-bad-rs", "cover-src-half-rs"]);
+        assert_eq!(tasks[0].priority, PRIORITY_HIGH);
+        assert_eq!(tasks[1].priority, PRIORITY_NORMAL);
+        assert_eq!(tasks[0].tags, vec!["coverage"]);
+        assert_eq!(tasks[0].after, vec!["impl"]);
+        assert!(
+            tasks[0]
+                .description
+                .as_ref()
+                .unwrap()
+                .contains("10.0% (10/100 lines)")
+        );
+
+        let capped = CoverageConfig {
+            max_followups: 1,
+            ..config
+        };
+        assert_eq!(
+            plan_followups(&graph, &source, "lcov.info", &files, &capped).len(),
+            1
+        );
+    }
+}
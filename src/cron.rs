@@ -1,4 +1,4 @@
-use crate::graph::Task;
+use crate::graph::{LogEntry, Task};
 use chrono::{DateTime, Duration, Utc};
 use cron::Schedule;
 use std::collections::hash_map::DefaultHasher;
@@ -18,6 +18,8 @@ pub enum CronError {
 ///
 /// Supports both 5-field ("min hour day month dow") and 6-field ("sec min hour day month dow") formats.
 /// 5-field expressions are automatically converted to 6-field by prepending "0" for seconds.
+/// iCalendar recurrence rules (`RRULE:FREQ=WEEKLY;BYDAY=MO;BYHOUR=9`) are
+/// translated with [`rrule_to_cron`].
 ///
 /// # Arguments
 /// * `expr` - A cron expression string (5 or 6 field format)
@@ -33,6 +35,10 @@ pub enum CronError {
 /// let schedule2 = parse_cron_expression("0 0 2 * * *").unwrap();  // 6-field: daily at 2 AM
 /// ```
 pub fn parse_cron_expression(expr: &str) -> Result<Schedule, CronError> {
+    if is_rrule(expr) {
+        let cron = rrule_to_cron(expr)?;
+        return Schedule::from_str(&cron).map_err(CronError::ParseError);
+    }
     let parts: Vec<&str> = expr.split_whitespace().collect();
 
     let expr_to_parse = match parts.len() {
@@ -55,6 +61,174 @@ pub fn parse_cron_expression(expr: &str) -> Result<Schedule, CronError> {
     Schedule::from_str(&expr_to_parse).map_err(CronError::ParseError)
 }
 
+/// Whether `expr` is an iCalendar RRULE rather than a cron expression.
+pub fn is_rrule(expr: &str) -> bool {
+    let expr = expr.trim().to_ascii_uppercase();
+    expr.starts_with("RRULE:") || expr.starts_with("FREQ=")
+}
+
+fn rrule_day(day: &str) -> Option<&'static str> {
+    Some(match day {
+        "MO" => "Mon",
+        "TU" => "Tue",
+        "WE" => "Wed",
+        "TH" => "Thu",
+        "FR" => "Fri",
+        "SA" => "Sat",
+        "SU" => "Sun",
+        _ => return None,
+    })
+}
+
+/// Translate an iCalendar RRULE into a 6-field cron expression.
+///
+/// Supports `FREQ` (MINUTELY through YEARLY), `INTERVAL`, `BYMINUTE`,
+/// `BYHOUR`, `BYDAY` (plain weekdays), `BYMONTHDAY` and `BYMONTH`. Times are
+/// UTC and default to midnight, since there is no `DTSTART`. `INTERVAL` on
+/// DAILY and MONTHLY steps through the day-of-month / month fields, so it
+/// restarts at each month / year boundary. Rules cron cannot express
+/// (`COUNT`, `UNTIL`, ordinal `BYDAY` like `1MO`, WEEKLY or YEARLY
+/// intervals) are rejected.
+///
+/// ```
+/// use workgraph::cron::rrule_to_cron;
+///
+/// assert_eq!(
+///     rrule_to_cron("RRULE:FREQ=WEEKLY;BYDAY=MO,TH;BYHOUR=9").unwrap(),
+///     "0 0 9 * * Mon,Thu"
+/// );
+/// ```
+pub fn rrule_to_cron(rule: &str) -> Result<String, CronError> {
+    let invalid = |msg: String| CronError::InvalidExpression(format!("RRULE {}", msg));
+    let body = rule.trim();
+    let body = body
+        .get(..6)
+        .filter(|p| p.eq_ignore_ascii_case("RRULE:"))
+        .map_or(body, |_| &body[6..]);
+
+    let mut freq = None;
+    let mut interval = 1u32;
+    let (mut minute, mut hour, mut dom, mut month, mut dow) = (None, None, None, None, None);
+    for part in body.split(';').filter(|p| !p.trim().is_empty()) {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| invalid(format!("part '{}' is not KEY=VALUE", part)))?;
+        let value = value.trim().to_ascii_uppercase();
+        let list = |value: &str| -> Result<String, CronError> {
+            value
+                .split(',')
+                .map(|v| {
+                    v.trim()
+                        .parse::<u32>()
+                        .map(|n| n.to_string())
+                        .map_err(|_| invalid(format!("{} value '{}' is not a number", key, v)))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|v| v.join(","))
+        };
+        match key.trim().to_ascii_uppercase().as_str() {
+            "FREQ" => freq = Some(value),
+            "INTERVAL" => {
+                interval = value
+                    .parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| invalid(format!("INTERVAL '{}' must be positive", value)))?
+            }
+            "BYMINUTE" => minute = Some(list(&value)?),
+            "BYHOUR" => hour = Some(list(&value)?),
+            "BYMONTHDAY" => dom = Some(list(&value)?),
+            "BYMONTH" => month = Some(list(&value)?),
+            "BYDAY" => {
+                let days = value
+                    .split(',')
+                    .map(|d| {
+                        rrule_day(d.trim())
+                            .ok_or_else(|| invalid(format!("BYDAY '{}' is not supported", d)))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                dow = Some(days.join(","));
+            }
+            "WKST" => {}
+            other => return Err(invalid(format!("{} is not supported", other))),
+        }
+    }
+
+    let step = |start: u32| {
+        if interval == 1 {
+            "*".to_string()
+        } else {
+            format!("{}/{}", start, interval)
+        }
+    };
+    let freq = freq.ok_or_else(|| invalid("needs FREQ".to_string()))?;
+    match freq.as_str() {
+        "MINUTELY" => {
+            minute = minute.or_else(|| Some(step(0)));
+        }
+        "HOURLY" => {
+            hour = hour.or_else(|| Some(step(0)));
+        }
+        "DAILY" => {
+            if interval > 1 && dom.is_none() {
+                dom = Some(step(1));
+            }
+        }
+        "WEEKLY" => {
+            if interval > 1 {
+                return Err(invalid(
+                    "WEEKLY with INTERVAL > 1 is not supported".to_string(),
+                ));
+            }
+            dow = dow.or_else(|| Some("Mon".to_string()));
+        }
+        "MONTHLY" => {
+            if dow.is_none() {
+                dom = dom.or_else(|| Some("1".to_string()));
+            }
+            if interval > 1 && month.is_none() {
+                month = Some(step(1));
+            }
+        }
+        "YEARLY" => {
+            if interval > 1 {
+                return Err(invalid(
+                    "YEARLY with INTERVAL > 1 is not supported".to_string(),
+                ));
+            }
+            if dow.is_none() {
+                dom = dom.or_else(|| Some("1".to_string()));
+            }
+            month = month.or_else(|| Some("1".to_string()));
+        }
+        other => return Err(invalid(format!("FREQ '{}' is not supported", other))),
+    }
+
+    let any = || "*".to_string();
+    let minute = minute.unwrap_or_else(|| {
+        if freq == "MINUTELY" {
+            any()
+        } else {
+            "0".to_string()
+        }
+    });
+    let hour = hour.unwrap_or_else(|| {
+        if freq == "MINUTELY" {
+            any()
+        } else {
+            "0".to_string()
+        }
+    });
+    Ok(format!(
+        "0 {} {} {} {} {}",
+        minute,
+        hour,
+        dom.unwrap_or_else(any),
+        month.unwrap_or_else(any),
+        dow.unwrap_or_else(any)
+    ))
+}
+
 /// Calculate the next fire time for a cron schedule from a given datetime
 ///
 /// # Arguments
@@ -175,6 +349,17 @@ pub fn reset_cron_task(task: &mut Task) -> bool {
     task.next_cron_fire =
         calculate_next_fire_with_jitter(&task.id, &schedule, now).map(|dt| dt.to_rfc3339());
 
+    // Keep a trace of each completed run in the log
+    task.log.push(LogEntry {
+        timestamp: now.to_rfc3339(),
+        actor: Some("dispatcher".to_string()),
+        user: None,
+        message: format!(
+            "Recurring task reopened after completion (next fire: {})",
+            task.next_cron_fire.as_deref().unwrap_or("unknown")
+        ),
+    });
+
     // Reset task to Open for next cron cycle
     task.status = crate::graph::Status::Open;
     task.assigned = None;
@@ -272,6 +457,42 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_rrule_to_cron() {
+        assert_eq!(rrule_to_cron("FREQ=DAILY;BYHOUR=6").unwrap(), "0 0 6 * * *");
+        assert_eq!(
+            rrule_to_cron("RRULE:FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=30").unwrap(),
+            "0 30 9 * * Mon"
+        );
+        assert_eq!(rrule_to_cron("FREQ=WEEKLY").unwrap(), "0 0 0 * * Mon");
+        assert_eq!(
+            rrule_to_cron("FREQ=HOURLY;INTERVAL=4").unwrap(),
+            "0 0 0/4 * * *"
+        );
+        assert_eq!(
+            rrule_to_cron("FREQ=MINUTELY;INTERVAL=15").unwrap(),
+            "0 0/15 * * * *"
+        );
+        assert_eq!(
+            rrule_to_cron("FREQ=MONTHLY;INTERVAL=3;BYMONTHDAY=15").unwrap(),
+            "0 0 0 15 1/3 *"
+        );
+        assert_eq!(rrule_to_cron("FREQ=YEARLY").unwrap(), "0 0 0 1 1 *");
+
+        assert!(rrule_to_cron("FREQ=WEEKLY;INTERVAL=2").is_err());
+        assert!(rrule_to_cron("FREQ=MONTHLY;BYDAY=1MO").is_err());
+        assert!(rrule_to_cron("FREQ=DAILY;COUNT=3").is_err());
+        assert!(rrule_to_cron("BYHOUR=9").is_err());
+
+        // Parsed schedules fire where the rule says
+        let schedule = parse_cron_expression("RRULE:FREQ=WEEKLY;BYDAY=FR;BYHOUR=17").unwrap();
+        let from = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(); // Monday
+        assert_eq!(
+            calculate_next_fire(&schedule, from).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 5, 17, 0, 0).unwrap()
+        );
+    }
+
     #[test]
     fn test_parse_cron_expression_invalid() {
         // Invalid format
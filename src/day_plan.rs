@@ -0,0 +1,403 @@
+//! Daily plans (`wg plan today`).
+//!
+//! A day plan fills the time a participant has left today with work from
+//! their focus queue (see [`crate::focus`]): tasks they already have in
+//! progress first, then the queue in order, skipping anything that no
+//! longer fits. Available time is today's working block from their working
+//! calendar (or `day_plan.day_hours` from now without one), less any
+//! availability windows that overlap it. Tasks without an estimate count
+//! as `day_plan.unestimated_hours`.
+//!
+//! The service can post plans to Matrix each morning; see
+//! [`crate::config::DayPlanConfig`].
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::availability::AvailabilityCalendar;
+use crate::config::{Config, DayPlanConfig};
+use crate::focus::{self, Focuser};
+use crate::graph::{Status, WorkGraph, aged_priority};
+use crate::working_hours::{WorkingCalendar, WorkingHours};
+
+/// Dates each participant's plan was last posted, keyed by name.
+const POSTED_FILE: &str = "day_plans_posted.json";
+
+/// One scheduled task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanEntry {
+    pub id: String,
+    pub title: String,
+    pub hours: f64,
+    /// Whether `hours` comes from the task's estimate.
+    pub estimated: bool,
+    pub in_progress: bool,
+    /// RFC 3339, in the participant's local offset.
+    pub start: String,
+    pub end: String,
+}
+
+/// A proposed plan for one participant's day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayPlan {
+    pub who: String,
+    /// Local date, `YYYY-MM-DD`.
+    pub date: String,
+    /// Hours available today after availability windows.
+    pub capacity_hours: f64,
+    pub planned_hours: f64,
+    pub entries: Vec<PlanEntry>,
+    /// Queue items that did not fit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deferred: Vec<String>,
+    /// Why nothing could be planned (day off, away, day over).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl DayPlan {
+    /// Plain-text rendering, as printed and posted.
+    pub fn message(&self) -> String {
+        let mut out = format!("Plan for {} on {}", self.who, self.date);
+        if let Some(ref note) = self.note {
+            out.push_str(&format!(": {}", note));
+            return out;
+        }
+        out.push_str(&format!(
+            " ({}h of {}h available):",
+            crate::format_hours(self.planned_hours),
+            crate::format_hours(self.capacity_hours)
+        ));
+        if self.entries.is_empty() {
+            out.push_str("\n  nothing ready to pick up");
+        }
+        for e in &self.entries {
+            out.push_str(&format!(
+                "\n  {}-{}  {} - {}{}",
+                clock(&e.start),
+                clock(&e.end),
+                e.id,
+                e.title,
+                match (e.in_progress, e.estimated) {
+                    (true, _) => " (in progress)",
+                    (false, false) => " (unestimated)",
+                    _ => "",
+                }
+            ));
+        }
+        if !self.deferred.is_empty() {
+            out.push_str(&format!("\n  Doesn't fit: {}", self.deferred.join(", ")));
+        }
+        out
+    }
+}
+
+fn clock(rfc3339: &str) -> String {
+    DateTime::parse_from_rfc3339(rfc3339)
+        .map(|t| t.format("%H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Propose today's plan for `who` as of `now`.
+pub fn build(
+    graph: &WorkGraph,
+    who: &Focuser,
+    now: DateTime<Utc>,
+    calendar: Option<&WorkingCalendar>,
+    availability: &AvailabilityCalendar,
+    config: &DayPlanConfig,
+    aging_hours: u64,
+) -> DayPlan {
+    let offset = calendar
+        .map(WorkingCalendar::offset)
+        .unwrap_or_else(|| FixedOffset::east_opt(0).expect("zero offset"));
+    let local = |t: DateTime<Utc>| t.with_timezone(&offset).to_rfc3339();
+    let mut plan = DayPlan {
+        who: who.name.clone(),
+        date: now.with_timezone(&offset).format("%Y-%m-%d").to_string(),
+        capacity_hours: 0.0,
+        planned_hours: 0.0,
+        entries: Vec::new(),
+        deferred: Vec::new(),
+        note: None,
+    };
+
+    let (start, end) = match calendar {
+        Some(cal) => match cal.day_window(now) {
+            Some((start, end)) => (start.max(now), end),
+            None => {
+                plan.note = Some("not a working day".to_string());
+                return plan;
+            }
+        },
+        None => (
+            now,
+            now + Duration::seconds((config.day_hours.max(0.0) * 3600.0) as i64),
+        ),
+    };
+    if start >= end {
+        plan.note = Some("working day is over".to_string());
+        return plan;
+    }
+
+    let mut names = vec![who.name.as_str()];
+    names.extend(who.agent_id.as_deref());
+    let away = availability.unavailable_fraction(&names, start, end);
+    if away >= 1.0 {
+        plan.note = Some("unavailable all day".to_string());
+        return plan;
+    }
+    plan.capacity_hours = (end - start).num_seconds() as f64 / 3600.0 * (1.0 - away);
+
+    // Work already under way comes first, then the focus queue.
+    let mut in_progress: Vec<_> = graph
+        .tasks()
+        .filter(|t| {
+            t.status == Status::InProgress && t.assigned.as_deref() == Some(who.name.as_str())
+        })
+        .collect();
+    in_progress.sort_by(|a, b| {
+        aged_priority(b, now, aging_hours)
+            .cmp(&aged_priority(a, now, aging_hours))
+            .then(a.id.cmp(&b.id))
+    });
+    let queued = focus::compute(graph, who, usize::MAX, now, aging_hours);
+    let candidates = in_progress.into_iter().map(|t| (t, true)).chain(
+        queued
+            .iter()
+            .filter_map(|i| graph.get_task(&i.id))
+            .map(|t| (t, false)),
+    );
+
+    let mut cursor = start;
+    for (task, in_progress) in candidates {
+        let estimate = task.estimate.as_ref().and_then(|e| e.hours);
+        let hours = estimate.unwrap_or(config.unestimated_hours);
+        if plan.planned_hours + hours > plan.capacity_hours + 1e-9 {
+            plan.deferred.push(task.id.clone());
+            continue;
+        }
+        let finish = match calendar {
+            Some(cal) => cal.add_working_hours(cursor, hours),
+            None => cursor + Duration::seconds((hours * 3600.0) as i64),
+        };
+        plan.entries.push(PlanEntry {
+            id: task.id.clone(),
+            title: task.title.clone(),
+            hours,
+            estimated: estimate.is_some(),
+            in_progress,
+            start: local(cursor),
+            end: local(finish),
+        });
+        plan.planned_hours += hours;
+        cursor = finish;
+    }
+    plan
+}
+
+/// Build today's plan for `name` from the project on disk.
+pub fn build_for(workgraph_dir: &Path, name: &str, now: DateTime<Utc>) -> Result<DayPlan> {
+    let graph = crate::parser::load_graph(workgraph_dir.join("graph.jsonl"))
+        .context("Failed to load graph")?;
+    let config = Config::load_or_default(workgraph_dir);
+    let calendars = WorkingHours::load_or_warn(workgraph_dir);
+    let who = Focuser::resolve(workgraph_dir, name);
+    Ok(build(
+        &graph,
+        &who,
+        now,
+        calendars.for_participant(name),
+        &AvailabilityCalendar::load_or_warn(workgraph_dir),
+        &config.day_plan,
+        config.coordinator.priority_aging_hours,
+    ))
+}
+
+/// Post a plan to the Matrix default room.
+#[cfg(feature = "matrix-lite")]
+pub fn post(workgraph_dir: &Path, plan: &DayPlan) -> Result<()> {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    rt.block_on(crate::matrix_lite::send_notification(
+        workgraph_dir,
+        &plan.message(),
+    ))
+}
+
+#[cfg(not(feature = "matrix-lite"))]
+pub fn post(_workgraph_dir: &Path, _plan: &DayPlan) -> Result<()> {
+    anyhow::bail!("Matrix support not compiled in (enable the matrix-lite feature)")
+}
+
+fn posted_path(workgraph_dir: &Path) -> std::path::PathBuf {
+    workgraph_dir.join("service").join(POSTED_FILE)
+}
+
+/// Plans due for their morning post: every `day_plan.post_for` participant
+/// whose local `post_at` time has passed and who has not had today's plan
+/// yet. Days off are skipped. Returned plans are recorded as posted, so a
+/// failed post is not retried until the next day.
+pub fn due_morning_plans(workgraph_dir: &Path, now: DateTime<Utc>) -> Result<Vec<DayPlan>> {
+    let config = Config::load_or_default(workgraph_dir);
+    let Some(ref post_at) = config.day_plan.post_at else {
+        return Ok(Vec::new());
+    };
+    if config.day_plan.post_for.is_empty() {
+        return Ok(Vec::new());
+    }
+    let post_at = NaiveTime::parse_from_str(post_at, "%H:%M")
+        .with_context(|| format!("Invalid day_plan.post_at '{}': expected HH:MM", post_at))?;
+
+    let path = posted_path(workgraph_dir);
+    let mut posted: BTreeMap<String, NaiveDate> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+
+    let calendars = WorkingHours::load_or_warn(workgraph_dir);
+    let mut due = Vec::new();
+    for name in &config.day_plan.post_for {
+        let calendar = calendars.for_participant(name);
+        let local = match calendar {
+            Some(cal) => now.with_timezone(&cal.offset()).naive_local(),
+            None => now.naive_utc(),
+        };
+        if local.time() < post_at || posted.get(name) == Some(&local.date()) {
+            continue;
+        }
+        if calendar.is_none_or(|cal| cal.day_window(now).is_some()) {
+            due.push(build_for(workgraph_dir, name, now)?);
+        }
+        posted.insert(name.clone(), local.date());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&posted)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(due)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Estimate, Node};
+    use crate::test_helpers::make_task;
+    use crate::working_hours::WorkingHoursConfig;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn task(id: &str, priority: u32, hours: Option<f64>) -> Node {
+        let mut t = make_task(id, id);
+        t.priority = priority;
+        t.estimate = hours.map(|h| Estimate {
+            hours: Some(h),
+            cost: None,
+        });
+        Node::Task(t)
+    }
+
+    fn alice_calendar() -> WorkingHours {
+        let config: WorkingHoursConfig = toml::from_str(
+            r#"
+            hours = "09:00-17:00"
+            timezone = "+02:00"
+            [participants.alice]
+            "#,
+        )
+        .unwrap();
+        WorkingHours::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn test_build_fills_remaining_working_day() {
+        let mut graph = WorkGraph::new();
+        let mut doing = make_task("doing", "doing");
+        doing.status = Status::InProgress;
+        doing.assigned = Some("alice".into());
+        doing.estimate = Some(Estimate {
+            hours: Some(2.0),
+            cost: None,
+        });
+        graph.add_node(Node::Task(doing));
+        graph.add_node(task("big", 50, Some(5.0)));
+        graph.add_node(task("medium", 10, Some(3.0)));
+        graph.add_node(task("vague", 5, None));
+
+        let who = Focuser {
+            name: "alice".into(),
+            ..Focuser::default()
+        };
+        let hours = alice_calendar();
+        let cal = hours.for_participant("alice");
+        // 11:00 local on a Monday: six working hours left.
+        let now = at("2026-03-02T09:00:00Z");
+        let plan = build(
+            &graph,
+            &who,
+            now,
+            cal,
+            &AvailabilityCalendar::default(),
+            &DayPlanConfig::default(),
+            0,
+        );
+        assert_eq!(plan.date, "2026-03-02");
+        assert_eq!(plan.capacity_hours, 6.0);
+        let ids: Vec<&str> = plan.entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["doing", "medium", "vague"]);
+        assert_eq!(plan.deferred, ["big"]);
+        assert_eq!(plan.entries[0].start, "2026-03-02T11:00:00+02:00");
+        assert_eq!(plan.entries[2].end, "2026-03-02T17:00:00+02:00");
+        assert!(!plan.entries[2].estimated);
+        assert!(
+            plan.message()
+                .contains("11:00-13:00  doing - doing (in progress)")
+        );
+
+        // Half the remaining day away halves the capacity.
+        let mut away = AvailabilityCalendar::default();
+        away.add("alice", now, now + Duration::hours(3), None);
+        let plan = build(&graph, &who, now, cal, &away, &DayPlanConfig::default(), 0);
+        assert_eq!(plan.capacity_hours, 3.0);
+
+        // Saturday is a day off.
+        let plan = build(
+            &graph,
+            &who,
+            at("2026-03-07T09:00:00Z"),
+            cal,
+            &AvailabilityCalendar::default(),
+            &DayPlanConfig::default(),
+            0,
+        );
+        assert!(plan.entries.is_empty());
+        assert_eq!(plan.note.as_deref(), Some("not a working day"));
+    }
+
+    #[test]
+    fn test_morning_plans_due_once_per_day() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::parser::save_graph(&WorkGraph::new(), dir.path().join("graph.jsonl")).unwrap();
+        std::fs::write(
+            dir.path().join("config.toml"),
+            "[day_plan]\npost_at = \"08:30\"\npost_for = [\"alice\"]\n",
+        )
+        .unwrap();
+
+        let due = |t: &str| due_morning_plans(dir.path(), at(t)).unwrap();
+        assert!(due("2026-03-02T08:00:00Z").is_empty());
+        let first = due("2026-03-02T08:45:00Z");
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].who, "alice");
+        assert!(due("2026-03-02T12:00:00Z").is_empty());
+        assert_eq!(due("2026-03-03T08:45:00Z").len(), 1);
+    }
+}
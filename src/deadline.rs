@@ -0,0 +1,600 @@
+//! Deadlines and SLA tracking.
+//!
+//! A task with a `due` timestamp breaches its deadline once `due` passes
+//! while the task is still unfinished. Milestones (tasks tagged `milestone`)
+//! are also checked ahead of time: their critical-path forecast — now plus
+//! the longest chain of remaining estimated hours through unfinished
+//! upstream work, counted in working time for assignees with a working
+//! calendar — is compared against `due`, and a forecast that lands after it
+//! marks the milestone as at risk.
+//!
+//! The service runs [`run_sweep`] after each coordinator tick. Each alert
+//! fires once per task (`deadline_alert` records the strongest alert already
+//! sent) and goes out on the urgent route of `notify.toml` (e.g. a webhook),
+//! falling back to the Matrix default room. Every alert is written to the
+//! task log. The alert is cleared once the task is back on schedule.
+
+use crate::escalation::MILESTONE_TAG;
+use crate::graph::{LogEntry, Status, Task, WorkGraph, parse_delay};
+use crate::notify::EventType;
+use crate::notify::config::NotifyConfig;
+use crate::parser::modify_graph;
+use crate::working_hours::WorkingHours;
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Kind of deadline alert, ordered by severity.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadlineAlert {
+    /// Milestone whose critical-path forecast finishes after `due`.
+    AtRisk,
+    /// Unfinished task past `due`.
+    Overdue,
+}
+
+impl std::fmt::Display for DeadlineAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AtRisk => write!(f, "at risk"),
+            Self::Overdue => write!(f, "overdue"),
+        }
+    }
+}
+
+/// A task that is past due, or a milestone forecast to miss its due date.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadlineBreach {
+    pub task_id: String,
+    pub title: String,
+    pub status: Status,
+    pub kind: DeadlineAlert,
+    pub due: String,
+    /// Seconds past `due` (negative while time is left).
+    pub late_secs: i64,
+    /// Critical-path forecast, for milestones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forecast: Option<String>,
+    /// Seconds the forecast lands after `due`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slip_secs: Option<i64>,
+}
+
+impl DeadlineBreach {
+    /// Text sent to the notification channel.
+    pub fn message(&self) -> String {
+        match self.kind {
+            DeadlineAlert::Overdue => format!(
+                "Deadline breached: task '{}' ({}) was due {} and is {} overdue ({})",
+                self.task_id,
+                self.title,
+                self.due,
+                crate::format_duration(self.late_secs, false),
+                self.status,
+            ),
+            DeadlineAlert::AtRisk => format!(
+                "Milestone at risk: '{}' ({}) is due {} but the critical path forecasts {} ({} late)",
+                self.task_id,
+                self.title,
+                self.due,
+                self.forecast.as_deref().unwrap_or("?"),
+                crate::format_duration(self.slip_secs.unwrap_or(0), false),
+            ),
+        }
+    }
+
+    /// Line recorded in the task log.
+    fn log_message(&self, outcome: &str) -> String {
+        match self.kind {
+            DeadlineAlert::Overdue => format!(
+                "Deadline breached ({} overdue): {}",
+                crate::format_duration(self.late_secs, false),
+                outcome
+            ),
+            DeadlineAlert::AtRisk => format!(
+                "Deadline at risk (forecast {} late): {}",
+                crate::format_duration(self.slip_secs.unwrap_or(0), false),
+                outcome
+            ),
+        }
+    }
+}
+
+/// Parse a `--due` value: an RFC 3339 timestamp, `YYYY-MM-DDTHH:MM:SS`
+/// (UTC), a bare date (end of that day, UTC), or a duration from `now`
+/// such as `3d` or `12h`.
+pub fn parse_due(s: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(dt) = s.parse::<DateTime<Utc>>() {
+        return Some(dt);
+    }
+    if let Ok(ndt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
+        return Some(ndt.and_utc());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return date.and_hms_opt(23, 59, 59).map(|ndt| ndt.and_utc());
+    }
+    parse_delay(s).map(|secs| now + Duration::seconds(secs as i64))
+}
+
+/// The task's deadline, if set and parseable.
+pub fn due_at(task: &Task) -> Option<DateTime<Utc>> {
+    task.due.as_deref()?.parse::<DateTime<Utc>>().ok()
+}
+
+/// Critical-path forecast for finishing `task_id`.
+///
+/// Works forward from `now` through the task's unfinished upstream work,
+/// the task itself included: each task starts once all of its dependencies
+/// are forecast to finish, plus the lag and weight of the edge (and no
+/// earlier than its `not_before`), then takes
+/// its remaining estimated hours. Those hours count only working time when
+/// a calendar in `calendars` applies to the assignee. In-progress tasks
+/// count only the part of their estimate not yet spent; tasks without an
+/// estimate count as zero.
+pub fn forecast_finish(
+    graph: &WorkGraph,
+    task_id: &str,
+    now: DateTime<Utc>,
+    calendars: &WorkingHours,
+) -> DateTime<Utc> {
+    let mut memo = HashMap::new();
+    let mut visiting = HashSet::new();
+    finish_at(graph, task_id, now, calendars, &mut memo, &mut visiting)
+}
+
+fn finish_at(
+    graph: &WorkGraph,
+    task_id: &str,
+    now: DateTime<Utc>,
+    calendars: &WorkingHours,
+    memo: &mut HashMap<String, DateTime<Utc>>,
+    visiting: &mut HashSet<String>,
+) -> DateTime<Utc> {
+    if let Some(&at) = memo.get(task_id) {
+        return at;
+    }
+    // Back edges of a cycle add nothing.
+    if !visiting.insert(task_id.to_string()) {
+        return now;
+    }
+    let at = match graph.get_task(task_id) {
+        Some(task) if !task.status.is_terminal() => {
+            let upstream = task
+                .after
+                .iter()
+                .map(|dep| {
+                    let finish = finish_at(graph, dep, now, calendars, memo, visiting);
+                    let Some(edge) = task.edges.get(dep) else {
+                        return finish;
+                    };
+                    // A finished upstream task's lag runs from when it completed.
+                    let from = graph
+                        .get_task(dep)
+                        .filter(|t| t.status.is_terminal())
+                        .and_then(|t| t.completed_at.as_deref())
+                        .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+                        .unwrap_or(finish);
+                    from + Duration::seconds((edge.hours() * 3600.0) as i64)
+                })
+                .fold(now, Ord::max);
+            let start = task
+                .not_before
+                .as_deref()
+                .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+                .map_or(upstream, |nb| upstream.max(nb));
+            calendars.finish(task, start, remaining_hours(task, now))
+        }
+        _ => now,
+    };
+    visiting.remove(task_id);
+    memo.insert(task_id.to_string(), at);
+    at
+}
+
+fn remaining_hours(task: &Task, now: DateTime<Utc>) -> f64 {
+    let estimate = task.estimate.as_ref().and_then(|e| e.hours).unwrap_or(0.0);
+    if task.status == Status::InProgress
+        && let Some(started) = task
+            .started_at
+            .as_deref()
+            .and_then(|s| s.parse::<DateTime<Utc>>().ok())
+    {
+        let spent = (now - started).num_seconds() as f64 / 3600.0;
+        return (estimate - spent).max(0.0);
+    }
+    estimate
+}
+
+/// Unfinished tasks past their deadline and milestones forecast to miss
+/// theirs, most late first.
+pub fn check(
+    graph: &WorkGraph,
+    now: DateTime<Utc>,
+    calendars: &WorkingHours,
+) -> Vec<DeadlineBreach> {
+    let mut breaches = Vec::new();
+    for task in graph.tasks() {
+        if task.status.is_terminal() {
+            continue;
+        }
+        let Some(due) = due_at(task) else {
+            continue;
+        };
+        let forecast = task
+            .tags
+            .iter()
+            .any(|t| t == MILESTONE_TAG)
+            .then(|| forecast_finish(graph, &task.id, now, calendars));
+        let kind = if now > due {
+            DeadlineAlert::Overdue
+        } else if forecast.is_some_and(|f| f > due) {
+            DeadlineAlert::AtRisk
+        } else {
+            continue;
+        };
+        breaches.push(DeadlineBreach {
+            task_id: task.id.clone(),
+            title: task.title.clone(),
+            status: task.status,
+            kind,
+            due: due.to_rfc3339(),
+            late_secs: (now - due).num_seconds(),
+            forecast: forecast.map(|f| f.to_rfc3339()),
+            slip_secs: forecast.map(|f| (f - due).num_seconds()),
+        });
+    }
+    breaches.sort_by(|a, b| {
+        b.late_secs
+            .cmp(&a.late_secs)
+            .then_with(|| a.task_id.cmp(&b.task_id))
+    });
+    breaches
+}
+
+/// Unfinished tasks due between `now` and `now + within_secs`, soonest first.
+pub fn due_within(graph: &WorkGraph, now: DateTime<Utc>, within_secs: u64) -> Vec<&Task> {
+    let horizon = now + Duration::seconds(within_secs as i64);
+    let mut tasks: Vec<(DateTime<Utc>, &Task)> = graph
+        .tasks()
+        .filter(|t| !t.status.is_terminal())
+        .filter_map(|t| due_at(t).map(|due| (due, t)))
+        .filter(|(due, _)| *due >= now && *due <= horizon)
+        .collect();
+    tasks.sort_by_key(|(due, _)| *due);
+    tasks.into_iter().map(|(_, t)| t).collect()
+}
+
+/// Deliver an urgent alert about `subject` (a task ID or other short key).
+/// Returns a short outcome for the log.
+///
+/// Tries the urgent route of the notification router first, then the Matrix
+/// default room. Alerts sent during maintenance carry its reason (see
+/// [`crate::maintenance::annotate`]).
+pub(crate) fn deliver(
+    dir: &Path,
+    config: Option<&NotifyConfig>,
+    subject: &str,
+    message: &str,
+) -> String {
+    let message = crate::maintenance::annotate(dir, message);
+    send_urgent(dir, config, subject, &message)
+}
+
+/// [`deliver`] without the maintenance annotation, for maintenance's own
+/// start and end announcements.
+pub(crate) fn send_urgent(
+    dir: &Path,
+    config: Option<&NotifyConfig>,
+    subject: &str,
+    message: &str,
+) -> String {
+    let rt = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) => return format!("failed: {}", e),
+    };
+    let mut failure = None;
+    if let Some(config) = config {
+        let (router, _warnings) = config.build_router();
+        if !router.channels_for_event(EventType::Urgent).is_empty() {
+            match rt.block_on(router.send(EventType::Urgent, subject, message)) {
+                Ok((ch, _)) => return format!("notified via {}", ch),
+                Err(e) => failure = Some(e),
+            }
+        }
+    }
+    match send_matrix(&rt, dir, message) {
+        Some(Ok(())) => "notified via matrix".to_string(),
+        Some(Err(e)) => format!("failed: {}", e),
+        None => match failure {
+            Some(e) => format!("failed: {}", e),
+            None => "not sent, no notification channels configured".to_string(),
+        },
+    }
+}
+
+/// Send to the Matrix default room. `None` when Matrix is not configured.
+#[cfg(feature = "matrix-lite")]
+fn send_matrix(rt: &tokio::runtime::Runtime, dir: &Path, message: &str) -> Option<Result<()>> {
+    let config = crate::config::MatrixConfig::load().ok()?;
+    if !config.is_complete() {
+        return None;
+    }
+    Some(rt.block_on(crate::matrix_lite::send_notification(dir, message)))
+}
+
+#[cfg(not(feature = "matrix-lite"))]
+fn send_matrix(_rt: &tokio::runtime::Runtime, _dir: &Path, _message: &str) -> Option<Result<()>> {
+    None
+}
+
+/// Run one deadline sweep over the graph.
+///
+/// Sends an alert for every breach stronger than the one already recorded
+/// on the task, logs it, and clears the alert of tasks back on schedule.
+/// With `dry_run`, only reports the alerts that would be sent. Returns the
+/// alerts sent (or due).
+pub fn run_sweep(
+    dir: &Path,
+    graph_path: &Path,
+    config: Option<&NotifyConfig>,
+    dry_run: bool,
+) -> Result<Vec<DeadlineBreach>> {
+    let graph = crate::parser::load_graph(graph_path)?;
+    let breaches = check(&graph, Utc::now(), &WorkingHours::load_or_warn(dir));
+    let flagged: HashSet<&str> = breaches.iter().map(|b| b.task_id.as_str()).collect();
+    let needs_reset = graph
+        .tasks()
+        .any(|t| t.deadline_alert.is_some() && !flagged.contains(t.id.as_str()));
+    let alerts: Vec<DeadlineBreach> = breaches
+        .iter()
+        .filter(|b| {
+            graph
+                .get_task(&b.task_id)
+                .is_some_and(|t| t.deadline_alert < Some(b.kind))
+        })
+        .cloned()
+        .collect();
+    if dry_run || (alerts.is_empty() && !needs_reset) {
+        return Ok(alerts);
+    }
+
+    let outcomes: Vec<String> = alerts
+        .iter()
+        .map(|b| deliver(dir, config, &b.task_id, &b.message()))
+        .collect();
+
+    modify_graph(graph_path, |graph| {
+        let mut modified = false;
+        for task in graph.tasks_mut() {
+            if task.deadline_alert.is_some() && !flagged.contains(task.id.as_str()) {
+                task.deadline_alert = None;
+                modified = true;
+            }
+        }
+        for (breach, outcome) in alerts.iter().zip(&outcomes) {
+            let Some(task) = graph.get_task_mut(&breach.task_id) else {
+                continue;
+            };
+            // Another writer may have finished the task or moved the deadline.
+            if task.status.is_terminal()
+                || due_at(task).map(|d| d.to_rfc3339()).as_deref() != Some(breach.due.as_str())
+            {
+                continue;
+            }
+            task.deadline_alert = Some(breach.kind);
+            task.log.push(LogEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                actor: Some("deadline".to_string()),
+                user: Some(crate::current_user()),
+                message: breach.log_message(outcome),
+            });
+            modified = true;
+        }
+        modified
+    })?;
+
+    let rotation = crate::config::Config::load_or_default(dir)
+        .log
+        .rotation_threshold;
+    for breach in &alerts {
+        let _ = crate::provenance::record(
+            dir,
+            "deadline_alert",
+            Some(&breach.task_id),
+            Some("deadline"),
+            serde_json::json!({
+                "kind": breach.kind,
+                "due": breach.due,
+                "forecast": breach.forecast,
+            }),
+            rotation,
+        );
+    }
+
+    Ok(alerts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Estimate, Node};
+
+    fn task(id: &str, due: Option<DateTime<Utc>>, hours: f64, after: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            title: id.to_string(),
+            due: due.map(|d| d.to_rfc3339()),
+            estimate: Some(Estimate {
+                hours: Some(hours),
+                cost: None,
+            }),
+            after: after.iter().map(|s| s.to_string()).collect(),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_due_formats() {
+        let now = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            parse_due("2026-03-05", now).unwrap().to_rfc3339(),
+            "2026-03-05T23:59:59+00:00"
+        );
+        assert_eq!(
+            parse_due("2026-03-05T09:30:00", now).unwrap().to_rfc3339(),
+            "2026-03-05T09:30:00+00:00"
+        );
+        assert_eq!(parse_due("2d", now).unwrap(), now + Duration::days(2));
+        assert!(parse_due("next week", now).is_none());
+    }
+
+    #[test]
+    fn test_check_flags_overdue_and_milestone_slip() {
+        let now = Utc::now();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task(
+            "late",
+            Some(now - Duration::hours(3)),
+            1.0,
+            &[],
+        )));
+        graph.add_node(Node::Task(task("design", None, 10.0, &[])));
+        graph.add_node(Node::Task(task("build", None, 30.0, &["design"])));
+        graph.add_node(Node::Task(task("docs", None, 5.0, &["design"])));
+        let mut release = task(
+            "release",
+            Some(now + Duration::hours(24)),
+            2.0,
+            &["build", "docs"],
+        );
+        release.tags = vec![MILESTONE_TAG.to_string()];
+        graph.add_node(Node::Task(release));
+        let mut done = task("shipped", Some(now - Duration::days(2)), 1.0, &[]);
+        done.status = Status::Done;
+        graph.add_node(Node::Task(done));
+
+        let breaches = check(&graph, now, &WorkingHours::default());
+        assert_eq!(breaches.len(), 2);
+        assert_eq!(breaches[0].task_id, "late");
+        assert_eq!(breaches[0].kind, DeadlineAlert::Overdue);
+        assert_eq!(breaches[1].task_id, "release");
+        assert_eq!(breaches[1].kind, DeadlineAlert::AtRisk);
+        // design (10h) → build (30h) → release (2h) = 42h, due in 24h
+        assert_eq!(breaches[1].slip_secs, Some(18 * 3600));
+
+        graph.get_task_mut("build").unwrap().status = Status::Done;
+        assert_eq!(check(&graph, now, &WorkingHours::default()).len(), 1);
+    }
+
+    #[test]
+    fn test_forecast_finish_counts_working_hours() {
+        // Friday 15:00 UTC
+        let now = "2026-03-06T15:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut graph = WorkGraph::new();
+        let mut review = task("review", None, 3.0, &[]);
+        review.assigned = Some("alice".to_string());
+        graph.add_node(Node::Task(review));
+        graph.add_node(Node::Task(task("deploy", None, 1.0, &["review"])));
+        let config: crate::working_hours::WorkingHoursConfig =
+            toml::from_str("[participants.alice]").unwrap();
+        let calendars = WorkingHours::from_config(&config).unwrap();
+
+        assert_eq!(
+            forecast_finish(&graph, "deploy", now, &WorkingHours::default()),
+            now + Duration::hours(4)
+        );
+        // alice works 2h Friday and 1h Monday; deploy runs around the clock
+        assert_eq!(
+            forecast_finish(&graph, "deploy", now, &calendars).to_rfc3339(),
+            "2026-03-09T11:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_run_sweep_alerts_once_and_clears() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task(
+            "late",
+            Some(Utc::now() - Duration::hours(1)),
+            1.0,
+            &[],
+        )));
+        crate::parser::save_graph(&graph, &path).unwrap();
+
+        assert_eq!(run_sweep(dir.path(), &path, None, true).unwrap().len(), 1);
+        let g = crate::parser::load_graph(&path).unwrap();
+        assert_eq!(g.get_task("late").unwrap().deadline_alert, None);
+
+        let sent = run_sweep(dir.path(), &path, None, false).unwrap();
+        assert_eq!(sent.len(), 1);
+        let g = crate::parser::load_graph(&path).unwrap();
+        let t = g.get_task("late").unwrap();
+        assert_eq!(t.deadline_alert, Some(DeadlineAlert::Overdue));
+        assert!(
+            t.log
+                .last()
+                .unwrap()
+                .message
+                .starts_with("Deadline breached")
+        );
+
+        // Already alerted
+        assert!(
+            run_sweep(dir.path(), &path, None, false)
+                .unwrap()
+                .is_empty()
+        );
+
+        modify_graph(&path, |g| {
+            g.get_task_mut("late").unwrap().due =
+                Some((Utc::now() + Duration::days(1)).to_rfc3339());
+            true
+        })
+        .unwrap();
+        run_sweep(dir.path(), &path, None, false).unwrap();
+        let g = crate::parser::load_graph(&path).unwrap();
+        assert_eq!(g.get_task("late").unwrap().deadline_alert, None);
+    }
+
+    #[test]
+    fn test_deliver_annotates_alerts_during_maintenance() {
+        use crate::service::http::{read_request, write_response};
+
+        let dir = tempfile::tempdir().unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let receiver = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let req = read_request(&mut stream).unwrap();
+            write_response(&mut stream, 200, &serde_json::json!({})).unwrap();
+            String::from_utf8(req.body).unwrap()
+        });
+        let config: NotifyConfig = toml::from_str(&format!(
+            "[routing]\nurgent = [\"webhook\"]\n\n[webhook]\nurl = \"http://{}/\"\n",
+            addr
+        ))
+        .unwrap();
+
+        crate::maintenance::enter(dir.path(), "db migration", None).unwrap();
+        let outcome = deliver(dir.path(), Some(&config), "late", "Task 'late' is overdue");
+        assert_eq!(outcome, "notified via webhook");
+        let body = receiver.join().unwrap();
+        assert!(
+            body.contains("[during maintenance: db migration]"),
+            "{}",
+            body
+        );
+    }
+}
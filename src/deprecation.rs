@@ -0,0 +1,215 @@
+//! Renamed fields in on-disk records.
+//!
+//! When a field is renamed, the old name stays readable as a serde alias so
+//! existing files keep loading. Reads of an old name are counted per file
+//! and reported once per process as a warning, and `wg migrate fields`
+//! rewrites the files to the canonical names.
+//!
+//! Config keys have their own path: see [`crate::config::detect_deprecated_keys`]
+//! and `wg migrate config`.
+
+use serde::de::IgnoredAny;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A field that was renamed; `old` is still accepted on read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenamedField {
+    pub old: &'static str,
+    pub new: &'static str,
+}
+
+/// A JSONL record format and the renames it accepts.
+#[derive(Debug)]
+pub struct RecordFormat {
+    /// What one line holds, for messages ("task", "run").
+    pub record: &'static str,
+    pub renames: &'static [RenamedField],
+}
+
+/// Task lines in `graph.jsonl`.
+pub static TASKS: RecordFormat = RecordFormat {
+    record: "task",
+    renames: &[
+        RenamedField {
+            old: "blocked_by",
+            new: "after",
+        },
+        RenamedField {
+            old: "blocks",
+            new: "before",
+        },
+    ],
+};
+
+/// Run summaries in `functions/<id>.runs.jsonl`.
+pub static RUN_SUMMARIES: RecordFormat = RecordFormat {
+    record: "run",
+    renames: &[RenamedField {
+        old: "instantiated_at",
+        new: "applied_at",
+    }],
+};
+
+/// How many records of one file use each renamed field.
+#[derive(Debug, Clone)]
+pub struct Tally {
+    pub format: &'static RecordFormat,
+    /// Parallel to `format.renames`.
+    counts: Vec<usize>,
+}
+
+impl Tally {
+    pub fn new(format: &'static RecordFormat) -> Self {
+        Self {
+            format,
+            counts: vec![0; format.renames.len()],
+        }
+    }
+
+    /// Count the old field names among the top-level keys of one JSONL line.
+    pub fn scan_line(&mut self, line: &str) {
+        // Cheap substring test first: almost every line is canonical.
+        if !self.format.renames.iter().any(|r| line.contains(r.old)) {
+            return;
+        }
+        let Ok(keys) = serde_json::from_str::<HashMap<String, IgnoredAny>>(line) else {
+            return;
+        };
+        for (rename, count) in self.format.renames.iter().zip(&mut self.counts) {
+            if keys.contains_key(rename.old) {
+                *count += 1;
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|&c| c == 0)
+    }
+
+    /// Number of records using any old name (a record using two counts twice).
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// The renames in use, with how many records use each.
+    pub fn uses(&self) -> impl Iterator<Item = (RenamedField, usize)> + '_ {
+        self.format
+            .renames
+            .iter()
+            .copied()
+            .zip(self.counts.iter().copied())
+            .filter(|&(_, count)| count > 0)
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (rename, count)) in self.uses().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "`{}` (now `{}`) in {} {}{}",
+                rename.old,
+                rename.new,
+                count,
+                self.format.record,
+                if count == 1 { "" } else { "s" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Files already warned about by this process.
+static WARNED: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Stop warning for the rest of the process. Used by `wg migrate fields`,
+/// which reports the same counts itself.
+pub fn quiet() {
+    QUIET.store(true, Ordering::Relaxed);
+}
+
+/// Print a deprecation warning for `path` unless `tally` is empty or this
+/// process already warned about the file.
+pub fn warn_once(path: &Path, tally: &Tally) {
+    if tally.is_empty() || QUIET.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut warned = WARNED.lock().unwrap_or_else(|e| e.into_inner());
+    if !warned
+        .get_or_insert_with(HashSet::new)
+        .insert(path.to_path_buf())
+    {
+        return;
+    }
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    );
+    eprintln!(
+        "Warning: {} uses deprecated field names: {}. Run `wg migrate fields` to rewrite them.",
+        name, tally
+    );
+}
+
+/// Rename the old fields of one JSON object to their canonical names.
+/// An old field is left alone when the new one is also present. Returns
+/// whether anything changed.
+pub fn rename_fields(
+    object: &mut serde_json::Map<String, serde_json::Value>,
+    format: &RecordFormat,
+) -> bool {
+    let mut changed = false;
+    for rename in format.renames {
+        if object.contains_key(rename.new) {
+            continue;
+        }
+        if let Some(value) = object.remove(rename.old) {
+            object.insert(rename.new.to_string(), value);
+            changed = true;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tally_counts_top_level_keys_only() {
+        let mut tally = Tally::new(&TASKS);
+        tally.scan_line(r#"{"kind":"task","id":"a","title":"A","blocked_by":["b"]}"#);
+        tally.scan_line(r#"{"kind":"task","id":"b","title":"B","blocks":["a"],"blocked_by":[]}"#);
+        // Mentions in values don't count.
+        tally.scan_line(r#"{"kind":"task","id":"c","title":"blocks","description":"blocked_by"}"#);
+        tally.scan_line(r#"{"kind":"task","id":"d","title":"D","after":["a"]}"#);
+        assert_eq!(tally.total(), 3);
+        assert_eq!(
+            tally.to_string(),
+            "`blocked_by` (now `after`) in 2 tasks, `blocks` (now `before`) in 1 task"
+        );
+    }
+
+    #[test]
+    fn test_rename_fields_keeps_canonical_value() {
+        let mut object =
+            serde_json::json!({"id": "a", "blocked_by": ["b"], "blocks": ["c"], "before": ["d"]});
+        let object = object.as_object_mut().unwrap();
+        assert!(rename_fields(object, &TASKS));
+        assert_eq!(object["after"], serde_json::json!(["b"]));
+        assert!(!object.contains_key("blocked_by"));
+        // `before` was already set, so the stale `blocks` is left for review.
+        assert_eq!(object["before"], serde_json::json!(["d"]));
+        assert!(object.contains_key("blocks"));
+        assert!(!rename_fields(object, &TASKS));
+    }
+}
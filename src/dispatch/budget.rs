@@ -0,0 +1,332 @@
+//! Per-agent daily cost budgets.
+//!
+//! `wg agent set-budget <id> --daily <USD>` caps what an agent may spend in
+//! a UTC day. An agent's spend is the recorded token usage of the tasks it
+//! worked on, attributed to the day each task completed (or started, while
+//! it has no completion), the same figures `wg cost` reports, plus what runs
+//! still in progress have spent so far. Once an agent has spent more than
+//! its budget the service stops dispatching to it: it is no longer a
+//! candidate at assignment, and ready tasks already assigned to it wait.
+//! Both lift when the day rolls over.
+//!
+//! The service alerts once per agent and day when an agent goes over its
+//! budget, on the urgent route of `notify.toml` (Matrix default room as
+//! fallback). Alerts sent are kept in `.wg/service/budget_alerts.json`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::agency::Agent;
+use crate::graph::{Status, Task, WorkGraph, parse_token_usage_live};
+use crate::notify::config::NotifyConfig;
+use crate::service::registry::AgentRegistry;
+
+const STATE_FILE: &str = "budget_alerts.json";
+
+/// An agent that has spent more than its daily budget.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OverBudget {
+    pub agent_id: String,
+    pub agent_name: String,
+    pub spent: f64,
+    pub budget: f64,
+}
+
+impl OverBudget {
+    pub fn message(&self) -> String {
+        format!(
+            "Agent '{}' spent ${:.2} today, over its ${:.2} daily budget; \
+             no more work is dispatched to it until tomorrow (UTC)",
+            self.agent_name, self.spent, self.budget
+        )
+    }
+}
+
+/// The UTC day a task's usage counts toward.
+pub fn task_day(task: &Task) -> Option<NaiveDate> {
+    let ts = task
+        .completed_at
+        .as_deref()
+        .or(task.started_at.as_deref())?;
+    let at = ts.parse::<DateTime<Utc>>().ok()?;
+    Some(at.date_naive())
+}
+
+/// Cost so far of each run still going, by task ID, read from the running
+/// agents' output the same way cost caps are checked.
+pub fn live_usage(workgraph_dir: &Path) -> HashMap<String, f64> {
+    let Ok(registry) = AgentRegistry::load(workgraph_dir) else {
+        return HashMap::new();
+    };
+    registry
+        .list_alive_agents()
+        .into_iter()
+        .filter_map(|a| {
+            let usage = parse_token_usage_live(Path::new(&a.output_file))?;
+            Some((a.task_id.clone(), usage.cost_usd))
+        })
+        .collect()
+}
+
+/// What every agent spent on one day, computed once and then consulted per
+/// agent.
+#[derive(Debug, Clone, Default)]
+pub struct DailySpend {
+    by_agent: HashMap<String, f64>,
+}
+
+impl DailySpend {
+    /// Recorded usage of the tasks attributed to `day`, plus `live` usage
+    /// (task ID to cost so far) of runs that have not recorded theirs yet.
+    pub fn new(graph: &WorkGraph, day: NaiveDate, live: &HashMap<String, f64>) -> Self {
+        let mut by_agent: HashMap<String, f64> = HashMap::new();
+        for task in graph.tasks().filter(|t| task_day(t) == Some(day)) {
+            let Some(ref agent) = task.agent else {
+                continue;
+            };
+            let recorded = task.token_usage.as_ref().map_or(0.0, |u| u.cost_usd);
+            let running = if task.status == Status::InProgress {
+                live.get(&task.id).copied().unwrap_or(0.0)
+            } else {
+                0.0
+            };
+            *by_agent.entry(agent.clone()).or_default() += recorded + running;
+        }
+        Self { by_agent }
+    }
+
+    /// Today's spend, including runs in progress.
+    pub fn today(graph: &WorkGraph, workgraph_dir: &Path) -> Self {
+        Self::new(graph, Utc::now().date_naive(), &live_usage(workgraph_dir))
+    }
+
+    /// What `agent_id` spent.
+    pub fn of(&self, agent_id: &str) -> f64 {
+        self.by_agent.get(agent_id).copied().unwrap_or(0.0)
+    }
+
+    /// `agent` if it is over its budget.
+    pub fn check(&self, agent: &Agent) -> Option<OverBudget> {
+        let budget = agent.daily_budget?;
+        let spent = self.of(&agent.id);
+        (spent > budget).then(|| OverBudget {
+            agent_id: agent.id.clone(),
+            agent_name: agent.name.clone(),
+            spent,
+            budget,
+        })
+    }
+
+    /// Split `agents` into those with budget left and those over it.
+    pub fn within_budget(&self, agents: Vec<Agent>) -> (Vec<Agent>, Vec<OverBudget>) {
+        let mut within = Vec::new();
+        let mut over = Vec::new();
+        for agent in agents {
+            match self.check(&agent) {
+                Some(o) => over.push(o),
+                None => within.push(agent),
+            }
+        }
+        (within, over)
+    }
+
+    /// Agents over budget, by agent ID.
+    pub fn over_budget(&self, agents: &[Agent]) -> BTreeMap<String, OverBudget> {
+        agents
+            .iter()
+            .filter_map(|a| self.check(a))
+            .map(|o| (o.agent_id.clone(), o))
+            .collect()
+    }
+}
+
+/// Agents already alerted on, with the day they were alerted for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BudgetAlerts {
+    #[serde(default)]
+    pub alerted: BTreeMap<String, NaiveDate>,
+}
+
+impl BudgetAlerts {
+    fn path(workgraph_dir: &Path) -> PathBuf {
+        workgraph_dir.join("service").join(STATE_FILE)
+    }
+
+    /// Load the state; empty if none was recorded yet.
+    pub fn load(workgraph_dir: &Path) -> Result<Self> {
+        let path = Self::path(workgraph_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, workgraph_dir: &Path) -> Result<()> {
+        let path = Self::path(workgraph_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Return the agents in `over` not yet alerted on `day`, marking them
+    /// alerted. Alerts from earlier days are forgotten.
+    pub fn observe(&mut self, over: &[OverBudget], day: NaiveDate) -> Vec<OverBudget> {
+        self.alerted.retain(|_, d| *d == day);
+        over.iter()
+            .filter(|o| self.alerted.insert(o.agent_id.clone(), day).is_none())
+            .cloned()
+            .collect()
+    }
+}
+
+/// Alert on agents that went over their budget today and persist the
+/// state. Returns the agents alerted, each with its delivery outcome.
+pub fn run_sweep(
+    dir: &Path,
+    graph_path: &Path,
+    config: Option<&NotifyConfig>,
+) -> Result<Vec<(OverBudget, String)>> {
+    let agents = crate::agency::load_all_agents_or_warn(&dir.join("agency").join("cache/agents"));
+    if agents.iter().all(|a| a.daily_budget.is_none()) {
+        return Ok(Vec::new());
+    }
+    let graph = crate::parser::load_graph(graph_path)?;
+    let today = Utc::now().date_naive();
+    let over: Vec<OverBudget> = DailySpend::new(&graph, today, &live_usage(dir))
+        .over_budget(&agents)
+        .into_values()
+        .collect();
+    let mut state = BudgetAlerts::load(dir)?;
+    let new = state.observe(&over, today);
+    state.save(dir)?;
+    Ok(new
+        .into_iter()
+        .map(|o| {
+            let subject = format!("budget:{}", o.agent_id);
+            let outcome = crate::deadline::deliver(dir, config, &subject, &o.message());
+            (o, outcome)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agency::{Lineage, PerformanceRecord, TrustLevel};
+    use crate::graph::{Node, Status, TokenUsage};
+    use crate::test_helpers::make_task;
+
+    fn agent(id: &str, daily_budget: Option<f64>) -> Agent {
+        Agent {
+            id: id.to_string(),
+            role_id: String::new(),
+            tradeoff_id: String::new(),
+            name: id.to_string(),
+            performance: PerformanceRecord::default(),
+            lineage: Lineage::default(),
+            capabilities: vec![],
+            rate: None,
+            capacity: None,
+            trust_level: TrustLevel::Provisional,
+            contact: None,
+            executor: "claude".to_string(),
+            preferred_model: None,
+            preferred_provider: None,
+            deployment_history: vec![],
+            attractor_weight: 0.5,
+            staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget,
+        }
+    }
+
+    fn spent(id: &str, agent: &str, cost: f64, completed_at: &str) -> Node {
+        let mut t = make_task(id, id);
+        t.status = Status::Done;
+        t.agent = Some(agent.to_string());
+        t.completed_at = Some(completed_at.to_string());
+        t.token_usage = Some(TokenUsage {
+            cost_usd: cost,
+            input_tokens: 1000,
+            output_tokens: 500,
+            cache_read_input_tokens: 0,
+            cache_creation_input_tokens: 0,
+        });
+        Node::Task(t)
+    }
+
+    fn day(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_spend_is_per_agent_and_day() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(spent("a", "x", 3.0, "2026-03-10T09:00:00Z"));
+        graph.add_node(spent("b", "x", 2.5, "2026-03-10T23:30:00Z"));
+        graph.add_node(spent("c", "x", 4.0, "2026-03-09T12:00:00Z"));
+        graph.add_node(spent("d", "y", 9.0, "2026-03-10T12:00:00Z"));
+        let spend = DailySpend::new(&graph, day("2026-03-10"), &HashMap::new());
+        assert_eq!(spend.of("x"), 5.5);
+
+        let agents = vec![
+            agent("x", Some(5.0)),
+            agent("y", Some(10.0)),
+            agent("z", None),
+        ];
+        let (within, over) = spend.within_budget(agents.clone());
+        let ids: Vec<&str> = within.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, ["y", "z"]);
+        assert_eq!(over.len(), 1);
+        assert_eq!((over[0].spent, over[0].budget), (5.5, 5.0));
+        assert!(over[0].message().contains("$5.50 today, over its $5.00"));
+
+        // The next day starts fresh.
+        let next_day = DailySpend::new(&graph, day("2026-03-11"), &HashMap::new());
+        assert!(next_day.over_budget(&agents).is_empty());
+    }
+
+    #[test]
+    fn test_runs_in_progress_count_live_usage() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(spent("a", "x", 3.0, "2026-03-10T09:00:00Z"));
+        for id in ["b", "c"] {
+            let mut t = make_task(id, id);
+            t.status = Status::InProgress;
+            t.agent = Some("x".to_string());
+            t.started_at = Some("2026-03-10T10:00:00Z".to_string());
+            graph.add_node(Node::Task(t));
+        }
+        let live = HashMap::from([("b".to_string(), 1.5), ("c".to_string(), 1.0)]);
+
+        let spend = DailySpend::new(&graph, day("2026-03-10"), &live);
+        assert_eq!(spend.of("x"), 5.5);
+        assert!(spend.check(&agent("x", Some(5.0))).is_some());
+        // Live usage of a finished run is already in its recorded usage
+        let live = HashMap::from([("a".to_string(), 3.0)]);
+        let spend = DailySpend::new(&graph, day("2026-03-10"), &live);
+        assert_eq!(spend.of("x"), 3.0);
+    }
+
+    #[test]
+    fn test_alerts_once_per_agent_and_day() {
+        let over = vec![OverBudget {
+            agent_id: "x".to_string(),
+            agent_name: "x".to_string(),
+            spent: 6.0,
+            budget: 5.0,
+        }];
+        let mut state = BudgetAlerts::default();
+        assert_eq!(state.observe(&over, day("2026-03-10")).len(), 1);
+        assert!(state.observe(&over, day("2026-03-10")).is_empty());
+        assert_eq!(state.observe(&over, day("2026-03-11")).len(), 1);
+    }
+}
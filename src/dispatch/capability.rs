@@ -0,0 +1,241 @@
+//! Checking task requirements against agents' capability documents.
+//!
+//! An agent that registered a [`CapabilityDocument`] only takes tasks it can
+//! meet in full: every required skill (matched the same way skill-first
+//! dispatch matches them), the task's model, its context window, its
+//! estimated cost, and every resource it `requires`. Agents without a
+//! document make no promises, so nothing is checked for them and they stay
+//! candidates as before.
+
+use std::fmt;
+
+use super::skill_match::SkillMatcher;
+use crate::agency::{Agent, CapabilityDocument};
+use crate::graph::Task;
+
+/// A task requirement an agent's capability document doesn't meet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Unmet {
+    Skill(String),
+    Model(String),
+    Context { needed: u64, max: u64 },
+    Cost { estimate: f64, ceiling: f64 },
+    Resource(String),
+}
+
+impl fmt::Display for Unmet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unmet::Skill(skill) => write!(f, "skill '{}' not covered", skill),
+            Unmet::Model(model) => write!(f, "model '{}' not available", model),
+            Unmet::Context { needed, max } => {
+                write!(f, "needs {} context tokens, agent max is {}", needed, max)
+            }
+            Unmet::Cost { estimate, ceiling } => write!(
+                f,
+                "estimated ${:.2} exceeds the agent's ${:.2} ceiling",
+                estimate, ceiling
+            ),
+            Unmet::Resource(resource) => write!(f, "no access to resource '{}'", resource),
+        }
+    }
+}
+
+/// Whether `declared` names the model a task asks for. Either side may carry
+/// a provider prefix (`claude:opus` vs `opus`).
+fn model_matches(declared: &str, wanted: &str) -> bool {
+    let bare = |m: &str| m.rsplit(':').next().unwrap_or(m).to_string();
+    declared == wanted || bare(declared) == bare(wanted)
+}
+
+/// Requirements of `task` that `doc` doesn't meet. `capabilities` are the
+/// agent's skills.
+pub fn check_document(
+    doc: &CapabilityDocument,
+    capabilities: &[String],
+    task: &Task,
+    matcher: &SkillMatcher,
+) -> Vec<Unmet> {
+    let mut unmet: Vec<Unmet> = matcher
+        .match_skills(&task.skills, capabilities)
+        .into_iter()
+        .filter(|m| !m.matched)
+        .map(|m| Unmet::Skill(m.skill))
+        .collect();
+    if let Some(ref model) = task.model
+        && !doc.models.is_empty()
+        && !doc.models.iter().any(|m| model_matches(m, model))
+    {
+        unmet.push(Unmet::Model(model.clone()));
+    }
+    if let (Some(needed), Some(max)) = (task.min_context, doc.max_context)
+        && needed > max
+    {
+        unmet.push(Unmet::Context { needed, max });
+    }
+    let estimate = task.estimate.as_ref().and_then(|e| e.cost);
+    if let (Some(estimate), Some(ceiling)) = (estimate, doc.cost_ceiling)
+        && estimate > ceiling
+    {
+        unmet.push(Unmet::Cost { estimate, ceiling });
+    }
+    for resource in &task.requires {
+        if !doc.resources.contains(resource) {
+            unmet.push(Unmet::Resource(resource.clone()));
+        }
+    }
+    unmet
+}
+
+/// Requirements of `task` that `agent` has declared it can't meet. Empty for
+/// agents without a capability document.
+pub fn unmet_requirements(agent: &Agent, task: &Task, matcher: &SkillMatcher) -> Vec<Unmet> {
+    match agent.capability_doc {
+        Some(ref doc) => check_document(doc, &agent.capabilities, task, matcher),
+        None => Vec::new(),
+    }
+}
+
+/// Split `agents` into those that can take `task` and, for the rest, why not.
+pub fn eligible_agents(
+    agents: Vec<Agent>,
+    task: &Task,
+    matcher: &SkillMatcher,
+) -> (Vec<Agent>, Vec<(Agent, Vec<Unmet>)>) {
+    let mut eligible = Vec::new();
+    let mut rejected = Vec::new();
+    for agent in agents {
+        let unmet = unmet_requirements(&agent, task, matcher);
+        if unmet.is_empty() {
+            eligible.push(agent);
+        } else {
+            rejected.push((agent, unmet));
+        }
+    }
+    (eligible, rejected)
+}
+
+/// One line per rejected agent, for error messages and logs.
+pub fn describe_rejections(rejected: &[(Agent, Vec<Unmet>)]) -> String {
+    rejected
+        .iter()
+        .map(|(agent, unmet)| {
+            let reasons: Vec<String> = unmet.iter().map(|u| u.to_string()).collect();
+            format!("  {}: {}", agent.name, reasons.join("; "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agency::{Lineage, PerformanceRecord, TrustLevel};
+    use crate::graph::Estimate;
+    use crate::test_helpers::make_task;
+
+    fn agent(id: &str, capabilities: &[&str], doc: Option<CapabilityDocument>) -> Agent {
+        Agent {
+            id: id.to_string(),
+            role_id: String::new(),
+            tradeoff_id: String::new(),
+            name: id.to_string(),
+            performance: PerformanceRecord::default(),
+            lineage: Lineage::default(),
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            rate: None,
+            capacity: None,
+            trust_level: TrustLevel::Provisional,
+            contact: None,
+            executor: "claude".to_string(),
+            preferred_model: None,
+            preferred_provider: None,
+            deployment_history: vec![],
+            attractor_weight: 0.5,
+            staleness_flags: vec![],
+            capability_doc: doc,
+            daily_budget: None,
+        }
+    }
+
+    fn demanding_task() -> Task {
+        let mut task = make_task("t", "Migrate schema");
+        task.skills = vec!["rust".to_string(), "sql".to_string()];
+        task.model = Some("claude:opus".to_string());
+        task.min_context = Some(200_000);
+        task.estimate = Some(Estimate {
+            hours: None,
+            cost: Some(4.0),
+        });
+        task.requires = vec!["prod-db".to_string()];
+        task
+    }
+
+    #[test]
+    fn test_document_meets_or_lists_unmet() {
+        let task = demanding_task();
+        let matcher = SkillMatcher::exact();
+
+        let capable = agent(
+            "capable",
+            &["rust", "sql"],
+            Some(CapabilityDocument {
+                models: vec!["opus".to_string(), "sonnet".to_string()],
+                max_context: Some(200_000),
+                cost_ceiling: Some(5.0),
+                resources: vec!["prod-db".to_string()],
+                ..Default::default()
+            }),
+        );
+        assert!(unmet_requirements(&capable, &task, &matcher).is_empty());
+
+        let limited = agent(
+            "limited",
+            &["rust"],
+            Some(CapabilityDocument {
+                models: vec!["haiku".to_string()],
+                max_context: Some(100_000),
+                cost_ceiling: Some(1.0),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(
+            unmet_requirements(&limited, &task, &matcher),
+            vec![
+                Unmet::Skill("sql".to_string()),
+                Unmet::Model("claude:opus".to_string()),
+                Unmet::Context {
+                    needed: 200_000,
+                    max: 100_000
+                },
+                Unmet::Cost {
+                    estimate: 4.0,
+                    ceiling: 1.0
+                },
+                Unmet::Resource("prod-db".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_agents_without_document_stay_eligible() {
+        let task = demanding_task();
+        let agents = vec![
+            agent("legacy", &[], None),
+            agent(
+                "limited",
+                &["rust", "sql"],
+                Some(CapabilityDocument::default()),
+            ),
+        ];
+        let (eligible, rejected) = eligible_agents(agents, &task, &SkillMatcher::exact());
+        assert_eq!(eligible.len(), 1);
+        assert_eq!(eligible[0].id, "legacy");
+        // An empty document allows any model and no resources.
+        assert_eq!(rejected[0].1, vec![Unmet::Resource("prod-db".to_string())]);
+        assert_eq!(
+            describe_rejections(&rejected),
+            "  limited: no access to resource 'prod-db'"
+        );
+    }
+}
@@ -0,0 +1,261 @@
+//! Agent selection under `coordinator.dispatch`.
+//!
+//! `greedy` keeps the historical behavior (best performance score wins, so
+//! one strong agent ends up with everything). `fair` spreads work by load:
+//! agents with the least open work go first, then those with the fewest
+//! recent completions. `skill-first` ranks by how many of the task's skills
+//! an agent's capabilities cover (per [`SkillMatcher`], so by embedding
+//! similarity when `[skill_matching]` asks for it) and falls back to the fair
+//! order on ties. `reputation` ranks by each agent's track record (see
+//! [`crate::agency::reputation`]), also falling back to the fair order.
+
+use chrono::{DateTime, Duration, Utc};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::agency::Agent;
+use crate::agency::reputation;
+use crate::config::DispatchPolicy;
+use crate::graph::{Task, WorkGraph};
+
+use super::skill_match::SkillMatcher;
+
+/// How far back completions count toward an agent's recent load.
+pub const RECENT_WINDOW_HOURS: i64 = 24;
+
+/// Load carried by one agent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AgentLoad {
+    /// Unfinished tasks assigned to the agent (queued or in progress)
+    pub in_flight: usize,
+    /// Tasks the agent finished within [`RECENT_WINDOW_HOURS`]
+    pub recent: usize,
+}
+
+/// Per-agent load, keyed by agent id (the task's `agent` field).
+pub fn agent_loads(graph: &WorkGraph, now: DateTime<Utc>) -> HashMap<String, AgentLoad> {
+    let since = now - Duration::hours(RECENT_WINDOW_HOURS);
+    let mut loads: HashMap<String, AgentLoad> = HashMap::new();
+    for task in graph.tasks() {
+        let Some(ref agent) = task.agent else {
+            continue;
+        };
+        if !task.status.is_terminal() {
+            loads.entry(agent.clone()).or_default().in_flight += 1;
+        } else if task
+            .completed_at
+            .as_deref()
+            .and_then(|ts| ts.parse::<DateTime<Utc>>().ok())
+            .is_some_and(|at| at >= since)
+        {
+            loads.entry(agent.clone()).or_default().recent += 1;
+        }
+    }
+    loads
+}
+
+fn score(agent: &Agent) -> f64 {
+    agent.performance.avg_score.unwrap_or(0.0)
+}
+
+fn load_order(a: &Agent, b: &Agent, loads: &HashMap<String, AgentLoad>) -> Ordering {
+    let la = loads.get(&a.id).copied().unwrap_or_default();
+    let lb = loads.get(&b.id).copied().unwrap_or_default();
+    la.in_flight
+        .cmp(&lb.in_flight)
+        .then(la.recent.cmp(&lb.recent))
+        .then(score(b).partial_cmp(&score(a)).unwrap_or(Ordering::Equal))
+}
+
+/// Order candidates best-first under `policy`. The sort is stable, so agents
+/// that tie keep their incoming order.
+pub fn rank_agents<'a>(
+    policy: DispatchPolicy,
+    agents: &'a [Agent],
+    task: &Task,
+    loads: &HashMap<String, AgentLoad>,
+    matcher: &SkillMatcher,
+    reputations: &HashMap<String, f64>,
+) -> Vec<&'a Agent> {
+    let rep = |a: &Agent| {
+        reputations
+            .get(&a.id)
+            .copied()
+            .unwrap_or(reputation::NEUTRAL)
+    };
+    let mut ranked: Vec<&Agent> = agents.iter().collect();
+    match policy {
+        DispatchPolicy::Greedy => {
+            ranked.sort_by(|a, b| score(b).partial_cmp(&score(a)).unwrap_or(Ordering::Equal))
+        }
+        DispatchPolicy::Fair => ranked.sort_by(|a, b| load_order(a, b, loads)),
+        DispatchPolicy::SkillFirst => ranked.sort_by(|a, b| {
+            matcher
+                .covered(task, b)
+                .cmp(&matcher.covered(task, a))
+                .then_with(|| load_order(a, b, loads))
+        }),
+        DispatchPolicy::Reputation => ranked.sort_by(|a, b| {
+            rep(b)
+                .partial_cmp(&rep(a))
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| load_order(a, b, loads))
+        }),
+    }
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agency::{Lineage, PerformanceRecord, TrustLevel};
+    use crate::graph::{Node, Status};
+    use crate::test_helpers::make_task;
+
+    fn agent(id: &str, score: f64, capabilities: &[&str]) -> Agent {
+        Agent {
+            id: id.to_string(),
+            role_id: String::new(),
+            tradeoff_id: String::new(),
+            name: id.to_string(),
+            performance: PerformanceRecord {
+                avg_score: Some(score),
+                ..PerformanceRecord::default()
+            },
+            lineage: Lineage::default(),
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            rate: None,
+            capacity: None,
+            trust_level: TrustLevel::Provisional,
+            contact: None,
+            executor: "claude".to_string(),
+            preferred_model: None,
+            preferred_provider: None,
+            deployment_history: vec![],
+            attractor_weight: 0.5,
+            staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
+        }
+    }
+
+    fn assigned(id: &str, agent: &str, status: Status, completed_at: Option<&str>) -> Node {
+        let mut t = make_task(id, id);
+        t.agent = Some(agent.to_string());
+        t.status = status;
+        t.completed_at = completed_at.map(String::from);
+        Node::Task(t)
+    }
+
+    #[test]
+    fn test_agent_loads() {
+        let now = "2026-03-02T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(assigned("a", "x", Status::InProgress, None));
+        graph.add_node(assigned("b", "x", Status::Open, None));
+        graph.add_node(assigned(
+            "c",
+            "y",
+            Status::Done,
+            Some("2026-03-02T08:00:00Z"),
+        ));
+        graph.add_node(assigned(
+            "d",
+            "y",
+            Status::Done,
+            Some("2026-02-20T08:00:00Z"),
+        ));
+        graph.add_node(Node::Task(make_task("e", "Unassigned")));
+
+        let loads = agent_loads(&graph, now);
+        assert_eq!(
+            loads["x"],
+            AgentLoad {
+                in_flight: 2,
+                recent: 0
+            }
+        );
+        assert_eq!(
+            loads["y"],
+            AgentLoad {
+                in_flight: 0,
+                recent: 1
+            }
+        );
+        assert_eq!(loads.len(), 2);
+    }
+
+    #[test]
+    fn test_rank_agents_by_policy() {
+        let agents = vec![
+            agent("star", 0.9, &[]),
+            agent("idle", 0.5, &[]),
+            agent("rust", 0.4, &["rust"]),
+        ];
+        let mut task = make_task("t", "Task");
+        task.skills = vec!["rust".to_string()];
+        let mut loads = HashMap::new();
+        loads.insert(
+            "star".to_string(),
+            AgentLoad {
+                in_flight: 3,
+                recent: 0,
+            },
+        );
+        loads.insert(
+            "rust".to_string(),
+            AgentLoad {
+                in_flight: 1,
+                recent: 0,
+            },
+        );
+
+        let mut reputations = HashMap::new();
+        reputations.insert("rust".to_string(), 0.9);
+        reputations.insert("star".to_string(), 0.2);
+
+        let ids = |policy| -> Vec<String> {
+            rank_agents(
+                policy,
+                &agents,
+                &task,
+                &loads,
+                &SkillMatcher::exact(),
+                &reputations,
+            )
+            .iter()
+            .map(|a| a.id.clone())
+            .collect()
+        };
+        assert_eq!(ids(DispatchPolicy::Greedy), ["star", "idle", "rust"]);
+        assert_eq!(ids(DispatchPolicy::Fair), ["idle", "rust", "star"]);
+        assert_eq!(ids(DispatchPolicy::SkillFirst), ["rust", "idle", "star"]);
+        // "idle" has no record and sits at the neutral reputation.
+        assert_eq!(ids(DispatchPolicy::Reputation), ["rust", "idle", "star"]);
+    }
+
+    #[test]
+    fn test_skill_first_uses_similarity() {
+        let agents = vec![agent("web", 0.9, &["frontend"]), agent("db", 0.5, &["sql"])];
+        let mut task = make_task("t", "Task");
+        task.skills = vec!["postgres".to_string()];
+        let embeddings = [
+            ("postgres", vec![1.0, 0.1]),
+            ("sql", vec![0.9, 0.2]),
+            ("frontend", vec![0.0, 1.0]),
+        ]
+        .into_iter()
+        .map(|(t, v)| (t.to_string(), v))
+        .collect();
+        let matcher = SkillMatcher::with_embeddings(embeddings, 0.8);
+        let ranked = rank_agents(
+            DispatchPolicy::SkillFirst,
+            &agents,
+            &task,
+            &HashMap::new(),
+            &matcher,
+            &HashMap::new(),
+        );
+        assert_eq!(ranked[0].id, "db");
+    }
+}
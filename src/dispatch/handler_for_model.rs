@@ -33,7 +33,7 @@
 //! | `openrouter:*`            | `native` (nex) | OAI-compat  | optional          |
 //! | `local:*` (deprecated)    | `native` (nex) | OAI-compat  | yes               |
 //! | `oai-compat:*` (deprecated) / `openai:*` | `native` (nex) | OAI-compat  | yes               |
-//! | `ollama:*`                | `native` (nex) | OAI-compat  | yes               |
+//! | `ollama:*`                | `native` (nex) | Ollama      | optional          |
 //! | `vllm:*`/`llamacpp:*`     | `native` (nex) | OAI-compat  | yes               |
 //! | `gemini:*`                | `native` (nex) | (per impl)  | yes               |
 //! | `native:*`                | `native` (nex) | OAI-compat  | yes               |
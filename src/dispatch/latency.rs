@@ -0,0 +1,392 @@
+//! Dispatch latency: how long ready work waits for an agent.
+//!
+//! The service observes the graph after every dispatcher tick. Ready,
+//! unassigned tasks are recorded as pending from the first time they are
+//! seen; when an agent is spawned on one ("Spawned by ..." in its log) the
+//! wait becomes a sample. Tasks spawned within a single tick of becoming
+//! ready count from the previous observation at the latest, so the short
+//! end of the histogram is bounded by the tick interval.
+//!
+//! With `coordinator.dispatch_latency_alerts` set (e.g. `["30m", "2h"]`),
+//! each threshold a pending task crosses raises one alert through the
+//! urgent notification route (Matrix default room as fallback), so a
+//! dispatcher that stalls does not go unnoticed. State lives in
+//! `.wg/service/dispatch_latency.json`; `wg service latency` reports it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::graph::{Task, WorkGraph, is_system_task, parse_delay};
+use crate::notify::config::NotifyConfig;
+use crate::query::ready_tasks;
+
+const STATE_FILE: &str = "dispatch_latency.json";
+
+/// Samples kept; older ones are dropped.
+const MAX_SAMPLES: usize = 5000;
+
+/// Log prefix written when the dispatcher (or `wg spawn`) starts an agent.
+const SPAWN_LOG_PREFIX: &str = "Spawned by ";
+
+/// Histogram bucket upper bounds in seconds; the last bucket is open.
+pub const BUCKETS: [(i64, &str); 6] = [
+    (60, "< 1m"),
+    (300, "1-5m"),
+    (900, "5-15m"),
+    (3600, "15m-1h"),
+    (4 * 3600, "1-4h"),
+    (i64::MAX, ">= 4h"),
+];
+
+/// A ready task not yet picked up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pending {
+    pub ready_since: String,
+    /// Thresholds already alerted on.
+    #[serde(default)]
+    pub alerted: usize,
+}
+
+/// One observed wait from ready to agent start.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    pub task_id: String,
+    pub ready_at: String,
+    pub started_at: String,
+    pub secs: i64,
+}
+
+/// Persisted tracker state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_observed: Option<String>,
+    #[serde(default)]
+    pub pending: BTreeMap<String, Pending>,
+    #[serde(default)]
+    pub samples: Vec<Sample>,
+}
+
+/// A pending task that crossed an alert threshold.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyBreach {
+    pub task_id: String,
+    pub waited_secs: i64,
+    pub threshold_secs: u64,
+}
+
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    s.parse::<DateTime<Utc>>().ok()
+}
+
+/// When an agent was last spawned on the task.
+fn spawned_at(task: &Task) -> Option<DateTime<Utc>> {
+    task.log
+        .iter()
+        .rev()
+        .find(|e| e.message.starts_with(SPAWN_LOG_PREFIX))
+        .and_then(|e| parse_time(&e.timestamp))
+}
+
+/// Parse alert thresholds such as `"30m"`, smallest first.
+pub fn parse_thresholds(values: &[String]) -> Result<Vec<u64>> {
+    let mut secs = values
+        .iter()
+        .map(|v| {
+            parse_delay(v).with_context(|| {
+                format!(
+                    "Invalid dispatch latency threshold '{}': expected e.g. 30m, 2h",
+                    v
+                )
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    secs.sort_unstable();
+    secs.dedup();
+    Ok(secs)
+}
+
+impl LatencyState {
+    fn path(workgraph_dir: &Path) -> PathBuf {
+        workgraph_dir.join("service").join(STATE_FILE)
+    }
+
+    /// Load the tracker state; empty if none was recorded yet.
+    pub fn load(workgraph_dir: &Path) -> Result<Self> {
+        let path = Self::path(workgraph_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, workgraph_dir: &Path) -> Result<()> {
+        let path = Self::path(workgraph_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Fold one look at the graph into the state: record samples for tasks
+    /// spawned since the last observation, track ready tasks, and return
+    /// the pending tasks that crossed a new threshold.
+    pub fn observe(
+        &mut self,
+        graph: &WorkGraph,
+        now: DateTime<Utc>,
+        thresholds: &[u64],
+    ) -> Vec<LatencyBreach> {
+        let last = self.last_observed.as_deref().and_then(parse_time);
+
+        if let Some(last) = last {
+            for task in graph.tasks().filter(|t| !is_system_task(&t.id)) {
+                let Some(started) = spawned_at(task).filter(|t| *t > last) else {
+                    continue;
+                };
+                let ready_at = self
+                    .pending
+                    .get(&task.id)
+                    .and_then(|p| parse_time(&p.ready_since))
+                    .unwrap_or(last)
+                    .min(started);
+                self.samples.push(Sample {
+                    task_id: task.id.clone(),
+                    ready_at: ready_at.to_rfc3339(),
+                    started_at: started.to_rfc3339(),
+                    secs: (started - ready_at).num_seconds(),
+                });
+            }
+            if self.samples.len() > MAX_SAMPLES {
+                let excess = self.samples.len() - MAX_SAMPLES;
+                self.samples.drain(..excess);
+            }
+        }
+
+        let ready: HashSet<&str> = ready_tasks(graph)
+            .into_iter()
+            .filter(|t| t.assigned.is_none() && !is_system_task(&t.id))
+            .map(|t| t.id.as_str())
+            .collect();
+        self.pending.retain(|id, _| ready.contains(id.as_str()));
+        for id in &ready {
+            self.pending
+                .entry(id.to_string())
+                .or_insert_with(|| Pending {
+                    ready_since: now.to_rfc3339(),
+                    alerted: 0,
+                });
+        }
+        self.last_observed = Some(now.to_rfc3339());
+
+        let mut breaches = Vec::new();
+        for (id, pending) in &mut self.pending {
+            let Some(since) = parse_time(&pending.ready_since) else {
+                continue;
+            };
+            let waited = (now - since).num_seconds();
+            let crossed = thresholds.iter().filter(|t| waited >= **t as i64).count();
+            if crossed > pending.alerted {
+                breaches.push(LatencyBreach {
+                    task_id: id.clone(),
+                    waited_secs: waited,
+                    threshold_secs: thresholds[crossed - 1],
+                });
+                pending.alerted = crossed;
+            }
+        }
+        breaches.sort_by(|a, b| b.waited_secs.cmp(&a.waited_secs));
+        breaches
+    }
+
+    /// Samples whose agent started at or after `since`.
+    pub fn samples_since(&self, since: Option<DateTime<Utc>>) -> Vec<&Sample> {
+        self.samples
+            .iter()
+            .filter(|s| {
+                since.is_none_or(|since| parse_time(&s.started_at).is_some_and(|t| t >= since))
+            })
+            .collect()
+    }
+}
+
+/// Summary statistics over samples.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyReport {
+    pub count: usize,
+    pub p50_secs: i64,
+    pub p90_secs: i64,
+    pub p99_secs: i64,
+    pub max_secs: i64,
+    /// `(label, count)` per bucket in [`BUCKETS`].
+    pub buckets: Vec<(String, usize)>,
+}
+
+impl LatencyReport {
+    pub fn from_samples(samples: &[&Sample]) -> Self {
+        let mut secs: Vec<i64> = samples.iter().map(|s| s.secs).collect();
+        secs.sort_unstable();
+        let pct = |p: usize| {
+            if secs.is_empty() {
+                0
+            } else {
+                secs[((secs.len() - 1) * p) / 100]
+            }
+        };
+        let mut buckets: Vec<(String, usize)> =
+            BUCKETS.iter().map(|(_, l)| (l.to_string(), 0)).collect();
+        for s in &secs {
+            let i = BUCKETS
+                .iter()
+                .position(|(bound, _)| s < bound)
+                .unwrap_or(BUCKETS.len() - 1);
+            buckets[i].1 += 1;
+        }
+        Self {
+            count: secs.len(),
+            p50_secs: pct(50),
+            p90_secs: pct(90),
+            p99_secs: pct(99),
+            max_secs: secs.last().copied().unwrap_or(0),
+            buckets,
+        }
+    }
+}
+
+/// One alert message for every breach of a sweep.
+fn alert_message(breaches: &[LatencyBreach]) -> String {
+    let oldest = &breaches[0];
+    let threshold = breaches
+        .iter()
+        .map(|b| b.threshold_secs)
+        .min()
+        .unwrap_or(oldest.threshold_secs);
+    format!(
+        "Dispatch latency: {} ready task(s) waiting over {} for an agent (longest: '{}', {}). Tasks: {}",
+        breaches.len(),
+        crate::format_duration(threshold as i64, false),
+        oldest.task_id,
+        crate::format_duration(oldest.waited_secs, false),
+        breaches
+            .iter()
+            .map(|b| b.task_id.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Observe the graph, persist the state, and alert on new breaches.
+/// Returns the breaches and the delivery outcome (if any were alerted).
+pub fn run_sweep(
+    dir: &Path,
+    graph_path: &Path,
+    thresholds: &[String],
+    config: Option<&NotifyConfig>,
+) -> Result<(Vec<LatencyBreach>, Option<String>)> {
+    let thresholds = parse_thresholds(thresholds)?;
+    let graph = crate::parser::load_graph(graph_path)?;
+    let mut state = LatencyState::load(dir)?;
+    let breaches = state.observe(&graph, Utc::now(), &thresholds);
+    state.save(dir)?;
+    if breaches.is_empty() {
+        return Ok((breaches, None));
+    }
+    let message = alert_message(&breaches);
+    let outcome = crate::deadline::deliver(dir, config, "dispatch-latency", &message);
+    Ok((breaches, Some(outcome)))
+}
+
+/// Parse `--since` for the report: a duration back from `now` (e.g. `7d`).
+pub fn parse_since(s: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let secs = parse_delay(s)
+        .with_context(|| format!("Invalid --since '{}': expected e.g. 24h, 7d", s))?;
+    Ok(now - Duration::seconds(secs as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{LogEntry, Node, Status};
+    use crate::test_helpers::make_task;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn spawn(graph: &mut WorkGraph, id: &str, when: &str) {
+        let task = graph.get_task_mut(id).unwrap();
+        task.status = Status::InProgress;
+        task.assigned = Some("agent-1".into());
+        task.log.push(LogEntry {
+            timestamp: when.to_string(),
+            actor: Some("agent-1".into()),
+            user: None,
+            message: "Spawned by wg --executor claude".into(),
+        });
+    }
+
+    #[test]
+    fn test_observe_records_samples_and_alerts_once_per_threshold() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("a", "A")));
+        graph.add_node(Node::Task(make_task("b", "B")));
+        let thresholds = parse_thresholds(&["2h".into(), "30m".into()]).unwrap();
+        assert_eq!(thresholds, [1800, 7200]);
+
+        let mut state = LatencyState::default();
+        assert!(
+            state
+                .observe(&graph, at("2026-03-02T10:00:00Z"), &thresholds)
+                .is_empty()
+        );
+        assert_eq!(state.pending.len(), 2);
+
+        spawn(&mut graph, "a", "2026-03-02T10:05:00Z");
+        let breaches = state.observe(&graph, at("2026-03-02T10:40:00Z"), &thresholds);
+        assert_eq!(state.samples.len(), 1);
+        assert_eq!(state.samples[0].secs, 300);
+        assert_eq!(
+            breaches,
+            [LatencyBreach {
+                task_id: "b".into(),
+                waited_secs: 2400,
+                threshold_secs: 1800
+            }]
+        );
+        assert!(
+            state
+                .observe(&graph, at("2026-03-02T11:00:00Z"), &thresholds)
+                .is_empty()
+        );
+        let breaches = state.observe(&graph, at("2026-03-02T12:30:00Z"), &thresholds);
+        assert_eq!(breaches[0].threshold_secs, 7200);
+        assert!(alert_message(&breaches).contains("waiting over 2h"));
+    }
+
+    #[test]
+    fn test_report_percentiles_and_buckets() {
+        let samples: Vec<Sample> = [10, 30, 200, 1000, 20000]
+            .iter()
+            .map(|secs| Sample {
+                task_id: "t".into(),
+                ready_at: String::new(),
+                started_at: String::new(),
+                secs: *secs,
+            })
+            .collect();
+        let refs: Vec<&Sample> = samples.iter().collect();
+        let report = LatencyReport::from_samples(&refs);
+        assert_eq!(report.count, 5);
+        assert_eq!(report.p50_secs, 200);
+        assert_eq!(report.max_secs, 20000);
+        let counts: Vec<usize> = report.buckets.iter().map(|(_, c)| *c).collect();
+        assert_eq!(counts, [2, 1, 0, 1, 0, 1]);
+    }
+}
@@ -7,8 +7,15 @@
 //! by exactly one function (`plan_spawn`). Every spawn site calls it; nobody
 //! else picks the executor.
 
+pub mod budget;
+pub mod capability;
+pub mod fair_share;
 pub mod handler_for_model;
+pub mod latency;
 pub mod plan;
+pub mod rate_limit;
+pub mod skill_match;
+pub mod strategy;
 
 pub use handler_for_model::handler_for_model;
 pub use plan::{ExecutorKind, ResolvedModelSpec, SpawnPlan, SpawnProvenance, plan_spawn};
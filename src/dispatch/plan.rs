@@ -24,6 +24,8 @@
 //!   pass `--endpoint`.
 //! - `executor=shell`   →  endpoint is always `None`.
 //! - `executor=codex`   →  endpoint is always `None` (codex CLI handles its own).
+//! - `executor=webhook` →  endpoint is always `None` (the URL lives in
+//!   `[webhook_executor]`).
 //! - `executor=native`  →  endpoint is required; resolved via merged config
 //!   (per-task → role → default).
 //!
@@ -53,6 +55,10 @@ pub enum ExecutorKind {
     Shell,
     /// Codex CLI (`codex exec …`). Handles its own auth.
     Codex,
+    /// Webhook executor (`wg webhook-exec …`): POSTs the task to the
+    /// `[webhook_executor]` URL and waits for an external system to report
+    /// back. No model, no endpoint.
+    Webhook,
 }
 
 impl ExecutorKind {
@@ -62,6 +68,7 @@ impl ExecutorKind {
             ExecutorKind::Native => "native",
             ExecutorKind::Shell => "shell",
             ExecutorKind::Codex => "codex",
+            ExecutorKind::Webhook => "webhook",
         }
     }
 
@@ -71,6 +78,7 @@ impl ExecutorKind {
             "native" => Some(ExecutorKind::Native),
             "shell" => Some(ExecutorKind::Shell),
             "codex" => Some(ExecutorKind::Codex),
+            "webhook" => Some(ExecutorKind::Webhook),
             _ => None,
         }
     }
@@ -544,6 +552,19 @@ mod tests {
         assert!(plan.provenance.executor_source.contains("task.exec"));
     }
 
+    #[test]
+    fn test_webhook_executor_has_no_endpoint() {
+        let mut config = Config::default();
+        config.llm_endpoints.endpoints = vec![openrouter_default_endpoint()];
+        config.coordinator.executor = Some("webhook".to_string());
+
+        let task = base_task("t1");
+        let plan = plan_spawn(&task, &config, None, None).unwrap();
+        assert_eq!(plan.executor, ExecutorKind::Webhook);
+        assert!(plan.endpoint.is_none());
+        assert_eq!(plan.env["WG_EXECUTOR_TYPE"], "webhook");
+    }
+
     #[test]
     fn test_default_executor_is_claude() {
         let config = Config::default();
@@ -0,0 +1,111 @@
+//! Spawn rate limiting under `coordinator.spawn_rate`.
+//!
+//! `coordinator.max_agents` caps how many agents run at once; the spawn
+//! rate caps how quickly the dispatcher starts them, so a large ready set
+//! is worked through gradually instead of launching every free slot in one
+//! tick. A rate is written `<count>/<period>`: `2/min`, `30/h`, `1/30s`.
+//! Spawns are counted over a sliding window of one period, from the
+//! `started_at` of the agents in the registry.
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::graph::parse_delay;
+use crate::service::registry::AgentRegistry;
+
+/// At most `count` spawns in any window of `period_secs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpawnRate {
+    pub count: usize,
+    pub period_secs: u64,
+}
+
+impl SpawnRate {
+    /// Parse `<count>/<period>`, where the period is a unit (`s`, `sec`,
+    /// `m`, `min`, `h`, `hour`, `d`, `day`) or a duration such as `30s`.
+    pub fn parse(s: &str) -> Result<Self> {
+        let (count, period) = s
+            .split_once('/')
+            .with_context(|| format!("Invalid spawn rate '{}': expected e.g. 2/min", s))?;
+        let count: usize = count
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid spawn rate '{}': bad count", s))?;
+        if count == 0 {
+            bail!("Invalid spawn rate '{}': count must be at least 1", s);
+        }
+        let period = period.trim();
+        let period_secs = match period {
+            "s" | "sec" | "second" => 1,
+            "m" | "min" | "minute" => 60,
+            "h" | "hr" | "hour" => 3600,
+            "d" | "day" => 86400,
+            _ => parse_delay(period)
+                .filter(|secs| *secs > 0)
+                .with_context(|| format!("Invalid spawn rate '{}': bad period", s))?,
+        };
+        Ok(Self { count, period_secs })
+    }
+
+    /// Spawns still allowed at `now`, given when earlier spawns happened.
+    pub fn allowance(
+        &self,
+        spawned_at: impl IntoIterator<Item = DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> usize {
+        let since = now - Duration::seconds(self.period_secs as i64);
+        let recent = spawned_at.into_iter().filter(|at| *at > since).count();
+        self.count.saturating_sub(recent)
+    }
+}
+
+impl std::fmt::Display for SpawnRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.period_secs {
+            1 => write!(f, "{}/s", self.count),
+            60 => write!(f, "{}/min", self.count),
+            3600 => write!(f, "{}/h", self.count),
+            86400 => write!(f, "{}/day", self.count),
+            secs => write!(f, "{}/{}s", self.count, secs),
+        }
+    }
+}
+
+/// When each agent in the registry was started.
+pub fn spawn_times(registry: &AgentRegistry) -> Vec<DateTime<Utc>> {
+    registry
+        .all()
+        .filter_map(|a| a.started_at.parse::<DateTime<Utc>>().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spawn_rate() {
+        let rate = |count, period_secs| SpawnRate { count, period_secs };
+        assert_eq!(SpawnRate::parse("2/min").unwrap(), rate(2, 60));
+        assert_eq!(SpawnRate::parse("30/h").unwrap(), rate(30, 3600));
+        assert_eq!(SpawnRate::parse(" 1 / 30s ").unwrap(), rate(1, 30));
+        assert_eq!(SpawnRate::parse("5/10m").unwrap(), rate(5, 600));
+        assert_eq!(SpawnRate::parse("2/min").unwrap().to_string(), "2/min");
+        assert!(SpawnRate::parse("2").is_err());
+        assert!(SpawnRate::parse("0/min").is_err());
+        assert!(SpawnRate::parse("2/fortnight").is_err());
+    }
+
+    #[test]
+    fn test_allowance_uses_sliding_window() {
+        let now: DateTime<Utc> = "2025-06-10T12:00:00Z".parse().unwrap();
+        let rate = SpawnRate::parse("2/min").unwrap();
+        assert_eq!(rate.allowance([], now), 2);
+        let one = [now - Duration::seconds(10)];
+        assert_eq!(rate.allowance(one, now), 1);
+        let two = [now - Duration::seconds(50), now - Duration::seconds(10)];
+        assert_eq!(rate.allowance(two, now), 0);
+        // The older spawn leaves the window after a minute
+        assert_eq!(rate.allowance(two, now + Duration::seconds(15)), 1);
+    }
+}
@@ -0,0 +1,400 @@
+//! Matching task skills against agent capabilities.
+//!
+//! `exact` mode (the default) only counts a capability with the same name as
+//! the skill. `embedding` mode (`[skill_matching] mode = "embedding"`) also
+//! counts the capability most similar to the skill, by cosine similarity of
+//! their embeddings, when it clears the configured threshold — so a
+//! `postgres` task matches an agent advertising `sql, databases`.
+//!
+//! Embeddings come from an OpenAI-compatible `/embeddings` endpoint and are
+//! cached in `.wg/cache/skill-embeddings.json`, keyed by model, so a fixed
+//! vocabulary of skills is only embedded once. When the endpoint can't be
+//! reached the matcher falls back to exact names and backs off for a few
+//! minutes before trying again.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::agency::Agent;
+use crate::config::{Config, EndpointConfig, SkillMatchMode};
+use crate::graph::Task;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to stop calling the embedding endpoint after it fails.
+const FAILURE_BACKOFF: Duration = Duration::from_secs(300);
+
+static LAST_FAILURE: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// How one required skill fared against an agent's capabilities.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkillMatch {
+    pub skill: String,
+    /// The closest capability, if any could be compared
+    pub capability: Option<String>,
+    /// Cosine similarity to that capability (1.0 for an exact name match)
+    pub similarity: f64,
+    /// Whether the capability covers the skill
+    pub matched: bool,
+}
+
+/// Matches skills to capabilities for one task.
+#[derive(Debug, Clone)]
+pub struct SkillMatcher {
+    /// Embeddings by text; empty in exact mode
+    embeddings: HashMap<String, Vec<f32>>,
+    threshold: f64,
+    description: String,
+}
+
+impl SkillMatcher {
+    /// Exact-name matching only.
+    pub fn exact() -> Self {
+        Self {
+            embeddings: HashMap::new(),
+            threshold: 1.0,
+            description: SkillMatchMode::Exact.to_string(),
+        }
+    }
+
+    /// Match with precomputed embeddings.
+    pub fn with_embeddings(embeddings: HashMap<String, Vec<f32>>, threshold: f64) -> Self {
+        Self {
+            embeddings,
+            threshold,
+            description: format!("embedding (threshold {:.2})", threshold),
+        }
+    }
+
+    /// Matcher for `task` against `agents` under `[skill_matching]`. In
+    /// embedding mode this embeds any skill or capability not yet cached;
+    /// on failure it warns and returns an exact matcher.
+    pub fn for_task(dir: &Path, config: &Config, task: &Task, agents: &[Agent]) -> Self {
+        let settings = &config.skill_matching;
+        if settings.mode == SkillMatchMode::Exact || task.skills.is_empty() {
+            return Self::exact();
+        }
+        let mut texts: Vec<&str> = task.skills.iter().map(String::as_str).collect();
+        texts.extend(
+            agents
+                .iter()
+                .flat_map(|a| a.capabilities.iter().map(String::as_str)),
+        );
+        match load_embeddings(dir, config, &texts) {
+            Ok(embeddings) => {
+                let mut matcher = Self::with_embeddings(embeddings, settings.threshold);
+                matcher.description = format!(
+                    "embedding ({}, threshold {:.2})",
+                    settings.model, settings.threshold
+                );
+                matcher
+            }
+            Err(e) => {
+                eprintln!(
+                    "[skill-match] Embeddings unavailable, matching skills exactly: {:#}",
+                    e
+                );
+                let mut matcher = Self::exact();
+                matcher.description = "exact (embedding endpoint unavailable)".to_string();
+                matcher
+            }
+        }
+    }
+
+    /// How this matcher decides, for display.
+    pub fn describe(&self) -> &str {
+        &self.description
+    }
+
+    /// Per-skill result for one agent's capabilities.
+    pub fn match_skills(&self, skills: &[String], capabilities: &[String]) -> Vec<SkillMatch> {
+        skills
+            .iter()
+            .map(|skill| {
+                if capabilities.contains(skill) {
+                    return SkillMatch {
+                        skill: skill.clone(),
+                        capability: Some(skill.clone()),
+                        similarity: 1.0,
+                        matched: true,
+                    };
+                }
+                let best = self.embeddings.get(skill).and_then(|sv| {
+                    capabilities
+                        .iter()
+                        .filter_map(|c| Some((c, cosine(sv, self.embeddings.get(c)?))))
+                        .max_by(|a, b| a.1.total_cmp(&b.1))
+                });
+                match best {
+                    Some((capability, similarity)) => SkillMatch {
+                        skill: skill.clone(),
+                        capability: Some(capability.clone()),
+                        similarity,
+                        matched: similarity >= self.threshold,
+                    },
+                    None => SkillMatch {
+                        skill: skill.clone(),
+                        capability: None,
+                        similarity: 0.0,
+                        matched: false,
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// How many of the task's skills an agent covers.
+    pub fn covered(&self, task: &Task, agent: &Agent) -> usize {
+        self.match_skills(&task.skills, &agent.capabilities)
+            .iter()
+            .filter(|m| m.matched)
+            .count()
+    }
+}
+
+/// Cosine similarity; 0 for mismatched or zero-length vectors.
+pub fn cosine(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let (mut dot, mut na, mut nb) = (0.0f64, 0.0f64, 0.0f64);
+    for (x, y) in a.iter().zip(b) {
+        let (x, y) = (*x as f64, *y as f64);
+        dot += x * y;
+        na += x * x;
+        nb += y * y;
+    }
+    if na == 0.0 || nb == 0.0 {
+        return 0.0;
+    }
+    dot / (na.sqrt() * nb.sqrt())
+}
+
+/// Cached embeddings: model → text → vector.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct EmbeddingCache {
+    #[serde(flatten)]
+    models: BTreeMap<String, BTreeMap<String, Vec<f32>>>,
+}
+
+fn cache_path(dir: &Path) -> PathBuf {
+    dir.join("cache").join("skill-embeddings.json")
+}
+
+impl EmbeddingCache {
+    fn load(dir: &Path) -> Self {
+        std::fs::read_to_string(cache_path(dir))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, dir: &Path) -> Result<()> {
+        let path = cache_path(dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Embeddings for `texts`, from the cache where possible.
+fn load_embeddings(
+    dir: &Path,
+    config: &Config,
+    texts: &[&str],
+) -> Result<HashMap<String, Vec<f32>>> {
+    let model = &config.skill_matching.model;
+    let mut cache = EmbeddingCache::load(dir);
+    let cached = cache.models.entry(model.clone()).or_default();
+
+    let mut missing: Vec<&str> = texts
+        .iter()
+        .copied()
+        .filter(|t| !cached.contains_key(*t))
+        .collect();
+    missing.sort_unstable();
+    missing.dedup();
+
+    if !missing.is_empty() {
+        if let Some(at) = *LAST_FAILURE.lock().unwrap_or_else(|e| e.into_inner())
+            && at.elapsed() < FAILURE_BACKOFF
+        {
+            bail!("endpoint failed recently; retrying after a short backoff");
+        }
+        let vectors = match fetch_embeddings(dir, config, &missing) {
+            Ok(v) => v,
+            Err(e) => {
+                *LAST_FAILURE.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+                return Err(e);
+            }
+        };
+        for (text, vector) in missing.iter().zip(vectors) {
+            cached.insert(text.to_string(), vector);
+        }
+        if let Err(e) = cache.save(dir) {
+            eprintln!("[skill-match] Failed to cache embeddings: {:#}", e);
+        }
+    }
+
+    let cached = &cache.models[model];
+    Ok(texts
+        .iter()
+        .filter_map(|t| Some((t.to_string(), cached.get(*t)?.clone())))
+        .collect())
+}
+
+fn embedding_endpoint(config: &Config) -> Result<&EndpointConfig> {
+    let endpoints = &config.llm_endpoints;
+    match config.skill_matching.endpoint.as_deref() {
+        Some(name) => endpoints
+            .find_by_name(name)
+            .with_context(|| format!("[skill_matching] endpoint '{}' is not configured", name)),
+        None => endpoints
+            .find_default()
+            .context("[skill_matching] needs an endpoint: no LLM endpoints are configured"),
+    }
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingDatum>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingDatum {
+    #[serde(default)]
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// POST `texts` to the endpoint's `/embeddings`, returning vectors in order.
+fn fetch_embeddings(dir: &Path, config: &Config, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+    let endpoint = embedding_endpoint(config)?;
+    let base = endpoint
+        .url
+        .as_deref()
+        .unwrap_or_else(|| EndpointConfig::default_url_for_provider(&endpoint.provider));
+    if base.is_empty() {
+        bail!(
+            "endpoint '{}' has no URL for provider '{}'",
+            endpoint.name,
+            endpoint.provider
+        );
+    }
+    let url = format!("{}/embeddings", base.trim_end_matches('/'));
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .context("Failed to build HTTP client")?;
+    let mut request = client.post(&url).json(&serde_json::json!({
+        "model": config.skill_matching.model,
+        "input": texts,
+    }));
+    if let Some(key) = endpoint.resolve_api_key(Some(dir))? {
+        request = request.bearer_auth(key);
+    }
+    let response = request
+        .send()
+        .with_context(|| format!("Request to {} failed", url))?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("{} returned {}", url, status);
+    }
+    let mut body: EmbeddingResponse = response
+        .json()
+        .with_context(|| format!("Invalid embeddings response from {}", url))?;
+    if body.data.len() != texts.len() {
+        bail!(
+            "{} returned {} embeddings for {} inputs",
+            url,
+            body.data.len(),
+            texts.len()
+        );
+    }
+    body.data.sort_by_key(|d| d.index);
+    Ok(body.data.into_iter().map(|d| d.embedding).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skills(s: &[&str]) -> Vec<String> {
+        s.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn matcher() -> SkillMatcher {
+        let embeddings = [
+            ("postgres", vec![0.9, 0.1, 0.0]),
+            ("sql", vec![0.8, 0.3, 0.0]),
+            ("databases", vec![1.0, 0.0, 0.1]),
+            ("frontend", vec![0.0, 0.2, 1.0]),
+        ]
+        .into_iter()
+        .map(|(t, v)| (t.to_string(), v))
+        .collect();
+        SkillMatcher::with_embeddings(embeddings, 0.9)
+    }
+
+    #[test]
+    fn test_cosine() {
+        assert!((cosine(&[1.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < 1e-9);
+        assert!(cosine(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-9);
+        assert_eq!(cosine(&[1.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_similar_capability_covers_skill() {
+        let m = matcher();
+        let result = m.match_skills(&skills(&["postgres"]), &skills(&["sql", "databases"]));
+        assert_eq!(result.len(), 1);
+        assert!(result[0].matched);
+        assert_eq!(result[0].capability.as_deref(), Some("databases"));
+        assert!(result[0].similarity > 0.9 && result[0].similarity < 1.0);
+
+        let result = m.match_skills(&skills(&["postgres"]), &skills(&["frontend"]));
+        assert!(!result[0].matched);
+        assert_eq!(result[0].capability.as_deref(), Some("frontend"));
+    }
+
+    #[test]
+    fn test_exact_names_and_unknown_texts() {
+        let exact = SkillMatcher::exact();
+        let result = exact.match_skills(&skills(&["rust", "postgres"]), &skills(&["rust", "sql"]));
+        assert!(result[0].matched);
+        assert_eq!(result[0].similarity, 1.0);
+        assert!(!result[1].matched);
+        assert_eq!(result[1].capability, None);
+
+        // A capability without an embedding can still match by name.
+        let result = matcher().match_skills(&skills(&["rust"]), &skills(&["rust"]));
+        assert!(result[0].matched);
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut cache = EmbeddingCache::default();
+        cache
+            .models
+            .entry("m".to_string())
+            .or_default()
+            .insert("sql".to_string(), vec![0.5, 0.5]);
+        cache.save(tmp.path()).unwrap();
+
+        // Everything requested is cached, so no endpoint is needed.
+        let mut config = Config::default();
+        config.skill_matching.model = "m".to_string();
+        let got = load_embeddings(tmp.path(), &config, &["sql"]).unwrap();
+        assert_eq!(got["sql"], vec![0.5, 0.5]);
+    }
+}
@@ -0,0 +1,510 @@
+//! Dispatch strategies under `coordinator.dispatch_strategy`.
+//!
+//! A strategy decides which ready task the dispatcher starts next and,
+//! optionally, which agent the assigner should prefer for it. The default,
+//! `priority`, is the dispatcher's own order (effective priority with aging
+//! and inheritance, then fair share) and lives with the dispatcher; this
+//! module holds the alternatives:
+//!
+//! - `fifo`: oldest task first
+//! - `critical-path-first`: the task with the longest chain of work behind
+//!   it first, so the project's finish date moves as little as possible
+//! - `cost-aware`: among equal priorities, cheaper estimates first; agents
+//!   with the lowest rate first
+//! - `script:<path>`: an external program ranks tasks and agents (see
+//!   [`ScriptStrategy`])
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::agency::Agent;
+use crate::graph::{Estimate, Priority, Task, WorkGraph};
+use crate::query::build_reverse_index;
+
+/// The strategy used when `coordinator.dispatch_strategy` is unset.
+pub const DEFAULT_STRATEGY: &str = "priority";
+
+/// How long a strategy script may run before its answer is ignored.
+pub const SCRIPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Hours assumed for a task without an estimate when measuring paths.
+const UNESTIMATED_HOURS: f64 = 1.0;
+
+/// Picks the next task to dispatch and, optionally, the agent for it.
+pub trait DispatchStrategy {
+    /// Name shown in dispatcher logs.
+    fn name(&self) -> String;
+
+    /// Order the ready set best-first. Tasks left out are held this tick.
+    fn order_tasks<'a>(&self, graph: &WorkGraph, ready: Vec<&'a Task>) -> Vec<&'a Task>;
+
+    /// Rank candidate agents for `task` best-first, or `None` to leave the
+    /// choice to `coordinator.dispatch`.
+    fn rank_agents<'a>(&self, _task: &Task, _agents: &'a [Agent]) -> Option<Vec<&'a Agent>> {
+        None
+    }
+}
+
+/// Resolve a `coordinator.dispatch_strategy` value. `priority` returns
+/// `None`: the dispatcher's built-in order applies. Relative script paths
+/// are resolved against the project root (the parent of `dir`).
+pub fn load(spec: &str, dir: &Path) -> Result<Option<Box<dyn DispatchStrategy>>> {
+    let spec = spec.trim();
+    let strategy: Box<dyn DispatchStrategy> = match spec {
+        "priority" => return Ok(None),
+        "fifo" => Box::new(Fifo),
+        "critical-path-first" => Box::new(CriticalPathFirst),
+        "cost-aware" => Box::new(CostAware),
+        _ => match spec.strip_prefix("script:") {
+            Some(path) if !path.trim().is_empty() => {
+                let path = Path::new(path.trim());
+                let path = if path.is_absolute() {
+                    path.to_path_buf()
+                } else {
+                    dir.parent().unwrap_or(dir).join(path)
+                };
+                Box::new(ScriptStrategy::new(path))
+            }
+            _ => bail!(
+                "Unknown dispatch strategy '{}': expected priority, fifo, \
+                 critical-path-first, cost-aware or script:<path>",
+                spec
+            ),
+        },
+    };
+    Ok(Some(strategy))
+}
+
+/// Check a `coordinator.dispatch_strategy` value without touching disk.
+pub fn validate(spec: &str) -> Result<()> {
+    load(spec, Path::new(".")).map(|_| ())
+}
+
+fn created_order(a: &Task, b: &Task) -> Ordering {
+    match (&a.created_at, &b.created_at) {
+        (Some(x), Some(y)) => x.cmp(y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Oldest task first, by `created_at` (tasks without one go last).
+pub struct Fifo;
+
+impl DispatchStrategy for Fifo {
+    fn name(&self) -> String {
+        "fifo".to_string()
+    }
+
+    fn order_tasks<'a>(&self, _graph: &WorkGraph, mut ready: Vec<&'a Task>) -> Vec<&'a Task> {
+        ready.sort_by(|a, b| created_order(a, b));
+        ready
+    }
+}
+
+/// Longest remaining path first: a task's own estimate plus the longest
+/// chain of dependents (and edge lag/weight) that waits on it.
+pub struct CriticalPathFirst;
+
+/// Hours from the start of each task to the end of the longest chain of
+/// non-terminal work behind it.
+pub fn tail_hours(graph: &WorkGraph) -> HashMap<String, f64> {
+    fn visit(
+        id: &str,
+        graph: &WorkGraph,
+        dependents: &HashMap<String, Vec<String>>,
+        memo: &mut HashMap<String, f64>,
+        visiting: &mut Vec<String>,
+    ) -> f64 {
+        if let Some(&hours) = memo.get(id) {
+            return hours;
+        }
+        let Some(task) = graph.get_task(id) else {
+            return 0.0;
+        };
+        if visiting.iter().any(|v| v == id) {
+            // Cycle: count the task once and stop
+            return 0.0;
+        }
+        visiting.push(id.to_string());
+        let own = task
+            .estimate
+            .as_ref()
+            .and_then(|e| e.hours)
+            .unwrap_or(UNESTIMATED_HOURS);
+        let downstream = dependents
+            .get(id)
+            .into_iter()
+            .flatten()
+            .filter_map(|dep| graph.get_task(dep))
+            .filter(|dep| !dep.status.is_terminal())
+            .map(|dep| {
+                let edge = dep.edges.get(id).map_or(0.0, |e| e.hours());
+                edge + visit(&dep.id, graph, dependents, memo, visiting)
+            })
+            .fold(0.0, f64::max);
+        visiting.pop();
+        memo.insert(id.to_string(), own + downstream);
+        own + downstream
+    }
+
+    let dependents = build_reverse_index(graph);
+    let mut memo = HashMap::new();
+    let mut visiting = Vec::new();
+    for task in graph.tasks() {
+        visit(&task.id, graph, &dependents, &mut memo, &mut visiting);
+    }
+    memo
+}
+
+impl DispatchStrategy for CriticalPathFirst {
+    fn name(&self) -> String {
+        "critical-path-first".to_string()
+    }
+
+    fn order_tasks<'a>(&self, graph: &WorkGraph, mut ready: Vec<&'a Task>) -> Vec<&'a Task> {
+        let tails = tail_hours(graph);
+        let tail = |t: &Task| tails.get(&t.id).copied().unwrap_or(0.0);
+        ready.sort_by(|a, b| {
+            tail(b)
+                .partial_cmp(&tail(a))
+                .unwrap_or(Ordering::Equal)
+                .then(b.priority.cmp(&a.priority))
+                .then_with(|| created_order(a, b))
+        });
+        ready
+    }
+}
+
+/// Priority first, then the cheapest estimate (unestimated tasks after
+/// estimated ones); agents with the lowest rate first.
+pub struct CostAware;
+
+fn cost(task: &Task) -> Option<f64> {
+    task.estimate.as_ref().and_then(|e| e.cost)
+}
+
+fn cheapest_first(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+impl DispatchStrategy for CostAware {
+    fn name(&self) -> String {
+        "cost-aware".to_string()
+    }
+
+    fn order_tasks<'a>(&self, _graph: &WorkGraph, mut ready: Vec<&'a Task>) -> Vec<&'a Task> {
+        ready.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(cheapest_first(cost(a), cost(b)))
+                .then_with(|| created_order(a, b))
+        });
+        ready
+    }
+
+    fn rank_agents<'a>(&self, _task: &Task, agents: &'a [Agent]) -> Option<Vec<&'a Agent>> {
+        let mut ranked: Vec<&Agent> = agents.iter().collect();
+        ranked.sort_by(|a, b| {
+            cheapest_first(a.rate, b.rate).then_with(|| {
+                let score = |x: &Agent| x.performance.avg_score.unwrap_or(0.0);
+                score(b).partial_cmp(&score(a)).unwrap_or(Ordering::Equal)
+            })
+        });
+        Some(ranked)
+    }
+}
+
+/// A strategy implemented by an external program.
+///
+/// The program is run once per decision with a JSON request on stdin and
+/// must print a JSON array of IDs, best first, on stdout:
+///
+/// - `{"phase": "tasks", "tasks": [...]}`: ready tasks (id, title, priority,
+///   created_at, estimate, tags, skills, after). IDs left out are held this
+///   tick.
+/// - `{"phase": "agents", "task": {...}, "agents": [...]}`: candidate agents
+///   (id, name, capabilities, rate, score). Agents left out keep their
+///   relative order after the ranked ones.
+///
+/// If the program fails, times out after [`SCRIPT_TIMEOUT`] or prints
+/// something else, the incoming order is kept and a warning is logged.
+pub struct ScriptStrategy {
+    path: PathBuf,
+}
+
+#[derive(Serialize)]
+struct ScriptTask<'a> {
+    id: &'a str,
+    title: &'a str,
+    priority: Priority,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_at: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimate: Option<&'a Estimate>,
+    tags: &'a [String],
+    skills: &'a [String],
+    after: &'a [String],
+}
+
+impl<'a> From<&'a Task> for ScriptTask<'a> {
+    fn from(task: &'a Task) -> Self {
+        Self {
+            id: &task.id,
+            title: &task.title,
+            priority: task.priority,
+            created_at: task.created_at.as_deref(),
+            estimate: task.estimate.as_ref(),
+            tags: &task.tags,
+            skills: &task.skills,
+            after: &task.after,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ScriptAgent<'a> {
+    id: &'a str,
+    name: &'a str,
+    capabilities: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<f64>,
+}
+
+impl ScriptStrategy {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Run the script with `request` on stdin and parse its ID list.
+    fn ask(&self, request: &serde_json::Value) -> Result<Vec<String>> {
+        let mut child = Command::new(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Failed to run {}", self.path.display()))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(request.to_string().as_bytes())
+                .context("Failed to write to strategy script stdin")?;
+        }
+        let deadline = Instant::now() + SCRIPT_TIMEOUT;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!("timed out after {}s", SCRIPT_TIMEOUT.as_secs());
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        let mut stdout = String::new();
+        if let Some(mut out) = child.stdout.take() {
+            out.read_to_string(&mut stdout)?;
+        }
+        if !status.success() {
+            bail!("exited with {}", status);
+        }
+        serde_json::from_str(stdout.trim()).context("expected a JSON array of IDs on stdout")
+    }
+}
+
+/// Reorder `items` by `ids`; items whose ID is missing go last in their
+/// incoming order when `keep_rest`, or are dropped otherwise.
+fn reorder<'a, T>(
+    items: Vec<&'a T>,
+    ids: &[String],
+    id_of: impl Fn(&T) -> &str,
+    keep_rest: bool,
+) -> Vec<&'a T> {
+    let mut ranked: Vec<&T> = Vec::new();
+    for id in ids {
+        if let Some(item) = items.iter().find(|i| id_of(i) == id)
+            && !ranked.iter().any(|r| id_of(r) == id)
+        {
+            ranked.push(item);
+        }
+    }
+    if keep_rest {
+        for item in items {
+            if !ranked.iter().any(|r| id_of(r) == id_of(item)) {
+                ranked.push(item);
+            }
+        }
+    }
+    ranked
+}
+
+impl DispatchStrategy for ScriptStrategy {
+    fn name(&self) -> String {
+        format!("script:{}", self.path.display())
+    }
+
+    fn order_tasks<'a>(&self, _graph: &WorkGraph, ready: Vec<&'a Task>) -> Vec<&'a Task> {
+        let tasks: Vec<ScriptTask> = ready.iter().map(|t| ScriptTask::from(*t)).collect();
+        let request = serde_json::json!({ "phase": "tasks", "tasks": tasks });
+        match self.ask(&request) {
+            Ok(ids) => reorder(ready, &ids, |t: &Task| &t.id, false),
+            Err(e) => {
+                eprintln!(
+                    "[dispatcher] Dispatch strategy {} failed ({:#}); keeping ready order",
+                    self.name(),
+                    e
+                );
+                ready
+            }
+        }
+    }
+
+    fn rank_agents<'a>(&self, task: &Task, agents: &'a [Agent]) -> Option<Vec<&'a Agent>> {
+        let candidates: Vec<ScriptAgent> = agents
+            .iter()
+            .map(|a| ScriptAgent {
+                id: &a.id,
+                name: &a.name,
+                capabilities: &a.capabilities,
+                rate: a.rate,
+                score: a.performance.avg_score,
+            })
+            .collect();
+        let request = serde_json::json!({
+            "phase": "agents",
+            "task": ScriptTask::from(task),
+            "agents": candidates,
+        });
+        match self.ask(&request) {
+            Ok(ids) => Some(reorder(
+                agents.iter().collect(),
+                &ids,
+                |a: &Agent| &a.id,
+                true,
+            )),
+            Err(e) => {
+                eprintln!(
+                    "[dispatcher] Dispatch strategy {} failed to rank agents for '{}' ({:#})",
+                    self.name(),
+                    task.id,
+                    e
+                );
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{DependencyEdge, Node, PRIORITY_HIGH};
+    use crate::test_helpers::make_task;
+
+    fn task(id: &str, created: &str, hours: Option<f64>, cost: Option<f64>) -> Task {
+        let mut t = make_task(id, id);
+        t.created_at = Some(format!("2026-01-0{}T00:00:00Z", created));
+        t.estimate = Some(Estimate { hours, cost });
+        t
+    }
+
+    fn ids(tasks: &[&Task]) -> Vec<String> {
+        tasks.iter().map(|t| t.id.clone()).collect()
+    }
+
+    #[test]
+    fn test_load_specs() {
+        let dir = Path::new("/project/.wg");
+        assert!(load("priority", dir).unwrap().is_none());
+        assert_eq!(load("fifo", dir).unwrap().unwrap().name(), "fifo");
+        assert_eq!(
+            load("script:tools/rank", dir).unwrap().unwrap().name(),
+            "script:/project/tools/rank"
+        );
+        assert!(load("random", dir).is_err());
+        assert!(validate("script:").is_err());
+    }
+
+    #[test]
+    fn test_fifo_and_cost_aware_order() {
+        let mut graph = WorkGraph::new();
+        let mut urgent = task("urgent", "3", None, Some(50.0));
+        urgent.priority = PRIORITY_HIGH;
+        graph.add_node(Node::Task(task("old", "1", None, Some(20.0))));
+        graph.add_node(Node::Task(task("cheap", "2", None, Some(5.0))));
+        graph.add_node(Node::Task(urgent));
+        graph.add_node(Node::Task(task("unknown", "4", None, None)));
+        let ready = || graph.tasks().collect::<Vec<_>>();
+
+        assert_eq!(
+            ids(&Fifo.order_tasks(&graph, ready())),
+            ["old", "cheap", "urgent", "unknown"]
+        );
+        assert_eq!(
+            ids(&CostAware.order_tasks(&graph, ready())),
+            ["urgent", "cheap", "old", "unknown"]
+        );
+    }
+
+    #[test]
+    fn test_critical_path_first_prefers_longest_tail() {
+        // short (1h) is ready on its own; head (2h) unblocks a 10h chain
+        // with a 1d lag on the edge
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task("short", "1", Some(4.0), None)));
+        graph.add_node(Node::Task(task("head", "2", Some(2.0), None)));
+        let mut tail = task("tail", "3", Some(10.0), None);
+        tail.after = vec!["head".to_string()];
+        tail.edges.insert(
+            "head".to_string(),
+            DependencyEdge {
+                lag: Some("1d".to_string()),
+                weight: None,
+            },
+        );
+        graph.add_node(Node::Task(tail));
+
+        let tails = tail_hours(&graph);
+        assert_eq!(tails["head"], 36.0);
+        assert_eq!(tails["short"], 4.0);
+        let ready = vec![
+            graph.get_task("short").unwrap(),
+            graph.get_task("head").unwrap(),
+        ];
+        assert_eq!(
+            ids(&CriticalPathFirst.order_tasks(&graph, ready)),
+            ["head", "short"]
+        );
+    }
+
+    #[test]
+    fn test_reorder_by_script_ids() {
+        let a = make_task("a", "a");
+        let b = make_task("b", "b");
+        let c = make_task("c", "c");
+        let items = vec![&a, &b, &c];
+        let ranked = reorder(
+            items.clone(),
+            &["c".into(), "x".into(), "a".into()],
+            |t: &Task| &t.id,
+            false,
+        );
+        assert_eq!(ids(&ranked), ["c", "a"]);
+        let ranked = reorder(items, &["b".into()], |t: &Task| &t.id, true);
+        assert_eq!(ids(&ranked), ["b", "a", "c"]);
+    }
+}
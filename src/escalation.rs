@@ -0,0 +1,668 @@
+//! Escalation chains for stuck tasks.
+//!
+//! When a task sits in-progress or blocked past configured thresholds, the
+//! chain notifies, in order, the task's assignee, the owner of the milestone
+//! it feeds into, and finally a fallback channel from the notification rules
+//! engine. The chain is configured in `notify.toml`:
+//!
+//! ```toml
+//! [[escalation.chain]]
+//! after = "4h"
+//! notify = "assignee"
+//!
+//! [[escalation.chain]]
+//! after = "1d"
+//! notify = "owner"
+//!
+//! [[escalation.chain]]
+//! after = "2d"
+//! notify = "channel"
+//! ```
+//!
+//! Thresholds are measured from when the task got stuck. Each sweep fires at
+//! most one step per task; the task's `escalation_level` records how far the
+//! chain has progressed and every fired step is written to the task log. The
+//! level resets once the task is no longer stuck.
+
+use crate::availability::AvailabilityCalendar;
+use crate::graph::{LogEntry, Status, Task, WorkGraph, parse_delay};
+use crate::notify::EventType;
+use crate::notify::config::NotifyConfig;
+use crate::parser::modify_graph;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
+
+/// Tag marking a task as a milestone whose assignee owns everything upstream.
+pub const MILESTONE_TAG: &str = "milestone";
+
+/// Actor recorded on the log entries the sweep writes.
+pub const ESCALATION_ACTOR: &str = "escalation";
+
+/// Who a chain step notifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationTarget {
+    /// The agent or human the task is assigned to.
+    Assignee,
+    /// The assignee of the nearest downstream milestone task.
+    Owner,
+    /// The urgent channels from the notification routing rules.
+    Channel,
+}
+
+impl std::fmt::Display for EscalationTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Assignee => write!(f, "assignee"),
+            Self::Owner => write!(f, "milestone owner"),
+            Self::Channel => write!(f, "fallback channel"),
+        }
+    }
+}
+
+/// One step of an escalation chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EscalationStep {
+    /// How long the task must be stuck before this step fires (e.g. "4h", "2d").
+    pub after: String,
+    pub notify: EscalationTarget,
+}
+
+/// A chain step due to fire for a task.
+#[derive(Debug, Clone, Serialize)]
+pub struct EscalationAction {
+    pub task_id: String,
+    pub title: String,
+    /// 1-based index of the step in the chain.
+    pub level: u32,
+    pub chain_len: u32,
+    pub target: EscalationTarget,
+    /// Resolved recipient (assignee or milestone owner); `None` for channels
+    /// or when nobody could be resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    /// Milestone the owner was resolved through.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+    pub status: Status,
+    pub stuck_secs: i64,
+}
+
+impl EscalationAction {
+    /// Text sent to the recipient.
+    pub fn message(&self) -> String {
+        format!(
+            "Escalation: task '{}' ({}) has been {} for {}",
+            self.task_id,
+            self.title,
+            self.status,
+            format_stuck(self.stuck_secs),
+        )
+    }
+
+    /// Line recorded in the task log.
+    fn log_message(&self, outcome: &str) -> String {
+        let who = match (&self.recipient, &self.milestone) {
+            (Some(r), Some(m)) => format!("{} {} via {}", self.target, r, m),
+            (Some(r), None) => format!("{} {}", self.target, r),
+            (None, _) => self.target.to_string(),
+        };
+        format!(
+            "Escalation step {}/{} → {}: {} ({} for {})",
+            self.level,
+            self.chain_len,
+            who,
+            outcome,
+            self.status,
+            format_stuck(self.stuck_secs),
+        )
+    }
+}
+
+fn format_stuck(secs: i64) -> String {
+    let hours = secs / 3600;
+    if hours >= 24 {
+        format!("{}d {}h", hours / 24, hours % 24)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, (secs % 3600) / 60)
+    } else {
+        format!("{}m", secs / 60)
+    }
+}
+
+/// When the task entered its current stuck state, if it is stuck at all.
+///
+/// In-progress tasks count from `started_at`; blocked tasks from
+/// `blocked_at`, which the sweep stamps the first time it sees the task
+/// blocked so that later notes and messages don't restart the clock. Until
+/// then, the newest log entry mentioning the block stands in, falling back
+/// to `created_at`. The sweep's own entries never count, or escalating would
+/// restart the clock.
+pub fn stuck_since(task: &Task) -> Option<DateTime<Utc>> {
+    let ts = match task.status {
+        Status::InProgress => task.started_at.as_deref(),
+        Status::Blocked => task.blocked_at.as_deref().or_else(|| {
+            task.log
+                .iter()
+                .rev()
+                .filter(|e| e.actor.as_deref() != Some(ESCALATION_ACTOR))
+                .find(|e| e.message.to_lowercase().contains("block"))
+                .map(|e| e.timestamp.as_str())
+                .or(task.created_at.as_deref())
+        }),
+        _ => None,
+    }?;
+    ts.parse::<DateTime<Utc>>().ok()
+}
+
+/// Whether the sweep needs to stamp or clear the task's `blocked_at`.
+fn needs_blocked_stamp(task: &Task) -> bool {
+    (task.status == Status::Blocked) != task.blocked_at.is_some()
+}
+
+/// Breadth-first search downstream of `task_id` (the task included) for the
+/// first milestone `pick` accepts.
+fn find_milestone<T>(
+    graph: &WorkGraph,
+    task_id: &str,
+    pick: impl Fn(&Task) -> Option<T>,
+) -> Option<T> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<String> = VecDeque::from([task_id.to_string()]);
+    while let Some(id) = queue.pop_front() {
+        if !seen.insert(id.clone()) {
+            continue;
+        }
+        if let Some(t) = graph.get_task(&id)
+            && t.tags.iter().any(|tag| tag == MILESTONE_TAG)
+            && let Some(found) = pick(t)
+        {
+            return Some(found);
+        }
+        for dependent in graph.tasks().filter(|t| t.after.contains(&id)) {
+            queue.push_back(dependent.id.clone());
+        }
+    }
+    None
+}
+
+/// The nearest downstream milestone of a task, owned or not. A task tagged
+/// `milestone` is its own milestone.
+pub fn nearest_milestone(graph: &WorkGraph, task_id: &str) -> Option<String> {
+    find_milestone(graph, task_id, |t| Some(t.id.clone()))
+}
+
+/// Find the nearest downstream milestone with an owner.
+///
+/// Returns `(milestone_id, owner)`. A task tagged `milestone` is its own
+/// milestone.
+pub fn milestone_owner(graph: &WorkGraph, task_id: &str) -> Option<(String, String)> {
+    find_milestone(graph, task_id, |t| {
+        t.assigned
+            .as_ref()
+            .map(|owner| (t.id.clone(), owner.clone()))
+    })
+}
+
+/// Determine which chain steps are due.
+///
+/// At most one step fires per task per sweep: the next unfired step whose
+/// threshold has passed. Steps with an unparseable `after` never fire.
+pub fn plan(
+    graph: &WorkGraph,
+    chain: &[EscalationStep],
+    now: DateTime<Utc>,
+) -> Vec<EscalationAction> {
+    let mut actions = Vec::new();
+    for task in graph.tasks() {
+        // Tasks blocked outside the project are followed up by `wg waiting`
+        if task.paused || task.external_blocker.is_some() {
+            continue;
+        }
+        let Some(since) = stuck_since(task) else {
+            continue;
+        };
+        let Some(step) = chain.get(task.escalation_level as usize) else {
+            continue;
+        };
+        let Some(threshold) = parse_delay(&step.after) else {
+            continue;
+        };
+        let stuck_secs = (now - since).num_seconds();
+        if stuck_secs < threshold as i64 {
+            continue;
+        }
+
+        let (recipient, milestone) = match step.notify {
+            EscalationTarget::Assignee => (task.assigned.clone(), None),
+            EscalationTarget::Owner => match milestone_owner(graph, &task.id) {
+                Some((m, owner)) => (Some(owner), Some(m)),
+                None => (None, None),
+            },
+            EscalationTarget::Channel => (None, None),
+        };
+        actions.push(EscalationAction {
+            task_id: task.id.clone(),
+            title: task.title.clone(),
+            level: task.escalation_level + 1,
+            chain_len: chain.len() as u32,
+            target: step.notify,
+            recipient,
+            milestone,
+            status: task.status,
+            stuck_secs,
+        });
+    }
+    actions
+}
+
+/// Deliver a step's notification. Returns a short outcome for the task log.
+///
+/// Assignees are reached through the task's message queue (which agents
+/// poll); milestone owners through the milestone task's queue; the channel
+/// step goes out on the urgent route of the notification router. Recipients
+/// on the availability calendar are skipped so the chain moves on to the
+/// next step instead of waiting on someone who is away.
+fn deliver(
+    dir: &Path,
+    config: &NotifyConfig,
+    calendar: &AvailabilityCalendar,
+    action: &EscalationAction,
+) -> String {
+    match action.target {
+        EscalationTarget::Assignee | EscalationTarget::Owner => {
+            let Some(ref recipient) = action.recipient else {
+                return format!("skipped, no {}", action.target);
+            };
+            if let Some(back) = calendar.unavailable_until(&[recipient], Utc::now()) {
+                return format!(
+                    "skipped, unavailable until {}",
+                    back.format("%Y-%m-%d %H:%M")
+                );
+            }
+            let queue = action.milestone.as_deref().unwrap_or(&action.task_id);
+            let message = crate::maintenance::annotate(dir, &action.message());
+            let body = format!("@{} {}", recipient, message);
+            match crate::messages::send_message(dir, queue, &body, ESCALATION_ACTOR, "urgent") {
+                Ok(_) => "notified".to_string(),
+                Err(e) => format!("failed: {}", e),
+            }
+        }
+        EscalationTarget::Channel => {
+            let (router, _warnings) = config.build_router();
+            if router.channels_for_event(EventType::Urgent).is_empty() {
+                return "skipped, no urgent channels configured".to_string();
+            }
+            let rt = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(rt) => rt,
+                Err(e) => return format!("failed: {}", e),
+            };
+            let message = crate::maintenance::annotate(dir, &action.message());
+            match rt.block_on(router.send(EventType::Urgent, &action.task_id, &message)) {
+                Ok((ch, _)) => format!("notified via {}", ch),
+                Err(e) => format!("failed: {}", e),
+            }
+        }
+    }
+}
+
+/// Run one escalation sweep over the graph.
+///
+/// Fires due chain steps, records each one in the task log, resets the
+/// level of tasks that are no longer stuck, and stamps (or clears) when
+/// tasks became blocked. With `dry_run`, only reports the
+/// steps that would fire. Returns the steps fired (or due).
+pub fn run_sweep(
+    dir: &Path,
+    graph_path: &Path,
+    config: &NotifyConfig,
+    dry_run: bool,
+) -> Result<Vec<EscalationAction>> {
+    let chain = &config.escalation.chain;
+    let graph = crate::parser::load_graph(graph_path)?;
+    let actions = if chain.is_empty() {
+        Vec::new()
+    } else {
+        plan(&graph, chain, Utc::now())
+    };
+    let needs_reset = graph
+        .tasks()
+        .any(|t| (t.escalation_level > 0 && stuck_since(t).is_none()) || needs_blocked_stamp(t));
+    if dry_run || (actions.is_empty() && !needs_reset) {
+        return Ok(actions);
+    }
+
+    let calendar = AvailabilityCalendar::load_or_warn(dir);
+    let outcomes: Vec<String> = actions
+        .iter()
+        .map(|a| deliver(dir, config, &calendar, a))
+        .collect();
+
+    modify_graph(graph_path, |graph| {
+        let mut modified = false;
+        for task in graph.tasks_mut() {
+            if task.escalation_level > 0 && stuck_since(task).is_none() {
+                task.escalation_level = 0;
+                modified = true;
+            }
+            if needs_blocked_stamp(task) {
+                task.blocked_at = stuck_since(task)
+                    .filter(|_| task.status == Status::Blocked)
+                    .map(|since| since.to_rfc3339());
+                modified = true;
+            }
+        }
+        for (action, outcome) in actions.iter().zip(&outcomes) {
+            let Some(task) = graph.get_task_mut(&action.task_id) else {
+                continue;
+            };
+            // Another writer may have moved the task on since we planned.
+            if task.escalation_level + 1 != action.level || stuck_since(task).is_none() {
+                continue;
+            }
+            task.escalation_level = action.level;
+            task.log.push(LogEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                actor: Some(ESCALATION_ACTOR.to_string()),
+                user: Some(crate::current_user()),
+                message: action.log_message(outcome),
+            });
+            modified = true;
+        }
+        modified
+    })?;
+
+    let rotation = crate::config::Config::load_or_default(dir)
+        .log
+        .rotation_threshold;
+    for action in &actions {
+        let _ = crate::provenance::record(
+            dir,
+            "escalate",
+            Some(&action.task_id),
+            Some(ESCALATION_ACTOR),
+            serde_json::json!({
+                "level": action.level,
+                "target": action.target,
+                "recipient": action.recipient,
+                "milestone": action.milestone,
+            }),
+            rotation,
+        );
+    }
+
+    Ok(actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use chrono::Duration;
+
+    fn chain() -> Vec<EscalationStep> {
+        vec![
+            EscalationStep {
+                after: "4h".to_string(),
+                notify: EscalationTarget::Assignee,
+            },
+            EscalationStep {
+                after: "1d".to_string(),
+                notify: EscalationTarget::Owner,
+            },
+            EscalationStep {
+                after: "2d".to_string(),
+                notify: EscalationTarget::Channel,
+            },
+        ]
+    }
+
+    fn stuck_task(id: &str, hours_ago: i64) -> Task {
+        Task {
+            id: id.to_string(),
+            title: id.to_string(),
+            status: Status::InProgress,
+            assigned: Some("agent-1".to_string()),
+            started_at: Some((Utc::now() - Duration::hours(hours_ago)).to_rfc3339()),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn test_plan_fires_next_due_step_only() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(stuck_task("fresh", 1)));
+        graph.add_node(Node::Task(stuck_task("stale", 72)));
+
+        let actions = plan(&graph, &chain(), Utc::now());
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].task_id, "stale");
+        assert_eq!(actions[0].level, 1);
+        assert_eq!(actions[0].target, EscalationTarget::Assignee);
+        assert_eq!(actions[0].recipient.as_deref(), Some("agent-1"));
+
+        graph.get_task_mut("stale").unwrap().escalation_level = 3;
+        assert!(plan(&graph, &chain(), Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn test_owner_step_resolves_downstream_milestone() {
+        let mut graph = WorkGraph::new();
+        let mut t = stuck_task("impl", 30);
+        t.escalation_level = 1;
+        graph.add_node(Node::Task(t));
+        graph.add_node(Node::Task(Task {
+            id: "review".to_string(),
+            after: vec!["impl".to_string()],
+            ..Task::default()
+        }));
+        graph.add_node(Node::Task(Task {
+            id: "v1".to_string(),
+            after: vec!["review".to_string()],
+            tags: vec![MILESTONE_TAG.to_string()],
+            assigned: Some("alice".to_string()),
+            ..Task::default()
+        }));
+
+        let actions = plan(&graph, &chain(), Utc::now());
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].target, EscalationTarget::Owner);
+        assert_eq!(actions[0].recipient.as_deref(), Some("alice"));
+        assert_eq!(actions[0].milestone.as_deref(), Some("v1"));
+    }
+
+    #[test]
+    fn test_run_sweep_records_step_and_resets_when_unstuck() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(stuck_task("stale", 5)));
+        crate::parser::save_graph(&graph, &path).unwrap();
+
+        let mut config = NotifyConfig::default();
+        config.escalation.chain = chain();
+
+        let fired = run_sweep(dir.path(), &path, &config, false).unwrap();
+        assert_eq!(fired.len(), 1);
+        let g = crate::parser::load_graph(&path).unwrap();
+        let task = g.get_task("stale").unwrap();
+        assert_eq!(task.escalation_level, 1);
+        assert!(
+            task.log
+                .last()
+                .unwrap()
+                .message
+                .starts_with("Escalation step 1/3 → assignee agent-1: notified")
+        );
+        assert_eq!(
+            crate::messages::list_messages(dir.path(), "stale")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Nothing further is due yet
+        assert!(
+            run_sweep(dir.path(), &path, &config, false)
+                .unwrap()
+                .is_empty()
+        );
+
+        modify_graph(&path, |g| {
+            g.get_task_mut("stale").unwrap().status = Status::Done;
+            true
+        })
+        .unwrap();
+        run_sweep(dir.path(), &path, &config, false).unwrap();
+        let g = crate::parser::load_graph(&path).unwrap();
+        assert_eq!(g.get_task("stale").unwrap().escalation_level, 0);
+    }
+
+    #[test]
+    fn test_blocked_clock_ignores_escalation_entries() {
+        let blocked_at = Utc::now() - Duration::hours(30);
+        let mut task = Task {
+            id: "t".to_string(),
+            status: Status::Blocked,
+            ..Task::default()
+        };
+        task.log.push(LogEntry {
+            timestamp: blocked_at.to_rfc3339(),
+            actor: None,
+            user: None,
+            message: "Blocked".to_string(),
+        });
+        task.log.push(LogEntry {
+            timestamp: Utc::now().to_rfc3339(),
+            actor: Some(ESCALATION_ACTOR.to_string()),
+            user: None,
+            message: "Escalation step 1/3".to_string(),
+        });
+        task.escalation_level = 1;
+        assert_eq!(
+            stuck_since(&task).unwrap().timestamp(),
+            blocked_at.timestamp()
+        );
+
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task));
+        let actions = plan(&graph, &chain(), Utc::now());
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].level, 2);
+    }
+
+    #[test]
+    fn test_later_notes_do_not_restart_blocked_clock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.jsonl");
+        let blocked_at = Utc::now() - Duration::hours(30);
+        let mut task = Task {
+            id: "t".to_string(),
+            status: Status::Blocked,
+            assigned: Some("agent-1".to_string()),
+            ..Task::default()
+        };
+        task.log.push(LogEntry {
+            timestamp: blocked_at.to_rfc3339(),
+            actor: None,
+            user: None,
+            message: "Blocked on review".to_string(),
+        });
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task));
+        crate::parser::save_graph(&graph, &path).unwrap();
+
+        // The sweep stamps when the task became blocked
+        run_sweep(dir.path(), &path, &NotifyConfig::default(), false).unwrap();
+        modify_graph(&path, |g| {
+            g.get_task_mut("t").unwrap().log.push(LogEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                actor: None,
+                user: Some("bob".to_string()),
+                message: "Still blocked, pinged the reviewer".to_string(),
+            });
+            true
+        })
+        .unwrap();
+        let g = crate::parser::load_graph(&path).unwrap();
+        let task = g.get_task("t").unwrap();
+        assert_eq!(
+            stuck_since(task).unwrap().timestamp(),
+            blocked_at.timestamp()
+        );
+        assert_eq!(plan(&g, &chain(), Utc::now()).len(), 1);
+
+        // Unblocking clears the stamp
+        modify_graph(&path, |g| {
+            g.get_task_mut("t").unwrap().status = Status::Open;
+            true
+        })
+        .unwrap();
+        run_sweep(dir.path(), &path, &NotifyConfig::default(), false).unwrap();
+        let g = crate::parser::load_graph(&path).unwrap();
+        assert_eq!(g.get_task("t").unwrap().blocked_at, None);
+    }
+
+    #[test]
+    fn test_unavailable_recipient_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(stuck_task("stale", 5)));
+        crate::parser::save_graph(&graph, &path).unwrap();
+        let mut calendar = AvailabilityCalendar::default();
+        calendar.add(
+            "agent-1",
+            Utc::now() - Duration::days(1),
+            Utc::now() + Duration::days(1),
+            Some("vacation"),
+        );
+        calendar.save(dir.path()).unwrap();
+
+        let mut config = NotifyConfig::default();
+        config.escalation.chain = chain();
+        run_sweep(dir.path(), &path, &config, false).unwrap();
+
+        let g = crate::parser::load_graph(&path).unwrap();
+        let task = g.get_task("stale").unwrap();
+        // The step still counts, so the next sweep moves on to the owner.
+        assert_eq!(task.escalation_level, 1);
+        assert!(
+            task.log
+                .last()
+                .unwrap()
+                .message
+                .contains("skipped, unavailable until")
+        );
+        assert!(
+            crate::messages::list_messages(dir.path(), "stale")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_chain_config_parses_from_toml() {
+        let config: NotifyConfig = toml::from_str(
+            r#"
+[[escalation.chain]]
+after = "4h"
+notify = "assignee"
+
+[[escalation.chain]]
+after = "2d"
+notify = "channel"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.escalation.chain.len(), 2);
+        assert_eq!(config.escalation.chain[1].notify, EscalationTarget::Channel);
+        assert_eq!(config.escalation.urgent_timeout, 3600);
+    }
+}
@@ -0,0 +1,166 @@
+//! External event gates: `waiting_on: webhook:<name>`.
+//!
+//! A task listing gates in `waiting_on` is not ready until each gate fires.
+//! Gates fire when the service's webhook listener receives
+//! `POST /hooks/<name>` (e.g. from CI or a forge), or locally via
+//! `wg event fire <name>`. Firing removes the gate from every task waiting
+//! on it, so a later event with the same name only affects tasks gated
+//! after it.
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::path::Path;
+
+use crate::graph::{LogEntry, WorkGraph};
+use crate::parser::modify_graph;
+
+/// Prefix of webhook gates in `waiting_on`.
+pub const WEBHOOK_PREFIX: &str = "webhook:";
+
+/// Header carrying `sha256=<hex hmac>` of the request body when
+/// `webhook_secret` is configured. Matches the header outbound
+/// notifications are signed with.
+pub const SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// Event names become URL path segments, so keep them to a safe alphabet.
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        bail!(
+            "Invalid event name '{}': use letters, digits, '-', '_' or '.'",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Parse a `waiting_on` entry. Only `webhook:<name>` is supported.
+pub fn parse_gate(gate: &str) -> Result<String> {
+    let gate = gate.trim();
+    let Some(name) = gate.strip_prefix(WEBHOOK_PREFIX) else {
+        bail!("Unknown gate '{}'. Expected webhook:<name>", gate);
+    };
+    validate_name(name)?;
+    Ok(gate.to_string())
+}
+
+/// Remove `webhook:<name>` from every task waiting on it, logging the event.
+/// Returns the IDs of the tasks that were waiting.
+pub fn fire(graph: &mut WorkGraph, name: &str, source: &str) -> Vec<String> {
+    let gate = format!("{}{}", WEBHOOK_PREFIX, name);
+    let now = Utc::now().to_rfc3339();
+    let mut released = Vec::new();
+    for task in graph.tasks_mut() {
+        let before = task.waiting_on.len();
+        task.waiting_on.retain(|g| *g != gate);
+        if task.waiting_on.len() == before {
+            continue;
+        }
+        let remaining = if task.waiting_on.is_empty() {
+            String::new()
+        } else {
+            format!(" (still waiting on {})", task.waiting_on.join(", "))
+        };
+        task.log.push(LogEntry {
+            timestamp: now.clone(),
+            actor: Some(source.to_string()),
+            user: Some(crate::current_user()),
+            message: format!("Event '{}' received{}", gate, remaining),
+        });
+        released.push(task.id.clone());
+    }
+    released
+}
+
+/// [`fire`] against the graph on disk. Saves only if some task was waiting.
+pub fn fire_at(graph_path: &Path, name: &str, source: &str) -> Result<Vec<String>> {
+    validate_name(name)?;
+    let mut released = Vec::new();
+    modify_graph(graph_path, |graph| {
+        released = fire(graph, name, source);
+        !released.is_empty()
+    })
+    .context("Failed to modify graph")?;
+    Ok(released)
+}
+
+/// The `sha256=<hex>` signature header value for a body.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Check a `sha256=<hex>` signature header against the body.
+pub fn verify_signature(secret: &str, body: &[u8], header: Option<&str>) -> bool {
+    let Some(hex_sig) = header.and_then(|h| h.trim().strip_prefix("sha256=")) else {
+        return false;
+    };
+    let Ok(sig) = hex::decode(hex_sig) else {
+        return false;
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    mac.verify_slice(&sig).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use crate::test_helpers::make_task;
+
+    #[test]
+    fn test_parse_gate() {
+        assert_eq!(
+            parse_gate(" webhook:ci-green ").unwrap(),
+            "webhook:ci-green"
+        );
+        assert!(parse_gate("webhook:").is_err());
+        assert!(parse_gate("webhook:pr/42").is_err());
+        assert!(parse_gate("timer:5m").is_err());
+    }
+
+    #[test]
+    fn test_fire_removes_gate_and_logs() {
+        let mut graph = WorkGraph::new();
+        let mut a = make_task("a", "A");
+        a.waiting_on = vec!["webhook:ci-green".into(), "webhook:pr-merged".into()];
+        let mut b = make_task("b", "B");
+        b.waiting_on = vec!["webhook:ci-green".into()];
+        graph.add_node(Node::Task(a));
+        graph.add_node(Node::Task(b));
+        graph.add_node(Node::Task(make_task("c", "C")));
+
+        let mut released = fire(&mut graph, "ci-green", "webhook");
+        released.sort();
+        assert_eq!(released, ["a", "b"]);
+        assert_eq!(
+            graph.get_task("a").unwrap().waiting_on,
+            ["webhook:pr-merged"]
+        );
+        assert!(graph.get_task("b").unwrap().waiting_on.is_empty());
+        assert_eq!(graph.get_task("b").unwrap().log.len(), 1);
+        assert!(fire(&mut graph, "ci-green", "webhook").is_empty());
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let body = br#"{"ref":"main"}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret").unwrap();
+        mac.update(body);
+        let header = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+        assert!(verify_signature("s3cret", body, Some(&header)));
+        assert!(!verify_signature("other", body, Some(&header)));
+        assert!(!verify_signature("s3cret", body, None));
+        assert!(!verify_signature("s3cret", body, Some("sha256=zz")));
+        assert_eq!(sign("s3cret", body), header);
+    }
+}
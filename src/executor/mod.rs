@@ -1,3 +1,4 @@
 //! Executor implementations for spawning agents.
 
 pub mod native;
+pub mod webhook;
@@ -2,7 +2,8 @@
 //!
 //! Supports multiple LLM providers through the `Provider` trait:
 //! - Anthropic Messages API (`client.rs`)
-//! - OpenAI-compatible APIs (`openai_client.rs`) — OpenRouter, OpenAI, Ollama, etc.
+//! - OpenAI-compatible APIs (`openai_client.rs`) — OpenRouter, OpenAI, vLLM, etc.
+//! - Ollama's native chat API (`ollama_client.rs`) — local models via `ollama:<model>`
 //!
 //! Use `provider::create_provider()` to route a model string to the right backend.
 //! Executes tools in-process. Eliminates external dependencies on
@@ -19,6 +20,7 @@ pub mod inbox;
 pub mod journal;
 pub mod l0_defense;
 pub mod mcp;
+pub mod ollama_client;
 pub mod openai_client;
 pub mod provider;
 pub mod resume;
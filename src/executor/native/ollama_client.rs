@@ -0,0 +1,500 @@
+//! Ollama native chat client (`POST /api/chat`).
+//!
+//! Used for `ollama:<model>` models and for endpoints configured with
+//! `provider = "ollama"`, so agents can run offline against a local
+//! `ollama serve`. The per-task `model` field picks the model as usual
+//! (`ollama:qwen3:32b` → model `qwen3:32b`).
+//!
+//! Ollama also serves an OpenAI-compatible API, but that one runs every
+//! request at the model's default context length (often 2–4k tokens) and
+//! silently truncates longer prompts. The native API takes the context
+//! length per request (`options.num_ctx`), and reports exact prompt and
+//! completion token counts, which become the response's [`Usage`].
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::client::{
+    ContentBlock, Message, MessagesRequest, MessagesResponse, Role, StopReason, ToolDefinition,
+    Usage,
+};
+use super::openai_client::{ApiError, is_retryable_status, max_retries_for_status};
+
+const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+const DEFAULT_MAX_TOKENS: u32 = 8192;
+/// Context length requested when neither the endpoint nor the model
+/// registry names one. Ollama allocates KV cache for the whole window, so
+/// this stays modest enough for a single consumer GPU.
+const DEFAULT_CONTEXT_WINDOW: usize = 32_768;
+
+// ── Ollama wire format types ────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+struct OllamaRequest {
+    model: String,
+    messages: Vec<OllamaMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<OllamaTool>,
+    stream: bool,
+    options: OllamaOptions,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OllamaOptions {
+    num_ctx: usize,
+    num_predict: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OllamaTool {
+    #[serde(rename = "type")]
+    tool_type: &'static str,
+    function: OllamaFunctionDef,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OllamaFunctionDef {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct OllamaMessage {
+    role: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    thinking: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tool_calls: Vec<OllamaToolCall>,
+    /// Name of the tool a `tool` message answers. Ollama has no call IDs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tool_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OllamaToolCall {
+    function: OllamaFunctionCall,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OllamaFunctionCall {
+    name: String,
+    /// A JSON object, not a string as in the OpenAI format.
+    #[serde(default)]
+    arguments: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OllamaResponse {
+    #[serde(default)]
+    created_at: String,
+    message: OllamaMessage,
+    #[serde(default)]
+    done_reason: Option<String>,
+    /// Prompt tokens evaluated (absent when the whole prompt was cached).
+    #[serde(default)]
+    prompt_eval_count: u32,
+    /// Tokens generated.
+    #[serde(default)]
+    eval_count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OllamaErrorResponse {
+    error: String,
+}
+
+// ── Client ──────────────────────────────────────────────────────────────
+
+/// Ollama native chat API client.
+#[derive(Debug)]
+pub struct OllamaClient {
+    http: reqwest::Client,
+    base_url: String,
+    pub model: String,
+    pub max_tokens: u32,
+    context_window_tokens: usize,
+}
+
+impl OllamaClient {
+    /// Create a client for `model`. `base_url` is the server root; a
+    /// trailing `/v1` (the OpenAI-compatible path, as stored by
+    /// `wg endpoints add`) is dropped. Defaults to `localhost:11434`.
+    pub fn new(model: &str, base_url: Option<&str>) -> Result<Self> {
+        // Local models can take minutes to load and answer on CPU.
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(900))
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self {
+            http,
+            base_url: native_base_url(base_url.unwrap_or(DEFAULT_BASE_URL)),
+            model: model.to_string(),
+            max_tokens: DEFAULT_MAX_TOKENS,
+            context_window_tokens: DEFAULT_CONTEXT_WINDOW,
+        })
+    }
+
+    /// Resolved server root, e.g. `http://localhost:11434`.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Override max tokens per response.
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    /// Override the context length sent as `num_ctx`. As with
+    /// `OpenAiClient`, `max_tokens` is capped to a quarter of the window so
+    /// output can't crowd out the prompt.
+    pub fn with_context_window(mut self, tokens: usize) -> Self {
+        self.context_window_tokens = tokens;
+        let cap = (tokens / 4) as u32;
+        if cap > 0 && self.max_tokens > cap {
+            self.max_tokens = cap;
+        }
+        self
+    }
+
+    fn translate_tools(tools: &[ToolDefinition]) -> Vec<OllamaTool> {
+        tools
+            .iter()
+            .map(|t| OllamaTool {
+                tool_type: "function",
+                function: OllamaFunctionDef {
+                    name: t.name.clone(),
+                    description: t.description.clone(),
+                    parameters: t.input_schema.clone(),
+                },
+            })
+            .collect()
+    }
+
+    /// Convert canonical messages to Ollama format. Tool results are
+    /// matched to their call by name, since Ollama has no call IDs.
+    fn translate_messages(system: &Option<String>, messages: &[Message]) -> Vec<OllamaMessage> {
+        let mut tool_names: HashMap<&str, &str> = HashMap::new();
+        let mut out = Vec::new();
+        if let Some(sys) = system {
+            out.push(OllamaMessage {
+                role: "system".to_string(),
+                content: sys.clone(),
+                ..Default::default()
+            });
+        }
+
+        for msg in messages {
+            match msg.role {
+                Role::User => {
+                    let mut text = Vec::new();
+                    for block in &msg.content {
+                        match block {
+                            ContentBlock::ToolResult {
+                                tool_use_id,
+                                content,
+                                ..
+                            } => out.push(OllamaMessage {
+                                role: "tool".to_string(),
+                                content: content.clone(),
+                                tool_name: tool_names
+                                    .get(tool_use_id.as_str())
+                                    .map(|n| n.to_string()),
+                                ..Default::default()
+                            }),
+                            ContentBlock::Text { text: t } => text.push(t.as_str()),
+                            _ => {}
+                        }
+                    }
+                    if !text.is_empty() {
+                        out.push(OllamaMessage {
+                            role: "user".to_string(),
+                            content: text.join("\n"),
+                            ..Default::default()
+                        });
+                    }
+                }
+                Role::Assistant => {
+                    let mut message = OllamaMessage {
+                        role: "assistant".to_string(),
+                        ..Default::default()
+                    };
+                    let mut text = Vec::new();
+                    for block in &msg.content {
+                        match block {
+                            ContentBlock::Text { text: t } => text.push(t.as_str()),
+                            ContentBlock::ToolUse { id, name, input } => {
+                                tool_names.insert(id.as_str(), name.as_str());
+                                message.tool_calls.push(OllamaToolCall {
+                                    function: OllamaFunctionCall {
+                                        name: name.clone(),
+                                        arguments: input.clone(),
+                                    },
+                                });
+                            }
+                            _ => {}
+                        }
+                    }
+                    message.content = text.join("\n");
+                    out.push(message);
+                }
+            }
+        }
+        out
+    }
+
+    /// Convert an Ollama response to canonical format. Tool calls get
+    /// synthetic IDs so results can be routed back through the agent loop.
+    fn translate_response(resp: OllamaResponse) -> MessagesResponse {
+        let mut content = Vec::new();
+        if let Some(thinking) = resp.message.thinking.filter(|t| !t.is_empty()) {
+            content.push(ContentBlock::Thinking {
+                thinking,
+                reasoning_details: None,
+            });
+        }
+        if !resp.message.content.is_empty() {
+            content.push(ContentBlock::Text {
+                text: resp.message.content,
+            });
+        }
+        let has_tool_calls = !resp.message.tool_calls.is_empty();
+        for (i, call) in resp.message.tool_calls.into_iter().enumerate() {
+            let input = match call.function.arguments {
+                serde_json::Value::Null => serde_json::json!({}),
+                args => args,
+            };
+            content.push(ContentBlock::ToolUse {
+                id: format!("ollama-{}-{}", resp.created_at, i),
+                name: call.function.name,
+                input,
+            });
+        }
+        if content.is_empty() {
+            content.push(ContentBlock::Text {
+                text: String::new(),
+            });
+        }
+
+        let stop_reason = if has_tool_calls {
+            Some(StopReason::ToolUse)
+        } else {
+            match resp.done_reason.as_deref() {
+                Some("length") => Some(StopReason::MaxTokens),
+                _ => Some(StopReason::EndTurn),
+            }
+        };
+
+        MessagesResponse {
+            id: format!("ollama-{}", resp.created_at),
+            content,
+            stop_reason,
+            usage: Usage {
+                input_tokens: resp.prompt_eval_count,
+                output_tokens: resp.eval_count,
+                ..Usage::default()
+            },
+        }
+    }
+
+    async fn chat(&self, request: &MessagesRequest) -> Result<MessagesResponse> {
+        let body = OllamaRequest {
+            model: request.model.clone(),
+            messages: Self::translate_messages(&request.system, &request.messages),
+            tools: Self::translate_tools(&request.tools),
+            stream: false,
+            options: OllamaOptions {
+                num_ctx: self.context_window_tokens,
+                num_predict: request.max_tokens,
+            },
+        };
+        let url = format!("{}/api/chat", self.base_url);
+
+        let mut retry_count = 0;
+        let mut backoff_ms = 1000u64;
+        loop {
+            let response = self
+                .http
+                .post(&url)
+                .json(&body)
+                .send()
+                .await
+                .with_context(|| {
+                    format!(
+                        "Could not reach Ollama at {} (is `ollama serve` running?)",
+                        self.base_url
+                    )
+                })?;
+            let status = response.status();
+            let text = response
+                .text()
+                .await
+                .context("Failed to read Ollama response body")?;
+            if status.is_success() {
+                let resp: OllamaResponse = serde_json::from_str(&text).with_context(|| {
+                    format!(
+                        "Failed to parse Ollama response: {}",
+                        text.chars().take(500).collect::<String>()
+                    )
+                })?;
+                return Ok(Self::translate_response(resp));
+            }
+            let status = status.as_u16();
+            if is_retryable_status(status) && retry_count < max_retries_for_status(status) {
+                retry_count += 1;
+                eprintln!(
+                    "[ollama-client] Retryable error {} (attempt {}), waiting {}ms",
+                    status, retry_count, backoff_ms
+                );
+                tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                backoff_ms = (backoff_ms * 2).min(60_000);
+                continue;
+            }
+            return Err(ollama_api_error(status, &text, &request.model));
+        }
+    }
+}
+
+/// Strip the OpenAI-compatible `/v1` suffix: the native API lives at the
+/// server root.
+fn native_base_url(url: &str) -> String {
+    let trimmed = url.trim_end_matches('/');
+    trimmed
+        .strip_suffix("/v1")
+        .unwrap_or(trimmed)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn ollama_api_error(status: u16, body: &str, model: &str) -> anyhow::Error {
+    let mut message = serde_json::from_str::<OllamaErrorResponse>(body)
+        .map(|e| e.error)
+        .unwrap_or_else(|_| body.chars().take(500).collect());
+    if status == 404 && message.contains("not found") {
+        message.push_str(&format!(" (run `ollama pull {}`)", model));
+    }
+    ApiError { status, message }.into()
+}
+
+#[async_trait::async_trait]
+impl super::provider::Provider for OllamaClient {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn max_tokens(&self) -> u32 {
+        self.max_tokens
+    }
+
+    fn context_window(&self) -> usize {
+        self.context_window_tokens
+    }
+
+    async fn send(&self, request: &MessagesRequest) -> Result<MessagesResponse> {
+        self.chat(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_base_url_drops_openai_suffix() {
+        assert_eq!(
+            native_base_url("http://localhost:11434/v1"),
+            "http://localhost:11434"
+        );
+        assert_eq!(native_base_url("http://gpu:11434/"), "http://gpu:11434");
+        let client = OllamaClient::new("llama3", None)
+            .unwrap()
+            .with_context_window(8192);
+        assert_eq!(client.base_url(), DEFAULT_BASE_URL);
+        assert_eq!(client.max_tokens, 2048);
+    }
+
+    #[test]
+    fn test_translate_messages_routes_tool_results_by_name() {
+        let messages = vec![
+            Message {
+                role: Role::User,
+                content: vec![ContentBlock::Text {
+                    text: "list files".into(),
+                }],
+            },
+            Message {
+                role: Role::Assistant,
+                content: vec![ContentBlock::ToolUse {
+                    id: "call-1".into(),
+                    name: "bash".into(),
+                    input: serde_json::json!({"command": "ls"}),
+                }],
+            },
+            Message {
+                role: Role::User,
+                content: vec![ContentBlock::ToolResult {
+                    tool_use_id: "call-1".into(),
+                    content: "Cargo.toml".into(),
+                    is_error: false,
+                }],
+            },
+        ];
+        let out = OllamaClient::translate_messages(&Some("be brief".into()), &messages);
+        let roles: Vec<&str> = out.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["system", "user", "assistant", "tool"]);
+        assert_eq!(
+            out[2].tool_calls[0].function.arguments,
+            serde_json::json!({"command": "ls"})
+        );
+        assert_eq!(out[3].tool_name.as_deref(), Some("bash"));
+        assert_eq!(out[3].content, "Cargo.toml");
+    }
+
+    #[test]
+    fn test_translate_response_tool_calls_and_usage() {
+        let resp: OllamaResponse = serde_json::from_str(
+            r#"{"model":"qwen3","created_at":"2026-10-15T10:00:00Z","done":true,
+                "done_reason":"stop","prompt_eval_count":120,"eval_count":30,
+                "message":{"role":"assistant","content":"","thinking":"look first",
+                "tool_calls":[{"function":{"name":"bash","arguments":{"command":"ls"}}}]}}"#,
+        )
+        .unwrap();
+        let out = OllamaClient::translate_response(resp);
+        assert_eq!(out.stop_reason, Some(StopReason::ToolUse));
+        assert_eq!(out.usage.input_tokens, 120);
+        assert_eq!(out.usage.output_tokens, 30);
+        assert!(matches!(out.content[0], ContentBlock::Thinking { .. }));
+        match &out.content[1] {
+            ContentBlock::ToolUse { name, input, .. } => {
+                assert_eq!(name, "bash");
+                assert_eq!(input["command"], "ls");
+            }
+            other => panic!("expected tool use, got {:?}", other),
+        }
+
+        let resp: OllamaResponse = serde_json::from_str(
+            r#"{"created_at":"t","done_reason":"length","message":{"role":"assistant","content":"cut"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            OllamaClient::translate_response(resp).stop_reason,
+            Some(StopReason::MaxTokens)
+        );
+    }
+
+    #[test]
+    fn test_missing_model_error_suggests_pull() {
+        let err = ollama_api_error(404, r#"{"error":"model \"qwen3\" not found"}"#, "qwen3");
+        assert!(err.to_string().contains("ollama pull qwen3"));
+    }
+}
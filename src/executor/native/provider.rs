@@ -8,12 +8,14 @@
 //! Use `create_provider()` to route a model string to the appropriate backend:
 //! - Bare name (`claude-sonnet-4-6`) → Anthropic native API
 //! - Prefixed (`openai/gpt-4o`, `deepseek/deepseek-chat`) → OpenAI-compatible
+//! - `ollama:<model>` → Ollama native API
 
 use std::path::Path;
 
 use anyhow::{Context, Result};
 
 use super::client::{AnthropicClient, MessagesRequest, MessagesResponse};
+use super::ollama_client::OllamaClient;
 use super::openai_client::OpenAiClient;
 
 /// Provider-agnostic LLM client trait.
@@ -384,7 +386,28 @@ pub fn create_provider_ext(
         .and_then(|v| v.as_integer())
         .map(|v| v as u32);
 
+    // `ollama:` models and `provider = "ollama"` endpoints talk to Ollama's
+    // native API instead of its OpenAI-compatible one — see `ollama_client`.
+    let is_ollama = original_prefix.as_deref() == Some("ollama")
+        || endpoint.is_some_and(|ep| ep.provider == "ollama");
+
     match provider_name.as_str() {
+        "local" if is_ollama => {
+            let mut client = OllamaClient::new(model, api_base.as_deref())
+                .context("Failed to initialize Ollama client")?;
+            if let Some(mt) = max_tokens {
+                client = client.with_max_tokens(mt);
+            }
+            if let Some(cw) = resolved_context_window {
+                client = client.with_context_window(cw as usize);
+            }
+            log::debug!(
+                "[native-exec] Using Ollama provider ({} at {})",
+                client.model,
+                client.base_url()
+            );
+            Ok(Box::new(client))
+        }
         "oai-compat" | "openai" | "openrouter" | "local" => {
             // Resolve API key from CONFIG ONLY:
             //   override (e.g. `wg nex --api-key`) > endpoint's config-side fields
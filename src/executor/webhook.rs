@@ -0,0 +1,333 @@
+//! Webhook executor: hand a task to an external HTTP service.
+//!
+//! `wg webhook-exec` (spawned by the dispatcher like any other executor)
+//! POSTs a JSON description of the task, including the rendered prompt, to
+//! `[webhook_executor].url`. The task stays in progress until the service
+//! reports a result, either by calling the dispatcher's webhook listener at
+//! `POST /callbacks/<task-id>` (the payload's `callback_url`) or through a
+//! status URL that `wg webhook-exec` polls.
+//!
+//! Reports, whether in a callback, a poll response, or the POST response
+//! itself, share one shape:
+//!
+//! ```json
+//! {"status": "done", "message": "Published", "artifacts": ["https://..."]}
+//! {"status": "failed", "reason": "Upstream rejected the record"}
+//! {"status": "running", "status_url": "https://.../jobs/42"}
+//! ```
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::{Config, WebhookExecutorConfig};
+use crate::event_gate;
+use crate::graph::Task;
+
+/// Callback reports waiting to be picked up, under `.wg/service/`.
+const CALLBACKS_DIR: &str = "webhook-callbacks";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest response body echoed back in an error message.
+const MAX_ERROR_BODY: usize = 500;
+
+/// Where the external service says the task stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReportStatus {
+    #[serde(alias = "pending", alias = "accepted", alias = "in-progress")]
+    Running,
+    #[serde(alias = "success", alias = "succeeded", alias = "completed")]
+    Done,
+    #[serde(alias = "error", alias = "failure")]
+    Failed,
+}
+
+/// A status report from the external service.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub status: ReportStatus,
+    /// Why the task failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Summary appended to the task log
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Paths or URLs registered as task artifacts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<String>,
+    /// Where to poll for status (read from the POST response)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_url: Option<String>,
+}
+
+impl Report {
+    pub fn is_final(&self) -> bool {
+        self.status != ReportStatus::Running
+    }
+}
+
+/// The JSON body POSTed to the configured URL.
+pub fn payload(task: &Task, prompt: &str, callback_url: Option<&str>) -> serde_json::Value {
+    json!({
+        "task_id": task.id,
+        "title": task.title,
+        "description": task.description,
+        "tags": task.tags,
+        "skills": task.skills,
+        "inputs": task.inputs,
+        "deliverables": task.deliverables,
+        "after": task.after,
+        "prompt": prompt,
+        "callback_url": callback_url,
+    })
+}
+
+/// Where the service should POST its report: `callback_url` from the
+/// config, else the dispatcher's webhook listener. `None` when neither is
+/// set, in which case only polling can complete the task.
+pub fn callback_url(config: &Config, task_id: &str) -> Option<String> {
+    let base = config.webhook_executor.callback_url.clone().or_else(|| {
+        config
+            .coordinator
+            .webhook_listen
+            .as_ref()
+            .map(|addr| format!("http://{}", addr))
+    })?;
+    Some(format!(
+        "{}/callbacks/{}",
+        base.trim_end_matches('/'),
+        task_id
+    ))
+}
+
+/// Status URL to poll: the `status_url` from the POST response, else
+/// `poll_url` from the config with `{{task_id}}` substituted.
+pub fn poll_url(
+    config: &WebhookExecutorConfig,
+    accepted: Option<&Report>,
+    task_id: &str,
+) -> Option<String> {
+    accepted.and_then(|r| r.status_url.clone()).or_else(|| {
+        config
+            .poll_url
+            .as_ref()
+            .map(|url| url.replace("{{task_id}}", task_id))
+    })
+}
+
+/// Replace `${VAR}` references with values from the environment. Unset
+/// variables expand to the empty string.
+pub fn expand_env(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        out.push_str(&std::env::var(&rest[start + 2..start + 2 + len]).unwrap_or_default());
+        rest = &rest[start + 3 + len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+pub fn callback_path(workgraph_dir: &Path, task_id: &str) -> PathBuf {
+    workgraph_dir
+        .join("service")
+        .join(CALLBACKS_DIR)
+        .join(format!("{}.json", task_id))
+}
+
+/// Parse a callback body and store it for `wg webhook-exec` to pick up.
+pub fn record_callback(workgraph_dir: &Path, task_id: &str, body: &[u8]) -> Result<Report> {
+    let report: Report = serde_json::from_slice(body).context("Invalid report")?;
+    let path = callback_path(workgraph_dir, task_id);
+    let dir = path.parent().expect("callback path has a parent");
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(&report)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(report)
+}
+
+/// Remove and return the stored callback report for a task, if any.
+pub fn take_callback(workgraph_dir: &Path, task_id: &str) -> Result<Option<Report>> {
+    let path = callback_path(workgraph_dir, task_id);
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let _ = std::fs::remove_file(&path);
+    let report = serde_json::from_slice(&bytes)
+        .with_context(|| format!("Invalid report in {}", path.display()))?;
+    Ok(Some(report))
+}
+
+/// HTTP client for the configured service.
+pub struct Client {
+    config: WebhookExecutorConfig,
+    http: reqwest::blocking::Client,
+}
+
+impl Client {
+    pub fn new(config: &WebhookExecutorConfig) -> Result<Self> {
+        let http = reqwest::blocking::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self {
+            config: config.clone(),
+            http,
+        })
+    }
+
+    /// POST the payload. Returns the report in the response body, if the
+    /// service sent one (an empty or non-report body means "accepted").
+    pub fn submit(&self, payload: &serde_json::Value) -> Result<Option<Report>> {
+        let Some(url) = self.config.url.as_deref() else {
+            bail!("[webhook_executor] url is not set");
+        };
+        let body = serde_json::to_vec(payload)?;
+        let mut request = self
+            .with_headers(self.http.post(url))
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.config.secret {
+            request = request.header(
+                event_gate::SIGNATURE_HEADER,
+                event_gate::sign(&expand_env(secret), &body),
+            );
+        }
+        let text = send(request.body(body), url)?;
+        Ok(serde_json::from_str(&text).ok())
+    }
+
+    /// GET a status URL and parse the report.
+    pub fn poll(&self, url: &str) -> Result<Report> {
+        let text = send(self.with_headers(self.http.get(url)), url)?;
+        serde_json::from_str(&text).with_context(|| format!("Invalid report from {}", url))
+    }
+
+    fn with_headers(
+        &self,
+        mut request: reqwest::blocking::RequestBuilder,
+    ) -> reqwest::blocking::RequestBuilder {
+        for (name, value) in &self.config.headers {
+            request = request.header(name, expand_env(value));
+        }
+        request
+    }
+}
+
+fn send(request: reqwest::blocking::RequestBuilder, url: &str) -> Result<String> {
+    let response = request
+        .send()
+        .with_context(|| format!("Request to {} failed", url))?;
+    let status = response.status();
+    let text = response.text().unwrap_or_default();
+    if !status.is_success() {
+        let mut snippet = text.trim().to_string();
+        if snippet.len() > MAX_ERROR_BODY {
+            snippet.truncate(snippet.floor_char_boundary(MAX_ERROR_BODY));
+            snippet.push_str("...");
+        }
+        bail!("{} returned {}: {}", url, status, snippet);
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::make_task;
+
+    #[test]
+    fn test_report_accepts_common_status_spellings() {
+        let report: Report =
+            serde_json::from_str(r#"{"status":"completed","artifacts":["out.csv"]}"#).unwrap();
+        assert_eq!(report.status, ReportStatus::Done);
+        assert_eq!(report.artifacts, vec!["out.csv"]);
+        assert!(report.is_final());
+
+        let report: Report =
+            serde_json::from_str(r#"{"status":"pending","status_url":"http://x/1"}"#).unwrap();
+        assert!(!report.is_final());
+        assert!(serde_json::from_str::<Report>(r#"{"status":"maybe"}"#).is_err());
+    }
+
+    #[test]
+    fn test_callback_and_poll_urls() {
+        let mut config = Config::default();
+        assert_eq!(callback_url(&config, "t1"), None);
+        config.coordinator.webhook_listen = Some("127.0.0.1:8787".to_string());
+        assert_eq!(
+            callback_url(&config, "t1").as_deref(),
+            Some("http://127.0.0.1:8787/callbacks/t1")
+        );
+        config.webhook_executor.callback_url = Some("https://wg.example.com/".to_string());
+        assert_eq!(
+            callback_url(&config, "t1").as_deref(),
+            Some("https://wg.example.com/callbacks/t1")
+        );
+
+        let mut executor = WebhookExecutorConfig::default();
+        assert_eq!(poll_url(&executor, None, "t1"), None);
+        executor.poll_url = Some("https://svc/jobs/{{task_id}}".to_string());
+        assert_eq!(
+            poll_url(&executor, None, "t1").as_deref(),
+            Some("https://svc/jobs/t1")
+        );
+        let accepted = Report {
+            status: ReportStatus::Running,
+            reason: None,
+            message: None,
+            artifacts: vec![],
+            status_url: Some("https://svc/status/42".to_string()),
+        };
+        assert_eq!(
+            poll_url(&executor, Some(&accepted), "t1").as_deref(),
+            Some("https://svc/status/42")
+        );
+    }
+
+    #[test]
+    fn test_expand_env() {
+        // SAFETY: test-only variable name, not read elsewhere.
+        unsafe { std::env::set_var("WG_TEST_WEBHOOK_TOKEN", "abc") };
+        assert_eq!(expand_env("Bearer ${WG_TEST_WEBHOOK_TOKEN}"), "Bearer abc");
+        assert_eq!(expand_env("${WG_TEST_WEBHOOK_UNSET_XYZ}-x"), "-x");
+        assert_eq!(expand_env("no ${vars"), "no ${vars");
+    }
+
+    #[test]
+    fn test_record_and_take_callback() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        assert!(take_callback(tmp.path(), "t1").unwrap().is_none());
+        assert!(record_callback(tmp.path(), "t1", b"not json").is_err());
+
+        let report =
+            record_callback(tmp.path(), "t1", br#"{"status":"failed","reason":"nope"}"#).unwrap();
+        assert_eq!(report.status, ReportStatus::Failed);
+        assert_eq!(take_callback(tmp.path(), "t1").unwrap(), Some(report));
+        // Taken reports are consumed.
+        assert!(take_callback(tmp.path(), "t1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_payload_carries_task_and_prompt() {
+        let mut task = make_task("t1", "Publish report");
+        task.tags = vec!["zapier".to_string()];
+        let body = payload(&task, "Do the thing", Some("http://wg/callbacks/t1"));
+        assert_eq!(body["task_id"], "t1");
+        assert_eq!(body["title"], "Publish report");
+        assert_eq!(body["tags"][0], "zapier");
+        assert_eq!(body["prompt"], "Do the thing");
+        assert_eq!(body["callback_url"], "http://wg/callbacks/t1");
+    }
+}
@@ -102,7 +102,7 @@ fn which_probes(candidates: &[&str]) -> Option<PathBuf> {
 
 /// Minimal which(1): split $PATH on `:` and return the first
 /// executable file named `cmd`. Skips empty PATH entries.
-fn which_on_path(cmd: &str) -> Option<PathBuf> {
+pub(crate) fn which_on_path(cmd: &str) -> Option<PathBuf> {
     let path_var = std::env::var_os("PATH")?;
     for dir in std::env::split_paths(&path_var) {
         if dir.as_os_str().is_empty() {
@@ -0,0 +1,323 @@
+//! Tasks blocked on a team or vendor outside the project.
+//!
+//! `wg edit --external-blocker` records an [`ExternalBlocker`] on a task:
+//! what it waits for, who to chase, and when it is expected to clear. Nobody
+//! on the project can move such a task forward, so it is not dispatched and
+//! is left out of `wg bottlenecks`, `wg aging` and the escalation chain;
+//! `wg waiting` lists it instead.
+//!
+//! The service runs [`run_sweep`] after each coordinator tick. The first
+//! follow-up reminder goes out when the expected date passes (or one
+//! follow-up interval after the blocker was recorded, if no date was given)
+//! and repeats every `escalation.external_follow_up` seconds of
+//! `notify.toml` while the blocker stays. Reminders use the urgent route,
+//! like deadline alerts, and are written to the task log.
+
+use crate::graph::{ExternalBlocker, LogEntry, Status, Task, WorkGraph};
+use crate::notify::config::{EscalationConfig, NotifyConfig};
+use crate::parser::modify_graph;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::path::Path;
+
+/// A task waiting on an external blocker, as listed by `wg waiting`.
+#[derive(Debug, Clone, Serialize)]
+pub struct WaitingTask {
+    pub task_id: String,
+    pub title: String,
+    pub status: Status,
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    pub since: String,
+    /// Seconds since the blocker was recorded.
+    pub waiting_secs: i64,
+    /// Seconds past the expected date (negative while time is left).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub late_secs: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_reminded_at: Option<String>,
+    /// When the next follow-up reminder is due.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_reminder: Option<String>,
+}
+
+impl WaitingTask {
+    /// Text sent to the notification channel.
+    pub fn message(&self) -> String {
+        let mut msg = format!(
+            "Follow up on external blocker: task '{}' ({}) has been waiting {} on: {}",
+            self.task_id,
+            self.title,
+            crate::format_duration(self.waiting_secs, false),
+            self.reason,
+        );
+        if let Some(ref contact) = self.contact {
+            msg.push_str(&format!(" — contact {}", contact));
+        }
+        if let (Some(expected), Some(late)) = (&self.expected, self.late_secs)
+            && late > 0
+        {
+            msg.push_str(&format!(
+                " (expected {}, {} late)",
+                expected,
+                crate::format_duration(late, false)
+            ));
+        }
+        msg
+    }
+}
+
+/// Parse an RFC 3339 timestamp field.
+fn parse_ts(ts: Option<&str>) -> Option<DateTime<Utc>> {
+    ts?.parse::<DateTime<Utc>>().ok()
+}
+
+/// Seconds between follow-up reminders under `config`.
+pub fn follow_up_secs(config: Option<&NotifyConfig>) -> u64 {
+    config.map_or_else(
+        || EscalationConfig::default().external_follow_up,
+        |c| c.escalation.external_follow_up,
+    )
+}
+
+/// When the next follow-up reminder for `blocker` is due. `None` when no
+/// further reminder will be sent (`follow_up_secs` of 0 after the one on
+/// the expected date).
+pub fn next_reminder(blocker: &ExternalBlocker, follow_up_secs: u64) -> Option<DateTime<Utc>> {
+    let interval = Duration::seconds(follow_up_secs as i64);
+    if let Some(last) = parse_ts(blocker.last_reminded_at.as_deref()) {
+        return (follow_up_secs > 0).then(|| last + interval);
+    }
+    if let Some(expected) = parse_ts(blocker.expected.as_deref()) {
+        return Some(expected);
+    }
+    let since = parse_ts(Some(&blocker.since))?;
+    (follow_up_secs > 0).then(|| since + interval)
+}
+
+fn waiting_task(
+    task: &Task,
+    blocker: &ExternalBlocker,
+    now: DateTime<Utc>,
+    follow_up_secs: u64,
+) -> WaitingTask {
+    let since = parse_ts(Some(&blocker.since));
+    WaitingTask {
+        task_id: task.id.clone(),
+        title: task.title.clone(),
+        status: task.status,
+        reason: blocker.reason.clone(),
+        contact: blocker.contact.clone(),
+        expected: blocker.expected.clone(),
+        since: blocker.since.clone(),
+        waiting_secs: since.map_or(0, |s| (now - s).num_seconds()),
+        late_secs: parse_ts(blocker.expected.as_deref()).map(|e| (now - e).num_seconds()),
+        last_reminded_at: blocker.last_reminded_at.clone(),
+        next_reminder: next_reminder(blocker, follow_up_secs).map(|t| t.to_rfc3339()),
+    }
+}
+
+/// Unfinished tasks with an external blocker, longest waiting first.
+pub fn waiting(graph: &WorkGraph, now: DateTime<Utc>, follow_up_secs: u64) -> Vec<WaitingTask> {
+    let mut tasks: Vec<WaitingTask> = graph
+        .tasks()
+        .filter(|t| !t.status.is_terminal())
+        .filter_map(|t| {
+            let blocker = t.external_blocker.as_ref()?;
+            Some(waiting_task(t, blocker, now, follow_up_secs))
+        })
+        .collect();
+    tasks.sort_by(|a, b| {
+        b.waiting_secs
+            .cmp(&a.waiting_secs)
+            .then_with(|| a.task_id.cmp(&b.task_id))
+    });
+    tasks
+}
+
+/// Run one follow-up sweep over the graph.
+///
+/// Sends a reminder for every blocker whose next follow-up is due, logs it
+/// and records the time on the blocker. With `dry_run`, only reports the
+/// reminders that would be sent. Returns the reminders sent (or due).
+pub fn run_sweep(
+    dir: &Path,
+    graph_path: &Path,
+    config: Option<&NotifyConfig>,
+    dry_run: bool,
+) -> Result<Vec<WaitingTask>> {
+    let follow_up_secs = follow_up_secs(config);
+    let graph = crate::parser::load_graph(graph_path)?;
+    let now = Utc::now();
+    let due: Vec<WaitingTask> = waiting(&graph, now, follow_up_secs)
+        .into_iter()
+        .filter(|w| parse_ts(w.next_reminder.as_deref()).is_some_and(|at| at <= now))
+        .collect();
+    if dry_run || due.is_empty() {
+        return Ok(due);
+    }
+
+    let outcomes: Vec<String> = due
+        .iter()
+        .map(|w| crate::deadline::deliver(dir, config, &w.task_id, &w.message()))
+        .collect();
+
+    modify_graph(graph_path, |graph| {
+        let mut modified = false;
+        for (reminder, outcome) in due.iter().zip(&outcomes) {
+            let Some(task) = graph.get_task_mut(&reminder.task_id) else {
+                continue;
+            };
+            if task.status.is_terminal() {
+                continue;
+            }
+            // Another writer may have cleared or replaced the blocker.
+            let Some(ref mut blocker) = task.external_blocker else {
+                continue;
+            };
+            if blocker.since != reminder.since {
+                continue;
+            }
+            blocker.last_reminded_at = Some(now.to_rfc3339());
+            task.log.push(LogEntry {
+                timestamp: now.to_rfc3339(),
+                actor: Some("external-blocker".to_string()),
+                user: Some(crate::current_user()),
+                message: format!("Follow-up reminder for external blocker: {}", outcome),
+            });
+            modified = true;
+        }
+        modified
+    })?;
+
+    Ok(due)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+
+    fn blocked(id: &str, since: DateTime<Utc>, expected: Option<DateTime<Utc>>) -> Task {
+        Task {
+            id: id.to_string(),
+            title: id.to_string(),
+            external_blocker: Some(ExternalBlocker {
+                reason: "Payments team to ship the v2 API".to_string(),
+                contact: Some("payments@example.com".to_string()),
+                expected: expected.map(|e| e.to_rfc3339()),
+                since: since.to_rfc3339(),
+                last_reminded_at: None,
+            }),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn test_next_reminder() {
+        let since = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut blocker = blocked("a", since, None).external_blocker.unwrap();
+        assert_eq!(
+            next_reminder(&blocker, 86400),
+            Some(since + Duration::days(1))
+        );
+        assert_eq!(next_reminder(&blocker, 0), None);
+
+        let expected = since + Duration::days(10);
+        blocker.expected = Some(expected.to_rfc3339());
+        assert_eq!(next_reminder(&blocker, 86400), Some(expected));
+        assert_eq!(next_reminder(&blocker, 0), Some(expected));
+
+        blocker.last_reminded_at = Some(expected.to_rfc3339());
+        assert_eq!(
+            next_reminder(&blocker, 86400),
+            Some(expected + Duration::days(1))
+        );
+        assert_eq!(next_reminder(&blocker, 0), None);
+    }
+
+    #[test]
+    fn test_waiting_skips_finished_and_sorts_longest_first() {
+        let now = Utc::now();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(blocked("recent", now - Duration::days(1), None)));
+        graph.add_node(Node::Task(blocked(
+            "old",
+            now - Duration::days(9),
+            Some(now - Duration::days(2)),
+        )));
+        let mut done = blocked("shipped", now - Duration::days(20), None);
+        done.status = Status::Done;
+        graph.add_node(Node::Task(done));
+        graph.add_node(Node::Task(Task {
+            id: "free".to_string(),
+            ..Task::default()
+        }));
+
+        let list = waiting(&graph, now, 86400);
+        let ids: Vec<&str> = list.iter().map(|w| w.task_id.as_str()).collect();
+        assert_eq!(ids, vec!["old", "recent"]);
+        assert_eq!(list[0].late_secs, Some(2 * 86400));
+        assert!(list[0].message().contains("contact payments@example.com"));
+    }
+
+    #[test]
+    fn test_run_sweep_reminds_once_per_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.jsonl");
+        let now = Utc::now();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(blocked(
+            "late",
+            now - Duration::days(5),
+            Some(now - Duration::hours(1)),
+        )));
+        graph.add_node(Node::Task(blocked(
+            "on-track",
+            now - Duration::days(5),
+            Some(now + Duration::days(1)),
+        )));
+        crate::parser::save_graph(&graph, &path).unwrap();
+
+        let due = run_sweep(dir.path(), &path, None, true).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].task_id, "late");
+        let g = crate::parser::load_graph(&path).unwrap();
+        let blocker = g
+            .get_task("late")
+            .unwrap()
+            .external_blocker
+            .clone()
+            .unwrap();
+        assert_eq!(blocker.last_reminded_at, None);
+
+        assert_eq!(run_sweep(dir.path(), &path, None, false).unwrap().len(), 1);
+        let g = crate::parser::load_graph(&path).unwrap();
+        let task = g.get_task("late").unwrap();
+        assert!(
+            task.external_blocker
+                .as_ref()
+                .unwrap()
+                .last_reminded_at
+                .is_some()
+        );
+        assert!(
+            task.log
+                .last()
+                .unwrap()
+                .message
+                .starts_with("Follow-up reminder")
+        );
+
+        // Next reminder is a follow-up interval away
+        assert!(
+            run_sweep(dir.path(), &path, None, false)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -296,11 +296,22 @@ pub struct ResolvedPeer {
 ///
 /// Resolution order (per §2.3 of cross-repo design doc):
 /// 1. Named peer in federation.yaml → look up `path`
-/// 2. Absolute path or `~/` → filesystem path
-/// 3. Relative path → resolve from CWD
+/// 2. Named graph in the same workspace (see `graphs`) → its directory
+/// 3. Absolute path or `~/` → filesystem path
+/// 4. Relative path → resolve from CWD
 pub fn resolve_peer(reference: &str, workgraph_dir: &Path) -> Result<ResolvedPeer, anyhow::Error> {
     let config = load_federation_config(workgraph_dir)?;
 
+    if !config.peers.contains_key(reference)
+        && let Some(graph_dir) = crate::graphs::resolve(workgraph_dir, reference)
+    {
+        let root = crate::graphs::workspace_root(&graph_dir).unwrap_or_else(|| graph_dir.clone());
+        return Ok(ResolvedPeer {
+            project_path: root.parent().map(Path::to_path_buf).unwrap_or(root),
+            workgraph_dir: graph_dir,
+        });
+    }
+
     // Check named peers first
     let raw_path = if let Some(peer) = config.peers.get(reference) {
         peer.path.clone()
@@ -1192,6 +1203,7 @@ fn merge_role(target: &Role, source: &Role) -> Role {
         lineage: merge_lineage(&target.lineage, &source.lineage),
         default_context_scope: target.default_context_scope.clone(),
         default_exec_mode: target.default_exec_mode.clone(),
+        tool_policy: target.tool_policy.clone(),
     }
 }
 
@@ -1245,6 +1257,11 @@ fn merge_agent(target: &Agent, source: &Agent) -> Agent {
             .preferred_provider
             .clone()
             .or_else(|| source.preferred_provider.clone()),
+        capability_doc: target
+            .capability_doc
+            .clone()
+            .or_else(|| source.capability_doc.clone()),
+        daily_budget: target.daily_budget.or(source.daily_budget),
     }
 }
 
@@ -1296,6 +1313,7 @@ mod tests {
             lineage: Lineage::default(),
             default_context_scope: None,
             default_exec_mode: None,
+            tool_policy: Default::default(),
         }
     }
 
@@ -1341,6 +1359,8 @@ mod tests {
             deployment_history: Vec::new(),
             attractor_weight: 0.5,
             staleness_flags: Vec::new(),
+            capability_doc: None,
+            daily_budget: None,
         }
     }
 
@@ -0,0 +1,307 @@
+//! Personal work queues (`wg focus`).
+//!
+//! A focus queue is the short, ordered list of tasks one person or agent
+//! should pick up next, so they can work without reading the whole graph.
+//! Candidates are ready, non-system tasks that are unassigned (or already
+//! assigned to them) and whose required skills they cover. Candidates are
+//! ordered by aged priority, then by how much unfinished work waits
+//! downstream of them (critical-path tail), then by deadline.
+//!
+//! The computed queue is pinned in `.wg/focus/<who>.json` so that
+//! `wg focus next` hands out items in the order that was shown.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::graph::{Priority, Status, Task, WorkGraph, aged_priority, is_system_task};
+use crate::query::ready_tasks;
+
+const FOCUS_DIR: &str = "focus";
+
+/// Who a queue is for.
+#[derive(Debug, Clone, Default)]
+pub struct Focuser {
+    /// Actor name as used in a task's `assigned` field.
+    pub name: String,
+    /// Agency agent id, when the actor is an agent.
+    pub agent_id: Option<String>,
+    /// Capabilities to match against task skills. `None` skips skill
+    /// matching (people have no recorded skills).
+    pub skills: Option<Vec<String>>,
+}
+
+impl Focuser {
+    /// Resolve an actor name. An agency agent contributes its capabilities
+    /// for skill matching; anyone else is matched on assignment only.
+    pub fn resolve(workgraph_dir: &Path, name: &str) -> Self {
+        let agents_dir = workgraph_dir.join("agency").join("cache/agents");
+        match crate::agency::find_agent_by_prefix(&agents_dir, name) {
+            Ok(agent) => Self {
+                name: name.to_string(),
+                agent_id: Some(agent.id),
+                skills: Some(agent.capabilities),
+            },
+            Err(_) => Self {
+                name: name.to_string(),
+                ..Self::default()
+            },
+        }
+    }
+}
+
+/// One entry in a focus queue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FocusItem {
+    pub id: String,
+    pub title: String,
+    /// Priority after aging.
+    pub priority: Priority,
+    /// Estimated hours of unfinished work on the longest chain downstream.
+    pub tail_hours: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hours: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+}
+
+/// A pinned queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusQueue {
+    pub who: String,
+    pub computed_at: String,
+    pub items: Vec<FocusItem>,
+}
+
+fn hours(task: &Task) -> f64 {
+    task.estimate.as_ref().and_then(|e| e.hours).unwrap_or(0.0)
+}
+
+/// Longest chain of estimated hours among unfinished dependents of each task.
+fn tail_hours(graph: &WorkGraph) -> HashMap<String, f64> {
+    let mut dependents: HashMap<&str, Vec<&Task>> = HashMap::new();
+    for task in graph.tasks().filter(|t| !t.status.is_terminal()) {
+        for dep in &task.after {
+            dependents.entry(dep.as_str()).or_default().push(task);
+        }
+    }
+
+    fn tail<'a>(
+        id: &'a str,
+        dependents: &HashMap<&'a str, Vec<&'a Task>>,
+        memo: &mut HashMap<String, f64>,
+        visiting: &mut HashSet<&'a str>,
+    ) -> f64 {
+        if let Some(&h) = memo.get(id) {
+            return h;
+        }
+        // Back edges of a cycle add nothing.
+        if !visiting.insert(id) {
+            return 0.0;
+        }
+        let h = dependents
+            .get(id)
+            .into_iter()
+            .flatten()
+            .copied()
+            .map(|d| hours(d) + tail(&d.id, dependents, memo, visiting))
+            .fold(0.0, f64::max);
+        visiting.remove(id);
+        memo.insert(id.to_string(), h);
+        h
+    }
+
+    let mut memo = HashMap::new();
+    let mut visiting = HashSet::new();
+    for task in graph.tasks() {
+        tail(&task.id, &dependents, &mut memo, &mut visiting);
+    }
+    memo
+}
+
+/// Whether `who` may pick up `task` right now (ignoring readiness).
+pub fn is_claimable(task: &Task, who: &Focuser) -> bool {
+    if is_system_task(&task.id) || !matches!(task.status, Status::Open | Status::Incomplete) {
+        return false;
+    }
+    if task.assigned.as_ref().is_some_and(|a| *a != who.name) {
+        return false;
+    }
+    is_suited(task, who)
+}
+
+/// Whether `who` matches the task's agent pin and required skills.
+pub fn is_suited(task: &Task, who: &Focuser) -> bool {
+    if let (Some(agent), Some(mine)) = (&task.agent, &who.agent_id)
+        && agent != mine
+    {
+        return false;
+    }
+    match &who.skills {
+        Some(skills) => task.skills.iter().all(|s| skills.contains(s)),
+        None => true,
+    }
+}
+
+/// Compute the next `limit` tasks for `who`, best first.
+pub fn compute(
+    graph: &WorkGraph,
+    who: &Focuser,
+    limit: usize,
+    now: DateTime<Utc>,
+    aging_hours: u64,
+) -> Vec<FocusItem> {
+    let tails = tail_hours(graph);
+    let mut items: Vec<(FocusItem, Option<DateTime<Utc>>)> = ready_tasks(graph)
+        .into_iter()
+        .filter(|t| is_claimable(t, who))
+        .map(|t| {
+            let item = FocusItem {
+                id: t.id.clone(),
+                title: t.title.clone(),
+                priority: aged_priority(t, now, aging_hours),
+                tail_hours: tails.get(&t.id).copied().unwrap_or(0.0),
+                hours: t.estimate.as_ref().and_then(|e| e.hours),
+                due: t.due.clone(),
+            };
+            (item, crate::deadline::due_at(t))
+        })
+        .collect();
+    items.sort_by(|(a, a_due), (b, b_due)| {
+        b.priority
+            .cmp(&a.priority)
+            .then(b.tail_hours.total_cmp(&a.tail_hours))
+            // Earliest deadline first; no deadline last.
+            .then(match (a_due, b_due) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            })
+            .then(a.id.cmp(&b.id))
+    });
+    items.into_iter().take(limit).map(|(i, _)| i).collect()
+}
+
+/// Queue file for `who`; names are sanitised so any actor string is safe.
+pub fn queue_path(workgraph_dir: &Path, who: &str) -> PathBuf {
+    let file: String = who
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    workgraph_dir.join(FOCUS_DIR).join(format!("{}.json", file))
+}
+
+/// The pinned queue for `who`, if any.
+pub fn load(workgraph_dir: &Path, who: &str) -> Result<Option<FocusQueue>> {
+    let path = queue_path(workgraph_dir, who);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let queue = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(queue))
+}
+
+/// Pin a queue.
+pub fn save(workgraph_dir: &Path, queue: &FocusQueue) -> Result<()> {
+    let path = queue_path(workgraph_dir, &queue.who);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(queue)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Drop pinned items that are no longer ready or claimable by `who`.
+pub fn prune(graph: &WorkGraph, who: &Focuser, items: &mut Vec<FocusItem>) {
+    let ready: HashSet<&str> = ready_tasks(graph).iter().map(|t| t.id.as_str()).collect();
+    items.retain(|item| {
+        ready.contains(item.id.as_str())
+            && graph
+                .get_task(&item.id)
+                .is_some_and(|t| is_claimable(t, who))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Estimate, Node};
+    use crate::test_helpers::make_task;
+
+    fn task(id: &str, priority: u32, hours: f64, after: &[&str]) -> Node {
+        let mut t = make_task(id, id);
+        t.priority = priority;
+        t.estimate = Some(Estimate {
+            hours: Some(hours),
+            cost: None,
+        });
+        t.after = after.iter().map(|s| s.to_string()).collect();
+        Node::Task(t)
+    }
+
+    fn ids(items: &[FocusItem]) -> Vec<&str> {
+        items.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_compute_orders_by_priority_then_tail() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(task("short-tail", 10, 1.0, &[]));
+        graph.add_node(task("long-tail", 10, 1.0, &[]));
+        graph.add_node(task("urgent", 50, 1.0, &[]));
+        graph.add_node(task("after-long", 10, 8.0, &["long-tail"]));
+        graph.add_node(task("after-short", 10, 2.0, &["short-tail"]));
+        let who = Focuser {
+            name: "erin".into(),
+            ..Focuser::default()
+        };
+
+        let items = compute(&graph, &who, 5, Utc::now(), 0);
+        assert_eq!(ids(&items), ["urgent", "long-tail", "short-tail"]);
+        assert_eq!(items[1].tail_hours, 8.0);
+        assert_eq!(ids(&compute(&graph, &who, 1, Utc::now(), 0)), ["urgent"]);
+    }
+
+    #[test]
+    fn test_compute_filters_claimable_and_skills() {
+        let mut graph = WorkGraph::new();
+        let mut theirs = make_task("theirs", "Assigned elsewhere");
+        theirs.assigned = Some("sam".into());
+        let mut rust = make_task("rust", "Needs rust");
+        rust.skills = vec!["rust".into()];
+        let mut mine = make_task("mine", "Assigned to me");
+        mine.assigned = Some("agent-x".into());
+        graph.add_node(Node::Task(theirs));
+        graph.add_node(Node::Task(rust));
+        graph.add_node(Node::Task(mine));
+        graph.add_node(Node::Task(make_task(".evaluate-mine", "System")));
+
+        let who = Focuser {
+            name: "agent-x".into(),
+            agent_id: None,
+            skills: Some(vec!["python".into()]),
+        };
+        assert_eq!(ids(&compute(&graph, &who, 5, Utc::now(), 0)), ["mine"]);
+
+        let who = Focuser {
+            skills: Some(vec!["rust".into()]),
+            ..who
+        };
+        assert_eq!(
+            ids(&compute(&graph, &who, 5, Utc::now(), 0)),
+            ["mine", "rust"]
+        );
+    }
+}
@@ -1,9 +1,12 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use crate::graph::ChangeSummary;
+
 // ---------------------------------------------------------------------------
 // Error type
 // ---------------------------------------------------------------------------
@@ -14,6 +17,20 @@ pub enum TraceFunctionError {
     Io(#[from] std::io::Error),
     #[error("YAML error: {0}")]
     Yaml(#[from] serde_yaml::Error),
+    #[error("Failed to parse {}: {source}", .path.display())]
+    Parse {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+    /// A field no trace function part accepts, found in strict mode (see
+    /// [`crate::strict`]).
+    #[error("{0}")]
+    UnknownField(crate::strict::UnknownField),
+    #[error("Failed to parse {}: {source}", .path.display())]
+    UnknownFieldIn {
+        path: PathBuf,
+        source: crate::strict::UnknownField,
+    },
     #[error("{0}")]
     NotFound(String),
     #[error("{0}")]
@@ -27,7 +44,7 @@ pub enum TraceFunctionError {
 // ---------------------------------------------------------------------------
 
 /// A parameterized workflow template extracted from completed traces.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TraceFunction {
     pub kind: String,
     pub version: u32,
@@ -66,7 +83,7 @@ pub struct TraceFunction {
     pub redacted_fields: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ExtractionSource {
     pub task_id: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -74,7 +91,7 @@ pub struct ExtractionSource {
     pub timestamp: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FunctionInput {
     pub name: String,
     #[serde(rename = "type")]
@@ -83,8 +100,10 @@ pub struct FunctionInput {
     #[serde(default)]
     pub required: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<serde_json::Value>")]
     pub default: Option<serde_yaml::Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<serde_json::Value>")]
     pub example: Option<serde_yaml::Value>,
     // Type-specific validation
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -95,7 +114,7 @@ pub struct FunctionInput {
     pub values: Option<Vec<String>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum InputType {
     String,
@@ -108,7 +127,7 @@ pub enum InputType {
     Json,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplate {
     pub template_id: String,
     pub title: String,
@@ -129,7 +148,7 @@ pub struct TaskTemplate {
     pub tags: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LoopEdgeTemplate {
     pub target: String,
     pub max_iterations: u32,
@@ -139,7 +158,7 @@ pub struct LoopEdgeTemplate {
     pub delay: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FunctionOutput {
     pub name: String,
     pub description: String,
@@ -152,7 +171,7 @@ pub struct FunctionOutput {
 // ---------------------------------------------------------------------------
 
 /// Controls who can discover and use a function.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 #[derive(Default)]
 pub enum FunctionVisibility {
@@ -205,7 +224,7 @@ impl std::fmt::Display for FunctionVisibility {
 }
 
 /// Configuration for a planning node (Layer 2: Generative functions).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PlanningConfig {
     /// The task template for the planning node itself.
     pub planner_template: TaskTemplate,
@@ -232,7 +251,7 @@ fn default_true() -> bool {
 }
 
 /// Constraints on the shape of a generated task graph (Layer 2).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StructuralConstraints {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_tasks: Option<u32>,
@@ -253,14 +272,14 @@ pub struct StructuralConstraints {
 }
 
 /// A tag combination that is forbidden in generated task graphs.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ForbiddenPattern {
     pub tags: Vec<String>,
     pub reason: String,
 }
 
 /// Configuration for trace memory (Layer 3: Adaptive functions).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TraceMemoryConfig {
     /// Maximum past run summaries to include in planning prompt.
     #[serde(default = "default_max_runs")]
@@ -275,7 +294,7 @@ fn default_max_runs() -> u32 {
 }
 
 /// Which aspects of past runs to include in trace memory.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MemoryInclusions {
     #[serde(default = "default_true")]
     pub outcomes: bool,
@@ -292,11 +311,12 @@ pub struct MemoryInclusions {
 }
 
 /// Summary of a single past application run (used in trace memory).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RunSummary {
     #[serde(alias = "instantiated_at")]
     pub applied_at: String,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[schemars(with = "HashMap<String, serde_json::Value>")]
     pub inputs: HashMap<String, serde_yaml::Value>,
     pub prefix: String,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -311,7 +331,7 @@ pub struct RunSummary {
 }
 
 /// Outcome of a single task within a run.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TaskOutcome {
     pub template_id: String,
     pub task_id: String,
@@ -322,10 +342,13 @@ pub struct TaskOutcome {
     pub duration_secs: Option<i64>,
     #[serde(default)]
     pub retry_count: u32,
+    /// Files and lines the task changed, when it recorded them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<ChangeSummary>,
 }
 
 /// Summary of a human or system intervention during a run.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct InterventionSummary {
     pub task_id: String,
     pub kind: String,
@@ -341,11 +364,32 @@ pub struct InterventionSummary {
 /// Directory name under .wg/ for trace functions.
 pub const FUNCTIONS_DIR: &str = "functions";
 
+/// Parse a trace function from YAML text. Malformed or adversarial input
+/// (e.g. a function shared by a peer) is an error, never a panic; the
+/// error carries the line and column when YAML reports one.
+pub fn parse_function(contents: &str) -> Result<TraceFunction, TraceFunctionError> {
+    if crate::strict::enabled()
+        && let Some(unknown) = crate::strict::check_function(contents)
+    {
+        return Err(TraceFunctionError::UnknownField(unknown));
+    }
+    Ok(serde_yaml::from_str(contents)?)
+}
+
 /// Load a single trace function from a YAML file.
 pub fn load_function(path: &Path) -> Result<TraceFunction, TraceFunctionError> {
     let contents = fs::read_to_string(path)?;
-    let func: TraceFunction = serde_yaml::from_str(&contents)?;
-    Ok(func)
+    parse_function(&contents).map_err(|e| match e {
+        TraceFunctionError::Yaml(source) => TraceFunctionError::Parse {
+            path: path.to_path_buf(),
+            source,
+        },
+        TraceFunctionError::UnknownField(source) => TraceFunctionError::UnknownFieldIn {
+            path: path.to_path_buf(),
+            source,
+        },
+        other => other,
+    })
 }
 
 /// Save a trace function as `<id>.yaml` inside the given directory.
@@ -1369,6 +1413,40 @@ mod tests {
 
     // -- YAML format compatibility --
 
+    #[test]
+    fn parse_function_rejects_adversarial_yaml() {
+        let deep = format!("{}x{}", "[".repeat(10_000), "]".repeat(10_000));
+        let bomb = "a: &a [x, x, x, x, x, x, x, x, x]\n\
+                    b: &b [*a, *a, *a, *a, *a, *a, *a, *a, *a]\n\
+                    c: &c [*b, *b, *b, *b, *b, *b, *b, *b, *b]\n\
+                    d: &d [*c, *c, *c, *c, *c, *c, *c, *c, *c]\n\
+                    e: &e [*d, *d, *d, *d, *d, *d, *d, *d, *d]\n\
+                    f: &f [*e, *e, *e, *e, *e, *e, *e, *e, *e]\n\
+                    g: &g [*f, *f, *f, *f, *f, *f, *f, *f, *f]\n";
+        for input in [
+            deep.as_str(),
+            bomb,
+            "kind: trace-function\nversion: -1\n",
+            "id: [unterminated",
+            "\t- not yaml",
+            "",
+        ] {
+            assert!(parse_function(input).is_err(), "accepted {:?}", input);
+        }
+    }
+
+    #[test]
+    fn load_function_error_names_the_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("broken.yaml");
+        fs::write(&path, "kind: trace-function\nid: [\n").unwrap();
+        let err = load_function(&path).unwrap_err();
+        assert!(matches!(err, TraceFunctionError::Parse { .. }));
+        let msg = err.to_string();
+        assert!(msg.contains("broken.yaml"), "{}", msg);
+        assert!(msg.contains("line"), "{}", msg);
+    }
+
     #[test]
     fn deserialize_yaml_from_design_doc() {
         // Verify we can parse the YAML format shown in the design doc
@@ -1749,6 +1827,7 @@ planner_template:
                 score: Some(0.95),
                 duration_secs: Some(120),
                 retry_count: 0,
+                changes: None,
             }],
             interventions: vec![InterventionSummary {
                 task_id: "deploy-1.0/test".to_string(),
@@ -173,6 +173,7 @@ pub fn build_run_summary(
             score,
             duration_secs,
             retry_count: task.retry_count,
+            changes: task.changes.clone(),
         });
     }
 
@@ -253,6 +254,9 @@ pub fn render_summaries_text(summaries: &[RunSummary]) -> String {
                 if outcome.retry_count > 0 {
                     parts.push(format!("retries={}", outcome.retry_count));
                 }
+                if let Some(ref changes) = outcome.changes {
+                    parts.push(format!("changed {}", crate::changes::describe(changes)));
+                }
                 lines.push(parts.join(" "));
             }
         }
@@ -414,7 +418,11 @@ pub fn append_run_summary(
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
-    let line = serde_json::to_string(summary).map_err(std::io::Error::other)?;
+    let mut value = serde_json::to_value(summary).map_err(std::io::Error::other)?;
+    if let Some(scrubber) = crate::scrub::Scrubber::load(workgraph_dir) {
+        scrubber.scrub_json(&mut value);
+    }
+    let line = serde_json::to_string(&value).map_err(std::io::Error::other)?;
     use std::io::Write;
     let mut file = std::fs::OpenOptions::new()
         .create(true)
@@ -564,6 +572,7 @@ mod tests {
                     score: Some(0.9),
                     duration_secs: Some(60),
                     retry_count: 0,
+                    changes: None,
                 },
                 TaskOutcome {
                     template_id: "implement".to_string(),
@@ -572,6 +581,7 @@ mod tests {
                     score: Some(0.85),
                     duration_secs: Some(300),
                     retry_count: 1,
+                    changes: None,
                 },
             ],
             interventions: vec![InterventionSummary {
@@ -990,6 +1000,8 @@ mod tests {
                 task_id: Some("pfx/build".to_string()),
                 actor: Some("agent".to_string()),
                 detail: serde_json::Value::Null,
+                prev_hash: None,
+                hash: None,
                 user: None,
             },
             OperationEntry {
@@ -998,6 +1010,8 @@ mod tests {
                 task_id: Some("pfx/test".to_string()),
                 actor: Some("human".to_string()),
                 detail: serde_json::json!({"reason": "Flaky test"}),
+                prev_hash: None,
+                hash: None,
                 user: None,
             },
             OperationEntry {
@@ -1006,6 +1020,8 @@ mod tests {
                 task_id: Some("pfx/test".to_string()),
                 actor: Some("human".to_string()),
                 detail: serde_json::json!({"detail": "Updated description"}),
+                prev_hash: None,
+                hash: None,
                 user: None,
             },
         ];
@@ -1032,6 +1048,8 @@ mod tests {
                 task_id: Some("task-1".to_string()),
                 actor: None,
                 detail: serde_json::Value::Null,
+                prev_hash: None,
+                hash: None,
                 user: None,
             },
             OperationEntry {
@@ -1040,6 +1058,8 @@ mod tests {
                 task_id: Some("task-1".to_string()),
                 actor: None,
                 detail: serde_json::Value::Null,
+                prev_hash: None,
+                hash: None,
                 user: None,
             },
         ];
@@ -1057,6 +1077,8 @@ mod tests {
             task_id: Some("task-1".to_string()),
             actor: None,
             detail: serde_json::Value::Null,
+            prev_hash: None,
+            hash: None,
             user: None,
         }];
 
@@ -1123,6 +1145,7 @@ mod tests {
                 score: None,
                 duration_secs: None,
                 retry_count: 0,
+                changes: None,
             }],
             interventions: vec![],
             wall_clock_secs: None,
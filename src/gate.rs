@@ -0,0 +1,621 @@
+//! Phase gates: named gate nodes between graph phases.
+//!
+//! A gate is a task carrying a [`PhaseGate`]. Its upstream tasks (`after`) form
+//! the phase it guards; downstream phase tasks depend on the gate. The gate is
+//! never dispatched to an agent — the dispatcher (or `wg gate check`) evaluates
+//! its conditions once the gate is ready and marks it done when all of them
+//! hold, which in turn unblocks the next phase.
+//!
+//! Command conditions run with a timeout ([`COMMAND_TIMEOUT`]); a command
+//! still running then is killed and counts as failing. A failing command is
+//! not re-run on every dispatcher tick: each consecutive failure doubles the
+//! wait before the next run, up to [`MAX_BACKOFF_SECS`]. `wg gate check <id>`
+//! runs it regardless.
+
+use crate::agency;
+use crate::contract;
+use crate::graph::{LogEntry, Status, Task, WorkGraph};
+use crate::parser::modify_graph;
+use anyhow::Result;
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long a gate command may run before it is killed.
+pub const COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+/// Wait before re-running a command after its first failure.
+pub const BASE_BACKOFF_SECS: i64 = 30;
+/// Longest wait between runs of a failing command.
+pub const MAX_BACKOFF_SECS: i64 = 30 * 60;
+
+/// Conditions attached to a gate node.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PhaseGate {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<GateCondition>,
+    /// Who approved the gate (satisfies [`GateCondition::Approval`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_by: Option<String>,
+}
+
+/// A single gate condition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GateCondition {
+    /// Every task in the phase (the gate's upstream tasks) is done.
+    PhaseDone,
+    /// The average evaluation score across the phase is at least `min`.
+    MinScore { min: f64 },
+    /// A shell command (e.g. a linter) exits successfully.
+    /// `passed` caches the outcome of the last run; `failures` counts
+    /// consecutive failing runs and `retry_at` is when the dispatcher may
+    /// run it again.
+    Command {
+        command: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        passed: Option<bool>,
+        #[serde(default, skip_serializing_if = "is_zero")]
+        failures: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        retry_at: Option<String>,
+    },
+    /// A human has approved the gate with `wg gate approve`.
+    Approval,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+impl GateCondition {
+    pub fn label(&self) -> String {
+        match self {
+            GateCondition::PhaseDone => "phase done".to_string(),
+            GateCondition::MinScore { min } => format!("avg score >= {:.2}", min),
+            GateCondition::Command { command, .. } => format!("`{}` passes", command),
+            GateCondition::Approval => "human approval".to_string(),
+        }
+    }
+}
+
+/// Outcome of evaluating one condition.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConditionResult {
+    pub condition: String,
+    pub met: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Whether a command condition was actually run for this report.
+    #[serde(skip)]
+    pub(crate) ran: bool,
+}
+
+/// Evaluation of all conditions on a gate.
+#[derive(Debug, Clone, Serialize)]
+pub struct GateReport {
+    pub gate_id: String,
+    pub results: Vec<ConditionResult>,
+}
+
+impl GateReport {
+    pub fn met(&self) -> usize {
+        self.results.iter().filter(|r| r.met).count()
+    }
+
+    pub fn total(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.met() == self.total()
+    }
+
+    /// Compact summary, e.g. "gate: 3/4 conditions met".
+    pub fn summary(&self) -> String {
+        format!("gate: {}/{} conditions met", self.met(), self.total())
+    }
+}
+
+/// Average evaluation score across the given tasks, if any were evaluated.
+fn phase_avg_score(dir: &Path, task_ids: &[String]) -> Option<f64> {
+    let evals = agency::load_all_evaluations_or_warn(&dir.join("agency").join("evaluations"));
+    let scores: Vec<f64> = evals
+        .iter()
+        .filter(|e| task_ids.contains(&e.task_id))
+        .map(|e| e.score)
+        .collect();
+    if scores.is_empty() {
+        None
+    } else {
+        Some(scores.iter().sum::<f64>() / scores.len() as f64)
+    }
+}
+
+/// Run a gate command, killing it (and anything it started) after
+/// [`COMMAND_TIMEOUT`]. Returns whether it succeeded and, on failure, why.
+fn run_gate_command(command: &str, project_root: &Path) -> (bool, Option<String>) {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(command)
+        .current_dir(project_root)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    let mut child = match cmd.spawn() {
+        Ok(c) => c,
+        Err(e) => return (false, Some(format!("failed to run: {}", e))),
+    };
+    let deadline = Instant::now() + COMMAND_TIMEOUT;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return (true, None),
+            Ok(Some(status)) => return (false, Some(format!("failing ({})", status))),
+            Ok(None) if Instant::now() >= deadline => {
+                #[cfg(unix)]
+                unsafe {
+                    libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+                }
+                let _ = child.kill();
+                let _ = child.wait();
+                return (
+                    false,
+                    Some(format!("timed out after {}s", COMMAND_TIMEOUT.as_secs())),
+                );
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return (false, Some(format!("failed to wait: {}", e))),
+        }
+    }
+}
+
+/// Wait before the next run after `failures` consecutive failures.
+pub fn backoff_secs(failures: u32) -> i64 {
+    let doublings = failures.saturating_sub(1).min(16);
+    (BASE_BACKOFF_SECS << doublings).min(MAX_BACKOFF_SECS)
+}
+
+/// Whether a failing command's backoff has elapsed.
+fn retry_due(retry_at: Option<&str>) -> bool {
+    retry_at
+        .and_then(|ts| ts.parse::<chrono::DateTime<Utc>>().ok())
+        .is_none_or(|at| Utc::now() >= at)
+}
+
+/// Evaluate a gate's conditions.
+///
+/// When `run_commands` is false, command conditions report their cached
+/// outcome from the last check instead of re-running (used by `wg status`).
+/// A command that already passed is not re-run, nor is a failing one whose
+/// backoff has not elapsed.
+/// Returns `None` if the task is not a gate.
+pub fn evaluate(
+    dir: &Path,
+    graph: &WorkGraph,
+    task: &Task,
+    run_commands: bool,
+) -> Option<GateReport> {
+    evaluate_inner(dir, graph, task, run_commands, false)
+}
+
+/// [`evaluate`], optionally ignoring command backoff.
+fn evaluate_inner(
+    dir: &Path,
+    graph: &WorkGraph,
+    task: &Task,
+    run_commands: bool,
+    ignore_backoff: bool,
+) -> Option<GateReport> {
+    let gate = task.gate.as_ref()?;
+    let project_root = dir.parent().unwrap_or(dir);
+
+    let results = gate
+        .conditions
+        .iter()
+        .map(|cond| {
+            let mut ran = false;
+            let (met, detail) = match cond {
+                GateCondition::PhaseDone => {
+                    let pending: Vec<&str> = task
+                        .after
+                        .iter()
+                        .filter(|id| graph.get_task(id).is_none_or(|t| t.status != Status::Done))
+                        .map(String::as_str)
+                        .collect();
+                    if pending.is_empty() {
+                        (true, None)
+                    } else {
+                        (false, Some(format!("pending: {}", pending.join(", "))))
+                    }
+                }
+                GateCondition::MinScore { min } => match phase_avg_score(dir, &task.after) {
+                    Some(avg) => (avg >= *min, Some(format!("avg {:.2}", avg))),
+                    None => (false, Some("no evaluations yet".to_string())),
+                },
+                GateCondition::Command {
+                    command,
+                    passed,
+                    retry_at,
+                    ..
+                } => {
+                    let due = ignore_backoff || retry_due(retry_at.as_deref());
+                    if run_commands && *passed != Some(true) && due {
+                        ran = true;
+                        run_gate_command(command, project_root)
+                    } else {
+                        let ok = passed.unwrap_or(false);
+                        let detail = match (passed, retry_at) {
+                            (None, _) => Some("not run yet".to_string()),
+                            _ if ok => None,
+                            (_, Some(at)) => Some(format!("failing; next run after {}", at)),
+                            _ => Some("failing".to_string()),
+                        };
+                        (ok, detail)
+                    }
+                }
+                GateCondition::Approval => match gate.approved_by {
+                    Some(ref who) => (true, Some(format!("approved by {}", who))),
+                    None => (false, Some("awaiting `wg gate approve`".to_string())),
+                },
+            };
+            ConditionResult {
+                condition: cond.label(),
+                met,
+                detail,
+                ran,
+            }
+        })
+        .collect();
+
+    Some(GateReport {
+        gate_id: task.id.clone(),
+        results,
+    })
+}
+
+/// Whether a gate's upstream phase has settled enough for it to be checked.
+fn is_gate_checkable(graph: &WorkGraph, task: &Task) -> bool {
+    task.gate.is_some()
+        && matches!(task.status, Status::Open | Status::Blocked)
+        && task
+            .after
+            .iter()
+            .all(|id| graph.get_task(id).is_none_or(|t| t.status.is_terminal()))
+}
+
+/// Re-check a gate against the locked graph before opening it: the graph may
+/// have changed while its commands ran (e.g. an upstream task was reopened).
+/// Command conditions use the outcomes just cached on the gate.
+fn still_open(dir: &Path, graph: &WorkGraph, gate_id: &str, forced: bool) -> bool {
+    let Some(task) = graph.get_task(gate_id) else {
+        return false;
+    };
+    let settled = if forced {
+        !task.status.is_terminal()
+    } else {
+        is_gate_checkable(graph, task)
+    };
+    settled && evaluate_inner(dir, graph, task, false, false).is_some_and(|r| r.is_open())
+}
+
+/// Evaluate gates and open those whose conditions all hold.
+///
+/// With `only` set, checks just that gate (regardless of upstream state);
+/// otherwise checks every gate whose upstream phase has settled. Command
+/// conditions run outside the graph lock; their outcomes and backoff are
+/// cached on the gate. A gate named in `only` runs its commands even when
+/// backing off. Opening a gate completes it like `wg done` would, so consumers'
+/// input contracts are enforced against it. Returns the report for every gate
+/// checked.
+pub fn check_gates(dir: &Path, graph_path: &Path, only: Option<&str>) -> Result<Vec<GateReport>> {
+    let graph = crate::parser::load_graph(graph_path)?;
+    let reports: Vec<GateReport> = graph
+        .tasks()
+        .filter(|t| match only {
+            Some(id) => t.id == id,
+            None => is_gate_checkable(&graph, t),
+        })
+        .filter_map(|t| evaluate_inner(dir, &graph, t, true, only.is_some()))
+        .collect();
+
+    if reports.is_empty() {
+        return Ok(reports);
+    }
+
+    let project_root = dir.parent().unwrap_or(dir);
+    let mut violations = Vec::new();
+    modify_graph(graph_path, |graph| {
+        let mut modified = false;
+        for report in &reports {
+            let Some(task) = graph.get_task_mut(&report.gate_id) else {
+                continue;
+            };
+            if let Some(ref mut gate) = task.gate {
+                for (cond, result) in gate.conditions.iter_mut().zip(&report.results) {
+                    if let GateCondition::Command {
+                        passed,
+                        failures,
+                        retry_at,
+                        ..
+                    } = cond
+                        && result.ran
+                    {
+                        *passed = Some(result.met);
+                        if result.met {
+                            *failures = 0;
+                            *retry_at = None;
+                        } else {
+                            *failures += 1;
+                            let at =
+                                Utc::now() + chrono::Duration::seconds(backoff_secs(*failures));
+                            *retry_at = Some(at.to_rfc3339());
+                        }
+                        modified = true;
+                    }
+                }
+            }
+            if !report.is_open() || !still_open(dir, graph, &report.gate_id, only.is_some()) {
+                continue;
+            }
+            let Some(task) = graph.get_task_mut(&report.gate_id) else {
+                continue;
+            };
+            task.status = Status::Done;
+            task.completed_at = Some(Utc::now().to_rfc3339());
+            task.log.push(LogEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                actor: None,
+                user: Some(crate::current_user()),
+                message: format!("Gate opened ({})", report.summary()),
+            });
+            violations.extend(contract::enforce_on_completion(
+                graph,
+                &report.gate_id,
+                project_root,
+            ));
+            modified = true;
+        }
+        modified
+    })?;
+
+    for v in &violations {
+        eprintln!(
+            "Warning: contract violation: {} (paused '{}')",
+            v, v.task_id
+        );
+    }
+
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use tempfile::tempdir;
+
+    fn task(id: &str, status: Status) -> Task {
+        Task {
+            id: id.to_string(),
+            title: id.to_string(),
+            status,
+            ..Task::default()
+        }
+    }
+
+    fn gate_task(after: &[&str], conditions: Vec<GateCondition>) -> Task {
+        Task {
+            after: after.iter().map(|s| s.to_string()).collect(),
+            gate: Some(PhaseGate {
+                conditions,
+                approved_by: None,
+            }),
+            ..task("gate", Status::Open)
+        }
+    }
+
+    #[test]
+    fn test_evaluate_counts_met_conditions() {
+        let dir = tempdir().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task("a", Status::Done)));
+        graph.add_node(Node::Task(task("b", Status::InProgress)));
+        let gate = gate_task(
+            &["a", "b"],
+            vec![
+                GateCondition::PhaseDone,
+                GateCondition::Approval,
+                GateCondition::Command {
+                    command: "true".to_string(),
+                    passed: None,
+                    failures: 0,
+                    retry_at: None,
+                },
+            ],
+        );
+
+        let report = evaluate(dir.path(), &graph, &gate, true).unwrap();
+        assert_eq!(report.met(), 1);
+        assert_eq!(report.summary(), "gate: 1/3 conditions met");
+        assert_eq!(report.results[0].detail.as_deref(), Some("pending: b"));
+
+        // Without running commands, an unrun command counts as unmet
+        let report = evaluate(dir.path(), &graph, &gate, false).unwrap();
+        assert_eq!(report.met(), 0);
+    }
+
+    #[test]
+    fn test_check_gates_opens_gate_when_all_conditions_hold() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task("a", Status::Done)));
+        graph.add_node(Node::Task(gate_task(
+            &["a"],
+            vec![GateCondition::PhaseDone, GateCondition::Approval],
+        )));
+        crate::parser::save_graph(&graph, &path).unwrap();
+
+        let reports = check_gates(dir.path(), &path, None).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert!(!reports[0].is_open());
+        let g = crate::parser::load_graph(&path).unwrap();
+        assert_eq!(g.get_task("gate").unwrap().status, Status::Open);
+
+        modify_graph(&path, |g| {
+            g.get_task_mut("gate")
+                .unwrap()
+                .gate
+                .as_mut()
+                .unwrap()
+                .approved_by = Some("alice".to_string());
+            true
+        })
+        .unwrap();
+        let reports = check_gates(dir.path(), &path, None).unwrap();
+        assert!(reports[0].is_open());
+        let g = crate::parser::load_graph(&path).unwrap();
+        assert_eq!(g.get_task("gate").unwrap().status, Status::Done);
+    }
+
+    #[test]
+    fn test_gate_not_checkable_until_phase_settles() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task("a", Status::InProgress)));
+        let gate = gate_task(&["a"], vec![GateCondition::Approval]);
+        assert!(!is_gate_checkable(&graph, &gate));
+        graph.get_task_mut("a").unwrap().status = Status::Done;
+        assert!(is_gate_checkable(&graph, &gate));
+    }
+
+    #[test]
+    fn test_still_open_rechecks_upstream_under_lock() {
+        let dir = tempdir().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task("a", Status::Done)));
+        let mut gate = gate_task(&["a"], vec![GateCondition::PhaseDone]);
+        gate.gate.as_mut().unwrap().approved_by = Some("alice".to_string());
+        graph.add_node(Node::Task(gate));
+        assert!(still_open(dir.path(), &graph, "gate", false));
+
+        // Upstream reopened after the gate was evaluated
+        graph.get_task_mut("a").unwrap().status = Status::Open;
+        assert!(!still_open(dir.path(), &graph, "gate", false));
+        assert!(!still_open(dir.path(), &graph, "gate", true));
+    }
+
+    #[test]
+    fn test_opening_gate_enforces_consumer_contracts() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task("a", Status::Done)));
+        graph.add_node(Node::Task(gate_task(
+            &["a"],
+            vec![GateCondition::PhaseDone],
+        )));
+        let mut consumer = task("c", Status::Open);
+        consumer.after = vec!["gate".to_string()];
+        consumer.expects = vec!["artifact:*.json".to_string()];
+        graph.add_node(Node::Task(consumer));
+        crate::parser::save_graph(&graph, &path).unwrap();
+
+        check_gates(dir.path(), &path, None).unwrap();
+        let g = crate::parser::load_graph(&path).unwrap();
+        assert_eq!(g.get_task("gate").unwrap().status, Status::Done);
+        assert!(g.get_task("c").unwrap().paused);
+    }
+
+    #[test]
+    fn test_failing_command_backs_off() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(task("a", Status::Done)));
+        graph.add_node(Node::Task(gate_task(
+            &["a"],
+            vec![GateCondition::Command {
+                command: "false".to_string(),
+                passed: None,
+                failures: 0,
+                retry_at: None,
+            }],
+        )));
+        crate::parser::save_graph(&graph, &path).unwrap();
+
+        let command = |path: &Path| {
+            let g = crate::parser::load_graph(path).unwrap();
+            g.get_task("gate")
+                .unwrap()
+                .gate
+                .as_ref()
+                .unwrap()
+                .conditions[0]
+                .clone()
+        };
+        check_gates(dir.path(), &path, None).unwrap();
+        let GateCondition::Command {
+            failures, retry_at, ..
+        } = command(&path)
+        else {
+            panic!("expected a command condition");
+        };
+        assert_eq!(failures, 1);
+        assert!(retry_at.is_some());
+
+        // Within the backoff the dispatcher doesn't re-run it...
+        let reports = check_gates(dir.path(), &path, None).unwrap();
+        assert!(!reports[0].results[0].ran);
+        assert!(matches!(
+            command(&path),
+            GateCondition::Command { failures: 1, .. }
+        ));
+
+        // ...but an explicit check does.
+        let reports = check_gates(dir.path(), &path, Some("gate")).unwrap();
+        assert!(reports[0].results[0].ran);
+        assert!(matches!(
+            command(&path),
+            GateCondition::Command { failures: 2, .. }
+        ));
+
+        assert_eq!(backoff_secs(1), BASE_BACKOFF_SECS);
+        assert_eq!(backoff_secs(2), 2 * BASE_BACKOFF_SECS);
+        assert_eq!(backoff_secs(40), MAX_BACKOFF_SECS);
+    }
+
+    #[test]
+    fn test_run_gate_command() {
+        let dir = tempdir().unwrap();
+        let (ok, detail) = run_gate_command("true", dir.path());
+        assert!(ok && detail.is_none());
+        let (ok, detail) = run_gate_command("exit 3", dir.path());
+        assert!(!ok);
+        assert!(detail.unwrap().contains("exit status: 3"));
+    }
+
+    #[test]
+    fn test_gate_serde_roundtrip() {
+        let gate = PhaseGate {
+            conditions: vec![
+                GateCondition::PhaseDone,
+                GateCondition::MinScore { min: 0.8 },
+                GateCondition::Command {
+                    command: "cargo clippy".to_string(),
+                    passed: Some(true),
+                    failures: 2,
+                    retry_at: None,
+                },
+                GateCondition::Approval,
+            ],
+            approved_by: None,
+        };
+        let json = serde_json::to_string(&gate).unwrap();
+        assert!(json.contains("\"type\":\"min_score\""));
+        let back: PhaseGate = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, gate);
+    }
+}
@@ -1,11 +1,14 @@
 use crate::config::{Config, ModelRegistryEntry};
 use chrono::{Duration, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Mutex;
 
 /// Configuration for structural cycle iteration.
 /// Only present on the cycle header task.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct CycleConfig {
     /// Hard cap on cycle iterations
     pub max_iterations: u32,
@@ -41,7 +44,7 @@ fn default_true() -> bool {
 }
 
 /// Guard condition for a loop edge
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum LoopGuard {
     /// Loop if a specific task has this status
     TaskStatus { task: String, status: Status },
@@ -74,7 +77,7 @@ pub fn parse_delay(s: &str) -> Option<u64> {
 }
 
 /// A log entry for tracking progress/notes on a task
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct LogEntry {
     pub timestamp: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -86,7 +89,7 @@ pub struct LogEntry {
 }
 
 /// Cost/time estimate for a task
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Default)]
 pub struct Estimate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hours: Option<f64>,
@@ -94,8 +97,40 @@ pub struct Estimate {
     pub cost: Option<f64>,
 }
 
+/// Attributes of a dependency edge, kept in [`Task::edges`] under the
+/// upstream task's ID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, Default)]
+pub struct DependencyEdge {
+    /// Time to wait after the upstream task completes before this one may
+    /// start, e.g. `2d` for a deployment soak (`30m`, `12h`, `2d` format)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lag: Option<String>,
+    /// Extra hours the edge adds to forecasts and the critical path, on top
+    /// of the lag (hand-off, review turnaround)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+}
+
+impl DependencyEdge {
+    /// The lag, if set and parseable.
+    pub fn lag_duration(&self) -> Option<Duration> {
+        self.lag
+            .as_deref()
+            .and_then(parse_delay)
+            .map(|secs| Duration::seconds(secs as i64))
+    }
+
+    /// Lag plus weight, in hours: what the edge adds to a path.
+    pub fn hours(&self) -> f64 {
+        let lag = self
+            .lag_duration()
+            .map_or(0.0, |d| d.num_seconds() as f64 / 3600.0);
+        lag + self.weight.unwrap_or(0.0).max(0.0)
+    }
+}
+
 /// Wait condition for `wg wait` — specifies what a Waiting task is waiting for.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case", tag = "type")]
 pub enum WaitCondition {
     /// Wait for a task to reach a specific status
@@ -111,7 +146,7 @@ pub enum WaitCondition {
 }
 
 /// Composite wait specification: AND (All) or OR (Any) of conditions.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case", tag = "mode", content = "conditions")]
 pub enum WaitSpec {
     /// All conditions must be true
@@ -124,7 +159,7 @@ pub enum WaitSpec {
 /// `wg classify-failure`) and surfaced in `wg show` / `wg service status`.
 /// Pairs with `failure_reason` which carries human prose. None means either
 /// the task succeeded or the row predates this field.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum FailureClass {
     /// HTTP 400 from the Anthropic API on a document attachment (e.g. malformed/
@@ -141,6 +176,12 @@ pub enum FailureClass {
     AgentExitNonzero,
     /// Wrapper-side issue (e.g., missing raw_stream.jsonl). Inspect wrapper log.
     WrapperInternal,
+    /// Agent ran a tool or command its role's tool policy denies.
+    /// Not auto-retriable — the same agent will likely try again.
+    ToolPolicyViolation,
+    /// Run spent more than the task's cost cap and was killed by the
+    /// dispatcher. Not auto-retriable — the task is likely too big.
+    CostCapExceeded,
 }
 
 impl std::fmt::Display for FailureClass {
@@ -152,13 +193,15 @@ impl std::fmt::Display for FailureClass {
             FailureClass::AgentHardTimeout => "agent-hard-timeout",
             FailureClass::AgentExitNonzero => "agent-exit-nonzero",
             FailureClass::WrapperInternal => "wrapper-internal",
+            FailureClass::ToolPolicyViolation => "tool-policy-violation",
+            FailureClass::CostCapExceeded => "cost-cap-exceeded",
         };
         write!(f, "{}", s)
     }
 }
 
 /// Task status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum Status {
     #[default]
@@ -303,9 +346,16 @@ where
     let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
     match value {
         None => Ok(None),
-        Some(serde_json::Value::Number(n)) => {
-            Ok(Some(n.as_u64().unwrap_or(PRIORITY_DEFAULT as u64) as u32))
-        }
+        Some(serde_json::Value::Number(n)) => match n.as_u64() {
+            Some(p) => u32::try_from(p).map(Some).map_err(|_| {
+                serde::de::Error::custom(format!(
+                    "priority {} is out of range (max {})",
+                    p,
+                    u32::MAX
+                ))
+            }),
+            None => Ok(Some(PRIORITY_DEFAULT)),
+        },
         Some(serde_json::Value::String(s)) => Ok(Some(match s.to_lowercase().as_str() {
             "critical" => PRIORITY_CRITICAL,
             "high" => PRIORITY_HIGH,
@@ -329,6 +379,29 @@ pub fn boost_priority(p: Priority) -> Priority {
     }
 }
 
+/// Priority after aging: one [`boost_priority`] tier for every
+/// `aging_hours` since the task was created. `aging_hours == 0` disables
+/// aging; tasks without a parseable `created_at` don't age.
+pub fn aged_priority(task: &Task, now: chrono::DateTime<Utc>, aging_hours: u64) -> Priority {
+    if aging_hours == 0 {
+        return task.priority;
+    }
+    let Some(created) = task
+        .created_at
+        .as_deref()
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+    else {
+        return task.priority;
+    };
+    let age_hours = now
+        .signed_duration_since(created.with_timezone(&Utc))
+        .num_hours()
+        .max(0) as u64;
+    // Four boosts take idle to critical; more would be no-ops
+    let tiers = (age_hours / aging_hours).min(4);
+    (0..tiers).fold(task.priority, |p, _| boost_priority(p))
+}
+
 /// Lower priority by one tier (for eval/flip scaffolding tasks).
 pub fn lower_priority(p: Priority) -> Priority {
     match p {
@@ -348,7 +421,7 @@ pub fn lower_priority(p: Priority) -> Priority {
 /// Custom `Deserialize` handles migration from the old `identity` field
 /// (`{"role_id": "...", "motivation_id": "..."}`) to the new `agent` field
 /// (content-hash string).
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
 pub struct Task {
     pub id: String,
     pub title: String,
@@ -367,6 +440,10 @@ pub struct Task {
     pub before: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty", alias = "blocked_by")]
     pub after: Vec<String>,
+    /// Lag and weight of `after` edges, keyed by upstream task ID. Edges
+    /// without an entry have neither.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub edges: BTreeMap<String, DependencyEdge>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub requires: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -380,9 +457,33 @@ pub struct Task {
     /// Expected output paths/artifacts
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub deliverables: Vec<String>,
+    /// Input contracts on upstream outputs (e.g. "artifact:schemas/*.yaml@design"),
+    /// validated when upstream tasks complete. See [`crate::contract`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expects: Vec<String>,
+    /// Phase gate conditions. A task with a gate is never dispatched; it is
+    /// marked done once all conditions hold. See [`crate::gate`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gate: Option<crate::gate::PhaseGate>,
     /// Actual produced artifacts (paths/references)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub artifacts: Vec<String>,
+    /// External references: issues, PRs, docs, designs (`wg link`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<TaskLink>,
+    /// External event gates (e.g. `webhook:ci-green`). The task is not ready
+    /// until every gate has fired; firing removes it. See [`crate::event_gate`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waiting_on: Vec<String>,
+    /// Held up by a team or vendor outside the project. The task is not
+    /// ready while set and is tracked by `wg waiting` instead of the
+    /// bottleneck and aging reports. See [`crate::external_blocker`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_blocker: Option<ExternalBlocker>,
+    /// The completing agent's account of how it did the work
+    /// (`wg done --report`). Carried into templates by `wg func extract`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_report: Option<SelfReport>,
     /// Shell command to execute for this task (optional, for wg exec)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exec: Option<String>,
@@ -393,6 +494,10 @@ pub struct Task {
     /// Task is not ready until this timestamp (ISO 8601 / RFC 3339)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub not_before: Option<String>,
+    /// Deadline (ISO 8601 / RFC 3339). Checked by `wg overdue` and the
+    /// service deadline sweep (see [`crate::deadline`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
     /// Timestamp when the task was created (ISO 8601 / RFC 3339)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
@@ -409,6 +514,11 @@ pub struct Task {
     /// group. Defaults to `created_at` for tasks predating this field.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_interaction_at: Option<String>,
+    /// Per-task revision counter, bumped on every substantive change written
+    /// through the parser. `save_graph` compares it with the revision the
+    /// writer loaded to detect concurrent modifications.
+    #[serde(default, skip_serializing_if = "is_zero_u64")]
+    pub revision: u64,
     /// Progress log entries
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub log: Vec<LogEntry>,
@@ -498,6 +608,23 @@ pub struct Task {
     /// Token usage and cost data extracted from agent output.log
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub token_usage: Option<TokenUsage>,
+    /// Spend limit (USD) for a single run. The dispatcher kills the agent and
+    /// fails the task once the run's live usage passes it. None falls back to
+    /// the `[cost_caps]` tag defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost: Option<f64>,
+    /// Context window (tokens) an agent needs to take this task. Checked
+    /// against the `max_context` of agents that registered a capability
+    /// document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_context: Option<u64>,
+    /// Repository HEAD when the task was claimed; the base `changes` are
+    /// diffed against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claim_sha: Option<String>,
+    /// Repository changes made by the task, recorded by `wg done`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<ChangeSummary>,
     /// Claude session ID for resume/resurrection (populated from stream.jsonl Init events)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
@@ -510,6 +637,17 @@ pub struct Task {
     /// Number of times this task has been requeued via failed-dependency triage
     #[serde(default, skip_serializing_if = "is_zero")]
     pub triage_count: u32,
+    /// Number of escalation-chain steps fired since the task became stuck
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub escalation_level: u32,
+    /// When the escalation sweep first saw the task blocked; cleared once
+    /// it is no longer blocked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_at: Option<String>,
+    /// Strongest deadline alert sent for the current `due`; cleared once
+    /// the task is back on schedule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_alert: Option<crate::deadline::DeadlineAlert>,
     /// Number of times this task has been resurrected (Done → Open) due to messages
     #[serde(default, skip_serializing_if = "is_zero")]
     pub resurrection_count: u32,
@@ -639,19 +777,25 @@ impl Default for Task {
             estimate: None,
             before: vec![],
             after: vec![],
+            edges: BTreeMap::new(),
             requires: vec![],
             tags: vec![],
             skills: vec![],
             inputs: vec![],
             deliverables: vec![],
+            expects: vec![],
+            gate: None,
             artifacts: vec![],
+            self_report: None,
             exec: None,
             timeout: None,
             not_before: None,
+            due: None,
             created_at: None,
             started_at: None,
             completed_at: None,
             last_interaction_at: None,
+            revision: 0,
             log: vec![],
             retry_count: 0,
             max_retries: None,
@@ -676,10 +820,20 @@ impl Default for Task {
             context_scope: None,
             exec_mode: None,
             token_usage: None,
+            max_cost: None,
+            min_context: None,
+            claim_sha: None,
+            changes: None,
             session_id: None,
             wait_condition: None,
             checkpoint: None,
             triage_count: 0,
+            escalation_level: 0,
+            blocked_at: None,
+            deadline_alert: None,
+            waiting_on: vec![],
+            external_blocker: None,
+            links: vec![],
             resurrection_count: 0,
             last_resurrected_at: None,
             validation: None,
@@ -740,8 +894,8 @@ impl Task {
 
     /// Compare two tasks for the purposes of detecting whether the closure
     /// passed to `modify_graph` made a *substantive* change. The comparison
-    /// ignores the interaction timestamp itself so the bump pass doesn't
-    /// trigger on the bump.
+    /// ignores the interaction timestamp and revision counter themselves so
+    /// the bump pass doesn't trigger on the bump.
     pub fn substantively_eq(&self, other: &Task) -> bool {
         if std::ptr::eq(self, other) {
             return true;
@@ -750,6 +904,8 @@ impl Task {
         let mut b = other.clone();
         a.last_interaction_at = None;
         b.last_interaction_at = None;
+        a.revision = 0;
+        b.revision = 0;
         a == b
     }
 }
@@ -864,7 +1020,7 @@ pub fn user_board_seq(task_id: &str) -> Option<u32> {
 
 /// Token usage and cost data from a Claude CLI agent run.
 /// Extracted from the final `type=result` line in the agent's output.log.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TokenUsage {
     /// Total cost in USD
     #[serde(default, skip_serializing_if = "is_f64_zero")]
@@ -883,6 +1039,21 @@ pub struct TokenUsage {
     pub cache_creation_input_tokens: u64,
 }
 
+/// Files and lines a task changed, from `git diff --numstat` against the
+/// commit checked out when it was claimed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ChangeSummary {
+    /// Commit the diff was taken against
+    pub base: String,
+    /// Paths touched, relative to the repository root
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    #[serde(default)]
+    pub insertions: u64,
+    #[serde(default)]
+    pub deletions: u64,
+}
+
 fn is_f64_zero(val: &f64) -> bool {
     *val == 0.0
 }
@@ -1137,7 +1308,10 @@ fn infer_model_pricing(
 ) -> Option<ModelRegistryEntry> {
     let workgraph_dir = infer_workgraph_dir(output_log_path)?;
     let config = Config::load_or_default(&workgraph_dir);
-    let model_spec = model_spec?;
+    find_model_pricing(&config, model_spec?)
+}
+
+fn find_model_pricing(config: &Config, model_spec: &str) -> Option<ModelRegistryEntry> {
     let model_without_provider = model_spec
         .split_once(':')
         .map(|(_, model)| model)
@@ -1152,6 +1326,22 @@ fn infer_model_pricing(
     })
 }
 
+/// Price `usage` at the configured model registry's rates for `model_spec`.
+/// Returns `None` when the model has no known pricing.
+pub fn registry_cost_usd(config: &Config, model_spec: &str, usage: &TokenUsage) -> Option<f64> {
+    let pricing = find_model_pricing(config, model_spec);
+    if pricing.is_none() && fallback_model_pricing_mtok(model_spec).is_none() {
+        return None;
+    }
+    Some(estimate_model_cost_usd(
+        Some(model_spec),
+        pricing.as_ref(),
+        usage.input_tokens + usage.cache_creation_input_tokens,
+        usage.output_tokens,
+        usage.cache_read_input_tokens,
+    ))
+}
+
 /// Parse token usage from an agent output.log, including mid-run data.
 ///
 /// First tries to find a `type=result` line (completed runs). If none exists,
@@ -1242,8 +1432,19 @@ pub fn parse_token_usage_live(output_log_path: &std::path::Path) -> Option<Token
     }
 
     if found_any {
+        // Claude/native per-turn messages don't include cumulative cost, so
+        // price their tokens from the model registry (0 when unknown).
+        if total_cost == 0.0 {
+            total_cost = estimate_model_cost_usd(
+                model_spec.as_deref(),
+                model_pricing.as_ref(),
+                total_input + total_cache_creation,
+                total_output,
+                total_cache_read,
+            );
+        }
         Some(TokenUsage {
-            cost_usd: total_cost, // Claude/native per-turn messages don't include cumulative cost
+            cost_usd: total_cost,
             input_tokens: total_input,
             output_tokens: total_output,
             cache_read_input_tokens: total_cache_read,
@@ -1484,6 +1685,8 @@ struct TaskHelper {
     #[serde(default, alias = "blocked_by")]
     after: Vec<String>,
     #[serde(default)]
+    edges: BTreeMap<String, DependencyEdge>,
+    #[serde(default)]
     requires: Vec<String>,
     #[serde(default)]
     tags: Vec<String>,
@@ -1494,14 +1697,28 @@ struct TaskHelper {
     #[serde(default)]
     deliverables: Vec<String>,
     #[serde(default)]
+    expects: Vec<String>,
+    #[serde(default)]
+    gate: Option<crate::gate::PhaseGate>,
+    #[serde(default)]
     artifacts: Vec<String>,
     #[serde(default)]
+    links: Vec<TaskLink>,
+    #[serde(default)]
+    waiting_on: Vec<String>,
+    #[serde(default)]
+    external_blocker: Option<ExternalBlocker>,
+    #[serde(default)]
+    self_report: Option<SelfReport>,
+    #[serde(default)]
     exec: Option<String>,
     #[serde(default)]
     timeout: Option<String>,
     #[serde(default)]
     not_before: Option<String>,
     #[serde(default)]
+    due: Option<String>,
+    #[serde(default)]
     created_at: Option<String>,
     #[serde(default)]
     started_at: Option<String>,
@@ -1510,6 +1727,8 @@ struct TaskHelper {
     #[serde(default)]
     last_interaction_at: Option<String>,
     #[serde(default)]
+    revision: u64,
+    #[serde(default)]
     log: Vec<LogEntry>,
     #[serde(default)]
     retry_count: u32,
@@ -1563,6 +1782,14 @@ struct TaskHelper {
     #[serde(default)]
     token_usage: Option<TokenUsage>,
     #[serde(default)]
+    max_cost: Option<f64>,
+    #[serde(default)]
+    min_context: Option<u64>,
+    #[serde(default)]
+    claim_sha: Option<String>,
+    #[serde(default)]
+    changes: Option<ChangeSummary>,
+    #[serde(default)]
     session_id: Option<String>,
     #[serde(default)]
     wait_condition: Option<WaitSpec>,
@@ -1571,6 +1798,12 @@ struct TaskHelper {
     #[serde(default)]
     triage_count: u32,
     #[serde(default)]
+    escalation_level: u32,
+    #[serde(default)]
+    blocked_at: Option<String>,
+    #[serde(default)]
+    deadline_alert: Option<crate::deadline::DeadlineAlert>,
+    #[serde(default)]
     resurrection_count: u32,
     #[serde(default)]
     last_resurrected_at: Option<String>,
@@ -1662,15 +1895,23 @@ impl<'de> Deserialize<'de> for Task {
             estimate: helper.estimate,
             before: helper.before,
             after: helper.after,
+            edges: helper.edges,
             requires: helper.requires,
             tags: helper.tags,
             skills: helper.skills,
             inputs: helper.inputs,
             deliverables: helper.deliverables,
+            expects: helper.expects,
+            gate: helper.gate,
             artifacts: helper.artifacts,
+            links: helper.links,
+            waiting_on: helper.waiting_on,
+            external_blocker: helper.external_blocker,
+            self_report: helper.self_report,
             exec: helper.exec,
             timeout: helper.timeout,
             not_before: helper.not_before,
+            due: helper.due,
             created_at: helper.created_at.clone(),
             started_at: helper.started_at,
             completed_at: helper.completed_at,
@@ -1680,6 +1921,7 @@ impl<'de> Deserialize<'de> for Task {
             last_interaction_at: helper
                 .last_interaction_at
                 .or_else(|| helper.created_at.clone()),
+            revision: helper.revision,
             log: helper.log,
             retry_count: helper.retry_count,
             max_retries: helper.max_retries,
@@ -1704,10 +1946,17 @@ impl<'de> Deserialize<'de> for Task {
             context_scope: helper.context_scope,
             exec_mode: helper.exec_mode,
             token_usage: helper.token_usage,
+            max_cost: helper.max_cost,
+            min_context: helper.min_context,
+            claim_sha: helper.claim_sha,
+            changes: helper.changes,
             session_id: helper.session_id,
             wait_condition: helper.wait_condition,
             checkpoint: helper.checkpoint,
             triage_count: helper.triage_count,
+            escalation_level: helper.escalation_level,
+            blocked_at: helper.blocked_at,
+            deadline_alert: helper.deadline_alert,
             resurrection_count: helper.resurrection_count,
             last_resurrected_at: helper.last_resurrected_at,
             validation: helper.validation,
@@ -1749,6 +1998,10 @@ fn is_zero(val: &u32) -> bool {
     *val == 0
 }
 
+fn is_zero_u64(val: &u64) -> bool {
+    *val == 0
+}
+
 fn is_bool_false(val: &bool) -> bool {
     !*val
 }
@@ -1766,6 +2019,90 @@ pub enum TrustLevel {
     Unknown,
 }
 
+/// How an agent says it completed a task: the method, not just the result
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SelfReport {
+    /// Summary of the approach taken
+    pub approach: String,
+    /// Steps in the order they were done
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<String>,
+    /// What went wrong or would trip up the next attempt
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pitfalls: Vec<String>,
+}
+
+/// What an external reference on a task points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    Issue,
+    Pr,
+    Doc,
+    Design,
+    Other,
+}
+
+impl LinkKind {
+    pub const ALL: [LinkKind; 5] = [
+        LinkKind::Issue,
+        LinkKind::Pr,
+        LinkKind::Doc,
+        LinkKind::Design,
+        LinkKind::Other,
+    ];
+
+    /// Parse a kind name (case-insensitive).
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.to_string().eq_ignore_ascii_case(s.trim()))
+    }
+}
+
+impl std::fmt::Display for LinkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            LinkKind::Issue => "issue",
+            LinkKind::Pr => "pr",
+            LinkKind::Doc => "doc",
+            LinkKind::Design => "design",
+            LinkKind::Other => "other",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// An external reference (issue, PR, doc, design) attached to a task.
+/// Unlike artifacts, links point at things the task relates to rather than
+/// things it produced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TaskLink {
+    #[serde(rename = "type")]
+    pub kind: LinkKind,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// What a task waits for outside the project (`wg edit --external-blocker`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExternalBlocker {
+    /// Free text: what is needed, from whom
+    pub reason: String,
+    /// Who to follow up with (person, team, email, channel)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact: Option<String>,
+    /// When the blocker is expected to clear (ISO 8601 / RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// When the blocker was recorded (ISO 8601 / RFC 3339)
+    pub since: String,
+    /// When the last follow-up reminder went out (ISO 8601 / RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_reminded_at: Option<String>,
+}
+
 /// A resource (budget, compute, etc.)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Resource {
@@ -1914,6 +2251,40 @@ pub struct WorkGraph {
     nodes: HashMap<String, Node>,
     /// Cached cycle analysis. Lazily computed; invalidated on structural mutations.
     cycle_analysis: Option<CycleAnalysis>,
+    /// What the graph file looked like when this graph was read from (or
+    /// last written to) it. See [`LoadBaseline`].
+    baseline: LoadBaseline,
+    /// Set by `parser::load_graph_subset`: only some nodes were loaded, so
+    /// the graph must not be saved.
+    partial: bool,
+}
+
+/// How one node looked when the graph was last read from or written to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NodeBaseline {
+    /// Hash of the in-memory node's JSON at that point; a different hash at
+    /// save time means this writer changed the node. `None` when it is the
+    /// same as `disk`, which is the case right after a load or save, so
+    /// loading doesn't have to serialize every node.
+    pub ours: Option<u64>,
+    /// Hash of the node's line in the file; a different line on disk at save
+    /// time means another writer changed the node.
+    pub disk: u64,
+    /// The task's revision on disk (0 for resources).
+    pub revision: u64,
+}
+
+/// Per-node snapshot taken by the parser so `save_graph` can tell this
+/// writer's edits apart from concurrent ones. Kept behind a mutex because
+/// `save_graph` only borrows the graph but must refresh it after writing.
+#[derive(Debug, Default)]
+pub(crate) struct LoadBaseline(Mutex<Option<(PathBuf, HashMap<String, NodeBaseline>)>>);
+
+impl Clone for LoadBaseline {
+    fn clone(&self) -> Self {
+        let inner = self.0.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        Self(Mutex::new(inner))
+    }
 }
 
 impl WorkGraph {
@@ -1921,9 +2292,35 @@ impl WorkGraph {
         Self {
             nodes: HashMap::new(),
             cycle_analysis: None,
+            baseline: LoadBaseline::default(),
+            partial: false,
         }
     }
 
+    /// Whether this graph holds only some of the nodes of its file (see
+    /// `parser::load_graph_subset`).
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+
+    pub(crate) fn mark_partial(&mut self) {
+        self.partial = true;
+    }
+
+    /// The file this graph was read from or written to, and each node's
+    /// state there.
+    pub(crate) fn baseline(&self) -> Option<(PathBuf, HashMap<String, NodeBaseline>)> {
+        self.baseline
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn set_baseline(&self, path: PathBuf, nodes: HashMap<String, NodeBaseline>) {
+        *self.baseline.0.lock().unwrap_or_else(|e| e.into_inner()) = Some((path, nodes));
+    }
+
     /// Insert a node (task or resource) into the graph.
     pub fn add_node(&mut self, node: Node) {
         self.cycle_analysis = None;
@@ -4050,6 +4447,25 @@ cache_read_discount = 0.5
         assert_eq!(default_task.priority, PRIORITY_DEFAULT);
     }
 
+    #[test]
+    fn test_aged_priority_boosts_one_tier_per_interval() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-03T01:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut task = Task {
+            id: "t".to_string(),
+            priority: PRIORITY_LOW,
+            created_at: Some("2026-01-01T00:00:00Z".to_string()),
+            ..Task::default()
+        };
+        // 49h old: two 24h intervals
+        assert_eq!(aged_priority(&task, now, 24), PRIORITY_HIGH);
+        assert_eq!(aged_priority(&task, now, 1), PRIORITY_CRITICAL);
+        assert_eq!(aged_priority(&task, now, 0), PRIORITY_LOW);
+        task.created_at = None;
+        assert_eq!(aged_priority(&task, now, 24), PRIORITY_LOW);
+    }
+
     #[test]
     fn test_priority_serde_migration_from_named_enum() {
         // Old graph.jsonl entries with string-enum values must deserialize to u32
@@ -0,0 +1,203 @@
+//! Task-level diff between two graph states, used by `wg diff`.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+use crate::graph::{Task, WorkGraph};
+
+/// Fields reported separately (status, dependencies) or too noisy to be
+/// worth listing as modifications.
+const IGNORED_FIELDS: &[&str] = &["id", "status", "after", "last_interaction_at"];
+
+/// A task present on only one side.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskRef {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+}
+
+/// A task whose status differs between the two states.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusChange {
+    pub id: String,
+    pub title: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Dependencies (`after` edges) added or removed on a task.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DependencyChange {
+    pub id: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<String>,
+}
+
+/// Other fields that changed on a task.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub id: String,
+    pub fields: Vec<String>,
+}
+
+/// Differences going from an `old` graph to a `new` one.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GraphDiff {
+    pub added: Vec<TaskRef>,
+    pub removed: Vec<TaskRef>,
+    pub status_changes: Vec<StatusChange>,
+    pub dependency_changes: Vec<DependencyChange>,
+    pub modified: Vec<FieldChange>,
+}
+
+impl GraphDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.status_changes.is_empty()
+            && self.dependency_changes.is_empty()
+            && self.modified.is_empty()
+    }
+}
+
+fn task_ref(task: &Task) -> TaskRef {
+    TaskRef {
+        id: task.id.clone(),
+        title: task.title.clone(),
+        status: task.status.to_string(),
+    }
+}
+
+fn changed_fields(old: &Task, new: &Task) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(a)), Ok(serde_json::Value::Object(b))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+    let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+    keys.into_iter()
+        .filter(|k| !IGNORED_FIELDS.contains(&k.as_str()) && a.get(*k) != b.get(*k))
+        .cloned()
+        .collect()
+}
+
+/// Compute the task-level diff from `old` to `new`, sorted by task ID.
+pub fn diff(old: &WorkGraph, new: &WorkGraph) -> GraphDiff {
+    let ids: BTreeSet<&str> = old
+        .tasks()
+        .chain(new.tasks())
+        .map(|t| t.id.as_str())
+        .collect();
+
+    let mut out = GraphDiff::default();
+    for id in ids {
+        match (old.get_task(id), new.get_task(id)) {
+            (None, Some(t)) => out.added.push(task_ref(t)),
+            (Some(t), None) => out.removed.push(task_ref(t)),
+            (Some(a), Some(b)) => {
+                if a.status != b.status {
+                    out.status_changes.push(StatusChange {
+                        id: id.to_string(),
+                        title: b.title.clone(),
+                        from: a.status.to_string(),
+                        to: b.status.to_string(),
+                    });
+                }
+                let added: Vec<String> = b
+                    .after
+                    .iter()
+                    .filter(|d| !a.after.contains(d))
+                    .cloned()
+                    .collect();
+                let removed: Vec<String> = a
+                    .after
+                    .iter()
+                    .filter(|d| !b.after.contains(d))
+                    .cloned()
+                    .collect();
+                if !added.is_empty() || !removed.is_empty() {
+                    out.dependency_changes.push(DependencyChange {
+                        id: id.to_string(),
+                        added,
+                        removed,
+                    });
+                }
+                let fields = changed_fields(a, b);
+                if !fields.is_empty() {
+                    out.modified.push(FieldChange {
+                        id: id.to_string(),
+                        fields,
+                    });
+                }
+            }
+            (None, None) => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Node, Status};
+
+    fn task(id: &str, status: Status, after: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            title: id.to_uppercase(),
+            status,
+            after: after.iter().map(|s| s.to_string()).collect(),
+            ..Task::default()
+        }
+    }
+
+    fn graph(tasks: Vec<Task>) -> WorkGraph {
+        let mut g = WorkGraph::new();
+        for t in tasks {
+            g.add_node(Node::Task(t));
+        }
+        g
+    }
+
+    #[test]
+    fn test_diff_reports_each_kind_of_change() {
+        let old = graph(vec![
+            task("a", Status::Open, &[]),
+            task("b", Status::Open, &["a"]),
+            task("gone", Status::Open, &[]),
+        ]);
+        let mut b = task("b", Status::Done, &["new"]);
+        b.description = Some("now described".to_string());
+        let new = graph(vec![
+            task("a", Status::Open, &[]),
+            b,
+            task("new", Status::Open, &[]),
+        ]);
+
+        let d = diff(&old, &new);
+        assert_eq!(d.added.len(), 1);
+        assert_eq!(d.added[0].id, "new");
+        assert_eq!(d.removed[0].id, "gone");
+        assert_eq!(
+            d.status_changes,
+            vec![StatusChange {
+                id: "b".to_string(),
+                title: "B".to_string(),
+                from: "open".to_string(),
+                to: "done".to_string(),
+            }]
+        );
+        assert_eq!(d.dependency_changes[0].added, vec!["new".to_string()]);
+        assert_eq!(d.dependency_changes[0].removed, vec!["a".to_string()]);
+        assert_eq!(d.modified[0].fields, vec!["description".to_string()]);
+    }
+
+    #[test]
+    fn test_identical_graphs_have_empty_diff() {
+        let g = graph(vec![task("a", Status::Open, &[])]);
+        assert!(diff(&g, &g.clone()).is_empty());
+    }
+}
@@ -0,0 +1,355 @@
+//! On-disk index of `graph.jsonl`: the byte range, status, tags and `after`
+//! edges of every node, so read-only commands such as `wg show` and
+//! `wg ready` can parse just the records they need instead of the whole file.
+//! Each entry also carries the line's hash and the task's revision, which
+//! `save_graph` compares with what the writer loaded instead of re-parsing
+//! the file.
+//!
+//! The index is stored next to the graph as `graph.index.json` and rewritten
+//! by the parser on every save. It records the size and mtime of the graph
+//! file it describes; an index that doesn't match (the graph was edited by
+//! hand or by an older `wg`) is rebuilt from a full scan on first use. Only a
+//! workgraph's own `graph.jsonl` is indexed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::graph::{Node, Status, is_agency_scaffold_task};
+use crate::parser::ParseError;
+
+/// File name of the index, next to `graph.jsonl`.
+pub const INDEX_FILE: &str = "graph.index.json";
+
+const INDEX_VERSION: u32 = 3;
+
+/// Where one node lives in `graph.jsonl`, plus the fields needed to choose
+/// which nodes to load.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Byte offset of the node's line.
+    pub offset: u64,
+    /// Length of the line in bytes, excluding the newline.
+    pub len: u64,
+    /// Hash of the line's content, compared by `save_graph` against the
+    /// hash taken at load time to spot concurrent edits.
+    #[serde(default)]
+    pub hash: u64,
+    /// The task's revision (0 for resources).
+    #[serde(default)]
+    pub revision: u64,
+    /// Task status; `None` for resources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
+}
+
+impl IndexEntry {
+    /// Entry for `node`, whose JSONL `line` (without the newline) starts at
+    /// byte `offset`.
+    pub fn for_node(node: &Node, offset: u64, line: &str) -> Self {
+        let len = line.len() as u64;
+        let hash = crate::parser::content_hash(line.trim());
+        match node {
+            Node::Task(t) => Self {
+                offset,
+                len,
+                hash,
+                revision: t.revision,
+                status: Some(t.status),
+                tags: t.tags.clone(),
+                after: t.after.clone(),
+            },
+            _ => Self {
+                offset,
+                len,
+                hash,
+                revision: 0,
+                status: None,
+                tags: Vec::new(),
+                after: Vec::new(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphIndex {
+    pub version: u32,
+    /// Size of the indexed graph file in bytes.
+    pub graph_len: u64,
+    /// Modification time of the indexed graph file, in nanoseconds since
+    /// the Unix epoch.
+    pub graph_mtime_ns: u64,
+    pub entries: BTreeMap<String, IndexEntry>,
+}
+
+/// Path of the index for a graph file, or `None` if the file isn't a
+/// workgraph's `graph.jsonl`.
+pub fn index_path(graph_path: &Path) -> Option<PathBuf> {
+    if graph_path.file_name()? != "graph.jsonl" {
+        return None;
+    }
+    Some(graph_path.with_file_name(INDEX_FILE))
+}
+
+/// Size and mtime of a graph file, used to tell whether an index is current.
+fn file_stamp(graph_path: &Path) -> Option<(u64, u64)> {
+    let meta = fs::metadata(graph_path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((meta.len(), mtime.as_nanos() as u64))
+}
+
+impl GraphIndex {
+    /// Read the index of `graph_path` if it exists and still describes the
+    /// file on disk.
+    pub fn load(graph_path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(index_path(graph_path)?).ok()?;
+        let index: Self = serde_json::from_str(&content).ok()?;
+        let (len, mtime) = file_stamp(graph_path)?;
+        (index.version == INDEX_VERSION && index.graph_len == len && index.graph_mtime_ns == mtime)
+            .then_some(index)
+    }
+
+    /// The current index of `graph_path`, rebuilding (and saving) it if it is
+    /// missing or stale. Returns `None` for files that aren't indexed.
+    pub fn open(graph_path: &Path) -> Result<Option<Self>, ParseError> {
+        if index_path(graph_path).is_none() {
+            return Ok(None);
+        }
+        if let Some(index) = Self::load(graph_path) {
+            return Ok(Some(index));
+        }
+        let entries = scan(graph_path)?;
+        // Best effort: a read-only directory just means rebuilding next time.
+        Ok(write(graph_path, entries).ok())
+    }
+
+    /// IDs of tasks in any of `statuses`.
+    pub fn ids_with_status(&self, statuses: &[Status]) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(_, e)| e.status.is_some_and(|s| statuses.contains(&s)))
+            .map(|(id, _)| id.as_str())
+            .collect()
+    }
+
+    /// IDs of tasks carrying `tag`.
+    pub fn ids_with_tag(&self, tag: &str) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(_, e)| e.tags.iter().any(|t| t == tag))
+            .map(|(id, _)| id.as_str())
+            .collect()
+    }
+
+    /// IDs of tasks that list `id` in their `after`.
+    pub fn dependents(&self, id: &str) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(_, e)| e.after.iter().any(|a| a == id))
+            .map(|(dep, _)| dep.as_str())
+            .collect()
+    }
+
+    /// The records `wg show <id>` needs: the task, its blockers and its
+    /// dependents.
+    pub fn neighbourhood(&self, id: &str) -> HashSet<String> {
+        let mut ids: HashSet<String> = self.dependents(id).into_iter().map(String::from).collect();
+        if let Some(entry) = self.entries.get(id) {
+            ids.extend(entry.after.iter().cloned());
+        }
+        ids.insert(id.to_string());
+        ids
+    }
+
+    /// The records needed to decide readiness: every open or incomplete
+    /// task, its blockers and their `.evaluate-*` gates, plus every member of
+    /// a dependency cycle involving any of those (with the members' own
+    /// blockers) so cycle back-edges are classified as in the full graph.
+    pub fn ready_candidates(&self) -> HashSet<String> {
+        let mut ids: HashSet<String> = HashSet::new();
+        for id in self.ids_with_status(&[Status::Open, Status::Incomplete]) {
+            ids.insert(id.to_string());
+            for blocker in &self.entries[id].after {
+                ids.insert(blocker.clone());
+                let gate = format!(".evaluate-{}", blocker);
+                if self.entries.contains_key(&gate) {
+                    ids.insert(gate);
+                }
+            }
+        }
+
+        let cycle_members: Vec<&str> = self
+            .cycles()
+            .into_iter()
+            .filter(|members| members.iter().any(|m| ids.contains(*m)))
+            .flatten()
+            .collect();
+        for member in cycle_members {
+            ids.insert(member.to_string());
+            ids.extend(self.entries[member].after.iter().cloned());
+        }
+        ids.retain(|id| self.entries.contains_key(id));
+        ids
+    }
+
+    /// Non-trivial strongly connected components of the `after` graph,
+    /// ignoring agency scaffolding as `CycleAnalysis` does.
+    fn cycles(&self) -> Vec<Vec<&str>> {
+        let ids: Vec<&str> = self
+            .entries
+            .keys()
+            .map(String::as_str)
+            .filter(|id| !is_agency_scaffold_task(id))
+            .collect();
+        let position: HashMap<&str, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let mut adj = vec![Vec::new(); ids.len()];
+        for (i, id) in ids.iter().enumerate() {
+            for blocker in &self.entries[*id].after {
+                if let Some(&b) = position.get(blocker.as_str()) {
+                    adj[b].push(i);
+                }
+            }
+        }
+        crate::cycle::tarjan_scc(ids.len(), &adj)
+            .into_iter()
+            .filter(|scc| scc.members.len() > 1)
+            .map(|scc| scc.members.into_iter().map(|m| ids[m]).collect())
+            .collect()
+    }
+}
+
+/// Index every node of a graph file by scanning it line by line.
+fn scan(graph_path: &Path) -> Result<BTreeMap<String, IndexEntry>, ParseError> {
+    let mut reader = BufReader::new(fs::File::open(graph_path)?);
+    let mut entries = BTreeMap::new();
+    let mut offset = 0u64;
+    let mut line = String::new();
+    let mut line_num = 0;
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)? as u64;
+        if read == 0 {
+            break;
+        }
+        line_num += 1;
+        let content = line.trim_end_matches(['\n', '\r']);
+        if let Some(node) = crate::parser::parse_node_line(content, line_num)? {
+            let entry = IndexEntry::for_node(&node, offset, content);
+            entries.insert(node.id().to_string(), entry);
+        }
+        offset += read;
+    }
+    Ok(entries)
+}
+
+/// Write the index for the current state of `graph_path`.
+pub(crate) fn write(
+    graph_path: &Path,
+    entries: BTreeMap<String, IndexEntry>,
+) -> Result<GraphIndex, ParseError> {
+    let path = index_path(graph_path)
+        .ok_or_else(|| ParseError::Io(std::io::Error::other("graph file is not indexed")))?;
+    let (graph_len, graph_mtime_ns) = file_stamp(graph_path)
+        .ok_or_else(|| ParseError::Io(std::io::Error::other("cannot stat graph file")))?;
+    let index = GraphIndex {
+        version: INDEX_VERSION,
+        graph_len,
+        graph_mtime_ns,
+        entries,
+    };
+    let json = serde_json::to_string(&index).map_err(ParseError::Serialize)?;
+    let tmp = path.with_file_name(format!(".{}.tmp.{}", INDEX_FILE, std::process::id()));
+    fs::write(&tmp, json)?;
+    fs::rename(&tmp, &path)?;
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::WorkGraph;
+    use crate::parser::{load_graph, load_graph_subset, save_graph};
+    use crate::test_helpers::make_task_with_status;
+    use tempfile::TempDir;
+
+    fn task(id: &str, status: Status, after: &[&str]) -> Node {
+        let mut t = make_task_with_status(id, id, status);
+        t.after = after.iter().map(|s| s.to_string()).collect();
+        Node::Task(t)
+    }
+
+    #[test]
+    fn test_index_written_on_save_and_rebuilt_when_stale() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        graph.add_node(task("a", Status::Done, &[]));
+        graph.add_node(task("b", Status::Open, &["a"]));
+        save_graph(&graph, &path).unwrap();
+
+        let index = GraphIndex::load(&path).unwrap();
+        assert_eq!(index.ids_with_status(&[Status::Open]), vec!["b"]);
+        assert_eq!(index.dependents("a"), vec!["b"]);
+
+        // A hand edit invalidates the index; `open` rebuilds it.
+        let mut content = fs::read_to_string(&path).unwrap();
+        content.push_str("{\"id\":\"c\",\"kind\":\"task\",\"title\":\"C\",\"status\":\"open\",\"tags\":[\"x\"]}\n");
+        fs::write(&path, content).unwrap();
+        assert!(GraphIndex::load(&path).is_none());
+        let rebuilt = GraphIndex::open(&path).unwrap().unwrap();
+        assert_eq!(rebuilt.ids_with_tag("x"), vec!["c"]);
+        assert_eq!(GraphIndex::load(&path).unwrap(), rebuilt);
+
+        // Other file names are never indexed.
+        assert!(
+            GraphIndex::open(&tmp.path().join("other.jsonl"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_subset_matches_full_graph() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        graph.add_node(task("root", Status::Done, &[]));
+        graph.add_node(task("x", Status::Open, &["root", "y"]));
+        graph.add_node(task("y", Status::Done, &["x"]));
+        graph.add_node(task("z", Status::Open, &["x"]));
+        for i in 0..50 {
+            graph.add_node(task(&format!("old-{}", i), Status::Done, &[]));
+        }
+        save_graph(&graph, &path).unwrap();
+
+        let full = load_graph(&path).unwrap();
+        let subset = load_graph_subset(&path, |index| index.ready_candidates()).unwrap();
+        assert!(subset.is_partial());
+        assert!(subset.get_task("old-3").is_none());
+        let ready = |g: &WorkGraph| {
+            let analysis = g.compute_cycle_analysis();
+            let mut ids: Vec<String> = crate::query::ready_tasks_cycle_aware(g, &analysis)
+                .into_iter()
+                .map(|t| t.id.clone())
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ready(&subset), ready(&full));
+
+        let around = load_graph_subset(&path, |index| index.neighbourhood("x")).unwrap();
+        let mut ids: Vec<&str> = around.tasks().map(|t| t.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["root", "x", "y", "z"]);
+        assert!(save_graph(&around, &path).is_err());
+    }
+}
@@ -0,0 +1,504 @@
+//! Three-way merge of `graph.jsonl` at the node level.
+//!
+//! Each node (task or resource) is merged independently against the common
+//! ancestor. A node changed on only one side takes that side's version. When
+//! both sides changed the same node, fields are merged one by one: list
+//! fields such as `after`, `tags` and `log` are merged as sets (keeping
+//! additions from both sides and honouring removals), and any other field
+//! changed differently on both sides is a conflict. A node deleted on one
+//! side and modified on the other is also a conflict.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::{Node, WorkGraph};
+
+/// List fields merged as sets rather than conflicting.
+const SET_FIELDS: &[&str] = &[
+    "after",
+    "before",
+    "requires",
+    "tags",
+    "skills",
+    "inputs",
+    "deliverables",
+    "expects",
+    "artifacts",
+    "log",
+    "tried_models",
+    "superseded_by",
+];
+
+/// Which side of a merge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Ours,
+    Theirs,
+}
+
+/// Why a node could not be merged automatically.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// Both sides changed these fields to different values
+    BothModified { fields: Vec<String> },
+    /// One side deleted the node, the other modified it
+    DeletedModified { deleted_in: Side },
+    /// Both sides added a node with this ID, with different content
+    BothAdded,
+}
+
+/// A node that needs manual resolution.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeConflict {
+    pub id: String,
+    #[serde(flatten)]
+    pub kind: ConflictKind,
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+}
+
+/// One line of merge output: a cleanly merged node or a conflict.
+#[derive(Debug, Clone)]
+pub enum MergedNode {
+    Clean(Value),
+    Conflict(NodeConflict),
+}
+
+/// Result of a three-way graph merge, in output order.
+#[derive(Debug, Clone, Default)]
+pub struct GraphMerge {
+    pub nodes: Vec<MergedNode>,
+}
+
+impl GraphMerge {
+    pub fn conflicts(&self) -> impl Iterator<Item = &NodeConflict> {
+        self.nodes.iter().filter_map(|n| match n {
+            MergedNode::Conflict(c) => Some(c),
+            MergedNode::Clean(_) => None,
+        })
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicts().next().is_some()
+    }
+
+    /// Render as JSONL, with git-style conflict markers around the two
+    /// versions of each conflicting node.
+    pub fn to_jsonl(&self, ours_label: &str, theirs_label: &str) -> String {
+        let mut out = String::new();
+        for node in &self.nodes {
+            match node {
+                MergedNode::Clean(v) => {
+                    out.push_str(&v.to_string());
+                    out.push('\n');
+                }
+                MergedNode::Conflict(c) => {
+                    out.push_str(&format!("<<<<<<< {}\n", ours_label));
+                    if let Some(ref v) = c.ours {
+                        out.push_str(&v.to_string());
+                        out.push('\n');
+                    }
+                    out.push_str("=======\n");
+                    if let Some(ref v) = c.theirs {
+                        out.push_str(&v.to_string());
+                        out.push('\n');
+                    }
+                    out.push_str(&format!(">>>>>>> {}\n", theirs_label));
+                }
+            }
+        }
+        out
+    }
+
+    /// Build the merged graph, resolving any remaining conflicts by taking
+    /// the given side's version of each conflicting node. Fails if there
+    /// are conflicts and no side is given.
+    pub fn into_graph(self, resolve: Option<Side>) -> Result<WorkGraph> {
+        let mut graph = WorkGraph::new();
+        for node in self.nodes {
+            let value = match node {
+                MergedNode::Clean(v) => Some(v),
+                MergedNode::Conflict(c) => match resolve {
+                    Some(Side::Ours) => c.ours,
+                    Some(Side::Theirs) => c.theirs,
+                    None => anyhow::bail!("Unresolved merge conflict on '{}'", c.id),
+                },
+            };
+            if let Some(value) = value {
+                let node: Node = serde_json::from_value(value)
+                    .context("Merged node is not a valid graph node")?;
+                graph.add_node(node);
+            }
+        }
+        Ok(graph)
+    }
+}
+
+/// A graph's nodes as `(id, node)` pairs, ready for [`merge`].
+pub fn graph_nodes(graph: &WorkGraph) -> Vec<(String, Value)> {
+    graph
+        .nodes()
+        .filter_map(|n| Some((n.id().to_string(), serde_json::to_value(n).ok()?)))
+        .collect()
+}
+
+/// Parse graph JSONL into `(id, node)` pairs in file order.
+///
+/// Blank lines and `#` comments are skipped. Conflict markers from an
+/// earlier unresolved merge are rejected.
+pub fn parse_nodes(content: &str) -> Result<Vec<(String, Value)>> {
+    let mut nodes = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let value: Value = serde_json::from_str(trimmed)
+            .with_context(|| format!("line {}: not a graph node", i + 1))?;
+        let id = value
+            .get("id")
+            .and_then(Value::as_str)
+            .with_context(|| format!("line {}: node has no id", i + 1))?
+            .to_string();
+        nodes.push((id, value));
+    }
+    Ok(nodes)
+}
+
+/// Merge two descendants of a common ancestor.
+pub fn merge(
+    base: &[(String, Value)],
+    ours: &[(String, Value)],
+    theirs: &[(String, Value)],
+) -> GraphMerge {
+    let base_map: HashMap<&str, &Value> = base.iter().map(|(id, v)| (id.as_str(), v)).collect();
+    let ours_map: HashMap<&str, &Value> = ours.iter().map(|(id, v)| (id.as_str(), v)).collect();
+    let theirs_map: HashMap<&str, &Value> = theirs.iter().map(|(id, v)| (id.as_str(), v)).collect();
+
+    // Output order: our nodes, then nodes only they have (including ones we
+    // deleted, so a delete/modify conflict shows up where theirs was).
+    let mut order: Vec<&str> = ours.iter().map(|(id, _)| id.as_str()).collect();
+    let mut seen: HashSet<&str> = order.iter().copied().collect();
+    for (id, _) in theirs.iter().chain(base.iter()) {
+        if seen.insert(id.as_str()) {
+            order.push(id.as_str());
+        }
+    }
+
+    let mut result = GraphMerge::default();
+    for id in order {
+        let b = base_map.get(id).copied();
+        let o = ours_map.get(id).copied();
+        let t = theirs_map.get(id).copied();
+        if let Some(node) = merge_node(id, b, o, t) {
+            result.nodes.push(node);
+        }
+    }
+    result
+}
+
+fn merge_node(
+    id: &str,
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+) -> Option<MergedNode> {
+    let conflict = |kind| {
+        Some(MergedNode::Conflict(NodeConflict {
+            id: id.to_string(),
+            kind,
+            ours: ours.cloned(),
+            theirs: theirs.cloned(),
+        }))
+    };
+
+    if ours == theirs {
+        return ours.map(|v| MergedNode::Clean(v.clone()));
+    }
+    if ours == base {
+        return theirs.map(|v| MergedNode::Clean(v.clone()));
+    }
+    if theirs == base {
+        return ours.map(|v| MergedNode::Clean(v.clone()));
+    }
+
+    match (base, ours, theirs) {
+        (Some(_), None, Some(_)) => conflict(ConflictKind::DeletedModified {
+            deleted_in: Side::Ours,
+        }),
+        (Some(_), Some(_), None) => conflict(ConflictKind::DeletedModified {
+            deleted_in: Side::Theirs,
+        }),
+        (_, Some(o), Some(t)) => {
+            let empty = Value::Object(Map::new());
+            match merge_fields(base.unwrap_or(&empty), o, t) {
+                Ok(merged) => Some(MergedNode::Clean(merged)),
+                Err(_) if base.is_none() => conflict(ConflictKind::BothAdded),
+                Err(fields) => conflict(ConflictKind::BothModified { fields }),
+            }
+        }
+        // Deleted on both sides (or never existed)
+        _ => None,
+    }
+}
+
+/// Field-by-field merge of two node objects. Returns the names of fields
+/// that conflict.
+fn merge_fields(base: &Value, ours: &Value, theirs: &Value) -> Result<Value, Vec<String>> {
+    let (Some(b), Some(o), Some(t)) = (base.as_object(), ours.as_object(), theirs.as_object())
+    else {
+        return Err(vec!["<node>".to_string()]);
+    };
+
+    let mut keys: Vec<&String> = o.keys().collect();
+    for k in t.keys().chain(b.keys()) {
+        if !keys.contains(&k) {
+            keys.push(k);
+        }
+    }
+
+    let mut merged = Map::new();
+    let mut conflicts = Vec::new();
+    for key in keys {
+        let bv = b.get(key);
+        let ov = o.get(key);
+        let tv = t.get(key);
+        let value = if ov == tv || tv == bv {
+            ov.cloned()
+        } else if ov == bv {
+            tv.cloned()
+        } else if SET_FIELDS.contains(&key.as_str()) {
+            Some(merge_set(key, bv, ov, tv))
+        } else if key == "last_interaction_at" {
+            // Bookkeeping timestamp: the later one wins
+            let later = match (ov.and_then(Value::as_str), tv.and_then(Value::as_str)) {
+                (Some(a), Some(b)) if b > a => tv,
+                (None, _) => tv,
+                _ => ov,
+            };
+            later.cloned()
+        } else if key == "revision" {
+            // Edit counter bumped independently on each side: the higher one
+            // wins
+            let rev = |v: Option<&Value>| v.and_then(Value::as_u64).unwrap_or(0);
+            if rev(tv) > rev(ov) {
+                tv.cloned()
+            } else {
+                ov.cloned()
+            }
+        } else {
+            conflicts.push(key.clone());
+            continue;
+        };
+        if let Some(v) = value {
+            merged.insert(key.clone(), v);
+        }
+    }
+
+    if conflicts.is_empty() {
+        Ok(Value::Object(merged))
+    } else {
+        Err(conflicts)
+    }
+}
+
+/// Set-merge a list field: keep everything either side has, except items
+/// one side removed from the base.
+fn merge_set(
+    key: &str,
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+) -> Value {
+    let items = |v: Option<&Value>| -> Vec<Value> {
+        v.and_then(Value::as_array).cloned().unwrap_or_default()
+    };
+    let base = items(base);
+    let ours = items(ours);
+    let theirs = items(theirs);
+    let removed = |side: &[Value]| -> Vec<Value> {
+        base.iter().filter(|v| !side.contains(v)).cloned().collect()
+    };
+    let removed_by_either: Vec<Value> =
+        removed(&ours).into_iter().chain(removed(&theirs)).collect();
+
+    let mut merged: Vec<Value> = Vec::new();
+    for v in ours.into_iter().chain(theirs) {
+        if !merged.contains(&v) && !removed_by_either.contains(&v) {
+            merged.push(v);
+        }
+    }
+    if key == "log" {
+        merged.sort_by(|a, b| {
+            let ts = |v: &Value| v.get("timestamp").and_then(Value::as_str).map(String::from);
+            ts(a).cmp(&ts(b))
+        });
+    }
+    Value::Array(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn nodes(values: Vec<Value>) -> Vec<(String, Value)> {
+        values
+            .into_iter()
+            .map(|v| (v["id"].as_str().unwrap().to_string(), v))
+            .collect()
+    }
+
+    fn clean(m: &GraphMerge) -> Vec<Value> {
+        m.nodes
+            .iter()
+            .filter_map(|n| match n {
+                MergedNode::Clean(v) => Some(v.clone()),
+                MergedNode::Conflict(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_disjoint_changes_merge_cleanly() {
+        let base = nodes(vec![
+            json!({"id": "a", "status": "open"}),
+            json!({"id": "b", "status": "open"}),
+        ]);
+        let ours = nodes(vec![
+            json!({"id": "a", "status": "done"}),
+            json!({"id": "b", "status": "open"}),
+        ]);
+        let theirs = nodes(vec![
+            json!({"id": "a", "status": "open"}),
+            json!({"id": "b", "status": "open", "title": "B"}),
+            json!({"id": "c", "status": "open"}),
+        ]);
+        let m = merge(&base, &ours, &theirs);
+        assert!(!m.has_conflicts());
+        assert_eq!(
+            clean(&m),
+            vec![
+                json!({"id": "a", "status": "done"}),
+                json!({"id": "b", "status": "open", "title": "B"}),
+                json!({"id": "c", "status": "open"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_same_task_different_fields_merge() {
+        let base = nodes(vec![
+            json!({"id": "a", "status": "open", "after": ["x", "y"]}),
+        ]);
+        let ours = nodes(vec![json!({"id": "a", "status": "done", "after": ["x"]})]);
+        let theirs = nodes(vec![
+            json!({"id": "a", "status": "open", "after": ["x", "y", "z"], "title": "T"}),
+        ]);
+        let m = merge(&base, &ours, &theirs);
+        assert!(!m.has_conflicts());
+        assert_eq!(
+            clean(&m),
+            vec![json!({"id": "a", "status": "done", "after": ["x", "z"], "title": "T"})]
+        );
+    }
+
+    #[test]
+    fn test_revision_takes_the_higher_side() {
+        let base = nodes(vec![json!({"id": "a", "status": "open", "revision": 3})]);
+        let ours = nodes(vec![json!({"id": "a", "status": "done", "revision": 4})]);
+        let theirs = nodes(vec![
+            json!({"id": "a", "status": "open", "title": "T", "revision": 6}),
+        ]);
+        let m = merge(&base, &ours, &theirs);
+        assert!(!m.has_conflicts());
+        assert_eq!(
+            clean(&m),
+            vec![json!({"id": "a", "status": "done", "revision": 6, "title": "T"})]
+        );
+    }
+
+    #[test]
+    fn test_log_entries_are_unioned_in_time_order() {
+        let e = |ts: &str, msg: &str| json!({"timestamp": ts, "message": msg});
+        let base = nodes(vec![json!({"id": "a", "log": [e("1", "start")]})]);
+        let ours = nodes(vec![
+            json!({"id": "a", "log": [e("1", "start"), e("3", "ours")]}),
+        ]);
+        let theirs = nodes(vec![
+            json!({"id": "a", "log": [e("1", "start"), e("2", "theirs")]}),
+        ]);
+        let m = merge(&base, &ours, &theirs);
+        assert_eq!(
+            clean(&m)[0]["log"],
+            json!([e("1", "start"), e("2", "theirs"), e("3", "ours")])
+        );
+    }
+
+    #[test]
+    fn test_true_conflicts_are_reported_per_task() {
+        let base = nodes(vec![
+            json!({"id": "a", "status": "open"}),
+            json!({"id": "b", "status": "open"}),
+        ]);
+        let ours = nodes(vec![json!({"id": "a", "status": "done"})]);
+        let theirs = nodes(vec![
+            json!({"id": "a", "status": "failed"}),
+            json!({"id": "b", "status": "in-progress"}),
+        ]);
+        let m = merge(&base, &ours, &theirs);
+        let conflicts: Vec<&NodeConflict> = m.conflicts().collect();
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(
+            conflicts[0].kind,
+            ConflictKind::BothModified {
+                fields: vec!["status".to_string()]
+            }
+        );
+        assert_eq!(
+            conflicts[1].kind,
+            ConflictKind::DeletedModified {
+                deleted_in: Side::Ours
+            }
+        );
+
+        let text = m.to_jsonl("ours", "theirs");
+        assert!(text.contains("<<<<<<< ours\n{\"id\":\"a\",\"status\":\"done\"}\n=======\n"));
+        assert!(parse_nodes(&text).is_err());
+    }
+
+    #[test]
+    fn test_into_graph_resolves_conflicts_by_side() {
+        use crate::graph::{Status, Task};
+        let task = |status| {
+            let mut g = WorkGraph::new();
+            g.add_node(Node::Task(Task {
+                id: "a".to_string(),
+                status,
+                ..Task::default()
+            }));
+            graph_nodes(&g)
+        };
+        let base = task(Status::Open);
+        let m = merge(&base, &task(Status::Done), &task(Status::Failed));
+        assert!(m.clone().into_graph(None).is_err());
+        let ours = m.clone().into_graph(Some(Side::Ours)).unwrap();
+        assert_eq!(ours.get_task("a").unwrap().status, Status::Done);
+        let theirs = m.into_graph(Some(Side::Theirs)).unwrap();
+        assert_eq!(theirs.get_task("a").unwrap().status, Status::Failed);
+    }
+
+    #[test]
+    fn test_deleted_on_one_side_unchanged_on_other() {
+        let base = nodes(vec![json!({"id": "a"}), json!({"id": "b"})]);
+        let ours = nodes(vec![json!({"id": "a"})]);
+        let theirs = nodes(vec![json!({"id": "a"}), json!({"id": "b"})]);
+        let m = merge(&base, &ours, &theirs);
+        assert!(!m.has_conflicts());
+        assert_eq!(clean(&m), vec![json!({"id": "a"})]);
+    }
+}
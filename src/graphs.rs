@@ -0,0 +1,187 @@
+//! Named graphs: several independent task graphs in one WG directory.
+//!
+//! The root `.wg/graph.jsonl` is the `main` graph. Every other graph lives
+//! in `.wg/graphs/<name>/` and is a complete WG directory of its own (graph,
+//! logs, service state), selected with `wg --graph <name>` or `$WG_GRAPH`.
+//! A named graph without its own `config.toml` uses the root's.
+//!
+//! Tasks can depend on tasks in a sibling graph with the cross-repo
+//! `after` syntax, `<graph>:<task-id>`; see `federation::resolve_peer`.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the root graph.
+pub const MAIN_GRAPH: &str = "main";
+
+/// Directory holding the named graphs of a workspace.
+pub fn graphs_dir(root: &Path) -> PathBuf {
+    root.join("graphs")
+}
+
+/// If `dir` is a named graph, the WG directory that contains it.
+pub fn workspace_root(dir: &Path) -> Option<PathBuf> {
+    let parent = dir.parent()?;
+    if parent.file_name()? != "graphs" {
+        return None;
+    }
+    let root = parent.parent()?;
+    let is_wg_dir = matches!(
+        root.file_name().and_then(|n| n.to_str()),
+        Some(".wg") | Some(".workgraph")
+    ) || root.join("graph.jsonl").is_file();
+    is_wg_dir.then(|| root.to_path_buf())
+}
+
+/// The name of the graph stored in `dir`.
+pub fn current_name(dir: &Path) -> String {
+    match workspace_root(dir) {
+        Some(_) => dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| MAIN_GRAPH.to_string()),
+        None => MAIN_GRAPH.to_string(),
+    }
+}
+
+/// Graph names are used as directory names and as the prefix of
+/// `<graph>:<task-id>` references, so they follow task ID rules.
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!(
+            "Invalid graph name '{}': use letters, digits, '-' and '_'",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Directory of the graph `name` in the workspace containing `dir`,
+/// whether or not it exists yet.
+pub fn graph_dir(dir: &Path, name: &str) -> Result<PathBuf> {
+    let root = workspace_root(dir).unwrap_or_else(|| dir.to_path_buf());
+    if name == MAIN_GRAPH {
+        return Ok(root);
+    }
+    validate_name(name)?;
+    Ok(graphs_dir(&root).join(name))
+}
+
+/// Resolve a sibling graph by name, if it exists.
+pub fn resolve(dir: &Path, name: &str) -> Option<PathBuf> {
+    let path = graph_dir(dir, name).ok()?;
+    path.join("graph.jsonl").is_file().then_some(path)
+}
+
+/// Names of all graphs in the workspace containing `dir`, `main` first.
+pub fn list(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let root = workspace_root(dir).unwrap_or_else(|| dir.to_path_buf());
+    let mut graphs = vec![(MAIN_GRAPH.to_string(), root.clone())];
+    let Ok(entries) = fs::read_dir(graphs_dir(&root)) else {
+        return Ok(graphs);
+    };
+    let mut named: Vec<(String, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.join("graph.jsonl").is_file())
+        .filter_map(|p| Some((p.file_name()?.to_string_lossy().to_string(), p)))
+        .collect();
+    named.sort();
+    graphs.extend(named);
+    Ok(graphs)
+}
+
+/// Create an empty named graph.
+pub fn create(dir: &Path, name: &str) -> Result<PathBuf> {
+    if name == MAIN_GRAPH {
+        anyhow::bail!("'{}' is the root graph and always exists", MAIN_GRAPH);
+    }
+    let path = graph_dir(dir, name)?;
+    if path.join("graph.jsonl").exists() {
+        anyhow::bail!("Graph '{}' already exists", name);
+    }
+    fs::create_dir_all(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+    fs::write(path.join("graph.jsonl"), "").context("Failed to create graph.jsonl")?;
+    Ok(path)
+}
+
+/// Delete a named graph and everything stored with it.
+pub fn remove(dir: &Path, name: &str) -> Result<PathBuf> {
+    if name == MAIN_GRAPH {
+        anyhow::bail!("Cannot remove the root graph '{}'", MAIN_GRAPH);
+    }
+    let path = resolve(dir, name).with_context(|| format!("Graph '{}' not found", name))?;
+    fs::remove_dir_all(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_list_resolve_remove() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join(".wg");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("graph.jsonl"), "").unwrap();
+
+        let refactor = create(&root, "refactor").unwrap();
+        assert!(create(&root, "refactor").is_err());
+        assert!(create(&root, "bad name").is_err());
+        assert_eq!(workspace_root(&refactor).unwrap(), root);
+        assert_eq!(current_name(&refactor), "refactor");
+        assert_eq!(current_name(&root), MAIN_GRAPH);
+
+        // Siblings resolve from either side.
+        assert_eq!(resolve(&refactor, MAIN_GRAPH).unwrap(), root);
+        assert_eq!(resolve(&root, "refactor").unwrap(), refactor);
+        assert!(resolve(&root, "missing").is_none());
+
+        let names: Vec<String> = list(&refactor).unwrap().into_iter().map(|g| g.0).collect();
+        assert_eq!(names, vec!["main", "refactor"]);
+
+        assert!(remove(&root, MAIN_GRAPH).is_err());
+        remove(&root, "refactor").unwrap();
+        assert!(!refactor.exists());
+    }
+
+    #[test]
+    fn test_cross_graph_dependency() {
+        use crate::graph::{Node, Status, WorkGraph};
+        use crate::parser::save_graph;
+        use crate::query::is_blocker_satisfied;
+        use crate::test_helpers::make_task_with_status;
+
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join(".wg");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("graph.jsonl"), "").unwrap();
+        let refactor = create(&root, "refactor").unwrap();
+
+        let mut other = WorkGraph::new();
+        other.add_node(Node::Task(make_task_with_status(
+            "cleanup",
+            "Cleanup",
+            Status::Open,
+        )));
+        save_graph(&other, refactor.join("graph.jsonl")).unwrap();
+
+        let main = WorkGraph::new();
+        assert!(!is_blocker_satisfied(
+            "refactor:cleanup",
+            &main,
+            Some(&root)
+        ));
+
+        other.get_task_mut("cleanup").unwrap().status = Status::Done;
+        save_graph(&other, refactor.join("graph.jsonl")).unwrap();
+        assert!(is_blocker_satisfied("refactor:cleanup", &main, Some(&root)));
+    }
+}
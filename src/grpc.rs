@@ -0,0 +1,12 @@
+//! Generated messages, server trait and client of the gRPC control API
+//! (`proto/workgraph/v1/control.proto`), served by
+//! `wg service start --grpc <addr>`.
+//!
+//! Rust orchestration code can drive the service through
+//! [`control_client::ControlClient`], adding the bearer token from
+//! `.wg/service/http.token` as `authorization` metadata on each request;
+//! other languages generate their client from the same proto.
+
+#![allow(clippy::all)]
+
+tonic::include_proto!("workgraph.v1");
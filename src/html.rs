@@ -35,7 +35,7 @@ use std::sync::OnceLock;
 
 use crate::chat::{self, ChatMessage};
 use crate::chat_id;
-use crate::graph::{Status, Task, WorkGraph};
+use crate::graph::{Status, Task, TaskLink, WorkGraph};
 use crate::messages::{self as msg_queue, CoordinatorMessageStatus, Message, MessageStats};
 use crate::parser::load_graph;
 
@@ -2392,6 +2392,15 @@ fn render_task_page(
             .join(", ");
         meta_rows.push(("Tags".into(), tags));
     }
+    if !task.links.is_empty() {
+        let links = task
+            .links
+            .iter()
+            .map(render_link)
+            .collect::<Vec<_>>()
+            .join("<br />");
+        meta_rows.push(("Links".into(), links));
+    }
     if let Some(usage) = &task.token_usage {
         meta_rows.push((
             "Tokens".into(),
@@ -2484,6 +2493,22 @@ fn render_task_page(
     )
 }
 
+/// One external link as `type: <a>label-or-url</a>`. Only http(s) URLs
+/// become anchors, in case the graph was edited by hand.
+fn render_link(link: &TaskLink) -> String {
+    let text = escape_html(link.label.as_deref().unwrap_or(&link.url));
+    let body = if link.url.starts_with("https://") || link.url.starts_with("http://") {
+        format!(
+            "<a href=\"{}\" rel=\"noopener noreferrer\">{}</a>",
+            escape_html(&link.url),
+            text
+        )
+    } else {
+        text
+    };
+    format!("<span class=\"note\">{}</span> {}", link.kind, body)
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // Chat transcript rendering (`--chat` flag)
 // ────────────────────────────────────────────────────────────────────────────
@@ -2673,6 +2698,25 @@ mod tests {
         assert_eq!(strip_ansi(s), "hello world");
     }
 
+    #[test]
+    fn render_link_hyperlinks_only_web_urls() {
+        let link = TaskLink {
+            kind: crate::graph::LinkKind::Issue,
+            url: "https://example.com/issues/1?a=1&b=2".to_string(),
+            label: Some("<Bug>".to_string()),
+        };
+        assert_eq!(
+            render_link(&link),
+            "<span class=\"note\">issue</span> <a href=\"https://example.com/issues/1?a=1&amp;b=2\" rel=\"noopener noreferrer\">&lt;Bug&gt;</a>"
+        );
+        let link = TaskLink {
+            url: "javascript:alert(1)".to_string(),
+            label: None,
+            ..link
+        };
+        assert!(!render_link(&link).contains("<a "));
+    }
+
     #[test]
     fn strip_ansi_preserves_unicode() {
         let s = "\x1b[36m├→\x1b[0m foo";
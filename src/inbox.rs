@@ -0,0 +1,476 @@
+//! Triage inbox for externally submitted work.
+//!
+//! Requests that arrive from outside the CLI — the service `submit_request`
+//! IPC call, `POST /api/inbox` on the HTTP API, Matrix `!wg request ...`, or
+//! an email piped to `wg inbox add --email` — land as paused draft tasks tagged [`INBOX_TAG`].
+//! Drafts are never dispatched; `wg triage inbox` accepts (optionally
+//! splitting into subtasks), rejects, or merges them into existing tasks.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::path::Path;
+
+use crate::graph::{LogEntry, Node, Status, Task, WorkGraph};
+use crate::parser::modify_graph;
+
+/// Tag carried by every untriaged draft.
+pub const INBOX_TAG: &str = "inbox";
+
+/// Prefix of the tag recording where a draft came from (`source:matrix`).
+pub const SOURCE_TAG_PREFIX: &str = "source:";
+
+/// Titles derived from free text are cut to this many characters.
+const TITLE_MAX_CHARS: usize = 80;
+
+/// An externally submitted request.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Submission {
+    /// Channel it arrived on (`matrix`, `email`, `ipc`, `http`, `cli`)
+    pub source: String,
+    pub submitter: Option<String>,
+    pub title: String,
+    pub body: Option<String>,
+}
+
+impl Submission {
+    /// Build a submission from free text: the first line becomes the title
+    /// and the full text is kept as the body when there is more to it.
+    pub fn from_text(source: &str, submitter: Option<&str>, text: &str) -> Option<Self> {
+        let text = text.trim();
+        let first = text.lines().next()?.trim();
+        if first.is_empty() {
+            return None;
+        }
+        let title = if first.chars().count() > TITLE_MAX_CHARS {
+            let cut: String = first.chars().take(TITLE_MAX_CHARS - 3).collect();
+            format!("{}...", cut.trim_end())
+        } else {
+            first.to_string()
+        };
+        let body = (title != text).then(|| text.to_string());
+        Some(Self {
+            source: source.to_string(),
+            submitter: submitter.map(String::from),
+            title,
+            body,
+        })
+    }
+}
+
+/// Parse an RFC 822 message: `Subject` is the title, `From` the submitter
+/// and everything after the header block the body. Folded header lines are
+/// joined; MIME multipart bodies are not decoded.
+pub fn parse_email(raw: &str) -> Option<Submission> {
+    let raw = raw.replace("\r\n", "\n");
+    let (headers, body) = raw.split_once("\n\n").unwrap_or((raw.as_str(), ""));
+
+    let mut unfolded: Vec<String> = Vec::new();
+    for line in headers.lines() {
+        if line.starts_with([' ', '\t'])
+            && let Some(last) = unfolded.last_mut()
+        {
+            last.push(' ');
+            last.push_str(line.trim());
+        } else {
+            unfolded.push(line.to_string());
+        }
+    }
+    let header = |name: &str| {
+        unfolded.iter().find_map(|l| {
+            let (k, v) = l.split_once(':')?;
+            k.trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| v.trim().to_string())
+        })
+    };
+
+    let body = body.trim();
+    let title = header("subject").filter(|s| !s.is_empty());
+    let mut submission = match title {
+        Some(title) => Submission {
+            source: "email".to_string(),
+            submitter: None,
+            title,
+            body: (!body.is_empty()).then(|| body.to_string()),
+        },
+        None => Submission::from_text("email", None, body)?,
+    };
+    submission.submitter = header("from").filter(|s| !s.is_empty());
+    Some(submission)
+}
+
+/// Whether a task is an untriaged inbox draft.
+pub fn is_draft(task: &Task) -> bool {
+    task.tags.iter().any(|t| t == INBOX_TAG) && !task.status.is_terminal()
+}
+
+/// Where a draft came from, from its `source:` tag.
+pub fn source_of(task: &Task) -> Option<&str> {
+    task.tags
+        .iter()
+        .find_map(|t| t.strip_prefix(SOURCE_TAG_PREFIX))
+}
+
+/// Untriaged drafts, oldest first.
+pub fn drafts(graph: &WorkGraph) -> Vec<&Task> {
+    let mut drafts: Vec<&Task> = graph.tasks().filter(|t| is_draft(t)).collect();
+    drafts.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+    drafts
+}
+
+/// A unique task id built from up to four words of `title`.
+fn unique_id(prefix: &str, title: &str, graph: &WorkGraph) -> String {
+    let normalized: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    let words: Vec<&str> = normalized
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .take(4)
+        .collect();
+    let base = if words.is_empty() {
+        format!("{}task", prefix)
+    } else {
+        format!("{}{}", prefix, words.join("-"))
+    };
+    if graph.get_node(&base).is_none() {
+        return base;
+    }
+    (2..)
+        .map(|i| format!("{}-{}", base, i))
+        .find(|id| graph.get_node(id).is_none())
+        .expect("unbounded search finds a free id")
+}
+
+fn log_entry(message: String) -> LogEntry {
+    LogEntry {
+        timestamp: Utc::now().to_rfc3339(),
+        actor: None,
+        user: Some(crate::current_user()),
+        message,
+    }
+}
+
+/// Add a submission to the inbox as a paused draft. Returns the draft id.
+pub fn submit(dir: &Path, submission: &Submission) -> Result<String> {
+    if submission.title.trim().is_empty() {
+        anyhow::bail!("Inbox request needs a title");
+    }
+    let graph_path = dir.join("graph.jsonl");
+    if !graph_path.exists() {
+        anyhow::bail!("WG not initialized. Run 'wg init' first.");
+    }
+
+    let mut draft_id = String::new();
+    modify_graph(&graph_path, |graph| {
+        draft_id = unique_id("req-", &submission.title, graph);
+        let via = match submission.submitter {
+            Some(ref who) => format!("Submitted via {} by {}", submission.source, who),
+            None => format!("Submitted via {}", submission.source),
+        };
+        graph.add_node(Node::Task(Task {
+            id: draft_id.clone(),
+            title: submission.title.clone(),
+            description: submission.body.clone(),
+            tags: vec![
+                INBOX_TAG.to_string(),
+                format!("{}{}", SOURCE_TAG_PREFIX, submission.source),
+            ],
+            paused: true,
+            created_at: Some(Utc::now().to_rfc3339()),
+            log: vec![log_entry(via)],
+            ..Task::default()
+        }));
+        true
+    })
+    .context("Failed to save graph")?;
+    Ok(draft_id)
+}
+
+fn draft_mut<'a>(graph: &'a mut WorkGraph, id: &str) -> Result<&'a mut Task> {
+    let task = graph
+        .get_task_mut(id)
+        .with_context(|| format!("Task '{}' not found", id))?;
+    if !is_draft(task) {
+        anyhow::bail!("Task '{}' is not in the inbox", id);
+    }
+    Ok(task)
+}
+
+/// Accept a draft: it leaves the inbox and becomes dispatchable. Each title
+/// in `split` becomes a subtask that inherits the draft's dependencies; the
+/// draft then waits on all of them. Returns the subtask ids.
+pub fn accept(dir: &Path, id: &str, split: &[String]) -> Result<Vec<String>> {
+    let graph_path = dir.join("graph.jsonl");
+    let mut result: Result<Vec<String>> = Ok(Vec::new());
+    modify_graph(&graph_path, |graph| {
+        let (after, tags) = match draft_mut(graph, id) {
+            Ok(draft) => (
+                draft.after.clone(),
+                draft
+                    .tags
+                    .iter()
+                    .filter(|t| *t != INBOX_TAG)
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            ),
+            Err(e) => {
+                result = Err(e);
+                return false;
+            }
+        };
+
+        let mut created = Vec::new();
+        for title in split.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            let child_id = unique_id("", title, graph);
+            graph.add_node(Node::Task(Task {
+                id: child_id.clone(),
+                title: title.to_string(),
+                after: after.clone(),
+                tags: tags.clone(),
+                created_at: Some(Utc::now().to_rfc3339()),
+                log: vec![log_entry(format!("Split from inbox request '{}'", id))],
+                ..Task::default()
+            }));
+            created.push(child_id);
+        }
+
+        let draft = graph.get_task_mut(id).expect("checked above");
+        draft.tags = tags;
+        draft.paused = false;
+        draft.after.extend(created.iter().cloned());
+        draft.log.push(log_entry(if created.is_empty() {
+            "Accepted from inbox".to_string()
+        } else {
+            format!("Accepted from inbox, split into {}", created.join(", "))
+        }));
+        result = Ok(created);
+        true
+    })
+    .context("Failed to save graph")?;
+    result
+}
+
+/// Reject a draft: it leaves the inbox as abandoned.
+pub fn reject(dir: &Path, id: &str, reason: Option<&str>) -> Result<()> {
+    let graph_path = dir.join("graph.jsonl");
+    let mut result = Ok(());
+    modify_graph(&graph_path, |graph| match draft_mut(graph, id) {
+        Ok(draft) => {
+            draft.tags.retain(|t| t != INBOX_TAG);
+            draft.paused = false;
+            draft.status = Status::Abandoned;
+            draft.failure_reason = reason.map(String::from);
+            draft.log.push(log_entry(match reason {
+                Some(r) => format!("Rejected from inbox: {}", r),
+                None => "Rejected from inbox".to_string(),
+            }));
+            true
+        }
+        Err(e) => {
+            result = Err(e);
+            false
+        }
+    })
+    .context("Failed to save graph")?;
+    result
+}
+
+/// Merge a draft into an existing task: the request text is appended to the
+/// target's description and the draft is abandoned as superseded by it.
+pub fn merge(dir: &Path, id: &str, into: &str) -> Result<()> {
+    if id == into {
+        anyhow::bail!("Cannot merge '{}' into itself", id);
+    }
+    let graph_path = dir.join("graph.jsonl");
+    let mut result = Ok(());
+    modify_graph(&graph_path, |graph| {
+        if graph.get_task(into).is_none() {
+            result = Err(anyhow::anyhow!("Target task '{}' not found", into));
+            return false;
+        }
+        let (title, body, source) = match draft_mut(graph, id) {
+            Ok(draft) => {
+                draft.tags.retain(|t| t != INBOX_TAG);
+                draft.paused = false;
+                draft.status = Status::Abandoned;
+                draft.superseded_by = vec![into.to_string()];
+                draft
+                    .log
+                    .push(log_entry(format!("Merged from inbox into '{}'", into)));
+                (
+                    draft.title.clone(),
+                    draft.description.clone(),
+                    source_of(draft).unwrap_or("inbox").to_string(),
+                )
+            }
+            Err(e) => {
+                result = Err(e);
+                return false;
+            }
+        };
+
+        let target = graph.get_task_mut(into).expect("checked above");
+        let mut section = format!("Merged request '{}' ({}): {}", id, source, title);
+        if let Some(body) = body {
+            section.push_str("\n\n");
+            section.push_str(&body);
+        }
+        target.description = Some(match target.description.take() {
+            Some(existing) if !existing.trim().is_empty() => {
+                format!("{}\n\n---\n{}", existing.trim_end(), section)
+            }
+            _ => section,
+        });
+        target
+            .log
+            .push(log_entry(format!("Merged inbox request '{}'", id)));
+        true
+    })
+    .context("Failed to save graph")?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{load_graph, save_graph};
+
+    fn setup(graph: &WorkGraph) -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        save_graph(graph, tmp.path().join("graph.jsonl")).unwrap();
+        tmp
+    }
+
+    fn load(dir: &Path) -> WorkGraph {
+        load_graph(dir.join("graph.jsonl")).unwrap()
+    }
+
+    fn text(title: &str, body: Option<&str>) -> Submission {
+        Submission {
+            source: "matrix".to_string(),
+            submitter: Some("alice".to_string()),
+            title: title.to_string(),
+            body: body.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_from_text_and_parse_email() {
+        let s = Submission::from_text("matrix", Some("bob"), "Fix login\nIt crashes on submit")
+            .unwrap();
+        assert_eq!(s.title, "Fix login");
+        assert_eq!(s.body.as_deref(), Some("Fix login\nIt crashes on submit"));
+        assert!(
+            Submission::from_text("matrix", None, "Short")
+                .unwrap()
+                .body
+                .is_none()
+        );
+        assert!(Submission::from_text("matrix", None, "   ").is_none());
+        let long = "x".repeat(200);
+        assert_eq!(
+            Submission::from_text("cli", None, &long)
+                .unwrap()
+                .title
+                .len(),
+            80
+        );
+
+        let mail = "From: Carol <carol@example.com>\r\nSubject: Export is\r\n  too slow\r\n\r\nTakes 5 minutes.\r\n";
+        let s = parse_email(mail).unwrap();
+        assert_eq!(s.source, "email");
+        assert_eq!(s.title, "Export is too slow");
+        assert_eq!(s.submitter.as_deref(), Some("Carol <carol@example.com>"));
+        assert_eq!(s.body.as_deref(), Some("Takes 5 minutes."));
+    }
+
+    #[test]
+    fn test_submit_creates_paused_draft() {
+        let tmp = setup(&WorkGraph::new());
+        let id = submit(tmp.path(), &text("Add dark mode", Some("Please"))).unwrap();
+        let id2 = submit(tmp.path(), &text("Add dark mode", None)).unwrap();
+        assert_eq!(id, "req-add-dark-mode");
+        assert_eq!(id2, "req-add-dark-mode-2");
+
+        let graph = load(tmp.path());
+        let draft = graph.get_task(&id).unwrap();
+        assert!(draft.paused);
+        assert_eq!(source_of(draft), Some("matrix"));
+        assert!(draft.log[0].message.contains("by alice"));
+        assert_eq!(drafts(&graph).len(), 2);
+    }
+
+    #[test]
+    fn test_accept_with_split() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(Task {
+            id: "base".to_string(),
+            ..Task::default()
+        }));
+        let tmp = setup(&graph);
+        let id = submit(tmp.path(), &text("Billing export", None)).unwrap();
+        modify_graph(tmp.path().join("graph.jsonl"), |g| {
+            g.get_task_mut(&id).unwrap().after = vec!["base".to_string()];
+            true
+        })
+        .unwrap();
+
+        let created = accept(
+            tmp.path(),
+            &id,
+            &["Export CSV".to_string(), "Export PDF".to_string()],
+        )
+        .unwrap();
+        assert_eq!(created, vec!["export-csv", "export-pdf"]);
+
+        let graph = load(tmp.path());
+        let parent = graph.get_task(&id).unwrap();
+        assert!(!parent.paused);
+        assert!(!is_draft(parent));
+        assert_eq!(parent.after, vec!["base", "export-csv", "export-pdf"]);
+        let child = graph.get_task("export-csv").unwrap();
+        assert_eq!(child.after, vec!["base"]);
+        assert_eq!(child.tags, vec!["source:matrix"]);
+
+        assert!(accept(tmp.path(), &id, &[]).is_err());
+    }
+
+    #[test]
+    fn test_reject_and_merge() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(Task {
+            id: "search".to_string(),
+            title: "Search".to_string(),
+            description: Some("Full-text search".to_string()),
+            ..Task::default()
+        }));
+        let tmp = setup(&graph);
+        let spam = submit(tmp.path(), &text("Buy now", None)).unwrap();
+        let dup = submit(tmp.path(), &text("Search by tag", Some("Need tag filter"))).unwrap();
+
+        reject(tmp.path(), &spam, Some("spam")).unwrap();
+        assert!(merge(tmp.path(), &dup, "missing").is_err());
+        merge(tmp.path(), &dup, "search").unwrap();
+
+        let graph = load(tmp.path());
+        let spam = graph.get_task(&spam).unwrap();
+        assert_eq!(spam.status, Status::Abandoned);
+        assert_eq!(spam.failure_reason.as_deref(), Some("spam"));
+        let dup_task = graph.get_task(&dup).unwrap();
+        assert_eq!(dup_task.superseded_by, vec!["search"]);
+        let target = graph
+            .get_task("search")
+            .unwrap()
+            .description
+            .clone()
+            .unwrap();
+        assert!(target.starts_with(
+            "Full-text search\n\n---\nMerged request 'req-search-by-tag' (matrix): Search by tag"
+        ));
+        assert!(target.ends_with("Need tag filter"));
+        assert!(drafts(&graph).is_empty());
+    }
+}
@@ -0,0 +1,724 @@
+//! Undo/redo journal for graph mutations.
+//!
+//! Every substantive `modify_graph` write appends an entry to
+//! `.wg/log/journal.jsonl` holding the before and after state of each task it
+//! touched, which is enough to compute the inverse. `wg undo` re-applies the
+//! before states of the newest change and `wg redo` re-applies the after
+//! states; both are themselves appended as `undo`/`redo` entries so the
+//! undo and redo stacks can be rebuilt from the file alone. A new change
+//! clears the redo stack of whoever made it, as in an editor.
+//!
+//! Each entry records the user and, for agents and the service daemon, the
+//! actor that made it. `wg undo` and `wg redo` step through the caller's own
+//! changes, so a user's undo doesn't revert a coordinator write that landed
+//! after theirs; `--any` steps through everyone's.
+//!
+//! The last sequence number and entry count are kept in
+//! `.wg/log/journal.seq`, so recording a change (which happens with the graph
+//! lock held) appends one line instead of re-reading the journal.
+//!
+//! The same entries let [`state_at`] wind the graph back to an earlier time
+//! for `wg at`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::graph::{Node, Task, WorkGraph};
+use crate::parser::modify_graph_without_journal;
+
+/// Entries kept when the journal is compacted.
+pub const MAX_ENTRIES: usize = 500;
+
+/// Before/after state of one task. `None` means the task did not exist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskChange {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    /// A graph mutation
+    Change,
+    /// The change `target` was reverted
+    Undo,
+    /// The change `target` was re-applied
+    Redo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub seq: u64,
+    pub timestamp: String,
+    pub kind: EntryKind,
+    /// Command line that made the change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<TaskChange>,
+    /// For undo/redo entries, the `seq` of the change they act on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<u64>,
+    /// User who made the entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Agent or service that made the entry on the user's behalf
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+}
+
+impl JournalEntry {
+    /// IDs of the tasks this entry touches.
+    pub fn task_ids(&self) -> Vec<&str> {
+        self.changes.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    /// Who made the entry, e.g. `alice` or `alice (service)`.
+    pub fn owner(&self) -> String {
+        let user = self.user.as_deref().unwrap_or("unknown");
+        match &self.actor {
+            Some(actor) => format!("{} ({})", user, actor),
+            None => user.to_string(),
+        }
+    }
+
+    /// Whether `owner` made the entry. Entries recorded before ownership
+    /// was tracked belong to everyone.
+    pub fn is_owned_by(&self, owner: &Owner) -> bool {
+        self.user.is_none() || (self.user == owner.user && self.actor == owner.actor)
+    }
+}
+
+/// The user and actor journal entries are attributed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Owner {
+    pub user: Option<String>,
+    pub actor: Option<String>,
+}
+
+impl Owner {
+    /// The owner of changes made by this process.
+    pub fn current() -> Self {
+        Owner {
+            user: Some(crate::current_user()),
+            actor: ACTOR
+                .get()
+                .cloned()
+                .or_else(|| std::env::var("WG_AGENT_ID").ok()),
+        }
+    }
+}
+
+static ACTOR: OnceLock<String> = OnceLock::new();
+
+/// Attribute this process's journal entries to `actor` (e.g. the service
+/// daemon). Agents are attributed by `WG_AGENT_ID` without calling this.
+pub fn set_actor(actor: &str) {
+    let _ = ACTOR.set(actor.to_string());
+}
+
+/// Whose changes `wg undo` and `wg redo` step through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+    /// Only changes made by this owner
+    Own(Owner),
+    /// Everyone's changes
+    Any,
+}
+
+impl Scope {
+    fn includes(&self, entry: &JournalEntry) -> bool {
+        match self {
+            Scope::Own(owner) => entry.is_owned_by(owner),
+            Scope::Any => true,
+        }
+    }
+}
+
+/// Return the path to the journal: `.wg/log/journal.jsonl`
+pub fn journal_path(workgraph_dir: &Path) -> PathBuf {
+    crate::provenance::log_dir(workgraph_dir).join("journal.jsonl")
+}
+
+/// Load all journal entries, oldest first. A missing journal is empty.
+pub fn load(workgraph_dir: &Path) -> Result<Vec<JournalEntry>> {
+    let path = journal_path(workgraph_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+/// Rebuild the undo and redo stacks (change seqs, top last).
+pub fn stacks(entries: &[JournalEntry]) -> (Vec<u64>, Vec<u64>) {
+    let by_seq: HashMap<u64, &JournalEntry> = entries
+        .iter()
+        .filter(|e| e.kind == EntryKind::Change)
+        .map(|e| (e.seq, e))
+        .collect();
+    let mut undo = Vec::new();
+    let mut redo = Vec::new();
+    for entry in entries {
+        match (entry.kind, entry.target) {
+            (EntryKind::Change, _) => {
+                undo.push(entry.seq);
+                // A new change only clears its owner's redo stack.
+                redo.retain(|s| {
+                    by_seq
+                        .get(s)
+                        .is_some_and(|e| (&e.user, &e.actor) != (&entry.user, &entry.actor))
+                });
+            }
+            (EntryKind::Undo, Some(target)) => {
+                undo.retain(|s| *s != target);
+                redo.push(target);
+            }
+            (EntryKind::Redo, Some(target)) => {
+                redo.retain(|s| *s != target);
+                undo.push(target);
+            }
+            _ => {}
+        }
+    }
+    (undo, redo)
+}
+
+fn current_command() -> String {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    format!("wg {}", args.join(" ")).trim_end().to_string()
+}
+
+/// Last sequence number and entry count, kept beside the journal.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SeqState {
+    last_seq: u64,
+    entries: usize,
+}
+
+fn seq_path(workgraph_dir: &Path) -> PathBuf {
+    crate::provenance::log_dir(workgraph_dir).join("journal.seq")
+}
+
+fn load_seq(workgraph_dir: &Path) -> Result<SeqState> {
+    if !journal_path(workgraph_dir).exists() {
+        return Ok(SeqState::default());
+    }
+    if let Ok(content) = fs::read_to_string(seq_path(workgraph_dir))
+        && let Ok(state) = serde_json::from_str(&content)
+    {
+        return Ok(state);
+    }
+    // No sidecar yet (or it was lost): rebuild it from the journal once.
+    let entries = load(workgraph_dir)?;
+    Ok(SeqState {
+        last_seq: entries.last().map_or(0, |e| e.seq),
+        entries: entries.len(),
+    })
+}
+
+/// Append `entry` with the next sequence number, compacting the journal
+/// once it reaches twice [`MAX_ENTRIES`]. Returns the sequence number.
+fn append(workgraph_dir: &Path, mut entry: JournalEntry) -> Result<u64> {
+    let path = journal_path(workgraph_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut state = load_seq(workgraph_dir)?;
+    entry.seq = state.last_seq + 1;
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    state.last_seq = entry.seq;
+    state.entries += 1;
+
+    if state.entries >= MAX_ENTRIES * 2 {
+        // Compact: keep the newest entries and rewrite the file.
+        let entries = load(workgraph_dir)?;
+        let keep = &entries[entries.len().saturating_sub(MAX_ENTRIES)..];
+        let mut content = String::new();
+        for e in keep {
+            content.push_str(&serde_json::to_string(e)?);
+            content.push('\n');
+        }
+        fs::write(&path, content)?;
+        state.entries = keep.len();
+    }
+    fs::write(seq_path(workgraph_dir), serde_json::to_string(&state)?)?;
+    Ok(entry.seq)
+}
+
+fn new_entry(kind: EntryKind, changes: Vec<TaskChange>, target: Option<u64>) -> JournalEntry {
+    let owner = Owner::current();
+    JournalEntry {
+        seq: 0,
+        timestamp: Utc::now().to_rfc3339(),
+        kind,
+        command: Some(current_command()),
+        changes,
+        target,
+        user: owner.user,
+        actor: owner.actor,
+    }
+}
+
+/// Record the tasks that changed between `before` and `after`.
+///
+/// Called by `modify_graph` with the graph lock held. Changes touching only
+/// `last_interaction_at` are not recorded.
+pub(crate) fn record(
+    workgraph_dir: &Path,
+    before: &HashMap<String, Task>,
+    after: &WorkGraph,
+) -> Result<()> {
+    let mut changes = Vec::new();
+    for task in after.tasks() {
+        match before.get(&task.id) {
+            Some(prev) if prev.substantively_eq(task) => {}
+            prev => changes.push(TaskChange {
+                id: task.id.clone(),
+                before: prev.map(serde_json::to_value).transpose()?,
+                after: Some(serde_json::to_value(task)?),
+            }),
+        }
+    }
+    for (id, prev) in before {
+        if after.get_task(id).is_none() {
+            changes.push(TaskChange {
+                id: id.clone(),
+                before: Some(serde_json::to_value(prev)?),
+                after: None,
+            });
+        }
+    }
+    if changes.is_empty() {
+        return Ok(());
+    }
+    changes.sort_by(|a, b| a.id.cmp(&b.id));
+
+    append(workgraph_dir, new_entry(EntryKind::Change, changes, None))?;
+    Ok(())
+}
+
+/// Revert the newest change in `scope` on the undo stack.
+///
+/// Refuses if a task it touched has since been changed outside the journal,
+/// unless `force` is set. Returns the reverted change, or `None` when there
+/// is nothing to undo.
+pub fn undo(workgraph_dir: &Path, scope: &Scope, force: bool) -> Result<Option<JournalEntry>> {
+    step(workgraph_dir, EntryKind::Undo, scope, force)
+}
+
+/// Re-apply the most recently undone change in `scope`. See [`undo`].
+pub fn redo(workgraph_dir: &Path, scope: &Scope, force: bool) -> Result<Option<JournalEntry>> {
+    step(workgraph_dir, EntryKind::Redo, scope, force)
+}
+
+/// The change `wg undo` or `wg redo` would step to next, without applying it.
+pub fn peek(workgraph_dir: &Path, kind: EntryKind, scope: &Scope) -> Result<Option<JournalEntry>> {
+    let entries = load(workgraph_dir)?;
+    Ok(next_target(&entries, kind, scope).cloned())
+}
+
+fn next_target<'a>(
+    entries: &'a [JournalEntry],
+    kind: EntryKind,
+    scope: &Scope,
+) -> Option<&'a JournalEntry> {
+    let (undo_stack, redo_stack) = stacks(entries);
+    let stack = match kind {
+        EntryKind::Undo => undo_stack,
+        _ => redo_stack,
+    };
+    stack.iter().rev().find_map(|seq| {
+        entries
+            .iter()
+            .find(|e| e.seq == *seq && e.kind == EntryKind::Change)
+            .filter(|e| scope.includes(e))
+    })
+}
+
+fn step(
+    workgraph_dir: &Path,
+    kind: EntryKind,
+    scope: &Scope,
+    force: bool,
+) -> Result<Option<JournalEntry>> {
+    let graph_path = workgraph_dir.join("graph.jsonl");
+    let mut outcome = Ok(None);
+    modify_graph_without_journal(&graph_path, |graph| {
+        match apply_step(workgraph_dir, graph, kind, scope, force) {
+            Ok(Some(entry)) => {
+                outcome = Ok(Some(entry));
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                outcome = Err(e);
+                false
+            }
+        }
+    })
+    .context("Failed to update the graph")?;
+    outcome
+}
+
+fn apply_step(
+    workgraph_dir: &Path,
+    graph: &mut WorkGraph,
+    kind: EntryKind,
+    scope: &Scope,
+    force: bool,
+) -> Result<Option<JournalEntry>> {
+    let entries = load(workgraph_dir)?;
+    let verb = match kind {
+        EntryKind::Undo => "undo",
+        _ => "redo",
+    };
+    let Some(change) = next_target(&entries, kind, scope).cloned() else {
+        return Ok(None);
+    };
+
+    let parse = |v: &Option<serde_json::Value>| -> Result<Option<Task>> {
+        v.clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .context("Corrupt task state in journal")
+    };
+    let mut drifted = Vec::new();
+    let mut replacements = Vec::new();
+    for c in &change.changes {
+        let (expected, replacement) = match kind {
+            EntryKind::Undo => (parse(&c.after)?, parse(&c.before)?),
+            _ => (parse(&c.before)?, parse(&c.after)?),
+        };
+        let matches = match (graph.get_task(&c.id), &expected) {
+            (Some(current), Some(expected)) => current.substantively_eq(expected),
+            (None, None) => true,
+            _ => false,
+        };
+        if !matches {
+            drifted.push(c.id.clone());
+        }
+        replacements.push((c.id.clone(), replacement));
+    }
+    if !drifted.is_empty() && !force {
+        anyhow::bail!(
+            "Cannot {} #{} ({}): task(s) changed since: {}. Use --force to {} anyway",
+            verb,
+            change.seq,
+            change.command.as_deref().unwrap_or("unknown command"),
+            drifted.join(", "),
+            verb
+        );
+    }
+
+    for (id, replacement) in replacements {
+        match replacement {
+            Some(task) => {
+                graph.remove_node(&id);
+                graph.add_node(Node::Task(task));
+            }
+            None => {
+                graph.remove_node(&id);
+            }
+        }
+    }
+    append(workgraph_dir, new_entry(kind, Vec::new(), Some(change.seq)))?;
+    Ok(Some(change))
+}
+
+/// The graph as it stood at an earlier time. See [`state_at`].
+#[derive(Debug)]
+pub struct PastState {
+    pub graph: WorkGraph,
+    /// Journal entries unwound to get there
+    pub unwound: usize,
+    /// Timestamp of the oldest journal entry, if any
+    pub journal_start: Option<String>,
+    /// Whether the journal reaches back to the requested time. When it
+    /// doesn't (a new project, or entries lost to compaction), changes
+    /// older than `journal_start` are missing from the reconstruction.
+    pub complete: bool,
+}
+
+/// Reconstruct the graph as it stood at `at`.
+///
+/// Starting from `current`, reverts every journal entry recorded after `at`,
+/// newest first: a change is reverted to its before states, an undo to the
+/// after states of the change it undid, and a redo to the before states.
+/// Tasks whose `created_at` is after `at` are dropped as well, which covers
+/// tasks added outside the journal's window.
+pub fn state_at(workgraph_dir: &Path, current: &WorkGraph, at: DateTime<Utc>) -> Result<PastState> {
+    let entries = load(workgraph_dir)?;
+    let timestamp = |e: &JournalEntry| e.timestamp.parse::<DateTime<Utc>>().ok();
+    let parse = |v: &Option<serde_json::Value>| -> Result<Option<Task>> {
+        v.clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .context("Corrupt task state in journal")
+    };
+
+    let mut graph = current.clone();
+    let mut unwound = 0;
+    for entry in entries.iter().rev() {
+        match timestamp(entry) {
+            Some(t) if t <= at => break,
+            Some(_) => {}
+            None => continue,
+        }
+        let target = || {
+            entries
+                .iter()
+                .find(|e| Some(e.seq) == entry.target && e.kind == EntryKind::Change)
+        };
+        let states: Vec<(&str, Option<Task>)> = match entry.kind {
+            EntryKind::Change => entry
+                .changes
+                .iter()
+                .map(|c| Ok((c.id.as_str(), parse(&c.before)?)))
+                .collect::<Result<_>>()?,
+            EntryKind::Undo => target()
+                .into_iter()
+                .flat_map(|t| &t.changes)
+                .map(|c| Ok((c.id.as_str(), parse(&c.after)?)))
+                .collect::<Result<_>>()?,
+            EntryKind::Redo => target()
+                .into_iter()
+                .flat_map(|t| &t.changes)
+                .map(|c| Ok((c.id.as_str(), parse(&c.before)?)))
+                .collect::<Result<_>>()?,
+        };
+        for (id, state) in states {
+            graph.remove_node(id);
+            if let Some(task) = state {
+                graph.add_node(Node::Task(task));
+            }
+        }
+        unwound += 1;
+    }
+
+    let created_later: Vec<String> = graph
+        .tasks()
+        .filter(|t| {
+            t.created_at
+                .as_deref()
+                .and_then(|c| c.parse::<DateTime<Utc>>().ok())
+                .is_some_and(|c| c > at)
+        })
+        .map(|t| t.id.clone())
+        .collect();
+    for id in created_later {
+        graph.remove_node(&id);
+    }
+
+    let start = entries.first().and_then(timestamp);
+    Ok(PastState {
+        graph,
+        unwound,
+        journal_start: entries.first().map(|e| e.timestamp.clone()),
+        complete: start.is_some_and(|s| s <= at),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Status;
+    use crate::parser::{load_graph, modify_graph};
+    use crate::test_helpers::{make_task, setup_workgraph};
+    use tempfile::TempDir;
+
+    fn set_status(dir: &Path, id: &str, status: Status) {
+        modify_graph(dir.join("graph.jsonl"), |g| {
+            g.get_task_mut(id).unwrap().status = status;
+            true
+        })
+        .unwrap();
+    }
+
+    fn status(dir: &Path, id: &str) -> Status {
+        load_graph(dir.join("graph.jsonl"))
+            .unwrap()
+            .get_task(id)
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn test_modify_graph_records_changes() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("a", "A")]);
+        set_status(&dir, "a", Status::Done);
+        // A no-op write is not journaled.
+        modify_graph(dir.join("graph.jsonl"), |_| true).unwrap();
+
+        let entries = load(&dir).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].kind, EntryKind::Change);
+        assert_eq!(entries[0].task_ids(), vec!["a"]);
+        assert_eq!(
+            entries[0].changes[0].before.as_ref().unwrap()["status"],
+            "open"
+        );
+    }
+
+    #[test]
+    fn test_undo_redo_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("a", "A")]);
+        set_status(&dir, "a", Status::InProgress);
+        set_status(&dir, "a", Status::Done);
+        modify_graph(dir.join("graph.jsonl"), |g| {
+            g.add_node(Node::Task(make_task("b", "B")));
+            true
+        })
+        .unwrap();
+
+        assert!(undo(&dir, &Scope::Any, false).unwrap().is_some());
+        assert!(
+            load_graph(dir.join("graph.jsonl"))
+                .unwrap()
+                .get_task("b")
+                .is_none()
+        );
+        undo(&dir, &Scope::Any, false).unwrap();
+        assert_eq!(status(&dir, "a"), Status::InProgress);
+
+        redo(&dir, &Scope::Any, false).unwrap();
+        assert_eq!(status(&dir, "a"), Status::Done);
+        redo(&dir, &Scope::Any, false).unwrap();
+        assert!(
+            load_graph(dir.join("graph.jsonl"))
+                .unwrap()
+                .get_task("b")
+                .is_some()
+        );
+        assert!(redo(&dir, &Scope::Any, false).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_new_change_clears_redo_stack() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("a", "A"), make_task("b", "B")]);
+        set_status(&dir, "a", Status::Done);
+        undo(&dir, &Scope::Any, false).unwrap();
+        set_status(&dir, "b", Status::Done);
+
+        let (undo_stack, redo_stack) = stacks(&load(&dir).unwrap());
+        assert_eq!(undo_stack.len(), 1);
+        assert!(redo_stack.is_empty());
+        assert!(redo(&dir, &Scope::Any, false).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_state_at_unwinds_later_changes() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("a", "A")]);
+        set_status(&dir, "a", Status::InProgress);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let checkpoint = Utc::now();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        set_status(&dir, "a", Status::Done);
+        modify_graph(dir.join("graph.jsonl"), |g| {
+            g.add_node(Node::Task(make_task("b", "B")));
+            true
+        })
+        .unwrap();
+        undo(&dir, &Scope::Any, false).unwrap();
+        redo(&dir, &Scope::Any, false).unwrap();
+
+        let current = load_graph(dir.join("graph.jsonl")).unwrap();
+        let past = state_at(&dir, &current, checkpoint).unwrap();
+        assert_eq!(past.unwound, 4);
+        assert!(past.complete);
+        assert_eq!(past.graph.get_task("a").unwrap().status, Status::InProgress);
+        assert!(past.graph.get_task("b").is_none());
+
+        let before_journal = checkpoint - chrono::Duration::days(1);
+        let past = state_at(&dir, &current, before_journal).unwrap();
+        assert!(!past.complete);
+        assert_eq!(past.graph.get_task("a").unwrap().status, Status::Open);
+    }
+
+    #[test]
+    fn test_undo_refuses_when_task_drifted() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("a", "A")]);
+        set_status(&dir, "a", Status::Done);
+        // Edit behind the journal's back.
+        setup_workgraph(&dir, vec![make_task("a", "Renamed")]);
+
+        assert!(undo(&dir, &Scope::Any, false).is_err());
+        undo(&dir, &Scope::Any, true).unwrap();
+        let g = load_graph(dir.join("graph.jsonl")).unwrap();
+        assert_eq!(g.get_task("a").unwrap().title, "A");
+        assert_eq!(g.get_task("a").unwrap().status, Status::Open);
+    }
+
+    #[test]
+    fn test_undo_skips_other_owners_changes() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("a", "A"), make_task("b", "B")]);
+        set_status(&dir, "a", Status::Done);
+        set_status(&dir, "b", Status::Done);
+
+        // Attribute the newer change to the service daemon.
+        let mut entries = load(&dir).unwrap();
+        entries[1].actor = Some("service".to_string());
+        let content: String = entries
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap() + "\n")
+            .collect();
+        fs::write(journal_path(&dir), content).unwrap();
+
+        let mine = Scope::Own(Owner::current());
+        assert_eq!(peek(&dir, EntryKind::Undo, &mine).unwrap().unwrap().seq, 1);
+        let undone = undo(&dir, &mine, false).unwrap().unwrap();
+        assert_eq!(undone.task_ids(), vec!["a"]);
+        assert_eq!(status(&dir, "a"), Status::Open);
+        assert_eq!(status(&dir, "b"), Status::Done);
+        assert!(undo(&dir, &mine, false).unwrap().is_none());
+
+        // Ours can be redone; --any still reaches the service's change.
+        assert!(peek(&dir, EntryKind::Redo, &mine).unwrap().is_some());
+        assert_eq!(undo(&dir, &Scope::Any, false).unwrap().unwrap().seq, 2);
+    }
+
+    #[test]
+    fn test_sequence_survives_lost_sidecar() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        setup_workgraph(&dir, vec![make_task("a", "A")]);
+        set_status(&dir, "a", Status::InProgress);
+        set_status(&dir, "a", Status::Done);
+        assert!(seq_path(&dir).exists());
+
+        fs::remove_file(seq_path(&dir)).unwrap();
+        set_status(&dir, "a", Status::Open);
+        let seqs: Vec<u64> = load(&dir).unwrap().iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![1, 2, 3]);
+        let state = load_seq(&dir).unwrap();
+        assert_eq!((state.last_seq, state.entries), (3, 3));
+    }
+}
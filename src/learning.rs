@@ -0,0 +1,454 @@
+//! Coordinator learning loop (Layer 3): turn function run summaries into
+//! dispatch adjustments.
+//!
+//! Outcomes in `functions/<id>.runs.jsonl` are aggregated per template
+//! (`<function>/<template>`) and per skill (`skill:<name>`). A key with at
+//! least `min_runs` runs whose mean score is below `min_score`, or whose
+//! failure rate exceeds `max_failure_rate`, is adjusted: its tasks are
+//! dispatched on the `escalate_to` tier and at most `max_concurrent` of them
+//! run at once. `[learning.overrides]` pin or disable individual keys.
+//!
+//! The daemon refreshes `service/learning.json` each tick and logs every
+//! change; the spawner and coordinator read the saved adjustments.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, LearningConfig, Tier};
+use crate::graph::{Task, WorkGraph};
+
+/// Tag prefix recording which function template produced a task.
+pub const TEMPLATE_TAG_PREFIX: &str = "template:";
+
+/// Aggregated outcomes for one key.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyStats {
+    pub runs: usize,
+    pub failures: usize,
+    pub scores: Vec<f64>,
+}
+
+impl KeyStats {
+    pub fn mean_score(&self) -> Option<f64> {
+        if self.scores.is_empty() {
+            None
+        } else {
+            Some(self.scores.iter().sum::<f64>() / self.scores.len() as f64)
+        }
+    }
+
+    pub fn failure_rate(&self) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.runs as f64
+        }
+    }
+}
+
+/// Where an adjustment came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    Learned,
+    Override,
+}
+
+/// A dispatch adjustment for every task matching `key`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Adjustment {
+    pub key: String,
+    pub source: Source,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent: Option<usize>,
+    pub reason: String,
+}
+
+impl Adjustment {
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(m) = &self.model {
+            parts.push(format!("model={}", m));
+        } else if let Some(t) = &self.tier {
+            parts.push(format!("tier={}", t));
+        }
+        if let Some(c) = self.max_concurrent {
+            parts.push(format!("max_concurrent={}", c));
+        }
+        format!("{} [{}] ({})", self.key, parts.join(", "), self.reason)
+    }
+}
+
+/// Saved result of the last refresh.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LearningState {
+    #[serde(default)]
+    pub updated_at: String,
+    #[serde(default)]
+    pub adjustments: Vec<Adjustment>,
+}
+
+/// Tag added to tasks created from a function template.
+pub fn template_tag(function_id: &str, template_id: &str) -> String {
+    format!("{}{}/{}", TEMPLATE_TAG_PREFIX, function_id, template_id)
+}
+
+/// Learning keys a task belongs to: its template first, then its skills.
+pub fn task_keys(task: &Task) -> Vec<String> {
+    let mut keys: Vec<String> = task
+        .tags
+        .iter()
+        .filter_map(|t| t.strip_prefix(TEMPLATE_TAG_PREFIX))
+        .map(String::from)
+        .collect();
+    keys.extend(task.skills.iter().map(|s| format!("skill:{}", s)));
+    keys
+}
+
+/// Aggregate the most recent `window` runs of every function. Skill keys
+/// are resolved through `graph`; outcomes for tasks no longer in it only
+/// count towards their template.
+pub fn collect_stats(
+    workgraph_dir: &Path,
+    graph: &WorkGraph,
+    window: usize,
+) -> BTreeMap<String, KeyStats> {
+    let func_dir = crate::function::functions_dir(workgraph_dir);
+    let mut stats: BTreeMap<String, KeyStats> = BTreeMap::new();
+    let Ok(entries) = fs::read_dir(&func_dir) else {
+        return stats;
+    };
+    let mut function_ids: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .strip_suffix(".runs.jsonl")
+                .map(String::from)
+        })
+        .collect();
+    function_ids.sort();
+
+    for function_id in function_ids {
+        let runs = crate::function::load_runs(&func_dir, &function_id);
+        let skip = runs.len().saturating_sub(window);
+        for run in runs.into_iter().skip(skip) {
+            for outcome in &run.task_outcomes {
+                let template_id = outcome.template_id.trim_start_matches('-');
+                let mut keys = vec![format!("{}/{}", function_id, template_id)];
+                if let Some(task) = graph.get_task(&outcome.task_id) {
+                    keys.extend(task.skills.iter().map(|s| format!("skill:{}", s)));
+                }
+                let failed = matches!(outcome.status.as_str(), "Failed" | "Abandoned");
+                for key in keys {
+                    let entry = stats.entry(key).or_default();
+                    entry.runs += 1;
+                    if failed {
+                        entry.failures += 1;
+                    }
+                    if let Some(score) = outcome.score {
+                        entry.scores.push(score);
+                    }
+                }
+            }
+        }
+    }
+    stats
+}
+
+/// Decide adjustments from aggregated stats and configured overrides.
+pub fn compute(stats: &BTreeMap<String, KeyStats>, config: &LearningConfig) -> Vec<Adjustment> {
+    let mut out = BTreeMap::new();
+    for (key, s) in stats {
+        if s.runs < config.min_runs {
+            continue;
+        }
+        let low_score = s.mean_score().filter(|m| *m < config.min_score);
+        let failing = s.failure_rate() > config.max_failure_rate;
+        if low_score.is_none() && !failing {
+            continue;
+        }
+        let reason = match low_score {
+            Some(mean) => format!(
+                "mean score {:.2} < {:.2} over {} runs",
+                mean, config.min_score, s.runs
+            ),
+            None => format!("{} of {} runs failed", s.failures, s.runs),
+        };
+        out.insert(
+            key.clone(),
+            Adjustment {
+                key: key.clone(),
+                source: Source::Learned,
+                tier: Some(config.escalate_to.clone()),
+                model: None,
+                max_concurrent: (config.max_concurrent > 0).then_some(config.max_concurrent),
+                reason,
+            },
+        );
+    }
+
+    for (key, o) in &config.overrides {
+        if o.disabled {
+            out.remove(key);
+            continue;
+        }
+        let learned = out.remove(key);
+        out.insert(
+            key.clone(),
+            Adjustment {
+                key: key.clone(),
+                source: Source::Override,
+                tier: o
+                    .tier
+                    .clone()
+                    .or_else(|| learned.as_ref().and_then(|l| l.tier.clone())),
+                model: o.model.clone(),
+                max_concurrent: o
+                    .max_concurrent
+                    .or_else(|| learned.as_ref().and_then(|l| l.max_concurrent))
+                    .filter(|c| *c > 0),
+                reason: "configured override".to_string(),
+            },
+        );
+    }
+    out.into_values().collect()
+}
+
+pub fn state_path(workgraph_dir: &Path) -> PathBuf {
+    workgraph_dir.join("service").join("learning.json")
+}
+
+pub fn load_state(workgraph_dir: &Path) -> LearningState {
+    fs::read_to_string(state_path(workgraph_dir))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Recompute adjustments and save them. Returns a description of every
+/// adjustment added, changed, or removed since the last refresh.
+pub fn refresh(
+    workgraph_dir: &Path,
+    graph: &WorkGraph,
+    config: &LearningConfig,
+) -> Result<Vec<String>> {
+    let stats = collect_stats(workgraph_dir, graph, config.window);
+    let adjustments = compute(&stats, config);
+    let previous = load_state(workgraph_dir).adjustments;
+
+    let mut changes = Vec::new();
+    for adj in &adjustments {
+        match previous.iter().find(|p| p.key == adj.key) {
+            None => changes.push(format!("added {}", adj.describe())),
+            Some(p) if p != adj => changes.push(format!("changed {}", adj.describe())),
+            Some(_) => {}
+        }
+    }
+    for p in &previous {
+        if !adjustments.iter().any(|a| a.key == p.key) {
+            changes.push(format!("removed {}", p.key));
+        }
+    }
+
+    if !changes.is_empty() || !state_path(workgraph_dir).exists() {
+        let path = state_path(workgraph_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let state = LearningState {
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            adjustments,
+        };
+        fs::write(&path, serde_json::to_string_pretty(&state)?)?;
+    }
+    Ok(changes)
+}
+
+/// Adjustments that apply to `task`, most specific (template) first.
+pub fn matching<'a>(adjustments: &'a [Adjustment], task: &Task) -> Vec<&'a Adjustment> {
+    task_keys(task)
+        .iter()
+        .filter_map(|k| adjustments.iter().find(|a| &a.key == k))
+        .collect()
+}
+
+/// The model the learning loop wants for `task`, with the adjustment that
+/// chose it. Tasks with an explicit model or tier, or that opted out of
+/// tier escalation, are left alone.
+pub fn model_for_task(
+    workgraph_dir: &Path,
+    config: &Config,
+    task: &Task,
+) -> Option<(String, Adjustment)> {
+    if !config.learning.enabled
+        || task.model.is_some()
+        || task.tier.is_some()
+        || task.no_tier_escalation
+    {
+        return None;
+    }
+    let state = load_state(workgraph_dir);
+    matching(&state.adjustments, task)
+        .into_iter()
+        .find_map(|adj| {
+            let model = adj.model.clone().or_else(|| {
+                let tier: Tier = adj.tier.as_deref()?.parse().ok()?;
+                config.resolve_tier(tier).map(|r| r.model)
+            })?;
+            Some((model, adj.clone()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LearningOverride;
+    use crate::function::{RunSummary, TaskOutcome};
+    use crate::test_helpers::make_task;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn run(outcomes: &[(&str, &str, Option<f64>)]) -> RunSummary {
+        RunSummary {
+            applied_at: "2026-01-01T00:00:00Z".to_string(),
+            inputs: HashMap::new(),
+            prefix: "p".to_string(),
+            task_outcomes: outcomes
+                .iter()
+                .map(|(template, status, score)| TaskOutcome {
+                    template_id: template.to_string(),
+                    task_id: format!("p-{}", template),
+                    status: status.to_string(),
+                    score: *score,
+                    duration_secs: None,
+                    retry_count: 0,
+                    changes: None,
+                })
+                .collect(),
+            interventions: vec![],
+            wall_clock_secs: None,
+            all_succeeded: false,
+            avg_score: None,
+        }
+    }
+
+    fn write_runs(dir: &Path, function_id: &str, runs: &[RunSummary]) {
+        for r in runs {
+            crate::function_memory::append_run_summary(dir, function_id, r).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_low_scores_and_failures_are_adjusted() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let runs: Vec<RunSummary> = (0..3)
+            .map(|_| {
+                run(&[
+                    ("plan", "Done", Some(0.9)),
+                    ("implement", "Done", Some(0.4)),
+                    ("validate", "Failed", None),
+                ])
+            })
+            .collect();
+        write_runs(dir, "impl-feature", &runs);
+
+        let mut graph = WorkGraph::new();
+        let mut t = make_task("p-implement", "Implement");
+        t.skills = vec!["rust".to_string()];
+        graph.add_node(crate::graph::Node::Task(t));
+
+        let config = LearningConfig {
+            enabled: true,
+            ..LearningConfig::default()
+        };
+        let stats = collect_stats(dir, &graph, config.window);
+        assert_eq!(stats["impl-feature/implement"].runs, 3);
+        let adjustments = compute(&stats, &config);
+        let keys: Vec<&str> = adjustments.iter().map(|a| a.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "impl-feature/implement",
+                "impl-feature/validate",
+                "skill:rust"
+            ]
+        );
+        assert_eq!(adjustments[0].tier.as_deref(), Some("premium"));
+        assert_eq!(adjustments[0].max_concurrent, Some(1));
+        assert!(adjustments[1].reason.contains("3 of 3 runs failed"));
+
+        // Overrides pin or disable keys.
+        let mut config = config;
+        config.overrides.insert(
+            "impl-feature/implement".to_string(),
+            LearningOverride {
+                model: Some("claude:sonnet".to_string()),
+                ..Default::default()
+            },
+        );
+        config.overrides.insert(
+            "skill:rust".to_string(),
+            LearningOverride {
+                disabled: true,
+                ..Default::default()
+            },
+        );
+        let adjustments = compute(&stats, &config);
+        assert_eq!(adjustments.len(), 2);
+        assert_eq!(adjustments[0].source, Source::Override);
+        assert_eq!(adjustments[0].model.as_deref(), Some("claude:sonnet"));
+        assert_eq!(adjustments[0].max_concurrent, Some(1));
+    }
+
+    #[test]
+    fn test_refresh_reports_changes_and_matches_tasks() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let runs: Vec<RunSummary> = (0..3)
+            .map(|_| run(&[("implement", "Done", Some(0.2))]))
+            .collect();
+        write_runs(dir, "impl-feature", &runs);
+        let graph = WorkGraph::new();
+        let config = LearningConfig {
+            enabled: true,
+            ..LearningConfig::default()
+        };
+
+        let changes = refresh(dir, &graph, &config).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].starts_with("added impl-feature/implement"));
+        assert!(refresh(dir, &graph, &config).unwrap().is_empty());
+
+        let mut task = make_task("x-implement", "Implement");
+        task.tags = vec![template_tag("impl-feature", "implement")];
+        let state = load_state(dir);
+        assert_eq!(matching(&state.adjustments, &task).len(), 1);
+
+        let full = Config {
+            learning: config.clone(),
+            ..Config::default()
+        };
+        let (model, adj) = model_for_task(dir, &full, &task).unwrap();
+        assert_eq!(adj.key, "impl-feature/implement");
+        assert_eq!(model, full.resolve_tier(Tier::Premium).unwrap().model);
+        task.no_tier_escalation = true;
+        assert!(model_for_task(dir, &full, &task).is_none());
+
+        // Not enough runs any more once the window shrinks.
+        let narrow = LearningConfig {
+            window: 2,
+            ..config
+        };
+        let changes = refresh(dir, &graph, &narrow).unwrap();
+        assert_eq!(changes, vec!["removed impl-feature/implement"]);
+    }
+}
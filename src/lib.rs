@@ -18,6 +18,12 @@
 extern crate self as workgraph;
 
 pub mod agency;
+pub mod analytics;
+pub mod anomaly;
+pub mod archive;
+pub mod availability;
+pub mod branch;
+pub mod changes;
 pub mod chat;
 pub mod chat_command;
 pub mod chat_id;
@@ -25,21 +31,43 @@ pub mod chat_sessions;
 pub mod check;
 pub mod config;
 pub mod config_defaults;
+pub mod config_drift;
 pub mod context_scope;
+pub mod contract;
+pub mod cost_report;
+pub mod coverage;
 pub mod cron;
 pub mod cycle;
+pub mod day_plan;
+pub mod deadline;
+pub mod deprecation;
 pub mod dispatch;
+pub mod escalation;
+pub mod event_gate;
 pub mod executor;
 pub mod executor_discovery;
+pub mod external_blocker;
 pub mod federation;
+pub mod focus;
 pub mod function;
 pub mod function_memory;
+pub mod gate;
 pub mod graph;
+pub mod graph_diff;
+pub mod graph_index;
+pub mod graph_merge;
+pub mod graphs;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod html;
+pub mod inbox;
+pub mod journal;
 pub mod json_extract;
 pub mod launcher_history;
+pub mod learning;
 pub mod lifecycle;
 pub mod lock;
+pub mod maintenance;
 pub mod markdown;
 #[cfg(feature = "matrix")]
 pub mod matrix;
@@ -61,17 +89,29 @@ pub mod registry {
     pub use crate::service::registry::AgentRegistry as Registry;
     pub use crate::service::registry::AgentStatus;
 }
+pub mod resource_pool;
+pub mod retention;
+pub mod rubric;
 pub mod runs;
+pub mod schema;
+pub mod scrub;
+pub mod search;
 pub mod secret;
 pub mod service;
 pub mod session_lock;
+pub mod size_guard;
 pub mod smoke;
+pub mod snapshot;
+pub mod strict;
 pub mod stream_event;
 pub mod syntect_convert;
 pub mod telegram_commands;
+pub mod test_results;
 pub mod usage;
 pub mod vendor_history;
 pub mod verify_lint;
+pub mod work_stealing;
+pub mod working_hours;
 
 pub use config::MatrixConfig;
 pub use graph::WorkGraph;
@@ -741,6 +741,33 @@ fn main() -> Result<()> {
 
     let workgraph_dir = workgraph_dir.canonicalize().unwrap_or(workgraph_dir);
 
+    // Redirect to a named graph in the same workspace.
+    let graph_name = cli
+        .graph
+        .clone()
+        .or_else(|| std::env::var("WG_GRAPH").ok().filter(|g| !g.is_empty()));
+    let workgraph_dir = match graph_name {
+        Some(name) => {
+            let dir = workgraph::graphs::graph_dir(&workgraph_dir, &name)?;
+            if !dir.join("graph.jsonl").is_file() {
+                anyhow::bail!(
+                    "Graph '{}' does not exist. Create it with: wg graphs create {}",
+                    name,
+                    name
+                );
+            }
+            dir
+        }
+        None => workgraph_dir,
+    };
+
+    // Strict field checking is read wherever graph and function files are
+    // parsed, so hand it down through the environment (agents inherit it).
+    if cli.strict || Config::load_or_default(&workgraph_dir).project.strict_fields {
+        // SAFETY: no other threads exist yet this early in main.
+        unsafe { std::env::set_var(workgraph::strict::STRICT_ENV, "1") };
+    }
+
     // Handle help flags (top-level custom help with usage-based ordering)
     if cli.help || cli.help_all || cli.command.is_none() {
         print_help(&workgraph_dir, cli.help_all, cli.alphabetical);
@@ -962,6 +989,8 @@ fn main() -> Result<()> {
             assign,
             hours,
             cost,
+            max_cost,
+            min_context,
             tag,
             skill,
             input,
@@ -999,6 +1028,12 @@ fn main() -> Result<()> {
             priority,
             cron,
             subtask,
+            expects,
+            requires,
+            due,
+            waiting_on,
+            lag,
+            edge_weight,
         } => {
             // Determine effective paused/unplaced state:
             // - --paused always pauses (user-managed draft, skips placement)
@@ -1081,6 +1116,14 @@ fn main() -> Result<()> {
                     priority.as_deref(),
                     cron.as_deref(),
                     subtask,
+                    &expects,
+                    &requires,
+                    due.as_deref(),
+                    &waiting_on,
+                    &lag,
+                    &edge_weight,
+                    max_cost,
+                    min_context,
                 )
             }
         }
@@ -1111,6 +1154,17 @@ fn main() -> Result<()> {
             cron,
             allow_phantom,
             allow_cycle,
+            priority,
+            due,
+            external_blocker,
+            blocker_contact,
+            blocker_expected,
+            add_waiting_on,
+            remove_waiting_on,
+            lag,
+            edge_weight,
+            max_cost,
+            min_context,
         } => commands::edit::run(
             &workgraph_dir,
             &id,
@@ -1139,6 +1193,17 @@ fn main() -> Result<()> {
             cron.as_deref(),
             allow_phantom,
             allow_cycle,
+            priority.as_deref(),
+            due.as_deref(),
+            &add_waiting_on,
+            &remove_waiting_on,
+            &lag,
+            &edge_weight,
+            external_blocker.as_deref(),
+            blocker_contact.as_deref(),
+            blocker_expected.as_deref(),
+            max_cost,
+            min_context,
         ),
         Commands::Reprioritize { id, priority } => {
             commands::reprioritize::run(&workgraph_dir, &id, &priority)
@@ -1150,15 +1215,21 @@ fn main() -> Result<()> {
             ignore_unmerged_worktree,
             full_smoke,
             skip_smoke,
-        } => commands::done::run(
-            &workgraph_dir,
-            &id,
-            converged,
-            skip_verify,
-            ignore_unmerged_worktree,
-            full_smoke,
-            skip_smoke,
-        ),
+            report,
+        } => {
+            if let Some(report) = report {
+                commands::done::record_report(&workgraph_dir, &id, &report)?;
+            }
+            commands::done::run(
+                &workgraph_dir,
+                &id,
+                converged,
+                skip_verify,
+                ignore_unmerged_worktree,
+                full_smoke,
+                skip_smoke,
+            )
+        }
         Commands::Fail {
             id,
             reason,
@@ -1219,9 +1290,22 @@ fn main() -> Result<()> {
         Commands::Requeue { id, reason } => commands::requeue::run(&workgraph_dir, &id, &reason),
         Commands::Approve { id } => commands::approve::run(&workgraph_dir, &id),
         Commands::Reject { id, reason } => commands::reject::run(&workgraph_dir, &id, &reason),
-        Commands::Claim { id, actor } => {
-            commands::claim::claim(&workgraph_dir, &id, actor.as_deref())
-        }
+        Commands::Claim {
+            id,
+            actor,
+            preempt,
+            batch,
+            skills,
+            steal,
+        } => match (id, batch) {
+            (_, Some(n)) => {
+                commands::claim::run_batch(&workgraph_dir, actor.as_deref(), n, &skills)
+            }
+            _ if steal => commands::claim::run_steal(&workgraph_dir, actor.as_deref(), &skills),
+            (Some(id), None) if preempt => commands::service::run_preempt(&workgraph_dir, &id),
+            (Some(id), None) => commands::claim::claim(&workgraph_dir, &id, actor.as_deref()),
+            (None, None) => unreachable!("clap requires TASK, --batch or --steal"),
+        },
         Commands::Unclaim { id } => commands::claim::unclaim(&workgraph_dir, &id),
         Commands::Pause { id } => commands::pause::run(&workgraph_dir, &id),
         Commands::Resume { id, only } => commands::resume::run(&workgraph_dir, &id, only),
@@ -1247,7 +1331,11 @@ fn main() -> Result<()> {
             since,
             with_artifacts,
         } => commands::discover::run(&workgraph_dir, Some(&since), with_artifacts, cli.json),
-        Commands::Blocked { id } => commands::blocked::run(&workgraph_dir, &id, cli.json),
+        Commands::Blocked { id, view } => match (id, view) {
+            (_, Some(view)) => commands::blocked::run_view(&workgraph_dir, &view, cli.json),
+            (Some(id), None) => commands::blocked::run(&workgraph_dir, &id, cli.json),
+            (None, None) => unreachable!("clap requires TASK or --view"),
+        },
         Commands::WhyBlocked { id } => commands::why_blocked::run(&workgraph_dir, &id, cli.json),
         Commands::Check => commands::check::run(&workgraph_dir, cli.json),
         Commands::Cleanup { subcmd } => {
@@ -1260,17 +1348,43 @@ fn main() -> Result<()> {
             paused,
             tags,
             cron,
+            where_expr,
+            view,
             all,
-        } => commands::list::run(
+            columns,
+            format,
+            no_header,
+        } => commands::list::Layout::parse(columns.as_deref(), format.as_deref(), !no_header)
+            .and_then(|layout| {
+                commands::list::run_with_layout(
+                    &workgraph_dir,
+                    status.as_deref(),
+                    paused,
+                    &tags,
+                    None,
+                    cron,
+                    where_expr.as_deref(),
+                    view.as_deref(),
+                    cli.json,
+                    all,
+                    &layout,
+                )
+            }),
+        Commands::Query { expr, all } => commands::list::run(
             &workgraph_dir,
-            status.as_deref(),
-            paused,
-            &tags,
             None,
-            cron,
+            false,
+            &[],
+            None,
+            false,
+            Some(&expr),
+            None,
             cli.json,
             all,
         ),
+        Commands::View { name, all } => {
+            commands::view::run(&workgraph_dir, name.as_deref(), cli.json, all)
+        }
         Commands::Viz {
             focus,
             all,
@@ -1279,6 +1393,8 @@ fn main() -> Result<()> {
             dot,
             mermaid,
             graph,
+            gantt,
+            format,
             output,
             show_internal,
             tui: tui_mode,
@@ -1290,7 +1406,8 @@ fn main() -> Result<()> {
             columns,
         } => {
             let layout_mode: commands::viz::LayoutMode = layout.parse().unwrap_or_default();
-            let _explicit_static_format = dot || mermaid || graph || output.is_some();
+            let _explicit_static_format =
+                dot || mermaid || graph || gantt || format.is_some() || output.is_some();
             let use_tui = tui_mode;
 
             // Resolve edge color: CLI flag > config > default ("gray")
@@ -1329,12 +1446,18 @@ fn main() -> Result<()> {
                     false,
                 )
             } else {
-                let fmt = if dot {
+                let fmt = if let Some(format) = format {
+                    format
+                        .parse::<commands::viz::OutputFormat>()
+                        .map_err(|e: String| anyhow::anyhow!(e))?
+                } else if dot {
                     commands::viz::OutputFormat::Dot
                 } else if mermaid {
                     commands::viz::OutputFormat::Mermaid
                 } else if graph {
                     commands::viz::OutputFormat::Graph
+                } else if gantt {
+                    commands::viz::OutputFormat::Gantt
                 } else {
                     commands::viz::OutputFormat::Ascii
                 };
@@ -1362,26 +1485,145 @@ fn main() -> Result<()> {
         }
         Commands::GraphExport {
             archive,
+            format,
             since,
             until,
-        } => commands::graph::run(&workgraph_dir, archive, since.as_deref(), until.as_deref()),
-        Commands::Cost { id } => commands::cost::run(&workgraph_dir, &id, cli.json),
+        } => commands::graph::run(
+            &workgraph_dir,
+            archive,
+            &format,
+            since.as_deref(),
+            until.as_deref(),
+        ),
+        Commands::Cost { id: Some(id), .. } => commands::cost::run(&workgraph_dir, &id, cli.json),
+        Commands::Cost {
+            id: None,
+            month,
+            by,
+            format,
+            output,
+        } => commands::cost::run_report(
+            &workgraph_dir,
+            month.as_deref(),
+            &by,
+            format.as_deref(),
+            output.as_deref(),
+            cli.json,
+        ),
         Commands::Coordinate { max_parallel } => {
             commands::coordinate::run(&workgraph_dir, cli.json, max_parallel)
         }
-        Commands::Plan { budget, hours } => {
-            commands::plan::run(&workgraph_dir, budget, hours, cli.json)
-        }
-        Commands::Reschedule { id, after, at } => {
-            commands::reschedule::run(&workgraph_dir, &id, after, at.as_deref())
-        }
+        Commands::Plan {
+            budget,
+            hours,
+            command,
+        } => match command {
+            Some(PlanCommands::Today { actor, post }) => {
+                commands::plan::run_today(&workgraph_dir, actor.as_deref(), post, cli.json)
+            }
+            None => commands::plan::run(&workgraph_dir, budget, hours, cli.json),
+        },
+        Commands::Reschedule {
+            id,
+            after,
+            at,
+            working,
+        } => commands::reschedule::run(&workgraph_dir, &id, after, at.as_deref(), working),
         Commands::Impact { id } => commands::impact::run(&workgraph_dir, &id, cli.json),
         Commands::Structure => commands::structure::run(&workgraph_dir, cli.json),
         Commands::Bottlenecks => commands::bottlenecks::run(&workgraph_dir, cli.json),
         Commands::Velocity { weeks } => commands::velocity::run(&workgraph_dir, cli.json, weeks),
-        Commands::Aging => commands::aging::run(&workgraph_dir, cli.json),
+        Commands::Report { html, weeks } => commands::report::run(&workgraph_dir, &html, weeks),
+        Commands::Search {
+            terms,
+            status,
+            tag,
+            fields,
+            archive,
+            limit,
+            reindex,
+        } => commands::search::run(
+            &workgraph_dir,
+            &terms,
+            status.as_deref(),
+            &tag,
+            &fields,
+            archive,
+            limit,
+            reindex,
+            cli.json,
+        ),
+        Commands::Aging { escalate, dry_run } => {
+            commands::aging::run(&workgraph_dir, escalate, dry_run, cli.json)
+        }
+        Commands::Overdue { soon, notify } => {
+            commands::overdue::run(&workgraph_dir, soon.as_deref(), notify, cli.json)
+        }
+        Commands::Anomalies { notify } => {
+            commands::anomalies::run(&workgraph_dir, notify, cli.json)
+        }
+        Commands::Waiting { notify } => commands::waiting::run(&workgraph_dir, notify, cli.json),
+        Commands::Progress { id, tag } => {
+            commands::progress::run(&workgraph_dir, id.as_deref(), tag.as_deref(), cli.json)
+        }
         Commands::Forecast => commands::forecast::run(&workgraph_dir, cli.json),
-        Commands::Workload => commands::workload::run(&workgraph_dir, cli.json),
+        Commands::Portfolio { roots, project } => {
+            commands::portfolio::run(&roots, project.as_deref(), cli.json)
+        }
+        Commands::Workload { command } => match command {
+            Some(WorkloadCommands::Forecast { agent, weeks }) => {
+                commands::workload::run_forecast(&workgraph_dir, agent.as_deref(), weeks, cli.json)
+            }
+            None => commands::workload::run(&workgraph_dir, cli.json),
+        },
+        Commands::Availability { command } => match command {
+            AvailabilityCommands::Add {
+                who,
+                from,
+                until,
+                reason,
+            } => commands::availability::run_add(
+                &workgraph_dir,
+                &who,
+                from.as_deref(),
+                &until,
+                reason.as_deref(),
+            ),
+            AvailabilityCommands::List { who, all } => {
+                commands::availability::run_list(&workgraph_dir, who.as_deref(), all, cli.json)
+            }
+            AvailabilityCommands::Remove { id } => {
+                commands::availability::run_remove(&workgraph_dir, id)
+            }
+        },
+        Commands::Rubric { command } => match command {
+            RubricCommands::List => commands::rubric::run_list(&workgraph_dir, cli.json),
+            RubricCommands::Show { id } => {
+                commands::rubric::run_show(&workgraph_dir, &id, cli.json)
+            }
+            RubricCommands::Test {
+                rubric,
+                task,
+                dry_run,
+            } => commands::rubric::run_test(&workgraph_dir, &rubric, &task, dry_run, cli.json),
+        },
+        Commands::Calendar { command } => match command {
+            CalendarCommands::Export { output, tag } => {
+                commands::calendar::run_export(&workgraph_dir, output.as_deref(), &tag)
+            }
+        },
+        Commands::Feed {
+            format,
+            since,
+            visibility,
+            output,
+        } => commands::feed::run(
+            &workgraph_dir,
+            &format,
+            &since,
+            &visibility,
+            output.as_deref(),
+        ),
         Commands::Worktree(sub) => match sub {
             cli::WorktreeCommand::List => commands::worktree_cmd::list(&workgraph_dir),
             cli::WorktreeCommand::Archive { agent_id, remove } => {
@@ -1452,7 +1694,13 @@ fn main() -> Result<()> {
                 commands::gc::run(&workgraph_dir, dry_run, include_done, older.as_deref())
             }
         }
-        Commands::Show { id } => commands::show::run(&workgraph_dir, &id, cli.json),
+        Commands::Show { id, archived } => {
+            if archived {
+                commands::show::run_archived(&workgraph_dir, &id, cli.json)
+            } else {
+                commands::show::run(&workgraph_dir, &id, cli.json)
+            }
+        }
         Commands::Trace { command } => match command {
             TraceCommands::Show {
                 id,
@@ -1508,6 +1756,8 @@ fn main() -> Result<()> {
             // Hidden aliases: print deprecation warning then delegate
             TraceCommands::ExtractAlias {
                 task_ids,
+                tag,
+                between,
                 name,
                 subgraph,
                 recursive,
@@ -1516,11 +1766,26 @@ fn main() -> Result<()> {
                 output,
                 force,
                 include_evaluations,
+                review,
             } => {
                 eprintln!(
                     "Warning: 'wg trace extract' is deprecated. Use 'wg func extract' instead."
                 );
-                if generative {
+                if task_ids.is_empty() {
+                    commands::func_extract::ExtractFilter::new(&tag, between.as_deref())
+                        .and_then(|filter| {
+                            commands::func_extract::run_filtered(
+                                &workgraph_dir,
+                                &filter,
+                                name.as_deref(),
+                                generalize,
+                                output.as_deref(),
+                                force,
+                                include_evaluations,
+                                review,
+                            )
+                        })
+                } else if generative {
                     commands::func_extract::run_generative(
                         &workgraph_dir,
                         &task_ids,
@@ -1530,7 +1795,7 @@ fn main() -> Result<()> {
                         include_evaluations,
                     )
                 } else {
-                    commands::func_extract::run(
+                    commands::func_extract::run_with_review(
                         &workgraph_dir,
                         &task_ids[0],
                         name.as_deref(),
@@ -1539,6 +1804,7 @@ fn main() -> Result<()> {
                         output.as_deref(),
                         force,
                         include_evaluations,
+                        review,
                     )
                 }
             }
@@ -1551,22 +1817,28 @@ fn main() -> Result<()> {
                 dry_run,
                 after,
                 model,
+                not_before,
+                stagger,
             } => {
                 eprintln!(
                     "Warning: 'wg trace instantiate' is deprecated. Use 'wg func apply' instead."
                 );
-                commands::func_apply::run(
-                    &workgraph_dir,
-                    &function_id,
-                    from.as_deref(),
-                    &inputs,
-                    input_file.as_deref(),
-                    prefix.as_deref(),
-                    dry_run,
-                    &after,
-                    model.as_deref(),
-                    cli.json,
-                )
+                commands::func_apply::ApplySchedule::parse(not_before.as_deref(), stagger.as_deref())
+                    .and_then(|schedule| {
+                        commands::func_apply::run_with_schedule(
+                            &workgraph_dir,
+                            &function_id,
+                            from.as_deref(),
+                            &inputs,
+                            input_file.as_deref(),
+                            prefix.as_deref(),
+                            dry_run,
+                            &after,
+                            model.as_deref(),
+                            &schedule,
+                            cli.json,
+                        )
+                    })
             }
             TraceCommands::ListFunctionsAlias {
                 verbose,
@@ -1621,8 +1893,16 @@ fn main() -> Result<()> {
             FuncCommands::Show { id } => {
                 commands::func_cmd::run_show(&workgraph_dir, &id, cli.json)
             }
+            FuncCommands::Docs { id, format, output } => commands::func_docs::run(
+                &workgraph_dir,
+                &id,
+                &format,
+                output.as_deref(),
+            ),
             FuncCommands::Extract {
                 task_ids,
+                tag,
+                between,
                 name,
                 subgraph,
                 recursive,
@@ -1631,8 +1911,23 @@ fn main() -> Result<()> {
                 output,
                 force,
                 include_evaluations,
+                review,
             } => {
-                if generative {
+                if task_ids.is_empty() {
+                    commands::func_extract::ExtractFilter::new(&tag, between.as_deref())
+                        .and_then(|filter| {
+                            commands::func_extract::run_filtered(
+                                &workgraph_dir,
+                                &filter,
+                                name.as_deref(),
+                                generalize,
+                                output.as_deref(),
+                                force,
+                                include_evaluations,
+                                review,
+                            )
+                        })
+                } else if generative {
                     commands::func_extract::run_generative(
                         &workgraph_dir,
                         &task_ids,
@@ -1642,7 +1937,7 @@ fn main() -> Result<()> {
                         include_evaluations,
                     )
                 } else {
-                    commands::func_extract::run(
+                    commands::func_extract::run_with_review(
                         &workgraph_dir,
                         &task_ids[0],
                         name.as_deref(),
@@ -1651,6 +1946,7 @@ fn main() -> Result<()> {
                         output.as_deref(),
                         force,
                         include_evaluations,
+                        review,
                     )
                 }
             }
@@ -1663,18 +1959,24 @@ fn main() -> Result<()> {
                 dry_run,
                 after,
                 model,
-            } => commands::func_apply::run(
-                &workgraph_dir,
-                &function_id,
-                from.as_deref(),
-                &inputs,
-                input_file.as_deref(),
-                prefix.as_deref(),
-                dry_run,
-                &after,
-                model.as_deref(),
-                cli.json,
-            ),
+                not_before,
+                stagger,
+            } => commands::func_apply::ApplySchedule::parse(not_before.as_deref(), stagger.as_deref())
+                .and_then(|schedule| {
+                    commands::func_apply::run_with_schedule(
+                        &workgraph_dir,
+                        &function_id,
+                        from.as_deref(),
+                        &inputs,
+                        input_file.as_deref(),
+                        prefix.as_deref(),
+                        dry_run,
+                        &after,
+                        model.as_deref(),
+                        &schedule,
+                        cli.json,
+                    )
+                }),
             FuncCommands::Bootstrap { force } => {
                 commands::func_bootstrap::run(&workgraph_dir, force)
             }
@@ -1715,6 +2017,150 @@ fn main() -> Result<()> {
                 commands::runs_cmd::run_diff(&workgraph_dir, &id, cli.json)
             }
         },
+        Commands::Branch { command } => match command {
+            BranchCommands::Create {
+                name,
+                from,
+                message,
+            } => commands::branch::run_create(
+                &workgraph_dir,
+                &name,
+                from.as_deref(),
+                message.as_deref(),
+                cli.json,
+            ),
+            BranchCommands::List => commands::branch::run_list(&workgraph_dir, cli.json),
+            BranchCommands::Compare { branch, other } => {
+                commands::branch::run_compare(&workgraph_dir, &branch, other.as_deref(), cli.json)
+            }
+            BranchCommands::Merge { name, force } => {
+                commands::branch::run_merge(&workgraph_dir, &name, force, cli.json)
+            }
+            BranchCommands::Delete { name } => {
+                commands::branch::run_delete(&workgraph_dir, &name, cli.json)
+            }
+        },
+        Commands::Diff { from, to } => {
+            commands::diff::run(&workgraph_dir, &from, to.as_deref(), cli.json)
+        }
+        Commands::Merge {
+            other,
+            base,
+            ours,
+            theirs,
+            dry_run,
+        } => {
+            use workgraph::graph_merge::Side;
+            let resolve = if ours {
+                Some(Side::Ours)
+            } else if theirs {
+                Some(Side::Theirs)
+            } else {
+                None
+            };
+            commands::merge::run(
+                &workgraph_dir,
+                &other,
+                base.as_deref(),
+                resolve,
+                dry_run,
+                cli.json,
+            )
+        }
+        Commands::MergeDriver {
+            base,
+            ours,
+            theirs,
+            path,
+            install,
+        } => match (install, base, ours, theirs) {
+            (true, ..) => commands::merge_driver::run_install(&workgraph_dir),
+            (false, Some(base), Some(ours), Some(theirs)) => {
+                let conflicts =
+                    commands::merge_driver::run(&base, &ours, &theirs, path.as_deref())?;
+                if conflicts > 0 {
+                    std::process::exit(1);
+                }
+                Ok(())
+            }
+            _ => anyhow::bail!("Usage: wg merge-driver <base> <ours> <theirs> [path]"),
+        },
+        Commands::Provenance { command } => match command {
+            ProvenanceCommands::Verify => {
+                commands::provenance_cmd::run_verify(&workgraph_dir, cli.json)
+            }
+            ProvenanceCommands::Show { id, graph } => {
+                commands::provenance_cmd::run_show(&workgraph_dir, &id, graph, cli.json)
+            }
+        },
+        Commands::Undo {
+            force,
+            any,
+            list,
+            limit,
+        } => {
+            if list {
+                commands::undo::run_history(&workgraph_dir, limit, any, cli.json)
+            } else {
+                commands::undo::run_undo(&workgraph_dir, any, force, cli.json)
+            }
+        }
+        Commands::Redo { force, any } => {
+            commands::undo::run_redo(&workgraph_dir, any, force, cli.json)
+        }
+        Commands::Learning { refresh } => {
+            commands::learning::run(&workgraph_dir, refresh, cli.json)
+        }
+        Commands::Bench { command } => match command {
+            BenchCommands::Models {
+                sample,
+                skills,
+                models,
+                dry_run,
+            } => commands::bench::run_models(
+                &workgraph_dir,
+                sample,
+                &skills,
+                &models,
+                dry_run,
+                cli.json,
+            ),
+        },
+        Commands::Retention { dry_run, last } => {
+            if last {
+                commands::retention::run_last(&workgraph_dir, cli.json)
+            } else {
+                commands::retention::run(&workgraph_dir, dry_run, cli.json)
+            }
+        }
+        Commands::Snapshot { command } => match command {
+            SnapshotCommands::Create {
+                name,
+                message,
+                agency,
+                functions,
+            } => commands::snapshot::run_create(
+                &workgraph_dir,
+                name.as_deref(),
+                message.as_deref(),
+                agency,
+                functions,
+                cli.json,
+            ),
+            SnapshotCommands::List => commands::snapshot::run_list(&workgraph_dir, cli.json),
+            SnapshotCommands::Restore { name } => {
+                commands::snapshot::run_restore(&workgraph_dir, &name, cli.json)
+            }
+        },
+        Commands::At { time, view } => match view {
+            AtCommands::Status { all } => {
+                commands::at::run_status(&workgraph_dir, &time, all, cli.json)
+            }
+            AtCommands::List { status, all } => {
+                commands::at::run_list(&workgraph_dir, &time, status.as_deref(), all, cli.json)
+            }
+            AtCommands::Viz { all } => commands::at::run_viz(&workgraph_dir, &time, all, cli.json),
+        },
         Commands::Log {
             id,
             message,
@@ -1722,6 +2168,7 @@ fn main() -> Result<()> {
             list,
             agent,
             operations,
+            follow,
         } => {
             if operations {
                 commands::log::run_operations(&workgraph_dir, cli.json)
@@ -1731,7 +2178,9 @@ fn main() -> Result<()> {
                         "Task ID is required (use --operations to view the operations log)"
                     )
                 })?;
-                if agent {
+                if follow {
+                    commands::log::run_follow(&workgraph_dir, id, cli.json)
+                } else if agent {
                     commands::log::run_agent(&workgraph_dir, id, cli.json)
                 } else if let (false, Some(msg)) = (list, &message) {
                     let agent_id = std::env::var("WG_AGENT_ID").ok();
@@ -1888,6 +2337,31 @@ fn main() -> Result<()> {
                 commands::chat::run_interactive(&workgraph_dir, timeout, coordinator)
             }
         }
+        Commands::Gate { command } => match command {
+            GateCommands::Create {
+                id,
+                title,
+                after,
+                phase_done,
+                min_score,
+                command,
+                approval,
+            } => commands::gate::run_create(
+                &workgraph_dir,
+                &id,
+                title.as_deref(),
+                &after,
+                phase_done,
+                min_score,
+                &command,
+                approval,
+            ),
+            GateCommands::Check { id } => {
+                commands::gate::run_check(&workgraph_dir, id.as_deref(), cli.json)
+            }
+            GateCommands::Approve { id } => commands::gate::run_approve(&workgraph_dir, &id),
+            GateCommands::List => commands::gate::run_list(&workgraph_dir, cli.json),
+        },
         Commands::Resource { command } => match command {
             ResourceCommands::Add {
                 id,
@@ -1912,6 +2386,9 @@ fn main() -> Result<()> {
                 commands::skills::run_find(&workgraph_dir, &skill, cli.json)
             }
             SkillCommands::Install => commands::skills::run_install(),
+            SkillCommands::Gaps { weeks } => {
+                commands::skills::run_gaps(&workgraph_dir, weeks, cli.json)
+            }
         },
         Commands::Agency { command } => match command {
             AgencyCommands::Init => commands::agency_init::run(&workgraph_dir),
@@ -2062,6 +2539,81 @@ fn main() -> Result<()> {
                 },
             ),
         },
+        Commands::Graph { command } => match command {
+            GraphCommands::Export {
+                archive,
+                format,
+                since,
+                until,
+            } => commands::graph::run(
+                &workgraph_dir,
+                archive,
+                &format,
+                since.as_deref(),
+                until.as_deref(),
+            ),
+            GraphCommands::Import {
+                file,
+                format,
+                dry_run,
+            } => commands::graph_import::run(
+                &workgraph_dir,
+                &file,
+                format.as_deref(),
+                dry_run,
+                cli.json,
+            ),
+        },
+        Commands::Export { csv, output } => {
+            commands::task_csv::run_export(&workgraph_dir, csv, output.as_deref())
+        }
+        Commands::Import { file, csv, dry_run } => {
+            commands::task_csv::run_import(&workgraph_dir, &file, csv, dry_run, cli.json)
+        }
+        Commands::Inbox { command } => match command {
+            None => commands::inbox::run_list(&workgraph_dir, cli.json),
+            Some(InboxCommands::Add {
+                title,
+                body,
+                source,
+                from,
+                email,
+            }) => commands::inbox::run_add(
+                &workgraph_dir,
+                title.as_deref(),
+                body.as_deref(),
+                &source,
+                from.as_deref(),
+                email,
+                cli.json,
+            ),
+        },
+        Commands::Triage {
+            interactive,
+            command,
+        } => match command {
+            None if interactive => commands::triage::run_interactive(&workgraph_dir),
+            None => anyhow::bail!("Specify a queue (e.g. 'wg triage inbox') or --interactive"),
+            Some(TriageCommands::Inbox { action }) => match action {
+                None => commands::inbox::run_list(&workgraph_dir, cli.json),
+                Some(TriageInboxCommands::Accept { id, split }) => {
+                    commands::inbox::run_accept(&workgraph_dir, &id, &split)
+                }
+                Some(TriageInboxCommands::Reject { id, reason }) => {
+                    commands::inbox::run_reject(&workgraph_dir, &id, reason.as_deref())
+                }
+                Some(TriageInboxCommands::Merge { id, into }) => {
+                    commands::inbox::run_merge(&workgraph_dir, &id, &into)
+                }
+            },
+        },
+        Commands::Graphs { command } => match command {
+            GraphsCommands::List => commands::graphs::run_list(&workgraph_dir, cli.json),
+            GraphsCommands::Create { name } => commands::graphs::run_create(&workgraph_dir, &name),
+            GraphsCommands::Remove { name, force } => {
+                commands::graphs::run_remove(&workgraph_dir, &name, force)
+            }
+        },
         Commands::Peer { command } => match command {
             PeerCommands::Add {
                 name,
@@ -2125,7 +2677,9 @@ fn main() -> Result<()> {
             clear,
             auto,
         } => commands::assign::run(&workgraph_dir, &task, agent_hash.as_deref(), clear, auto),
-        Commands::Match { task } => commands::match_cmd::run(&workgraph_dir, &task, cli.json),
+        Commands::Match { task, explain } => {
+            commands::match_cmd::run(&workgraph_dir, &task, explain, cli.json)
+        }
         Commands::Heartbeat {
             agent,
             check,
@@ -2187,6 +2741,32 @@ fn main() -> Result<()> {
                 commands::artifact::run_list(&workgraph_dir, &task, cli.json)
             }
         }
+        Commands::Link { command } => match command {
+            LinkCommands::Add {
+                task,
+                kind,
+                url,
+                label,
+            } => commands::external_link::run_add(
+                &workgraph_dir,
+                &task,
+                &kind,
+                &url,
+                label.as_deref(),
+            ),
+            LinkCommands::Remove { task, url } => {
+                commands::external_link::run_remove(&workgraph_dir, &task, &url)
+            }
+            LinkCommands::List { task } => {
+                commands::external_link::run_list(&workgraph_dir, &task, cli.json)
+            }
+        },
+        Commands::Event { command } => match command {
+            EventCommands::Fire { name } => {
+                commands::event::run_fire(&workgraph_dir, &name, cli.json)
+            }
+            EventCommands::List => commands::event::run_list(&workgraph_dir, cli.json),
+        },
         Commands::Context { task, dependents } => {
             if dependents {
                 commands::context::run_dependents(&workgraph_dir, &task, cli.json)
@@ -2194,7 +2774,19 @@ fn main() -> Result<()> {
                 commands::context::run(&workgraph_dir, &task, cli.json)
             }
         }
-        Commands::Next { actor } => commands::next::run(&workgraph_dir, &actor, cli.json),
+        Commands::Next { actor, view } => {
+            commands::next::run(&workgraph_dir, &actor, view.as_deref(), cli.json)
+        }
+        Commands::Focus {
+            actor,
+            limit,
+            command,
+        } => match command {
+            Some(FocusCommands::Next) => {
+                commands::focus::run_next(&workgraph_dir, actor.as_deref(), limit)
+            }
+            None => commands::focus::run(&workgraph_dir, actor.as_deref(), limit, cli.json),
+        },
         Commands::Trajectory { task, actor } => {
             if let Some(actor_id) = actor {
                 commands::trajectory::suggest_for_actor(&workgraph_dir, &actor_id, cli.json)
@@ -2268,6 +2860,35 @@ fn main() -> Result<()> {
             AgentCommands::Performance { id } => {
                 commands::agent_crud::run_performance(&workgraph_dir, &id, cli.json)
             }
+            AgentCommands::Stats { id } => {
+                commands::agent_crud::run_stats(&workgraph_dir, &id, cli.json)
+            }
+            AgentCommands::Register {
+                id,
+                file,
+                skill,
+                model,
+                max_context,
+                cost_ceiling,
+                resource,
+                clear,
+            } => commands::agent_crud::run_register(
+                &workgraph_dir,
+                &id,
+                file.as_deref(),
+                commands::agent_crud::CapabilityFlags {
+                    skills: skill,
+                    models: model,
+                    max_context,
+                    cost_ceiling,
+                    resources: resource,
+                },
+                clear,
+                cli.json,
+            ),
+            AgentCommands::SetBudget { id, daily, clear } => {
+                commands::agent_crud::run_set_budget(&workgraph_dir, &id, daily, clear, cli.json)
+            }
             AgentCommands::Run {
                 actor,
                 once,
@@ -2283,6 +2904,9 @@ fn main() -> Result<()> {
                 reset_state,
                 cli.json,
             ),
+            AgentCommands::Shell { target } => {
+                commands::agent_shell::run(&workgraph_dir, &target, cli.json)
+            }
         },
         Commands::Spawn {
             task,
@@ -2357,7 +2981,11 @@ fn main() -> Result<()> {
             event_types,
             task,
             replay,
-        } => commands::watch::run(&workgraph_dir, &event_types, task.as_deref(), replay),
+            view,
+        } => match view {
+            Some(view) => commands::watch::run_view(&workgraph_dir, &view, cli.json),
+            None => commands::watch::run(&workgraph_dir, &event_types, task.as_deref(), replay),
+        },
         Commands::Evolve { command } => match command {
             EvolveCommands::Run {
                 dry_run,
@@ -2597,6 +3225,13 @@ fn main() -> Result<()> {
                         };
                         return commands::config_cmd::lint_config(&workgraph_dir, target, cli.json);
                     }
+                    ConfigSubcommand::Diff { against } => {
+                        return commands::config_cmd::diff_config(
+                            &workgraph_dir,
+                            against.as_deref(),
+                            cli.json,
+                        );
+                    }
                 }
             }
 
@@ -2950,6 +3585,9 @@ fn main() -> Result<()> {
                 };
                 commands::migrate::run_config_migrate(&workgraph_dir, target, dry_run, cli.json)
             }
+            MigrateCommands::Fields { dry_run } => {
+                commands::migrate::run_fields(&workgraph_dir, dry_run, cli.json)
+            }
             MigrateCommands::Secrets {
                 dry_run,
                 global,
@@ -3025,6 +3663,8 @@ fn main() -> Result<()> {
         Commands::Service { command } => match command {
             ServiceCommands::Start {
                 port,
+                http,
+                grpc,
                 socket,
                 max_agents,
                 executor,
@@ -3032,10 +3672,13 @@ fn main() -> Result<()> {
                 model,
                 force,
                 no_chat_agent,
+                standby,
             } => commands::service::run_start(
                 &workgraph_dir,
                 socket.as_deref(),
                 port,
+                http.as_deref(),
+                grpc.as_deref(),
                 max_agents,
                 executor.as_deref(),
                 interval,
@@ -3043,10 +3686,14 @@ fn main() -> Result<()> {
                 cli.json,
                 force,
                 no_chat_agent,
+                standby,
             ),
             ServiceCommands::Stop { force, kill_agents } => {
                 commands::service::run_stop(&workgraph_dir, force, kill_agents, cli.json)
             }
+            ServiceCommands::Drain { timeout } => {
+                commands::service::run_drain(&workgraph_dir, timeout.as_deref(), cli.json)
+            }
             ServiceCommands::Restart => commands::service::run_restart(&workgraph_dir, cli.json),
             ServiceCommands::Status => commands::service::run_status(&workgraph_dir, cli.json),
             ServiceCommands::Reload {
@@ -3062,11 +3709,34 @@ fn main() -> Result<()> {
                 model.as_deref(),
                 cli.json,
             ),
-            ServiceCommands::Pause => commands::service::run_pause(&workgraph_dir, cli.json),
-            ServiceCommands::Resume => commands::service::run_resume(&workgraph_dir, cli.json),
+            ServiceCommands::Pause { tags, queues } => {
+                commands::service::run_pause(&workgraph_dir, &tags, &queues, cli.json)
+            }
+            ServiceCommands::Resume { tags, queues } => {
+                commands::service::run_resume(&workgraph_dir, &tags, &queues, cli.json)
+            }
             ServiceCommands::Freeze => commands::service::run_freeze(&workgraph_dir, cli.json),
             ServiceCommands::Thaw => commands::service::run_thaw(&workgraph_dir, cli.json),
             ServiceCommands::Install => commands::service::generate_systemd_service(&workgraph_dir),
+            ServiceCommands::Latency { since } => {
+                commands::service::run_latency(&workgraph_dir, since.as_deref(), cli.json)
+            }
+            ServiceCommands::Maintenance { command } => match command {
+                cli::MaintenanceCommands::On { reason, duration } => {
+                    commands::service::run_maintenance_on(
+                        &workgraph_dir,
+                        &reason,
+                        duration.as_deref(),
+                        cli.json,
+                    )
+                }
+                cli::MaintenanceCommands::Off => {
+                    commands::service::run_maintenance_off(&workgraph_dir, cli.json)
+                }
+                cli::MaintenanceCommands::Status => {
+                    commands::service::run_maintenance_status(&workgraph_dir, cli.json)
+                }
+            },
             ServiceCommands::Tick {
                 max_agents,
                 executor,
@@ -3139,6 +3809,8 @@ fn main() -> Result<()> {
                 interval,
                 model,
                 no_chat_agent,
+                http,
+                grpc,
             } => commands::service::run_daemon(
                 &workgraph_dir,
                 &socket,
@@ -3147,6 +3819,8 @@ fn main() -> Result<()> {
                 interval,
                 model.as_deref(),
                 no_chat_agent,
+                http.as_deref(),
+                grpc.as_deref(),
             ),
         },
         Commands::Tui {
@@ -3278,8 +3952,16 @@ fn main() -> Result<()> {
             commands::setup::run_with_args(&args)
         }
         Commands::Quickstart => commands::quickstart::run(cli.json),
+        Commands::Onboard {
+            skills,
+            agent,
+            limit,
+        } => commands::onboard::run(&workgraph_dir, &skills, agent.as_deref(), limit, cli.json),
         Commands::DevCheck => commands::dev_check::run(cli.json),
         Commands::AgentGuide => commands::agent_guide::run(),
+        Commands::Schema { kind, output } => {
+            commands::schema_cmd::run(kind.as_deref(), output.as_deref(), cli.json)
+        }
         Commands::Status { all } => commands::status::run(&workgraph_dir, cli.json, all),
         Commands::Stats => commands::stats::run(&workgraph_dir, cli.json),
         Commands::Metrics { json } => commands::metrics::run(&workgraph_dir, json),
@@ -3601,6 +4283,10 @@ fn main() -> Result<()> {
             max_turns,
             no_resume,
         ),
+        Commands::WebhookExec {
+            prompt_file,
+            task_id,
+        } => commands::webhook_exec::run(&workgraph_dir, &prompt_file, &task_id),
         Commands::ApplyPlacement {
             output_dir,
             source_task_id,
@@ -3642,6 +4328,7 @@ fn main() -> Result<()> {
         cli::Commands::Openrouter { command } => {
             commands::openrouter::run(&workgraph_dir, &command, cli.json)
         }
+        Commands::Cache { command } => commands::cache::run(&command, cli.json),
         Commands::Secret { command } => match command {
             cli::SecretCommands::Set {
                 name,
@@ -3689,6 +4376,8 @@ fn parse_failure_class(s: &str) -> Option<workgraph::graph::FailureClass> {
         "agent-hard-timeout" => Some(FailureClass::AgentHardTimeout),
         "agent-exit-nonzero" => Some(FailureClass::AgentExitNonzero),
         "wrapper-internal" => Some(FailureClass::WrapperInternal),
+        "tool-policy-violation" => Some(FailureClass::ToolPolicyViolation),
+        "cost-cap-exceeded" => Some(FailureClass::CostCapExceeded),
         _ => None,
     }
 }
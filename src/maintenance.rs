@@ -0,0 +1,386 @@
+//! Maintenance mode and scheduled pause windows.
+//!
+//! While maintenance is active the dispatcher starts no new agents; running
+//! agents are left alone. It is either entered by hand with `wg service
+//! maintenance on --reason "..." [--for 2h]`, or scheduled with
+//! `[[coordinator.pause_windows]]` (a cron expression for the start and a
+//! duration). Manual maintenance with `--for` ends by itself; windows end
+//! when their duration is up.
+//!
+//! The service runs [`run_sweep`] after each coordinator tick: it expires
+//! manual maintenance, and announces each start and end once on the urgent
+//! notification route. Urgent alerts sent while maintenance is active —
+//! failure spikes, deadlines, latency, budgets, escalations — carry the
+//! reason (see [`annotate`]), so alerts caused by planned work are
+//! recognisable as such. State lives in `.wg/service/maintenance.json`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, PauseWindow};
+use crate::graph::parse_delay;
+use crate::notify::config::NotifyConfig;
+
+const STATE_FILE: &str = "maintenance.json";
+
+/// Maintenance entered with `wg service maintenance on`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManualMaintenance {
+    pub reason: String,
+    pub since: String,
+    /// When it ends by itself; `None` lasts until `maintenance off`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MaintenanceState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manual: Option<ManualMaintenance>,
+    /// Reason of the maintenance last announced as started, cleared when
+    /// its end is announced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub announced: Option<String>,
+}
+
+impl MaintenanceState {
+    fn path(workgraph_dir: &Path) -> PathBuf {
+        workgraph_dir.join("service").join(STATE_FILE)
+    }
+
+    pub fn load(workgraph_dir: &Path) -> Result<Self> {
+        let path = Self::path(workgraph_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, workgraph_dir: &Path) -> Result<()> {
+        let path = Self::path(workgraph_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Where an active maintenance period comes from.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MaintenanceSource {
+    Manual,
+    Window { cron: String },
+}
+
+/// Maintenance in effect right now.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ActiveMaintenance {
+    pub reason: String,
+    pub source: MaintenanceSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl std::fmt::Display for ActiveMaintenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.reason)?;
+        if let MaintenanceSource::Window { ref cron } = self.source {
+            write!(f, " (pause window '{}')", cron)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ", until {}", until.format("%Y-%m-%d %H:%M UTC"))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Check a pause window's cron expression and duration.
+pub fn validate_window(window: &PauseWindow) -> Result<()> {
+    crate::cron::parse_cron_expression(&window.cron)
+        .with_context(|| format!("Invalid pause window cron '{}'", window.cron))?;
+    parse_delay(&window.duration).with_context(|| {
+        format!(
+            "Invalid pause window duration '{}': expected e.g. 90m, 2h",
+            window.duration
+        )
+    })?;
+    Ok(())
+}
+
+/// End of the occurrence of `window` covering `now`, if one does.
+///
+/// Overlapping occurrences (a duration longer than the cron period) are
+/// merged, so the latest end wins.
+pub fn window_end(window: &PauseWindow, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>> {
+    validate_window(window)?;
+    let schedule = crate::cron::parse_cron_expression(&window.cron)?;
+    let duration = Duration::seconds(parse_delay(&window.duration).unwrap_or(0) as i64);
+    // `after` is exclusive; step back a second so a window starting exactly
+    // `duration` ago still counts as its last instant.
+    let end = schedule
+        .after(&(now - duration - Duration::seconds(1)))
+        .take_while(|start| *start <= now)
+        .map(|start| start + duration)
+        .filter(|end| *end > now)
+        .max();
+    Ok(end)
+}
+
+/// The maintenance in effect at `now`: manual maintenance first, then the
+/// first pause window covering `now`. Invalid windows are skipped.
+pub fn active_at(
+    state: &MaintenanceState,
+    windows: &[PauseWindow],
+    now: DateTime<Utc>,
+) -> Option<ActiveMaintenance> {
+    if let Some(ref manual) = state.manual {
+        let until = manual.until.as_deref().and_then(parse_time);
+        if until.is_none_or(|u| u > now) {
+            return Some(ActiveMaintenance {
+                reason: manual.reason.clone(),
+                source: MaintenanceSource::Manual,
+                until,
+            });
+        }
+    }
+    windows.iter().find_map(|w| {
+        let end = window_end(w, now).ok().flatten()?;
+        Some(ActiveMaintenance {
+            reason: w
+                .reason
+                .clone()
+                .unwrap_or_else(|| "scheduled pause window".to_string()),
+            source: MaintenanceSource::Window {
+                cron: w.cron.clone(),
+            },
+            until: Some(end),
+        })
+    })
+}
+
+/// The maintenance in effect now, from the state file and config.
+pub fn current(workgraph_dir: &Path) -> Option<ActiveMaintenance> {
+    let state = MaintenanceState::load(workgraph_dir).unwrap_or_default();
+    let windows = Config::load_or_default(workgraph_dir)
+        .coordinator
+        .pause_windows;
+    active_at(&state, &windows, Utc::now())
+}
+
+/// Append the maintenance reason to a notification sent while maintenance
+/// is active.
+pub fn annotate(workgraph_dir: &Path, message: &str) -> String {
+    match current(workgraph_dir) {
+        Some(m) => format!("{} [during maintenance: {}]", message, m.reason),
+        None => message.to_string(),
+    }
+}
+
+/// Enter manual maintenance, ending after `duration` (e.g. "2h") if given.
+pub fn enter(
+    workgraph_dir: &Path,
+    reason: &str,
+    duration: Option<&str>,
+) -> Result<ManualMaintenance> {
+    let now = Utc::now();
+    let until = duration
+        .map(|d| {
+            parse_delay(d)
+                .map(|secs| (now + Duration::seconds(secs as i64)).to_rfc3339())
+                .with_context(|| format!("Invalid duration '{}': expected e.g. 30m, 2h", d))
+        })
+        .transpose()?;
+    let manual = ManualMaintenance {
+        reason: reason.to_string(),
+        since: now.to_rfc3339(),
+        until,
+    };
+    let mut state = MaintenanceState::load(workgraph_dir)?;
+    state.manual = Some(manual.clone());
+    state.save(workgraph_dir)?;
+    Ok(manual)
+}
+
+/// Leave manual maintenance. Returns what was cleared. Pause windows are
+/// not affected.
+pub fn leave(workgraph_dir: &Path) -> Result<Option<ManualMaintenance>> {
+    let mut state = MaintenanceState::load(workgraph_dir)?;
+    let cleared = state.manual.take();
+    if cleared.is_some() {
+        state.save(workgraph_dir)?;
+    }
+    Ok(cleared)
+}
+
+/// A maintenance start or end announced by [`run_sweep`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transition {
+    Started(ActiveMaintenance),
+    Ended { reason: String },
+}
+
+impl Transition {
+    /// Text sent to the notification channel.
+    pub fn message(&self) -> String {
+        match self {
+            Self::Started(m) => format!("Maintenance started: {}. Dispatch is paused.", m),
+            Self::Ended { reason } => {
+                format!("Maintenance over ({}). Dispatch resumed.", reason)
+            }
+        }
+    }
+}
+
+/// Expire manual maintenance and work out what to announce.
+fn sweep_state(
+    state: &mut MaintenanceState,
+    windows: &[PauseWindow],
+    now: DateTime<Utc>,
+) -> Vec<Transition> {
+    if let Some(ref manual) = state.manual
+        && manual
+            .until
+            .as_deref()
+            .and_then(parse_time)
+            .is_some_and(|u| u <= now)
+    {
+        state.manual = None;
+    }
+    let active = active_at(state, windows, now);
+    let mut transitions = Vec::new();
+    let changed = active.as_ref().map(|m| &m.reason) != state.announced.as_ref();
+    if changed {
+        if let Some(reason) = state.announced.take() {
+            transitions.push(Transition::Ended { reason });
+        }
+        if let Some(m) = active {
+            state.announced = Some(m.reason.clone());
+            transitions.push(Transition::Started(m));
+        }
+    }
+    transitions
+}
+
+/// Run one maintenance sweep: expire manual maintenance, announce starts
+/// and ends, and persist the state. Returns the transitions with their
+/// delivery outcome.
+pub fn run_sweep(
+    workgraph_dir: &Path,
+    windows: &[PauseWindow],
+    config: Option<&NotifyConfig>,
+) -> Result<Vec<(Transition, String)>> {
+    let mut state = MaintenanceState::load(workgraph_dir)?;
+    let before = (state.manual.clone(), state.announced.clone());
+    let transitions = sweep_state(&mut state, windows, Utc::now());
+    if (state.manual.clone(), state.announced.clone()) != before {
+        state.save(workgraph_dir)?;
+    }
+    Ok(transitions
+        .into_iter()
+        .map(|t| {
+            let outcome =
+                crate::deadline::send_urgent(workgraph_dir, config, "maintenance", &t.message());
+            (t, outcome)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn nightly() -> PauseWindow {
+        PauseWindow {
+            cron: "0 2 * * *".to_string(),
+            duration: "2h".to_string(),
+            reason: Some("db backup".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_window_covers_its_duration_only() {
+        let w = nightly();
+        assert_eq!(
+            window_end(&w, at("2026-03-01T02:00:00Z")).unwrap(),
+            Some(at("2026-03-01T04:00:00Z"))
+        );
+        assert_eq!(
+            window_end(&w, at("2026-03-01T03:59:00Z")).unwrap(),
+            Some(at("2026-03-01T04:00:00Z"))
+        );
+        assert_eq!(window_end(&w, at("2026-03-01T04:00:00Z")).unwrap(), None);
+        assert_eq!(window_end(&w, at("2026-03-01T01:59:00Z")).unwrap(), None);
+
+        let bad = PauseWindow {
+            duration: "soon".to_string(),
+            ..nightly()
+        };
+        assert!(window_end(&bad, at("2026-03-01T02:30:00Z")).is_err());
+    }
+
+    #[test]
+    fn test_manual_maintenance_wins_and_expires() {
+        let mut state = MaintenanceState {
+            manual: Some(ManualMaintenance {
+                reason: "k8s upgrade".to_string(),
+                since: "2026-03-01T01:00:00Z".to_string(),
+                until: Some("2026-03-01T03:00:00Z".to_string()),
+            }),
+            announced: None,
+        };
+        let windows = [nightly()];
+        let active = active_at(&state, &windows, at("2026-03-01T02:30:00Z")).unwrap();
+        assert_eq!(active.source, MaintenanceSource::Manual);
+        assert_eq!(active.reason, "k8s upgrade");
+
+        let expired = sweep_state(&mut state, &windows, at("2026-03-01T03:00:00Z"));
+        assert!(state.manual.is_none());
+        assert_eq!(
+            expired,
+            vec![Transition::Started(ActiveMaintenance {
+                reason: "db backup".to_string(),
+                source: MaintenanceSource::Window {
+                    cron: "0 2 * * *".to_string()
+                },
+                until: Some(at("2026-03-01T04:00:00Z")),
+            })]
+        );
+    }
+
+    #[test]
+    fn test_sweep_announces_start_and_end_once() {
+        let mut state = MaintenanceState::default();
+        let windows = [nightly()];
+        assert!(sweep_state(&mut state, &windows, at("2026-03-01T01:00:00Z")).is_empty());
+
+        let started = sweep_state(&mut state, &windows, at("2026-03-01T02:01:00Z"));
+        assert!(matches!(started.as_slice(), [Transition::Started(_)]));
+        assert!(sweep_state(&mut state, &windows, at("2026-03-01T03:00:00Z")).is_empty());
+
+        let ended = sweep_state(&mut state, &windows, at("2026-03-01T04:01:00Z"));
+        assert_eq!(
+            ended,
+            vec![Transition::Ended {
+                reason: "db backup".to_string()
+            }]
+        );
+        assert!(state.announced.is_none());
+    }
+}
@@ -11,6 +11,7 @@
 //! - `fail <task> [reason]` - Mark a task as failed
 //! - `input <task> <text>` - Add input/log entry to a task
 //! - `unclaim <task>` - Release a claimed task
+//! - `request <text>` - Submit a request to the triage inbox (prefix required)
 //! - `status` - Show current status
 //! - `ready` - List ready tasks
 //! - `help` - Show help
@@ -20,6 +21,7 @@ use std::path::Path;
 use chrono::Utc;
 
 use crate::graph::{LogEntry, Status};
+use crate::inbox::{self, Submission};
 use crate::parser::{load_graph, modify_graph};
 
 /// A parsed command from a Matrix message
@@ -41,6 +43,8 @@ pub enum MatrixCommand {
     Input { task_id: String, text: String },
     /// Release a claimed task
     Unclaim { task_id: String },
+    /// Submit a request to the triage inbox
+    Request { text: String },
     /// Show current status summary
     Status,
     /// List ready tasks
@@ -76,8 +80,23 @@ impl MatrixCommand {
 
         let command_word = words[0].to_lowercase();
 
-        // Only parse if we had a prefix OR if it starts with a known command
+        // `request` keeps the rest of the message verbatim (line breaks
+        // included) and is only recognised with a prefix, since the bare
+        // word is common in ordinary chat.
         let has_prefix = stripped.len() < message.len();
+        if has_prefix && command_word == "request" {
+            let text = stripped[words[0].len()..].trim();
+            if text.is_empty() {
+                return Some(MatrixCommand::Unknown {
+                    command: "request (missing text)".to_string(),
+                });
+            }
+            return Some(MatrixCommand::Request {
+                text: text.to_string(),
+            });
+        }
+
+        // Only parse if we had a prefix OR if it starts with a known command
         if !has_prefix && !is_known_command(&command_word) {
             return None;
         }
@@ -101,6 +120,7 @@ impl MatrixCommand {
                 format!("Add input to task '{}': {}", task_id, text)
             }
             MatrixCommand::Unclaim { task_id } => format!("Unclaim task '{}'", task_id),
+            MatrixCommand::Request { text } => format!("Submit request: {}", text),
             MatrixCommand::Status => "Show status".to_string(),
             MatrixCommand::Ready => "List ready tasks".to_string(),
             MatrixCommand::Help => "Show help".to_string(),
@@ -257,6 +277,7 @@ pub fn help_text() -> String {
 • `fail <task> [reason]` - Mark a task as failed
 • `input <task> <text>` - Add a log entry to a task
 • `unclaim <task>` - Release a claimed task
+• `!wg request <text>` - Submit a request for triage (first line is the title)
 • `ready` - List tasks ready to work on
 • `status` - Show project status
 • `help` - Show this help
@@ -294,6 +315,9 @@ pub fn execute_command(workgraph_dir: &Path, command: &MatrixCommand, sender: &s
             execute_input(workgraph_dir, task_id, text, &actor)
         }
         MatrixCommand::Unclaim { task_id } => execute_unclaim(workgraph_dir, task_id),
+        MatrixCommand::Request { text } => {
+            execute_request(workgraph_dir, text, &extract_localpart(sender))
+        }
         MatrixCommand::Status => execute_status(workgraph_dir),
         MatrixCommand::Ready => execute_ready(workgraph_dir),
         MatrixCommand::Help => help_text(),
@@ -577,6 +601,17 @@ pub fn execute_status(workgraph_dir: &Path) -> String {
     )
 }
 
+/// Execute request command: file the text as an inbox draft
+pub fn execute_request(workgraph_dir: &Path, text: &str, submitter: &str) -> String {
+    let Some(submission) = Submission::from_text("matrix", Some(submitter), text) else {
+        return "Error: request text is empty".to_string();
+    };
+    match inbox::submit(workgraph_dir, &submission) {
+        Ok(id) => format!("Request filed as '{}' for triage", id),
+        Err(e) => format!("Error: {}", e),
+    }
+}
+
 /// Execute ready command
 pub fn execute_ready(workgraph_dir: &Path) -> String {
     let graph_path = workgraph_dir.join("graph.jsonl");
@@ -747,6 +782,19 @@ mod tests {
         assert_eq!(cmd, MatrixCommand::Help);
     }
 
+    #[test]
+    fn test_parse_request() {
+        let cmd = MatrixCommand::parse("!wg request Add CSV export\nfor invoices").unwrap();
+        assert_eq!(
+            cmd,
+            MatrixCommand::Request {
+                text: "Add CSV export\nfor invoices".to_string()
+            }
+        );
+        // Bare "request" is ordinary chat, not a command
+        assert!(MatrixCommand::parse("request a review please").is_none());
+    }
+
     #[test]
     fn test_parse_with_wg_prefix() {
         let cmd = MatrixCommand::parse("wg claim task-1").unwrap();
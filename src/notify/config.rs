@@ -11,7 +11,7 @@ use std::time::Duration;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
-use super::{EventType, RoutingRule};
+use super::{EventType, NotificationChannel, NotificationRouter, RoutingRule};
 
 // ---------------------------------------------------------------------------
 // Top-level config
@@ -53,7 +53,8 @@ pub struct RoutingConfig {
     pub digest: Vec<String>,
 }
 
-/// Escalation timeout configuration (values in seconds).
+/// Escalation configuration: channel timeouts (values in seconds) and the
+/// stuck-task chain (see [`crate::escalation`]).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EscalationConfig {
     /// Seconds before escalating an unanswered approval request.
@@ -63,6 +64,16 @@ pub struct EscalationConfig {
     /// Seconds before escalating an unanswered urgent notification.
     #[serde(default = "default_urgent_timeout")]
     pub urgent_timeout: u64,
+
+    /// Ordered steps escalating tasks stuck in-progress or blocked.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chain: Vec<crate::escalation::EscalationStep>,
+
+    /// Seconds between follow-up reminders for tasks blocked on an external
+    /// team (see [`crate::external_blocker`]). 0 sends only the reminder on
+    /// the expected date.
+    #[serde(default = "default_external_follow_up")]
+    pub external_follow_up: u64,
 }
 
 impl Default for EscalationConfig {
@@ -70,6 +81,8 @@ impl Default for EscalationConfig {
         Self {
             approval_timeout: default_approval_timeout(),
             urgent_timeout: default_urgent_timeout(),
+            chain: Vec::new(),
+            external_follow_up: default_external_follow_up(),
         }
     }
 }
@@ -82,6 +95,10 @@ fn default_urgent_timeout() -> u64 {
     3600
 }
 
+fn default_external_follow_up() -> u64 {
+    3 * 24 * 3600
+}
+
 // ---------------------------------------------------------------------------
 // Loading
 // ---------------------------------------------------------------------------
@@ -159,6 +176,39 @@ impl NotifyConfig {
         &self.routing.default
     }
 
+    /// Build a [`NotificationRouter`] from the configured routing rules and
+    /// the channel sections that can be constructed without an external
+    /// runtime (webhook, telegram). Returns warnings for invalid sections.
+    pub fn build_router(&self) -> (NotificationRouter, Vec<String>) {
+        let mut channels: Vec<Box<dyn NotificationChannel>> = Vec::new();
+        let mut warnings = Vec::new();
+
+        if let Some(val) = self.channels.get("webhook") {
+            match val.clone().try_into::<super::webhook::WebhookConfig>() {
+                Ok(wh_config) => {
+                    channels.push(Box::new(super::webhook::WebhookChannel::new(wh_config)));
+                }
+                Err(e) => warnings.push(format!("Invalid webhook config: {}", e)),
+            }
+        }
+
+        if self.has_channel_config("telegram") {
+            match super::telegram::TelegramConfig::from_notify_config(self) {
+                Ok(tg_config) => {
+                    channels.push(Box::new(super::telegram::TelegramChannel::new(tg_config)));
+                }
+                Err(e) => warnings.push(format!("Invalid telegram config: {}", e)),
+            }
+        }
+
+        let router = NotificationRouter::new(
+            channels,
+            self.to_routing_rules(),
+            self.default_channels().to_vec(),
+        );
+        (router, warnings)
+    }
+
     /// Check whether a named channel section exists in the config.
     pub fn has_channel_config(&self, name: &str) -> bool {
         self.channels.contains_key(name)
@@ -283,6 +333,8 @@ to = ["user@example.com"]
             escalation: EscalationConfig {
                 approval_timeout: 900,
                 urgent_timeout: 1800,
+                chain: Vec::new(),
+                external_follow_up: 0,
             },
             channels: HashMap::new(),
         };
@@ -748,6 +748,8 @@ mod tests {
             escalation: EscalationConfig {
                 approval_timeout: 600,
                 urgent_timeout: 1200,
+                chain: Vec::new(),
+                external_follow_up: 0,
             },
             channels: HashMap::new(),
         };
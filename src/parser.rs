@@ -1,7 +1,10 @@
-use crate::graph::{Node, Task, WorkGraph};
-use std::collections::HashMap;
+use crate::deprecation::{TASKS, Tally};
+use crate::graph::{Node, NodeBaseline, Task, WorkGraph};
+use crate::graph_index::{GraphIndex, IndexEntry};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
@@ -14,8 +17,22 @@ pub enum ParseError {
         line: usize,
         source: serde_json::Error,
     },
+    #[error("Invalid UTF-8 on line {line}")]
+    Encoding { line: usize },
+    /// A field no node accepts, found in strict mode (see [`crate::strict`]).
+    #[error("{0}")]
+    UnknownField(crate::strict::UnknownField),
+    #[error("Failed to serialize graph: {0}")]
+    Serialize(serde_json::Error),
     #[error("Lock error: {0}")]
     Lock(String),
+    #[error(
+        "Concurrent modification of {}: changed by another process since this graph was loaded; reload and retry",
+        .0.join(", ")
+    )]
+    Conflict(Vec<String>),
+    #[error("Refusing to save a partially loaded graph; load the full graph to modify it")]
+    Partial,
 }
 
 /// RAII guard for file locks - automatically releases lock on drop
@@ -165,26 +182,66 @@ fn get_lock_path<P: AsRef<Path>>(graph_path: P) -> PathBuf {
 /// Callers must hold the flock themselves or use [`load_graph`] which
 /// acquires it automatically.
 fn load_graph_inner<P: AsRef<Path>>(path: P) -> Result<WorkGraph, ParseError> {
+    let path = path.as_ref();
     let file = File::open(path)?;
-    let reader = BufReader::new(file);
+    let mut deprecated = Tally::new(&TASKS);
+    let (graph, line_hashes) = parse_graph_lines(BufReader::new(file).lines(), &mut deprecated)?;
+    crate::deprecation::warn_once(path, &deprecated);
+    let baseline = line_hashes
+        .into_iter()
+        .filter_map(|(id, disk)| {
+            let revision = node_revision(graph.get_node(&id)?);
+            Some((
+                id,
+                NodeBaseline {
+                    ours: None,
+                    disk,
+                    revision,
+                },
+            ))
+        })
+        .collect();
+    graph.set_baseline(path.to_path_buf(), baseline);
+    Ok(graph)
+}
+
+/// Parse a WG task graph from JSONL content already in memory (e.g. a
+/// historical `graph.jsonl` read from git).
+pub fn parse_graph(content: &str) -> Result<WorkGraph, ParseError> {
+    parse_graph_lines(
+        content.lines().map(|l| Ok(l.to_string())),
+        &mut Tally::new(&TASKS),
+    )
+    .map(|(graph, _)| graph)
+}
+
+/// Parse a WG task graph from raw JSONL bytes, exactly as [`load_graph`]
+/// reads a file. Malformed input of any kind (bad UTF-8, bad JSON, wrong
+/// field types) is an error, never a panic; this is the fuzzing entry point
+/// for graph files received from elsewhere.
+pub fn parse_graph_reader<R: BufRead>(reader: R) -> Result<WorkGraph, ParseError> {
+    parse_graph_lines(reader.lines(), &mut Tally::new(&TASKS)).map(|(graph, _)| graph)
+}
+
+/// Parse JSONL lines into a graph, also returning a hash of each node's line.
+/// Uses of renamed fields are counted in `deprecated`.
+fn parse_graph_lines(
+    lines: impl Iterator<Item = std::io::Result<String>>,
+    deprecated: &mut Tally,
+) -> Result<(WorkGraph, HashMap<String, u64>), ParseError> {
     let mut graph = WorkGraph::new();
+    let mut line_hashes = HashMap::new();
 
-    for (line_num, line) in reader.lines().enumerate() {
-        let line = line?;
+    for (line_num, line) in lines.enumerate() {
+        let line = line.map_err(|e| match e.kind() {
+            std::io::ErrorKind::InvalidData => ParseError::Encoding { line: line_num + 1 },
+            _ => ParseError::Io(e),
+        })?;
         let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-        // Skip legacy Actor nodes (removed in actor-system cleanup)
-        if let Ok(v) = serde_json::from_str::<serde_json::Value>(trimmed)
-            && v.get("kind").and_then(|k| k.as_str()) == Some("actor")
-        {
+        deprecated.scan_line(trimmed);
+        let Some(node) = parse_node_line(trimmed, line_num + 1)? else {
             continue;
-        }
-        let node: Node = serde_json::from_str(trimmed).map_err(|e| ParseError::Json {
-            line: line_num + 1,
-            source: e,
-        })?;
+        };
         let node_id = node.id().to_string();
         if graph.get_node(&node_id).is_some() {
             eprintln!(
@@ -193,10 +250,60 @@ fn load_graph_inner<P: AsRef<Path>>(path: P) -> Result<WorkGraph, ParseError> {
                 line_num + 1
             );
         }
+        line_hashes.insert(node_id, content_hash(trimmed));
         graph.add_node(node);
     }
 
-    Ok(graph)
+    Ok((graph, line_hashes))
+}
+
+/// Parse one JSONL line into a node. Blank lines, comments, and legacy
+/// Actor nodes yield `None`.
+pub(crate) fn parse_node_line(line: &str, line_num: usize) -> Result<Option<Node>, ParseError> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return Ok(None);
+    }
+    // Skip legacy Actor nodes (removed in actor-system cleanup)
+    if let Ok(v) = serde_json::from_str::<serde_json::Value>(trimmed)
+        && v.get("kind").and_then(|k| k.as_str()) == Some("actor")
+    {
+        return Ok(None);
+    }
+    if crate::strict::enabled()
+        && let Some(unknown) = crate::strict::check_node(trimmed, line_num)
+    {
+        return Err(ParseError::UnknownField(unknown));
+    }
+    serde_json::from_str(trimmed)
+        .map(Some)
+        .map_err(|e| ParseError::Json {
+            line: line_num,
+            source: e,
+        })
+}
+
+/// 64-bit FNV-1a of a serialized node line.
+///
+/// These hashes are persisted in the graph index, so they must not change
+/// between builds — `DefaultHasher` makes no such promise.
+pub(crate) fn content_hash(json: &str) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    json.bytes()
+        .fold(OFFSET, |hash, b| (hash ^ u64::from(b)).wrapping_mul(PRIME))
+}
+
+fn node_hash(node: &Node) -> Result<u64, ParseError> {
+    let json = serde_json::to_string(node).map_err(ParseError::Serialize)?;
+    Ok(content_hash(&json))
+}
+
+fn node_revision(node: &Node) -> u64 {
+    match node {
+        Node::Task(t) => t.revision,
+        _ => 0,
+    }
 }
 
 /// Load a WG task graph from a JSONL file.
@@ -218,12 +325,89 @@ pub fn load_graph<P: AsRef<Path>>(path: P) -> Result<WorkGraph, ParseError> {
     // Lock (if acquired) is automatically released when _lock goes out of scope
 }
 
+/// Load only the nodes chosen by `select` from a graph file, using its
+/// on-disk index (see [`crate::graph_index`]) to read just those records.
+///
+/// `select` receives the index and returns the IDs to load; IDs without a
+/// record are skipped. Files without an index (anything but a workgraph's
+/// `graph.jsonl`) are loaded in full. A subset is marked
+/// [`WorkGraph::is_partial`] and [`save_graph`] refuses to write it, so use
+/// this only for read-only commands.
+pub fn load_graph_subset<P, F>(path: P, select: F) -> Result<WorkGraph, ParseError>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&GraphIndex) -> HashSet<String>,
+{
+    let path = path.as_ref();
+    let lock_path = get_lock_path(path);
+    let _lock = FileLock::try_acquire_shared(&lock_path)?;
+    let Some(index) = GraphIndex::open(path)? else {
+        return load_graph_inner(path);
+    };
+
+    let mut wanted: Vec<(&String, &IndexEntry)> = select(&index)
+        .iter()
+        .filter_map(|id| index.entries.get_key_value(id))
+        .collect();
+    wanted.sort_by_key(|(_, entry)| entry.offset);
+
+    let mut file = File::open(path)?;
+    let mut graph = WorkGraph::new();
+    for (id, entry) in wanted {
+        match read_record(&mut file, id, entry)? {
+            Some(node) => graph.add_node(node),
+            // The file changed underneath the index; read it all instead.
+            None => return load_graph_inner(path),
+        }
+    }
+    graph.mark_partial();
+    Ok(graph)
+}
+
+/// Read the record `entry` points at, or `None` if it doesn't hold node `id`
+/// (the index is out of date).
+fn read_record(file: &mut File, id: &str, entry: &IndexEntry) -> Result<Option<Node>, ParseError> {
+    let mut buf = vec![0; entry.len as usize];
+    file.seek(SeekFrom::Start(entry.offset))?;
+    let node = file
+        .read_exact(&mut buf)
+        .ok()
+        .and_then(|()| String::from_utf8(buf).ok())
+        .and_then(|line| parse_node_line(&line, 0).ok().flatten());
+    Ok(node.filter(|node| node.id() == id))
+}
+
 /// Save a WG task graph to a JSONL file (internal, no locking).
 ///
 /// Callers must hold the flock themselves or use [`save_graph`] which
 /// acquires it automatically.
 fn save_graph_inner<P: AsRef<Path>>(graph: &WorkGraph, path: P) -> Result<(), ParseError> {
     let path = path.as_ref();
+    let nodes: Vec<Cow<Node>> = graph.nodes().map(Cow::Borrowed).collect();
+    let written = write_nodes(&nodes, path)?;
+    let baseline = written
+        .into_iter()
+        .map(|(id, disk)| {
+            let revision = graph.get_node(&id).map_or(0, node_revision);
+            (
+                id,
+                NodeBaseline {
+                    ours: None,
+                    disk,
+                    revision,
+                },
+            )
+        })
+        .collect();
+    graph.set_baseline(path.to_path_buf(), baseline);
+    Ok(())
+}
+
+/// Atomically replace the file at `path` with `nodes`, returning the hash
+/// of each node's line.
+fn write_nodes(nodes: &[Cow<Node>], path: &Path) -> Result<HashMap<String, u64>, ParseError> {
+    let mut written = HashMap::with_capacity(nodes.len());
+    let mut index_entries = BTreeMap::new();
 
     // Write to a temporary file in the same directory, then atomically rename.
     // This ensures a crash mid-write leaves the original file intact.
@@ -237,10 +421,16 @@ fn save_graph_inner<P: AsRef<Path>>(graph: &WorkGraph, path: P) -> Result<(), Pa
             .truncate(true)
             .open(&tmp_path)?;
 
-        for node in graph.nodes() {
-            let json =
-                serde_json::to_string(node).map_err(|e| ParseError::Json { line: 0, source: e })?;
+        let mut offset = 0u64;
+        for node in nodes {
+            let json = serde_json::to_string(node.as_ref()).map_err(ParseError::Serialize)?;
             writeln!(file, "{}", json)?;
+            written.insert(node.id().to_string(), content_hash(&json));
+            index_entries.insert(
+                node.id().to_string(),
+                IndexEntry::for_node(node, offset, &json),
+            );
+            offset += json.len() as u64 + 1;
         }
 
         file.flush()?;
@@ -259,25 +449,288 @@ fn save_graph_inner<P: AsRef<Path>>(graph: &WorkGraph, path: P) -> Result<(), Pa
 
     if result.is_ok() {
         std::fs::rename(&tmp_path, path)?;
+        if crate::graph_index::index_path(path).is_some() {
+            // Best effort: a missing or stale index is rebuilt on next use.
+            let _ = crate::graph_index::write(path, index_entries);
+        }
     } else {
         // Clean up temp file on failure
         let _ = std::fs::remove_file(&tmp_path);
     }
 
-    result
+    result.map(|()| written)
 }
 
 /// Save a WG task graph to a JSONL file
 /// Uses advisory file locking and atomic write (temp file + rename) to
 /// prevent data loss on crash.
+///
+/// When the graph was loaded from the same file, the write is optimistic:
+/// under the lock the file is checked and merged node by node. Nodes this
+/// caller did not touch keep their on-disk version (so concurrent changes by
+/// other agents survive), nodes added by others are kept, and each changed
+/// task's `revision` is bumped. If another process changed or removed a task
+/// this caller also changed, nothing is written and [`ParseError::Conflict`]
+/// is returned; reload (or use [`modify_graph`]) and retry. Concurrent
+/// changes are spotted from the line hashes and revisions in the graph's
+/// index, so only the records changed by other writers are parsed.
 pub fn save_graph<P: AsRef<Path>>(graph: &WorkGraph, path: P) -> Result<(), ParseError> {
     let path = path.as_ref();
+    if graph.is_partial() {
+        return Err(ParseError::Partial);
+    }
     let lock_path = get_lock_path(path);
     let _lock = FileLock::acquire(&lock_path)?;
-    save_graph_inner(graph, path)
+    match graph.baseline() {
+        Some((loaded_from, baseline)) if same_file(&loaded_from, path) && path.exists() => {
+            merge_and_save(graph, &baseline, path)
+        }
+        _ => save_graph_inner(graph, path),
+    }
     // Lock is automatically released when _lock goes out of scope
 }
 
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b
+        || matches!(
+            (a.canonicalize(), b.canonicalize()),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+/// The graph file as `save_graph` finds it under the lock: the line hash and
+/// revision of each node, with records parsed only on request.
+enum DiskState {
+    /// Read through the on-disk index.
+    Indexed { index: GraphIndex, file: File },
+    /// Parsed in full, for files without an index.
+    Parsed {
+        graph: WorkGraph,
+        stamps: HashMap<String, NodeBaseline>,
+    },
+}
+
+impl DiskState {
+    fn open(path: &Path) -> Result<Self, ParseError> {
+        match GraphIndex::open(path)? {
+            Some(index) => Ok(DiskState::Indexed {
+                index,
+                file: File::open(path)?,
+            }),
+            None => Self::parse(path),
+        }
+    }
+
+    fn parse(path: &Path) -> Result<Self, ParseError> {
+        let graph = load_graph_inner(path)?;
+        let stamps = graph.baseline().map(|(_, b)| b).unwrap_or_default();
+        Ok(DiskState::Parsed { graph, stamps })
+    }
+
+    /// Line hash and revision of node `id`, if the file has it.
+    fn stamp(&self, id: &str) -> Option<(u64, u64)> {
+        match self {
+            DiskState::Indexed { index, .. } => index.entries.get(id).map(|e| (e.hash, e.revision)),
+            DiskState::Parsed { stamps, .. } => stamps.get(id).map(|b| (b.disk, b.revision)),
+        }
+    }
+
+    /// Whether another writer rewrote node `id` since `base` was taken.
+    fn changed_since(&self, id: &str, base: &NodeBaseline) -> bool {
+        self.stamp(id)
+            .is_some_and(|(hash, revision)| hash != base.disk || revision != base.revision)
+    }
+
+    fn ids(&self) -> Vec<String> {
+        match self {
+            DiskState::Indexed { index, .. } => index.entries.keys().cloned().collect(),
+            DiskState::Parsed { stamps, .. } => stamps.keys().cloned().collect(),
+        }
+    }
+
+    /// Parse node `id`, which must have a stamp. `None` means the index
+    /// didn't match the file after all.
+    fn node(&mut self, id: &str) -> Result<Option<Node>, ParseError> {
+        match self {
+            DiskState::Indexed { index, file } => match index.entries.get(id) {
+                Some(entry) => read_record(file, id, entry),
+                None => Ok(None),
+            },
+            DiskState::Parsed { graph, .. } => Ok(graph.get_node(id).cloned()),
+        }
+    }
+}
+
+/// Three-way merge of `graph` (this writer), its `baseline` (what it
+/// loaded) and the current file, written to `path`.
+fn merge_and_save(
+    graph: &WorkGraph,
+    baseline: &HashMap<String, NodeBaseline>,
+    path: &Path,
+) -> Result<(), ParseError> {
+    let merged = match merge_nodes(graph, baseline, &mut DiskState::open(path)?)? {
+        Some(merged) => Some(merged),
+        // The index was out of date; read the whole file instead.
+        None => merge_nodes(graph, baseline, &mut DiskState::parse(path)?)?,
+    };
+    let Some((nodes, mut next_baseline)) = merged else {
+        return Err(ParseError::Io(std::io::Error::other(
+            "graph file changed during merge",
+        )));
+    };
+
+    let written = write_nodes(&nodes, path)?;
+    // Only nodes this graph holds are tracked; nodes kept from disk stay
+    // "added by another writer" on the next save.
+    for (id, entry) in next_baseline.iter_mut() {
+        entry.disk = written.get(id).copied().unwrap_or_default();
+    }
+    graph.set_baseline(path.to_path_buf(), next_baseline);
+    Ok(())
+}
+
+type Merged<'a> = (Vec<Cow<'a, Node>>, HashMap<String, NodeBaseline>);
+
+/// The nodes to write for [`merge_and_save`] and their new baseline, or
+/// `None` if `disk` turned out not to match the file.
+fn merge_nodes<'a>(
+    graph: &'a WorkGraph,
+    baseline: &HashMap<String, NodeBaseline>,
+    disk: &mut DiskState,
+) -> Result<Option<Merged<'a>>, ParseError> {
+    let mut nodes: Vec<Cow<Node>> = Vec::with_capacity(graph.len());
+    let mut next_baseline = HashMap::with_capacity(graph.len());
+    let mut conflicts = Vec::new();
+
+    for node in graph.nodes() {
+        let id = node.id();
+        let ours = node_hash(node)?;
+        let base = baseline.get(id);
+        let stamp = disk.stamp(id);
+        let changed = base.is_some_and(|b| disk.changed_since(id, b));
+
+        let untouched = match base {
+            Some(b) if b.ours.unwrap_or(b.disk) == ours => true,
+            // Either changed here, or the loaded line doesn't round-trip
+            // through serialization (it was written by hand or by an older
+            // `wg`). Nothing to write if the record on disk already matches.
+            Some(b) if b.ours.is_none() && stamp.is_some() => {
+                let Some(current) = disk.node(id)? else {
+                    return Ok(None);
+                };
+                current == *node
+            }
+            _ => false,
+        };
+        if untouched {
+            // Untouched here: whatever is on disk now wins, including a
+            // concurrent deletion.
+            let Some((hash, revision)) = stamp else {
+                continue;
+            };
+            let current = if hash == ours {
+                Cow::Borrowed(node)
+            } else {
+                let Some(current) = disk.node(id)? else {
+                    return Ok(None);
+                };
+                Cow::Owned(current)
+            };
+            nodes.push(current);
+            next_baseline.insert(
+                id.to_string(),
+                NodeBaseline {
+                    ours: Some(ours),
+                    disk: 0,
+                    revision,
+                },
+            );
+            continue;
+        }
+
+        let concurrent = match (base, stamp) {
+            (Some(_), Some(_)) => changed,
+            // Removed by someone else while we edited it.
+            (Some(_), None) => true,
+            // Added by both sides under the same ID.
+            (None, Some(_)) => true,
+            (None, None) => false,
+        };
+        if concurrent {
+            let same = match stamp {
+                Some(_) => {
+                    let Some(current) = disk.node(id)? else {
+                        return Ok(None);
+                    };
+                    same_content(&current, node)
+                }
+                None => false,
+            };
+            if !same {
+                conflicts.push(id.to_string());
+                continue;
+            }
+        }
+
+        let revision = match node {
+            Node::Task(_) => {
+                let prev = base.map_or(0, |b| b.revision);
+                prev.max(stamp.map_or(0, |(_, revision)| revision)) + 1
+            }
+            _ => 0,
+        };
+        let written = match node {
+            Node::Task(t) if t.revision != revision => {
+                let mut t = t.clone();
+                t.revision = revision;
+                Cow::Owned(Node::Task(t))
+            }
+            _ => Cow::Borrowed(node),
+        };
+        nodes.push(written);
+        next_baseline.insert(
+            id.to_string(),
+            NodeBaseline {
+                ours: Some(ours),
+                disk: 0,
+                revision,
+            },
+        );
+    }
+
+    // Nodes on disk that this graph doesn't have: added by another writer
+    // (kept), or deleted here (dropped unless changed concurrently).
+    for id in disk.ids() {
+        if graph.get_node(&id).is_some() {
+            continue;
+        }
+        match baseline.get(&id) {
+            None => {
+                let Some(current) = disk.node(&id)? else {
+                    return Ok(None);
+                };
+                nodes.push(Cow::Owned(current));
+            }
+            Some(base) if disk.changed_since(&id, base) => conflicts.push(id),
+            Some(_) => {}
+        }
+    }
+
+    if !conflicts.is_empty() {
+        conflicts.sort();
+        return Err(ParseError::Conflict(conflicts));
+    }
+    Ok(Some((nodes, next_baseline)))
+}
+
+/// Whether two versions of a node are equal apart from bookkeeping.
+fn same_content(a: &Node, b: &Node) -> bool {
+    match (a, b) {
+        (Node::Task(a), Node::Task(b)) => a.substantively_eq(b),
+        _ => a == b,
+    }
+}
+
 /// Atomically load, modify, and save a graph file.
 ///
 /// The file lock is held for the entire load-modify-save cycle, preventing
@@ -294,7 +747,23 @@ where
     P: AsRef<Path>,
     F: FnOnce(&mut WorkGraph) -> bool,
 {
-    let path = path.as_ref();
+    modify_graph_inner(path.as_ref(), f, true)
+}
+
+/// Like [`modify_graph`], but the change is not recorded in the undo/redo
+/// journal. Used by `wg undo`/`wg redo` themselves.
+pub fn modify_graph_without_journal<P, F>(path: P, f: F) -> Result<WorkGraph, ParseError>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut WorkGraph) -> bool,
+{
+    modify_graph_inner(path.as_ref(), f, false)
+}
+
+fn modify_graph_inner<F>(path: &Path, f: F, journal: bool) -> Result<WorkGraph, ParseError>
+where
+    F: FnOnce(&mut WorkGraph) -> bool,
+{
     let lock_path = get_lock_path(path);
     let _lock = FileLock::acquire(&lock_path)?;
 
@@ -308,13 +777,21 @@ where
     if modified {
         bump_interaction_timestamps(&mut graph, &before);
         save_graph_inner(&graph, path)?;
+        // Only a workgraph's own graph.jsonl has a journal next to it.
+        if journal
+            && path.file_name().is_some_and(|n| n == "graph.jsonl")
+            && let Some(dir) = path.parent()
+        {
+            let _ = crate::journal::record(dir, &before, &graph);
+        }
     }
     Ok(graph)
     // Lock is automatically released when _lock goes out of scope
 }
 
 /// For every task whose persistent fields changed (other than
-/// `last_interaction_at` itself), set `last_interaction_at` to now.
+/// `last_interaction_at` itself), set `last_interaction_at` to now and bump
+/// its `revision`.
 /// New tasks created in the closure are bumped if they don't already have
 /// an interaction timestamp set.
 fn bump_interaction_timestamps(graph: &mut WorkGraph, before: &HashMap<String, Task>) {
@@ -329,6 +806,7 @@ fn bump_interaction_timestamps(graph: &mut WorkGraph, before: &HashMap<String, T
             Some(prev) => {
                 if !task.substantively_eq(prev) {
                     task.last_interaction_at = Some(now.clone());
+                    task.revision = prev.revision + 1;
                 }
             }
         }
@@ -338,7 +816,7 @@ fn bump_interaction_timestamps(graph: &mut WorkGraph, before: &HashMap<String, T
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::Task;
+    use crate::graph::{Status, Task};
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -633,10 +1111,13 @@ mod tests {
         writeln!(file).unwrap();
         file.flush().unwrap();
 
-        // BufReader::lines() returns Err for non-UTF8 lines, which becomes ParseError::Io
+        // BufReader::lines() returns Err for non-UTF8 lines, reported with the line number
         let result = load_graph(file.path());
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), ParseError::Io(_)));
+        assert!(matches!(
+            result.unwrap_err(),
+            ParseError::Encoding { line: 1 }
+        ));
     }
 
     #[test]
@@ -803,6 +1284,35 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_graph_reader_adversarial_input_is_an_error() {
+        let deep = format!(
+            r#"{{"id":"t1","kind":"task","title":"x","tags":{}{}}}"#,
+            "[".repeat(10_000),
+            "]".repeat(10_000)
+        );
+        let cases: [(&[u8], usize); 4] = [
+            (deep.as_bytes(), 1),
+            (
+                b"\n{\"id\":\"t1\",\"kind\":\"task\",\"title\":\"x\",\"priority\":99999999999}",
+                2,
+            ),
+            (
+                b"{\"id\":\"t1\",\"kind\":\"task\",\"title\":\"x\"}\n\xff\n",
+                2,
+            ),
+            (b"{\"id\":1e999,\"kind\":\"task\"}", 1),
+        ];
+        for (input, line) in cases {
+            match parse_graph_reader(input) {
+                Err(ParseError::Json { line: l, .. }) | Err(ParseError::Encoding { line: l }) => {
+                    assert_eq!(l, line)
+                }
+                other => panic!("expected a located error, got {:?}", other.map(|g| g.len())),
+            }
+        }
+    }
+
     #[test]
     fn test_save_to_readonly_path() {
         use std::os::unix::fs::PermissionsExt;
@@ -1096,4 +1606,143 @@ mod tests {
             final_graph.len()
         );
     }
+
+    #[test]
+    fn test_save_graph_merges_concurrent_edits_to_different_tasks() {
+        let file = NamedTempFile::new().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("t1", "Task 1")));
+        graph.add_node(Node::Task(make_task("t2", "Task 2")));
+        graph.add_node(Node::Task(make_task("t3", "Task 3")));
+        save_graph(&graph, file.path()).unwrap();
+
+        // Two agents load the same snapshot and finish different tasks.
+        let mut a = load_graph(file.path()).unwrap();
+        let mut b = load_graph(file.path()).unwrap();
+        a.get_task_mut("t1").unwrap().status = Status::Done;
+        a.add_node(Node::Task(make_task("t4", "Added by a")));
+        b.get_task_mut("t2").unwrap().status = Status::Done;
+        b.remove_node("t3");
+        save_graph(&a, file.path()).unwrap();
+        save_graph(&b, file.path()).unwrap();
+
+        let merged = load_graph(file.path()).unwrap();
+        assert_eq!(merged.get_task("t1").unwrap().status, Status::Done);
+        assert_eq!(merged.get_task("t2").unwrap().status, Status::Done);
+        assert!(merged.get_task("t3").is_none());
+        assert!(merged.get_task("t4").is_some());
+        assert_eq!(merged.get_task("t1").unwrap().revision, 1);
+
+        // Saving the same graph again after further edits is not a conflict.
+        b.get_task_mut("t2").unwrap().title = "Renamed".to_string();
+        save_graph(&b, file.path()).unwrap();
+        let again = load_graph(file.path()).unwrap();
+        assert_eq!(again.get_task("t2").unwrap().revision, 2);
+        assert_eq!(again.get_task("t1").unwrap().status, Status::Done);
+        assert!(again.get_task("t4").is_some());
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        // Persisted in graph.index.json; changing it invalidates every index.
+        assert_eq!(content_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(content_hash(r#"{"id":"a"}"#), 0x4084_b641_96dc_b05d);
+    }
+
+    #[test]
+    fn test_save_graph_merges_through_the_index() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("t1", "Task 1")));
+        graph.add_node(Node::Task(make_task("t2", "Task 2")));
+        save_graph(&graph, &path).unwrap();
+        // A hand-written record that doesn't round-trip byte for byte.
+        let mut content = std::fs::read_to_string(&path).unwrap();
+        content.push_str("{ \"kind\": \"task\", \"title\": \"Legacy\", \"id\": \"old\" }\n");
+        std::fs::write(&path, content).unwrap();
+
+        let mut a = load_graph(&path).unwrap();
+        let mut b = load_graph(&path).unwrap();
+        a.get_task_mut("t1").unwrap().status = Status::Done;
+        a.add_node(Node::Task(make_task("t3", "Added by a")));
+        b.get_task_mut("t2").unwrap().status = Status::Done;
+        save_graph(&a, &path).unwrap();
+        assert!(GraphIndex::load(&path).is_some());
+        save_graph(&b, &path).unwrap();
+
+        let merged = load_graph(&path).unwrap();
+        assert_eq!(merged.get_task("t1").unwrap().status, Status::Done);
+        assert_eq!(merged.get_task("t2").unwrap().status, Status::Done);
+        assert!(merged.get_task("t3").is_some());
+        assert_eq!(merged.get_task("old").unwrap().revision, 0);
+
+        let mut c = load_graph(&path).unwrap();
+        c.get_task_mut("t1").unwrap().title = "Renamed".to_string();
+        b.get_task_mut("t1").unwrap().title = "Other".to_string();
+        save_graph(&c, &path).unwrap();
+        assert!(matches!(
+            save_graph(&b, &path),
+            Err(ParseError::Conflict(ids)) if ids == ["t1".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_save_graph_rejects_conflicting_edit() {
+        let file = NamedTempFile::new().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("t1", "Task 1")));
+        save_graph(&graph, file.path()).unwrap();
+
+        let mut a = load_graph(file.path()).unwrap();
+        let mut b = load_graph(file.path()).unwrap();
+        a.get_task_mut("t1").unwrap().status = Status::Done;
+        b.get_task_mut("t1").unwrap().status = Status::Failed;
+        save_graph(&a, file.path()).unwrap();
+
+        let err = save_graph(&b, file.path()).unwrap_err();
+        assert!(matches!(&err, ParseError::Conflict(ids) if ids == &["t1".to_string()]));
+        assert!(err.to_string().contains("reload and retry"));
+        assert_eq!(
+            load_graph(file.path())
+                .unwrap()
+                .get_task("t1")
+                .unwrap()
+                .status,
+            Status::Done
+        );
+
+        // The same change made by both sides is not a conflict.
+        let mut c = load_graph(file.path()).unwrap();
+        let mut d = load_graph(file.path()).unwrap();
+        c.get_task_mut("t1").unwrap().title = "Same".to_string();
+        d.get_task_mut("t1").unwrap().title = "Same".to_string();
+        save_graph(&c, file.path()).unwrap();
+        save_graph(&d, file.path()).unwrap();
+    }
+
+    #[test]
+    fn test_modify_graph_bumps_revision_of_changed_tasks() {
+        let file = NamedTempFile::new().unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task("t1", "Task 1")));
+        graph.add_node(Node::Task(make_task("t2", "Task 2")));
+        save_graph(&graph, file.path()).unwrap();
+
+        // A stale copy loaded before the modification can't overwrite it.
+        let mut stale = load_graph(file.path()).unwrap();
+        let graph = modify_graph(file.path(), |g| {
+            g.get_task_mut("t1").unwrap().status = Status::Done;
+            true
+        })
+        .unwrap();
+        assert_eq!(graph.get_task("t1").unwrap().revision, 1);
+        assert_eq!(graph.get_task("t2").unwrap().revision, 0);
+
+        stale.get_task_mut("t1").unwrap().status = Status::Abandoned;
+        assert!(matches!(
+            save_graph(&stale, file.path()),
+            Err(ParseError::Conflict(_))
+        ));
+    }
 }
@@ -480,7 +480,7 @@ fn find_closest<'a>(target: &str, candidates: &'a [String]) -> Option<&'a str> {
 }
 
 /// Trivial edit distance (Levenshtein) for closest-match suggestions.
-fn edit_distance(a: &str, b: &str) -> usize {
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
     let a: Vec<char> = a.chars().collect();
     let b: Vec<char> = b.chars().collect();
     let m = a.len();
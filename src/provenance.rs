@@ -4,14 +4,24 @@
 //! When the file exceeds a configurable threshold (default 10 MB), the current
 //! file is compressed with zstd and renamed to `<UTC-timestamp>.jsonl.zst`,
 //! and a fresh `operations.jsonl` is started.
+//!
+//! Entries are hash-chained: each carries the SHA-256 of its own content and
+//! the hash of the entry before it, and the hash of the newest entry is kept
+//! in `.wg/log/chain_head`. [`verify_chain`] walks the whole log (rotated
+//! files included) and reports edits, insertions, deletions, and truncation.
+//! When retention purges old rotated files, the hash of the last purged
+//! entry is kept in `.wg/log/chain_anchor` so the remaining chain still
+//! verifies.
 
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 
+use sha2::{Digest, Sha256};
+
 /// Default rotation threshold: 10 MB.
 pub const DEFAULT_ROTATION_THRESHOLD: u64 = 10 * 1024 * 1024;
 
@@ -29,6 +39,27 @@ pub struct OperationEntry {
     pub user: Option<String>,
     #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub detail: serde_json::Value,
+    /// Hash of the previous entry in the chain (`None` for the first entry).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+    /// SHA-256 over this entry's content including `prev_hash`. Entries
+    /// written before chaining was introduced have no hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+impl OperationEntry {
+    /// Compute the chain hash of this entry (ignoring any stored `hash`).
+    pub fn compute_hash(&self) -> String {
+        let mut unhashed = self.clone();
+        unhashed.hash = None;
+        // Serializing through the struct gives a fixed field order, so a
+        // stored line re-read from disk hashes to the same value.
+        let canonical = serde_json::to_value(&unhashed)
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+        hex::encode(Sha256::digest(canonical.as_bytes()))
+    }
 }
 
 /// Return the log directory: `.wg/log/`
@@ -41,6 +72,74 @@ pub fn operations_path(workgraph_dir: &Path) -> PathBuf {
     log_dir(workgraph_dir).join("operations.jsonl")
 }
 
+/// Return the path to the file holding the hash of the newest entry.
+pub fn chain_head_path(workgraph_dir: &Path) -> PathBuf {
+    log_dir(workgraph_dir).join("chain_head")
+}
+
+/// Return the path to the file holding the hash of the newest purged entry.
+pub fn chain_anchor_path(workgraph_dir: &Path) -> PathBuf {
+    log_dir(workgraph_dir).join("chain_anchor")
+}
+
+/// RAII exclusive lock on `.wg/log/operations.lock`, so concurrent writers
+/// append in a consistent chain order.
+struct AppendLock {
+    #[cfg(unix)]
+    file: File,
+}
+
+impl AppendLock {
+    fn acquire(dir: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join("operations.lock"))
+            .context("Failed to open operations lock")?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::io::AsRawFd;
+            let rc = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) };
+            if rc != 0 {
+                return Err(std::io::Error::last_os_error())
+                    .context("Failed to lock operations log");
+            }
+            Ok(Self { file })
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = file;
+            Ok(Self {})
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for AppendLock {
+    fn drop(&mut self) {
+        use std::os::unix::io::AsRawFd;
+        unsafe {
+            libc::flock(self.file.as_raw_fd(), libc::LOCK_UN);
+        }
+    }
+}
+
+/// Hash of the newest chained entry, if any.
+///
+/// Reads `chain_head`; logs written before chaining existed fall back to the
+/// last hashed entry in the log.
+fn current_head(workgraph_dir: &Path) -> Result<Option<String>> {
+    if let Ok(head) = fs::read_to_string(chain_head_path(workgraph_dir)) {
+        let head = head.trim();
+        return Ok((!head.is_empty()).then(|| head.to_string()));
+    }
+    Ok(read_all_operations(workgraph_dir)?
+        .into_iter()
+        .rev()
+        .find_map(|e| e.hash))
+}
+
 /// Append an operation entry, rotating if the file exceeds `threshold` bytes.
 pub fn append_operation(
     workgraph_dir: &Path,
@@ -51,6 +150,7 @@ pub fn append_operation(
     fs::create_dir_all(&dir).context("Failed to create log directory")?;
 
     let path = operations_path(workgraph_dir);
+    let _lock = AppendLock::acquire(&dir)?;
 
     // Check if rotation is needed before appending.
     if path.exists() {
@@ -60,7 +160,12 @@ pub fn append_operation(
         }
     }
 
-    let mut line = serde_json::to_string(entry).context("Failed to serialize operation entry")?;
+    let mut entry = entry.clone();
+    entry.prev_hash = current_head(workgraph_dir)?;
+    let hash = entry.compute_hash();
+    entry.hash = Some(hash.clone());
+
+    let mut line = serde_json::to_string(&entry).context("Failed to serialize operation entry")?;
     line.push('\n');
 
     let mut file = OpenOptions::new()
@@ -72,6 +177,7 @@ pub fn append_operation(
     // Single write_all call ensures atomicity on O_APPEND files for sizes < PIPE_BUF
     file.write_all(line.as_bytes())
         .context("Failed to write operation entry")?;
+    fs::write(chain_head_path(workgraph_dir), hash).context("Failed to update chain head")?;
 
     Ok(())
 }
@@ -93,6 +199,8 @@ pub fn record(
         actor: actor.map(String::from),
         user: Some(crate::current_user()),
         detail,
+        prev_hash: None,
+        hash: None,
     };
     append_operation(workgraph_dir, &entry, threshold)
 }
@@ -117,17 +225,13 @@ fn rotate(path: &Path, dir: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Read all operations across rotated (compressed) and current files,
-/// returned in chronological order (oldest first).
-pub fn read_all_operations(workgraph_dir: &Path) -> Result<Vec<OperationEntry>> {
+/// Rotated (compressed) log files, oldest first.
+pub fn rotated_files(workgraph_dir: &Path) -> Result<Vec<PathBuf>> {
     let dir = log_dir(workgraph_dir);
     if !dir.exists() {
         return Ok(Vec::new());
     }
-
-    let mut entries = Vec::new();
-
-    // Collect rotated files, sorted by name (which is chronological).
+    // Names are rotation timestamps, so sorting by name is chronological.
     let mut rotated: Vec<PathBuf> = Vec::new();
     for entry in fs::read_dir(&dir).context("Failed to read log directory")? {
         let entry = entry?;
@@ -138,26 +242,90 @@ pub fn read_all_operations(workgraph_dir: &Path) -> Result<Vec<OperationEntry>>
         }
     }
     rotated.sort();
+    Ok(rotated)
+}
 
-    // Read rotated (compressed) files.
-    for rpath in &rotated {
-        let compressed = fs::read(rpath)
-            .with_context(|| format!("Failed to read rotated file {}", rpath.display()))?;
-        let mut decompressed = Vec::new();
-        zstd::stream::read::Decoder::new(compressed.as_slice())
-            .context("Failed to create zstd decoder")?
-            .read_to_end(&mut decompressed)
-            .context("Failed to decompress rotated file")?;
-
-        for line in decompressed.split(|&b| b == b'\n') {
-            if line.is_empty() {
-                continue;
+/// Read the entries of one rotated (compressed) log file.
+fn read_rotated(rpath: &Path) -> Result<Vec<OperationEntry>> {
+    let compressed = fs::read(rpath)
+        .with_context(|| format!("Failed to read rotated file {}", rpath.display()))?;
+    let mut decompressed = Vec::new();
+    zstd::stream::read::Decoder::new(compressed.as_slice())
+        .context("Failed to create zstd decoder")?
+        .read_to_end(&mut decompressed)
+        .context("Failed to decompress rotated file")?;
+
+    let mut entries = Vec::new();
+    for line in decompressed.split(|&b| b == b'\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let entry: OperationEntry = serde_json::from_slice(line)
+            .with_context(|| format!("Failed to parse operation entry from {}", rpath.display()))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// When a rotated file was rotated, from its `<UTC-timestamp>.jsonl.zst` name.
+fn rotated_at(rpath: &Path) -> Option<DateTime<Utc>> {
+    let name = rpath.file_name()?.to_str()?.strip_suffix(".jsonl.zst")?;
+    NaiveDateTime::parse_from_str(name, "%Y%m%dT%H%M%S%.fZ")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+/// Delete rotated log files whose entries all predate `cutoff`, oldest
+/// first, and record the hash of the last deleted entry as the chain
+/// anchor. Only a contiguous prefix of the log is removed, so the remaining
+/// chain stays verifiable. With `dry_run`, nothing is deleted.
+///
+/// Returns the removed files with their sizes in bytes.
+pub fn purge_rotated(
+    workgraph_dir: &Path,
+    cutoff: DateTime<Utc>,
+    dry_run: bool,
+) -> Result<Vec<(PathBuf, u64)>> {
+    let dir = log_dir(workgraph_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let _lock = AppendLock::acquire(&dir)?;
+    let mut purged = Vec::new();
+    let mut anchor = None;
+    for rpath in rotated_files(workgraph_dir)? {
+        match rotated_at(&rpath) {
+            Some(at) if at < cutoff => {}
+            _ => break,
+        }
+        if let Some(hash) = read_rotated(&rpath)?.into_iter().rev().find_map(|e| e.hash) {
+            anchor = Some(hash);
+        }
+        let size = fs::metadata(&rpath).map(|m| m.len()).unwrap_or(0);
+        if !dry_run {
+            if let Some(ref hash) = anchor {
+                fs::write(chain_anchor_path(workgraph_dir), hash)
+                    .context("Failed to write chain anchor")?;
             }
-            let entry: OperationEntry = serde_json::from_slice(line).with_context(|| {
-                format!("Failed to parse operation entry from {}", rpath.display())
-            })?;
-            entries.push(entry);
+            fs::remove_file(&rpath)
+                .with_context(|| format!("Failed to remove {}", rpath.display()))?;
         }
+        purged.push((rpath, size));
+    }
+    Ok(purged)
+}
+
+/// Read all operations across rotated (compressed) and current files,
+/// returned in chronological order (oldest first).
+pub fn read_all_operations(workgraph_dir: &Path) -> Result<Vec<OperationEntry>> {
+    let dir = log_dir(workgraph_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for rpath in rotated_files(workgraph_dir)? {
+        entries.extend(read_rotated(&rpath)?);
     }
 
     // Read current (uncompressed) file.
@@ -179,6 +347,138 @@ pub fn read_all_operations(workgraph_dir: &Path) -> Result<Vec<OperationEntry>>
     Ok(entries)
 }
 
+/// What is wrong at a point in the provenance chain.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChainProblem {
+    /// The entry's content no longer matches its hash (edited)
+    HashMismatch,
+    /// The entry doesn't link to the one before it (entries deleted,
+    /// inserted, or reordered)
+    BrokenLink {
+        expected: Option<String>,
+        found: Option<String>,
+    },
+    /// An unhashed entry appears after the chain started (inserted by hand)
+    Unhashed,
+    /// The first chained entry links to a predecessor that is missing
+    /// (older entries or rotated files deleted)
+    MissingPrefix { prev_hash: String },
+    /// The log ends before the recorded chain head (newest entries removed)
+    Truncated { head: String },
+}
+
+/// A problem found at a given position in the log.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainIssue {
+    /// 0-based position in the combined log (`None` for end-of-log issues)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub op: Option<String>,
+    #[serde(flatten)]
+    pub problem: ChainProblem,
+}
+
+/// Result of verifying the provenance chain.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainReport {
+    pub total_entries: usize,
+    /// Entries from before hash chaining was introduced
+    pub legacy_entries: usize,
+    pub chained_entries: usize,
+    pub issues: Vec<ChainIssue>,
+}
+
+impl ChainReport {
+    pub fn is_intact(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Walk the full operations log and check the hash chain.
+pub fn verify_chain(workgraph_dir: &Path) -> Result<ChainReport> {
+    let entries = read_all_operations(workgraph_dir)?;
+    verify_entries(
+        &entries,
+        fs::read_to_string(chain_head_path(workgraph_dir)).ok(),
+        fs::read_to_string(chain_anchor_path(workgraph_dir))
+            .ok()
+            .map(|a| a.trim().to_string()),
+    )
+}
+
+fn verify_entries(
+    entries: &[OperationEntry],
+    head: Option<String>,
+    anchor: Option<String>,
+) -> Result<ChainReport> {
+    let mut issues = Vec::new();
+    let mut legacy = 0;
+    let mut chained = 0;
+    let mut prev: Option<&str> = None;
+    let mut started = false;
+
+    for (i, entry) in entries.iter().enumerate() {
+        let issue = |problem| ChainIssue {
+            index: Some(i),
+            timestamp: Some(entry.timestamp.clone()),
+            op: Some(entry.op.clone()),
+            problem,
+        };
+        let Some(ref hash) = entry.hash else {
+            if started {
+                issues.push(issue(ChainProblem::Unhashed));
+            } else {
+                legacy += 1;
+            }
+            continue;
+        };
+        chained += 1;
+
+        if &entry.compute_hash() != hash {
+            issues.push(issue(ChainProblem::HashMismatch));
+        }
+        if !started {
+            if let Some(ref p) = entry.prev_hash
+                && anchor.as_ref() != Some(p)
+            {
+                issues.push(issue(ChainProblem::MissingPrefix {
+                    prev_hash: p.clone(),
+                }));
+            }
+        } else if entry.prev_hash.as_deref() != prev {
+            issues.push(issue(ChainProblem::BrokenLink {
+                expected: prev.map(String::from),
+                found: entry.prev_hash.clone(),
+            }));
+        }
+        started = true;
+        prev = Some(hash);
+    }
+
+    if let Some(head) = head.map(|h| h.trim().to_string())
+        && !head.is_empty()
+        && prev != Some(head.as_str())
+    {
+        issues.push(ChainIssue {
+            index: None,
+            timestamp: None,
+            op: None,
+            problem: ChainProblem::Truncated { head },
+        });
+    }
+
+    Ok(ChainReport {
+        total_entries: entries.len(),
+        legacy_entries: legacy,
+        chained_entries: chained,
+        issues,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,6 +492,8 @@ mod tests {
             actor: None,
             user: None,
             detail: serde_json::Value::Null,
+            prev_hash: None,
+            hash: None,
         }
     }
 
@@ -367,4 +669,111 @@ mod tests {
         let entry: OperationEntry = serde_json::from_str(json).unwrap();
         assert!(entry.user.is_none());
     }
+
+    #[test]
+    fn test_entries_are_hash_chained() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        for i in 0..3 {
+            let entry = make_entry("chain", Some(&format!("t{}", i)));
+            append_operation(&dir, &entry, DEFAULT_ROTATION_THRESHOLD).unwrap();
+        }
+        let all = read_all_operations(&dir).unwrap();
+        assert!(all[0].prev_hash.is_none());
+        assert_eq!(all[1].prev_hash, all[0].hash);
+        assert_eq!(all[2].prev_hash, all[1].hash);
+        assert!(verify_chain(&dir).unwrap().is_intact());
+    }
+
+    #[test]
+    fn test_chain_survives_rotation() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        for i in 0..20 {
+            let entry = make_entry("rotating", Some(&format!("t{}", i)));
+            append_operation(&dir, &entry, 200).unwrap();
+        }
+        let report = verify_chain(&dir).unwrap();
+        assert_eq!(report.chained_entries, 20);
+        assert!(report.is_intact(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_purged_prefix_still_verifies() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        for i in 0..20 {
+            let entry = make_entry("rotating", Some(&format!("t{}", i)));
+            append_operation(&dir, &entry, 200).unwrap();
+        }
+        let rotated = rotated_files(&dir).unwrap().len();
+        assert!(rotated > 1);
+
+        let cutoff = Utc::now() + chrono::Duration::days(1);
+        assert_eq!(purge_rotated(&dir, cutoff, true).unwrap().len(), rotated);
+        assert_eq!(rotated_files(&dir).unwrap().len(), rotated);
+
+        let purged = purge_rotated(&dir, cutoff, false).unwrap();
+        assert_eq!(purged.len(), rotated);
+        assert!(rotated_files(&dir).unwrap().is_empty());
+        let report = verify_chain(&dir).unwrap();
+        assert!(report.chained_entries < 20);
+        assert!(report.is_intact(), "{:?}", report.issues);
+
+        // Without the anchor the missing prefix is reported.
+        fs::remove_file(chain_anchor_path(&dir)).unwrap();
+        assert!(!verify_chain(&dir).unwrap().is_intact());
+    }
+
+    #[test]
+    fn test_verify_detects_tampering_and_truncation() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        for i in 0..4 {
+            let entry = make_entry("audit", Some(&format!("t{}", i)));
+            append_operation(&dir, &entry, DEFAULT_ROTATION_THRESHOLD).unwrap();
+        }
+        let path = operations_path(&dir);
+        let original = fs::read_to_string(&path).unwrap();
+
+        // Edit an entry in place
+        fs::write(&path, original.replacen("\"t1\"", "\"t9\"", 1)).unwrap();
+        let report = verify_chain(&dir).unwrap();
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].index, Some(1));
+        assert_eq!(report.issues[0].problem, ChainProblem::HashMismatch);
+
+        // Delete an entry from the middle
+        let lines: Vec<&str> = original.lines().collect();
+        let without_second = format!("{}\n{}\n{}\n", lines[0], lines[2], lines[3]);
+        fs::write(&path, without_second).unwrap();
+        let report = verify_chain(&dir).unwrap();
+        assert!(matches!(
+            report.issues[0].problem,
+            ChainProblem::BrokenLink { .. }
+        ));
+
+        // Drop the newest entry
+        fs::write(&path, format!("{}\n{}\n{}\n", lines[0], lines[1], lines[2])).unwrap();
+        let report = verify_chain(&dir).unwrap();
+        assert!(matches!(
+            report.issues[0].problem,
+            ChainProblem::Truncated { .. }
+        ));
+    }
+
+    #[test]
+    fn test_legacy_entries_before_chain_are_accepted() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join(".wg");
+        fs::create_dir_all(log_dir(&dir)).unwrap();
+        let legacy = serde_json::to_string(&make_entry("old", None)).unwrap();
+        fs::write(operations_path(&dir), format!("{}\n", legacy)).unwrap();
+
+        append_operation(&dir, &make_entry("new", None), DEFAULT_ROTATION_THRESHOLD).unwrap();
+        let report = verify_chain(&dir).unwrap();
+        assert_eq!(report.legacy_entries, 1);
+        assert_eq!(report.chained_entries, 1);
+        assert!(report.is_intact());
+    }
 }
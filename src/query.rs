@@ -35,6 +35,26 @@ pub fn is_time_ready(task: &Task) -> bool {
     true
 }
 
+/// Check if the lag of every `after` edge with one (see
+/// [`crate::graph::DependencyEdge`]) has passed since its upstream task
+/// completed. Upstream tasks not completed yet are left to the blocker checks.
+pub fn lags_elapsed(task: &Task, graph: &WorkGraph) -> bool {
+    if task.edges.is_empty() {
+        return true;
+    }
+    let now = Utc::now();
+    task.edges.iter().all(|(upstream, edge)| {
+        let Some(lag) = edge.lag_duration() else {
+            return true;
+        };
+        graph
+            .get_task(upstream)
+            .and_then(|t| t.completed_at.as_deref())
+            .and_then(|ts| ts.parse::<DateTime<Utc>>().ok())
+            .is_none_or(|completed| now >= completed + lag)
+    })
+}
+
 /// Summary of project status
 #[derive(Debug, Clone, Serialize)]
 pub struct ProjectSummary {
@@ -300,8 +320,16 @@ pub fn ready_tasks(graph: &WorkGraph) -> Vec<&Task> {
             if task.paused {
                 return false;
             }
-            // Must be past not_before timestamp
-            if !is_time_ready(task) {
+            // Must not be waiting on an external event (`waiting_on`)
+            if !task.waiting_on.is_empty() {
+                return false;
+            }
+            // Must not be held up outside the project (`external_blocker`)
+            if task.external_blocker.is_some() {
+                return false;
+            }
+            // Must be past not_before timestamp and any edge lag
+            if !is_time_ready(task) || !lags_elapsed(task, graph) {
                 return false;
             }
             // All blockers must be terminal (done, failed, or abandoned).
@@ -444,7 +472,10 @@ pub fn ready_tasks_with_peers<'a>(graph: &'a WorkGraph, workgraph_dir: &Path) ->
             if task.paused {
                 return false;
             }
-            if !is_time_ready(task) {
+            if !task.waiting_on.is_empty() || task.external_blocker.is_some() {
+                return false;
+            }
+            if !is_time_ready(task) || !lags_elapsed(task, graph) {
                 return false;
             }
             let dependent_is_system = task.id.starts_with('.');
@@ -479,7 +510,10 @@ pub fn ready_tasks_cycle_aware<'a>(
             if task.paused {
                 return false;
             }
-            if !is_time_ready(task) {
+            if !task.waiting_on.is_empty() || task.external_blocker.is_some() {
+                return false;
+            }
+            if !is_time_ready(task) || !lags_elapsed(task, graph) {
                 return false;
             }
             let dependent_is_system = task.id.starts_with('.');
@@ -555,7 +589,10 @@ pub fn ready_tasks_cycle_aware<'a>(
             .filter(|task| {
                 matches!(task.status, Status::Open | Status::Incomplete)
                     && !task.paused
+                    && task.waiting_on.is_empty()
+                    && task.external_blocker.is_none()
                     && is_time_ready(task)
+                    && lags_elapsed(task, graph)
             })
             .collect();
 
@@ -597,7 +634,10 @@ pub fn ready_tasks_with_peers_cycle_aware<'a>(
             if task.paused {
                 return false;
             }
-            if !is_time_ready(task) {
+            if !task.waiting_on.is_empty() || task.external_blocker.is_some() {
+                return false;
+            }
+            if !is_time_ready(task) || !lags_elapsed(task, graph) {
                 return false;
             }
             let dependent_is_system = task.id.starts_with('.');
@@ -698,6 +738,703 @@ fn cost_of_recursive(
     self_cost + deps_cost
 }
 
+// ── Filter expressions ──────────────────────────────────────────────────
+//
+// A small language for selecting tasks, used by `wg query` and
+// `wg list --where`:
+//
+//   status=in-progress AND tag:backend AND estimate>2d
+//   (assignee=alice OR assignee=none) AND NOT skill:docs
+//   completed>=2026-01-01 AND cost<50
+//
+// Terms are `field OP value`, where OP is one of `= != > >= < <= :`. `:` is
+// "contains": membership for tags and skills, a case-insensitive substring
+// for text fields. Terms combine with AND, OR, NOT (any case) and
+// parentheses; AND binds tighter than OR, and adjacent terms with no
+// operator between them are ANDed. Values containing spaces or operator
+// characters are double-quoted.
+
+/// Error from parsing a filter expression.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{message} (at column {column})")]
+pub struct ExprError {
+    pub message: String,
+    /// 1-based column in the expression where the problem was found.
+    pub column: usize,
+}
+
+/// Comparison operator of a filter term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    /// `:` — membership or substring
+    Contains,
+}
+
+/// Task attribute a filter term tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExprField {
+    Id,
+    Title,
+    Description,
+    Status,
+    Tag,
+    Skill,
+    /// `assigned`, falling back to the agent hash
+    Assignee,
+    Model,
+    /// Estimated hours
+    Estimate,
+    /// Estimated cost
+    Cost,
+    Priority,
+    Created,
+    Started,
+    Completed,
+}
+
+/// Field names accepted in expressions (aliases included).
+pub const EXPR_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "status",
+    "tag",
+    "skill",
+    "assignee",
+    "model",
+    "estimate",
+    "cost",
+    "priority",
+    "created",
+    "started",
+    "completed",
+];
+
+impl ExprField {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "id" => Self::Id,
+            "title" => Self::Title,
+            "description" | "desc" => Self::Description,
+            "status" => Self::Status,
+            "tag" | "tags" => Self::Tag,
+            "skill" | "skills" => Self::Skill,
+            "assignee" | "assigned" | "agent" => Self::Assignee,
+            "model" => Self::Model,
+            "estimate" | "hours" => Self::Estimate,
+            "cost" => Self::Cost,
+            "priority" => Self::Priority,
+            "created" | "created_at" => Self::Created,
+            "started" | "started_at" => Self::Started,
+            "completed" | "completed_at" => Self::Completed,
+            _ => return None,
+        })
+    }
+}
+
+/// A typed value on the right-hand side of a term.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprValue {
+    Text(String),
+    Number(f64),
+    Status(Status),
+    /// An instant; `date_only` values compare by calendar day for `=`/`!=`.
+    Time {
+        at: DateTime<Utc>,
+        date_only: bool,
+    },
+}
+
+/// A parsed filter expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Cmp {
+        field: ExprField,
+        op: CmpOp,
+        value: ExprValue,
+    },
+}
+
+/// Parse a duration for `estimate` into hours. Bare numbers are hours;
+/// `m`, `h`, `d` (8h working day) and `w` (5 working days) suffixes are
+/// accepted.
+fn parse_estimate_hours(s: &str) -> Option<f64> {
+    let (num, mult) = match s.char_indices().last()? {
+        (i, 'm') => (&s[..i], 1.0 / 60.0),
+        (i, 'h') => (&s[..i], 1.0),
+        (i, 'd') => (&s[..i], 8.0),
+        (i, 'w') => (&s[..i], 40.0),
+        _ => (s, 1.0),
+    };
+    num.parse::<f64>().ok().map(|n| n * mult)
+}
+
+/// Parse a date value: `YYYY-MM-DD`, RFC 3339, `today`, `now`, or
+/// `now-<N><m|h|d|w>` for a moment in the past.
+fn parse_expr_time(s: &str) -> Option<ExprValue> {
+    let now = Utc::now();
+    let lower = s.to_ascii_lowercase();
+    if lower == "now" {
+        return Some(ExprValue::Time {
+            at: now,
+            date_only: false,
+        });
+    }
+    if lower == "today" {
+        let at = now.date_naive().and_hms_opt(0, 0, 0)?.and_utc();
+        return Some(ExprValue::Time {
+            at,
+            date_only: true,
+        });
+    }
+    if let Some(ago) = lower.strip_prefix("now-") {
+        let (split, _) = ago.char_indices().last()?;
+        let (num, unit) = ago.split_at(split);
+        let n: i64 = num.parse().ok()?;
+        let delta = match unit {
+            "m" => chrono::TimeDelta::try_minutes(n),
+            "h" => chrono::TimeDelta::try_hours(n),
+            "d" => chrono::TimeDelta::try_days(n),
+            "w" => chrono::TimeDelta::try_weeks(n),
+            _ => return None,
+        }?;
+        return Some(ExprValue::Time {
+            at: now.checked_sub_signed(delta)?,
+            date_only: false,
+        });
+    }
+    if let Ok(at) = DateTime::parse_from_rfc3339(s) {
+        return Some(ExprValue::Time {
+            at: at.with_timezone(&Utc),
+            date_only: false,
+        });
+    }
+    let date = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?;
+    Some(ExprValue::Time {
+        at: date.and_hms_opt(0, 0, 0)?.and_utc(),
+        date_only: true,
+    })
+}
+
+fn parse_expr_status(s: &str) -> Option<Status> {
+    let normalized = s.to_ascii_lowercase().replace('_', "-");
+    serde_json::from_value(serde_json::Value::String(normalized)).ok()
+}
+
+struct ExprParser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> ExprParser<'a> {
+    fn err(&self, message: impl Into<String>) -> ExprError {
+        ExprError {
+            message: message.into(),
+            column: self.src[..self.pos].chars().count() + 1,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_ws(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.src.len() - trimmed.len();
+    }
+
+    /// Whether `keyword` (case-insensitive) comes next as a whole word.
+    fn at_keyword(&mut self, keyword: &str) -> bool {
+        self.skip_ws();
+        let rest = self.rest();
+        rest.get(..keyword.len())
+            .is_some_and(|word| word.eq_ignore_ascii_case(keyword))
+            && rest[keyword.len()..]
+                .chars()
+                .next()
+                .is_none_or(|c| c.is_whitespace() || c == '(')
+    }
+
+    /// Consume `keyword` (case-insensitive) if it comes next as a whole word.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let matches = self.at_keyword(keyword);
+        if matches {
+            self.pos += keyword.len();
+        }
+        matches
+    }
+
+    /// Whether another operand follows with no operator in between, which
+    /// reads as an implicit AND.
+    fn at_implicit_and(&mut self) -> bool {
+        self.skip_ws();
+        !self.rest().is_empty() && !self.rest().starts_with(')') && !self.at_keyword("or")
+    }
+
+    fn eat_char(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.rest().starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ExprError> {
+        let mut left = self.parse_and()?;
+        while self.eat_keyword("or") {
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ExprError> {
+        let mut left = self.parse_unary()?;
+        while self.eat_keyword("and") || self.at_implicit_and() {
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ExprError> {
+        if self.eat_keyword("not") {
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat_char('(') {
+            let inner = self.parse_or()?;
+            if !self.eat_char(')') {
+                return Err(self.err("expected ')'"));
+            }
+            return Ok(inner);
+        }
+        self.parse_term()
+    }
+
+    fn parse_term(&mut self) -> Result<Expr, ExprError> {
+        self.skip_ws();
+        let name_len = self
+            .rest()
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(self.rest().len());
+        if name_len == 0 {
+            return Err(self.err("expected a field name"));
+        }
+        let name = &self.rest()[..name_len];
+        let field = ExprField::from_name(name).ok_or_else(|| {
+            self.err(format!(
+                "unknown field '{}' (fields: {})",
+                name,
+                EXPR_FIELDS.join(", ")
+            ))
+        })?;
+        self.pos += name_len;
+
+        self.skip_ws();
+        let op = [
+            (">=", CmpOp::Ge),
+            ("<=", CmpOp::Le),
+            ("!=", CmpOp::Ne),
+            ("=", CmpOp::Eq),
+            (">", CmpOp::Gt),
+            ("<", CmpOp::Lt),
+            (":", CmpOp::Contains),
+        ]
+        .into_iter()
+        .find(|(sym, _)| self.rest().starts_with(sym))
+        .map(|(sym, op)| {
+            self.pos += sym.len();
+            op
+        })
+        .ok_or_else(|| self.err("expected one of = != > >= < <= :"))?;
+
+        self.skip_ws();
+        let value_start = self.pos;
+        let raw = if let Some(quoted) = self.rest().strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or_else(|| self.err("unterminated string"))?;
+            self.pos += end + 2;
+            quoted[..end].to_string()
+        } else {
+            let len = self
+                .rest()
+                .find(|c: char| c.is_whitespace() || c == '(' || c == ')')
+                .unwrap_or(self.rest().len());
+            if len == 0 {
+                return Err(self.err("expected a value"));
+            }
+            self.pos += len;
+            self.src[value_start..self.pos].to_string()
+        };
+
+        let bad_value = |what: &str| ExprError {
+            message: format!("'{}' is not {} for '{}'", raw, what, name),
+            column: self.src[..value_start].chars().count() + 1,
+        };
+        let ordered = matches!(op, CmpOp::Gt | CmpOp::Ge | CmpOp::Lt | CmpOp::Le);
+        let value = match field {
+            ExprField::Estimate => ExprValue::Number(
+                parse_estimate_hours(&raw).ok_or_else(|| bad_value("a duration"))?,
+            ),
+            ExprField::Cost | ExprField::Priority => {
+                ExprValue::Number(raw.parse().map_err(|_| bad_value("a number"))?)
+            }
+            ExprField::Created | ExprField::Started | ExprField::Completed => {
+                parse_expr_time(&raw).ok_or_else(|| bad_value("a date"))?
+            }
+            ExprField::Status => {
+                ExprValue::Status(parse_expr_status(&raw).ok_or_else(|| bad_value("a status"))?)
+            }
+            _ if ordered => {
+                return Err(ExprError {
+                    message: format!("'{}' only supports = != and :", name),
+                    column: self.src[..value_start].chars().count() + 1,
+                });
+            }
+            _ => ExprValue::Text(raw),
+        };
+        Ok(Expr::Cmp { field, op, value })
+    }
+}
+
+fn cmp_ordering(op: CmpOp, ord: std::cmp::Ordering) -> bool {
+    match op {
+        CmpOp::Eq | CmpOp::Contains => ord.is_eq(),
+        CmpOp::Ne => ord.is_ne(),
+        CmpOp::Gt => ord.is_gt(),
+        CmpOp::Ge => ord.is_ge(),
+        CmpOp::Lt => ord.is_lt(),
+        CmpOp::Le => ord.is_le(),
+    }
+}
+
+fn text_matches(op: CmpOp, actual: Option<&str>, want: &str) -> bool {
+    let actual = actual.unwrap_or("");
+    match op {
+        CmpOp::Contains => actual.to_lowercase().contains(&want.to_lowercase()),
+        CmpOp::Ne => actual != want,
+        _ => actual == want,
+    }
+}
+
+impl Expr {
+    /// Parse a filter expression.
+    pub fn parse(input: &str) -> Result<Expr, ExprError> {
+        let mut parser = ExprParser { src: input, pos: 0 };
+        let expr = parser.parse_or()?;
+        parser.skip_ws();
+        if !parser.rest().is_empty() {
+            return Err(parser.err("unexpected ')'"));
+        }
+        Ok(expr)
+    }
+
+    /// Whether `task` satisfies the expression. A term on a field the task
+    /// doesn't have (no estimate, never started, ...) is false, except `!=`.
+    pub fn matches(&self, task: &Task) -> bool {
+        match self {
+            Expr::And(a, b) => a.matches(task) && b.matches(task),
+            Expr::Or(a, b) => a.matches(task) || b.matches(task),
+            Expr::Not(inner) => !inner.matches(task),
+            Expr::Cmp { field, op, value } => cmp_matches(task, *field, *op, value),
+        }
+    }
+}
+
+fn cmp_matches(task: &Task, field: ExprField, op: CmpOp, value: &ExprValue) -> bool {
+    match (field, value) {
+        (ExprField::Tag | ExprField::Skill, ExprValue::Text(want)) => {
+            let list = if field == ExprField::Tag {
+                &task.tags
+            } else {
+                &task.skills
+            };
+            let has = list.iter().any(|v| v.eq_ignore_ascii_case(want));
+            if op == CmpOp::Ne { !has } else { has }
+        }
+        (ExprField::Assignee, ExprValue::Text(want)) => {
+            if want.eq_ignore_ascii_case("none") {
+                let unassigned = task.assigned.is_none() && task.agent.is_none();
+                return if op == CmpOp::Ne {
+                    !unassigned
+                } else {
+                    unassigned
+                };
+            }
+            // Agent hashes are usually given as a prefix.
+            let is = task.assigned.as_deref() == Some(want.as_str())
+                || task
+                    .agent
+                    .as_deref()
+                    .is_some_and(|a| a.starts_with(want.as_str()));
+            match op {
+                CmpOp::Ne => !is,
+                CmpOp::Contains => is || text_matches(op, task.assigned.as_deref(), want),
+                _ => is,
+            }
+        }
+        (ExprField::Id, ExprValue::Text(want)) => text_matches(op, Some(&task.id), want),
+        (ExprField::Title, ExprValue::Text(want)) => text_matches(op, Some(&task.title), want),
+        (ExprField::Description, ExprValue::Text(want)) => {
+            text_matches(op, task.description.as_deref(), want)
+        }
+        (ExprField::Model, ExprValue::Text(want)) => text_matches(op, task.model.as_deref(), want),
+        (ExprField::Status, ExprValue::Status(want)) => match op {
+            CmpOp::Ne => task.status != *want,
+            CmpOp::Eq | CmpOp::Contains => task.status == *want,
+            _ => false,
+        },
+        (_, ExprValue::Number(want)) => {
+            let actual = match field {
+                ExprField::Estimate => task.estimate.as_ref().and_then(|e| e.hours),
+                ExprField::Cost => task.estimate.as_ref().and_then(|e| e.cost),
+                ExprField::Priority => Some(task.priority as f64),
+                _ => None,
+            };
+            match actual {
+                Some(a) => cmp_ordering(op, a.total_cmp(want)),
+                None => op == CmpOp::Ne,
+            }
+        }
+        (_, ExprValue::Time { at, date_only }) => {
+            let actual = match field {
+                ExprField::Created => task.created_at.as_deref(),
+                ExprField::Started => task.started_at.as_deref(),
+                ExprField::Completed => task.completed_at.as_deref(),
+                _ => None,
+            }
+            .and_then(|s| s.parse::<DateTime<Utc>>().ok());
+            match actual {
+                // A bare date is a whole day: `=2026-01-05` is any time that
+                // day and `>2026-01-05` starts the day after.
+                Some(a) if *date_only && !matches!(op, CmpOp::Ge | CmpOp::Lt) => {
+                    cmp_ordering(op, a.date_naive().cmp(&at.date_naive()))
+                }
+                Some(a) => cmp_ordering(op, a.cmp(at)),
+                None => op == CmpOp::Ne,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Tasks in `graph` matching `expr`.
+pub fn filter_tasks<'a>(graph: &'a WorkGraph, expr: &Expr) -> Vec<&'a Task> {
+    graph.tasks().filter(|t| expr.matches(t)).collect()
+}
+
+// ── Task queries ────────────────────────────────────────────────────────
+//
+// A filter expression plus ordering directives — the form saved views take
+// in config and `wg list --where` accepts:
+//
+//   status=open tag:backend sort:priority
+//   status=done sort:-completed limit:20
+//
+// `sort:FIELD` orders by a field in its natural direction (priority highest
+// first, everything else ascending); `sort:-FIELD` reverses it. Several sort
+// directives break ties left to right. Tasks missing the field sort last.
+// `limit:N` keeps the first N tasks after sorting.
+
+/// One `sort:` directive of a task query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: ExprField,
+    pub descending: bool,
+}
+
+/// A filter expression with optional sort order and limit.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskQuery {
+    pub filter: Option<Expr>,
+    pub sort: Vec<SortKey>,
+    pub limit: Option<usize>,
+}
+
+enum SortValue {
+    Text(String),
+    Number(f64),
+    Time(DateTime<Utc>),
+}
+
+fn sort_value(task: &Task, field: ExprField) -> Option<SortValue> {
+    let time = |s: Option<&String>| {
+        s.and_then(|s| s.parse::<DateTime<Utc>>().ok())
+            .map(SortValue::Time)
+    };
+    match field {
+        ExprField::Id => Some(SortValue::Text(task.id.clone())),
+        ExprField::Title => Some(SortValue::Text(task.title.to_lowercase())),
+        ExprField::Description => task
+            .description
+            .as_ref()
+            .map(|d| SortValue::Text(d.to_lowercase())),
+        ExprField::Status => serde_json::to_value(task.status)
+            .ok()
+            .and_then(|v| v.as_str().map(|s| SortValue::Text(s.to_string()))),
+        ExprField::Assignee => task
+            .assigned
+            .as_ref()
+            .or(task.agent.as_ref())
+            .map(|a| SortValue::Text(a.clone())),
+        ExprField::Model => task.model.as_ref().map(|m| SortValue::Text(m.clone())),
+        ExprField::Estimate => task
+            .estimate
+            .as_ref()
+            .and_then(|e| e.hours)
+            .map(SortValue::Number),
+        ExprField::Cost => task
+            .estimate
+            .as_ref()
+            .and_then(|e| e.cost)
+            .map(SortValue::Number),
+        ExprField::Priority => Some(SortValue::Number(task.priority as f64)),
+        ExprField::Created => time(task.created_at.as_ref()),
+        ExprField::Started => time(task.started_at.as_ref()),
+        ExprField::Completed => time(task.completed_at.as_ref()),
+        ExprField::Tag | ExprField::Skill => None,
+    }
+}
+
+fn compare_sort_values(a: &SortValue, b: &SortValue) -> std::cmp::Ordering {
+    match (a, b) {
+        (SortValue::Text(a), SortValue::Text(b)) => a.cmp(b),
+        (SortValue::Number(a), SortValue::Number(b)) => a.total_cmp(b),
+        (SortValue::Time(a), SortValue::Time(b)) => a.cmp(b),
+        _ => std::cmp::Ordering::Equal,
+    }
+}
+
+impl TaskQuery {
+    /// Parse a query: a filter expression with `sort:` and `limit:`
+    /// directives anywhere among its terms. Either part may be empty.
+    pub fn parse(input: &str) -> Result<TaskQuery, ExprError> {
+        let mut query = TaskQuery::default();
+        // Directives are blanked out in place so filter errors keep their
+        // columns.
+        let mut filter_src = input.to_string();
+        let mut in_quotes = false;
+        let mut word_start: Option<usize> = None;
+        let mut words = Vec::new();
+        for (i, c) in input.char_indices().chain([(input.len(), ' ')]) {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            }
+            let boundary = !in_quotes && (c.is_whitespace() || c == '(' || c == ')');
+            match (word_start, boundary) {
+                (None, false) => word_start = Some(i),
+                (Some(start), true) => {
+                    words.push((start, i));
+                    word_start = None;
+                }
+                _ => {}
+            }
+        }
+        // Walk backwards so blanking one directive doesn't shift the next.
+        for (start, end) in words.into_iter().rev() {
+            let word = &input[start..end];
+            let column = input[..start].chars().count() + 1;
+            let lower = word.to_ascii_lowercase();
+            if let Some(spec) = lower.strip_prefix("sort:") {
+                let (descending, name) = match spec.strip_prefix('-') {
+                    Some(name) => (true, name),
+                    None => (false, spec),
+                };
+                let field = ExprField::from_name(name)
+                    .filter(|f| !matches!(f, ExprField::Tag | ExprField::Skill))
+                    .ok_or_else(|| ExprError {
+                        message: format!("cannot sort by '{}'", name),
+                        column,
+                    })?;
+                // Priority's natural order is most important first.
+                let descending = descending != (field == ExprField::Priority);
+                query.sort.insert(0, SortKey { field, descending });
+            } else if let Some(n) = lower.strip_prefix("limit:") {
+                query.limit = Some(n.parse().map_err(|_| ExprError {
+                    message: format!("'{}' is not a valid limit", n),
+                    column,
+                })?);
+            } else {
+                continue;
+            }
+            filter_src.replace_range(start..end, &" ".repeat(word.chars().count()));
+        }
+        if !filter_src.trim().is_empty() {
+            query.filter = Some(Expr::parse(&filter_src)?);
+        }
+        Ok(query)
+    }
+
+    /// Combine two queries: filters are ANDed, `other`'s sort keys break
+    /// ties after ours, and the smaller limit wins.
+    pub fn and(self, other: TaskQuery) -> TaskQuery {
+        let filter = match (self.filter, other.filter) {
+            (Some(a), Some(b)) => Some(Expr::And(Box::new(a), Box::new(b))),
+            (a, b) => a.or(b),
+        };
+        let mut sort = self.sort;
+        sort.extend(other.sort);
+        let limit = match (self.limit, other.limit) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        TaskQuery {
+            filter,
+            sort,
+            limit,
+        }
+    }
+
+    /// Whether `task` passes the filter (always true without one).
+    pub fn matches(&self, task: &Task) -> bool {
+        self.filter.as_ref().is_none_or(|f| f.matches(task))
+    }
+
+    /// Sort and truncate already-filtered tasks. Ties keep input order.
+    pub fn order<'a>(&self, mut tasks: Vec<&'a Task>) -> Vec<&'a Task> {
+        if !self.sort.is_empty() {
+            tasks.sort_by(|a, b| {
+                for key in &self.sort {
+                    let ord = match (sort_value(a, key.field), sort_value(b, key.field)) {
+                        (Some(x), Some(y)) => {
+                            let ord = compare_sort_values(&x, &y);
+                            if key.descending { ord.reverse() } else { ord }
+                        }
+                        (Some(_), None) => std::cmp::Ordering::Less,
+                        (None, Some(_)) => std::cmp::Ordering::Greater,
+                        (None, None) => std::cmp::Ordering::Equal,
+                    };
+                    if ord.is_ne() {
+                        return ord;
+                    }
+                }
+                std::cmp::Ordering::Equal
+            });
+        }
+        if let Some(limit) = self.limit {
+            tasks.truncate(limit);
+        }
+        tasks
+    }
+
+    /// Tasks in `graph` matching the query, sorted and limited.
+    pub fn run<'a>(&self, graph: &'a WorkGraph) -> Vec<&'a Task> {
+        self.order(graph.tasks().filter(|t| self.matches(t)).collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -755,6 +1492,26 @@ mod tests {
         assert_eq!(ready[0].id, "blocker");
     }
 
+    #[test]
+    fn test_ready_tasks_excludes_external_blocker() {
+        let mut graph = WorkGraph::new();
+
+        let mut t1 = make_task("t1", "Task 1");
+        t1.external_blocker = Some(crate::graph::ExternalBlocker {
+            reason: "Vendor contract signature".to_string(),
+            contact: None,
+            expected: None,
+            since: "2026-01-01T00:00:00Z".to_string(),
+            last_reminded_at: None,
+        });
+        graph.add_node(Node::Task(t1));
+        graph.add_node(Node::Task(make_task("t2", "Task 2")));
+
+        let ready = ready_tasks(&graph);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id, "t2");
+    }
+
     #[test]
     fn test_ready_tasks_unblocked_when_blocker_done() {
         let mut graph = WorkGraph::new();
@@ -1167,6 +1924,38 @@ mod tests {
         assert_eq!(ready[0].id, "t1");
     }
 
+    #[test]
+    fn test_ready_tasks_waits_for_edge_lag() {
+        let mut graph = WorkGraph::new();
+        let mut deploy = make_task("deploy", "Deploy");
+        deploy.status = Status::Done;
+        deploy.completed_at = Some((Utc::now() - chrono::Duration::hours(1)).to_rfc3339());
+        graph.add_node(Node::Task(deploy));
+
+        let mut rollout = make_task("rollout", "Rollout");
+        rollout.after = vec!["deploy".to_string()];
+        rollout.edges.insert(
+            "deploy".to_string(),
+            crate::graph::DependencyEdge {
+                lag: Some("2d".to_string()),
+                weight: None,
+            },
+        );
+        graph.add_node(Node::Task(rollout));
+        assert!(ready_tasks(&graph).is_empty(), "soak period not over");
+
+        graph
+            .get_task_mut("rollout")
+            .unwrap()
+            .edges
+            .get_mut("deploy")
+            .unwrap()
+            .lag = Some("30m".to_string());
+        let ready = ready_tasks(&graph);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id, "rollout");
+    }
+
     // ========== Transitive blocker tests ==========
 
     #[test]
@@ -2562,4 +3351,173 @@ mod tests {
             "Task 'a' (with cycle_config) should be ready"
         );
     }
+
+    // ── Filter expressions ──
+
+    fn expr_task() -> Task {
+        let mut t = make_task("api-auth", "Add auth to the API");
+        t.status = Status::InProgress;
+        t.tags = vec!["backend".to_string()];
+        t.skills = vec!["rust".to_string()];
+        t.assigned = Some("alice".to_string());
+        t.estimate = Some(Estimate {
+            hours: Some(24.0),
+            cost: Some(40.0),
+        });
+        t.created_at = Some("2026-01-05T12:00:00Z".to_string());
+        t
+    }
+
+    fn check(expr: &str) -> bool {
+        Expr::parse(expr).unwrap().matches(&expr_task())
+    }
+
+    #[test]
+    fn test_expr_terms() {
+        assert!(check("status=in_progress AND tag:backend AND estimate>2d"));
+        assert!(!check("estimate>3d"));
+        assert!(check("estimate>=24 and cost<50"));
+        assert!(check("skill=Rust AND NOT skill:docs"));
+        assert!(check("tag!=frontend"));
+        assert!(check("assignee=alice"));
+        assert!(!check("assignee=none"));
+        assert!(check("title:\"auth to\""));
+        assert!(check("id=api-auth"));
+        assert!(check("priority=10 OR status!=done"));
+        assert!(!check("started>2020-01-01"));
+        assert!(check("started!=2020-01-01"));
+    }
+
+    #[test]
+    fn test_expr_dates() {
+        assert!(check("created=2026-01-05"));
+        assert!(check("created>=2026-01-05"));
+        assert!(!check("created>2026-01-05"));
+        assert!(check("created<=2026-01-05"));
+        assert!(check("created<2026-01-05T13:00:00Z"));
+        assert!(check("created>now-52000w"));
+        assert!(check("created<now"));
+    }
+
+    #[test]
+    fn test_expr_malformed_times_are_errors() {
+        assert!(Expr::parse("created>now-5é").is_err());
+        assert!(Expr::parse("created>now-é").is_err());
+        assert!(Expr::parse("created>now-").is_err());
+        assert!(Expr::parse("created>now-99999999999999w").is_err());
+        assert!(Expr::parse("created>now-9223372036854775807m").is_err());
+        assert!(Expr::parse("created>now-2000000000d").is_err());
+    }
+
+    #[test]
+    fn test_expr_precedence_and_grouping() {
+        // AND binds tighter than OR
+        assert!(check("tag:frontend AND tag:x OR tag:backend"));
+        assert!(!check("tag:frontend AND (tag:x OR tag:backend)"));
+        assert!(check("NOT (tag:frontend OR assignee=bob)"));
+    }
+
+    #[test]
+    fn test_expr_errors() {
+        let err = Expr::parse("colour=red").unwrap_err();
+        assert!(err.message.contains("unknown field"));
+        assert_eq!(err.column, 1);
+        assert_eq!(Expr::parse("estimate>lots").unwrap_err().column, 10);
+        assert!(Expr::parse("status=sleeping").is_err());
+        assert!(Expr::parse("title>b").is_err());
+        assert!(Expr::parse("(tag:a").is_err());
+        assert!(Expr::parse("tag:a)").is_err());
+        assert!(Expr::parse("title:\"open").is_err());
+    }
+
+    #[test]
+    fn test_expr_implicit_and() {
+        assert_eq!(
+            Expr::parse("tag:a tag:b OR tag:c").unwrap(),
+            Expr::parse("(tag:a AND tag:b) OR tag:c").unwrap()
+        );
+        assert!(check("status=in-progress tag:backend NOT tag:frontend"));
+        assert!(!check("status=in-progress tag:frontend"));
+    }
+
+    #[test]
+    fn test_task_query_directives() {
+        let q = TaskQuery::parse("status=open tag:backend sort:priority limit:5").unwrap();
+        assert_eq!(
+            q.filter,
+            Some(Expr::parse("status=open tag:backend").unwrap())
+        );
+        assert_eq!(
+            q.sort,
+            vec![SortKey {
+                field: ExprField::Priority,
+                descending: true
+            }]
+        );
+        assert_eq!(q.limit, Some(5));
+
+        let q = TaskQuery::parse("sort:-created").unwrap();
+        assert!(q.filter.is_none());
+        assert!(q.sort[0].descending);
+        // Quoted values are not directives
+        let q = TaskQuery::parse("title:\"sort:x\"").unwrap();
+        assert!(q.sort.is_empty());
+
+        assert!(TaskQuery::parse("status=open sort:tag").is_err());
+        assert!(TaskQuery::parse("limit:many").is_err());
+        // Filter errors keep their column with directives blanked out
+        assert_eq!(
+            TaskQuery::parse("sort:id colour=red").unwrap_err().column,
+            9
+        );
+    }
+
+    #[test]
+    fn test_task_query_order_and_combine() {
+        let mut graph = WorkGraph::new();
+        for (id, priority, hours) in [("a", 10, Some(3.0)), ("b", 50, None), ("c", 10, Some(1.0))] {
+            let mut t = make_task(id, id);
+            t.priority = priority;
+            t.estimate = hours.map(|h| Estimate {
+                hours: Some(h),
+                cost: None,
+            });
+            graph.add_node(Node::Task(t));
+        }
+        let ids =
+            |q: &TaskQuery| -> Vec<String> { q.run(&graph).iter().map(|t| t.id.clone()).collect() };
+
+        assert_eq!(
+            ids(&TaskQuery::parse("sort:priority sort:estimate").unwrap()),
+            ["b", "c", "a"]
+        );
+        // Missing estimates sort last either way
+        assert_eq!(
+            ids(&TaskQuery::parse("sort:-estimate").unwrap()),
+            ["a", "c", "b"]
+        );
+        assert_eq!(
+            ids(&TaskQuery::parse("sort:id limit:2").unwrap()),
+            ["a", "b"]
+        );
+
+        let combined = TaskQuery::parse("priority=10 sort:-id limit:5")
+            .unwrap()
+            .and(TaskQuery::parse("estimate>2 limit:3").unwrap());
+        assert_eq!(combined.limit, Some(3));
+        assert_eq!(ids(&combined), ["a"]);
+    }
+
+    #[test]
+    fn test_filter_tasks() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(expr_task()));
+        graph.add_node(Node::Task(make_task("other", "Other")));
+        let expr = Expr::parse("status=open").unwrap();
+        let ids: Vec<&str> = filter_tasks(&graph, &expr)
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["other"]);
+    }
 }
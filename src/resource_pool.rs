@@ -0,0 +1,125 @@
+//! Named resource pools with concurrency limits
+//!
+//! Pools are declared in config (`[pools] gpu = 2`) and claimed by tasks
+//! through their `requires` list. While a task is in progress it holds one
+//! slot in every pool it requires; the dispatcher will not start a task whose
+//! pools are full.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::graph::{Status, Task, WorkGraph};
+
+/// Slots in use per pool, for dispatch decisions.
+pub struct PoolUsage<'a> {
+    capacity: &'a BTreeMap<String, u32>,
+    in_use: HashMap<&'a str, u32>,
+}
+
+impl<'a> PoolUsage<'a> {
+    /// Count slots held by in-progress tasks.
+    pub fn from_graph(capacity: &'a BTreeMap<String, u32>, graph: &WorkGraph) -> Self {
+        let mut usage = PoolUsage {
+            capacity,
+            in_use: HashMap::new(),
+        };
+        for task in graph.tasks().filter(|t| t.status == Status::InProgress) {
+            usage.acquire(task);
+        }
+        usage
+    }
+
+    /// The first pool `task` requires that has no free slot.
+    pub fn full_pool(&self, task: &Task) -> Option<(&'a str, u32)> {
+        task.requires.iter().find_map(|name| {
+            let (name, &cap) = self.capacity.get_key_value(name)?;
+            let used = self.in_use.get(name.as_str()).copied().unwrap_or(0);
+            (used >= cap).then_some((name.as_str(), cap))
+        })
+    }
+
+    /// Take one slot in each pool `task` requires.
+    pub fn acquire(&mut self, task: &Task) {
+        for name in &task.requires {
+            if let Some((name, _)) = self.capacity.get_key_value(name) {
+                *self.in_use.entry(name.as_str()).or_default() += 1;
+            }
+        }
+    }
+}
+
+/// Utilization of one pool, for `wg resources`
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolUtilization {
+    pub name: String,
+    pub capacity: u32,
+    pub in_use: u32,
+    /// In-progress tasks holding a slot
+    pub holders: Vec<String>,
+    /// Open tasks that will need a slot
+    pub waiting: Vec<String>,
+}
+
+/// Utilization of every configured pool, sorted by name.
+pub fn utilization(capacity: &BTreeMap<String, u32>, graph: &WorkGraph) -> Vec<PoolUtilization> {
+    capacity
+        .iter()
+        .map(|(name, &cap)| {
+            let requiring = || graph.tasks().filter(|t| t.requires.contains(name));
+            let holders: Vec<String> = requiring()
+                .filter(|t| t.status == Status::InProgress)
+                .map(|t| t.id.clone())
+                .collect();
+            let waiting = requiring()
+                .filter(|t| t.status == Status::Open)
+                .map(|t| t.id.clone())
+                .collect();
+            PoolUtilization {
+                name: name.clone(),
+                capacity: cap,
+                in_use: holders.len() as u32,
+                holders,
+                waiting,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use crate::test_helpers::make_task;
+
+    #[test]
+    fn test_pool_usage_blocks_when_full() {
+        let pools = BTreeMap::from([("gpu".to_string(), 1), ("staging-db".to_string(), 2)]);
+        let mut graph = WorkGraph::new();
+        let mut running = make_task("train", "Train");
+        running.status = Status::InProgress;
+        running.requires = vec!["gpu".to_string()];
+        graph.add_node(Node::Task(running));
+        let mut queued = make_task("eval", "Eval");
+        queued.requires = vec!["staging-db".to_string(), "gpu".to_string()];
+        graph.add_node(Node::Task(queued.clone()));
+
+        let mut usage = PoolUsage::from_graph(&pools, &graph);
+        assert_eq!(usage.full_pool(&queued), Some(("gpu", 1)));
+
+        let db_only = Task {
+            requires: vec!["staging-db".to_string(), "not-a-pool".to_string()],
+            ..make_task("migrate", "Migrate")
+        };
+        assert_eq!(usage.full_pool(&db_only), None);
+        usage.acquire(&db_only);
+        usage.acquire(&db_only);
+        assert_eq!(usage.full_pool(&db_only), Some(("staging-db", 2)));
+
+        let util = utilization(&pools, &graph);
+        assert_eq!(util[0].name, "gpu");
+        assert_eq!(util[0].in_use, 1);
+        assert_eq!(util[0].holders, vec!["train"]);
+        assert_eq!(util[0].waiting, vec!["eval"]);
+        assert_eq!(util[1].in_use, 0);
+    }
+}
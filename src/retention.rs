@@ -0,0 +1,415 @@
+//! Data retention: purge logs, runs, provenance, archives, and telemetry
+//! older than the ages configured under `[retention]`.
+//!
+//! The daemon runs a pass at most once every `retention.interval_hours`;
+//! `wg retention` runs one on demand. Each pass (except dry runs) writes a
+//! report of what was purged to `.wg/service/retention-report.json`.
+//!
+//! | category     | what is purged                                         |
+//! |--------------|--------------------------------------------------------|
+//! | `logs`       | archived agent logs, `log/agents/<task>/<timestamp>/`  |
+//! | `runs`       | replay run snapshots, `runs/<run-id>/`                 |
+//! | `provenance` | rotated operation logs, `log/*.jsonl.zst`              |
+//! | `archives`   | task archive partitions, `archive/YYYY-MM.jsonl`       |
+//! | `telemetry`  | command usage entries in `usage.log`                   |
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::RetentionConfig;
+
+/// What one category's policy removed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryReport {
+    pub category: String,
+    /// The configured maximum age, e.g. `90d`
+    pub max_age: String,
+    /// Everything older than this was purged
+    pub cutoff: String,
+    /// Purged items: paths relative to the workgraph directory, or a count
+    /// of purged entries for line-oriented files
+    pub purged: Vec<String>,
+    pub bytes_freed: u64,
+}
+
+/// Result of one retention pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub ran_at: String,
+    pub dry_run: bool,
+    pub categories: Vec<CategoryReport>,
+}
+
+impl RetentionReport {
+    pub fn total_purged(&self) -> usize {
+        self.categories.iter().map(|c| c.purged.len()).sum()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.categories.iter().map(|c| c.bytes_freed).sum()
+    }
+}
+
+/// Parse a maximum age like `90d`, `12w`, or `48h`. A bare number is days.
+pub fn parse_max_age(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let (num, unit) = match s.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&s[..i], c),
+        Some(_) => (s, 'd'),
+        None => anyhow::bail!("Empty retention age"),
+    };
+    let n: i64 = num
+        .parse()
+        .with_context(|| format!("Invalid retention age '{}'", s))?;
+    match unit {
+        'h' => Ok(Duration::hours(n)),
+        'd' => Ok(Duration::days(n)),
+        'w' => Ok(Duration::weeks(n)),
+        _ => anyhow::bail!(
+            "Unknown unit '{}' in retention age '{}' (use h, d, or w)",
+            unit,
+            s
+        ),
+    }
+}
+
+/// Path of the report written by the last retention pass.
+pub fn report_path(workgraph_dir: &Path) -> PathBuf {
+    workgraph_dir.join("service").join("retention-report.json")
+}
+
+/// Load the report of the last (non-dry-run) retention pass.
+pub fn load_last_report(workgraph_dir: &Path) -> Option<RetentionReport> {
+    let content = fs::read_to_string(report_path(workgraph_dir)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Whether the daemon should run a pass now.
+pub fn is_due(workgraph_dir: &Path, config: &RetentionConfig) -> bool {
+    if config.interval_hours == 0 || config.policies().is_empty() {
+        return false;
+    }
+    match load_last_report(workgraph_dir).and_then(|r| DateTime::parse_from_rfc3339(&r.ran_at).ok())
+    {
+        Some(last) => {
+            Utc::now().signed_duration_since(last) >= Duration::hours(config.interval_hours as i64)
+        }
+        None => true,
+    }
+}
+
+/// Apply every configured policy. Unless `dry_run`, data is deleted and the
+/// report is saved for `wg retention --last`.
+pub fn enforce(
+    workgraph_dir: &Path,
+    config: &RetentionConfig,
+    dry_run: bool,
+) -> Result<RetentionReport> {
+    let now = Utc::now();
+    let mut categories = Vec::new();
+    for (category, max_age) in config.policies() {
+        let cutoff = now - parse_max_age(max_age)?;
+        let (purged, bytes_freed) = match category {
+            "logs" => purge_agent_logs(workgraph_dir, cutoff, dry_run)?,
+            "runs" => purge_runs(workgraph_dir, cutoff, dry_run)?,
+            "provenance" => purge_provenance(workgraph_dir, cutoff, dry_run)?,
+            "archives" => purge_archives(workgraph_dir, cutoff, dry_run)?,
+            "telemetry" => purge_telemetry(workgraph_dir, cutoff, dry_run)?,
+            _ => continue,
+        };
+        categories.push(CategoryReport {
+            category: category.to_string(),
+            max_age: max_age.to_string(),
+            cutoff: cutoff.to_rfc3339(),
+            purged,
+            bytes_freed,
+        });
+    }
+
+    let report = RetentionReport {
+        ran_at: now.to_rfc3339(),
+        dry_run,
+        categories,
+    };
+    if !dry_run {
+        let path = report_path(workgraph_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&report)?)
+            .context("Failed to write retention report")?;
+    }
+    Ok(report)
+}
+
+type Purged = (Vec<String>, u64);
+
+fn relative(workgraph_dir: &Path, path: &Path) -> String {
+    path.strip_prefix(workgraph_dir)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn modified_at(path: &Path) -> Option<DateTime<Utc>> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(DateTime::<Utc>::from)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| {
+            let p = e.path();
+            if p.is_dir() {
+                dir_size(&p)
+            } else {
+                e.metadata().map(|m| m.len()).unwrap_or(0)
+            }
+        })
+        .sum()
+}
+
+fn subdirs(path: &Path) -> Vec<PathBuf> {
+    let mut out: Vec<PathBuf> = fs::read_dir(path)
+        .map(|rd| {
+            rd.filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    out.sort();
+    out
+}
+
+/// Agent log archives are named by their RFC 3339 archive time.
+fn purge_agent_logs(workgraph_dir: &Path, cutoff: DateTime<Utc>, dry_run: bool) -> Result<Purged> {
+    let base = crate::provenance::log_dir(workgraph_dir).join("agents");
+    let mut purged = Vec::new();
+    let mut bytes = 0;
+    for task_dir in subdirs(&base) {
+        for attempt in subdirs(&task_dir) {
+            let at = attempt
+                .file_name()
+                .and_then(|n| DateTime::parse_from_rfc3339(&n.to_string_lossy()).ok())
+                .map(|dt| dt.with_timezone(&Utc))
+                .or_else(|| modified_at(&attempt));
+            if at.is_none_or(|at| at >= cutoff) {
+                continue;
+            }
+            bytes += dir_size(&attempt);
+            if !dry_run {
+                fs::remove_dir_all(&attempt)
+                    .with_context(|| format!("Failed to remove {}", attempt.display()))?;
+            }
+            purged.push(relative(workgraph_dir, &attempt));
+        }
+        if !dry_run && subdirs(&task_dir).is_empty() {
+            let _ = fs::remove_dir(&task_dir);
+        }
+    }
+    Ok((purged, bytes))
+}
+
+fn purge_runs(workgraph_dir: &Path, cutoff: DateTime<Utc>, dry_run: bool) -> Result<Purged> {
+    let mut purged = Vec::new();
+    let mut bytes = 0;
+    for id in crate::runs::list_runs(workgraph_dir)? {
+        let dir = crate::runs::run_dir(workgraph_dir, &id);
+        let at = crate::runs::load_run_meta(workgraph_dir, &id)
+            .ok()
+            .and_then(|m| DateTime::parse_from_rfc3339(&m.timestamp).ok())
+            .map(|dt| dt.with_timezone(&Utc))
+            .or_else(|| modified_at(&dir));
+        if at.is_none_or(|at| at >= cutoff) {
+            continue;
+        }
+        bytes += dir_size(&dir);
+        if !dry_run {
+            fs::remove_dir_all(&dir)
+                .with_context(|| format!("Failed to remove {}", dir.display()))?;
+        }
+        purged.push(relative(workgraph_dir, &dir));
+    }
+    Ok((purged, bytes))
+}
+
+fn purge_provenance(workgraph_dir: &Path, cutoff: DateTime<Utc>, dry_run: bool) -> Result<Purged> {
+    let files = crate::provenance::purge_rotated(workgraph_dir, cutoff, dry_run)?;
+    let bytes = files.iter().map(|(_, size)| size).sum();
+    Ok((
+        files
+            .iter()
+            .map(|(p, _)| relative(workgraph_dir, p))
+            .collect(),
+        bytes,
+    ))
+}
+
+/// A partition is purged once its whole month is older than the cutoff.
+fn purge_archives(workgraph_dir: &Path, cutoff: DateTime<Utc>, dry_run: bool) -> Result<Purged> {
+    let legacy = crate::archive::legacy_path(workgraph_dir);
+    let mut purged = Vec::new();
+    let mut bytes = 0;
+    for path in crate::archive::files(workgraph_dir)? {
+        if path == legacy {
+            continue;
+        }
+        let month_end = path
+            .file_stem()
+            .and_then(|s| {
+                NaiveDate::parse_from_str(&format!("{}-01", s.to_string_lossy()), "%Y-%m-%d").ok()
+            })
+            .and_then(|first| {
+                let (y, m) = if first.month() == 12 {
+                    (first.year() + 1, 1)
+                } else {
+                    (first.year(), first.month() + 1)
+                };
+                NaiveDate::from_ymd_opt(y, m, 1)
+            })
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|dt| dt.and_utc());
+        if month_end.is_none_or(|end| end > cutoff) {
+            continue;
+        }
+        bytes += fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        if !dry_run {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        purged.push(relative(workgraph_dir, &path));
+    }
+    Ok((purged, bytes))
+}
+
+/// Usage log lines start with their UTC timestamp.
+fn purge_telemetry(workgraph_dir: &Path, cutoff: DateTime<Utc>, dry_run: bool) -> Result<Purged> {
+    let path = crate::usage::log_path(workgraph_dir);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok((Vec::new(), 0));
+    };
+    let (old, keep): (Vec<&str>, Vec<&str>) = content.lines().partition(|line| {
+        line.split_whitespace()
+            .next()
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .is_some_and(|ts| ts < cutoff)
+    });
+    if old.is_empty() {
+        return Ok((Vec::new(), 0));
+    }
+    let bytes = old.iter().map(|l| l.len() as u64 + 1).sum();
+    if !dry_run {
+        let mut rest = keep.join("\n");
+        if !rest.is_empty() {
+            rest.push('\n');
+        }
+        fs::write(&path, rest).context("Failed to rewrite usage.log")?;
+    }
+    Ok((
+        vec![format!(
+            "{} ({} entries)",
+            relative(workgraph_dir, &path),
+            old.len()
+        )],
+        bytes,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn policy(f: impl FnOnce(&mut RetentionConfig)) -> RetentionConfig {
+        let mut config = RetentionConfig::default();
+        f(&mut config);
+        config
+    }
+
+    #[test]
+    fn test_parse_max_age() {
+        assert_eq!(parse_max_age("90d").unwrap(), Duration::days(90));
+        assert_eq!(parse_max_age("2w").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_max_age("48h").unwrap(), Duration::hours(48));
+        assert_eq!(parse_max_age("7").unwrap(), Duration::days(7));
+        assert!(parse_max_age("3y").is_err());
+        assert!(parse_max_age("").is_err());
+    }
+
+    #[test]
+    fn test_purges_old_logs_archives_and_telemetry() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let agents = dir.join("log").join("agents").join("t1");
+        fs::create_dir_all(agents.join("2020-01-01T00:00:00Z")).unwrap();
+        fs::write(
+            agents.join("2020-01-01T00:00:00Z").join("output.txt"),
+            "old",
+        )
+        .unwrap();
+        let recent = Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        fs::create_dir_all(agents.join(&recent)).unwrap();
+
+        fs::create_dir_all(dir.join("archive")).unwrap();
+        fs::write(dir.join("archive").join("2020-01.jsonl"), "{}\n").unwrap();
+        let this_month = Utc::now().format("%Y-%m").to_string();
+        fs::write(
+            dir.join("archive").join(format!("{}.jsonl", this_month)),
+            "{}\n",
+        )
+        .unwrap();
+
+        fs::write(
+            dir.join("usage.log"),
+            format!("2020-01-01T00:00:00Z list\n{} show\n", recent),
+        )
+        .unwrap();
+
+        let config = policy(|c| {
+            c.logs = Some("30d".to_string());
+            c.archives = Some("365d".to_string());
+            c.telemetry = Some("90d".to_string());
+        });
+
+        let dry = enforce(dir, &config, true).unwrap();
+        assert_eq!(dry.total_purged(), 3);
+        assert!(agents.join("2020-01-01T00:00:00Z").exists());
+        assert!(!report_path(dir).exists());
+
+        let report = enforce(dir, &config, false).unwrap();
+        assert_eq!(report.total_purged(), 3);
+        assert!(!agents.join("2020-01-01T00:00:00Z").exists());
+        assert!(agents.join(&recent).exists());
+        assert!(!dir.join("archive").join("2020-01.jsonl").exists());
+        assert!(
+            dir.join("archive")
+                .join(format!("{}.jsonl", this_month))
+                .exists()
+        );
+        let usage = fs::read_to_string(dir.join("usage.log")).unwrap();
+        assert_eq!(usage.lines().count(), 1);
+        assert!(usage.contains("show"));
+
+        let last = load_last_report(dir).unwrap();
+        assert_eq!(last.categories.len(), 3);
+        assert!(!is_due(dir, &config));
+    }
+
+    #[test]
+    fn test_not_due_without_policies() {
+        let tmp = TempDir::new().unwrap();
+        assert!(!is_due(tmp.path(), &RetentionConfig::default()));
+        let config = policy(|c| c.runs = Some("90d".to_string()));
+        assert!(is_due(tmp.path(), &config));
+    }
+}
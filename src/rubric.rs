@@ -0,0 +1,413 @@
+//! Reusable evaluation rubrics.
+//!
+//! A rubric is a named set of weighted criteria with a pass threshold,
+//! stored as `.wg/rubrics/<id>.yaml`. Rubrics attach to tasks by tag, by
+//! function template (the `template:<function>/<template>` tag stamped on
+//! applied tasks), or explicitly through a `rubric:<id>` task tag. The
+//! evaluator scores each applicable criterion, the LLM verification gate
+//! fails tasks whose rubric does not pass, and `wg show` lists them as a
+//! review checklist — so the same definition of "done well" is used
+//! everywhere.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::graph::Task;
+use crate::learning::TEMPLATE_TAG_PREFIX;
+
+const RUBRICS_DIR: &str = "rubrics";
+
+/// Task tag prefix that attaches a rubric by id (`rubric:<id>`).
+pub const RUBRIC_TAG_PREFIX: &str = "rubric:";
+
+/// Prefix for rubric criterion keys in evaluation dimensions, keeping them
+/// apart from the built-in dimensions (`correctness`, `completeness`, ...).
+pub const DIMENSION_PREFIX: &str = "rubric:";
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+fn default_pass_threshold() -> f64 {
+    0.7
+}
+
+/// One scored criterion of a rubric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Criterion {
+    pub name: String,
+    /// What a reviewer should look for when scoring this criterion.
+    pub description: String,
+    /// Relative weight in the rubric's overall score.
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    /// Hard floor: the rubric fails if this criterion scores below it,
+    /// whatever the overall score.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<f64>,
+}
+
+/// A rubric loaded from `.wg/rubrics/<id>.yaml`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rubric {
+    /// Defaults to the file stem.
+    #[serde(default)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Tasks carrying any of these tags use this rubric.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Tasks applied from these functions use this rubric. An entry is a
+    /// function id (any template) or `<function>/<template>`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<String>,
+    pub criteria: Vec<Criterion>,
+    /// Minimum weighted score (0.0–1.0) for the rubric to pass.
+    #[serde(default = "default_pass_threshold")]
+    pub pass_threshold: f64,
+}
+
+/// Outcome of checking a set of scores against a rubric.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RubricResult {
+    pub rubric: String,
+    /// Weighted score over all criteria; unscored criteria count as 0.
+    pub score: f64,
+    pub pass_threshold: f64,
+    pub passed: bool,
+    /// Per-criterion scores, in rubric order (`None` when not scored).
+    pub criteria: Vec<(String, Option<f64>)>,
+    /// Criteria that scored below their `min_score`.
+    pub below_min: Vec<String>,
+    /// Criteria the evaluator did not score.
+    pub missing: Vec<String>,
+}
+
+impl RubricResult {
+    /// Human-readable reasons the rubric failed (empty when it passed).
+    pub fn failures(&self) -> Vec<String> {
+        if self.passed {
+            return Vec::new();
+        }
+        let mut reasons = Vec::new();
+        if self.score < self.pass_threshold {
+            reasons.push(format!(
+                "rubric '{}' scored {:.2}, below its pass threshold {:.2}",
+                self.rubric, self.score, self.pass_threshold
+            ));
+        }
+        for name in &self.below_min {
+            reasons.push(format!(
+                "rubric '{}': criterion '{}' is below its minimum score",
+                self.rubric, name
+            ));
+        }
+        for name in &self.missing {
+            reasons.push(format!(
+                "rubric '{}': criterion '{}' was not scored",
+                self.rubric, name
+            ));
+        }
+        reasons
+    }
+}
+
+impl Rubric {
+    /// Check that weights and thresholds make sense.
+    pub fn validate(&self) -> Result<()> {
+        if self.criteria.is_empty() {
+            anyhow::bail!("rubric '{}' has no criteria", self.id);
+        }
+        if !(0.0..=1.0).contains(&self.pass_threshold) {
+            anyhow::bail!(
+                "rubric '{}': pass_threshold must be between 0.0 and 1.0",
+                self.id
+            );
+        }
+        let mut seen = BTreeSet::new();
+        for c in &self.criteria {
+            if !seen.insert(c.name.as_str()) {
+                anyhow::bail!("rubric '{}': duplicate criterion '{}'", self.id, c.name);
+            }
+            if c.weight <= 0.0 || !c.weight.is_finite() {
+                anyhow::bail!(
+                    "rubric '{}': criterion '{}' must have a positive weight",
+                    self.id,
+                    c.name
+                );
+            }
+            if c.min_score.is_some_and(|m| !(0.0..=1.0).contains(&m)) {
+                anyhow::bail!(
+                    "rubric '{}': criterion '{}' min_score must be between 0.0 and 1.0",
+                    self.id,
+                    c.name
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether this rubric applies to `task`.
+    pub fn applies_to(&self, task: &Task) -> bool {
+        task.tags.iter().any(|tag| {
+            if tag
+                .strip_prefix(RUBRIC_TAG_PREFIX)
+                .is_some_and(|id| id == self.id)
+            {
+                return true;
+            }
+            if let Some(template) = tag.strip_prefix(TEMPLATE_TAG_PREFIX) {
+                let function = template.split('/').next().unwrap_or(template);
+                if self
+                    .functions
+                    .iter()
+                    .any(|f| f == template || f == function)
+                {
+                    return true;
+                }
+            }
+            self.tags.contains(tag)
+        })
+    }
+
+    /// Evaluation dimension key for a criterion.
+    pub fn dimension_key(criterion: &str) -> String {
+        format!("{}{}", DIMENSION_PREFIX, criterion)
+    }
+
+    /// Check evaluation dimensions (keyed by [`Rubric::dimension_key`])
+    /// against this rubric.
+    pub fn assess(&self, dimensions: &HashMap<String, f64>) -> RubricResult {
+        let total_weight: f64 = self.criteria.iter().map(|c| c.weight).sum();
+        let mut weighted = 0.0;
+        let mut criteria = Vec::new();
+        let mut below_min = Vec::new();
+        let mut missing = Vec::new();
+        for c in &self.criteria {
+            let score = dimensions
+                .get(&Self::dimension_key(&c.name))
+                .map(|s| s.clamp(0.0, 1.0));
+            match score {
+                Some(s) => {
+                    weighted += s * c.weight;
+                    if c.min_score.is_some_and(|min| s < min) {
+                        below_min.push(c.name.clone());
+                    }
+                }
+                None => missing.push(c.name.clone()),
+            }
+            criteria.push((c.name.clone(), score));
+        }
+        let score = if total_weight > 0.0 {
+            weighted / total_weight
+        } else {
+            0.0
+        };
+        RubricResult {
+            rubric: self.id.clone(),
+            score,
+            pass_threshold: self.pass_threshold,
+            passed: score >= self.pass_threshold && below_min.is_empty() && missing.is_empty(),
+            criteria,
+            below_min,
+            missing,
+        }
+    }
+}
+
+/// Directory holding rubric files.
+pub fn rubrics_dir(workgraph_dir: &Path) -> PathBuf {
+    workgraph_dir.join(RUBRICS_DIR)
+}
+
+fn load_file(path: &Path) -> Result<Rubric> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let mut rubric: Rubric =
+        serde_yaml::from_str(&content).with_context(|| format!("parsing {}", path.display()))?;
+    if rubric.id.is_empty() {
+        rubric.id = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+    }
+    rubric
+        .validate()
+        .with_context(|| format!("invalid rubric {}", path.display()))?;
+    Ok(rubric)
+}
+
+/// Load every rubric in `.wg/rubrics/`, sorted by id. A missing directory
+/// means no rubrics.
+pub fn load_all(workgraph_dir: &Path) -> Result<Vec<Rubric>> {
+    let dir = rubrics_dir(workgraph_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut rubrics = Vec::new();
+    for entry in std::fs::read_dir(&dir).with_context(|| format!("reading {}", dir.display()))? {
+        let path = entry?.path();
+        let is_yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml");
+        if is_yaml {
+            rubrics.push(load_file(&path)?);
+        }
+    }
+    rubrics.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(rubrics)
+}
+
+/// Load a single rubric by id.
+pub fn load(workgraph_dir: &Path, id: &str) -> Result<Rubric> {
+    load_all(workgraph_dir)?
+        .into_iter()
+        .find(|r| r.id == id)
+        .with_context(|| {
+            format!(
+                "Rubric '{}' not found in {}",
+                id,
+                rubrics_dir(workgraph_dir).display()
+            )
+        })
+}
+
+/// Rubrics that apply to `task`. Unreadable rubric files are reported as a
+/// warning and treated as absent so evaluation is never blocked by them.
+pub fn for_task(workgraph_dir: &Path, task: &Task) -> Vec<Rubric> {
+    match load_all(workgraph_dir) {
+        Ok(rubrics) => rubrics.into_iter().filter(|r| r.applies_to(task)).collect(),
+        Err(e) => {
+            eprintln!("Warning: {:#}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Prompt section asking the evaluator to score each rubric criterion as
+/// its own dimension.
+pub fn render_prompt_section(rubrics: &[Rubric]) -> String {
+    let mut out = String::new();
+    for rubric in rubrics {
+        out.push_str(&format!("### Rubric: {}\n", rubric.id));
+        if let Some(desc) = &rubric.description {
+            out.push_str(&format!("{}\n", desc));
+        }
+        out.push_str(&format!(
+            "Passes at a weighted score of {:.2}.\n\n",
+            rubric.pass_threshold
+        ));
+        for c in &rubric.criteria {
+            out.push_str(&format!(
+                "- `{}` (weight {}): {}",
+                Rubric::dimension_key(&c.name),
+                c.weight,
+                c.description
+            ));
+            if let Some(min) = c.min_score {
+                out.push_str(&format!(" Must score at least {:.2}.", min));
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out.push_str(
+        "Score every rubric criterion from 0.0 to 1.0 and include it in \"dimensions\" \
+         under the exact key shown above.\n",
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn rubric() -> Rubric {
+        Rubric {
+            id: "api-change".to_string(),
+            description: None,
+            tags: vec!["api".to_string()],
+            functions: vec!["release".to_string()],
+            criteria: vec![
+                Criterion {
+                    name: "compat".to_string(),
+                    description: "No breaking changes".to_string(),
+                    weight: 3.0,
+                    min_score: Some(0.5),
+                },
+                Criterion {
+                    name: "docs".to_string(),
+                    description: "Documented".to_string(),
+                    weight: 1.0,
+                    min_score: None,
+                },
+            ],
+            pass_threshold: 0.7,
+        }
+    }
+
+    fn task_with_tags(tags: &[&str]) -> Task {
+        Task {
+            id: "t".to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Task::default()
+        }
+    }
+
+    fn dims(pairs: &[(&str, f64)]) -> HashMap<String, f64> {
+        pairs
+            .iter()
+            .map(|(k, v)| (Rubric::dimension_key(k), *v))
+            .collect()
+    }
+
+    #[test]
+    fn applies_by_tag_function_and_explicit_reference() {
+        let r = rubric();
+        assert!(r.applies_to(&task_with_tags(&["api"])));
+        assert!(r.applies_to(&task_with_tags(&["template:release/ship"])));
+        assert!(r.applies_to(&task_with_tags(&["rubric:api-change"])));
+        assert!(!r.applies_to(&task_with_tags(&["template:other/ship"])));
+        assert!(!r.applies_to(&task_with_tags(&["rubric:other"])));
+    }
+
+    #[test]
+    fn assess_uses_weights_and_minimums() {
+        let r = rubric();
+        let result = r.assess(&dims(&[("compat", 0.8), ("docs", 0.4)]));
+        assert!((result.score - 0.7).abs() < 1e-9);
+        assert!(result.passed);
+
+        let result = r.assess(&dims(&[("compat", 0.4), ("docs", 1.0)]));
+        assert!(!result.passed);
+        assert_eq!(result.below_min, vec!["compat".to_string()]);
+
+        let result = r.assess(&dims(&[("compat", 1.0)]));
+        assert!(!result.passed);
+        assert_eq!(result.missing, vec!["docs".to_string()]);
+    }
+
+    #[test]
+    fn load_all_defaults_id_and_validates() {
+        let tmp = TempDir::new().unwrap();
+        let dir = rubrics_dir(tmp.path());
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("docs.yaml"),
+            "criteria:\n  - name: clarity\n    description: Clear prose\n",
+        )
+        .unwrap();
+        let rubrics = load_all(tmp.path()).unwrap();
+        assert_eq!(rubrics.len(), 1);
+        assert_eq!(rubrics[0].id, "docs");
+        assert_eq!(rubrics[0].criteria[0].weight, 1.0);
+        assert_eq!(rubrics[0].pass_threshold, 0.7);
+
+        std::fs::write(dir.join("bad.yaml"), "criteria: []\n").unwrap();
+        assert!(load_all(tmp.path()).is_err());
+    }
+}
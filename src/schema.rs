@@ -0,0 +1,141 @@
+//! JSON Schemas for the on-disk formats.
+//!
+//! The schemas are generated from the Rust types with schemars, so they
+//! describe exactly what the parser accepts: field names, serde renames,
+//! enum spellings, and defaults. `wg schema <kind>` prints one; point an
+//! editor at it to validate `.wg` files, or hand it to a third-party tool.
+
+use schemars::schema_for;
+use serde_json::{Value, json};
+
+use crate::config::Config;
+use crate::function::{RunSummary, TraceFunction};
+use crate::graph::Task;
+
+/// A file format with a published schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    Task,
+    Function,
+    Config,
+    RunSummary,
+}
+
+impl SchemaKind {
+    pub const ALL: [SchemaKind; 4] = [
+        SchemaKind::Task,
+        SchemaKind::Function,
+        SchemaKind::Config,
+        SchemaKind::RunSummary,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SchemaKind::Task => "task",
+            SchemaKind::Function => "function",
+            SchemaKind::Config => "config",
+            SchemaKind::RunSummary => "run-summary",
+        }
+    }
+
+    /// Where documents of this kind live, relative to the `.wg` directory.
+    pub fn location(self) -> &'static str {
+        match self {
+            SchemaKind::Task => "graph.jsonl (one task per line)",
+            SchemaKind::Function => "functions/<id>.yaml",
+            SchemaKind::Config => "config.toml",
+            SchemaKind::RunSummary => "functions/<id>.runs.jsonl (one run per line)",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase().replace('_', "-");
+        Self::ALL
+            .into_iter()
+            .find(|k| k.name() == name || (*k == SchemaKind::Function && name == "trace-function"))
+    }
+
+    /// The JSON Schema (draft 2020-12) for this kind.
+    pub fn schema(self) -> Value {
+        match self {
+            SchemaKind::Task => {
+                let mut schema = schema_for!(Task).to_value();
+                // Task lines in graph.jsonl carry the node tag, which lives
+                // on `Node` rather than on `Task` itself.
+                if let Some(properties) = schema["properties"].as_object_mut() {
+                    properties.insert("kind".to_string(), json!({ "const": "task" }));
+                }
+                schema
+            }
+            SchemaKind::Function => schema_for!(TraceFunction).to_value(),
+            SchemaKind::Config => schema_for!(Config).to_value(),
+            SchemaKind::RunSummary => schema_for!(RunSummary).to_value(),
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn property<'a>(schema: &'a Value, name: &str) -> &'a Value {
+        &schema["properties"][name]
+    }
+
+    #[test]
+    fn test_from_name() {
+        for kind in SchemaKind::ALL {
+            assert_eq!(SchemaKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(
+            SchemaKind::from_name("Run_Summary"),
+            Some(SchemaKind::RunSummary)
+        );
+        assert_eq!(
+            SchemaKind::from_name("trace-function"),
+            Some(SchemaKind::Function)
+        );
+        assert_eq!(SchemaKind::from_name("agent"), None);
+    }
+
+    #[test]
+    fn test_task_schema_follows_serde_attributes() {
+        let schema = SchemaKind::Task.schema();
+        assert_eq!(schema["title"], "Task");
+        assert_eq!(property(&schema, "kind")["const"], "task");
+        for field in ["id", "title", "status", "after", "skills", "log"] {
+            assert!(!property(&schema, field).is_null(), "missing {}", field);
+        }
+        // `required` lists only fields without a serde default.
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(Value::as_str)
+            .collect();
+        assert!(required.contains(&"id"));
+        assert!(!required.contains(&"status"));
+        // Status spellings are the kebab-case ones written to disk.
+        let status = serde_json::to_string(&schema["$defs"]["Status"]).unwrap();
+        assert!(status.contains("in-progress"), "{}", status);
+    }
+
+    #[test]
+    fn test_every_kind_produces_an_object_schema() {
+        for kind in SchemaKind::ALL {
+            let schema = kind.schema();
+            assert_eq!(schema["type"], "object", "{}", kind);
+            assert!(schema["$schema"].is_string(), "{}", kind);
+        }
+        let config = SchemaKind::Config.schema();
+        assert!(!property(&config, "dispatcher").is_null());
+        let function = SchemaKind::Function.schema();
+        assert!(!property(&function, "inputs").is_null());
+    }
+}
@@ -0,0 +1,505 @@
+//! Full-text index over task titles, descriptions, log messages and
+//! artifacts, backing `wg search`.
+//!
+//! The index is an inverted map from lowercase tokens to the documents
+//! (one per title, description, log entry or artifact) and word positions
+//! they occur at, so phrase queries can be answered without rescanning
+//! text. Artifacts contribute their path plus, for small UTF-8 files, their
+//! contents.
+//!
+//! It is stored as `.wg/search.index.json` and records the size and mtime of
+//! `graph.jsonl` and every archive file it was built from; when any of those
+//! change the index is rebuilt on next use. Edits to artifact files alone
+//! don't invalidate it (`wg search --reindex` forces a rebuild).
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::graph::Task;
+
+/// File name of the index, inside the `.wg` directory.
+pub const INDEX_FILE: &str = "search.index.json";
+
+const INDEX_VERSION: u32 = 1;
+
+/// Artifact files larger than this are indexed by path only.
+const MAX_ARTIFACT_BYTES: u64 = 256 * 1024;
+
+/// Which part of a task a document came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Field {
+    Title,
+    Description,
+    Log,
+    Artifact,
+}
+
+impl Field {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "title" => Some(Field::Title),
+            "description" | "desc" => Some(Field::Description),
+            "log" | "logs" => Some(Field::Log),
+            "artifact" | "artifacts" => Some(Field::Artifact),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Field::Title => "title",
+            Field::Description => "description",
+            Field::Log => "log",
+            Field::Artifact => "artifact",
+        }
+    }
+
+    /// Relevance multiplier: a title hit says more than a log mention.
+    fn weight(self) -> f64 {
+        match self {
+            Field::Title => 3.0,
+            Field::Description => 1.5,
+            Field::Log | Field::Artifact => 1.0,
+        }
+    }
+}
+
+/// One indexed piece of text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Doc {
+    pub task: String,
+    pub field: Field,
+    /// Index into the task's `log` or `artifacts`; 0 for title/description.
+    pub slot: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Posting {
+    doc: u32,
+    positions: Vec<u32>,
+}
+
+/// Size and mtime of one source file the index was built from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SourceStamp {
+    path: PathBuf,
+    len: u64,
+    mtime_ns: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchIndex {
+    version: u32,
+    sources: Vec<SourceStamp>,
+    pub docs: Vec<Doc>,
+    postings: BTreeMap<String, Vec<Posting>>,
+}
+
+/// A query clause: every clause must match somewhere in a task.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Clause {
+    Term(String),
+    Phrase(Vec<String>),
+}
+
+/// One matching task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    pub task: String,
+    pub score: f64,
+    /// The document that matched the most clauses, for snippets.
+    pub best: Doc,
+}
+
+/// Split text into lowercase words; letters, digits and `_` are word chars.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Parse query arguments. Double-quoted text is a phrase; so is an argument
+/// containing spaces (the shell already removed its quotes).
+pub fn parse_query(args: &[String]) -> Vec<Clause> {
+    let mut clauses = Vec::new();
+    let mut push = |text: &str, phrase: bool| {
+        let words = tokenize(text);
+        if phrase && words.len() > 1 {
+            clauses.push(Clause::Phrase(words));
+        } else {
+            clauses.extend(words.into_iter().map(Clause::Term));
+        }
+    };
+    for arg in args {
+        if !arg.contains('"') {
+            push(arg, arg.trim().contains(char::is_whitespace));
+            continue;
+        }
+        for (i, part) in arg.split('"').enumerate() {
+            // Odd-numbered pieces sit between quotes.
+            push(part, i % 2 == 1);
+        }
+    }
+    clauses
+}
+
+fn file_stamp(path: &Path) -> Option<SourceStamp> {
+    let meta = fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some(SourceStamp {
+        path: path.to_path_buf(),
+        len: meta.len(),
+        mtime_ns: mtime.as_nanos() as u64,
+    })
+}
+
+/// Stamps of `graph.jsonl` and the archive files under `dir`.
+fn current_sources(dir: &Path) -> Vec<SourceStamp> {
+    let mut paths = vec![dir.join("graph.jsonl")];
+    paths.extend(crate::archive::files(dir).unwrap_or_default());
+    paths.iter().filter_map(|p| file_stamp(p)).collect()
+}
+
+fn read_artifact(project_root: &Path, artifact: &str) -> Option<String> {
+    let path = project_root.join(artifact);
+    let meta = fs::metadata(&path).ok()?;
+    if !meta.is_file() || meta.len() > MAX_ARTIFACT_BYTES {
+        return None;
+    }
+    String::from_utf8(fs::read(&path).ok()?).ok()
+}
+
+impl SearchIndex {
+    /// Index `tasks`. Artifact paths are resolved against `project_root`.
+    pub fn build<'a>(
+        tasks: impl IntoIterator<Item = &'a Task>,
+        project_root: Option<&Path>,
+    ) -> Self {
+        let mut index = Self {
+            version: INDEX_VERSION,
+            ..Self::default()
+        };
+        for task in tasks {
+            index.add(&task.id, Field::Title, 0, &task.title);
+            if let Some(desc) = &task.description {
+                index.add(&task.id, Field::Description, 0, desc);
+            }
+            for (i, entry) in task.log.iter().enumerate() {
+                index.add(&task.id, Field::Log, i, &entry.message);
+            }
+            for (i, artifact) in task.artifacts.iter().enumerate() {
+                let mut text = artifact.clone();
+                if let Some(content) = project_root.and_then(|root| read_artifact(root, artifact)) {
+                    text.push('\n');
+                    text.push_str(&content);
+                }
+                index.add(&task.id, Field::Artifact, i, &text);
+            }
+        }
+        index
+    }
+
+    fn add(&mut self, task: &str, field: Field, slot: usize, text: &str) {
+        let doc = self.docs.len() as u32;
+        let mut positions: HashMap<String, Vec<u32>> = HashMap::new();
+        for (pos, word) in tokenize(text).into_iter().enumerate() {
+            positions.entry(word).or_default().push(pos as u32);
+        }
+        if positions.is_empty() {
+            return;
+        }
+        self.docs.push(Doc {
+            task: task.to_string(),
+            field,
+            slot,
+        });
+        for (word, positions) in positions {
+            self.postings
+                .entry(word)
+                .or_default()
+                .push(Posting { doc, positions });
+        }
+    }
+
+    /// The index for the workgraph at `dir`, rebuilt (and saved) from the
+    /// graph and archive if missing, stale, or `rebuild` is set.
+    pub fn open(dir: &Path, rebuild: bool) -> anyhow::Result<Self> {
+        let sources = current_sources(dir);
+        let path = dir.join(INDEX_FILE);
+        if !rebuild
+            && let Some(index) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|s| serde_json::from_str::<Self>(&s).ok())
+            && index.version == INDEX_VERSION
+            && index.sources == sources
+        {
+            return Ok(index);
+        }
+
+        let graph = crate::parser::load_graph(dir.join("graph.jsonl"))?;
+        let archived = crate::archive::load_all(dir).unwrap_or_default();
+        let project_root = dir
+            .canonicalize()
+            .ok()
+            .and_then(|d| d.parent().map(Path::to_path_buf));
+        let mut index = Self::build(
+            graph.tasks().chain(archived.iter()),
+            project_root.as_deref(),
+        );
+        index.sources = sources;
+        // Best effort: a read-only directory just means rebuilding next time.
+        if let Ok(json) = serde_json::to_string(&index) {
+            let _ = fs::write(&path, json);
+        }
+        Ok(index)
+    }
+
+    /// Documents matching `clause`, with how often it occurs in each.
+    fn matches(&self, clause: &Clause) -> Vec<(u32, usize)> {
+        match clause {
+            Clause::Term(word) => self
+                .postings
+                .get(word)
+                .map(|ps| ps.iter().map(|p| (p.doc, p.positions.len())).collect())
+                .unwrap_or_default(),
+            Clause::Phrase(words) => {
+                let lists: Option<Vec<&Vec<Posting>>> =
+                    words.iter().map(|w| self.postings.get(w)).collect();
+                let Some(lists) = lists else {
+                    return Vec::new();
+                };
+                let by_doc: Vec<HashMap<u32, &Vec<u32>>> = lists
+                    .iter()
+                    .map(|ps| ps.iter().map(|p| (p.doc, &p.positions)).collect())
+                    .collect();
+                lists[0]
+                    .iter()
+                    .filter_map(|first| {
+                        let count = first
+                            .positions
+                            .iter()
+                            .filter(|&&start| {
+                                by_doc.iter().enumerate().skip(1).all(|(offset, docs)| {
+                                    docs.get(&first.doc)
+                                        .is_some_and(|ps| ps.contains(&(start + offset as u32)))
+                                })
+                            })
+                            .count();
+                        (count > 0).then_some((first.doc, count))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Tasks matching every clause, best first. `fields` restricts which
+    /// parts of a task may match (empty = all).
+    pub fn search(&self, clauses: &[Clause], fields: &[Field]) -> Vec<Hit> {
+        if clauses.is_empty() {
+            return Vec::new();
+        }
+        let total = self.docs.len().max(1) as f64;
+        // doc -> (clauses matched, score)
+        let mut per_doc: HashMap<u32, (usize, f64)> = HashMap::new();
+        // task -> clauses matched
+        let mut per_task: HashMap<&str, Vec<bool>> = HashMap::new();
+
+        for (ci, clause) in clauses.iter().enumerate() {
+            let docs: Vec<(u32, usize)> = self
+                .matches(clause)
+                .into_iter()
+                .filter(|(d, _)| {
+                    fields.is_empty() || fields.contains(&self.docs[*d as usize].field)
+                })
+                .collect();
+            let idf = (1.0 + total / docs.len().max(1) as f64).ln();
+            for (d, tf) in docs {
+                let doc = &self.docs[d as usize];
+                let entry = per_doc.entry(d).or_default();
+                entry.0 += 1;
+                entry.1 += doc.field.weight() * (1.0 + (tf as f64).ln()) * idf;
+                per_task
+                    .entry(doc.task.as_str())
+                    .or_insert_with(|| vec![false; clauses.len()])[ci] = true;
+            }
+        }
+
+        // task -> (hit, clauses matched by its best doc, that doc's score)
+        let mut hits: HashMap<&str, (Hit, usize, f64)> = HashMap::new();
+        for (d, (matched, score)) in per_doc {
+            let doc = &self.docs[d as usize];
+            if !per_task[doc.task.as_str()].iter().all(|&m| m) {
+                continue;
+            }
+            let (hit, best_matched, best_score) =
+                hits.entry(doc.task.as_str()).or_insert_with(|| {
+                    let hit = Hit {
+                        task: doc.task.clone(),
+                        score: 0.0,
+                        best: doc.clone(),
+                    };
+                    (hit, 0, f64::MIN)
+                });
+            hit.score += score;
+            if (matched, score) > (*best_matched, *best_score) {
+                hit.best = doc.clone();
+                *best_matched = matched;
+                *best_score = score;
+            }
+        }
+
+        let mut hits: Vec<Hit> = hits.into_values().map(|(hit, _, _)| hit).collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.task.cmp(&b.task))
+        });
+        hits
+    }
+}
+
+/// The text a document was built from, looked up on its task.
+pub fn doc_text<'a>(task: &'a Task, doc: &Doc) -> Option<&'a str> {
+    match doc.field {
+        Field::Title => Some(&task.title),
+        Field::Description => task.description.as_deref(),
+        Field::Log => task.log.get(doc.slot).map(|e| e.message.as_str()),
+        Field::Artifact => task.artifacts.get(doc.slot).map(String::as_str),
+    }
+}
+
+/// A one-line excerpt of `text` around the first occurrence of any of
+/// `words`, about `width` characters wide.
+pub fn snippet(text: &str, words: &[&str], width: usize) -> String {
+    let flat: String = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let lower = flat.to_lowercase();
+    let hit = words
+        .iter()
+        .filter_map(|w| lower.find(w))
+        .min()
+        .unwrap_or(0);
+    // Work in chars so multi-byte text is never split mid-character.
+    let hit_char = lower[..hit].chars().count();
+    let chars: Vec<char> = flat.chars().collect();
+    let start = hit_char.saturating_sub(width / 3);
+    let end = (start + width).min(chars.len());
+    let mut out: String = chars[start..end].iter().collect();
+    if start > 0 {
+        out.insert(0, '…');
+    }
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::LogEntry;
+
+    fn task(id: &str, title: &str, desc: &str, logs: &[&str]) -> Task {
+        Task {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: Some(desc.to_string()),
+            log: logs
+                .iter()
+                .map(|m| LogEntry {
+                    timestamp: "2026-01-01T00:00:00Z".to_string(),
+                    actor: None,
+                    user: None,
+                    message: m.to_string(),
+                })
+                .collect(),
+            ..Task::default()
+        }
+    }
+
+    fn args(a: &[&str]) -> Vec<String> {
+        a.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(
+            parse_query(&args(&["rate", "Limit"])),
+            vec![Clause::Term("rate".into()), Clause::Term("limit".into())]
+        );
+        assert_eq!(
+            parse_query(&args(&["connection pool", "\"retry budget\" tls"])),
+            vec![
+                Clause::Phrase(vec!["connection".into(), "pool".into()]),
+                Clause::Phrase(vec!["retry".into(), "budget".into()]),
+                Clause::Term("tls".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_terms_phrases_and_fields() {
+        let tasks = [
+            task("a", "Fix login", "Users see a timeout on login", &[]),
+            task(
+                "b",
+                "Tune database",
+                "Connection pool sizing",
+                &["pool connection reset by peer"],
+            ),
+            task("c", "Docs", "Write up the connection pool design", &[]),
+        ];
+        let index = SearchIndex::build(tasks.iter(), None);
+
+        let hits = index.search(&parse_query(&args(&["login"])), &[]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].best.field, Field::Title);
+
+        let ids = |q: &[&str], f: &[Field]| -> Vec<String> {
+            let mut v: Vec<String> = index
+                .search(&parse_query(&args(q)), f)
+                .into_iter()
+                .map(|h| h.task)
+                .collect();
+            v.sort();
+            v
+        };
+        assert_eq!(ids(&["connection pool"], &[]), vec!["b", "c"]);
+        assert_eq!(ids(&["pool connection"], &[]), vec!["b"]);
+        assert_eq!(ids(&["reset"], &[Field::Log]), vec!["b"]);
+        assert!(ids(&["reset"], &[Field::Title]).is_empty());
+        // Every clause must match
+        assert!(ids(&["login", "pool"], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_open_reuses_and_rebuilds() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let mut graph = crate::graph::WorkGraph::new();
+        graph.add_node(crate::graph::Node::Task(task("a", "Alpha", "", &[])));
+        crate::parser::save_graph(&graph, dir.join("graph.jsonl")).unwrap();
+
+        let first = SearchIndex::open(dir, false).unwrap();
+        assert!(dir.join(INDEX_FILE).exists());
+        assert_eq!(SearchIndex::open(dir, false).unwrap(), first);
+
+        graph.add_node(crate::graph::Node::Task(task("b", "Beta", "", &[])));
+        crate::parser::save_graph(&graph, dir.join("graph.jsonl")).unwrap();
+        let index = SearchIndex::open(dir, false).unwrap();
+        assert_eq!(index.search(&[Clause::Term("beta".into())], &[]).len(), 1);
+    }
+
+    #[test]
+    fn test_snippet() {
+        let text = "x ".repeat(50) + "the Needle is here";
+        let s = snippet(&text, &["needle"], 20);
+        assert!(s.starts_with('…'));
+        assert!(s.contains("Needle"));
+    }
+}
@@ -17,6 +17,7 @@
 //! Use `wg secret backend show` to see which backend is actually reachable.
 
 use anyhow::{Context, Result, bail};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -26,7 +27,7 @@ const KEYRING_SERVICE: &str = "wg";
 
 // ── Backend selection ─────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum Backend {
     /// OS native credential store (macOS Keychain, secret-service, etc.) with
@@ -71,7 +72,7 @@ impl std::str::FromStr for Backend {
 
 // ── Secrets config section ────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct SecretsConfig {
     /// Enable the plaintext file backend. Off by default for safety.
     #[serde(default)]
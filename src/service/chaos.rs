@@ -0,0 +1,333 @@
+//! Chaos mode: fault injection for exercising the service's recovery paths.
+//!
+//! Test-only, and compiled in only with the `chaos` feature: release builds
+//! ignore `WG_CHAOS`. When it is set in the daemon's environment, the daemon
+//! randomly injects failures so that dead-agent detection, task reclaim and
+//! retries, and IPC clients can be tested against a misbehaving system:
+//!
+//! - `kill=P`: before each coordinator tick, SIGKILL each live agent (and
+//!   its process tree) with probability `P`.
+//! - `heartbeat=P`: before each tick, corrupt each live agent's registry
+//!   heartbeat with probability `P` (stale, in the future, or unparseable).
+//! - `ipc_delay=P`, `ipc_delay_ms=N`: hold each IPC response for `N` ms
+//!   (default 500) with probability `P`, after the request has been handled.
+//! - `seed=N`: fixed seed, so a failing run can be replayed.
+//!
+//! The value is a comma-separated list, e.g.
+//! `WG_CHAOS=kill=0.2,heartbeat=0.3,ipc_delay=0.5,seed=7`. Agents working on
+//! system tasks (chat and coordinator agents) are left alone.
+
+use anyhow::{Context, Result, bail};
+use chrono::{Duration as ChronoDuration, Utc};
+use std::path::Path;
+use std::time::Duration;
+
+use super::registry::AgentRegistry;
+
+/// Environment variable holding the chaos spec.
+pub const CHAOS_ENV: &str = "WG_CHAOS";
+
+const DEFAULT_IPC_DELAY_MS: u64 = 500;
+
+/// Parsed chaos spec. Probabilities are in `0.0..=1.0`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChaosConfig {
+    pub kill: f64,
+    pub heartbeat: f64,
+    pub ipc_delay: f64,
+    pub ipc_delay_ms: u64,
+    pub seed: Option<u64>,
+}
+
+impl ChaosConfig {
+    /// Parse a spec such as `kill=0.2,ipc_delay=0.5,ipc_delay_ms=800`.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut config = Self {
+            ipc_delay_ms: DEFAULT_IPC_DELAY_MS,
+            ..Self::default()
+        };
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .with_context(|| format!("Expected key=value, got '{}'", part))?;
+            let probability = || -> Result<f64> {
+                let p: f64 = value
+                    .parse()
+                    .with_context(|| format!("Invalid probability for '{}': '{}'", key, value))?;
+                if !(0.0..=1.0).contains(&p) {
+                    bail!(
+                        "Probability for '{}' must be between 0 and 1, got {}",
+                        key,
+                        p
+                    );
+                }
+                Ok(p)
+            };
+            match key {
+                "kill" => config.kill = probability()?,
+                "heartbeat" => config.heartbeat = probability()?,
+                "ipc_delay" => config.ipc_delay = probability()?,
+                "ipc_delay_ms" => {
+                    config.ipc_delay_ms = value
+                        .parse()
+                        .with_context(|| format!("Invalid ipc_delay_ms: '{}'", value))?
+                }
+                "seed" => {
+                    config.seed = Some(
+                        value
+                            .parse()
+                            .with_context(|| format!("Invalid seed: '{}'", value))?,
+                    )
+                }
+                other => bail!(
+                    "Unknown chaos key '{}' (expected kill, heartbeat, ipc_delay, ipc_delay_ms, seed)",
+                    other
+                ),
+            }
+        }
+        Ok(config)
+    }
+}
+
+impl std::fmt::Display for ChaosConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "kill={}, heartbeat={}, ipc_delay={} ({}ms)",
+            self.kill, self.heartbeat, self.ipc_delay, self.ipc_delay_ms
+        )?;
+        if let Some(seed) = self.seed {
+            write!(f, ", seed={}", seed)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a heartbeat was corrupted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartbeatCorruption {
+    /// Far in the past: looks like a hung agent.
+    Stale,
+    /// In the future: looks like clock skew.
+    Future,
+    /// Not a timestamp at all.
+    Garbage,
+}
+
+/// A fault injected into the running service.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChaosEvent {
+    Killed {
+        agent_id: String,
+        task_id: String,
+        pid: u32,
+    },
+    CorruptedHeartbeat {
+        agent_id: String,
+        kind: HeartbeatCorruption,
+    },
+}
+
+impl std::fmt::Display for ChaosEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChaosEvent::Killed {
+                agent_id,
+                task_id,
+                pid,
+            } => write!(f, "killed {} (PID {}, task '{}')", agent_id, pid, task_id),
+            ChaosEvent::CorruptedHeartbeat { agent_id, kind } => {
+                write!(f, "corrupted heartbeat of {} ({:?})", agent_id, kind)
+            }
+        }
+    }
+}
+
+/// Fault injector with its own seeded generator (splitmix64).
+#[derive(Debug, Clone)]
+pub struct Chaos {
+    pub config: ChaosConfig,
+    state: u64,
+}
+
+impl Chaos {
+    pub fn new(config: ChaosConfig) -> Self {
+        let state = config.seed.unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64
+        });
+        Self { config, state }
+    }
+
+    /// Chaos from `WG_CHAOS`, or `None` when unset or empty.
+    #[cfg(any(test, feature = "chaos"))]
+    pub fn from_env() -> Result<Option<Self>> {
+        match std::env::var(CHAOS_ENV) {
+            Ok(spec) if !spec.trim().is_empty() => {
+                let config = ChaosConfig::parse(&spec)
+                    .with_context(|| format!("Invalid {}='{}'", CHAOS_ENV, spec))?;
+                Ok(Some(Self::new(config)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Always `None`: builds without the `chaos` feature ignore `WG_CHAOS`.
+    #[cfg(not(any(test, feature = "chaos")))]
+    pub fn from_env() -> Result<Option<Self>> {
+        Ok(None)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn roll(&mut self, probability: f64) -> bool {
+        // 53 random bits as a uniform float in [0, 1).
+        let sample = (self.next_u64() >> 11) as f64 / ((1u64 << 53) as f64);
+        probability > 0.0 && sample < probability
+    }
+
+    /// How long to hold the next IPC response, if at all.
+    pub fn ipc_delay(&mut self) -> Option<Duration> {
+        self.roll(self.config.ipc_delay)
+            .then_some(Duration::from_millis(self.config.ipc_delay_ms))
+    }
+
+    /// Pick faults for the live agents in `registry` and apply the
+    /// heartbeat corruptions to it. Kills are returned for the caller to
+    /// carry out; nothing is signalled here.
+    pub fn plan(&mut self, registry: &mut AgentRegistry) -> Vec<ChaosEvent> {
+        let mut ids: Vec<String> = registry
+            .agents
+            .values()
+            .filter(|a| a.is_alive() && !crate::graph::is_system_task(&a.task_id))
+            .map(|a| a.id.clone())
+            .collect();
+        // Registry order is a hash map's; sort so a seed replays exactly.
+        ids.sort();
+
+        let mut events = Vec::new();
+        for id in ids {
+            if self.roll(self.config.kill)
+                && let Some(agent) = registry.agents.get(&id)
+            {
+                events.push(ChaosEvent::Killed {
+                    agent_id: id.clone(),
+                    task_id: agent.task_id.clone(),
+                    pid: agent.pid,
+                });
+                continue;
+            }
+            if self.roll(self.config.heartbeat)
+                && let Some(agent) = registry.agents.get_mut(&id)
+            {
+                let now = Utc::now();
+                let kind = match self.next_u64() % 3 {
+                    0 => HeartbeatCorruption::Stale,
+                    1 => HeartbeatCorruption::Future,
+                    _ => HeartbeatCorruption::Garbage,
+                };
+                agent.last_heartbeat = match kind {
+                    HeartbeatCorruption::Stale => (now - ChronoDuration::days(1)).to_rfc3339(),
+                    HeartbeatCorruption::Future => (now + ChronoDuration::days(1)).to_rfc3339(),
+                    HeartbeatCorruption::Garbage => "\u{fffd}chaos\u{fffd}".to_string(),
+                };
+                events.push(ChaosEvent::CorruptedHeartbeat { agent_id: id, kind });
+            }
+        }
+        events
+    }
+
+    /// Inject agent faults into the service in `workgraph_dir`: corrupt
+    /// heartbeats in the registry, then kill the chosen agents.
+    pub fn strike(&mut self, workgraph_dir: &Path) -> Result<Vec<ChaosEvent>> {
+        if self.config.kill == 0.0 && self.config.heartbeat == 0.0 {
+            return Ok(Vec::new());
+        }
+        let mut locked = AgentRegistry::load_locked(workgraph_dir)?;
+        let events = self.plan(&mut locked);
+        if events
+            .iter()
+            .any(|e| matches!(e, ChaosEvent::CorruptedHeartbeat { .. }))
+        {
+            locked.save_ref()?;
+        }
+        drop(locked);
+        for event in &events {
+            if let ChaosEvent::Killed { pid, .. } = event {
+                let _ = super::kill_process_force(*pid);
+            }
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::registry::AgentStatus;
+
+    fn registry(n: usize) -> AgentRegistry {
+        let mut registry = AgentRegistry::new();
+        for i in 0..n {
+            registry.register_agent(1000 + i as u32, &format!("task-{}", i), "shell", "out.log");
+        }
+        registry.register_agent(2000, ".coordinator-0", "claude", "out.log");
+        registry
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let config = ChaosConfig::parse("kill=0.25, ipc_delay=1,ipc_delay_ms=50,seed=9").unwrap();
+        assert_eq!(
+            config,
+            ChaosConfig {
+                kill: 0.25,
+                heartbeat: 0.0,
+                ipc_delay: 1.0,
+                ipc_delay_ms: 50,
+                seed: Some(9),
+            }
+        );
+        assert!(ChaosConfig::parse("kill=2").is_err());
+        assert!(ChaosConfig::parse("explode=0.5").is_err());
+        assert!(ChaosConfig::parse("kill").is_err());
+    }
+
+    #[test]
+    fn test_plan_is_seeded_and_spares_system_agents() {
+        let config = ChaosConfig::parse("kill=0.5,heartbeat=1,seed=42").unwrap();
+        let mut a = registry(8);
+        let mut b = registry(8);
+        let events = Chaos::new(config.clone()).plan(&mut a);
+        assert_eq!(events, Chaos::new(config).plan(&mut b));
+        // Every agent is hit exactly once: killed, or else its heartbeat corrupted.
+        assert_eq!(events.len(), 8);
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, ChaosEvent::Killed { .. }))
+        );
+        assert!(
+            a.agents
+                .values()
+                .filter(|a| a.task_id.starts_with('.'))
+                .all(|a| a.last_heartbeat.parse::<chrono::DateTime<Utc>>().is_ok())
+        );
+
+        let mut dead = registry(1);
+        for agent in dead.agents.values_mut() {
+            agent.status = AgentStatus::Dead;
+        }
+        let mut chaos = Chaos::new(ChaosConfig::parse("kill=1").unwrap());
+        assert!(chaos.plan(&mut dead).is_empty());
+        assert_eq!(chaos.ipc_delay(), None);
+    }
+}
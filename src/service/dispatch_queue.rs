@@ -0,0 +1,346 @@
+//! Durable record of dispatches in flight.
+//!
+//! Spawning claims the task in the graph (`in-progress`, `assigned` set to
+//! the new agent's ID) before it starts the executor and registers the
+//! agent. A crash in between used to leave the task claimed by an agent the
+//! registry had never heard of — and since the registry's `next_agent_id`
+//! was never bumped, the same ID was soon handed to another task, so the
+//! orphan sweep saw a live agent and left the claim alone forever.
+//!
+//! Each spawn now writes a [`PendingDispatch`] to
+//! `.wg/service/dispatch-queue.json` before claiming, and removes it once
+//! the agent is registered. All of that happens under the registry lock, so
+//! any entry found while holding the lock belongs to a spawn that died.
+//! [`reconcile`] (run at the start of every dispatcher tick, and therefore
+//! on every service start) settles those entries: a claim still held by the
+//! unregistered agent is released so the task is dispatched again. Delivery
+//! is at-least-once: a spawned process that never got registered is
+//! stopped, and its work redone by the next agent.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::graph::{LogEntry, Status};
+use crate::parser::modify_graph;
+use crate::service::registry::AgentRegistry;
+
+const QUEUE_FILE: &str = "dispatch-queue.json";
+
+/// One spawn between "task claimed" and "agent registered".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingDispatch {
+    pub task_id: String,
+    /// Agent ID the task was claimed for
+    pub agent_id: String,
+    pub executor: String,
+    pub claimed_at: String,
+    /// Wrapper script the executor runs, used to recognise its process
+    pub wrapper: PathBuf,
+    /// PID of the executor, once started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DispatchQueue {
+    #[serde(default)]
+    pub pending: Vec<PendingDispatch>,
+}
+
+impl DispatchQueue {
+    pub fn path(workgraph_dir: &Path) -> PathBuf {
+        workgraph_dir.join("service").join(QUEUE_FILE)
+    }
+
+    pub fn load(workgraph_dir: &Path) -> Result<Self> {
+        let path = Self::path(workgraph_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))
+    }
+
+    /// Save atomically (write to a temp file, then rename). An empty queue
+    /// removes the file.
+    pub fn save(&self, workgraph_dir: &Path) -> Result<()> {
+        let path = Self::path(workgraph_dir);
+        if self.pending.is_empty() {
+            if path.exists() {
+                fs::remove_file(&path).with_context(|| format!("Failed to remove {:?}", path))?;
+            }
+            return Ok(());
+        }
+        let service_dir = workgraph_dir.join("service");
+        fs::create_dir_all(&service_dir)?;
+        let temp_path = service_dir.join(".dispatch-queue.json.tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {:?}", temp_path))?;
+        fs::rename(&temp_path, &path).with_context(|| format!("Failed to rename to {:?}", path))
+    }
+
+    fn update(workgraph_dir: &Path, f: impl FnOnce(&mut Self)) -> Result<()> {
+        let mut queue = Self::load(workgraph_dir)?;
+        f(&mut queue);
+        queue.save(workgraph_dir)
+    }
+}
+
+/// Record a dispatch about to claim its task. Call with the registry lock
+/// held.
+pub fn record(workgraph_dir: &Path, entry: PendingDispatch) -> Result<()> {
+    DispatchQueue::update(workgraph_dir, |q| {
+        q.pending.retain(|p| p.agent_id != entry.agent_id);
+        q.pending.push(entry);
+    })
+}
+
+/// Note the executor's PID once it has started.
+pub fn mark_started(workgraph_dir: &Path, agent_id: &str, pid: u32) -> Result<()> {
+    DispatchQueue::update(workgraph_dir, |q| {
+        if let Some(p) = q.pending.iter_mut().find(|p| p.agent_id == agent_id) {
+            p.pid = Some(pid);
+        }
+    })
+}
+
+/// Drop a dispatch that finished: the agent is registered, or the claim
+/// was rolled back.
+pub fn complete(workgraph_dir: &Path, agent_id: &str) -> Result<()> {
+    DispatchQueue::update(workgraph_dir, |q| {
+        q.pending.retain(|p| p.agent_id != agent_id)
+    })
+}
+
+/// What [`reconcile`] did with an interrupted dispatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    /// The agent made it into the registry; nothing to do.
+    Registered,
+    /// The claim was released; the task will be dispatched again.
+    Requeued,
+    /// The task had already moved on (finished, reset, reclaimed).
+    Superseded,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Reconciled {
+    pub task_id: String,
+    pub agent_id: String,
+    pub resolution: Resolution,
+    /// Unregistered executor process that was stopped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stopped_pid: Option<u32>,
+}
+
+/// Whether `pid` is still running `wrapper` (guards against PID reuse after
+/// a reboot). Only checkable on Linux; elsewhere the process is left alone.
+fn runs_wrapper(pid: u32, wrapper: &Path) -> bool {
+    let Ok(cmdline) = fs::read(format!("/proc/{}/cmdline", pid)) else {
+        return false;
+    };
+    let wrapper = wrapper.to_string_lossy();
+    String::from_utf8_lossy(&cmdline)
+        .split('\0')
+        .any(|arg| arg == wrapper)
+}
+
+/// Settle dispatches interrupted between claiming and registering.
+///
+/// Takes the registry lock, so no spawn is in flight while it runs; every
+/// entry left in the queue is from a spawn that died.
+pub fn reconcile(workgraph_dir: &Path, graph_path: &Path) -> Result<Vec<Reconciled>> {
+    if !DispatchQueue::path(workgraph_dir).exists() {
+        return Ok(Vec::new());
+    }
+    let locked = AgentRegistry::load_locked(workgraph_dir)?;
+    let queue = DispatchQueue::load(workgraph_dir)?;
+    if queue.pending.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut results = Vec::new();
+    let mut release = Vec::new();
+    for entry in &queue.pending {
+        let registered = locked
+            .get_agent(&entry.agent_id)
+            .is_some_and(|a| a.task_id == entry.task_id);
+        if registered {
+            results.push(Reconciled {
+                task_id: entry.task_id.clone(),
+                agent_id: entry.agent_id.clone(),
+                resolution: Resolution::Registered,
+                stopped_pid: None,
+            });
+            continue;
+        }
+        let stopped_pid = entry.pid.filter(|&pid| {
+            crate::service::is_process_alive(pid)
+                && runs_wrapper(pid, &entry.wrapper)
+                && crate::service::kill_process_graceful(pid, 5).is_ok()
+        });
+        release.push((entry, stopped_pid));
+    }
+
+    let mut requeued = std::collections::HashSet::new();
+    if !release.is_empty() {
+        modify_graph(graph_path, |graph| {
+            let mut changed = false;
+            for (entry, stopped_pid) in &release {
+                let Some(task) = graph.get_task_mut(&entry.task_id) else {
+                    continue;
+                };
+                if task.status != Status::InProgress
+                    || task.assigned.as_deref() != Some(entry.agent_id.as_str())
+                {
+                    continue;
+                }
+                task.status = Status::Open;
+                task.assigned = None;
+                task.started_at = None;
+                task.log.push(LogEntry {
+                    timestamp: Utc::now().to_rfc3339(),
+                    actor: Some("dispatcher".to_string()),
+                    user: None,
+                    message: format!(
+                        "Dispatch to {} was interrupted before the agent was registered{}; \
+                         released for re-dispatch",
+                        entry.agent_id,
+                        stopped_pid
+                            .map(|p| format!(" (stopped its process, PID {})", p))
+                            .unwrap_or_default()
+                    ),
+                });
+                requeued.insert(entry.agent_id.clone());
+                changed = true;
+            }
+            changed
+        })
+        .context("Failed to release interrupted dispatch claims")?;
+    }
+    for (entry, stopped_pid) in release {
+        results.push(Reconciled {
+            task_id: entry.task_id.clone(),
+            agent_id: entry.agent_id.clone(),
+            resolution: if requeued.contains(&entry.agent_id) {
+                Resolution::Requeued
+            } else {
+                Resolution::Superseded
+            },
+            stopped_pid,
+        });
+    }
+
+    DispatchQueue::default().save(workgraph_dir)?;
+    drop(locked);
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Node, Task, WorkGraph};
+    use crate::parser::{load_graph, save_graph};
+    use tempfile::TempDir;
+
+    fn pending(task_id: &str, agent_id: &str) -> PendingDispatch {
+        PendingDispatch {
+            task_id: task_id.to_string(),
+            agent_id: agent_id.to_string(),
+            executor: "claude".to_string(),
+            claimed_at: Utc::now().to_rfc3339(),
+            wrapper: PathBuf::from("/nonexistent/run.sh"),
+            pid: None,
+        }
+    }
+
+    fn claimed(id: &str, agent_id: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            title: id.to_string(),
+            status: Status::InProgress,
+            assigned: Some(agent_id.to_string()),
+            started_at: Some(Utc::now().to_rfc3339()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_interrupted_claim_is_released() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let graph_path = dir.join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        // t1's dispatch died before registering agent-3; agent-3 was then
+        // handed to t2, which did register.
+        graph.add_node(Node::Task(claimed("t1", "agent-3")));
+        graph.add_node(Node::Task(claimed("t2", "agent-3")));
+        save_graph(&graph, &graph_path).unwrap();
+
+        // Register t2's agent under the reused ID.
+        let mut registry = AgentRegistry::new();
+        let id = registry.register_agent(std::process::id(), "t2", "claude", "/tmp/out.log");
+        let mut entry = registry.agents.remove(&id).unwrap();
+        entry.id = "agent-3".to_string();
+        registry.agents.insert("agent-3".to_string(), entry);
+        registry.save(dir).unwrap();
+
+        record(dir, pending("t1", "agent-3")).unwrap();
+        let results = reconcile(dir, &graph_path).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].resolution, Resolution::Requeued);
+
+        let graph = load_graph(&graph_path).unwrap();
+        let t1 = graph.get_task("t1").unwrap();
+        assert_eq!(t1.status, Status::Open);
+        assert!(t1.assigned.is_none());
+        assert_eq!(
+            graph.get_task("t2").unwrap().status,
+            Status::InProgress,
+            "the registered agent's task is untouched"
+        );
+        assert!(!DispatchQueue::path(dir).exists());
+    }
+
+    #[test]
+    fn test_registered_and_superseded_entries_are_dropped() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let graph_path = dir.join("graph.jsonl");
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(claimed("t1", "agent-1")));
+        graph.add_node(Node::Task(Task {
+            id: "t2".to_string(),
+            title: "t2".to_string(),
+            status: Status::Done,
+            ..Default::default()
+        }));
+        save_graph(&graph, &graph_path).unwrap();
+
+        let mut registry = AgentRegistry::new();
+        registry.register_agent(std::process::id(), "t1", "claude", "/tmp/out.log");
+        registry.save(dir).unwrap();
+
+        record(dir, pending("t1", "agent-1")).unwrap();
+        record(dir, pending("t2", "agent-2")).unwrap();
+        let results = reconcile(dir, &graph_path).unwrap();
+        let resolutions: Vec<_> = results.iter().map(|r| r.resolution).collect();
+        assert_eq!(
+            resolutions,
+            vec![Resolution::Registered, Resolution::Superseded]
+        );
+        assert_eq!(
+            load_graph(&graph_path)
+                .unwrap()
+                .get_task("t1")
+                .unwrap()
+                .status,
+            Status::InProgress
+        );
+    }
+}
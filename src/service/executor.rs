@@ -11,7 +11,7 @@ use std::path::{Path, PathBuf};
 
 use crate::agency;
 use crate::context_scope::ContextScope;
-use crate::graph::Task;
+use crate::graph::{FailureClass, Task};
 
 // --- Prompt section constants for scope-based assembly ---
 
@@ -98,6 +98,10 @@ is grow-only.
    wg done {{task_id}}
    wg done {{task_id}} --converged  # Use this if task has loop edges and work is complete
    ```
+   Add `--report` with how you did it, so the work can be reused as a template:
+   ```bash
+   wg done {{task_id}} --report '{\"approach\": \"...\", \"steps\": [\"...\"], \"pitfalls\": [\"...\"]}'
+   ```
 
 7. **Mark as failed** ONLY after genuine attempt:
    You MUST attempt the actual work before calling `wg fail`. Explaining why something \
@@ -975,6 +979,9 @@ pub struct ScopeContext {
     /// has `read_file`/`write_file`/`edit_file`/`grep`/`glob` available
     /// and should prefer them over bash equivalents.
     pub native_file_tools: bool,
+    /// Onboarding brief from `wg onboard` (task+ scope, opt-in via
+    /// `agent.onboarding_brief`)
+    pub onboarding_brief: String,
 }
 
 /// Build a scope-aware prompt for built-in executors.
@@ -1078,6 +1085,11 @@ pub fn build_prompt(vars: &TemplateVars, scope: ContextScope, ctx: &ScopeContext
         parts.push(format!("## WG Usage Guide\n\n{}", ctx.wg_guide_content));
     }
 
+    // Task+ scope: onboarding brief (project summary, conventions, ready work)
+    if scope >= ContextScope::Task && !ctx.onboarding_brief.is_empty() {
+        parts.push(ctx.onboarding_brief.clone());
+    }
+
     // Task+ scope: native-executor file-tool guidance. Teaches the model
     // that it has dedicated read_file/write_file/edit_file/grep/glob tools
     // and should prefer them over bash equivalents (echo/cat/heredoc/sed).
@@ -1194,6 +1206,75 @@ pub struct TemplateVars {
     pub failed_deps_info: String,
     /// True when the agent is running in a wg-managed worktree
     pub in_worktree: bool,
+    /// Environment facts exposed as `{{date}}`, `{{git_head}}`, etc.
+    pub facts: SystemFacts,
+}
+
+/// Facts about the host and project, gathered when a prompt is rendered so
+/// templates don't hard-code details that drift (dates, versions, paths).
+#[derive(Debug, Clone, Default)]
+pub struct SystemFacts {
+    /// Current UTC date, `YYYY-MM-DD`
+    pub date: String,
+    /// Short hash of the repository's HEAD commit, empty outside git
+    pub git_head: String,
+    /// Operating system (`linux`, `macos`, `windows`, ...)
+    pub os: String,
+    /// Git top-level directory, falling back to the project root
+    pub repo_root: String,
+    /// Version of the running `wg` binary
+    pub wg_version: String,
+    /// Done tasks upstream of this task (see [`SystemFacts::count_related_done`])
+    pub related_done: usize,
+}
+
+impl SystemFacts {
+    /// Gather facts for a project rooted at `project_root` (parent of `.wg/`).
+    /// Git lookups are skipped when no root is known.
+    pub fn gather(project_root: Option<&Path>) -> Self {
+        let git = |args: &[&str]| -> Option<String> {
+            let output = std::process::Command::new("git")
+                .args(args)
+                .current_dir(project_root?)
+                .output()
+                .ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            let s = String::from_utf8(output.stdout).ok()?;
+            Some(s.trim().to_string()).filter(|s| !s.is_empty())
+        };
+
+        Self {
+            date: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+            git_head: git(&["rev-parse", "--short", "HEAD"]).unwrap_or_default(),
+            os: std::env::consts::OS.to_string(),
+            repo_root: git(&["rev-parse", "--show-toplevel"])
+                .or_else(|| project_root.map(|p| p.to_string_lossy().to_string()))
+                .unwrap_or_default(),
+            wg_version: env!("CARGO_PKG_VERSION").to_string(),
+            related_done: 0,
+        }
+    }
+
+    /// Number of done tasks among `task`'s transitive dependencies.
+    pub fn count_related_done(graph: &crate::graph::WorkGraph, task: &Task) -> usize {
+        let mut seen: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        let mut stack: Vec<&str> = task.after.iter().map(String::as_str).collect();
+        let mut done = 0;
+        while let Some(id) = stack.pop() {
+            if id == task.id || !seen.insert(id) {
+                continue;
+            }
+            if let Some(dep) = graph.get_task(id) {
+                if dep.status == crate::graph::Status::Done {
+                    done += 1;
+                }
+                stack.extend(dep.after.iter().map(String::as_str));
+            }
+        }
+        done
+    }
 }
 
 impl TemplateVars {
@@ -1251,6 +1332,9 @@ impl TemplateVars {
             .and_then(|dir| crate::config::Config::load_merged(dir).ok())
             .map(|cfg| cfg.guardrails)
             .unwrap_or_default();
+        let facts = SystemFacts::gather(
+            Some(Path::new(&working_dir)).filter(|p| !p.as_os_str().is_empty()),
+        );
 
         Self {
             task_id: task.id.clone(),
@@ -1268,6 +1352,7 @@ impl TemplateVars {
             has_failed_deps: false,
             failed_deps_info: String::new(),
             in_worktree: false,
+            facts,
         }
     }
 
@@ -1398,6 +1483,12 @@ impl TemplateVars {
             .replace("{{task_verify}}", self.task_verify.as_deref().unwrap_or(""))
             .replace("{{max_child_tasks}}", &self.max_child_tasks.to_string())
             .replace("{{max_task_depth}}", &self.max_task_depth.to_string())
+            .replace("{{date}}", &self.facts.date)
+            .replace("{{git_head}}", &self.facts.git_head)
+            .replace("{{os}}", &self.facts.os)
+            .replace("{{repo_root}}", &self.facts.repo_root)
+            .replace("{{wg_version}}", &self.facts.wg_version)
+            .replace("{{related_done}}", &self.facts.related_done.to_string())
     }
 }
 
@@ -1443,6 +1534,161 @@ pub struct ExecutorSettings {
     /// Hierarchy: task.model > executor.model > coordinator.model > 'default'.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
+
+    /// Automatic retry of tasks that fail under this executor (`[executor.retry]`).
+    /// Unset: failures are terminal until `wg retry`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+}
+
+/// Kind of failure, as matched by [`RetryPolicy::retry_on`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RetryOn {
+    /// The agent hit its hard timeout.
+    Timeout,
+    /// The agent exited non-zero, including transient API errors.
+    Exit,
+    /// The evaluation gate rejected the work.
+    Validation,
+}
+
+impl RetryOn {
+    /// Classify a failure. `None` for failures no retry can fix: a bad input
+    /// document, a tool policy violation, a blown cost cap, or a failure
+    /// reported without a class (`wg fail` by an agent or a person).
+    pub fn classify(class: Option<FailureClass>, eval_reject: bool) -> Option<Self> {
+        if eval_reject {
+            return Some(Self::Validation);
+        }
+        match class? {
+            FailureClass::AgentHardTimeout => Some(Self::Timeout),
+            FailureClass::AgentExitNonzero
+            | FailureClass::ApiError429RateLimit
+            | FailureClass::ApiError5xxTransient
+            | FailureClass::WrapperInternal => Some(Self::Exit),
+            FailureClass::ApiError400Document
+            | FailureClass::ToolPolicyViolation
+            | FailureClass::CostCapExceeded => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RetryOn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "timeout"),
+            Self::Exit => write!(f, "exit"),
+            Self::Validation => write!(f, "validation"),
+        }
+    }
+}
+
+/// Retry policy for an executor.
+///
+/// The delay before retry `n` (1-based) is `backoff * multiplier^(n-1)`,
+/// capped at `max_backoff`, then spread by up to `jitter` of itself either
+/// way. The spread is derived from the task ID and attempt so it is stable
+/// across runs, as with cron jitter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total attempts, the first included. 1 disables retries.
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Delay before the first retry (e.g. "30s", "5m").
+    #[serde(default = "default_retry_backoff")]
+    pub backoff: String,
+
+    /// Factor applied to the delay after each retry.
+    #[serde(default = "default_retry_multiplier")]
+    pub multiplier: f64,
+
+    /// Upper bound on the delay.
+    #[serde(default = "default_retry_max_backoff")]
+    pub max_backoff: String,
+
+    /// Fraction of the delay to spread retries by (0.0–1.0).
+    #[serde(default = "default_retry_jitter")]
+    pub jitter: f64,
+
+    /// Failures that are retried. Default: timeout and exit.
+    #[serde(default = "default_retry_on")]
+    pub retry_on: Vec<RetryOn>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_max_attempts(),
+            backoff: default_retry_backoff(),
+            multiplier: default_retry_multiplier(),
+            max_backoff: default_retry_max_backoff(),
+            jitter: default_retry_jitter(),
+            retry_on: default_retry_on(),
+        }
+    }
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_backoff() -> String {
+    "30s".to_string()
+}
+
+fn default_retry_multiplier() -> f64 {
+    2.0
+}
+
+fn default_retry_max_backoff() -> String {
+    "1h".to_string()
+}
+
+fn default_retry_jitter() -> f64 {
+    0.1
+}
+
+fn default_retry_on() -> Vec<RetryOn> {
+    vec![RetryOn::Timeout, RetryOn::Exit]
+}
+
+impl RetryPolicy {
+    /// Delay in seconds before retry `retry` (1-based) of `task_id`.
+    pub fn delay_secs(&self, task_id: &str, retry: u32) -> u64 {
+        let base = crate::graph::parse_delay(&self.backoff).unwrap_or(30) as f64;
+        let cap = crate::graph::parse_delay(&self.max_backoff).unwrap_or(3600) as f64;
+        let exp = retry.saturating_sub(1).min(64) as i32;
+        let delay = (base * self.multiplier.max(1.0).powi(exp)).min(cap);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 || delay == 0.0 {
+            return delay.round() as u64;
+        }
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+        let mut hasher = DefaultHasher::new();
+        (task_id, retry).hash(&mut hasher);
+        // Map the hash to [-1.0, 1.0]
+        let unit = (hasher.finish() % 2001) as f64 / 1000.0 - 1.0;
+        (delay * (1.0 + jitter * unit)).max(0.0).round() as u64
+    }
+
+    /// Seconds to wait before retrying `task` after a failure of `kind`, or
+    /// `None` when the failure is final. `task.retry_count` counts failed
+    /// attempts, this one included; a task-level `max_retries` caps the
+    /// policy.
+    pub fn next_retry(&self, task: &Task, kind: Option<RetryOn>) -> Option<u64> {
+        let kind = kind?;
+        if !self.retry_on.contains(&kind) || task.retry_count >= self.max_attempts {
+            return None;
+        }
+        if task.max_retries.is_some_and(|max| task.retry_count > max) {
+            return None;
+        }
+        Some(self.delay_secs(&task.id, task.retry_count))
+    }
 }
 
 /// Prompt template for injecting task context.
@@ -1562,6 +1808,7 @@ impl ExecutorRegistry {
                     working_dir: Some("{{working_dir}}".to_string()),
                     timeout: None,
                     model: None,
+                    retry: None,
                 },
             }),
             "codex" => Ok(ExecutorConfig {
@@ -1588,6 +1835,7 @@ impl ExecutorRegistry {
                     working_dir: Some("{{working_dir}}".to_string()),
                     timeout: None,
                     model: None,
+                    retry: None,
                 },
             }),
             "shell" => Ok(ExecutorConfig {
@@ -1605,6 +1853,7 @@ impl ExecutorRegistry {
                     working_dir: None,
                     timeout: None,
                     model: None,
+                    retry: None,
                 },
             }),
             "native" => Ok(ExecutorConfig {
@@ -1622,6 +1871,25 @@ impl ExecutorRegistry {
                     working_dir: Some("{{working_dir}}".to_string()),
                     timeout: None,
                     model: None,
+                    retry: None,
+                },
+            }),
+            "webhook" => Ok(ExecutorConfig {
+                executor: ExecutorSettings {
+                    executor_type: "webhook".to_string(),
+                    command: "wg".to_string(),
+                    args: vec!["webhook-exec".to_string()],
+                    env: {
+                        let mut env = HashMap::new();
+                        env.insert("WG_TASK_ID".to_string(), "{{task_id}}".to_string());
+                        env
+                    },
+                    // The rendered prompt goes into the payload's `prompt` field.
+                    prompt_template: None,
+                    working_dir: Some("{{working_dir}}".to_string()),
+                    timeout: None,
+                    model: None,
+                    retry: None,
                 },
             }),
             "default" => Ok(ExecutorConfig {
@@ -1634,10 +1902,11 @@ impl ExecutorRegistry {
                     working_dir: None,
                     timeout: None,
                     model: None,
+                    retry: None,
                 },
             }),
             _ => Err(anyhow!(
-                "Unknown executor '{}'. Available: claude, codex, native, shell, default",
+                "Unknown executor '{}'. Available: claude, codex, native, shell, webhook, default",
                 name,
             )),
         }
@@ -1687,19 +1956,25 @@ mod tests {
             estimate: None,
             before: vec![],
             after: vec![],
+            edges: Default::default(),
             requires: vec![],
             tags: vec![],
             skills: vec![],
             inputs: vec![],
             deliverables: vec![],
+            expects: vec![],
+            gate: None,
             artifacts: vec![],
+            self_report: None,
             exec: None,
             timeout: None,
             not_before: None,
+            due: None,
             created_at: None,
             started_at: None,
             completed_at: None,
             last_interaction_at: None,
+            revision: 0,
             log: vec![],
             retry_count: 0,
             max_retries: None,
@@ -1724,10 +1999,20 @@ mod tests {
             exec_mode: None,
             cycle_config: None,
             token_usage: None,
+            max_cost: None,
+            min_context: None,
+            claim_sha: None,
+            changes: None,
             session_id: None,
             wait_condition: None,
             checkpoint: None,
             triage_count: 0,
+            escalation_level: 0,
+            blocked_at: None,
+            deadline_alert: None,
+            external_blocker: None,
+            waiting_on: vec![],
+            links: vec![],
             resurrection_count: 0,
             last_resurrected_at: None,
             validation: None,
@@ -1832,6 +2117,7 @@ template = "Work on {{task_id}}"
                 working_dir: Some("/work/{{task_id}}".to_string()),
                 timeout: None,
                 model: None,
+                retry: None,
             },
         };
 
@@ -1945,6 +2231,8 @@ template = "Work on {{task_id}}"
             attractor_weight: 1.0,
             deployment_history: vec![],
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         agency::save_agent(&agent, &agents_dir).unwrap();
 
@@ -2180,6 +2468,46 @@ template = "Work on {{task_id}}"
         assert_eq!(vars.working_dir, expected.to_string_lossy().to_string());
     }
 
+    #[test]
+    fn test_template_apply_system_facts() {
+        let task = make_test_task("task-1", "Test");
+        let mut vars = TemplateVars::from_task(&task, None, None);
+        vars.facts.git_head = "abc1234".to_string();
+        vars.facts.related_done = 3;
+
+        let result = vars.apply("{{os}} {{wg_version}} {{git_head}} {{related_done}} {{date}}");
+        let expected = format!(
+            "{} {} abc1234 3 {}",
+            std::env::consts::OS,
+            env!("CARGO_PKG_VERSION"),
+            chrono::Utc::now().format("%Y-%m-%d")
+        );
+        assert_eq!(result, expected);
+        // No project dir: no git lookups, no repo root
+        assert_eq!(vars.facts.repo_root, "");
+    }
+
+    #[test]
+    fn test_count_related_done_is_transitive() {
+        use crate::graph::{Node, Status, WorkGraph};
+
+        let mut graph = WorkGraph::new();
+        let mut a = make_test_task("a", "A");
+        a.status = Status::Done;
+        let mut b = make_test_task("b", "B");
+        b.status = Status::Done;
+        b.after = vec!["a".to_string()];
+        let mut c = make_test_task("c", "C");
+        c.after = vec!["b".to_string()];
+        let mut d = make_test_task("d", "D");
+        d.after = vec!["c".to_string(), "b".to_string()];
+        for t in [a, b, c, d.clone()] {
+            graph.add_node(Node::Task(t));
+        }
+
+        assert_eq!(SystemFacts::count_related_done(&graph, &d), 2);
+    }
+
     // --- ExecutorRegistry error path tests ---
 
     #[test]
@@ -2230,6 +2558,96 @@ args = ["--custom-flag"]
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_retry_policy_parses_with_defaults() {
+        let config: ExecutorConfig = toml::from_str(
+            r#"
+[executor]
+type = "claude"
+command = "claude"
+
+[executor.retry]
+max_attempts = 4
+retry_on = ["timeout", "validation"]
+"#,
+        )
+        .unwrap();
+        let retry = config.executor.retry.unwrap();
+        assert_eq!(retry.max_attempts, 4);
+        assert_eq!(retry.backoff, "30s");
+        assert_eq!(retry.retry_on, vec![RetryOn::Timeout, RetryOn::Validation]);
+
+        let registry = ExecutorRegistry::new(Path::new("/nonexistent"));
+        assert!(
+            registry
+                .load_config("claude")
+                .unwrap()
+                .executor
+                .retry
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_retry_policy_backoff_grows_and_caps() {
+        let policy = RetryPolicy {
+            backoff: "10s".to_string(),
+            max_backoff: "1m".to_string(),
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        };
+        let delays: Vec<u64> = (1..=5).map(|n| policy.delay_secs("t", n)).collect();
+        assert_eq!(delays, vec![10, 20, 40, 60, 60]);
+
+        let jittered = RetryPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+        for n in 1..=5 {
+            let d = jittered.delay_secs("t", n);
+            let base = delays[n as usize - 1];
+            assert!(d >= base / 2 && d <= base * 3 / 2, "{} vs {}", d, base);
+            // Stable for the same task and attempt
+            assert_eq!(d, jittered.delay_secs("t", n));
+        }
+    }
+
+    #[test]
+    fn test_retry_policy_next_retry() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            ..RetryPolicy::default()
+        };
+        let mut task = Task {
+            id: "t".to_string(),
+            retry_count: 1,
+            ..Task::default()
+        };
+        assert_eq!(policy.next_retry(&task, Some(RetryOn::Exit)), Some(30));
+        assert_eq!(policy.next_retry(&task, Some(RetryOn::Validation)), None);
+        assert_eq!(policy.next_retry(&task, None), None);
+
+        task.retry_count = 2;
+        assert_eq!(policy.next_retry(&task, Some(RetryOn::Timeout)), Some(60));
+        task.retry_count = 3;
+        assert_eq!(policy.next_retry(&task, Some(RetryOn::Timeout)), None);
+
+        // Task-level max_retries still caps the policy
+        task.retry_count = 2;
+        task.max_retries = Some(1);
+        assert_eq!(policy.next_retry(&task, Some(RetryOn::Exit)), None);
+
+        assert_eq!(
+            RetryOn::classify(Some(FailureClass::AgentHardTimeout), false),
+            Some(RetryOn::Timeout)
+        );
+        assert_eq!(
+            RetryOn::classify(Some(FailureClass::ToolPolicyViolation), false),
+            None
+        );
+        assert_eq!(RetryOn::classify(None, true), Some(RetryOn::Validation));
+    }
+
     #[test]
     fn test_registry_init_idempotent() {
         let temp_dir = TempDir::new().unwrap();
@@ -2342,6 +2760,7 @@ args = ["--custom-flag"]
                 working_dir: None,
                 timeout: None,
                 model: None,
+                retry: None,
             },
         };
 
@@ -2365,6 +2784,7 @@ args = ["--custom-flag"]
                 working_dir: None,
                 timeout: None,
                 model: None,
+                retry: None,
             },
         };
 
@@ -2394,6 +2814,7 @@ args = ["--custom-flag"]
                 working_dir: None,
                 timeout: None,
                 model: None,
+                retry: None,
             },
         };
 
@@ -2445,6 +2866,8 @@ args = ["--custom-flag"]
             attractor_weight: 1.0,
             deployment_history: vec![],
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         agency::save_agent(&agent, &agents_dir).unwrap();
 
@@ -2941,6 +3364,22 @@ args = ["--custom-flag"]
         );
     }
 
+    #[test]
+    fn test_build_prompt_onboarding_brief_task_scope_only() {
+        let task = make_test_task("task-1", "Onboarding task");
+        let vars = TemplateVars::from_task(&task, None, None);
+        let ctx = ScopeContext {
+            onboarding_brief: "## Onboarding Brief\n\n- Use anyhow".to_string(),
+            ..Default::default()
+        };
+
+        let prompt = build_prompt(&vars, ContextScope::Task, &ctx);
+        assert!(prompt.contains("## Onboarding Brief"));
+
+        let prompt = build_prompt(&vars, ContextScope::Clean, &ctx);
+        assert!(!prompt.contains("## Onboarding Brief"));
+    }
+
     #[test]
     fn test_default_wg_guide_covers_key_topics() {
         let guide = DEFAULT_WG_GUIDE;
@@ -0,0 +1,294 @@
+//! Executor preflight checks with a TTL cache
+//!
+//! Before dispatching, the coordinator checks each executor it is about to
+//! use: the backing binary is on PATH, the endpoint's API key resolves, and
+//! the endpoint host accepts a TCP connection. Results are cached in
+//! `.wg/service/executor_health.json` for `coordinator.executor_preflight_ttl`
+//! seconds. Tasks routed to an unhealthy executor stay in the ready queue
+//! instead of burning spawn attempts one by one.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::config::{Config, EndpointConfig};
+use crate::service::executor::ExecutorRegistry;
+
+/// How long to wait for an endpoint's TCP handshake
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Providers that reject unauthenticated requests
+const KEYED_PROVIDERS: &[&str] = &["anthropic", "openai", "openrouter", "gemini"];
+
+/// Result of one preflight run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutorCheck {
+    pub healthy: bool,
+    pub checked_at: String,
+    /// Human-readable reasons the executor is unhealthy (empty when healthy)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problems: Vec<String>,
+}
+
+impl ExecutorCheck {
+    fn is_fresh(&self, ttl_secs: u64, now: DateTime<Utc>) -> bool {
+        DateTime::parse_from_rfc3339(&self.checked_at)
+            .map(|at| (now - at.with_timezone(&Utc)).num_seconds() < ttl_secs as i64)
+            .unwrap_or(false)
+    }
+}
+
+/// Cached preflight results, keyed by [`health_key`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutorHealth {
+    pub executors: BTreeMap<String, ExecutorCheck>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl ExecutorHealth {
+    /// Load cached results; a missing or corrupt file yields an empty cache.
+    pub fn load(dir: &Path) -> Self {
+        fs::read_to_string(executor_health_path(dir))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persist results if any check ran since [`Self::load`].
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let path = executor_health_path(dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("Failed to create {:?}", parent))?;
+        }
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize executor health")?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write executor health to {:?}", path))
+    }
+
+    /// Return the cached check for this executor/endpoint, re-running the
+    /// preflight when the cached result is older than `ttl_secs`.
+    pub fn check(
+        &mut self,
+        dir: &Path,
+        executor: &str,
+        endpoint: Option<&EndpointConfig>,
+        ttl_secs: u64,
+    ) -> &ExecutorCheck {
+        let key = health_key(executor, endpoint);
+        let now = Utc::now();
+        let stale = self
+            .executors
+            .get(&key)
+            .is_none_or(|c| !c.is_fresh(ttl_secs, now));
+        if stale {
+            let problems = preflight(dir, executor, endpoint);
+            self.executors.insert(
+                key.clone(),
+                ExecutorCheck {
+                    healthy: problems.is_empty(),
+                    checked_at: now.to_rfc3339(),
+                    problems,
+                },
+            );
+            self.dirty = true;
+        }
+        &self.executors[&key]
+    }
+
+    pub fn unhealthy(&self) -> impl Iterator<Item = (&String, &ExecutorCheck)> {
+        self.executors.iter().filter(|(_, c)| !c.healthy)
+    }
+}
+
+fn executor_health_path(dir: &Path) -> PathBuf {
+    dir.join("service").join("executor_health.json")
+}
+
+/// Cache key: the executor name, qualified by endpoint for native spawns
+/// since each endpoint has its own credentials and host.
+pub fn health_key(executor: &str, endpoint: Option<&EndpointConfig>) -> String {
+    match endpoint {
+        Some(ep) => format!("{}:{}", executor, ep.name),
+        None => executor.to_string(),
+    }
+}
+
+/// Run the preflight checks and return what's wrong (empty = healthy).
+pub fn preflight(dir: &Path, executor: &str, endpoint: Option<&EndpointConfig>) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    match ExecutorRegistry::new(dir).load_config(executor) {
+        Ok(config) => {
+            // native and webhook run inside this binary; nothing to look up
+            let executor_type = config.executor.executor_type.as_str();
+            let command = &config.executor.command;
+            if !matches!(executor_type, "native" | "webhook") && !command_exists(command) {
+                problems.push(format!("`{}` not found on PATH", command));
+            }
+            if executor_type == "webhook" {
+                match Config::load_or_default(dir).webhook_executor.url {
+                    Some(url) => {
+                        if let Err(e) = probe_url(&url) {
+                            problems.push(e);
+                        }
+                    }
+                    None => problems.push("[webhook_executor] url is not set".to_string()),
+                }
+            }
+        }
+        Err(e) => problems.push(format!("executor config: {}", e)),
+    }
+
+    if let Some(ep) = endpoint {
+        match ep.resolve_api_key(Some(dir)) {
+            Ok(Some(_)) => {}
+            Ok(None) if KEYED_PROVIDERS.contains(&ep.provider.as_str()) => {
+                problems.push(format!("no API key configured for endpoint '{}'", ep.name));
+            }
+            Ok(None) => {}
+            Err(e) => problems.push(format!("API key for endpoint '{}': {}", ep.name, e)),
+        }
+        let url = ep
+            .url
+            .clone()
+            .unwrap_or_else(|| EndpointConfig::default_url_for_provider(&ep.provider).to_string());
+        if let Err(e) = probe_url(&url) {
+            problems.push(e);
+        }
+    }
+
+    problems
+}
+
+fn command_exists(command: &str) -> bool {
+    if command.contains(std::path::MAIN_SEPARATOR) {
+        Path::new(command).is_file()
+    } else {
+        crate::executor_discovery::which_on_path(command).is_some()
+    }
+}
+
+/// Open (and drop) a TCP connection to the URL's host.
+fn probe_url(url: &str) -> std::result::Result<(), String> {
+    let parsed =
+        url::Url::parse(url).map_err(|e| format!("invalid endpoint URL '{}': {}", url, e))?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| format!("endpoint URL '{}' has no host", url))?;
+    let port = parsed.port_or_known_default().unwrap_or(443);
+    let addr = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("cannot resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("cannot resolve {}", host))?;
+    TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)
+        .map(drop)
+        .map_err(|e| format!("cannot reach {}:{}: {}", host, port, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn custom_executor(dir: &Path, name: &str, command: &str) {
+        let executors = dir.join("executors");
+        fs::create_dir_all(&executors).unwrap();
+        fs::write(
+            executors.join(format!("{}.toml", name)),
+            format!("[executor]\ntype = \"custom\"\ncommand = \"{}\"\n", command),
+        )
+        .unwrap();
+    }
+
+    fn endpoint(provider: &str, url: &str) -> EndpointConfig {
+        EndpointConfig {
+            name: "ep".to_string(),
+            provider: provider.to_string(),
+            url: Some(url.to_string()),
+            model: None,
+            api_key: None,
+            api_key_file: None,
+            api_key_env: None,
+            api_key_ref: None,
+            is_default: false,
+            context_window: None,
+        }
+    }
+
+    #[test]
+    fn test_preflight_missing_binary() {
+        let tmp = TempDir::new().unwrap();
+        custom_executor(tmp.path(), "ghost", "wg-no-such-binary-xyz");
+        let problems = preflight(tmp.path(), "ghost", None);
+        assert_eq!(problems, vec!["`wg-no-such-binary-xyz` not found on PATH"]);
+
+        custom_executor(tmp.path(), "sh", "sh");
+        assert!(preflight(tmp.path(), "sh", None).is_empty());
+    }
+
+    #[test]
+    fn test_preflight_endpoint_key_and_reachability() {
+        let tmp = TempDir::new().unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+
+        assert!(preflight(tmp.path(), "native", Some(&endpoint("local", &url))).is_empty());
+
+        let mut keyed = endpoint("openrouter", &url);
+        keyed.api_key_file = Some(tmp.path().join("missing-key").display().to_string());
+        let problems = preflight(tmp.path(), "native", Some(&keyed));
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("API key for endpoint 'ep'"));
+
+        drop(listener);
+        let problems = preflight(tmp.path(), "native", Some(&endpoint("local", &url)));
+        assert!(problems[0].starts_with("cannot reach 127.0.0.1"));
+    }
+
+    #[test]
+    fn test_preflight_webhook_needs_reachable_url() {
+        let tmp = TempDir::new().unwrap();
+        let problems = preflight(tmp.path(), "webhook", None);
+        assert_eq!(problems, vec!["[webhook_executor] url is not set"]);
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        fs::write(
+            tmp.path().join("config.toml"),
+            format!(
+                "[webhook_executor]\nurl = \"http://{}/tasks\"\n",
+                listener.local_addr().unwrap()
+            ),
+        )
+        .unwrap();
+        assert!(preflight(tmp.path(), "webhook", None).is_empty());
+    }
+
+    #[test]
+    fn test_check_caches_for_ttl() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        custom_executor(dir, "flaky", "sh");
+
+        let mut health = ExecutorHealth::load(dir);
+        assert!(health.check(dir, "flaky", None, 300).healthy);
+        health.save(dir).unwrap();
+
+        // Binary disappears; the cached result holds until the TTL expires.
+        custom_executor(dir, "flaky", "wg-no-such-binary-xyz");
+        let mut health = ExecutorHealth::load(dir);
+        assert!(health.check(dir, "flaky", None, 300).healthy);
+        assert!(!health.check(dir, "flaky", None, 0).healthy);
+        assert_eq!(health.unhealthy().count(), 1);
+    }
+}
@@ -0,0 +1,292 @@
+//! Structured events about graph and agent changes.
+//!
+//! [`EventTracker`] compares successive states of the graph and the agent
+//! registry and reports what changed as [`GraphEvent`]s; [`EventHub`] fans
+//! them out to subscribers. The service daemon runs one publisher thread
+//! ([`spawn_publisher`]) behind its HTTP API, which streams the events as
+//! server-sent events on `GET /api/events` so dashboards and bots don't have
+//! to poll `graph.jsonl`.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use super::registry::AgentRegistry;
+use crate::graph::{Status, WorkGraph};
+use crate::parser::load_graph;
+
+/// How often the publisher checks the graph and registry for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GraphEvent {
+    TaskCreated {
+        task_id: String,
+        title: String,
+        status: Status,
+    },
+    StatusChanged {
+        task_id: String,
+        title: String,
+        from: Status,
+        to: Status,
+    },
+    AgentSpawned {
+        agent_id: String,
+        task_id: String,
+        executor: String,
+    },
+    /// A live agent went longer than the heartbeat timeout without one.
+    /// Reported once per silence.
+    HeartbeatMissed {
+        agent_id: String,
+        task_id: String,
+        last_heartbeat: String,
+    },
+}
+
+impl GraphEvent {
+    /// Event name, as used for the SSE `event:` field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GraphEvent::TaskCreated { .. } => "task_created",
+            GraphEvent::StatusChanged { .. } => "status_changed",
+            GraphEvent::AgentSpawned { .. } => "agent_spawned",
+            GraphEvent::HeartbeatMissed { .. } => "heartbeat_missed",
+        }
+    }
+}
+
+/// Turns successive graph and registry states into events. The first state
+/// of each only sets the baseline.
+#[derive(Debug, Default)]
+pub struct EventTracker {
+    statuses: Option<HashMap<String, Status>>,
+    agents: Option<HashSet<String>>,
+    silent: HashSet<String>,
+}
+
+impl EventTracker {
+    /// Tasks created and status changes since the last graph seen.
+    pub fn update_graph(&mut self, graph: &WorkGraph) -> Vec<GraphEvent> {
+        let statuses: HashMap<String, Status> =
+            graph.tasks().map(|t| (t.id.clone(), t.status)).collect();
+        let mut events = Vec::new();
+        if let Some(ref previous) = self.statuses {
+            for task in graph.tasks() {
+                match previous.get(&task.id) {
+                    None => events.push(GraphEvent::TaskCreated {
+                        task_id: task.id.clone(),
+                        title: task.title.clone(),
+                        status: task.status,
+                    }),
+                    Some(&from) if from != task.status => events.push(GraphEvent::StatusChanged {
+                        task_id: task.id.clone(),
+                        title: task.title.clone(),
+                        from,
+                        to: task.status,
+                    }),
+                    Some(_) => {}
+                }
+            }
+        }
+        self.statuses = Some(statuses);
+        events
+    }
+
+    /// Agents spawned and heartbeats missed since the last registry seen.
+    pub fn update_agents(
+        &mut self,
+        registry: &AgentRegistry,
+        heartbeat_timeout_secs: i64,
+    ) -> Vec<GraphEvent> {
+        let mut events = Vec::new();
+        if let Some(ref previous) = self.agents {
+            let mut spawned: Vec<_> = registry
+                .all()
+                .filter(|a| !previous.contains(&a.id))
+                .collect();
+            spawned.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+            events.extend(spawned.into_iter().map(|a| GraphEvent::AgentSpawned {
+                agent_id: a.id.clone(),
+                task_id: a.task_id.clone(),
+                executor: a.executor.clone(),
+            }));
+        }
+        let baseline = self.agents.is_none();
+
+        let silent: HashSet<String> = registry
+            .find_dead_agents(heartbeat_timeout_secs)
+            .into_iter()
+            .map(|a| a.id.clone())
+            .collect();
+        if !baseline {
+            for agent in registry.all() {
+                if silent.contains(&agent.id) && !self.silent.contains(&agent.id) {
+                    events.push(GraphEvent::HeartbeatMissed {
+                        agent_id: agent.id.clone(),
+                        task_id: agent.task_id.clone(),
+                        last_heartbeat: agent.last_heartbeat.clone(),
+                    });
+                }
+            }
+        }
+        self.silent = silent;
+        self.agents = Some(registry.all().map(|a| a.id.clone()).collect());
+        events
+    }
+}
+
+/// Fans events out to any number of subscribers.
+#[derive(Debug, Default)]
+pub struct EventHub {
+    subscribers: Mutex<Vec<Sender<GraphEvent>>>,
+}
+
+impl EventHub {
+    pub fn subscribe(&self) -> Receiver<GraphEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(tx);
+        rx
+    }
+
+    pub fn has_subscribers(&self) -> bool {
+        !self
+            .subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    }
+
+    /// Send `event` to every subscriber, dropping those that went away.
+    pub fn publish(&self, event: &GraphEvent) {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
+
+/// Publish events for the workgraph at `dir` to `hub` on a background
+/// thread. Does no work while nobody is subscribed; the baseline is taken
+/// again when the next subscriber arrives.
+pub fn spawn_publisher(dir: PathBuf, hub: Arc<EventHub>, heartbeat_timeout_secs: i64) {
+    std::thread::spawn(move || {
+        let graph_file = dir.join("graph.jsonl");
+        let mut tracker = EventTracker::default();
+        let mut graph_mtime: Option<SystemTime> = None;
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            if !hub.has_subscribers() {
+                tracker = EventTracker::default();
+                graph_mtime = None;
+                continue;
+            }
+
+            let mut events = Vec::new();
+            let mtime = std::fs::metadata(&graph_file)
+                .and_then(|m| m.modified())
+                .ok();
+            if (graph_mtime.is_none() || mtime != graph_mtime)
+                && let Ok(graph) = load_graph(&graph_file)
+            {
+                events.extend(tracker.update_graph(&graph));
+                graph_mtime = mtime;
+            }
+            if let Ok(registry) = AgentRegistry::load(&dir) {
+                events.extend(tracker.update_agents(&registry, heartbeat_timeout_secs));
+            }
+            for event in &events {
+                hub.publish(event);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use crate::test_helpers::make_task_with_status;
+
+    #[test]
+    fn test_tracker_reports_created_and_status_changes() {
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(make_task_with_status("a", "A", Status::Open)));
+        let mut tracker = EventTracker::default();
+        assert!(tracker.update_graph(&graph).is_empty());
+
+        graph.get_task_mut("a").unwrap().status = Status::InProgress;
+        graph.add_node(Node::Task(make_task_with_status("b", "B", Status::Open)));
+        let mut events = tracker.update_graph(&graph);
+        events.sort_by_key(|e| e.name());
+        assert_eq!(
+            events,
+            vec![
+                GraphEvent::StatusChanged {
+                    task_id: "a".to_string(),
+                    title: "A".to_string(),
+                    from: Status::Open,
+                    to: Status::InProgress,
+                },
+                GraphEvent::TaskCreated {
+                    task_id: "b".to_string(),
+                    title: "B".to_string(),
+                    status: Status::Open,
+                },
+            ]
+        );
+        assert!(tracker.update_graph(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_tracker_reports_spawned_agents_and_missed_heartbeats() {
+        let mut registry = AgentRegistry::new();
+        let mut tracker = EventTracker::default();
+        assert!(tracker.update_agents(&registry, 300).is_empty());
+
+        let id = registry.register_agent(1234, "t1", "shell", "out.log");
+        assert_eq!(
+            tracker.update_agents(&registry, 300),
+            vec![GraphEvent::AgentSpawned {
+                agent_id: id.clone(),
+                task_id: "t1".to_string(),
+                executor: "shell".to_string(),
+            }]
+        );
+
+        let stale = (chrono::Utc::now() - chrono::Duration::minutes(10)).to_rfc3339();
+        registry.get_agent_mut(&id).unwrap().last_heartbeat = stale;
+        let events = tracker.update_agents(&registry, 300);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].name(), "heartbeat_missed");
+        // Reported once, not on every poll
+        assert!(tracker.update_agents(&registry, 300).is_empty());
+    }
+
+    #[test]
+    fn test_hub_drops_departed_subscribers() {
+        let hub = EventHub::default();
+        let rx = hub.subscribe();
+        let gone = hub.subscribe();
+        drop(gone);
+        let event = GraphEvent::AgentSpawned {
+            agent_id: "agent-1".to_string(),
+            task_id: "t".to_string(),
+            executor: "shell".to_string(),
+        };
+        hub.publish(&event);
+        assert_eq!(rx.try_recv().unwrap(), event);
+        assert_eq!(hub.subscribers.lock().unwrap().len(), 1);
+        drop(rx);
+        hub.publish(&event);
+        assert!(!hub.has_subscribers());
+    }
+}
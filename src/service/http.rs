@@ -0,0 +1,316 @@
+//! Minimal HTTP/1.1 plumbing shared by the service's listeners
+//! ([`super::webhook_listener`] and the REST API of `wg service start --http`).
+//!
+//! One request per connection, JSON responses, no TLS, no chunked bodies.
+//! Requests are bounded in size and time and listeners cap how many
+//! connections they serve at once, since all of this runs before any auth.
+
+use anyhow::Result;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Largest request body accepted.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// Largest request line plus headers accepted.
+const MAX_HEAD_BYTES: usize = 8 * 1024;
+/// Most header lines accepted.
+const MAX_HEADERS: usize = 100;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// Time allowed to receive a whole request, however slowly it trickles in.
+const REQUEST_DEADLINE: Duration = Duration::from_secs(15);
+
+/// Most connections a listener serves at once.
+pub const MAX_CONNECTIONS: usize = 32;
+
+/// Counts a listener's open connections; see [`Self::try_acquire`].
+#[derive(Clone, Default)]
+pub struct ConnectionLimit {
+    active: Arc<AtomicUsize>,
+}
+
+/// A held connection slot, released on drop.
+pub struct ConnectionSlot {
+    active: Arc<AtomicUsize>,
+}
+
+impl ConnectionLimit {
+    /// Take a slot, or `None` when [`MAX_CONNECTIONS`] are already open.
+    pub fn try_acquire(&self) -> Option<ConnectionSlot> {
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < MAX_CONNECTIONS).then_some(n + 1)
+            })
+            .ok()?;
+        Some(ConnectionSlot {
+            active: self.active.clone(),
+        })
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Turn away a connection when the listener is at capacity.
+pub fn reject_busy(mut stream: TcpStream) {
+    let _ = stream.set_write_timeout(Some(READ_TIMEOUT));
+    let _ = write_response(
+        &mut stream,
+        503,
+        &serde_json::json!({ "error": "too many connections" }),
+    );
+}
+
+/// A parsed request.
+pub struct Request {
+    pub method: String,
+    /// Request target, query string included.
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Path without the query string.
+    pub fn route(&self) -> &str {
+        self.path.split('?').next().unwrap_or_default()
+    }
+
+    /// Value of a query string parameter, percent-decoded.
+    pub fn query(&self, name: &str) -> Option<String> {
+        let (_, query) = self.path.split_once('?')?;
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(k, _)| percent_decode(k) == name)
+            .map(|(_, v)| percent_decode(v))
+    }
+}
+
+/// Decode `%XX` escapes and `+` (a space) in a query string component.
+/// Malformed escapes are kept as they are; invalid UTF-8 is replaced.
+fn percent_decode(s: &str) -> String {
+    let hex = |c: u8| (c as char).to_digit(16).unwrap_or(0) as u8;
+    let mut out = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        match b {
+            b'%' if tail.len() >= 2 && tail[..2].iter().all(u8::is_ascii_hexdigit) => {
+                out.push((hex(tail[0]) << 4) | hex(tail[1]));
+                rest = &tail[2..];
+                continue;
+            }
+            b'+' => out.push(b' '),
+            _ => out.push(b),
+        }
+        rest = tail;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Point the next read at whichever comes first: the per-read timeout or the
+/// whole-request deadline.
+fn arm(stream: &TcpStream, deadline: Instant) -> Result<()> {
+    let left = deadline.saturating_duration_since(Instant::now());
+    if left.is_zero() {
+        anyhow::bail!(
+            "request not received within {}s",
+            REQUEST_DEADLINE.as_secs()
+        );
+    }
+    stream.set_read_timeout(Some(left.min(READ_TIMEOUT)))?;
+    Ok(())
+}
+
+/// Read one line of the request head, charging it against `budget`.
+fn read_head_line(
+    reader: &mut BufReader<&mut TcpStream>,
+    line: &mut String,
+    budget: &mut usize,
+    deadline: Instant,
+) -> Result<usize> {
+    line.clear();
+    arm(reader.get_ref(), deadline)?;
+    let n = reader.by_ref().take(*budget as u64).read_line(line)?;
+    if n == *budget && !line.ends_with('\n') {
+        anyhow::bail!("request head exceeds {} bytes", MAX_HEAD_BYTES);
+    }
+    *budget -= n;
+    Ok(n)
+}
+
+/// Read one request from `stream`.
+pub fn read_request(stream: &mut TcpStream) -> Result<Request> {
+    let deadline = Instant::now() + REQUEST_DEADLINE;
+    let mut budget = MAX_HEAD_BYTES;
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    read_head_line(&mut reader, &mut line, &mut budget, deadline)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        anyhow::bail!("malformed request line");
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut headers = Vec::new();
+    loop {
+        if read_head_line(&mut reader, &mut line, &mut budget, deadline)? == 0 {
+            break;
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            anyhow::bail!("more than {} headers", MAX_HEADERS);
+        }
+        if let Some((k, v)) = header.split_once(':') {
+            headers.push((k.trim().to_string(), v.trim().to_string()));
+        }
+    }
+
+    let len = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    if len > MAX_BODY_BYTES {
+        anyhow::bail!("body exceeds {} bytes", MAX_BODY_BYTES);
+    }
+    let mut body = vec![0; len];
+    let mut filled = 0;
+    while filled < len {
+        arm(reader.get_ref(), deadline)?;
+        match reader.read(&mut body[filled..])? {
+            0 => anyhow::bail!("connection closed mid-body"),
+            n => filled += n,
+        }
+    }
+
+    Ok(Request {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+/// Write a JSON response and close the exchange (`Connection: close`).
+pub fn write_response(stream: &mut TcpStream, status: u16, body: &serde_json::Value) -> Result<()> {
+    let reason = match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(path: &str) -> Request {
+        Request {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_query_values_are_percent_decoded() {
+        let req =
+            request("/api/tasks?tag=a%20b&status=in%2Dprogress&q=x+y&bad=100%&t%61g2=%E2%9C%93");
+        assert_eq!(req.route(), "/api/tasks");
+        assert_eq!(req.query("tag").as_deref(), Some("a b"));
+        assert_eq!(req.query("status").as_deref(), Some("in-progress"));
+        assert_eq!(req.query("q").as_deref(), Some("x y"));
+        assert_eq!(req.query("bad").as_deref(), Some("100%"));
+        assert_eq!(req.query("tag2").as_deref(), Some("\u{2713}"));
+        assert_eq!(req.query("missing"), None);
+    }
+
+    /// Send `raw` to a local listener and parse what arrives.
+    fn read_raw(raw: Vec<u8>) -> Result<Request> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let _ = stream.write_all(&raw);
+            stream
+        });
+        let (mut stream, _) = listener.accept().unwrap();
+        let result = read_request(&mut stream);
+        drop(client.join());
+        result
+    }
+
+    #[test]
+    fn test_read_request_parses_headers_and_body() {
+        let req =
+            read_raw(b"POST /hooks/x HTTP/1.1\r\nContent-Length: 2\r\n\r\nok".to_vec()).unwrap();
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.header("content-length"), Some("2"));
+        assert_eq!(req.body, b"ok");
+    }
+
+    #[test]
+    fn test_read_request_bounds_the_head() {
+        let mut raw = b"GET / HTTP/1.1\r\nX-Big: ".to_vec();
+        raw.extend(std::iter::repeat_n(b'a', MAX_HEAD_BYTES));
+        raw.extend(b"\r\n\r\n");
+        let err = read_raw(raw).err().unwrap();
+        assert!(err.to_string().contains("exceeds"), "{}", err);
+
+        let mut raw = b"GET / HTTP/1.1\r\n".to_vec();
+        for i in 0..=MAX_HEADERS {
+            raw.extend(format!("X-{}: 1\r\n", i).bytes());
+        }
+        raw.extend(b"\r\n");
+        let err = read_raw(raw).err().unwrap();
+        assert!(err.to_string().contains("headers"), "{}", err);
+    }
+
+    #[test]
+    fn test_connection_limit() {
+        let limit = ConnectionLimit::default();
+        let slots: Vec<_> = (0..MAX_CONNECTIONS)
+            .map(|_| limit.try_acquire().unwrap())
+            .collect();
+        assert!(limit.try_acquire().is_none());
+        drop(slots);
+        assert!(limit.try_acquire().is_some());
+    }
+}
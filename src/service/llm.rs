@@ -17,6 +17,7 @@ use crate::config::{
 };
 use crate::dispatch::{ExecutorKind, handler_for_model};
 use crate::graph::TokenUsage;
+use crate::service::response_cache::ResponseCache;
 
 /// Result of a lightweight LLM call, including both the text response and token usage.
 #[derive(Debug, Clone)]
@@ -149,12 +150,62 @@ pub fn resolve_agency_dispatch(config: &Config, role: DispatchRole) -> AgencyDis
 ///    back to the claude CLI.
 /// 3. Falls back to shelling out to `claude` CLI.
 ///
-/// Returns both the text response and token usage when available.
+/// Returns both the text response and token usage when available. With
+/// `[response_cache]` enabled, an identical earlier call within the TTL is
+/// answered from the cache (with no token usage).
 pub fn run_lightweight_llm_call(
     config: &Config,
     role: DispatchRole,
     prompt: &str,
     timeout_secs: u64,
+) -> Result<LlmCallResult> {
+    let cache = (config.response_cache.enabled && !ResponseCache::bypassed())
+        .then(ResponseCache::default_root)
+        .and_then(|root| root.ok())
+        .map(|root| ResponseCache::new(root, config.response_cache.ttl_secs));
+    let Some(cache) = cache else {
+        return dispatch_lightweight_llm_call(config, role, prompt, timeout_secs);
+    };
+
+    let fingerprint = executor_fingerprint(config, role);
+    if let Some(text) = cache.get(&fingerprint, prompt) {
+        return Ok(LlmCallResult {
+            text,
+            token_usage: None,
+        });
+    }
+    let result = dispatch_lightweight_llm_call(config, role, prompt, timeout_secs)?;
+    if let Err(e) = cache.put(&fingerprint, prompt, &result.text) {
+        eprintln!("[lightweight-llm] response cache write failed: {e:#}");
+    }
+    Ok(result)
+}
+
+/// Identifies which executor would answer a call for `role`: handler,
+/// provider, model and endpoint as resolved from config.
+fn executor_fingerprint(config: &Config, role: DispatchRole) -> String {
+    if is_agency_oneshot_role(role) {
+        let dispatch = resolve_agency_dispatch(config, role);
+        return format!(
+            "{:?}|{}|{}",
+            dispatch.handler, dispatch.raw_spec, LIGHTWEIGHT_MAX_TOKENS
+        );
+    }
+    let resolved = config.resolve_model_for_role(role);
+    format!(
+        "{}|{}|{}|{}",
+        resolved.provider.as_deref().unwrap_or(""),
+        resolved.model,
+        resolved.endpoint.as_deref().unwrap_or(""),
+        LIGHTWEIGHT_MAX_TOKENS
+    )
+}
+
+fn dispatch_lightweight_llm_call(
+    config: &Config,
+    role: DispatchRole,
+    prompt: &str,
+    timeout_secs: u64,
 ) -> Result<LlmCallResult> {
     if is_agency_oneshot_role(role) {
         let dispatch = resolve_agency_dispatch(config, role);
@@ -175,9 +226,9 @@ pub fn run_lightweight_llm_call(
                 // local, oai-compat, etc. are real HTTP providers that the
                 // cascade-based dispatch handles correctly.
             }
-            ExecutorKind::Shell => {
-                // Shell doesn't make sense for a one-shot LLM call; degrade to
-                // the safe default (claude CLI on haiku).
+            ExecutorKind::Shell | ExecutorKind::Webhook => {
+                // Shell and webhook don't make sense for a one-shot LLM call;
+                // degrade to the safe default (claude CLI on haiku).
                 return call_claude_cli(CLAUDE_HAIKU_MODEL_ID, prompt, timeout_secs);
             }
         }
@@ -6,13 +6,21 @@
 //! - Executor configuration for spawning agents
 //! - Agent registry for tracking running agents
 
+pub mod chaos;
 pub mod chat_compactor;
 pub mod dispatch_boot;
+pub mod dispatch_queue;
 pub mod executor;
+pub mod executor_health;
+pub mod graph_events;
 pub mod graph_watcher;
+pub mod http;
 pub mod llm;
+pub mod output_stream;
 pub mod provider_health;
 pub mod registry;
+pub mod response_cache;
+pub mod webhook_listener;
 
 pub use dispatch_boot::{
     ChatSupervisorBootSpec, enumerate_chat_supervisors_for_boot,
@@ -21,6 +29,7 @@ pub use dispatch_boot::{
 pub use executor::{
     ExecutorConfig, ExecutorRegistry, ExecutorSettings, PromptTemplate, TemplateVars,
 };
+pub use executor_health::{ExecutorCheck, ExecutorHealth};
 pub use provider_health::{
     ProviderErrorKind, ProviderHealth, ProviderHealthStatus, classify_error, extract_provider_id,
 };
@@ -208,6 +217,25 @@ pub fn kill_process_force(_pid: u32) -> anyhow::Result<()> {
     anyhow::bail!("Process killing is only supported on Unix systems")
 }
 
+/// Suspend (`stop = true`, SIGSTOP) or resume (SIGCONT) `pid` and all its
+/// descendants. Stopping only the root would leave the real worker running
+/// under its shell or `timeout` wrapper.
+#[cfg(unix)]
+pub fn suspend_process_tree(pid: u32, stop: bool) -> anyhow::Result<()> {
+    let signal = if stop { libc::SIGSTOP } else { libc::SIGCONT };
+    let descendants = collect_process_descendants(pid);
+    signal_pid(pid, signal)?;
+    for child in &descendants {
+        let _ = signal_pid(*child, signal);
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn suspend_process_tree(_pid: u32, _stop: bool) -> anyhow::Result<()> {
+    anyhow::bail!("Suspending processes is only supported on Unix systems")
+}
+
 /// SIGKILL every descendant of `root_pid` *without* touching `root_pid`
 /// itself. Use this when the caller IS the root (e.g. a native-executor
 /// session hard-cancelling its own spawned subprocess tree — we want
@@ -0,0 +1,195 @@
+//! Live agent output.
+//!
+//! The spawn wrapper appends an agent's stdout and stderr to its
+//! `output.log` as they are produced. [`follow`] tails that file for as long
+//! as the agent runs, handing each complete line to a callback. The service
+//! daemon uses it to stream output to `wg log --follow` over IPC (the
+//! `follow_output` request); the CLI falls back to calling it directly when
+//! no service is running.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::registry::{AgentEntry, AgentRegistry};
+
+/// How often the output file and agent liveness are polled.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Incremental reader over a growing output file.
+///
+/// Only complete lines are returned; a trailing partial line is held back
+/// until its newline arrives (or [`OutputTail::flush`] is called once the
+/// writer is gone). A file that shrinks (truncated or replaced) is read
+/// again from the start.
+#[derive(Debug)]
+pub struct OutputTail {
+    path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+}
+
+impl OutputTail {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            offset: 0,
+            partial: Vec::new(),
+        }
+    }
+
+    /// Complete lines appended since the last call. A file that does not
+    /// exist yet reads as empty.
+    pub fn read_lines(&mut self) -> Result<Vec<String>> {
+        let mut file = match File::open(&self.path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to open {}", self.path.display()));
+            }
+        };
+        let len = file.metadata()?.len();
+        if len < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        self.offset += buf.len() as u64;
+        self.partial.extend_from_slice(&buf);
+
+        let Some(end) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return Ok(Vec::new());
+        };
+        let rest = self.partial.split_off(end + 1);
+        let complete = std::mem::replace(&mut self.partial, rest);
+        Ok(String::from_utf8_lossy(&complete)
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+
+    /// The held-back partial line, if any.
+    pub fn flush(&mut self) -> Option<String> {
+        if self.partial.is_empty() {
+            return None;
+        }
+        let line = String::from_utf8_lossy(&self.partial).into_owned();
+        self.partial.clear();
+        Some(line)
+    }
+}
+
+/// Absolute path of an agent's output file. Registry paths may be relative
+/// to the project root.
+pub fn output_path(dir: &Path, agent: &AgentEntry) -> PathBuf {
+    let path = Path::new(&agent.output_file);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        dir.parent().unwrap_or(dir).join(path)
+    }
+}
+
+/// The agent currently running `task_id`, if any.
+pub fn running_agent(dir: &Path, task_id: &str) -> Result<Option<AgentEntry>> {
+    let registry = AgentRegistry::load(dir)?;
+    Ok(registry
+        .agents_for_task(task_id)
+        .into_iter()
+        .find(|a| a.is_alive() && super::is_process_alive(a.pid))
+        .cloned())
+}
+
+/// Tail the output of `agent` until it exits, calling `on_line` for each
+/// line (existing output first). Returns early with the callback's error,
+/// e.g. when the reader went away.
+pub fn follow(
+    dir: &Path,
+    agent: &AgentEntry,
+    mut on_line: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let mut tail = OutputTail::new(output_path(dir, agent));
+    loop {
+        // Check liveness before reading so output written just before exit
+        // is still drained on the final pass.
+        let running = super::is_process_alive(agent.pid)
+            && AgentRegistry::load(dir)
+                .ok()
+                .and_then(|r| r.get_agent(&agent.id).map(AgentEntry::is_alive))
+                .unwrap_or(false);
+        for line in tail.read_lines()? {
+            on_line(&line)?;
+        }
+        if !running {
+            if let Some(line) = tail.flush() {
+                on_line(&line)?;
+            }
+            return Ok(());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_output_tail_returns_complete_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("output.log");
+        let mut tail = OutputTail::new(&path);
+        assert!(tail.read_lines().unwrap().is_empty());
+
+        let mut file = File::create(&path).unwrap();
+        write!(file, "first\nsec").unwrap();
+        assert_eq!(tail.read_lines().unwrap(), vec!["first"]);
+
+        write!(file, "ond\nthird").unwrap();
+        assert_eq!(tail.read_lines().unwrap(), vec!["second"]);
+        assert!(tail.read_lines().unwrap().is_empty());
+        assert_eq!(tail.flush().as_deref(), Some("third"));
+        assert_eq!(tail.flush(), None);
+    }
+
+    #[test]
+    fn test_output_tail_restarts_after_truncation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("output.log");
+        std::fs::write(&path, "old line one\nold line two\n").unwrap();
+        let mut tail = OutputTail::new(&path);
+        assert_eq!(tail.read_lines().unwrap().len(), 2);
+
+        std::fs::write(&path, "new\n").unwrap();
+        assert_eq!(tail.read_lines().unwrap(), vec!["new"]);
+    }
+
+    #[test]
+    fn test_follow_drains_output_of_finished_agent() {
+        let dir = tempfile::tempdir().unwrap();
+        let wg_dir = dir.path().join(".wg");
+        std::fs::create_dir_all(&wg_dir).unwrap();
+        let output = dir.path().join("output.log");
+        std::fs::write(&output, "hello\nworld").unwrap();
+
+        let mut registry = AgentRegistry::new();
+        // PID that is not running: follow reads what is there and stops
+        let id = registry.register_agent(999_999_999, "t1", "shell", output.to_str().unwrap());
+        registry.save(&wg_dir).unwrap();
+        let agent = registry.get_agent(&id).unwrap().clone();
+
+        let mut lines = Vec::new();
+        follow(&wg_dir, &agent, |l| {
+            lines.push(l.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(lines, vec!["hello", "world"]);
+        assert!(running_agent(&wg_dir, "t1").unwrap().is_none());
+    }
+}
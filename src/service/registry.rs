@@ -89,6 +89,14 @@ pub struct AgentEntry {
     /// agent — see `reaper-edge-case`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub worktree_path: Option<String>,
+    /// Urgent task this agent was frozen for (`wg claim --preempt`). The
+    /// dispatcher thaws the agent once that task finishes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preempted_by: Option<String>,
+    /// Whether the preemption paused this agent's task (it wasn't paused
+    /// already), so thawing the agent unpauses it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paused_task: bool,
 }
 
 impl AgentEntry {
@@ -363,6 +371,8 @@ impl AgentRegistry {
             model: model.map(std::string::ToString::to_string),
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
 
         self.agents.insert(agent_id.clone(), entry);
@@ -650,6 +660,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
         assert!(!entry.is_live(300), "Done status should not be live");
 
@@ -666,6 +678,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
         assert!(
             entry.is_live(300),
@@ -687,6 +701,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
         assert!(
             !entry.is_live(300),
@@ -707,6 +723,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
         assert!(
             !entry.is_live(300),
@@ -728,6 +746,8 @@ mod tests {
             model: None,
             completed_at: None,
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
         assert!(
             !entry.is_live(0),
@@ -0,0 +1,134 @@
+//! On-disk cache for one-shot LLM responses
+//!
+//! Entries live in `~/.wg/cache/responses/<sha256>.json`, keyed by a hash of
+//! the executor fingerprint (resolved handler, provider, model, endpoint)
+//! and the prompt. Only the response text is stored; a hit reports no token
+//! usage, so cached calls never show up as spend.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Set to any non-empty value to skip the cache for one invocation
+pub const BYPASS_ENV: &str = "WG_NO_RESPONSE_CACHE";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResponse {
+    created_at: String,
+    fingerprint: String,
+    text: String,
+}
+
+pub struct ResponseCache {
+    root: PathBuf,
+    ttl_secs: u64,
+}
+
+impl ResponseCache {
+    pub fn new(root: impl Into<PathBuf>, ttl_secs: u64) -> Self {
+        Self {
+            root: root.into(),
+            ttl_secs,
+        }
+    }
+
+    /// Default location under the global wg directory.
+    pub fn default_root() -> Result<PathBuf> {
+        Ok(crate::config::Config::global_dir()?
+            .join("cache")
+            .join("responses"))
+    }
+
+    /// True when [`BYPASS_ENV`] is set.
+    pub fn bypassed() -> bool {
+        std::env::var(BYPASS_ENV).is_ok_and(|v| !v.is_empty())
+    }
+
+    fn entry_path(&self, fingerprint: &str, prompt: &str) -> PathBuf {
+        self.root
+            .join(format!("{}.json", cache_key(fingerprint, prompt)))
+    }
+
+    /// Cached text for this call, if present and younger than the TTL.
+    pub fn get(&self, fingerprint: &str, prompt: &str) -> Option<String> {
+        let content = fs::read_to_string(self.entry_path(fingerprint, prompt)).ok()?;
+        let entry: CachedResponse = serde_json::from_str(&content).ok()?;
+        let created = DateTime::parse_from_rfc3339(&entry.created_at).ok()?;
+        let age = (Utc::now() - created.with_timezone(&Utc)).num_seconds();
+        (age >= 0 && (age as u64) < self.ttl_secs).then_some(entry.text)
+    }
+
+    pub fn put(&self, fingerprint: &str, prompt: &str, text: &str) -> Result<()> {
+        fs::create_dir_all(&self.root)
+            .with_context(|| format!("Failed to create {:?}", self.root))?;
+        let entry = CachedResponse {
+            created_at: Utc::now().to_rfc3339(),
+            fingerprint: fingerprint.to_string(),
+            text: text.to_string(),
+        };
+        let path = self.entry_path(fingerprint, prompt);
+        fs::write(&path, serde_json::to_string(&entry)?)
+            .with_context(|| format!("Failed to write {:?}", path))
+    }
+
+    /// Delete every entry; returns how many were removed.
+    pub fn clear(&self) -> Result<usize> {
+        clear_dir(&self.root)
+    }
+}
+
+fn clear_dir(root: &Path) -> Result<usize> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(0),
+    };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            fs::remove_file(&path).with_context(|| format!("Failed to remove {:?}", path))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Hex SHA-256 over the fingerprint and prompt.
+pub fn cache_key(fingerprint: &str, prompt: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(fingerprint.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(prompt.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_put_respects_fingerprint_and_ttl() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(tmp.path(), 3600);
+        assert_eq!(cache.get("claude:haiku", "score this"), None);
+
+        cache
+            .put("claude:haiku", "score this", "{\"score\": 0.8}")
+            .unwrap();
+        assert_eq!(
+            cache.get("claude:haiku", "score this").as_deref(),
+            Some("{\"score\": 0.8}")
+        );
+        assert_eq!(cache.get("claude:sonnet", "score this"), None);
+        assert_eq!(cache.get("claude:haiku", "score that"), None);
+
+        let expired = ResponseCache::new(tmp.path(), 0);
+        assert_eq!(expired.get("claude:haiku", "score this"), None);
+
+        assert_eq!(cache.clear().unwrap(), 1);
+        assert_eq!(cache.get("claude:haiku", "score this"), None);
+    }
+}
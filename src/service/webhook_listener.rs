@@ -0,0 +1,315 @@
+//! HTTP listener that fires external event gates.
+//!
+//! Enabled by `coordinator.webhook_listen` (e.g. `"127.0.0.1:8787"`). Each
+//! `POST /hooks/<name>` fires `webhook:<name>` (see [`crate::event_gate`]);
+//! the graph watcher then wakes the dispatcher for any task it released.
+//! `POST /callbacks/<task-id>` takes a status report for a task running on
+//! the webhook executor (see [`crate::executor::webhook`]).
+//! With `coordinator.webhook_secret` set, requests must carry a valid
+//! `X-Webhook-Signature: sha256=<hmac>` of the body. Callbacks can complete
+//! tasks, so they are refused outright while no secret is configured.
+//!
+//! Deliberately minimal: one request per connection, no TLS, no chunked
+//! bodies. Each connection is served on its own thread, so a slow client
+//! cannot hold up other deliveries. Put a reverse proxy in front when
+//! exposing it beyond localhost.
+
+use anyhow::{Context, Result};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use super::http::{ConnectionLimit, Request, read_request, reject_busy, write_response};
+use crate::event_gate;
+use crate::executor::webhook;
+use crate::graph::Status;
+use crate::parser::load_graph;
+
+/// Bind `addr` and accept connections on a background thread, serving each
+/// on its own thread. `log` receives one line per request handled.
+pub fn spawn(
+    workgraph_dir: PathBuf,
+    addr: &str,
+    secret: Option<String>,
+    log: impl Fn(&str) + Send + Sync + 'static,
+) -> Result<JoinHandle<()>> {
+    let listener = TcpListener::bind(addr)
+        .with_context(|| format!("Failed to bind webhook listener on {}", addr))?;
+    let workgraph_dir = Arc::new(workgraph_dir);
+    let secret = Arc::new(secret);
+    let log = Arc::new(log);
+    let limit = ConnectionLimit::default();
+    let handle = std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let Some(slot) = limit.try_acquire() else {
+                reject_busy(stream);
+                continue;
+            };
+            let (workgraph_dir, secret, log) = (workgraph_dir.clone(), secret.clone(), log.clone());
+            std::thread::spawn(move || {
+                let _slot = slot;
+                serve(stream, &workgraph_dir, secret.as_deref(), &*log);
+            });
+        }
+    });
+    Ok(handle)
+}
+
+fn serve(mut stream: TcpStream, workgraph_dir: &Path, secret: Option<&str>, log: &dyn Fn(&str)) {
+    let (status, body) = match read_request(&mut stream) {
+        Ok(req) => {
+            let (status, body) = route(&req, workgraph_dir, secret);
+            log(&format!(
+                "Webhook {} {} -> {}",
+                req.method, req.path, status
+            ));
+            (status, body)
+        }
+        Err(e) => (400, serde_json::json!({ "error": e.to_string() })),
+    };
+    let _ = write_response(&mut stream, status, &body);
+}
+
+enum Route<'a> {
+    Hook(&'a str),
+    Callback(&'a str),
+}
+
+fn route(req: &Request, workgraph_dir: &Path, secret: Option<&str>) -> (u16, serde_json::Value) {
+    let path = req.route();
+    let target = if let Some(name) = path.strip_prefix("/hooks/") {
+        Route::Hook(name)
+    } else if let Some(task_id) = path.strip_prefix("/callbacks/") {
+        Route::Callback(task_id)
+    } else {
+        return (404, serde_json::json!({ "error": "not found" }));
+    };
+    if req.method != "POST" {
+        return (405, serde_json::json!({ "error": "use POST" }));
+    }
+    if matches!(target, Route::Callback(_)) && secret.is_none() {
+        return (
+            403,
+            serde_json::json!({ "error": "callbacks require coordinator.webhook_secret" }),
+        );
+    }
+    if let Some(secret) = secret
+        && !event_gate::verify_signature(
+            secret,
+            &req.body,
+            req.header(event_gate::SIGNATURE_HEADER),
+        )
+    {
+        return (401, serde_json::json!({ "error": "bad signature" }));
+    }
+    match target {
+        Route::Hook(name) => fire_hook(workgraph_dir, name),
+        Route::Callback(task_id) => record_callback(workgraph_dir, task_id, &req.body),
+    }
+}
+
+fn fire_hook(workgraph_dir: &Path, name: &str) -> (u16, serde_json::Value) {
+    if let Err(e) = event_gate::validate_name(name) {
+        return (400, serde_json::json!({ "error": e.to_string() }));
+    }
+    match event_gate::fire_at(&workgraph_dir.join("graph.jsonl"), name, "webhook") {
+        Ok(released) => (
+            200,
+            serde_json::json!({ "event": name, "released": released }),
+        ),
+        Err(e) => (500, serde_json::json!({ "error": format!("{:#}", e) })),
+    }
+}
+
+/// Store a webhook executor report. Only in-progress tasks take reports,
+/// which also keeps arbitrary path segments out of the callbacks directory.
+fn record_callback(workgraph_dir: &Path, task_id: &str, body: &[u8]) -> (u16, serde_json::Value) {
+    let in_progress = match load_graph(workgraph_dir.join("graph.jsonl")) {
+        Ok(graph) => graph
+            .get_task(task_id)
+            .is_some_and(|t| t.status == Status::InProgress),
+        Err(e) => return (500, serde_json::json!({ "error": format!("{:#}", e) })),
+    };
+    if !in_progress {
+        return (
+            404,
+            serde_json::json!({ "error": format!("no in-progress task '{}'", task_id) }),
+        );
+    }
+    match webhook::record_callback(workgraph_dir, task_id, body) {
+        Ok(report) => (
+            200,
+            serde_json::json!({ "task": task_id, "status": report.status }),
+        ),
+        Err(e) if e.downcast_ref::<serde_json::Error>().is_some() => {
+            (400, serde_json::json!({ "error": format!("{:#}", e) }))
+        }
+        Err(e) => (500, serde_json::json!({ "error": format!("{:#}", e) })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Node, WorkGraph};
+    use crate::parser::save_graph;
+    use crate::test_helpers::make_task;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    fn post(addr: &str, path: &str, extra_headers: &str, body: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: x\r\n{}Content-Length: {}\r\n\r\n{}",
+            path,
+            extra_headers,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    /// `X-Webhook-Signature` header line for `body`.
+    fn signed(secret: &str, body: &str) -> String {
+        use hmac::{Hmac, Mac};
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body.as_bytes());
+        format!(
+            "X-Webhook-Signature: sha256={}\r\n",
+            hex::encode(mac.finalize().into_bytes())
+        )
+    }
+
+    #[test]
+    fn test_webhook_releases_gated_task() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut graph = WorkGraph::new();
+        let mut task = make_task("deploy", "Deploy");
+        task.waiting_on = vec!["webhook:ci-green".to_string()];
+        graph.add_node(Node::Task(task));
+        save_graph(&graph, tmp.path().join("graph.jsonl")).unwrap();
+
+        // Reserve a free port, then hand it to the listener.
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        spawn(
+            tmp.path().to_path_buf(),
+            &addr,
+            Some("s3cret".into()),
+            |_| {},
+        )
+        .unwrap();
+
+        let unsigned = post(&addr, "/hooks/ci-green", "", "{}");
+        assert!(unsigned.starts_with("HTTP/1.1 401"), "{}", unsigned);
+
+        let ok = post(&addr, "/hooks/ci-green", &signed("s3cret", "{}"), "{}");
+        assert!(ok.starts_with("HTTP/1.1 200"), "{}", ok);
+        assert!(ok.contains(r#""released":["deploy"]"#));
+
+        let graph = load_graph(tmp.path().join("graph.jsonl")).unwrap();
+        assert!(graph.get_task("deploy").unwrap().waiting_on.is_empty());
+
+        let missing = post(&addr, "/other", "", "");
+        assert!(missing.starts_with("HTTP/1.1 404"));
+    }
+
+    #[test]
+    fn test_callback_stores_report_for_in_progress_task() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut graph = WorkGraph::new();
+        let mut task = make_task("publish", "Publish");
+        task.status = Status::InProgress;
+        graph.add_node(Node::Task(task));
+        graph.add_node(Node::Task(make_task("idle", "Idle")));
+        save_graph(&graph, tmp.path().join("graph.jsonl")).unwrap();
+
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        spawn(
+            tmp.path().to_path_buf(),
+            &addr,
+            Some("s3cret".into()),
+            |_| {},
+        )
+        .unwrap();
+
+        let body = "not json";
+        let bad = post(&addr, "/callbacks/publish", &signed("s3cret", body), body);
+        assert!(bad.starts_with("HTTP/1.1 400"), "{}", bad);
+        let body = r#"{"status":"done"}"#;
+        let idle = post(&addr, "/callbacks/idle", &signed("s3cret", body), body);
+        assert!(idle.starts_with("HTTP/1.1 404"), "{}", idle);
+
+        let body = r#"{"status":"done","artifacts":["https://example.com/post/1"]}"#;
+        let ok = post(&addr, "/callbacks/publish", &signed("s3cret", body), body);
+        assert!(ok.starts_with("HTTP/1.1 200"), "{}", ok);
+        let report = webhook::take_callback(tmp.path(), "publish")
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.artifacts, vec!["https://example.com/post/1"]);
+    }
+
+    #[test]
+    fn test_callbacks_refused_without_secret() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut graph = WorkGraph::new();
+        let mut task = make_task("publish", "Publish");
+        task.status = Status::InProgress;
+        graph.add_node(Node::Task(task));
+        save_graph(&graph, tmp.path().join("graph.jsonl")).unwrap();
+
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        spawn(tmp.path().to_path_buf(), &addr, None, |_| {}).unwrap();
+
+        let refused = post(&addr, "/callbacks/publish", "", r#"{"status":"done"}"#);
+        assert!(refused.starts_with("HTTP/1.1 403"), "{}", refused);
+        assert!(
+            webhook::take_callback(tmp.path(), "publish")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_slow_client_does_not_block_others() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut graph = WorkGraph::new();
+        let mut task = make_task("deploy", "Deploy");
+        task.waiting_on = vec!["webhook:ci-green".to_string()];
+        graph.add_node(Node::Task(task));
+        save_graph(&graph, tmp.path().join("graph.jsonl")).unwrap();
+
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        spawn(tmp.path().to_path_buf(), &addr, None, |_| {}).unwrap();
+
+        // Opens a connection and sends half a request line.
+        let mut slow = TcpStream::connect(&addr).unwrap();
+        write!(slow, "POST /hooks/").unwrap();
+
+        let ok = post(&addr, "/hooks/ci-green", "", "{}");
+        assert!(ok.starts_with("HTTP/1.1 200"), "{}", ok);
+    }
+}
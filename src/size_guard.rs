@@ -0,0 +1,188 @@
+//! Size guards for rendered prompts and captured agent output
+//!
+//! A task whose dependency context drags in a pathological file list can
+//! render a prompt larger than the model's window, and a chatty agent can
+//! fill the disk with `output.log`. These helpers estimate sizes and cut
+//! text or files down to a budget, leaving a marker where content was
+//! dropped.
+
+use anyhow::{Context, Result};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Rough bytes-per-token ratio used for estimates (matches the native
+/// tokenizer's fallback).
+pub const BYTES_PER_TOKEN: usize = 4;
+
+/// What to keep when text exceeds its budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Truncation {
+    /// Keep the beginning, drop the end
+    Head,
+    /// Keep the end, drop the beginning
+    Tail,
+    /// Ask an LLM to condense the text (prompts only; falls back to
+    /// [`truncate_middle`])
+    Summary,
+}
+
+impl Truncation {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "head" => Some(Self::Head),
+            "tail" => Some(Self::Tail),
+            "summary" => Some(Self::Summary),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Head => "head",
+            Self::Tail => "tail",
+            Self::Summary => "summary",
+        }
+    }
+}
+
+/// Cheap token estimate: ~4 bytes per token.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(BYTES_PER_TOKEN)
+}
+
+fn marker(dropped: usize) -> String {
+    format!("\n[... {} bytes truncated by wg size guard ...]\n", dropped)
+}
+
+/// Cut `text` to at most `max_bytes` (plus a marker line), keeping the head
+/// or the tail. `Summary` behaves like `Head` here. Cuts land on char
+/// boundaries.
+pub fn truncate_text(text: &str, max_bytes: usize, keep: Truncation) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let dropped = text.len() - max_bytes;
+    match keep {
+        Truncation::Tail => {
+            let mut start = dropped;
+            while !text.is_char_boundary(start) {
+                start += 1;
+            }
+            format!("{}{}", marker(start), &text[start..])
+        }
+        Truncation::Head | Truncation::Summary => {
+            let mut end = max_bytes;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            format!("{}{}", &text[..end], marker(text.len() - end))
+        }
+    }
+}
+
+/// Cut `text` to at most `max_bytes` (plus a marker line) by dropping its
+/// middle, keeping the first and last halves of the budget.
+pub fn truncate_middle(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let mut end = max_bytes / 2;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut start = text.len() - (max_bytes - max_bytes / 2);
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("{}{}{}", &text[..end], marker(start - end), &text[start..])
+}
+
+/// Shrink the file at `path` in place to about `max_bytes`, keeping its head
+/// or tail. The file is rewritten through the same inode so writers holding
+/// it open in append mode keep writing to it. Returns the number of bytes
+/// dropped, or `None` when the file was already within budget.
+pub fn cap_file(path: &Path, max_bytes: u64, keep: Truncation) -> Result<Option<u64>> {
+    let len = match fs::metadata(path) {
+        Ok(meta) => meta.len(),
+        Err(_) => return Ok(None),
+    };
+    if len <= max_bytes {
+        return Ok(None);
+    }
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {:?}", path))?;
+    let dropped = len - max_bytes;
+    let mut kept = Vec::with_capacity(max_bytes as usize);
+    let offset = match keep {
+        Truncation::Tail => dropped,
+        Truncation::Head | Truncation::Summary => 0,
+    };
+    file.seek(SeekFrom::Start(offset))?;
+    (&mut file).take(max_bytes).read_to_end(&mut kept)?;
+
+    let marker = marker(dropped as usize);
+    let content = match keep {
+        Truncation::Tail => [marker.as_bytes(), &kept].concat(),
+        Truncation::Head | Truncation::Summary => [&kept, marker.as_bytes()].concat(),
+    };
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(&content)
+        .with_context(|| format!("Failed to rewrite {:?}", path))?;
+    Ok(Some(dropped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_text_head_and_tail() {
+        let text = "abcdefghij";
+        assert_eq!(truncate_text(text, 20, Truncation::Head), text);
+
+        let head = truncate_text(text, 4, Truncation::Head);
+        assert!(head.starts_with("abcd\n[... 6 bytes truncated"));
+        let tail = truncate_text(text, 4, Truncation::Tail);
+        assert!(tail.ends_with("truncated by wg size guard ...]\nghij"));
+
+        // Never splits a multi-byte char
+        let cut = truncate_text("ééé", 3, Truncation::Head);
+        assert!(cut.starts_with("é\n"));
+        assert_eq!(estimate_tokens("12345678"), 2);
+    }
+
+    #[test]
+    fn test_truncate_middle() {
+        let text = "abcdefghij";
+        assert_eq!(truncate_middle(text, 20), text);
+        let cut = truncate_middle(text, 4);
+        assert!(cut.starts_with("ab\n[... 6 bytes truncated"));
+        assert!(cut.ends_with("...]\nij"));
+
+        let cut = truncate_middle("éééé", 5);
+        assert!(cut.starts_with("é\n[... 4 bytes"));
+        assert!(cut.ends_with("]\né"));
+    }
+
+    #[test]
+    fn test_cap_file_keeps_tail() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("output.log");
+        fs::write(&path, "0123456789").unwrap();
+
+        assert_eq!(cap_file(&path, 20, Truncation::Tail).unwrap(), None);
+        assert_eq!(cap_file(&path, 3, Truncation::Tail).unwrap(), Some(7));
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("\n[... 7 bytes truncated"));
+        assert!(content.ends_with("789"));
+
+        fs::write(&path, "0123456789").unwrap();
+        cap_file(&path, 3, Truncation::Head).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().starts_with("012\n"));
+    }
+}
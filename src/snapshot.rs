@@ -0,0 +1,345 @@
+//! Named graph snapshots for `wg snapshot`.
+//!
+//! A snapshot is a point-in-time copy of `graph.jsonl` and `config.toml`
+//! (optionally also the `agency/` and `functions/` directories) stored under
+//! `.wg/snapshots/<name>/`. Unnamed snapshots get auto-incrementing names
+//! (`snap-001`, `snap-002`, …). Restoring first takes a safety snapshot of
+//! the current state so a restore can itself be undone.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::function::functions_dir;
+use crate::parser::{load_graph, modify_graph};
+
+/// Metadata for a single snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotMeta {
+    pub name: String,
+    /// ISO 8601 timestamp when the snapshot was taken
+    pub timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Number of tasks in the snapshotted graph
+    pub task_count: usize,
+    /// Whether `agency/` was captured
+    #[serde(default)]
+    pub agency: bool,
+    /// Whether `functions/` was captured
+    #[serde(default)]
+    pub functions: bool,
+}
+
+/// Directory where all snapshots live.
+pub fn snapshots_dir(workgraph_dir: &Path) -> PathBuf {
+    workgraph_dir.join("snapshots")
+}
+
+/// Directory for a specific snapshot.
+pub fn snapshot_dir(workgraph_dir: &Path, name: &str) -> PathBuf {
+    snapshots_dir(workgraph_dir).join(name)
+}
+
+/// Generate the next auto name by scanning existing snapshots.
+pub fn next_snapshot_name(workgraph_dir: &Path) -> String {
+    let mut max = 0u32;
+    if let Ok(entries) = fs::read_dir(snapshots_dir(workgraph_dir)) {
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            if let Some(num) = name
+                .to_string_lossy()
+                .strip_prefix("snap-")
+                .and_then(|n| n.parse::<u32>().ok())
+            {
+                max = max.max(num);
+            }
+        }
+    }
+    format!("snap-{:03}", max + 1)
+}
+
+/// Check that a snapshot or branch name is safe to use as a directory name.
+pub(crate) fn validate_name(kind: &str, name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        || name.starts_with('.')
+    {
+        anyhow::bail!(
+            "Invalid {} name '{}': use letters, digits, '-', '_' or '.'",
+            kind,
+            name
+        );
+    }
+    Ok(())
+}
+
+fn copy_dir_recursive(src: &Path, dest: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Take a snapshot of the current state.
+///
+/// Fails if a snapshot with the same name already exists.
+pub fn create(
+    workgraph_dir: &Path,
+    name: Option<&str>,
+    message: Option<&str>,
+    include_agency: bool,
+    include_functions: bool,
+) -> Result<SnapshotMeta> {
+    let name = match name {
+        Some(n) => {
+            validate_name("snapshot", n)?;
+            n.to_string()
+        }
+        None => next_snapshot_name(workgraph_dir),
+    };
+    let dest = snapshot_dir(workgraph_dir, &name);
+    if dest.exists() {
+        anyhow::bail!("Snapshot '{}' already exists", name);
+    }
+
+    let graph_src = workgraph_dir.join("graph.jsonl");
+    let task_count = load_graph(&graph_src)
+        .context("Failed to load graph for snapshot")?
+        .tasks()
+        .count();
+
+    fs::create_dir_all(&dest).context("Failed to create snapshot directory")?;
+    fs::copy(&graph_src, dest.join("graph.jsonl"))
+        .context("Failed to copy graph.jsonl to snapshot")?;
+    let config_src = workgraph_dir.join("config.toml");
+    if config_src.exists() {
+        fs::copy(&config_src, dest.join("config.toml"))
+            .context("Failed to copy config.toml to snapshot")?;
+    }
+
+    let agency_src = workgraph_dir.join("agency");
+    let agency = include_agency && agency_src.is_dir();
+    if agency {
+        copy_dir_recursive(&agency_src, &dest.join("agency"))
+            .context("Failed to copy agency/ to snapshot")?;
+    }
+    let functions_src = functions_dir(workgraph_dir);
+    let functions = include_functions && functions_src.is_dir();
+    if functions {
+        copy_dir_recursive(&functions_src, &dest.join("functions"))
+            .context("Failed to copy functions/ to snapshot")?;
+    }
+
+    let meta = SnapshotMeta {
+        name,
+        timestamp: Utc::now().to_rfc3339(),
+        message: message.map(String::from),
+        task_count,
+        agency,
+        functions,
+    };
+    fs::write(
+        dest.join("meta.json"),
+        serde_json::to_string_pretty(&meta).context("Failed to serialize snapshot metadata")?,
+    )
+    .context("Failed to write snapshot metadata")?;
+    Ok(meta)
+}
+
+/// Directory of an existing snapshot, rejecting names that would resolve
+/// outside the snapshots directory.
+fn require_snapshot(workgraph_dir: &Path, name: &str) -> Result<PathBuf> {
+    validate_name("snapshot", name)?;
+    Ok(snapshot_dir(workgraph_dir, name))
+}
+
+/// Load metadata for a specific snapshot.
+pub fn load_meta(workgraph_dir: &Path, name: &str) -> Result<SnapshotMeta> {
+    let path = require_snapshot(workgraph_dir, name)?.join("meta.json");
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Snapshot '{}' not found", name))?;
+    serde_json::from_str(&content).context("Failed to parse snapshot metadata")
+}
+
+/// List all snapshots, oldest first.
+pub fn list(workgraph_dir: &Path) -> Result<Vec<SnapshotMeta>> {
+    let dir = snapshots_dir(workgraph_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut metas = Vec::new();
+    for entry in fs::read_dir(&dir).context("Failed to read snapshots directory")? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            match load_meta(workgraph_dir, &name) {
+                Ok(meta) => metas.push(meta),
+                Err(e) => eprintln!("Warning: skipping snapshot '{}': {}", name, e),
+            }
+        }
+    }
+    metas.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then(a.name.cmp(&b.name)));
+    Ok(metas)
+}
+
+/// Restore a snapshot over the current state.
+///
+/// Takes a safety snapshot first and returns its metadata. The graph is
+/// replaced under the graph lock; `agency/` and `functions/` are replaced
+/// only if the snapshot captured them.
+pub fn restore(workgraph_dir: &Path, name: &str) -> Result<SnapshotMeta> {
+    let src = require_snapshot(workgraph_dir, name)?;
+    let meta = load_meta(workgraph_dir, name)?;
+    let snap_graph =
+        load_graph(src.join("graph.jsonl")).context("Failed to load snapshot graph")?;
+
+    let safety = create(
+        workgraph_dir,
+        None,
+        Some(&format!("pre-restore safety snapshot (restoring {})", name)),
+        meta.agency,
+        meta.functions,
+    )?;
+
+    modify_graph(workgraph_dir.join("graph.jsonl"), |graph| {
+        *graph = snap_graph;
+        true
+    })
+    .context("Failed to restore graph")?;
+
+    let config_src = src.join("config.toml");
+    if config_src.exists() {
+        fs::copy(&config_src, workgraph_dir.join("config.toml"))
+            .context("Failed to restore config.toml")?;
+    }
+    if meta.agency {
+        let target = workgraph_dir.join("agency");
+        if target.exists() {
+            fs::remove_dir_all(&target).context("Failed to clear agency/")?;
+        }
+        copy_dir_recursive(&src.join("agency"), &target).context("Failed to restore agency/")?;
+    }
+    if meta.functions {
+        let target = functions_dir(workgraph_dir);
+        if target.exists() {
+            fs::remove_dir_all(&target).context("Failed to clear functions/")?;
+        }
+        copy_dir_recursive(&src.join("functions"), &target)
+            .context("Failed to restore functions/")?;
+    }
+
+    Ok(safety)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Node, Task, WorkGraph};
+    use crate::parser::save_graph;
+    use tempfile::TempDir;
+
+    fn setup_wg(tmp: &TempDir) -> PathBuf {
+        let dir = tmp.path().join(".wg");
+        fs::create_dir_all(&dir).unwrap();
+        let mut graph = WorkGraph::new();
+        graph.add_node(Node::Task(Task {
+            id: "t1".to_string(),
+            title: "Original".to_string(),
+            ..Task::default()
+        }));
+        save_graph(&graph, dir.join("graph.jsonl")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_create_auto_names_and_rejects_duplicates() {
+        let tmp = TempDir::new().unwrap();
+        let dir = setup_wg(&tmp);
+
+        let a = create(&dir, None, Some("before bulk edit"), false, false).unwrap();
+        assert_eq!(a.name, "snap-001");
+        assert_eq!(a.task_count, 1);
+        assert_eq!(
+            create(&dir, None, None, false, false).unwrap().name,
+            "snap-002"
+        );
+
+        create(&dir, Some("plan-a"), None, false, false).unwrap();
+        assert!(create(&dir, Some("plan-a"), None, false, false).is_err());
+        assert!(create(&dir, Some("../escape"), None, false, false).is_err());
+
+        let names: Vec<String> = list(&dir).unwrap().into_iter().map(|m| m.name).collect();
+        assert_eq!(names.len(), 3);
+        assert!(names.contains(&"plan-a".to_string()));
+    }
+
+    #[test]
+    fn test_restore_replaces_graph_and_takes_safety_snapshot() {
+        let tmp = TempDir::new().unwrap();
+        let dir = setup_wg(&tmp);
+        fs::create_dir_all(dir.join("functions")).unwrap();
+        fs::write(dir.join("functions/f.yaml"), "id: f\n").unwrap();
+        create(&dir, Some("good"), None, false, true).unwrap();
+
+        // Break the graph and the functions dir
+        modify_graph(dir.join("graph.jsonl"), |g| {
+            g.get_task_mut("t1").unwrap().title = "Broken".to_string();
+            g.add_node(Node::Task(Task {
+                id: "t2".to_string(),
+                ..Task::default()
+            }));
+            true
+        })
+        .unwrap();
+        fs::remove_file(dir.join("functions/f.yaml")).unwrap();
+
+        let safety = restore(&dir, "good").unwrap();
+        let graph = load_graph(dir.join("graph.jsonl")).unwrap();
+        assert_eq!(graph.get_task("t1").unwrap().title, "Original");
+        assert!(graph.get_task("t2").is_none());
+        assert!(dir.join("functions/f.yaml").exists());
+
+        // The broken state is recoverable from the safety snapshot
+        assert_eq!(safety.task_count, 2);
+        assert!(load_meta(&dir, &safety.name).is_ok());
+    }
+
+    #[test]
+    fn test_restore_missing_snapshot_errors() {
+        let tmp = TempDir::new().unwrap();
+        let dir = setup_wg(&tmp);
+        let err = restore(&dir, "nope").unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_names_outside_snapshots_dir_are_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let dir = setup_wg(&tmp);
+        // A snapshot-shaped directory outside .wg/snapshots
+        let meta = create(&dir, Some("real"), None, false, false).unwrap();
+        copy_dir_recursive(&snapshot_dir(&dir, &meta.name), &tmp.path().join("x")).unwrap();
+
+        for name in ["../../x", "../snapshots/real", "/tmp"] {
+            let err = restore(&dir, name).unwrap_err();
+            assert!(
+                err.to_string().contains("Invalid snapshot name"),
+                "{}",
+                name
+            );
+            assert!(load_meta(&dir, name).is_err());
+        }
+    }
+}
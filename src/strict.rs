@@ -0,0 +1,453 @@
+//! Strict mode: reject unknown fields in `graph.jsonl` and function YAML.
+//!
+//! By default unknown fields are silently dropped, so files written by a
+//! newer `wg` still load. That also drops typos (`skils:` for `skills:`).
+//! With `--strict`, `project.strict_fields = true`, or `WG_STRICT=1`, loading
+//! fails instead, naming the field, where it is, and the closest known field.
+//!
+//! The known fields come from the types' own `Deserialize` impls (see
+//! [`fields_of`]), so they never drift from the structs.
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess,
+    Visitor,
+};
+use std::cell::RefCell;
+use std::fmt;
+
+use crate::function::{
+    ExtractionSource, ForbiddenPattern, FunctionInput, FunctionOutput, LoopEdgeTemplate,
+    MemoryInclusions, PlanningConfig, StructuralConstraints, TaskTemplate, TraceFunction,
+    TraceMemoryConfig,
+};
+use crate::graph::{Resource, Task};
+
+/// Environment variable that turns strict mode on. `wg` sets it for the
+/// process (and the agents it spawns) from `--strict` or the config.
+pub const STRICT_ENV: &str = "WG_STRICT";
+
+/// Whether strict mode is on for this process.
+pub fn enabled() -> bool {
+    std::env::var(STRICT_ENV)
+        .map(|v| !matches!(v.trim(), "" | "0" | "false" | "no" | "off"))
+        .unwrap_or(false)
+}
+
+/// A field no known struct accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownField {
+    pub field: String,
+    /// Where the field sits, e.g. `tasks[2]`; empty at the top level.
+    pub path: String,
+    /// 1-based position of the field name (within the line for JSONL).
+    pub line: usize,
+    pub column: usize,
+    /// Closest known field, if one is near enough to be a typo.
+    pub suggestion: Option<&'static str>,
+}
+
+impl fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown field `{}`", self.field)?;
+        if !self.path.is_empty() {
+            write!(f, " in {}", self.path)?;
+        }
+        write!(f, " on line {}, column {}", self.line, self.column)?;
+        if let Some(suggestion) = self.suggestion {
+            write!(f, " (did you mean `{}`?)", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownField {}
+
+/// Field names `T` accepts, read from its `Deserialize` impl by handing it
+/// a deserializer that records the field list and then bails.
+pub fn fields_of<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldProbe(&mut fields));
+    fields
+}
+
+struct FieldProbe<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for FieldProbe<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("fields recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// The fields of one struct, and which of them hold nested structs that
+/// are checked too.
+struct Schema {
+    fields: fn() -> &'static [&'static str],
+    /// Accepted on top of `fields`: serde aliases and the node `kind` tag.
+    extra: &'static [&'static str],
+    nested: &'static [(&'static str, Shape)],
+}
+
+#[derive(Clone, Copy)]
+enum Shape {
+    One(&'static Schema),
+    List(&'static Schema),
+}
+
+static TASK: Schema = Schema {
+    fields: fields_of::<Task>,
+    extra: &["kind", "blocks", "blocked_by"],
+    nested: &[],
+};
+
+static RESOURCE: Schema = Schema {
+    fields: fields_of::<Resource>,
+    extra: &["kind"],
+    nested: &[],
+};
+
+static FUNCTION: Schema = Schema {
+    fields: fields_of::<TraceFunction>,
+    extra: &[],
+    nested: &[
+        ("extracted_from", Shape::List(&EXTRACTION_SOURCE)),
+        ("inputs", Shape::List(&INPUT)),
+        ("tasks", Shape::List(&TASK_TEMPLATE)),
+        ("outputs", Shape::List(&OUTPUT)),
+        ("planning", Shape::One(&PLANNING)),
+        ("constraints", Shape::One(&CONSTRAINTS)),
+        ("memory", Shape::One(&MEMORY)),
+    ],
+};
+
+static EXTRACTION_SOURCE: Schema = Schema {
+    fields: fields_of::<ExtractionSource>,
+    extra: &[],
+    nested: &[],
+};
+
+static INPUT: Schema = Schema {
+    fields: fields_of::<FunctionInput>,
+    extra: &[],
+    nested: &[],
+};
+
+static TASK_TEMPLATE: Schema = Schema {
+    fields: fields_of::<TaskTemplate>,
+    extra: &[],
+    nested: &[("loops_to", Shape::List(&LOOP_EDGE))],
+};
+
+static LOOP_EDGE: Schema = Schema {
+    fields: fields_of::<LoopEdgeTemplate>,
+    extra: &[],
+    nested: &[],
+};
+
+static OUTPUT: Schema = Schema {
+    fields: fields_of::<FunctionOutput>,
+    extra: &[],
+    nested: &[],
+};
+
+static PLANNING: Schema = Schema {
+    fields: fields_of::<PlanningConfig>,
+    extra: &[],
+    nested: &[("planner_template", Shape::One(&TASK_TEMPLATE))],
+};
+
+static CONSTRAINTS: Schema = Schema {
+    fields: fields_of::<StructuralConstraints>,
+    extra: &[],
+    nested: &[("forbidden_patterns", Shape::List(&FORBIDDEN_PATTERN))],
+};
+
+static FORBIDDEN_PATTERN: Schema = Schema {
+    fields: fields_of::<ForbiddenPattern>,
+    extra: &[],
+    nested: &[],
+};
+
+static MEMORY: Schema = Schema {
+    fields: fields_of::<TraceMemoryConfig>,
+    extra: &[],
+    nested: &[("include", Shape::One(&MEMORY_INCLUSIONS))],
+};
+
+static MEMORY_INCLUSIONS: Schema = Schema {
+    fields: fields_of::<MemoryInclusions>,
+    extra: &[],
+    nested: &[],
+};
+
+/// Check one `graph.jsonl` line (number `line_num`) for fields its node
+/// kind doesn't have. Lines that aren't valid JSON pass: the real parse
+/// reports those.
+pub fn check_node(line: &str, line_num: usize) -> Option<UnknownField> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    let schema = match value.get("kind").and_then(|k| k.as_str()) {
+        Some("resource") => &RESOURCE,
+        _ => &TASK,
+    };
+    let found = RefCell::new(None);
+    let mut de = serde_json::Deserializer::from_str(line);
+    let err = Walk::new(Shape::One(schema), &found)
+        .deserialize(&mut de)
+        .err()?;
+    found
+        .into_inner()
+        .map(|unknown: UnknownField| UnknownField {
+            line: line_num,
+            column: err.column(),
+            ..unknown
+        })
+}
+
+/// Check trace function YAML for fields the function format doesn't have,
+/// including inside its inputs, task templates, outputs and settings.
+pub fn check_function(yaml: &str) -> Option<UnknownField> {
+    let found = RefCell::new(None);
+    let err = Walk::new(Shape::One(&FUNCTION), &found)
+        .deserialize(serde_yaml::Deserializer::from_str(yaml))
+        .err()?;
+    let location = err.location();
+    found
+        .into_inner()
+        .map(|unknown: UnknownField| UnknownField {
+            line: location.as_ref().map_or(0, |l| l.line()),
+            column: location.as_ref().map_or(0, |l| l.column()),
+            ..unknown
+        })
+}
+
+/// Known field closest to `field`, if it is within a couple of edits.
+fn suggest(field: &str, known: &[&'static str]) -> Option<&'static str> {
+    let field = field.to_lowercase();
+    known
+        .iter()
+        .map(|k| (crate::profile::named::edit_distance(&field, k), *k))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, k)| k)
+}
+
+/// Walks a document against a [`Shape`], failing at the first unknown
+/// field. The field is stashed in `found`; the format's own error carries
+/// the position. Values of the wrong type are skipped, not reported: the
+/// real deserialization does that.
+struct Walk<'a> {
+    shape: Shape,
+    path: String,
+    found: &'a RefCell<Option<UnknownField>>,
+}
+
+impl<'a> Walk<'a> {
+    fn new(shape: Shape, found: &'a RefCell<Option<UnknownField>>) -> Self {
+        Self {
+            shape,
+            path: String::new(),
+            found,
+        }
+    }
+
+    fn child(&self, shape: Shape, path: String) -> Self {
+        Self {
+            shape,
+            path,
+            found: self.found,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Walk<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Walk<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let Shape::One(schema) = self.shape else {
+            while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            return Ok(());
+        };
+        let fields = (schema.fields)();
+        while let Some(key) = map.next_key::<String>()? {
+            if !fields.contains(&key.as_str()) && !schema.extra.contains(&key.as_str()) {
+                let message = format!("unknown field `{}`", key);
+                *self.found.borrow_mut() = Some(UnknownField {
+                    suggestion: suggest(&key, fields),
+                    field: key,
+                    path: self.path.clone(),
+                    line: 0,
+                    column: 0,
+                });
+                return Err(de::Error::custom(message));
+            }
+            match schema.nested.iter().find(|(name, _)| *name == key) {
+                Some((_, shape)) => {
+                    let path = if self.path.is_empty() {
+                        key
+                    } else {
+                        format!("{}.{}", self.path, key)
+                    };
+                    map.next_value_seed(self.child(*shape, path))?;
+                }
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let Shape::List(schema) = self.shape else {
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+            return Ok(());
+        };
+        let mut i = 0;
+        while seq
+            .next_element_seed(self.child(Shape::One(schema), format!("{}[{}]", self.path, i)))?
+            .is_some()
+        {
+            i += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<(), A::Error> {
+        IgnoredAny.visit_enum(data).map(|_| ())
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i128<E>(self, _: i128) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u128<E>(self, _: u128) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_bytes<E>(self, _: &[u8]) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_none<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_of_reads_derived_and_custom_impls() {
+        let fields = fields_of::<Task>();
+        assert!(fields.contains(&"skills"));
+        assert!(fields.contains(&"after"));
+        assert!(fields_of::<TaskTemplate>().contains(&"template_id"));
+        assert!(fields_of::<FunctionInput>().contains(&"type"));
+    }
+
+    #[test]
+    fn test_check_node_locates_typo() {
+        let line = r#"{"kind":"task","id":"a","title":"A","skils":["rust"],"blocks":[]}"#;
+        let unknown = check_node(line, 7).unwrap();
+        assert_eq!(unknown.field, "skils");
+        assert_eq!(unknown.suggestion, Some("skills"));
+        assert_eq!(unknown.line, 7);
+        let key = line.find(r#""skils""#).unwrap();
+        assert!((key + 1..=key + 8).contains(&unknown.column));
+        assert!(
+            unknown
+                .to_string()
+                .starts_with("Unknown field `skils` on line 7, column")
+        );
+
+        assert!(check_node(r#"{"kind":"task","id":"a","title":"A"}"#, 1).is_none());
+        assert!(check_node(r#"{"kind":"resource","id":"r","type":"money"}"#, 1).is_none());
+        assert!(check_node(r#"{"kind":"resource","id":"r","skills":[]}"#, 1).is_some());
+        assert!(check_node("not json", 1).is_none());
+    }
+
+    #[test]
+    fn test_check_function_descends_into_templates() {
+        let yaml = "\
+kind: trace-function
+version: 1
+id: f
+name: F
+description: d
+tasks:
+  - template_id: a
+    title: A
+    description: d
+  - template_id: b
+    title: B
+    description: d
+    skils: [rust]
+";
+        let unknown = check_function(yaml).unwrap();
+        assert_eq!(unknown.field, "skils");
+        assert_eq!(unknown.path, "tasks[1]");
+        assert_eq!(unknown.line, 14);
+        assert_eq!(unknown.suggestion, Some("skills"));
+
+        assert!(check_function(&yaml.replace("skils", "skills")).is_none());
+        assert!(check_function(&yaml.replace("version: 1", "verison: 1")).is_some());
+    }
+}
@@ -0,0 +1,564 @@
+//! Structured test results and flaky-test tracking.
+//!
+//! Test output is parsed from JUnit XML, libtest JSON (`cargo test -- -Z
+//! unstable-options --format json`) or plain libtest text (`test foo ... ok`),
+//! whether it arrives as a task artifact or as the output of a verify command.
+//! Each parsed run is appended to `test-results/<task-id>.jsonl` together
+//! with a fingerprint of the working tree.
+//!
+//! A test that both passed and failed on the same fingerprint flipped without
+//! a code change, and is recorded in `test-results/flaky.json`. Together with
+//! `coordinator.flaky_tests` this registry lets verify tell real failures from
+//! known-flaky ones: flaky-only failures are rerun and don't count toward the
+//! verify circuit breaker.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// Directory under `.wg/` holding per-task result history and the flaky registry.
+pub const TEST_RESULTS_DIR: &str = "test-results";
+
+/// Flaky-test registry file inside [`TEST_RESULTS_DIR`].
+pub const FLAKY_FILE: &str = "flaky.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestCase {
+    pub name: String,
+    pub outcome: TestOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// One parsed test run for a task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestReport {
+    /// Where the results came from: an artifact path or `verify`.
+    pub source: String,
+    pub recorded_at: String,
+    /// Working-tree fingerprint at the time of the run (see
+    /// [`working_tree_fingerprint`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    pub cases: Vec<TestCase>,
+}
+
+impl TestReport {
+    pub fn new(source: &str, fingerprint: Option<String>, cases: Vec<TestCase>) -> Self {
+        TestReport {
+            source: source.to_string(),
+            recorded_at: Utc::now().to_rfc3339(),
+            fingerprint,
+            cases,
+        }
+    }
+
+    pub fn count(&self, outcome: TestOutcome) -> usize {
+        self.cases.iter().filter(|c| c.outcome == outcome).count()
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &TestCase> {
+        self.cases
+            .iter()
+            .filter(|c| c.outcome == TestOutcome::Failed)
+    }
+
+    /// e.g. "41 passed, 2 failed, 1 skipped".
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            format!("{} passed", self.count(TestOutcome::Passed)),
+            format!("{} failed", self.count(TestOutcome::Failed)),
+        ];
+        let skipped = self.count(TestOutcome::Skipped);
+        if skipped > 0 {
+            parts.push(format!("{} skipped", skipped));
+        }
+        parts.join(", ")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+fn attrs(s: &str) -> BTreeMap<String, String> {
+    static ATTR: OnceLock<Regex> = OnceLock::new();
+    let attr = ATTR.get_or_init(|| {
+        Regex::new(r#"([\w:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("attribute regex")
+    });
+    attr.captures_iter(s)
+        .map(|c| {
+            let value = c.get(2).or_else(|| c.get(3)).map_or("", |m| m.as_str());
+            (c[1].to_string(), unescape_xml(value))
+        })
+        .collect()
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Parse JUnit XML `<testcase>` elements. The test name is
+/// `classname::name` when a classname is present.
+pub fn parse_junit(xml: &str) -> Vec<TestCase> {
+    static TESTCASE: OnceLock<Regex> = OnceLock::new();
+    static FAILURE: OnceLock<Regex> = OnceLock::new();
+    let testcase = TESTCASE.get_or_init(|| {
+        Regex::new(r"(?s)<testcase\b([^>]*?)(?:/>|>(.*?)</testcase>)").expect("testcase regex")
+    });
+    let failure_re =
+        FAILURE.get_or_init(|| Regex::new(r"<(failure|error)\b([^>]*)").expect("failure regex"));
+    testcase
+        .captures_iter(xml)
+        .filter_map(|c| {
+            let a = attrs(&c[1]);
+            let name = a.get("name")?;
+            let name = match a.get("classname") {
+                Some(class) if !class.is_empty() => format!("{}::{}", class, name),
+                _ => name.clone(),
+            };
+            let body = c.get(2).map_or("", |m| m.as_str());
+            let failure = failure_re.captures(body);
+            let outcome = if failure.is_some() {
+                TestOutcome::Failed
+            } else if body.contains("<skipped") {
+                TestOutcome::Skipped
+            } else {
+                TestOutcome::Passed
+            };
+            Some(TestCase {
+                name,
+                outcome,
+                duration_secs: a.get("time").and_then(|t| t.parse().ok()),
+                message: failure.and_then(|f| attrs(&f[2]).remove("message")),
+            })
+        })
+        .collect()
+}
+
+/// Parse libtest JSON events (one object per line). Non-JSON lines are
+/// ignored, so mixed cargo output works.
+pub fn parse_libtest_json(output: &str) -> Vec<TestCase> {
+    output
+        .lines()
+        .filter(|l| l.trim_start().starts_with('{'))
+        .filter_map(|l| serde_json::from_str::<serde_json::Value>(l).ok())
+        .filter(|v| v["type"] == "test")
+        .filter_map(|v| {
+            let outcome = match v["event"].as_str()? {
+                "ok" => TestOutcome::Passed,
+                "failed" | "timeout" => TestOutcome::Failed,
+                "ignored" => TestOutcome::Skipped,
+                _ => return None,
+            };
+            Some(TestCase {
+                name: v["name"].as_str()?.to_string(),
+                outcome,
+                duration_secs: v["exec_time"].as_f64(),
+                message: v["stdout"]
+                    .as_str()
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Parse plain libtest output lines such as `test foo::bar ... FAILED`.
+pub fn parse_libtest_text(output: &str) -> Vec<TestCase> {
+    static LINE: OnceLock<Regex> = OnceLock::new();
+    let line = LINE.get_or_init(|| {
+        Regex::new(r"(?m)^test (\S+) \.\.\. (ok|FAILED|ignored)\b").expect("libtest regex")
+    });
+    line.captures_iter(output)
+        .map(|c| TestCase {
+            name: c[1].to_string(),
+            outcome: match &c[2] {
+                "ok" => TestOutcome::Passed,
+                "FAILED" => TestOutcome::Failed,
+                _ => TestOutcome::Skipped,
+            },
+            duration_secs: None,
+            message: None,
+        })
+        .collect()
+}
+
+/// Parse test output in whichever supported format it is in. Returns `None`
+/// when no test cases are recognised.
+pub fn parse(content: &str) -> Option<Vec<TestCase>> {
+    let cases = if content.contains("<testcase") {
+        parse_junit(content)
+    } else {
+        let json = parse_libtest_json(content);
+        if json.is_empty() {
+            parse_libtest_text(content)
+        } else {
+            json
+        }
+    };
+    (!cases.is_empty()).then_some(cases)
+}
+
+/// Whether an artifact path looks like a test report worth parsing.
+pub fn is_test_report_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    let file = lower.rsplit('/').next().unwrap_or(&lower);
+    if file.ends_with(".xml") {
+        return file.contains("junit") || file.contains("test");
+    }
+    (file.ends_with(".json") || file.ends_with(".jsonl") || file.ends_with(".log"))
+        && file.contains("test")
+}
+
+// ---------------------------------------------------------------------------
+// Storage
+// ---------------------------------------------------------------------------
+
+fn results_dir(dir: &Path) -> PathBuf {
+    dir.join(TEST_RESULTS_DIR)
+}
+
+fn history_path(dir: &Path, task_id: &str) -> PathBuf {
+    results_dir(dir).join(format!("{}.jsonl", task_id))
+}
+
+/// Append a report to the task's history.
+pub fn record(dir: &Path, task_id: &str, report: &TestReport) -> Result<()> {
+    fs::create_dir_all(results_dir(dir))?;
+    let path = history_path(dir, task_id);
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(report)?)?;
+    Ok(())
+}
+
+/// All recorded reports for a task, oldest first.
+pub fn load_history(dir: &Path, task_id: &str) -> Vec<TestReport> {
+    fs::read_to_string(history_path(dir, task_id))
+        .map(|s| {
+            s.lines()
+                .filter_map(|l| serde_json::from_str(l).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The most recent report for a task.
+pub fn latest(dir: &Path, task_id: &str) -> Option<TestReport> {
+    load_history(dir, task_id).pop()
+}
+
+/// Hash of `HEAD`, the uncommitted diff and the untracked file list in
+/// `project_root`. Two runs with the same fingerprint ran against the same
+/// code. `None` outside a git repository.
+pub fn working_tree_fingerprint(project_root: &Path) -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(project_root)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| o.stdout)
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(git(&["rev-parse", "HEAD"])?);
+    hasher.update(git(&["diff", "HEAD"])?);
+    hasher.update(git(&["status", "--porcelain"])?);
+    Some(hex::encode(hasher.finalize()))
+}
+
+// ---------------------------------------------------------------------------
+// Flaky tests
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlakyRecord {
+    /// Times the test changed outcome without a code change.
+    pub flips: u32,
+    pub last_seen: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlakyRegistry {
+    #[serde(default)]
+    pub tests: BTreeMap<String, FlakyRecord>,
+}
+
+impl FlakyRegistry {
+    pub fn load(dir: &Path) -> Self {
+        fs::read_to_string(results_dir(dir).join(FLAKY_FILE))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(results_dir(dir))?;
+        fs::write(
+            results_dir(dir).join(FLAKY_FILE),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn record_flip(&mut self, name: &str) {
+        let record = self.tests.entry(name.to_string()).or_default();
+        record.flips += 1;
+        record.last_seen = Utc::now().to_rfc3339();
+    }
+
+    /// Whether `name` is flaky, either by registry or by one of the
+    /// configured `patterns` (exact names, `::`-suffixes, or `prefix*`).
+    pub fn is_flaky(&self, name: &str, patterns: &[String]) -> bool {
+        self.tests.contains_key(name) || patterns.iter().any(|p| pattern_matches(p, name))
+    }
+}
+
+fn pattern_matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix) || name.contains(&format!("::{}", prefix)),
+        None => name == pattern || name.ends_with(&format!("::{}", pattern)),
+    }
+}
+
+/// Tests that passed in one report and failed in the other although both ran
+/// against the same working tree.
+pub fn detect_flips(previous: &TestReport, current: &TestReport) -> Vec<String> {
+    if previous.fingerprint.is_none() || previous.fingerprint != current.fingerprint {
+        return Vec::new();
+    }
+    let before: BTreeMap<&str, TestOutcome> = previous
+        .cases
+        .iter()
+        .map(|c| (c.name.as_str(), c.outcome))
+        .collect();
+    current
+        .cases
+        .iter()
+        .filter(|c| {
+            matches!(
+                (before.get(c.name.as_str()), c.outcome),
+                (Some(TestOutcome::Passed), TestOutcome::Failed)
+                    | (Some(TestOutcome::Failed), TestOutcome::Passed)
+            )
+        })
+        .map(|c| c.name.clone())
+        .collect()
+}
+
+/// Failing tests split into real failures and known-flaky ones.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FailureTriage {
+    pub real: Vec<String>,
+    pub flaky: Vec<String>,
+}
+
+impl FailureTriage {
+    /// Every failure is in a known-flaky test.
+    pub fn flaky_only(&self) -> bool {
+        self.real.is_empty() && !self.flaky.is_empty()
+    }
+}
+
+pub fn triage(report: &TestReport, registry: &FlakyRegistry, patterns: &[String]) -> FailureTriage {
+    let mut out = FailureTriage::default();
+    for case in report.failed() {
+        if registry.is_flaky(&case.name, patterns) {
+            out.flaky.push(case.name.clone());
+        } else {
+            out.real.push(case.name.clone());
+        }
+    }
+    out
+}
+
+/// Record a report for `task_id` and update the flaky registry with any
+/// flips against the task's previous report.
+pub fn ingest(dir: &Path, task_id: &str, report: &TestReport) -> Result<Vec<String>> {
+    let flips = latest(dir, task_id)
+        .map(|prev| detect_flips(&prev, report))
+        .unwrap_or_default();
+    record(dir, task_id, report)?;
+    if !flips.is_empty() {
+        let mut registry = FlakyRegistry::load(dir);
+        for name in &flips {
+            registry.record_flip(name);
+        }
+        registry.save(dir)?;
+    }
+    Ok(flips)
+}
+
+/// Markdown summary of a report for prompts, marking flaky failures.
+pub fn render_summary(report: &TestReport, triage: &FailureTriage) -> String {
+    let mut out = format!("{} (source: {})\n", report.summary(), report.source);
+    for name in &triage.real {
+        out.push_str(&format!("- FAILED `{}`\n", name));
+    }
+    for name in &triage.flaky {
+        out.push_str(&format!("- FAILED `{}` (known flaky)\n", name));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn case(name: &str, outcome: TestOutcome) -> TestCase {
+        TestCase {
+            name: name.to_string(),
+            outcome,
+            duration_secs: None,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_junit() {
+        let xml = r#"<?xml version="1.0"?>
+<testsuites><testsuite name="unit" tests="3">
+  <testcase classname="core" name="adds" time="0.01"/>
+  <testcase classname="core" name="divides" time="0.20">
+    <failure message="expected 2 &amp; got 3">assertion failed</failure>
+  </testcase>
+  <testcase name='later'><skipped/></testcase>
+</testsuite></testsuites>"#;
+        let cases = parse(xml).unwrap();
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].name, "core::adds");
+        assert_eq!(cases[0].outcome, TestOutcome::Passed);
+        assert_eq!(cases[0].duration_secs, Some(0.01));
+        assert_eq!(cases[1].outcome, TestOutcome::Failed);
+        assert_eq!(cases[1].message.as_deref(), Some("expected 2 & got 3"));
+        assert_eq!(cases[2].name, "later");
+        assert_eq!(cases[2].outcome, TestOutcome::Skipped);
+    }
+
+    #[test]
+    fn test_parse_libtest_json_and_text() {
+        let json = r#"{ "type": "suite", "event": "started", "test_count": 2 }
+{ "type": "test", "event": "started", "name": "a::works" }
+{ "type": "test", "name": "a::works", "event": "ok", "exec_time": 0.5 }
+{ "type": "test", "name": "a::breaks", "event": "failed", "stdout": "panicked" }
+   Compiling foo v0.1.0"#;
+        let cases = parse(json).unwrap();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[1].outcome, TestOutcome::Failed);
+        assert_eq!(cases[1].message.as_deref(), Some("panicked"));
+
+        let text = "running 3 tests\ntest a::works ... ok\ntest a::breaks ... FAILED\n\
+                    test a::slow ... ignored, needs network\n";
+        let cases = parse(text).unwrap();
+        let outcomes: Vec<TestOutcome> = cases.iter().map(|c| c.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                TestOutcome::Passed,
+                TestOutcome::Failed,
+                TestOutcome::Skipped
+            ]
+        );
+        assert!(parse("error: could not compile").is_none());
+    }
+
+    #[test]
+    fn test_is_test_report_path() {
+        assert!(is_test_report_path("target/junit.xml"));
+        assert!(is_test_report_path("out/test-results.json"));
+        assert!(!is_test_report_path("Cargo.toml"));
+        assert!(!is_test_report_path("config/pom.xml"));
+    }
+
+    #[test]
+    fn test_ingest_detects_flips_on_same_fingerprint() {
+        let dir = tempdir().unwrap();
+        let fp = Some("abc".to_string());
+        let first = TestReport::new(
+            "verify",
+            fp.clone(),
+            vec![
+                case("net::retry", TestOutcome::Failed),
+                case("ok", TestOutcome::Passed),
+            ],
+        );
+        assert!(ingest(dir.path(), "t1", &first).unwrap().is_empty());
+
+        let second = TestReport::new(
+            "verify",
+            fp,
+            vec![
+                case("net::retry", TestOutcome::Passed),
+                case("ok", TestOutcome::Passed),
+            ],
+        );
+        assert_eq!(
+            ingest(dir.path(), "t1", &second).unwrap(),
+            vec!["net::retry"]
+        );
+
+        // A different fingerprint means the code changed: not a flip.
+        let third = TestReport::new(
+            "verify",
+            Some("def".to_string()),
+            vec![case("ok", TestOutcome::Failed)],
+        );
+        assert!(ingest(dir.path(), "t1", &third).unwrap().is_empty());
+
+        assert_eq!(load_history(dir.path(), "t1").len(), 3);
+        let registry = FlakyRegistry::load(dir.path());
+        assert_eq!(registry.tests["net::retry"].flips, 1);
+        assert!(!registry.tests.contains_key("ok"));
+    }
+
+    #[test]
+    fn test_triage_with_registry_and_patterns() {
+        let mut registry = FlakyRegistry::default();
+        registry.record_flip("net::retry");
+        let patterns = vec!["timing_*".to_string(), "db::pool".to_string()];
+        let report = TestReport::new(
+            "verify",
+            None,
+            vec![
+                case("net::retry", TestOutcome::Failed),
+                case("perf::timing_budget", TestOutcome::Failed),
+                case("store::db::pool", TestOutcome::Failed),
+                case("parser::eof", TestOutcome::Failed),
+                case("parser::ok", TestOutcome::Passed),
+            ],
+        );
+        let t = triage(&report, &registry, &patterns);
+        assert_eq!(t.real, vec!["parser::eof"]);
+        assert_eq!(t.flaky.len(), 3);
+        assert!(!t.flaky_only());
+        assert!(render_summary(&report, &t).contains("`net::retry` (known flaky)"));
+    }
+}
@@ -3026,7 +3026,7 @@ pub struct ArchiveEntry {
 pub struct ArchiveBrowserState {
     /// Whether the archive browser is currently open/visible.
     pub active: bool,
-    /// All archived entries loaded from archive.jsonl.
+    /// All archived entries loaded from the archive files.
     pub entries: Vec<ArchiveEntry>,
     /// Currently selected index in the (filtered) list.
     pub selected: usize,
@@ -3041,11 +3041,14 @@ pub struct ArchiveBrowserState {
 }
 
 impl ArchiveBrowserState {
-    /// Reload entries from the archive.jsonl file.
+    /// Reload entries from the archive files (legacy and monthly partitions).
     pub fn load(&mut self, workgraph_dir: &std::path::Path) {
-        let archive_path = workgraph_dir.join("archive.jsonl");
         self.entries.clear();
-        if let Ok(file) = std::fs::File::open(&archive_path) {
+        let archive_files = workgraph::archive::files(workgraph_dir).unwrap_or_default();
+        for file in archive_files
+            .iter()
+            .filter_map(|p| std::fs::File::open(p).ok())
+        {
             let reader = BufReader::new(file);
             for line in reader.lines() {
                 let line = match line {
@@ -7378,19 +7381,17 @@ impl VizApp {
         }
 
         // Count archived tasks
-        let archive_path = self.workgraph_dir.join("archive.jsonl");
-        counts.archived = if archive_path.exists() {
-            std::fs::File::open(&archive_path)
-                .map(|f| {
-                    BufReader::new(f)
-                        .lines()
-                        .filter(|l| l.as_ref().is_ok_and(|s| !s.trim().is_empty()))
-                        .count()
-                })
-                .unwrap_or(0)
-        } else {
-            0
-        };
+        counts.archived = workgraph::archive::files(&self.workgraph_dir)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|p| std::fs::File::open(p).ok())
+            .map(|f| {
+                BufReader::new(f)
+                    .lines()
+                    .filter(|l| l.as_ref().is_ok_and(|s| !s.trim().is_empty()))
+                    .count()
+            })
+            .sum();
 
         self.task_snapshots = new_snapshots;
         self.task_counts = counts;
@@ -18352,6 +18353,8 @@ mod hud_tests {
                 model: Some("openrouter/minimax".to_string()),
                 completed_at: None,
                 worktree_path: None,
+                preempted_by: None,
+                paused_task: false,
             },
         );
         registry.save(_tmp.path()).unwrap();
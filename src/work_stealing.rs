@@ -0,0 +1,328 @@
+//! Batch claims and work stealing.
+//!
+//! `wg claim --batch N` lets an actor take several ready tasks in one graph
+//! write: the first is claimed (in progress) and the rest are *queued* —
+//! left open but assigned to the actor, which keeps the dispatcher and
+//! other actors off them until the actor claims each in turn.
+//!
+//! An actor with work in progress and a non-empty queue is overloaded.
+//! Idle capacity takes queued tasks back from the longest queue, from its
+//! tail (the work its owner would reach last): `wg claim --steal` for an
+//! idle actor, and the dispatcher itself when `coordinator.work_stealing`
+//! is on and it has free agent slots but no unassigned ready work.
+
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::focus::{self, Focuser};
+use crate::graph::{LogEntry, Status, Task, WorkGraph, aged_priority, is_system_task};
+use crate::query::ready_tasks;
+
+/// A task moved out of another actor's queue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stolen {
+    pub task_id: String,
+    pub from: String,
+}
+
+fn log(task: &mut Task, actor: Option<&str>, message: String) {
+    task.log.push(LogEntry {
+        timestamp: Utc::now().to_rfc3339(),
+        actor: actor.map(String::from),
+        user: Some(crate::current_user()),
+        message,
+    });
+}
+
+/// Claim up to `n` tasks from `who`'s focus order: the first is set in
+/// progress (with `claim_sha` as its diff base, see [`crate::changes`]), the
+/// rest are queued. Returns the IDs taken, claimed one first.
+pub fn claim_batch(
+    graph: &mut WorkGraph,
+    who: &Focuser,
+    n: usize,
+    now: DateTime<Utc>,
+    aging_hours: u64,
+    claim_sha: Option<&str>,
+) -> Vec<String> {
+    let ids: Vec<String> = focus::compute(graph, who, n, now, aging_hours)
+        .into_iter()
+        .map(|item| item.id)
+        .collect();
+    let timestamp = now.to_rfc3339();
+    for (i, id) in ids.iter().enumerate() {
+        let Some(task) = graph.get_task_mut(id) else {
+            continue;
+        };
+        task.assigned = Some(who.name.clone());
+        if i == 0 {
+            task.status = Status::InProgress;
+            task.started_at = Some(timestamp.clone());
+            task.claim_sha = claim_sha.map(String::from);
+            log(
+                task,
+                Some(&who.name),
+                format!("Task claimed by @{} (batch of {})", who.name, ids.len()),
+            );
+        } else {
+            log(
+                task,
+                Some(&who.name),
+                format!("Task queued for @{} (batch claim)", who.name),
+            );
+        }
+    }
+    ids
+}
+
+/// Queued ready tasks of every overloaded actor, longest queue first. Each
+/// queue is in the order its owner would work it (aged priority, then ID).
+pub fn queues(
+    graph: &WorkGraph,
+    now: DateTime<Utc>,
+    aging_hours: u64,
+) -> Vec<(String, Vec<&Task>)> {
+    let busy: HashSet<&str> = graph
+        .tasks()
+        .filter(|t| t.status == Status::InProgress)
+        .filter_map(|t| t.assigned.as_deref())
+        .collect();
+    let mut by_actor: BTreeMap<&str, Vec<&Task>> = BTreeMap::new();
+    for task in ready_tasks(graph) {
+        if let Some(actor) = task.assigned.as_deref()
+            && busy.contains(actor)
+            && !is_system_task(&task.id)
+        {
+            by_actor.entry(actor).or_default().push(task);
+        }
+    }
+    let mut queues: Vec<(String, Vec<&Task>)> = by_actor
+        .into_iter()
+        .map(|(actor, mut tasks)| {
+            tasks.sort_by(|a, b| {
+                aged_priority(b, now, aging_hours)
+                    .cmp(&aged_priority(a, now, aging_hours))
+                    .then(a.id.cmp(&b.id))
+            });
+            (actor.to_string(), tasks)
+        })
+        .collect();
+    // Stable sort keeps actors with equal queues in name order.
+    queues.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
+    queues
+}
+
+/// Take the last queued task `thief` is suited for from the longest queue
+/// of another actor, and claim it for `thief` with `claim_sha` as its diff
+/// base.
+pub fn steal(
+    graph: &mut WorkGraph,
+    thief: &Focuser,
+    now: DateTime<Utc>,
+    aging_hours: u64,
+    claim_sha: Option<&str>,
+) -> Option<Stolen> {
+    let stolen = queues(graph, now, aging_hours)
+        .into_iter()
+        .filter(|(actor, _)| *actor != thief.name)
+        .find_map(|(actor, tasks)| {
+            tasks
+                .iter()
+                .rev()
+                .find(|t| focus::is_suited(t, thief))
+                .map(|t| Stolen {
+                    task_id: t.id.clone(),
+                    from: actor,
+                })
+        })?;
+    let task = graph.get_task_mut(&stolen.task_id)?;
+    task.assigned = Some(thief.name.clone());
+    task.status = Status::InProgress;
+    task.started_at = Some(now.to_rfc3339());
+    task.claim_sha = claim_sha.map(String::from);
+    log(
+        task,
+        Some(&thief.name),
+        format!(
+            "Task stolen from @{}'s queue by @{} (work stealing)",
+            stolen.from, thief.name
+        ),
+    );
+    Some(stolen)
+}
+
+/// Dispatcher side of work stealing: with `idle_slots` free agent slots,
+/// release queued tasks for dispatch until every slot has ready work.
+/// Released tasks are unassigned so the dispatcher picks them up normally.
+pub fn release_for_idle_slots(
+    graph: &mut WorkGraph,
+    idle_slots: usize,
+    now: DateTime<Utc>,
+    aging_hours: u64,
+) -> Vec<Stolen> {
+    let unassigned_ready = ready_tasks(graph)
+        .iter()
+        .filter(|t| t.assigned.is_none() && !is_system_task(&t.id))
+        .count();
+    let mut released = Vec::new();
+    for _ in unassigned_ready..idle_slots {
+        let Some(stolen) = queues(graph, now, aging_hours)
+            .first()
+            .and_then(|(actor, tasks)| {
+                tasks.last().map(|t| Stolen {
+                    task_id: t.id.clone(),
+                    from: actor.clone(),
+                })
+            })
+        else {
+            break;
+        };
+        if let Some(task) = graph.get_task_mut(&stolen.task_id) {
+            task.assigned = None;
+            log(
+                task,
+                None,
+                format!(
+                    "Task released from @{}'s queue to an idle dispatcher slot (work stealing)",
+                    stolen.from
+                ),
+            );
+        }
+        released.push(stolen);
+    }
+    released
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Node;
+    use crate::test_helpers::make_task;
+
+    fn who(name: &str, skills: Option<&[&str]>) -> Focuser {
+        Focuser {
+            name: name.into(),
+            agent_id: None,
+            skills: skills.map(|s| s.iter().map(|s| s.to_string()).collect()),
+        }
+    }
+
+    fn graph() -> WorkGraph {
+        let mut graph = WorkGraph::new();
+        for (id, priority, skill) in [
+            ("a", 50, "rust"),
+            ("b", 10, "rust"),
+            ("c", 10, "rust"),
+            ("d", 5, "python"),
+        ] {
+            let mut t = make_task(id, id);
+            t.priority = priority;
+            t.skills = vec![skill.into()];
+            graph.add_node(Node::Task(t));
+        }
+        graph
+    }
+
+    #[test]
+    fn test_claim_batch_claims_first_and_queues_rest() {
+        let mut graph = graph();
+        let ids = claim_batch(
+            &mut graph,
+            &who("bot", Some(&["rust"])),
+            5,
+            Utc::now(),
+            0,
+            Some("abc123"),
+        );
+        assert_eq!(ids, ["a", "b", "c"]);
+        let a = graph.get_task("a").unwrap();
+        assert_eq!(a.status, Status::InProgress);
+        assert_eq!(a.claim_sha.as_deref(), Some("abc123"));
+        let b = graph.get_task("b").unwrap();
+        assert_eq!(b.status, Status::Open);
+        assert!(b.claim_sha.is_none());
+        assert_eq!(b.assigned.as_deref(), Some("bot"));
+        assert!(graph.get_task("d").unwrap().assigned.is_none());
+
+        // Queued tasks are no longer claimable by anyone else.
+        assert!(
+            claim_batch(
+                &mut graph,
+                &who("other", Some(&["rust"])),
+                5,
+                Utc::now(),
+                0,
+                None
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_steal_takes_from_tail_of_longest_queue() {
+        let mut graph = graph();
+        claim_batch(
+            &mut graph,
+            &who("bot", Some(&["rust"])),
+            3,
+            Utc::now(),
+            0,
+            None,
+        );
+        let qs = queues(&graph, Utc::now(), 0);
+        assert_eq!(qs.len(), 1);
+        assert_eq!(qs[0].0, "bot");
+
+        assert!(
+            steal(
+                &mut graph,
+                &who("py", Some(&["python"])),
+                Utc::now(),
+                0,
+                None
+            )
+            .is_none()
+        );
+        let stolen = steal(
+            &mut graph,
+            &who("idle", None),
+            Utc::now(),
+            0,
+            Some("def456"),
+        )
+        .unwrap();
+        assert_eq!(
+            stolen,
+            Stolen {
+                task_id: "c".into(),
+                from: "bot".into()
+            }
+        );
+        let c = graph.get_task("c").unwrap();
+        assert_eq!(c.status, Status::InProgress);
+        assert_eq!(c.assigned.as_deref(), Some("idle"));
+        assert_eq!(c.claim_sha.as_deref(), Some("def456"));
+        // An actor never steals from itself.
+        assert!(steal(&mut graph, &who("bot", None), Utc::now(), 0, None).is_none());
+    }
+
+    #[test]
+    fn test_release_for_idle_slots_leaves_unassigned_work_first() {
+        let mut graph = graph();
+        claim_batch(
+            &mut graph,
+            &who("bot", Some(&["rust"])),
+            3,
+            Utc::now(),
+            0,
+            None,
+        );
+        // "d" is unassigned and ready, so one idle slot has work already.
+        assert!(release_for_idle_slots(&mut graph, 1, Utc::now(), 0).is_empty());
+
+        let released = release_for_idle_slots(&mut graph, 3, Utc::now(), 0);
+        let ids: Vec<&str> = released.iter().map(|s| s.task_id.as_str()).collect();
+        assert_eq!(ids, ["c", "b"]);
+        assert!(graph.get_task("b").unwrap().assigned.is_none());
+    }
+}
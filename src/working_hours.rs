@@ -0,0 +1,423 @@
+//! Working calendars: when human participants actually work.
+//!
+//! Forecasts count estimated hours as wall-clock time unless a calendar
+//! applies to the task's assignee. Calendars are configured in the
+//! `[working_hours]` section of `config.toml`: daily hours, working days,
+//! holidays, and a fixed UTC offset, with per-participant overrides keyed by
+//! the name used in a task's `assigned` (or `agent`) field.
+//!
+//! ```toml
+//! [working_hours]
+//! hours = "09:00-17:00"
+//! days = ["mon", "tue", "wed", "thu", "fri"]
+//! timezone = "+01:00"
+//! holidays = ["2026-12-24", "2026-12-25"]
+//!
+//! [working_hours.participants.alice]
+//! timezone = "-05:00"
+//!
+//! [working_hours.participants.bob]
+//! hours = "10:00-14:00"
+//! ```
+//!
+//! Only listed participants follow a calendar; set `everyone = true` to
+//! apply the default calendar to all assignees (agents included).
+
+use anyhow::{Context, Result, bail};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use crate::graph::Task;
+
+/// Upper bound on days scanned for the next working period, so a calendar
+/// made entirely of holidays can't loop forever.
+const MAX_SCAN_DAYS: u32 = 3660;
+
+/// One calendar as written in `config.toml`. Unset fields inherit from the
+/// default calendar (or the built-in 09:00-17:00, Monday to Friday, UTC).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CalendarSpec {
+    /// Daily working hours, `HH:MM-HH:MM` in local time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hours: Option<String>,
+    /// Working weekdays (`mon` .. `sun`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<Vec<String>>,
+    /// UTC offset such as `+02:00`, `-05:00`, or `UTC`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Non-working dates (`YYYY-MM-DD`, local). A participant's holidays add
+    /// to the default calendar's.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub holidays: Vec<String>,
+}
+
+/// The `[working_hours]` config section.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct WorkingHoursConfig {
+    #[serde(flatten)]
+    pub default: CalendarSpec,
+    /// Apply the default calendar to every assignee, not just listed ones
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub everyone: bool,
+    /// Per-participant overrides
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub participants: BTreeMap<String, CalendarSpec>,
+}
+
+impl WorkingHoursConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A resolved working calendar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkingCalendar {
+    offset: FixedOffset,
+    start: NaiveTime,
+    end: NaiveTime,
+    days: Vec<Weekday>,
+    holidays: HashSet<NaiveDate>,
+}
+
+impl WorkingCalendar {
+    fn from_spec(spec: &CalendarSpec, base: Option<&CalendarSpec>) -> Result<Self> {
+        let pick = |f: fn(&CalendarSpec) -> Option<&String>| {
+            f(spec).or_else(|| base.and_then(f)).map(String::as_str)
+        };
+        let (start, end) = parse_hours(pick(|s| s.hours.as_ref()).unwrap_or("09:00-17:00"))?;
+        let offset = parse_timezone(pick(|s| s.timezone.as_ref()).unwrap_or("UTC"))?;
+        let days = match spec.days.as_ref().or(base.and_then(|b| b.days.as_ref())) {
+            Some(days) => days
+                .iter()
+                .map(|d| {
+                    d.parse::<Weekday>()
+                        .map_err(|_| anyhow::anyhow!("Invalid working day '{}'", d))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+        };
+        if days.is_empty() {
+            bail!("Working calendar has no working days");
+        }
+        let holidays = base
+            .map(|b| b.holidays.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .chain(&spec.holidays)
+            .map(|h| {
+                NaiveDate::parse_from_str(h, "%Y-%m-%d")
+                    .with_context(|| format!("Invalid holiday '{}': expected YYYY-MM-DD", h))
+            })
+            .collect::<Result<HashSet<_>>>()?;
+        Ok(Self {
+            offset,
+            start,
+            end,
+            days,
+            holidays,
+        })
+    }
+
+    fn is_working_day(&self, date: NaiveDate) -> bool {
+        self.days.contains(&date.weekday()) && !self.holidays.contains(&date)
+    }
+
+    fn to_local(&self, at: DateTime<Utc>) -> NaiveDateTime {
+        at.with_timezone(&self.offset).naive_local()
+    }
+
+    fn to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        (local - self.offset_duration()).and_utc()
+    }
+
+    fn offset_duration(&self) -> Duration {
+        Duration::seconds(self.offset.local_minus_utc() as i64)
+    }
+
+    /// The first working instant at or after `at`.
+    pub fn next_working_time(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let mut local = self.to_local(at);
+        for _ in 0..MAX_SCAN_DAYS {
+            let date = local.date();
+            if self.is_working_day(date) && local.time() < self.end {
+                return self.to_utc(local.max(date.and_time(self.start)));
+            }
+            let Some(next) = date.succ_opt() else {
+                break;
+            };
+            local = next.and_time(NaiveTime::MIN);
+        }
+        at
+    }
+
+    /// When `hours` of work starting at `start` will be finished, counting
+    /// only working time.
+    pub fn add_working_hours(&self, start: DateTime<Utc>, hours: f64) -> DateTime<Utc> {
+        if !hours.is_finite() || hours <= 0.0 {
+            return start;
+        }
+        let mut remaining = Duration::seconds((hours * 3600.0) as i64);
+        let mut at = start;
+        for _ in 0..MAX_SCAN_DAYS {
+            at = self.next_working_time(at);
+            let local = self.to_local(at);
+            let block_end = self.to_utc(local.date().and_time(self.end));
+            let available = block_end - at;
+            if remaining <= available {
+                return at + remaining;
+            }
+            remaining -= available;
+            at = block_end;
+        }
+        at + remaining
+    }
+
+    /// The working block on the local date of `at`, or `None` on a day off.
+    pub fn day_window(&self, at: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let date = self.to_local(at).date();
+        self.is_working_day(date).then(|| {
+            (
+                self.to_utc(date.and_time(self.start)),
+                self.to_utc(date.and_time(self.end)),
+            )
+        })
+    }
+
+    /// The calendar's UTC offset.
+    pub fn offset(&self) -> FixedOffset {
+        self.offset
+    }
+
+    /// Working hours in a full week without holidays.
+    pub fn hours_per_week(&self) -> f64 {
+        (self.end - self.start).num_seconds() as f64 / 3600.0 * self.days.len() as f64
+    }
+}
+
+/// Resolved calendars for the whole project. The default value has none,
+/// so everything runs around the clock.
+#[derive(Debug, Clone, Default)]
+pub struct WorkingHours {
+    default: Option<WorkingCalendar>,
+    everyone: bool,
+    participants: HashMap<String, WorkingCalendar>,
+}
+
+impl WorkingHours {
+    /// Validate and resolve a `[working_hours]` section.
+    pub fn from_config(config: &WorkingHoursConfig) -> Result<Self> {
+        if config.is_default() {
+            return Ok(Self::default());
+        }
+        let default = WorkingCalendar::from_spec(&config.default, None)
+            .context("Invalid [working_hours] calendar")?;
+        let participants = config
+            .participants
+            .iter()
+            .map(|(who, spec)| {
+                WorkingCalendar::from_spec(spec, Some(&config.default))
+                    .with_context(|| format!("Invalid working hours for '{}'", who))
+                    .map(|cal| (who.clone(), cal))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+        Ok(Self {
+            default: Some(default),
+            everyone: config.everyone,
+            participants,
+        })
+    }
+
+    /// Load from the project config, treating an invalid section as absent
+    /// (with a warning).
+    pub fn load_or_warn(workgraph_dir: &Path) -> Self {
+        let config = crate::config::Config::load_or_default(workgraph_dir);
+        Self::from_config(&config.working_hours).unwrap_or_else(|e| {
+            eprintln!("Warning: {:#}", e);
+            Self::default()
+        })
+    }
+
+    /// Whether any calendar is configured.
+    pub fn is_configured(&self) -> bool {
+        self.default.is_some()
+    }
+
+    /// The calendar for a participant, if one applies.
+    pub fn for_participant(&self, who: &str) -> Option<&WorkingCalendar> {
+        self.participants
+            .get(who)
+            .or_else(|| self.default.as_ref().filter(|_| self.everyone))
+    }
+
+    /// The calendar for whoever executes `task`, if one applies. Unassigned
+    /// tasks use the default calendar only when it applies to everyone.
+    pub fn for_task(&self, task: &Task) -> Option<&WorkingCalendar> {
+        [task.assigned.as_deref(), task.agent.as_deref()]
+            .into_iter()
+            .flatten()
+            .find_map(|who| self.participants.get(who))
+            .or_else(|| self.default.as_ref().filter(|_| self.everyone))
+    }
+
+    /// When `hours` of `task`'s work starting at `start` will be finished.
+    pub fn finish(&self, task: &Task, start: DateTime<Utc>, hours: f64) -> DateTime<Utc> {
+        match self.for_task(task) {
+            Some(cal) => cal.add_working_hours(start, hours),
+            None => start + Duration::seconds((hours.max(0.0) * 3600.0) as i64),
+        }
+    }
+}
+
+/// Parse `HH:MM-HH:MM`.
+fn parse_hours(s: &str) -> Result<(NaiveTime, NaiveTime)> {
+    let parse = |t: &str| {
+        NaiveTime::parse_from_str(t.trim(), "%H:%M")
+            .with_context(|| format!("Invalid working hours '{}': expected HH:MM-HH:MM", s))
+    };
+    let (start, end) = s
+        .split_once('-')
+        .with_context(|| format!("Invalid working hours '{}': expected HH:MM-HH:MM", s))?;
+    let (start, end) = (parse(start)?, parse(end)?);
+    if end <= start {
+        bail!("Invalid working hours '{}': end must be after start", s);
+    }
+    Ok((start, end))
+}
+
+/// Parse `UTC`, `Z`, or an offset such as `+05:30` / `-0800`.
+fn parse_timezone(s: &str) -> Result<FixedOffset> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("utc") || s == "Z" {
+        return Ok(FixedOffset::east_opt(0).expect("zero offset"));
+    }
+    let invalid = || anyhow::anyhow!("Invalid timezone '{}': expected UTC or +HH:MM", s);
+    let (sign, rest) = match s.as_bytes().first() {
+        Some(b'+') => (1, &s[1..]),
+        Some(b'-') => (-1, &s[1..]),
+        _ => return Err(invalid()),
+    };
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let hours: i32 = digits[..2].parse().map_err(|_| invalid())?;
+    let minutes: i32 = digits[2..].parse().map_err(|_| invalid())?;
+    if minutes >= 60 {
+        return Err(invalid());
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn config(toml_src: &str) -> WorkingHoursConfig {
+        toml::from_str(toml_src).unwrap()
+    }
+
+    #[test]
+    fn test_add_working_hours_skips_nights_weekends_and_holidays() {
+        let hours = WorkingHours::from_config(&config(
+            r#"
+            everyone = true
+            holidays = ["2026-03-09"]
+            "#,
+        ))
+        .unwrap();
+        let cal = hours.for_participant("anyone").unwrap();
+        assert_eq!(cal.hours_per_week(), 40.0);
+
+        // Friday 15:00 + 4h: 2h Friday, weekend and Monday holiday skipped,
+        // 2h Tuesday morning.
+        assert_eq!(
+            cal.add_working_hours(at("2026-03-06T15:00:00Z"), 4.0),
+            at("2026-03-10T11:00:00Z")
+        );
+        // Saturday night snaps to Tuesday 09:00
+        assert_eq!(
+            cal.next_working_time(at("2026-03-07T22:00:00Z")),
+            at("2026-03-10T09:00:00Z")
+        );
+        assert_eq!(
+            cal.next_working_time(at("2026-03-10T10:30:00Z")),
+            at("2026-03-10T10:30:00Z")
+        );
+    }
+
+    #[test]
+    fn test_participant_override_inherits_and_applies_timezone() {
+        let hours = WorkingHours::from_config(&config(
+            r#"
+            hours = "09:00-17:00"
+            timezone = "+02:00"
+
+            [participants.alice]
+            timezone = "-05:00"
+            "#,
+        ))
+        .unwrap();
+        assert!(hours.for_participant("bob").is_none());
+        let alice = hours.for_participant("alice").unwrap();
+        // 09:00 in UTC-5 is 14:00 UTC
+        assert_eq!(
+            alice.next_working_time(at("2026-03-02T08:00:00Z")),
+            at("2026-03-02T14:00:00Z")
+        );
+
+        let mut task = Task {
+            id: "t".to_string(),
+            assigned: Some("alice".to_string()),
+            ..Task::default()
+        };
+        assert_eq!(
+            hours.finish(&task, at("2026-03-02T08:00:00Z"), 9.0),
+            at("2026-03-03T15:00:00Z")
+        );
+        task.assigned = Some("agent-1".to_string());
+        assert_eq!(
+            hours.finish(&task, at("2026-03-02T08:00:00Z"), 9.0),
+            at("2026-03-02T17:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_invalid_calendars_rejected() {
+        for bad in [
+            r#"hours = "17:00-09:00""#,
+            r#"hours = "nine to five""#,
+            r#"timezone = "Europe/Berlin""#,
+            r#"days = []"#,
+            r#"days = ["funday"]"#,
+            r#"holidays = ["christmas"]"#,
+        ] {
+            assert!(
+                WorkingHours::from_config(&config(bad)).is_err(),
+                "accepted {}",
+                bad
+            );
+        }
+        assert!(
+            !WorkingHours::from_config(&WorkingHoursConfig::default())
+                .unwrap()
+                .is_configured()
+        );
+    }
+}
@@ -75,6 +75,8 @@ impl TestFixture {
                 attractor_weight: 0.5,
                 deployment_history: vec![],
                 staleness_flags: vec![],
+                capability_doc: None,
+                daily_budget: None,
             };
             agency::save_agent(&agent, &agency_dir.join("cache/agents")).unwrap();
         }
@@ -496,6 +496,8 @@ fn test_full_agency_lifecycle_new_design() {
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     let agents_dir = agency_dir.join("cache/agents");
     agency::save_agent(&agent, &agents_dir).unwrap();
@@ -579,6 +581,8 @@ fn test_full_agency_lifecycle_new_design() {
         child_tasks: &[],
         constraint_fidelity_score: None,
         constraint_fidelity_unanchored: None,
+        test_results: None,
+        rubric: None,
     });
     assert!(evaluator_prompt.contains("integration-test-agent"));
     assert!(evaluator_prompt.contains("Integration Implementer"));
@@ -875,6 +879,8 @@ fn test_full_agency_lifecycle_new_design() {
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     agency::save_agent(&evolved_agent, &agents_dir).unwrap();
 
@@ -1085,6 +1091,8 @@ fn test_agent_independent_performance() {
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     let agent_b = Agent {
         id: agent_b_id.clone(),
@@ -1104,6 +1112,8 @@ fn test_agent_independent_performance() {
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     agency::save_agent(&agent_a, &agents_dir).unwrap();
     agency::save_agent(&agent_b, &agents_dir).unwrap();
@@ -45,6 +45,8 @@ fn test_create_agent_with_nonexistent_role_and_motivation() {
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
 
     // Save succeeds even though the role/motivation don't exist
@@ -220,6 +222,8 @@ fn test_delete_role_referenced_by_agent() {
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     agency::save_agent(&agent, &agents_dir).unwrap();
 
@@ -305,6 +309,8 @@ fn test_delete_motivation_referenced_by_agent() {
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     agency::save_agent(&agent, &agents_dir).unwrap();
 
@@ -646,6 +652,8 @@ fn test_prefix_lookup_zero_matches() {
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     agency::save_agent(&agent, &agents_dir).unwrap();
 
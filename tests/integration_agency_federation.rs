@@ -39,6 +39,7 @@ fn make_role(id: &str, name: &str) -> Role {
         lineage: Lineage::default(),
         default_context_scope: None,
         default_exec_mode: None,
+        tool_policy: Default::default(),
     }
 }
 
@@ -84,6 +85,8 @@ fn make_agent(id: &str, name: &str, role_id: &str, tradeoff_id: &str) -> Agent {
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     }
 }
 
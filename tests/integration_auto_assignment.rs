@@ -110,6 +110,8 @@ fn setup_agency(dir: &Path) -> (String, String, String) {
         attractor_weight: 0.5,
         deployment_history: vec![],
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     agency::save_agent(&agent, &agency_dir.join("cache/agents")).unwrap();
 
@@ -157,6 +159,8 @@ fn setup_second_agent(dir: &Path) -> String {
         attractor_weight: 0.5,
         deployment_history: vec![],
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     agency::save_agent(&agent, &agency_dir.join("cache/agents")).unwrap();
 
@@ -235,19 +239,25 @@ fn build_assign_subgraph(dir: &Path) {
             estimate: None,
             before: vec![ready_task.id.clone()],
             after: vec![],
+            edges: Default::default(),
             requires: vec![],
             tags: vec!["assignment".to_string(), "agency".to_string()],
             skills: vec![],
             inputs: vec![],
             deliverables: vec![],
+            expects: vec![],
+            gate: None,
             artifacts: vec![],
+            self_report: None,
             exec: None,
             timeout: None,
             not_before: None,
+            due: None,
             created_at: Some(chrono::Utc::now().to_rfc3339()),
             started_at: None,
             completed_at: None,
             last_interaction_at: None,
+            revision: 0,
             log: vec![],
             retry_count: 0,
             max_retries: None,
@@ -272,10 +282,20 @@ fn build_assign_subgraph(dir: &Path) {
             exec_mode: None,
             cycle_config: None,
             token_usage: None,
+            max_cost: None,
+            min_context: None,
+            claim_sha: None,
+            changes: None,
             session_id: None,
             wait_condition: None,
             checkpoint: None,
             triage_count: 0,
+            escalation_level: 0,
+            blocked_at: None,
+            deadline_alert: None,
+            external_blocker: None,
+            waiting_on: vec![],
+            links: vec![],
             resurrection_count: 0,
             last_resurrected_at: None,
             validation: None,
@@ -846,6 +866,8 @@ fn test_assigned_agent_appears_in_rendered_prompt() {
         attractor_weight: 0.5,
         deployment_history: vec![],
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     agency::save_agent(&agent, &agency_dir.join("cache/agents")).unwrap();
 
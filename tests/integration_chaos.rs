@@ -0,0 +1,446 @@
+//! Chaos tests: run the service daemon with `WG_CHAOS` fault injection and
+//! check that it recovers.
+//!
+//! Failure matrix:
+//! 1. Killed agents: dead-agent detection reclaims the task and a new agent
+//!    retries it
+//! 2. Corrupted heartbeats (stale, future, garbage): the service keeps
+//!    ticking and tasks still complete
+//! 3. Delayed IPC responses: clients are still served and notifications
+//!    still lead to dispatch
+//! 4. All of the above at once, seeded: every task converges to done
+//!
+//! These tests run serially because each spawns daemon and agent processes
+//! that are sensitive to CPU/scheduling contention under parallel execution.
+//!
+//! Run with: cargo test --features chaos --test integration_chaos
+#![cfg(feature = "chaos")]
+
+use serial_test::serial;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Get the path to the compiled `wg` binary (from target/debug or target/release).
+fn wg_binary() -> PathBuf {
+    let mut path = std::env::current_exe().expect("could not get current exe path");
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.push("wg");
+    assert!(
+        path.exists(),
+        "wg binary not found at {:?}. Run `cargo build` first.",
+        path
+    );
+    path
+}
+
+/// Derive a fake HOME from the wg_dir path so global config doesn't leak in.
+fn fake_home_for(wg_dir: &Path) -> PathBuf {
+    wg_dir
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| wg_dir.to_path_buf())
+}
+
+/// Skip under CI or high load: chaos runs depend on daemon tick timing.
+fn should_skip_timing_tests() -> bool {
+    if std::env::var("CI").is_ok() || std::env::var("GITHUB_ACTIONS").is_ok() {
+        return true;
+    }
+    if let Ok(loadavg) = fs::read_to_string("/proc/loadavg")
+        && let Some(load) = loadavg
+            .split_whitespace()
+            .next()
+            .and_then(|l| l.parse::<f64>().ok())
+    {
+        return load > 2.0;
+    }
+    false
+}
+
+/// Helper: run `wg` with given args and extra environment.
+fn wg_cmd_env(wg_dir: &Path, args: &[&str], env: &[(&str, &str)]) -> std::process::Output {
+    Command::new(wg_binary())
+        .arg("--dir")
+        .arg(wg_dir)
+        .args(args)
+        .env("HOME", fake_home_for(wg_dir))
+        .envs(env.iter().copied())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run wg {:?}: {}", args, e))
+}
+
+/// Helper: run `wg` and assert success, returning stdout as string.
+fn wg_ok(wg_dir: &Path, args: &[&str]) -> String {
+    let output = wg_cmd_env(wg_dir, args, &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(
+        output.status.success(),
+        "wg {:?} failed.\nstdout: {}\nstderr: {}",
+        args,
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    stdout
+}
+
+/// Helper: initialize a graph with a shell executor whose wrapper resolves
+/// bare `wg` to the binary under test.
+fn setup_workgraph(tmp_root: &Path) -> PathBuf {
+    let wg_dir = tmp_root.join(".wg");
+    wg_ok(&wg_dir, &["init", "--route", "claude-cli"]);
+
+    let config_content = r#"
+[agent]
+heartbeat_timeout = 1
+reaper_grace_seconds = 0
+
+[coordinator]
+max_agents = 3
+poll_interval = 2
+executor = "shell"
+
+[agency]
+auto_assign = false
+auto_evaluate = false
+"#;
+    fs::write(wg_dir.join("config.toml"), config_content).unwrap();
+
+    let wg_bin_dir = wg_binary().parent().unwrap().to_string_lossy().to_string();
+    let path_with_test_binary = format!(
+        "{}:{}",
+        wg_bin_dir,
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let executors_dir = wg_dir.join("executors");
+    fs::create_dir_all(&executors_dir).unwrap();
+    let shell_config = format!(
+        r#"[executor]
+type = "shell"
+command = "bash"
+args = ["-c", "{{{{task_context}}}}"]
+working_dir = "{}"
+
+[executor.env]
+TASK_ID = "{{{{task_id}}}}"
+TASK_TITLE = "{{{{task_title}}}}"
+PATH = "{}"
+"#,
+        tmp_root.display(),
+        path_with_test_binary
+    );
+    fs::write(executors_dir.join("shell.toml"), shell_config).unwrap();
+
+    wg_dir
+}
+
+/// Helper: start the daemon with the given `WG_CHAOS` spec.
+fn start_chaos_service(tmp_root: &Path, wg_dir: &Path, chaos: &str) {
+    let socket = format!("{}/wg-test.sock", tmp_root.display());
+    let output = wg_cmd_env(
+        wg_dir,
+        &[
+            "service",
+            "start",
+            "--socket",
+            &socket,
+            "--executor",
+            "shell",
+            "--max-agents",
+            "3",
+            "--interval",
+            "2",
+        ],
+        &[("WG_CHAOS", chaos)],
+    );
+    assert!(
+        output.status.success(),
+        "Service did not start: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        wait_for(Duration::from_secs(5), 100, || send_ipc(wg_dir, "status")),
+        "Service daemon socket did not become ready"
+    );
+    assert!(
+        daemon_log(wg_dir).contains("Chaos mode enabled"),
+        "Daemon did not report chaos mode"
+    );
+}
+
+/// Helper: add a task with a shell exec command.
+fn add_shell_task(wg_dir: &Path, task_id: &str, exec_cmd: &str) {
+    wg_ok(wg_dir, &["add", task_id, "--id", task_id, "--immediate"]);
+    let graph_path = wg_dir.join("graph.jsonl");
+    let content = fs::read_to_string(&graph_path).unwrap();
+    let lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if line.contains(&format!("\"id\":\"{}\"", task_id)) {
+                let mut val: serde_json::Value = serde_json::from_str(line).unwrap();
+                val["exec"] = serde_json::Value::String(exec_cmd.to_string());
+                serde_json::to_string(&val).unwrap()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    fs::write(&graph_path, lines.join("\n") + "\n").unwrap();
+}
+
+/// Helper: `wg show <id> --json`, or Null on any error.
+fn show_task(wg_dir: &Path, task_id: &str) -> serde_json::Value {
+    let output = wg_cmd_env(wg_dir, &["show", task_id, "--json"], &[]);
+    serde_json::from_slice(&output.stdout).unwrap_or(serde_json::Value::Null)
+}
+
+fn task_status(wg_dir: &Path, task_id: &str) -> String {
+    show_task(wg_dir, task_id)["status"]
+        .as_str()
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// Helper: send a bare IPC command; true if a response came back.
+fn send_ipc(wg_dir: &Path, cmd: &str) -> bool {
+    let state_path = wg_dir.join("service").join("state.json");
+    if let Ok(content) = fs::read_to_string(&state_path)
+        && let Ok(state) = serde_json::from_str::<serde_json::Value>(&content)
+        && let Some(socket_path) = state["socket_path"].as_str()
+        && let Ok(mut stream) = std::os::unix::net::UnixStream::connect(socket_path)
+    {
+        let _ = writeln!(stream, r#"{{"cmd":"{}"}}"#, cmd);
+        let _ = stream.flush();
+        let mut response = String::new();
+        let mut reader = BufReader::new(&stream);
+        return reader.read_line(&mut response).is_ok() && !response.is_empty();
+    }
+    false
+}
+
+fn read_registry(wg_dir: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(wg_dir.join("service").join("registry.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Agent IDs ever registered for `task_id`.
+fn agents_for(wg_dir: &Path, task_id: &str) -> Vec<String> {
+    read_registry(wg_dir)
+        .and_then(|r| {
+            r["agents"].as_object().map(|agents| {
+                agents
+                    .values()
+                    .filter(|a| a["task_id"].as_str() == Some(task_id))
+                    .filter_map(|a| a["id"].as_str().map(String::from))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+fn daemon_log(wg_dir: &Path) -> String {
+    fs::read_to_string(wg_dir.join("service").join("daemon.log")).unwrap_or_default()
+}
+
+/// Guard that stops the daemon (and its agents) even if a test panics.
+struct ServiceGuard<'a> {
+    wg_dir: &'a Path,
+}
+
+impl Drop for ServiceGuard<'_> {
+    fn drop(&mut self) {
+        let _ = wg_cmd_env(
+            self.wg_dir,
+            &["service", "stop", "--force", "--kill-agents"],
+            &[],
+        );
+        let state_path = self.wg_dir.join("service").join("state.json");
+        if let Ok(content) = fs::read_to_string(&state_path)
+            && let Ok(state) = serde_json::from_str::<serde_json::Value>(&content)
+            && let Some(pid) = state["pid"].as_u64()
+        {
+            unsafe {
+                libc::kill(pid as i32, libc::SIGKILL);
+            }
+        }
+    }
+}
+
+/// Helper: wait for a condition with timeout, polling at interval.
+fn wait_for<F>(timeout: Duration, poll_ms: u64, mut condition: F) -> bool
+where
+    F: FnMut() -> bool,
+{
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        if condition() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(poll_ms));
+    }
+    false
+}
+
+/// Wait until every task is done, nudging the coordinator meanwhile.
+fn wait_all_done(wg_dir: &Path, ids: &[&str], timeout: Duration) -> bool {
+    wait_for(timeout, 500, || {
+        if ids.iter().all(|id| task_status(wg_dir, id) == "done") {
+            return true;
+        }
+        send_ipc(wg_dir, "graph_changed");
+        false
+    })
+}
+
+/// Matrix 1: every live agent is killed before each tick. The killed agent
+/// must be detected as dead, its task reclaimed, and a new agent spawned.
+#[test]
+#[serial]
+fn test_chaos_killed_agents_are_reclaimed_and_retried() {
+    if should_skip_timing_tests() {
+        eprintln!("Skipping chaos test: unsuitable environment for timing-sensitive tests");
+        return;
+    }
+    let tmp = tempfile::tempdir().unwrap();
+    let wg_dir = setup_workgraph(tmp.path());
+    let _guard = ServiceGuard { wg_dir: &wg_dir };
+    start_chaos_service(tmp.path(), &wg_dir, "kill=1,seed=1");
+
+    add_shell_task(&wg_dir, "victim", "sleep 60");
+    send_ipc(&wg_dir, "graph_changed");
+
+    let retried = wait_for(Duration::from_secs(30), 300, || {
+        send_ipc(&wg_dir, "graph_changed");
+        agents_for(&wg_dir, "victim").len() >= 2
+    });
+    assert!(
+        retried,
+        "Killed agent was not replaced. Agents: {:?}",
+        agents_for(&wg_dir, "victim")
+    );
+
+    let task = show_task(&wg_dir, "victim");
+    let log = task["log"].to_string();
+    assert!(
+        log.contains("process exited"),
+        "Task log should record the reclaim: {}",
+        log
+    );
+    assert!(daemon_log(&wg_dir).contains("Chaos: killed"));
+    let registry = read_registry(&wg_dir).expect("registry should parse");
+    assert!(
+        registry["agents"]
+            .as_object()
+            .unwrap()
+            .values()
+            .any(|a| a["task_id"] == "victim" && a["status"] == "dead"),
+        "Killed agent should be marked dead"
+    );
+}
+
+/// Matrix 2: every heartbeat is corrupted before each tick. Agents whose
+/// heartbeat looks stale are reaped and their tasks retried; future and
+/// unparseable heartbeats must not wedge the service. Tasks still finish.
+#[test]
+#[serial]
+fn test_chaos_corrupted_heartbeats_do_not_wedge_service() {
+    if should_skip_timing_tests() {
+        eprintln!("Skipping chaos test: unsuitable environment for timing-sensitive tests");
+        return;
+    }
+    let tmp = tempfile::tempdir().unwrap();
+    let wg_dir = setup_workgraph(tmp.path());
+    let _guard = ServiceGuard { wg_dir: &wg_dir };
+    start_chaos_service(tmp.path(), &wg_dir, "heartbeat=1,seed=2");
+
+    add_shell_task(&wg_dir, "hb-a", "sleep 3");
+    add_shell_task(&wg_dir, "hb-b", "sleep 3");
+    send_ipc(&wg_dir, "graph_changed");
+
+    assert!(
+        wait_all_done(&wg_dir, &["hb-a", "hb-b"], Duration::from_secs(60)),
+        "Tasks did not complete under heartbeat corruption: hb-a={}, hb-b={}",
+        task_status(&wg_dir, "hb-a"),
+        task_status(&wg_dir, "hb-b")
+    );
+    assert!(daemon_log(&wg_dir).contains("Chaos: corrupted heartbeat"));
+    assert!(
+        read_registry(&wg_dir).is_some(),
+        "Registry must stay readable"
+    );
+    assert!(send_ipc(&wg_dir, "status"), "Daemon should still respond");
+}
+
+/// Matrix 3: every IPC response is held back. Clients must still get
+/// answers, and graph-change notifications must still lead to dispatch.
+#[test]
+#[serial]
+fn test_chaos_delayed_ipc_still_serves_clients() {
+    if should_skip_timing_tests() {
+        eprintln!("Skipping chaos test: unsuitable environment for timing-sensitive tests");
+        return;
+    }
+    let tmp = tempfile::tempdir().unwrap();
+    let wg_dir = setup_workgraph(tmp.path());
+    let _guard = ServiceGuard { wg_dir: &wg_dir };
+    start_chaos_service(tmp.path(), &wg_dir, "ipc_delay=1,ipc_delay_ms=400");
+
+    let start = Instant::now();
+    let status = wg_ok(&wg_dir, &["service", "status", "--json"]);
+    assert!(
+        start.elapsed() >= Duration::from_millis(400),
+        "Status response was not delayed ({:?})",
+        start.elapsed()
+    );
+    assert!(!status.is_empty());
+
+    add_shell_task(&wg_dir, "slow-ipc", "true");
+    send_ipc(&wg_dir, "graph_changed");
+    assert!(
+        wait_all_done(&wg_dir, &["slow-ipc"], Duration::from_secs(30)),
+        "Task did not complete with delayed IPC: {}",
+        task_status(&wg_dir, "slow-ipc")
+    );
+    assert!(daemon_log(&wg_dir).contains("Chaos: delaying IPC response"));
+}
+
+/// Matrix 4: all faults at once with a fixed seed. Work must converge.
+#[test]
+#[serial]
+fn test_chaos_failure_matrix_converges() {
+    if should_skip_timing_tests() {
+        eprintln!("Skipping chaos test: unsuitable environment for timing-sensitive tests");
+        return;
+    }
+    let tmp = tempfile::tempdir().unwrap();
+    let wg_dir = setup_workgraph(tmp.path());
+    let _guard = ServiceGuard { wg_dir: &wg_dir };
+    start_chaos_service(
+        tmp.path(),
+        &wg_dir,
+        "kill=0.3,heartbeat=0.3,ipc_delay=0.3,ipc_delay_ms=200,seed=7",
+    );
+
+    let ids = ["mix-a", "mix-b", "mix-c", "mix-d"];
+    for id in ids {
+        add_shell_task(&wg_dir, id, "sleep 1");
+    }
+    send_ipc(&wg_dir, "graph_changed");
+
+    assert!(
+        wait_all_done(&wg_dir, &ids, Duration::from_secs(120)),
+        "Tasks did not converge under chaos: {:?}",
+        ids.iter()
+            .map(|id| (*id, task_status(&wg_dir, id)))
+            .collect::<Vec<_>>()
+    );
+    assert!(send_ipc(&wg_dir, "status"), "Daemon should still respond");
+}
@@ -0,0 +1,99 @@
+//! Integration tests for `wg config diff`: the effective config compared
+//! with a committed `workgraph.toml` baseline or the built-in defaults.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+fn wg_binary() -> PathBuf {
+    let mut path = std::env::current_exe().expect("current_exe");
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.push("wg");
+    assert!(path.exists(), "wg binary not found at {:?}", path);
+    path
+}
+
+/// Run wg with an empty HOME so no global config leaks in.
+fn wg(wg_dir: &Path, home: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(wg_binary())
+        .arg("--dir")
+        .arg(wg_dir)
+        .args(args)
+        .env("HOME", home)
+        .env_remove("WG_DIR")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("spawn wg")
+}
+
+fn setup() -> (TempDir, PathBuf, PathBuf) {
+    let tmp = TempDir::new().unwrap();
+    let home = tmp.path().join("home");
+    fs::create_dir_all(&home).unwrap();
+    let wg_dir = tmp.path().join("project").join(".wg");
+    fs::create_dir_all(&wg_dir).unwrap();
+    fs::write(wg_dir.join("graph.jsonl"), "").unwrap();
+    fs::write(
+        wg_dir.join("config.toml"),
+        "[dispatcher]\nmax_agents = 12\n",
+    )
+    .unwrap();
+    (tmp, home, wg_dir)
+}
+
+fn changes(output: &std::process::Output) -> Vec<serde_json::Value> {
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let body: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    body["changes"].as_array().unwrap().clone()
+}
+
+#[test]
+fn test_diff_against_committed_baseline() {
+    let (_tmp, home, wg_dir) = setup();
+    let project = wg_dir.parent().unwrap();
+
+    let missing = wg(&wg_dir, &home, &["config", "diff"]);
+    assert!(!missing.status.success());
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("No baseline at"));
+
+    fs::write(
+        project.join("workgraph.toml"),
+        "[dispatcher]\nmax_agents = 4\n",
+    )
+    .unwrap();
+    let diff = changes(&wg(&wg_dir, &home, &["--json", "config", "diff"]));
+    assert_eq!(diff.len(), 1, "{:?}", diff);
+    assert_eq!(diff[0]["key"], "dispatcher.max_agents");
+    assert_eq!(diff[0]["baseline"], 4);
+    assert_eq!(diff[0]["effective"], 12);
+
+    fs::write(
+        project.join("workgraph.toml"),
+        "[dispatcher]\nmax_agents = 12\n",
+    )
+    .unwrap();
+    let output = wg(&wg_dir, &home, &["config", "diff"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Effective config matches"));
+}
+
+#[test]
+fn test_diff_against_defaults() {
+    let (_tmp, home, wg_dir) = setup();
+    let diff = changes(&wg(
+        &wg_dir,
+        &home,
+        &["--json", "config", "diff", "--against", "defaults"],
+    ));
+    let keys: Vec<&str> = diff.iter().filter_map(|c| c["key"].as_str()).collect();
+    assert_eq!(keys, vec!["dispatcher.max_agents"]);
+}
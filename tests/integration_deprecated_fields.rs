@@ -0,0 +1,79 @@
+//! Integration tests for renamed-field deprecation warnings and
+//! `wg migrate fields`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+/// Find the wg binary for CLI testing
+fn wg_binary() -> PathBuf {
+    let mut path = std::env::current_exe().expect("could not get current exe path");
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.push("wg");
+    assert!(
+        path.exists(),
+        "wg binary not found at {:?}. Run `cargo build` first.",
+        path
+    );
+    path
+}
+
+/// Run a wg command and return output
+fn wg_cmd(wg_dir: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(wg_binary())
+        .arg("--dir")
+        .arg(wg_dir)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run wg {:?}: {}", args, e))
+}
+
+#[test]
+fn test_renamed_fields_warn_until_migrated() {
+    let tmp = TempDir::new().unwrap();
+    let wg_dir = tmp.path().join(".wg");
+    fs::create_dir_all(&wg_dir).unwrap();
+    fs::write(
+        wg_dir.join("graph.jsonl"),
+        concat!(
+            r#"{"kind":"task","id":"a","title":"A","status":"open"}"#,
+            "\n",
+            r#"{"kind":"task","id":"b","title":"B","status":"open","blocked_by":["a"]}"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+
+    let output = wg_cmd(&wg_dir, &["list"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            "graph.jsonl uses deprecated field names: `blocked_by` (now `after`) in 1 task"
+        ),
+        "stderr: {}",
+        stderr
+    );
+    assert_eq!(stderr.matches("deprecated field names").count(), 1);
+
+    let output = wg_cmd(&wg_dir, &["migrate", "fields"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Warning"));
+
+    let output = wg_cmd(&wg_dir, &["show", "b"]);
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("deprecated field names"));
+    let graph = fs::read_to_string(wg_dir.join("graph.jsonl")).unwrap();
+    assert!(graph.contains(r#""after":["a"]"#), "{}", graph);
+}
@@ -52,6 +52,8 @@ fn test_failure_class_display() {
     assert_eq!(AgentHardTimeout.to_string(), "agent-hard-timeout");
     assert_eq!(AgentExitNonzero.to_string(), "agent-exit-nonzero");
     assert_eq!(WrapperInternal.to_string(), "wrapper-internal");
+    assert_eq!(ToolPolicyViolation.to_string(), "tool-policy-violation");
+    assert_eq!(CostCapExceeded.to_string(), "cost-cap-exceeded");
 }
 
 /// Verify legacy rows (no failure_class field in JSON) deserialize with None.
@@ -82,6 +84,8 @@ fn test_failure_class_serde_round_trip() {
         AgentHardTimeout,
         AgentExitNonzero,
         WrapperInternal,
+        ToolPolicyViolation,
+        CostCapExceeded,
     ];
 
     for class in classes {
@@ -449,6 +449,8 @@ fn rotation_triggers_under_high_volume() {
             actor: None,
             user: None,
             detail: serde_json::json!({ "index": i }),
+            prev_hash: None,
+            hash: None,
         };
         provenance::append_operation(&wg_dir, &entry, threshold).unwrap();
     }
@@ -491,6 +493,8 @@ fn rotated_zstd_files_can_be_read_back() {
             actor: Some("test-actor".to_string()),
             user: None,
             detail: serde_json::json!({ "n": i }),
+            prev_hash: None,
+            hash: None,
         };
         provenance::append_operation(&wg_dir, &entry, threshold).unwrap();
     }
@@ -549,6 +553,8 @@ fn concurrent_writes_to_operation_log() {
                         actor: Some(format!("thread-{}", tid)),
                         user: None,
                         detail: serde_json::Value::Null,
+                        prev_hash: None,
+                        hash: None,
                     };
                     // Use large threshold to avoid rotation complicating things
                     provenance::append_operation(&wg, &entry, 100 * 1024 * 1024).unwrap();
@@ -541,6 +541,8 @@ fn adapter_claude_writes_notification_file() {
         model: None,
         completed_at: None,
         worktree_path: None,
+        preempted_by: None,
+        paused_task: false,
     };
 
     // Deliver message via the full deliver_message path
@@ -596,6 +598,8 @@ fn adapter_shell_writes_notification_file() {
         model: None,
         completed_at: None,
         worktree_path: None,
+        preempted_by: None,
+        paused_task: false,
     };
 
     let (msg_id, delivered) = messages::deliver_message(
@@ -659,6 +663,8 @@ fn adapter_notification_accumulates_multiple_messages() {
         model: None,
         completed_at: None,
         worktree_path: None,
+        preempted_by: None,
+        paused_task: false,
     };
 
     // Deliver 5 messages
@@ -904,6 +910,8 @@ fn coordinator_deliver_message_stores_and_notifies() {
         model: None,
         completed_at: None,
         worktree_path: None,
+        preempted_by: None,
+        paused_task: false,
     };
 
     // Coordinator delivers a message
@@ -952,6 +960,8 @@ fn coordinator_multiple_deliveries_across_tasks() {
         model: None,
         completed_at: None,
         worktree_path: None,
+        preempted_by: None,
+        paused_task: false,
     };
 
     let agent_b = workgraph::service::registry::AgentEntry {
@@ -966,6 +976,8 @@ fn coordinator_multiple_deliveries_across_tasks() {
         model: None,
         completed_at: None,
         worktree_path: None,
+        preempted_by: None,
+        paused_task: false,
     };
 
     // Deliver to both tasks
@@ -16,6 +16,7 @@ use workgraph::config::{
     EndpointConfig, EndpointsConfig, ModelRoutingConfig, RoleModelConfig,
 };
 use workgraph::executor::native::client::AnthropicClient;
+use workgraph::executor::native::ollama_client::OllamaClient;
 use workgraph::executor::native::openai_client::OpenAiClient;
 use workgraph::executor::native::provider::{create_provider, create_provider_ext};
 use workgraph::models::{ModelEntry, ModelRegistry, ModelTier};
@@ -767,6 +768,47 @@ async fn test_openai_provider_send_via_mock() {
     assert_eq!(response.usage.output_tokens, 5);
 }
 
+#[tokio::test]
+async fn test_ollama_provider_send_via_mock() {
+    let mock_body = r#"{"model":"qwen3:8b","created_at":"2026-10-15T10:00:00Z","message":{"role":"assistant","content":"hello from qwen3"},"done":true,"done_reason":"stop","prompt_eval_count":42,"eval_count":7}"#;
+    let (base_url, paths) = start_recording_mock_server(mock_body.to_string(), 1);
+
+    // Endpoints store the OpenAI-compatible URL; the client drops `/v1`.
+    let client = OllamaClient::new("qwen3:8b", Some(&format!("{}/v1", base_url))).unwrap();
+
+    use workgraph::executor::native::provider::Provider;
+    let request = workgraph::executor::native::client::MessagesRequest {
+        model: "qwen3:8b".to_string(),
+        max_tokens: 100,
+        system: None,
+        messages: vec![workgraph::executor::native::client::Message {
+            role: workgraph::executor::native::client::Role::User,
+            content: vec![workgraph::executor::native::client::ContentBlock::Text {
+                text: "test".to_string(),
+            }],
+        }],
+        tools: vec![],
+        stream: false,
+    };
+
+    let response = client.send(&request).await.unwrap();
+    assert_eq!(paths.lock().unwrap().as_slice(), ["/api/chat"]);
+    assert_eq!(response.usage.input_tokens, 42);
+    assert_eq!(response.usage.output_tokens, 7);
+}
+
+#[test]
+fn test_create_provider_ollama_prefix_uses_native_api() {
+    let tmp = setup_workgraph_dir();
+    let provider = create_provider(tmp.path(), "ollama:qwen3:8b").unwrap();
+    assert_eq!(provider.name(), "ollama");
+    assert_eq!(provider.model(), "qwen3:8b");
+
+    // Other local servers keep the OpenAI-compatible client.
+    let provider = create_provider(tmp.path(), "vllm:qwen3-coder").unwrap();
+    assert_eq!(provider.name(), "local");
+}
+
 // ── All dispatch roles resolve ──────────────────────────────────────────
 
 #[test]
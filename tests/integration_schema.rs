@@ -0,0 +1,82 @@
+//! Integration tests for `wg schema`: JSON Schemas for the on-disk formats.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+/// Find the wg binary for CLI testing
+fn wg_binary() -> PathBuf {
+    let mut path = std::env::current_exe().expect("could not get current exe path");
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.push("wg");
+    assert!(
+        path.exists(),
+        "wg binary not found at {:?}. Run `cargo build` first.",
+        path
+    );
+    path
+}
+
+/// Run a wg command and return output
+fn wg_cmd(wg_dir: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(wg_binary())
+        .arg("--dir")
+        .arg(wg_dir)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run wg {:?}: {}", args, e))
+}
+
+#[test]
+fn test_schema_prints_json_schema_for_each_kind() {
+    let tmp = TempDir::new().unwrap();
+    for (kind, title) in [
+        ("task", "Task"),
+        ("function", "TraceFunction"),
+        ("config", "Config"),
+        ("run-summary", "RunSummary"),
+    ] {
+        let output = wg_cmd(tmp.path(), &["schema", kind]);
+        assert!(
+            output.status.success(),
+            "wg schema {}: {}",
+            kind,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(schema["title"], title);
+    }
+}
+
+#[test]
+fn test_schema_lists_kinds_and_rejects_unknown() {
+    let tmp = TempDir::new().unwrap();
+    let output = wg_cmd(tmp.path(), &["--json", "schema"]);
+    assert!(output.status.success());
+    let kinds: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(kinds.as_array().unwrap().len(), 4);
+
+    let output = wg_cmd(tmp.path(), &["schema", "agent"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown schema kind 'agent'"));
+}
+
+#[test]
+fn test_schema_output_writes_file() {
+    let tmp = TempDir::new().unwrap();
+    let path = tmp.path().join("config.schema.json");
+    let output = wg_cmd(
+        tmp.path(),
+        &["schema", "config", "--output", path.to_str().unwrap()],
+    );
+    assert!(output.status.success());
+    let schema: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert!(schema["properties"]["dispatcher"].is_object());
+}
@@ -64,6 +64,8 @@ fn make_agent_entry(id: &str, pid: u32, task_id: &str, status: AgentStatus) -> A
         model: None,
         completed_at: None,
         worktree_path: None,
+        preempted_by: None,
+        paused_task: false,
     }
 }
 
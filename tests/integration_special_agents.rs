@@ -58,6 +58,8 @@ fn compose_special_agents_and_config(wg_dir: &std::path::Path, agency_dir: &std:
             deployment_history: vec![],
             attractor_weight: 0.5,
             staleness_flags: vec![],
+            capability_doc: None,
+            daily_budget: None,
         };
         agency::save_agent(&agent, &agents_dir).unwrap();
     }
@@ -104,6 +106,8 @@ fn compose_special_agents_and_config(wg_dir: &std::path::Path, agency_dir: &std:
                 deployment_history: vec![],
                 attractor_weight: 0.5,
                 staleness_flags: vec![],
+                capability_doc: None,
+                daily_budget: None,
             };
             agency::save_agent(&agent, &agents_dir).unwrap();
         }
@@ -0,0 +1,98 @@
+//! Integration tests for strict mode (`--strict` / `project.strict_fields`):
+//! unknown fields in graph.jsonl fail the load instead of being dropped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+/// Find the wg binary for CLI testing
+fn wg_binary() -> PathBuf {
+    let mut path = std::env::current_exe().expect("could not get current exe path");
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.push("wg");
+    assert!(
+        path.exists(),
+        "wg binary not found at {:?}. Run `cargo build` first.",
+        path
+    );
+    path
+}
+
+/// Run a wg command and return output
+fn wg_cmd(wg_dir: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(wg_binary())
+        .arg("--dir")
+        .arg(wg_dir)
+        .args(args)
+        .env_remove("WG_STRICT")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run wg {:?}: {}", args, e))
+}
+
+/// A workgraph whose second task misspells `skills`.
+fn setup_typo_graph() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    let wg_dir = tmp.path().join(".wg");
+    fs::create_dir_all(&wg_dir).unwrap();
+    fs::write(
+        wg_dir.join("graph.jsonl"),
+        concat!(
+            r#"{"kind":"task","id":"a","title":"A","status":"open"}"#,
+            "\n",
+            r#"{"kind":"task","id":"b","title":"B","status":"open","skils":["rust"]}"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+    tmp
+}
+
+#[test]
+fn test_unknown_fields_are_dropped_by_default() {
+    let tmp = setup_typo_graph();
+    let output = wg_cmd(&tmp.path().join(".wg"), &["list"]);
+    assert!(
+        output.status.success(),
+        "lenient load should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_strict_flag_reports_unknown_field_with_location() {
+    let tmp = setup_typo_graph();
+    let output = wg_cmd(&tmp.path().join(".wg"), &["--strict", "list"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unknown field `skils` on line 2, column"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("did you mean `skills`?"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_strict_fields_config_enables_strict_mode() {
+    let tmp = setup_typo_graph();
+    let wg_dir = tmp.path().join(".wg");
+    fs::write(
+        wg_dir.join("config.toml"),
+        "[project]\nstrict_fields = true\n",
+    )
+    .unwrap();
+    let output = wg_cmd(&wg_dir, &["list"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown field `skils`"));
+}
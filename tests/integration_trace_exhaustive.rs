@@ -731,6 +731,8 @@ fn test_trace_with_rotated_operations_logs() {
         actor: None,
         user: None,
         detail: serde_json::json!({"title": "Rotated logs task"}),
+        prev_hash: None,
+        hash: None,
     };
     let old_json = format!("{}\n", serde_json::to_string(&old_entry).unwrap());
     let compressed = zstd::encode_all(old_json.as_bytes(), 3).unwrap();
@@ -748,6 +750,8 @@ fn test_trace_with_rotated_operations_logs() {
         actor: Some("agent-1".to_string()),
         user: None,
         detail: serde_json::Value::Null,
+        prev_hash: None,
+        hash: None,
     };
     let new_json = format!("{}\n", serde_json::to_string(&new_entry).unwrap());
     fs::write(log_dir.join("operations.jsonl"), &new_json).unwrap();
@@ -101,6 +101,7 @@ fn sample_run_summary() -> RunSummary {
                 score: Some(0.9),
                 duration_secs: Some(60),
                 retry_count: 0,
+                changes: None,
             },
             TaskOutcome {
                 template_id: "implement".to_string(),
@@ -109,6 +110,7 @@ fn sample_run_summary() -> RunSummary {
                 score: Some(0.85),
                 duration_secs: Some(300),
                 retry_count: 1,
+                changes: None,
             },
         ],
         interventions: vec![InterventionSummary {
@@ -410,6 +410,8 @@ fn test_eval_prompt_includes_verify_findings() {
         child_tasks: &[],
         constraint_fidelity_score: None,
         constraint_fidelity_unanchored: None,
+        test_results: None,
+        rubric: None,
     };
 
     let prompt = render_evaluator_prompt(&input);
@@ -477,6 +479,8 @@ fn test_eval_prompt_no_verify_section_when_absent() {
         child_tasks: &[],
         constraint_fidelity_score: None,
         constraint_fidelity_unanchored: None,
+        test_results: None,
+        rubric: None,
     };
 
     let prompt = render_evaluator_prompt(&input);
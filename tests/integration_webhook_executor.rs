@@ -0,0 +1,151 @@
+//! Integration tests for the webhook executor (`wg webhook-exec`): the task
+//! is POSTed to `[webhook_executor].url` and resolved from the report.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread::JoinHandle;
+use tempfile::TempDir;
+
+/// Find the wg binary for CLI testing
+fn wg_binary() -> PathBuf {
+    let mut path = std::env::current_exe().expect("could not get current exe path");
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.push("wg");
+    assert!(
+        path.exists(),
+        "wg binary not found at {:?}. Run `cargo build` first.",
+        path
+    );
+    path
+}
+
+/// Run a wg command and return output
+fn wg_cmd(wg_dir: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(wg_binary())
+        .arg("--dir")
+        .arg(wg_dir)
+        .args(args)
+        .env_remove("WG_AGENT_ID")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run wg {:?}: {}", args, e))
+}
+
+/// Serve one request, answering with `response_body`. Returns the address
+/// and a handle yielding the request body.
+fn one_shot_server(response_body: &'static str) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut len = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((k, v)) = line.split_once(':')
+                && k.eq_ignore_ascii_case("content-length")
+            {
+                len = v.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; len];
+        reader.read_exact(&mut body).unwrap();
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response_body.len(),
+            response_body
+        )
+        .unwrap();
+        String::from_utf8(body).unwrap()
+    });
+    (addr, handle)
+}
+
+fn setup(url: &str) -> (TempDir, PathBuf) {
+    let tmp = TempDir::new().unwrap();
+    let wg_dir = tmp.path().join(".wg");
+    fs::create_dir_all(&wg_dir).unwrap();
+    fs::write(
+        wg_dir.join("graph.jsonl"),
+        concat!(
+            r#"{"kind":"task","id":"publish","title":"Publish report","status":"in-progress","tags":["zapier"]}"#,
+            "\n",
+        ),
+    )
+    .unwrap();
+    fs::write(
+        wg_dir.join("config.toml"),
+        format!("[webhook_executor]\nurl = \"{}\"\n", url),
+    )
+    .unwrap();
+    fs::write(tmp.path().join("prompt.txt"), "Publish the weekly report").unwrap();
+    (tmp, wg_dir)
+}
+
+fn webhook_exec(tmp: &TempDir, wg_dir: &Path) -> std::process::Output {
+    let prompt = tmp.path().join("prompt.txt");
+    wg_cmd(
+        wg_dir,
+        &[
+            "webhook-exec",
+            "--prompt-file",
+            prompt.to_str().unwrap(),
+            "--task-id",
+            "publish",
+        ],
+    )
+}
+
+#[test]
+fn test_final_report_in_response_completes_task() {
+    let (addr, server) = one_shot_server(
+        r#"{"status":"done","message":"Posted to the blog","artifacts":["https://example.com/post/1"]}"#,
+    );
+    let (tmp, wg_dir) = setup(&format!("http://{}/tasks", addr));
+
+    let output = webhook_exec(&tmp, &wg_dir);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let payload: serde_json::Value = serde_json::from_str(&server.join().unwrap()).unwrap();
+    assert_eq!(payload["task_id"], "publish");
+    assert_eq!(payload["tags"][0], "zapier");
+    assert_eq!(payload["prompt"], "Publish the weekly report");
+
+    let show = wg_cmd(&wg_dir, &["show", "publish", "--json"]);
+    let task: serde_json::Value = serde_json::from_slice(&show.stdout).unwrap();
+    assert_eq!(task["status"], "done");
+    assert_eq!(task["artifacts"][0], "https://example.com/post/1");
+}
+
+#[test]
+fn test_failed_report_fails_task_with_reason() {
+    let (addr, server) = one_shot_server(r#"{"status":"failed","reason":"Quota exceeded"}"#);
+    let (tmp, wg_dir) = setup(&format!("http://{}/tasks", addr));
+
+    let output = webhook_exec(&tmp, &wg_dir);
+    assert!(output.status.success());
+    server.join().unwrap();
+
+    let show = wg_cmd(&wg_dir, &["show", "publish", "--json"]);
+    let task: serde_json::Value = serde_json::from_slice(&show.stdout).unwrap();
+    assert_eq!(task["status"], "failed");
+    assert_eq!(task["failure_reason"], "Quota exceeded");
+}
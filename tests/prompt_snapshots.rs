@@ -106,6 +106,7 @@ fn test_scope_context() -> ScopeContext {
         decomp_guidance: true,
         telegram_available: false,
         native_file_tools: false,
+        onboarding_brief: String::new(),
     }
 }
 
@@ -199,6 +200,8 @@ fn snapshot_evaluator_prompt_full() {
         child_tasks: &[],
         constraint_fidelity_score: None,
         constraint_fidelity_unanchored: None,
+        test_results: None,
+        rubric: None,
     };
 
     let output = render_evaluator_prompt(&input);
@@ -229,6 +232,8 @@ fn snapshot_evaluator_prompt_minimal() {
         child_tasks: &[],
         constraint_fidelity_score: None,
         constraint_fidelity_unanchored: None,
+        test_results: None,
+        rubric: None,
     };
 
     let output = render_evaluator_prompt(&input);
@@ -261,6 +266,8 @@ fn snapshot_evaluator_prompt_with_evaluator_identity() {
         child_tasks: &[],
         constraint_fidelity_score: None,
         constraint_fidelity_unanchored: None,
+        test_results: None,
+        rubric: None,
     };
 
     let output = render_evaluator_prompt(&input);
@@ -305,6 +312,8 @@ fn snapshot_evaluator_prompt_with_downstream_tasks() {
         child_tasks: &[],
         constraint_fidelity_score: None,
         constraint_fidelity_unanchored: None,
+        test_results: None,
+        rubric: None,
     };
 
     let output = render_evaluator_prompt(&input);
@@ -75,6 +75,8 @@ fn setup_agency(wg_dir: &std::path::Path) -> (String, String, String) {
         attractor_weight: 0.5,
         deployment_history: vec![],
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     agency::save_agent(&agent, &agency_dir.join("cache/agents")).unwrap();
 
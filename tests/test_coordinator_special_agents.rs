@@ -57,6 +57,8 @@ fn bootstrap_agency() -> (TempDir, std::path::PathBuf) {
         deployment_history: vec![],
         attractor_weight: 0.5,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     save_agent(&default_agent, &agents_dir).unwrap();
 
@@ -100,6 +102,8 @@ fn bootstrap_agency() -> (TempDir, std::path::PathBuf) {
                 deployment_history: vec![],
                 attractor_weight: 0.5,
                 staleness_flags: vec![],
+                capability_doc: None,
+                daily_budget: None,
             };
             save_agent(&agent, &agents_dir).unwrap();
         }
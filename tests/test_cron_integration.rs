@@ -256,19 +256,25 @@ fn create_test_task() -> Task {
         estimate: None,
         before: vec![],
         after: vec![],
+        edges: Default::default(),
         requires: vec![],
         tags: vec![],
         skills: vec![],
         inputs: vec![],
         deliverables: vec![],
+        expects: vec![],
+        gate: None,
         artifacts: vec![],
+        self_report: None,
         exec: None,
         timeout: None,
         not_before: None,
+        due: None,
         created_at: None,
         started_at: None,
         completed_at: None,
         last_interaction_at: None,
+        revision: 0,
         log: vec![],
         retry_count: 0,
         max_retries: None,
@@ -293,10 +299,20 @@ fn create_test_task() -> Task {
         context_scope: None,
         exec_mode: None,
         token_usage: None,
+        max_cost: None,
+        min_context: None,
+        claim_sha: None,
+        changes: None,
         session_id: None,
         wait_condition: None,
         checkpoint: None,
         triage_count: 0,
+        escalation_level: 0,
+        blocked_at: None,
+        deadline_alert: None,
+        external_blocker: None,
+        waiting_on: vec![],
+        links: vec![],
         resurrection_count: 0,
         last_resurrected_at: None,
         validation: None,
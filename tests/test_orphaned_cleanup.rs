@@ -150,6 +150,8 @@ fn create_agent_registry(service_dir: &Path, agents: Vec<(&str, u32, bool)>) ->
             model: Some("test-model".to_string()),
             completed_at: if is_alive { None } else { Some(now.clone()) },
             worktree_path: None,
+            preempted_by: None,
+            paused_task: false,
         };
 
         registry.agents.insert(agent_id.to_string(), agent_entry);
@@ -50,6 +50,8 @@ fn create_and_save_agent(
         deployment_history: vec![],
         attractor_weight: 1.0,
         staleness_flags: vec![],
+        capability_doc: None,
+        daily_budget: None,
     };
     let agents_dir = agency_dir.join("cache/agents");
     std::fs::create_dir_all(&agents_dir).unwrap();
@@ -346,6 +348,8 @@ fn evaluator_prompt_no_hardcoded_text_when_agent_configured() {
         child_tasks: &[],
         constraint_fidelity_score: None,
         constraint_fidelity_unanchored: None,
+        test_results: None,
+        rubric: None,
     };
     let full_prompt = render_evaluator_prompt(&input);
 
@@ -389,6 +393,8 @@ fn evaluator_prompt_falls_back_to_hardcoded_when_no_agent() {
         child_tasks: &[],
         constraint_fidelity_score: None,
         constraint_fidelity_unanchored: None,
+        test_results: None,
+        rubric: None,
     };
     let prompt = render_evaluator_prompt(&input);
 